@@ -0,0 +1,16 @@
+-- migrate:up
+
+ALTER TABLE users ADD COLUMN role_id VARCHAR(50);
+
+CREATE TABLE roles (
+    key VARCHAR(255) PRIMARY KEY,
+    data JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_roles_created_at ON roles(created_at);
+
+-- migrate:down
+
+-- DON'T EVER INCLUDE DOWN MIGRATIONS!
@@ -0,0 +1,7 @@
+-- migrate:up
+
+ALTER TABLE users ADD COLUMN mfa JSONB;
+
+-- migrate:down
+
+-- DON'T EVER INCLUDE DOWN MIGRATIONS!
@@ -0,0 +1,16 @@
+-- migrate:up
+
+CREATE TABLE audit_logs (
+    key VARCHAR(255) PRIMARY KEY,
+    data JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_audit_logs_created_at ON audit_logs(created_at);
+CREATE INDEX idx_audit_logs_entity ON audit_logs((data->>'entity_type'), (data->>'entity_id'));
+CREATE INDEX idx_audit_logs_actor ON audit_logs((data->'actor'->>'id'));
+
+-- migrate:down
+
+-- DON'T EVER INCLUDE DOWN MIGRATIONS!
@@ -0,0 +1,14 @@
+-- migrate:up
+
+CREATE TABLE organizations (
+    key VARCHAR(255) PRIMARY KEY,
+    data JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_teams_organization_id ON teams((data->>'organization_id'));
+
+-- migrate:down
+
+-- DON'T EVER INCLUDE DOWN MIGRATIONS!
@@ -0,0 +1,12 @@
+-- migrate:up
+
+CREATE TABLE service_accounts (
+    key VARCHAR(255) PRIMARY KEY,
+    data JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- migrate:down
+
+-- DON'T EVER INCLUDE DOWN MIGRATIONS!
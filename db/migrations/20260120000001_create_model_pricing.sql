@@ -0,0 +1,14 @@
+-- migrate:up
+
+CREATE TABLE model_pricing (
+    key VARCHAR(255) PRIMARY KEY,
+    data JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_model_pricing_model_id ON model_pricing((data->>'model_id'));
+
+-- migrate:down
+
+-- DON'T EVER INCLUDE DOWN MIGRATIONS!
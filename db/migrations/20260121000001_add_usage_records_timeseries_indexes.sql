@@ -0,0 +1,9 @@
+-- migrate:up
+
+-- Numeric timestamp index for the range scans of time-bucketed usage aggregation
+CREATE INDEX idx_usage_records_timestamp_bigint ON usage_records(((data->>'timestamp')::bigint));
+CREATE INDEX idx_usage_records_team_id ON usage_records((data->>'team_id'));
+
+-- migrate:down
+
+-- DON'T EVER INCLUDE DOWN MIGRATIONS!
@@ -0,0 +1,11 @@
+-- migrate:up
+
+-- Request/response payload capture settings
+INSERT INTO app_configurations (key, value, metadata) VALUES
+('persistence.payload_capture_percent', '{"type": "float", "value": 0.0}', '{"category": "persistence", "description": "Percentage (0-100) of requests of opted-in teams whose redacted request/response payloads are captured", "value_type": "float"}'),
+('persistence.payload_capture_teams', '{"type": "string_list", "value": []}', '{"category": "persistence", "description": "Team IDs opted in to payload capture", "value_type": "string_list"}')
+ON CONFLICT (key) DO NOTHING;
+
+-- migrate:down
+
+-- DON'T EVER INCLUDE DOWN MIGRATIONS!
@@ -0,0 +1,14 @@
+-- migrate:up
+
+CREATE TABLE slos (
+    key VARCHAR(255) PRIMARY KEY,
+    data JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_slos_enabled ON slos((data->>'enabled'));
+
+-- migrate:down
+
+-- DON'T EVER INCLUDE DOWN MIGRATIONS!
@@ -105,6 +105,7 @@ const API = (function() {
         updatePrompt: (id, data) => request('PUT', `/prompts/${encodeURIComponent(id)}`, data),
         deletePrompt: (id) => request('DELETE', `/prompts/${encodeURIComponent(id)}`),
         renderPrompt: (id, variables) => request('POST', `/prompts/${encodeURIComponent(id)}/render`, { variables }),
+        getPromptVariables: (id) => request('GET', `/prompts/${encodeURIComponent(id)}/variables`),
         listPromptVersions: (id) => request('GET', `/prompts/${encodeURIComponent(id)}/versions`),
         revertPromptVersion: (id, version) => request('POST', `/prompts/${encodeURIComponent(id)}/revert/${version}`),
 
@@ -268,6 +269,9 @@ const API = (function() {
         },
         cleanupExecutionLogs: (data) => request('POST', '/execution-logs/cleanup', data),
 
+        // Live stats
+        getLiveStats: () => request('GET', '/stats'),
+
         // Webhooks
         listWebhooks: () => request('GET', '/webhooks'),
         getWebhook: (id) => request('GET', `/webhooks/${encodeURIComponent(id)}`),
@@ -282,6 +286,7 @@ const API = (function() {
             const qs = query.toString();
             return request('GET', `/webhooks/${encodeURIComponent(id)}/deliveries${qs ? '?' + qs : ''}`);
         },
+        redeliverWebhookDelivery: (id, deliveryId) => request('POST', `/webhooks/${encodeURIComponent(id)}/deliveries/${encodeURIComponent(deliveryId)}/redeliver`),
         listWebhookEventTypes: () => request('GET', '/webhooks/event-types')
     };
 })();
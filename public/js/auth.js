@@ -42,6 +42,8 @@ const Auth = (function() {
         $('#app').addClass('hidden');
         $('#username-input').val('').focus();
         $('#password-input').val('');
+        $('#mfa-input').val('');
+        $('#mfa-field').addClass('hidden');
         $('#login-error').addClass('hidden');
     }
 
@@ -50,24 +52,34 @@ const Auth = (function() {
         $('#app').removeClass('hidden');
     }
 
-    async function login(username, password) {
+    async function login(username, password, mfaCode) {
+        const body = { username, password };
+
+        if (mfaCode) {
+            body.mfa_code = mfaCode;
+        }
+
         const response = await fetch('/auth/login', {
             method: 'POST',
             headers: {
                 'Content-Type': 'application/json'
             },
-            body: JSON.stringify({ username, password })
+            body: JSON.stringify(body)
         });
 
         if (!response.ok) {
             let errorMessage = 'Login failed';
+            let errorCode = null;
             try {
                 const error = await response.json();
                 errorMessage = error.error?.message || error.message || errorMessage;
+                errorCode = error.error?.code || null;
             } catch (e) {
                 // Ignore JSON parse errors
             }
-            throw new Error(errorMessage);
+            const err = new Error(errorMessage);
+            err.code = errorCode;
+            throw err;
         }
 
         const data = await response.json();
@@ -107,6 +119,7 @@ const Auth = (function() {
             e.preventDefault();
             const username = $('#username-input').val().trim();
             const password = $('#password-input').val();
+            const mfaCode = $('#mfa-input').val().trim();
 
             if (!username) {
                 showError('Please enter a username');
@@ -123,11 +136,17 @@ const Auth = (function() {
             $btn.prop('disabled', true).text('Logging in...');
 
             try {
-                await login(username, password);
+                await login(username, password, mfaCode);
                 hideLoginModal();
                 // Trigger initial navigation
                 App.navigate(window.location.hash.slice(1) || 'dashboard');
             } catch (e) {
+                if (e.code === 'mfa_required') {
+                    $('#mfa-field').removeClass('hidden');
+                    $('#mfa-input').focus();
+                    showError('Enter the code from your authenticator app');
+                    return;
+                }
                 showError(e.message || 'Invalid username or password');
             } finally {
                 $btn.prop('disabled', false).text(originalText);
@@ -2,7 +2,10 @@
  * Dashboard view with statistics and graphs
  */
 const Dashboard = (function() {
+    const LIVE_STATS_INTERVAL_MS = 5000;
+
     let charts = {};
+    let liveStatsTimer = null;
 
     async function render() {
         $('#content').html(Utils.renderLoading());
@@ -34,6 +37,7 @@ const Dashboard = (function() {
 
             bindEvents();
             renderCharts(timeseriesData, modelUsageData, stats);
+            startLiveStats();
         } catch (error) {
             $('#content').html(Utils.renderError(error.message));
         }
@@ -108,8 +112,64 @@ const Dashboard = (function() {
             .slice(0, 10);
     }
 
+    function startLiveStats() {
+        if (liveStatsTimer) {
+            clearInterval(liveStatsTimer);
+        }
+
+        refreshLiveStats();
+        liveStatsTimer = setInterval(refreshLiveStats, LIVE_STATS_INTERVAL_MS);
+    }
+
+    async function refreshLiveStats() {
+        // Stop polling once the dashboard is left
+        if (!$('#live-stats').length) {
+            clearInterval(liveStatsTimer);
+            liveStatsTimer = null;
+            return;
+        }
+
+        try {
+            const stats = await API.getLiveStats();
+            $('#live-stats').html(renderLiveStats(stats));
+        } catch (error) {
+            $('#live-stats').html(`<p class="text-sm text-gray-500">Live stats unavailable: ${Utils.escapeHtml(error.message)}</p>`);
+        }
+    }
+
+    function renderLiveStats(stats) {
+        const hitRate = stats.cache.hit_rate === null ? '-' : (stats.cache.hit_rate * 100).toFixed(1) + '%';
+        const statusColors = {
+            healthy: 'bg-green-100 text-green-700',
+            degraded: 'bg-yellow-100 text-yellow-700',
+            down: 'bg-red-100 text-red-700'
+        };
+        const providers = stats.providers.length
+            ? stats.providers.map(p => `
+                <span class="inline-block px-2 py-1 mr-2 mb-2 rounded text-xs font-medium ${statusColors[p.status] || ''}">
+                    ${Utils.escapeHtml(p.provider)}: ${p.status}
+                </span>
+            `).join('')
+            : '<span class="text-sm text-gray-500">No provider calls in the last minute</span>';
+
+        return `
+            <div class="grid grid-cols-2 md:grid-cols-4 gap-4 mb-4">
+                ${renderStatCard('Active Requests', stats.requests.active, 'blue')}
+                ${renderStatCard('Requests / s', stats.requests.rps.toFixed(2), 'green')}
+                ${renderStatCard('Tokens / min', Math.round(stats.tokens.per_minute).toLocaleString(), 'purple')}
+                ${renderStatCard('Cache Hit Rate', hitRate, 'orange')}
+            </div>
+            <div>${providers}</div>
+        `;
+    }
+
     function renderDashboard(data) {
         return `
+            <div class="card mb-6">
+                <h3 class="text-lg font-semibold mb-4">Live (Last Minute)</h3>
+                <div id="live-stats">${Utils.renderLoading()}</div>
+            </div>
+
             <div class="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-4 gap-4 mb-6">
                 ${renderStatCard('Total Executions', data.stats.total_executions, 'blue')}
                 ${renderStatCard('Success Rate', data.stats.success_rate.toFixed(1) + '%', 'green')}
@@ -133,6 +133,15 @@ const Experiments = (function() {
                             placeholder="Optional description">${Utils.escapeHtml(experiment?.description || '')}</textarea>
                     </div>
 
+                    <div class="mb-4">
+                        <label class="block text-sm font-medium text-gray-700 mb-1">Assign By</label>
+                        <select name="assignment_key" class="form-input">
+                            <option value="api_key" ${experiment?.assignment_key !== 'user' ? 'selected' : ''}>API key</option>
+                            <option value="user" ${experiment?.assignment_key === 'user' ? 'selected' : ''}>End user (request user field)</option>
+                        </select>
+                        <p class="text-xs text-gray-500 mt-1">Requests without a user fall back to their API key</p>
+                    </div>
+
                     <div class="border-t pt-4 mt-4">
                         <div class="flex justify-between items-center mb-3">
                             <h3 class="font-medium">Variants</h3>
@@ -205,6 +214,12 @@ const Experiments = (function() {
                 </div>
 
                 <div class="config-overrides mt-3 ${configType !== 'config_override' ? 'hidden' : ''}">
+                    <div class="mb-3">
+                        <label class="block text-xs text-gray-500 mb-1">Prompt ID</label>
+                        <input type="text"
+                            class="form-input text-sm variant-prompt-id"
+                            value="${Utils.escapeHtml(variant?.config?.prompt_id || '')}" placeholder="Keep the request's prompt">
+                    </div>
                     <div class="grid grid-cols-3 gap-3">
                         <div>
                             <label class="block text-xs text-gray-500 mb-1">Temperature</label>
@@ -449,7 +464,8 @@ const Experiments = (function() {
                 name: $('input[name="name"]').val(),
                 description: $('textarea[name="description"]').val() || null,
                 variants: collectVariants(),
-                traffic_allocation: collectTrafficAllocation()
+                traffic_allocation: collectTrafficAllocation(),
+                assignment_key: $('select[name="assignment_key"]').val()
             };
 
             // Validate
@@ -543,10 +559,12 @@ const Experiments = (function() {
             };
 
             if (configType === 'config_override') {
+                const promptId = $item.find('.variant-prompt-id').val().trim();
                 const temp = $item.find('.variant-temperature').val();
                 const maxTokens = $item.find('.variant-max-tokens').val();
                 const topP = $item.find('.variant-top-p').val();
 
+                if (promptId) variant.config.prompt_id = promptId;
                 if (temp) variant.config.temperature = parseFloat(temp);
                 if (maxTokens) variant.config.max_tokens = parseInt(maxTokens);
                 if (topP) variant.config.top_p = parseFloat(topP);
@@ -147,19 +147,30 @@ const Prompts = (function() {
         `;
     }
 
-    function renderPreview(prompt) {
-        // Extract variables from content
-        const varRegex = /\$\{var:([a-zA-Z0-9_-]+)(?::([^}]*))?\}/g;
-        const variables = [];
-        let match;
-
-        while ((match = varRegex.exec(prompt.content)) !== null) {
-            variables.push({
-                name: match[1],
-                defaultValue: match[2] || ''
-            });
+    function renderVariableInput(v) {
+        const name = Utils.escapeHtml(v.name);
+        const value = Utils.escapeHtml(v.default || '');
+
+        switch (v.type) {
+            case 'enum':
+                return `
+                    <select name="${name}" class="form-input">
+                        ${v.required ? '' : '<option value=""></option>'}
+                        ${(v.values || []).map(option => `
+                            <option value="${Utils.escapeHtml(option)}" ${option === v.default ? 'selected' : ''}>${Utils.escapeHtml(option)}</option>
+                        `).join('')}
+                    </select>
+                `;
+            case 'number':
+                return `<input type="number" step="any" name="${name}" value="${value}" class="form-input">`;
+            case 'json':
+                return `<textarea name="${name}" rows="4" class="form-input font-mono text-sm" placeholder="{}">${value}</textarea>`;
+            default:
+                return `<input type="text" name="${name}" value="${value}" class="form-input" placeholder="Enter value...">`;
         }
+    }
 
+    function renderPreview(prompt, variables) {
         return `
             <div class="max-w-3xl">
                 <div class="flex items-center mb-6">
@@ -173,10 +184,12 @@ const Prompts = (function() {
                         <form id="preview-form" class="space-y-4">
                             ${variables.map(v => `
                                 <div>
-                                    <label class="block text-sm font-medium text-gray-700 mb-1">${Utils.escapeHtml(v.name)}</label>
-                                    <input type="text" name="${Utils.escapeHtml(v.name)}"
-                                        value="${Utils.escapeHtml(v.defaultValue)}"
-                                        class="form-input" placeholder="Enter value...">
+                                    <label class="block text-sm font-medium text-gray-700 mb-1">
+                                        ${Utils.escapeHtml(v.name)}${v.required ? ' <span class="text-red-500">*</span>' : ''}
+                                        <span class="text-xs text-gray-400 ml-1">${Utils.escapeHtml(v.type)}</span>
+                                    </label>
+                                    ${renderVariableInput(v)}
+                                    ${v.description ? `<p class="text-xs text-gray-500 mt-1">${Utils.escapeHtml(v.description)}</p>` : ''}
                                 </div>
                             `).join('')}
                             <button type="submit" class="btn btn-primary">Render Preview</button>
@@ -350,8 +363,11 @@ const Prompts = (function() {
         $('#content').html(Utils.renderLoading());
 
         try {
-            const prompt = await API.getPrompt(id);
-            $('#content').html(renderPreview(prompt));
+            const [prompt, schema] = await Promise.all([
+                API.getPrompt(id),
+                API.getPromptVariables(id)
+            ]);
+            $('#content').html(renderPreview(prompt, schema.variables));
             bindPreviewEvents(id);
         } catch (error) {
             Utils.showToast('Failed to load prompt', 'error');
@@ -486,7 +502,7 @@ const Prompts = (function() {
 
             // Get variables as strings (don't use getFormData which converts types)
             const variables = {};
-            $(this).find('input[type="text"]').each(function() {
+            $(this).find('input, select, textarea').each(function() {
                 const name = $(this).attr('name');
                 const value = $(this).val();
 
@@ -73,6 +73,8 @@ const Webhooks = (function() {
                 <td>
                     <div class="font-mono text-sm" title="${Utils.escapeHtml(webhook.url)}">${Utils.escapeHtml(truncatedUrl)}</div>
                     ${webhook.has_secret ? '<span class="text-xs text-green-600">Signed</span>' : ''}
+                    ${webhook.filter ? '<span class="text-xs text-blue-600 ml-1">Filtered</span>' : ''}
+                    ${webhook.payload_template ? '<span class="text-xs text-purple-600 ml-1">Templated</span>' : ''}
                 </td>
                 <td class="text-sm">
                     ${webhook.events.map(e => `<span class="inline-block bg-gray-100 rounded px-1 mr-1 mb-1 text-xs">${e}</span>`).join('')}
@@ -98,6 +100,12 @@ const Webhooks = (function() {
         const isEdit = !!webhook;
         const title = isEdit ? 'Edit Webhook' : 'Create Webhook';
 
+        const filter = webhook?.filter || {};
+        const listValue = values => Utils.escapeHtml((values || []).join(', '));
+        const template = webhook?.payload_template
+            ? Utils.escapeHtml(JSON.stringify(webhook.payload_template, null, 2))
+            : '';
+
         const eventCheckboxes = eventTypes.map(et => `
             <label class="flex items-start mb-2">
                 <input type="checkbox" name="events" value="${Utils.escapeHtml(et.name)}"
@@ -149,11 +157,11 @@ const Webhooks = (function() {
 
                     <div class="mb-4">
                         <label class="block text-sm font-medium text-gray-700 mb-1">
-                            Secret ${isEdit && webhook.has_secret ? '(leave blank to keep existing)' : '(optional)'}
+                            Secret ${isEdit && webhook.has_secret ? '(leave blank to keep existing)' : '(leave blank to generate one)'}
                         </label>
                         <input type="password" name="secret" class="form-input font-mono"
-                            placeholder="${isEdit && webhook.has_secret ? '********' : 'Optional signing secret'}">
-                        <p class="text-xs text-gray-500 mt-1">Used to sign webhook payloads with HMAC-SHA256</p>
+                            placeholder="${isEdit && webhook.has_secret ? '********' : 'Generated when blank'}">
+                        <p class="text-xs text-gray-500 mt-1">Used to sign webhook payloads with HMAC-SHA256 (X-PMP-Signature header)</p>
                     </div>
 
                     <div class="border-t pt-4 mt-4">
@@ -163,6 +171,44 @@ const Webhooks = (function() {
                         </div>
                     </div>
 
+                    <div class="border-t pt-4 mt-4">
+                        <h3 class="font-medium mb-1">Filters</h3>
+                        <p class="text-xs text-gray-500 mb-3">Comma-separated; leave blank to receive every subscribed event</p>
+                        <div class="grid grid-cols-2 gap-4">
+                            <div>
+                                <label class="block text-sm font-medium text-gray-700 mb-1">Team IDs</label>
+                                <input type="text" name="filter_team_ids" class="form-input font-mono"
+                                    placeholder="team-1, team-2" value="${listValue(filter.team_ids)}">
+                            </div>
+                            <div>
+                                <label class="block text-sm font-medium text-gray-700 mb-1">Model IDs</label>
+                                <input type="text" name="filter_model_ids" class="form-input font-mono"
+                                    placeholder="gpt-4o" value="${listValue(filter.model_ids)}">
+                            </div>
+                            <div>
+                                <label class="block text-sm font-medium text-gray-700 mb-1">Subtypes</label>
+                                <input type="text" name="filter_subtypes" class="form-input font-mono"
+                                    placeholder="cost, requests" value="${listValue(filter.subtypes)}">
+                            </div>
+                            <div>
+                                <label class="block text-sm font-medium text-gray-700 mb-1">Min Cost (USD)</label>
+                                <input type="number" name="filter_min_cost" class="form-input"
+                                    min="0" step="0.01"
+                                    value="${filter.min_cost_micros != null ? filter.min_cost_micros / 1000000 : ''}">
+                            </div>
+                        </div>
+                    </div>
+
+                    <div class="border-t pt-4 mt-4">
+                        <h3 class="font-medium mb-1">Payload Template</h3>
+                        <p class="text-xs text-gray-500 mb-3">
+                            Optional JSON sent instead of the event, with <code>{{data.field}}</code> placeholders
+                            (e.g. <code>{"text": "Budget {{data.budget_name}} exceeded"}</code> for Slack)
+                        </p>
+                        <textarea name="payload_template" class="form-input font-mono text-sm" rows="5"
+                            placeholder='{"text": "{{event_type}}: {{data.budget_name}}"}'>${template}</textarea>
+                    </div>
+
                     <div class="border-t pt-4 mt-4">
                         <h3 class="font-medium mb-3">Retry Configuration</h3>
                         <div class="grid grid-cols-3 gap-4">
@@ -203,12 +249,50 @@ const Webhooks = (function() {
         `;
     }
 
+    function renderSecret(webhook) {
+        return `
+            <div class="max-w-2xl">
+                <div class="card">
+                    <div class="text-center mb-6">
+                        <div class="text-green-500 text-5xl mb-4">&#10003;</div>
+                        <h2 class="text-xl font-semibold">Webhook Created</h2>
+                    </div>
+
+                    <div class="bg-yellow-50 border border-yellow-200 rounded-lg p-4 mb-6">
+                        <p class="text-yellow-800 text-sm font-medium mb-2">
+                            Important: Copy the signing secret now. You won't be able to see it again!
+                        </p>
+                        <div class="flex items-center gap-2">
+                            <input type="text" id="webhook-secret" value="${Utils.escapeHtml(webhook.secret)}"
+                                class="form-input font-mono text-sm flex-1" readonly>
+                            <button id="copy-btn" class="btn btn-primary">Copy</button>
+                        </div>
+                    </div>
+
+                    <div class="text-center">
+                        <button id="done-btn" class="btn btn-secondary">Done</button>
+                    </div>
+                </div>
+            </div>
+        `;
+    }
+
+    function bindSecretEvents() {
+        $('#copy-btn').on('click', function() {
+            $('#webhook-secret').select();
+            document.execCommand('copy');
+            Utils.showToast('Secret copied to clipboard', 'success');
+        });
+
+        $('#done-btn').on('click', () => render());
+    }
+
     function renderDeliveries(webhookId, deliveries) {
         const statusColors = {
             pending: 'badge-gray',
             success: 'badge-success',
-            failed: 'badge-error',
-            retrying: 'badge-warning'
+            failed: 'badge-warning',
+            dead_letter: 'badge-error'
         };
 
         return `
@@ -233,6 +317,7 @@ const Webhooks = (function() {
                                     <th>Attempts</th>
                                     <th>Response</th>
                                     <th>Created</th>
+                                    <th></th>
                                 </tr>
                             </thead>
                             <tbody>
@@ -247,6 +332,7 @@ const Webhooks = (function() {
                                             ${d.error_message ? `<span class="text-red-600 text-xs">${Utils.escapeHtml(d.error_message)}</span>` : ''}
                                         </td>
                                         <td class="text-sm text-gray-500">${Utils.formatDate(d.created_at)}</td>
+                                        <td><button class="redeliver-btn btn-sm btn-secondary-sm" data-id="${Utils.escapeHtml(d.id)}">Redeliver</button></td>
                                     </tr>
                                 `).join('')}
                             </tbody>
@@ -317,6 +403,15 @@ const Webhooks = (function() {
             const data = await API.getWebhookDeliveries(webhookId);
             $('#content').html(renderDeliveries(webhookId, data.deliveries || []));
             $('#back-btn').on('click', () => render());
+            $('.redeliver-btn').on('click', async function() {
+                try {
+                    await API.redeliverWebhookDelivery(webhookId, $(this).data('id'));
+                    Utils.showToast('Delivery replayed', 'success');
+                    showDeliveries(webhookId);
+                } catch (error) {
+                    Utils.showToast(error.message, 'error');
+                }
+            });
         } catch (error) {
             Utils.showToast(error.message, 'error');
             render();
@@ -341,11 +436,38 @@ const Webhooks = (function() {
                 return;
             }
 
+            const list = name => ($(`input[name="${name}"]`).val() || '')
+                .split(',').map(v => v.trim()).filter(v => v);
+            const filter = {
+                team_ids: list('filter_team_ids'),
+                model_ids: list('filter_model_ids'),
+                subtypes: list('filter_subtypes')
+            };
+            const minCost = $('input[name="filter_min_cost"]').val();
+
+            if (minCost !== '') {
+                filter.min_cost_micros = Math.round(parseFloat(minCost) * 1000000);
+            }
+
+            let payloadTemplate = null;
+            const templateText = $('textarea[name="payload_template"]').val().trim();
+
+            if (templateText) {
+                try {
+                    payloadTemplate = JSON.parse(templateText);
+                } catch (error) {
+                    Utils.showToast('Payload template must be valid JSON', 'error');
+                    return;
+                }
+            }
+
             const data = {
                 name: $('input[name="name"]').val(),
                 description: $('input[name="description"]').val() || null,
                 url: $('input[name="url"]').val(),
                 events: events,
+                filter: filter,
+                payload_template: payloadTemplate,
                 max_retries: parseInt($('input[name="max_retries"]').val()) || 3,
                 retry_delay_secs: parseInt($('input[name="retry_delay_secs"]').val()) || 60,
                 timeout_secs: parseInt($('input[name="timeout_secs"]').val()) || 30
@@ -372,8 +494,10 @@ const Webhooks = (function() {
                     await API.updateWebhook(webhook.id, data);
                     Utils.showToast('Webhook updated', 'success');
                 } else {
-                    await API.createWebhook(data);
-                    Utils.showToast('Webhook created', 'success');
+                    const created = await API.createWebhook(data);
+                    $('#content').html(renderSecret(created));
+                    bindSecretEvents();
+                    return;
                 }
                 render();
             } catch (error) {
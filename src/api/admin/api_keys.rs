@@ -1,16 +1,27 @@
 //! API key management admin endpoints
 
 use axum::extract::{Path, State};
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
 use tracing::debug;
+use utoipa::ToSchema;
 
 use crate::api::middleware::RequireAdmin;
 use crate::api::state::AppState;
 use crate::api::types::{ApiError, Json};
-use crate::domain::api_key::{ApiKey, ApiKeyPermissions, ApiKeyStatus, ResourcePermission};
+use crate::domain::api_key::{
+    ApiKey, ApiKeyPermissions, ApiKeyStatus, ParameterPolicy, RateLimitConfig,
+    ResourcePermission,
+};
+use crate::domain::guardrail::InjectionAction;
+use crate::domain::network::IpNetwork;
+use crate::infrastructure::api_key::{check_expiration, LimitType, QuotaWindow};
+
+use super::bulk::{ensure_bulk_size, BulkResponse};
 
 /// Request to create a new API key
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Serialize))]
 pub struct CreateApiKeyRequest {
     pub name: String,
     pub team_id: String,
@@ -18,10 +29,73 @@ pub struct CreateApiKeyRequest {
     pub description: Option<String>,
     #[serde(default)]
     pub permissions: PermissionsRequest,
+    /// Optional expiration date; the key transitions to `expired` afterwards
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Client networks allowed to use the key (CIDR or bare IP); empty allows any
+    #[serde(default)]
+    pub allowed_cidrs: Option<Vec<String>>,
+    /// Rate limits and usage quotas of the key
+    #[serde(default)]
+    pub rate_limits: Option<RateLimitsRequest>,
+    /// Action on prompt-injection detections; absent uses the gateway default
+    #[serde(default)]
+    pub injection_action: Option<InjectionAction>,
+    /// Zero-retention mode: never store request and completion bodies
+    #[serde(default)]
+    pub no_log: bool,
+}
+
+/// Rate limits and quotas in request format. Unset request limits use the
+/// defaults; unset token and cost quotas are unlimited.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Serialize))]
+pub struct RateLimitsRequest {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    pub requests_per_minute: Option<u32>,
+    pub requests_per_hour: Option<u32>,
+    pub requests_per_day: Option<u32>,
+    pub tokens_per_minute: Option<u32>,
+    pub tokens_per_day: Option<u64>,
+    pub cost_per_month_usd: Option<f64>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl TryFrom<RateLimitsRequest> for RateLimitConfig {
+    type Error = ApiError;
+
+    fn try_from(req: RateLimitsRequest) -> Result<Self, Self::Error> {
+        let defaults = RateLimitConfig::default();
+        let mut config = RateLimitConfig {
+            enabled: req.enabled,
+            requests_per_minute: req.requests_per_minute.unwrap_or(defaults.requests_per_minute),
+            requests_per_hour: req.requests_per_hour.unwrap_or(defaults.requests_per_hour),
+            requests_per_day: req.requests_per_day.unwrap_or(defaults.requests_per_day),
+            tokens_per_minute: req.tokens_per_minute,
+            tokens_per_day: req.tokens_per_day,
+            cost_per_month_micros: None,
+        };
+
+        if let Some(usd) = req.cost_per_month_usd {
+            if !usd.is_finite() || usd < 0.0 {
+                return Err(ApiError::bad_request(
+                    "cost_per_month_usd must be a non-negative number",
+                ));
+            }
+            config = config.with_cost_per_month(usd);
+        }
+
+        Ok(config)
+    }
 }
 
 /// Permissions in request format
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Serialize))]
 pub struct PermissionsRequest {
     #[serde(default)]
     pub admin: bool,
@@ -33,10 +107,14 @@ pub struct PermissionsRequest {
     pub prompts: ResourcePermissionRequest,
     #[serde(default)]
     pub chains: ResourcePermissionRequest,
+    /// Limits on chat completion parameters
+    #[serde(default)]
+    pub parameters: ParameterPolicy,
 }
 
 /// Resource permission in request format
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Serialize))]
 #[serde(rename_all = "lowercase")]
 pub enum ResourcePermissionRequest {
     #[default]
@@ -65,18 +143,72 @@ impl From<PermissionsRequest> for ApiKeyPermissions {
             knowledge_bases: req.knowledge_bases.into(),
             prompts: req.prompts.into(),
             chains: req.chains.into(),
+            parameters: req.parameters,
         }
     }
 }
 
 /// Request to update an API key
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Serialize))]
 pub struct UpdateApiKeyRequest {
     pub permissions: Option<PermissionsRequest>,
+    /// Absent leaves the expiration unchanged, null clears it
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub expires_at: Option<Option<DateTime<Utc>>>,
+    /// Replaces the allowed client networks; an empty list removes the restriction
+    pub allowed_cidrs: Option<Vec<String>>,
+    /// Replaces the rate limits and usage quotas
+    pub rate_limits: Option<RateLimitsRequest>,
+    /// Absent leaves the injection action unchanged, null resets it to the
+    /// gateway default
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub injection_action: Option<Option<InjectionAction>>,
+    /// Enables or disables zero-retention mode
+    pub no_log: Option<bool>,
+}
+
+/// Parse a list of CIDR strings from an admin request
+pub(super) fn parse_allowed_cidrs(cidrs: &[String]) -> Result<Vec<IpNetwork>, ApiError> {
+    cidrs
+        .iter()
+        .map(|c| {
+            c.parse::<IpNetwork>()
+                .map_err(|e| ApiError::bad_request(format!("Invalid CIDR '{}': {}", c, e)))
+        })
+        .collect()
+}
+
+/// Deserialize a field that distinguishes between absent and explicit null
+pub(super) fn deserialize_present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Request to rotate an API key
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct RotateApiKeyRequest {
+    /// How long the previous key stays valid after rotation
+    #[serde(default = "default_grace_period_secs")]
+    pub grace_period_secs: u64,
+}
+
+fn default_grace_period_secs() -> u64 {
+    86400
+}
+
+/// Request to revoke several API keys in one call
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct BulkRevokeApiKeysRequest {
+    pub key_ids: Vec<String>,
 }
 
 /// API key response for admin API
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 pub struct ApiKeyResponse {
     pub id: String,
     pub name: String,
@@ -86,31 +218,124 @@ pub struct ApiKeyResponse {
     pub status: String,
     pub permissions: PermissionsResponse,
     pub last_used_at: Option<String>,
+    pub last_used_ip: Option<String>,
     pub expires_at: Option<String>,
+    pub replaced_by: Option<String>,
+    pub allowed_cidrs: Vec<String>,
+    pub rate_limits: RateLimitsResponse,
+    /// Action on prompt-injection detections, none when using the gateway default
+    pub injection_action: Option<InjectionAction>,
+    /// Zero-retention mode of the key itself; the team can also enable it
+    pub no_log: bool,
     pub created_at: String,
     pub updated_at: String,
 }
 
+/// Rate limits and quotas in response format
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
+pub struct RateLimitsResponse {
+    pub enabled: bool,
+    pub requests_per_minute: u32,
+    pub requests_per_hour: u32,
+    pub requests_per_day: u32,
+    pub tokens_per_minute: Option<u32>,
+    pub tokens_per_day: Option<u64>,
+    pub cost_per_month_usd: Option<f64>,
+}
+
+impl From<&RateLimitConfig> for RateLimitsResponse {
+    fn from(config: &RateLimitConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            requests_per_minute: config.requests_per_minute,
+            requests_per_hour: config.requests_per_hour,
+            requests_per_day: config.requests_per_day,
+            tokens_per_minute: config.tokens_per_minute,
+            tokens_per_day: config.tokens_per_day,
+            cost_per_month_usd: config
+                .cost_per_month_micros
+                .map(|micros| micros as f64 / 1_000_000.0),
+        }
+    }
+}
+
+/// Consumption of one rate limit or quota window
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct QuotaWindowResponse {
+    /// `per_minute`, `per_hour`, `per_day`, `tokens_per_minute`,
+    /// `tokens_per_day` or `cost_per_month`
+    pub quota: String,
+    /// `requests`, `tokens` or `micro_usd`
+    pub unit: &'static str,
+    pub window_secs: u64,
+    pub limit: u64,
+    pub used: u64,
+    pub remaining: u64,
+    pub reset_in_seconds: u64,
+}
+
+impl From<&QuotaWindow> for QuotaWindowResponse {
+    fn from(window: &QuotaWindow) -> Self {
+        let unit = match window.limit_type {
+            LimitType::PerMinute | LimitType::PerHour | LimitType::PerDay => "requests",
+            LimitType::TokensPerMinute | LimitType::TokensPerDay => "tokens",
+            LimitType::CostPerMonth => "micro_usd",
+        };
+
+        Self {
+            quota: window.limit_type.to_string(),
+            unit,
+            window_secs: window.limit_type.window_secs(),
+            limit: window.limit,
+            used: window.used,
+            remaining: window.remaining(),
+            reset_in_seconds: window.reset_in_seconds,
+        }
+    }
+}
+
+/// Current consumption of the rate limits and quotas of an API key
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiKeyUsageResponse {
+    pub api_key_id: String,
+    pub enabled: bool,
+    pub quotas: Vec<QuotaWindowResponse>,
+}
+
 /// API key response with secret (only on creation)
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 pub struct ApiKeyWithSecretResponse {
     #[serde(flatten)]
     pub api_key: ApiKeyResponse,
     pub secret: String,
 }
 
+/// Response for a key rotation: the new key with its secret and the previous key
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RotateApiKeyResponse {
+    #[serde(flatten)]
+    pub api_key: ApiKeyWithSecretResponse,
+    pub previous_key: ApiKeyResponse,
+}
+
 /// Permissions in response format
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 pub struct PermissionsResponse {
     pub admin: bool,
     pub models: ResourcePermissionResponse,
     pub knowledge_bases: ResourcePermissionResponse,
     pub prompts: ResourcePermissionResponse,
     pub chains: ResourcePermissionResponse,
+    #[serde(default)]
+    pub parameters: ParameterPolicy,
 }
 
 /// Resource permission in response format
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 #[serde(rename_all = "lowercase")]
 pub enum ResourcePermissionResponse {
     All,
@@ -138,6 +363,7 @@ impl From<&ApiKeyPermissions> for PermissionsResponse {
             knowledge_bases: (&perms.knowledge_bases).into(),
             prompts: (&perms.prompts).into(),
             chains: (&perms.chains).into(),
+            parameters: perms.parameters.clone(),
         }
     }
 }
@@ -162,7 +388,13 @@ impl From<&ApiKey> for ApiKeyResponse {
             status: status_to_string(key.status()),
             permissions: key.permissions().into(),
             last_used_at: key.last_used_at().map(|dt| dt.to_rfc3339()),
+            last_used_ip: key.last_used_ip().map(String::from),
             expires_at: key.expires_at().map(|dt| dt.to_rfc3339()),
+            replaced_by: key.replaced_by().map(|id| id.as_str().to_string()),
+            allowed_cidrs: key.allowed_cidrs().iter().map(|c| c.to_string()).collect(),
+            rate_limits: key.rate_limits().into(),
+            injection_action: key.injection_action(),
+            no_log: key.no_log(),
             created_at: key.created_at().to_rfc3339(),
             updated_at: key.updated_at().to_rfc3339(),
         }
@@ -170,13 +402,20 @@ impl From<&ApiKey> for ApiKeyResponse {
 }
 
 /// List API keys response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 pub struct ListApiKeysResponse {
     pub api_keys: Vec<ApiKeyResponse>,
     pub total: usize,
 }
 
-/// GET /admin/api-keys
+/// List API keys
+#[utoipa::path(
+    get,
+    path = "/admin/api-keys",
+    tag = "admin/api-keys",
+    responses((status = 200, body = ListApiKeysResponse)),
+)]
 pub async fn list_api_keys(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -194,7 +433,14 @@ pub async fn list_api_keys(
     }))
 }
 
-/// POST /admin/api-keys
+/// Create API key
+#[utoipa::path(
+    post,
+    path = "/admin/api-keys",
+    tag = "admin/api-keys",
+    request_body = CreateApiKeyRequest,
+    responses((status = 200, body = ApiKeyWithSecretResponse)),
+)]
 pub async fn create_api_key(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -202,21 +448,82 @@ pub async fn create_api_key(
 ) -> Result<Json<ApiKeyWithSecretResponse>, ApiError> {
     debug!(name = %request.name, team_id = %request.team_id, "Admin creating API key");
 
+    request.permissions.parameters.validate()?;
     let permissions: ApiKeyPermissions = request.permissions.into();
+    let allowed_cidrs = request
+        .allowed_cidrs
+        .as_deref()
+        .map(parse_allowed_cidrs)
+        .transpose()?;
+    let rate_limits = request
+        .rate_limits
+        .map(RateLimitConfig::try_from)
+        .transpose()?;
+    // Checked before creating, so a rejected expiration leaves no key behind
+    if let Some(expires_at) = request.expires_at {
+        check_expiration(expires_at).map_err(ApiError::from)?;
+    }
 
-    let (created_key, secret) = state
+    let (mut created_key, secret) = state
         .api_key_service
         .create(&request.name, &request.team_id, permissions)
         .await
         .map_err(ApiError::from)?;
 
+    if let Some(expires_at) = request.expires_at {
+        created_key = state
+            .api_key_service
+            .set_expiration(created_key.id().as_str(), Some(expires_at))
+            .await
+            .map_err(ApiError::from)?;
+    }
+
+    if let Some(allowed_cidrs) = allowed_cidrs {
+        created_key = state
+            .api_key_service
+            .set_allowed_cidrs(created_key.id().as_str(), allowed_cidrs)
+            .await
+            .map_err(ApiError::from)?;
+    }
+
+    if let Some(rate_limits) = rate_limits {
+        created_key = state
+            .api_key_service
+            .set_rate_limits(created_key.id().as_str(), rate_limits)
+            .await
+            .map_err(ApiError::from)?;
+    }
+
+    if let Some(injection_action) = request.injection_action {
+        created_key = state
+            .api_key_service
+            .set_injection_action(created_key.id().as_str(), Some(injection_action))
+            .await
+            .map_err(ApiError::from)?;
+    }
+
+    if request.no_log {
+        created_key = state
+            .api_key_service
+            .set_no_log(created_key.id().as_str(), true)
+            .await
+            .map_err(ApiError::from)?;
+    }
+
     Ok(Json(ApiKeyWithSecretResponse {
         api_key: ApiKeyResponse::from(&created_key),
         secret,
     }))
 }
 
-/// GET /admin/api-keys/:key_id
+/// Get API key
+#[utoipa::path(
+    get,
+    path = "/admin/api-keys/{key_id}",
+    tag = "admin/api-keys",
+    params(("key_id" = String, Path, description = "Key ID")),
+    responses((status = 200, body = ApiKeyResponse)),
+)]
 pub async fn get_api_key(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -234,7 +541,15 @@ pub async fn get_api_key(
     Ok(Json(ApiKeyResponse::from(&key)))
 }
 
-/// PUT /admin/api-keys/:key_id
+/// Update API key
+#[utoipa::path(
+    put,
+    path = "/admin/api-keys/{key_id}",
+    tag = "admin/api-keys",
+    params(("key_id" = String, Path, description = "Key ID")),
+    request_body = UpdateApiKeyRequest,
+    responses((status = 200, body = ApiKeyResponse)),
+)]
 pub async fn update_api_key(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -243,7 +558,18 @@ pub async fn update_api_key(
 ) -> Result<Json<ApiKeyResponse>, ApiError> {
     debug!(key_id = %key_id, "Admin updating API key");
 
+    let allowed_cidrs = request
+        .allowed_cidrs
+        .as_deref()
+        .map(parse_allowed_cidrs)
+        .transpose()?;
+    let rate_limits = request
+        .rate_limits
+        .map(RateLimitConfig::try_from)
+        .transpose()?;
+
     if let Some(permissions_req) = request.permissions {
+        permissions_req.parameters.validate()?;
         let permissions: ApiKeyPermissions = permissions_req.into();
         state
             .api_key_service
@@ -252,6 +578,46 @@ pub async fn update_api_key(
             .map_err(ApiError::from)?;
     }
 
+    if let Some(expires_at) = request.expires_at {
+        state
+            .api_key_service
+            .set_expiration(&key_id, expires_at)
+            .await
+            .map_err(ApiError::from)?;
+    }
+
+    if let Some(allowed_cidrs) = allowed_cidrs {
+        state
+            .api_key_service
+            .set_allowed_cidrs(&key_id, allowed_cidrs)
+            .await
+            .map_err(ApiError::from)?;
+    }
+
+    if let Some(rate_limits) = rate_limits {
+        state
+            .api_key_service
+            .set_rate_limits(&key_id, rate_limits)
+            .await
+            .map_err(ApiError::from)?;
+    }
+
+    if let Some(injection_action) = request.injection_action {
+        state
+            .api_key_service
+            .set_injection_action(&key_id, injection_action)
+            .await
+            .map_err(ApiError::from)?;
+    }
+
+    if let Some(no_log) = request.no_log {
+        state
+            .api_key_service
+            .set_no_log(&key_id, no_log)
+            .await
+            .map_err(ApiError::from)?;
+    }
+
     let key = state
         .api_key_service
         .get(&key_id)
@@ -262,7 +628,45 @@ pub async fn update_api_key(
     Ok(Json(ApiKeyResponse::from(&key)))
 }
 
-/// DELETE /admin/api-keys/:key_id
+/// Get API key usage
+#[utoipa::path(
+    get,
+    path = "/admin/api-keys/{key_id}/usage",
+    tag = "admin/api-keys",
+    params(("key_id" = String, Path, description = "Key ID")),
+    responses((status = 200, body = ApiKeyUsageResponse)),
+)]
+pub async fn get_api_key_usage(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(key_id): Path<String>,
+) -> Result<Json<ApiKeyUsageResponse>, ApiError> {
+    debug!(key_id = %key_id, "Admin getting API key usage");
+
+    let key = state
+        .api_key_service
+        .get(&key_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found(format!("API key '{}' not found", key_id)))?;
+
+    let usage = state.api_key_service.quota_usage(&key).await;
+
+    Ok(Json(ApiKeyUsageResponse {
+        api_key_id: key.id().as_str().to_string(),
+        enabled: key.rate_limits().is_enabled(),
+        quotas: usage.iter().map(QuotaWindowResponse::from).collect(),
+    }))
+}
+
+/// Delete API key
+#[utoipa::path(
+    delete,
+    path = "/admin/api-keys/{key_id}",
+    tag = "admin/api-keys",
+    params(("key_id" = String, Path, description = "Key ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn delete_api_key(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -282,7 +686,14 @@ pub async fn delete_api_key(
     })))
 }
 
-/// POST /admin/api-keys/:key_id/suspend
+/// Suspend API key
+#[utoipa::path(
+    post,
+    path = "/admin/api-keys/{key_id}/suspend",
+    tag = "admin/api-keys",
+    params(("key_id" = String, Path, description = "Key ID")),
+    responses((status = 200, body = ApiKeyResponse)),
+)]
 pub async fn suspend_api_key(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -306,7 +717,14 @@ pub async fn suspend_api_key(
     Ok(Json(ApiKeyResponse::from(&key)))
 }
 
-/// POST /admin/api-keys/:key_id/activate
+/// Activate API key
+#[utoipa::path(
+    post,
+    path = "/admin/api-keys/{key_id}/activate",
+    tag = "admin/api-keys",
+    params(("key_id" = String, Path, description = "Key ID")),
+    responses((status = 200, body = ApiKeyResponse)),
+)]
 pub async fn activate_api_key(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -330,7 +748,14 @@ pub async fn activate_api_key(
     Ok(Json(ApiKeyResponse::from(&key)))
 }
 
-/// POST /admin/api-keys/:key_id/revoke
+/// Revoke API key
+#[utoipa::path(
+    post,
+    path = "/admin/api-keys/{key_id}/revoke",
+    tag = "admin/api-keys",
+    params(("key_id" = String, Path, description = "Key ID")),
+    responses((status = 200, body = ApiKeyResponse)),
+)]
 pub async fn revoke_api_key(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -354,6 +779,73 @@ pub async fn revoke_api_key(
     Ok(Json(ApiKeyResponse::from(&key)))
 }
 
+/// Revoke several API keys, reporting each key
+#[utoipa::path(
+    post,
+    path = "/admin/api-keys/bulk-revoke",
+    tag = "admin/api-keys",
+    request_body = BulkRevokeApiKeysRequest,
+    responses((status = 200, body = BulkResponse)),
+)]
+pub async fn bulk_revoke_api_keys(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Json(request): Json<BulkRevokeApiKeysRequest>,
+) -> Result<Json<BulkResponse>, ApiError> {
+    ensure_bulk_size("key_ids", request.key_ids.len())?;
+
+    debug!(keys = request.key_ids.len(), "Admin revoking API keys in bulk");
+
+    let mut response = BulkResponse::new();
+
+    for key_id in request.key_ids {
+        let result = state
+            .api_key_service
+            .revoke(&key_id)
+            .await
+            .map_err(ApiError::from);
+        response.record(key_id, result);
+    }
+
+    Ok(Json(response))
+}
+
+/// Rotate API key
+#[utoipa::path(
+    post,
+    path = "/admin/api-keys/{key_id}/rotate",
+    tag = "admin/api-keys",
+    params(("key_id" = String, Path, description = "Key ID")),
+    request_body = RotateApiKeyRequest,
+    responses((status = 200, body = RotateApiKeyResponse)),
+)]
+pub async fn rotate_api_key(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(key_id): Path<String>,
+    Json(request): Json<RotateApiKeyRequest>,
+) -> Result<Json<RotateApiKeyResponse>, ApiError> {
+    debug!(
+        key_id = %key_id,
+        grace_period_secs = request.grace_period_secs,
+        "Admin rotating API key"
+    );
+
+    let result = state
+        .api_key_service
+        .rotate(&key_id, request.grace_period_secs)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(RotateApiKeyResponse {
+        api_key: ApiKeyWithSecretResponse {
+            api_key: ApiKeyResponse::from(&result.api_key),
+            secret: result.secret,
+        },
+        previous_key: ApiKeyResponse::from(&result.previous),
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -438,6 +930,26 @@ mod tests {
         assert!(request.permissions.admin);
     }
 
+    #[test]
+    fn test_permissions_parameter_policy() {
+        let json = r#"{
+            "name": "Internal Tool",
+            "team_id": "tools",
+            "permissions": {
+                "parameters": {"max_tokens": 2000, "max_temperature": 1.0, "streaming_only": true}
+            }
+        }"#;
+
+        let request: CreateApiKeyRequest = serde_json::from_str(json).unwrap();
+        let perms: ApiKeyPermissions = request.permissions.into();
+        assert_eq!(perms.parameters.max_tokens, Some(2000));
+        assert!(perms.parameters.streaming_only);
+
+        let resp: PermissionsResponse = (&perms).into();
+        let json = serde_json::to_value(&resp).unwrap();
+        assert_eq!(json["parameters"]["max_temperature"], 1.0);
+    }
+
     #[test]
     fn test_update_api_key_request_empty() {
         let json = r#"{}"#;
@@ -460,6 +972,48 @@ mod tests {
         assert!(request.permissions.unwrap().admin);
     }
 
+    #[test]
+    fn test_rate_limits_request() {
+        let json = r#"{
+            "requests_per_minute": 60,
+            "tokens_per_day": 100000,
+            "cost_per_month_usd": 12.5
+        }"#;
+
+        let request: RateLimitsRequest = serde_json::from_str(json).unwrap();
+        let config = RateLimitConfig::try_from(request).unwrap();
+
+        assert!(config.enabled);
+        assert_eq!(config.requests_per_minute, 60);
+        assert_eq!(config.requests_per_hour, 5000);
+        assert_eq!(config.tokens_per_day, Some(100_000));
+        assert_eq!(config.cost_per_month_micros, Some(12_500_000));
+        assert_eq!(
+            RateLimitsResponse::from(&config).cost_per_month_usd,
+            Some(12.5)
+        );
+
+        let negative: RateLimitsRequest =
+            serde_json::from_str(r#"{"cost_per_month_usd": -1}"#).unwrap();
+        assert!(RateLimitConfig::try_from(negative).is_err());
+    }
+
+    #[test]
+    fn test_quota_window_response() {
+        let window = QuotaWindow {
+            limit_type: LimitType::CostPerMonth,
+            limit: 5_000_000,
+            used: 6_000_000,
+            reset_in_seconds: 120,
+        };
+
+        let response = QuotaWindowResponse::from(&window);
+        assert_eq!(response.quota, "cost_per_month");
+        assert_eq!(response.unit, "micro_usd");
+        assert_eq!(response.window_secs, 30 * 86400);
+        assert_eq!(response.remaining, 0);
+    }
+
     #[test]
     fn test_status_to_string() {
         assert_eq!(status_to_string(ApiKeyStatus::Active), "active");
@@ -504,6 +1058,7 @@ mod tests {
             knowledge_bases: ResourcePermissionRequest::None,
             prompts: ResourcePermissionRequest::Specific(vec!["prompt-1".to_string()]),
             chains: ResourcePermissionRequest::All,
+            parameters: ParameterPolicy::default(),
         };
 
         let perms: ApiKeyPermissions = req.into();
@@ -550,6 +1105,7 @@ mod tests {
             knowledge_bases: ResourcePermission::None,
             prompts: ResourcePermission::All,
             chains: ResourcePermission::All,
+            parameters: ParameterPolicy::default(),
         };
 
         let resp: PermissionsResponse = (&perms).into();
@@ -566,6 +1122,7 @@ mod tests {
             knowledge_bases: ResourcePermissionResponse::None,
             prompts: ResourcePermissionResponse::Specific(vec!["p1".to_string()]),
             chains: ResourcePermissionResponse::All,
+            parameters: ParameterPolicy::default(),
         };
 
         let json = serde_json::to_string(&resp).unwrap();
@@ -589,9 +1146,16 @@ mod tests {
                 knowledge_bases: ResourcePermissionResponse::All,
                 prompts: ResourcePermissionResponse::All,
                 chains: ResourcePermissionResponse::All,
+                parameters: ParameterPolicy::default(),
             },
             last_used_at: None,
+            last_used_ip: None,
             expires_at: None,
+            replaced_by: None,
+            allowed_cidrs: vec![],
+            rate_limits: (&RateLimitConfig::default()).into(),
+            injection_action: None,
+            no_log: false,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-01T00:00:00Z".to_string(),
         };
@@ -619,9 +1183,16 @@ mod tests {
                     knowledge_bases: ResourcePermissionResponse::All,
                     prompts: ResourcePermissionResponse::All,
                     chains: ResourcePermissionResponse::All,
+                    parameters: ParameterPolicy::default(),
                 },
                 last_used_at: None,
+                last_used_ip: None,
                 expires_at: None,
+                replaced_by: None,
+                allowed_cidrs: vec![],
+                rate_limits: (&RateLimitConfig::default()).into(),
+                injection_action: None,
+                no_log: false,
                 created_at: "2024-01-01T00:00:00Z".to_string(),
                 updated_at: "2024-01-01T00:00:00Z".to_string(),
             },
@@ -670,4 +1241,71 @@ mod tests {
             panic!("Expected Specific");
         }
     }
+
+    #[test]
+    fn test_create_api_key_request_with_expiration() {
+        let json = r#"{
+            "name": "Expiring Key",
+            "team_id": "administrators",
+            "expires_at": "2030-01-01T00:00:00Z"
+        }"#;
+
+        let request: CreateApiKeyRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            request.expires_at.unwrap().to_rfc3339(),
+            "2030-01-01T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_update_api_key_request_expiration() {
+        let request: UpdateApiKeyRequest = serde_json::from_str("{}").unwrap();
+        assert!(request.expires_at.is_none());
+
+        let request: UpdateApiKeyRequest =
+            serde_json::from_str(r#"{"expires_at": null}"#).unwrap();
+        assert_eq!(request.expires_at, Some(None));
+
+        let request: UpdateApiKeyRequest =
+            serde_json::from_str(r#"{"expires_at": "2030-01-01T00:00:00Z"}"#).unwrap();
+        assert!(matches!(request.expires_at, Some(Some(_))));
+    }
+
+    #[test]
+    fn test_rotate_api_key_request_defaults() {
+        let request: RotateApiKeyRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(request.grace_period_secs, 86400);
+
+        let request: RotateApiKeyRequest =
+            serde_json::from_str(r#"{"grace_period_secs": 0}"#).unwrap();
+        assert_eq!(request.grace_period_secs, 0);
+    }
+
+    #[test]
+    fn test_update_api_key_request_injection_action() {
+        let request: UpdateApiKeyRequest =
+            serde_json::from_str(r#"{"injection_action": "block"}"#).unwrap();
+        assert_eq!(request.injection_action, Some(Some(InjectionAction::Block)));
+
+        let request: UpdateApiKeyRequest =
+            serde_json::from_str(r#"{"injection_action": null}"#).unwrap();
+        assert_eq!(request.injection_action, Some(None));
+
+        let request: UpdateApiKeyRequest = serde_json::from_str("{}").unwrap();
+        assert!(request.injection_action.is_none());
+
+        assert!(serde_json::from_str::<UpdateApiKeyRequest>(r#"{"injection_action": "drop"}"#)
+            .is_err());
+    }
+
+    #[test]
+    fn test_update_api_key_request_allowed_cidrs() {
+        let request: UpdateApiKeyRequest =
+            serde_json::from_str(r#"{"allowed_cidrs": ["10.0.0.0/8", "192.168.1.5"]}"#).unwrap();
+        let cidrs = parse_allowed_cidrs(&request.allowed_cidrs.unwrap()).unwrap();
+        assert_eq!(cidrs.len(), 2);
+        assert_eq!(cidrs[1].to_string(), "192.168.1.5/32");
+
+        assert!(parse_allowed_cidrs(&["10.0.0.0/33".to_string()]).is_err());
+    }
 }
@@ -0,0 +1,308 @@
+//! Assistant management admin endpoints
+
+use std::collections::HashMap;
+
+use axum::extract::{Path, State};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{debug, info};
+use utoipa::ToSchema;
+
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::assistant::{Assistant, AssistantTool, KnowledgeBaseBinding};
+use crate::infrastructure::assistant::{CreateAssistantRequest, UpdateAssistantRequest};
+
+/// Request to create a new assistant
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateAssistantApiRequest {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub model_id: String,
+    /// Prompt rendered as the system message
+    pub prompt_id: String,
+    #[serde(default)]
+    pub prompt_variables: HashMap<String, String>,
+    #[serde(default)]
+    pub knowledge_bases: Vec<KnowledgeBaseBinding>,
+    #[serde(default)]
+    pub tools: Vec<AssistantTool>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Request to update an assistant
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct UpdateAssistantApiRequest {
+    pub name: Option<String>,
+    pub description: Option<Option<String>>,
+    pub model_id: Option<String>,
+    pub prompt_id: Option<String>,
+    pub prompt_variables: Option<HashMap<String, String>>,
+    pub knowledge_bases: Option<Vec<KnowledgeBaseBinding>>,
+    pub tools: Option<Vec<AssistantTool>>,
+    pub enabled: Option<bool>,
+}
+
+/// Assistant response for admin API
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AssistantResponse {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub model_id: String,
+    pub prompt_id: String,
+    pub prompt_variables: HashMap<String, String>,
+    pub knowledge_bases: Vec<KnowledgeBaseBinding>,
+    pub tools: Vec<AssistantTool>,
+    pub enabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<Assistant> for AssistantResponse {
+    fn from(assistant: Assistant) -> Self {
+        Self {
+            id: assistant.id().to_string(),
+            name: assistant.name().to_string(),
+            description: assistant.description().map(String::from),
+            model_id: assistant.model_id().to_string(),
+            prompt_id: assistant.prompt_id().to_string(),
+            prompt_variables: assistant.prompt_variables().clone(),
+            knowledge_bases: assistant.knowledge_bases().to_vec(),
+            tools: assistant.tools().to_vec(),
+            enabled: assistant.is_enabled(),
+            created_at: assistant.created_at().to_rfc3339(),
+            updated_at: assistant.updated_at().to_rfc3339(),
+        }
+    }
+}
+
+/// List assistants response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListAssistantsResponse {
+    pub assistants: Vec<AssistantResponse>,
+    pub total: usize,
+}
+
+/// List assistants
+#[utoipa::path(
+    get,
+    path = "/admin/assistants",
+    tag = "admin/assistants",
+    responses((status = 200, body = ListAssistantsResponse)),
+)]
+pub async fn list_assistants(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+) -> Result<Json<ListAssistantsResponse>, ApiError> {
+    debug!("Listing assistants");
+
+    let assistants: Vec<AssistantResponse> = state
+        .assistant_service
+        .list()
+        .await?
+        .into_iter()
+        .map(AssistantResponse::from)
+        .collect();
+
+    Ok(Json(ListAssistantsResponse {
+        total: assistants.len(),
+        assistants,
+    }))
+}
+
+/// Get assistant
+#[utoipa::path(
+    get,
+    path = "/admin/assistants/{assistant_id}",
+    tag = "admin/assistants",
+    params(("assistant_id" = String, Path, description = "Assistant ID")),
+    responses((status = 200, body = AssistantResponse)),
+)]
+pub async fn get_assistant(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+) -> Result<Json<AssistantResponse>, ApiError> {
+    debug!(assistant_id = %id, "Getting assistant");
+
+    let assistant = state
+        .assistant_service
+        .get(&id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Assistant '{}' not found", id)))?;
+
+    Ok(Json(AssistantResponse::from(assistant)))
+}
+
+/// Create assistant
+#[utoipa::path(
+    post,
+    path = "/admin/assistants",
+    tag = "admin/assistants",
+    request_body = CreateAssistantApiRequest,
+    responses((status = 200, body = AssistantResponse)),
+)]
+pub async fn create_assistant(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Json(request): Json<CreateAssistantApiRequest>,
+) -> Result<Json<AssistantResponse>, ApiError> {
+    info!(assistant_id = %request.id, "Creating assistant");
+
+    ensure_references(
+        &state,
+        Some(&request.model_id),
+        Some(&request.prompt_id),
+        Some(&request.knowledge_bases),
+        Some(&request.tools),
+    )
+    .await?;
+
+    let service_request = CreateAssistantRequest {
+        id: request.id,
+        name: request.name,
+        description: request.description,
+        model_id: request.model_id,
+        prompt_id: request.prompt_id,
+        prompt_variables: request.prompt_variables,
+        knowledge_bases: request.knowledge_bases,
+        tools: request.tools,
+        enabled: request.enabled,
+    };
+
+    let assistant = state.assistant_service.create(service_request).await?;
+
+    Ok(Json(AssistantResponse::from(assistant)))
+}
+
+/// Update assistant
+#[utoipa::path(
+    put,
+    path = "/admin/assistants/{assistant_id}",
+    tag = "admin/assistants",
+    params(("assistant_id" = String, Path, description = "Assistant ID")),
+    request_body = UpdateAssistantApiRequest,
+    responses((status = 200, body = AssistantResponse)),
+)]
+pub async fn update_assistant(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateAssistantApiRequest>,
+) -> Result<Json<AssistantResponse>, ApiError> {
+    info!(assistant_id = %id, "Updating assistant");
+
+    ensure_references(
+        &state,
+        request.model_id.as_deref(),
+        request.prompt_id.as_deref(),
+        request.knowledge_bases.as_deref(),
+        request.tools.as_deref(),
+    )
+    .await?;
+
+    let service_request = UpdateAssistantRequest {
+        name: request.name,
+        description: request.description,
+        model_id: request.model_id,
+        prompt_id: request.prompt_id,
+        prompt_variables: request.prompt_variables,
+        knowledge_bases: request.knowledge_bases,
+        tools: request.tools,
+        enabled: request.enabled,
+    };
+
+    let assistant = state.assistant_service.update(&id, service_request).await?;
+
+    Ok(Json(AssistantResponse::from(assistant)))
+}
+
+/// Delete assistant
+#[utoipa::path(
+    delete,
+    path = "/admin/assistants/{assistant_id}",
+    tag = "admin/assistants",
+    params(("assistant_id" = String, Path, description = "Assistant ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
+pub async fn delete_assistant(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!(assistant_id = %id, "Deleting assistant");
+
+    let deleted = state.assistant_service.delete(&id).await?;
+
+    if deleted {
+        Ok(Json(json!({"deleted": true})))
+    } else {
+        Err(ApiError::not_found(format!("Assistant '{}' not found", id)))
+    }
+}
+
+/// Check the model, prompt, knowledge bases and tool workflows exist
+async fn ensure_references(
+    state: &AppState,
+    model_id: Option<&str>,
+    prompt_id: Option<&str>,
+    knowledge_bases: Option<&[KnowledgeBaseBinding]>,
+    tools: Option<&[AssistantTool]>,
+) -> Result<(), ApiError> {
+    if let Some(model_id) = model_id
+        && state.model_service.get(model_id).await?.is_none()
+    {
+        return Err(
+            ApiError::bad_request(format!("Model '{}' not found", model_id)).with_param("model_id"),
+        );
+    }
+
+    if let Some(prompt_id) = prompt_id
+        && state.prompt_service.get(prompt_id).await?.is_none()
+    {
+        return Err(
+            ApiError::bad_request(format!("Prompt '{}' not found", prompt_id))
+                .with_param("prompt_id"),
+        );
+    }
+
+    for binding in knowledge_bases.unwrap_or_default() {
+        if !state
+            .knowledge_base_service
+            .exists(&binding.knowledge_base_id)
+            .await?
+        {
+            return Err(ApiError::bad_request(format!(
+                "Knowledge base '{}' not found",
+                binding.knowledge_base_id
+            ))
+            .with_param("knowledge_bases"));
+        }
+    }
+
+    for tool in tools.unwrap_or_default() {
+        if state
+            .workflow_service
+            .get(&tool.workflow_id)
+            .await?
+            .is_none()
+        {
+            return Err(ApiError::bad_request(format!(
+                "Workflow '{}' not found",
+                tool.workflow_id
+            ))
+            .with_param("tools"));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,224 @@
+//! Audit log admin endpoints
+
+use axum::extract::{Path, Query, State};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::audit::{AuditActorType, AuditLog, AuditLogQuery};
+
+/// Default page size when listing audit logs
+const DEFAULT_LIMIT: usize = 100;
+
+/// Audit actor response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AuditActorResponse {
+    #[serde(rename = "type")]
+    pub actor_type: String,
+    pub id: String,
+    pub name: Option<String>,
+}
+
+/// Audit log response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AuditLogResponse {
+    pub id: String,
+    pub actor: AuditActorResponse,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changes: Option<serde_json::Value>,
+    pub status_code: Option<u16>,
+    pub success: bool,
+    pub method: Option<String>,
+    pub path: Option<String>,
+    pub client_ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: String,
+}
+
+impl From<&AuditLog> for AuditLogResponse {
+    fn from(log: &AuditLog) -> Self {
+        Self {
+            id: log.id().to_string(),
+            actor: AuditActorResponse {
+                actor_type: log.actor().actor_type.to_string(),
+                id: log.actor().id.clone(),
+                name: log.actor().name.clone(),
+            },
+            action: log.action().to_string(),
+            entity_type: log.entity_type().to_string(),
+            entity_id: log.entity_id().map(String::from),
+            changes: log.changes().cloned(),
+            status_code: log.status_code(),
+            success: log.is_success(),
+            method: log.method().map(String::from),
+            path: log.path().map(String::from),
+            client_ip: log.client_ip().map(String::from),
+            user_agent: log.user_agent().map(String::from),
+            created_at: log.created_at().to_rfc3339(),
+        }
+    }
+}
+
+/// List audit logs response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListAuditLogsResponse {
+    pub logs: Vec<AuditLogResponse>,
+    pub total: usize,
+}
+
+/// Query parameters for listing audit logs
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListAuditLogsQuery {
+    pub actor_type: Option<String>,
+    pub actor_id: Option<String>,
+    pub action: Option<String>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub from_date: Option<String>,
+    pub to_date: Option<String>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl ListAuditLogsQuery {
+    /// Build the domain filter (without pagination)
+    fn to_domain_query(&self) -> Result<AuditLogQuery, ApiError> {
+        let actor_type = self
+            .actor_type
+            .as_deref()
+            .map(|t| {
+                AuditActorType::parse(t)
+                    .ok_or_else(|| ApiError::bad_request(format!("Invalid actor type: {}", t)))
+            })
+            .transpose()?;
+
+        Ok(AuditLogQuery {
+            actor_type,
+            actor_id: self.actor_id.clone(),
+            action: self.action.clone(),
+            entity_type: self.entity_type.as_deref().map(|t| t.replace('-', "_")),
+            entity_id: self.entity_id.clone(),
+            from_date: parse_date("from_date", self.from_date.as_deref())?,
+            to_date: parse_date("to_date", self.to_date.as_deref())?,
+            limit: None,
+            offset: None,
+        })
+    }
+}
+
+fn parse_date(field: &str, value: Option<&str>) -> Result<Option<DateTime<Utc>>, ApiError> {
+    value
+        .map(|v| {
+            DateTime::parse_from_rfc3339(v)
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| ApiError::bad_request(format!("Invalid {}: {}", field, e)))
+        })
+        .transpose()
+}
+
+/// List audit logs
+#[utoipa::path(
+    get,
+    path = "/admin/audit-logs",
+    tag = "admin/audit-logs",
+    params(ListAuditLogsQuery),
+    responses((status = 200, body = ListAuditLogsResponse)),
+)]
+pub async fn list_audit_logs(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Query(params): Query<ListAuditLogsQuery>,
+) -> Result<Json<ListAuditLogsResponse>, ApiError> {
+    let filter = params.to_domain_query()?;
+    let total = state.audit_log_service.count(&filter).await?;
+
+    let query = filter
+        .with_limit(params.limit.unwrap_or(DEFAULT_LIMIT))
+        .with_offset(params.offset.unwrap_or(0));
+    let logs = state.audit_log_service.list(&query).await?;
+
+    Ok(Json(ListAuditLogsResponse {
+        logs: logs.iter().map(AuditLogResponse::from).collect(),
+        total,
+    }))
+}
+
+/// Get audit log
+#[utoipa::path(
+    get,
+    path = "/admin/audit-logs/{log_id}",
+    tag = "admin/audit-logs",
+    params(("log_id" = String, Path, description = "Log ID")),
+    responses((status = 200, body = AuditLogResponse)),
+)]
+pub async fn get_audit_log(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(log_id): Path<String>,
+) -> Result<Json<AuditLogResponse>, ApiError> {
+    let log = state
+        .audit_log_service
+        .get(&log_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Audit log '{}' not found", log_id)))?;
+
+    Ok(Json(AuditLogResponse::from(&log)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::audit::AuditActor;
+
+    #[test]
+    fn test_list_query_to_domain() {
+        let params = ListAuditLogsQuery {
+            actor_type: Some("api_key".to_string()),
+            entity_type: Some("api-keys".to_string()),
+            from_date: Some("2026-01-01T00:00:00Z".to_string()),
+            ..Default::default()
+        };
+
+        let query = params.to_domain_query().unwrap();
+        assert_eq!(query.actor_type, Some(AuditActorType::ApiKey));
+        assert_eq!(query.entity_type, Some("api_keys".to_string()));
+        assert!(query.from_date.is_some());
+        assert!(query.to_date.is_none());
+    }
+
+    #[test]
+    fn test_list_query_invalid_values() {
+        let params = ListAuditLogsQuery {
+            actor_type: Some("robot".to_string()),
+            ..Default::default()
+        };
+        assert!(params.to_domain_query().is_err());
+
+        let params = ListAuditLogsQuery {
+            to_date: Some("yesterday".to_string()),
+            ..Default::default()
+        };
+        assert!(params.to_domain_query().is_err());
+    }
+
+    #[test]
+    fn test_audit_log_response_serialization() {
+        let log = AuditLog::new(AuditActor::user("user-1", "alice"), "rotate", "api_keys")
+            .with_entity_id("key-1")
+            .with_status_code(200);
+
+        let json = serde_json::to_value(AuditLogResponse::from(&log)).unwrap();
+        assert_eq!(json["actor"]["type"], "user");
+        assert_eq!(json["actor"]["name"], "alice");
+        assert_eq!(json["entity_id"], "key-1");
+        assert_eq!(json["success"], true);
+        assert!(json.get("changes").is_none());
+    }
+}
@@ -0,0 +1,109 @@
+//! Shared types of the bulk admin endpoints
+//!
+//! Bulk endpoints apply their items in order and report the outcome of each
+//! one. Items are independent: a failing item neither stops nor rolls back
+//! the others.
+
+use axum::http::StatusCode;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::api::types::{ApiError, ApiErrorDetail};
+
+/// Maximum number of items in one bulk request
+pub const MAX_BULK_ITEMS: usize = 500;
+
+/// Outcome of one item of a bulk request
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BulkItemResult {
+    /// Position of the item in the request
+    pub index: usize,
+    /// ID of the entity the item targeted
+    pub id: String,
+    /// HTTP status the item would have had as a single request
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ApiErrorDetail>,
+}
+
+/// Per-item results of a bulk request
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct BulkResponse {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BulkItemResult>,
+}
+
+impl BulkResponse {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the outcome of the next item
+    pub fn record<T>(&mut self, id: impl Into<String>, result: Result<T, ApiError>) {
+        let (status, error) = match result {
+            Ok(_) => {
+                self.succeeded += 1;
+                (StatusCode::OK, None)
+            }
+            Err(err) => {
+                self.failed += 1;
+                (err.status, Some(err.response.error))
+            }
+        };
+
+        self.results.push(BulkItemResult {
+            index: self.results.len(),
+            id: id.into(),
+            status: status.as_u16(),
+            error,
+        });
+    }
+}
+
+/// Reject bulk requests over `MAX_BULK_ITEMS` items
+pub fn ensure_bulk_size(field: &str, len: usize) -> Result<(), ApiError> {
+    if len > MAX_BULK_ITEMS {
+        return Err(ApiError::invalid_field(
+            field,
+            format!("at most {} items per request, got {}", MAX_BULK_ITEMS, len),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_results() {
+        let mut response = BulkResponse::new();
+        response.record("first", Ok::<(), ApiError>(()));
+        response.record(
+            "second",
+            Err::<(), _>(ApiError::not_found("Prompt 'second' not found")),
+        );
+
+        assert_eq!(response.succeeded, 1);
+        assert_eq!(response.failed, 1);
+        assert_eq!(response.results[0].status, 200);
+        assert!(response.results[0].error.is_none());
+        assert_eq!(response.results[1].index, 1);
+        assert_eq!(response.results[1].status, 404);
+        assert_eq!(
+            response.results[1].error.as_ref().unwrap().message,
+            "Prompt 'second' not found"
+        );
+    }
+
+    #[test]
+    fn test_ensure_bulk_size() {
+        assert!(ensure_bulk_size("operations", MAX_BULK_ITEMS).is_ok());
+
+        let err = ensure_bulk_size("operations", MAX_BULK_ITEMS + 1).unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(err.response.error.param.as_deref(), Some("operations"));
+    }
+}
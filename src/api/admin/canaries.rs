@@ -0,0 +1,60 @@
+//! Canary test case admin endpoints
+
+use axum::extract::State;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::infrastructure::health::CanaryStatus;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CanaryStatusListResponse {
+    pub canaries: Vec<CanaryStatus>,
+    /// Models routed to their fallback model while their canaries fail
+    pub degraded_models: Vec<String>,
+}
+
+impl CanaryStatusListResponse {
+    fn from_state(state: &AppState) -> Self {
+        Self {
+            canaries: state.canary_health.statuses(),
+            degraded_models: state.canary_health.degraded_models(),
+        }
+    }
+}
+
+/// Latest outcome of every canary test case
+#[utoipa::path(
+    get,
+    path = "/admin/canaries",
+    tag = "admin/canaries",
+    responses((status = 200, body = CanaryStatusListResponse)),
+)]
+pub async fn list_canaries(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+) -> Result<Json<CanaryStatusListResponse>, ApiError> {
+    Ok(Json(CanaryStatusListResponse::from_state(&state)))
+}
+
+/// Run every enabled canary test case now
+#[utoipa::path(
+    post,
+    path = "/admin/canaries/run",
+    tag = "admin/canaries",
+    responses((status = 200, body = CanaryStatusListResponse)),
+)]
+pub async fn run_canaries(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+) -> Result<Json<CanaryStatusListResponse>, ApiError> {
+    let Some(runner) = &state.canary_runner else {
+        return Err(ApiError::not_found("Canary runs are not configured"));
+    };
+
+    runner.run_all().await?;
+
+    Ok(Json(CanaryStatusListResponse::from_state(&state)))
+}
@@ -2,6 +2,7 @@
 
 use axum::extract::{Path, State};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::api::middleware::RequireAdmin;
 use crate::api::state::AppState;
@@ -9,7 +10,7 @@ use crate::api::types::{ApiError, Json};
 use crate::domain::{ConfigCategory, ConfigValue};
 
 /// Configuration entry response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ConfigEntryResponse {
     pub key: String,
     pub value: ConfigValueResponse,
@@ -19,7 +20,7 @@ pub struct ConfigEntryResponse {
 }
 
 /// Configuration value in response format
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 #[serde(tag = "type", content = "value")]
 pub enum ConfigValueResponse {
     #[serde(rename = "string")]
@@ -47,19 +48,19 @@ impl From<&ConfigValue> for ConfigValueResponse {
 }
 
 /// List configuration response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListConfigResponse {
     pub config: Vec<ConfigEntryResponse>,
 }
 
 /// Request to update a configuration value
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct UpdateConfigRequest {
     pub value: ConfigValueRequest,
 }
 
 /// Configuration value in request format
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 #[serde(tag = "type", content = "value")]
 pub enum ConfigValueRequest {
     #[serde(rename = "string")]
@@ -87,6 +88,12 @@ impl From<ConfigValueRequest> for ConfigValue {
 }
 
 /// List all configuration entries
+#[utoipa::path(
+    get,
+    path = "/admin/config",
+    tag = "admin/config",
+    responses((status = 200, body = ListConfigResponse)),
+)]
 pub async fn list_config(
     _admin: RequireAdmin,
     State(state): State<AppState>,
@@ -108,6 +115,13 @@ pub async fn list_config(
 }
 
 /// List configuration entries by category
+#[utoipa::path(
+    get,
+    path = "/admin/config/category/{category}",
+    tag = "admin/config",
+    params(("category" = String, Path, description = "Configuration category")),
+    responses((status = 200, body = ListConfigResponse)),
+)]
 pub async fn list_config_by_category(
     _admin: RequireAdmin,
     State(state): State<AppState>,
@@ -145,6 +159,13 @@ pub async fn list_config_by_category(
 }
 
 /// Get a specific configuration entry
+#[utoipa::path(
+    get,
+    path = "/admin/config/{key}",
+    tag = "admin/config",
+    params(("key" = String, Path, description = "Configuration key")),
+    responses((status = 200, body = ConfigEntryResponse)),
+)]
 pub async fn get_config(
     _admin: RequireAdmin,
     State(state): State<AppState>,
@@ -166,6 +187,14 @@ pub async fn get_config(
 }
 
 /// Update a configuration value
+#[utoipa::path(
+    put,
+    path = "/admin/config/{key}",
+    tag = "admin/config",
+    params(("key" = String, Path, description = "Configuration key")),
+    request_body = UpdateConfigRequest,
+    responses((status = 200, body = ConfigEntryResponse)),
+)]
 pub async fn update_config(
     _admin: RequireAdmin,
     State(state): State<AppState>,
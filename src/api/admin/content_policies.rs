@@ -0,0 +1,304 @@
+//! Content policy admin endpoints
+
+use axum::extract::{Path, State};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::api::admin::api_keys::deserialize_present;
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::guardrail::{BlockedTopic, ContentPolicy, PolicyStage, PolicyViolation};
+
+// ============================================================================
+// Content policy DTOs
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateContentPolicyRequest {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// Team the policy applies to, every team when unset
+    pub team_id: Option<String>,
+    pub stages: Option<Vec<PolicyStage>>,
+    #[serde(default)]
+    pub blocked_topics: Vec<BlockedTopic>,
+    #[serde(default)]
+    pub banned_phrases: Vec<String>,
+    pub max_toxicity: Option<f64>,
+    #[serde(default)]
+    pub allowed_languages: Vec<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Partial update, unset fields keep their value; `null` clears the team,
+/// description and toxicity ceiling
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateContentPolicyRequest {
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub team_id: Option<Option<String>>,
+    pub stages: Option<Vec<PolicyStage>>,
+    pub blocked_topics: Option<Vec<BlockedTopic>>,
+    pub banned_phrases: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub max_toxicity: Option<Option<f64>>,
+    pub allowed_languages: Option<Vec<String>>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct EvaluateContentPolicyRequest {
+    pub team_id: String,
+    pub stage: PolicyStage,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EvaluateContentPolicyResponse {
+    pub allowed: bool,
+    pub violations: Vec<PolicyViolation>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ContentPolicyListResponse {
+    pub policies: Vec<ContentPolicy>,
+    pub total: usize,
+}
+
+fn content_policy_not_found(id: &str) -> ApiError {
+    ApiError::not_found(format!("Content policy '{}' not found", id))
+}
+
+/// Reject policies scoped to a team that does not exist
+async fn check_team(state: &AppState, team_id: Option<&str>) -> Result<(), ApiError> {
+    let Some(team_id) = team_id else {
+        return Ok(());
+    };
+
+    if state.team_service.get(team_id).await?.is_none() {
+        return Err(
+            ApiError::bad_request(format!("Team '{}' not found", team_id)).with_param("team_id"),
+        );
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Content policy endpoints
+// ============================================================================
+
+/// List every content policy
+#[utoipa::path(
+    get,
+    path = "/admin/content-policies",
+    tag = "admin/content-policies",
+    responses((status = 200, body = ContentPolicyListResponse)),
+)]
+pub async fn list_content_policies(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+) -> Result<Json<ContentPolicyListResponse>, ApiError> {
+    let policies = state.content_policy_service.list().await?;
+    let total = policies.len();
+
+    Ok(Json(ContentPolicyListResponse { policies, total }))
+}
+
+/// Create a content policy
+#[utoipa::path(
+    post,
+    path = "/admin/content-policies",
+    tag = "admin/content-policies",
+    request_body = CreateContentPolicyRequest,
+    responses((status = 200, body = ContentPolicy)),
+)]
+pub async fn create_content_policy(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Json(request): Json<CreateContentPolicyRequest>,
+) -> Result<Json<ContentPolicy>, ApiError> {
+    check_team(&state, request.team_id.as_deref()).await?;
+
+    let mut policy = ContentPolicy::new(request.id, request.name)
+        .with_allowed_languages(request.allowed_languages);
+    policy.description = request.description;
+    policy.team_id = request.team_id;
+    policy.blocked_topics = request.blocked_topics;
+    policy.banned_phrases = request.banned_phrases;
+    policy.max_toxicity = request.max_toxicity;
+    policy.enabled = request.enabled;
+
+    if let Some(stages) = request.stages {
+        policy = policy.with_stages(stages);
+    }
+
+    let created = state.content_policy_service.create(policy).await?;
+
+    Ok(Json(created))
+}
+
+/// Get a content policy
+#[utoipa::path(
+    get,
+    path = "/admin/content-policies/{id}",
+    tag = "admin/content-policies",
+    params(("id" = String, Path, description = "Content policie ID")),
+    responses((status = 200, body = ContentPolicy)),
+)]
+pub async fn get_content_policy(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ContentPolicy>, ApiError> {
+    let policy = state
+        .content_policy_service
+        .get(&id)
+        .await?
+        .ok_or_else(|| content_policy_not_found(&id))?;
+
+    Ok(Json(policy))
+}
+
+/// Change the rules of a content policy
+#[utoipa::path(
+    put,
+    path = "/admin/content-policies/{id}",
+    tag = "admin/content-policies",
+    params(("id" = String, Path, description = "Content policie ID")),
+    request_body = UpdateContentPolicyRequest,
+    responses((status = 200, body = ContentPolicy)),
+)]
+pub async fn update_content_policy(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateContentPolicyRequest>,
+) -> Result<Json<ContentPolicy>, ApiError> {
+    let mut policy = state
+        .content_policy_service
+        .get(&id)
+        .await?
+        .ok_or_else(|| content_policy_not_found(&id))?;
+
+    if let Some(name) = request.name {
+        policy.name = name;
+    }
+    if let Some(description) = request.description {
+        policy.description = description;
+    }
+    if let Some(team_id) = request.team_id {
+        check_team(&state, team_id.as_deref()).await?;
+        policy.team_id = team_id;
+    }
+    if let Some(stages) = request.stages {
+        policy.stages = stages;
+    }
+    if let Some(blocked_topics) = request.blocked_topics {
+        policy.blocked_topics = blocked_topics;
+    }
+    if let Some(banned_phrases) = request.banned_phrases {
+        policy.banned_phrases = banned_phrases;
+    }
+    if let Some(max_toxicity) = request.max_toxicity {
+        policy.max_toxicity = max_toxicity;
+    }
+    if let Some(allowed_languages) = request.allowed_languages {
+        policy.allowed_languages = allowed_languages;
+    }
+    if let Some(enabled) = request.enabled {
+        policy.enabled = enabled;
+    }
+
+    let updated = state.content_policy_service.update(policy).await?;
+
+    Ok(Json(updated))
+}
+
+/// Delete a content policy
+#[utoipa::path(
+    delete,
+    path = "/admin/content-policies/{id}",
+    tag = "admin/content-policies",
+    params(("id" = String, Path, description = "Content policie ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
+pub async fn delete_content_policy(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    if state.content_policy_service.delete(&id).await? {
+        Ok(Json(serde_json::json!({
+            "deleted": true,
+            "id": id
+        })))
+    } else {
+        Err(content_policy_not_found(&id))
+    }
+}
+
+/// Evaluate the policies of a team against a text without a completion
+#[utoipa::path(
+    post,
+    path = "/admin/content-policies/evaluate",
+    tag = "admin/content-policies",
+    request_body = EvaluateContentPolicyRequest,
+    responses((status = 200, body = EvaluateContentPolicyResponse)),
+)]
+pub async fn evaluate_content_policies(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Json(request): Json<EvaluateContentPolicyRequest>,
+) -> Result<Json<EvaluateContentPolicyResponse>, ApiError> {
+    let violations = state
+        .content_policy_service
+        .evaluate(&request.team_id, request.stage, &request.text)
+        .await?;
+
+    Ok(Json(EvaluateContentPolicyResponse {
+        allowed: violations.is_empty(),
+        violations,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_request_defaults() {
+        let request: CreateContentPolicyRequest = serde_json::from_str(
+            r#"{"id": "support", "name": "Support", "banned_phrases": ["acme"]}"#,
+        )
+        .unwrap();
+
+        assert!(request.enabled);
+        assert!(request.stages.is_none());
+        assert!(request.blocked_topics.is_empty());
+        assert_eq!(request.banned_phrases, vec!["acme".to_string()]);
+    }
+
+    #[test]
+    fn test_update_request_clears_fields() {
+        let request: UpdateContentPolicyRequest =
+            serde_json::from_str(r#"{"team_id": null, "max_toxicity": 0.4}"#).unwrap();
+        assert_eq!(request.team_id, Some(None));
+        assert_eq!(request.max_toxicity, Some(Some(0.4)));
+        assert!(request.description.is_none());
+
+        let request: UpdateContentPolicyRequest =
+            serde_json::from_str(r#"{"stages": ["output"]}"#).unwrap();
+        assert_eq!(request.stages, Some(vec![PolicyStage::Output]));
+        assert!(request.team_id.is_none());
+    }
+}
@@ -1,10 +1,14 @@
 //! Credentials management admin endpoints
 
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use serde::{Deserialize, Serialize};
 use std::time::Instant;
 use tracing::debug;
+use utoipa::{IntoParams, ToSchema};
 
+use crate::api::admin::api_keys::deserialize_present;
+use crate::api::admin::organizations::resolve_organization;
+use crate::api::admin::teams::parse_region;
 use crate::api::middleware::RequireAdmin;
 use crate::api::state::AppState;
 use crate::api::types::{ApiError, Json};
@@ -13,20 +17,20 @@ use crate::domain::llm::{LlmRequest, Message};
 use crate::infrastructure::llm::{LlmProviderConfig, LlmProviderFactory};
 
 /// Credential provider info response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct CredentialProviderInfo {
     pub provider_type: String,
     pub description: String,
 }
 
 /// List credentials response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListCredentialProvidersResponse {
     pub providers: Vec<CredentialProviderInfo>,
 }
 
 /// Request to create a new stored credential
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct CreateCredentialApiRequest {
     pub id: String,
     pub name: String,
@@ -40,21 +44,42 @@ pub struct CreateCredentialApiRequest {
     pub deployment: Option<String>,
     /// Header value template for HTTP API Key credentials (e.g., "Bearer ${api-key}")
     pub header_value: Option<String>,
+    /// Organization sharing the credential with all of its teams
+    #[serde(default)]
+    pub organization_id: Option<String>,
+    /// Region the provider endpoint serves from (e.g. `eu-west-1`), required
+    /// for use by teams pinned to regions
+    #[serde(default)]
+    pub region: Option<String>,
 }
 
 /// Request to update a stored credential
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct UpdateCredentialApiRequest {
     pub name: Option<String>,
     pub api_key: Option<String>,
     pub endpoint: Option<Option<String>>,
     pub deployment: Option<Option<String>>,
     pub header_value: Option<Option<String>>,
+    /// Moves the credential to another organization; `null` makes it global
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub organization_id: Option<Option<String>>,
+    /// Changes the endpoint's region; `null` leaves it unknown
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub region: Option<Option<String>>,
     pub enabled: Option<bool>,
 }
 
+/// Query parameters for listing credentials
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListCredentialsQuery {
+    /// Only return credentials shared by this organization
+    pub organization_id: Option<String>,
+}
+
 /// Stored credential response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct CredentialResponse {
     pub id: String,
     pub name: String,
@@ -63,13 +88,15 @@ pub struct CredentialResponse {
     pub deployment: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub header_value: Option<String>,
+    pub organization_id: Option<String>,
+    pub region: Option<String>,
     pub enabled: bool,
     pub created_at: String,
     pub updated_at: String,
 }
 
 /// List credentials response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListCredentialsResponse {
     pub credentials: Vec<CredentialResponse>,
     pub total: usize,
@@ -85,6 +112,7 @@ fn credential_type_to_string(ct: &CredentialType) -> String {
         CredentialType::AwsKnowledgeBase => "aws_knowledge_base".to_string(),
         CredentialType::Pinecone => "pinecone".to_string(),
         CredentialType::HttpApiKey => "http_api_key".to_string(),
+        CredentialType::Mock => "mock".to_string(),
         CredentialType::Custom(s) => s.clone(),
     }
 }
@@ -101,6 +129,7 @@ fn parse_credential_type(s: &str) -> Result<CredentialType, ApiError> {
         }
         "pinecone" => Ok(CredentialType::Pinecone),
         "http_api_key" | "http-api-key" | "httpapikey" => Ok(CredentialType::HttpApiKey),
+        "mock" => Ok(CredentialType::Mock),
         other => Ok(CredentialType::Custom(other.to_string())),
     }
 }
@@ -121,6 +150,8 @@ impl From<&StoredCredential> for CredentialResponse {
             endpoint,
             deployment: cred.deployment().map(|s| s.to_string()),
             header_value: cred.header_value().map(|s| s.to_string()),
+            organization_id: cred.organization_id().map(|o| o.as_str().to_string()),
+            region: cred.region().map(|r| r.to_string()),
             enabled: cred.is_enabled(),
             created_at: cred.created_at().to_rfc3339(),
             updated_at: cred.updated_at().to_rfc3339(),
@@ -128,8 +159,13 @@ impl From<&StoredCredential> for CredentialResponse {
     }
 }
 
-/// GET /admin/credentials/providers
 /// Lists available credential provider types
+#[utoipa::path(
+    get,
+    path = "/admin/credentials/providers",
+    tag = "admin/credentials",
+    responses((status = 200, body = ListCredentialProvidersResponse)),
+)]
 pub async fn list_credential_providers(
     State(_state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -167,25 +203,43 @@ pub async fn list_credential_providers(
             provider_type: credential_type_to_string(&CredentialType::Pinecone),
             description: "Pinecone vector database credentials".to_string(),
         },
+        // Development
+        CredentialProviderInfo {
+            provider_type: credential_type_to_string(&CredentialType::Mock),
+            description: "Mock LLM provider returning canned responses".to_string(),
+        },
     ];
 
     Ok(Json(ListCredentialProvidersResponse { providers }))
 }
 
-/// GET /admin/credentials
 /// List all stored credentials
+#[utoipa::path(
+    get,
+    path = "/admin/credentials",
+    tag = "admin/credentials",
+    params(ListCredentialsQuery),
+    responses((status = 200, body = ListCredentialsResponse)),
+)]
 pub async fn list_credentials(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
+    Query(query): Query<ListCredentialsQuery>,
 ) -> Result<Json<ListCredentialsResponse>, ApiError> {
     debug!("Admin listing all credentials");
 
-    let credentials = state
+    let mut credentials = state
         .credential_service
         .list()
         .await
         .map_err(ApiError::from)?;
 
+    if let Some(ref organization_id) = query.organization_id {
+        credentials.retain(|c| {
+            c.organization_id().map(|o| o.as_str()) == Some(organization_id.as_str())
+        });
+    }
+
     let cred_responses: Vec<CredentialResponse> =
         credentials.iter().map(CredentialResponse::from).collect();
     let total = cred_responses.len();
@@ -208,8 +262,14 @@ fn requires_api_key(credential_type: &CredentialType) -> bool {
     )
 }
 
-/// POST /admin/credentials
 /// Create a new stored credential
+#[utoipa::path(
+    post,
+    path = "/admin/credentials",
+    tag = "admin/credentials",
+    request_body = CreateCredentialApiRequest,
+    responses((status = 200, body = CredentialResponse)),
+)]
 pub async fn create_credential(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -232,6 +292,16 @@ pub async fn create_credential(
         request.api_key.unwrap_or_default()
     };
 
+    let organization_id = match request.organization_id {
+        Some(ref id) => Some(resolve_organization(&state, id).await?),
+        None => None,
+    };
+    let region = request
+        .region
+        .as_deref()
+        .map(|r| parse_region(r, "region"))
+        .transpose()?;
+
     let create_request = crate::infrastructure::credentials::CreateCredentialRequest {
         id: request.id,
         name: request.name,
@@ -240,6 +310,8 @@ pub async fn create_credential(
         endpoint: request.endpoint,
         deployment: request.deployment,
         header_value: request.header_value,
+        organization_id,
+        region,
     };
 
     let credential = state
@@ -251,8 +323,14 @@ pub async fn create_credential(
     Ok(Json(CredentialResponse::from(&credential)))
 }
 
-/// GET /admin/credentials/:credential_id
 /// Get a specific stored credential
+#[utoipa::path(
+    get,
+    path = "/admin/credentials/{credential_id}",
+    tag = "admin/credentials",
+    params(("credential_id" = String, Path, description = "Credential ID")),
+    responses((status = 200, body = CredentialResponse)),
+)]
 pub async fn get_credential(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -270,8 +348,15 @@ pub async fn get_credential(
     Ok(Json(CredentialResponse::from(&credential)))
 }
 
-/// PUT /admin/credentials/:credential_id
 /// Update a stored credential
+#[utoipa::path(
+    put,
+    path = "/admin/credentials/{credential_id}",
+    tag = "admin/credentials",
+    params(("credential_id" = String, Path, description = "Credential ID")),
+    request_body = UpdateCredentialApiRequest,
+    responses((status = 200, body = CredentialResponse)),
+)]
 pub async fn update_credential(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -280,12 +365,25 @@ pub async fn update_credential(
 ) -> Result<Json<CredentialResponse>, ApiError> {
     debug!(credential_id = %credential_id, "Admin updating credential");
 
+    let organization_id = match request.organization_id {
+        Some(Some(ref id)) => Some(Some(resolve_organization(&state, id).await?)),
+        Some(None) => Some(None),
+        None => None,
+    };
+    let region = match request.region {
+        Some(Some(ref region)) => Some(Some(parse_region(region, "region")?)),
+        Some(None) => Some(None),
+        None => None,
+    };
+
     let update_request = crate::infrastructure::credentials::UpdateCredentialRequest {
         name: request.name,
         api_key: request.api_key,
         endpoint: request.endpoint,
         deployment: request.deployment,
         header_value: request.header_value,
+        organization_id,
+        region,
         enabled: request.enabled,
     };
 
@@ -298,8 +396,14 @@ pub async fn update_credential(
     Ok(Json(CredentialResponse::from(&credential)))
 }
 
-/// DELETE /admin/credentials/:credential_id
 /// Delete a stored credential
+#[utoipa::path(
+    delete,
+    path = "/admin/credentials/{credential_id}",
+    tag = "admin/credentials",
+    params(("credential_id" = String, Path, description = "Credential ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn delete_credential(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -337,7 +441,7 @@ pub async fn delete_credential(
 }
 
 /// Request to test a credential
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct TestCredentialRequest {
     pub message: String,
     #[serde(default = "default_test_model")]
@@ -349,7 +453,7 @@ fn default_test_model() -> String {
 }
 
 /// Response from testing a credential
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct TestCredentialResponse {
     pub success: bool,
     pub provider: String,
@@ -359,8 +463,15 @@ pub struct TestCredentialResponse {
     pub latency_ms: u64,
 }
 
-/// POST /admin/credentials/:credential_id/test
 /// Test a credential by sending a simple chat request or testing database connection
+#[utoipa::path(
+    post,
+    path = "/admin/credentials/{credential_id}/test",
+    tag = "admin/credentials",
+    params(("credential_id" = String, Path, description = "Credential ID")),
+    request_body = TestCredentialRequest,
+    responses((status = 200, body = TestCredentialResponse)),
+)]
 pub async fn test_credential(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -545,6 +656,9 @@ fn create_provider_config(cred: &StoredCredential) -> Result<LlmProviderConfig,
         CredentialType::HttpApiKey => Err(ApiError::bad_request(
             "HTTP API Key credentials cannot be tested as LLM providers",
         )),
+        CredentialType::Mock => Ok(LlmProviderConfig::Mock {
+            options: cred.endpoint().map(|s| s.to_string()),
+        }),
         CredentialType::Custom(name) => {
             Err(ApiError::bad_request(format!("Unsupported provider: {}", name)))
         }
@@ -587,6 +701,7 @@ mod tests {
         assert!(matches!(parse_credential_type("pinecone").unwrap(), CredentialType::Pinecone));
         assert!(matches!(parse_credential_type("http_api_key").unwrap(), CredentialType::HttpApiKey));
         assert!(matches!(parse_credential_type("http-api-key").unwrap(), CredentialType::HttpApiKey));
+        assert!(matches!(parse_credential_type("mock").unwrap(), CredentialType::Mock));
     }
 
     #[test]
@@ -605,6 +720,7 @@ mod tests {
         assert!(!requires_api_key(&CredentialType::AwsBedrock));
         assert!(!requires_api_key(&CredentialType::Pgvector));
         assert!(!requires_api_key(&CredentialType::AwsKnowledgeBase));
+        assert!(!requires_api_key(&CredentialType::Mock));
     }
 
     #[test]
@@ -712,6 +828,25 @@ mod tests {
         assert_eq!(request.model, "gpt-4");
     }
 
+    #[test]
+    fn test_update_credential_request_organization() {
+        let request: UpdateCredentialApiRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(request.organization_id, None);
+
+        let request: UpdateCredentialApiRequest =
+            serde_json::from_str(r#"{"organization_id": null}"#).unwrap();
+        assert_eq!(request.organization_id, Some(None));
+
+        let request: UpdateCredentialApiRequest =
+            serde_json::from_str(r#"{"organization_id": "acme"}"#).unwrap();
+        assert_eq!(request.organization_id, Some(Some("acme".to_string())));
+
+        let request: UpdateCredentialApiRequest =
+            serde_json::from_str(r#"{"region": null}"#).unwrap();
+        assert_eq!(request.region, Some(None));
+        assert!(request.organization_id.is_none());
+    }
+
     #[test]
     fn test_credential_response_serialization() {
         let response = CredentialResponse {
@@ -721,6 +856,8 @@ mod tests {
             endpoint: None,
             deployment: None,
             header_value: None,
+            organization_id: None,
+            region: None,
             enabled: true,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-01T00:00:00Z".to_string(),
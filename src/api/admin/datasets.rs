@@ -0,0 +1,391 @@
+//! Dataset management admin endpoints
+
+use axum::extract::{Path, State};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{debug, info};
+use utoipa::ToSchema;
+
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::dataset::{parse_rows, Dataset, DatasetFormat, DatasetRow, DatasetVersion};
+use crate::infrastructure::dataset::{CreateDatasetRequest, UpdateDatasetRequest};
+
+/// Rows of a dataset version, given inline or as CSV/JSONL content
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct DatasetRowsApiRequest {
+    /// Rows given as JSON
+    #[serde(default)]
+    pub rows: Option<Vec<DatasetRow>>,
+    /// Format of `content`: `csv` or `jsonl`
+    #[serde(default)]
+    pub format: Option<String>,
+    /// CSV or JSONL content to import
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
+/// Request to create a new dataset
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateDatasetApiRequest {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(flatten)]
+    pub rows: DatasetRowsApiRequest,
+    /// What the first version contains
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// Request to update a dataset
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct UpdateDatasetApiRequest {
+    pub name: Option<String>,
+    pub description: Option<Option<String>>,
+}
+
+/// Request to create a new dataset version
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateDatasetVersionApiRequest {
+    #[serde(flatten)]
+    pub rows: DatasetRowsApiRequest,
+    /// What changed in this version
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// Dataset response for admin API
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DatasetResponse {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub latest_version: u32,
+    pub row_count: usize,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// List datasets response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListDatasetsResponse {
+    pub datasets: Vec<DatasetResponse>,
+    pub total: usize,
+}
+
+/// Dataset version response; rows are only included for a single version
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DatasetVersionResponse {
+    pub dataset_id: String,
+    pub version: u32,
+    pub row_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rows: Option<Vec<DatasetRow>>,
+    pub created_at: String,
+}
+
+/// List dataset versions response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListDatasetVersionsResponse {
+    pub versions: Vec<DatasetVersionResponse>,
+    pub total: usize,
+}
+
+/// List datasets
+#[utoipa::path(
+    get,
+    path = "/admin/datasets",
+    tag = "admin/datasets",
+    responses((status = 200, body = ListDatasetsResponse)),
+)]
+pub async fn list_datasets(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+) -> Result<Json<ListDatasetsResponse>, ApiError> {
+    debug!("Listing datasets");
+
+    let datasets: Vec<DatasetResponse> = state
+        .dataset_service
+        .list()
+        .await?
+        .into_iter()
+        .map(to_response)
+        .collect();
+
+    Ok(Json(ListDatasetsResponse {
+        total: datasets.len(),
+        datasets,
+    }))
+}
+
+/// Get dataset
+#[utoipa::path(
+    get,
+    path = "/admin/datasets/{dataset_id}",
+    tag = "admin/datasets",
+    params(("dataset_id" = String, Path, description = "Dataset ID")),
+    responses((status = 200, body = DatasetResponse)),
+)]
+pub async fn get_dataset(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+) -> Result<Json<DatasetResponse>, ApiError> {
+    debug!(dataset_id = %id, "Getting dataset");
+
+    let dataset = state
+        .dataset_service
+        .get(&id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Dataset '{}' not found", id)))?;
+
+    Ok(Json(to_response(dataset)))
+}
+
+/// Create dataset
+#[utoipa::path(
+    post,
+    path = "/admin/datasets",
+    tag = "admin/datasets",
+    request_body = CreateDatasetApiRequest,
+    responses((status = 200, body = DatasetResponse)),
+)]
+pub async fn create_dataset(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Json(request): Json<CreateDatasetApiRequest>,
+) -> Result<Json<DatasetResponse>, ApiError> {
+    info!(dataset_id = %request.id, "Creating dataset");
+
+    let service_request = CreateDatasetRequest {
+        id: request.id,
+        name: request.name,
+        description: request.description,
+        rows: resolve_rows(request.rows)?,
+        note: request.note,
+    };
+
+    let dataset = state.dataset_service.create(service_request).await?;
+
+    Ok(Json(to_response(dataset)))
+}
+
+/// Update dataset
+#[utoipa::path(
+    put,
+    path = "/admin/datasets/{dataset_id}",
+    tag = "admin/datasets",
+    params(("dataset_id" = String, Path, description = "Dataset ID")),
+    request_body = UpdateDatasetApiRequest,
+    responses((status = 200, body = DatasetResponse)),
+)]
+pub async fn update_dataset(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateDatasetApiRequest>,
+) -> Result<Json<DatasetResponse>, ApiError> {
+    info!(dataset_id = %id, "Updating dataset");
+
+    let service_request = UpdateDatasetRequest {
+        name: request.name,
+        description: request.description,
+    };
+
+    let dataset = state.dataset_service.update(&id, service_request).await?;
+
+    Ok(Json(to_response(dataset)))
+}
+
+/// Delete dataset
+#[utoipa::path(
+    delete,
+    path = "/admin/datasets/{dataset_id}",
+    tag = "admin/datasets",
+    params(("dataset_id" = String, Path, description = "Dataset ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
+pub async fn delete_dataset(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!(dataset_id = %id, "Deleting dataset");
+
+    let deleted = state.dataset_service.delete(&id).await?;
+
+    if deleted {
+        Ok(Json(json!({"deleted": true})))
+    } else {
+        Err(ApiError::not_found(format!("Dataset '{}' not found", id)))
+    }
+}
+
+/// Create dataset version
+#[utoipa::path(
+    post,
+    path = "/admin/datasets/{dataset_id}/versions",
+    tag = "admin/datasets",
+    params(("dataset_id" = String, Path, description = "Dataset ID")),
+    request_body = CreateDatasetVersionApiRequest,
+    responses((status = 200, body = DatasetVersionResponse)),
+)]
+pub async fn create_dataset_version(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+    Json(request): Json<CreateDatasetVersionApiRequest>,
+) -> Result<Json<DatasetVersionResponse>, ApiError> {
+    info!(dataset_id = %id, "Creating dataset version");
+
+    let rows = resolve_rows(request.rows)?;
+    let version = state
+        .dataset_service
+        .add_version(&id, rows, request.note)
+        .await?;
+
+    Ok(Json(to_version_response(version, false)))
+}
+
+/// List dataset versions
+#[utoipa::path(
+    get,
+    path = "/admin/datasets/{dataset_id}/versions",
+    tag = "admin/datasets",
+    params(("dataset_id" = String, Path, description = "Dataset ID")),
+    responses((status = 200, body = ListDatasetVersionsResponse)),
+)]
+pub async fn list_dataset_versions(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+) -> Result<Json<ListDatasetVersionsResponse>, ApiError> {
+    debug!(dataset_id = %id, "Listing dataset versions");
+
+    let versions: Vec<DatasetVersionResponse> = state
+        .dataset_service
+        .list_versions(&id)
+        .await?
+        .into_iter()
+        .map(|version| to_version_response(version, false))
+        .collect();
+
+    Ok(Json(ListDatasetVersionsResponse {
+        total: versions.len(),
+        versions,
+    }))
+}
+
+/// Get dataset version
+#[utoipa::path(
+    get,
+    path = "/admin/datasets/{dataset_id}/versions/{version}",
+    tag = "admin/datasets",
+    params(
+        ("dataset_id" = String, Path, description = "Dataset ID"),
+        ("version" = u32, Path, description = "Version number"),
+    ),
+    responses((status = 200, body = DatasetVersionResponse)),
+)]
+pub async fn get_dataset_version(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path((id, version)): Path<(String, u32)>,
+) -> Result<Json<DatasetVersionResponse>, ApiError> {
+    debug!(dataset_id = %id, version = version, "Getting dataset version");
+
+    let version = state
+        .dataset_service
+        .get_version(&id, version)
+        .await?
+        .ok_or_else(|| {
+            ApiError::not_found(format!("Version {} of dataset '{}' not found", version, id))
+        })?;
+
+    Ok(Json(to_version_response(version, true)))
+}
+
+/// Take the inline rows, or parse the CSV/JSONL content
+fn resolve_rows(request: DatasetRowsApiRequest) -> Result<Vec<DatasetRow>, ApiError> {
+    match (request.rows, request.content) {
+        (Some(_), Some(_)) => Err(ApiError::bad_request(
+            "Provide either 'rows' or 'content', not both",
+        )),
+        (Some(rows), None) => Ok(rows),
+        (None, Some(content)) => {
+            let format = request.format.as_deref().unwrap_or("csv");
+            let format = DatasetFormat::parse(format).ok_or_else(|| {
+                ApiError::bad_request(format!(
+                    "Invalid dataset format: {}. Expected 'csv' or 'jsonl'",
+                    format
+                ))
+            })?;
+
+            Ok(parse_rows(format, &content)?)
+        }
+        (None, None) => Err(ApiError::bad_request("Either 'rows' or 'content' is required")),
+    }
+}
+
+fn to_response(dataset: Dataset) -> DatasetResponse {
+    DatasetResponse {
+        id: dataset.id().to_string(),
+        name: dataset.name().to_string(),
+        description: dataset.description().map(String::from),
+        latest_version: dataset.latest_version(),
+        row_count: dataset.row_count(),
+        created_at: dataset.created_at().to_rfc3339(),
+        updated_at: dataset.updated_at().to_rfc3339(),
+    }
+}
+
+fn to_version_response(version: DatasetVersion, include_rows: bool) -> DatasetVersionResponse {
+    DatasetVersionResponse {
+        dataset_id: version.dataset_id.to_string(),
+        version: version.version,
+        row_count: version.rows.len(),
+        note: version.note,
+        rows: include_rows.then_some(version.rows),
+        created_at: version.created_at.to_rfc3339(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_rows() {
+        let request: CreateDatasetApiRequest = serde_json::from_value(json!({
+            "id": "faq",
+            "name": "FAQ",
+            "format": "jsonl",
+            "content": "{\"question\": \"Hi\", \"expected_output\": \"Hello\"}"
+        }))
+        .unwrap();
+
+        let rows = resolve_rows(request.rows).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].expected_output.as_deref(), Some("Hello"));
+
+        let both = DatasetRowsApiRequest {
+            rows: Some(Vec::new()),
+            format: None,
+            content: Some("a\n1".to_string()),
+        };
+        assert!(resolve_rows(both).is_err());
+        assert!(resolve_rows(DatasetRowsApiRequest::default()).is_err());
+
+        let unknown = DatasetRowsApiRequest {
+            rows: None,
+            format: Some("xml".to_string()),
+            content: Some("<rows/>".to_string()),
+        };
+        assert!(resolve_rows(unknown).is_err());
+    }
+}
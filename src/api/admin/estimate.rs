@@ -0,0 +1,410 @@
+//! Cost estimation admin endpoint
+//!
+//! Estimates the tokens and cost of a prompt sent to a model, or of a
+//! workflow run on a sample input, without calling any provider. Token counts
+//! use the same heuristic as budget enforcement and costs come from the
+//! pricing catalog, so the figures are meant for budgeting, not billing.
+
+use axum::extract::State;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use tracing::debug;
+use utoipa::ToSchema;
+
+use crate::api::middleware::{RequireAdmin, estimate_prompt_tokens};
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::llm::Message;
+use crate::domain::{
+    ChatCompletionStep, Model, ModelPricing, PromptTemplate, WorkflowContext, WorkflowStepType,
+};
+
+/// Output tokens assumed when neither the request, the step nor the model
+/// sets a limit
+const DEFAULT_MAX_OUTPUT_TOKENS: u32 = 1024;
+
+// ============================================================================
+// Estimate DTOs
+// ============================================================================
+
+/// Estimate a prompt (`model_id` with `prompt_id` or `prompt`) or a workflow
+/// (`workflow_id` with a sample `input`)
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct EstimateApiRequest {
+    pub model_id: Option<String>,
+    /// Stored prompt to render with `variables`
+    pub prompt_id: Option<String>,
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    /// Literal prompt text, sent as a single user message
+    pub prompt: Option<String>,
+    pub workflow_id: Option<String>,
+    /// Sample workflow input resolving `${request:*}` references
+    #[serde(default)]
+    pub input: Value,
+    /// Lower bound of the output tokens per completion
+    #[serde(default)]
+    pub min_output_tokens: u32,
+    /// Upper bound of the output tokens per completion; defaults to the
+    /// step or model `max_tokens`
+    pub max_output_tokens: Option<u32>,
+}
+
+/// Estimate of one completion
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CompletionEstimate {
+    /// Workflow step name, absent for prompt estimates
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step: Option<String>,
+    pub model_id: String,
+    pub input_tokens: u32,
+    pub min_output_tokens: u32,
+    pub max_output_tokens: u32,
+    /// Absent when the model has no pricing
+    pub min_cost_micros: Option<i64>,
+    pub max_cost_micros: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct EstimateResponse {
+    pub completions: Vec<CompletionEstimate>,
+    pub input_tokens: u32,
+    pub min_output_tokens: u32,
+    pub max_output_tokens: u32,
+    /// Totals over the priced completions
+    pub min_cost_micros: i64,
+    pub max_cost_micros: i64,
+    /// Parts of the estimate that could not be computed exactly
+    pub warnings: Vec<String>,
+}
+
+impl EstimateResponse {
+    fn new(completions: Vec<CompletionEstimate>, warnings: Vec<String>) -> Self {
+        Self {
+            input_tokens: completions.iter().map(|c| c.input_tokens).sum(),
+            min_output_tokens: completions.iter().map(|c| c.min_output_tokens).sum(),
+            max_output_tokens: completions.iter().map(|c| c.max_output_tokens).sum(),
+            min_cost_micros: completions.iter().filter_map(|c| c.min_cost_micros).sum(),
+            max_cost_micros: completions.iter().filter_map(|c| c.max_cost_micros).sum(),
+            completions,
+            warnings,
+        }
+    }
+}
+
+/// Estimate one completion of `content` sent as a user message
+fn estimate_completion(
+    step: Option<String>,
+    model: &Model,
+    pricing: Option<&ModelPricing>,
+    content: String,
+    min_output_tokens: u32,
+    max_output_tokens: Option<u32>,
+) -> CompletionEstimate {
+    let input_tokens = estimate_prompt_tokens(&[Message::user(content)]);
+    let max_output_tokens = max_output_tokens
+        .or(model.config().max_tokens)
+        .unwrap_or(DEFAULT_MAX_OUTPUT_TOKENS)
+        .max(min_output_tokens);
+
+    CompletionEstimate {
+        step,
+        model_id: model.id().to_string(),
+        input_tokens,
+        min_output_tokens,
+        max_output_tokens,
+        min_cost_micros: pricing.map(|p| p.calculate_cost(input_tokens, min_output_tokens)),
+        max_cost_micros: pricing.map(|p| p.calculate_cost(input_tokens, max_output_tokens)),
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Estimate the tokens and cost of a prompt or workflow without executing it
+#[utoipa::path(
+    post,
+    path = "/admin/estimate",
+    tag = "admin/estimate",
+    request_body = EstimateApiRequest,
+    responses((status = 200, body = EstimateResponse)),
+)]
+pub async fn estimate(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Json(request): Json<EstimateApiRequest>,
+) -> Result<Json<EstimateResponse>, ApiError> {
+    debug!("Admin estimating cost");
+
+    match (&request.model_id, &request.workflow_id) {
+        (Some(model_id), None) => estimate_prompt(&state, model_id, &request).await,
+        (None, Some(workflow_id)) => estimate_workflow(&state, workflow_id, &request).await,
+        _ => Err(ApiError::bad_request(
+            "Exactly one of model_id or workflow_id is required",
+        )),
+    }
+    .map(Json)
+}
+
+async fn estimate_prompt(
+    state: &AppState,
+    model_id: &str,
+    request: &EstimateApiRequest,
+) -> Result<EstimateResponse, ApiError> {
+    let model = get_model(state, model_id, "model_id").await?;
+
+    let content = match (&request.prompt_id, &request.prompt) {
+        (Some(prompt_id), None) => state
+            .prompt_service
+            .render(prompt_id, &request.variables)
+            .await
+            .map_err(ApiError::from)?,
+        (None, Some(prompt)) => prompt.clone(),
+        _ => {
+            return Err(ApiError::bad_request(
+                "Exactly one of prompt_id or prompt is required with model_id",
+            ));
+        }
+    };
+
+    let mut warnings = Vec::new();
+    let pricing = pricing_for(state, &model, &mut warnings);
+
+    let completion = estimate_completion(
+        None,
+        &model,
+        pricing.as_ref(),
+        content,
+        request.min_output_tokens,
+        request.max_output_tokens,
+    );
+
+    Ok(EstimateResponse::new(vec![completion], warnings))
+}
+
+async fn estimate_workflow(
+    state: &AppState,
+    workflow_id: &str,
+    request: &EstimateApiRequest,
+) -> Result<EstimateResponse, ApiError> {
+    let workflow = state
+        .workflow_service
+        .get(workflow_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| {
+            ApiError::not_found(format!("Workflow '{}' not found", workflow_id))
+                .with_param("workflow_id")
+        })?;
+
+    let context = WorkflowContext::new(request.input.clone());
+    let mut completions = Vec::new();
+    let mut warnings = Vec::new();
+
+    // Every step is counted, so conditional branches make this an upper bound
+    for step in workflow.steps() {
+        match step.step_type() {
+            WorkflowStepType::ChatCompletion(chat_step) => {
+                let model = get_model(state, &chat_step.model_id, "workflow_id").await?;
+                let pricing = pricing_for(state, &model, &mut warnings);
+                let content =
+                    render_step_prompt(state, step.name(), chat_step, &context, &mut warnings)
+                        .await?;
+
+                completions.push(estimate_completion(
+                    Some(step.name().to_string()),
+                    &model,
+                    pricing.as_ref(),
+                    content,
+                    request.min_output_tokens,
+                    request.max_output_tokens.or(chat_step.max_tokens),
+                ));
+            }
+            WorkflowStepType::CragScoring(_) => warnings.push(format!(
+                "Step '{}' scores retrieved documents with a model; its cost depends on the \
+                 documents and is not estimated",
+                step.name()
+            )),
+            _ => {}
+        }
+    }
+
+    Ok(EstimateResponse::new(completions, warnings))
+}
+
+/// Render a chat completion step prompt the way the executor does, falling
+/// back to the unresolved text where step outputs are needed
+async fn render_step_prompt(
+    state: &AppState,
+    step_name: &str,
+    step: &ChatCompletionStep,
+    context: &WorkflowContext,
+    warnings: &mut Vec<String>,
+) -> Result<String, ApiError> {
+    let prompt = state
+        .prompt_service
+        .get(&step.prompt_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| {
+            ApiError::not_found(format!("Prompt '{}' not found", step.prompt_id))
+                .with_param("workflow_id")
+        })?;
+    let template = state
+        .prompt_service
+        .expand_partials(prompt.content())
+        .await
+        .map_err(ApiError::from)?;
+
+    let mut unresolved = false;
+    let mut variables = HashMap::with_capacity(step.prompt_variables.len());
+    for (key, value) in &step.prompt_variables {
+        let resolved = context.resolve_string(value).unwrap_or_else(|_| {
+            unresolved = true;
+            value.clone()
+        });
+        variables.insert(key.clone(), resolved);
+    }
+
+    let rendered = PromptTemplate::parse(template.as_str())
+        .and_then(|parsed| parsed.render(&variables))
+        .unwrap_or_else(|_| {
+            unresolved = true;
+            template.clone()
+        });
+    let content = context.resolve_string(&rendered).unwrap_or_else(|_| {
+        unresolved = true;
+        rendered
+    });
+
+    if unresolved {
+        warnings.push(format!(
+            "Step '{}' references values not available before execution; they are counted \
+             as their placeholders",
+            step_name
+        ));
+    }
+
+    Ok(content)
+}
+
+async fn get_model(state: &AppState, model_id: &str, param: &str) -> Result<Model, ApiError> {
+    state
+        .model_service
+        .get(model_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| {
+            ApiError::not_found(format!("Model '{}' not found", model_id)).with_param(param)
+        })
+}
+
+fn pricing_for(
+    state: &AppState,
+    model: &Model,
+    warnings: &mut Vec<String>,
+) -> Option<ModelPricing> {
+    let pricing = state.usage_service.get_pricing(model.provider_model());
+
+    if pricing.is_none() {
+        warnings.push(format!(
+            "No pricing for model '{}'; its cost is not included",
+            model.id()
+        ));
+    }
+
+    pricing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{CredentialType, ModelConfig, ModelId};
+
+    fn model(max_tokens: Option<u32>) -> Model {
+        let mut config = ModelConfig::new();
+        config.max_tokens = max_tokens;
+
+        Model::new(
+            ModelId::new("gpt-4").unwrap(),
+            "GPT-4",
+            CredentialType::OpenAi,
+            "gpt-4",
+            "openai-default",
+        )
+        .with_config(config)
+    }
+
+    #[test]
+    fn test_estimate_completion_cost_range() {
+        let pricing = ModelPricing::new("gpt-4", "openai", 0.03, 0.06);
+        let estimate = estimate_completion(
+            Some("answer".to_string()),
+            &model(None),
+            Some(&pricing),
+            "a".repeat(400),
+            100,
+            Some(500),
+        );
+
+        assert_eq!(estimate.input_tokens, 104);
+        assert_eq!(estimate.min_output_tokens, 100);
+        assert_eq!(estimate.max_output_tokens, 500);
+        assert_eq!(
+            estimate.min_cost_micros,
+            Some(pricing.calculate_cost(104, 100))
+        );
+        assert_eq!(
+            estimate.max_cost_micros,
+            Some(pricing.calculate_cost(104, 500))
+        );
+        assert!(estimate.min_cost_micros < estimate.max_cost_micros);
+    }
+
+    #[test]
+    fn test_estimate_completion_output_limit_fallbacks() {
+        let from_model = estimate_completion(None, &model(Some(256)), None, String::new(), 0, None);
+        assert_eq!(from_model.max_output_tokens, 256);
+        assert_eq!(from_model.max_cost_micros, None);
+
+        let default = estimate_completion(None, &model(None), None, String::new(), 0, None);
+        assert_eq!(default.max_output_tokens, DEFAULT_MAX_OUTPUT_TOKENS);
+
+        let raised = estimate_completion(None, &model(None), None, String::new(), 2000, Some(10));
+        assert_eq!(raised.max_output_tokens, 2000);
+    }
+
+    #[test]
+    fn test_response_totals_skip_unpriced() {
+        let pricing = ModelPricing::new("gpt-4", "openai", 0.001, 0.002);
+        let priced = estimate_completion(
+            Some("a".to_string()),
+            &model(None),
+            Some(&pricing),
+            "text".to_string(),
+            0,
+            Some(100),
+        );
+        let unpriced = estimate_completion(
+            Some("b".to_string()),
+            &model(None),
+            None,
+            "text".to_string(),
+            0,
+            Some(100),
+        );
+        let (min_cost, max_cost) = (
+            priced.min_cost_micros.unwrap(),
+            priced.max_cost_micros.unwrap(),
+        );
+
+        let response = EstimateResponse::new(vec![priced, unpriced], Vec::new());
+
+        assert_eq!(response.input_tokens, 10);
+        assert_eq!(response.max_output_tokens, 200);
+        assert_eq!(response.min_cost_micros, min_cost);
+        assert_eq!(response.max_cost_micros, max_cost);
+    }
+}
@@ -1,15 +1,29 @@
 //! Execution log management admin endpoints
 
+use std::convert::Infallible;
+
 use axum::extract::{Path, Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures::Stream;
 use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::api::middleware::RequireAdmin;
 use crate::api::state::AppState;
 use crate::api::types::{ApiError, Json};
-use crate::domain::{ExecutionLogQuery, ExecutionStatus, ExecutionType};
+use crate::domain::fine_tune::build_training_export;
+use crate::domain::guardrail::InjectionDetection;
+use crate::domain::usage::parse_usage_tags;
+use crate::domain::{
+    ExecutionLog, ExecutionLogQuery, ExecutionStatus, ExecutionType, ShadowComparison,
+    StreamingMetrics,
+};
 
 /// Execution log response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ExecutionLogResponse {
     pub id: String,
     pub execution_type: String,
@@ -28,10 +42,71 @@ pub struct ExecutionLogResponse {
     pub created_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub workflow_steps: Option<Vec<WorkflowStepLogResponse>>,
+    /// Prompt-injection detection on the request's messages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub injection: Option<InjectionDetection>,
+    /// Time to first token, token rate and abort of a streamed response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub streaming: Option<StreamingMetrics>,
+    /// Model served and judge verdict of a shadow request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadow: Option<ShadowComparison>,
+    /// ID of the API request, as returned in `x-pmp-request-id`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl From<ExecutionLog> for ExecutionLogResponse {
+    fn from(log: ExecutionLog) -> Self {
+        Self {
+            id: log.id().to_string(),
+            execution_type: log.execution_type().to_string(),
+            resource_id: log.resource_id().to_string(),
+            resource_name: log.resource_name().map(|s| s.to_string()),
+            status: log.status().to_string(),
+            input: log.input().cloned(),
+            output: log.output().cloned(),
+            error: log.error().map(|s| s.to_string()),
+            cost_micros: log.cost_micros(),
+            token_usage: log.token_usage().map(|u| TokenUsageResponse {
+                input_tokens: u.input_tokens,
+                output_tokens: u.output_tokens,
+                total_tokens: u.total_tokens,
+            }),
+            execution_time_ms: log.execution_time_ms(),
+            executor: ExecutorResponse {
+                user_id: log.executor().user_id.clone(),
+                api_key_id: log.executor().api_key_id.clone(),
+                team_id: log.executor().team_id.clone(),
+                ip_address: log.executor().ip_address.clone(),
+                user_agent: log.executor().user_agent.clone(),
+            },
+            created_at: log.created_at().to_rfc3339(),
+            workflow_steps: log.workflow_steps().map(|steps| {
+                steps
+                    .iter()
+                    .map(|step| WorkflowStepLogResponse {
+                        step_name: step.step_name.clone(),
+                        step_type: step.step_type.clone(),
+                        input: step.input.clone(),
+                        output: step.output.clone(),
+                        error: step.error.clone(),
+                        execution_time_ms: step.execution_time_ms,
+                        status: step.status.to_string(),
+                        streaming: step.streaming.clone(),
+                    })
+                    .collect()
+            }),
+            injection: log.injection().cloned(),
+            streaming: log.streaming().cloned(),
+            shadow: log.shadow().cloned(),
+            request_id: log.request_id().map(|s| s.to_string()),
+        }
+    }
 }
 
 /// Workflow step log response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct WorkflowStepLogResponse {
     pub step_name: String,
     pub step_type: String,
@@ -43,10 +118,12 @@ pub struct WorkflowStepLogResponse {
     pub error: Option<String>,
     pub execution_time_ms: u64,
     pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub streaming: Option<StreamingMetrics>,
 }
 
 /// Token usage response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct TokenUsageResponse {
     pub input_tokens: u32,
     pub output_tokens: u32,
@@ -54,23 +131,25 @@ pub struct TokenUsageResponse {
 }
 
 /// Executor response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ExecutorResponse {
     pub user_id: Option<String>,
     pub api_key_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<String>,
     pub ip_address: Option<String>,
     pub user_agent: Option<String>,
 }
 
 /// List execution logs response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListExecutionLogsResponse {
     pub logs: Vec<ExecutionLogResponse>,
     pub total: usize,
 }
 
 /// Execution statistics response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ExecutionStatsResponse {
     pub total_executions: usize,
     pub successful_executions: usize,
@@ -85,13 +164,22 @@ pub struct ExecutionStatsResponse {
 }
 
 /// Query parameters for listing execution logs
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListExecutionLogsQuery {
     pub execution_type: Option<String>,
     pub resource_id: Option<String>,
     pub status: Option<String>,
     pub api_key_id: Option<String>,
     pub user_id: Option<String>,
+    pub team_id: Option<String>,
+    pub payload_captured: Option<bool>,
+    pub injection_detected: Option<bool>,
+    pub stream_aborted: Option<bool>,
+    /// Only logs carrying all of these `key=value` tags, comma-separated
+    pub tags: Option<String>,
+    /// Only logs of the API request with this `x-pmp-request-id`
+    pub request_id: Option<String>,
     pub from_date: Option<String>,
     pub to_date: Option<String>,
     pub limit: Option<usize>,
@@ -107,6 +195,7 @@ impl ListExecutionLogsQuery {
                 "model" => ExecutionType::Model,
                 "workflow" => ExecutionType::Workflow,
                 "chat_completion" => ExecutionType::ChatCompletion,
+                "shadow" => ExecutionType::Shadow,
                 _ => return Err(ApiError::bad_request(format!(
                     "Invalid execution type: {}",
                     exec_type
@@ -138,6 +227,31 @@ impl ListExecutionLogsQuery {
             query = query.with_user_id(user_id.clone());
         }
 
+        if let Some(ref team_id) = self.team_id {
+            query = query.with_team_id(team_id.clone());
+        }
+
+        if let Some(payload_captured) = self.payload_captured {
+            query = query.with_payload_captured(payload_captured);
+        }
+
+        if let Some(injection_detected) = self.injection_detected {
+            query = query.with_injection_detected(injection_detected);
+        }
+
+        if let Some(stream_aborted) = self.stream_aborted {
+            query = query.with_stream_aborted(stream_aborted);
+        }
+
+        if let Some(ref tags) = self.tags {
+            let tags = parse_usage_tags(tags).map_err(|e| ApiError::from(e).with_param("tags"))?;
+            query = query.with_tags(tags);
+        }
+
+        if let Some(ref request_id) = self.request_id {
+            query = query.with_request_id(request_id.clone());
+        }
+
         if let (Some(from), Some(to)) = (&self.from_date, &self.to_date) {
             let from_date = chrono::DateTime::parse_from_rfc3339(from)
                 .map_err(|e| ApiError::bad_request(format!("Invalid from_date: {}", e)))?
@@ -161,6 +275,13 @@ impl ListExecutionLogsQuery {
 }
 
 /// List execution logs
+#[utoipa::path(
+    get,
+    path = "/admin/execution-logs",
+    tag = "admin/execution-logs",
+    params(ListExecutionLogsQuery),
+    responses((status = 200, body = ListExecutionLogsResponse)),
+)]
 pub async fn list_execution_logs(
     _admin: RequireAdmin,
     State(state): State<AppState>,
@@ -174,50 +295,262 @@ pub async fn list_execution_logs(
 
     let logs = logs
         .into_iter()
-        .map(|log| ExecutionLogResponse {
+        .map(ExecutionLogResponse::from)
+        .collect();
+
+    Ok(Json(ListExecutionLogsResponse { logs, total }))
+}
+
+/// Query parameters for tailing execution logs
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StreamExecutionLogsQuery {
+    pub execution_type: Option<String>,
+    pub resource_id: Option<String>,
+    /// Shorthand for `execution_type=workflow&resource_id=<id>`
+    pub workflow_id: Option<String>,
+    pub status: Option<String>,
+    pub api_key_id: Option<String>,
+    pub team_id: Option<String>,
+}
+
+impl StreamExecutionLogsQuery {
+    fn to_domain_query(&self) -> Result<ExecutionLogQuery, ApiError> {
+        let query = ListExecutionLogsQuery {
+            execution_type: self.execution_type.clone(),
+            resource_id: self.resource_id.clone(),
+            status: self.status.clone(),
+            api_key_id: self.api_key_id.clone(),
+            user_id: None,
+            team_id: self.team_id.clone(),
+            payload_captured: None,
+            injection_detected: None,
+            stream_aborted: None,
+            tags: None,
+            request_id: None,
+            from_date: None,
+            to_date: None,
+            limit: None,
+            offset: None,
+        }
+        .to_domain_query()?;
+
+        Ok(match &self.workflow_id {
+            Some(workflow_id) => query
+                .with_execution_type(ExecutionType::Workflow)
+                .with_resource_id(workflow_id.clone()),
+            None => query,
+        })
+    }
+}
+
+/// Tail execution logs matching the filter as they are recorded, as
+/// server-sent `execution_log` events. A `lagged` event reports logs skipped
+/// because the client fell behind.
+#[utoipa::path(
+    get,
+    path = "/admin/execution-logs/stream",
+    tag = "admin/execution-logs",
+    params(StreamExecutionLogsQuery),
+    responses((status = 200, description = "Server-sent `execution_log` and `lagged` events", content_type = "text/event-stream")),
+)]
+pub async fn stream_execution_logs(
+    _admin: RequireAdmin,
+    State(state): State<AppState>,
+    Query(query_params): Query<StreamExecutionLogsQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let query = query_params.to_domain_query()?;
+    let receiver = state.execution_log_service.subscribe();
+
+    let stream = futures::stream::unfold((receiver, query), |(mut receiver, query)| async move {
+        loop {
+            let event = match receiver.recv().await {
+                Ok(log) if query.matches(&log) => {
+                    match Event::default()
+                        .event("execution_log")
+                        .json_data(ExecutionLogResponse::from(log))
+                    {
+                        Ok(event) => event,
+                        Err(_) => continue,
+                    }
+                }
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => Event::default()
+                    .event("lagged")
+                    .data(serde_json::json!({ "skipped": skipped }).to_string()),
+                Err(RecvError::Closed) => return None,
+            };
+
+            return Some((Ok(event), (receiver, query)));
+        }
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// Captured request/response payloads of a completion
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CapturedPayloadResponse {
+    pub id: String,
+    pub model: String,
+    pub team_id: Option<String>,
+    pub api_key_id: Option<String>,
+    pub status: String,
+    pub request: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub execution_time_ms: u64,
+    pub created_at: String,
+}
+
+/// List captured payloads response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListCapturedPayloadsResponse {
+    pub payloads: Vec<CapturedPayloadResponse>,
+    pub total: usize,
+}
+
+/// List the redacted request/response payloads captured by payload sampling,
+/// newest first
+#[utoipa::path(
+    get,
+    path = "/admin/execution-logs/payloads",
+    tag = "admin/execution-logs",
+    params(ListExecutionLogsQuery),
+    responses((status = 200, body = ListCapturedPayloadsResponse)),
+)]
+pub async fn list_captured_payloads(
+    _admin: RequireAdmin,
+    State(state): State<AppState>,
+    Query(query_params): Query<ListExecutionLogsQuery>,
+) -> Result<Json<ListCapturedPayloadsResponse>, ApiError> {
+    let query = query_params.to_domain_query()?.with_payload_captured(true);
+    let count_query = query_params.to_domain_query()?.with_payload_captured(true);
+
+    let logs = state.execution_log_service.list(&query).await?;
+    let total = state.execution_log_service.count(&count_query).await?;
+
+    let payloads = logs
+        .into_iter()
+        .map(|log| CapturedPayloadResponse {
             id: log.id().to_string(),
-            execution_type: log.execution_type().to_string(),
-            resource_id: log.resource_id().to_string(),
-            resource_name: log.resource_name().map(|s| s.to_string()),
+            model: log.resource_id().to_string(),
+            team_id: log.executor().team_id.clone(),
+            api_key_id: log.executor().api_key_id.clone(),
             status: log.status().to_string(),
-            input: log.input().cloned(),
-            output: log.output().cloned(),
+            request: log.input().cloned(),
+            response: log.output().cloned(),
             error: log.error().map(|s| s.to_string()),
-            cost_micros: log.cost_micros(),
-            token_usage: log.token_usage().map(|u| TokenUsageResponse {
-                input_tokens: u.input_tokens,
-                output_tokens: u.output_tokens,
-                total_tokens: u.total_tokens,
-            }),
             execution_time_ms: log.execution_time_ms(),
-            executor: ExecutorResponse {
-                user_id: log.executor().user_id.clone(),
-                api_key_id: log.executor().api_key_id.clone(),
-                ip_address: log.executor().ip_address.clone(),
-                user_agent: log.executor().user_agent.clone(),
-            },
             created_at: log.created_at().to_rfc3339(),
-            workflow_steps: log.workflow_steps().map(|steps| {
-                steps
-                    .iter()
-                    .map(|step| WorkflowStepLogResponse {
-                        step_name: step.step_name.clone(),
-                        step_type: step.step_type.clone(),
-                        input: step.input.clone(),
-                        output: step.output.clone(),
-                        error: step.error.clone(),
-                        execution_time_ms: step.execution_time_ms,
-                        status: step.status.to_string(),
-                    })
-                    .collect()
-            }),
         })
         .collect();
 
-    Ok(Json(ListExecutionLogsResponse { logs, total }))
+    Ok(Json(ListCapturedPayloadsResponse { payloads, total }))
+}
+
+/// Query parameters for exporting training data
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportTrainingDataQuery {
+    /// Only executions of this workflow
+    pub workflow_id: Option<String>,
+    /// Only chat completions of this model
+    pub model: Option<String>,
+    pub api_key_id: Option<String>,
+    pub team_id: Option<String>,
+    /// Only completions carrying all of these `key=value` tags, comma-separated
+    pub tags: Option<String>,
+    pub from_date: Option<String>,
+    pub to_date: Option<String>,
+    /// Most recent logs to export, all by default
+    pub limit: Option<usize>,
+    /// System message added to conversations without one
+    pub system_prompt: Option<String>,
+}
+
+impl ExportTrainingDataQuery {
+    fn to_domain_query(&self) -> Result<ExecutionLogQuery, ApiError> {
+        if self.workflow_id.is_some() && self.model.is_some() {
+            return Err(ApiError::bad_request(
+                "Filter on either workflow_id or model, not both",
+            ));
+        }
+
+        let (execution_type, resource_id) = match (&self.workflow_id, &self.model) {
+            (Some(workflow_id), _) => (Some("workflow"), Some(workflow_id.clone())),
+            (None, Some(model)) => (Some("chat_completion"), Some(model.clone())),
+            (None, None) => (None, None),
+        };
+
+        ListExecutionLogsQuery {
+            execution_type: execution_type.map(String::from),
+            resource_id,
+            status: Some("success".to_string()),
+            api_key_id: self.api_key_id.clone(),
+            user_id: None,
+            team_id: self.team_id.clone(),
+            payload_captured: None,
+            injection_detected: None,
+            stream_aborted: None,
+            tags: self.tags.clone(),
+            request_id: None,
+            from_date: self.from_date.clone(),
+            to_date: self.to_date.clone(),
+            limit: self.limit,
+            offset: None,
+        }
+        .to_domain_query()
+    }
+}
+
+/// Export successful chat completions and workflow chat steps as an OpenAI
+/// fine-tuning chat-format JSONL file, with secrets and personal data
+/// redacted. Only logs recorded with their payloads can be exported.
+#[utoipa::path(
+    get,
+    path = "/admin/execution-logs/training-data",
+    tag = "admin/execution-logs",
+    params(ExportTrainingDataQuery),
+    responses((
+        status = 200,
+        description = "Training examples, one `{\"messages\": [...]}` conversation per line",
+        content_type = "application/jsonl",
+        body = String,
+    )),
+)]
+pub async fn export_training_data(
+    _admin: RequireAdmin,
+    State(state): State<AppState>,
+    Query(query_params): Query<ExportTrainingDataQuery>,
+) -> Result<Response, ApiError> {
+    let query = query_params.to_domain_query()?;
+    let logs = state.execution_log_service.list(&query).await?;
+    let export = build_training_export(&logs, query_params.system_prompt.as_deref());
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/jsonl".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"training-data.jsonl\"".to_string(),
+            ),
+        ],
+        export.content,
+    )
+        .into_response())
 }
 
 /// Get execution log by ID
+#[utoipa::path(
+    get,
+    path = "/admin/execution-logs/{log_id}",
+    tag = "admin/execution-logs",
+    params(("log_id" = String, Path, description = "Log ID")),
+    responses((status = 200, body = ExecutionLogResponse)),
+)]
 pub async fn get_execution_log(
     _admin: RequireAdmin,
     State(state): State<AppState>,
@@ -229,47 +562,17 @@ pub async fn get_execution_log(
         .await?
         .ok_or_else(|| ApiError::not_found(format!("Execution log '{}' not found", id)))?;
 
-    Ok(Json(ExecutionLogResponse {
-        id: log.id().to_string(),
-        execution_type: log.execution_type().to_string(),
-        resource_id: log.resource_id().to_string(),
-        resource_name: log.resource_name().map(|s| s.to_string()),
-        status: log.status().to_string(),
-        input: log.input().cloned(),
-        output: log.output().cloned(),
-        error: log.error().map(|s| s.to_string()),
-        cost_micros: log.cost_micros(),
-        token_usage: log.token_usage().map(|u| TokenUsageResponse {
-            input_tokens: u.input_tokens,
-            output_tokens: u.output_tokens,
-            total_tokens: u.total_tokens,
-        }),
-        execution_time_ms: log.execution_time_ms(),
-        executor: ExecutorResponse {
-            user_id: log.executor().user_id.clone(),
-            api_key_id: log.executor().api_key_id.clone(),
-            ip_address: log.executor().ip_address.clone(),
-            user_agent: log.executor().user_agent.clone(),
-        },
-        created_at: log.created_at().to_rfc3339(),
-        workflow_steps: log.workflow_steps().map(|steps| {
-            steps
-                .iter()
-                .map(|step| WorkflowStepLogResponse {
-                    step_name: step.step_name.clone(),
-                    step_type: step.step_type.clone(),
-                    input: step.input.clone(),
-                    output: step.output.clone(),
-                    error: step.error.clone(),
-                    execution_time_ms: step.execution_time_ms,
-                    status: step.status.to_string(),
-                })
-                .collect()
-        }),
-    }))
+    Ok(Json(ExecutionLogResponse::from(log)))
 }
 
 /// Delete execution log by ID
+#[utoipa::path(
+    delete,
+    path = "/admin/execution-logs/{log_id}",
+    tag = "admin/execution-logs",
+    params(("log_id" = String, Path, description = "Log ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn delete_execution_log(
     _admin: RequireAdmin,
     State(state): State<AppState>,
@@ -291,6 +594,13 @@ pub async fn delete_execution_log(
 }
 
 /// Get execution statistics
+#[utoipa::path(
+    get,
+    path = "/admin/execution-logs/stats",
+    tag = "admin/execution-logs",
+    params(ListExecutionLogsQuery),
+    responses((status = 200, body = ExecutionStatsResponse)),
+)]
 pub async fn get_execution_stats(
     _admin: RequireAdmin,
     State(state): State<AppState>,
@@ -314,11 +624,20 @@ pub async fn get_execution_stats(
 }
 
 /// Cleanup old execution logs
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct CleanupRequest {
     pub days: Option<i64>,
 }
 
+/// Delete execution logs older than `days`, or past the configured retention
+/// when unset
+#[utoipa::path(
+    post,
+    path = "/admin/execution-logs/cleanup",
+    tag = "admin/execution-logs",
+    request_body = CleanupRequest,
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn cleanup_execution_logs(
     _admin: RequireAdmin,
     State(state): State<AppState>,
@@ -358,6 +677,7 @@ mod tests {
         let response = ExecutorResponse {
             user_id: Some("user-123".to_string()),
             api_key_id: Some("key-456".to_string()),
+            team_id: None,
             ip_address: Some("192.168.1.1".to_string()),
             user_agent: Some("Mozilla/5.0".to_string()),
         };
@@ -374,6 +694,7 @@ mod tests {
         let response = ExecutorResponse {
             user_id: None,
             api_key_id: Some("key-789".to_string()),
+            team_id: None,
             ip_address: None,
             user_agent: None,
         };
@@ -404,11 +725,16 @@ mod tests {
             executor: ExecutorResponse {
                 user_id: Some("user-1".to_string()),
                 api_key_id: None,
+                team_id: None,
                 ip_address: None,
                 user_agent: None,
             },
             created_at: "2024-01-01T00:00:00Z".to_string(),
             workflow_steps: None,
+            injection: None,
+            streaming: None,
+            shadow: None,
+            request_id: None,
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -436,11 +762,16 @@ mod tests {
             executor: ExecutorResponse {
                 user_id: None,
                 api_key_id: Some("key-test".to_string()),
+                team_id: None,
                 ip_address: Some("10.0.0.1".to_string()),
                 user_agent: None,
             },
             created_at: "2024-01-01T00:00:00Z".to_string(),
             workflow_steps: None,
+            injection: None,
+            streaming: None,
+            shadow: None,
+            request_id: None,
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -468,11 +799,16 @@ mod tests {
                     executor: ExecutorResponse {
                         user_id: None,
                         api_key_id: None,
+                        team_id: None,
                         ip_address: None,
                         user_agent: None,
                     },
                     created_at: "2024-01-01T00:00:00Z".to_string(),
                     workflow_steps: None,
+                    injection: None,
+                    streaming: None,
+                    shadow: None,
+                    request_id: None,
                 },
             ],
             total: 50,
@@ -573,6 +909,12 @@ mod tests {
             status: None,
             api_key_id: None,
             user_id: None,
+            team_id: None,
+            payload_captured: None,
+            injection_detected: None,
+            stream_aborted: None,
+            tags: None,
+            request_id: None,
             from_date: None,
             to_date: None,
             limit: None,
@@ -591,6 +933,12 @@ mod tests {
             status: None,
             api_key_id: None,
             user_id: None,
+            team_id: None,
+            payload_captured: None,
+            injection_detected: None,
+            stream_aborted: None,
+            tags: None,
+            request_id: None,
             from_date: None,
             to_date: None,
             limit: None,
@@ -609,6 +957,12 @@ mod tests {
             status: None,
             api_key_id: None,
             user_id: None,
+            team_id: None,
+            payload_captured: None,
+            injection_detected: None,
+            stream_aborted: None,
+            tags: None,
+            request_id: None,
             from_date: None,
             to_date: None,
             limit: None,
@@ -627,6 +981,12 @@ mod tests {
             status: None,
             api_key_id: None,
             user_id: None,
+            team_id: None,
+            payload_captured: None,
+            injection_detected: None,
+            stream_aborted: None,
+            tags: None,
+            request_id: None,
             from_date: None,
             to_date: None,
             limit: None,
@@ -645,6 +1005,12 @@ mod tests {
             status: Some("success".to_string()),
             api_key_id: None,
             user_id: None,
+            team_id: None,
+            payload_captured: None,
+            injection_detected: None,
+            stream_aborted: None,
+            tags: None,
+            request_id: None,
             from_date: None,
             to_date: None,
             limit: None,
@@ -663,6 +1029,12 @@ mod tests {
             status: Some("failed".to_string()),
             api_key_id: None,
             user_id: None,
+            team_id: None,
+            payload_captured: None,
+            injection_detected: None,
+            stream_aborted: None,
+            tags: None,
+            request_id: None,
             from_date: None,
             to_date: None,
             limit: None,
@@ -681,6 +1053,12 @@ mod tests {
             status: Some("timeout".to_string()),
             api_key_id: None,
             user_id: None,
+            team_id: None,
+            payload_captured: None,
+            injection_detected: None,
+            stream_aborted: None,
+            tags: None,
+            request_id: None,
             from_date: None,
             to_date: None,
             limit: None,
@@ -699,6 +1077,12 @@ mod tests {
             status: Some("cancelled".to_string()),
             api_key_id: None,
             user_id: None,
+            team_id: None,
+            payload_captured: None,
+            injection_detected: None,
+            stream_aborted: None,
+            tags: None,
+            request_id: None,
             from_date: None,
             to_date: None,
             limit: None,
@@ -717,6 +1101,12 @@ mod tests {
             status: Some("unknown".to_string()),
             api_key_id: None,
             user_id: None,
+            team_id: None,
+            payload_captured: None,
+            injection_detected: None,
+            stream_aborted: None,
+            tags: None,
+            request_id: None,
             from_date: None,
             to_date: None,
             limit: None,
@@ -735,6 +1125,12 @@ mod tests {
             status: None,
             api_key_id: None,
             user_id: None,
+            team_id: None,
+            payload_captured: None,
+            injection_detected: None,
+            stream_aborted: None,
+            tags: None,
+            request_id: None,
             from_date: Some("2024-01-01T00:00:00Z".to_string()),
             to_date: Some("2024-01-31T23:59:59Z".to_string()),
             limit: None,
@@ -753,6 +1149,12 @@ mod tests {
             status: None,
             api_key_id: None,
             user_id: None,
+            team_id: None,
+            payload_captured: None,
+            injection_detected: None,
+            stream_aborted: None,
+            tags: None,
+            request_id: None,
             from_date: Some("not-a-date".to_string()),
             to_date: Some("2024-01-31T23:59:59Z".to_string()),
             limit: None,
@@ -771,6 +1173,12 @@ mod tests {
             status: None,
             api_key_id: None,
             user_id: None,
+            team_id: None,
+            payload_captured: None,
+            injection_detected: None,
+            stream_aborted: None,
+            tags: None,
+            request_id: None,
             from_date: None,
             to_date: None,
             limit: Some(50),
@@ -781,6 +1189,49 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_stream_query_to_domain_query() {
+        let query: StreamExecutionLogsQuery =
+            serde_json::from_str(r#"{"workflow_id": "triage", "status": "failed", "team_id": "team-1"}"#)
+                .unwrap();
+        let domain_query = query.to_domain_query().unwrap();
+
+        assert_eq!(domain_query.execution_type, Some(ExecutionType::Workflow));
+        assert_eq!(domain_query.resource_id, Some("triage".to_string()));
+        assert_eq!(domain_query.status, Some(ExecutionStatus::Failed));
+        assert_eq!(domain_query.team_id, Some("team-1".to_string()));
+        assert!(domain_query.limit.is_none());
+
+        let query: StreamExecutionLogsQuery =
+            serde_json::from_str(r#"{"status": "exploded"}"#).unwrap();
+        assert!(query.to_domain_query().is_err());
+    }
+
+    #[test]
+    fn test_training_data_query_to_domain_query() {
+        let query: ExportTrainingDataQuery = serde_json::from_str(
+            r#"{"model": "gpt-4o", "tags": "feature=support", "limit": 500}"#,
+        )
+        .unwrap();
+        let domain_query = query.to_domain_query().unwrap();
+
+        assert_eq!(
+            domain_query.execution_type,
+            Some(ExecutionType::ChatCompletion)
+        );
+        assert_eq!(domain_query.resource_id, Some("gpt-4o".to_string()));
+        assert_eq!(domain_query.status, Some(ExecutionStatus::Success));
+        assert_eq!(
+            domain_query.tags.get("feature").map(String::as_str),
+            Some("support")
+        );
+        assert_eq!(domain_query.limit, Some(500));
+
+        let query: ExportTrainingDataQuery =
+            serde_json::from_str(r#"{"model": "gpt-4o", "workflow_id": "triage"}"#).unwrap();
+        assert!(query.to_domain_query().is_err());
+    }
+
     #[test]
     fn test_cleanup_request_deserialization() {
         let json = r#"{"days": 30}"#;
@@ -3,13 +3,16 @@
 use axum::extract::{Path, Query, State};
 use serde::{Deserialize, Serialize};
 use tracing::debug;
+use utoipa::{IntoParams, ToSchema};
 
 use crate::api::middleware::RequireAdmin;
 use crate::api::state::AppState;
 use crate::api::types::{ApiError, Json};
+use crate::domain::dataset::{DatasetRef, DatasetSelector};
 use crate::domain::experiment::{
-    Experiment, ExperimentQuery, ExperimentResult, ExperimentStatus, LatencyStats,
-    StatisticalSignificance, VariantConfig, VariantMetrics,
+    AssignmentKey, AutoStop, Experiment, ExperimentQuery, ExperimentResult, ExperimentStatus,
+    LatencyStats, Rollback, RolloutGuard, SequentialTest, StatisticalSignificance, StepOverride,
+    VariantConfig, VariantMetrics,
 };
 use crate::infrastructure::services::{
     CreateExperimentRequest, CreateVariantRequest, UpdateExperimentRequest,
@@ -20,7 +23,7 @@ use crate::infrastructure::services::{
 // ============================================================================
 
 /// Request to create a new experiment
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct CreateExperimentApiRequest {
     pub id: String,
     pub name: String,
@@ -30,19 +33,48 @@ pub struct CreateExperimentApiRequest {
     pub variants: Vec<CreateVariantApiRequest>,
     #[serde(default)]
     pub traffic_allocation: Vec<TrafficAllocationRequest>,
+    /// Hash requests by `api_key` (default) or end `user`
+    #[serde(default)]
+    pub assignment_key: AssignmentKey,
+    /// Complete the experiment once a sequential test reaches a decision
+    #[serde(default)]
+    pub auto_stop: Option<AutoStop>,
+    /// Roll the treatments back once one breaches a threshold against the
+    /// control
+    #[serde(default)]
+    pub rollout: Option<RolloutGuard>,
+    /// Evaluation dataset, pinned to the latest version when none is given
+    #[serde(default)]
+    pub dataset: Option<DatasetSelector>,
 }
 
 /// Request to update an experiment
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct UpdateExperimentApiRequest {
     pub name: Option<String>,
     pub description: Option<String>,
     pub traffic_allocation: Option<Vec<TrafficAllocationRequest>>,
+    pub assignment_key: Option<AssignmentKey>,
+    /// Enable or reconfigure sequential testing
+    pub auto_stop: Option<AutoStop>,
+    /// Disable sequential testing
+    #[serde(default)]
+    pub disable_auto_stop: bool,
+    /// Guard the treatments as a canary rollout, or change the thresholds
+    pub rollout: Option<RolloutGuard>,
+    /// Stop guarding the treatments
+    #[serde(default)]
+    pub disable_rollout: bool,
+    /// Pin another evaluation dataset or version
+    pub dataset: Option<DatasetSelector>,
+    /// Detach the evaluation dataset
+    #[serde(default)]
+    pub remove_dataset: bool,
     pub enabled: Option<bool>,
 }
 
 /// Request to create a variant
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct CreateVariantApiRequest {
     pub id: String,
     pub name: String,
@@ -54,13 +86,19 @@ pub struct CreateVariantApiRequest {
 }
 
 /// Variant configuration request
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum VariantConfigRequest {
     ModelReference { model_id: String },
     ConfigOverride {
         model_id: String,
         #[serde(default)]
+        prompt_id: Option<String>,
+        /// Retained version of `prompt_id` to render, the current one when
+        /// unset
+        #[serde(default)]
+        prompt_version: Option<u32>,
+        #[serde(default)]
         temperature: Option<f32>,
         #[serde(default)]
         max_tokens: Option<u32>,
@@ -71,17 +109,25 @@ pub enum VariantConfigRequest {
         #[serde(default)]
         frequency_penalty: Option<f32>,
     },
+    /// Run a workflow, with optional step overrides, when the experiment's
+    /// workflows are executed
+    Workflow {
+        workflow_id: String,
+        #[serde(default)]
+        step_overrides: Vec<StepOverride>,
+    },
 }
 
 /// Traffic allocation request
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct TrafficAllocationRequest {
     pub variant_id: String,
     pub percentage: u8,
 }
 
 /// Query parameters for listing experiments
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListExperimentsQuery {
     pub status: Option<String>,
     pub model_id: Option<String>,
@@ -94,7 +140,7 @@ pub struct ListExperimentsQuery {
 // ============================================================================
 
 /// Experiment response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ExperimentResponse {
     pub id: String,
     pub name: String,
@@ -102,6 +148,16 @@ pub struct ExperimentResponse {
     pub status: String,
     pub variants: Vec<VariantResponse>,
     pub traffic_allocation: Vec<TrafficAllocationResponse>,
+    pub assignment_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_stop: Option<AutoStop>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollout: Option<RolloutGuard>,
+    /// Why the rollout was rolled back
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollback: Option<Rollback>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dataset: Option<DatasetRef>,
     pub started_at: Option<String>,
     pub completed_at: Option<String>,
     pub created_at: String,
@@ -110,7 +166,7 @@ pub struct ExperimentResponse {
 }
 
 /// Variant response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct VariantResponse {
     pub id: String,
     pub name: String,
@@ -120,13 +176,17 @@ pub struct VariantResponse {
 }
 
 /// Variant configuration response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum VariantConfigResponse {
     ModelReference { model_id: String },
     ConfigOverride {
         model_id: String,
         #[serde(skip_serializing_if = "Option::is_none")]
+        prompt_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        prompt_version: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         temperature: Option<f32>,
         #[serde(skip_serializing_if = "Option::is_none")]
         max_tokens: Option<u32>,
@@ -137,24 +197,29 @@ pub enum VariantConfigResponse {
         #[serde(skip_serializing_if = "Option::is_none")]
         frequency_penalty: Option<f32>,
     },
+    Workflow {
+        workflow_id: String,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        step_overrides: Vec<StepOverride>,
+    },
 }
 
 /// Traffic allocation response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct TrafficAllocationResponse {
     pub variant_id: String,
     pub percentage: u8,
 }
 
 /// List experiments response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListExperimentsResponse {
     pub experiments: Vec<ExperimentResponse>,
     pub total: usize,
 }
 
 /// Experiment results response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ExperimentResultsResponse {
     pub experiment_id: String,
     pub experiment_name: String,
@@ -163,12 +228,16 @@ pub struct ExperimentResultsResponse {
     pub total_requests: u64,
     pub variant_metrics: Vec<VariantMetricsResponse>,
     pub significance_tests: Vec<SignificanceResponse>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sequential_tests: Vec<SequentialTest>,
     pub winner_variant_id: Option<String>,
     pub recommendation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dataset: Option<DatasetRef>,
 }
 
 /// Variant metrics response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct VariantMetricsResponse {
     pub variant_id: String,
     pub variant_name: String,
@@ -182,10 +251,18 @@ pub struct VariantMetricsResponse {
     pub total_cost_micros: i64,
     pub avg_cost_micros: f64,
     pub latency: LatencyStatsResponse,
+    pub thumbs_up: u64,
+    pub thumbs_down: u64,
+    pub thumbs_up_rate: Option<f64>,
+    pub ratings: u64,
+    pub avg_rating: Option<f64>,
+    pub conversions: u64,
+    pub conversion_rate: f64,
+    pub conversion_value: f64,
 }
 
 /// Latency stats response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct LatencyStatsResponse {
     pub avg_ms: f64,
     pub min_ms: u64,
@@ -196,7 +273,7 @@ pub struct LatencyStatsResponse {
 }
 
 /// Statistical significance response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct SignificanceResponse {
     pub metric: String,
     pub control_variant_id: String,
@@ -243,6 +320,8 @@ impl From<&VariantConfig> for VariantConfigResponse {
             },
             VariantConfig::ConfigOverride {
                 model_id,
+                prompt_id,
+                prompt_version,
                 temperature,
                 max_tokens,
                 top_p,
@@ -250,12 +329,21 @@ impl From<&VariantConfig> for VariantConfigResponse {
                 frequency_penalty,
             } => VariantConfigResponse::ConfigOverride {
                 model_id: model_id.clone(),
+                prompt_id: prompt_id.clone(),
+                prompt_version: *prompt_version,
                 temperature: *temperature,
                 max_tokens: *max_tokens,
                 top_p: *top_p,
                 presence_penalty: *presence_penalty,
                 frequency_penalty: *frequency_penalty,
             },
+            VariantConfig::Workflow {
+                workflow_id,
+                step_overrides,
+            } => VariantConfigResponse::Workflow {
+                workflow_id: workflow_id.clone(),
+                step_overrides: step_overrides.clone(),
+            },
         }
     }
 }
@@ -286,6 +374,11 @@ impl From<&Experiment> for ExperimentResponse {
                     percentage: t.percentage(),
                 })
                 .collect(),
+            assignment_key: experiment.assignment_key().to_string(),
+            auto_stop: experiment.auto_stop().cloned(),
+            rollout: experiment.rollout().cloned(),
+            rollback: experiment.rollback().cloned(),
+            dataset: experiment.dataset().cloned(),
             started_at: experiment.started_at().map(|t| t.to_rfc3339()),
             completed_at: experiment.completed_at().map(|t| t.to_rfc3339()),
             created_at: experiment.created_at().to_rfc3339(),
@@ -323,6 +416,14 @@ impl From<&VariantMetrics> for VariantMetricsResponse {
             total_cost_micros: metrics.total_cost_micros,
             avg_cost_micros: metrics.avg_cost_micros,
             latency: LatencyStatsResponse::from(&metrics.latency),
+            thumbs_up: metrics.thumbs_up,
+            thumbs_down: metrics.thumbs_down,
+            thumbs_up_rate: metrics.thumbs_up_rate,
+            ratings: metrics.ratings,
+            avg_rating: metrics.avg_rating,
+            conversions: metrics.conversions,
+            conversion_rate: metrics.conversion_rate,
+            conversion_value: metrics.conversion_value,
         }
     }
 }
@@ -361,8 +462,10 @@ impl From<&ExperimentResult> for ExperimentResultsResponse {
                 .iter()
                 .map(SignificanceResponse::from)
                 .collect(),
+            sequential_tests: result.sequential_tests.clone(),
             winner_variant_id: result.winner_variant_id.clone(),
             recommendation: result.recommendation.clone(),
+            dataset: result.dataset.clone(),
         }
     }
 }
@@ -374,6 +477,8 @@ fn build_variant_config(request: &VariantConfigRequest) -> VariantConfig {
         },
         VariantConfigRequest::ConfigOverride {
             model_id,
+            prompt_id,
+            prompt_version,
             temperature,
             max_tokens,
             top_p,
@@ -381,12 +486,21 @@ fn build_variant_config(request: &VariantConfigRequest) -> VariantConfig {
             frequency_penalty,
         } => VariantConfig::ConfigOverride {
             model_id: model_id.clone(),
+            prompt_id: prompt_id.clone(),
+            prompt_version: *prompt_version,
             temperature: *temperature,
             max_tokens: *max_tokens,
             top_p: *top_p,
             presence_penalty: *presence_penalty,
             frequency_penalty: *frequency_penalty,
         },
+        VariantConfigRequest::Workflow {
+            workflow_id,
+            step_overrides,
+        } => VariantConfig::Workflow {
+            workflow_id: workflow_id.clone(),
+            step_overrides: step_overrides.clone(),
+        },
     }
 }
 
@@ -394,7 +508,14 @@ fn build_variant_config(request: &VariantConfigRequest) -> VariantConfig {
 // Handlers
 // ============================================================================
 
-/// GET /admin/experiments
+/// List experiments
+#[utoipa::path(
+    get,
+    path = "/admin/experiments",
+    tag = "admin/experiments",
+    params(ListExperimentsQuery),
+    responses((status = 200, body = ListExperimentsResponse)),
+)]
 pub async fn list_experiments(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -437,7 +558,14 @@ pub async fn list_experiments(
     }))
 }
 
-/// POST /admin/experiments
+/// Create experiment
+#[utoipa::path(
+    post,
+    path = "/admin/experiments",
+    tag = "admin/experiments",
+    request_body = CreateExperimentApiRequest,
+    responses((status = 200, body = ExperimentResponse)),
+)]
 pub async fn create_experiment(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -469,6 +597,10 @@ pub async fn create_experiment(
         description: request.description,
         variants,
         traffic_allocation,
+        assignment_key: request.assignment_key,
+        auto_stop: request.auto_stop,
+        rollout: request.rollout,
+        dataset: request.dataset,
         enabled: true,
     };
 
@@ -481,7 +613,14 @@ pub async fn create_experiment(
     Ok(Json(ExperimentResponse::from(&experiment)))
 }
 
-/// GET /admin/experiments/:id
+/// Get experiment
+#[utoipa::path(
+    get,
+    path = "/admin/experiments/{experiment_id}",
+    tag = "admin/experiments",
+    params(("experiment_id" = String, Path, description = "Experiment ID")),
+    responses((status = 200, body = ExperimentResponse)),
+)]
 pub async fn get_experiment(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -501,7 +640,15 @@ pub async fn get_experiment(
     Ok(Json(ExperimentResponse::from(&experiment)))
 }
 
-/// PUT /admin/experiments/:id
+/// Update experiment
+#[utoipa::path(
+    put,
+    path = "/admin/experiments/{experiment_id}",
+    tag = "admin/experiments",
+    params(("experiment_id" = String, Path, description = "Experiment ID")),
+    request_body = UpdateExperimentApiRequest,
+    responses((status = 200, body = ExperimentResponse)),
+)]
 pub async fn update_experiment(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -522,6 +669,22 @@ pub async fn update_experiment(
         description: request.description.map(Some),
         variants: None,
         traffic_allocation,
+        assignment_key: request.assignment_key,
+        auto_stop: if request.disable_auto_stop {
+            Some(None)
+        } else {
+            request.auto_stop.map(Some)
+        },
+        rollout: if request.disable_rollout {
+            Some(None)
+        } else {
+            request.rollout.map(Some)
+        },
+        dataset: if request.remove_dataset {
+            Some(None)
+        } else {
+            request.dataset.map(Some)
+        },
         enabled: request.enabled,
     };
 
@@ -534,7 +697,14 @@ pub async fn update_experiment(
     Ok(Json(ExperimentResponse::from(&experiment)))
 }
 
-/// DELETE /admin/experiments/:id
+/// Delete experiment
+#[utoipa::path(
+    delete,
+    path = "/admin/experiments/{experiment_id}",
+    tag = "admin/experiments",
+    params(("experiment_id" = String, Path, description = "Experiment ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn delete_experiment(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -554,7 +724,15 @@ pub async fn delete_experiment(
     })))
 }
 
-/// POST /admin/experiments/:id/variants
+/// Add variant
+#[utoipa::path(
+    post,
+    path = "/admin/experiments/{experiment_id}/variants",
+    tag = "admin/experiments",
+    params(("experiment_id" = String, Path, description = "Experiment ID")),
+    request_body = CreateVariantApiRequest,
+    responses((status = 200, body = ExperimentResponse)),
+)]
 pub async fn add_variant(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -584,7 +762,17 @@ pub async fn add_variant(
     Ok(Json(ExperimentResponse::from(&experiment)))
 }
 
-/// DELETE /admin/experiments/:id/variants/:variant_id
+/// Remove variant
+#[utoipa::path(
+    delete,
+    path = "/admin/experiments/{experiment_id}/variants/{variant_id}",
+    tag = "admin/experiments",
+    params(
+        ("experiment_id" = String, Path, description = "Experiment ID"),
+        ("variant_id" = String, Path, description = "Variant ID"),
+    ),
+    responses((status = 200, body = ExperimentResponse)),
+)]
 pub async fn remove_variant(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -605,7 +793,14 @@ pub async fn remove_variant(
     Ok(Json(ExperimentResponse::from(&experiment)))
 }
 
-/// POST /admin/experiments/:id/start
+/// Start experiment
+#[utoipa::path(
+    post,
+    path = "/admin/experiments/{experiment_id}/start",
+    tag = "admin/experiments",
+    params(("experiment_id" = String, Path, description = "Experiment ID")),
+    responses((status = 200, body = ExperimentResponse)),
+)]
 pub async fn start_experiment(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -622,7 +817,14 @@ pub async fn start_experiment(
     Ok(Json(ExperimentResponse::from(&experiment)))
 }
 
-/// POST /admin/experiments/:id/pause
+/// Pause experiment
+#[utoipa::path(
+    post,
+    path = "/admin/experiments/{experiment_id}/pause",
+    tag = "admin/experiments",
+    params(("experiment_id" = String, Path, description = "Experiment ID")),
+    responses((status = 200, body = ExperimentResponse)),
+)]
 pub async fn pause_experiment(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -639,7 +841,14 @@ pub async fn pause_experiment(
     Ok(Json(ExperimentResponse::from(&experiment)))
 }
 
-/// POST /admin/experiments/:id/resume
+/// Resume experiment
+#[utoipa::path(
+    post,
+    path = "/admin/experiments/{experiment_id}/resume",
+    tag = "admin/experiments",
+    params(("experiment_id" = String, Path, description = "Experiment ID")),
+    responses((status = 200, body = ExperimentResponse)),
+)]
 pub async fn resume_experiment(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -656,7 +865,14 @@ pub async fn resume_experiment(
     Ok(Json(ExperimentResponse::from(&experiment)))
 }
 
-/// POST /admin/experiments/:id/complete
+/// Complete experiment
+#[utoipa::path(
+    post,
+    path = "/admin/experiments/{experiment_id}/complete",
+    tag = "admin/experiments",
+    params(("experiment_id" = String, Path, description = "Experiment ID")),
+    responses((status = 200, body = ExperimentResponse)),
+)]
 pub async fn complete_experiment(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -673,7 +889,14 @@ pub async fn complete_experiment(
     Ok(Json(ExperimentResponse::from(&experiment)))
 }
 
-/// GET /admin/experiments/:id/results
+/// Get experiment results
+#[utoipa::path(
+    get,
+    path = "/admin/experiments/{experiment_id}/results",
+    tag = "admin/experiments",
+    params(("experiment_id" = String, Path, description = "Experiment ID")),
+    responses((status = 200, body = ExperimentResultsResponse)),
+)]
 pub async fn get_experiment_results(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -809,6 +1032,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_variant_config_workflow() {
+        let json = r#"{
+            "type": "workflow",
+            "workflow_id": "rag-crag",
+            "step_overrides": [{"step": "score", "skip": true}]
+        }"#;
+
+        let request: VariantConfigRequest = serde_json::from_str(json).unwrap();
+        let config = build_variant_config(&request);
+
+        assert_eq!(config.workflow_id(), Some("rag-crag"));
+        assert_eq!(config.step_overrides(), &[StepOverride::skip("score")]);
+    }
+
     #[test]
     fn test_variant_config_model_reference() {
         let json = r#"{
@@ -892,6 +1130,8 @@ mod tests {
     fn test_build_variant_config_override() {
         let request = VariantConfigRequest::ConfigOverride {
             model_id: "gpt-4".to_string(),
+            prompt_id: Some("support-v2".to_string()),
+            prompt_version: Some(2),
             temperature: Some(0.8),
             max_tokens: Some(1000),
             top_p: None,
@@ -901,8 +1141,18 @@ mod tests {
 
         let config = build_variant_config(&request);
 
-        if let VariantConfig::ConfigOverride { model_id, temperature, max_tokens, .. } = config {
+        assert_eq!(config.prompt_id(), Some("support-v2"));
+
+        if let VariantConfig::ConfigOverride {
+            model_id,
+            prompt_version,
+            temperature,
+            max_tokens,
+            ..
+        } = config
+        {
             assert_eq!(model_id, "gpt-4");
+            assert_eq!(prompt_version, Some(2));
             assert_eq!(temperature, Some(0.8));
             assert_eq!(max_tokens, Some(1000));
         } else {
@@ -929,6 +1179,8 @@ mod tests {
     fn test_variant_config_response_from_override() {
         let config = VariantConfig::ConfigOverride {
             model_id: "gpt-4".to_string(),
+            prompt_id: None,
+            prompt_version: None,
             temperature: Some(0.7),
             max_tokens: Some(500),
             top_p: None,
@@ -976,6 +1228,11 @@ mod tests {
             status: "active".to_string(),
             variants: vec![],
             traffic_allocation: vec![],
+            assignment_key: "api_key".to_string(),
+            auto_stop: None,
+            rollout: None,
+            rollback: None,
+            dataset: None,
             started_at: None,
             completed_at: None,
             created_at: "2024-01-01T00:00:00Z".to_string(),
@@ -1061,14 +1318,17 @@ mod tests {
             total_requests: 1000,
             variant_metrics: vec![],
             significance_tests: vec![],
+            sequential_tests: vec![],
             winner_variant_id: Some("treatment".to_string()),
             recommendation: Some("Deploy treatment variant".to_string()),
+            dataset: Some(DatasetRef::new("faq", 3)),
         };
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"experiment_id\":\"exp-1\""));
         assert!(json.contains("\"total_requests\":1000"));
         assert!(json.contains("\"winner_variant_id\":\"treatment\""));
+        assert!(json.contains("\"dataset\":{\"dataset_id\":\"faq\",\"version\":3}"));
     }
 
     #[test]
@@ -1093,11 +1353,20 @@ mod tests {
                 p95_ms: 300,
                 p99_ms: 450,
             },
+            thumbs_up: 40,
+            thumbs_down: 10,
+            thumbs_up_rate: Some(0.8),
+            ratings: 0,
+            avg_rating: None,
+            conversions: 25,
+            conversion_rate: 0.05,
+            conversion_value: 250.0,
         };
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"variant_id\":\"v1\""));
         assert!(json.contains("\"success_rate\":0.98"));
         assert!(json.contains("\"total_tokens\":15000"));
+        assert!(json.contains("\"thumbs_up_rate\":0.8"));
     }
 }
@@ -5,6 +5,7 @@ use std::collections::HashMap;
 use axum::extract::{Path, State};
 use serde::{Deserialize, Serialize};
 use tracing::debug;
+use utoipa::ToSchema;
 
 use crate::api::middleware::RequireAdmin;
 use crate::api::state::AppState;
@@ -12,7 +13,7 @@ use crate::api::types::{ApiError, Json};
 use crate::domain::ExternalApi;
 
 /// Request to create a new external API
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct CreateExternalApiRequest {
     pub id: String,
     pub name: String,
@@ -23,7 +24,7 @@ pub struct CreateExternalApiRequest {
 }
 
 /// Request to update an external API
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct UpdateExternalApiRequest {
     pub name: Option<String>,
     pub description: Option<Option<String>>,
@@ -33,7 +34,7 @@ pub struct UpdateExternalApiRequest {
 }
 
 /// External API response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ExternalApiResponse {
     pub id: String,
     pub name: String,
@@ -61,14 +62,19 @@ impl From<&ExternalApi> for ExternalApiResponse {
 }
 
 /// List external APIs response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListExternalApisResponse {
     pub external_apis: Vec<ExternalApiResponse>,
     pub total: usize,
 }
 
-/// GET /admin/external-apis
 /// List all external APIs
+#[utoipa::path(
+    get,
+    path = "/admin/external-apis",
+    tag = "admin/external-apis",
+    responses((status = 200, body = ListExternalApisResponse)),
+)]
 pub async fn list_external_apis(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -90,8 +96,14 @@ pub async fn list_external_apis(
     }))
 }
 
-/// POST /admin/external-apis
 /// Create a new external API
+#[utoipa::path(
+    post,
+    path = "/admin/external-apis",
+    tag = "admin/external-apis",
+    request_body = CreateExternalApiRequest,
+    responses((status = 200, body = ExternalApiResponse)),
+)]
 pub async fn create_external_api(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -116,8 +128,14 @@ pub async fn create_external_api(
     Ok(Json(ExternalApiResponse::from(&api)))
 }
 
-/// GET /admin/external-apis/:api_id
 /// Get a specific external API
+#[utoipa::path(
+    get,
+    path = "/admin/external-apis/{api_id}",
+    tag = "admin/external-apis",
+    params(("api_id" = String, Path, description = "Api ID")),
+    responses((status = 200, body = ExternalApiResponse)),
+)]
 pub async fn get_external_api(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -135,8 +153,15 @@ pub async fn get_external_api(
     Ok(Json(ExternalApiResponse::from(&api)))
 }
 
-/// PUT /admin/external-apis/:api_id
 /// Update an external API
+#[utoipa::path(
+    put,
+    path = "/admin/external-apis/{api_id}",
+    tag = "admin/external-apis",
+    params(("api_id" = String, Path, description = "Api ID")),
+    request_body = UpdateExternalApiRequest,
+    responses((status = 200, body = ExternalApiResponse)),
+)]
 pub async fn update_external_api(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -162,8 +187,14 @@ pub async fn update_external_api(
     Ok(Json(ExternalApiResponse::from(&api)))
 }
 
-/// DELETE /admin/external-apis/:api_id
 /// Delete an external API
+#[utoipa::path(
+    delete,
+    path = "/admin/external-apis/{api_id}",
+    tag = "admin/external-apis",
+    params(("api_id" = String, Path, description = "Api ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn delete_external_api(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -0,0 +1,223 @@
+//! Fine-tuning job admin endpoints
+
+use axum::extract::{Path, State};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+use utoipa::ToSchema;
+
+use super::models::credential_type_to_string;
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::dataset::DatasetSelector;
+use crate::domain::fine_tune::{FineTuneHyperparameters, FineTuneJob, FineTuneStatus};
+use crate::infrastructure::fine_tune::CreateFineTuneJobRequest;
+
+/// Request to start a fine-tuning job
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateFineTuneApiRequest {
+    /// OpenAI or Azure OpenAI credential the job runs with
+    pub credential_id: String,
+    /// Provider model to fine-tune, e.g. `gpt-4o-mini-2024-07-18`
+    pub base_model: String,
+    /// Dataset whose rows with an expected output are the training examples
+    pub dataset: DatasetSelector,
+    /// Gateway model registered when the job succeeds
+    pub model_id: String,
+    /// System message of every training example
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+    /// Suffix of the trained model name
+    #[serde(default)]
+    pub suffix: Option<String>,
+    #[serde(default)]
+    pub hyperparameters: FineTuneHyperparameters,
+}
+
+/// Fine-tuning job response for admin API
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FineTuneJobResponse {
+    pub id: String,
+    pub credential_id: String,
+    pub provider: String,
+    pub base_model: String,
+    pub dataset_id: String,
+    pub dataset_version: u32,
+    pub model_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
+    pub hyperparameters: FineTuneHyperparameters,
+    pub training_file_id: String,
+    pub provider_job_id: String,
+    pub status: FineTuneStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fine_tuned_model: Option<String>,
+    /// Whether the trained model is registered as `model_id`
+    pub model_registered: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trained_tokens: Option<u64>,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+}
+
+impl From<FineTuneJob> for FineTuneJobResponse {
+    fn from(job: FineTuneJob) -> Self {
+        Self {
+            id: job.id().to_string(),
+            credential_id: job.credential_id().to_string(),
+            provider: credential_type_to_string(job.provider()),
+            base_model: job.base_model().to_string(),
+            dataset_id: job.dataset().dataset_id.clone(),
+            dataset_version: job.dataset().version,
+            model_id: job.model_id().to_string(),
+            suffix: job.suffix().map(String::from),
+            hyperparameters: job.hyperparameters().clone(),
+            training_file_id: job.training_file_id().to_string(),
+            provider_job_id: job.provider_job_id().to_string(),
+            status: job.status(),
+            fine_tuned_model: job.fine_tuned_model().map(String::from),
+            model_registered: job.is_model_registered(),
+            error: job.error().map(String::from),
+            trained_tokens: job.trained_tokens(),
+            created_at: job.created_at().to_rfc3339(),
+            updated_at: job.updated_at().to_rfc3339(),
+            finished_at: job.finished_at().map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+/// List fine-tuning jobs response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListFineTuneJobsResponse {
+    pub jobs: Vec<FineTuneJobResponse>,
+    pub total: usize,
+}
+
+/// List fine-tuning jobs, newest first
+#[utoipa::path(
+    get,
+    path = "/admin/fine-tunes",
+    tag = "admin/fine-tunes",
+    responses((status = 200, body = ListFineTuneJobsResponse)),
+)]
+pub async fn list_fine_tunes(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+) -> Result<Json<ListFineTuneJobsResponse>, ApiError> {
+    debug!("Listing fine-tuning jobs");
+
+    let jobs: Vec<FineTuneJobResponse> = state
+        .fine_tune_service
+        .list()
+        .await?
+        .into_iter()
+        .map(FineTuneJobResponse::from)
+        .collect();
+
+    Ok(Json(ListFineTuneJobsResponse {
+        total: jobs.len(),
+        jobs,
+    }))
+}
+
+/// Get fine-tuning job
+#[utoipa::path(
+    get,
+    path = "/admin/fine-tunes/{job_id}",
+    tag = "admin/fine-tunes",
+    params(("job_id" = String, Path, description = "Fine-tuning job ID")),
+    responses((status = 200, body = FineTuneJobResponse)),
+)]
+pub async fn get_fine_tune(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+) -> Result<Json<FineTuneJobResponse>, ApiError> {
+    debug!(job_id = %id, "Getting fine-tuning job");
+
+    let job = state
+        .fine_tune_service
+        .get(&id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Fine-tuning job '{}' not found", id)))?;
+
+    Ok(Json(FineTuneJobResponse::from(job)))
+}
+
+/// Start a fine-tuning job
+#[utoipa::path(
+    post,
+    path = "/admin/fine-tunes",
+    tag = "admin/fine-tunes",
+    request_body = CreateFineTuneApiRequest,
+    responses((status = 200, body = FineTuneJobResponse)),
+)]
+pub async fn create_fine_tune(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Json(request): Json<CreateFineTuneApiRequest>,
+) -> Result<Json<FineTuneJobResponse>, ApiError> {
+    info!(
+        dataset_id = %request.dataset.dataset_id,
+        base_model = %request.base_model,
+        "Starting fine-tuning job"
+    );
+
+    let job = state
+        .fine_tune_service
+        .create(CreateFineTuneJobRequest {
+            credential_id: request.credential_id,
+            base_model: request.base_model,
+            dataset: request.dataset,
+            model_id: request.model_id,
+            system_prompt: request.system_prompt,
+            suffix: request.suffix,
+            hyperparameters: request.hyperparameters,
+        })
+        .await?;
+
+    Ok(Json(FineTuneJobResponse::from(job)))
+}
+
+/// Fetch the job status from the provider now, registering the trained model
+/// if the job succeeded
+#[utoipa::path(
+    post,
+    path = "/admin/fine-tunes/{job_id}/refresh",
+    tag = "admin/fine-tunes",
+    params(("job_id" = String, Path, description = "Fine-tuning job ID")),
+    responses((status = 200, body = FineTuneJobResponse)),
+)]
+pub async fn refresh_fine_tune(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+) -> Result<Json<FineTuneJobResponse>, ApiError> {
+    debug!(job_id = %id, "Refreshing fine-tuning job");
+
+    let job = state.fine_tune_service.refresh(&id).await?;
+    Ok(Json(FineTuneJobResponse::from(job)))
+}
+
+/// Cancel a running fine-tuning job
+#[utoipa::path(
+    post,
+    path = "/admin/fine-tunes/{job_id}/cancel",
+    tag = "admin/fine-tunes",
+    params(("job_id" = String, Path, description = "Fine-tuning job ID")),
+    responses((status = 200, body = FineTuneJobResponse)),
+)]
+pub async fn cancel_fine_tune(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+) -> Result<Json<FineTuneJobResponse>, ApiError> {
+    info!(job_id = %id, "Cancelling fine-tuning job");
+
+    let job = state.fine_tune_service.cancel(&id).await?;
+    Ok(Json(FineTuneJobResponse::from(job)))
+}
@@ -0,0 +1,207 @@
+//! Import admin endpoints
+//!
+//! Migrating from a LiteLLM proxy: its `config.yaml` is converted into
+//! credentials, models and budgets. Entities whose ID is already taken are
+//! left unchanged, so an import can be re-run after fixing a failure.
+
+use std::collections::HashMap;
+
+use axum::extract::State;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::litellm::LiteLlmConfig;
+use crate::infrastructure::credentials::CreateCredentialRequest;
+use crate::infrastructure::services::CreateModelRequest;
+
+// ============================================================================
+// Import DTOs
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LiteLlmImportRequest {
+    /// Contents of the LiteLLM proxy `config.yaml`
+    pub config: String,
+    /// Values of the variables the config references as `os.environ/NAME`
+    #[serde(default)]
+    pub environment: HashMap<String, String>,
+    /// Report what would be imported without creating anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    Created,
+    /// Would be created, in a dry run
+    Planned,
+    /// The ID is taken; the stored entity was left unchanged
+    Exists,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportedEntity {
+    pub id: String,
+    pub name: String,
+    pub status: ImportStatus,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LiteLlmImportResponse {
+    pub dry_run: bool,
+    pub credentials: Vec<ImportedEntity>,
+    pub models: Vec<ImportedEntity>,
+    pub budgets: Vec<ImportedEntity>,
+    /// Parts of the config that were skipped or changed on import
+    pub warnings: Vec<String>,
+}
+
+fn status(exists: bool, dry_run: bool) -> ImportStatus {
+    match (exists, dry_run) {
+        (true, _) => ImportStatus::Exists,
+        (false, true) => ImportStatus::Planned,
+        (false, false) => ImportStatus::Created,
+    }
+}
+
+/// Create the credentials, models and budgets of a LiteLLM config whose
+/// environment references are resolved. Shared by the endpoint and the
+/// `import-litellm` command.
+pub async fn import_litellm_config(
+    state: &AppState,
+    config: &LiteLlmConfig,
+    dry_run: bool,
+) -> Result<LiteLlmImportResponse, ApiError> {
+    let import = config.to_import();
+
+    let mut credentials = Vec::new();
+    for credential in import.credentials {
+        let status = status(
+            state
+                .credential_service
+                .get(&credential.id)
+                .await?
+                .is_some(),
+            dry_run,
+        );
+
+        if status == ImportStatus::Created {
+            state
+                .credential_service
+                .create(CreateCredentialRequest {
+                    id: credential.id.clone(),
+                    name: credential.name.clone(),
+                    credential_type: credential.credential_type,
+                    api_key: credential.api_key,
+                    endpoint: credential.endpoint,
+                    deployment: None,
+                    header_value: None,
+                    organization_id: None,
+                    region: credential.region,
+                })
+                .await?;
+        }
+
+        credentials.push(ImportedEntity {
+            id: credential.id,
+            name: credential.name,
+            status,
+        });
+    }
+
+    let mut models = Vec::new();
+    for model in import.models {
+        let status = status(state.model_service.get(&model.id).await?.is_some(), dry_run);
+
+        if status == ImportStatus::Created {
+            state
+                .model_service
+                .create(CreateModelRequest {
+                    id: model.id.clone(),
+                    name: model.name.clone(),
+                    description: Some("Imported from LiteLLM".to_string()),
+                    provider: model.provider,
+                    provider_model: model.provider_model,
+                    credential_id: model.credential_id,
+                    config: Some(model.config),
+                    enabled: true,
+                    lineage: None,
+                })
+                .await?;
+        }
+
+        models.push(ImportedEntity {
+            id: model.id,
+            name: model.name,
+            status,
+        });
+    }
+
+    let mut budgets = Vec::new();
+    for budget in import.budgets {
+        let id = budget.id().as_str().to_string();
+        let name = budget.name.clone();
+        let status = status(
+            state.budget_service.get(budget.id()).await?.is_some(),
+            dry_run,
+        );
+
+        if status == ImportStatus::Created {
+            state.budget_service.create(budget).await?;
+        }
+
+        budgets.push(ImportedEntity { id, name, status });
+    }
+
+    if !dry_run {
+        info!(
+            credentials = credentials.len(),
+            models = models.len(),
+            budgets = budgets.len(),
+            "Imported LiteLLM config"
+        );
+    }
+
+    Ok(LiteLlmImportResponse {
+        dry_run,
+        credentials,
+        models,
+        budgets,
+        warnings: import.warnings,
+    })
+}
+
+// ============================================================================
+// Import endpoints
+// ============================================================================
+
+/// Import the models, credentials and budgets of a LiteLLM proxy config
+#[utoipa::path(
+    post,
+    path = "/admin/import/litellm",
+    tag = "admin/import",
+    request_body = LiteLlmImportRequest,
+    responses((status = 200, body = LiteLlmImportResponse)),
+)]
+pub async fn import_litellm(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Json(request): Json<LiteLlmImportRequest>,
+) -> Result<Json<LiteLlmImportResponse>, ApiError> {
+    let mut config = LiteLlmConfig::from_yaml(&request.config)
+        .map_err(|e| ApiError::from(e).with_param("config"))?;
+
+    // Variables are never read from the gateway's own environment
+    config
+        .resolve_environment(|name| request.environment.get(name).cloned())
+        .map_err(|e| ApiError::from(e).with_param("environment"))?;
+
+    let response = import_litellm_config(&state, &config, request.dry_run).await?;
+
+    Ok(Json(response))
+}
@@ -0,0 +1,165 @@
+//! Team chargeback invoice admin endpoints
+
+use std::collections::BTreeMap;
+
+use axum::extract::{Path, State};
+use chrono::Utc;
+use serde::Serialize;
+use tracing::debug;
+use utoipa::ToSchema;
+
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::usage::{InvoiceLine, InvoiceMonth, TeamInvoice, UsageQuery};
+
+// ============================================================================
+// Invoice DTOs
+// ============================================================================
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InvoiceLineResponse {
+    pub key: String,
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+    pub markup_usd: f64,
+    pub total_usd: f64,
+}
+
+impl From<&InvoiceLine> for InvoiceLineResponse {
+    fn from(line: &InvoiceLine) -> Self {
+        Self {
+            key: line.key.clone(),
+            requests: line.requests,
+            input_tokens: line.input_tokens,
+            output_tokens: line.output_tokens,
+            cost_usd: micros_to_usd(line.cost_micros),
+            markup_usd: micros_to_usd(line.markup_micros),
+            total_usd: micros_to_usd(line.total_micros()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TeamInvoiceResponse {
+    pub invoice_number: String,
+    pub team_id: String,
+    pub team_name: String,
+    pub month: String,
+    pub period_start: u64,
+    pub period_end: u64,
+    /// `final` once the month is over, `preliminary` while usage can still change
+    pub status: String,
+    pub currency: String,
+    pub markup_percent: f64,
+    pub requests: u64,
+    pub subtotal_usd: f64,
+    pub markup_usd: f64,
+    pub total_usd: f64,
+    pub by_model: Vec<InvoiceLineResponse>,
+    pub by_api_key: Vec<InvoiceLineResponse>,
+    pub by_tag: BTreeMap<String, Vec<InvoiceLineResponse>>,
+    pub generated_at: String,
+}
+
+impl TeamInvoiceResponse {
+    fn new(invoice: &TeamInvoice, team_name: String) -> Self {
+        let now = Utc::now();
+        let period_end = invoice.month.end();
+        let status = if now.timestamp() as u64 >= period_end {
+            "final"
+        } else {
+            "preliminary"
+        };
+
+        Self {
+            invoice_number: invoice.number(),
+            team_id: invoice.team_id.clone(),
+            team_name,
+            month: invoice.month.to_string(),
+            period_start: invoice.month.start(),
+            period_end,
+            status: status.to_string(),
+            currency: "USD".to_string(),
+            markup_percent: invoice.markup_percent,
+            requests: invoice.total.requests,
+            subtotal_usd: micros_to_usd(invoice.total.cost_micros),
+            markup_usd: micros_to_usd(invoice.total.markup_micros),
+            total_usd: micros_to_usd(invoice.total.total_micros()),
+            by_model: invoice.by_model.iter().map(Into::into).collect(),
+            by_api_key: invoice.by_api_key.iter().map(Into::into).collect(),
+            by_tag: invoice
+                .by_tag
+                .iter()
+                .map(|(key, lines)| (key.clone(), lines.iter().map(Into::into).collect()))
+                .collect(),
+            generated_at: now.to_rfc3339(),
+        }
+    }
+}
+
+fn micros_to_usd(micros: i64) -> f64 {
+    micros as f64 / 1_000_000.0
+}
+
+// ============================================================================
+// Invoice Endpoints
+// ============================================================================
+
+/// Chargeback statement of a team's usage in a month (`YYYY-MM`)
+#[utoipa::path(
+    get,
+    path = "/admin/teams/{team_id}/invoices/{month}",
+    tag = "admin/teams",
+    params(
+        ("team_id" = String, Path, description = "Team ID"),
+        ("month" = String, Path, description = "Invoice month (YYYY-MM)"),
+    ),
+    responses((status = 200, body = TeamInvoiceResponse)),
+)]
+pub async fn get_team_invoice(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path((team_id, month)): Path<(String, String)>,
+) -> Result<Json<TeamInvoiceResponse>, ApiError> {
+    debug!(team_id = %team_id, month = %month, "Admin getting team invoice");
+
+    let month: InvoiceMonth = month
+        .parse()
+        .map_err(|e| ApiError::from(e).with_param("month"))?;
+
+    let team = state
+        .team_service
+        .get(&team_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Team '{}' not found", team_id)))?;
+
+    let api_key_ids: Vec<String> = state
+        .api_key_service
+        .list()
+        .await?
+        .iter()
+        .filter(|key| key.team_id() == team.id())
+        .map(|key| key.id().as_str().to_string())
+        .collect();
+
+    let mut records = Vec::new();
+
+    for api_key_id in api_key_ids {
+        let query = UsageQuery::new()
+            .with_api_key(api_key_id)
+            .with_time_range(month.start(), month.end());
+        records.extend(state.usage_service.query(&query).await?);
+    }
+
+    let invoice = TeamInvoice::build(
+        team.id().as_str(),
+        month,
+        &records,
+        &state.invoice_markup,
+    );
+
+    Ok(Json(TeamInvoiceResponse::new(&invoice, team.name().to_string())))
+}
@@ -1,37 +1,55 @@
 //! Knowledge Bases management admin endpoints
 
 use std::collections::HashMap;
+use std::convert::Infallible;
 
-use axum::extract::{Multipart, Path, State};
+use axum::extract::{Multipart, Path, Query, State};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures::{Stream, StreamExt};
+use tokio::sync::broadcast::error::RecvError;
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use tracing::debug;
+use tracing::{debug, warn};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-use crate::api::middleware::RequireAdmin;
+use crate::api::admin::teams::resolve_owner_team;
+use crate::api::middleware::{AdminAuth, RequireAdmin};
 use crate::api::state::AppState;
 use crate::api::types::{ApiError, Json};
-use crate::domain::ingestion::{ChunkingType, ParserType};
-use crate::domain::knowledge_base::{KnowledgeBaseConfig, KnowledgeBaseType};
+use crate::domain::ingestion::{
+    detect_parser_from_filename, ChunkEnrichmentConfig, ChunkingType, IngestionProgress,
+    IngestionProgressEvent, IngestionStage, ParserType,
+};
+use crate::domain::operation::OperationType;
+use crate::domain::{DistanceMetric, DomainError, Executor};
+use crate::infrastructure::background::spawn_tracked;
+use crate::infrastructure::ingestion::UploadSpool;
+use crate::infrastructure::knowledge_base::IngestionUpdate;
+use crate::domain::knowledge_base::{
+    validate_namespace, KnowledgeBaseAnalytics, KnowledgeBaseConfig, KnowledgeBaseType,
+    VectorIndexConfig, VectorIndexStatus,
+};
 use crate::infrastructure::services::{
     CreateKnowledgeBaseRequest, IngestDocumentRequest, IngestDocumentV2Request,
     UpdateKnowledgeBaseRequest,
 };
 
 /// Knowledge base type info response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct KnowledgeBaseTypeInfo {
     pub kb_type: String,
     pub description: String,
 }
 
 /// List knowledge base types response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListKnowledgeBaseTypesResponse {
     pub types: Vec<KnowledgeBaseTypeInfo>,
 }
 
 /// Request to create a new knowledge base
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct CreateKnowledgeBaseApiRequest {
     pub id: String,
     pub name: String,
@@ -42,8 +60,19 @@ pub struct CreateKnowledgeBaseApiRequest {
     pub credential_id: String,
     pub default_top_k: Option<u32>,
     pub default_similarity_threshold: Option<f32>,
+    /// `cosine` (default), `inner_product` or `l2`; inner product requires
+    /// an embedding model returning normalized vectors
+    #[serde(default)]
+    pub distance_metric: Option<DistanceMetric>,
+    /// Dedicated vector index type and parameters (pgvector); the shared
+    /// default index when unset
+    #[serde(default)]
+    pub vector_index: Option<VectorIndexConfig>,
     #[serde(default = "default_enabled")]
     pub enabled: bool,
+    /// Owning team; defaults to the team of the caller
+    #[serde(default)]
+    pub team_id: Option<String>,
 }
 
 fn default_enabled() -> bool {
@@ -51,17 +80,24 @@ fn default_enabled() -> bool {
 }
 
 /// Request to update a knowledge base
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct UpdateKnowledgeBaseApiRequest {
     pub name: Option<String>,
     pub description: Option<Option<String>>,
     pub default_top_k: Option<u32>,
     pub default_similarity_threshold: Option<f32>,
+    /// New distance metric; a dedicated vector index is rebuilt for it by
+    /// the next reindex
+    #[serde(default)]
+    pub distance_metric: Option<DistanceMetric>,
+    /// New vector index settings, applied by the next reindex
+    #[serde(default)]
+    pub vector_index: Option<VectorIndexConfig>,
     pub enabled: Option<bool>,
 }
 
 /// Knowledge base response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct KnowledgeBaseResponse {
     pub id: String,
     pub name: String,
@@ -72,13 +108,42 @@ pub struct KnowledgeBaseResponse {
     pub credential_id: Option<String>,
     pub default_top_k: u32,
     pub default_similarity_threshold: f32,
+    pub distance_metric: DistanceMetric,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vector_index: Option<VectorIndexConfig>,
     pub enabled: bool,
+    pub team_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+/// Request to clone a knowledge base
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CloneKnowledgeBaseRequest {
+    /// New ID for the cloned knowledge base
+    pub new_id: String,
+    /// New name for the cloned knowledge base (optional, defaults to "Copy of {original_name}")
+    #[serde(default)]
+    pub new_name: Option<String>,
+    /// Copy the documents too, in the background; otherwise only the
+    /// configuration is cloned
+    #[serde(default)]
+    pub include_documents: bool,
+}
+
+/// Cloned knowledge base, with the operation copying its documents
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CloneKnowledgeBaseResponse {
+    #[serde(flatten)]
+    pub knowledge_base: KnowledgeBaseResponse,
+    /// Ingestion operation copying the documents, listed under the
+    /// ingestions of the clone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document_copy_id: Option<String>,
+}
+
 /// List knowledge bases response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListKnowledgeBasesResponse {
     pub knowledge_bases: Vec<KnowledgeBaseResponse>,
     pub total: usize,
@@ -126,15 +191,23 @@ impl From<&crate::domain::KnowledgeBase> for KnowledgeBaseResponse {
             credential_id,
             default_top_k: kb.config().default_top_k,
             default_similarity_threshold: kb.config().default_similarity_threshold,
+            distance_metric: kb.config().distance_metric,
+            vector_index: kb.config().vector_index.clone(),
             enabled: kb.is_enabled(),
+            team_id: kb.team_id().map(|t| t.as_str().to_string()),
             created_at: kb.created_at().to_rfc3339(),
             updated_at: kb.updated_at().to_rfc3339(),
         }
     }
 }
 
-/// GET /admin/knowledge-bases/types
 /// Lists available knowledge base types
+#[utoipa::path(
+    get,
+    path = "/admin/knowledge-bases/types",
+    tag = "admin/knowledge-bases",
+    responses((status = 200, body = ListKnowledgeBaseTypesResponse)),
+)]
 pub async fn list_knowledge_base_types(
     State(_state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -159,8 +232,13 @@ pub async fn list_knowledge_base_types(
     Ok(Json(ListKnowledgeBaseTypesResponse { types }))
 }
 
-/// GET /admin/knowledge-bases
 /// List all knowledge bases
+#[utoipa::path(
+    get,
+    path = "/admin/knowledge-bases",
+    tag = "admin/knowledge-bases",
+    responses((status = 200, body = ListKnowledgeBasesResponse)),
+)]
 pub async fn list_knowledge_bases(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -183,16 +261,23 @@ pub async fn list_knowledge_bases(
     }))
 }
 
-/// POST /admin/knowledge-bases
 /// Create a new knowledge base
+#[utoipa::path(
+    post,
+    path = "/admin/knowledge-bases",
+    tag = "admin/knowledge-bases",
+    request_body = CreateKnowledgeBaseApiRequest,
+    responses((status = 200, body = KnowledgeBaseResponse)),
+)]
 pub async fn create_knowledge_base(
     State(state): State<AppState>,
-    RequireAdmin(_): RequireAdmin,
+    RequireAdmin(admin): RequireAdmin,
     Json(request): Json<CreateKnowledgeBaseApiRequest>,
 ) -> Result<Json<KnowledgeBaseResponse>, ApiError> {
     debug!(kb_id = %request.id, "Admin creating knowledge base");
 
     let kb_type = parse_kb_type(&request.kb_type)?;
+    let team_id = resolve_owner_team(&state, &admin, request.team_id.as_deref()).await?;
 
     // Verify credential exists and is a KB credential type
     let credential = state
@@ -215,9 +300,15 @@ pub async fn create_knowledge_base(
         )));
     }
 
-    let config = KnowledgeBaseConfig::new()
+    let mut config = KnowledgeBaseConfig::new()
         .with_default_top_k(request.default_top_k.unwrap_or(10))
         .with_default_similarity_threshold(request.default_similarity_threshold.unwrap_or(0.7));
+    if let Some(metric) = request.distance_metric {
+        config = config.with_distance_metric(metric);
+    }
+    if let Some(index) = request.vector_index {
+        config = config.with_vector_index(index);
+    }
 
     let create_request = CreateKnowledgeBaseRequest {
         id: request.id,
@@ -229,6 +320,7 @@ pub async fn create_knowledge_base(
         credential_id: request.credential_id,
         config: Some(config),
         enabled: request.enabled,
+        team_id: Some(team_id),
     };
 
     let kb = state
@@ -240,8 +332,14 @@ pub async fn create_knowledge_base(
     Ok(Json(KnowledgeBaseResponse::from(&kb)))
 }
 
-/// GET /admin/knowledge-bases/:kb_id
 /// Get a specific knowledge base
+#[utoipa::path(
+    get,
+    path = "/admin/knowledge-bases/{kb_id}",
+    tag = "admin/knowledge-bases",
+    params(("kb_id" = String, Path, description = "Knowledge base ID")),
+    responses((status = 200, body = KnowledgeBaseResponse)),
+)]
 pub async fn get_knowledge_base(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -259,8 +357,15 @@ pub async fn get_knowledge_base(
     Ok(Json(KnowledgeBaseResponse::from(&kb)))
 }
 
-/// PUT /admin/knowledge-bases/:kb_id
 /// Update a knowledge base
+#[utoipa::path(
+    put,
+    path = "/admin/knowledge-bases/{kb_id}",
+    tag = "admin/knowledge-bases",
+    params(("kb_id" = String, Path, description = "Knowledge base ID")),
+    request_body = UpdateKnowledgeBaseApiRequest,
+    responses((status = 200, body = KnowledgeBaseResponse)),
+)]
 pub async fn update_knowledge_base(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -270,7 +375,10 @@ pub async fn update_knowledge_base(
     debug!(kb_id = %kb_id, "Admin updating knowledge base");
 
     // Build config if any config fields provided
-    let config = if request.default_top_k.is_some() || request.default_similarity_threshold.is_some()
+    let config = if request.default_top_k.is_some()
+        || request.default_similarity_threshold.is_some()
+        || request.distance_metric.is_some()
+        || request.vector_index.is_some()
     {
         // Get existing KB to preserve config values
         let existing = state
@@ -290,6 +398,14 @@ pub async fn update_knowledge_base(
             config.default_similarity_threshold = threshold;
         }
 
+        if let Some(metric) = request.distance_metric {
+            config.distance_metric = metric;
+        }
+
+        if let Some(index) = request.vector_index {
+            config.vector_index = Some(index);
+        }
+
         Some(config)
     } else {
         None
@@ -311,8 +427,14 @@ pub async fn update_knowledge_base(
     Ok(Json(KnowledgeBaseResponse::from(&kb)))
 }
 
-/// DELETE /admin/knowledge-bases/:kb_id
 /// Delete a knowledge base
+#[utoipa::path(
+    delete,
+    path = "/admin/knowledge-bases/{kb_id}",
+    tag = "admin/knowledge-bases",
+    params(("kb_id" = String, Path, description = "Knowledge base ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn delete_knowledge_base(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -332,12 +454,133 @@ pub async fn delete_knowledge_base(
     })))
 }
 
+/// Clone a knowledge base with a new ID, optionally copying its documents
+#[utoipa::path(
+    post,
+    path = "/admin/knowledge-bases/{kb_id}/clone",
+    tag = "admin/knowledge-bases",
+    params(("kb_id" = String, Path, description = "Knowledge base ID")),
+    request_body = CloneKnowledgeBaseRequest,
+    responses((status = 200, body = CloneKnowledgeBaseResponse)),
+)]
+pub async fn clone_knowledge_base(
+    State(state): State<AppState>,
+    RequireAdmin(admin): RequireAdmin,
+    Path(kb_id): Path<String>,
+    Json(request): Json<CloneKnowledgeBaseRequest>,
+) -> Result<Json<CloneKnowledgeBaseResponse>, ApiError> {
+    debug!(kb_id = %kb_id, new_id = %request.new_id, "Admin cloning knowledge base");
+
+    let original = state
+        .knowledge_base_service
+        .get(&kb_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found(format!("Knowledge base '{}' not found", kb_id)))?;
+
+    let credential_id = original
+        .connection_config()
+        .and_then(|cc| cc.get("credential_id").cloned())
+        .unwrap_or_default();
+
+    let create_request = CreateKnowledgeBaseRequest {
+        id: request.new_id,
+        name: request
+            .new_name
+            .unwrap_or_else(|| format!("Copy of {}", original.name())),
+        description: original.description().map(String::from),
+        kb_type: original.kb_type().clone(),
+        embedding_model: original.embedding().model.clone(),
+        embedding_dimensions: original.embedding().dimensions,
+        credential_id,
+        config: Some(original.config().clone()),
+        enabled: true,
+        team_id: Some(
+            original
+                .team_id()
+                .cloned()
+                .unwrap_or_else(|| admin.team_id().clone()),
+        ),
+    };
+
+    let cloned = state
+        .knowledge_base_service
+        .create(create_request)
+        .await
+        .map_err(ApiError::from)?;
+
+    let clone_id = cloned.id().as_str().to_string();
+
+    let document_copy_id = if request.include_documents {
+        let input = serde_json::json!({
+            "kb_id": clone_id,
+            "source_kb_id": kb_id,
+        });
+
+        let mut log = state
+            .execution_log_service
+            .record_pending_ingestion(
+                &clone_id,
+                &format!("clone of {}", kb_id),
+                admin_executor(&admin),
+                input,
+            )
+            .await
+            .map_err(ApiError::from)?;
+
+        let log_id = log.id().as_str().to_string();
+        let ingestion_service = state.ingestion_service.clone();
+        let execution_log_service = state.execution_log_service.clone();
+
+        spawn_tracked(async move {
+            let start = std::time::Instant::now();
+
+            log.set_in_progress();
+            let _ = execution_log_service.update(&log).await;
+
+            let result = ingestion_service.copy_documents(&kb_id, &clone_id).await;
+            let execution_time_ms = start.elapsed().as_millis() as u64;
+
+            match result {
+                Ok(copied) => {
+                    log.set_success(execution_time_ms, serde_json::to_value(copied).ok());
+                }
+                Err(e) => {
+                    log.set_failed(execution_time_ms, e.to_string());
+                }
+            }
+
+            let _ = execution_log_service.update(&log).await;
+        });
+
+        Some(log_id)
+    } else {
+        None
+    };
+
+    Ok(Json(CloneKnowledgeBaseResponse {
+        knowledge_base: KnowledgeBaseResponse::from(&cloned),
+        document_copy_id,
+    }))
+}
+
+/// Executor recorded on the ingestion operations started by an admin
+fn admin_executor(admin: &AdminAuth) -> Executor {
+    match admin {
+        AdminAuth::User(user) => Executor::from_user(user.id().as_str()),
+        AdminAuth::ApiKey(api_key) => Executor::from_api_key(api_key.id().as_str()),
+        AdminAuth::ServiceAccount { account, .. } => {
+            Executor::from_service_account(account.id().as_str())
+        }
+    }
+}
+
 // ============================================================================
 // Document Ingestion Endpoints
 // ============================================================================
 
 /// Request to ingest a document into a knowledge base
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct IngestDocumentApiRequest {
     pub content: String,
     #[serde(default)]
@@ -357,7 +600,7 @@ pub struct IngestDocumentApiRequest {
 }
 
 /// Response from document ingestion
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct IngestDocumentResponse {
     pub document_id: String,
     pub chunks_created: usize,
@@ -366,7 +609,7 @@ pub struct IngestDocumentResponse {
 }
 
 /// A stored document
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct DocumentResponse {
     pub id: String,
     pub content: String,
@@ -377,21 +620,21 @@ pub struct DocumentResponse {
 }
 
 /// List documents response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListDocumentsResponse {
     pub documents: Vec<DocumentResponse>,
     pub total: usize,
 }
 
 /// A source entry (grouped documents)
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct SourceResponse {
     pub source: String,
     pub document_count: usize,
 }
 
 /// List sources response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListSourcesResponse {
     pub sources: Vec<SourceResponse>,
     pub total: usize,
@@ -424,22 +667,112 @@ fn parse_chunking_type(s: &str) -> Result<ChunkingType, ApiError> {
 }
 
 /// Response for batch file upload
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct BatchIngestResponse {
+    /// Files queued for ingestion
     pub total: usize,
+    /// Files rejected during the upload
+    pub failed: usize,
+    /// Operation recording the progress of every file
+    pub operation_id: String,
     pub log_ids: Vec<String>,
+    /// Outcome of each uploaded file, in upload order
+    pub files: Vec<BatchIngestFileResult>,
     pub message: String,
 }
 
-/// POST /admin/knowledge-bases/:kb_id/documents/upload
+/// Outcome of one file of a batch upload
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BatchIngestFileResult {
+    pub filename: String,
+    pub size_bytes: u64,
+    /// Execution log tracking the ingestion, when the file was queued
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_id: Option<String>,
+    /// Why the file was rejected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BatchIngestFileResult {
+    fn failed(filename: String, size_bytes: u64, error: impl Into<String>) -> Self {
+        Self {
+            filename,
+            size_bytes,
+            log_id: None,
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// Message of an upload error, without the error kind prefix
+fn upload_error_message(error: DomainError) -> String {
+    match error {
+        DomainError::Validation { message } | DomainError::Internal { message } => message,
+        other => other.to_string(),
+    }
+}
+
+/// Query parameters of the batch file upload
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BatchIngestQuery {
+    /// Namespace of the uploaded documents within the knowledge base
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// Comma-separated teams whose searches may return the documents
+    #[serde(default)]
+    pub allowed_teams: Option<String>,
+    /// Comma-separated roles whose searches may return the documents
+    #[serde(default)]
+    pub allowed_roles: Option<String>,
+}
+
+/// Values of a comma-separated query parameter
+fn comma_separated(value: Option<&str>) -> Vec<String> {
+    value
+        .map(|v| {
+            v.split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// Batch ingest files via multipart form upload
+///
+/// Files are streamed to temporary files rather than buffered in memory;
+/// files over the size limit, beyond the batch limit, empty or not UTF-8
+/// text are reported as failed in `files` while the others are ingested.
+#[utoipa::path(
+    post,
+    path = "/admin/knowledge-bases/{kb_id}/documents/upload",
+    tag = "admin/knowledge-bases",
+    params(
+        ("kb_id" = String, Path, description = "Knowledge base ID"),
+        BatchIngestQuery,
+    ),
+    request_body(content_type = "multipart/form-data", description = "Files to ingest"),
+    responses((status = 200, body = BatchIngestResponse)),
+)]
 pub async fn ingest_files_batch(
     State(state): State<AppState>,
     RequireAdmin(admin_claims): RequireAdmin,
     Path(kb_id): Path<String>,
+    Query(query): Query<BatchIngestQuery>,
     mut multipart: Multipart,
 ) -> Result<Json<BatchIngestResponse>, ApiError> {
-    debug!(kb_id = %kb_id, "Admin batch ingesting files via multipart upload");
+    debug!(
+        kb_id = %kb_id,
+        namespace = ?query.namespace,
+        "Admin batch ingesting files via multipart upload"
+    );
+
+    if let Some(namespace) = &query.namespace {
+        validate_namespace(namespace).map_err(|e| ApiError::bad_request(e.to_string()))?;
+    }
 
     // Verify knowledge base exists
     let kb_exists = state
@@ -455,115 +788,265 @@ pub async fn ingest_files_batch(
         )));
     }
 
-    // Create executor from admin auth
-    let executor = match &admin_claims {
-        crate::api::middleware::AdminAuth::User(user) => {
-            crate::domain::Executor::from_user(user.id().as_str())
-        }
-        crate::api::middleware::AdminAuth::ApiKey(api_key) => {
-            crate::domain::Executor::from_api_key(api_key.id().as_str())
-        }
-    };
+    // The batch runs as an operation recording the progress of each file
+    let operation = state
+        .operation_service
+        .create_pending(
+            OperationType::KnowledgeBaseIngestion,
+            serde_json::json!({ "kb_id": kb_id, "namespace": query.namespace }),
+            serde_json::json!({ "kb_id": kb_id }),
+        )
+        .await?;
+    let operation_id = operation.id().to_string();
+    state.ingestion_progress.start(&operation_id).await?;
+    let cancellation = state.operation_service.cancellation_token(&operation_id);
+
+    let executor = admin_executor(&admin_claims);
+    let limits = state.upload_limits.clone();
+    let mut files = Vec::new();
+    let mut log_ids = Vec::new();
+
+    let uploaded: Result<(), ApiError> = async {
+        while let Some(mut field) = multipart
+            .next_field()
+            .await
+            .map_err(|e| ApiError::bad_request(format!("Failed to read multipart field: {}", e)))?
+        {
+            let filename = field
+                .file_name()
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| format!("file-{}", uuid::Uuid::new_v4()));
+
+            // Unread fields are skipped by the next `next_field`
+            if cancellation.is_cancelled() || files.len() >= limits.max_files {
+                let error = if cancellation.is_cancelled() {
+                    "Operation cancelled".to_string()
+                } else {
+                    format!("Batch exceeds the limit of {} files", limits.max_files)
+                };
+                record_rejected(&state, &operation_id, &filename, &error).await;
+                files.push(BatchIngestFileResult::failed(filename, 0, error));
+                continue;
+            }
 
-    // Collect files from multipart form
-    let mut files: Vec<(String, String)> = Vec::new();
+            // PDFs and images are binary, their text comes from the parser or OCR
+            let binary = detect_parser_from_filename(&filename).is_some_and(|t| t.needs_ocr());
 
-    while let Some(field) = multipart
-        .next_field()
-        .await
-        .map_err(|e| ApiError::bad_request(format!("Failed to read multipart field: {}", e)))?
-    {
-        let filename = field
-            .file_name()
-            .map(|s| s.to_string())
-            .unwrap_or_else(|| format!("file-{}", uuid::Uuid::new_v4()));
+            // Write the file to disk as it arrives, stopping at the first error
+            let mut spool = UploadSpool::create(&limits).await.map_err(ApiError::from)?;
+            if binary {
+                spool = spool.binary();
+            }
+            let mut size_bytes = 0;
+            let mut spool_error = None;
+
+            while let Some(chunk) = field.chunk().await.map_err(|e| {
+                ApiError::bad_request(format!("Failed to read file '{}': {}", filename, e))
+            })? {
+                size_bytes += chunk.len() as u64;
+                if let Err(e) = spool.write(&chunk).await {
+                    spool_error = Some(e);
+                    break;
+                }
+            }
 
-        let content = field
-            .text()
-            .await
-            .map_err(|e| ApiError::bad_request(format!("Failed to read file '{}': {}", filename, e)))?;
+            let spooled = match spool_error {
+                Some(e) => Err(e),
+                None => spool.finish().await,
+            };
+            let spooled = match spooled {
+                Ok(spooled) => spooled,
+                Err(e) => {
+                    let error = upload_error_message(e);
+                    record_rejected(&state, &operation_id, &filename, &error).await;
+                    files.push(BatchIngestFileResult::failed(filename, size_bytes, error));
+                    continue;
+                }
+            };
+
+            // Create execution log
+            let input_json = serde_json::json!({
+                "kb_id": kb_id,
+                "source": filename,
+                "content_length": spooled.size(),
+                "parser_type": "auto",
+                "namespace": query.namespace,
+            });
+
+            let mut log = state
+                .execution_log_service
+                .record_pending_ingestion(&kb_id, &filename, executor.clone(), input_json)
+                .await
+                .map_err(ApiError::from)?;
+
+            let log_id = log.id().as_str().to_string();
+            log_ids.push(log_id.clone());
+            files.push(BatchIngestFileResult {
+                filename: filename.clone(),
+                size_bytes: spooled.size(),
+                log_id: Some(log_id),
+                error: None,
+            });
+            state
+                .ingestion_progress
+                .record(IngestionProgressEvent::new(
+                    &operation_id,
+                    &filename,
+                    IngestionStage::Queued,
+                ))
+                .await;
+
+            // Clone for async task
+            let ingestion_service = state.ingestion_service.clone();
+            let execution_log_service = state.execution_log_service.clone();
+            let kb_id_clone = kb_id.clone();
+            let namespace = query.namespace.clone();
+            let allowed_teams = comma_separated(query.allowed_teams.as_deref());
+            let allowed_roles = comma_separated(query.allowed_roles.as_deref());
+            let progress = state.ingestion_progress.clone();
+            let operation_id = operation_id.clone();
+            let document = filename.clone();
+            let cancellation = cancellation.clone();
+
+            // Spawn async ingestion task, reading the file back from disk
+            spawn_tracked(Box::pin(async move {
+                let start = std::time::Instant::now();
+
+                // Mark as in progress
+                log.set_in_progress();
+                let _ = execution_log_service.update(&log).await;
+
+                let ingest_request = if binary {
+                    spooled.into_bytes().await.map(IngestDocumentRequest::from_bytes)
+                } else {
+                    spooled.into_text().await.map(IngestDocumentRequest::new)
+                };
+                let ingest_request = match ingest_request {
+                    Ok(request) => request,
+                    Err(e) => {
+                        progress
+                            .record(
+                                IngestionProgressEvent::new(
+                                    &operation_id,
+                                    &document,
+                                    IngestionStage::Failed,
+                                )
+                                .with_error(e.to_string()),
+                            )
+                            .await;
+                        log.set_failed(start.elapsed().as_millis() as u64, e.to_string());
+                        let _ = execution_log_service.update(&log).await;
+                        return;
+                    }
+                };
+
+                // Build ingestion request - use filename for auto-detection of parser type
+                let mut ingest_request = ingest_request
+                    .with_filename(filename.clone())
+                    .with_source_id(filename);
+                ingest_request.namespace = namespace;
+                ingest_request.allowed_teams = allowed_teams;
+                ingest_request.allowed_roles = allowed_roles;
+
+                // Perform ingestion, reporting each stage as the file reaches it;
+                // cancelling the operation drops it along with its embedding calls
+                let (stages, mut reached) = tokio::sync::mpsc::unbounded_channel();
+                let ingestion = async move {
+                    let on_stage = |stage, chunks| {
+                        let _ = stages.send((stage, chunks));
+                    };
+                    let ingest =
+                        ingestion_service.ingest_with_progress(&kb_id_clone, ingest_request, &on_stage);
+                    tokio::select! {
+                        outcome = ingest => outcome,
+                        _ = cancellation.cancelled() => {
+                            Err(DomainError::conflict("Operation cancelled"))
+                        }
+                    }
+                };
+                let report = async {
+                    while let Some((stage, chunks)) = reached.recv().await {
+                        let mut event = IngestionProgressEvent::new(&operation_id, &document, stage);
+                        if let Some(chunks) = chunks {
+                            event = event.with_chunks(chunks);
+                        }
+                        progress.record(event).await;
+                    }
+                };
+                let (outcome, ()) = tokio::join!(ingestion, report);
+                let execution_time_ms = start.elapsed().as_millis() as u64;
+
+                match outcome {
+                    Ok(result) => {
+                        let output = serde_json::json!({
+                            "document_id": result.document_id,
+                            "chunks_created": result.chunks_created,
+                            "chunks_failed": result.chunks_failed,
+                            "errors": result.errors.iter().map(|e| &e.message).collect::<Vec<_>>(),
+                        });
+                        log.set_success(execution_time_ms, Some(output));
+                    }
+                    Err(e) => {
+                        progress
+                            .record(
+                                IngestionProgressEvent::new(
+                                    &operation_id,
+                                    &document,
+                                    IngestionStage::Failed,
+                                )
+                                .with_error(e.to_string()),
+                            )
+                            .await;
+                        log.set_failed(execution_time_ms, e.to_string());
+                    }
+                }
 
-        if !content.is_empty() {
-            files.push((filename, content));
+                let _ = execution_log_service.update(&log).await;
+            }));
         }
-    }
 
-    if files.is_empty() {
-        return Err(ApiError::bad_request("No files provided"));
+        Ok(())
     }
+    .await;
 
-    let mut log_ids = Vec::with_capacity(files.len());
-
-    // Process each file
-    for (filename, content) in files {
-        // Create execution log
-        let input_json = serde_json::json!({
-            "kb_id": kb_id,
-            "source": filename,
-            "content_length": content.len(),
-            "parser_type": "auto",
-        });
-
-        let mut log = state
-            .execution_log_service
-            .record_pending_ingestion(&kb_id, &filename, executor.clone(), input_json)
-            .await
-            .map_err(ApiError::from)?;
-
-        log_ids.push(log.id().as_str().to_string());
-
-        // Clone for async task
-        let ingestion_service = state.ingestion_service.clone();
-        let execution_log_service = state.execution_log_service.clone();
-        let kb_id_clone = kb_id.clone();
-        let filename_clone = filename.clone();
-
-        // Spawn async ingestion task
-        tokio::spawn(Box::pin(async move {
-            let start = std::time::Instant::now();
-
-            // Mark as in progress
-            log.set_in_progress();
-            let _ = execution_log_service.update(&log).await;
-
-            // Build ingestion request - use filename for auto-detection of parser type
-            let ingest_request = IngestDocumentRequest::new(content)
-                .with_filename(filename_clone.clone())
-                .with_source_id(filename_clone);
-
-            // Perform ingestion
-            let execution_time_ms = start.elapsed().as_millis() as u64;
-
-            match ingestion_service.ingest(&kb_id_clone, ingest_request).await {
-                Ok(result) => {
-                    let output = serde_json::json!({
-                        "document_id": result.document_id,
-                        "chunks_created": result.chunks_created,
-                        "chunks_failed": result.chunks_failed,
-                        "errors": result.errors.iter().map(|e| &e.message).collect::<Vec<_>>(),
-                    });
-                    log.set_success(execution_time_ms, Some(output));
-                }
-                Err(e) => {
-                    log.set_failed(execution_time_ms, e.to_string());
-                }
-            }
+    // Every file is uploaded, the operation finishes with the last ingestion
+    state.ingestion_progress.seal(&operation_id).await;
+    uploaded?;
 
-            let _ = execution_log_service.update(&log).await;
-        }));
+    if files.is_empty() {
+        return Err(ApiError::bad_request("No files provided"));
     }
 
     let total = log_ids.len();
+    let failed = files.len() - total;
 
     Ok(Json(BatchIngestResponse {
         total,
+        failed,
+        message: format!(
+            "{} file(s) queued for ingestion, {} rejected. Stream progress from \
+             /admin/knowledge-bases/{}/ingestions/{}/stream.",
+            total, failed, kb_id, operation_id
+        ),
+        operation_id,
         log_ids,
-        message: format!("{} file(s) queued for ingestion. Check execution logs for progress.", total),
+        files,
     }))
 }
 
+/// Record a file rejected during the upload as failed
+async fn record_rejected(state: &AppState, operation_id: &str, filename: &str, error: &str) {
+    state
+        .ingestion_progress
+        .record(
+            IngestionProgressEvent::new(operation_id, filename, IngestionStage::Failed)
+                .with_error(error),
+        )
+        .await;
+}
+
 /// Ingestion operation info
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct IngestionOperationResponse {
     pub id: String,
     pub source_name: Option<String>,
@@ -574,14 +1057,20 @@ pub struct IngestionOperationResponse {
 }
 
 /// List ingestion operations response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListIngestionOperationsResponse {
     pub operations: Vec<IngestionOperationResponse>,
     pub total: usize,
 }
 
-/// GET /admin/knowledge-bases/:kb_id/ingestions
 /// List ingestion operations for a knowledge base
+#[utoipa::path(
+    get,
+    path = "/admin/knowledge-bases/{kb_id}/ingestions",
+    tag = "admin/knowledge-bases",
+    params(("kb_id" = String, Path, description = "Knowledge base ID")),
+    responses((status = 200, body = ListIngestionOperationsResponse)),
+)]
 pub async fn list_ingestion_operations(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -632,8 +1121,110 @@ pub async fn list_ingestion_operations(
     Ok(Json(ListIngestionOperationsResponse { operations, total }))
 }
 
-/// POST /admin/knowledge-bases/:kb_id/schema
+/// Follow the progress of a batch ingestion as server-sent events: a
+/// `snapshot` of every file first, then a `progress` event each time a file
+/// is parsed, chunked, embedded, stored or fails, and a `finished` event
+/// carrying the final snapshot. A `lagged` event reports updates skipped
+/// because the client fell behind.
+#[utoipa::path(
+    get,
+    path = "/admin/knowledge-bases/{kb_id}/ingestions/{op_id}/stream",
+    tag = "admin/knowledge-bases",
+    params(
+        ("kb_id" = String, Path, description = "Knowledge base ID"),
+        ("op_id" = String, Path, description = "Operation ID of the batch upload"),
+    ),
+    responses(
+        (status = 200, description = "Server-sent `snapshot`, `progress`, `finished` and `lagged` events", content_type = "text/event-stream"),
+        (status = 404, description = "Ingestion operation not found"),
+    ),
+)]
+pub async fn stream_ingestion_progress(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path((kb_id, op_id)): Path<(String, String)>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    debug!(kb_id = %kb_id, operation_id = %op_id, "Admin streaming ingestion progress");
+
+    // Subscribe before reading the snapshot so no update falls in between
+    let receiver = state.ingestion_progress.subscribe();
+
+    let operation = state
+        .operation_service
+        .get(&op_id)
+        .await?
+        .filter(|op| {
+            op.operation_type() == OperationType::KnowledgeBaseIngestion
+                && op.metadata().get("kb_id").and_then(|v| v.as_str()) == Some(kb_id.as_str())
+        })
+        .ok_or_else(|| {
+            ApiError::not_found(format!(
+                "Ingestion '{}' of knowledge base '{}' not found",
+                op_id, kb_id
+            ))
+        })?;
+
+    let snapshot: IngestionProgress = operation
+        .progress()
+        .and_then(|p| serde_json::from_value(p.clone()).ok())
+        .unwrap_or_default();
+    let first = if operation.is_terminal() {
+        progress_event("finished", &snapshot)
+    } else {
+        progress_event("snapshot", &snapshot)
+    };
+    let done = operation.is_terminal();
+
+    let updates = futures::stream::unfold(
+        (receiver, op_id, done),
+        |(mut receiver, op_id, done)| async move {
+            if done {
+                return None;
+            }
+
+            loop {
+                let (event, done) = match receiver.recv().await {
+                    Ok(update) if update.operation_id() != op_id => continue,
+                    Ok(IngestionUpdate::Document(event)) => {
+                        (progress_event("progress", &event), false)
+                    }
+                    Ok(IngestionUpdate::Finished { progress, .. }) => {
+                        (progress_event("finished", &progress), true)
+                    }
+                    Err(RecvError::Lagged(skipped)) => (
+                        Event::default()
+                            .event("lagged")
+                            .data(serde_json::json!({ "skipped": skipped }).to_string()),
+                        false,
+                    ),
+                    Err(RecvError::Closed) => return None,
+                };
+
+                return Some((Ok(event), (receiver, op_id, done)));
+            }
+        },
+    );
+
+    let stream = futures::stream::once(async move { Ok(first) }).chain(updates);
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+fn progress_event(name: &str, data: &impl Serialize) -> Event {
+    Event::default()
+        .event(name)
+        .json_data(data)
+        .unwrap_or_else(|_| Event::default().event(name))
+}
+
 /// Ensure the knowledge base schema exists (create tables/indexes)
+#[utoipa::path(
+    post,
+    path = "/admin/knowledge-bases/{kb_id}/schema",
+    tag = "admin/knowledge-bases",
+    params(("kb_id" = String, Path, description = "Knowledge base ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn ensure_schema(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -667,12 +1258,176 @@ pub async fn ensure_schema(
     })))
 }
 
+/// Vector index of a knowledge base
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct VectorIndexResponse {
+    pub kb_id: String,
+    /// Index serving the searches, none when the provider manages no index
+    /// or the schema was not initialized
+    pub index: Option<VectorIndexStatus>,
+}
+
+async fn require_knowledge_base(state: &AppState, kb_id: &str) -> Result<(), ApiError> {
+    let kb_exists = state
+        .knowledge_base_service
+        .exists(kb_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    if !kb_exists {
+        return Err(ApiError::not_found(format!(
+            "Knowledge base '{}' not found",
+            kb_id
+        )));
+    }
+
+    Ok(())
+}
+
+/// Get the status of the vector index of a knowledge base
+#[utoipa::path(
+    get,
+    path = "/admin/knowledge-bases/{kb_id}/index",
+    tag = "admin/knowledge-bases",
+    params(("kb_id" = String, Path, description = "Knowledge base ID")),
+    responses((status = 200, body = VectorIndexResponse)),
+)]
+pub async fn get_vector_index(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(kb_id): Path<String>,
+) -> Result<Json<VectorIndexResponse>, ApiError> {
+    debug!(kb_id = %kb_id, "Admin getting vector index status");
+
+    require_knowledge_base(&state, &kb_id).await?;
+
+    let index = state
+        .ingestion_service
+        .index_status(&kb_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(VectorIndexResponse { kb_id, index }))
+}
+
+/// Rebuild the vector index of a knowledge base with its configured type
+/// and parameters; without a dedicated index the shared one is rebuilt
+#[utoipa::path(
+    post,
+    path = "/admin/knowledge-bases/{kb_id}/reindex",
+    tag = "admin/knowledge-bases",
+    params(("kb_id" = String, Path, description = "Knowledge base ID")),
+    responses((status = 200, body = VectorIndexResponse)),
+)]
+pub async fn reindex_knowledge_base(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(kb_id): Path<String>,
+) -> Result<Json<VectorIndexResponse>, ApiError> {
+    debug!(kb_id = %kb_id, "Admin reindexing knowledge base");
+
+    require_knowledge_base(&state, &kb_id).await?;
+
+    let index = state
+        .ingestion_service
+        .reindex(&kb_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(VectorIndexResponse {
+        kb_id,
+        index: Some(index),
+    }))
+}
+
+/// Query parameters of the knowledge base analytics
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct KnowledgeBaseAnalyticsQuery {
+    /// Documents listed per ranking (1-100, default 10)
+    #[serde(default = "default_analytics_limit")]
+    pub limit: usize,
+    /// Days without update after which a document is stale (default 90)
+    #[serde(default = "default_stale_days")]
+    pub stale_days: u32,
+}
+
+fn default_analytics_limit() -> usize {
+    10
+}
+
+fn default_stale_days() -> u32 {
+    90
+}
+
+/// Retrieval statistics of a knowledge base: most and least retrieved
+/// documents, zero-hit queries and stale documents
+#[utoipa::path(
+    get,
+    path = "/admin/knowledge-bases/{kb_id}/analytics",
+    tag = "admin/knowledge-bases",
+    params(
+        ("kb_id" = String, Path, description = "Knowledge base ID"),
+        KnowledgeBaseAnalyticsQuery,
+    ),
+    responses((status = 200, body = KnowledgeBaseAnalytics)),
+)]
+pub async fn get_knowledge_base_analytics(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(kb_id): Path<String>,
+    Query(query): Query<KnowledgeBaseAnalyticsQuery>,
+) -> Result<Json<KnowledgeBaseAnalytics>, ApiError> {
+    debug!(kb_id = %kb_id, "Admin getting knowledge base analytics");
+
+    if !(1..=100).contains(&query.limit) {
+        return Err(ApiError::bad_request("limit must be between 1 and 100"));
+    }
+
+    let kb_exists = state
+        .knowledge_base_service
+        .exists(&kb_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    if !kb_exists {
+        return Err(ApiError::not_found(format!(
+            "Knowledge base '{}' not found",
+            kb_id
+        )));
+    }
+
+    let stats = state
+        .kb_access_tracker
+        .stats(&kb_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    // Knowledge bases without the document schema only report the
+    // documents seen in search results
+    let documents = match state.ingestion_service.list_documents_v2(&kb_id).await {
+        Ok(documents) => documents,
+        Err(e) => {
+            warn!(kb_id = %kb_id, error = %e, "Failed to list documents for analytics");
+            Vec::new()
+        }
+    };
+
+    Ok(Json(KnowledgeBaseAnalytics::build(
+        &stats,
+        &documents,
+        query.limit,
+        query.stale_days,
+        Utc::now(),
+    )))
+}
+
 // ============================================================================
 // New Schema Endpoints (document/chunk separation)
 // ============================================================================
 
 /// Request to ingest a document using the new schema
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct IngestDocumentV2ApiRequest {
     pub content: String,
     #[serde(default)]
@@ -693,10 +1448,23 @@ pub struct IngestDocumentV2ApiRequest {
     pub chunk_size: Option<usize>,
     #[serde(default)]
     pub chunk_overlap: Option<usize>,
+    /// Generate a title, summary and keywords per chunk with a model
+    #[serde(default)]
+    pub enrichment: Option<ChunkEnrichmentConfig>,
+    /// Namespace of the document within the knowledge base (e.g. a product
+    /// version), searchable on its own
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// Teams whose searches may return the document, every team if empty
+    #[serde(default)]
+    pub allowed_teams: Vec<String>,
+    /// Roles whose searches may return the document, every role if empty
+    #[serde(default)]
+    pub allowed_roles: Vec<String>,
 }
 
 /// Response for document ingestion (new schema)
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct DocumentV2Response {
     pub id: String,
     pub kb_id: String,
@@ -711,7 +1479,7 @@ pub struct DocumentV2Response {
 }
 
 /// Document summary response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct DocumentSummaryResponse {
     pub id: String,
     pub title: Option<String>,
@@ -722,14 +1490,14 @@ pub struct DocumentSummaryResponse {
 }
 
 /// List documents response (new schema)
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListDocumentsV2Response {
     pub documents: Vec<DocumentSummaryResponse>,
     pub total: usize,
 }
 
 /// Document chunk response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct DocumentChunkResponse {
     pub id: String,
     pub document_id: String,
@@ -740,14 +1508,21 @@ pub struct DocumentChunkResponse {
 }
 
 /// List chunks response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListChunksResponse {
     pub chunks: Vec<DocumentChunkResponse>,
     pub total: usize,
 }
 
-/// POST /admin/knowledge-bases/:kb_id/documents
 /// Ingest a document into a knowledge base
+#[utoipa::path(
+    post,
+    path = "/admin/knowledge-bases/{kb_id}/documents",
+    tag = "admin/knowledge-bases",
+    params(("kb_id" = String, Path, description = "Knowledge base ID")),
+    request_body = IngestDocumentV2ApiRequest,
+    responses((status = 200, body = DocumentV2Response)),
+)]
 pub async fn ingest_document(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -810,6 +1585,10 @@ pub async fn ingest_document(
     ingest_request.chunking_type = chunking_type;
     ingest_request.chunk_size = request.chunk_size;
     ingest_request.chunk_overlap = request.chunk_overlap;
+    ingest_request.enrichment = request.enrichment;
+    ingest_request.namespace = request.namespace;
+    ingest_request.allowed_teams = request.allowed_teams;
+    ingest_request.allowed_roles = request.allowed_roles;
 
     // Perform ingestion
     let document = state
@@ -832,8 +1611,14 @@ pub async fn ingest_document(
     }))
 }
 
-/// GET /admin/knowledge-bases/:kb_id/documents
 /// List all documents in a knowledge base
+#[utoipa::path(
+    get,
+    path = "/admin/knowledge-bases/{kb_id}/documents",
+    tag = "admin/knowledge-bases",
+    params(("kb_id" = String, Path, description = "Knowledge base ID")),
+    responses((status = 200, body = ListDocumentsV2Response)),
+)]
 pub async fn list_documents(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -881,8 +1666,17 @@ pub async fn list_documents(
     }))
 }
 
-/// GET /admin/knowledge-bases/:kb_id/documents/:document_id
 /// Get a document by ID
+#[utoipa::path(
+    get,
+    path = "/admin/knowledge-bases/{kb_id}/documents/{document_id}",
+    tag = "admin/knowledge-bases",
+    params(
+        ("kb_id" = String, Path, description = "Knowledge base ID"),
+        ("document_id" = String, Path, description = "Document ID"),
+    ),
+    responses((status = 200, body = DocumentV2Response)),
+)]
 pub async fn get_document(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -928,8 +1722,17 @@ pub async fn get_document(
     }))
 }
 
-/// GET /admin/knowledge-bases/:kb_id/documents/:document_id/chunks
 /// Get chunks for a document
+#[utoipa::path(
+    get,
+    path = "/admin/knowledge-bases/{kb_id}/documents/{document_id}/chunks",
+    tag = "admin/knowledge-bases",
+    params(
+        ("kb_id" = String, Path, description = "Knowledge base ID"),
+        ("document_id" = String, Path, description = "Document ID"),
+    ),
+    responses((status = 200, body = ListChunksResponse)),
+)]
 pub async fn get_document_chunks(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -980,8 +1783,17 @@ pub async fn get_document_chunks(
     }))
 }
 
-/// DELETE /admin/knowledge-bases/:kb_id/documents/:document_id
 /// Delete a document and its chunks
+#[utoipa::path(
+    delete,
+    path = "/admin/knowledge-bases/{kb_id}/documents/{document_id}",
+    tag = "admin/knowledge-bases",
+    params(
+        ("kb_id" = String, Path, description = "Knowledge base ID"),
+        ("document_id" = String, Path, description = "Document ID"),
+    ),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn delete_document(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -1019,8 +1831,17 @@ pub async fn delete_document(
     })))
 }
 
-/// POST /admin/knowledge-bases/:kb_id/documents/:document_id/disable
 /// Disable a document (soft delete - excludes from search)
+#[utoipa::path(
+    post,
+    path = "/admin/knowledge-bases/{kb_id}/documents/{document_id}/disable",
+    tag = "admin/knowledge-bases",
+    params(
+        ("kb_id" = String, Path, description = "Knowledge base ID"),
+        ("document_id" = String, Path, description = "Document ID"),
+    ),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn disable_document(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -1058,8 +1879,17 @@ pub async fn disable_document(
     })))
 }
 
-/// POST /admin/knowledge-bases/:kb_id/documents/:document_id/enable
 /// Enable a previously disabled document
+#[utoipa::path(
+    post,
+    path = "/admin/knowledge-bases/{kb_id}/documents/{document_id}/enable",
+    tag = "admin/knowledge-bases",
+    params(
+        ("kb_id" = String, Path, description = "Knowledge base ID"),
+        ("document_id" = String, Path, description = "Document ID"),
+    ),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn enable_document(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -1170,6 +2000,21 @@ mod tests {
         assert!(parse_kb_type("unknown").is_err());
     }
 
+    #[test]
+    fn test_clone_knowledge_base_request_deserialization() {
+        let json = r#"{"new_id": "docs-staging"}"#;
+
+        let request: CloneKnowledgeBaseRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.new_id, "docs-staging");
+        assert!(request.new_name.is_none());
+        assert!(!request.include_documents);
+
+        let json = r#"{"new_id": "docs-staging", "include_documents": true}"#;
+
+        let request: CloneKnowledgeBaseRequest = serde_json::from_str(json).unwrap();
+        assert!(request.include_documents);
+    }
+
     #[test]
     fn test_default_enabled() {
         assert!(default_enabled());
@@ -1383,7 +2228,10 @@ mod tests {
             credential_id: Some("cred-001".to_string()),
             default_top_k: 10,
             default_similarity_threshold: 0.7,
+            distance_metric: DistanceMetric::Cosine,
+            vector_index: None,
             enabled: true,
+            team_id: Some("team-a".to_string()),
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-01T00:00:00Z".to_string(),
         };
@@ -1518,14 +2366,24 @@ mod tests {
     fn test_batch_ingest_response_serialization() {
         let response = BatchIngestResponse {
             total: 3,
+            failed: 1,
+            operation_id: "op-12345678-1234-1234-1234-123456789abc".to_string(),
             log_ids: vec!["log-1".to_string(), "log-2".to_string(), "log-3".to_string()],
+            files: vec![BatchIngestFileResult::failed(
+                "scan.pdf".to_string(),
+                2048,
+                "File is not UTF-8 text",
+            )],
             message: "3 ingestion(s) started".to_string(),
         };
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"total\":3"));
+        assert!(json.contains("\"failed\":1"));
         assert!(json.contains("\"log_ids\":["));
         assert!(json.contains("\"log-1\""));
+        assert!(json.contains("\"error\":\"File is not UTF-8 text\""));
+        assert!(!json.contains("\"log_id\""));
     }
 
     #[test]
@@ -0,0 +1,238 @@
+//! MCP tool management admin endpoints
+
+use axum::extract::{Path, State};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{debug, info};
+use utoipa::ToSchema;
+
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::mcp::{McpTool, McpToolTarget};
+use crate::infrastructure::mcp::{CreateMcpToolRequest, UpdateMcpToolRequest};
+
+/// Request to expose a workflow or knowledge base search as an MCP tool
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateMcpToolApiRequest {
+    /// Tool name listed to MCP clients
+    pub id: String,
+    /// What the tool does, for the agent deciding when to call it
+    pub description: String,
+    pub target: McpToolTarget,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Request to update an MCP tool
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct UpdateMcpToolApiRequest {
+    pub description: Option<String>,
+    pub target: Option<McpToolTarget>,
+    pub enabled: Option<bool>,
+}
+
+/// MCP tool response for admin API
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct McpToolResponse {
+    pub id: String,
+    pub description: String,
+    pub target: McpToolTarget,
+    pub enabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<McpTool> for McpToolResponse {
+    fn from(tool: McpTool) -> Self {
+        Self {
+            id: tool.id().to_string(),
+            description: tool.description().to_string(),
+            target: tool.target().clone(),
+            enabled: tool.is_enabled(),
+            created_at: tool.created_at().to_rfc3339(),
+            updated_at: tool.updated_at().to_rfc3339(),
+        }
+    }
+}
+
+/// List MCP tools response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListMcpToolsResponse {
+    pub tools: Vec<McpToolResponse>,
+    pub total: usize,
+}
+
+/// List MCP tools
+#[utoipa::path(
+    get,
+    path = "/admin/mcp-tools",
+    tag = "admin/mcp-tools",
+    responses((status = 200, body = ListMcpToolsResponse)),
+)]
+pub async fn list_mcp_tools(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+) -> Result<Json<ListMcpToolsResponse>, ApiError> {
+    debug!("Listing MCP tools");
+
+    let tools: Vec<McpToolResponse> = state
+        .mcp_tool_service
+        .list()
+        .await?
+        .into_iter()
+        .map(McpToolResponse::from)
+        .collect();
+
+    Ok(Json(ListMcpToolsResponse {
+        total: tools.len(),
+        tools,
+    }))
+}
+
+/// Get MCP tool
+#[utoipa::path(
+    get,
+    path = "/admin/mcp-tools/{tool_id}",
+    tag = "admin/mcp-tools",
+    params(("tool_id" = String, Path, description = "Tool name")),
+    responses((status = 200, body = McpToolResponse)),
+)]
+pub async fn get_mcp_tool(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+) -> Result<Json<McpToolResponse>, ApiError> {
+    debug!(tool = %id, "Getting MCP tool");
+
+    let tool = state
+        .mcp_tool_service
+        .get(&id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("MCP tool '{}' not found", id)))?;
+
+    Ok(Json(McpToolResponse::from(tool)))
+}
+
+/// Create MCP tool
+#[utoipa::path(
+    post,
+    path = "/admin/mcp-tools",
+    tag = "admin/mcp-tools",
+    request_body = CreateMcpToolApiRequest,
+    responses((status = 200, body = McpToolResponse)),
+)]
+pub async fn create_mcp_tool(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Json(request): Json<CreateMcpToolApiRequest>,
+) -> Result<Json<McpToolResponse>, ApiError> {
+    info!(tool = %request.id, "Creating MCP tool");
+
+    ensure_target(&state, &request.target).await?;
+
+    let tool = state
+        .mcp_tool_service
+        .create(CreateMcpToolRequest {
+            id: request.id,
+            description: request.description,
+            target: request.target,
+            enabled: request.enabled,
+        })
+        .await?;
+
+    Ok(Json(McpToolResponse::from(tool)))
+}
+
+/// Update MCP tool
+#[utoipa::path(
+    put,
+    path = "/admin/mcp-tools/{tool_id}",
+    tag = "admin/mcp-tools",
+    params(("tool_id" = String, Path, description = "Tool name")),
+    request_body = UpdateMcpToolApiRequest,
+    responses((status = 200, body = McpToolResponse)),
+)]
+pub async fn update_mcp_tool(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateMcpToolApiRequest>,
+) -> Result<Json<McpToolResponse>, ApiError> {
+    info!(tool = %id, "Updating MCP tool");
+
+    if let Some(target) = &request.target {
+        ensure_target(&state, target).await?;
+    }
+
+    let tool = state
+        .mcp_tool_service
+        .update(
+            &id,
+            UpdateMcpToolRequest {
+                description: request.description,
+                target: request.target,
+                enabled: request.enabled,
+            },
+        )
+        .await?;
+
+    Ok(Json(McpToolResponse::from(tool)))
+}
+
+/// Delete MCP tool
+#[utoipa::path(
+    delete,
+    path = "/admin/mcp-tools/{tool_id}",
+    tag = "admin/mcp-tools",
+    params(("tool_id" = String, Path, description = "Tool name")),
+    responses((status = 200, body = serde_json::Value)),
+)]
+pub async fn delete_mcp_tool(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!(tool = %id, "Deleting MCP tool");
+
+    if state.mcp_tool_service.delete(&id).await? {
+        Ok(Json(json!({"deleted": true})))
+    } else {
+        Err(ApiError::not_found(format!("MCP tool '{}' not found", id)))
+    }
+}
+
+/// Check the workflow or knowledge base behind a tool exists
+async fn ensure_target(state: &AppState, target: &McpToolTarget) -> Result<(), ApiError> {
+    match target {
+        McpToolTarget::Workflow { workflow_id } => {
+            if state.workflow_service.get(workflow_id).await?.is_none() {
+                return Err(
+                    ApiError::bad_request(format!("Workflow '{}' not found", workflow_id))
+                        .with_param("target.workflow_id"),
+                );
+            }
+        }
+        McpToolTarget::KnowledgeBaseSearch {
+            knowledge_base_id, ..
+        } => {
+            if !state
+                .knowledge_base_service
+                .exists(knowledge_base_id)
+                .await?
+            {
+                return Err(ApiError::bad_request(format!(
+                    "Knowledge base '{}' not found",
+                    knowledge_base_id
+                ))
+                .with_param("target.knowledge_base_id"));
+            }
+        }
+    }
+
+    Ok(())
+}
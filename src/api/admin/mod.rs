@@ -1,25 +1,53 @@
 //! Admin API endpoints for managing gateway resources
 
 pub mod api_keys;
+pub mod assistants;
+pub mod audit_logs;
+pub mod bulk;
+pub mod canaries;
 pub mod config;
+pub mod content_policies;
 pub mod credentials;
+pub mod datasets;
+pub mod estimate;
 pub mod execution_logs;
 pub mod experiments;
+pub mod import;
 pub mod external_apis;
+pub mod fine_tunes;
+pub mod invoices;
 pub mod knowledge_bases;
+pub mod mcp_tools;
 pub mod models;
+pub mod organizations;
+pub mod playground;
+pub mod pricing;
+pub mod privacy;
 pub mod prompts;
+pub mod reconcile;
+pub mod references;
+pub mod requests;
+pub mod roles;
+pub mod service_accounts;
+pub mod slo;
+pub mod stats;
 pub mod teams;
 pub mod test_cases;
+pub mod test_suites;
+pub mod transform_rules;
 pub mod usage;
+pub mod users;
 pub mod webhooks;
 pub mod workflows;
 
 use axum::{
+    extract::DefaultBodyLimit,
+    middleware,
     routing::{delete, get, post, put},
     Router,
 };
 
+use super::middleware::audit_middleware;
 use super::state::AppState;
 
 /// Create admin API router
@@ -28,25 +56,41 @@ pub fn create_admin_router() -> Router<AppState> {
         // Model management
         .route("/models", get(models::list_models))
         .route("/models", post(models::create_model))
+        .route("/models/bulk", post(models::bulk_models))
         .route("/models/{model_id}", get(models::get_model))
         .route("/models/{model_id}", put(models::update_model))
         .route("/models/{model_id}", delete(models::delete_model))
         .route("/models/{model_id}/execute", post(models::execute_model))
+        .route("/models/{model_id}/clone", post(models::clone_model))
+        // Side-by-side model comparison
+        .route("/playground/execute", post(playground::execute_playground))
         // Prompt management
         .route("/prompts", get(prompts::list_prompts))
         .route("/prompts", post(prompts::create_prompt))
+        .route("/prompts/validate", post(prompts::validate_prompt))
+        .route("/prompts/bulk", post(prompts::bulk_prompts))
         .route("/prompts/{prompt_id}", get(prompts::get_prompt))
         .route("/prompts/{prompt_id}", put(prompts::update_prompt))
         .route("/prompts/{prompt_id}", delete(prompts::delete_prompt))
         .route("/prompts/{prompt_id}/render", post(prompts::render_prompt))
+        .route("/prompts/{prompt_id}/variables", get(prompts::get_prompt_variables))
+        .route("/prompts/{prompt_id}/clone", post(prompts::clone_prompt))
         .route(
             "/prompts/{prompt_id}/versions",
             get(prompts::list_versions),
         )
+        .route(
+            "/prompts/{prompt_id}/versions/{from}/diff/{to}",
+            get(prompts::diff_versions),
+        )
         .route(
             "/prompts/{prompt_id}/revert/{version}",
             post(prompts::revert_to_version),
         )
+        .route(
+            "/prompts/{prompt_id}/regressions",
+            get(prompts::list_regressions),
+        )
         // Workflow management
         .route("/workflows", get(workflows::list_workflows))
         .route("/workflows", post(workflows::create_workflow))
@@ -65,15 +109,68 @@ pub fn create_admin_router() -> Router<AppState> {
             "/workflows/{workflow_id}/clone",
             post(workflows::clone_workflow),
         )
+        .route(
+            "/workflows/{workflow_id}/regressions",
+            get(workflows::list_regressions),
+        )
+        // Assistant management
+        .route("/assistants", get(assistants::list_assistants))
+        .route("/assistants", post(assistants::create_assistant))
+        .route("/assistants/{assistant_id}", get(assistants::get_assistant))
+        .route("/assistants/{assistant_id}", put(assistants::update_assistant))
+        .route(
+            "/assistants/{assistant_id}",
+            delete(assistants::delete_assistant),
+        )
+        // Fine-tuning jobs
+        .route("/fine-tunes", get(fine_tunes::list_fine_tunes))
+        .route("/fine-tunes", post(fine_tunes::create_fine_tune))
+        .route("/fine-tunes/{job_id}", get(fine_tunes::get_fine_tune))
+        .route(
+            "/fine-tunes/{job_id}/refresh",
+            post(fine_tunes::refresh_fine_tune),
+        )
+        .route(
+            "/fine-tunes/{job_id}/cancel",
+            post(fine_tunes::cancel_fine_tune),
+        )
+        // MCP tool management
+        .route("/mcp-tools", get(mcp_tools::list_mcp_tools))
+        .route("/mcp-tools", post(mcp_tools::create_mcp_tool))
+        .route("/mcp-tools/{tool_id}", get(mcp_tools::get_mcp_tool))
+        .route("/mcp-tools/{tool_id}", put(mcp_tools::update_mcp_tool))
+        .route("/mcp-tools/{tool_id}", delete(mcp_tools::delete_mcp_tool))
         // API key management
         .route("/api-keys", get(api_keys::list_api_keys))
         .route("/api-keys", post(api_keys::create_api_key))
+        .route("/api-keys/bulk-revoke", post(api_keys::bulk_revoke_api_keys))
         .route("/api-keys/{key_id}", get(api_keys::get_api_key))
         .route("/api-keys/{key_id}", put(api_keys::update_api_key))
         .route("/api-keys/{key_id}", delete(api_keys::delete_api_key))
         .route("/api-keys/{key_id}/suspend", post(api_keys::suspend_api_key))
         .route("/api-keys/{key_id}/activate", post(api_keys::activate_api_key))
         .route("/api-keys/{key_id}/revoke", post(api_keys::revoke_api_key))
+        .route("/api-keys/{key_id}/rotate", post(api_keys::rotate_api_key))
+        .route("/api-keys/{key_id}/usage", get(api_keys::get_api_key_usage))
+        // Service account management
+        .route("/service-accounts", get(service_accounts::list_service_accounts))
+        .route("/service-accounts", post(service_accounts::create_service_account))
+        .route(
+            "/service-accounts/{account_id}",
+            get(service_accounts::get_service_account),
+        )
+        .route(
+            "/service-accounts/{account_id}",
+            put(service_accounts::update_service_account),
+        )
+        .route(
+            "/service-accounts/{account_id}",
+            delete(service_accounts::delete_service_account),
+        )
+        .route(
+            "/service-accounts/{account_id}/rotate-secret",
+            post(service_accounts::rotate_service_account_secret),
+        )
         // Team management
         .route("/teams", get(teams::list_teams))
         .route("/teams", post(teams::create_team))
@@ -82,6 +179,56 @@ pub fn create_admin_router() -> Router<AppState> {
         .route("/teams/{team_id}", delete(teams::delete_team))
         .route("/teams/{team_id}/suspend", post(teams::suspend_team))
         .route("/teams/{team_id}/activate", post(teams::activate_team))
+        .route("/teams/{team_id}/quota", get(teams::get_team_quota))
+        .route("/teams/{team_id}/quota", put(teams::update_team_quota))
+        .route(
+            "/teams/{team_id}/request-defaults",
+            get(teams::get_team_request_defaults),
+        )
+        .route(
+            "/teams/{team_id}/request-defaults",
+            put(teams::update_team_request_defaults),
+        )
+        .route(
+            "/teams/{team_id}/invoices/{month}",
+            get(invoices::get_team_invoice),
+        )
+        // Organization management
+        .route("/organizations", get(organizations::list_organizations))
+        .route("/organizations", post(organizations::create_organization))
+        .route(
+            "/organizations/{organization_id}",
+            get(organizations::get_organization),
+        )
+        .route(
+            "/organizations/{organization_id}",
+            put(organizations::update_organization),
+        )
+        .route(
+            "/organizations/{organization_id}",
+            delete(organizations::delete_organization),
+        )
+        .route(
+            "/organizations/{organization_id}/teams",
+            get(organizations::list_organization_teams),
+        )
+        .route(
+            "/organizations/{organization_id}/teams/{team_id}",
+            put(organizations::add_organization_team),
+        )
+        .route(
+            "/organizations/{organization_id}/teams/{team_id}",
+            delete(organizations::remove_organization_team),
+        )
+        // Role management
+        .route("/roles", get(roles::list_roles))
+        .route("/roles", post(roles::create_role))
+        .route("/roles/permissions", get(roles::list_permissions))
+        .route("/roles/{role_id}", get(roles::get_role))
+        .route("/roles/{role_id}", put(roles::update_role))
+        .route("/roles/{role_id}", delete(roles::delete_role))
+        .route("/users/{user_id}/role", put(roles::assign_user_role))
+        .route("/users/{user_id}/mfa/reset", post(users::reset_user_mfa))
         // Credential management
         .route("/credentials", get(credentials::list_credentials))
         .route("/credentials", post(credentials::create_credential))
@@ -145,6 +292,10 @@ pub fn create_admin_router() -> Router<AppState> {
             "/knowledge-bases/{kb_id}",
             delete(knowledge_bases::delete_knowledge_base),
         )
+        .route(
+            "/knowledge-bases/{kb_id}/clone",
+            post(knowledge_bases::clone_knowledge_base),
+        )
         // Knowledge Base documents
         .route(
             "/knowledge-bases/{kb_id}/documents",
@@ -156,7 +307,8 @@ pub fn create_admin_router() -> Router<AppState> {
         )
         .route(
             "/knowledge-bases/{kb_id}/documents/upload",
-            post(knowledge_bases::ingest_files_batch),
+            // Uploads are streamed to disk with per-file limits instead
+            post(knowledge_bases::ingest_files_batch).layer(DefaultBodyLimit::disable()),
         )
         .route(
             "/knowledge-bases/{kb_id}/documents/{document_id}",
@@ -182,15 +334,34 @@ pub fn create_admin_router() -> Router<AppState> {
             "/knowledge-bases/{kb_id}/ingestions",
             get(knowledge_bases::list_ingestion_operations),
         )
+        .route(
+            "/knowledge-bases/{kb_id}/ingestions/{op_id}/stream",
+            get(knowledge_bases::stream_ingestion_progress),
+        )
         .route(
             "/knowledge-bases/{kb_id}/schema",
             post(knowledge_bases::ensure_schema),
         )
+        .route(
+            "/knowledge-bases/{kb_id}/index",
+            get(knowledge_bases::get_vector_index),
+        )
+        .route(
+            "/knowledge-bases/{kb_id}/reindex",
+            post(knowledge_bases::reindex_knowledge_base),
+        )
+        .route(
+            "/knowledge-bases/{kb_id}/analytics",
+            get(knowledge_bases::get_knowledge_base_analytics),
+        )
         // Usage tracking
         .route("/usage", get(usage::list_usage))
         .route("/usage", delete(usage::delete_usage))
         .route("/usage/aggregate", get(usage::get_usage_aggregate))
+        .route("/usage/export", get(usage::export_usage))
         .route("/usage/summary", get(usage::get_usage_summary))
+        .route("/usage/timeseries", get(usage::get_usage_timeseries))
+        .route("/usage/reconciliation/{date}", get(usage::get_usage_reconciliation))
         // Budget management
         .route("/budgets", get(usage::list_budgets))
         .route("/budgets", post(usage::create_budget))
@@ -200,6 +371,95 @@ pub fn create_admin_router() -> Router<AppState> {
         .route("/budgets/{budget_id}", put(usage::update_budget))
         .route("/budgets/{budget_id}", delete(usage::delete_budget))
         .route("/budgets/{budget_id}/reset", post(usage::reset_budget))
+        // Model pricing catalog
+        .route("/pricing", get(pricing::list_pricing))
+        .route("/pricing", post(pricing::create_pricing))
+        .route("/pricing/sync", post(pricing::sync_pricing))
+        .route("/pricing/{model_id}", get(pricing::get_pricing))
+        .route("/pricing/{model_id}", put(pricing::update_pricing))
+        .route("/pricing/{model_id}", delete(pricing::delete_pricing))
+        .route(
+            "/pricing/{model_id}/versions/{pricing_id}",
+            delete(pricing::delete_pricing_version),
+        )
+        // Model SLOs
+        .route("/slo", get(slo::list_slos))
+        .route("/slo", post(slo::create_slo))
+        .route("/slo/reports", get(slo::list_slo_reports))
+        .route("/slo/evaluate", post(slo::evaluate_slos))
+        .route("/slo/{model_id}", get(slo::get_slo))
+        .route("/slo/{model_id}", put(slo::update_slo))
+        .route("/slo/{model_id}", delete(slo::delete_slo))
+        .route("/slo/{model_id}/evaluate", post(slo::evaluate_slo))
+        // Content policies evaluated on completion input and output
+        .route(
+            "/content-policies",
+            get(content_policies::list_content_policies),
+        )
+        .route(
+            "/content-policies",
+            post(content_policies::create_content_policy),
+        )
+        .route(
+            "/content-policies/evaluate",
+            post(content_policies::evaluate_content_policies),
+        )
+        .route(
+            "/content-policies/{id}",
+            get(content_policies::get_content_policy),
+        )
+        .route(
+            "/content-policies/{id}",
+            put(content_policies::update_content_policy),
+        )
+        .route(
+            "/content-policies/{id}",
+            delete(content_policies::delete_content_policy),
+        )
+        // Transform rules applied to requests before routing
+        .route(
+            "/transform-rules",
+            get(transform_rules::list_transform_rules),
+        )
+        .route(
+            "/transform-rules",
+            post(transform_rules::create_transform_rule),
+        )
+        .route(
+            "/transform-rules/evaluate",
+            post(transform_rules::evaluate_transform_rules),
+        )
+        .route(
+            "/transform-rules/{id}",
+            get(transform_rules::get_transform_rule),
+        )
+        .route(
+            "/transform-rules/{id}",
+            put(transform_rules::update_transform_rule),
+        )
+        .route(
+            "/transform-rules/{id}",
+            delete(transform_rules::delete_transform_rule),
+        )
+        // End user data export and erasure
+        .route("/privacy/export", post(privacy::export_subject_data))
+        .route("/privacy/delete", post(privacy::delete_subject_data))
+        // Cross-entity references
+        .route(
+            "/entities/{entity_type}/{id}/references",
+            get(references::list_entity_references),
+        )
+        // Token and cost estimation
+        .route("/estimate", post(estimate::estimate))
+        // Declarative state reconciliation
+        .route("/state", put(reconcile::reconcile_state))
+        // Migration from other gateways
+        .route("/import/litellm", post(import::import_litellm))
+        // Canary test case health
+        .route("/canaries", get(canaries::list_canaries))
+        .route("/canaries/run", post(canaries::run_canaries))
+        // Live stats
+        .route("/stats", get(stats::get_stats))
         // Experiment (A/B Testing) management
         .route("/experiments", get(experiments::list_experiments))
         .route("/experiments", post(experiments::create_experiment))
@@ -263,14 +523,66 @@ pub fn create_admin_router() -> Router<AppState> {
             "/test-cases/{test_case_id}/results",
             get(test_cases::get_test_case_results),
         )
+        // Test suite management
+        .route("/test-suites", get(test_suites::list_test_suites))
+        .route("/test-suites", post(test_suites::create_test_suite))
+        .route(
+            "/test-suites/{test_suite_id}",
+            get(test_suites::get_test_suite),
+        )
+        .route(
+            "/test-suites/{test_suite_id}",
+            put(test_suites::update_test_suite),
+        )
+        .route(
+            "/test-suites/{test_suite_id}",
+            delete(test_suites::delete_test_suite),
+        )
+        .route(
+            "/test-suites/{test_suite_id}/run",
+            post(test_suites::run_test_suite),
+        )
+        .route(
+            "/test-suites/{test_suite_id}/runs",
+            get(test_suites::list_test_suite_runs),
+        )
+        .route(
+            "/test-suites/{test_suite_id}/runs/{run_id}",
+            get(test_suites::get_test_suite_run),
+        )
+        // Dataset management
+        .route("/datasets", get(datasets::list_datasets))
+        .route("/datasets", post(datasets::create_dataset))
+        .route("/datasets/{dataset_id}", get(datasets::get_dataset))
+        .route("/datasets/{dataset_id}", put(datasets::update_dataset))
+        .route("/datasets/{dataset_id}", delete(datasets::delete_dataset))
+        .route(
+            "/datasets/{dataset_id}/versions",
+            get(datasets::list_dataset_versions),
+        )
+        .route(
+            "/datasets/{dataset_id}/versions",
+            post(datasets::create_dataset_version),
+        )
+        .route(
+            "/datasets/{dataset_id}/versions/{version}",
+            get(datasets::get_dataset_version),
+        )
         // Configuration management
         .route("/config", get(config::list_config))
         .route("/config/category/{category}", get(config::list_config_by_category))
         .route("/config/{key}", get(config::get_config))
         .route("/config/{key}", put(config::update_config))
         // Execution log management
+        .route("/requests/{request_id}", get(requests::get_request_trace))
         .route("/execution-logs", get(execution_logs::list_execution_logs))
         .route("/execution-logs/stats", get(execution_logs::get_execution_stats))
+        .route("/execution-logs/payloads", get(execution_logs::list_captured_payloads))
+        .route(
+            "/execution-logs/training-data",
+            get(execution_logs::export_training_data),
+        )
+        .route("/execution-logs/stream", get(execution_logs::stream_execution_logs))
         .route("/execution-logs/cleanup", post(execution_logs::cleanup_execution_logs))
         .route("/execution-logs/{log_id}", get(execution_logs::get_execution_log))
         .route("/execution-logs/{log_id}", delete(execution_logs::delete_execution_log))
@@ -286,4 +598,13 @@ pub fn create_admin_router() -> Router<AppState> {
             "/webhooks/{webhook_id}/deliveries",
             get(webhooks::get_deliveries),
         )
+        .route(
+            "/webhooks/{webhook_id}/deliveries/{delivery_id}/redeliver",
+            post(webhooks::redeliver),
+        )
+        // Audit trail
+        .route("/audit-logs", get(audit_logs::list_audit_logs))
+        .route("/audit-logs/{log_id}", get(audit_logs::get_audit_log))
+        // Record every authenticated mutation to the audit trail
+        .layer(middleware::from_fn(audit_middleware))
 }
@@ -6,19 +6,23 @@ use std::time::Instant;
 use axum::extract::{Path, State};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
+use utoipa::ToSchema;
 
 use crate::api::middleware::{AdminAuth, RequireAdmin};
 use crate::api::state::AppState;
 use crate::api::types::{ApiError, Json};
+use crate::domain::cache::CachePolicy;
 use crate::domain::credentials::CredentialType;
 use crate::domain::llm::{LlmJsonSchema, LlmProvider, LlmRequest, LlmResponseFormat, Message};
-use crate::domain::model::{Model, ModelConfig};
-use crate::domain::usage::default_model_pricing;
+use crate::domain::model::{Model, ModelCapacity, ModelConfig, ModelLineage, ShadowConfig};
 use crate::domain::{ExecutionTokenUsage, Executor};
 use crate::infrastructure::services::{CreateModelRequest, RecordExecutionParams, UpdateModelRequest};
 
+use super::bulk::{ensure_bulk_size, BulkResponse};
+
 /// Request to create a new model
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Serialize))]
 pub struct CreateModelApiRequest {
     pub id: String,
     pub name: String,
@@ -36,7 +40,8 @@ fn default_enabled() -> bool {
 }
 
 /// Request to update a model
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Serialize))]
 pub struct UpdateModelApiRequest {
     pub name: Option<String>,
     pub provider_model: Option<String>,
@@ -45,8 +50,39 @@ pub struct UpdateModelApiRequest {
     pub config: Option<ModelConfigRequest>,
 }
 
+/// Request to clone a model
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Serialize))]
+pub struct CloneModelRequest {
+    /// New ID for the cloned model
+    pub new_id: String,
+    /// New name for the cloned model (optional, defaults to "Copy of {original_name}")
+    #[serde(default)]
+    pub new_name: Option<String>,
+}
+
+/// One operation of a bulk model request
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BulkModelOperation {
+    Create(CreateModelApiRequest),
+    Update {
+        id: String,
+        changes: UpdateModelApiRequest,
+    },
+    Delete {
+        id: String,
+    },
+}
+
+/// Request to create, update and delete models in one call
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct BulkModelsRequest {
+    pub operations: Vec<BulkModelOperation>,
+}
+
 /// Model configuration in request format
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct ModelConfigRequest {
     pub temperature: Option<f32>,
     pub max_tokens: Option<u32>,
@@ -57,10 +93,20 @@ pub struct ModelConfigRequest {
     pub max_retries: Option<u32>,
     pub retry_delay_ms: Option<u64>,
     pub fallback_model_id: Option<String>,
+    /// Provisioned throughput; requests beyond it spill to `fallback_model_id`
+    #[serde(default)]
+    pub capacity: Option<ModelCapacity>,
+    /// Response caching of the model's requests
+    #[serde(default)]
+    pub cache_policy: Option<CachePolicy>,
+    /// Candidate model a share of the chat completions is mirrored to
+    #[serde(default)]
+    pub shadow: Option<ShadowConfig>,
 }
 
 /// Model response for admin API
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 pub struct ModelResponse {
     pub id: String,
     pub name: String,
@@ -70,12 +116,16 @@ pub struct ModelResponse {
     pub enabled: bool,
     pub config: ModelConfigResponse,
     pub config_version: u32,
+    /// Fine-tuning job, base model and dataset of fine-tuned models
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lineage: Option<ModelLineage>,
     pub created_at: String,
     pub updated_at: String,
 }
 
 /// Model configuration in response format
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 pub struct ModelConfigResponse {
     pub temperature: Option<f32>,
     pub max_tokens: Option<u32>,
@@ -90,9 +140,15 @@ pub struct ModelConfigResponse {
     pub retry_delay_ms: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fallback_model_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capacity: Option<ModelCapacity>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_policy: Option<CachePolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadow: Option<ShadowConfig>,
 }
 
-fn credential_type_to_string(ct: &CredentialType) -> String {
+pub(super) fn credential_type_to_string(ct: &CredentialType) -> String {
     match ct {
         CredentialType::OpenAi => "openai".to_string(),
         CredentialType::Anthropic => "anthropic".to_string(),
@@ -102,6 +158,7 @@ fn credential_type_to_string(ct: &CredentialType) -> String {
         CredentialType::AwsKnowledgeBase => "aws_knowledge_base".to_string(),
         CredentialType::Pinecone => "pinecone".to_string(),
         CredentialType::HttpApiKey => "http_api_key".to_string(),
+        CredentialType::Mock => "mock".to_string(),
         CredentialType::Custom(s) => s.clone(),
     }
 }
@@ -112,10 +169,16 @@ fn parse_credential_type(s: &str) -> Result<CredentialType, ApiError> {
         "anthropic" => Ok(CredentialType::Anthropic),
         "azure_openai" | "azure-openai" | "azureopenai" => Ok(CredentialType::AzureOpenAi),
         "aws_bedrock" | "aws-bedrock" | "awsbedrock" | "bedrock" => Ok(CredentialType::AwsBedrock),
+        "mock" => Ok(CredentialType::Mock),
         other => Ok(CredentialType::Custom(other.to_string())),
     }
 }
 
+/// Provider name as returned in model responses
+pub(super) fn canonical_provider(provider: &str) -> Result<String, ApiError> {
+    parse_credential_type(provider).map(|ct| credential_type_to_string(&ct))
+}
+
 impl From<&Model> for ModelResponse {
     fn from(model: &Model) -> Self {
         let config = model.config();
@@ -136,8 +199,12 @@ impl From<&Model> for ModelResponse {
                 max_retries: config.max_retries,
                 retry_delay_ms: config.retry_delay_ms,
                 fallback_model_id: config.fallback_model_id.clone(),
+                capacity: config.capacity,
+                cache_policy: config.cache_policy.clone(),
+                shadow: config.shadow.clone(),
             },
             config_version: model.version(),
+            lineage: model.lineage().cloned(),
             created_at: model.created_at().to_rfc3339(),
             updated_at: model.updated_at().to_rfc3339(),
         }
@@ -145,7 +212,8 @@ impl From<&Model> for ModelResponse {
 }
 
 /// List models response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 pub struct ListModelsResponse {
     pub models: Vec<ModelResponse>,
     pub total: usize,
@@ -200,6 +268,21 @@ fn build_model_config(req: &ModelConfigRequest) -> Option<ModelConfig> {
         has_value = true;
     }
 
+    if let Some(capacity) = req.capacity {
+        config = config.with_capacity(capacity);
+        has_value = true;
+    }
+
+    if let Some(ref cache_policy) = req.cache_policy {
+        config = config.with_cache_policy(cache_policy.clone());
+        has_value = true;
+    }
+
+    if let Some(ref shadow) = req.shadow {
+        config = config.with_shadow(shadow.clone());
+        has_value = true;
+    }
+
     if has_value {
         Some(config)
     } else {
@@ -207,7 +290,13 @@ fn build_model_config(req: &ModelConfigRequest) -> Option<ModelConfig> {
     }
 }
 
-/// GET /admin/models
+/// List models
+#[utoipa::path(
+    get,
+    path = "/admin/models",
+    tag = "admin/models",
+    responses((status = 200, body = ListModelsResponse)),
+)]
 pub async fn list_models(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -225,7 +314,14 @@ pub async fn list_models(
     }))
 }
 
-/// POST /admin/models
+/// Create model
+#[utoipa::path(
+    post,
+    path = "/admin/models",
+    tag = "admin/models",
+    request_body = CreateModelApiRequest,
+    responses((status = 200, body = ModelResponse)),
+)]
 pub async fn create_model(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -233,6 +329,12 @@ pub async fn create_model(
 ) -> Result<Json<ModelResponse>, ApiError> {
     debug!(model_id = %request.id, "Admin creating model");
 
+    let model = apply_create(&state, request).await?;
+
+    Ok(Json(ModelResponse::from(&model)))
+}
+
+async fn apply_create(state: &AppState, request: CreateModelApiRequest) -> Result<Model, ApiError> {
     let provider = parse_credential_type(&request.provider)?;
 
     let create_request = CreateModelRequest {
@@ -244,18 +346,24 @@ pub async fn create_model(
         credential_id: request.credential_id,
         config: build_model_config(&request.config),
         enabled: request.enabled,
+        lineage: None,
     };
 
-    let model = state
+    state
         .model_service
         .create(create_request)
         .await
-        .map_err(ApiError::from)?;
-
-    Ok(Json(ModelResponse::from(&model)))
+        .map_err(ApiError::from)
 }
 
-/// GET /admin/models/:model_id
+/// Get model
+#[utoipa::path(
+    get,
+    path = "/admin/models/{model_id}",
+    tag = "admin/models",
+    params(("model_id" = String, Path, description = "Model ID")),
+    responses((status = 200, body = ModelResponse)),
+)]
 pub async fn get_model(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -273,7 +381,15 @@ pub async fn get_model(
     Ok(Json(ModelResponse::from(&model)))
 }
 
-/// PUT /admin/models/:model_id
+/// Update model
+#[utoipa::path(
+    put,
+    path = "/admin/models/{model_id}",
+    tag = "admin/models",
+    params(("model_id" = String, Path, description = "Model ID")),
+    request_body = UpdateModelApiRequest,
+    responses((status = 200, body = ModelResponse)),
+)]
 pub async fn update_model(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -282,6 +398,16 @@ pub async fn update_model(
 ) -> Result<Json<ModelResponse>, ApiError> {
     debug!(model_id = %model_id, "Admin updating model");
 
+    let model = apply_update(&state, &model_id, request).await?;
+
+    Ok(Json(ModelResponse::from(&model)))
+}
+
+async fn apply_update(
+    state: &AppState,
+    model_id: &str,
+    request: UpdateModelApiRequest,
+) -> Result<Model, ApiError> {
     let update_request = UpdateModelRequest {
         name: request.name,
         description: None,
@@ -291,16 +417,21 @@ pub async fn update_model(
         enabled: request.enabled,
     };
 
-    let model = state
+    state
         .model_service
-        .update(&model_id, update_request)
+        .update(model_id, update_request)
         .await
-        .map_err(ApiError::from)?;
-
-    Ok(Json(ModelResponse::from(&model)))
+        .map_err(ApiError::from)
 }
 
-/// DELETE /admin/models/:model_id
+/// Delete model
+#[utoipa::path(
+    delete,
+    path = "/admin/models/{model_id}",
+    tag = "admin/models",
+    params(("model_id" = String, Path, description = "Model ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn delete_model(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -320,8 +451,100 @@ pub async fn delete_model(
     })))
 }
 
+/// Clone a model with a new ID, keeping its provider, credential and config
+#[utoipa::path(
+    post,
+    path = "/admin/models/{model_id}/clone",
+    tag = "admin/models",
+    params(("model_id" = String, Path, description = "Model ID")),
+    request_body = CloneModelRequest,
+    responses((status = 200, body = ModelResponse)),
+)]
+pub async fn clone_model(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(model_id): Path<String>,
+    Json(request): Json<CloneModelRequest>,
+) -> Result<Json<ModelResponse>, ApiError> {
+    debug!(model_id = %model_id, new_id = %request.new_id, "Admin cloning model");
+
+    let original = state
+        .model_service
+        .get(&model_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found(format!("Model '{}' not found", model_id)))?;
+
+    let new_name = request
+        .new_name
+        .unwrap_or_else(|| format!("Copy of {}", original.name()));
+
+    let create_request = CreateModelRequest {
+        id: request.new_id,
+        name: new_name,
+        description: original.description().map(String::from),
+        provider: original.provider().clone(),
+        provider_model: original.provider_model().to_string(),
+        credential_id: original.credential_id().to_string(),
+        config: Some(original.config().clone()),
+        enabled: true, // Cloned models start enabled for immediate testing
+        lineage: original.lineage().cloned(),
+    };
+
+    let cloned = state
+        .model_service
+        .create(create_request)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(ModelResponse::from(&cloned)))
+}
+
+/// Create, update and delete models in one call, reporting each operation
+#[utoipa::path(
+    post,
+    path = "/admin/models/bulk",
+    tag = "admin/models",
+    request_body = BulkModelsRequest,
+    responses((status = 200, body = BulkResponse)),
+)]
+pub async fn bulk_models(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Json(request): Json<BulkModelsRequest>,
+) -> Result<Json<BulkResponse>, ApiError> {
+    ensure_bulk_size("operations", request.operations.len())?;
+
+    debug!(operations = request.operations.len(), "Admin applying bulk model operations");
+
+    let mut response = BulkResponse::new();
+
+    for operation in request.operations {
+        match operation {
+            BulkModelOperation::Create(request) => {
+                let id = request.id.clone();
+                response.record(id, apply_create(&state, request).await);
+            }
+            BulkModelOperation::Update { id, changes } => {
+                let result = apply_update(&state, &id, changes).await;
+                response.record(id, result);
+            }
+            BulkModelOperation::Delete { id } => {
+                let result = match state.model_service.delete(&id).await {
+                    Ok(true) => Ok(()),
+                    Ok(false) => Err(ApiError::not_found(format!("Model '{}' not found", id))),
+                    Err(e) => Err(ApiError::from(e)),
+                };
+                response.record(id, result);
+            }
+        }
+    }
+
+    Ok(Json(response))
+}
+
 /// Request to execute a model with a prompt
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct ExecuteModelRequest {
     /// Prompt ID to use for the system message
     #[serde(default)]
@@ -343,7 +566,7 @@ pub struct ExecuteModelRequest {
 }
 
 /// Response format specification for structured outputs
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ResponseFormat {
     /// Return plain text (default)
@@ -357,7 +580,7 @@ pub enum ResponseFormat {
 }
 
 /// JSON Schema specification
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, ToSchema)]
 pub struct JsonSchemaSpec {
     /// Name of the schema
     pub name: String,
@@ -369,7 +592,7 @@ pub struct JsonSchemaSpec {
 }
 
 /// Variable information for a prompt
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct PromptVariableInfo {
     pub name: String,
     pub required: bool,
@@ -378,7 +601,7 @@ pub struct PromptVariableInfo {
 }
 
 /// Response from model execution
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ExecuteModelResponse {
     pub model_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -389,15 +612,22 @@ pub struct ExecuteModelResponse {
 }
 
 /// Usage information from model execution
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ExecuteModelUsage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
     pub total_tokens: u32,
 }
 
-/// POST /admin/models/:model_id/execute
 /// Execute a model with an optional prompt and user message
+#[utoipa::path(
+    post,
+    path = "/admin/models/{model_id}/execute",
+    tag = "admin/models",
+    params(("model_id" = String, Path, description = "Model ID")),
+    request_body = ExecuteModelRequest,
+    responses((status = 200, body = ExecuteModelResponse)),
+)]
 pub async fn execute_model(
     State(state): State<AppState>,
     RequireAdmin(admin): RequireAdmin,
@@ -411,6 +641,9 @@ pub async fn execute_model(
     let executor = match &admin {
         AdminAuth::ApiKey(key) => Executor::from_api_key(key.id().as_str()),
         AdminAuth::User(user) => Executor::from_user(user.id().as_str()),
+        AdminAuth::ServiceAccount { account, .. } => {
+            Executor::from_service_account(account.id().as_str())
+        }
     };
 
     // Get and validate the model
@@ -528,12 +761,10 @@ pub async fn execute_model(
     log_params.resource_name = Some(model.name().to_string());
 
     // Calculate cost from model pricing
-    if let Some(usage) = &response.usage {
-        let pricing_map = default_model_pricing();
-
-        if let Some(pricing) = pricing_map.get(model.provider_model()) {
-            log_params.cost_micros = Some(pricing.calculate_cost(usage.prompt_tokens, usage.completion_tokens));
-        }
+    if let Some(usage) = &response.usage
+        && let Some(pricing) = state.usage_service.get_pricing(model.provider_model())
+    {
+        log_params.cost_micros = Some(pricing.calculate_cost(usage.prompt_tokens, usage.completion_tokens));
     }
 
     if let Err(e) = state.execution_log_service.record(log_params).await {
@@ -550,7 +781,7 @@ pub async fn execute_model(
 }
 
 /// Get the appropriate LLM provider for a model
-async fn get_provider_for_model(
+pub(crate) async fn get_provider_for_model(
     state: &AppState,
     model: &Model,
 ) -> std::sync::Arc<dyn LlmProvider> {
@@ -645,6 +876,15 @@ mod tests {
         assert!(request.config.temperature.is_none());
     }
 
+    #[test]
+    fn test_clone_model_request_deserialization() {
+        let json = r#"{"new_id": "gpt-4-eval", "new_name": "GPT-4 (eval)"}"#;
+
+        let request: CloneModelRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.new_id, "gpt-4-eval");
+        assert_eq!(request.new_name, Some("GPT-4 (eval)".to_string()));
+    }
+
     #[test]
     fn test_update_model_request_partial() {
         let json = r#"{"name": "New Name"}"#;
@@ -757,10 +997,19 @@ mod tests {
             max_retries: Some(3),
             retry_delay_ms: Some(1000),
             fallback_model_id: Some("fallback".to_string()),
+            capacity: Some(ModelCapacity::new().with_tokens_per_minute(100_000)),
+            cache_policy: Some(CachePolicy::disabled()),
+            shadow: Some(ShadowConfig::new("gpt-4o", 5)),
         };
 
         let config = build_model_config(&req).unwrap();
         assert_eq!(config.temperature, Some(0.7));
+        assert_eq!(
+            config.capacity.and_then(|c| c.tokens_per_minute),
+            Some(100_000)
+        );
+        assert_eq!(config.cache_policy, Some(CachePolicy::disabled()));
+        assert_eq!(config.shadow, Some(ShadowConfig::new("gpt-4o", 5)));
         assert_eq!(config.max_tokens, Some(1000));
         assert_eq!(config.top_p, Some(0.9));
     }
@@ -811,6 +1060,9 @@ mod tests {
             max_retries: None,
             retry_delay_ms: None,
             fallback_model_id: None,
+            capacity: None,
+            cache_policy: None,
+            shadow: None,
         };
 
         let json = serde_json::to_string(&config).unwrap();
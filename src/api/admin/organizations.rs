@@ -0,0 +1,382 @@
+//! Organization management admin endpoints
+
+use axum::extract::{Path, State};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use utoipa::ToSchema;
+
+use crate::api::admin::teams::{ListTeamsResponse, TeamResponse};
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::organization::{Organization, OrganizationId};
+use crate::infrastructure::organization::{
+    CreateOrganizationRequest, UpdateOrganizationRequest,
+};
+
+/// Request to create a new organization
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateOrganizationApiRequest {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Users allowed to manage the organization and its teams
+    #[serde(default)]
+    pub admin_user_ids: Vec<String>,
+}
+
+/// Request to update an organization
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateOrganizationApiRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    /// Replaces the organization admins
+    pub admin_user_ids: Option<Vec<String>>,
+}
+
+/// Organization response for admin API
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct OrganizationResponse {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub admin_user_ids: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<&Organization> for OrganizationResponse {
+    fn from(organization: &Organization) -> Self {
+        Self {
+            id: organization.id().as_str().to_string(),
+            name: organization.name().to_string(),
+            description: organization.description().map(String::from),
+            admin_user_ids: organization
+                .admin_user_ids()
+                .iter()
+                .map(|id| id.as_str().to_string())
+                .collect(),
+            created_at: organization.created_at().to_rfc3339(),
+            updated_at: organization.updated_at().to_rfc3339(),
+        }
+    }
+}
+
+/// List organizations response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListOrganizationsResponse {
+    pub organizations: Vec<OrganizationResponse>,
+    pub total: usize,
+}
+
+/// Resolve the organization referenced by a request, rejecting unknown ones
+pub(super) async fn resolve_organization(
+    state: &AppState,
+    organization_id: &str,
+) -> Result<OrganizationId, ApiError> {
+    state
+        .organization_service
+        .get(organization_id)
+        .await
+        .map_err(ApiError::from)?
+        .map(|organization| organization.id().clone())
+        .ok_or_else(|| {
+            ApiError::bad_request(format!("Organization '{}' not found", organization_id))
+        })
+}
+
+/// Ensure every organization admin refers to an existing user
+async fn validate_admin_users(state: &AppState, user_ids: &[String]) -> Result<(), ApiError> {
+    for user_id in user_ids {
+        if state
+            .user_service
+            .get(user_id)
+            .await
+            .map_err(ApiError::from)?
+            .is_none()
+        {
+            return Err(ApiError::bad_request(format!("User '{}' not found", user_id)));
+        }
+    }
+
+    Ok(())
+}
+
+/// List organizations
+#[utoipa::path(
+    get,
+    path = "/admin/organizations",
+    tag = "admin/organizations",
+    responses((status = 200, body = ListOrganizationsResponse)),
+)]
+pub async fn list_organizations(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+) -> Result<Json<ListOrganizationsResponse>, ApiError> {
+    debug!("Admin listing all organizations");
+
+    let organizations = state
+        .organization_service
+        .list()
+        .await
+        .map_err(ApiError::from)?;
+
+    let organizations: Vec<OrganizationResponse> =
+        organizations.iter().map(OrganizationResponse::from).collect();
+    let total = organizations.len();
+
+    Ok(Json(ListOrganizationsResponse {
+        organizations,
+        total,
+    }))
+}
+
+/// Create organization
+#[utoipa::path(
+    post,
+    path = "/admin/organizations",
+    tag = "admin/organizations",
+    request_body = CreateOrganizationApiRequest,
+    responses((status = 200, body = OrganizationResponse)),
+)]
+pub async fn create_organization(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Json(request): Json<CreateOrganizationApiRequest>,
+) -> Result<Json<OrganizationResponse>, ApiError> {
+    debug!(id = %request.id, name = %request.name, "Admin creating organization");
+
+    validate_admin_users(&state, &request.admin_user_ids).await?;
+
+    let organization = state
+        .organization_service
+        .create(CreateOrganizationRequest {
+            id: request.id,
+            name: request.name,
+            description: request.description,
+            admin_user_ids: request.admin_user_ids,
+        })
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(OrganizationResponse::from(&organization)))
+}
+
+/// Get organization
+#[utoipa::path(
+    get,
+    path = "/admin/organizations/{organization_id}",
+    tag = "admin/organizations",
+    params(("organization_id" = String, Path, description = "Organization ID")),
+    responses((status = 200, body = OrganizationResponse)),
+)]
+pub async fn get_organization(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(organization_id): Path<String>,
+) -> Result<Json<OrganizationResponse>, ApiError> {
+    debug!(organization_id = %organization_id, "Admin getting organization");
+
+    let organization = state
+        .organization_service
+        .get(&organization_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| {
+            ApiError::not_found(format!("Organization '{}' not found", organization_id))
+        })?;
+
+    Ok(Json(OrganizationResponse::from(&organization)))
+}
+
+/// Update organization
+#[utoipa::path(
+    put,
+    path = "/admin/organizations/{organization_id}",
+    tag = "admin/organizations",
+    params(("organization_id" = String, Path, description = "Organization ID")),
+    request_body = UpdateOrganizationApiRequest,
+    responses((status = 200, body = OrganizationResponse)),
+)]
+pub async fn update_organization(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(organization_id): Path<String>,
+    Json(request): Json<UpdateOrganizationApiRequest>,
+) -> Result<Json<OrganizationResponse>, ApiError> {
+    debug!(organization_id = %organization_id, "Admin updating organization");
+
+    if let Some(ref admin_user_ids) = request.admin_user_ids {
+        validate_admin_users(&state, admin_user_ids).await?;
+    }
+
+    let organization = state
+        .organization_service
+        .update(
+            &organization_id,
+            UpdateOrganizationRequest {
+                name: request.name,
+                description: request.description,
+                admin_user_ids: request.admin_user_ids,
+            },
+        )
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(OrganizationResponse::from(&organization)))
+}
+
+/// Delete organization
+#[utoipa::path(
+    delete,
+    path = "/admin/organizations/{organization_id}",
+    tag = "admin/organizations",
+    params(("organization_id" = String, Path, description = "Organization ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
+pub async fn delete_organization(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(organization_id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    debug!(organization_id = %organization_id, "Admin deleting organization");
+
+    let deleted = state
+        .organization_service
+        .delete(&organization_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    if !deleted {
+        return Err(ApiError::not_found(format!(
+            "Organization '{}' not found",
+            organization_id
+        )));
+    }
+
+    Ok(Json(serde_json::json!({
+        "deleted": true,
+        "id": organization_id
+    })))
+}
+
+/// List organization teams
+#[utoipa::path(
+    get,
+    path = "/admin/organizations/{organization_id}/teams",
+    tag = "admin/organizations",
+    params(("organization_id" = String, Path, description = "Organization ID")),
+    responses((status = 200, body = ListTeamsResponse)),
+)]
+pub async fn list_organization_teams(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(organization_id): Path<String>,
+) -> Result<Json<ListTeamsResponse>, ApiError> {
+    debug!(organization_id = %organization_id, "Admin listing organization teams");
+
+    let teams = state
+        .organization_service
+        .list_teams(&organization_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    let teams: Vec<TeamResponse> = teams.iter().map(TeamResponse::from).collect();
+    let total = teams.len();
+
+    Ok(Json(ListTeamsResponse { teams, total }))
+}
+
+/// Add organization team
+#[utoipa::path(
+    put,
+    path = "/admin/organizations/{organization_id}/teams/{team_id}",
+    tag = "admin/organizations",
+    params(
+        ("organization_id" = String, Path, description = "Organization ID"),
+        ("team_id" = String, Path, description = "Team ID"),
+    ),
+    responses((status = 200, body = TeamResponse)),
+)]
+pub async fn add_organization_team(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path((organization_id, team_id)): Path<(String, String)>,
+) -> Result<Json<TeamResponse>, ApiError> {
+    debug!(organization_id = %organization_id, team_id = %team_id, "Admin adding team to organization");
+
+    let team = state
+        .organization_service
+        .add_team(&organization_id, &team_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(TeamResponse::from(&team)))
+}
+
+/// Remove organization team
+#[utoipa::path(
+    delete,
+    path = "/admin/organizations/{organization_id}/teams/{team_id}",
+    tag = "admin/organizations",
+    params(
+        ("organization_id" = String, Path, description = "Organization ID"),
+        ("team_id" = String, Path, description = "Team ID"),
+    ),
+    responses((status = 200, body = TeamResponse)),
+)]
+pub async fn remove_organization_team(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path((organization_id, team_id)): Path<(String, String)>,
+) -> Result<Json<TeamResponse>, ApiError> {
+    debug!(organization_id = %organization_id, team_id = %team_id, "Admin removing team from organization");
+
+    let team = state
+        .organization_service
+        .remove_team(&organization_id, &team_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(TeamResponse::from(&team)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::user::UserId;
+
+    #[test]
+    fn test_create_organization_request_defaults() {
+        let json = r#"{"id": "acme", "name": "Acme"}"#;
+
+        let request: CreateOrganizationApiRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.id, "acme");
+        assert!(request.description.is_none());
+        assert!(request.admin_user_ids.is_empty());
+    }
+
+    #[test]
+    fn test_update_organization_request_partial() {
+        let json = r#"{"admin_user_ids": ["alice"]}"#;
+
+        let request: UpdateOrganizationApiRequest = serde_json::from_str(json).unwrap();
+        assert!(request.name.is_none());
+        assert_eq!(request.admin_user_ids, Some(vec!["alice".to_string()]));
+    }
+
+    #[test]
+    fn test_organization_response_from_entity() {
+        let organization = Organization::new(OrganizationId::new("acme").unwrap(), "Acme")
+            .unwrap()
+            .with_admins(vec![UserId::new("alice").unwrap()]);
+
+        let response = OrganizationResponse::from(&organization);
+        assert_eq!(response.id, "acme");
+        assert_eq!(response.admin_user_ids, vec!["alice".to_string()]);
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("\"name\":\"Acme\""));
+    }
+}
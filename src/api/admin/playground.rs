@@ -0,0 +1,308 @@
+//! Playground endpoint comparing an ad-hoc prompt across models
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use axum::extract::State;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::llm::{LlmRequest, Message};
+use crate::domain::prompt::PromptTemplate;
+
+use super::models::{ExecuteModelUsage, get_provider_for_model};
+
+/// Maximum number of model+parameter combinations per playground run
+pub const MAX_PLAYGROUND_VARIANTS: usize = 10;
+
+/// Request to run a prompt against several model+parameter combinations
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct PlaygroundExecuteRequest {
+    /// Stored prompt to render as the system message
+    #[serde(default)]
+    pub prompt_id: Option<String>,
+    /// Ad-hoc prompt template to render as the system message, takes
+    /// precedence over `prompt_id`
+    #[serde(default)]
+    pub prompt: Option<String>,
+    /// Variables to substitute in the prompt template
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    /// User message to send
+    pub user_message: String,
+    /// Combinations to compare
+    pub variants: Vec<PlaygroundVariant>,
+}
+
+impl PlaygroundExecuteRequest {
+    fn validate(&self) -> Result<(), ApiError> {
+        if self.variants.is_empty() {
+            return Err(
+                ApiError::bad_request("At least one variant is required").with_param("variants")
+            );
+        }
+
+        if self.variants.len() > MAX_PLAYGROUND_VARIANTS {
+            return Err(ApiError::bad_request(format!(
+                "At most {} variants can be compared",
+                MAX_PLAYGROUND_VARIANTS
+            ))
+            .with_param("variants"));
+        }
+
+        Ok(())
+    }
+}
+
+/// A model with optional parameter overrides
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct PlaygroundVariant {
+    pub model_id: String,
+    /// Display name of the variant, defaults to the model ID
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+}
+
+/// Outcome of one variant
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PlaygroundResult {
+    pub label: String,
+    pub model_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<ExecuteModelUsage>,
+    /// Cost in micro-dollars, none without pricing for the model
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_micros: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PlaygroundExecuteResponse {
+    /// Results in the order of the requested variants
+    pub results: Vec<PlaygroundResult>,
+}
+
+/// Run a prompt against every variant concurrently. Nothing is persisted and
+/// a failing variant does not fail the others.
+#[utoipa::path(
+    post,
+    path = "/admin/playground/execute",
+    tag = "admin/playground",
+    request_body = PlaygroundExecuteRequest,
+    responses((status = 200, body = PlaygroundExecuteResponse)),
+)]
+pub async fn execute_playground(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Json(request): Json<PlaygroundExecuteRequest>,
+) -> Result<Json<PlaygroundExecuteResponse>, ApiError> {
+    request.validate()?;
+    info!(
+        variants = request.variants.len(),
+        "Admin executing playground"
+    );
+
+    let system_message = match (&request.prompt, &request.prompt_id) {
+        (Some(content), _) => Some(
+            state
+                .prompt_service
+                .expand_partials(content)
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|content| {
+                    PromptTemplate::parse(content)
+                        .and_then(|template| template.render(&request.variables))
+                        .map_err(|e| e.to_string())
+                })
+                .map_err(|e| {
+                    ApiError::bad_request(format!("Failed to render prompt: {}", e))
+                        .with_param("prompt")
+                })?,
+        ),
+        (None, Some(prompt_id)) => Some(
+            state
+                .prompt_service
+                .render(prompt_id, &request.variables)
+                .await
+                .map_err(|e| {
+                    ApiError::bad_request(format!("Failed to render prompt '{}': {}", prompt_id, e))
+                        .with_param("prompt_id")
+                })?,
+        ),
+        (None, None) => None,
+    };
+
+    let mut messages = Vec::new();
+
+    if let Some(system_message) = system_message {
+        messages.push(Message::system(system_message));
+    }
+
+    messages.push(Message::user(&request.user_message));
+
+    let results = join_all(
+        request
+            .variants
+            .iter()
+            .map(|variant| execute_variant(&state, variant, &messages)),
+    )
+    .await;
+
+    Ok(Json(PlaygroundExecuteResponse { results }))
+}
+
+async fn execute_variant(
+    state: &AppState,
+    variant: &PlaygroundVariant,
+    messages: &[Message],
+) -> PlaygroundResult {
+    let start = Instant::now();
+    let mut result = PlaygroundResult {
+        label: variant
+            .label
+            .clone()
+            .unwrap_or_else(|| variant.model_id.clone()),
+        model_id: variant.model_id.clone(),
+        output: None,
+        error: None,
+        latency_ms: 0,
+        usage: None,
+        cost_micros: None,
+    };
+
+    let model = match state.model_service.get(&variant.model_id).await {
+        Ok(Some(model)) if model.is_enabled() => model,
+        Ok(Some(_)) => {
+            result.error = Some(format!("Model '{}' is disabled", variant.model_id));
+            return result;
+        }
+        Ok(None) => {
+            result.error = Some(format!("Model '{}' not found", variant.model_id));
+            return result;
+        }
+        Err(e) => {
+            result.error = Some(e.to_string());
+            return result;
+        }
+    };
+
+    let model_config = model.config();
+    let mut builder = LlmRequest::builder().messages(messages.to_vec());
+
+    if let Some(temperature) = variant.temperature.or(model_config.temperature) {
+        builder = builder.temperature(temperature);
+    }
+
+    if let Some(max_tokens) = variant.max_tokens.or(model_config.max_tokens) {
+        builder = builder.max_tokens(max_tokens);
+    }
+
+    if let Some(top_p) = variant.top_p.or(model_config.top_p) {
+        builder = builder.top_p(top_p);
+    }
+
+    if let Some(presence_penalty) = model_config.presence_penalty {
+        builder = builder.presence_penalty(presence_penalty);
+    }
+
+    if let Some(frequency_penalty) = model_config.frequency_penalty {
+        builder = builder.frequency_penalty(frequency_penalty);
+    }
+
+    let provider = get_provider_for_model(state, &model).await;
+    let response = provider.chat(model.provider_model(), builder.build()).await;
+    result.latency_ms = start.elapsed().as_millis() as u64;
+
+    match response {
+        Ok(response) => {
+            result.output = Some(response.content().unwrap_or("").to_string());
+
+            if let Some(usage) = &response.usage {
+                result.usage = Some(ExecuteModelUsage {
+                    prompt_tokens: usage.prompt_tokens,
+                    completion_tokens: usage.completion_tokens,
+                    total_tokens: usage.total_tokens,
+                });
+                result.cost_micros =
+                    state
+                        .usage_service
+                        .get_pricing(model.provider_model())
+                        .map(|pricing| {
+                            pricing.calculate_cost(usage.prompt_tokens, usage.completion_tokens)
+                        });
+            }
+        }
+        Err(e) => result.error = Some(e.to_string()),
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(variants: usize) -> PlaygroundExecuteRequest {
+        PlaygroundExecuteRequest {
+            prompt_id: None,
+            prompt: None,
+            variables: HashMap::new(),
+            user_message: "Hello".to_string(),
+            variants: (0..variants)
+                .map(|i| PlaygroundVariant {
+                    model_id: format!("model-{}", i),
+                    label: None,
+                    temperature: None,
+                    max_tokens: None,
+                    top_p: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_request_deserialization() {
+        let json = r#"{
+            "prompt": "You are ${var:tone:friendly}",
+            "variables": {"tone": "terse"},
+            "user_message": "Hi",
+            "variants": [
+                {"model_id": "gpt-4"},
+                {"model_id": "gpt-4", "label": "hot", "temperature": 1.2, "max_tokens": 50}
+            ]
+        }"#;
+
+        let request: PlaygroundExecuteRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            request.prompt.as_deref(),
+            Some("You are ${var:tone:friendly}")
+        );
+        assert_eq!(request.variants.len(), 2);
+        assert_eq!(request.variants[1].label.as_deref(), Some("hot"));
+        assert_eq!(request.variants[1].temperature, Some(1.2));
+        assert_eq!(request.variants[1].max_tokens, Some(50));
+    }
+
+    #[test]
+    fn test_request_validation() {
+        assert!(request(0).validate().is_err());
+        assert!(request(1).validate().is_ok());
+        assert!(request(MAX_PLAYGROUND_VARIANTS).validate().is_ok());
+        assert!(request(MAX_PLAYGROUND_VARIANTS + 1).validate().is_err());
+    }
+}
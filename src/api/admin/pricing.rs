@@ -0,0 +1,352 @@
+//! Model pricing catalog admin endpoints
+
+use axum::extract::{Path, State};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::usage::{ModelPricing, PricingId, PricingSource, PricingTier};
+use crate::infrastructure::usage::PricingSyncReport;
+
+// ============================================================================
+// Pricing DTOs
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PricingTierRequest {
+    pub min_tokens: u64,
+    pub input_per_1k: f64,
+    pub output_per_1k: f64,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetPricingRequest {
+    pub model_id: String,
+    pub provider: String,
+    pub input_per_1k: f64,
+    pub output_per_1k: f64,
+    #[serde(default)]
+    pub tiers: Vec<PricingTierRequest>,
+    /// Unix timestamp the price takes effect at, defaults to now
+    pub effective_from: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdatePricingRequest {
+    pub provider: Option<String>,
+    pub input_per_1k: f64,
+    pub output_per_1k: f64,
+    #[serde(default)]
+    pub tiers: Vec<PricingTierRequest>,
+    /// Unix timestamp the price takes effect at, defaults to now
+    pub effective_from: Option<u64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PricingTierResponse {
+    pub min_tokens: u64,
+    pub input_per_1k: f64,
+    pub output_per_1k: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PricingResponse {
+    pub id: String,
+    pub model_id: String,
+    pub provider: String,
+    pub input_per_1k: f64,
+    pub output_per_1k: f64,
+    pub tiers: Vec<PricingTierResponse>,
+    pub currency: String,
+    pub source: String,
+    pub effective_from: Option<u64>,
+    pub effective_until: Option<u64>,
+}
+
+impl From<ModelPricing> for PricingResponse {
+    fn from(pricing: ModelPricing) -> Self {
+        Self {
+            id: pricing.id.to_string(),
+            input_per_1k: pricing.input_price_per_1k(),
+            output_per_1k: pricing.output_price_per_1k(),
+            tiers: pricing
+                .tiers
+                .iter()
+                .map(|tier| PricingTierResponse {
+                    min_tokens: tier.min_tokens,
+                    input_per_1k: tier.input_price_per_1k(),
+                    output_per_1k: tier.output_price_per_1k(),
+                })
+                .collect(),
+            source: pricing.source.to_string(),
+            model_id: pricing.model_id,
+            provider: pricing.provider,
+            currency: pricing.currency,
+            effective_from: pricing.effective_from,
+            effective_until: pricing.effective_until,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PricingListResponse {
+    pub pricing: Vec<PricingResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ModelPricingHistoryResponse {
+    pub model_id: String,
+    pub current: Option<PricingResponse>,
+    pub versions: Vec<PricingResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PricingSyncResponse {
+    pub added: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub skipped_manual: usize,
+}
+
+impl From<PricingSyncReport> for PricingSyncResponse {
+    fn from(report: PricingSyncReport) -> Self {
+        Self {
+            added: report.added,
+            updated: report.updated,
+            unchanged: report.unchanged,
+            skipped_manual: report.skipped_manual,
+        }
+    }
+}
+
+fn build_pricing(
+    model_id: String,
+    provider: String,
+    input_per_1k: f64,
+    output_per_1k: f64,
+    tiers: Vec<PricingTierRequest>,
+    effective_from: Option<u64>,
+) -> ModelPricing {
+    let mut pricing = ModelPricing::new(model_id, provider, input_per_1k, output_per_1k)
+        .with_source(PricingSource::Manual);
+
+    for tier in tiers {
+        pricing = pricing.with_tier(PricingTier::new(
+            tier.min_tokens,
+            tier.input_per_1k,
+            tier.output_per_1k,
+        ));
+    }
+
+    match effective_from {
+        Some(from) => pricing.with_effective_from(from),
+        None => pricing,
+    }
+}
+
+// ============================================================================
+// Pricing Endpoints
+// ============================================================================
+
+/// List the current pricing of every model
+#[utoipa::path(
+    get,
+    path = "/admin/pricing",
+    tag = "admin/pricing",
+    responses((status = 200, body = PricingListResponse)),
+)]
+pub async fn list_pricing(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+) -> Result<Json<PricingListResponse>, ApiError> {
+    let pricing: Vec<PricingResponse> = state
+        .pricing_service
+        .list_current()
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    let total = pricing.len();
+
+    Ok(Json(PricingListResponse { pricing, total }))
+}
+
+/// Add a pricing version for a model
+#[utoipa::path(
+    post,
+    path = "/admin/pricing",
+    tag = "admin/pricing",
+    request_body = SetPricingRequest,
+    responses((status = 200, body = PricingResponse)),
+)]
+pub async fn create_pricing(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Json(request): Json<SetPricingRequest>,
+) -> Result<Json<PricingResponse>, ApiError> {
+    let pricing = build_pricing(
+        request.model_id,
+        request.provider,
+        request.input_per_1k,
+        request.output_per_1k,
+        request.tiers,
+        request.effective_from,
+    );
+
+    let created = state.pricing_service.set_pricing(pricing).await?;
+
+    Ok(Json(created.into()))
+}
+
+/// Get the current pricing and pricing history of a model
+#[utoipa::path(
+    get,
+    path = "/admin/pricing/{model_id}",
+    tag = "admin/pricing",
+    params(("model_id" = String, Path, description = "Model ID")),
+    responses((status = 200, body = ModelPricingHistoryResponse)),
+)]
+pub async fn get_pricing(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Path(model_id): Path<String>,
+) -> Result<Json<ModelPricingHistoryResponse>, ApiError> {
+    let versions = state.pricing_service.history(&model_id);
+
+    if versions.is_empty() {
+        return Err(ApiError::not_found(format!(
+            "No pricing for model '{}'",
+            model_id
+        )));
+    }
+
+    let current = state.usage_service.get_pricing(&model_id).map(Into::into);
+
+    Ok(Json(ModelPricingHistoryResponse {
+        model_id,
+        current,
+        versions: versions.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Change the price of a model, keeping previous prices as history
+#[utoipa::path(
+    put,
+    path = "/admin/pricing/{model_id}",
+    tag = "admin/pricing",
+    params(("model_id" = String, Path, description = "Model ID")),
+    request_body = UpdatePricingRequest,
+    responses((status = 200, body = PricingResponse)),
+)]
+pub async fn update_pricing(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Path(model_id): Path<String>,
+    Json(request): Json<UpdatePricingRequest>,
+) -> Result<Json<PricingResponse>, ApiError> {
+    let provider = match request.provider {
+        Some(provider) => provider,
+        None => state
+            .usage_service
+            .get_pricing(&model_id)
+            .map(|p| p.provider)
+            .ok_or_else(|| {
+                ApiError::bad_request(format!(
+                    "Model '{}' has no pricing yet, provider is required",
+                    model_id
+                ))
+            })?,
+    };
+
+    let pricing = build_pricing(
+        model_id,
+        provider,
+        request.input_per_1k,
+        request.output_per_1k,
+        request.tiers,
+        request.effective_from,
+    );
+
+    let updated = state.pricing_service.set_pricing(pricing).await?;
+
+    Ok(Json(updated.into()))
+}
+
+/// Delete every pricing version of a model
+#[utoipa::path(
+    delete,
+    path = "/admin/pricing/{model_id}",
+    tag = "admin/pricing",
+    params(("model_id" = String, Path, description = "Model ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
+pub async fn delete_pricing(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Path(model_id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let deleted = state.pricing_service.delete_model(&model_id).await?;
+
+    if deleted > 0 {
+        Ok(Json(serde_json::json!({
+            "deleted": true,
+            "model_id": model_id,
+            "versions": deleted
+        })))
+    } else {
+        Err(ApiError::not_found(format!(
+            "No pricing for model '{}'",
+            model_id
+        )))
+    }
+}
+
+/// Delete a single pricing version of a model
+#[utoipa::path(
+    delete,
+    path = "/admin/pricing/{model_id}/versions/{pricing_id}",
+    tag = "admin/pricing",
+    params(
+        ("model_id" = String, Path, description = "Model ID"),
+        ("pricing_id" = String, Path, description = "Pricing ID"),
+    ),
+    responses((status = 200, body = serde_json::Value)),
+)]
+pub async fn delete_pricing_version(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Path((model_id, pricing_id)): Path<(String, String)>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let id = PricingId::new(&pricing_id);
+    let deleted = state.pricing_service.delete_version(&model_id, &id).await?;
+
+    if deleted {
+        Ok(Json(serde_json::json!({
+            "deleted": true,
+            "id": pricing_id
+        })))
+    } else {
+        Err(ApiError::not_found(format!(
+            "Pricing version '{}' not found",
+            pricing_id
+        )))
+    }
+}
+
+/// Synchronise prices from the configured pricing feed
+#[utoipa::path(
+    post,
+    path = "/admin/pricing/sync",
+    tag = "admin/pricing",
+    responses((status = 200, body = PricingSyncResponse)),
+)]
+pub async fn sync_pricing(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+) -> Result<Json<PricingSyncResponse>, ApiError> {
+    let report = state.pricing_service.sync_from_feed().await?;
+
+    Ok(Json(report.into()))
+}
@@ -0,0 +1,293 @@
+//! Privacy admin endpoints
+//!
+//! Data subject requests locate the records of an end user of client
+//! applications, identified by a value clients send in the request metadata,
+//! across usage records, captured execution logs, async operations and
+//! knowledge base documents, and export or erase them.
+
+use axum::extract::State;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::api::admin::execution_logs::ExecutionLogResponse;
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::privacy::{DEFAULT_USER_METADATA_KEY, DataSubject};
+use crate::domain::{
+    ExecutionLog, ExecutionLogQuery, FilterCondition, MetadataFilter, Operation, UsageQuery,
+    UsageRecord,
+};
+use crate::infrastructure::services::StoredDocument;
+
+// ============================================================================
+// Privacy DTOs
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DataSubjectRequest {
+    /// Identifier of the end user
+    pub user_id: String,
+    /// Request metadata key holding the identifier, `user_id` when unset
+    pub metadata_key: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct KnowledgeBaseDocuments {
+    pub knowledge_base_id: String,
+    pub documents: Vec<StoredDocument>,
+}
+
+/// A knowledge base that could not be searched or purged, e.g. because its
+/// provider does not support metadata filters
+#[derive(Debug, Serialize, ToSchema)]
+pub struct KnowledgeBaseFailure {
+    pub knowledge_base_id: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PrivacyExportResponse {
+    pub user_id: String,
+    pub metadata_key: String,
+    pub usage_records: Vec<UsageRecord>,
+    pub execution_logs: Vec<ExecutionLogResponse>,
+    pub operations: Vec<Operation>,
+    pub knowledge_base_documents: Vec<KnowledgeBaseDocuments>,
+    pub errors: Vec<KnowledgeBaseFailure>,
+}
+
+/// Number of records erased from each store
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PrivacyDeleteResponse {
+    pub user_id: String,
+    pub metadata_key: String,
+    pub usage_records: usize,
+    pub execution_logs: usize,
+    pub operations: usize,
+    pub knowledge_base_documents: usize,
+    pub errors: Vec<KnowledgeBaseFailure>,
+}
+
+fn data_subject(request: DataSubjectRequest) -> Result<DataSubject, ApiError> {
+    let metadata_key = request
+        .metadata_key
+        .unwrap_or_else(|| DEFAULT_USER_METADATA_KEY.to_string());
+
+    DataSubject::new(metadata_key, request.user_id)
+        .map_err(|e| ApiError::from(e).with_param("user_id"))
+}
+
+/// Knowledge base documents tagged with the identifier in their metadata
+fn subject_filter(subject: &DataSubject) -> MetadataFilter {
+    MetadataFilter::condition(FilterCondition::eq(
+        subject.metadata_key(),
+        subject.user_id(),
+    ))
+}
+
+/// Execution logs whose captured request belongs to the subject
+async fn subject_execution_logs(
+    state: &AppState,
+    subject: &DataSubject,
+) -> Result<Vec<ExecutionLog>, ApiError> {
+    let logs = state
+        .execution_log_service
+        .list(&ExecutionLogQuery::new())
+        .await?;
+
+    Ok(logs
+        .into_iter()
+        .filter(|log| {
+            log.input()
+                .is_some_and(|input| subject.matches_request(input))
+        })
+        .collect())
+}
+
+/// Async operations whose request belongs to the subject
+async fn subject_operations(
+    state: &AppState,
+    subject: &DataSubject,
+) -> Result<Vec<Operation>, ApiError> {
+    let operations = state.operation_service.list().await?;
+
+    Ok(operations
+        .into_iter()
+        .filter(|operation| subject.matches_request(operation.input()))
+        .collect())
+}
+
+// ============================================================================
+// Privacy endpoints
+// ============================================================================
+
+/// Export every record associated with an end user
+#[utoipa::path(
+    post,
+    path = "/admin/privacy/export",
+    tag = "admin/privacy",
+    request_body = DataSubjectRequest,
+    responses((status = 200, body = PrivacyExportResponse)),
+)]
+pub async fn export_subject_data(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Json(request): Json<DataSubjectRequest>,
+) -> Result<Json<PrivacyExportResponse>, ApiError> {
+    let subject = data_subject(request)?;
+
+    let usage_records = state
+        .usage_service
+        .query(&UsageQuery::new().with_tag(subject.metadata_key(), subject.user_id()))
+        .await?;
+
+    let execution_logs = subject_execution_logs(&state, &subject)
+        .await?
+        .into_iter()
+        .map(ExecutionLogResponse::from)
+        .collect();
+
+    let operations = subject_operations(&state, &subject).await?;
+
+    let mut knowledge_base_documents = Vec::new();
+    let mut errors = Vec::new();
+
+    for kb in state.knowledge_base_service.list().await? {
+        let kb_id = kb.id().as_str().to_string();
+
+        match state
+            .ingestion_service
+            .get_documents_by_filter(&kb_id, subject_filter(&subject))
+            .await
+        {
+            Ok(documents) if documents.is_empty() => {}
+            Ok(documents) => knowledge_base_documents.push(KnowledgeBaseDocuments {
+                knowledge_base_id: kb_id,
+                documents,
+            }),
+            Err(e) => {
+                warn!(knowledge_base_id = %kb_id, error = %e, "Failed to export knowledge base documents");
+                errors.push(KnowledgeBaseFailure {
+                    knowledge_base_id: kb_id,
+                    message: e.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(Json(PrivacyExportResponse {
+        user_id: subject.user_id().to_string(),
+        metadata_key: subject.metadata_key().to_string(),
+        usage_records,
+        execution_logs,
+        operations,
+        knowledge_base_documents,
+        errors,
+    }))
+}
+
+/// Erase every record associated with an end user
+#[utoipa::path(
+    post,
+    path = "/admin/privacy/delete",
+    tag = "admin/privacy",
+    request_body = DataSubjectRequest,
+    responses((status = 200, body = PrivacyDeleteResponse)),
+)]
+pub async fn delete_subject_data(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Json(request): Json<DataSubjectRequest>,
+) -> Result<Json<PrivacyDeleteResponse>, ApiError> {
+    let subject = data_subject(request)?;
+
+    let usage_records = state
+        .usage_service
+        .delete_by_tag(subject.metadata_key(), subject.user_id())
+        .await?;
+
+    let mut execution_logs = 0;
+    for log in subject_execution_logs(&state, &subject).await? {
+        if state
+            .execution_log_service
+            .delete(log.id().as_str())
+            .await?
+        {
+            execution_logs += 1;
+        }
+    }
+
+    let mut operations = 0;
+    for operation in subject_operations(&state, &subject).await? {
+        if state
+            .operation_service
+            .delete(operation.id().as_str())
+            .await?
+        {
+            operations += 1;
+        }
+    }
+
+    let mut knowledge_base_documents = 0;
+    let mut errors = Vec::new();
+
+    for kb in state.knowledge_base_service.list().await? {
+        let kb_id = kb.id().as_str().to_string();
+
+        match state
+            .ingestion_service
+            .delete_by_filter(&kb_id, subject_filter(&subject))
+            .await
+        {
+            Ok(deleted) => knowledge_base_documents += deleted,
+            Err(e) => {
+                warn!(knowledge_base_id = %kb_id, error = %e, "Failed to delete knowledge base documents");
+                errors.push(KnowledgeBaseFailure {
+                    knowledge_base_id: kb_id,
+                    message: e.to_string(),
+                });
+            }
+        }
+    }
+
+    info!(
+        metadata_key = %subject.metadata_key(),
+        usage_records,
+        execution_logs,
+        operations,
+        knowledge_base_documents,
+        "Erased end user data"
+    );
+
+    Ok(Json(PrivacyDeleteResponse {
+        user_id: subject.user_id().to_string(),
+        metadata_key: subject.metadata_key().to_string(),
+        usage_records,
+        execution_logs,
+        operations,
+        knowledge_base_documents,
+        errors,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_subject_defaults_metadata_key() {
+        let request: DataSubjectRequest =
+            serde_json::from_str(r#"{"user_id": "user-42"}"#).unwrap();
+        let subject = data_subject(request).unwrap();
+        assert_eq!(subject.metadata_key(), DEFAULT_USER_METADATA_KEY);
+        assert_eq!(subject.user_id(), "user-42");
+
+        let request: DataSubjectRequest =
+            serde_json::from_str(r#"{"user_id": "", "metadata_key": "customer"}"#).unwrap();
+        let error = data_subject(request).unwrap_err();
+        assert_eq!(error.status, axum::http::StatusCode::BAD_REQUEST);
+        assert_eq!(error.response.error.param.as_deref(), Some("user_id"));
+    }
+}
@@ -2,17 +2,29 @@
 
 use axum::extract::{Path, State};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use tracing::debug;
+use utoipa::ToSchema;
 
-use crate::api::middleware::RequireAdmin;
+use crate::api::middleware::{estimate_prompt_tokens, RequireAdmin};
 use crate::api::state::AppState;
 use crate::api::types::{ApiError, Json};
-use crate::domain::prompt::{Prompt, PromptOutputSchema, PromptVersion};
+use crate::domain::cache::CachePolicy;
+use crate::domain::llm::Message;
+use crate::domain::prompt::{
+    lint_template, LintCode, LintIssue, Prompt, PromptOutputSchema, PromptTemplate, PromptVariable,
+    PromptVersion, VariableSchema,
+};
+
+use super::bulk::{ensure_bulk_size, BulkResponse};
+use super::models::PromptVariableInfo;
+use crate::domain::test_case::{
+    line_diff, DiffLine, DiffOp, RegressionPolicy, RegressionReport, RegressionSubject,
+};
 use crate::infrastructure::services::{CreatePromptRequest, UpdatePromptRequest};
 
 /// Output schema for API requests/responses
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct OutputSchemaApi {
     pub name: String,
     #[serde(default)]
@@ -41,7 +53,8 @@ impl From<OutputSchemaApi> for PromptOutputSchema {
 }
 
 /// Request to create a new prompt
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Serialize))]
 pub struct CreatePromptApiRequest {
     pub id: String,
     pub name: String,
@@ -52,27 +65,88 @@ pub struct CreatePromptApiRequest {
     pub tags: Vec<String>,
     #[serde(default)]
     pub output_schema: Option<OutputSchemaApi>,
+    /// Response caching of requests using the prompt
+    #[serde(default)]
+    pub cache_policy: Option<CachePolicy>,
+    /// Types, descriptions and requiredness of the template variables
+    #[serde(default)]
+    pub variables: Vec<PromptVariable>,
+    /// Content variants by locale tag (`es`, `pt-BR`)
+    #[serde(default)]
+    pub locales: BTreeMap<String, String>,
 }
 
 /// Request to update a prompt
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Serialize))]
 pub struct UpdatePromptApiRequest {
     pub name: Option<String>,
     pub content: Option<String>,
     pub description: Option<String>,
     pub tags: Option<Vec<String>>,
     pub output_schema: Option<OutputSchemaApi>,
+    #[serde(default)]
+    pub cache_policy: Option<CachePolicy>,
+    /// Replaces the variable declarations as a whole
+    #[serde(default)]
+    pub variables: Option<Vec<PromptVariable>>,
+    /// Replaces the locale variants as a whole
+    #[serde(default)]
+    pub locales: Option<BTreeMap<String, String>>,
+    /// Note describing the content change, kept on the new version
+    #[serde(default)]
+    pub change_note: Option<String>,
+    /// Run the linked test cases against the new content first and block
+    /// the update if it introduces more regressions than allowed
+    #[serde(default)]
+    pub regression_check: Option<RegressionPolicy>,
+}
+
+/// Request to clone a prompt
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Serialize))]
+pub struct ClonePromptRequest {
+    /// New ID for the cloned prompt
+    pub new_id: String,
+    /// New name for the cloned prompt (optional, defaults to "Copy of {original_name}")
+    #[serde(default)]
+    pub new_name: Option<String>,
+}
+
+/// One operation of a bulk prompt request
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BulkPromptOperation {
+    Create(CreatePromptApiRequest),
+    Update {
+        id: String,
+        changes: UpdatePromptApiRequest,
+    },
+    Delete {
+        id: String,
+    },
+}
+
+/// Request to create, update and delete prompts in one call
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct BulkPromptsRequest {
+    pub operations: Vec<BulkPromptOperation>,
 }
 
 /// Request to render a prompt
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Serialize))]
 pub struct RenderPromptApiRequest {
     #[serde(default)]
     pub variables: HashMap<String, String>,
+    /// Accepted locales (`pt-BR, es;q=0.8`) selecting the variant to render
+    #[serde(default)]
+    pub locale: Option<String>,
 }
 
 /// Prompt response for admin API
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 pub struct PromptResponse {
     pub id: String,
     pub name: String,
@@ -81,10 +155,27 @@ pub struct PromptResponse {
     pub tags: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub output_schema: Option<OutputSchemaApi>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_policy: Option<CachePolicy>,
+    /// Declared template variables
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<PromptVariable>,
+    /// Content variants by normalized locale tag
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub locales: BTreeMap<String, String>,
     pub version: u32,
+    /// Change note of the current version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_note: Option<String>,
+    /// Who made the change that produced the current version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
     pub enabled: bool,
     pub created_at: String,
     pub updated_at: String,
+    /// Comparison against the previous version, when a check was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regression_report: Option<RegressionReport>,
 }
 
 impl From<&Prompt> for PromptResponse {
@@ -96,34 +187,91 @@ impl From<&Prompt> for PromptResponse {
             description: prompt.description().map(String::from),
             tags: prompt.tags().to_vec(),
             output_schema: prompt.output_schema().map(OutputSchemaApi::from),
+            cache_policy: prompt.cache_policy().cloned(),
+            variables: prompt.variables().to_vec(),
+            locales: prompt.locales().clone(),
             version: prompt.version(),
+            change_note: prompt.change_note().map(String::from),
+            author: prompt.author().map(String::from),
             enabled: prompt.is_enabled(),
             created_at: prompt.created_at().to_rfc3339(),
             updated_at: prompt.updated_at().to_rfc3339(),
+            regression_report: None,
         }
     }
 }
 
+/// List regression reports response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListRegressionReportsResponse {
+    pub reports: Vec<RegressionReport>,
+    pub total: usize,
+}
+
 /// List prompts response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 pub struct ListPromptsResponse {
     pub prompts: Vec<PromptResponse>,
     pub total: usize,
 }
 
 /// Render prompt response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 pub struct RenderPromptResponse {
     pub rendered: String,
 }
 
+/// Request to lint a prompt template
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ValidatePromptApiRequest {
+    pub content: String,
+    /// JSON Schema object declaring the variables the template may use
+    #[serde(default)]
+    pub schema: Option<serde_json::Value>,
+    /// Models to estimate the prompt for, all enabled models when omitted
+    #[serde(default)]
+    pub model_ids: Option<Vec<String>>,
+}
+
+/// Estimated size of a rendered template for a model
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PromptTokenEstimate {
+    pub model_id: String,
+    pub estimated_tokens: u32,
+    /// Input cost in micro-dollars, none without pricing for the model
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_cost_micros: Option<i64>,
+}
+
+/// Variable schema of a prompt
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
+pub struct PromptVariablesResponse {
+    pub prompt_id: String,
+    pub variables: Vec<PromptVariable>,
+}
+
+/// Prompt lint response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ValidatePromptResponse {
+    /// Whether the template has no error-level issue
+    pub valid: bool,
+    pub variables: Vec<PromptVariableInfo>,
+    pub issues: Vec<LintIssue>,
+    pub token_estimates: Vec<PromptTokenEstimate>,
+}
+
 /// Response for a single prompt version
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct PromptVersionResponse {
     pub version: u32,
     pub content: String,
     pub created_at: String,
     pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
 }
 
 impl From<&PromptVersion> for PromptVersionResponse {
@@ -133,19 +281,58 @@ impl From<&PromptVersion> for PromptVersionResponse {
             content: v.content().to_string(),
             created_at: v.created_at().to_rfc3339(),
             message: v.message().map(String::from),
+            author: v.author().map(String::from),
         }
     }
 }
 
+/// Metadata of one side of a version diff
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PromptVersionInfo {
+    pub version: u32,
+    pub created_at: String,
+    /// Change note of the version
+    pub message: Option<String>,
+    pub author: Option<String>,
+}
+
+impl From<&PromptVersion> for PromptVersionInfo {
+    fn from(v: &PromptVersion) -> Self {
+        Self {
+            version: v.version(),
+            created_at: v.created_at().to_rfc3339(),
+            message: v.message().map(String::from),
+            author: v.author().map(String::from),
+        }
+    }
+}
+
+/// Line diff between two prompt versions
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PromptVersionDiffResponse {
+    pub prompt_id: String,
+    pub from: PromptVersionInfo,
+    pub to: PromptVersionInfo,
+    pub additions: usize,
+    pub deletions: usize,
+    pub diff: Vec<DiffLine>,
+}
+
 /// List versions response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListVersionsResponse {
     pub current_version: u32,
     pub versions: Vec<PromptVersionResponse>,
     pub total: usize,
 }
 
-/// GET /admin/prompts
+/// List prompts
+#[utoipa::path(
+    get,
+    path = "/admin/prompts",
+    tag = "admin/prompts",
+    responses((status = 200, body = ListPromptsResponse)),
+)]
 pub async fn list_prompts(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -164,7 +351,14 @@ pub async fn list_prompts(
     }))
 }
 
-/// POST /admin/prompts
+/// Create prompt
+#[utoipa::path(
+    post,
+    path = "/admin/prompts",
+    tag = "admin/prompts",
+    request_body = CreatePromptApiRequest,
+    responses((status = 200, body = PromptResponse)),
+)]
 pub async fn create_prompt(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -172,6 +366,15 @@ pub async fn create_prompt(
 ) -> Result<Json<PromptResponse>, ApiError> {
     debug!(prompt_id = %request.id, "Admin creating prompt");
 
+    let prompt = apply_create(&state, request).await?;
+
+    Ok(Json(PromptResponse::from(&prompt)))
+}
+
+async fn apply_create(
+    state: &AppState,
+    request: CreatePromptApiRequest,
+) -> Result<Prompt, ApiError> {
     let create_request = CreatePromptRequest {
         id: request.id,
         name: request.name,
@@ -181,18 +384,26 @@ pub async fn create_prompt(
         enabled: true,
         max_history: None,
         output_schema: request.output_schema.map(Into::into),
+        cache_policy: request.cache_policy,
+        variables: request.variables,
+        locales: request.locales,
     };
 
-    let prompt = state
+    state
         .prompt_service
         .create(create_request)
         .await
-        .map_err(ApiError::from)?;
-
-    Ok(Json(PromptResponse::from(&prompt)))
+        .map_err(ApiError::from)
 }
 
-/// GET /admin/prompts/:prompt_id
+/// Get prompt
+#[utoipa::path(
+    get,
+    path = "/admin/prompts/{prompt_id}",
+    tag = "admin/prompts",
+    params(("prompt_id" = String, Path, description = "Prompt ID")),
+    responses((status = 200, body = PromptResponse)),
+)]
 pub async fn get_prompt(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -210,35 +421,78 @@ pub async fn get_prompt(
     Ok(Json(PromptResponse::from(&prompt)))
 }
 
-/// PUT /admin/prompts/:prompt_id
+/// Update prompt
+#[utoipa::path(
+    put,
+    path = "/admin/prompts/{prompt_id}",
+    tag = "admin/prompts",
+    params(("prompt_id" = String, Path, description = "Prompt ID")),
+    request_body = UpdatePromptApiRequest,
+    responses((status = 200, body = PromptResponse)),
+)]
 pub async fn update_prompt(
     State(state): State<AppState>,
-    RequireAdmin(_): RequireAdmin,
+    RequireAdmin(admin): RequireAdmin,
     Path(prompt_id): Path<String>,
     Json(request): Json<UpdatePromptApiRequest>,
 ) -> Result<Json<PromptResponse>, ApiError> {
     debug!(prompt_id = %prompt_id, "Admin updating prompt");
 
+    apply_update(&state, admin.identifier(), &prompt_id, request)
+        .await
+        .map(Json)
+}
+
+async fn apply_update(
+    state: &AppState,
+    author: String,
+    prompt_id: &str,
+    request: UpdatePromptApiRequest,
+) -> Result<PromptResponse, ApiError> {
+    let regression_report = match (&request.regression_check, &request.content) {
+        (Some(policy), Some(content)) => state
+            .regression_service
+            .check_prompt(prompt_id, content, policy)
+            .await
+            .map_err(ApiError::from)?,
+        _ => None,
+    };
+    ensure_not_blocked(regression_report.as_ref())?;
+
     let update_request = UpdatePromptRequest {
         name: request.name,
         description: request.description,
         content: request.content,
-        content_message: None,
+        content_message: request.change_note,
+        content_author: Some(author),
         tags: request.tags,
         enabled: None,
         output_schema: request.output_schema.map(Into::into),
+        cache_policy: request.cache_policy,
+        variables: request.variables,
+        locales: request.locales,
     };
 
     let prompt = state
         .prompt_service
-        .update(&prompt_id, update_request)
+        .update(prompt_id, update_request)
         .await
         .map_err(ApiError::from)?;
 
-    Ok(Json(PromptResponse::from(&prompt)))
+    let mut response = PromptResponse::from(&prompt);
+    response.regression_report = regression_report;
+
+    Ok(response)
 }
 
-/// DELETE /admin/prompts/:prompt_id
+/// Delete prompt
+#[utoipa::path(
+    delete,
+    path = "/admin/prompts/{prompt_id}",
+    tag = "admin/prompts",
+    params(("prompt_id" = String, Path, description = "Prompt ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn delete_prompt(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -258,7 +512,110 @@ pub async fn delete_prompt(
     })))
 }
 
-/// POST /admin/prompts/:prompt_id/render
+/// Clone a prompt with a new ID, starting from the current version
+#[utoipa::path(
+    post,
+    path = "/admin/prompts/{prompt_id}/clone",
+    tag = "admin/prompts",
+    params(("prompt_id" = String, Path, description = "Prompt ID")),
+    request_body = ClonePromptRequest,
+    responses((status = 200, body = PromptResponse)),
+)]
+pub async fn clone_prompt(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(prompt_id): Path<String>,
+    Json(request): Json<ClonePromptRequest>,
+) -> Result<Json<PromptResponse>, ApiError> {
+    debug!(prompt_id = %prompt_id, new_id = %request.new_id, "Admin cloning prompt");
+
+    let original = state
+        .prompt_service
+        .get(&prompt_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found(format!("Prompt '{}' not found", prompt_id)))?;
+
+    let new_name = request
+        .new_name
+        .unwrap_or_else(|| format!("Copy of {}", original.name()));
+
+    // Version history stays with the original
+    let create_request = CreatePromptRequest {
+        id: request.new_id,
+        name: new_name,
+        description: original.description().map(String::from),
+        content: original.content().to_string(),
+        tags: original.tags().to_vec(),
+        enabled: true,
+        max_history: Some(original.max_history()),
+        output_schema: original.output_schema().cloned(),
+        cache_policy: original.cache_policy().cloned(),
+        variables: original.variables().to_vec(),
+        locales: original.locales().clone(),
+    };
+
+    let cloned = state
+        .prompt_service
+        .create(create_request)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(PromptResponse::from(&cloned)))
+}
+
+/// Create, update and delete prompts in one call, reporting each operation
+#[utoipa::path(
+    post,
+    path = "/admin/prompts/bulk",
+    tag = "admin/prompts",
+    request_body = BulkPromptsRequest,
+    responses((status = 200, body = BulkResponse)),
+)]
+pub async fn bulk_prompts(
+    State(state): State<AppState>,
+    RequireAdmin(admin): RequireAdmin,
+    Json(request): Json<BulkPromptsRequest>,
+) -> Result<Json<BulkResponse>, ApiError> {
+    ensure_bulk_size("operations", request.operations.len())?;
+
+    debug!(operations = request.operations.len(), "Admin applying bulk prompt operations");
+
+    let mut response = BulkResponse::new();
+
+    for operation in request.operations {
+        match operation {
+            BulkPromptOperation::Create(request) => {
+                let id = request.id.clone();
+                response.record(id, apply_create(&state, request).await);
+            }
+            BulkPromptOperation::Update { id, changes } => {
+                let result = apply_update(&state, admin.identifier(), &id, changes).await;
+                response.record(id, result);
+            }
+            BulkPromptOperation::Delete { id } => {
+                let result = match state.prompt_service.delete(&id).await {
+                    Ok(true) => Ok(()),
+                    Ok(false) => Err(ApiError::not_found(format!("Prompt '{}' not found", id))),
+                    Err(e) => Err(ApiError::from(e)),
+                };
+                response.record(id, result);
+            }
+        }
+    }
+
+    Ok(Json(response))
+}
+
+/// Render prompt
+#[utoipa::path(
+    post,
+    path = "/admin/prompts/{prompt_id}/render",
+    tag = "admin/prompts",
+    params(("prompt_id" = String, Path, description = "Prompt ID")),
+    request_body = RenderPromptApiRequest,
+    responses((status = 200, body = RenderPromptResponse)),
+)]
 pub async fn render_prompt(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -269,14 +626,148 @@ pub async fn render_prompt(
 
     let rendered = state
         .prompt_service
-        .render(&prompt_id, &request.variables)
+        .render_variant(&prompt_id, None, request.locale.as_deref(), &request.variables)
         .await
         .map_err(ApiError::from)?;
 
     Ok(Json(RenderPromptResponse { rendered }))
 }
 
-/// GET /admin/prompts/:prompt_id/versions
+/// Variables of a prompt and its partials, with their declared types and
+/// descriptions, for generating input forms
+#[utoipa::path(
+    get,
+    path = "/admin/prompts/{prompt_id}/variables",
+    tag = "admin/prompts",
+    params(("prompt_id" = String, Path, description = "Prompt ID")),
+    responses((status = 200, body = PromptVariablesResponse)),
+)]
+pub async fn get_prompt_variables(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(prompt_id): Path<String>,
+) -> Result<Json<PromptVariablesResponse>, ApiError> {
+    debug!(prompt_id = %prompt_id, "Admin getting prompt variables");
+
+    let variables = state
+        .prompt_service
+        .variable_schema(&prompt_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(PromptVariablesResponse {
+        prompt_id,
+        variables,
+    }))
+}
+
+/// Lint a template and estimate its size per model without saving it
+#[utoipa::path(
+    post,
+    path = "/admin/prompts/validate",
+    tag = "admin/prompts",
+    request_body = ValidatePromptApiRequest,
+    responses((status = 200, body = ValidatePromptResponse)),
+)]
+pub async fn validate_prompt(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Json(request): Json<ValidatePromptApiRequest>,
+) -> Result<Json<ValidatePromptResponse>, ApiError> {
+    debug!("Admin validating prompt template");
+
+    let schema = request.schema.as_ref().map(VariableSchema::from_json_schema);
+
+    // Partials are linted as part of the template, unresolvable ones are errors
+    let (content, partial_issue) = match state
+        .prompt_service
+        .expand_partials(&request.content)
+        .await
+    {
+        Ok(content) => (content, None),
+        Err(e) => (
+            request.content.clone(),
+            Some(LintIssue::error(LintCode::InvalidPartial, e.to_string(), None)),
+        ),
+    };
+    let mut report = lint_template(&content, schema.as_ref());
+    report.issues.extend(partial_issue);
+
+    // Required variables count as empty, so estimates are a lower bound
+    let values: HashMap<String, String> = report
+        .variables
+        .iter()
+        .filter(|v| v.required)
+        .map(|v| (v.name.clone(), String::new()))
+        .collect();
+    let rendered = PromptTemplate::parse(content.as_str())
+        .and_then(|template| template.render(&values))
+        .unwrap_or(content);
+    let estimated_tokens = estimate_prompt_tokens(&[Message::system(rendered)]);
+
+    let models = match &request.model_ids {
+        Some(model_ids) => {
+            let mut models = Vec::with_capacity(model_ids.len());
+            for model_id in model_ids {
+                let model = state
+                    .model_service
+                    .get(model_id)
+                    .await
+                    .map_err(ApiError::from)?
+                    .ok_or_else(|| {
+                        ApiError::not_found(format!("Model '{}' not found", model_id))
+                            .with_param("model_ids")
+                    })?;
+                models.push(model);
+            }
+            models
+        }
+        None => state
+            .model_service
+            .list()
+            .await
+            .map_err(ApiError::from)?
+            .into_iter()
+            .filter(|model| model.is_enabled())
+            .collect(),
+    };
+
+    let token_estimates = models
+        .iter()
+        .map(|model| PromptTokenEstimate {
+            model_id: model.id().to_string(),
+            estimated_tokens,
+            estimated_cost_micros: state
+                .usage_service
+                .get_pricing(model.provider_model())
+                .map(|pricing| pricing.calculate_cost(estimated_tokens, 0)),
+        })
+        .collect();
+
+    Ok(Json(ValidatePromptResponse {
+        valid: report.is_valid(),
+        variables: report
+            .variables
+            .iter()
+            .map(|v| PromptVariableInfo {
+                name: v.name.clone(),
+                required: v.required,
+                default: v.default.clone(),
+            })
+            .collect(),
+        issues: report.issues,
+        token_estimates,
+    }))
+}
+
+/// List versions
+#[utoipa::path(
+    get,
+    path = "/admin/prompts/{prompt_id}/versions",
+    tag = "admin/prompts",
+    params(("prompt_id" = String, Path, description = "Prompt ID")),
+    responses((status = 200, body = ListVersionsResponse)),
+)]
 pub async fn list_versions(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -302,7 +793,66 @@ pub async fn list_versions(
     }))
 }
 
-/// POST /admin/prompts/:prompt_id/revert/:version
+/// Diff versions
+#[utoipa::path(
+    get,
+    path = "/admin/prompts/{prompt_id}/versions/{from}/diff/{to}",
+    tag = "admin/prompts",
+    params(
+        ("prompt_id" = String, Path, description = "Prompt ID"),
+        ("from" = u32, Path, description = "Version to compare from"),
+        ("to" = u32, Path, description = "Version to compare to"),
+    ),
+    responses((status = 200, body = PromptVersionDiffResponse)),
+)]
+pub async fn diff_versions(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path((prompt_id, from, to)): Path<(String, u32, u32)>,
+) -> Result<Json<PromptVersionDiffResponse>, ApiError> {
+    debug!(prompt_id = %prompt_id, from = from, to = to, "Admin diffing prompt versions");
+
+    let prompt = state
+        .prompt_service
+        .get(&prompt_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found(format!("Prompt '{}' not found", prompt_id)))?;
+
+    let snapshot = |version: u32| {
+        prompt.version_snapshot(version).ok_or_else(|| {
+            ApiError::not_found(format!(
+                "Version {} of prompt '{}' not found",
+                version, prompt_id
+            ))
+        })
+    };
+    let old = snapshot(from)?;
+    let new = snapshot(to)?;
+
+    let diff = line_diff(old.content(), new.content());
+
+    Ok(Json(PromptVersionDiffResponse {
+        prompt_id: prompt_id.clone(),
+        from: PromptVersionInfo::from(&old),
+        to: PromptVersionInfo::from(&new),
+        additions: diff.iter().filter(|l| l.op == DiffOp::Insert).count(),
+        deletions: diff.iter().filter(|l| l.op == DiffOp::Delete).count(),
+        diff,
+    }))
+}
+
+/// Revert to version
+#[utoipa::path(
+    post,
+    path = "/admin/prompts/{prompt_id}/revert/{version}",
+    tag = "admin/prompts",
+    params(
+        ("prompt_id" = String, Path, description = "Prompt ID"),
+        ("version" = u32, Path, description = "Version number"),
+    ),
+    responses((status = 200, body = PromptResponse)),
+)]
 pub async fn revert_to_version(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -319,6 +869,45 @@ pub async fn revert_to_version(
     Ok(Json(PromptResponse::from(&prompt)))
 }
 
+/// List regressions
+#[utoipa::path(
+    get,
+    path = "/admin/prompts/{prompt_id}/regressions",
+    tag = "admin/prompts",
+    operation_id = "list_prompt_regressions",
+    params(("prompt_id" = String, Path, description = "Prompt ID")),
+    responses((status = 200, body = ListRegressionReportsResponse)),
+)]
+pub async fn list_regressions(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(prompt_id): Path<String>,
+) -> Result<Json<ListRegressionReportsResponse>, ApiError> {
+    debug!(prompt_id = %prompt_id, "Admin listing prompt regression reports");
+
+    let reports = state
+        .regression_service
+        .list_reports(RegressionSubject::Prompt, &prompt_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(ListRegressionReportsResponse {
+        total: reports.len(),
+        reports,
+    }))
+}
+
+/// Reject an update whose regression report exceeds its policy
+pub(crate) fn ensure_not_blocked(report: Option<&RegressionReport>) -> Result<(), ApiError> {
+    match report {
+        Some(report) if report.blocked => Err(ApiError::conflict(format!(
+            "Update blocked by {} regressions (max {}), see regression report '{}'",
+            report.regressions, report.max_regressions, report.id
+        ))),
+        _ => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -356,6 +945,45 @@ mod tests {
         assert!(request.tags.is_empty());
     }
 
+    #[test]
+    fn test_bulk_prompts_request_deserialization() {
+        let json = r#"{
+            "operations": [
+                {"op": "create", "id": "greeting", "name": "Greeting", "content": "Hello"},
+                {"op": "update", "id": "farewell", "changes": {"content": "Bye"}},
+                {"op": "delete", "id": "legacy"}
+            ]
+        }"#;
+
+        let request: BulkPromptsRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.operations.len(), 3);
+        assert!(matches!(
+            &request.operations[0],
+            BulkPromptOperation::Create(create) if create.id == "greeting"
+        ));
+        assert!(matches!(
+            &request.operations[1],
+            BulkPromptOperation::Update { id, changes }
+                if id == "farewell" && changes.content.as_deref() == Some("Bye")
+        ));
+        assert!(matches!(
+            &request.operations[2],
+            BulkPromptOperation::Delete { id } if id == "legacy"
+        ));
+
+        let unknown = r#"{"operations": [{"op": "rename", "id": "greeting"}]}"#;
+        assert!(serde_json::from_str::<BulkPromptsRequest>(unknown).is_err());
+    }
+
+    #[test]
+    fn test_clone_prompt_request_deserialization() {
+        let json = r#"{"new_id": "greeting-v2"}"#;
+
+        let request: ClonePromptRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.new_id, "greeting-v2");
+        assert!(request.new_name.is_none());
+    }
+
     #[test]
     fn test_update_prompt_request_full() {
         let json = r#"{
@@ -372,6 +1000,15 @@ mod tests {
         assert_eq!(request.tags, Some(vec!["new-tag".to_string()]));
     }
 
+    #[test]
+    fn test_update_prompt_request_change_note() {
+        let json = r#"{"content": "New content", "change_note": "Tighten tone"}"#;
+
+        let request: UpdatePromptApiRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.content, Some("New content".to_string()));
+        assert_eq!(request.change_note, Some("Tighten tone".to_string()));
+    }
+
     #[test]
     fn test_update_prompt_request_partial() {
         let json = r#"{"name": "New Name"}"#;
@@ -394,6 +1031,25 @@ mod tests {
         assert_eq!(request.variables.get("greeting"), Some(&"Hello".to_string()));
     }
 
+    #[test]
+    fn test_validate_prompt_request_deserialization() {
+        let json = r#"{
+            "content": "Hello ${var:name}",
+            "schema": {"properties": {"name": {"type": "string"}}},
+            "model_ids": ["gpt-4"]
+        }"#;
+
+        let request: ValidatePromptApiRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.content, "Hello ${var:name}");
+        assert!(request.schema.is_some());
+        assert_eq!(request.model_ids, Some(vec!["gpt-4".to_string()]));
+
+        let request: ValidatePromptApiRequest =
+            serde_json::from_str(r#"{"content": "Hi"}"#).unwrap();
+        assert!(request.schema.is_none());
+        assert!(request.model_ids.is_none());
+    }
+
     #[test]
     fn test_render_prompt_request_empty() {
         let json = r#"{}"#;
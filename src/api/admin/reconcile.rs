@@ -0,0 +1,660 @@
+//! Declarative state admin endpoint
+//!
+//! Clients such as a Terraform provider or GitOps controller declare the full
+//! set of teams, external APIs, models, prompts and workflows in one request.
+//! The gateway diffs it against the stored entities and either reports the
+//! plan or applies it through the same code paths as the CRUD endpoints.
+//!
+//! Sections left out of the request are not managed. Within a declared
+//! section, stored entities that are not declared are deleted, and fields
+//! that are null or absent keep their stored value.
+
+use std::collections::{BTreeMap, HashMap};
+
+use axum::extract::{Path, State};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::api::admin::external_apis::{
+    self, CreateExternalApiRequest, ExternalApiResponse, UpdateExternalApiRequest,
+};
+use crate::api::admin::models::{
+    self, CreateModelApiRequest, ModelConfigRequest, ModelResponse, UpdateModelApiRequest,
+};
+use crate::api::admin::prompts::{
+    self, CreatePromptApiRequest, OutputSchemaApi, PromptResponse, UpdatePromptApiRequest,
+};
+use crate::api::admin::teams::{self, CreateTeamApiRequest, TeamResponse, UpdateTeamApiRequest};
+use crate::api::admin::workflows::{
+    self, CreateWorkflowApiRequest, UpdateWorkflowApiRequest, WorkflowResponse,
+    WorkflowStepApiRequest,
+};
+use crate::api::middleware::{AdminAuth, RequireAdmin};
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::cache::CachePolicy;
+use crate::domain::prompt::PromptVariable;
+use crate::domain::reconcile::{
+    ChangeAction, EntityChange, EntityKind, ReconcileMode, diff_entities, overlay,
+};
+use crate::domain::team::{TeamId, TeamQuota};
+
+// ============================================================================
+// Declared entities
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TeamSpec {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub allowed_cidrs: Option<Vec<String>>,
+    pub allowed_regions: Option<Vec<String>>,
+    pub no_log: Option<bool>,
+    /// Declared limits; limits left out keep their stored value
+    pub quota: Option<TeamQuota>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExternalApiSpec {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub base_url: String,
+    pub base_headers: Option<HashMap<String, String>>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ModelSpec {
+    pub id: String,
+    pub name: String,
+    /// Provider of the model; it cannot change once the model exists
+    pub provider: String,
+    pub provider_model: String,
+    pub credential_id: String,
+    pub enabled: Option<bool>,
+    pub config: Option<ModelConfigRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PromptSpec {
+    pub id: String,
+    pub name: String,
+    pub content: String,
+    pub description: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub output_schema: Option<OutputSchemaApi>,
+    pub cache_policy: Option<CachePolicy>,
+    pub variables: Option<Vec<PromptVariable>>,
+    /// Content variants by lowercase locale tag
+    pub locales: Option<BTreeMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WorkflowSpec {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub input_schema: Option<Value>,
+    /// Steps in the format of the workflow endpoints, replaced as a whole
+    #[schema(value_type = Option<Vec<WorkflowStepApiRequest>>)]
+    pub steps: Option<Vec<Value>>,
+    pub enabled: Option<bool>,
+    /// Owning team; it cannot change once the workflow exists
+    pub team_id: Option<String>,
+}
+
+// ============================================================================
+// Reconcile DTOs
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReconcileStateRequest {
+    /// `plan` reports the changes, `apply` also performs them
+    #[serde(default)]
+    pub mode: ReconcileMode,
+    pub teams: Option<Vec<TeamSpec>>,
+    pub external_apis: Option<Vec<ExternalApiSpec>>,
+    pub models: Option<Vec<ModelSpec>>,
+    pub prompts: Option<Vec<PromptSpec>>,
+    pub workflows: Option<Vec<WorkflowSpec>>,
+}
+
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct ReconcileSummary {
+    pub create: usize,
+    pub update: usize,
+    pub delete: usize,
+}
+
+/// The change that could not be applied
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReconcileFailure {
+    pub kind: EntityKind,
+    pub id: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReconcileStateResponse {
+    pub mode: ReconcileMode,
+    /// Changes in the order they are applied
+    pub changes: Vec<EntityChange>,
+    pub summary: ReconcileSummary,
+    /// Number of changes performed; applying stops at the first failure
+    pub applied: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ReconcileFailure>,
+}
+
+fn section(kind: EntityKind) -> &'static str {
+    match kind {
+        EntityKind::Team => "teams",
+        EntityKind::ExternalApi => "external_apis",
+        EntityKind::Model => "models",
+        EntityKind::Prompt => "prompts",
+        EntityKind::Workflow => "workflows",
+    }
+}
+
+fn to_values<T: Serialize>(items: impl IntoIterator<Item = T>) -> Result<Vec<Value>, ApiError> {
+    items
+        .into_iter()
+        .map(|item| serde_json::to_value(item).map_err(|e| ApiError::internal(e.to_string())))
+        .collect()
+}
+
+/// Declared entities of a kind, or `None` when the section is not managed
+fn declared_entities(
+    request: &ReconcileStateRequest,
+    kind: EntityKind,
+) -> Result<Option<Vec<Value>>, ApiError> {
+    match kind {
+        EntityKind::Team => request.teams.as_ref().map(to_values).transpose(),
+        EntityKind::ExternalApi => request.external_apis.as_ref().map(to_values).transpose(),
+        EntityKind::Model => request.models.as_ref().map(to_values).transpose(),
+        EntityKind::Prompt => request.prompts.as_ref().map(to_values).transpose(),
+        EntityKind::Workflow => request.workflows.as_ref().map(to_values).transpose(),
+    }
+}
+
+/// Stored entities of a kind, in the format of the admin API responses
+async fn stored_entities(state: &AppState, kind: EntityKind) -> Result<Vec<Value>, ApiError> {
+    match kind {
+        EntityKind::Team => to_values(
+            state
+                .team_service
+                .list(None)
+                .await?
+                .iter()
+                .map(TeamResponse::from),
+        ),
+        EntityKind::ExternalApi => to_values(
+            state
+                .external_api_service
+                .list()
+                .await?
+                .iter()
+                .map(ExternalApiResponse::from),
+        ),
+        EntityKind::Model => to_values(
+            state
+                .model_service
+                .list()
+                .await?
+                .iter()
+                .map(ModelResponse::from),
+        ),
+        EntityKind::Prompt => to_values(
+            state
+                .prompt_service
+                .list()
+                .await?
+                .iter()
+                .map(PromptResponse::from),
+        ),
+        EntityKind::Workflow => to_values(
+            state
+                .workflow_service
+                .list()
+                .await?
+                .iter()
+                .map(WorkflowResponse::from),
+        ),
+    }
+}
+
+/// Bring declared values to their stored format and check that they can be
+/// applied, so that a plan fails before any change is made
+fn normalize_request(request: &mut ReconcileStateRequest) -> Result<(), ApiError> {
+    for model in request.models.iter_mut().flatten() {
+        model.provider = models::canonical_provider(&model.provider)?;
+    }
+
+    for workflow in request.workflows.iter().flatten() {
+        workflow_steps(workflow)?;
+    }
+
+    Ok(())
+}
+
+fn workflow_steps(spec: &WorkflowSpec) -> Result<Option<Vec<WorkflowStepApiRequest>>, ApiError> {
+    spec.steps
+        .clone()
+        .map(|steps| {
+            steps
+                .into_iter()
+                .map(serde_json::from_value)
+                .collect::<Result<Vec<WorkflowStepApiRequest>, _>>()
+        })
+        .transpose()
+        .map_err(|e| {
+            ApiError::bad_request(format!("Invalid steps of workflow '{}': {}", spec.id, e))
+                .with_param("workflows")
+        })
+}
+
+/// Reject updates of fields the CRUD endpoints cannot change
+fn check_updatable(change: &EntityChange) -> Result<(), ApiError> {
+    let immutable = match change.kind {
+        EntityKind::Model => "provider",
+        EntityKind::Workflow => "team_id",
+        _ => return Ok(()),
+    };
+
+    if change.action == ChangeAction::Update && change.touches(immutable) {
+        return Err(ApiError::bad_request(format!(
+            "The {} of {} '{}' cannot be changed; delete and recreate it instead",
+            immutable, change.kind, change.id
+        ))
+        .with_param(section(change.kind)));
+    }
+
+    Ok(())
+}
+
+/// Plan the changes: creates and updates in dependency order, then deletes
+/// in reverse order. The built-in administrators team is never deleted.
+async fn plan(
+    state: &AppState,
+    request: &ReconcileStateRequest,
+) -> Result<(Vec<EntityChange>, HashMap<(EntityKind, String), Value>), ApiError> {
+    let mut changes = Vec::new();
+    let mut deletes = Vec::new();
+    let mut stored = HashMap::new();
+
+    for kind in EntityKind::APPLY_ORDER {
+        let Some(desired) = declared_entities(request, kind)? else {
+            continue;
+        };
+        let current = stored_entities(state, kind).await?;
+
+        for change in diff_entities(kind, &current, &desired)
+            .map_err(|e| ApiError::from(e).with_param(section(kind)))?
+        {
+            check_updatable(&change)?;
+
+            if change.action != ChangeAction::Delete {
+                changes.push(change);
+            } else if !(kind == EntityKind::Team && change.id == TeamId::ADMINISTRATORS) {
+                deletes.push(change);
+            }
+        }
+
+        for entity in current {
+            if let Some(id) = entity.get("id").and_then(Value::as_str) {
+                stored.insert((kind, id.to_string()), entity.clone());
+            }
+        }
+    }
+
+    deletes.sort_by_key(|change| std::cmp::Reverse(change.kind));
+    changes.extend(deletes);
+
+    Ok((changes, stored))
+}
+
+/// The declared value when the change touches the field
+fn changed<T>(change: &EntityChange, field: &str, value: Option<T>) -> Option<T> {
+    value.filter(|_| change.touches(field))
+}
+
+/// A stored object with the declared fields laid over it
+fn merged<T: Serialize, R: serde::de::DeserializeOwned>(
+    stored: Option<&Value>,
+    field: &str,
+    declared: &T,
+) -> Result<R, ApiError> {
+    let current = stored
+        .and_then(|entity| entity.get(field))
+        .unwrap_or(&Value::Null);
+    let declared = serde_json::to_value(declared).map_err(|e| ApiError::internal(e.to_string()))?;
+
+    serde_json::from_value(overlay(current, &declared))
+        .map_err(|e| ApiError::bad_request(format!("Invalid {}: {}", field, e)))
+}
+
+fn find<'a, T>(specs: &'a Option<Vec<T>>, id: &str, spec_id: fn(&T) -> &str) -> Option<&'a T> {
+    specs.iter().flatten().find(|spec| spec_id(spec) == id)
+}
+
+async fn apply_change(
+    state: &AppState,
+    admin: &AdminAuth,
+    request: &ReconcileStateRequest,
+    stored: &HashMap<(EntityKind, String), Value>,
+    change: &EntityChange,
+) -> Result<(), ApiError> {
+    let id = change.id.clone();
+    let st = || State(state.clone());
+    let auth = || RequireAdmin(admin.clone());
+    let stored = stored.get(&(change.kind, id.clone()));
+    let undeclared =
+        || ApiError::internal(format!("{} '{}' is not declared", change.kind, change.id));
+
+    if change.action == ChangeAction::Delete {
+        match change.kind {
+            EntityKind::Team => teams::delete_team(st(), auth(), Path(id)).await?,
+            EntityKind::ExternalApi => {
+                external_apis::delete_external_api(st(), auth(), Path(id)).await?
+            }
+            EntityKind::Model => models::delete_model(st(), auth(), Path(id)).await?,
+            EntityKind::Prompt => prompts::delete_prompt(st(), auth(), Path(id)).await?,
+            EntityKind::Workflow => workflows::delete_workflow(st(), auth(), Path(id)).await?,
+        };
+        return Ok(());
+    }
+
+    let create = change.action == ChangeAction::Create;
+
+    match change.kind {
+        EntityKind::Team => {
+            let spec = find(&request.teams, &id, |s| &s.id)
+                .ok_or_else(undeclared)?
+                .clone();
+
+            if create {
+                let request = CreateTeamApiRequest {
+                    id: id.clone(),
+                    name: spec.name,
+                    description: spec.description,
+                    allowed_cidrs: spec.allowed_cidrs,
+                    allowed_regions: spec.allowed_regions,
+                    no_log: spec.no_log.unwrap_or(false),
+                };
+                teams::create_team(st(), auth(), Json(request)).await?;
+            } else if [
+                "name",
+                "description",
+                "allowed_cidrs",
+                "allowed_regions",
+                "no_log",
+            ]
+            .iter()
+            .any(|field| change.touches(field))
+            {
+                let request = UpdateTeamApiRequest {
+                    name: changed(change, "name", Some(spec.name)),
+                    description: changed(change, "description", spec.description),
+                    allowed_cidrs: changed(change, "allowed_cidrs", spec.allowed_cidrs),
+                    allowed_regions: changed(change, "allowed_regions", spec.allowed_regions),
+                    no_log: changed(change, "no_log", spec.no_log),
+                };
+                teams::update_team(st(), auth(), Path(id.clone()), Json(request)).await?;
+            }
+
+            if let Some(quota) = changed(change, "quota", spec.quota) {
+                let quota: TeamQuota = merged(stored, "quota", &quota)?;
+                teams::update_team_quota(st(), auth(), Path(id), Json(quota)).await?;
+            }
+        }
+        EntityKind::ExternalApi => {
+            let spec = find(&request.external_apis, &id, |s| &s.id)
+                .ok_or_else(undeclared)?
+                .clone();
+
+            if create {
+                let request = CreateExternalApiRequest {
+                    id: id.clone(),
+                    name: spec.name,
+                    description: spec.description,
+                    base_url: spec.base_url,
+                    base_headers: spec.base_headers.unwrap_or_default(),
+                };
+                external_apis::create_external_api(st(), auth(), Json(request)).await?;
+
+                // New external APIs are enabled
+                if spec.enabled == Some(false) {
+                    let request = UpdateExternalApiRequest {
+                        name: None,
+                        description: None,
+                        base_url: None,
+                        base_headers: None,
+                        enabled: Some(false),
+                    };
+                    external_apis::update_external_api(st(), auth(), Path(id), Json(request))
+                        .await?;
+                }
+            } else {
+                let request = UpdateExternalApiRequest {
+                    name: changed(change, "name", Some(spec.name)),
+                    description: changed(change, "description", spec.description).map(Some),
+                    base_url: changed(change, "base_url", Some(spec.base_url)),
+                    base_headers: changed(change, "base_headers", spec.base_headers),
+                    enabled: changed(change, "enabled", spec.enabled),
+                };
+                external_apis::update_external_api(st(), auth(), Path(id), Json(request)).await?;
+            }
+        }
+        EntityKind::Model => {
+            let spec = find(&request.models, &id, |s| &s.id)
+                .ok_or_else(undeclared)?
+                .clone();
+
+            if create {
+                let request = CreateModelApiRequest {
+                    id,
+                    name: spec.name,
+                    provider: spec.provider,
+                    provider_model: spec.provider_model,
+                    credential_id: spec.credential_id,
+                    enabled: spec.enabled.unwrap_or(true),
+                    config: spec.config.unwrap_or_default(),
+                };
+                models::create_model(st(), auth(), Json(request)).await?;
+            } else {
+                let config = changed(change, "config", spec.config)
+                    .map(|config| merged(stored, "config", &config))
+                    .transpose()?;
+                let request = UpdateModelApiRequest {
+                    name: changed(change, "name", Some(spec.name)),
+                    provider_model: changed(change, "provider_model", Some(spec.provider_model)),
+                    credential_id: changed(change, "credential_id", Some(spec.credential_id)),
+                    enabled: changed(change, "enabled", spec.enabled),
+                    config,
+                };
+                models::update_model(st(), auth(), Path(id), Json(request)).await?;
+            }
+        }
+        EntityKind::Prompt => {
+            let spec = find(&request.prompts, &id, |s| &s.id)
+                .ok_or_else(undeclared)?
+                .clone();
+
+            if create {
+                let request = CreatePromptApiRequest {
+                    id,
+                    name: spec.name,
+                    content: spec.content,
+                    description: spec.description,
+                    tags: spec.tags.unwrap_or_default(),
+                    output_schema: spec.output_schema,
+                    cache_policy: spec.cache_policy,
+                    variables: spec.variables.unwrap_or_default(),
+                    locales: spec.locales.unwrap_or_default(),
+                };
+                prompts::create_prompt(st(), auth(), Json(request)).await?;
+            } else {
+                let request = UpdatePromptApiRequest {
+                    name: changed(change, "name", Some(spec.name)),
+                    content: changed(change, "content", Some(spec.content)),
+                    description: changed(change, "description", spec.description),
+                    tags: changed(change, "tags", spec.tags),
+                    output_schema: changed(change, "output_schema", spec.output_schema),
+                    cache_policy: changed(change, "cache_policy", spec.cache_policy),
+                    variables: changed(change, "variables", spec.variables),
+                    locales: changed(change, "locales", spec.locales),
+                    change_note: None,
+                    regression_check: None,
+                };
+                prompts::update_prompt(st(), auth(), Path(id), Json(request)).await?;
+            }
+        }
+        EntityKind::Workflow => {
+            let spec = find(&request.workflows, &id, |s| &s.id)
+                .ok_or_else(undeclared)?
+                .clone();
+            let steps = workflow_steps(&spec)?;
+
+            if create {
+                let request = CreateWorkflowApiRequest {
+                    id,
+                    name: spec.name,
+                    description: spec.description,
+                    input_schema: spec.input_schema,
+                    steps: steps.unwrap_or_default(),
+                    enabled: spec.enabled.unwrap_or(true),
+                    team_id: spec.team_id,
+                };
+                workflows::create_workflow(st(), auth(), Json(request)).await?;
+            } else {
+                let request = UpdateWorkflowApiRequest {
+                    name: changed(change, "name", Some(spec.name)),
+                    description: changed(change, "description", spec.description).map(Some),
+                    input_schema: changed(change, "input_schema", spec.input_schema).map(Some),
+                    steps: changed(change, "steps", steps),
+                    enabled: changed(change, "enabled", spec.enabled),
+                    regression_check: None,
+                };
+                workflows::update_workflow(st(), auth(), Path(id), Json(request)).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Reconcile endpoint
+// ============================================================================
+
+/// Reconcile the gateway configuration with a declared state
+#[utoipa::path(
+    put,
+    path = "/admin/state",
+    tag = "admin/state",
+    request_body = ReconcileStateRequest,
+    responses((status = 200, body = ReconcileStateResponse)),
+)]
+pub async fn reconcile_state(
+    State(state): State<AppState>,
+    RequireAdmin(admin): RequireAdmin,
+    Json(mut request): Json<ReconcileStateRequest>,
+) -> Result<Json<ReconcileStateResponse>, ApiError> {
+    normalize_request(&mut request)?;
+
+    let (changes, stored) = plan(&state, &request).await?;
+
+    let mut summary = ReconcileSummary::default();
+    for change in &changes {
+        match change.action {
+            ChangeAction::Create => summary.create += 1,
+            ChangeAction::Update => summary.update += 1,
+            ChangeAction::Delete => summary.delete += 1,
+        }
+    }
+
+    let mut applied = 0;
+    let mut error = None;
+
+    if request.mode == ReconcileMode::Apply {
+        for change in &changes {
+            if let Err(e) = apply_change(&state, &admin, &request, &stored, change).await {
+                warn!(kind = %change.kind, id = %change.id, error = %e.response.error.message, "Failed to apply declared state change");
+                error = Some(ReconcileFailure {
+                    kind: change.kind,
+                    id: change.id.clone(),
+                    message: e.response.error.message,
+                });
+                break;
+            }
+            applied += 1;
+        }
+
+        info!(applied, planned = changes.len(), "Applied declared state");
+    }
+
+    Ok(Json(ReconcileStateResponse {
+        mode: request.mode,
+        changes,
+        summary,
+        applied,
+        error,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::reconcile::FieldChange;
+
+    #[test]
+    fn test_normalize_request() {
+        let mut request: ReconcileStateRequest = serde_json::from_value(serde_json::json!({
+            "models": [{
+                "id": "gpt-4",
+                "name": "GPT-4",
+                "provider": "OpenAI",
+                "provider_model": "gpt-4",
+                "credential_id": "openai"
+            }]
+        }))
+        .unwrap();
+
+        assert_eq!(request.mode, ReconcileMode::Plan);
+        normalize_request(&mut request).unwrap();
+        assert_eq!(request.models.unwrap()[0].provider, "openai");
+
+        let mut request: ReconcileStateRequest = serde_json::from_value(serde_json::json!({
+            "mode": "apply",
+            "workflows": [{"id": "flow", "name": "Flow", "steps": [{"name": "step"}]}]
+        }))
+        .unwrap();
+
+        let error = normalize_request(&mut request).unwrap_err();
+        assert_eq!(error.status, axum::http::StatusCode::BAD_REQUEST);
+        assert_eq!(error.response.error.param.as_deref(), Some("workflows"));
+    }
+
+    #[test]
+    fn test_check_updatable() {
+        let change = |kind, field: &str| EntityChange {
+            kind,
+            id: "id".to_string(),
+            action: ChangeAction::Update,
+            changes: vec![FieldChange {
+                field: field.to_string(),
+                from: Value::Null,
+                to: Value::Bool(true),
+            }],
+        };
+
+        assert!(check_updatable(&change(EntityKind::Model, "provider")).is_err());
+        assert!(check_updatable(&change(EntityKind::Workflow, "team_id")).is_err());
+        assert!(check_updatable(&change(EntityKind::Model, "provider_model")).is_ok());
+        assert!(check_updatable(&change(EntityKind::Team, "team_id")).is_ok());
+    }
+}
@@ -0,0 +1,264 @@
+//! Entity references admin endpoint
+//!
+//! Reports the workflows, test cases, experiments, models, prompts,
+//! knowledge bases and assistants that point to a prompt, model, knowledge
+//! base or credential: the blast radius of editing or deleting it.
+
+use axum::extract::{Path, State};
+use serde::Serialize;
+use tracing::debug;
+use utoipa::ToSchema;
+
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::reference::{
+    Reference, ReferencedKind, ReferrerKind, assistant_references, experiment_references,
+    knowledge_base_references, model_references, prompt_references, test_case_references,
+    workflow_references,
+};
+use crate::domain::test_case::TestCaseQuery;
+
+// ============================================================================
+// References DTOs
+// ============================================================================
+
+/// Entity pointing to the requested one
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct EntityReferenceResponse {
+    pub entity_type: ReferrerKind,
+    pub id: String,
+    pub name: String,
+    /// Field holding the reference, e.g. `steps.summarize.model_id`; an
+    /// entity referencing through several fields is listed once per field
+    pub field: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct EntityReferencesResponse {
+    pub entity_type: ReferencedKind,
+    pub entity_id: String,
+    pub references: Vec<EntityReferenceResponse>,
+    pub total: usize,
+}
+
+/// Collects the references of the referrers to one entity
+struct Collector {
+    kind: ReferencedKind,
+    id: String,
+    references: Vec<EntityReferenceResponse>,
+}
+
+impl Collector {
+    fn add(&mut self, entity_type: ReferrerKind, id: &str, name: &str, references: Vec<Reference>) {
+        for reference in references {
+            if reference.kind == self.kind && reference.id == self.id {
+                self.references.push(EntityReferenceResponse {
+                    entity_type,
+                    id: id.to_string(),
+                    name: name.to_string(),
+                    field: reference.field,
+                });
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// List the entities referencing a prompt, model, knowledge base or
+/// credential
+#[utoipa::path(
+    get,
+    path = "/admin/entities/{entity_type}/{id}/references",
+    tag = "admin/entities",
+    params(
+        ("entity_type" = String, Path, description = "prompt, model, knowledge_base or credential"),
+        ("id" = String, Path, description = "Entity ID"),
+    ),
+    responses((status = 200, body = EntityReferencesResponse)),
+)]
+pub async fn list_entity_references(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path((entity_type, id)): Path<(String, String)>,
+) -> Result<Json<EntityReferencesResponse>, ApiError> {
+    debug!(entity_type = %entity_type, id = %id, "Admin listing entity references");
+
+    let kind = ReferencedKind::parse(&entity_type).map_err(ApiError::from)?;
+    ensure_exists(&state, kind, &id).await?;
+
+    let mut collector = Collector {
+        kind,
+        id,
+        references: Vec::new(),
+    };
+
+    for workflow in state
+        .workflow_service
+        .list()
+        .await
+        .map_err(ApiError::from)?
+    {
+        collector.add(
+            ReferrerKind::Workflow,
+            workflow.id().as_str(),
+            workflow.name(),
+            workflow_references(&workflow),
+        );
+    }
+
+    let test_cases = state
+        .test_case_service
+        .list(&TestCaseQuery::new())
+        .await
+        .map_err(ApiError::from)?;
+
+    for test_case in test_cases {
+        collector.add(
+            ReferrerKind::TestCase,
+            test_case.id().as_str(),
+            test_case.name(),
+            test_case_references(&test_case),
+        );
+    }
+
+    let experiments = state
+        .experiment_service
+        .list(None)
+        .await
+        .map_err(ApiError::from)?;
+
+    for experiment in experiments {
+        collector.add(
+            ReferrerKind::Experiment,
+            experiment.id().as_str(),
+            experiment.name(),
+            experiment_references(&experiment),
+        );
+    }
+
+    for model in state.model_service.list().await.map_err(ApiError::from)? {
+        collector.add(
+            ReferrerKind::Model,
+            model.id().as_str(),
+            model.name(),
+            model_references(&model),
+        );
+    }
+
+    for prompt in state.prompt_service.list().await.map_err(ApiError::from)? {
+        collector.add(
+            ReferrerKind::Prompt,
+            prompt.id().as_str(),
+            prompt.name(),
+            prompt_references(&prompt),
+        );
+    }
+
+    let knowledge_bases = state
+        .knowledge_base_service
+        .list()
+        .await
+        .map_err(ApiError::from)?;
+
+    for knowledge_base in knowledge_bases {
+        collector.add(
+            ReferrerKind::KnowledgeBase,
+            knowledge_base.id().as_str(),
+            knowledge_base.name(),
+            knowledge_base_references(&knowledge_base),
+        );
+    }
+
+    let assistants = state
+        .assistant_service
+        .list()
+        .await
+        .map_err(ApiError::from)?;
+
+    for assistant in assistants {
+        collector.add(
+            ReferrerKind::Assistant,
+            assistant.id().as_str(),
+            assistant.name(),
+            assistant_references(&assistant),
+        );
+    }
+
+    let total = collector.references.len();
+
+    Ok(Json(EntityReferencesResponse {
+        entity_type: kind,
+        entity_id: collector.id,
+        references: collector.references,
+        total,
+    }))
+}
+
+async fn ensure_exists(state: &AppState, kind: ReferencedKind, id: &str) -> Result<(), ApiError> {
+    let (exists, label) = match kind {
+        ReferencedKind::Prompt => (state.prompt_service.get(id).await?.is_some(), "Prompt"),
+        ReferencedKind::Model => (state.model_service.get(id).await?.is_some(), "Model"),
+        ReferencedKind::KnowledgeBase => (
+            state.knowledge_base_service.get(id).await?.is_some(),
+            "Knowledge base",
+        ),
+        ReferencedKind::Credential => (
+            state.credential_service.get(id).await?.is_some(),
+            "Credential",
+        ),
+    };
+
+    if !exists {
+        return Err(ApiError::not_found(format!("{} '{}' not found", label, id)));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collector_keeps_matching_references() {
+        let mut collector = Collector {
+            kind: ReferencedKind::Model,
+            id: "gpt-4".to_string(),
+            references: Vec::new(),
+        };
+
+        collector.add(
+            ReferrerKind::Experiment,
+            "support-ab",
+            "Support A/B",
+            vec![
+                Reference {
+                    kind: ReferencedKind::Model,
+                    id: "gpt-4".to_string(),
+                    field: "variants.control.model_id".to_string(),
+                },
+                Reference {
+                    kind: ReferencedKind::Model,
+                    id: "gpt-4-turbo".to_string(),
+                    field: "variants.treatment.model_id".to_string(),
+                },
+                Reference {
+                    kind: ReferencedKind::Prompt,
+                    id: "gpt-4".to_string(),
+                    field: "variants.treatment.prompt_id".to_string(),
+                },
+            ],
+        );
+
+        assert_eq!(collector.references.len(), 1);
+        assert_eq!(collector.references[0].id, "support-ab");
+        assert_eq!(collector.references[0].field, "variants.control.model_id");
+
+        let json = serde_json::to_value(&collector.references[0]).unwrap();
+        assert_eq!(json["entity_type"], "experiment");
+    }
+}
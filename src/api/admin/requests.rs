@@ -0,0 +1,110 @@
+//! Request trace admin endpoint
+
+use axum::extract::{Path, State};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::execution_logs::ExecutionLogResponse;
+use super::usage::UsageRecordResponse;
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::request_trace::{RequestTrace, RequestTraceStage};
+use crate::domain::usage::UsageRecordId;
+use crate::domain::ExecutionLogQuery;
+
+/// Everything recorded about an API request
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RequestTraceResponse {
+    pub request_id: String,
+    /// Trace of the request; absent once evicted or when the request was
+    /// served by another replica
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace: Option<RequestTrace>,
+    pub execution_logs: Vec<ExecutionLogResponse>,
+    pub usage_records: Vec<UsageRecordResponse>,
+}
+
+/// IDs of the usage records charged for a traced request
+fn usage_record_ids(trace: &RequestTrace) -> Vec<String> {
+    trace
+        .events_of(RequestTraceStage::Usage)
+        .filter_map(|event| event.data.as_ref())
+        .filter_map(|data| data.get("usage_record_id")?.as_str())
+        .map(String::from)
+        .collect()
+}
+
+/// Get the routing, cache, guardrail, provider and usage events of a request by
+/// its `x-pmp-request-id`, with its execution logs and usage records
+#[utoipa::path(
+    get,
+    path = "/admin/requests/{request_id}",
+    tag = "admin/requests",
+    params(("request_id" = String, Path, description = "Value of the `x-pmp-request-id` response header")),
+    responses(
+        (status = 200, body = RequestTraceResponse),
+        (status = 404, description = "No trace or execution log for the request"),
+    ),
+)]
+pub async fn get_request_trace(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Path(request_id): Path<String>,
+) -> Result<Json<RequestTraceResponse>, ApiError> {
+    let trace = state.request_traces.get(&request_id);
+
+    let execution_logs: Vec<ExecutionLogResponse> = state
+        .execution_log_service
+        .list(&ExecutionLogQuery::new().with_request_id(request_id.clone()))
+        .await?
+        .into_iter()
+        .map(ExecutionLogResponse::from)
+        .collect();
+
+    if trace.is_none() && execution_logs.is_empty() {
+        return Err(ApiError::not_found(format!(
+            "Request '{}' not found",
+            request_id
+        )));
+    }
+
+    let mut usage_records = Vec::new();
+
+    for id in trace.as_ref().map(usage_record_ids).unwrap_or_default() {
+        if let Some(record) = state.usage_service.get(&UsageRecordId::from(id)).await? {
+            usage_records.push(UsageRecordResponse::from(record));
+        }
+    }
+
+    Ok(Json(RequestTraceResponse {
+        request_id,
+        trace,
+        execution_logs,
+        usage_records,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_usage_record_ids_come_from_usage_events() {
+        let mut trace = RequestTrace::new("req-1", "POST", "/v1/chat/completions");
+        trace.record(
+            RequestTraceStage::Provider,
+            "Provider 'openai' answered",
+            Some(json!({"usage_record_id": "not-a-usage-event"})),
+        );
+        trace.record(
+            RequestTraceStage::Usage,
+            "Recorded 30 tokens costing 12 micro-dollars",
+            Some(json!({"usage_record_id": "usage-1", "cost_micros": 12})),
+        );
+        trace.record(RequestTraceStage::Usage, "No data", None);
+
+        assert_eq!(usage_record_ids(&trace), vec!["usage-1".to_string()]);
+    }
+}
@@ -0,0 +1,426 @@
+//! Role management admin endpoints
+
+use axum::extract::{Path, State};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use utoipa::ToSchema;
+
+use crate::api::middleware::{AdminAuth, RequireAdmin};
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::role::{Permission, PermissionAction, PermissionResource, Role};
+use crate::infrastructure::role::{CreateRoleRequest, UpdateRoleRequest};
+
+/// Request to create a new custom role
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateRoleApiRequest {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+}
+
+/// Request to update a custom role
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateRoleApiRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub permissions: Option<Vec<String>>,
+}
+
+/// Request to assign a role to a user
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct AssignRoleApiRequest {
+    /// Role to assign; null restores the default derived from the team role
+    pub role_id: Option<String>,
+}
+
+/// Role response for admin API
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RoleResponse {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<String>,
+    pub built_in: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<&Role> for RoleResponse {
+    fn from(role: &Role) -> Self {
+        Self {
+            id: role.id().as_str().to_string(),
+            name: role.name().to_string(),
+            description: role.description().map(String::from),
+            permissions: role.permissions().iter().map(|p| p.to_string()).collect(),
+            built_in: role.is_built_in(),
+            created_at: role.created_at().to_rfc3339(),
+            updated_at: role.updated_at().to_rfc3339(),
+        }
+    }
+}
+
+/// List roles response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListRolesResponse {
+    pub roles: Vec<RoleResponse>,
+    pub total: usize,
+}
+
+/// Available permissions response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListPermissionsResponse {
+    pub resources: Vec<String>,
+    pub actions: Vec<String>,
+    pub permissions: Vec<String>,
+}
+
+/// User role assignment response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UserRoleResponse {
+    pub user_id: String,
+    pub role_id: Option<String>,
+    pub effective_role_id: String,
+}
+
+/// Ensure an admin only grants permissions they hold themselves: JWT users are
+/// limited to their role and service accounts to their token scopes.
+/// Admin API keys are unrestricted.
+pub(super) async fn ensure_can_grant(
+    state: &AppState,
+    auth: &AdminAuth,
+    permissions: &[Permission],
+) -> Result<(), ApiError> {
+    match auth {
+        AdminAuth::User(user) => {
+            let caller_role = state.role_service.resolve_for_user(user).await?;
+
+            if let Some(missing) = permissions.iter().find(|p| !caller_role.allows(p)) {
+                return Err(ApiError::forbidden(format!(
+                    "Cannot grant permission '{}' not held by role '{}'",
+                    missing,
+                    caller_role.id()
+                )));
+            }
+        }
+        AdminAuth::ServiceAccount { account, scopes } => {
+            if let Some(missing) = permissions
+                .iter()
+                .find(|p| !scopes.iter().any(|scope| scope.grants(p)))
+            {
+                return Err(ApiError::forbidden(format!(
+                    "Cannot grant permission '{}' not held by service account '{}'",
+                    missing,
+                    account.id()
+                )));
+            }
+        }
+        AdminAuth::ApiKey(_) => {}
+    }
+
+    Ok(())
+}
+
+pub(super) fn parse_permissions(permissions: &[String]) -> Result<Vec<Permission>, ApiError> {
+    permissions
+        .iter()
+        .map(|p| Permission::parse(p).map_err(|e| ApiError::bad_request(e.to_string())))
+        .collect()
+}
+
+/// List roles
+#[utoipa::path(
+    get,
+    path = "/admin/roles",
+    tag = "admin/roles",
+    responses((status = 200, body = ListRolesResponse)),
+)]
+pub async fn list_roles(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+) -> Result<Json<ListRolesResponse>, ApiError> {
+    debug!("Admin listing all roles");
+
+    let roles = state.role_service.list().await.map_err(ApiError::from)?;
+
+    let role_responses: Vec<RoleResponse> = roles.iter().map(RoleResponse::from).collect();
+    let total = role_responses.len();
+
+    Ok(Json(ListRolesResponse {
+        roles: role_responses,
+        total,
+    }))
+}
+
+/// List permissions
+#[utoipa::path(
+    get,
+    path = "/admin/roles/permissions",
+    tag = "admin/roles",
+    responses((status = 200, body = ListPermissionsResponse)),
+)]
+pub async fn list_permissions(
+    RequireAdmin(_): RequireAdmin,
+) -> Result<Json<ListPermissionsResponse>, ApiError> {
+    let actions = [PermissionAction::Read, PermissionAction::Write];
+
+    let resources: Vec<String> = std::iter::once(PermissionResource::All)
+        .chain(PermissionResource::all().iter().copied())
+        .map(|r| r.as_str().to_string())
+        .collect();
+
+    let permissions = std::iter::once(PermissionResource::All)
+        .chain(PermissionResource::all().iter().copied())
+        .flat_map(|r| actions.iter().map(move |a| Permission::new(r, *a).to_string()))
+        .collect();
+
+    Ok(Json(ListPermissionsResponse {
+        resources,
+        actions: actions.iter().map(|a| a.as_str().to_string()).collect(),
+        permissions,
+    }))
+}
+
+/// Create role
+#[utoipa::path(
+    post,
+    path = "/admin/roles",
+    tag = "admin/roles",
+    request_body = CreateRoleApiRequest,
+    responses((status = 200, body = RoleResponse)),
+)]
+pub async fn create_role(
+    State(state): State<AppState>,
+    RequireAdmin(auth): RequireAdmin,
+    Json(request): Json<CreateRoleApiRequest>,
+) -> Result<Json<RoleResponse>, ApiError> {
+    debug!(id = %request.id, name = %request.name, "Admin creating role");
+
+    ensure_can_grant(&state, &auth, &parse_permissions(&request.permissions)?).await?;
+
+    let service_request = CreateRoleRequest {
+        id: request.id,
+        name: request.name,
+        description: request.description,
+        permissions: request.permissions,
+    };
+
+    let role = state
+        .role_service
+        .create(service_request)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(RoleResponse::from(&role)))
+}
+
+/// Get role
+#[utoipa::path(
+    get,
+    path = "/admin/roles/{role_id}",
+    tag = "admin/roles",
+    params(("role_id" = String, Path, description = "Role ID")),
+    responses((status = 200, body = RoleResponse)),
+)]
+pub async fn get_role(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(role_id): Path<String>,
+) -> Result<Json<RoleResponse>, ApiError> {
+    debug!(role_id = %role_id, "Admin getting role");
+
+    let role = state
+        .role_service
+        .get(&role_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found(format!("Role '{}' not found", role_id)))?;
+
+    Ok(Json(RoleResponse::from(&role)))
+}
+
+/// Update role
+#[utoipa::path(
+    put,
+    path = "/admin/roles/{role_id}",
+    tag = "admin/roles",
+    params(("role_id" = String, Path, description = "Role ID")),
+    request_body = UpdateRoleApiRequest,
+    responses((status = 200, body = RoleResponse)),
+)]
+pub async fn update_role(
+    State(state): State<AppState>,
+    RequireAdmin(auth): RequireAdmin,
+    Path(role_id): Path<String>,
+    Json(request): Json<UpdateRoleApiRequest>,
+) -> Result<Json<RoleResponse>, ApiError> {
+    debug!(role_id = %role_id, "Admin updating role");
+
+    if let Some(ref permissions) = request.permissions {
+        ensure_can_grant(&state, &auth, &parse_permissions(permissions)?).await?;
+    }
+
+    let service_request = UpdateRoleRequest {
+        name: request.name,
+        description: request.description,
+        permissions: request.permissions,
+    };
+
+    let role = state
+        .role_service
+        .update(&role_id, service_request)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(RoleResponse::from(&role)))
+}
+
+/// Delete role
+#[utoipa::path(
+    delete,
+    path = "/admin/roles/{role_id}",
+    tag = "admin/roles",
+    params(("role_id" = String, Path, description = "Role ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
+pub async fn delete_role(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(role_id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    debug!(role_id = %role_id, "Admin deleting role");
+
+    // Check if any users are assigned to this role
+    let users = state.user_service.list(None).await.map_err(ApiError::from)?;
+    let assigned: Vec<_> = users
+        .iter()
+        .filter(|u| u.role_id().map(|r| r.as_str()) == Some(role_id.as_str()))
+        .map(|u| u.id().as_str())
+        .collect();
+
+    if !assigned.is_empty() {
+        return Err(ApiError::conflict(format!(
+            "Cannot delete role '{}': assigned to users: {}",
+            role_id,
+            assigned.join(", ")
+        )));
+    }
+
+    let deleted = state
+        .role_service
+        .delete(&role_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    if !deleted {
+        return Err(ApiError::not_found(format!("Role '{}' not found", role_id)));
+    }
+
+    Ok(Json(serde_json::json!({
+        "deleted": true,
+        "id": role_id
+    })))
+}
+
+/// Assign user role
+#[utoipa::path(
+    put,
+    path = "/admin/users/{user_id}/role",
+    tag = "admin/users",
+    params(("user_id" = String, Path, description = "User ID")),
+    request_body = AssignRoleApiRequest,
+    responses((status = 200, body = UserRoleResponse)),
+)]
+pub async fn assign_user_role(
+    State(state): State<AppState>,
+    RequireAdmin(auth): RequireAdmin,
+    Path(user_id): Path<String>,
+    Json(request): Json<AssignRoleApiRequest>,
+) -> Result<Json<UserRoleResponse>, ApiError> {
+    debug!(user_id = %user_id, role_id = ?request.role_id, "Admin assigning user role");
+
+    let role_id = match request.role_id {
+        Some(id) => {
+            let role = state
+                .role_service
+                .get(&id)
+                .await
+                .map_err(ApiError::from)?
+                .ok_or_else(|| ApiError::not_found(format!("Role '{}' not found", id)))?;
+
+            ensure_can_grant(&state, &auth, role.permissions()).await?;
+            Some(role.id().clone())
+        }
+        None => None,
+    };
+
+    let user = state
+        .user_service
+        .set_role(&user_id, role_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(UserRoleResponse {
+        user_id: user.id().as_str().to_string(),
+        role_id: user.role_id().map(|r| r.as_str().to_string()),
+        effective_role_id: user.effective_role_id().as_str().to_string(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::role::RoleId;
+
+    #[test]
+    fn test_create_role_request_deserialization() {
+        let json = r#"{
+            "id": "prompt-author",
+            "name": "Prompt Author",
+            "permissions": ["prompts:write", "models:read"]
+        }"#;
+
+        let request: CreateRoleApiRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.id, "prompt-author");
+        assert_eq!(request.permissions.len(), 2);
+        assert!(request.description.is_none());
+    }
+
+    #[test]
+    fn test_update_role_request_partial() {
+        let json = r#"{"name": "Renamed"}"#;
+
+        let request: UpdateRoleApiRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.name, Some("Renamed".to_string()));
+        assert!(request.permissions.is_none());
+    }
+
+    #[test]
+    fn test_assign_role_request() {
+        let request: AssignRoleApiRequest =
+            serde_json::from_str(r#"{"role_id": "viewer"}"#).unwrap();
+        assert_eq!(request.role_id, Some("viewer".to_string()));
+
+        let request: AssignRoleApiRequest = serde_json::from_str(r#"{"role_id": null}"#).unwrap();
+        assert!(request.role_id.is_none());
+    }
+
+    #[test]
+    fn test_role_response_from_role() {
+        let role = Role::builtin(RoleId::VIEWER).unwrap();
+        let response = RoleResponse::from(&role);
+
+        assert_eq!(response.id, "viewer");
+        assert!(response.built_in);
+        assert_eq!(response.permissions, vec!["*:read".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_permissions() {
+        assert!(parse_permissions(&["models:read".to_string()]).is_ok());
+        assert!(parse_permissions(&["models:delete".to_string()]).is_err());
+    }
+}
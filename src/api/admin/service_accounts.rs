@@ -0,0 +1,341 @@
+//! Service account management admin endpoints
+
+use axum::extract::{Path, State};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use utoipa::ToSchema;
+
+use crate::api::admin::roles::{ensure_can_grant, parse_permissions};
+use crate::api::admin::teams::resolve_owner_team;
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::service_account::{ServiceAccount, ServiceAccountStatus};
+use crate::infrastructure::service_account::{
+    CreateServiceAccountRequest, ServiceAccountWithSecret, UpdateServiceAccountRequest,
+};
+
+/// Request to create a new service account
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateServiceAccountApiRequest {
+    /// Unique ID, used as the OAuth client ID
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Permissions tokens may be issued for (e.g. `prompts:write`)
+    pub scopes: Vec<String>,
+    /// Owning team, defaults to the team of the caller
+    #[serde(default)]
+    pub team_id: Option<String>,
+}
+
+/// Request to update a service account
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpdateServiceAccountApiRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    /// Replaces the scopes
+    pub scopes: Option<Vec<String>>,
+    /// `active` or `disabled`
+    pub status: Option<ServiceAccountStatus>,
+}
+
+/// Service account response for admin API
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ServiceAccountResponse {
+    pub id: String,
+    pub client_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub scopes: Vec<String>,
+    pub team_id: String,
+    pub status: String,
+    pub last_used_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<&ServiceAccount> for ServiceAccountResponse {
+    fn from(account: &ServiceAccount) -> Self {
+        Self {
+            id: account.id().as_str().to_string(),
+            client_id: account.id().as_str().to_string(),
+            name: account.name().to_string(),
+            description: account.description().map(String::from),
+            scopes: account.scopes().iter().map(|s| s.to_string()).collect(),
+            team_id: account.team_id().as_str().to_string(),
+            status: account.status().to_string(),
+            last_used_at: account.last_used_at().map(|t| t.to_rfc3339()),
+            created_at: account.created_at().to_rfc3339(),
+            updated_at: account.updated_at().to_rfc3339(),
+        }
+    }
+}
+
+/// Service account response with client secret (only on creation and rotation)
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ServiceAccountWithSecretResponse {
+    #[serde(flatten)]
+    pub service_account: ServiceAccountResponse,
+    pub client_secret: String,
+}
+
+impl From<ServiceAccountWithSecret> for ServiceAccountWithSecretResponse {
+    fn from(result: ServiceAccountWithSecret) -> Self {
+        Self {
+            service_account: ServiceAccountResponse::from(&result.account),
+            client_secret: result.client_secret,
+        }
+    }
+}
+
+/// List service accounts response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListServiceAccountsResponse {
+    pub service_accounts: Vec<ServiceAccountResponse>,
+    pub total: usize,
+}
+
+/// List service accounts
+#[utoipa::path(
+    get,
+    path = "/admin/service-accounts",
+    tag = "admin/service-accounts",
+    responses((status = 200, body = ListServiceAccountsResponse)),
+)]
+pub async fn list_service_accounts(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+) -> Result<Json<ListServiceAccountsResponse>, ApiError> {
+    debug!("Admin listing all service accounts");
+
+    let accounts = state
+        .service_account_service
+        .list()
+        .await
+        .map_err(ApiError::from)?;
+
+    let service_accounts: Vec<ServiceAccountResponse> =
+        accounts.iter().map(ServiceAccountResponse::from).collect();
+    let total = service_accounts.len();
+
+    Ok(Json(ListServiceAccountsResponse {
+        service_accounts,
+        total,
+    }))
+}
+
+/// Create service account
+#[utoipa::path(
+    post,
+    path = "/admin/service-accounts",
+    tag = "admin/service-accounts",
+    request_body = CreateServiceAccountApiRequest,
+    responses((status = 200, body = ServiceAccountWithSecretResponse)),
+)]
+pub async fn create_service_account(
+    State(state): State<AppState>,
+    RequireAdmin(auth): RequireAdmin,
+    Json(request): Json<CreateServiceAccountApiRequest>,
+) -> Result<Json<ServiceAccountWithSecretResponse>, ApiError> {
+    debug!(id = %request.id, name = %request.name, "Admin creating service account");
+
+    let scopes = parse_permissions(&request.scopes)?;
+    ensure_can_grant(&state, &auth, &scopes).await?;
+
+    let team_id = resolve_owner_team(&state, &auth, request.team_id.as_deref()).await?;
+
+    let created = state
+        .service_account_service
+        .create(CreateServiceAccountRequest {
+            id: request.id,
+            name: request.name,
+            description: request.description,
+            scopes,
+            team_id,
+        })
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(ServiceAccountWithSecretResponse::from(created)))
+}
+
+/// Get service account
+#[utoipa::path(
+    get,
+    path = "/admin/service-accounts/{account_id}",
+    tag = "admin/service-accounts",
+    params(("account_id" = String, Path, description = "Account ID")),
+    responses((status = 200, body = ServiceAccountResponse)),
+)]
+pub async fn get_service_account(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(account_id): Path<String>,
+) -> Result<Json<ServiceAccountResponse>, ApiError> {
+    debug!(account_id = %account_id, "Admin getting service account");
+
+    let account = state
+        .service_account_service
+        .get(&account_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| {
+            ApiError::not_found(format!("Service account '{}' not found", account_id))
+        })?;
+
+    Ok(Json(ServiceAccountResponse::from(&account)))
+}
+
+/// Update service account
+#[utoipa::path(
+    put,
+    path = "/admin/service-accounts/{account_id}",
+    tag = "admin/service-accounts",
+    params(("account_id" = String, Path, description = "Account ID")),
+    request_body = UpdateServiceAccountApiRequest,
+    responses((status = 200, body = ServiceAccountResponse)),
+)]
+pub async fn update_service_account(
+    State(state): State<AppState>,
+    RequireAdmin(auth): RequireAdmin,
+    Path(account_id): Path<String>,
+    Json(request): Json<UpdateServiceAccountApiRequest>,
+) -> Result<Json<ServiceAccountResponse>, ApiError> {
+    debug!(account_id = %account_id, "Admin updating service account");
+
+    let scopes = request
+        .scopes
+        .as_deref()
+        .map(parse_permissions)
+        .transpose()?;
+
+    if let Some(ref scopes) = scopes {
+        ensure_can_grant(&state, &auth, scopes).await?;
+    }
+
+    let account = state
+        .service_account_service
+        .update(
+            &account_id,
+            UpdateServiceAccountRequest {
+                name: request.name,
+                description: request.description,
+                scopes,
+                status: request.status,
+            },
+        )
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(ServiceAccountResponse::from(&account)))
+}
+
+/// Delete service account
+#[utoipa::path(
+    delete,
+    path = "/admin/service-accounts/{account_id}",
+    tag = "admin/service-accounts",
+    params(("account_id" = String, Path, description = "Account ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
+pub async fn delete_service_account(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(account_id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    debug!(account_id = %account_id, "Admin deleting service account");
+
+    let deleted = state
+        .service_account_service
+        .delete(&account_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    if !deleted {
+        return Err(ApiError::not_found(format!(
+            "Service account '{}' not found",
+            account_id
+        )));
+    }
+
+    Ok(Json(serde_json::json!({
+        "deleted": true,
+        "id": account_id
+    })))
+}
+
+/// Rotate service account secret
+#[utoipa::path(
+    post,
+    path = "/admin/service-accounts/{account_id}/rotate-secret",
+    tag = "admin/service-accounts",
+    params(("account_id" = String, Path, description = "Account ID")),
+    responses((status = 200, body = ServiceAccountWithSecretResponse)),
+)]
+pub async fn rotate_service_account_secret(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(account_id): Path<String>,
+) -> Result<Json<ServiceAccountWithSecretResponse>, ApiError> {
+    debug!(account_id = %account_id, "Admin rotating service account secret");
+
+    let rotated = state
+        .service_account_service
+        .rotate_secret(&account_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(ServiceAccountWithSecretResponse::from(rotated)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::role::{Permission, PermissionResource};
+    use crate::domain::service_account::ServiceAccountId;
+    use crate::domain::team::TeamId;
+
+    #[test]
+    fn test_create_service_account_request() {
+        let json = r#"{"id": "ci", "name": "CI", "scopes": ["prompts:write"]}"#;
+
+        let request: CreateServiceAccountApiRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.scopes, vec!["prompts:write".to_string()]);
+        assert!(request.team_id.is_none());
+    }
+
+    #[test]
+    fn test_update_service_account_request_status() {
+        let json = r#"{"status": "disabled"}"#;
+
+        let request: UpdateServiceAccountApiRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.status, Some(ServiceAccountStatus::Disabled));
+        assert!(request.scopes.is_none());
+    }
+
+    #[test]
+    fn test_service_account_with_secret_response() {
+        let account = ServiceAccount::new(
+            ServiceAccountId::new("ci").unwrap(),
+            "CI",
+            "sha256$hash",
+            vec![Permission::write(PermissionResource::Workflows)],
+            TeamId::administrators(),
+        )
+        .unwrap();
+
+        let response = ServiceAccountWithSecretResponse::from(ServiceAccountWithSecret {
+            account,
+            client_secret: "sas_secret".to_string(),
+        });
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["client_id"], "ci");
+        assert_eq!(json["client_secret"], "sas_secret");
+        assert_eq!(json["scopes"][0], "workflows:write");
+        assert_eq!(json["status"], "active");
+        assert!(json.get("secret_hash").is_none());
+    }
+}
@@ -0,0 +1,322 @@
+//! Model SLO admin endpoints
+
+use axum::extract::{Path, State};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::slo::{Slo, SloReport, DEFAULT_SLO_WINDOW_SECS};
+
+// ============================================================================
+// SLO DTOs
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateSloRequest {
+    pub model_id: String,
+    pub latency_p95_ms: Option<u64>,
+    pub error_budget: Option<f64>,
+    #[serde(default = "default_window_secs")]
+    pub window_secs: u64,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_window_secs() -> u64 {
+    DEFAULT_SLO_WINDOW_SECS
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Partial update, unset fields keep their value
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateSloRequest {
+    pub latency_p95_ms: Option<u64>,
+    pub error_budget: Option<f64>,
+    pub window_secs: Option<u64>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SloReportResponse {
+    pub model_id: String,
+    pub window_secs: u64,
+    pub evaluated_at: u64,
+    pub requests: u64,
+    pub errors: u64,
+    pub error_rate: f64,
+    pub p95_latency_ms: Option<u64>,
+    pub error_burn_rate: Option<f64>,
+    pub latency_burn_rate: Option<f64>,
+    /// Highest burn rate of the objectives
+    pub burn_rate: Option<f64>,
+    pub status: String,
+}
+
+impl From<SloReport> for SloReportResponse {
+    fn from(report: SloReport) -> Self {
+        Self {
+            burn_rate: report.burn_rate(),
+            status: report.status.to_string(),
+            model_id: report.model_id,
+            window_secs: report.window_secs,
+            evaluated_at: report.evaluated_at,
+            requests: report.requests,
+            errors: report.errors,
+            error_rate: report.error_rate,
+            p95_latency_ms: report.p95_latency_ms,
+            error_burn_rate: report.error_burn_rate,
+            latency_burn_rate: report.latency_burn_rate,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SloResponse {
+    pub model_id: String,
+    pub latency_p95_ms: Option<u64>,
+    pub error_budget: Option<f64>,
+    pub window_secs: u64,
+    pub enabled: bool,
+    pub created_at: u64,
+    pub updated_at: u64,
+    /// Latest evaluation, none until the SLO is evaluated
+    pub report: Option<SloReportResponse>,
+}
+
+impl SloResponse {
+    fn new(slo: Slo, report: Option<SloReport>) -> Self {
+        Self {
+            model_id: slo.model_id,
+            latency_p95_ms: slo.latency_p95_ms,
+            error_budget: slo.error_budget,
+            window_secs: slo.window_secs,
+            enabled: slo.enabled,
+            created_at: slo.created_at,
+            updated_at: slo.updated_at,
+            report: report.map(Into::into),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SloListResponse {
+    pub slos: Vec<SloResponse>,
+    pub total: usize,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SloReportListResponse {
+    pub reports: Vec<SloReportResponse>,
+    pub total: usize,
+}
+
+impl From<Vec<SloReport>> for SloReportListResponse {
+    fn from(reports: Vec<SloReport>) -> Self {
+        let total = reports.len();
+
+        Self {
+            reports: reports.into_iter().map(Into::into).collect(),
+            total,
+        }
+    }
+}
+
+fn slo_not_found(model_id: &str) -> ApiError {
+    ApiError::not_found(format!("No SLO for model '{}'", model_id))
+}
+
+// ============================================================================
+// SLO Endpoints
+// ============================================================================
+
+/// List every SLO with its latest report
+#[utoipa::path(
+    get,
+    path = "/admin/slo",
+    tag = "admin/slo",
+    responses((status = 200, body = SloListResponse)),
+)]
+pub async fn list_slos(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+) -> Result<Json<SloListResponse>, ApiError> {
+    let slos: Vec<SloResponse> = state
+        .slo_service
+        .list()
+        .await?
+        .into_iter()
+        .map(|slo| {
+            let report = state.slo_service.report(&slo.model_id);
+            SloResponse::new(slo, report)
+        })
+        .collect();
+    let total = slos.len();
+
+    Ok(Json(SloListResponse { slos, total }))
+}
+
+/// Define the SLO of a model, replacing any previous one
+#[utoipa::path(
+    post,
+    path = "/admin/slo",
+    tag = "admin/slo",
+    request_body = CreateSloRequest,
+    responses((status = 200, body = SloResponse)),
+)]
+pub async fn create_slo(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Json(request): Json<CreateSloRequest>,
+) -> Result<Json<SloResponse>, ApiError> {
+    let mut slo = Slo::new(request.model_id)
+        .with_window_secs(request.window_secs)
+        .with_enabled(request.enabled);
+    slo.latency_p95_ms = request.latency_p95_ms;
+    slo.error_budget = request.error_budget;
+
+    let created = state.slo_service.set(slo).await?;
+
+    Ok(Json(SloResponse::new(created, None)))
+}
+
+/// Get the SLO of a model with its latest report
+#[utoipa::path(
+    get,
+    path = "/admin/slo/{model_id}",
+    tag = "admin/slo",
+    params(("model_id" = String, Path, description = "Model ID")),
+    responses((status = 200, body = SloResponse)),
+)]
+pub async fn get_slo(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Path(model_id): Path<String>,
+) -> Result<Json<SloResponse>, ApiError> {
+    let slo = state
+        .slo_service
+        .get(&model_id)
+        .await?
+        .ok_or_else(|| slo_not_found(&model_id))?;
+    let report = state.slo_service.report(&model_id);
+
+    Ok(Json(SloResponse::new(slo, report)))
+}
+
+/// Change the objectives of a model's SLO
+#[utoipa::path(
+    put,
+    path = "/admin/slo/{model_id}",
+    tag = "admin/slo",
+    params(("model_id" = String, Path, description = "Model ID")),
+    request_body = UpdateSloRequest,
+    responses((status = 200, body = SloResponse)),
+)]
+pub async fn update_slo(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Path(model_id): Path<String>,
+    Json(request): Json<UpdateSloRequest>,
+) -> Result<Json<SloResponse>, ApiError> {
+    let mut slo = state
+        .slo_service
+        .get(&model_id)
+        .await?
+        .ok_or_else(|| slo_not_found(&model_id))?;
+
+    if let Some(latency_ms) = request.latency_p95_ms {
+        slo.latency_p95_ms = Some(latency_ms);
+    }
+    if let Some(error_budget) = request.error_budget {
+        slo.error_budget = Some(error_budget);
+    }
+    if let Some(window_secs) = request.window_secs {
+        slo.window_secs = window_secs;
+    }
+    if let Some(enabled) = request.enabled {
+        slo.enabled = enabled;
+    }
+
+    let updated = state.slo_service.set(slo).await?;
+
+    Ok(Json(SloResponse::new(updated, None)))
+}
+
+/// Delete the SLO of a model
+#[utoipa::path(
+    delete,
+    path = "/admin/slo/{model_id}",
+    tag = "admin/slo",
+    params(("model_id" = String, Path, description = "Model ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
+pub async fn delete_slo(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Path(model_id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    if state.slo_service.delete(&model_id).await? {
+        Ok(Json(serde_json::json!({
+            "deleted": true,
+            "model_id": model_id
+        })))
+    } else {
+        Err(slo_not_found(&model_id))
+    }
+}
+
+/// Latest report of every evaluated SLO, with burn rates
+#[utoipa::path(
+    get,
+    path = "/admin/slo/reports",
+    tag = "admin/slo",
+    responses((status = 200, body = SloReportListResponse)),
+)]
+pub async fn list_slo_reports(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+) -> Result<Json<SloReportListResponse>, ApiError> {
+    Ok(Json(state.slo_service.reports().into()))
+}
+
+/// Evaluate every enabled SLO now
+#[utoipa::path(
+    post,
+    path = "/admin/slo/evaluate",
+    tag = "admin/slo",
+    responses((status = 200, body = SloReportListResponse)),
+)]
+pub async fn evaluate_slos(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+) -> Result<Json<SloReportListResponse>, ApiError> {
+    let reports = state.slo_service.evaluate_all().await?;
+
+    Ok(Json(reports.into()))
+}
+
+/// Evaluate the SLO of a model now
+#[utoipa::path(
+    post,
+    path = "/admin/slo/{model_id}/evaluate",
+    tag = "admin/slo",
+    params(("model_id" = String, Path, description = "Model ID")),
+    responses((status = 200, body = SloReportResponse)),
+)]
+pub async fn evaluate_slo(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Path(model_id): Path<String>,
+) -> Result<Json<SloReportResponse>, ApiError> {
+    let report = state
+        .slo_service
+        .evaluate(&model_id)
+        .await?
+        .ok_or_else(|| slo_not_found(&model_id))?;
+
+    Ok(Json(report.into()))
+}
@@ -0,0 +1,223 @@
+//! Live gateway stats endpoint
+//!
+//! The response schema is versioned by `schema_version`: fields are only
+//! added within a version, never renamed or removed, so dashboards can poll
+//! it without breaking on upgrades.
+
+use axum::extract::State;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::infrastructure::observability::{live_stats, LiveStatsSnapshot};
+
+/// Version of the `/admin/stats` response schema
+pub const STATS_SCHEMA_VERSION: u32 = 1;
+
+// ============================================================================
+// Stats DTOs
+// ============================================================================
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StatsResponse {
+    pub schema_version: u32,
+    /// Snapshot date (unix timestamp)
+    pub generated_at: u64,
+    /// Window the rates and window counters cover
+    pub window_secs: u64,
+    pub uptime_secs: u64,
+    pub requests: RequestStatsResponse,
+    pub tokens: TokenStatsResponse,
+    pub cache: CacheStatsResponse,
+    pub providers: Vec<ProviderStatsResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RequestStatsResponse {
+    /// Requests in flight
+    pub active: u64,
+    /// Requests completed since startup
+    pub total: u64,
+    /// Requests completed in the window
+    pub window: u64,
+    /// 5xx responses in the window
+    pub errors: u64,
+    pub error_rate: f64,
+    pub rps: f64,
+    /// Busiest routes first
+    pub by_route: Vec<RouteStatsResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RouteStatsResponse {
+    /// `METHOD /matched/path`
+    pub route: String,
+    pub requests: u64,
+    pub errors: u64,
+    pub rps: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenStatsResponse {
+    pub input: u64,
+    pub output: u64,
+    pub per_minute: f64,
+}
+
+/// Response cache lookups of the chat completions whose cache policy
+/// selects a cache
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CacheStatsResponse {
+    pub hits: u64,
+    pub misses: u64,
+    /// Null without lookups in the window
+    pub hit_rate: Option<f64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProviderStatsResponse {
+    pub provider: String,
+    /// `healthy`, `degraded` (errors in the window) or `down` (outage)
+    pub status: &'static str,
+    pub requests: u64,
+    pub errors: u64,
+    pub error_rate: f64,
+}
+
+impl StatsResponse {
+    /// Build the response of a snapshot; providers in `outages` are down
+    /// even without calls in the window
+    fn new(snapshot: LiveStatsSnapshot, outages: &[String]) -> Self {
+        let error_rate = snapshot.error_rate();
+        let cache_hit_rate = snapshot.cache_hit_rate();
+
+        let mut providers: Vec<ProviderStatsResponse> = snapshot
+            .providers
+            .iter()
+            .map(|stats| ProviderStatsResponse {
+                status: if outages.contains(&stats.provider) {
+                    "down"
+                } else if stats.errors > 0 {
+                    "degraded"
+                } else {
+                    "healthy"
+                },
+                error_rate: stats.error_rate(),
+                provider: stats.provider.clone(),
+                requests: stats.requests,
+                errors: stats.errors,
+            })
+            .collect();
+
+        for provider in outages {
+            if !providers.iter().any(|p| &p.provider == provider) {
+                providers.push(ProviderStatsResponse {
+                    provider: provider.clone(),
+                    status: "down",
+                    requests: 0,
+                    errors: 0,
+                    error_rate: 0.0,
+                });
+            }
+        }
+        providers.sort_by(|a, b| a.provider.cmp(&b.provider));
+
+        Self {
+            schema_version: STATS_SCHEMA_VERSION,
+            generated_at: snapshot.generated_at,
+            window_secs: snapshot.window_secs,
+            uptime_secs: snapshot.uptime_secs,
+            requests: RequestStatsResponse {
+                active: snapshot.active_requests,
+                total: snapshot.total_requests,
+                window: snapshot.requests,
+                errors: snapshot.errors,
+                error_rate,
+                rps: snapshot.requests_per_second,
+                by_route: snapshot
+                    .routes
+                    .into_iter()
+                    .map(|route| RouteStatsResponse {
+                        route: route.route,
+                        requests: route.requests,
+                        errors: route.errors,
+                        rps: route.requests_per_second,
+                    })
+                    .collect(),
+            },
+            tokens: TokenStatsResponse {
+                input: snapshot.input_tokens,
+                output: snapshot.output_tokens,
+                per_minute: snapshot.tokens_per_minute,
+            },
+            cache: CacheStatsResponse {
+                hits: snapshot.cache_hits,
+                misses: snapshot.cache_misses,
+                hit_rate: cache_hit_rate,
+            },
+            providers,
+        }
+    }
+}
+
+// ============================================================================
+// Stats Endpoints
+// ============================================================================
+
+/// Live counters of the last minute: active requests, requests per second
+/// by route, tokens per minute, cache hit rate and provider health
+#[utoipa::path(
+    get,
+    path = "/admin/stats",
+    tag = "admin/stats",
+    responses((status = 200, body = StatsResponse)),
+)]
+pub async fn get_stats(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+) -> Result<Json<StatsResponse>, ApiError> {
+    let snapshot = live_stats().snapshot(chrono::Utc::now().timestamp().max(0) as u64);
+
+    Ok(Json(StatsResponse::new(
+        snapshot,
+        &state.notifications.provider_outages(),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::observability::LiveStats;
+
+    #[test]
+    fn test_provider_status() {
+        let stats = LiveStats::new(0);
+        stats.record_llm_request("openai", true, 10, 10, 1);
+        stats.record_llm_request("anthropic", false, 10, 0, 1);
+        stats.record_llm_request("azure", false, 10, 0, 1);
+
+        let response = StatsResponse::new(
+            stats.snapshot(1),
+            &["azure".to_string(), "bedrock".to_string()],
+        );
+        let statuses: Vec<_> = response
+            .providers
+            .iter()
+            .map(|p| (p.provider.as_str(), p.status))
+            .collect();
+
+        assert_eq!(
+            statuses,
+            [
+                ("anthropic", "degraded"),
+                ("azure", "down"),
+                ("bedrock", "down"),
+                ("openai", "healthy"),
+            ]
+        );
+        assert_eq!(response.cache.hit_rate, None);
+        assert_eq!(response.tokens.input, 30);
+    }
+}
@@ -3,36 +3,60 @@
 use axum::extract::{Path, State};
 use serde::{Deserialize, Serialize};
 use tracing::debug;
+use utoipa::ToSchema;
 
-use crate::api::middleware::RequireAdmin;
+use crate::api::admin::api_keys::parse_allowed_cidrs;
+use crate::api::middleware::{AdminAuth, RequireAdmin};
 use crate::api::state::AppState;
 use crate::api::types::{ApiError, Json};
-use crate::domain::team::{Team, TeamStatus};
+use crate::domain::residency::Region;
+use crate::domain::team::{QuotaUsage, Team, TeamId, TeamQuota, TeamRequestDefaults, TeamStatus};
 use crate::infrastructure::team::{CreateTeamRequest, UpdateTeamRequest};
 
 /// Request to create a new team
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct CreateTeamApiRequest {
     pub id: String,
     pub name: String,
     #[serde(default)]
     pub description: Option<String>,
+    /// Client networks allowed to use the team's API keys; empty allows any
+    #[serde(default)]
+    pub allowed_cidrs: Option<Vec<String>>,
+    /// Provider regions the team's requests may be served from; empty allows any
+    #[serde(default)]
+    pub allowed_regions: Option<Vec<String>>,
+    /// Zero-retention mode: never store request and completion bodies
+    #[serde(default)]
+    pub no_log: bool,
 }
 
 /// Request to update a team
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct UpdateTeamApiRequest {
     pub name: Option<String>,
     pub description: Option<String>,
+    /// Replaces the allowed client networks; an empty list removes the restriction
+    pub allowed_cidrs: Option<Vec<String>>,
+    /// Replaces the allowed provider regions; an empty list removes the restriction
+    pub allowed_regions: Option<Vec<String>>,
+    /// Enables or disables zero-retention mode
+    pub no_log: Option<bool>,
 }
 
 /// Team response for admin API
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct TeamResponse {
     pub id: String,
     pub name: String,
     pub description: Option<String>,
     pub status: String,
+    pub allowed_cidrs: Vec<String>,
+    pub allowed_regions: Vec<String>,
+    pub no_log: bool,
+    pub quota: TeamQuota,
+    pub request_defaults: TeamRequestDefaults,
+    pub organization_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
@@ -51,6 +75,12 @@ impl From<&Team> for TeamResponse {
             name: team.name().to_string(),
             description: team.description().map(String::from),
             status: status_to_string(team.status()),
+            allowed_cidrs: team.allowed_cidrs().iter().map(|c| c.to_string()).collect(),
+            allowed_regions: team.allowed_regions().iter().map(|r| r.to_string()).collect(),
+            no_log: team.no_log(),
+            quota: team.quota().clone(),
+            request_defaults: team.request_defaults().clone(),
+            organization_id: team.organization_id().map(|o| o.as_str().to_string()),
             created_at: team.created_at().to_rfc3339(),
             updated_at: team.updated_at().to_rfc3339(),
         }
@@ -58,13 +88,62 @@ impl From<&Team> for TeamResponse {
 }
 
 /// List teams response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListTeamsResponse {
     pub teams: Vec<TeamResponse>,
     pub total: usize,
 }
 
-/// GET /admin/teams
+/// Team quota response: configured limits and current usage
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TeamQuotaResponse {
+    pub team_id: String,
+    pub limits: TeamQuota,
+    pub usage: QuotaUsage,
+}
+
+/// Parse a provider region from an admin request
+pub(super) fn parse_region(region: &str, param: &str) -> Result<Region, ApiError> {
+    region.parse::<Region>().map_err(|e| {
+        ApiError::bad_request(format!("Invalid region '{}': {}", region, e)).with_param(param)
+    })
+}
+
+/// Parse a list of allowed provider regions from an admin request
+fn parse_allowed_regions(regions: &[String]) -> Result<Vec<Region>, ApiError> {
+    regions
+        .iter()
+        .map(|r| parse_region(r, "allowed_regions"))
+        .collect()
+}
+
+/// Resolve the team owning a newly created resource: the requested team if
+/// given, otherwise the team of the caller
+pub(super) async fn resolve_owner_team(
+    state: &AppState,
+    admin: &AdminAuth,
+    team_id: Option<&str>,
+) -> Result<TeamId, ApiError> {
+    let Some(team_id) = team_id else {
+        return Ok(admin.team_id().clone());
+    };
+
+    state
+        .team_service
+        .get(team_id)
+        .await
+        .map_err(ApiError::from)?
+        .map(|team| team.id().clone())
+        .ok_or_else(|| ApiError::bad_request(format!("Team '{}' not found", team_id)))
+}
+
+/// List teams
+#[utoipa::path(
+    get,
+    path = "/admin/teams",
+    tag = "admin/teams",
+    responses((status = 200, body = ListTeamsResponse)),
+)]
 pub async fn list_teams(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -82,7 +161,14 @@ pub async fn list_teams(
     }))
 }
 
-/// POST /admin/teams
+/// Create team
+#[utoipa::path(
+    post,
+    path = "/admin/teams",
+    tag = "admin/teams",
+    request_body = CreateTeamApiRequest,
+    responses((status = 200, body = TeamResponse)),
+)]
 pub async fn create_team(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -90,22 +176,58 @@ pub async fn create_team(
 ) -> Result<Json<TeamResponse>, ApiError> {
     debug!(id = %request.id, name = %request.name, "Admin creating team");
 
+    let allowed_cidrs = request
+        .allowed_cidrs
+        .as_deref()
+        .map(parse_allowed_cidrs)
+        .transpose()?;
+    let allowed_regions = request
+        .allowed_regions
+        .as_deref()
+        .map(parse_allowed_regions)
+        .transpose()?;
+
     let service_request = CreateTeamRequest {
         id: request.id,
         name: request.name,
         description: request.description,
     };
 
-    let team = state
+    let mut team = state
         .team_service
         .create(service_request)
         .await
         .map_err(ApiError::from)?;
 
+    if allowed_cidrs.is_some() || allowed_regions.is_some() || request.no_log {
+        let update = UpdateTeamRequest {
+            name: None,
+            description: None,
+            allowed_cidrs,
+            allowed_regions,
+            no_log: request.no_log.then_some(true),
+            quota: None,
+            request_defaults: None,
+        };
+
+        team = state
+            .team_service
+            .update(team.id().as_str(), update)
+            .await
+            .map_err(ApiError::from)?;
+    }
+
     Ok(Json(TeamResponse::from(&team)))
 }
 
-/// GET /admin/teams/:team_id
+/// Get team
+#[utoipa::path(
+    get,
+    path = "/admin/teams/{team_id}",
+    tag = "admin/teams",
+    params(("team_id" = String, Path, description = "Team ID")),
+    responses((status = 200, body = TeamResponse)),
+)]
 pub async fn get_team(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -123,7 +245,15 @@ pub async fn get_team(
     Ok(Json(TeamResponse::from(&team)))
 }
 
-/// PUT /admin/teams/:team_id
+/// Update team
+#[utoipa::path(
+    put,
+    path = "/admin/teams/{team_id}",
+    tag = "admin/teams",
+    params(("team_id" = String, Path, description = "Team ID")),
+    request_body = UpdateTeamApiRequest,
+    responses((status = 200, body = TeamResponse)),
+)]
 pub async fn update_team(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -135,6 +265,19 @@ pub async fn update_team(
     let service_request = UpdateTeamRequest {
         name: request.name,
         description: request.description,
+        allowed_cidrs: request
+            .allowed_cidrs
+            .as_deref()
+            .map(parse_allowed_cidrs)
+            .transpose()?,
+        allowed_regions: request
+            .allowed_regions
+            .as_deref()
+            .map(parse_allowed_regions)
+            .transpose()?,
+        no_log: request.no_log,
+        quota: None,
+        request_defaults: None,
     };
 
     let team = state
@@ -146,7 +289,14 @@ pub async fn update_team(
     Ok(Json(TeamResponse::from(&team)))
 }
 
-/// DELETE /admin/teams/:team_id
+/// Delete team
+#[utoipa::path(
+    delete,
+    path = "/admin/teams/{team_id}",
+    tag = "admin/teams",
+    params(("team_id" = String, Path, description = "Team ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn delete_team(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -166,7 +316,14 @@ pub async fn delete_team(
     })))
 }
 
-/// POST /admin/teams/:team_id/suspend
+/// Suspend team
+#[utoipa::path(
+    post,
+    path = "/admin/teams/{team_id}/suspend",
+    tag = "admin/teams",
+    params(("team_id" = String, Path, description = "Team ID")),
+    responses((status = 200, body = TeamResponse)),
+)]
 pub async fn suspend_team(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -183,7 +340,14 @@ pub async fn suspend_team(
     Ok(Json(TeamResponse::from(&team)))
 }
 
-/// POST /admin/teams/:team_id/activate
+/// Activate team
+#[utoipa::path(
+    post,
+    path = "/admin/teams/{team_id}/activate",
+    tag = "admin/teams",
+    params(("team_id" = String, Path, description = "Team ID")),
+    responses((status = 200, body = TeamResponse)),
+)]
 pub async fn activate_team(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -200,6 +364,170 @@ pub async fn activate_team(
     Ok(Json(TeamResponse::from(&team)))
 }
 
+/// Get team quota
+#[utoipa::path(
+    get,
+    path = "/admin/teams/{team_id}/quota",
+    tag = "admin/teams",
+    params(("team_id" = String, Path, description = "Team ID")),
+    responses((status = 200, body = TeamQuotaResponse)),
+)]
+pub async fn get_team_quota(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(team_id): Path<String>,
+) -> Result<Json<TeamQuotaResponse>, ApiError> {
+    debug!(team_id = %team_id, "Admin getting team quota");
+
+    let team = state
+        .team_service
+        .get(&team_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found(format!("Team '{}' not found", team_id)))?;
+
+    let usage = quota_usage(&state, team.id()).await?;
+
+    Ok(Json(TeamQuotaResponse {
+        team_id: team.id().as_str().to_string(),
+        limits: team.quota().clone(),
+        usage,
+    }))
+}
+
+/// Replace the quota of a team; omitted limits are unlimited
+#[utoipa::path(
+    put,
+    path = "/admin/teams/{team_id}/quota",
+    tag = "admin/teams",
+    params(("team_id" = String, Path, description = "Team ID")),
+    request_body = TeamQuota,
+    responses((status = 200, body = TeamQuotaResponse)),
+)]
+pub async fn update_team_quota(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(team_id): Path<String>,
+    Json(quota): Json<TeamQuota>,
+) -> Result<Json<TeamQuotaResponse>, ApiError> {
+    debug!(team_id = %team_id, "Admin updating team quota");
+
+    let update = UpdateTeamRequest {
+        name: None,
+        description: None,
+        allowed_cidrs: None,
+        allowed_regions: None,
+        no_log: None,
+        quota: Some(quota),
+        request_defaults: None,
+    };
+
+    let team = state
+        .team_service
+        .update(&team_id, update)
+        .await
+        .map_err(ApiError::from)?;
+
+    let usage = quota_usage(&state, team.id()).await?;
+
+    Ok(Json(TeamQuotaResponse {
+        team_id: team.id().as_str().to_string(),
+        limits: team.quota().clone(),
+        usage,
+    }))
+}
+
+/// Get the chat completion parameter defaults and limits of a team
+#[utoipa::path(
+    get,
+    path = "/admin/teams/{team_id}/request-defaults",
+    tag = "admin/teams",
+    params(("team_id" = String, Path, description = "Team ID")),
+    responses((status = 200, body = TeamRequestDefaults)),
+)]
+pub async fn get_team_request_defaults(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(team_id): Path<String>,
+) -> Result<Json<TeamRequestDefaults>, ApiError> {
+    debug!(team_id = %team_id, "Admin getting team request defaults");
+
+    let team = state
+        .team_service
+        .get(&team_id)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found(format!("Team '{}' not found", team_id)))?;
+
+    Ok(Json(team.request_defaults().clone()))
+}
+
+/// Replace the chat completion parameter defaults and limits of a team;
+/// omitted fields are unset
+#[utoipa::path(
+    put,
+    path = "/admin/teams/{team_id}/request-defaults",
+    tag = "admin/teams",
+    params(("team_id" = String, Path, description = "Team ID")),
+    request_body = TeamRequestDefaults,
+    responses((status = 200, body = TeamRequestDefaults)),
+)]
+pub async fn update_team_request_defaults(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(team_id): Path<String>,
+    Json(request_defaults): Json<TeamRequestDefaults>,
+) -> Result<Json<TeamRequestDefaults>, ApiError> {
+    debug!(team_id = %team_id, "Admin updating team request defaults");
+
+    if let Some(model_id) = &request_defaults.default_model
+        && state.model_service.get(model_id).await?.is_none()
+    {
+        return Err(ApiError::bad_request(format!("Model '{}' not found", model_id))
+            .with_param("default_model"));
+    }
+
+    let update = UpdateTeamRequest {
+        name: None,
+        description: None,
+        allowed_cidrs: None,
+        allowed_regions: None,
+        no_log: None,
+        quota: None,
+        request_defaults: Some(request_defaults),
+    };
+
+    let team = state
+        .team_service
+        .update(&team_id, update)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(team.request_defaults().clone()))
+}
+
+async fn quota_usage(state: &AppState, team_id: &TeamId) -> Result<QuotaUsage, ApiError> {
+    let kb_ids: Vec<String> = state
+        .knowledge_base_service
+        .list()
+        .await?
+        .iter()
+        .filter(|kb| kb.team_id() == Some(team_id))
+        .map(|kb| kb.id().as_str().to_string())
+        .collect();
+
+    let documents = state.ingestion_service.document_usage(&kb_ids).await?;
+
+    Ok(QuotaUsage {
+        api_keys: state.api_key_service.count_for_team(team_id).await?,
+        knowledge_bases: kb_ids.len() as u64,
+        workflows: state.workflow_service.count_for_team(team_id).await?,
+        kb_documents: documents.documents,
+        kb_storage_bytes: documents.storage_bytes,
+        concurrent_requests: state.team_concurrency.in_flight(team_id),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -262,6 +590,28 @@ mod tests {
         let request: UpdateTeamApiRequest = serde_json::from_str(json).unwrap();
         assert!(request.name.is_none());
         assert!(request.description.is_none());
+        assert!(request.allowed_cidrs.is_none());
+    }
+
+    #[test]
+    fn test_update_team_request_allowed_cidrs() {
+        let json = r#"{
+            "allowed_cidrs": ["10.0.0.0/8", "2001:db8::/32"]
+        }"#;
+
+        let request: UpdateTeamApiRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.allowed_cidrs.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_parse_allowed_regions() {
+        let regions =
+            parse_allowed_regions(&["EU".to_string(), "eu-west-1".to_string()]).unwrap();
+        assert_eq!(regions[0].as_str(), "eu");
+        assert_eq!(regions[1].as_str(), "eu-west-1");
+
+        let error = parse_allowed_regions(&["eu west".to_string()]).unwrap_err();
+        assert_eq!(error.status, axum::http::StatusCode::BAD_REQUEST);
     }
 
     #[test]
@@ -3,10 +3,13 @@
 use axum::extract::{Path, Query, State};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
+use utoipa::{IntoParams, ToSchema};
 
+use crate::api::admin::api_keys::deserialize_present;
 use crate::api::middleware::RequireAdmin;
 use crate::api::state::AppState;
 use crate::api::types::{ApiError, Json};
+use crate::domain::dataset::{DatasetRef, DatasetSelector};
 use crate::domain::test_case::{
     AssertionCriteria, AssertionOperator, ModelPromptInput, TestCase, TestCaseInput, TestCaseQuery,
     TestCaseResultQuery, TestCaseType, WorkflowInput,
@@ -16,19 +19,21 @@ use crate::infrastructure::services::{
 };
 
 /// Query parameters for listing test cases
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListTestCasesQuery {
     pub test_type: Option<String>,
     pub enabled: Option<bool>,
     pub tag: Option<String>,
     pub model_id: Option<String>,
     pub workflow_id: Option<String>,
+    pub canary: Option<bool>,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
 }
 
 /// Request to create a new test case
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct CreateTestCaseApiRequest {
     pub id: String,
     pub name: String,
@@ -41,6 +46,12 @@ pub struct CreateTestCaseApiRequest {
     pub tags: Vec<String>,
     #[serde(default = "default_enabled")]
     pub enabled: bool,
+    /// Dataset to run against, pinned to the latest version when none is given
+    #[serde(default)]
+    pub dataset: Option<DatasetSelector>,
+    /// Run on the canary schedule as a health signal for its model
+    #[serde(default)]
+    pub canary: bool,
 }
 
 fn default_enabled() -> bool {
@@ -48,7 +59,7 @@ fn default_enabled() -> bool {
 }
 
 /// Test case input configuration in API request
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum TestCaseInputApiRequest {
     ModelPrompt(ModelPromptInputApiRequest),
@@ -56,7 +67,7 @@ pub enum TestCaseInputApiRequest {
 }
 
 /// Model+prompt input in API request
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct ModelPromptInputApiRequest {
     pub model_id: String,
     #[serde(default)]
@@ -71,7 +82,7 @@ pub struct ModelPromptInputApiRequest {
 }
 
 /// Workflow input in API request
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct WorkflowInputApiRequest {
     pub workflow_id: String,
     #[serde(default)]
@@ -79,7 +90,7 @@ pub struct WorkflowInputApiRequest {
 }
 
 /// Assertion in API request
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct AssertionApiRequest {
     pub name: String,
     pub operator: String,
@@ -89,7 +100,7 @@ pub struct AssertionApiRequest {
 }
 
 /// Request to update a test case
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
 pub struct UpdateTestCaseApiRequest {
     pub name: Option<String>,
     pub description: Option<Option<String>>,
@@ -97,10 +108,14 @@ pub struct UpdateTestCaseApiRequest {
     pub assertions: Option<Vec<AssertionApiRequest>>,
     pub tags: Option<Vec<String>>,
     pub enabled: Option<bool>,
+    /// Absent leaves the dataset unchanged, null detaches it
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub dataset: Option<Option<DatasetSelector>>,
+    pub canary: Option<bool>,
 }
 
 /// Test case response for admin API
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct TestCaseResponse {
     pub id: String,
     pub name: String,
@@ -110,12 +125,15 @@ pub struct TestCaseResponse {
     pub assertions: Vec<AssertionResponse>,
     pub tags: Vec<String>,
     pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dataset: Option<DatasetRef>,
+    pub canary: bool,
     pub created_at: String,
     pub updated_at: String,
 }
 
 /// Test case input in response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum TestCaseInputResponse {
     ModelPrompt(ModelPromptInputResponse),
@@ -123,7 +141,7 @@ pub enum TestCaseInputResponse {
 }
 
 /// Model+prompt input in response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ModelPromptInputResponse {
     pub model_id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -138,14 +156,14 @@ pub struct ModelPromptInputResponse {
 }
 
 /// Workflow input in response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct WorkflowInputResponse {
     pub workflow_id: String,
     pub input: serde_json::Value,
 }
 
 /// Assertion in response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct AssertionResponse {
     pub name: String,
     pub operator: String,
@@ -155,21 +173,21 @@ pub struct AssertionResponse {
 }
 
 /// List test cases response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListTestCasesResponse {
     pub test_cases: Vec<TestCaseResponse>,
     pub total: usize,
 }
 
 /// List test case results response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ListTestCaseResultsResponse {
     pub results: Vec<TestCaseResultResponse>,
     pub total: usize,
 }
 
 /// Test case execution result response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ExecuteTestCaseApiResponse {
     pub test_case_id: String,
     pub test_case_name: String,
@@ -181,10 +199,12 @@ pub struct ExecuteTestCaseApiResponse {
     pub tokens_used: Option<TokenUsageResponse>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dataset: Option<DatasetRef>,
 }
 
 /// Assertion result in API response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct AssertionResultApiResponse {
     pub name: String,
     pub passed: bool,
@@ -197,14 +217,22 @@ pub struct AssertionResultApiResponse {
 }
 
 /// Token usage in API response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[schema(as = TestCaseTokenUsageResponse)]
 pub struct TokenUsageResponse {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
     pub total_tokens: u32,
 }
 
-/// GET /admin/test-cases
+/// List test cases
+#[utoipa::path(
+    get,
+    path = "/admin/test-cases",
+    tag = "admin/test-cases",
+    params(ListTestCasesQuery),
+    responses((status = 200, body = ListTestCasesResponse)),
+)]
 pub async fn list_test_cases(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -235,6 +263,10 @@ pub async fn list_test_cases(
         query = query.with_workflow_id(workflow_id);
     }
 
+    if let Some(canary) = params.canary {
+        query = query.with_canary(canary);
+    }
+
     if let Some(limit) = params.limit {
         query = query.with_limit(limit);
     }
@@ -253,7 +285,14 @@ pub async fn list_test_cases(
     }))
 }
 
-/// GET /admin/test-cases/:id
+/// Get test case
+#[utoipa::path(
+    get,
+    path = "/admin/test-cases/{test_case_id}",
+    tag = "admin/test-cases",
+    params(("test_case_id" = String, Path, description = "Test case ID")),
+    responses((status = 200, body = TestCaseResponse)),
+)]
 pub async fn get_test_case(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -270,7 +309,14 @@ pub async fn get_test_case(
     Ok(Json(to_response(test_case)))
 }
 
-/// POST /admin/test-cases
+/// Create test case
+#[utoipa::path(
+    post,
+    path = "/admin/test-cases",
+    tag = "admin/test-cases",
+    request_body = CreateTestCaseApiRequest,
+    responses((status = 200, body = TestCaseResponse)),
+)]
 pub async fn create_test_case(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -286,6 +332,8 @@ pub async fn create_test_case(
         assertions: convert_assertions(request.assertions)?,
         tags: request.tags,
         enabled: request.enabled,
+        dataset: request.dataset,
+        canary: request.canary,
     };
 
     let test_case = state.test_case_service.create(service_request).await?;
@@ -293,7 +341,15 @@ pub async fn create_test_case(
     Ok(Json(to_response(test_case)))
 }
 
-/// PUT /admin/test-cases/:id
+/// Update test case
+#[utoipa::path(
+    put,
+    path = "/admin/test-cases/{test_case_id}",
+    tag = "admin/test-cases",
+    params(("test_case_id" = String, Path, description = "Test case ID")),
+    request_body = UpdateTestCaseApiRequest,
+    responses((status = 200, body = TestCaseResponse)),
+)]
 pub async fn update_test_case(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -321,6 +377,8 @@ pub async fn update_test_case(
         assertions,
         tags: request.tags,
         enabled: request.enabled,
+        dataset: request.dataset,
+        canary: request.canary,
     };
 
     let test_case = state.test_case_service.update(&id, service_request).await?;
@@ -328,7 +386,14 @@ pub async fn update_test_case(
     Ok(Json(to_response(test_case)))
 }
 
-/// DELETE /admin/test-cases/:id
+/// Delete test case
+#[utoipa::path(
+    delete,
+    path = "/admin/test-cases/{test_case_id}",
+    tag = "admin/test-cases",
+    params(("test_case_id" = String, Path, description = "Test case ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn delete_test_case(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -348,7 +413,14 @@ pub async fn delete_test_case(
     }
 }
 
-/// POST /admin/test-cases/:id/execute
+/// Execute test case
+#[utoipa::path(
+    post,
+    path = "/admin/test-cases/{test_case_id}/execute",
+    tag = "admin/test-cases",
+    params(("test_case_id" = String, Path, description = "Test case ID")),
+    responses((status = 200, body = ExecuteTestCaseApiResponse)),
+)]
 pub async fn execute_test_case(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -382,12 +454,23 @@ pub async fn execute_test_case(
             total_tokens: t.total_tokens,
         }),
         error: result.error,
+        dataset: result.dataset,
     };
 
     Ok(Json(response))
 }
 
-/// GET /admin/test-cases/:id/results
+/// Get test case results
+#[utoipa::path(
+    get,
+    path = "/admin/test-cases/{test_case_id}/results",
+    tag = "admin/test-cases",
+    params(
+        ("test_case_id" = String, Path, description = "Test case ID"),
+        ListResultsQuery,
+    ),
+    responses((status = 200, body = ListTestCaseResultsResponse)),
+)]
 pub async fn get_test_case_results(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -433,6 +516,7 @@ pub async fn get_test_case_results(
                 .collect(),
             execution_time_ms: r.execution_time_ms(),
             error: r.error().map(|s| s.to_string()),
+            dataset: r.dataset().cloned(),
             executed_at: r.executed_at().to_rfc3339(),
         })
         .collect();
@@ -444,14 +528,15 @@ pub async fn get_test_case_results(
 }
 
 /// Query parameters for listing results
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListResultsQuery {
     pub passed: Option<bool>,
     pub limit: Option<usize>,
 }
 
 /// Test case result response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct TestCaseResultResponse {
     pub id: String,
     pub test_case_id: String,
@@ -462,6 +547,8 @@ pub struct TestCaseResultResponse {
     pub execution_time_ms: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dataset: Option<DatasetRef>,
     pub executed_at: String,
 }
 
@@ -568,6 +655,8 @@ fn to_response(test_case: TestCase) -> TestCaseResponse {
         assertions,
         tags: test_case.tags().to_vec(),
         enabled: test_case.is_enabled(),
+        canary: test_case.is_canary(),
+        dataset: test_case.dataset().cloned(),
         created_at: test_case.created_at().to_rfc3339(),
         updated_at: test_case.updated_at().to_rfc3339(),
     }
@@ -900,6 +989,7 @@ mod tests {
                 total_tokens: 75,
             }),
             error: None,
+            dataset: None,
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -919,11 +1009,13 @@ mod tests {
             execution_time_ms: 50,
             tokens_used: None,
             error: Some("Model error".to_string()),
+            dataset: Some(DatasetRef::new("faq", 2)),
         };
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"passed\":false"));
         assert!(json.contains("\"error\":\"Model error\""));
+        assert!(json.contains("\"dataset\":{\"dataset_id\":\"faq\",\"version\":2}"));
     }
 
     #[test]
@@ -960,6 +1052,7 @@ mod tests {
             assertion_results: vec![],
             execution_time_ms: 200,
             error: None,
+            dataset: None,
             executed_at: "2024-01-01T00:00:00Z".to_string(),
         };
 
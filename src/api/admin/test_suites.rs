@@ -0,0 +1,461 @@
+//! Test suite management admin endpoints
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{debug, error, info, warn};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, AsyncOperationCreated, Json};
+use crate::domain::test_case::{
+    TestSuite, TestSuiteCaseOutcome, TestSuiteRun, DEFAULT_PASS_THRESHOLD,
+    DEFAULT_SUITE_CONCURRENCY,
+};
+use crate::domain::{DomainError, Operation, OperationType};
+use crate::infrastructure::background::spawn_tracked;
+use crate::infrastructure::services::{CreateTestSuiteRequest, UpdateTestSuiteRequest};
+
+/// Request to create a new test suite
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateTestSuiteApiRequest {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub test_case_ids: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default = "default_pass_threshold")]
+    pub pass_threshold: f64,
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+}
+
+fn default_pass_threshold() -> f64 {
+    DEFAULT_PASS_THRESHOLD
+}
+
+fn default_concurrency() -> usize {
+    DEFAULT_SUITE_CONCURRENCY
+}
+
+/// Request to update a test suite
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct UpdateTestSuiteApiRequest {
+    pub name: Option<String>,
+    pub description: Option<Option<String>>,
+    pub test_case_ids: Option<Vec<String>>,
+    pub tags: Option<Vec<String>>,
+    pub pass_threshold: Option<f64>,
+    pub concurrency: Option<usize>,
+}
+
+/// Query parameters for running a test suite
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RunTestSuiteQuery {
+    /// Overrides the suite pass threshold for this run
+    pub pass_threshold: Option<f64>,
+}
+
+/// Query parameters for listing suite runs
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListRunsQuery {
+    pub limit: Option<usize>,
+}
+
+/// Test suite response for admin API
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TestSuiteResponse {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub test_case_ids: Vec<String>,
+    pub tags: Vec<String>,
+    pub pass_threshold: f64,
+    pub concurrency: usize,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// List test suites response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListTestSuitesResponse {
+    pub test_suites: Vec<TestSuiteResponse>,
+    pub total: usize,
+}
+
+/// Aggregated report of a test suite run
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TestSuiteRunResponse {
+    pub id: String,
+    pub suite_id: String,
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub pass_rate: f64,
+    pub pass_threshold: f64,
+    pub gate_passed: bool,
+    pub execution_time_ms: u64,
+    pub cases: Vec<TestSuiteCaseOutcome>,
+    pub started_at: String,
+    pub completed_at: String,
+}
+
+/// List test suite runs response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ListTestSuiteRunsResponse {
+    pub runs: Vec<TestSuiteRunResponse>,
+    pub total: usize,
+}
+
+/// List test suites
+#[utoipa::path(
+    get,
+    path = "/admin/test-suites",
+    tag = "admin/test-suites",
+    responses((status = 200, body = ListTestSuitesResponse)),
+)]
+pub async fn list_test_suites(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+) -> Result<Json<ListTestSuitesResponse>, ApiError> {
+    debug!("Listing test suites");
+
+    let suites = state.test_suite_service.list().await?;
+    let test_suites: Vec<TestSuiteResponse> = suites.into_iter().map(to_response).collect();
+
+    Ok(Json(ListTestSuitesResponse {
+        total: test_suites.len(),
+        test_suites,
+    }))
+}
+
+/// Get test suite
+#[utoipa::path(
+    get,
+    path = "/admin/test-suites/{test_suite_id}",
+    tag = "admin/test-suites",
+    params(("test_suite_id" = String, Path, description = "Test suite ID")),
+    responses((status = 200, body = TestSuiteResponse)),
+)]
+pub async fn get_test_suite(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+) -> Result<Json<TestSuiteResponse>, ApiError> {
+    debug!(test_suite_id = %id, "Getting test suite");
+
+    let suite = state
+        .test_suite_service
+        .get(&id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Test suite '{}' not found", id)))?;
+
+    Ok(Json(to_response(suite)))
+}
+
+/// Create test suite
+#[utoipa::path(
+    post,
+    path = "/admin/test-suites",
+    tag = "admin/test-suites",
+    request_body = CreateTestSuiteApiRequest,
+    responses((status = 200, body = TestSuiteResponse)),
+)]
+pub async fn create_test_suite(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Json(request): Json<CreateTestSuiteApiRequest>,
+) -> Result<Json<TestSuiteResponse>, ApiError> {
+    info!(test_suite_id = %request.id, "Creating test suite");
+
+    let service_request = CreateTestSuiteRequest {
+        id: request.id,
+        name: request.name,
+        description: request.description,
+        test_case_ids: request.test_case_ids,
+        tags: request.tags,
+        pass_threshold: Some(request.pass_threshold),
+        concurrency: Some(request.concurrency),
+    };
+
+    let suite = state.test_suite_service.create(service_request).await?;
+
+    Ok(Json(to_response(suite)))
+}
+
+/// Update test suite
+#[utoipa::path(
+    put,
+    path = "/admin/test-suites/{test_suite_id}",
+    tag = "admin/test-suites",
+    params(("test_suite_id" = String, Path, description = "Test suite ID")),
+    request_body = UpdateTestSuiteApiRequest,
+    responses((status = 200, body = TestSuiteResponse)),
+)]
+pub async fn update_test_suite(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateTestSuiteApiRequest>,
+) -> Result<Json<TestSuiteResponse>, ApiError> {
+    info!(test_suite_id = %id, "Updating test suite");
+
+    let service_request = UpdateTestSuiteRequest {
+        name: request.name,
+        description: request.description,
+        test_case_ids: request.test_case_ids,
+        tags: request.tags,
+        pass_threshold: request.pass_threshold,
+        concurrency: request.concurrency,
+    };
+
+    let suite = state.test_suite_service.update(&id, service_request).await?;
+
+    Ok(Json(to_response(suite)))
+}
+
+/// Delete test suite
+#[utoipa::path(
+    delete,
+    path = "/admin/test-suites/{test_suite_id}",
+    tag = "admin/test-suites",
+    params(("test_suite_id" = String, Path, description = "Test suite ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
+pub async fn delete_test_suite(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    info!(test_suite_id = %id, "Deleting test suite");
+
+    let deleted = state.test_suite_service.delete(&id).await?;
+
+    if deleted {
+        Ok(Json(json!({"deleted": true})))
+    } else {
+        Err(ApiError::not_found(format!(
+            "Test suite '{}' not found",
+            id
+        )))
+    }
+}
+
+/// Runs every case of the suite in the background. The operation result is
+/// the run report, also listed under `/admin/test-suites/:id/runs`.
+#[utoipa::path(
+    post,
+    path = "/admin/test-suites/{test_suite_id}/run",
+    tag = "admin/test-suites",
+    params(
+        ("test_suite_id" = String, Path, description = "Test suite ID"),
+        RunTestSuiteQuery,
+    ),
+    responses((status = 202, body = AsyncOperationCreated)),
+)]
+pub async fn run_test_suite(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+    Query(params): Query<RunTestSuiteQuery>,
+) -> Result<(StatusCode, Json<AsyncOperationCreated>), ApiError> {
+    // Fail fast on unknown suites instead of queueing a failing operation
+    state
+        .test_suite_service
+        .get(&id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("Test suite '{}' not found", id)))?;
+
+    let operation = state
+        .operation_service
+        .create_pending(
+            OperationType::TestSuiteRun,
+            json!({ "pass_threshold": params.pass_threshold }),
+            json!({ "test_suite_id": &id }),
+        )
+        .await?;
+
+    let operation_id = operation.id().to_string();
+    info!(
+        operation_id = %operation_id,
+        test_suite_id = %id,
+        "Created test suite run operation"
+    );
+
+    let op_id = operation_id.clone();
+    spawn_tracked(async move {
+        execute_test_suite_run(state, op_id, id, params.pass_threshold).await
+    });
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(AsyncOperationCreated::pending(&operation_id)),
+    ))
+}
+
+/// Run a suite in the background and update the operation status
+///
+/// Returns a boxed future to avoid stack overflow from large future sizes
+/// caused by trait object indirection in AppState.
+/// Run a requeued test suite run again, with the suite and threshold
+/// recorded in its operation
+pub(crate) fn resume_test_suite_run(
+    state: &AppState,
+    operation: &Operation,
+) -> Result<(), DomainError> {
+    let suite_id = operation.metadata()["test_suite_id"]
+        .as_str()
+        .map(String::from)
+        .ok_or_else(|| DomainError::validation("Operation has no 'test_suite_id'"))?;
+    let pass_threshold = operation.input()["pass_threshold"].as_f64();
+
+    spawn_tracked(execute_test_suite_run(
+        state.clone(),
+        operation.id().to_string(),
+        suite_id,
+        pass_threshold,
+    ));
+
+    Ok(())
+}
+
+fn execute_test_suite_run(
+    state: AppState,
+    operation_id: String,
+    suite_id: String,
+    pass_threshold: Option<f64>,
+) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>> {
+    Box::pin(async move {
+        if let Err(e) = state.operation_service.mark_running(&operation_id).await {
+            warn!(
+                operation_id = %operation_id,
+                error = %e,
+                "Failed to mark operation as running"
+            );
+            return;
+        }
+
+        let marked = match state.test_suite_service.run(&suite_id, pass_threshold).await {
+            Ok(run) => {
+                let result = serde_json::to_value(to_run_response(run)).unwrap_or(json!({}));
+                state
+                    .operation_service
+                    .mark_completed(&operation_id, result)
+                    .await
+            }
+            Err(e) => {
+                warn!(
+                    operation_id = %operation_id,
+                    test_suite_id = %suite_id,
+                    error = %e,
+                    "Test suite run failed"
+                );
+                state
+                    .operation_service
+                    .mark_failed(&operation_id, e.to_string())
+                    .await
+            }
+        };
+
+        if let Err(e) = marked {
+            error!(
+                operation_id = %operation_id,
+                error = %e,
+                "Failed to update test suite run operation"
+            );
+        }
+    })
+}
+
+/// List test suite runs
+#[utoipa::path(
+    get,
+    path = "/admin/test-suites/{test_suite_id}/runs",
+    tag = "admin/test-suites",
+    params(
+        ("test_suite_id" = String, Path, description = "Test suite ID"),
+        ListRunsQuery,
+    ),
+    responses((status = 200, body = ListTestSuiteRunsResponse)),
+)]
+pub async fn list_test_suite_runs(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(id): Path<String>,
+    Query(params): Query<ListRunsQuery>,
+) -> Result<Json<ListTestSuiteRunsResponse>, ApiError> {
+    debug!(test_suite_id = %id, "Listing test suite runs");
+
+    let runs = state.test_suite_service.list_runs(&id, params.limit).await?;
+    let runs: Vec<TestSuiteRunResponse> = runs.into_iter().map(to_run_response).collect();
+
+    Ok(Json(ListTestSuiteRunsResponse {
+        total: runs.len(),
+        runs,
+    }))
+}
+
+/// Get test suite run
+#[utoipa::path(
+    get,
+    path = "/admin/test-suites/{test_suite_id}/runs/{run_id}",
+    tag = "admin/test-suites",
+    params(
+        ("test_suite_id" = String, Path, description = "Test suite ID"),
+        ("run_id" = String, Path, description = "Run ID"),
+    ),
+    responses((status = 200, body = TestSuiteRunResponse)),
+)]
+pub async fn get_test_suite_run(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path((id, run_id)): Path<(String, String)>,
+) -> Result<Json<TestSuiteRunResponse>, ApiError> {
+    debug!(test_suite_id = %id, run_id = %run_id, "Getting test suite run");
+
+    let run = state
+        .test_suite_service
+        .get_run(&run_id)
+        .await?
+        .filter(|run| run.suite_id.as_str() == id)
+        .ok_or_else(|| ApiError::not_found(format!("Test suite run '{}' not found", run_id)))?;
+
+    Ok(Json(to_run_response(run)))
+}
+
+fn to_response(suite: TestSuite) -> TestSuiteResponse {
+    TestSuiteResponse {
+        id: suite.id().to_string(),
+        name: suite.name().to_string(),
+        description: suite.description().map(String::from),
+        test_case_ids: suite.test_case_ids().iter().map(|id| id.to_string()).collect(),
+        tags: suite.tags().to_vec(),
+        pass_threshold: suite.pass_threshold(),
+        concurrency: suite.concurrency(),
+        created_at: suite.created_at().to_rfc3339(),
+        updated_at: suite.updated_at().to_rfc3339(),
+    }
+}
+
+fn to_run_response(run: TestSuiteRun) -> TestSuiteRunResponse {
+    TestSuiteRunResponse {
+        id: run.id.to_string(),
+        suite_id: run.suite_id.to_string(),
+        total: run.total,
+        passed: run.passed,
+        failed: run.failed,
+        pass_rate: run.pass_rate,
+        pass_threshold: run.pass_threshold,
+        gate_passed: run.gate_passed,
+        execution_time_ms: run.execution_time_ms,
+        cases: run.cases,
+        started_at: run.started_at.to_rfc3339(),
+        completed_at: run.completed_at.to_rfc3339(),
+    }
+}
@@ -0,0 +1,285 @@
+//! Transform rule admin endpoints
+
+use std::collections::HashMap;
+
+use axum::extract::{Path, State};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::api::admin::api_keys::deserialize_present;
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::transform::{TransformAction, TransformMatch, TransformRule, TransformTarget};
+
+// ============================================================================
+// Transform rule DTOs
+// ============================================================================
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateTransformRuleRequest {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// Rules are applied lowest priority first
+    #[serde(default)]
+    pub priority: i32,
+    /// Requests the rule applies to, every request when unset
+    #[serde(rename = "match", default)]
+    pub matcher: TransformMatch,
+    pub actions: Vec<TransformAction>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Partial update, unset fields keep their value; `null` clears the
+/// description
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateTransformRuleRequest {
+    pub name: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub description: Option<Option<String>>,
+    pub priority: Option<i32>,
+    #[serde(rename = "match")]
+    pub matcher: Option<TransformMatch>,
+    pub actions: Option<Vec<TransformAction>>,
+    pub enabled: Option<bool>,
+}
+
+/// Request to preview the rules applied to a request
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct EvaluateTransformRulesRequest {
+    pub route: String,
+    pub api_key_id: String,
+    pub model: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EvaluateTransformRulesResponse {
+    /// Matching rules, in the order they are applied
+    pub rule_ids: Vec<String>,
+    /// Model the request is served with
+    pub model: String,
+    pub system_messages: Vec<String>,
+    pub tags: HashMap<String, String>,
+    pub headers: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransformRuleListResponse {
+    pub rules: Vec<TransformRule>,
+    pub total: usize,
+}
+
+fn transform_rule_not_found(id: &str) -> ApiError {
+    ApiError::not_found(format!("Transform rule '{}' not found", id))
+}
+
+// ============================================================================
+// Transform rule endpoints
+// ============================================================================
+
+/// List every transform rule, in the order they are applied
+#[utoipa::path(
+    get,
+    path = "/admin/transform-rules",
+    tag = "admin/transform-rules",
+    responses((status = 200, body = TransformRuleListResponse)),
+)]
+pub async fn list_transform_rules(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+) -> Result<Json<TransformRuleListResponse>, ApiError> {
+    let rules = state.transform_rule_service.list().await?;
+    let total = rules.len();
+
+    Ok(Json(TransformRuleListResponse { rules, total }))
+}
+
+/// Create a transform rule
+#[utoipa::path(
+    post,
+    path = "/admin/transform-rules",
+    tag = "admin/transform-rules",
+    request_body = CreateTransformRuleRequest,
+    responses((status = 200, body = TransformRule)),
+)]
+pub async fn create_transform_rule(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Json(request): Json<CreateTransformRuleRequest>,
+) -> Result<Json<TransformRule>, ApiError> {
+    let mut rule = TransformRule::new(request.id, request.name)
+        .with_priority(request.priority)
+        .with_match(request.matcher);
+    rule.description = request.description;
+    rule.actions = request.actions;
+    rule.enabled = request.enabled;
+
+    let created = state.transform_rule_service.create(rule).await?;
+
+    Ok(Json(created))
+}
+
+/// Get a transform rule
+#[utoipa::path(
+    get,
+    path = "/admin/transform-rules/{id}",
+    tag = "admin/transform-rules",
+    params(("id" = String, Path, description = "Transform rule ID")),
+    responses((status = 200, body = TransformRule)),
+)]
+pub async fn get_transform_rule(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<TransformRule>, ApiError> {
+    let rule = state
+        .transform_rule_service
+        .get(&id)
+        .await?
+        .ok_or_else(|| transform_rule_not_found(&id))?;
+
+    Ok(Json(rule))
+}
+
+/// Change a transform rule
+#[utoipa::path(
+    put,
+    path = "/admin/transform-rules/{id}",
+    tag = "admin/transform-rules",
+    params(("id" = String, Path, description = "Transform rule ID")),
+    request_body = UpdateTransformRuleRequest,
+    responses((status = 200, body = TransformRule)),
+)]
+pub async fn update_transform_rule(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<UpdateTransformRuleRequest>,
+) -> Result<Json<TransformRule>, ApiError> {
+    let mut rule = state
+        .transform_rule_service
+        .get(&id)
+        .await?
+        .ok_or_else(|| transform_rule_not_found(&id))?;
+
+    if let Some(name) = request.name {
+        rule.name = name;
+    }
+    if let Some(description) = request.description {
+        rule.description = description;
+    }
+    if let Some(priority) = request.priority {
+        rule.priority = priority;
+    }
+    if let Some(matcher) = request.matcher {
+        rule.matcher = matcher;
+    }
+    if let Some(actions) = request.actions {
+        rule.actions = actions;
+    }
+    if let Some(enabled) = request.enabled {
+        rule.enabled = enabled;
+    }
+
+    let updated = state.transform_rule_service.update(rule).await?;
+
+    Ok(Json(updated))
+}
+
+/// Delete a transform rule
+#[utoipa::path(
+    delete,
+    path = "/admin/transform-rules/{id}",
+    tag = "admin/transform-rules",
+    params(("id" = String, Path, description = "Transform rule ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
+pub async fn delete_transform_rule(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    if state.transform_rule_service.delete(&id).await? {
+        Ok(Json(serde_json::json!({
+            "deleted": true,
+            "id": id
+        })))
+    } else {
+        Err(transform_rule_not_found(&id))
+    }
+}
+
+/// Preview the rules applied to a request without sending one
+#[utoipa::path(
+    post,
+    path = "/admin/transform-rules/evaluate",
+    tag = "admin/transform-rules",
+    request_body = EvaluateTransformRulesRequest,
+    responses((status = 200, body = EvaluateTransformRulesResponse)),
+)]
+pub async fn evaluate_transform_rules(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Json(request): Json<EvaluateTransformRulesRequest>,
+) -> Result<Json<EvaluateTransformRulesResponse>, ApiError> {
+    let outcome = state
+        .transform_rule_service
+        .evaluate(&TransformTarget {
+            route: &request.route,
+            api_key_id: &request.api_key_id,
+            model: &request.model,
+        })
+        .await?;
+
+    Ok(Json(EvaluateTransformRulesResponse {
+        rule_ids: outcome.rule_ids,
+        model: outcome.model.unwrap_or(request.model),
+        system_messages: outcome.system_messages,
+        tags: outcome.tags,
+        headers: outcome.headers.into_iter().collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_request_defaults() {
+        let request: CreateTransformRuleRequest = serde_json::from_str(
+            r#"{
+                "id": "legacy-model",
+                "name": "Legacy model",
+                "match": {"models": ["gpt-3.5-turbo"]},
+                "actions": [{"type": "rewrite_model", "model": "gpt-4o-mini"}]
+            }"#,
+        )
+        .unwrap();
+
+        assert!(request.enabled);
+        assert_eq!(request.priority, 0);
+        assert_eq!(request.matcher.models, vec!["gpt-3.5-turbo".to_string()]);
+        assert_eq!(
+            request.actions,
+            vec![TransformAction::RewriteModel {
+                model: "gpt-4o-mini".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_update_request_clears_description() {
+        let request: UpdateTransformRuleRequest =
+            serde_json::from_str(r#"{"description": null, "priority": 3}"#).unwrap();
+        assert_eq!(request.description, Some(None));
+        assert_eq!(request.priority, Some(3));
+        assert!(request.matcher.is_none());
+        assert!(request.actions.is_none());
+    }
+}
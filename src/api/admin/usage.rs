@@ -1,34 +1,64 @@
 //! Usage tracking and budget management admin endpoints
 
+use std::collections::HashMap;
+
 use axum::extract::{Path, Query, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use chrono::{NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
 use crate::api::middleware::RequireAdmin;
 use crate::api::state::AppState;
 use crate::api::types::{ApiError, Json};
 use crate::domain::usage::{
-    Budget, BudgetId, BudgetPeriod, BudgetScope, UsageAggregate, UsageRecord, UsageSummary,
+    parse_usage_tags, usage_series, Budget, BudgetId, BudgetPeriod, BudgetScope,
+    ReconciliationReport, TagUsage, TimeBucket, UsageAggregate, UsageBucket, UsageGroupBy, UsageQuery, UsageRecord, UsageSeries,
+    UsageSummary,
 };
+use crate::infrastructure::usage::{encode_usage_records, UsageExportFormat};
 
 // ============================================================================
 // Usage Query DTOs
 // ============================================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct UsageQueryParams {
     pub api_key_id: Option<String>,
+    pub team_id: Option<String>,
     pub model_id: Option<String>,
     pub from_timestamp: Option<u64>,
     pub to_timestamp: Option<u64>,
     pub limit: Option<usize>,
     pub offset: Option<usize>,
+    /// Only include records with these tags (`key=value,key=value`)
+    pub tags: Option<String>,
+    /// Break the aggregate down by the values of this tag key
+    pub group_by_tag: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UsageExportParams {
+    /// File format: "csv" (default) or "parquet"
+    pub format: Option<String>,
+    pub from_timestamp: u64,
+    pub to_timestamp: u64,
+    pub api_key_id: Option<String>,
+    pub model_id: Option<String>,
+    /// Only include records with these tags (`key=value,key=value`)
+    pub tags: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UsageRecordResponse {
     pub id: String,
     pub usage_type: String,
     pub api_key_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<String>,
     pub model_id: Option<String>,
     pub input_tokens: u32,
     pub output_tokens: u32,
@@ -38,6 +68,8 @@ pub struct UsageRecordResponse {
     pub success: bool,
     pub error: Option<String>,
     pub timestamp: u64,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub tags: HashMap<String, String>,
 }
 
 impl From<UsageRecord> for UsageRecordResponse {
@@ -47,6 +79,7 @@ impl From<UsageRecord> for UsageRecordResponse {
             id: record.id().to_string(),
             usage_type: record.usage_type.to_string(),
             api_key_id: record.api_key_id,
+            team_id: record.team_id,
             model_id: record.model_id,
             input_tokens: record.input_tokens,
             output_tokens: record.output_tokens,
@@ -56,11 +89,12 @@ impl From<UsageRecord> for UsageRecordResponse {
             success: record.success,
             error: record.error,
             timestamp: record.timestamp,
+            tags: record.tags,
         }
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UsageAggregateResponse {
     pub total_requests: u64,
     pub successful_requests: u64,
@@ -71,6 +105,26 @@ pub struct UsageAggregateResponse {
     pub total_cost_usd: f64,
     pub avg_latency_ms: f64,
     pub success_rate: f64,
+    /// Usage per value of the `group_by_tag` key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub by_tag: Option<HashMap<String, TagUsageResponse>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TagUsageResponse {
+    pub requests: u64,
+    pub tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl From<&TagUsage> for TagUsageResponse {
+    fn from(usage: &TagUsage) -> Self {
+        Self {
+            requests: usage.requests,
+            tokens: usage.tokens,
+            cost_usd: usage.cost_micros as f64 / 1_000_000.0,
+        }
+    }
 }
 
 impl From<UsageAggregate> for UsageAggregateResponse {
@@ -85,11 +139,12 @@ impl From<UsageAggregate> for UsageAggregateResponse {
             total_cost_usd: agg.total_cost_usd(),
             avg_latency_ms: agg.avg_latency_ms,
             success_rate: agg.success_rate(),
+            by_tag: None,
         }
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UsageSummaryResponse {
     pub period_start: u64,
     pub period_end: u64,
@@ -97,7 +152,7 @@ pub struct UsageSummaryResponse {
     pub daily: Vec<DailyUsageResponse>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct DailyUsageResponse {
     pub date: u64,
     pub requests: u64,
@@ -125,7 +180,96 @@ impl From<UsageSummary> for UsageSummaryResponse {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UsageTimeseriesParams {
+    /// Bucket width: "hour" (default) or "day"
+    pub bucket: Option<String>,
+    /// Split into one series per "team", "model" or "api_key"
+    pub group_by: Option<String>,
+    /// Defaults to 24 hours (hourly buckets) or 30 days (daily buckets) before `to_timestamp`
+    pub from_timestamp: Option<u64>,
+    /// Defaults to now
+    pub to_timestamp: Option<u64>,
+    pub api_key_id: Option<String>,
+    pub team_id: Option<String>,
+    pub model_id: Option<String>,
+    /// Only include records with these tags (`key=value,key=value`)
+    pub tags: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UsagePointResponse {
+    pub timestamp: u64,
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub total_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl From<&UsageBucket> for UsagePointResponse {
+    fn from(bucket: &UsageBucket) -> Self {
+        Self {
+            timestamp: bucket.bucket_start,
+            requests: bucket.requests,
+            input_tokens: bucket.input_tokens,
+            output_tokens: bucket.output_tokens,
+            total_tokens: bucket.total_tokens,
+            cost_usd: bucket.cost_micros as f64 / 1_000_000.0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UsageSeriesResponse {
+    /// Team, model or API key of the series; null when ungrouped or unknown
+    pub group: Option<String>,
+    pub total_requests: u64,
+    pub total_tokens: u64,
+    pub total_cost_usd: f64,
+    pub points: Vec<UsagePointResponse>,
+}
+
+impl From<UsageSeries> for UsageSeriesResponse {
+    fn from(series: UsageSeries) -> Self {
+        Self {
+            total_requests: series.points.iter().map(|p| p.requests).sum(),
+            total_tokens: series.points.iter().map(|p| p.total_tokens).sum(),
+            total_cost_usd: series.points.iter().map(|p| p.cost_micros).sum::<i64>() as f64
+                / 1_000_000.0,
+            points: series.points.iter().map(Into::into).collect(),
+            group: series.group,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UsageTimeseriesResponse {
+    pub bucket: String,
+    pub group_by: Option<String>,
+    pub from_timestamp: u64,
+    pub to_timestamp: u64,
+    pub series: Vec<UsageSeriesResponse>,
+}
+
+/// Maximum number of buckets a time series request may span
+const MAX_TIMESERIES_BUCKETS: u64 = 1_000;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UsageReconciliationParams {
+    /// Only reconcile this provider (e.g. "openai"), defaults to every configured provider
+    pub provider: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UsageReconciliationResponse {
+    pub date: NaiveDate,
+    pub reports: Vec<ReconciliationReport>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UsageListResponse {
     pub records: Vec<UsageRecordResponse>,
     pub count: usize,
@@ -135,7 +279,7 @@ pub struct UsageListResponse {
 // Budget DTOs
 // ============================================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateBudgetRequest {
     pub id: String,
     pub name: String,
@@ -145,11 +289,14 @@ pub struct CreateBudgetRequest {
     pub soft_limit_usd: Option<f64>,
     pub api_key_ids: Option<Vec<String>>,
     pub team_ids: Option<Vec<String>>,
+    pub organization_ids: Option<Vec<String>>,
     pub model_ids: Option<Vec<String>>,
+    /// Model requests are routed to when they would exceed the budget
+    pub fallback_model_id: Option<String>,
     pub alert_thresholds: Option<Vec<u8>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateBudgetRequest {
     pub name: Option<String>,
     pub description: Option<String>,
@@ -157,12 +304,15 @@ pub struct UpdateBudgetRequest {
     pub soft_limit_usd: Option<f64>,
     pub api_key_ids: Option<Vec<String>>,
     pub team_ids: Option<Vec<String>>,
+    pub organization_ids: Option<Vec<String>>,
     pub model_ids: Option<Vec<String>>,
+    /// Empty string removes the fallback model
+    pub fallback_model_id: Option<String>,
     pub alert_thresholds: Option<Vec<u8>>,
     pub enabled: Option<bool>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct BudgetResponse {
     pub id: String,
     pub name: String,
@@ -177,7 +327,9 @@ pub struct BudgetResponse {
     pub scope: String,
     pub api_key_ids: Vec<String>,
     pub team_ids: Vec<String>,
+    pub organization_ids: Vec<String>,
     pub model_ids: Vec<String>,
+    pub fallback_model_id: Option<String>,
     pub alerts: Vec<BudgetAlertResponse>,
     pub period_start: u64,
     pub enabled: bool,
@@ -185,7 +337,7 @@ pub struct BudgetResponse {
     pub updated_at: u64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct BudgetAlertResponse {
     pub threshold_percent: u8,
     pub triggered: bool,
@@ -217,7 +369,9 @@ impl From<Budget> for BudgetResponse {
             scope,
             api_key_ids: budget.api_key_ids,
             team_ids: budget.team_ids,
+            organization_ids: budget.organization_ids,
             model_ids: budget.model_ids,
+            fallback_model_id: budget.fallback_model_id,
             alerts: budget
                 .alerts
                 .into_iter()
@@ -240,11 +394,12 @@ fn scope_to_string(scope: BudgetScope) -> String {
         BudgetScope::AllApiKeys => "all_api_keys".to_string(),
         BudgetScope::SpecificApiKeys => "specific_api_keys".to_string(),
         BudgetScope::Teams => "teams".to_string(),
+        BudgetScope::Organizations => "organizations".to_string(),
         BudgetScope::Mixed => "mixed".to_string(),
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct BudgetListResponse {
     pub budgets: Vec<BudgetResponse>,
 }
@@ -254,12 +409,19 @@ pub struct BudgetListResponse {
 // ============================================================================
 
 /// List usage records
+#[utoipa::path(
+    get,
+    path = "/admin/usage",
+    tag = "admin/usage",
+    params(UsageQueryParams),
+    responses((status = 200, body = UsageListResponse)),
+)]
 pub async fn list_usage(
     RequireAdmin(_): RequireAdmin,
     State(state): State<AppState>,
     Query(params): Query<UsageQueryParams>,
 ) -> Result<Json<UsageListResponse>, ApiError> {
-    let query = build_usage_query(&params);
+    let query = build_usage_query(&params)?;
     let records = state.usage_service.query(&query).await?;
     let count = records.len();
 
@@ -270,35 +432,210 @@ pub async fn list_usage(
 }
 
 /// Get usage aggregate
+#[utoipa::path(
+    get,
+    path = "/admin/usage/aggregate",
+    tag = "admin/usage",
+    params(UsageQueryParams),
+    responses((status = 200, body = UsageAggregateResponse)),
+)]
 pub async fn get_usage_aggregate(
     RequireAdmin(_): RequireAdmin,
     State(state): State<AppState>,
     Query(params): Query<UsageQueryParams>,
 ) -> Result<Json<UsageAggregateResponse>, ApiError> {
-    let query = build_usage_query(&params);
+    let query = build_usage_query(&params)?;
     let aggregate = state.usage_service.aggregate(&query).await?;
 
-    Ok(Json(aggregate.into()))
+    let by_tag = params.group_by_tag.as_ref().map(|key| {
+        aggregate
+            .by_tag
+            .get(key)
+            .map(|values| values.iter().map(|(v, usage)| (v.clone(), usage.into())).collect())
+            .unwrap_or_default()
+    });
+
+    let mut response = UsageAggregateResponse::from(aggregate);
+    response.by_tag = by_tag;
+
+    Ok(Json(response))
 }
 
 /// Get usage summary with daily breakdown
+#[utoipa::path(
+    get,
+    path = "/admin/usage/summary",
+    tag = "admin/usage",
+    params(UsageQueryParams),
+    responses((status = 200, body = UsageSummaryResponse)),
+)]
 pub async fn get_usage_summary(
     RequireAdmin(_): RequireAdmin,
     State(state): State<AppState>,
     Query(params): Query<UsageQueryParams>,
 ) -> Result<Json<UsageSummaryResponse>, ApiError> {
-    let query = build_usage_query(&params);
+    let query = build_usage_query(&params)?;
     let summary = state.usage_service.summary(&query).await?;
 
     Ok(Json(summary.into()))
 }
 
+/// Get token and cost series per time bucket, aggregated by the repository
+#[utoipa::path(
+    get,
+    path = "/admin/usage/timeseries",
+    tag = "admin/usage",
+    params(UsageTimeseriesParams),
+    responses((status = 200, body = UsageTimeseriesResponse)),
+)]
+pub async fn get_usage_timeseries(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Query(params): Query<UsageTimeseriesParams>,
+) -> Result<Json<UsageTimeseriesResponse>, ApiError> {
+    let bucket: TimeBucket = match &params.bucket {
+        Some(bucket) => bucket
+            .parse()
+            .map_err(|e| ApiError::from(e).with_param("bucket"))?,
+        None => TimeBucket::Hour,
+    };
+
+    let group_by: Option<UsageGroupBy> = params
+        .group_by
+        .as_deref()
+        .map(str::parse)
+        .transpose()
+        .map_err(|e| ApiError::from(e).with_param("group_by"))?;
+
+    let to = params.to_timestamp.unwrap_or_else(|| Utc::now().timestamp() as u64);
+    let default_span = match bucket {
+        TimeBucket::Hour => 24 * 3_600,
+        TimeBucket::Day => 30 * 86_400,
+    };
+    let from = bucket.align(
+        params
+            .from_timestamp
+            .unwrap_or_else(|| to.saturating_sub(default_span)),
+    );
+
+    if from >= to {
+        return Err(
+            ApiError::bad_request("from_timestamp must be before to_timestamp")
+                .with_param("from_timestamp"),
+        );
+    }
+
+    if (to - from).div_ceil(bucket.seconds()) > MAX_TIMESERIES_BUCKETS {
+        return Err(ApiError::bad_request(format!(
+            "Time range spans more than {} buckets, use a wider bucket or a shorter range",
+            MAX_TIMESERIES_BUCKETS
+        ))
+        .with_param("from_timestamp"));
+    }
+
+    let mut query = UsageQuery::new().with_time_range(from, to);
+
+    if let Some(ref api_key_id) = params.api_key_id {
+        query = query.with_api_key(api_key_id);
+    }
+
+    if let Some(ref team_id) = params.team_id {
+        query = query.with_team(team_id);
+    }
+
+    if let Some(ref model_id) = params.model_id {
+        query = query.with_model(model_id);
+    }
+
+    if let Some(ref tags) = params.tags {
+        query.tags = parse_usage_tags(tags).map_err(|e| ApiError::from(e).with_param("tags"))?;
+    }
+
+    let buckets = state
+        .usage_service
+        .timeseries(&query, bucket, group_by)
+        .await?;
+
+    Ok(Json(UsageTimeseriesResponse {
+        bucket: bucket.to_string(),
+        group_by: group_by.map(|g| g.to_string()),
+        from_timestamp: from,
+        to_timestamp: to,
+        series: usage_series(buckets, bucket, from, to)
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+    }))
+}
+
+/// Compare the gateway usage of a UTC day (YYYY-MM-DD) with the usage
+/// reported by the providers
+#[utoipa::path(
+    get,
+    path = "/admin/usage/reconciliation/{date}",
+    tag = "admin/usage",
+    params(
+        ("date" = String, Path, description = "Day to reconcile (YYYY-MM-DD)"),
+        UsageReconciliationParams,
+    ),
+    responses((status = 200, body = UsageReconciliationResponse)),
+)]
+pub async fn get_usage_reconciliation(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Path(date): Path<String>,
+    Query(params): Query<UsageReconciliationParams>,
+) -> Result<Json<UsageReconciliationResponse>, ApiError> {
+    let Some(reconciler) = &state.usage_reconciler else {
+        return Err(ApiError::not_found(
+            "Usage reconciliation is not configured, set a provider admin key in [reconciliation]",
+        ));
+    };
+
+    let date: NaiveDate = date.parse().map_err(|_| {
+        ApiError::bad_request(format!("Invalid date '{}', expected YYYY-MM-DD", date))
+            .with_param("date")
+    })?;
+
+    if date >= Utc::now().date_naive() {
+        return Err(
+            ApiError::bad_request("Only days that have ended can be reconciled").with_param("date"),
+        );
+    }
+
+    let providers: Vec<String> = match params.provider {
+        Some(provider) => vec![provider],
+        None => reconciler
+            .providers()
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+    };
+
+    let mut reports = Vec::with_capacity(providers.len());
+
+    for provider in providers {
+        reports.push(reconciler.reconcile(&provider, date).await?);
+    }
+
+    Ok(Json(UsageReconciliationResponse { date, reports }))
+}
+
 /// Delete old usage records
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct DeleteUsageParams {
     pub before_timestamp: u64,
 }
 
+/// Delete usage records older than a timestamp
+#[utoipa::path(
+    delete,
+    path = "/admin/usage",
+    tag = "admin/usage",
+    params(DeleteUsageParams),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn delete_usage(
     RequireAdmin(_): RequireAdmin,
     State(state): State<AppState>,
@@ -314,13 +651,87 @@ pub async fn delete_usage(
     })))
 }
 
-fn build_usage_query(params: &UsageQueryParams) -> crate::domain::usage::UsageQuery {
-    let mut query = crate::domain::usage::UsageQuery::new();
+/// Export usage records of a time range as a CSV or Parquet file
+#[utoipa::path(
+    get,
+    path = "/admin/usage/export",
+    tag = "admin/usage",
+    params(UsageExportParams),
+    responses((
+        status = 200,
+        description = "Exported usage records",
+        content(
+            (String = "text/csv"),
+            (String = "application/vnd.apache.parquet"),
+        )
+    )),
+)]
+pub async fn export_usage(
+    RequireAdmin(_): RequireAdmin,
+    State(state): State<AppState>,
+    Query(params): Query<UsageExportParams>,
+) -> Result<Response, ApiError> {
+    let format: UsageExportFormat = match &params.format {
+        Some(format) => format
+            .parse()
+            .map_err(|e| ApiError::from(e).with_param("format"))?,
+        None => UsageExportFormat::default(),
+    };
+
+    if params.from_timestamp >= params.to_timestamp {
+        return Err(
+            ApiError::bad_request("from_timestamp must be before to_timestamp")
+                .with_param("from_timestamp"),
+        );
+    }
+
+    let mut query = UsageQuery::new().with_time_range(params.from_timestamp, params.to_timestamp);
+
+    if let Some(ref api_key_id) = params.api_key_id {
+        query = query.with_api_key(api_key_id);
+    }
+
+    if let Some(ref model_id) = params.model_id {
+        query = query.with_model(model_id);
+    }
+
+    if let Some(ref tags) = params.tags {
+        query.tags = parse_usage_tags(tags).map_err(|e| ApiError::from(e).with_param("tags"))?;
+    }
+
+    let records = state.usage_service.query(&query).await?;
+    let body = encode_usage_records(&records, format)?;
+    let filename = format!(
+        "usage-{}-{}.{}",
+        params.from_timestamp,
+        params.to_timestamp,
+        format.extension()
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+fn build_usage_query(params: &UsageQueryParams) -> Result<UsageQuery, ApiError> {
+    let mut query = UsageQuery::new();
 
     if let Some(ref api_key_id) = params.api_key_id {
         query = query.with_api_key(api_key_id);
     }
 
+    if let Some(ref team_id) = params.team_id {
+        query = query.with_team(team_id);
+    }
+
     if let Some(ref model_id) = params.model_id {
         query = query.with_model(model_id);
     }
@@ -337,7 +748,11 @@ fn build_usage_query(params: &UsageQueryParams) -> crate::domain::usage::UsageQu
         query = query.with_offset(offset);
     }
 
-    query
+    if let Some(ref tags) = params.tags {
+        query.tags = parse_usage_tags(tags).map_err(|e| ApiError::from(e).with_param("tags"))?;
+    }
+
+    Ok(query)
 }
 
 // ============================================================================
@@ -345,6 +760,12 @@ fn build_usage_query(params: &UsageQueryParams) -> crate::domain::usage::UsageQu
 // ============================================================================
 
 /// List all budgets
+#[utoipa::path(
+    get,
+    path = "/admin/budgets",
+    tag = "admin/budgets",
+    responses((status = 200, body = BudgetListResponse)),
+)]
 pub async fn list_budgets(
     RequireAdmin(_): RequireAdmin,
     State(state): State<AppState>,
@@ -357,6 +778,13 @@ pub async fn list_budgets(
 }
 
 /// List budgets by team
+#[utoipa::path(
+    get,
+    path = "/admin/budgets/by-team/{team_id}",
+    tag = "admin/budgets",
+    params(("team_id" = String, Path, description = "Team ID")),
+    responses((status = 200, body = BudgetListResponse)),
+)]
 pub async fn list_budgets_by_team(
     RequireAdmin(_): RequireAdmin,
     State(state): State<AppState>,
@@ -370,6 +798,13 @@ pub async fn list_budgets_by_team(
 }
 
 /// Create a new budget
+#[utoipa::path(
+    post,
+    path = "/admin/budgets",
+    tag = "admin/budgets",
+    request_body = CreateBudgetRequest,
+    responses((status = 200, body = BudgetResponse)),
+)]
 pub async fn create_budget(
     RequireAdmin(_): RequireAdmin,
     State(state): State<AppState>,
@@ -396,12 +831,20 @@ pub async fn create_budget(
         budget = budget.with_teams(team_ids);
     }
 
+    if let Some(organization_ids) = request.organization_ids {
+        budget = budget.with_organizations(organization_ids);
+    }
+
     if let Some(model_ids) = request.model_ids {
         for model_id in model_ids {
             budget = budget.with_model(model_id);
         }
     }
 
+    if let Some(fallback_model_id) = request.fallback_model_id {
+        budget = budget.with_fallback_model(fallback_model_id);
+    }
+
     if let Some(thresholds) = request.alert_thresholds {
         for threshold in thresholds {
             budget = budget.with_alert_at(threshold);
@@ -414,6 +857,13 @@ pub async fn create_budget(
 }
 
 /// Get a budget by ID
+#[utoipa::path(
+    get,
+    path = "/admin/budgets/{budget_id}",
+    tag = "admin/budgets",
+    params(("budget_id" = String, Path, description = "Budget ID")),
+    responses((status = 200, body = BudgetResponse)),
+)]
 pub async fn get_budget(
     RequireAdmin(_): RequireAdmin,
     State(state): State<AppState>,
@@ -430,6 +880,14 @@ pub async fn get_budget(
 }
 
 /// Update a budget
+#[utoipa::path(
+    put,
+    path = "/admin/budgets/{budget_id}",
+    tag = "admin/budgets",
+    params(("budget_id" = String, Path, description = "Budget ID")),
+    request_body = UpdateBudgetRequest,
+    responses((status = 200, body = BudgetResponse)),
+)]
 pub async fn update_budget(
     RequireAdmin(_): RequireAdmin,
     State(state): State<AppState>,
@@ -459,9 +917,10 @@ pub async fn update_budget(
         budget.soft_limit_micros = Some((soft_limit * 1_000_000.0) as i64);
     }
 
-    // Handle api_key_ids and team_ids updates with scope recalculation
-    let api_key_ids_changed = request.api_key_ids.is_some();
-    let team_ids_changed = request.team_ids.is_some();
+    // Handle api_key_ids, team_ids and organization_ids updates with scope recalculation
+    let scope_changed = request.api_key_ids.is_some()
+        || request.team_ids.is_some()
+        || request.organization_ids.is_some();
 
     if let Some(api_key_ids) = request.api_key_ids {
         budget.api_key_ids = api_key_ids;
@@ -471,20 +930,22 @@ pub async fn update_budget(
         budget.team_ids = team_ids;
     }
 
-    // Recalculate scope if api_key_ids or team_ids changed
-    if api_key_ids_changed || team_ids_changed {
-        budget.scope = match (!budget.api_key_ids.is_empty(), !budget.team_ids.is_empty()) {
-            (false, false) => BudgetScope::AllApiKeys,
-            (true, false) => BudgetScope::SpecificApiKeys,
-            (false, true) => BudgetScope::Teams,
-            (true, true) => BudgetScope::Mixed,
-        };
+    if let Some(organization_ids) = request.organization_ids {
+        budget.organization_ids = organization_ids;
+    }
+
+    if scope_changed {
+        budget.update_scope();
     }
 
     if let Some(model_ids) = request.model_ids {
         budget.model_ids = model_ids;
     }
 
+    if let Some(fallback_model_id) = request.fallback_model_id {
+        budget.fallback_model_id = Some(fallback_model_id).filter(|m| !m.is_empty());
+    }
+
     if let Some(enabled) = request.enabled {
         budget.enabled = enabled;
     }
@@ -502,6 +963,13 @@ pub async fn update_budget(
 }
 
 /// Delete a budget
+#[utoipa::path(
+    delete,
+    path = "/admin/budgets/{budget_id}",
+    tag = "admin/budgets",
+    params(("budget_id" = String, Path, description = "Budget ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn delete_budget(
     RequireAdmin(_): RequireAdmin,
     State(state): State<AppState>,
@@ -524,6 +992,13 @@ pub async fn delete_budget(
 }
 
 /// Reset a budget period
+#[utoipa::path(
+    post,
+    path = "/admin/budgets/{budget_id}/reset",
+    tag = "admin/budgets",
+    params(("budget_id" = String, Path, description = "Budget ID")),
+    responses((status = 200, body = BudgetResponse)),
+)]
 pub async fn reset_budget(
     RequireAdmin(_): RequireAdmin,
     State(state): State<AppState>,
@@ -544,7 +1019,7 @@ pub async fn reset_budget(
 }
 
 /// Check budget for a request
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CheckBudgetRequest {
     pub api_key_id: String,
     pub team_id: Option<String>,
@@ -552,14 +1027,25 @@ pub struct CheckBudgetRequest {
     pub estimated_cost_usd: f64,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct CheckBudgetResponse {
     pub allowed: bool,
     pub exceeded_budgets: Vec<String>,
     pub warning_budgets: Vec<String>,
     pub estimated_cost_usd: f64,
+    /// Model the request would be degraded to instead of being rejected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback_model_id: Option<String>,
 }
 
+/// Check whether a request of an estimated cost fits the budgets of its API key, team and model
+#[utoipa::path(
+    post,
+    path = "/admin/budgets/check",
+    tag = "admin/budgets",
+    request_body = CheckBudgetRequest,
+    responses((status = 200, body = CheckBudgetResponse)),
+)]
 pub async fn check_budget(
     RequireAdmin(_): RequireAdmin,
     State(state): State<AppState>,
@@ -582,6 +1068,7 @@ pub async fn check_budget(
         exceeded_budgets: result.exceeded_budgets.into_iter().map(|id| id.to_string()).collect(),
         warning_budgets: result.warning_budgets.into_iter().map(|id| id.to_string()).collect(),
         estimated_cost_usd: request.estimated_cost_usd,
+        fallback_model_id: result.fallback_model_id,
     }))
 }
 
@@ -616,6 +1103,7 @@ mod tests {
         assert_eq!(scope_to_string(BudgetScope::AllApiKeys), "all_api_keys");
         assert_eq!(scope_to_string(BudgetScope::SpecificApiKeys), "specific_api_keys");
         assert_eq!(scope_to_string(BudgetScope::Teams), "teams");
+        assert_eq!(scope_to_string(BudgetScope::Organizations), "organizations");
         assert_eq!(scope_to_string(BudgetScope::Mixed), "mixed");
     }
 
@@ -745,6 +1233,7 @@ mod tests {
             id: "rec-1".to_string(),
             usage_type: "chat".to_string(),
             api_key_id: "key-1".to_string(),
+            team_id: None,
             model_id: Some("gpt-4".to_string()),
             input_tokens: 100,
             output_tokens: 50,
@@ -754,6 +1243,7 @@ mod tests {
             success: true,
             error: None,
             timestamp: 1704067200,
+            tags: HashMap::new(),
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -774,11 +1264,20 @@ mod tests {
             total_cost_usd: 3.0,
             avg_latency_ms: 250.5,
             success_rate: 0.99,
+            by_tag: Some(HashMap::from([(
+                "onboarding".to_string(),
+                TagUsageResponse {
+                    requests: 10,
+                    tokens: 1500,
+                    cost_usd: 0.03,
+                },
+            )])),
         };
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"total_requests\":1000"));
         assert!(json.contains("\"success_rate\":0.99"));
+        assert!(json.contains("\"onboarding\":{\"requests\":10"));
     }
 
     #[test]
@@ -796,6 +1295,7 @@ mod tests {
                 total_cost_usd: 1.5,
                 avg_latency_ms: 200.0,
                 success_rate: 0.99,
+                by_tag: None,
             },
             daily: vec![],
         };
@@ -847,7 +1347,9 @@ mod tests {
             scope: "all_api_keys".to_string(),
             api_key_ids: vec![],
             team_ids: vec![],
+            organization_ids: vec![],
             model_ids: vec![],
+            fallback_model_id: None,
             alerts: vec![],
             period_start: 1704067200,
             enabled: true,
@@ -889,6 +1391,7 @@ mod tests {
             exceeded_budgets: vec![],
             warning_budgets: vec!["budget-1".to_string()],
             estimated_cost_usd: 0.05,
+            fallback_model_id: None,
         };
 
         let json = serde_json::to_string(&response).unwrap();
@@ -903,10 +1406,12 @@ mod tests {
             exceeded_budgets: vec!["budget-1".to_string(), "budget-2".to_string()],
             warning_budgets: vec![],
             estimated_cost_usd: 100.0,
+            fallback_model_id: Some("gpt-4o-mini".to_string()),
         };
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"allowed\":false"));
         assert!(json.contains("\"exceeded_budgets\":[\"budget-1\",\"budget-2\"]"));
+        assert!(json.contains("\"fallback_model_id\":\"gpt-4o-mini\""));
     }
 }
@@ -0,0 +1,45 @@
+//! User security admin endpoints
+
+use axum::extract::{Path, State};
+use serde::Serialize;
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::api::middleware::RequireAdmin;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+
+/// User MFA status response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UserMfaResponse {
+    pub user_id: String,
+    pub mfa_enabled: bool,
+}
+
+/// Force-remove a user's MFA enrollment, e.g. after losing their device and
+/// recovery codes. The user can log in with their password and enroll again.
+#[utoipa::path(
+    post,
+    path = "/admin/users/{user_id}/mfa/reset",
+    tag = "admin/users",
+    params(("user_id" = String, Path, description = "User ID")),
+    responses((status = 200, body = UserMfaResponse)),
+)]
+pub async fn reset_user_mfa(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(user_id): Path<String>,
+) -> Result<Json<UserMfaResponse>, ApiError> {
+    info!(user_id = %user_id, "Admin resetting user MFA");
+
+    let user = state
+        .user_service
+        .reset_mfa(&user_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(UserMfaResponse {
+        user_id: user.id().as_str().to_string(),
+        mfa_enabled: user.is_mfa_enabled(),
+    }))
+}
@@ -6,15 +6,20 @@ use axum::{
     response::IntoResponse,
 };
 
+use crate::api::middleware::RequireAdmin;
 use crate::api::state::AppState;
 use crate::api::types::{ApiError, Json};
-use crate::domain::{Webhook, WebhookDelivery, WebhookEventType, WebhookId, WebhookStatus};
+use crate::domain::{
+    Webhook, WebhookDelivery, WebhookEventType, WebhookFilter, WebhookId, WebhookStatus,
+};
+use crate::infrastructure::webhook::{SIGNATURE_HEADER, SIGNATURE_TOLERANCE_SECS};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use utoipa::{IntoParams, ToSchema};
 
 /// Request to create a webhook
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateWebhookRequest {
     pub id: String,
     pub name: String,
@@ -25,6 +30,10 @@ pub struct CreateWebhookRequest {
     pub secret: Option<String>,
     pub events: Vec<WebhookEventType>,
     #[serde(default)]
+    pub filter: WebhookFilter,
+    #[serde(default)]
+    pub payload_template: Option<serde_json::Value>,
+    #[serde(default)]
     pub headers: HashMap<String, String>,
     #[serde(default = "default_max_retries")]
     pub max_retries: u32,
@@ -47,7 +56,7 @@ fn default_timeout() -> u32 {
 }
 
 /// Request to update a webhook
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateWebhookRequest {
     pub name: String,
     #[serde(default)]
@@ -57,6 +66,10 @@ pub struct UpdateWebhookRequest {
     pub secret: Option<String>,
     pub events: Vec<WebhookEventType>,
     #[serde(default)]
+    pub filter: WebhookFilter,
+    #[serde(default)]
+    pub payload_template: Option<serde_json::Value>,
+    #[serde(default)]
     pub headers: HashMap<String, String>,
     pub status: WebhookStatus,
     #[serde(default = "default_max_retries")]
@@ -68,7 +81,7 @@ pub struct UpdateWebhookRequest {
 }
 
 /// Response for a single webhook
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct WebhookResponse {
     pub id: String,
     pub name: String,
@@ -77,6 +90,10 @@ pub struct WebhookResponse {
     pub url: String,
     pub has_secret: bool,
     pub events: Vec<WebhookEventType>,
+    #[serde(skip_serializing_if = "WebhookFilter::is_empty")]
+    pub filter: WebhookFilter,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_template: Option<serde_json::Value>,
     pub headers: HashMap<String, String>,
     pub status: WebhookStatus,
     pub failure_count: u32,
@@ -100,6 +117,8 @@ impl From<Webhook> for WebhookResponse {
             url: w.url,
             has_secret: w.secret.is_some(),
             events: w.events,
+            filter: w.filter,
+            payload_template: w.payload_template,
             headers: w.headers,
             status: w.status,
             failure_count: w.failure_count,
@@ -114,14 +133,36 @@ impl From<Webhook> for WebhookResponse {
     }
 }
 
+/// Webhook response with its signing secret (only on creation)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookWithSecretResponse {
+    #[serde(flatten)]
+    pub webhook: WebhookResponse,
+    pub secret: String,
+}
+
+impl From<Webhook> for WebhookWithSecretResponse {
+    fn from(mut w: Webhook) -> Self {
+        let secret = w.secret.take().unwrap_or_default();
+
+        Self {
+            webhook: WebhookResponse {
+                has_secret: !secret.is_empty(),
+                ..WebhookResponse::from(w)
+            },
+            secret,
+        }
+    }
+}
+
 /// Response for webhook list
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct WebhooksListResponse {
     pub webhooks: Vec<WebhookResponse>,
 }
 
 /// Response for webhook delivery
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct WebhookDeliveryResponse {
     pub id: String,
     pub webhook_id: String,
@@ -141,6 +182,8 @@ pub struct WebhookDeliveryResponse {
     pub next_retry_at: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub completed_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redelivery_of: Option<String>,
 }
 
 impl From<WebhookDelivery> for WebhookDeliveryResponse {
@@ -158,18 +201,20 @@ impl From<WebhookDelivery> for WebhookDeliveryResponse {
             last_attempt_at: d.last_attempt_at,
             next_retry_at: d.next_retry_at,
             completed_at: d.completed_at,
+            redelivery_of: d.redelivery_of.map(|id| id.to_string()),
         }
     }
 }
 
 /// Response for delivery list
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct DeliveriesListResponse {
     pub deliveries: Vec<WebhookDeliveryResponse>,
 }
 
 /// Query parameters for deliveries
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct DeliveriesQuery {
     #[serde(default = "default_limit")]
     pub limit: usize,
@@ -182,19 +227,59 @@ fn default_limit() -> usize {
 }
 
 /// Response for event types
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct EventTypesResponse {
     pub event_types: Vec<EventTypeInfo>,
+    /// How deliveries are signed and verified
+    pub signature: SignatureInfo,
+}
+
+/// Delivery signing scheme, see `infrastructure::webhook::verify_signature`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SignatureInfo {
+    pub header: &'static str,
+    pub algorithm: &'static str,
+    pub format: &'static str,
+    pub signed_content: &'static str,
+    pub tolerance_secs: i64,
+    pub verification: Vec<&'static str>,
+}
+
+impl Default for SignatureInfo {
+    fn default() -> Self {
+        Self {
+            header: SIGNATURE_HEADER,
+            algorithm: "HMAC-SHA256",
+            format: "t=<unix timestamp>,v1=<hex signature>",
+            signed_content: "<timestamp>.<raw request body>",
+            tolerance_secs: SIGNATURE_TOLERANCE_SECS,
+            verification: vec![
+                "Split the header on ',' and read the 't' and 'v1' values",
+                "Compute HMAC-SHA256 of '<t>.<raw request body>' keyed with the webhook secret",
+                "Compare the hex digest with 'v1' in constant time",
+                "Reject deliveries whose 't' differs from the current time by more than tolerance_secs",
+            ],
+        }
+    }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct EventTypeInfo {
     pub name: WebhookEventType,
     pub description: String,
 }
 
 /// List all webhooks
-pub async fn list_webhooks(State(state): State<AppState>) -> Result<impl IntoResponse, ApiError> {
+#[utoipa::path(
+    get,
+    path = "/admin/webhooks",
+    tag = "admin/webhooks",
+    responses((status = 200, body = WebhooksListResponse)),
+)]
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+    _admin: RequireAdmin,
+) -> Result<impl IntoResponse, ApiError> {
     let webhooks = state.webhook_service().list().await?;
 
     Ok(Json(WebhooksListResponse {
@@ -203,8 +288,16 @@ pub async fn list_webhooks(State(state): State<AppState>) -> Result<impl IntoRes
 }
 
 /// Get a webhook by ID
+#[utoipa::path(
+    get,
+    path = "/admin/webhooks/{webhook_id}",
+    tag = "admin/webhooks",
+    params(("webhook_id" = String, Path, description = "Webhook ID")),
+    responses((status = 200, body = WebhookResponse)),
+)]
 pub async fn get_webhook(
     State(state): State<AppState>,
+    _admin: RequireAdmin,
     Path(id): Path<String>,
 ) -> Result<impl IntoResponse, ApiError> {
     let webhook = state.webhook_service().get(&id).await?;
@@ -212,12 +305,21 @@ pub async fn get_webhook(
 }
 
 /// Create a new webhook
+#[utoipa::path(
+    post,
+    path = "/admin/webhooks",
+    tag = "admin/webhooks",
+    request_body = CreateWebhookRequest,
+    responses((status = 201, body = WebhookWithSecretResponse)),
+)]
 pub async fn create_webhook(
     State(state): State<AppState>,
+    _admin: RequireAdmin,
     Json(req): Json<CreateWebhookRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
     let mut webhook = Webhook::new(WebhookId::new(&req.id), &req.name, &req.url)
         .with_events(req.events)
+        .with_filter(req.filter)
         .with_retry_config(req.max_retries, req.retry_delay_secs)
         .with_timeout(req.timeout_secs);
 
@@ -229,17 +331,33 @@ pub async fn create_webhook(
         webhook = webhook.with_secret(secret);
     }
 
+    if let Some(template) = req.payload_template {
+        webhook = webhook.with_payload_template(template);
+    }
+
     for (key, value) in req.headers {
         webhook = webhook.with_header(key, value);
     }
 
     let created = state.webhook_service().create(webhook).await?;
-    Ok((StatusCode::CREATED, Json(WebhookResponse::from(created))))
+    Ok((
+        StatusCode::CREATED,
+        Json(WebhookWithSecretResponse::from(created)),
+    ))
 }
 
 /// Update an existing webhook
+#[utoipa::path(
+    put,
+    path = "/admin/webhooks/{webhook_id}",
+    tag = "admin/webhooks",
+    params(("webhook_id" = String, Path, description = "Webhook ID")),
+    request_body = UpdateWebhookRequest,
+    responses((status = 200, body = WebhookResponse)),
+)]
 pub async fn update_webhook(
     State(state): State<AppState>,
+    _admin: RequireAdmin,
     Path(id): Path<String>,
     Json(req): Json<UpdateWebhookRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
@@ -248,6 +366,7 @@ pub async fn update_webhook(
 
     let mut webhook = Webhook::new(existing.id, &req.name, &req.url)
         .with_events(req.events)
+        .with_filter(req.filter)
         .with_status(req.status)
         .with_retry_config(req.max_retries, req.retry_delay_secs)
         .with_timeout(req.timeout_secs);
@@ -256,6 +375,10 @@ pub async fn update_webhook(
         webhook = webhook.with_description(desc);
     }
 
+    if let Some(template) = req.payload_template {
+        webhook = webhook.with_payload_template(template);
+    }
+
     // Preserve secret if not provided in update
     if let Some(secret) = req.secret {
         webhook = webhook.with_secret(secret);
@@ -277,8 +400,16 @@ pub async fn update_webhook(
 }
 
 /// Delete a webhook
+#[utoipa::path(
+    delete,
+    path = "/admin/webhooks/{webhook_id}",
+    tag = "admin/webhooks",
+    params(("webhook_id" = String, Path, description = "Webhook ID")),
+    responses((status = 204, description = "Webhook deleted")),
+)]
 pub async fn delete_webhook(
     State(state): State<AppState>,
+    _admin: RequireAdmin,
     Path(id): Path<String>,
 ) -> Result<impl IntoResponse, ApiError> {
     state.webhook_service().delete(&id).await?;
@@ -286,8 +417,16 @@ pub async fn delete_webhook(
 }
 
 /// Reset a webhook's failure count and re-enable it
+#[utoipa::path(
+    post,
+    path = "/admin/webhooks/{webhook_id}/reset",
+    tag = "admin/webhooks",
+    params(("webhook_id" = String, Path, description = "Webhook ID")),
+    responses((status = 200, body = WebhookResponse)),
+)]
 pub async fn reset_webhook(
     State(state): State<AppState>,
+    _admin: RequireAdmin,
     Path(id): Path<String>,
 ) -> Result<impl IntoResponse, ApiError> {
     let webhook = state.webhook_service().reset_webhook(&id).await?;
@@ -295,8 +434,19 @@ pub async fn reset_webhook(
 }
 
 /// Get delivery history for a webhook
+#[utoipa::path(
+    get,
+    path = "/admin/webhooks/{webhook_id}/deliveries",
+    tag = "admin/webhooks",
+    params(
+        ("webhook_id" = String, Path, description = "Webhook ID"),
+        DeliveriesQuery,
+    ),
+    responses((status = 200, body = DeliveriesListResponse)),
+)]
 pub async fn get_deliveries(
     State(state): State<AppState>,
+    _admin: RequireAdmin,
     Path(id): Path<String>,
     Query(query): Query<DeliveriesQuery>,
 ) -> Result<impl IntoResponse, ApiError> {
@@ -313,8 +463,35 @@ pub async fn get_deliveries(
     }))
 }
 
+/// Replay a delivery (e.g. a dead-lettered one) as a new delivery
+#[utoipa::path(
+    post,
+    path = "/admin/webhooks/{webhook_id}/deliveries/{delivery_id}/redeliver",
+    tag = "admin/webhooks",
+    params(
+        ("webhook_id" = String, Path, description = "Webhook ID"),
+        ("delivery_id" = String, Path, description = "Delivery ID"),
+    ),
+    responses((status = 200, body = WebhookDeliveryResponse)),
+)]
+pub async fn redeliver(
+    State(state): State<AppState>,
+    _admin: RequireAdmin,
+    Path((id, delivery_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, ApiError> {
+    let delivery = state.webhook_service().redeliver(&id, &delivery_id).await?;
+
+    Ok(Json(WebhookDeliveryResponse::from(delivery)))
+}
+
 /// List available event types
-pub async fn list_event_types() -> impl IntoResponse {
+#[utoipa::path(
+    get,
+    path = "/admin/webhooks/event-types",
+    tag = "admin/webhooks",
+    responses((status = 200, body = EventTypesResponse)),
+)]
+pub async fn list_event_types(_admin: RequireAdmin) -> impl IntoResponse {
     let event_types: Vec<EventTypeInfo> = WebhookEventType::all()
         .into_iter()
         .map(|e| EventTypeInfo {
@@ -329,6 +506,9 @@ pub async fn list_event_types() -> impl IntoResponse {
                 WebhookEventType::ExperimentCompleted => {
                     "Triggered when an A/B experiment is completed".to_string()
                 }
+                WebhookEventType::ExperimentRolledBack => {
+                    "Triggered when a canary rollout is rolled back".to_string()
+                }
                 WebhookEventType::WorkflowFailed => {
                     "Triggered when a workflow execution fails".to_string()
                 }
@@ -347,11 +527,17 @@ pub async fn list_event_types() -> impl IntoResponse {
                 WebhookEventType::TestCaseFailed => {
                     "Triggered when a test case execution fails".to_string()
                 }
+                WebhookEventType::UsageAnomaly => {
+                    "Triggered when API key or team usage spikes over its baseline".to_string()
+                }
             },
         })
         .collect();
 
-    Json(EventTypesResponse { event_types })
+    Json(EventTypesResponse {
+        event_types,
+        signature: SignatureInfo::default(),
+    })
 }
 
 #[cfg(test)]
@@ -518,11 +704,48 @@ mod tests {
                 name: WebhookEventType::BudgetAlert,
                 description: "Test description".to_string(),
             }],
+            signature: SignatureInfo::default(),
         };
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"event_types\""));
         assert!(json.contains("\"name\":\"budget_alert\""));
         assert!(json.contains("\"description\":\"Test description\""));
+        assert!(json.contains("\"header\":\"X-PMP-Signature\""));
+    }
+
+    #[test]
+    fn test_webhook_with_secret_response() {
+        let webhook = Webhook::new("hook-1", "Test Hook", "https://example.com/webhook")
+            .with_event(WebhookEventType::BudgetAlert)
+            .with_secret("whsec_abc");
+
+        let json = serde_json::to_value(WebhookWithSecretResponse::from(webhook)).unwrap();
+
+        assert_eq!(json["id"], "hook-1");
+        assert_eq!(json["has_secret"], true);
+        assert_eq!(json["secret"], "whsec_abc");
+    }
+
+    #[test]
+    fn test_create_request_with_filter_and_template() {
+        let json = r#"{
+            "id": "slack",
+            "name": "Slack",
+            "url": "https://hooks.slack.com/services/T000/B000/XXX",
+            "events": ["usage_anomaly"],
+            "filter": {"team_ids": ["team-1"], "min_cost_micros": 1000000},
+            "payload_template": {"text": "{{data.team_id}} spiked"}
+        }"#;
+
+        let req: CreateWebhookRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.filter.team_ids, vec!["team-1".to_string()]);
+        assert_eq!(req.filter.min_cost_micros, Some(1_000_000));
+        assert_eq!(req.payload_template.unwrap()["text"], "{{data.team_id}} spiked");
+
+        let json = r#"{"id": "all", "name": "All", "url": "https://example.com", "events": []}"#;
+        let req: CreateWebhookRequest = serde_json::from_str(json).unwrap();
+        assert!(req.filter.is_empty());
+        assert!(req.payload_template.is_none());
     }
 }
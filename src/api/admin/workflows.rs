@@ -7,16 +7,21 @@ use axum::extract::{Path, State};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tracing::debug;
+use utoipa::ToSchema;
 
+use crate::api::admin::prompts::{ensure_not_blocked, ListRegressionReportsResponse};
+use crate::api::admin::teams::resolve_owner_team;
 use crate::api::middleware::{AdminAuth, RequireAdmin};
 use crate::api::state::AppState;
 use crate::api::types::{ApiError, Json};
+use crate::domain::test_case::{RegressionPolicy, RegressionReport, RegressionSubject};
 use crate::domain::workflow::{OnErrorAction, Workflow, WorkflowStep, WorkflowStepType};
 use crate::domain::{ExecutionStatus, ExecutionTokenUsage, Executor, WorkflowStepLog};
 use crate::infrastructure::services::{CreateWorkflowRequest, RecordExecutionParams, UpdateWorkflowRequest};
 
 /// Request to create a new workflow
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Serialize))]
 pub struct CreateWorkflowApiRequest {
     pub id: String,
     pub name: String,
@@ -28,6 +33,9 @@ pub struct CreateWorkflowApiRequest {
     pub steps: Vec<WorkflowStepApiRequest>,
     #[serde(default = "default_true")]
     pub enabled: bool,
+    /// Owning team; defaults to the team of the caller
+    #[serde(default)]
+    pub team_id: Option<String>,
 }
 
 fn default_true() -> bool {
@@ -35,17 +43,23 @@ fn default_true() -> bool {
 }
 
 /// Request to update a workflow
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Serialize))]
 pub struct UpdateWorkflowApiRequest {
     pub name: Option<String>,
     pub description: Option<Option<String>>,
     pub input_schema: Option<Option<serde_json::Value>>,
     pub steps: Option<Vec<WorkflowStepApiRequest>>,
     pub enabled: Option<bool>,
+    /// Run the linked test cases against the updated steps first and block
+    /// the update if it introduces more regressions than allowed
+    #[serde(default)]
+    pub regression_check: Option<RegressionPolicy>,
 }
 
 /// Workflow step in API request
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Serialize))]
 pub struct WorkflowStepApiRequest {
     pub name: String,
     #[serde(flatten)]
@@ -77,7 +91,8 @@ impl From<WorkflowStepApiRequest> for WorkflowStep {
 }
 
 /// Workflow response for admin API
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 pub struct WorkflowResponse {
     pub id: String,
     pub name: String,
@@ -86,12 +101,17 @@ pub struct WorkflowResponse {
     pub steps: Vec<WorkflowStepResponse>,
     pub version: u32,
     pub enabled: bool,
+    pub team_id: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    /// Comparison against the previous version, when a check was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regression_report: Option<RegressionReport>,
 }
 
 /// Workflow step response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 pub struct WorkflowStepResponse {
     pub name: String,
     #[serde(flatten)]
@@ -125,20 +145,29 @@ impl From<&Workflow> for WorkflowResponse {
             steps: workflow.steps().iter().map(WorkflowStepResponse::from).collect(),
             version: workflow.version(),
             enabled: workflow.is_enabled(),
+            team_id: workflow.team_id().map(|t| t.as_str().to_string()),
             created_at: workflow.created_at().to_rfc3339(),
             updated_at: workflow.updated_at().to_rfc3339(),
+            regression_report: None,
         }
     }
 }
 
 /// List workflows response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 pub struct ListWorkflowsResponse {
     pub workflows: Vec<WorkflowResponse>,
     pub total: usize,
 }
 
-/// GET /admin/workflows
+/// List workflows
+#[utoipa::path(
+    get,
+    path = "/admin/workflows",
+    tag = "admin/workflows",
+    responses((status = 200, body = ListWorkflowsResponse)),
+)]
 pub async fn list_workflows(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -157,14 +186,23 @@ pub async fn list_workflows(
     }))
 }
 
-/// POST /admin/workflows
+/// Create workflow
+#[utoipa::path(
+    post,
+    path = "/admin/workflows",
+    tag = "admin/workflows",
+    request_body = CreateWorkflowApiRequest,
+    responses((status = 200, body = WorkflowResponse)),
+)]
 pub async fn create_workflow(
     State(state): State<AppState>,
-    RequireAdmin(_): RequireAdmin,
+    RequireAdmin(admin): RequireAdmin,
     Json(request): Json<CreateWorkflowApiRequest>,
 ) -> Result<Json<WorkflowResponse>, ApiError> {
     debug!(workflow_id = %request.id, "Admin creating workflow");
 
+    let team_id = resolve_owner_team(&state, &admin, request.team_id.as_deref()).await?;
+
     let create_request = CreateWorkflowRequest {
         id: request.id,
         name: request.name,
@@ -172,6 +210,7 @@ pub async fn create_workflow(
         input_schema: request.input_schema,
         steps: request.steps.into_iter().map(WorkflowStep::from).collect(),
         enabled: request.enabled,
+        team_id: Some(team_id),
     };
 
     let workflow = state
@@ -183,7 +222,14 @@ pub async fn create_workflow(
     Ok(Json(WorkflowResponse::from(&workflow)))
 }
 
-/// GET /admin/workflows/:workflow_id
+/// Get workflow
+#[utoipa::path(
+    get,
+    path = "/admin/workflows/{workflow_id}",
+    tag = "admin/workflows",
+    params(("workflow_id" = String, Path, description = "Workflow ID")),
+    responses((status = 200, body = WorkflowResponse)),
+)]
 pub async fn get_workflow(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -201,7 +247,15 @@ pub async fn get_workflow(
     Ok(Json(WorkflowResponse::from(&workflow)))
 }
 
-/// PUT /admin/workflows/:workflow_id
+/// Update workflow
+#[utoipa::path(
+    put,
+    path = "/admin/workflows/{workflow_id}",
+    tag = "admin/workflows",
+    params(("workflow_id" = String, Path, description = "Workflow ID")),
+    request_body = UpdateWorkflowApiRequest,
+    responses((status = 200, body = WorkflowResponse)),
+)]
 pub async fn update_workflow(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -218,16 +272,64 @@ pub async fn update_workflow(
         enabled: request.enabled,
     };
 
+    let regression_report = match request.regression_check {
+        Some(policy) => state
+            .regression_service
+            .check_workflow(&workflow_id, update_request.clone(), &policy)
+            .await
+            .map_err(ApiError::from)?,
+        None => None,
+    };
+    ensure_not_blocked(regression_report.as_ref())?;
+
     let workflow = state
         .workflow_service
         .update(&workflow_id, update_request)
         .await
         .map_err(ApiError::from)?;
 
-    Ok(Json(WorkflowResponse::from(&workflow)))
+    let mut response = WorkflowResponse::from(&workflow);
+    response.regression_report = regression_report;
+
+    Ok(Json(response))
 }
 
-/// DELETE /admin/workflows/:workflow_id
+/// List regressions
+#[utoipa::path(
+    get,
+    path = "/admin/workflows/{workflow_id}/regressions",
+    tag = "admin/workflows",
+    operation_id = "list_workflow_regressions",
+    params(("workflow_id" = String, Path, description = "Workflow ID")),
+    responses((status = 200, body = ListRegressionReportsResponse)),
+)]
+pub async fn list_regressions(
+    State(state): State<AppState>,
+    RequireAdmin(_): RequireAdmin,
+    Path(workflow_id): Path<String>,
+) -> Result<Json<ListRegressionReportsResponse>, ApiError> {
+    debug!(workflow_id = %workflow_id, "Admin listing workflow regression reports");
+
+    let reports = state
+        .regression_service
+        .list_reports(RegressionSubject::Workflow, &workflow_id)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(ListRegressionReportsResponse {
+        total: reports.len(),
+        reports,
+    }))
+}
+
+/// Delete workflow
+#[utoipa::path(
+    delete,
+    path = "/admin/workflows/{workflow_id}",
+    tag = "admin/workflows",
+    params(("workflow_id" = String, Path, description = "Workflow ID")),
+    responses((status = 200, body = serde_json::Value)),
+)]
 pub async fn delete_workflow(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -248,7 +350,7 @@ pub async fn delete_workflow(
 }
 
 /// Request to test a workflow with mocked step outputs
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct TestWorkflowRequest {
     /// Input data for the workflow
     #[serde(default)]
@@ -260,7 +362,7 @@ pub struct TestWorkflowRequest {
 }
 
 /// Response from testing a workflow
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct TestWorkflowResponse {
     pub success: bool,
     pub workflow_id: String,
@@ -273,7 +375,7 @@ pub struct TestWorkflowResponse {
 }
 
 /// Result of a mocked step execution
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct MockStepResult {
     pub step_name: String,
     pub step_type: String,
@@ -284,8 +386,15 @@ pub struct MockStepResult {
     pub reason: Option<String>,
 }
 
-/// POST /admin/workflows/:workflow_id/test
 /// Test a workflow execution with mocked step outputs
+#[utoipa::path(
+    post,
+    path = "/admin/workflows/{workflow_id}/test",
+    tag = "admin/workflows",
+    params(("workflow_id" = String, Path, description = "Workflow ID")),
+    request_body = TestWorkflowRequest,
+    responses((status = 200, body = TestWorkflowResponse)),
+)]
 pub async fn test_workflow(
     State(state): State<AppState>,
     RequireAdmin(_): RequireAdmin,
@@ -371,7 +480,8 @@ fn get_step_type_name(step_type: &WorkflowStepType) -> String {
 }
 
 /// Request to clone a workflow
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Serialize))]
 pub struct CloneWorkflowRequest {
     /// New ID for the cloned workflow
     pub new_id: String,
@@ -380,11 +490,18 @@ pub struct CloneWorkflowRequest {
     pub new_name: Option<String>,
 }
 
-/// POST /admin/workflows/:workflow_id/clone
 /// Clone a workflow with a new ID
+#[utoipa::path(
+    post,
+    path = "/admin/workflows/{workflow_id}/clone",
+    tag = "admin/workflows",
+    params(("workflow_id" = String, Path, description = "Workflow ID")),
+    request_body = CloneWorkflowRequest,
+    responses((status = 200, body = WorkflowResponse)),
+)]
 pub async fn clone_workflow(
     State(state): State<AppState>,
-    RequireAdmin(_): RequireAdmin,
+    RequireAdmin(admin): RequireAdmin,
     Path(workflow_id): Path<String>,
     Json(request): Json<CloneWorkflowRequest>,
 ) -> Result<Json<WorkflowResponse>, ApiError> {
@@ -410,6 +527,12 @@ pub async fn clone_workflow(
         input_schema: original.input_schema().cloned(),
         steps: original.steps().to_vec(),
         enabled: true, // Cloned workflows start enabled for immediate testing
+        team_id: Some(
+            original
+                .team_id()
+                .cloned()
+                .unwrap_or_else(|| admin.team_id().clone()),
+        ),
     };
 
     let cloned = state
@@ -422,7 +545,7 @@ pub async fn clone_workflow(
 }
 
 /// Request to execute a workflow
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct ExecuteWorkflowRequest {
     /// Input data for the workflow (must match input_schema if defined)
     #[serde(default)]
@@ -430,7 +553,7 @@ pub struct ExecuteWorkflowRequest {
 }
 
 /// Response from workflow execution
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ExecuteWorkflowResponse {
     pub workflow_id: String,
     pub success: bool,
@@ -442,7 +565,7 @@ pub struct ExecuteWorkflowResponse {
 }
 
 /// Step result in execution response
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct StepResultResponse {
     pub step_name: String,
     pub success: bool,
@@ -453,8 +576,15 @@ pub struct StepResultResponse {
     pub execution_time_ms: u64,
 }
 
-/// POST /admin/workflows/:workflow_id/execute
 /// Execute a workflow with the given input
+#[utoipa::path(
+    post,
+    path = "/admin/workflows/{workflow_id}/execute",
+    tag = "admin/workflows",
+    params(("workflow_id" = String, Path, description = "Workflow ID")),
+    request_body = ExecuteWorkflowRequest,
+    responses((status = 200, body = ExecuteWorkflowResponse)),
+)]
 pub async fn execute_workflow(
     State(state): State<AppState>,
     RequireAdmin(admin): RequireAdmin,
@@ -467,6 +597,9 @@ pub async fn execute_workflow(
     let executor = match &admin {
         AdminAuth::ApiKey(key) => Executor::from_api_key(key.id().as_str()),
         AdminAuth::User(user) => Executor::from_user(user.id().as_str()),
+        AdminAuth::ServiceAccount { account, .. } => {
+            Executor::from_service_account(account.id().as_str())
+        }
     };
 
     // Clone input for logging before moving it to execute
@@ -1,36 +1,52 @@
 //! Authentication API endpoints
 //!
-//! Provides login, logout, and user info endpoints for JWT-based authentication.
+//! Provides login, logout, user info and TOTP multi-factor authentication
+//! endpoints for JWT-based authentication, plus the OAuth 2.0
+//! `client_credentials` token endpoint for service accounts.
 
 use axum::{
-    extract::State,
+    extract::{FromRequest, Request, State},
+    http::header,
     routing::{get, post},
-    Router,
+    Form, Router,
 };
 use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::api::middleware::RequireUser;
 use crate::api::state::AppState;
 use crate::api::types::{ApiError, Json};
+use crate::domain::role::Permission;
+use crate::domain::service_account::ServiceAccount;
+use crate::infrastructure::auth::SERVICE_ACCOUNT_TOKEN_EXPIRATION_SECS;
+use crate::infrastructure::user::LoginOutcome;
 
 /// Create the authentication router
 pub fn create_auth_router() -> Router<AppState> {
     Router::new()
         .route("/login", post(login))
         .route("/logout", post(logout))
+        .route("/token", post(issue_token))
         .route("/me", get(get_current_user))
+        .route("/mfa/enroll", post(enroll_mfa))
+        .route("/mfa/verify", post(verify_mfa))
+        .route("/mfa/recovery-codes", post(regenerate_recovery_codes))
+        .route("/mfa/disable", post(disable_mfa))
 }
 
 /// Login request
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct LoginRequest {
     pub username: String,
     pub password: String,
+    /// TOTP or recovery code, required when the user has MFA enabled
+    #[serde(default)]
+    pub mfa_code: Option<String>,
 }
 
 /// Login response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct LoginResponse {
     pub token: String,
     pub user: UserResponse,
@@ -38,13 +54,14 @@ pub struct LoginResponse {
 }
 
 /// User response (safe to expose)
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UserResponse {
     pub id: String,
     pub username: String,
     pub status: String,
     pub created_at: String,
     pub last_login_at: Option<String>,
+    pub mfa_enabled: bool,
 }
 
 impl UserResponse {
@@ -55,26 +72,43 @@ impl UserResponse {
             status: format!("{:?}", user.status()).to_lowercase(),
             created_at: user.created_at().to_rfc3339(),
             last_login_at: user.last_login_at().map(|t| t.to_rfc3339()),
+            mfa_enabled: user.is_mfa_enabled(),
         }
     }
 }
 
 /// Login with username and password
 ///
-/// POST /auth/login
-///
-/// Returns a JWT token on successful authentication.
+/// Returns a JWT token on successful authentication. Users with MFA enabled
+/// must also provide `mfa_code`; without it the request fails with the
+/// `mfa_required` error code.
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    tag = "auth",
+    request_body = LoginRequest,
+    responses((status = 200, body = LoginResponse)),
+)]
 pub async fn login(
     State(state): State<AppState>,
     Json(request): Json<LoginRequest>,
 ) -> Result<Json<LoginResponse>, ApiError> {
     // Authenticate user
-    let user = state
+    let outcome = state
         .user_service
-        .authenticate(&request.username, &request.password)
+        .login(&request.username, &request.password, request.mfa_code.as_deref())
         .await
-        .map_err(|e| ApiError::internal(e.to_string()))?
-        .ok_or_else(|| ApiError::unauthorized("Invalid username or password"))?;
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    let user = match outcome {
+        LoginOutcome::Authenticated(user) => *user,
+        LoginOutcome::MfaRequired => {
+            return Err(ApiError::unauthorized("MFA code required").with_code("mfa_required"));
+        }
+        LoginOutcome::Rejected => {
+            return Err(ApiError::unauthorized("Invalid username, password or MFA code"));
+        }
+    };
 
     // Generate JWT token
     let token = state
@@ -94,10 +128,14 @@ pub async fn login(
 
 /// Logout (client-side only for stateless JWT)
 ///
-/// POST /auth/logout
-///
 /// For JWT tokens, logout is handled client-side by discarding the token.
 /// This endpoint exists for API consistency.
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    tag = "auth",
+    responses((status = 200, body = LogoutResponse)),
+)]
 pub async fn logout(_user: RequireUser) -> Result<Json<LogoutResponse>, ApiError> {
     Ok(Json(LogoutResponse {
         message: "Logged out successfully".to_string(),
@@ -105,18 +143,325 @@ pub async fn logout(_user: RequireUser) -> Result<Json<LogoutResponse>, ApiError
 }
 
 /// Logout response
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct LogoutResponse {
     pub message: String,
 }
 
 /// Get current authenticated user
 ///
-/// GET /auth/me
-///
 /// Returns information about the currently authenticated user.
+#[utoipa::path(
+    get,
+    path = "/auth/me",
+    tag = "auth",
+    responses((status = 200, body = UserResponse)),
+)]
 pub async fn get_current_user(
     RequireUser(user): RequireUser,
 ) -> Result<Json<UserResponse>, ApiError> {
     Ok(Json(UserResponse::from_user(&user)))
 }
+
+/// MFA code request
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MfaCodeRequest {
+    pub code: String,
+}
+
+/// MFA enrollment response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MfaEnrollmentResponse {
+    pub secret: String,
+    pub provisioning_uri: String,
+}
+
+/// Recovery codes response (codes are only shown once)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+/// Start TOTP enrollment
+///
+/// Returns a new secret and `otpauth://` URI. MFA is not enforced until the
+/// enrollment is confirmed via `/auth/mfa/verify`.
+#[utoipa::path(
+    post,
+    path = "/auth/mfa/enroll",
+    tag = "auth",
+    responses((status = 200, body = MfaEnrollmentResponse)),
+)]
+pub async fn enroll_mfa(
+    State(state): State<AppState>,
+    RequireUser(user): RequireUser,
+) -> Result<Json<MfaEnrollmentResponse>, ApiError> {
+    let enrollment = state
+        .user_service
+        .begin_mfa_enrollment(user.id().as_str())
+        .await?;
+
+    Ok(Json(MfaEnrollmentResponse {
+        secret: enrollment.secret,
+        provisioning_uri: enrollment.provisioning_uri,
+    }))
+}
+
+/// Confirm TOTP enrollment
+///
+/// Enables MFA once a valid code is provided and returns recovery codes.
+#[utoipa::path(
+    post,
+    path = "/auth/mfa/verify",
+    tag = "auth",
+    request_body = MfaCodeRequest,
+    responses((status = 200, body = RecoveryCodesResponse)),
+)]
+pub async fn verify_mfa(
+    State(state): State<AppState>,
+    RequireUser(user): RequireUser,
+    Json(request): Json<MfaCodeRequest>,
+) -> Result<Json<RecoveryCodesResponse>, ApiError> {
+    let recovery_codes = state
+        .user_service
+        .confirm_mfa_enrollment(user.id().as_str(), &request.code)
+        .await?;
+
+    Ok(Json(RecoveryCodesResponse { recovery_codes }))
+}
+
+/// Regenerate recovery codes
+///
+#[utoipa::path(
+    post,
+    path = "/auth/mfa/recovery-codes",
+    tag = "auth",
+    request_body = MfaCodeRequest,
+    responses((status = 200, body = RecoveryCodesResponse)),
+)]
+pub async fn regenerate_recovery_codes(
+    State(state): State<AppState>,
+    RequireUser(user): RequireUser,
+    Json(request): Json<MfaCodeRequest>,
+) -> Result<Json<RecoveryCodesResponse>, ApiError> {
+    let recovery_codes = state
+        .user_service
+        .regenerate_recovery_codes(user.id().as_str(), &request.code)
+        .await?;
+
+    Ok(Json(RecoveryCodesResponse { recovery_codes }))
+}
+
+/// Disable MFA for the current user
+///
+#[utoipa::path(
+    post,
+    path = "/auth/mfa/disable",
+    tag = "auth",
+    request_body = MfaCodeRequest,
+    responses((status = 200, body = UserResponse)),
+)]
+pub async fn disable_mfa(
+    State(state): State<AppState>,
+    RequireUser(user): RequireUser,
+    Json(request): Json<MfaCodeRequest>,
+) -> Result<Json<UserResponse>, ApiError> {
+    let user = state
+        .user_service
+        .disable_mfa(user.id().as_str(), &request.code)
+        .await?;
+
+    Ok(Json(UserResponse::from_user(&user)))
+}
+
+/// OAuth 2.0 token request for the `client_credentials` grant
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TokenRequest {
+    pub grant_type: String,
+    pub client_id: String,
+    pub client_secret: String,
+    /// Space-separated subset of the service account scopes; defaults to all
+    #[serde(default)]
+    pub scope: Option<String>,
+}
+
+/// OAuth 2.0 token response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: u64,
+    pub scope: String,
+}
+
+/// Exchange service account credentials for a scoped JWT
+///
+/// Accepts a JSON or `application/x-www-form-urlencoded` body with
+/// `grant_type=client_credentials`, `client_id`, `client_secret` and an
+/// optional `scope`. The token can be used on the admin API, limited to the
+/// granted scopes.
+#[utoipa::path(
+    post,
+    path = "/auth/token",
+    tag = "auth",
+    request_body(
+        content(
+            (TokenRequest = "application/json"),
+            (TokenRequest = "application/x-www-form-urlencoded"),
+        ),
+    ),
+    responses((status = 200, body = TokenResponse)),
+)]
+pub async fn issue_token(
+    State(state): State<AppState>,
+    request: Request,
+) -> Result<Json<TokenResponse>, ApiError> {
+    let request = parse_token_request(request, &state).await?;
+
+    if request.grant_type != "client_credentials" {
+        return Err(ApiError::bad_request(format!(
+            "Unsupported grant type '{}'",
+            request.grant_type
+        ))
+        .with_code("unsupported_grant_type"));
+    }
+
+    let account = state
+        .service_account_service
+        .authenticate(&request.client_id, &request.client_secret)
+        .await?
+        .ok_or_else(|| {
+            ApiError::unauthorized("Invalid client credentials").with_code("invalid_client")
+        })?;
+
+    let scopes = resolve_token_scopes(&account, request.scope.as_deref())?;
+
+    let access_token = state
+        .jwt_service
+        .generate_for_service_account(&account, &scopes)
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    Ok(Json(TokenResponse {
+        access_token,
+        token_type: "Bearer".to_string(),
+        expires_in: SERVICE_ACCOUNT_TOKEN_EXPIRATION_SECS,
+        scope: scopes
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .join(" "),
+    }))
+}
+
+async fn parse_token_request(request: Request, state: &AppState) -> Result<TokenRequest, ApiError> {
+    let is_form = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/x-www-form-urlencoded"));
+
+    if is_form {
+        Form::<TokenRequest>::from_request(request, state)
+            .await
+            .map(|Form(request)| request)
+            .map_err(|e| ApiError::bad_request(e.body_text()).with_code("invalid_request"))
+    } else {
+        axum::Json::<TokenRequest>::from_request(request, state)
+            .await
+            .map(|axum::Json(request)| request)
+            .map_err(|e| ApiError::bad_request(e.body_text()).with_code("invalid_request"))
+    }
+}
+
+/// Resolve the scopes of a token request. Without a requested scope, every
+/// scope of the service account is granted.
+fn resolve_token_scopes(
+    account: &ServiceAccount,
+    requested: Option<&str>,
+) -> Result<Vec<Permission>, ApiError> {
+    let requested: Vec<&str> = requested.unwrap_or_default().split_whitespace().collect();
+
+    if requested.is_empty() {
+        return Ok(account.scopes().to_vec());
+    }
+
+    requested
+        .into_iter()
+        .map(|scope| {
+            Permission::parse(scope)
+                .ok()
+                .filter(|permission| account.allows(permission))
+                .ok_or_else(|| {
+                    ApiError::bad_request(format!("Scope '{}' is not allowed", scope))
+                        .with_code("invalid_scope")
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::role::PermissionResource;
+    use crate::domain::service_account::ServiceAccountId;
+    use crate::domain::team::TeamId;
+
+    fn create_account() -> ServiceAccount {
+        ServiceAccount::new(
+            ServiceAccountId::new("ci").unwrap(),
+            "CI",
+            "sha256$hash",
+            vec![
+                Permission::write(PermissionResource::Prompts),
+                Permission::write(PermissionResource::Workflows),
+            ],
+            TeamId::administrators(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolve_token_scopes_defaults_to_account_scopes() {
+        let account = create_account();
+
+        assert_eq!(resolve_token_scopes(&account, None).unwrap(), account.scopes());
+        assert_eq!(resolve_token_scopes(&account, Some(" ")).unwrap(), account.scopes());
+    }
+
+    #[test]
+    fn test_resolve_token_scopes_subset() {
+        let account = create_account();
+
+        let scopes = resolve_token_scopes(&account, Some("prompts:read workflows:write")).unwrap();
+        assert_eq!(
+            scopes,
+            vec![
+                Permission::read(PermissionResource::Prompts),
+                Permission::write(PermissionResource::Workflows),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_token_scopes_rejects_extra_scopes() {
+        let account = create_account();
+
+        let err = resolve_token_scopes(&account, Some("models:write")).unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::BAD_REQUEST);
+
+        assert!(resolve_token_scopes(&account, Some("not-a-scope")).is_err());
+    }
+
+    #[test]
+    fn test_token_request_deserialization() {
+        let json = r#"{
+            "grant_type": "client_credentials",
+            "client_id": "ci",
+            "client_secret": "sas_secret"
+        }"#;
+
+        let request: TokenRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.grant_type, "client_credentials");
+        assert!(request.scope.is_none());
+    }
+}
@@ -5,12 +5,14 @@ use std::time::Instant;
 use axum::{extract::State, http::StatusCode, response::IntoResponse};
 
 use crate::api::types::Json;
+use crate::infrastructure::health::DependencyProbe;
 use serde::Serialize;
+use utoipa::ToSchema;
 
 use super::state::AppState;
 
 /// Detailed health response with component status
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct HealthResponse {
     pub status: HealthStatus,
     pub version: String,
@@ -21,7 +23,7 @@ pub struct HealthResponse {
 }
 
 /// Health check status
-#[derive(Serialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum HealthStatus {
     Healthy,
@@ -30,7 +32,7 @@ pub enum HealthStatus {
 }
 
 /// Individual component health check
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct HealthCheck {
     pub name: String,
     pub status: HealthStatus,
@@ -42,6 +44,12 @@ pub struct HealthCheck {
 
 /// Simple health check - returns 200 if the service is running
 /// Used for basic liveness probes
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses((status = 200, body = HealthResponse)),
+)]
 pub async fn health_check() -> impl IntoResponse {
     let response = HealthResponse {
         status: HealthStatus::Healthy,
@@ -55,6 +63,15 @@ pub async fn health_check() -> impl IntoResponse {
 
 /// Readiness check with dependency verification
 /// Checks if the service can handle requests
+#[utoipa::path(
+    get,
+    path = "/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "Ready, possibly degraded", body = HealthResponse),
+        (status = 503, description = "A required dependency is unhealthy", body = HealthResponse),
+    ),
+)]
 pub async fn ready_check(State(state): State<AppState>) -> impl IntoResponse {
     let start = Instant::now();
     let mut checks = Vec::new();
@@ -76,6 +93,12 @@ pub async fn ready_check(State(state): State<AppState>) -> impl IntoResponse {
     }
     checks.push(api_key_check);
 
+    // Probe downstream dependencies when enabled under [health]
+    for check in check_dependencies(&state).await {
+        overall_status = overall_status.max(check.status);
+        checks.push(check);
+    }
+
     let latency = start.elapsed().as_millis() as u64;
     let response = HealthResponse {
         status: overall_status,
@@ -95,10 +118,51 @@ pub async fn ready_check(State(state): State<AppState>) -> impl IntoResponse {
 
 /// Liveness check - simple check to verify the service is running
 /// Used for Kubernetes liveness probes to detect crashes
+#[utoipa::path(
+    get,
+    path = "/live",
+    tag = "health",
+    responses((status = 200, description = "The process is alive")),
+)]
 pub async fn live_check() -> impl IntoResponse {
     StatusCode::OK
 }
 
+/// PostgreSQL failures make the gateway unready, Redis and provider failures
+/// only degrade it
+async fn check_dependencies(state: &AppState) -> Vec<HealthCheck> {
+    let prober = &state.dependency_prober;
+    let mut probes = prober.probe_infrastructure().await;
+
+    if prober.probes_providers() {
+        match state.credential_service.list().await {
+            Ok(credentials) => probes.extend(prober.probe_providers(&credentials).await),
+            Err(e) => probes.push(DependencyProbe {
+                name: "providers".to_string(),
+                error: Some(e.to_string()),
+                latency_ms: 0,
+            }),
+        }
+    }
+
+    probes.into_iter().map(dependency_check).collect()
+}
+
+fn dependency_check(probe: DependencyProbe) -> HealthCheck {
+    let status = match (&probe.error, probe.name.as_str()) {
+        (None, _) => HealthStatus::Healthy,
+        (Some(_), "postgres") => HealthStatus::Unhealthy,
+        (Some(_), _) => HealthStatus::Degraded,
+    };
+
+    HealthCheck {
+        name: probe.name,
+        status,
+        message: probe.error,
+        latency_ms: Some(probe.latency_ms),
+    }
+}
+
 async fn check_model_service(state: &AppState) -> HealthCheck {
     let start = Instant::now();
 
@@ -172,6 +236,29 @@ mod tests {
         assert!(!json.contains("checks"));
     }
 
+    #[test]
+    fn test_dependency_check_status() {
+        let probe = |name: &str, error: Option<&str>| DependencyProbe {
+            name: name.to_string(),
+            error: error.map(str::to_string),
+            latency_ms: 3,
+        };
+
+        assert!(dependency_check(probe("postgres", None)).status == HealthStatus::Healthy);
+        assert!(
+            dependency_check(probe("postgres", Some("refused"))).status
+                == HealthStatus::Unhealthy
+        );
+
+        let provider = dependency_check(probe("provider:openai", Some("HTTP status 503")));
+        assert!(provider.status == HealthStatus::Degraded);
+        assert_eq!(provider.message.as_deref(), Some("HTTP status 503"));
+        assert_eq!(provider.latency_ms, Some(3));
+
+        assert!(HealthStatus::Healthy.max(HealthStatus::Degraded) == HealthStatus::Degraded);
+        assert!(HealthStatus::Unhealthy.max(HealthStatus::Degraded) == HealthStatus::Unhealthy);
+    }
+
     #[test]
     fn test_health_response_with_checks() {
         let response = HealthResponse {
@@ -3,20 +3,34 @@
 //! Allows either:
 //! - API key with admin=true permission
 //! - Valid JWT token from an authenticated user
+//! - Valid JWT token issued to a service account
+//!
+//! JWT users are additionally checked against the permissions of their role:
+//! the required permission is derived from the route's resource segment and
+//! the HTTP method (GET/HEAD/OPTIONS require read, everything else write).
+//! Organization admins may additionally read and update their organization
+//! and manage its teams (except deleting them) regardless of their role.
+//! Service accounts are limited to the scopes of their token.
 
 use axum::{
     extract::FromRequestParts,
-    http::request::Parts,
+    http::{request::Parts, Method},
 };
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::api::state::AppState;
 use crate::api::types::ApiError;
 use crate::domain::api_key::ApiKey;
+use crate::domain::audit::AuditActor;
+use crate::domain::organization::OrganizationId;
+use crate::domain::role::{Permission, PermissionAction, PermissionResource};
+use crate::domain::service_account::ServiceAccount;
+use crate::domain::team::TeamId;
 use crate::domain::user::User;
 
+use super::audit::attach_audit_actor;
 use super::auth::RequireApiKey;
-use super::user_auth::try_jwt_auth;
+use super::user_auth::{try_jwt_auth, try_service_account_auth};
 
 /// Represents the type of admin authentication used
 #[derive(Debug, Clone)]
@@ -25,6 +39,12 @@ pub enum AdminAuth {
     ApiKey(ApiKey),
     /// Authenticated via JWT token
     User(User),
+    /// Authenticated via a service account JWT token
+    ServiceAccount {
+        account: ServiceAccount,
+        /// Permissions granted by the token
+        scopes: Vec<Permission>,
+    },
 }
 
 impl AdminAuth {
@@ -33,6 +53,18 @@ impl AdminAuth {
         match self {
             AdminAuth::ApiKey(key) => format!("api_key:{}", key.id()),
             AdminAuth::User(user) => format!("user:{}", user.id()),
+            AdminAuth::ServiceAccount { account, .. } => {
+                format!("service_account:{}", account.id())
+            }
+        }
+    }
+
+    /// Get the team of the authenticated entity
+    pub fn team_id(&self) -> &TeamId {
+        match self {
+            AdminAuth::ApiKey(key) => key.team_id(),
+            AdminAuth::User(user) => user.team_id(),
+            AdminAuth::ServiceAccount { account, .. } => account.team_id(),
         }
     }
 }
@@ -41,9 +73,12 @@ impl AdminAuth {
 ///
 /// Authentication methods (tried in order):
 /// 1. JWT token from Authorization: Bearer <jwt_token>
-/// 2. API key from Authorization: Bearer <api_key> or X-API-Key header
+/// 2. Service account JWT token from Authorization: Bearer <jwt_token>
+/// 3. API key from Authorization: Bearer <api_key> or X-API-Key header
 ///
 /// For API key auth, the key must have admin=true permission.
+/// For JWT auth, the user's role must grant the permission required by the route.
+/// For service account auth, a token scope must grant it.
 #[derive(Debug, Clone)]
 pub struct RequireAdmin(pub AdminAuth);
 
@@ -56,10 +91,59 @@ impl FromRequestParts<AppState> for RequireAdmin {
     ) -> Result<Self, Self::Rejection> {
         // Try JWT authentication first
         if let Some(user) = try_jwt_auth(&parts.headers, state).await {
-            debug!(user_id = %user.id(), "Admin access via JWT");
+            let required = required_permission(&parts.method, parts.uri.path());
+            let role = state.role_service.resolve_for_user(&user).await?;
+
+            if !role.allows(&required)
+                && !allowed_as_organization_admin(state, &user, &parts.method, parts.uri.path())
+                    .await?
+            {
+                warn!(
+                    user_id = %user.id(),
+                    role = %role.id(),
+                    permission = %required,
+                    "Admin access denied by role"
+                );
+                return Err(ApiError::forbidden(format!(
+                    "Role '{}' does not grant '{}'",
+                    role.id(),
+                    required
+                )));
+            }
+
+            debug!(user_id = %user.id(), role = %role.id(), "Admin access via JWT");
+            attach_audit_actor(
+                parts,
+                state,
+                AuditActor::user(user.id().as_str(), user.username()),
+            );
             return Ok(RequireAdmin(AdminAuth::User(user)));
         }
 
+        if let Some((account, scopes)) = try_service_account_auth(&parts.headers, state).await {
+            let required = required_permission(&parts.method, parts.uri.path());
+
+            if !scopes.iter().any(|scope| scope.grants(&required)) {
+                warn!(
+                    service_account_id = %account.id(),
+                    permission = %required,
+                    "Admin access denied by token scope"
+                );
+                return Err(ApiError::forbidden(format!(
+                    "Token scope does not grant '{}'",
+                    required
+                )));
+            }
+
+            debug!(service_account_id = %account.id(), "Admin access via service account");
+            attach_audit_actor(
+                parts,
+                state,
+                AuditActor::service_account(account.id().as_str(), account.name()),
+            );
+            return Ok(RequireAdmin(AdminAuth::ServiceAccount { account, scopes }));
+        }
+
         // Fall back to API key authentication
         match RequireApiKey::from_request_parts(parts, state).await {
             Ok(RequireApiKey(api_key)) => {
@@ -69,6 +153,11 @@ impl FromRequestParts<AppState> for RequireAdmin {
                 }
 
                 debug!(api_key_id = %api_key.id(), "Admin access via API key");
+                attach_audit_actor(
+                    parts,
+                    state,
+                    AuditActor::api_key(api_key.id().as_str(), api_key.name()),
+                );
                 Ok(RequireAdmin(AdminAuth::ApiKey(api_key)))
             }
             Err(_) => {
@@ -80,6 +169,86 @@ impl FromRequestParts<AppState> for RequireAdmin {
     }
 }
 
+/// Determine the permission required to access an admin route.
+///
+/// The resource is taken from the first path segment (e.g. `/api-keys/{id}`
+/// maps to `api_keys`). Unknown segments require full write access.
+pub fn required_permission(method: &Method, path: &str) -> Permission {
+    let action = match *method {
+        Method::GET | Method::HEAD | Method::OPTIONS => PermissionAction::Read,
+        _ => PermissionAction::Write,
+    };
+
+    let resource = path
+        .split('/')
+        .find(|segment| !segment.is_empty())
+        .and_then(PermissionResource::from_path_segment);
+
+    match resource {
+        Some(resource) => Permission::new(resource, action),
+        None => Permission::write(PermissionResource::All),
+    }
+}
+
+/// Resource an organization admin may manage regardless of their role
+#[derive(Debug, PartialEq, Eq)]
+enum OrganizationAdminTarget<'a> {
+    /// The organization itself: read, update and list its teams
+    Organization(&'a str),
+    /// A team of the organization and its sub-resources, except deletion
+    Team(&'a str),
+}
+
+fn organization_admin_target<'a>(
+    method: &Method,
+    path: &'a str,
+) -> Option<OrganizationAdminTarget<'a>> {
+    let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+    let resource = segments.next()?;
+    let id = segments.next()?;
+    let rest: Vec<&str> = segments.collect();
+
+    match (resource, rest.as_slice()) {
+        ("organizations", []) if *method == Method::GET || *method == Method::PUT => {
+            Some(OrganizationAdminTarget::Organization(id))
+        }
+        ("organizations", ["teams"]) if *method == Method::GET => {
+            Some(OrganizationAdminTarget::Organization(id))
+        }
+        ("teams", []) if *method == Method::DELETE => None,
+        ("teams", _) => Some(OrganizationAdminTarget::Team(id)),
+        _ => None,
+    }
+}
+
+/// Check if the user administers the organization targeted by the route
+async fn allowed_as_organization_admin(
+    state: &AppState,
+    user: &User,
+    method: &Method,
+    path: &str,
+) -> Result<bool, ApiError> {
+    let organization = match organization_admin_target(method, path) {
+        Some(OrganizationAdminTarget::Organization(id)) => match OrganizationId::new(id) {
+            Ok(id) => state.organization_service.get(id.as_str()).await?,
+            Err(_) => None,
+        },
+        Some(OrganizationAdminTarget::Team(id)) => match TeamId::new(id) {
+            Ok(team_id) => state.organization_service.get_for_team(&team_id).await?,
+            Err(_) => None,
+        },
+        None => None,
+    };
+
+    let allowed = organization.is_some_and(|o| o.is_admin(user.id()));
+
+    if allowed {
+        debug!(user_id = %user.id(), path = %path, "Admin access as organization admin");
+    }
+
+    Ok(allowed)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -111,4 +280,91 @@ mod tests {
         let auth = AdminAuth::User(user);
         assert!(auth.identifier().starts_with("user:"));
     }
+
+    #[test]
+    fn test_admin_auth_identifier_service_account() {
+        use crate::domain::service_account::ServiceAccountId;
+
+        let scopes = vec![Permission::write(PermissionResource::Prompts)];
+        let account = ServiceAccount::new(
+            ServiceAccountId::new("ci-pipeline").unwrap(),
+            "CI pipeline",
+            "hash",
+            scopes.clone(),
+            admin_team(),
+        )
+        .unwrap();
+
+        let auth = AdminAuth::ServiceAccount { account, scopes };
+        assert_eq!(auth.identifier(), "service_account:ci-pipeline");
+        assert_eq!(auth.team_id(), &admin_team());
+    }
+
+    #[test]
+    fn test_required_permission_read() {
+        let p = required_permission(&Method::GET, "/models/gpt-4");
+        assert_eq!(p, Permission::read(PermissionResource::Models));
+
+        let p = required_permission(&Method::HEAD, "/knowledge-bases");
+        assert_eq!(p, Permission::read(PermissionResource::KnowledgeBases));
+    }
+
+    #[test]
+    fn test_required_permission_write() {
+        let p = required_permission(&Method::POST, "/api-keys/key-1/suspend");
+        assert_eq!(p, Permission::write(PermissionResource::ApiKeys));
+
+        let p = required_permission(&Method::DELETE, "/roles/custom");
+        assert_eq!(p, Permission::write(PermissionResource::Roles));
+
+        let p = required_permission(&Method::POST, "/service-accounts/ci/rotate-secret");
+        assert_eq!(p, Permission::write(PermissionResource::ServiceAccounts));
+    }
+
+    #[test]
+    fn test_required_permission_unknown_resource() {
+        let p = required_permission(&Method::GET, "/unknown");
+        assert_eq!(p, Permission::write(PermissionResource::All));
+
+        let p = required_permission(&Method::GET, "/");
+        assert_eq!(p, Permission::write(PermissionResource::All));
+    }
+
+    #[test]
+    fn test_organization_admin_target() {
+        use OrganizationAdminTarget::{Organization, Team};
+
+        assert_eq!(
+            organization_admin_target(&Method::GET, "/organizations/acme"),
+            Some(Organization("acme"))
+        );
+        assert_eq!(
+            organization_admin_target(&Method::PUT, "/organizations/acme"),
+            Some(Organization("acme"))
+        );
+        assert_eq!(
+            organization_admin_target(&Method::GET, "/organizations/acme/teams"),
+            Some(Organization("acme"))
+        );
+        assert_eq!(
+            organization_admin_target(&Method::DELETE, "/organizations/acme"),
+            None
+        );
+        assert_eq!(
+            organization_admin_target(&Method::PUT, "/organizations/acme/teams/team-a"),
+            None
+        );
+        assert_eq!(organization_admin_target(&Method::GET, "/organizations"), None);
+
+        assert_eq!(
+            organization_admin_target(&Method::PUT, "/teams/team-a/quota"),
+            Some(Team("team-a"))
+        );
+        assert_eq!(
+            organization_admin_target(&Method::POST, "/teams/team-a/suspend"),
+            Some(Team("team-a"))
+        );
+        assert_eq!(organization_admin_target(&Method::DELETE, "/teams/team-a"), None);
+        assert_eq!(organization_admin_target(&Method::GET, "/models/gpt-4"), None);
+    }
 }
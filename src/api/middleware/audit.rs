@@ -0,0 +1,317 @@
+//! Audit trail middleware for admin mutations
+//!
+//! The middleware captures the payload of every mutating admin request and
+//! places an [`AuditSlot`] in the request extensions. The `RequireAdmin`
+//! extractor fills the slot with the authenticated actor; once the handler
+//! has completed, an audit log is recorded with the outcome. Requests
+//! rejected before authentication are not audited.
+
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::OriginalUri,
+    http::{header, request::Parts, HeaderMap, Method, Request},
+    middleware::Next,
+    response::Response,
+};
+use tracing::warn;
+
+use crate::api::state::{AppState, AuditLogServiceTrait};
+use crate::domain::audit::{AuditActor, AuditLog};
+
+/// Largest request body captured as the change set of an audit log
+const MAX_AUDITED_BODY_BYTES: usize = 64 * 1024;
+
+/// POST actions that execute or preview resources without modifying them
+const NON_MUTATING_ACTIONS: [&str; 5] = ["execute", "render", "test", "check", "validate"];
+
+/// Field names whose values are never stored in audit logs
+const REDACTED_FIELDS: [&str; 9] = [
+    "password",
+    "secret",
+    "token",
+    "api_key",
+    "apikey",
+    "authorization",
+    "private_key",
+    "header_value",
+    "connection_string",
+];
+
+/// Authenticated caller of an audited request
+#[derive(Clone)]
+struct AuditContext {
+    actor: AuditActor,
+    client_ip: Option<String>,
+    service: Arc<dyn AuditLogServiceTrait>,
+}
+
+/// Request extension shared between the audit middleware and the admin extractor
+#[derive(Clone, Default)]
+pub struct AuditSlot(Arc<Mutex<Option<AuditContext>>>);
+
+impl AuditSlot {
+    fn fill(&self, context: AuditContext) {
+        *self.0.lock().unwrap() = Some(context);
+    }
+
+    fn take(&self) -> Option<AuditContext> {
+        self.0.lock().unwrap().take()
+    }
+}
+
+/// Register the authenticated admin for auditing, if the request is audited
+pub fn attach_audit_actor(parts: &Parts, state: &AppState, actor: AuditActor) {
+    if let Some(slot) = parts.extensions.get::<AuditSlot>() {
+        slot.fill(AuditContext {
+            actor,
+            client_ip: state
+                .client_ip_resolver
+                .resolve_parts(parts)
+                .map(|ip| ip.to_string()),
+            service: state.audit_log_service.clone(),
+        });
+    }
+}
+
+/// Middleware recording an audit log for every authenticated admin mutation
+pub async fn audit_middleware(request: Request<Body>, next: Next) -> Response {
+    let method = request.method().clone();
+
+    let Some(target) = AuditTarget::parse(&method, request.uri().path()) else {
+        return next.run(request).await;
+    };
+
+    let path = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|uri| uri.path().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let user_agent = request
+        .headers()
+        .get(header::USER_AGENT)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+
+    let (parts, body) = request.into_parts();
+    let (body, changes) = capture_body(&parts.headers, body).await;
+
+    let slot = AuditSlot::default();
+    let mut request = Request::from_parts(parts, body);
+    request.extensions_mut().insert(slot.clone());
+
+    let response = next.run(request).await;
+
+    let Some(context) = slot.take() else {
+        return response;
+    };
+
+    let entity_id = target.entity_id.or_else(|| {
+        changes
+            .as_ref()
+            .and_then(|c| c.get("id"))
+            .and_then(|id| id.as_str())
+            .map(String::from)
+    });
+
+    let mut log = AuditLog::new(context.actor, target.action, target.entity_type)
+        .with_status_code(response.status().as_u16())
+        .with_request(method.as_str(), path);
+
+    if let Some(entity_id) = entity_id {
+        log = log.with_entity_id(entity_id);
+    }
+
+    if let Some(changes) = changes {
+        log = log.with_changes(changes);
+    }
+
+    if let Some(client_ip) = context.client_ip {
+        log = log.with_client_ip(client_ip);
+    }
+
+    if let Some(user_agent) = user_agent {
+        log = log.with_user_agent(user_agent);
+    }
+
+    if let Err(e) = context.service.record(log).await {
+        warn!(error = %e, "Failed to record audit log");
+    }
+
+    response
+}
+
+/// Buffer a JSON request body so it can be both audited and handled
+async fn capture_body(headers: &HeaderMap, body: Body) -> (Body, Option<serde_json::Value>) {
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/json"));
+
+    let within_limit = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok())
+        .is_some_and(|len| len <= MAX_AUDITED_BODY_BYTES);
+
+    if !is_json || !within_limit {
+        return (body, None);
+    }
+
+    match to_bytes(body, MAX_AUDITED_BODY_BYTES).await {
+        Ok(bytes) => {
+            let changes = serde_json::from_slice(&bytes).ok().map(redact_sensitive_fields);
+            (Body::from(bytes), changes)
+        }
+        Err(_) => (Body::empty(), None),
+    }
+}
+
+/// Replace the values of sensitive fields (passwords, tokens, keys), recursively
+pub fn redact_sensitive_fields(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => map
+            .into_iter()
+            .map(|(key, value)| {
+                let lower = key.to_ascii_lowercase();
+
+                let sensitive = REDACTED_FIELDS
+                    .iter()
+                    .any(|f| lower == *f || lower.ends_with(&format!("_{}", f)));
+
+                if sensitive && !value.is_null() {
+                    (key, serde_json::Value::String("[REDACTED]".to_string()))
+                } else {
+                    (key, redact_sensitive_fields(value))
+                }
+            })
+            .collect(),
+        serde_json::Value::Array(items) => items.into_iter().map(redact_sensitive_fields).collect(),
+        other => other,
+    }
+}
+
+/// Entity and action targeted by an admin request
+#[derive(Debug, PartialEq, Eq)]
+struct AuditTarget {
+    entity_type: String,
+    entity_id: Option<String>,
+    action: String,
+}
+
+impl AuditTarget {
+    /// Derive the audit target from an admin route path (relative to the
+    /// admin router). Returns `None` for requests that are not mutations.
+    fn parse(method: &Method, path: &str) -> Option<Self> {
+        let verb = match *method {
+            Method::POST => "create",
+            Method::PUT | Method::PATCH => "update",
+            Method::DELETE => "delete",
+            _ => return None,
+        };
+
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let entity_type = segments.first()?.replace('-', "_");
+
+        let (entity_id, action) = match (segments.len(), *method == Method::POST) {
+            // Collection-level actions, e.g. POST /execution-logs/cleanup
+            (2, true) => (None, segments[1].replace('-', "_")),
+            (0 | 1, _) => (None, verb.to_string()),
+            (_, is_post) => {
+                let id = Some(segments[1].to_string());
+
+                match segments.get(2) {
+                    None => (id, verb.to_string()),
+                    Some(sub) if is_post => (id, sub.replace('-', "_")),
+                    Some(sub) => (id, format!("{}_{}", verb, sub.replace('-', "_"))),
+                }
+            }
+        };
+
+        if NON_MUTATING_ACTIONS.contains(&action.as_str()) {
+            return None;
+        }
+
+        Some(Self {
+            entity_type,
+            entity_id,
+            action,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(entity_type: &str, entity_id: Option<&str>, action: &str) -> Option<AuditTarget> {
+        Some(AuditTarget {
+            entity_type: entity_type.to_string(),
+            entity_id: entity_id.map(String::from),
+            action: action.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_parse_crud_targets() {
+        assert_eq!(
+            AuditTarget::parse(&Method::POST, "/api-keys"),
+            target("api_keys", None, "create")
+        );
+        assert_eq!(
+            AuditTarget::parse(&Method::PUT, "/models/gpt-4"),
+            target("models", Some("gpt-4"), "update")
+        );
+        assert_eq!(
+            AuditTarget::parse(&Method::DELETE, "/teams/team-a"),
+            target("teams", Some("team-a"), "delete")
+        );
+    }
+
+    #[test]
+    fn test_parse_action_targets() {
+        assert_eq!(
+            AuditTarget::parse(&Method::POST, "/api-keys/key-1/rotate"),
+            target("api_keys", Some("key-1"), "rotate")
+        );
+        assert_eq!(
+            AuditTarget::parse(&Method::PUT, "/users/user-1/role"),
+            target("users", Some("user-1"), "update_role")
+        );
+        assert_eq!(
+            AuditTarget::parse(&Method::POST, "/execution-logs/cleanup"),
+            target("execution_logs", None, "cleanup")
+        );
+    }
+
+    #[test]
+    fn test_parse_skips_reads_and_executions() {
+        assert!(AuditTarget::parse(&Method::GET, "/models").is_none());
+        assert!(AuditTarget::parse(&Method::POST, "/models/gpt-4/execute").is_none());
+        assert!(AuditTarget::parse(&Method::POST, "/prompts/p/render").is_none());
+        assert!(AuditTarget::parse(&Method::POST, "/budgets/check").is_none());
+        assert!(AuditTarget::parse(&Method::POST, "/prompts/validate").is_none());
+        assert!(AuditTarget::parse(&Method::POST, "/").is_none());
+    }
+
+    #[test]
+    fn test_redact_sensitive_fields() {
+        let changes = serde_json::json!({
+            "name": "OpenAI",
+            "max_tokens": 100,
+            "api_key": "sk-secret",
+            "nested": {"client_secret": "abc", "items": [{"password": "p"}]},
+            "header_value": null
+        });
+
+        let redacted = redact_sensitive_fields(changes);
+
+        assert_eq!(redacted["name"], "OpenAI");
+        assert_eq!(redacted["max_tokens"], 100);
+        assert_eq!(redacted["api_key"], "[REDACTED]");
+        assert_eq!(redacted["nested"]["client_secret"], "[REDACTED]");
+        assert_eq!(redacted["nested"]["items"][0]["password"], "[REDACTED]");
+        assert!(redacted["header_value"].is_null());
+    }
+}
@@ -1,20 +1,35 @@
 //! API key authentication middleware
 
+use std::net::IpAddr;
+
 use axum::{
-    extract::FromRequestParts,
+    extract::{FromRequestParts, OriginalUri},
     http::{header, request::Parts},
 };
-use tracing::debug;
+use tracing::{debug, warn, Instrument, Span};
 
 use crate::api::state::AppState;
 use crate::api::types::ApiError;
 use crate::domain::api_key::ApiKey;
+use crate::domain::audit::{AuditActor, AuditLog};
+use crate::domain::network::ip_allowed;
+use crate::domain::team::Team;
+use crate::infrastructure::observability::{auth_span, record_result};
+
+use super::concurrency::acquire_team_permit;
+use super::quota::enforce_quota;
+use super::request_trace::register_request_trace;
 
 /// Extractor that requires a valid API key
 ///
 /// Extracts the API key from either:
 /// - Authorization header: `Bearer <api_key>`
 /// - X-API-Key header: `<api_key>`
+///
+/// Keys and teams with allowed CIDR lists are only accepted from matching
+/// client IP addresses. On concurrency-limited routes, the request also
+/// takes one of the in-flight slots of the key's team, and on quota-limited
+/// routes it counts against the rate limits and quotas of the key.
 #[derive(Debug, Clone)]
 pub struct RequireApiKey(pub ApiKey);
 
@@ -25,26 +40,115 @@ impl FromRequestParts<AppState> for RequireApiKey {
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
-        let api_key_value = extract_api_key_from_headers(&parts.headers)?;
+        let span = auth_span();
+        let result = authenticate(parts, state).instrument(span.clone()).await;
+
+        record_result(&span, result).map(RequireApiKey)
+    }
+}
+
+/// Validate the API key of a request and apply its access controls
+async fn authenticate(parts: &mut Parts, state: &AppState) -> Result<ApiKey, ApiError> {
+    let api_key_value = extract_api_key_from_headers(&parts.headers)?;
+
+    debug!(
+        key_prefix = %api_key_value.chars().take(8).collect::<String>(),
+        "Validating API key"
+    );
+
+    let client_ip = state.client_ip_resolver.resolve_parts(parts);
+
+    // Usage is only recorded once the access controls passed
+    let api_key = state
+        .api_key_service
+        .verify(&api_key_value)
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?
+        .ok_or_else(|| ApiError::unauthorized("Invalid API key"))?;
+
+    register_request_trace(parts, state, api_key.id().as_str());
+
+    let span = Span::current();
+    span.record("api_key.id", api_key.id().as_str());
+    span.record("team.id", api_key.team_id().as_str());
 
-        debug!(
-            key_prefix = %api_key_value.chars().take(8).collect::<String>(),
-            "Validating API key"
+    if !api_key.is_valid() {
+        return Err(ApiError::unauthorized("API key is not active or has expired"));
+    }
+
+    let team = state
+        .team_service
+        .get(api_key.team_id().as_str())
+        .await
+        .map_err(|e| ApiError::internal(e.to_string()))?;
+
+    check_ip_allowlist(state, parts, &api_key, team.as_ref(), client_ip).await?;
+    state
+        .api_key_service
+        .record_usage(&api_key, client_ip.map(|ip| ip.to_string()).as_deref())
+        .await;
+    acquire_team_permit(parts, state, api_key.team_id(), team.as_ref())?;
+    enforce_quota(parts, state, &api_key).await?;
+
+    Ok(api_key)
+}
+
+/// Enforce the allowed CIDR lists of the API key and its team.
+/// Violations are recorded to the audit trail.
+async fn check_ip_allowlist(
+    state: &AppState,
+    parts: &Parts,
+    api_key: &ApiKey,
+    team: Option<&Team>,
+    client_ip: Option<IpAddr>,
+) -> Result<(), ApiError> {
+    let scope = if !ip_allowed(api_key.allowed_cidrs(), client_ip) {
+        Some("api_key")
+    } else {
+        team.filter(|t| !ip_allowed(t.allowed_cidrs(), client_ip))
+            .map(|_| "team")
+    };
+
+    if let Some(scope) = scope {
+        warn!(
+            api_key_id = %api_key.id(),
+            team_id = %api_key.team_id(),
+            client_ip = ?client_ip,
+            scope,
+            "API key used from a disallowed IP address"
         );
 
-        let api_key = state
-            .api_key_service
-            .validate(&api_key_value)
-            .await
-            .map_err(|e| ApiError::internal(e.to_string()))?
-            .ok_or_else(|| ApiError::unauthorized("Invalid API key"))?;
+        let mut log = AuditLog::new(
+            AuditActor::api_key(api_key.id().as_str(), api_key.name()),
+            "ip_allowlist_violation",
+            "api_keys",
+        )
+        .with_entity_id(api_key.id().as_str())
+        .with_changes(serde_json::json!({ "scope": scope }))
+        .with_status_code(403)
+        .with_request(parts.method.as_str(), original_path(parts));
+
+        if let Some(ip) = client_ip {
+            log = log.with_client_ip(ip.to_string());
+        }
 
-        if !api_key.is_valid() {
-            return Err(ApiError::unauthorized("API key is not active or has expired"));
+        if let Err(e) = state.audit_log_service.record(log).await {
+            warn!(error = %e, "Failed to record audit log");
         }
 
-        Ok(RequireApiKey(api_key))
+        return Err(ApiError::forbidden("Client IP address is not allowed"));
     }
+
+    Ok(())
+}
+
+/// Full request path, including any prefix stripped by nested routers
+fn original_path(parts: &Parts) -> &str {
+    parts
+        .extensions
+        .get::<OriginalUri>()
+        .map(|uri| uri.path())
+        .unwrap_or_else(|| parts.uri.path())
 }
 
 fn extract_api_key_from_headers(
@@ -0,0 +1,357 @@
+//! Pre-flight budget enforcement for API requests
+//!
+//! Handlers estimate the cost of a request (prompt tokens × model pricing)
+//! and check it against the budgets of the API key, its team and its
+//! organization before calling the provider. Requests that would exceed a
+//! budget are routed to the budget's fallback model when that fits, and
+//! rejected otherwise. The middleware places a [`BudgetSlot`] in the request
+//! extensions and turns the headroom recorded there into response headers.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use axum::{
+    body::Body,
+    http::{HeaderMap, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use tracing::{info, warn};
+
+use crate::api::state::AppState;
+use crate::api::types::ApiError;
+use crate::domain::api_key::ApiKey;
+use crate::domain::event::GatewayEvent;
+use crate::domain::llm::Message;
+use crate::domain::usage::{UsageRecord, UsageType};
+use crate::infrastructure::usage::{BudgetCheckResult, BudgetHeadroom, RecordUsageParams};
+
+/// Hard limit of the most constrained budget, in USD
+pub const BUDGET_LIMIT_HEADER: &str = "x-ratelimit-limit-budget";
+/// Remaining amount of the most constrained budget, in USD
+pub const BUDGET_REMAINING_HEADER: &str = "x-ratelimit-remaining-budget";
+/// Seconds until the most constrained budget resets
+pub const BUDGET_RESET_HEADER: &str = "x-ratelimit-reset-budget";
+/// ID of the most constrained budget
+pub const BUDGET_ID_HEADER: &str = "x-budget-id";
+/// Model originally requested when the request was routed to a fallback model
+pub const BUDGET_DEGRADED_FROM_HEADER: &str = "x-budget-degraded-from";
+
+/// Request extension carrying budget state from the handler to the middleware
+#[derive(Clone, Default)]
+pub struct BudgetSlot(Arc<Mutex<Option<BudgetHeaders>>>);
+
+#[derive(Debug, Clone)]
+struct BudgetHeaders {
+    headroom: Option<BudgetHeadroom>,
+    degraded_from: Option<String>,
+}
+
+impl BudgetSlot {
+    fn fill(&self, headers: BudgetHeaders) {
+        *self.0.lock().unwrap() = Some(headers);
+    }
+
+    fn take(&self) -> Option<BudgetHeaders> {
+        self.0.lock().unwrap().take()
+    }
+}
+
+/// Rough token estimate of prompt messages (~4 characters per token plus
+/// per-message overhead)
+pub fn estimate_prompt_tokens(messages: &[Message]) -> u32 {
+    messages
+        .iter()
+        .map(|m| m.content_text().map_or(0, |t| t.chars().count().div_ceil(4)) + 4)
+        .sum::<usize>()
+        .try_into()
+        .unwrap_or(u32::MAX)
+}
+
+/// Check the budgets of an API key before a request is executed.
+///
+/// With a model, the prompt cost is estimated from its pricing; without one
+/// only exhausted budgets block. Returns the fallback model the request must
+/// be routed to when it was degraded. Budget lookup failures are logged and
+/// let the request through.
+pub async fn enforce_budget(
+    state: &AppState,
+    slot: Option<&BudgetSlot>,
+    api_key: &ApiKey,
+    model_id: Option<&str>,
+    prompt_tokens: u32,
+) -> Result<Option<String>, ApiError> {
+    let Some(check) = check_budget(state, api_key, model_id, prompt_tokens).await else {
+        return Ok(None);
+    };
+
+    if check.allowed {
+        fill_slot(slot, &check, None);
+        return Ok(None);
+    }
+
+    if let (Some(requested), Some(fallback)) = (model_id, check.fallback_model_id.as_deref())
+        && fallback != requested
+        && let Some(fallback_check) =
+            check_budget(state, api_key, Some(fallback), prompt_tokens).await
+        && fallback_check.allowed
+    {
+        info!(
+            api_key_id = %api_key.id(),
+            model = %requested,
+            fallback_model = %fallback,
+            "Budget exceeded, routing request to fallback model"
+        );
+
+        fill_slot(slot, &fallback_check, Some(requested.to_string()));
+        return Ok(Some(fallback.to_string()));
+    }
+
+    fill_slot(slot, &check, None);
+
+    let exceeded: Vec<&str> = check.exceeded_budgets.iter().map(|id| id.as_str()).collect();
+    state.events.spawn_publish(GatewayEvent::budget_exceeded(
+        api_key.id().as_str(),
+        model_id,
+        &exceeded,
+    ));
+
+    Err(ApiError::rate_limited(format!(
+        "Budget exceeded: {}",
+        exceeded.join(", ")
+    ))
+    .with_code("budget_exceeded"))
+}
+
+/// Record a completed chat request: its usage record (with cost attribution
+/// tags), its actual cost against the applicable budgets and its tokens and
+/// cost against the quotas of the API key. Returns the usage record when it
+/// was stored.
+pub async fn record_request_usage(
+    state: &AppState,
+    api_key: &ApiKey,
+    model_id: &str,
+    input_tokens: u32,
+    output_tokens: u32,
+    latency_ms: u64,
+    tags: &HashMap<String, String>,
+) -> Option<UsageRecord> {
+    let cost = estimate_cost(state, model_id, input_tokens, output_tokens).await;
+
+    state
+        .api_key_service
+        .record_consumption(api_key, input_tokens.saturating_add(output_tokens), cost)
+        .await;
+
+    let params = RecordUsageParams::new(UsageType::ChatCompletion, api_key.id().as_str())
+        .with_team(api_key.team_id().as_str())
+        .with_model(model_id)
+        .with_tokens(input_tokens, output_tokens)
+        .with_latency(latency_ms)
+        .with_cost_micros(cost)
+        .with_tags(tags.clone());
+
+    let record = state
+        .usage_service
+        .record(params)
+        .await
+        .inspect_err(|e| warn!(api_key_id = %api_key.id(), error = %e, "Failed to record usage"))
+        .ok();
+
+    state.events.spawn_publish(GatewayEvent::request_completed(
+        api_key.id().as_str(),
+        api_key.team_id().as_str(),
+        model_id,
+        input_tokens,
+        output_tokens,
+        cost,
+        latency_ms,
+    ));
+
+    if cost == 0 {
+        return record;
+    }
+
+    match state
+        .budget_service
+        .record_usage_with_team(
+            api_key.id().as_str(),
+            Some(api_key.team_id().as_str()),
+            Some(model_id),
+            cost,
+        )
+        .await
+    {
+        Ok(alerts) => state.notifications.spawn_budget_alerts(alerts),
+        Err(e) => warn!(api_key_id = %api_key.id(), error = %e, "Failed to record budget usage"),
+    }
+
+    record
+}
+
+/// Middleware adding budget headers to responses of budget-checked requests
+pub async fn budget_headers_middleware(mut request: Request<Body>, next: Next) -> Response {
+    let slot = BudgetSlot::default();
+    request.extensions_mut().insert(slot.clone());
+
+    let mut response = next.run(request).await;
+
+    if let Some(headers) = slot.take() {
+        apply_budget_headers(response.headers_mut(), &headers, unix_now());
+    }
+
+    response
+}
+
+async fn check_budget(
+    state: &AppState,
+    api_key: &ApiKey,
+    model_id: Option<&str>,
+    prompt_tokens: u32,
+) -> Option<BudgetCheckResult> {
+    let estimated_cost = match model_id {
+        Some(model_id) => estimate_cost(state, model_id, prompt_tokens, 0).await,
+        None => 0,
+    };
+
+    state
+        .budget_service
+        .check_budget_with_team(
+            api_key.id().as_str(),
+            Some(api_key.team_id().as_str()),
+            model_id,
+            estimated_cost,
+        )
+        .await
+        .inspect_err(|e| warn!(api_key_id = %api_key.id(), error = %e, "Budget check failed"))
+        .ok()
+}
+
+/// Cost in micro-dollars, using the pricing of the gateway model ID or,
+/// failing that, of the provider model it is configured with
+pub async fn estimate_cost(
+    state: &AppState,
+    model_id: &str,
+    input_tokens: u32,
+    output_tokens: u32,
+) -> i64 {
+    if state.usage_service.get_pricing(model_id).is_some() {
+        return state
+            .usage_service
+            .calculate_cost(model_id, input_tokens, output_tokens);
+    }
+
+    match state.model_service.get(model_id).await {
+        Ok(Some(model)) => {
+            state
+                .usage_service
+                .calculate_cost(model.provider_model(), input_tokens, output_tokens)
+        }
+        _ => 0,
+    }
+}
+
+fn fill_slot(slot: Option<&BudgetSlot>, check: &BudgetCheckResult, degraded_from: Option<String>) {
+    if let Some(slot) = slot {
+        slot.fill(BudgetHeaders {
+            headroom: check.most_constrained.clone(),
+            degraded_from,
+        });
+    }
+}
+
+fn apply_budget_headers(headers: &mut HeaderMap, budget: &BudgetHeaders, now: u64) {
+    if let Some(headroom) = &budget.headroom {
+        let mut insert = |name: &'static str, value: String| {
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                headers.insert(name, value);
+            }
+        };
+
+        insert(BUDGET_LIMIT_HEADER, format_usd(headroom.limit_micros));
+        insert(
+            BUDGET_REMAINING_HEADER,
+            format_usd(headroom.remaining_micros.max(0)),
+        );
+        insert(BUDGET_ID_HEADER, headroom.budget_id.to_string());
+
+        if let Some(resets_at) = headroom.resets_at {
+            insert(BUDGET_RESET_HEADER, resets_at.saturating_sub(now).to_string());
+        }
+    }
+
+    if let Some(model) = &budget.degraded_from
+        && let Ok(value) = HeaderValue::from_str(model)
+    {
+        headers.insert(BUDGET_DEGRADED_FROM_HEADER, value);
+    }
+}
+
+fn format_usd(micros: i64) -> String {
+    format!("{:.6}", micros as f64 / 1_000_000.0)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::usage::BudgetId;
+
+    #[test]
+    fn test_estimate_prompt_tokens() {
+        let messages = vec![Message::system("a".repeat(40)), Message::user("hello")];
+
+        // 10 + 4 for the system message, 2 + 4 for the user message
+        assert_eq!(estimate_prompt_tokens(&messages), 20);
+        assert_eq!(estimate_prompt_tokens(&[]), 0);
+    }
+
+    #[test]
+    fn test_apply_budget_headers() {
+        let mut headers = HeaderMap::new();
+        let budget = BudgetHeaders {
+            headroom: Some(BudgetHeadroom {
+                budget_id: BudgetId::new("team-monthly"),
+                limit_micros: 100_000_000,
+                remaining_micros: -500,
+                resets_at: Some(1_000),
+            }),
+            degraded_from: Some("gpt-4o".to_string()),
+        };
+
+        apply_budget_headers(&mut headers, &budget, 400);
+
+        assert_eq!(headers[BUDGET_LIMIT_HEADER], "100.000000");
+        assert_eq!(headers[BUDGET_REMAINING_HEADER], "0.000000");
+        assert_eq!(headers[BUDGET_RESET_HEADER], "600");
+        assert_eq!(headers[BUDGET_ID_HEADER], "team-monthly");
+        assert_eq!(headers[BUDGET_DEGRADED_FROM_HEADER], "gpt-4o");
+    }
+
+    #[test]
+    fn test_apply_budget_headers_lifetime_budget() {
+        let mut headers = HeaderMap::new();
+        let budget = BudgetHeaders {
+            headroom: Some(BudgetHeadroom {
+                budget_id: BudgetId::new("lifetime"),
+                limit_micros: 1_500_000,
+                remaining_micros: 250_000,
+                resets_at: None,
+            }),
+            degraded_from: None,
+        };
+
+        apply_budget_headers(&mut headers, &budget, 0);
+
+        assert_eq!(headers[BUDGET_REMAINING_HEADER], "0.250000");
+        assert!(!headers.contains_key(BUDGET_RESET_HEADER));
+        assert!(!headers.contains_key(BUDGET_DEGRADED_FROM_HEADER));
+    }
+}
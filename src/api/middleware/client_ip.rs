@@ -0,0 +1,182 @@
+//! Client IP address resolution
+//!
+//! The real client IP is the TCP peer address unless the peer is a trusted
+//! proxy, in which case it is taken from the configured forwarding headers.
+//! When the peer address is unknown (e.g. the server was started without
+//! connection info), forwarding headers are honored as-is.
+
+use std::net::{IpAddr, SocketAddr};
+
+use axum::extract::ConnectInfo;
+use axum::http::{request::Parts, HeaderMap};
+
+use crate::domain::network::IpNetwork;
+
+/// Default headers inspected for the forwarded client IP, in order
+pub const DEFAULT_CLIENT_IP_HEADERS: [&str; 2] = ["x-forwarded-for", "x-real-ip"];
+
+/// Resolves the client IP address of a request
+#[derive(Debug, Clone)]
+pub struct ClientIpResolver {
+    trusted_proxies: Vec<IpNetwork>,
+    headers: Vec<String>,
+}
+
+impl Default for ClientIpResolver {
+    fn default() -> Self {
+        Self::new(
+            Vec::new(),
+            DEFAULT_CLIENT_IP_HEADERS.iter().map(|h| h.to_string()).collect(),
+        )
+    }
+}
+
+impl ClientIpResolver {
+    /// Create a resolver trusting the given proxy networks and headers
+    pub fn new(trusted_proxies: Vec<IpNetwork>, headers: Vec<String>) -> Self {
+        Self {
+            trusted_proxies,
+            headers: headers.into_iter().map(|h| h.to_ascii_lowercase()).collect(),
+        }
+    }
+
+    fn is_trusted(&self, ip: IpAddr) -> bool {
+        self.trusted_proxies.iter().any(|network| network.contains(ip))
+    }
+
+    /// Resolve the client IP from the peer address and forwarding headers
+    pub fn resolve(&self, headers: &HeaderMap, peer: Option<IpAddr>) -> Option<IpAddr> {
+        if let Some(peer) = peer
+            && !self.is_trusted(peer)
+        {
+            return Some(peer);
+        }
+
+        self.headers
+            .iter()
+            .find_map(|name| self.forwarded_ip(headers, name))
+            .or(peer)
+    }
+
+    /// Resolve the client IP of a request
+    pub fn resolve_parts(&self, parts: &Parts) -> Option<IpAddr> {
+        self.resolve(&parts.headers, peer_addr(parts))
+    }
+
+    /// Walk a (possibly comma-separated) header from right to left, skipping
+    /// trusted proxies, and return the first untrusted address
+    fn forwarded_ip(&self, headers: &HeaderMap, name: &str) -> Option<IpAddr> {
+        let addresses: Vec<IpAddr> = headers
+            .get_all(name)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .filter_map(|v| v.trim().parse().ok())
+            .collect();
+
+        addresses
+            .iter()
+            .rev()
+            .find(|ip| !self.is_trusted(**ip))
+            .or_else(|| addresses.first())
+            .copied()
+    }
+}
+
+/// TCP peer address of a request, if the server provides connection info
+pub fn peer_addr(parts: &Parts) -> Option<IpAddr> {
+    parts
+        .extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    fn trusting(networks: &[&str]) -> ClientIpResolver {
+        ClientIpResolver::new(
+            networks.iter().map(|n| n.parse().unwrap()).collect(),
+            DEFAULT_CLIENT_IP_HEADERS.iter().map(|h| h.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn test_forwarded_for_first_entry() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "203.0.113.7, 10.0.0.1".parse().unwrap());
+        headers.insert("x-real-ip", "10.0.0.2".parse().unwrap());
+
+        let resolver = trusting(&["10.0.0.0/8"]);
+        assert_eq!(resolver.resolve(&headers, None), Some(ip("203.0.113.7")));
+    }
+
+    #[test]
+    fn test_real_ip_fallback() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-real-ip", " 198.51.100.4 ".parse().unwrap());
+
+        let resolver = ClientIpResolver::default();
+        assert_eq!(resolver.resolve(&headers, None), Some(ip("198.51.100.4")));
+    }
+
+    #[test]
+    fn test_missing_headers() {
+        let resolver = ClientIpResolver::default();
+        assert!(resolver.resolve(&HeaderMap::new(), None).is_none());
+        assert_eq!(
+            resolver.resolve(&HeaderMap::new(), Some(ip("192.0.2.1"))),
+            Some(ip("192.0.2.1"))
+        );
+    }
+
+    #[test]
+    fn test_untrusted_peer_ignores_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "203.0.113.7".parse().unwrap());
+
+        let resolver = ClientIpResolver::default();
+        assert_eq!(
+            resolver.resolve(&headers, Some(ip("192.0.2.1"))),
+            Some(ip("192.0.2.1"))
+        );
+    }
+
+    #[test]
+    fn test_trusted_peer_uses_rightmost_untrusted_entry() {
+        let mut headers = HeaderMap::new();
+        // A client can prepend arbitrary entries; only the ones appended by
+        // trusted proxies are reliable
+        headers.insert(
+            "x-forwarded-for",
+            "1.2.3.4, 203.0.113.7, 10.0.0.5".parse().unwrap(),
+        );
+
+        let resolver = trusting(&["10.0.0.0/8"]);
+        assert_eq!(
+            resolver.resolve(&headers, Some(ip("10.0.0.1"))),
+            Some(ip("203.0.113.7"))
+        );
+    }
+
+    #[test]
+    fn test_custom_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert("cf-connecting-ip", "198.51.100.9".parse().unwrap());
+        headers.insert("x-forwarded-for", "203.0.113.7".parse().unwrap());
+
+        let resolver = ClientIpResolver::new(
+            vec!["10.0.0.0/8".parse().unwrap()],
+            vec!["CF-Connecting-IP".to_string()],
+        );
+        assert_eq!(
+            resolver.resolve(&headers, Some(ip("10.0.0.1"))),
+            Some(ip("198.51.100.9"))
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! Per-team concurrency limiting for API requests
+//!
+//! The middleware places a [`ConcurrencySlot`] in the request extensions.
+//! `RequireApiKey` acquires an in-flight permit for the team of the key and
+//! stores it in the slot. The permit is held until the response body has been
+//! sent, so streamed completions count as in flight until they finish.
+
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    body::{Body, HttpBody},
+    http::{request::Parts, Request},
+    middleware::Next,
+    response::Response,
+};
+use futures::StreamExt;
+
+use crate::api::state::AppState;
+use crate::api::types::ApiError;
+use crate::domain::team::{QuotaResource, Team, TeamId};
+use crate::infrastructure::team::ConcurrencyPermit;
+
+/// Request extension shared between the concurrency middleware and the API key extractor
+#[derive(Clone, Default)]
+pub struct ConcurrencySlot(Arc<Mutex<Option<ConcurrencyPermit>>>);
+
+impl ConcurrencySlot {
+    fn fill(&self, permit: ConcurrencyPermit) {
+        *self.0.lock().unwrap() = Some(permit);
+    }
+
+    fn take(&self) -> Option<ConcurrencyPermit> {
+        self.0.lock().unwrap().take()
+    }
+}
+
+/// Acquire an in-flight permit for a team, if the request is concurrency limited
+pub fn acquire_team_permit(
+    parts: &Parts,
+    state: &AppState,
+    team_id: &TeamId,
+    team: Option<&Team>,
+) -> Result<(), ApiError> {
+    let Some(slot) = parts.extensions.get::<ConcurrencySlot>() else {
+        return Ok(());
+    };
+
+    let limit = team.and_then(|t| t.quota().limit(QuotaResource::ConcurrentRequests));
+
+    match state.team_concurrency.try_acquire(team_id, limit) {
+        Some(permit) => {
+            slot.fill(permit);
+            Ok(())
+        }
+        None => Err(ApiError::rate_limited(format!(
+            "Team '{}' has reached its limit of {} concurrent requests",
+            team_id,
+            limit.unwrap_or_default()
+        ))
+        .with_code("concurrency_limit_exceeded")),
+    }
+}
+
+/// Middleware holding the team permit of a request until its response completes
+pub async fn concurrency_middleware(mut request: Request<Body>, next: Next) -> Response {
+    let slot = ConcurrencySlot::default();
+    request.extensions_mut().insert(slot.clone());
+
+    let response = next.run(request).await;
+
+    let Some(permit) = slot.take() else {
+        return response;
+    };
+
+    // Buffered bodies are complete; only streams need to keep the permit
+    if response.body().size_hint().exact().is_some() {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let stream = body.into_data_stream().map(move |chunk| {
+        let _held = &permit;
+        chunk
+    });
+
+    Response::from_parts(parts, Body::from_stream(stream))
+}
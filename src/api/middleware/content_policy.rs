@@ -0,0 +1,111 @@
+//! Content policy enforcement on chat completion input and output
+//!
+//! Handlers evaluate the policies of the API key's team on the user messages
+//! before calling the provider and on its answer afterwards. Violations are
+//! counted in `llm_content_policy_violations_total` and rejected with a
+//! `content_policy_violation` error listing every broken rule.
+
+use serde_json::json;
+use tracing::warn;
+
+use crate::api::state::AppState;
+use crate::api::types::ApiError;
+use crate::domain::api_key::ApiKey;
+use crate::domain::guardrail::{PolicyStage, PolicyViolation};
+use crate::domain::llm::{Message, MessageRole};
+use crate::infrastructure::observability::record_policy_violation;
+
+/// Text of the user messages the input stage is evaluated on
+pub fn policy_input_text(messages: &[Message]) -> String {
+    messages
+        .iter()
+        .filter(|m| m.role == MessageRole::User)
+        .filter_map(Message::content_text)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Evaluate the content policies of the API key's team at a stage, failing
+/// with a `content_policy_violation` error when any rule is broken
+pub async fn enforce_content_policies(
+    state: &AppState,
+    api_key: &ApiKey,
+    stage: PolicyStage,
+    text: &str,
+) -> Result<(), ApiError> {
+    let violations = state
+        .content_policy_service
+        .evaluate(api_key.team_id().as_str(), stage, text)
+        .await?;
+
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    for violation in &violations {
+        record_policy_violation(
+            &violation.policy_id,
+            stage.as_str(),
+            violation.rule.as_str(),
+        );
+    }
+    warn!(
+        api_key_id = %api_key.id(),
+        team_id = %api_key.team_id(),
+        stage = stage.as_str(),
+        violations = violations.len(),
+        "Content policy violated"
+    );
+
+    Err(content_policy_error(stage, &violations))
+}
+
+/// Error returned for input or output breaking content policies, with the
+/// violations in `details`
+pub fn content_policy_error(stage: PolicyStage, violations: &[PolicyViolation]) -> ApiError {
+    let (message, param) = match stage {
+        PolicyStage::Input => ("Request violates content policy", "messages"),
+        PolicyStage::Output => ("Response blocked by content policy", "output"),
+    };
+    let summary = violations
+        .iter()
+        .map(|v| v.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ");
+
+    ApiError::bad_request(format!("{}: {}", message, summary))
+        .with_param(param)
+        .with_code("content_policy_violation")
+        .with_details(json!({ "violations": violations }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::guardrail::PolicyRule;
+
+    #[test]
+    fn test_content_policy_error() {
+        let violations = vec![PolicyViolation {
+            policy_id: "support".to_string(),
+            rule: PolicyRule::BannedPhrase,
+            stage: PolicyStage::Output,
+            message: "Contains banned phrase 'acme'".to_string(),
+        }];
+
+        let error = content_policy_error(PolicyStage::Output, &violations);
+        let json = serde_json::to_value(&error.response).unwrap();
+
+        assert_eq!(error.status, axum::http::StatusCode::BAD_REQUEST);
+        assert_eq!(json["error"]["code"], "content_policy_violation");
+        assert_eq!(json["error"]["param"], "output");
+        assert_eq!(
+            json["error"]["details"]["violations"][0]["rule"],
+            "banned_phrase"
+        );
+        assert_eq!(
+            json["error"]["details"]["violations"][0]["policy_id"],
+            "support"
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Inbound prompt-injection and jailbreak detection for chat requests
+//!
+//! Handlers run the user messages of a request through the
+//! [`InjectionGuard`](crate::domain::guardrail::InjectionGuard) before calling
+//! the provider. The heuristic score is combined with the optional classifier
+//! model's; detections are counted in `llm_injection_detections_total` and
+//! returned so the handler can record them in the execution log and, for the
+//! `block` action, reject the request.
+
+use tracing::{debug, warn};
+
+use crate::api::state::AppState;
+use crate::api::types::ApiError;
+use crate::domain::api_key::ApiKey;
+use crate::domain::guardrail::{
+    INJECTION_CLASSIFIER_PROMPT, InjectionAction, InjectionDetection, parse_classifier_score,
+    sanitize_injection, scan_injection,
+};
+use crate::domain::llm::{LlmRequest, Message, MessageRole};
+use crate::infrastructure::observability::record_injection_detection;
+
+/// Largest completion the classifier model may answer with
+const CLASSIFIER_MAX_TOKENS: u32 = 8;
+
+/// Scan the user messages of a request for prompt injection with the action
+/// of the API key. Sanitizes the messages in place for the `sanitize` action
+/// and returns the detection, if any.
+pub async fn enforce_injection_guard(
+    state: &AppState,
+    api_key: &ApiKey,
+    model_id: &str,
+    messages: &mut [Message],
+) -> Option<InjectionDetection> {
+    let guard = &state.injection_guard;
+    let action = guard.action_for(api_key.injection_action());
+
+    if action == InjectionAction::Off {
+        return None;
+    }
+
+    let text = messages
+        .iter()
+        .filter(|m| m.role == MessageRole::User)
+        .filter_map(Message::content_text)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if text.is_empty() {
+        return None;
+    }
+
+    let scan = scan_injection(&text);
+    let classifier_score = match &guard.classifier_model_id {
+        Some(classifier_model_id) => classify(state, classifier_model_id, &text).await,
+        None => None,
+    };
+    let score = classifier_score.map_or(scan.score, |c| c.max(scan.score));
+
+    if !guard.is_injection(score) {
+        return None;
+    }
+
+    if action == InjectionAction::Sanitize {
+        for message in messages.iter_mut().filter(|m| m.role == MessageRole::User) {
+            message.map_text(sanitize_injection);
+        }
+    }
+
+    record_injection_detection(model_id, action.as_str());
+    warn!(
+        api_key_id = %api_key.id(),
+        model = %model_id,
+        score,
+        rules = ?scan.rules,
+        action = action.as_str(),
+        "Possible prompt injection detected"
+    );
+
+    Some(InjectionDetection {
+        action,
+        score,
+        heuristic_score: scan.score,
+        classifier_score,
+        rules: scan.rules,
+    })
+}
+
+/// Error returned for a request blocked by the injection guard
+pub fn injection_blocked_error() -> ApiError {
+    ApiError::bad_request("Request blocked: possible prompt injection detected")
+        .with_param("messages")
+        .with_code("prompt_injection_detected")
+}
+
+/// Ask the classifier model to score the text; failures are logged and leave
+/// the heuristic score alone
+async fn classify(state: &AppState, model_id: &str, text: &str) -> Option<f64> {
+    let model = match state.model_service.get(model_id).await {
+        Ok(Some(model)) if model.is_enabled() => model,
+        Ok(_) => {
+            warn!(model = %model_id, "Injection classifier model not found or disabled");
+            return None;
+        }
+        Err(e) => {
+            warn!(model = %model_id, error = %e, "Failed to load injection classifier model");
+            return None;
+        }
+    };
+
+    let credential = match state.credential_service.get(model.credential_id()).await {
+        Ok(Some(credential)) => credential,
+        Ok(None) => {
+            warn!(model = %model_id, "Credential of injection classifier model not found");
+            return None;
+        }
+        Err(e) => {
+            warn!(model = %model_id, error = %e, "Failed to load injection classifier credential");
+            return None;
+        }
+    };
+
+    let provider = match state
+        .provider_router
+        .get_provider(&model, &credential.to_credential())
+        .await
+    {
+        Ok(provider) => provider,
+        Err(e) => {
+            warn!(model = %model_id, error = %e, "Failed to resolve injection classifier provider");
+            return None;
+        }
+    };
+
+    let request = LlmRequest::builder()
+        .messages(vec![
+            Message::system(INJECTION_CLASSIFIER_PROMPT),
+            Message::user(text),
+        ])
+        .temperature(0.0)
+        .max_tokens(CLASSIFIER_MAX_TOKENS)
+        .build();
+
+    match provider.chat(model.provider_model(), request).await {
+        Ok(response) => {
+            let score = response.content().and_then(parse_classifier_score);
+            debug!(model = %model_id, score = ?score, "Injection classifier answered");
+            score
+        }
+        Err(e) => {
+            warn!(model = %model_id, error = %e, "Injection classifier request failed");
+            None
+        }
+    }
+}
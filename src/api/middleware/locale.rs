@@ -0,0 +1,86 @@
+//! Locale of API requests, selecting prompt variants
+//!
+//! The `x-pmp-locale` header takes precedence over `Accept-Language`; both
+//! accept a weighted list (`pt-BR, es;q=0.8`). A `locale` field in the
+//! request body, or in the input of a workflow execution, overrides either
+//! header.
+
+use axum::{
+    extract::FromRequestParts,
+    http::{header::ACCEPT_LANGUAGE, request::Parts},
+};
+use serde_json::Value;
+
+use crate::api::state::AppState;
+use crate::api::types::ApiError;
+
+/// Header selecting the locale of rendered prompts
+pub const LOCALE_HEADER: &str = "x-pmp-locale";
+
+/// Extractor for the locales accepted by the caller
+#[derive(Debug, Clone, Default)]
+pub struct RequestLocale(pub Option<String>);
+
+impl RequestLocale {
+    /// Locales of the request body, falling back to those of the headers
+    pub fn or_field(self, field: Option<&str>) -> Option<String> {
+        field.map(String::from).or(self.0)
+    }
+
+    /// Set the `locale` of a workflow input object that has none
+    pub fn fill_input(self, input: &mut Value) {
+        if let (Some(locale), Some(input)) = (self.0, input.as_object_mut()) {
+            input.entry("locale").or_insert(Value::String(locale));
+        }
+    }
+}
+
+impl FromRequestParts<AppState> for RequestLocale {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        if let Some(value) = parts.headers.get(LOCALE_HEADER) {
+            let value = value
+                .to_str()
+                .map_err(|_| ApiError::bad_request("x-pmp-locale header must be valid ASCII"))?;
+            return Ok(Self(Some(value.to_string())));
+        }
+
+        // Browsers send Accept-Language unprompted, so a malformed one is ignored
+        let accept_language = parts
+            .headers
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from);
+
+        Ok(Self(accept_language))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_body_locale_overrides_header() {
+        let header = RequestLocale(Some("es".to_string()));
+
+        assert_eq!(header.clone().or_field(Some("pt-BR")).as_deref(), Some("pt-BR"));
+        assert_eq!(header.or_field(None).as_deref(), Some("es"));
+    }
+
+    #[test]
+    fn test_fill_workflow_input() {
+        let mut input = json!({"question": "hi"});
+        RequestLocale(Some("es".to_string())).fill_input(&mut input);
+        assert_eq!(input["locale"], "es");
+
+        let mut input = json!({"locale": "fr"});
+        RequestLocale(Some("es".to_string())).fill_input(&mut input);
+        assert_eq!(input["locale"], "fr");
+    }
+}
@@ -10,13 +10,17 @@ use axum::{
     response::Response,
 };
 
-use crate::infrastructure::observability::record_http_request;
+use crate::infrastructure::observability::{
+    live_stats, record_http_request, InFlightRequestGuard,
+};
 
 /// Middleware to record HTTP request metrics
 pub async fn metrics_middleware(request: Request<Body>, next: Next) -> Response {
     let start = Instant::now();
     let method = request.method().clone();
     let path = extract_path(&request);
+    let _active = live_stats().enter_request();
+    let _in_flight = InFlightRequestGuard::enter();
 
     let response = next.run(request).await;
 
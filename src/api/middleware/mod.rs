@@ -1,15 +1,60 @@
 //! API middleware components
 
 pub mod admin_auth;
+pub mod audit;
 pub mod auth;
+pub mod budget;
+pub mod client_ip;
+pub mod concurrency;
+pub mod content_policy;
+pub mod injection;
+pub mod locale;
 pub mod logging;
 pub mod metrics;
+pub mod parameter_policy;
+pub mod quota;
+pub mod request_trace;
+pub mod residency;
+pub mod retention;
+pub mod secret_scan;
 pub mod security;
+pub mod team_defaults;
+pub mod trace_context;
+pub mod transform;
+pub mod usage_tags;
 pub mod user_auth;
 
 pub use admin_auth::{AdminAuth, RequireAdmin};
+pub use audit::{attach_audit_actor, audit_middleware, redact_sensitive_fields, AuditSlot};
 pub use auth::RequireApiKey;
+pub use budget::{
+    budget_headers_middleware, enforce_budget, estimate_cost, estimate_prompt_tokens,
+    record_request_usage, BudgetSlot,
+};
+pub use client_ip::{peer_addr, ClientIpResolver};
+pub use concurrency::{acquire_team_permit, concurrency_middleware, ConcurrencySlot};
+pub use content_policy::{content_policy_error, enforce_content_policies, policy_input_text};
+pub use injection::{enforce_injection_guard, injection_blocked_error};
+pub use locale::{RequestLocale, LOCALE_HEADER};
 pub use logging::{logging_middleware, redact_json_sensitive_fields, truncate_for_log};
 pub use metrics::metrics_middleware;
-pub use security::{security_headers_middleware, validate_content_length, validate_request_security};
+pub use parameter_policy::{enforce_parameter_policy, parameter_policy_error};
+pub use quota::{enforce_quota, quota_headers_middleware, QuotaSlot};
+pub use request_trace::{
+    register_request_trace, request_trace_middleware, RequestTracing, REQUEST_ID_HEADER,
+};
+pub use residency::{enforce_data_residency, region_not_allowed_error};
+pub use retention::{no_log_async_error, zero_retention};
+pub use secret_scan::{
+    enforce_secret_scan, record_secret_leaks, secret_leak_error, secret_scanner,
+    secret_stream_filter,
+};
+pub use security::{
+    cors_layer, security_headers_middleware, validate_content_length, validate_request_security,
+    SecurityPolicy,
+};
+pub use team_defaults::{apply_team_request_defaults, team_request_defaults};
+pub use trace_context::{make_request_span, trace_context_middleware};
+pub use transform::{apply_transform_rules, transform_headers_middleware, TransformSlot};
+pub use usage_tags::{UsageTags, USAGE_TAGS_HEADER};
 pub use user_auth::RequireUser;
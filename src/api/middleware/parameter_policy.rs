@@ -0,0 +1,76 @@
+//! API key parameter policies
+//!
+//! Keys handed to semi-trusted tools may carry limits on `max_tokens` and
+//! temperature, or only allow streamed requests (see [`ParameterPolicy`]).
+//! Handlers check the parameters a client sent before team defaults are
+//! applied; requests outside the policy are rejected with
+//! `parameter_policy_violation`.
+
+use crate::api::types::{ApiError, ChatCompletionRequest};
+use crate::domain::api_key::{ApiKey, ParameterPolicy, ParameterPolicyViolation};
+
+/// Check a chat completion request against the parameter policy of its key
+pub fn enforce_parameter_policy(
+    api_key: &ApiKey,
+    request: &ChatCompletionRequest,
+) -> Result<(), ApiError> {
+    check_parameter_policy(&api_key.permissions().parameters, request)
+}
+
+fn check_parameter_policy(
+    policy: &ParameterPolicy,
+    request: &ChatCompletionRequest,
+) -> Result<(), ApiError> {
+    policy
+        .check(request.max_tokens, request.temperature, request.stream)
+        .map_err(parameter_policy_error)
+}
+
+/// Error returned for requests outside the parameter policy of their key
+pub fn parameter_policy_error(violation: ParameterPolicyViolation) -> ApiError {
+    ApiError::bad_request(violation.to_string())
+        .with_param(violation.param)
+        .with_code("parameter_policy_violation")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(stream: bool, max_tokens: Option<u32>) -> ChatCompletionRequest {
+        serde_json::from_value(serde_json::json!({
+            "model": "gpt-4o",
+            "messages": [{"role": "user", "content": "Hi"}],
+            "stream": stream,
+            "max_tokens": max_tokens,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_check_parameter_policy() {
+        let policy = ParameterPolicy {
+            max_tokens: Some(2000),
+            streaming_only: true,
+            ..Default::default()
+        };
+
+        assert!(check_parameter_policy(&policy, &request(true, Some(2000))).is_ok());
+
+        let err = check_parameter_policy(&policy, &request(true, Some(4000))).unwrap_err();
+        assert_eq!(err.response.error.param.as_deref(), Some("max_tokens"));
+        assert_eq!(
+            err.response.error.code.as_deref(),
+            Some("parameter_policy_violation")
+        );
+
+        let err = check_parameter_policy(&policy, &request(false, None)).unwrap_err();
+        assert_eq!(err.response.error.param.as_deref(), Some("stream"));
+    }
+
+    #[test]
+    fn test_empty_policy_allows_any_request() {
+        let policy = ParameterPolicy::new();
+        assert!(check_parameter_policy(&policy, &request(false, Some(100_000))).is_ok());
+    }
+}
@@ -0,0 +1,194 @@
+//! Per-key rate limits and usage quotas for API requests
+//!
+//! The middleware places a [`QuotaSlot`] in the request extensions.
+//! `RequireApiKey` checks the request against the rate limits and quotas of
+//! the key in the shared rate limiter and stores the resulting consumption in
+//! the slot, which the middleware turns into response headers. Tokens and
+//! cost are charged once the request has completed.
+
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    body::Body,
+    http::{request::Parts, HeaderMap, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::api::state::AppState;
+use crate::api::types::ApiError;
+use crate::domain::api_key::ApiKey;
+use crate::infrastructure::api_key::{LimitType, QuotaWindow};
+
+/// Request limit of the most constrained request window
+pub const REQUESTS_LIMIT_HEADER: &str = "x-ratelimit-limit-requests";
+/// Requests remaining in the most constrained request window
+pub const REQUESTS_REMAINING_HEADER: &str = "x-ratelimit-remaining-requests";
+/// Seconds until the most constrained request window frees up
+pub const REQUESTS_RESET_HEADER: &str = "x-ratelimit-reset-requests";
+/// Token limit of the most constrained token window
+pub const TOKENS_LIMIT_HEADER: &str = "x-ratelimit-limit-tokens";
+/// Tokens remaining in the most constrained token window
+pub const TOKENS_REMAINING_HEADER: &str = "x-ratelimit-remaining-tokens";
+/// Seconds until the most constrained token window frees up
+pub const TOKENS_RESET_HEADER: &str = "x-ratelimit-reset-tokens";
+/// Monthly cost quota, in USD
+pub const COST_LIMIT_HEADER: &str = "x-ratelimit-limit-cost";
+/// Cost remaining in the monthly quota, in USD
+pub const COST_REMAINING_HEADER: &str = "x-ratelimit-remaining-cost";
+/// Seconds until the monthly cost window frees up
+pub const COST_RESET_HEADER: &str = "x-ratelimit-reset-cost";
+
+/// Request extension carrying quota consumption from the API key extractor to the middleware
+#[derive(Clone, Default)]
+pub struct QuotaSlot(Arc<Mutex<Option<Vec<QuotaWindow>>>>);
+
+impl QuotaSlot {
+    fn fill(&self, usage: Vec<QuotaWindow>) {
+        *self.0.lock().unwrap() = Some(usage);
+    }
+
+    fn take(&self) -> Option<Vec<QuotaWindow>> {
+        self.0.lock().unwrap().take()
+    }
+}
+
+/// Check the rate limits and quotas of an API key, if the request is quota limited
+pub async fn enforce_quota(
+    parts: &Parts,
+    state: &AppState,
+    api_key: &ApiKey,
+) -> Result<(), ApiError> {
+    let Some(slot) = parts.extensions.get::<QuotaSlot>() else {
+        return Ok(());
+    };
+
+    if !api_key.rate_limits().is_enabled() {
+        return Ok(());
+    }
+
+    let result = state.api_key_service.check_rate_limit(api_key).await;
+    slot.fill(state.api_key_service.quota_usage(api_key).await);
+
+    let Some(limit_type) = result.limit_type.filter(|_| !result.allowed) else {
+        return Ok(());
+    };
+
+    let code = match limit_type {
+        LimitType::PerMinute | LimitType::PerHour | LimitType::PerDay => "rate_limit_exceeded",
+        _ => "quota_exceeded",
+    };
+
+    Err(ApiError::rate_limited(format!(
+        "API key '{}' exceeded its {} limit, retry in {} seconds",
+        api_key.id(),
+        limit_type,
+        result.reset_in_seconds
+    ))
+    .with_code(code))
+}
+
+/// Middleware adding rate limit and quota headers to responses of quota-limited requests
+pub async fn quota_headers_middleware(mut request: Request<Body>, next: Next) -> Response {
+    let slot = QuotaSlot::default();
+    request.extensions_mut().insert(slot.clone());
+
+    let mut response = next.run(request).await;
+
+    if let Some(usage) = slot.take() {
+        apply_quota_headers(response.headers_mut(), &usage);
+    }
+
+    response
+}
+
+fn apply_quota_headers(headers: &mut HeaderMap, usage: &[QuotaWindow]) {
+    let groups: [(&[LimitType], [&'static str; 3]); 3] = [
+        (
+            &[LimitType::PerMinute, LimitType::PerHour, LimitType::PerDay],
+            [REQUESTS_LIMIT_HEADER, REQUESTS_REMAINING_HEADER, REQUESTS_RESET_HEADER],
+        ),
+        (
+            &[LimitType::TokensPerMinute, LimitType::TokensPerDay],
+            [TOKENS_LIMIT_HEADER, TOKENS_REMAINING_HEADER, TOKENS_RESET_HEADER],
+        ),
+        (
+            &[LimitType::CostPerMonth],
+            [COST_LIMIT_HEADER, COST_REMAINING_HEADER, COST_RESET_HEADER],
+        ),
+    ];
+
+    for (types, [limit, remaining, reset]) in groups {
+        let Some(window) = usage
+            .iter()
+            .filter(|w| types.contains(&w.limit_type))
+            .min_by_key(|w| w.remaining())
+        else {
+            continue;
+        };
+
+        let format = |value: u64| match window.limit_type {
+            LimitType::CostPerMonth => format!("{:.6}", value as f64 / 1_000_000.0),
+            _ => value.to_string(),
+        };
+
+        let values = [
+            (limit, format(window.limit)),
+            (remaining, format(window.remaining())),
+            (reset, window.reset_in_seconds.to_string()),
+        ];
+
+        for (name, value) in values {
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                headers.insert(name, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(limit_type: LimitType, limit: u64, used: u64) -> QuotaWindow {
+        QuotaWindow {
+            limit_type,
+            limit,
+            used,
+            reset_in_seconds: 30,
+        }
+    }
+
+    #[test]
+    fn test_apply_quota_headers_most_constrained() {
+        let mut headers = HeaderMap::new();
+        let usage = vec![
+            window(LimitType::PerMinute, 60, 10),
+            window(LimitType::PerHour, 1000, 995),
+            window(LimitType::PerDay, 10000, 995),
+            window(LimitType::TokensPerDay, 100_000, 25_000),
+            window(LimitType::CostPerMonth, 10_000_000, 2_500_000),
+        ];
+
+        apply_quota_headers(&mut headers, &usage);
+
+        assert_eq!(headers[REQUESTS_LIMIT_HEADER], "1000");
+        assert_eq!(headers[REQUESTS_REMAINING_HEADER], "5");
+        assert_eq!(headers[REQUESTS_RESET_HEADER], "30");
+        assert_eq!(headers[TOKENS_LIMIT_HEADER], "100000");
+        assert_eq!(headers[TOKENS_REMAINING_HEADER], "75000");
+        assert_eq!(headers[COST_LIMIT_HEADER], "10.000000");
+        assert_eq!(headers[COST_REMAINING_HEADER], "7.500000");
+    }
+
+    #[test]
+    fn test_apply_quota_headers_without_token_quotas() {
+        let mut headers = HeaderMap::new();
+
+        apply_quota_headers(&mut headers, &[window(LimitType::PerMinute, 60, 60)]);
+
+        assert_eq!(headers[REQUESTS_REMAINING_HEADER], "0");
+        assert!(!headers.contains_key(TOKENS_LIMIT_HEADER));
+        assert!(!headers.contains_key(COST_LIMIT_HEADER));
+    }
+}
@@ -0,0 +1,87 @@
+//! Per-request traces of v1 API requests
+//!
+//! The middleware gives every v1 request an ID, returned in the
+//! `x-pmp-request-id` header, and places a [`RequestTracer`] in the request
+//! extensions. `RequireApiKey` stores the trace once the key is validated, so
+//! only authenticated requests are kept, and handlers record their routing,
+//! guardrail, provider and usage decisions on it. `GET /admin/requests/{id}`
+//! returns it with the execution logs and usage records of the request.
+
+use std::time::Instant;
+
+use axum::{
+    body::Body,
+    extract::{FromRequestParts, OriginalUri},
+    http::{request::Parts, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+use crate::api::state::AppState;
+use crate::api::types::ApiError;
+use crate::infrastructure::request_trace::RequestTracer;
+
+/// Response header carrying the ID of the request
+pub const REQUEST_ID_HEADER: &str = "x-pmp-request-id";
+
+/// Middleware assigning a request ID and tracer to every request
+pub async fn request_trace_middleware(mut request: Request<Body>, next: Next) -> Response {
+    let start = Instant::now();
+    let path = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map_or_else(|| request.uri().path().to_string(), |uri| uri.path().to_string());
+    let tracer = RequestTracer::new(
+        Uuid::new_v4().to_string(),
+        request.method().as_str(),
+        path,
+    );
+    request.extensions_mut().insert(tracer.clone());
+
+    let mut response = next.run(request).await;
+
+    tracer.finish(response.status().as_u16(), start.elapsed().as_millis() as u64);
+
+    if let Ok(value) = HeaderValue::from_str(tracer.request_id()) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    response
+}
+
+/// Keep the trace of an authenticated request
+pub fn register_request_trace(parts: &Parts, state: &AppState, api_key_id: &str) {
+    if let Some(tracer) = parts.extensions.get::<RequestTracer>() {
+        tracer.set_api_key(api_key_id);
+        state.request_traces.insert(tracer.clone());
+    }
+}
+
+/// Extractor for the tracer of the request. Outside the middleware (e.g. in
+/// tests) a tracer that is never stored is returned.
+#[derive(Debug, Clone)]
+pub struct RequestTracing(pub RequestTracer);
+
+impl FromRequestParts<AppState> for RequestTracing {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let tracer = parts
+            .extensions
+            .get::<RequestTracer>()
+            .cloned()
+            .unwrap_or_else(|| {
+                RequestTracer::new(
+                    Uuid::new_v4().to_string(),
+                    parts.method.as_str(),
+                    parts.uri.path(),
+                )
+            });
+
+        Ok(Self(tracer))
+    }
+}
@@ -0,0 +1,130 @@
+//! Data residency enforcement for chat completions
+//!
+//! Teams with allowed regions may only be routed to models whose credential
+//! declares one of those regions. Handlers check the model chosen by routing
+//! (after experiments, budget downgrades and canary fallbacks) before calling
+//! the provider; violations are rejected and recorded to the audit trail.
+
+use serde_json::json;
+use tracing::warn;
+
+use crate::api::state::AppState;
+use crate::api::types::ApiError;
+use crate::domain::api_key::ApiKey;
+use crate::domain::audit::{AuditActor, AuditLog};
+use crate::domain::residency::{Region, region_allowed};
+
+/// Reject routing a request of a region-pinned team to a model served from
+/// outside its allowed regions. Models without a stored, enabled credential
+/// are served by the default provider, whose region is unknown.
+pub async fn enforce_data_residency(
+    state: &AppState,
+    api_key: &ApiKey,
+    model_id: &str,
+) -> Result<(), ApiError> {
+    let Some(team) = state.team_service.get(api_key.team_id().as_str()).await? else {
+        return Ok(());
+    };
+
+    if team.allowed_regions().is_empty() {
+        return Ok(());
+    }
+
+    let (credential_id, region) = provider_region(state, model_id).await?;
+
+    if region_allowed(team.allowed_regions(), region.as_ref()) {
+        return Ok(());
+    }
+
+    let allowed: Vec<&str> = team.allowed_regions().iter().map(Region::as_str).collect();
+    warn!(
+        api_key_id = %api_key.id(),
+        team_id = %api_key.team_id(),
+        model = %model_id,
+        region = ?region.as_ref().map(Region::as_str),
+        allowed_regions = ?allowed,
+        "Request routed outside the team's allowed regions"
+    );
+
+    let log = AuditLog::new(
+        AuditActor::api_key(api_key.id().as_str(), api_key.name()),
+        "region_violation",
+        "teams",
+    )
+    .with_entity_id(api_key.team_id().as_str())
+    .with_changes(json!({
+        "model": model_id,
+        "credential_id": credential_id,
+        "region": region.as_ref().map(Region::as_str),
+        "allowed_regions": allowed,
+    }))
+    .with_status_code(403);
+
+    if let Err(e) = state.audit_log_service.record(log).await {
+        warn!(error = %e, "Failed to record audit log");
+    }
+
+    Err(region_not_allowed_error(model_id, region.as_ref()))
+}
+
+/// Credential and region serving a model, as resolved by the provider router
+async fn provider_region(
+    state: &AppState,
+    model_id: &str,
+) -> Result<(Option<String>, Option<Region>), ApiError> {
+    let Some(model) = state.model_service.get(model_id).await? else {
+        return Ok((None, None));
+    };
+
+    let credential_id = model.credential_id().to_string();
+    let region = state
+        .credential_service
+        .get(&credential_id)
+        .await?
+        .filter(|c| c.is_enabled())
+        .and_then(|c| c.region().cloned());
+
+    Ok((Some(credential_id), region))
+}
+
+/// Error returned for requests that would leave the team's allowed regions
+pub fn region_not_allowed_error(model_id: &str, region: Option<&Region>) -> ApiError {
+    let message = match region {
+        Some(region) => format!(
+            "Model '{}' is served from region '{}', which is not allowed for this team",
+            model_id, region
+        ),
+        None => format!(
+            "Model '{}' is served from an unknown region and this team is pinned to specific regions",
+            model_id
+        ),
+    };
+
+    ApiError::forbidden(message)
+        .with_param("model")
+        .with_code("region_not_allowed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_region_not_allowed_error() {
+        let region = Region::new("us-east-1").unwrap();
+        let error = region_not_allowed_error("gpt-4", Some(&region));
+        let json = serde_json::to_value(&error.response).unwrap();
+
+        assert_eq!(error.status, axum::http::StatusCode::FORBIDDEN);
+        assert_eq!(json["error"]["code"], "region_not_allowed");
+        assert!(
+            json["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("us-east-1")
+        );
+
+        let error = region_not_allowed_error("gpt-4", None);
+        assert!(error.response.error.message.contains("unknown region"));
+    }
+}
@@ -0,0 +1,56 @@
+//! Zero-retention mode for API requests
+//!
+//! API keys and teams flagged `no_log` are served without storing request or
+//! completion bodies: captured execution logs keep only their metadata, and
+//! async mode, which has to keep the result until it is polled, is rejected.
+
+use tracing::warn;
+
+use crate::api::state::AppState;
+use crate::api::types::ApiError;
+use crate::domain::api_key::ApiKey;
+
+/// Whether requests of the key must not have their bodies stored, because
+/// the key or its team is in zero-retention mode. When the team cannot be
+/// loaded the request is treated as zero-retention.
+pub async fn zero_retention(state: &AppState, api_key: &ApiKey) -> bool {
+    if api_key.no_log() {
+        return true;
+    }
+
+    match state.team_service.get(api_key.team_id().as_str()).await {
+        Ok(team) => team.is_some_and(|team| team.no_log()),
+        Err(e) => {
+            warn!(
+                team_id = %api_key.team_id(),
+                error = %e,
+                "Failed to load team for zero-retention check, not storing payloads"
+            );
+            true
+        }
+    }
+}
+
+/// Error returned for async requests of zero-retention keys
+pub fn no_log_async_error() -> ApiError {
+    ApiError::bad_request(
+        "Async mode stores the result until it is retrieved and is not available in zero-retention mode",
+    )
+    .with_param("async")
+    .with_code("no_log_async_unsupported")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_log_async_error() {
+        let error = no_log_async_error();
+        let json = serde_json::to_value(&error.response).unwrap();
+
+        assert_eq!(error.status, axum::http::StatusCode::BAD_REQUEST);
+        assert_eq!(json["error"]["code"], "no_log_async_unsupported");
+        assert_eq!(json["error"]["param"], "async");
+    }
+}
@@ -0,0 +1,118 @@
+//! Outbound secret and credential leak scanning for chat completions
+//!
+//! Handlers run the provider's answer through a
+//! [`SecretScanner`](crate::domain::guardrail::SecretScanner) before returning
+//! it. With the `redact` action leaked secrets are replaced in place; with
+//! `block` the answer is rejected with a `secret_leak_detected` error. Every
+//! secret found is counted in `llm_secret_leaks_total`.
+
+use tracing::warn;
+
+use crate::api::state::AppState;
+use crate::api::types::ApiError;
+use crate::domain::guardrail::{SecretLeakAction, SecretScanner, SecretStreamFilter};
+use crate::domain::llm::LlmResponse;
+use crate::infrastructure::observability::record_secret_leak;
+
+/// Scanner for the configured guard, `None` when scanning is off. Stored
+/// credential values are loaded on each call so rotated keys are picked up.
+pub async fn secret_scanner(state: &AppState) -> Option<SecretScanner> {
+    let guard = &state.secret_leak_guard;
+
+    if guard.action == SecretLeakAction::Off {
+        return None;
+    }
+
+    let scanner = SecretScanner::new();
+
+    if !guard.scan_stored_credentials {
+        return Some(scanner);
+    }
+
+    match state.credential_service.list().await {
+        Ok(credentials) => Some(
+            scanner.with_known_secrets(
+                credentials
+                    .iter()
+                    .map(|c| c.api_key().to_string())
+                    .collect::<Vec<_>>(),
+            ),
+        ),
+        Err(e) => {
+            warn!(error = %e, "Failed to load stored credentials for secret scanning");
+            Some(scanner)
+        }
+    }
+}
+
+/// Scan a provider answer for leaked secrets, redacting them in place or
+/// failing with a `secret_leak_detected` error for the `block` action
+pub async fn enforce_secret_scan(
+    state: &AppState,
+    model_id: &str,
+    response: &mut LlmResponse,
+) -> Result<(), ApiError> {
+    let Some(scanner) = secret_scanner(state).await else {
+        return Ok(());
+    };
+
+    let mut kinds = Vec::new();
+    response.message.map_text(|text| {
+        let (redacted, matches) = scanner.redact(text);
+        kinds.extend(matches.into_iter().map(|m| m.kind));
+        redacted
+    });
+
+    if kinds.is_empty() {
+        return Ok(());
+    }
+
+    let action = state.secret_leak_guard.action;
+    record_secret_leaks(model_id, action, &kinds);
+
+    if action == SecretLeakAction::Block {
+        return Err(secret_leak_error());
+    }
+
+    Ok(())
+}
+
+/// Stream filter for the configured guard, `None` when scanning is off
+pub async fn secret_stream_filter(state: &AppState) -> Option<SecretStreamFilter> {
+    secret_scanner(state).await.map(SecretStreamFilter::new)
+}
+
+/// Count and log the secrets found in an answer
+pub fn record_secret_leaks(model_id: &str, action: SecretLeakAction, kinds: &[String]) {
+    for kind in kinds {
+        record_secret_leak(model_id, kind, action.as_str());
+    }
+    warn!(
+        model = %model_id,
+        kinds = ?kinds,
+        action = action.as_str(),
+        "Secret detected in completion output"
+    );
+}
+
+/// Error returned for answers blocked because they contain a secret
+pub fn secret_leak_error() -> ApiError {
+    ApiError::bad_request("Response blocked because it contains a secret or credential")
+        .with_param("output")
+        .with_code("secret_leak_detected")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_leak_error() {
+        let error = secret_leak_error();
+        let json = serde_json::to_value(&error.response).unwrap();
+
+        assert_eq!(error.status, axum::http::StatusCode::BAD_REQUEST);
+        assert_eq!(json["error"]["code"], "secret_leak_detected");
+        assert_eq!(json["error"]["param"], "output");
+    }
+}
@@ -1,18 +1,137 @@
 //! Security middleware for HTTP headers and request validation
 
+use std::time::Duration;
+
 use axum::{
     body::Body,
-    http::{header, Request, StatusCode},
+    extract::State,
+    http::{header, HeaderName, HeaderValue, Method, Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+
+use crate::api::openapi::SWAGGER_UI_PATH;
+use crate::config::{CorsConfig, CspConfig};
 
 /// Maximum request body size (10 MB)
 pub const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
 
+/// Content Security Policies applied by the security headers middleware
+#[derive(Debug, Clone)]
+pub struct SecurityPolicy {
+    api_csp: HeaderValue,
+    ui_csp: HeaderValue,
+}
+
+impl SecurityPolicy {
+    /// Build the policy from configuration, rejecting policies that are not
+    /// valid header values
+    pub fn from_config(config: &CspConfig) -> anyhow::Result<Self> {
+        Ok(Self {
+            api_csp: parse_csp(&config.api_policy)?,
+            ui_csp: parse_csp(&config.ui_policy)?,
+        })
+    }
+
+    /// Content Security Policy for a request path; the admin UI and Swagger
+    /// UI get the UI policy
+    pub fn csp_for(&self, path: &str) -> &HeaderValue {
+        if path.starts_with("/ui") || path.starts_with(SWAGGER_UI_PATH) {
+            &self.ui_csp
+        } else {
+            &self.api_csp
+        }
+    }
+}
+
+impl Default for SecurityPolicy {
+    fn default() -> Self {
+        Self::from_config(&CspConfig::default()).expect("default CSP is a valid header value")
+    }
+}
+
+fn parse_csp(policy: &str) -> anyhow::Result<HeaderValue> {
+    HeaderValue::from_str(policy)
+        .map_err(|e| anyhow::anyhow!("Invalid Content Security Policy '{}': {}", policy, e))
+}
+
+/// Build the CORS layer from configuration. Returns `None` when no origins
+/// are allowed, leaving cross-origin browser requests blocked.
+pub fn cors_layer(config: &CorsConfig) -> anyhow::Result<Option<CorsLayer>> {
+    if config.allowed_origins.is_empty() {
+        return Ok(None);
+    }
+
+    let allow_origin = if config.allowed_origins.iter().any(|o| o == "*") {
+        if config.allow_credentials {
+            return Err(anyhow::anyhow!(
+                "CORS allowed origin '*' cannot be combined with allow_credentials"
+            ));
+        }
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(
+            config
+                .allowed_origins
+                .iter()
+                .map(|o| {
+                    HeaderValue::from_str(o.trim_end_matches('/'))
+                        .map_err(|e| anyhow::anyhow!("Invalid CORS origin '{}': {}", o, e))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?,
+        )
+    };
+
+    let allow_methods = if config.allowed_methods.iter().any(|m| m == "*") {
+        AllowMethods::mirror_request()
+    } else {
+        AllowMethods::list(
+            config
+                .allowed_methods
+                .iter()
+                .map(|m| {
+                    Method::from_bytes(m.to_uppercase().as_bytes())
+                        .map_err(|e| anyhow::anyhow!("Invalid CORS method '{}': {}", m, e))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?,
+        )
+    };
+
+    let allow_headers = if config.allowed_headers.iter().any(|h| h == "*") {
+        AllowHeaders::mirror_request()
+    } else {
+        AllowHeaders::list(parse_header_names(&config.allowed_headers)?)
+    };
+
+    Ok(Some(
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(allow_methods)
+            .allow_headers(allow_headers)
+            .expose_headers(parse_header_names(&config.exposed_headers)?)
+            .allow_credentials(config.allow_credentials)
+            .max_age(Duration::from_secs(config.max_age_secs)),
+    ))
+}
+
+fn parse_header_names(names: &[String]) -> anyhow::Result<Vec<HeaderName>> {
+    names
+        .iter()
+        .map(|h| {
+            HeaderName::from_bytes(h.as_bytes())
+                .map_err(|e| anyhow::anyhow!("Invalid CORS header '{}': {}", h, e))
+        })
+        .collect()
+}
+
 /// Middleware to add security headers to all responses
-pub async fn security_headers_middleware(request: Request<Body>, next: Next) -> Response {
-    let is_ui_path = request.uri().path().starts_with("/ui");
+pub async fn security_headers_middleware(
+    State(policy): State<SecurityPolicy>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    let csp = policy.csp_for(request.uri().path()).clone();
     let mut response = next.run(request).await;
     let headers = response.headers_mut();
 
@@ -41,19 +160,7 @@ pub async fn security_headers_middleware(request: Request<Body>, next: Next) ->
     );
 
     // Content Security Policy - different for UI vs API
-    let csp = if is_ui_path {
-        // UI needs scripts and styles from CDN and self
-        "default-src 'self'; \
-         script-src 'self' https://cdn.tailwindcss.com https://code.jquery.com 'unsafe-inline'; \
-         style-src 'self' 'unsafe-inline'; \
-         connect-src 'self'; \
-         img-src 'self' data:; \
-         frame-ancestors 'none'"
-    } else {
-        // Strict CSP for API responses
-        "default-src 'none'; frame-ancestors 'none'"
-    };
-    headers.insert(header::CONTENT_SECURITY_POLICY, csp.parse().unwrap());
+    headers.insert(header::CONTENT_SECURITY_POLICY, csp);
 
     // Strict Transport Security (HSTS)
     // Only effective over HTTPS, but safe to include
@@ -196,6 +303,69 @@ mod tests {
         assert!(matches!(result, Err(SecurityValidationError::InvalidCharacters)));
     }
 
+    #[test]
+    fn test_security_policy_csp_for_path() {
+        let policy = SecurityPolicy::from_config(&CspConfig {
+            api_policy: "default-src 'none'".to_string(),
+            ui_policy: "default-src 'self'".to_string(),
+        })
+        .unwrap();
+
+        assert_eq!(policy.csp_for("/v1/models"), "default-src 'none'");
+        assert_eq!(policy.csp_for("/ui/index.html"), "default-src 'self'");
+        assert_eq!(
+            policy.csp_for("/swagger-ui/index.html"),
+            "default-src 'self'"
+        );
+        assert_eq!(policy.csp_for("/openapi.json"), "default-src 'none'");
+    }
+
+    #[test]
+    fn test_security_policy_rejects_invalid_header_value() {
+        let result = SecurityPolicy::from_config(&CspConfig {
+            api_policy: "default-src 'none'\n".to_string(),
+            ..Default::default()
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cors_layer_disabled_without_origins() {
+        assert!(cors_layer(&CorsConfig::default()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_cors_layer_with_origins() {
+        let config = CorsConfig {
+            allowed_origins: vec!["https://app.example.com/".to_string()],
+            allowed_methods: vec!["*".to_string()],
+            exposed_headers: vec!["x-request-id".to_string()],
+            allow_credentials: true,
+            ..Default::default()
+        };
+        assert!(cors_layer(&config).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_cors_layer_rejects_wildcard_with_credentials() {
+        let config = CorsConfig {
+            allowed_origins: vec!["*".to_string()],
+            allow_credentials: true,
+            ..Default::default()
+        };
+        assert!(cors_layer(&config).is_err());
+    }
+
+    #[test]
+    fn test_cors_layer_rejects_invalid_values() {
+        let config = CorsConfig {
+            allowed_origins: vec!["*".to_string()],
+            allowed_headers: vec!["bad header".to_string()],
+            ..Default::default()
+        };
+        assert!(cors_layer(&config).is_err());
+    }
+
     #[test]
     fn test_max_body_size() {
         // 10 MB
@@ -0,0 +1,95 @@
+//! Team defaults and limits of chat completion parameters
+//!
+//! Teams may give the chat completions of their API keys a default model,
+//! temperature and `max_tokens` ceilings and a system prompt prefix (see
+//! [`TeamRequestDefaults`]). Handlers apply them to the request before
+//! routing; parameters above a ceiling are lowered to it or rejected with
+//! `team_limit_exceeded`, as the team configured.
+
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, ChatCompletionRequest};
+use crate::domain::api_key::ApiKey;
+use crate::domain::team::{ParameterLimitExceeded, TeamRequestDefaults};
+
+/// Defaults and limits of the team owning an API key
+pub async fn team_request_defaults(
+    state: &AppState,
+    api_key: &ApiKey,
+) -> Result<TeamRequestDefaults, ApiError> {
+    Ok(state
+        .team_service
+        .get(api_key.team_id().as_str())
+        .await?
+        .map(|team| team.request_defaults().clone())
+        .unwrap_or_default())
+}
+
+/// Apply the team's default model and parameter limits to a chat completion
+/// request
+pub fn apply_team_request_defaults(
+    defaults: &TeamRequestDefaults,
+    request: &mut ChatCompletionRequest,
+) -> Result<(), ApiError> {
+    request.model = defaults.resolve_model(&request.model).to_string();
+
+    if request.model.is_empty() {
+        return Err(ApiError::bad_request("Model is required").with_param("model"));
+    }
+
+    request.temperature = defaults
+        .resolve_temperature(request.temperature)
+        .map_err(team_limit_error)?;
+    request.max_tokens = defaults
+        .resolve_max_tokens(request.max_tokens)
+        .map_err(team_limit_error)?;
+
+    Ok(())
+}
+
+fn team_limit_error(error: ParameterLimitExceeded) -> ApiError {
+    ApiError::bad_request(error.to_string())
+        .with_param(error.param)
+        .with_code("team_limit_exceeded")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::team::ParameterLimitAction;
+
+    fn request(model: &str, max_tokens: Option<u32>) -> ChatCompletionRequest {
+        serde_json::from_value(serde_json::json!({
+            "model": model,
+            "messages": [{"role": "user", "content": "Hi"}],
+            "max_tokens": max_tokens,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_apply_team_request_defaults() {
+        let mut defaults = TeamRequestDefaults {
+            default_model: Some("gpt-4o-mini".to_string()),
+            max_tokens: Some(500),
+            ..Default::default()
+        };
+
+        let mut chat = request("", Some(2000));
+        apply_team_request_defaults(&defaults, &mut chat).unwrap();
+        assert_eq!(chat.model, "gpt-4o-mini");
+        assert_eq!(chat.max_tokens, Some(500));
+
+        defaults.on_limit_exceeded = ParameterLimitAction::Reject;
+        let err = apply_team_request_defaults(&defaults, &mut request("gpt-4o", Some(2000)))
+            .unwrap_err();
+        assert_eq!(err.response.error.param.as_deref(), Some("max_tokens"));
+        assert_eq!(err.response.error.code.as_deref(), Some("team_limit_exceeded"));
+    }
+
+    #[test]
+    fn test_model_required_without_team_default() {
+        let err = apply_team_request_defaults(&TeamRequestDefaults::new(), &mut request("", None))
+            .unwrap_err();
+        assert_eq!(err.response.error.param.as_deref(), Some("model"));
+    }
+}
@@ -0,0 +1,119 @@
+//! Request spans continuing the caller's trace, with the trace ID returned
+//! in the `x-trace-id` and `traceparent` response headers
+
+use axum::{
+    body::Body,
+    extract::MatchedPath,
+    http::{HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use opentelemetry::Context;
+use tracing::{field, info_span, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::infrastructure::observability::{
+    extract_trace_context, inject_trace_context, trace_id, TRACE_ID_HEADER,
+};
+
+/// Root span of a request for `TraceLayer::make_span_with`, parented to the
+/// trace context of the request headers when present
+pub fn make_request_span<B>(request: &Request<B>) -> Span {
+    request_span(request, extract_trace_context(request.headers()))
+}
+
+/// Root span of a request parented to the given trace context
+fn request_span<B>(request: &Request<B>, parent: Context) -> Span {
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+
+    let span = info_span!(
+        "http.request",
+        otel.name = %format!("{} {}", request.method(), route),
+        otel.kind = "server",
+        otel.status_code = field::Empty,
+        http.request.method = %request.method(),
+        http.route = %route,
+        url.path = %request.uri().path(),
+        http.response.status_code = field::Empty,
+    );
+    span.set_parent(parent);
+
+    span
+}
+
+/// Record the response status on the request span and return its trace ID.
+/// Must run inside the `TraceLayer` span created by [`make_request_span`].
+pub async fn trace_context_middleware(request: Request<Body>, next: Next) -> Response {
+    let mut response = next.run(request).await;
+    let span = Span::current();
+    let status = response.status();
+
+    span.record("http.response.status_code", status.as_u16());
+
+    if status.is_server_error() {
+        span.record("otel.status_code", "ERROR");
+    }
+
+    if let Some(trace_id) = trace_id(&span) {
+        if let Ok(value) = HeaderValue::from_str(&trace_id) {
+            response.headers_mut().insert(TRACE_ID_HEADER, value);
+        }
+
+        inject_trace_context(&span, response.headers_mut());
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::TracerProvider;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+    use crate::infrastructure::observability::extract_trace_context_with;
+
+    #[test]
+    fn test_make_request_span_continues_caller_trace() {
+        let propagator = TraceContextPropagator::new();
+        let tracer = TracerProvider::builder().build().tracer("test");
+        let subscriber =
+            tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let request = Request::builder()
+                .uri("/v1/chat/completions")
+                .header(
+                    "traceparent",
+                    "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+                )
+                .body(Body::empty())
+                .unwrap();
+            let span = request_span(
+                &request,
+                extract_trace_context_with(&propagator, request.headers()),
+            );
+            assert_eq!(
+                trace_id(&span).as_deref(),
+                Some("4bf92f3577b34da6a3ce929d0e0e4736")
+            );
+
+            let untraced = Request::builder()
+                .uri("/health")
+                .body(Body::empty())
+                .unwrap();
+            let root = request_span(
+                &untraced,
+                extract_trace_context_with(&propagator, untraced.headers()),
+            );
+            assert_eq!(trace_id(&root).map(|id| id.len()), Some(32));
+            assert_ne!(trace_id(&root), trace_id(&span));
+        });
+    }
+}
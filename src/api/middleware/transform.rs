@@ -0,0 +1,155 @@
+//! Request transform rules
+//!
+//! Admin-defined [`TransformRule`]s rewrite matching requests before routing:
+//! handlers call [`apply_transform_rules`] once the API key is known, which
+//! replaces the model, injects system messages and adds metadata tags. The
+//! middleware places a [`TransformSlot`] in the request extensions and sets
+//! the headers of the matching rules on the response.
+//!
+//! [`TransformRule`]: crate::domain::transform::TransformRule
+
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    body::Body,
+    http::{HeaderName, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::api::state::AppState;
+use crate::api::types::{
+    ApiError, ChatCompletionRequest, ChatMessage, ChatMessageRole, MessageContent,
+};
+use crate::domain::api_key::ApiKey;
+use crate::domain::transform::{TransformOutcome, TransformTarget};
+
+/// Request extension shared between the transform middleware and handlers
+#[derive(Clone, Default)]
+pub struct TransformSlot(Arc<Mutex<Vec<(String, String)>>>);
+
+impl TransformSlot {
+    fn fill(&self, headers: Vec<(String, String)>) {
+        *self.0.lock().unwrap() = headers;
+    }
+
+    fn take(&self) -> Vec<(String, String)> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+/// Apply the transform rules matching a chat completion request. Returns
+/// the changes, empty when no rule matched.
+pub async fn apply_transform_rules(
+    state: &AppState,
+    route: &str,
+    api_key: &ApiKey,
+    request: &mut ChatCompletionRequest,
+    slot: Option<&TransformSlot>,
+) -> Result<TransformOutcome, ApiError> {
+    let outcome = state
+        .transform_rule_service
+        .evaluate(&TransformTarget {
+            route,
+            api_key_id: api_key.id().as_str(),
+            model: &request.model,
+        })
+        .await?;
+
+    apply_outcome(&outcome, request);
+
+    if let Some(slot) = slot
+        && !outcome.headers.is_empty()
+    {
+        slot.fill(outcome.headers.clone());
+    }
+
+    Ok(outcome)
+}
+
+fn apply_outcome(outcome: &TransformOutcome, request: &mut ChatCompletionRequest) {
+    if let Some(model) = &outcome.model {
+        request.model = model.clone();
+    }
+
+    request.messages.splice(
+        0..0,
+        outcome.system_messages.iter().map(|content| system_message(content)),
+    );
+
+    if !outcome.tags.is_empty() {
+        request
+            .metadata
+            .get_or_insert_default()
+            .extend(outcome.tags.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+}
+
+fn system_message(content: &str) -> ChatMessage {
+    ChatMessage {
+        role: ChatMessageRole::System,
+        content: Some(MessageContent::Text(content.to_string())),
+        name: None,
+        tool_calls: None,
+        tool_call_id: None,
+        function_call: None,
+        prompt_id: None,
+        variables: None,
+    }
+}
+
+/// Middleware setting the headers of matching transform rules on responses
+pub async fn transform_headers_middleware(mut request: Request<Body>, next: Next) -> Response {
+    let slot = TransformSlot::default();
+    request.extensions_mut().insert(slot.clone());
+
+    let mut response = next.run(request).await;
+
+    for (name, value) in slot.take() {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            response.headers_mut().insert(name, value);
+        }
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_apply_outcome() {
+        let mut request: ChatCompletionRequest = serde_json::from_value(serde_json::json!({
+            "model": "gpt-4o",
+            "messages": [{"role": "user", "content": "Hi"}],
+            "metadata": {"feature": "search"},
+        }))
+        .unwrap();
+
+        let outcome = TransformOutcome {
+            rule_ids: vec!["rule".to_string()],
+            model: Some("gpt-4o-mini".to_string()),
+            system_messages: vec!["First.".to_string(), "Second.".to_string()],
+            tags: HashMap::from([("source".to_string(), "edge".to_string())]),
+            headers: Vec::new(),
+        };
+        apply_outcome(&outcome, &mut request);
+
+        assert_eq!(request.model, "gpt-4o-mini");
+        assert_eq!(request.messages.len(), 3);
+        assert_eq!(request.messages[0].role, ChatMessageRole::System);
+        assert_eq!(
+            request.messages[1].content.as_ref().map(MessageContent::to_text),
+            Some("Second.".to_string())
+        );
+
+        let metadata = request.metadata.unwrap();
+        assert_eq!(metadata.get("source").map(String::as_str), Some("edge"));
+        assert_eq!(metadata.get("feature").map(String::as_str), Some("search"));
+    }
+}
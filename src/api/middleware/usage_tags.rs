@@ -0,0 +1,85 @@
+//! Cost attribution tags supplied with API requests
+//!
+//! Tags come from the `x-pmp-tags` header (`feature=onboarding,customer=acme`)
+//! and the `metadata` field of the request body, which takes precedence for
+//! keys present in both. They are stored on the usage record of the request.
+
+use std::collections::HashMap;
+
+use axum::{extract::FromRequestParts, http::request::Parts};
+
+use crate::api::state::AppState;
+use crate::api::types::ApiError;
+use crate::domain::usage::{parse_usage_tags, validate_usage_tags};
+
+/// Header carrying cost attribution tags
+pub const USAGE_TAGS_HEADER: &str = "x-pmp-tags";
+
+/// Extractor for the tags of the `x-pmp-tags` header
+#[derive(Debug, Clone, Default)]
+pub struct UsageTags(pub HashMap<String, String>);
+
+impl UsageTags {
+    /// Merge tags from the request body metadata, which override header tags
+    pub fn merge(
+        self,
+        metadata: Option<&HashMap<String, String>>,
+    ) -> Result<HashMap<String, String>, ApiError> {
+        let mut tags = self.0;
+
+        if let Some(metadata) = metadata {
+            tags.extend(metadata.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+
+        validate_usage_tags(&tags).map_err(|e| ApiError::from(e).with_param("metadata"))?;
+        Ok(tags)
+    }
+}
+
+impl FromRequestParts<AppState> for UsageTags {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let Some(value) = parts.headers.get(USAGE_TAGS_HEADER) else {
+            return Ok(Self::default());
+        };
+
+        let value = value
+            .to_str()
+            .map_err(|_| ApiError::bad_request("x-pmp-tags header must be valid ASCII"))?;
+
+        parse_usage_tags(value)
+            .map(Self)
+            .map_err(ApiError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_metadata_overrides_header() {
+        let header = UsageTags(HashMap::from([
+            ("feature".to_string(), "onboarding".to_string()),
+            ("customer".to_string(), "acme".to_string()),
+        ]));
+        let metadata = HashMap::from([("feature".to_string(), "search".to_string())]);
+
+        let tags = header.merge(Some(&metadata)).unwrap();
+
+        assert_eq!(tags["feature"], "search");
+        assert_eq!(tags["customer"], "acme");
+    }
+
+    #[test]
+    fn test_merge_rejects_invalid_metadata() {
+        let metadata = HashMap::from([("bad key".to_string(), "x".to_string())]);
+
+        assert!(UsageTags::default().merge(Some(&metadata)).is_err());
+        assert!(UsageTags::default().merge(None).unwrap().is_empty());
+    }
+}
@@ -1,4 +1,7 @@
 //! User authentication middleware using JWT tokens
+//!
+//! Service account tokens (issued through the `client_credentials` grant)
+//! carry a `client_id` claim and are never accepted as user tokens.
 
 use axum::{
     extract::FromRequestParts,
@@ -8,6 +11,8 @@ use tracing::debug;
 
 use crate::api::state::AppState;
 use crate::api::types::ApiError;
+use crate::domain::role::Permission;
+use crate::domain::service_account::ServiceAccount;
 use crate::domain::user::User;
 
 /// Extractor that requires a valid JWT token
@@ -34,6 +39,12 @@ impl FromRequestParts<AppState> for RequireUser {
             .validate(&token)
             .map_err(|e| ApiError::unauthorized(format!("Invalid token: {}", e)))?;
 
+        if claims.is_service_account() {
+            return Err(ApiError::unauthorized(
+                "Service account tokens cannot be used for user endpoints",
+            ));
+        }
+
         // Look up the user
         let user = state
             .user_service
@@ -77,6 +88,10 @@ pub async fn try_jwt_auth(
 
     let claims = state.jwt_service.validate(&token).ok()?;
 
+    if claims.is_service_account() {
+        return None;
+    }
+
     let user = state
         .user_service
         .get(claims.user_id())
@@ -91,6 +106,39 @@ pub async fn try_jwt_auth(
     }
 }
 
+/// Try to authenticate a service account token. Returns the active service
+/// account with the token scopes it still holds, or None if the token is not
+/// a valid service account token.
+pub async fn try_service_account_auth(
+    headers: &axum::http::HeaderMap,
+    state: &AppState,
+) -> Option<(ServiceAccount, Vec<Permission>)> {
+    let token = extract_jwt_token(headers).ok()?;
+
+    let claims = state.jwt_service.validate(&token).ok()?;
+    let client_id = claims.client_id.as_deref()?;
+
+    let account = state
+        .service_account_service
+        .get(client_id)
+        .await
+        .ok()
+        .flatten()?;
+
+    if !account.is_active() {
+        return None;
+    }
+
+    // Scopes removed from the account since the token was issued no longer apply
+    let scopes = claims
+        .scopes()
+        .into_iter()
+        .filter(|scope| account.allows(scope))
+        .collect();
+
+    Some((account, scopes))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,6 +4,7 @@ pub mod admin;
 pub mod auth;
 pub mod health;
 pub mod middleware;
+pub mod openapi;
 pub mod router;
 pub mod state;
 pub mod types;
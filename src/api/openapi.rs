@@ -0,0 +1,478 @@
+//! OpenAPI specification of the gateway API
+//!
+//! Handlers of the health, authentication, OpenAI-compatible and admin
+//! endpoints are annotated with `#[utoipa::path]`; the document collects them
+//! and is served at `/openapi.json`, with Swagger UI at `/swagger-ui`, so
+//! clients can be generated from it.
+
+use axum::Router;
+use utoipa::openapi::path::Operation;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme};
+use utoipa::openapi::{ContentBuilder, Ref, RefOr, ResponseBuilder};
+use utoipa::{Modify, OpenApi};
+use utoipa_swagger_ui::SwaggerUi;
+
+use super::types::ApiErrorResponse;
+use super::{admin, auth, health, v1};
+
+/// Path of the OpenAPI document
+pub const OPENAPI_PATH: &str = "/openapi.json";
+
+/// Path of the Swagger UI
+pub const SWAGGER_UI_PATH: &str = "/swagger-ui";
+
+/// Name of the bearer security scheme
+const BEARER_SCHEME: &str = "bearer";
+
+/// Name of the shared error response
+const ERROR_RESPONSE: &str = "Error";
+
+/// Endpoints callable without credentials
+const PUBLIC_PATHS: &[&str] = &["/health", "/ready", "/live", "/auth/login", "/auth/token"];
+
+/// OpenAPI document of the gateway API; `/api/v1` mirrors the admin API and
+/// is not listed separately
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "PMP LLM Gateway API"),
+    paths(
+        admin::models::list_models,
+        admin::models::create_model,
+        admin::models::get_model,
+        admin::models::update_model,
+        admin::models::delete_model,
+        admin::models::bulk_models,
+        admin::models::clone_model,
+        admin::models::execute_model,
+        admin::playground::execute_playground,
+        admin::prompts::list_prompts,
+        admin::prompts::create_prompt,
+        admin::prompts::validate_prompt,
+        admin::prompts::get_prompt,
+        admin::prompts::update_prompt,
+        admin::prompts::delete_prompt,
+        admin::prompts::bulk_prompts,
+        admin::prompts::clone_prompt,
+        admin::prompts::render_prompt,
+        admin::prompts::get_prompt_variables,
+        admin::prompts::list_versions,
+        admin::prompts::diff_versions,
+        admin::prompts::revert_to_version,
+        admin::prompts::list_regressions,
+        admin::workflows::list_workflows,
+        admin::workflows::create_workflow,
+        admin::workflows::get_workflow,
+        admin::workflows::update_workflow,
+        admin::workflows::delete_workflow,
+        admin::workflows::test_workflow,
+        admin::workflows::execute_workflow,
+        admin::workflows::clone_workflow,
+        admin::workflows::list_regressions,
+        admin::assistants::list_assistants,
+        admin::assistants::create_assistant,
+        admin::assistants::get_assistant,
+        admin::assistants::update_assistant,
+        admin::assistants::delete_assistant,
+        admin::fine_tunes::list_fine_tunes,
+        admin::fine_tunes::create_fine_tune,
+        admin::fine_tunes::get_fine_tune,
+        admin::fine_tunes::refresh_fine_tune,
+        admin::fine_tunes::cancel_fine_tune,
+        admin::mcp_tools::list_mcp_tools,
+        admin::mcp_tools::create_mcp_tool,
+        admin::mcp_tools::get_mcp_tool,
+        admin::mcp_tools::update_mcp_tool,
+        admin::mcp_tools::delete_mcp_tool,
+        admin::api_keys::list_api_keys,
+        admin::api_keys::create_api_key,
+        admin::api_keys::get_api_key,
+        admin::api_keys::update_api_key,
+        admin::api_keys::delete_api_key,
+        admin::api_keys::suspend_api_key,
+        admin::api_keys::activate_api_key,
+        admin::api_keys::revoke_api_key,
+        admin::api_keys::bulk_revoke_api_keys,
+        admin::api_keys::rotate_api_key,
+        admin::api_keys::get_api_key_usage,
+        admin::service_accounts::list_service_accounts,
+        admin::service_accounts::create_service_account,
+        admin::service_accounts::get_service_account,
+        admin::service_accounts::update_service_account,
+        admin::service_accounts::delete_service_account,
+        admin::service_accounts::rotate_service_account_secret,
+        admin::teams::list_teams,
+        admin::teams::create_team,
+        admin::teams::get_team,
+        admin::teams::update_team,
+        admin::teams::delete_team,
+        admin::teams::suspend_team,
+        admin::teams::activate_team,
+        admin::teams::get_team_quota,
+        admin::teams::update_team_quota,
+        admin::teams::get_team_request_defaults,
+        admin::teams::update_team_request_defaults,
+        admin::invoices::get_team_invoice,
+        admin::organizations::list_organizations,
+        admin::organizations::create_organization,
+        admin::organizations::get_organization,
+        admin::organizations::update_organization,
+        admin::organizations::delete_organization,
+        admin::organizations::list_organization_teams,
+        admin::organizations::add_organization_team,
+        admin::organizations::remove_organization_team,
+        admin::roles::list_roles,
+        admin::roles::create_role,
+        admin::roles::list_permissions,
+        admin::roles::get_role,
+        admin::roles::update_role,
+        admin::roles::delete_role,
+        admin::roles::assign_user_role,
+        admin::users::reset_user_mfa,
+        admin::credentials::list_credentials,
+        admin::credentials::create_credential,
+        admin::credentials::list_credential_providers,
+        admin::credentials::get_credential,
+        admin::credentials::update_credential,
+        admin::credentials::delete_credential,
+        admin::credentials::test_credential,
+        admin::external_apis::list_external_apis,
+        admin::external_apis::create_external_api,
+        admin::external_apis::get_external_api,
+        admin::external_apis::update_external_api,
+        admin::external_apis::delete_external_api,
+        admin::knowledge_bases::list_knowledge_bases,
+        admin::knowledge_bases::create_knowledge_base,
+        admin::knowledge_bases::list_knowledge_base_types,
+        admin::knowledge_bases::get_knowledge_base,
+        admin::knowledge_bases::update_knowledge_base,
+        admin::knowledge_bases::delete_knowledge_base,
+        admin::knowledge_bases::clone_knowledge_base,
+        admin::knowledge_bases::list_documents,
+        admin::knowledge_bases::ingest_document,
+        admin::knowledge_bases::ingest_files_batch,
+        admin::knowledge_bases::get_document,
+        admin::knowledge_bases::delete_document,
+        admin::knowledge_bases::get_document_chunks,
+        admin::knowledge_bases::disable_document,
+        admin::knowledge_bases::enable_document,
+        admin::knowledge_bases::list_ingestion_operations,
+        admin::knowledge_bases::stream_ingestion_progress,
+        admin::knowledge_bases::ensure_schema,
+        admin::knowledge_bases::get_vector_index,
+        admin::knowledge_bases::reindex_knowledge_base,
+        admin::knowledge_bases::get_knowledge_base_analytics,
+        admin::usage::list_usage,
+        admin::usage::delete_usage,
+        admin::usage::get_usage_aggregate,
+        admin::usage::export_usage,
+        admin::usage::get_usage_summary,
+        admin::usage::get_usage_timeseries,
+        admin::usage::get_usage_reconciliation,
+        admin::usage::list_budgets,
+        admin::usage::create_budget,
+        admin::usage::check_budget,
+        admin::usage::list_budgets_by_team,
+        admin::usage::get_budget,
+        admin::usage::update_budget,
+        admin::usage::delete_budget,
+        admin::usage::reset_budget,
+        admin::pricing::list_pricing,
+        admin::pricing::create_pricing,
+        admin::pricing::sync_pricing,
+        admin::pricing::get_pricing,
+        admin::pricing::update_pricing,
+        admin::pricing::delete_pricing,
+        admin::pricing::delete_pricing_version,
+        admin::slo::list_slos,
+        admin::slo::create_slo,
+        admin::slo::list_slo_reports,
+        admin::slo::evaluate_slos,
+        admin::slo::get_slo,
+        admin::slo::update_slo,
+        admin::slo::delete_slo,
+        admin::slo::evaluate_slo,
+        admin::content_policies::list_content_policies,
+        admin::content_policies::create_content_policy,
+        admin::content_policies::evaluate_content_policies,
+        admin::content_policies::get_content_policy,
+        admin::content_policies::update_content_policy,
+        admin::content_policies::delete_content_policy,
+        admin::transform_rules::list_transform_rules,
+        admin::transform_rules::create_transform_rule,
+        admin::transform_rules::evaluate_transform_rules,
+        admin::transform_rules::get_transform_rule,
+        admin::transform_rules::update_transform_rule,
+        admin::transform_rules::delete_transform_rule,
+        admin::privacy::export_subject_data,
+        admin::privacy::delete_subject_data,
+        admin::references::list_entity_references,
+        admin::estimate::estimate,
+        admin::reconcile::reconcile_state,
+        admin::import::import_litellm,
+        admin::canaries::list_canaries,
+        admin::canaries::run_canaries,
+        admin::stats::get_stats,
+        admin::experiments::list_experiments,
+        admin::experiments::create_experiment,
+        admin::experiments::get_experiment,
+        admin::experiments::update_experiment,
+        admin::experiments::delete_experiment,
+        admin::experiments::add_variant,
+        admin::experiments::remove_variant,
+        admin::experiments::start_experiment,
+        admin::experiments::pause_experiment,
+        admin::experiments::resume_experiment,
+        admin::experiments::complete_experiment,
+        admin::experiments::get_experiment_results,
+        admin::test_cases::list_test_cases,
+        admin::test_cases::create_test_case,
+        admin::test_cases::get_test_case,
+        admin::test_cases::update_test_case,
+        admin::test_cases::delete_test_case,
+        admin::test_cases::execute_test_case,
+        admin::test_cases::get_test_case_results,
+        admin::test_suites::list_test_suites,
+        admin::test_suites::create_test_suite,
+        admin::test_suites::get_test_suite,
+        admin::test_suites::update_test_suite,
+        admin::test_suites::delete_test_suite,
+        admin::test_suites::run_test_suite,
+        admin::test_suites::list_test_suite_runs,
+        admin::test_suites::get_test_suite_run,
+        admin::datasets::list_datasets,
+        admin::datasets::create_dataset,
+        admin::datasets::get_dataset,
+        admin::datasets::update_dataset,
+        admin::datasets::delete_dataset,
+        admin::datasets::list_dataset_versions,
+        admin::datasets::create_dataset_version,
+        admin::datasets::get_dataset_version,
+        admin::config::list_config,
+        admin::config::list_config_by_category,
+        admin::config::get_config,
+        admin::config::update_config,
+        admin::execution_logs::list_execution_logs,
+        admin::execution_logs::get_execution_stats,
+        admin::execution_logs::list_captured_payloads,
+        admin::execution_logs::export_training_data,
+        admin::execution_logs::stream_execution_logs,
+        admin::execution_logs::cleanup_execution_logs,
+        admin::execution_logs::get_execution_log,
+        admin::execution_logs::delete_execution_log,
+        admin::requests::get_request_trace,
+        admin::webhooks::list_webhooks,
+        admin::webhooks::create_webhook,
+        admin::webhooks::list_event_types,
+        admin::webhooks::get_webhook,
+        admin::webhooks::update_webhook,
+        admin::webhooks::delete_webhook,
+        admin::webhooks::reset_webhook,
+        admin::webhooks::get_deliveries,
+        admin::webhooks::redeliver,
+        admin::audit_logs::list_audit_logs,
+        admin::audit_logs::get_audit_log,
+        v1::chat::create_chat_completion,
+        v1::feedback::submit_feedback,
+        v1::assistants::get_assistant,
+        v1::assistants::chat_with_assistant,
+        v1::assistants::run_assistant,
+        v1::mcp::handle_mcp,
+        v1::models::list_models,
+        v1::models::get_model,
+        v1::workflows::execute_workflow,
+        v1::operations::list_operations,
+        v1::operations::get_operation,
+        v1::operations::cancel_operation,
+        auth::login,
+        auth::logout,
+        auth::issue_token,
+        auth::get_current_user,
+        auth::enroll_mfa,
+        auth::verify_mfa,
+        auth::regenerate_recovery_codes,
+        auth::disable_mfa,
+        health::health_check,
+        health::ready_check,
+        health::live_check,
+    ),
+    components(schemas(ApiErrorResponse)),
+    modifiers(&GatewayConventions)
+)]
+pub struct ApiDoc;
+
+/// Adds what handlers share instead of repeating it on every annotation:
+/// bearer authentication and the OpenAI-compatible error response
+struct GatewayConventions;
+
+impl Modify for GatewayConventions {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+
+        components.add_security_scheme(
+            BEARER_SCHEME,
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .description(Some(
+                        "An API key on `/v1`; an admin API key, user JWT or service account \
+                         token on `/admin` and `/auth`",
+                    ))
+                    .build(),
+            ),
+        );
+
+        components.responses.insert(
+            ERROR_RESPONSE.to_string(),
+            RefOr::T(
+                ResponseBuilder::new()
+                    .description("Error in the OpenAI-compatible format")
+                    .content(
+                        "application/json",
+                        ContentBuilder::new()
+                            .schema(Some(Ref::from_schema_name("ApiErrorResponse")))
+                            .build(),
+                    )
+                    .build(),
+            ),
+        );
+
+        for (path, item) in openapi.paths.paths.iter_mut() {
+            let public = PUBLIC_PATHS.contains(&path.as_str());
+            let health = matches!(path.as_str(), "/health" | "/ready" | "/live");
+
+            let operations = [
+                &mut item.get,
+                &mut item.put,
+                &mut item.post,
+                &mut item.delete,
+                &mut item.patch,
+            ];
+
+            for operation in operations.into_iter().flatten() {
+                apply_conventions(operation, public, health);
+            }
+        }
+    }
+}
+
+fn apply_conventions(operation: &mut Operation, public: bool, health: bool) {
+    if !public {
+        operation.security = Some(vec![SecurityRequirement::new(
+            BEARER_SCHEME,
+            Vec::<String>::new(),
+        )]);
+    }
+
+    if !health {
+        operation
+            .responses
+            .responses
+            .entry("default".to_string())
+            .or_insert_with(|| RefOr::Ref(Ref::from_response_name(ERROR_RESPONSE)));
+    }
+}
+
+/// Router serving the OpenAPI document and Swagger UI
+pub fn create_openapi_router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    SwaggerUi::new(SWAGGER_UI_PATH)
+        .url(OPENAPI_PATH, ApiDoc::openapi())
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn operations(openapi: &utoipa::openapi::OpenApi) -> Vec<(&str, &Operation)> {
+        openapi
+            .paths
+            .paths
+            .iter()
+            .flat_map(|(path, item)| {
+                [&item.get, &item.put, &item.post, &item.delete, &item.patch]
+                    .into_iter()
+                    .flatten()
+                    .map(move |operation| (path.as_str(), operation))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_documents_api_groups() {
+        let openapi = ApiDoc::openapi();
+
+        for path in [
+            "/health",
+            "/auth/login",
+            "/v1/chat/completions",
+            "/v1/operations/{operation_id}",
+            "/admin/models/{model_id}",
+            "/admin/privacy/export",
+        ] {
+            assert!(
+                openapi.paths.paths.contains_key(path),
+                "{} is not documented",
+                path
+            );
+        }
+
+        assert!(
+            !openapi
+                .paths
+                .paths
+                .keys()
+                .any(|path| path.starts_with("/api/v1"))
+        );
+    }
+
+    #[test]
+    fn test_operation_ids_are_unique() {
+        let openapi = ApiDoc::openapi();
+        let mut seen = HashSet::new();
+
+        for (path, operation) in operations(&openapi) {
+            let id = operation.operation_id.clone().unwrap();
+            assert!(
+                seen.insert(id.clone()),
+                "duplicate operation id {} ({})",
+                id,
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_security_and_error_responses() {
+        let openapi = ApiDoc::openapi();
+
+        for (path, operation) in operations(&openapi) {
+            assert_eq!(
+                operation.security.is_none(),
+                PUBLIC_PATHS.contains(&path),
+                "unexpected security on {}",
+                path
+            );
+        }
+
+        let login = openapi.paths.paths["/auth/login"].post.as_ref().unwrap();
+        assert!(login.responses.responses.contains_key("default"));
+
+        let health = openapi.paths.paths["/health"].get.as_ref().unwrap();
+        assert!(!health.responses.responses.contains_key("default"));
+    }
+
+    #[test]
+    fn test_serializes_referenced_schemas() {
+        let json = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let schemas = json["components"]["schemas"].as_object().unwrap();
+
+        for schema in ["ApiErrorResponse", "ChatCompletionRequest", "ModelResponse"] {
+            assert!(schemas.contains_key(schema), "{} is not registered", schema);
+        }
+    }
+}
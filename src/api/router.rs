@@ -1,9 +1,11 @@
-use axum::{routing::get, Router};
+use axum::{middleware, routing::get, Router};
 use tower_http::trace::TraceLayer;
 
 use super::admin;
 use super::auth;
 use super::health;
+use super::middleware::{make_request_span, trace_context_middleware};
+use super::openapi::create_openapi_router;
 use super::state::AppState;
 use super::v1;
 
@@ -29,7 +31,10 @@ pub fn create_router_with_state(state: AppState) -> Router {
         .nest("/v1", v1::create_v1_router())
         // Admin API
         .nest("/admin", admin::create_admin_router())
+        // OpenAPI document and Swagger UI
+        .merge(create_openapi_router())
         // Add state and middleware
         .with_state(state)
-        .layer(TraceLayer::new_for_http())
+        .layer(middleware::from_fn(trace_context_middleware))
+        .layer(TraceLayer::new_for_http().make_span_with(make_request_span))
 }
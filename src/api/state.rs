@@ -2,56 +2,114 @@
 
 use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
 use serde_json::Value;
+use tokio_util::sync::CancellationToken;
 
-use crate::domain::api_key::{ApiKeyPermissions, ApiKeyRepository};
+use crate::api::middleware::ClientIpResolver;
+use crate::domain::api_key::{ApiKeyPermissions, ApiKeyRepository, RateLimitConfig};
 use crate::domain::config::{ConfigCategory, ConfigEntry, ConfigValue, ExecutionLog, ExecutionLogQuery, ExecutionStats};
 use crate::domain::credentials::StoredCredentialRepository;
+use crate::domain::assistant::{Assistant, AssistantRepository};
+use crate::domain::knowledge_base::{KnowledgeBaseCaller, SearchResult};
+use crate::domain::mcp::{McpTool, McpToolRepository};
+use crate::domain::fine_tune::{FineTuneJob, FineTuneJobRepository};
+use crate::domain::dataset::{Dataset, DatasetRef, DatasetRepository, DatasetRow, DatasetVersion};
 use crate::domain::experiment::{
-    AssignmentResult, Experiment, ExperimentQuery, ExperimentRecordRepository, ExperimentRepository,
-    ExperimentResult, ExperimentStatus,
+    AssignmentResult, Experiment, ExperimentQuery, ExperimentRecord, ExperimentRecordRepository,
+    ExperimentRepository, ExperimentResult, ExperimentStatus, FeedbackEvent, StepOverride,
+    WorkflowAssignment,
 };
+use crate::domain::agent::AgentLimits;
+use crate::domain::guardrail::{
+    ContentPolicy, InjectionAction, InjectionGuard, PolicyStage, PolicyViolation, SecretLeakGuard,
+};
+use crate::domain::ingestion::IngestionStage;
 use crate::domain::llm::LlmProvider;
+use crate::domain::network::IpNetwork;
 use crate::domain::operation::OperationRepository;
 use crate::domain::user::{User, UserRepository, UserStatus};
+use crate::domain::slo::{Slo, SloReport, SloRepository};
 use crate::domain::storage::Storage;
+use crate::domain::transform::{TransformOutcome, TransformRule, TransformTarget};
+use crate::domain::team::TeamId;
 use crate::domain::usage::{
-    Budget, BudgetId, BudgetRepository, ModelPricing, UsageAggregate, UsageQuery, UsageRecord,
+    Budget, BudgetId, BudgetRepository, InvoiceMarkup, ModelPricing, PricingId, PricingRepository,
+    TimeBucket, UsageAggregate, UsageBucket, UsageGroupBy, UsageQuery, UsageRecord,
     UsageRecordId, UsageRepository, UsageSummary,
 };
 use crate::domain::{
     ApiKey, DomainError, Executor, KnowledgeBase, Model, Operation, OperationType, Prompt,
-    StoredCredential, Workflow, WorkflowResult,
+    PromptVariable, StepProgress, StoredCredential, Workflow, WorkflowError, WorkflowResult,
+};
+use crate::infrastructure::api_key::{
+    ApiKeyService, QuotaWindow, RateLimitResult, RotateApiKeyResult,
 };
-use crate::infrastructure::api_key::ApiKeyService;
 use crate::infrastructure::auth::{JwtClaims, JwtGenerator, JwksJwtService, JwtService};
 use crate::infrastructure::credentials::{
     CreateCredentialRequest, CredentialService, UpdateCredentialRequest,
 };
+use crate::infrastructure::assistant::{
+    AssistantService, CreateAssistantRequest, UpdateAssistantRequest,
+};
+use crate::infrastructure::dataset::{CreateDatasetRequest, DatasetService, UpdateDatasetRequest};
+use crate::infrastructure::mcp::{CreateMcpToolRequest, McpToolService, UpdateMcpToolRequest};
+use crate::infrastructure::fine_tune::{CreateFineTuneJobRequest, FineTuneService};
+use crate::infrastructure::request_trace::RequestTraceStore;
 use crate::infrastructure::services::{
-    ConfigService, CreateExperimentRequest, CreateKnowledgeBaseRequest, CreateModelRequest,
+    ConfigService, CreateExperimentRequest, DocumentCopyResult, DocumentUsage, CreateKnowledgeBaseRequest, CreateModelRequest,
     CreatePromptRequest, CreateTestCaseRequest, CreateWorkflowRequest, CreateVariantRequest,
-    ExecuteTestCaseResponse, ExecutionLogService, ExperimentService, IngestDocumentRequest,
-    IngestDocumentV2Request, IngestionService, KnowledgeBaseService, ModelService, OperationService,
-    PromptService, RecordExperimentParams, RecordExecutionParams, StoredDocument, TestCaseService,
-    UpdateExperimentRequest, UpdateKnowledgeBaseRequest, UpdateModelRequest, UpdatePromptRequest,
-    UpdateTestCaseRequest, UpdateWorkflowRequest, WorkflowService,
+    ExecuteTestCaseResponse, ExecutionLogService, ExecutionOverrides, ExperimentService,
+    IngestDocumentRequest, IngestDocumentV2Request, IngestionService, KnowledgeBaseService,
+    ModelService, OperationService, PromptService, ReapedOperations, RecordExperimentParams, RecordExecutionParams,
+    RegressionService, ResponseCacheService, StoredDocument, TestCaseService, CreateTestSuiteRequest, TestSuiteService, UpdateExperimentRequest, UpdateKnowledgeBaseRequest,
+    UpdateModelRequest, UpdatePromptRequest, UpdateTestCaseRequest, UpdateTestSuiteRequest,
+    UpdateWorkflowRequest, WorkflowService,
+};
+use crate::domain::knowledge_base::{
+    DocumentChunk, DocumentSummary, KnowledgeBaseDocument, MetadataFilter, VectorIndexStatus,
 };
-use crate::domain::knowledge_base::{DocumentChunk, DocumentSummary, KnowledgeBaseDocument};
 use crate::domain::test_case::{
-    TestCase, TestCaseQuery, TestCaseRepository, TestCaseResult, TestCaseResultQuery,
-    TestCaseResultRepository,
+    RegressionPolicy, RegressionReport, RegressionSubject, TestCase, TestCaseQuery,
+    TestCaseRepository, TestCaseResult, TestCaseResultQuery, TestCaseResultRepository, TestSuite,
+    TestSuiteRepository, TestSuiteRun,
 };
+use crate::infrastructure::event::EventBus;
+use crate::infrastructure::health::{CanaryHealth, CanaryRunner, DependencyProber};
+use crate::infrastructure::ingestion::UploadLimits;
+use crate::infrastructure::knowledge_base::{IngestionProgressTracker, KnowledgeBaseAccessTracker};
+use crate::infrastructure::llm::{CredentialCooldowns, ModelCapacityTracker};
+use crate::infrastructure::notification::NotificationDispatcher;
 use crate::infrastructure::plugin::ProviderRouter;
+use crate::infrastructure::guardrail::ContentPolicyService;
+use crate::infrastructure::transform::TransformRuleService;
+use crate::infrastructure::slo::SloService;
 use crate::infrastructure::usage::{
-    BudgetCheckResult, BudgetService, BudgetServiceTrait, RecordUsageParams, UsageTrackingService,
+    AlertNotification, BudgetCheckResult, BudgetService, BudgetServiceTrait, PricingService,
+    PricingSyncReport, RecordUsageParams, UsageReconciler, UsageTrackingService,
     UsageTrackingServiceTrait,
 };
-use crate::infrastructure::team::{CreateTeamRequest, TeamService, UpdateTeamRequest};
+use crate::infrastructure::audit::AuditLogService;
+use crate::infrastructure::organization::{
+    CreateOrganizationRequest, OrganizationService, UpdateOrganizationRequest,
+};
+use crate::infrastructure::role::{CreateRoleRequest, RoleService, UpdateRoleRequest};
+use crate::infrastructure::service_account::{
+    CreateServiceAccountRequest, ServiceAccountService, ServiceAccountWithSecret,
+    UpdateServiceAccountRequest,
+};
+use crate::infrastructure::team::{
+    CreateTeamRequest, TeamConcurrencyLimiter, TeamService, UpdateTeamRequest,
+};
 use crate::infrastructure::user::{
-    CreateUserRequest, PasswordHasher, UpdatePasswordRequest, UserService,
+    CreateUserRequest, LoginOutcome, MfaEnrollment, PasswordHasher, UpdatePasswordRequest,
+    UserService,
 };
 use crate::infrastructure::webhook::{WebhookService, WebhookServiceTrait};
+use crate::domain::audit::{AuditLog, AuditLogQuery, AuditLogRepository};
+use crate::domain::organization::{Organization, OrganizationRepository};
+use crate::domain::role::{Permission, Role, RoleId, RoleRepository};
+use crate::domain::service_account::{ServiceAccount, ServiceAccountRepository};
 use crate::domain::team::{Team, TeamQuery, TeamRepository};
 use crate::domain::webhook::{
     Webhook, WebhookDelivery, WebhookDeliveryRepository, WebhookRepository,
@@ -71,20 +129,55 @@ pub struct AppState {
     pub operation_service: Arc<dyn OperationServiceTrait>,
     pub user_service: Arc<dyn UserServiceTrait>,
     pub team_service: Arc<dyn TeamServiceTrait>,
+    pub organization_service: Arc<dyn OrganizationServiceTrait>,
+    pub role_service: Arc<dyn RoleServiceTrait>,
+    pub service_account_service: Arc<dyn ServiceAccountServiceTrait>,
     pub jwt_service: Arc<dyn JwtServiceTrait>,
     pub credential_service: Arc<dyn CredentialServiceTrait>,
     pub external_api_service: Arc<dyn ExternalApiServiceTrait>,
     pub knowledge_base_service: Arc<dyn KnowledgeBaseServiceTrait>,
     pub ingestion_service: Arc<dyn IngestionServiceTrait>,
+    pub assistant_service: Arc<dyn AssistantServiceTrait>,
+    pub mcp_tool_service: Arc<dyn McpToolServiceTrait>,
+    pub fine_tune_service: Arc<dyn FineTuneServiceTrait>,
     pub usage_service: Arc<dyn UsageServiceTrait>,
     pub budget_service: Arc<dyn BudgetServiceStateTrait>,
+    pub pricing_service: Arc<dyn PricingServiceTrait>,
+    pub slo_service: Arc<dyn SloServiceTrait>,
+    pub content_policy_service: Arc<dyn ContentPolicyServiceTrait>,
+    pub transform_rule_service: Arc<dyn TransformRuleServiceTrait>,
     pub experiment_service: Arc<dyn ExperimentServiceTrait>,
     pub test_case_service: Arc<dyn TestCaseServiceTrait>,
+    pub test_suite_service: Arc<dyn TestSuiteServiceTrait>,
+    pub dataset_service: Arc<dyn DatasetServiceTrait>,
+    pub regression_service: Arc<dyn RegressionServiceTrait>,
     pub config_service: Arc<dyn ConfigServiceTrait>,
     pub execution_log_service: Arc<dyn ExecutionLogServiceTrait>,
+    pub audit_log_service: Arc<dyn AuditLogServiceTrait>,
     pub webhook_service: Arc<dyn WebhookServiceStateTrait>,
     pub llm_provider: Arc<dyn LlmProvider>,
     pub provider_router: Arc<ProviderRouter>,
+    pub client_ip_resolver: Arc<ClientIpResolver>,
+    pub team_concurrency: Arc<TeamConcurrencyLimiter>,
+    pub invoice_markup: Arc<InvoiceMarkup>,
+    pub notifications: Arc<NotificationDispatcher>,
+    pub usage_reconciler: Option<Arc<UsageReconciler>>,
+    pub events: Arc<EventBus>,
+    pub dependency_prober: Arc<DependencyProber>,
+    pub canary_health: Arc<CanaryHealth>,
+    pub canary_runner: Option<Arc<CanaryRunner>>,
+    pub injection_guard: Arc<InjectionGuard>,
+    pub secret_leak_guard: Arc<SecretLeakGuard>,
+    pub agent_limits: Arc<AgentLimits>,
+    pub request_traces: Arc<RequestTraceStore>,
+    pub model_capacity: Arc<ModelCapacityTracker>,
+    pub credential_cooldowns: Arc<CredentialCooldowns>,
+    pub upload_limits: Arc<UploadLimits>,
+    pub kb_access_tracker: Arc<KnowledgeBaseAccessTracker>,
+    /// Per-document progress of batch ingestions, kept in their operation
+    pub ingestion_progress: Arc<IngestionProgressTracker>,
+    /// Exact and semantic caches of chat completion responses
+    pub response_cache: Arc<ResponseCacheService>,
 }
 
 /// Trait for model service operations
@@ -110,6 +203,19 @@ pub trait PromptServiceTrait: Send + Sync {
         id: &str,
         variables: &std::collections::HashMap<String, String>,
     ) -> Result<String, DomainError>;
+    /// Render a retained version of a prompt, or without a version the
+    /// current one in the variant best matching the accepted locales
+    async fn render_variant(
+        &self,
+        id: &str,
+        version: Option<u32>,
+        locale: Option<&str>,
+        variables: &std::collections::HashMap<String, String>,
+    ) -> Result<String, DomainError>;
+    /// Variables of a prompt with their declared types, for form generation
+    async fn variable_schema(&self, id: &str) -> Result<Vec<PromptVariable>, DomainError>;
+    /// Expand the `${prompt:id}` partials of ad-hoc template content
+    async fn expand_partials(&self, content: &str) -> Result<String, DomainError>;
 
     async fn revert(&self, id: &str, version: u32) -> Result<Prompt, DomainError>;
 }
@@ -123,12 +229,75 @@ pub trait WorkflowServiceTrait: Send + Sync {
     async fn update(&self, id: &str, request: UpdateWorkflowRequest) -> Result<Workflow, DomainError>;
     async fn delete(&self, id: &str) -> Result<bool, DomainError>;
     async fn execute(&self, id: &str, input: Value) -> Result<WorkflowResult, DomainError>;
+    /// Execute a workflow on behalf of a caller, whose knowledge base searches
+    /// only return the documents it may read
+    async fn execute_as(
+        &self,
+        id: &str,
+        input: Value,
+        caller: KnowledgeBaseCaller,
+    ) -> Result<WorkflowResult, DomainError>;
+    /// Execute a workflow on behalf of a caller until it finishes or
+    /// `cancellation` is fired, reporting each step to `step_progress`
+    async fn execute_cancellable(
+        &self,
+        id: &str,
+        input: Value,
+        caller: KnowledgeBaseCaller,
+        cancellation: CancellationToken,
+        _step_progress: StepProgress,
+    ) -> Result<WorkflowResult, DomainError> {
+        tokio::select! {
+            biased;
+            _ = cancellation.cancelled() => {
+                Err(DomainError::internal(WorkflowError::cancelled(id).to_string()))
+            }
+            result = self.execute_as(id, input, caller) => result,
+        }
+    }
+    /// Execute a workflow with the step overrides of an experiment variant
+    async fn execute_variant(
+        &self,
+        id: &str,
+        step_overrides: &[StepOverride],
+        input: Value,
+        caller: KnowledgeBaseCaller,
+        cancellation: CancellationToken,
+        step_progress: StepProgress,
+    ) -> Result<WorkflowResult, DomainError> {
+        if !step_overrides.is_empty() {
+            return Err(DomainError::validation(format!(
+                "Workflow '{}' cannot be executed with step overrides",
+                id
+            )));
+        }
+
+        self.execute_cancellable(id, input, caller, cancellation, step_progress)
+            .await
+    }
+    /// Apply an update to a copy of a workflow without saving it
+    async fn preview_update(
+        &self,
+        id: &str,
+        request: UpdateWorkflowRequest,
+    ) -> Result<Workflow, DomainError>;
+    /// Execute a workflow definition that may not be saved
+    async fn execute_definition(
+        &self,
+        workflow: &Workflow,
+        input: Value,
+    ) -> Result<WorkflowResult, DomainError>;
+    /// Count the workflows owned by a team
+    async fn count_for_team(&self, team_id: &TeamId) -> Result<u64, DomainError>;
 }
 
 /// Trait for API key service operations
 #[async_trait::async_trait]
 pub trait ApiKeyServiceTrait: Send + Sync {
-    async fn validate(&self, key: &str) -> Result<Option<ApiKey>, DomainError>;
+    /// Find the active API key of a secret, without recording its usage
+    async fn verify(&self, key: &str) -> Result<Option<ApiKey>, DomainError>;
+    /// Record that an accepted request used an API key, from `client_ip`
+    async fn record_usage(&self, key: &ApiKey, client_ip: Option<&str>);
     async fn get(&self, id: &str) -> Result<Option<ApiKey>, DomainError>;
     async fn list(&self) -> Result<Vec<ApiKey>, DomainError>;
     async fn create(
@@ -155,6 +324,43 @@ pub trait ApiKeyServiceTrait: Send + Sync {
     async fn suspend(&self, id: &str) -> Result<(), DomainError>;
     async fn activate(&self, id: &str) -> Result<(), DomainError>;
     async fn revoke(&self, id: &str) -> Result<(), DomainError>;
+    /// Set or clear the expiration date of an API key
+    async fn set_expiration(
+        &self,
+        id: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<ApiKey, DomainError>;
+    /// Issue a replacement key, keeping the old one valid for the grace period
+    async fn rotate(&self, id: &str, grace_period_secs: u64)
+        -> Result<RotateApiKeyResult, DomainError>;
+    /// Set the client networks allowed to use an API key (empty = any)
+    async fn set_allowed_cidrs(
+        &self,
+        id: &str,
+        allowed_cidrs: Vec<IpNetwork>,
+    ) -> Result<ApiKey, DomainError>;
+    /// Set the action on prompt-injection detections (None = gateway default)
+    async fn set_injection_action(
+        &self,
+        id: &str,
+        injection_action: Option<InjectionAction>,
+    ) -> Result<ApiKey, DomainError>;
+    /// Enable or disable zero-retention mode
+    async fn set_no_log(&self, id: &str, no_log: bool) -> Result<ApiKey, DomainError>;
+    /// Count the usable API keys of a team
+    async fn count_for_team(&self, team_id: &TeamId) -> Result<u64, DomainError>;
+    /// Replace the rate limits and quotas of an API key
+    async fn set_rate_limits(
+        &self,
+        id: &str,
+        rate_limits: RateLimitConfig,
+    ) -> Result<ApiKey, DomainError>;
+    /// Check the rate limits and quotas of a key, counting the request if allowed
+    async fn check_rate_limit(&self, key: &ApiKey) -> RateLimitResult;
+    /// Charge tokens and cost of a completed request to the quotas of a key
+    async fn record_consumption(&self, key: &ApiKey, tokens: u32, cost_micros: i64);
+    /// Current consumption of the rate limits and quotas of a key
+    async fn quota_usage(&self, key: &ApiKey) -> Vec<QuotaWindow>;
 }
 
 /// Trait for operation service (async operations)
@@ -173,14 +379,37 @@ pub trait OperationServiceTrait: Send + Sync {
     async fn get_batch(&self, ids: &[String]) -> Result<Vec<Operation>, DomainError>;
     /// Mark an operation as running
     async fn mark_running(&self, id: &str) -> Result<Operation, DomainError>;
+    /// Replace the progress of a running operation
+    async fn update_progress(&self, id: &str, progress: Value) -> Result<Operation, DomainError>;
+    /// Record the units of work done by a running operation
+    async fn update_completion(
+        &self,
+        id: &str,
+        completed: u64,
+        total: u64,
+        unit: &str,
+    ) -> Result<Operation, DomainError>;
     /// Mark an operation as completed with result
     async fn mark_completed(&self, id: &str, result: Value) -> Result<Operation, DomainError>;
     /// Mark an operation as failed with error message
     async fn mark_failed(&self, id: &str, error: String) -> Result<Operation, DomainError>;
-    /// Cancel an operation
+    /// Cancel an operation, firing its cancellation token
     async fn cancel(&self, id: &str) -> Result<Operation, DomainError>;
+    /// Token fired when the operation is cancelled
+    fn cancellation_token(&self, id: &str) -> CancellationToken;
+    /// Refresh the heartbeat of the operations this replica runs
+    async fn heartbeat(&self) -> Result<usize, DomainError>;
+    /// Requeue or fail the operations whose replica stopped heartbeating
+    async fn reap_orphaned(
+        &self,
+        requeueable: &[OperationType],
+    ) -> Result<ReapedOperations, DomainError>;
     /// Clean up old completed operations
     async fn cleanup_old(&self) -> Result<u64, DomainError>;
+    /// List every operation, whatever its status
+    async fn list(&self) -> Result<Vec<Operation>, DomainError>;
+    /// Delete an operation
+    async fn delete(&self, id: &str) -> Result<bool, DomainError>;
 }
 
 /// Trait for user service operations
@@ -188,6 +417,13 @@ pub trait OperationServiceTrait: Send + Sync {
 pub trait UserServiceTrait: Send + Sync {
     /// Authenticate a user with username and password
     async fn authenticate(&self, username: &str, password: &str) -> Result<Option<User>, DomainError>;
+    /// Authenticate a user with username, password and optional MFA code
+    async fn login(
+        &self,
+        username: &str,
+        password: &str,
+        mfa_code: Option<&str>,
+    ) -> Result<LoginOutcome, DomainError>;
     /// Get a user by ID
     async fn get(&self, id: &str) -> Result<Option<User>, DomainError>;
     /// Get a user by username
@@ -204,6 +440,18 @@ pub trait UserServiceTrait: Send + Sync {
     async fn suspend(&self, id: &str) -> Result<User, DomainError>;
     /// Activate a user
     async fn activate(&self, id: &str) -> Result<User, DomainError>;
+    /// Assign an admin API role to a user
+    async fn set_role(&self, id: &str, role_id: Option<RoleId>) -> Result<User, DomainError>;
+    /// Start TOTP enrollment for a user
+    async fn begin_mfa_enrollment(&self, id: &str) -> Result<MfaEnrollment, DomainError>;
+    /// Confirm TOTP enrollment, returning one-time recovery codes
+    async fn confirm_mfa_enrollment(&self, id: &str, code: &str) -> Result<Vec<String>, DomainError>;
+    /// Replace a user's recovery codes after verifying an MFA code
+    async fn regenerate_recovery_codes(&self, id: &str, code: &str) -> Result<Vec<String>, DomainError>;
+    /// Disable MFA after verifying an MFA code
+    async fn disable_mfa(&self, id: &str, code: &str) -> Result<User, DomainError>;
+    /// Remove MFA without verification (admin-forced reset)
+    async fn reset_mfa(&self, id: &str) -> Result<User, DomainError>;
     /// Delete a user
     async fn delete(&self, id: &str) -> Result<bool, DomainError>;
 }
@@ -231,10 +479,104 @@ pub trait TeamServiceTrait: Send + Sync {
     async fn exists(&self, id: &str) -> Result<bool, DomainError>;
 }
 
+/// Trait for organization service operations
+#[async_trait::async_trait]
+pub trait OrganizationServiceTrait: Send + Sync {
+    /// Get an organization by ID
+    async fn get(&self, id: &str) -> Result<Option<Organization>, DomainError>;
+    /// List all organizations
+    async fn list(&self) -> Result<Vec<Organization>, DomainError>;
+    /// Create a new organization
+    async fn create(&self, request: CreateOrganizationRequest)
+        -> Result<Organization, DomainError>;
+    /// Update an organization
+    async fn update(
+        &self,
+        id: &str,
+        request: UpdateOrganizationRequest,
+    ) -> Result<Organization, DomainError>;
+    /// Delete an organization
+    async fn delete(&self, id: &str) -> Result<bool, DomainError>;
+    /// List the teams of an organization
+    async fn list_teams(&self, id: &str) -> Result<Vec<Team>, DomainError>;
+    /// Assign a team to an organization
+    async fn add_team(&self, id: &str, team_id: &str) -> Result<Team, DomainError>;
+    /// Remove a team from an organization
+    async fn remove_team(&self, id: &str, team_id: &str) -> Result<Team, DomainError>;
+    /// Get the organization a team belongs to
+    async fn get_for_team(&self, team_id: &TeamId) -> Result<Option<Organization>, DomainError>;
+}
+
+/// Trait for role service operations
+#[async_trait::async_trait]
+pub trait RoleServiceTrait: Send + Sync {
+    /// Get a role by ID (built-in or custom)
+    async fn get(&self, id: &str) -> Result<Option<Role>, DomainError>;
+    /// List all roles
+    async fn list(&self) -> Result<Vec<Role>, DomainError>;
+    /// Create a new custom role
+    async fn create(&self, request: CreateRoleRequest) -> Result<Role, DomainError>;
+    /// Update a custom role
+    async fn update(&self, id: &str, request: UpdateRoleRequest) -> Result<Role, DomainError>;
+    /// Delete a custom role
+    async fn delete(&self, id: &str) -> Result<bool, DomainError>;
+    /// Resolve the effective role of a user
+    async fn resolve_for_user(&self, user: &User) -> Result<Role, DomainError>;
+}
+
+/// Trait for service account operations
+#[async_trait::async_trait]
+pub trait ServiceAccountServiceTrait: Send + Sync {
+    /// Get a service account by ID
+    async fn get(&self, id: &str) -> Result<Option<ServiceAccount>, DomainError>;
+    /// List all service accounts
+    async fn list(&self) -> Result<Vec<ServiceAccount>, DomainError>;
+    /// Create a new service account, returning its client secret
+    async fn create(
+        &self,
+        request: CreateServiceAccountRequest,
+    ) -> Result<ServiceAccountWithSecret, DomainError>;
+    /// Update a service account
+    async fn update(
+        &self,
+        id: &str,
+        request: UpdateServiceAccountRequest,
+    ) -> Result<ServiceAccount, DomainError>;
+    /// Delete a service account
+    async fn delete(&self, id: &str) -> Result<bool, DomainError>;
+    /// Replace the client secret of a service account
+    async fn rotate_secret(&self, id: &str) -> Result<ServiceAccountWithSecret, DomainError>;
+    /// Verify client credentials of an active service account
+    async fn authenticate(
+        &self,
+        client_id: &str,
+        client_secret: &str,
+    ) -> Result<Option<ServiceAccount>, DomainError>;
+}
+
+/// Trait for audit log service operations
+#[async_trait::async_trait]
+pub trait AuditLogServiceTrait: Send + Sync {
+    /// Persist an audit log and export it to the configured sink
+    async fn record(&self, log: AuditLog) -> Result<AuditLog, DomainError>;
+    /// Get an audit log by ID
+    async fn get(&self, id: &str) -> Result<Option<AuditLog>, DomainError>;
+    /// List audit logs matching a query
+    async fn list(&self, query: &AuditLogQuery) -> Result<Vec<AuditLog>, DomainError>;
+    /// Count audit logs matching a query
+    async fn count(&self, query: &AuditLogQuery) -> Result<usize, DomainError>;
+}
+
 /// Trait for JWT service operations
 pub trait JwtServiceTrait: Send + Sync {
     /// Generate a JWT token for a user
     fn generate(&self, user: &User) -> Result<String, DomainError>;
+    /// Generate a JWT token for a service account, restricted to the given scopes
+    fn generate_for_service_account(
+        &self,
+        account: &ServiceAccount,
+        scopes: &[Permission],
+    ) -> Result<String, DomainError>;
     /// Validate a JWT token and return the claims
     fn validate(&self, token: &str) -> Result<JwtClaims, DomainError>;
     /// Get the token expiration time in hours
@@ -261,6 +603,8 @@ pub trait KnowledgeBaseServiceTrait: Send + Sync {
     async fn delete(&self, id: &str) -> Result<bool, DomainError>;
     /// Check if a knowledge base exists
     async fn exists(&self, id: &str) -> Result<bool, DomainError>;
+    /// Count the knowledge bases owned by a team
+    async fn count_for_team(&self, team_id: &TeamId) -> Result<u64, DomainError>;
 }
 
 /// Trait for document ingestion service operations
@@ -272,6 +616,14 @@ pub trait IngestionServiceTrait: Send + Sync {
         kb_id: &str,
         request: IngestDocumentRequest,
     ) -> Result<crate::domain::ingestion::IngestionResult, DomainError>;
+    /// Ingest a document into a knowledge base, reporting each stage it
+    /// reaches with the chunk count once chunked
+    async fn ingest_with_progress(
+        &self,
+        kb_id: &str,
+        request: IngestDocumentRequest,
+        on_stage: &(dyn Fn(IngestionStage, Option<usize>) + Send + Sync),
+    ) -> Result<crate::domain::ingestion::IngestionResult, DomainError>;
     /// List all sources in a knowledge base
     async fn list_sources(
         &self,
@@ -285,10 +637,26 @@ pub trait IngestionServiceTrait: Send + Sync {
     ) -> Result<Vec<StoredDocument>, DomainError>;
     /// Get document count for a knowledge base
     async fn document_count(&self, kb_id: &str) -> Result<usize, DomainError>;
+    /// Get documents matching a metadata filter
+    async fn get_documents_by_filter(
+        &self,
+        kb_id: &str,
+        filter: MetadataFilter,
+    ) -> Result<Vec<StoredDocument>, DomainError>;
     /// Delete documents by source ID
     async fn delete_by_source(&self, kb_id: &str, source: &str) -> Result<usize, DomainError>;
+    /// Delete documents matching a metadata filter
+    async fn delete_by_filter(
+        &self,
+        kb_id: &str,
+        filter: MetadataFilter,
+    ) -> Result<usize, DomainError>;
     /// Ensure the storage schema exists (create tables/indexes)
     async fn ensure_schema(&self, kb_id: &str) -> Result<(), DomainError>;
+    /// Status of the vector index of a knowledge base
+    async fn index_status(&self, kb_id: &str) -> Result<Option<VectorIndexStatus>, DomainError>;
+    /// Rebuild the vector index of a knowledge base
+    async fn reindex(&self, kb_id: &str) -> Result<VectorIndexStatus, DomainError>;
 
     // New schema methods (document/chunk separation)
     /// Ingest a document using the new schema
@@ -329,6 +697,14 @@ pub trait IngestionServiceTrait: Send + Sync {
         kb_id: &str,
         document_id: uuid::Uuid,
     ) -> Result<bool, DomainError>;
+    /// Count the documents and their size across knowledge bases
+    async fn document_usage(&self, kb_ids: &[String]) -> Result<DocumentUsage, DomainError>;
+    /// Copy every document of a knowledge base into another
+    async fn copy_documents(
+        &self,
+        source_kb_id: &str,
+        target_kb_id: &str,
+    ) -> Result<DocumentCopyResult, DomainError>;
 }
 
 /// Trait for credential service operations
@@ -391,16 +767,107 @@ pub trait UsageServiceTrait: Send + Sync {
     async fn aggregate(&self, query: &UsageQuery) -> Result<UsageAggregate, DomainError>;
     /// Get usage summary with daily breakdown
     async fn summary(&self, query: &UsageQuery) -> Result<UsageSummary, DomainError>;
+    /// Get usage per time bucket, optionally split by team, model or API key
+    async fn timeseries(
+        &self,
+        query: &UsageQuery,
+        bucket: TimeBucket,
+        group_by: Option<UsageGroupBy>,
+    ) -> Result<Vec<UsageBucket>, DomainError>;
     /// Delete records older than timestamp
     async fn delete_before(&self, timestamp: u64) -> Result<usize, DomainError>;
     /// Delete all records for an API key
     async fn delete_by_api_key(&self, api_key_id: &str) -> Result<usize, DomainError>;
+    /// Delete all records carrying a tag
+    async fn delete_by_tag(&self, key: &str, value: &str) -> Result<usize, DomainError>;
     /// Get pricing for a model
     fn get_pricing(&self, model_id: &str) -> Option<ModelPricing>;
     /// Calculate cost for tokens
     fn calculate_cost(&self, model_id: &str, input_tokens: u32, output_tokens: u32) -> i64;
 }
 
+/// Trait for model pricing operations
+#[async_trait::async_trait]
+pub trait PricingServiceTrait: Send + Sync {
+    /// Current pricing of every model
+    fn list_current(&self) -> Vec<ModelPricing>;
+    /// All pricing versions of a model, oldest first
+    fn history(&self, model_id: &str) -> Vec<ModelPricing>;
+    /// Add a pricing version
+    async fn set_pricing(&self, pricing: ModelPricing) -> Result<ModelPricing, DomainError>;
+    /// Delete a pricing version
+    async fn delete_version(&self, model_id: &str, id: &PricingId) -> Result<bool, DomainError>;
+    /// Delete every pricing version of a model
+    async fn delete_model(&self, model_id: &str) -> Result<usize, DomainError>;
+    /// Synchronise prices from the configured pricing feed
+    async fn sync_from_feed(&self) -> Result<PricingSyncReport, DomainError>;
+}
+
+/// Trait for model SLO operations
+#[async_trait::async_trait]
+pub trait SloServiceTrait: Send + Sync {
+    /// Every SLO, ordered by model
+    async fn list(&self) -> Result<Vec<Slo>, DomainError>;
+    /// SLO of a model
+    async fn get(&self, model_id: &str) -> Result<Option<Slo>, DomainError>;
+    /// Create or replace the SLO of a model
+    async fn set(&self, slo: Slo) -> Result<Slo, DomainError>;
+    /// Delete the SLO of a model
+    async fn delete(&self, model_id: &str) -> Result<bool, DomainError>;
+    /// Evaluate the SLO of a model now
+    async fn evaluate(&self, model_id: &str) -> Result<Option<SloReport>, DomainError>;
+    /// Evaluate every enabled SLO now
+    async fn evaluate_all(&self) -> Result<Vec<SloReport>, DomainError>;
+    /// Latest report of every evaluated SLO
+    fn reports(&self) -> Vec<SloReport>;
+    /// Latest report of a model's SLO
+    fn report(&self, model_id: &str) -> Option<SloReport>;
+    /// Whether a model's SLO was breached at its latest evaluation
+    fn is_breached(&self, model_id: &str) -> bool;
+}
+
+/// Trait for content policy operations
+#[async_trait::async_trait]
+pub trait ContentPolicyServiceTrait: Send + Sync {
+    /// Every policy, ordered by ID
+    async fn list(&self) -> Result<Vec<ContentPolicy>, DomainError>;
+    /// Policy by ID
+    async fn get(&self, id: &str) -> Result<Option<ContentPolicy>, DomainError>;
+    /// Create a policy
+    async fn create(&self, policy: ContentPolicy) -> Result<ContentPolicy, DomainError>;
+    /// Replace the rules of an existing policy
+    async fn update(&self, policy: ContentPolicy) -> Result<ContentPolicy, DomainError>;
+    /// Delete a policy
+    async fn delete(&self, id: &str) -> Result<bool, DomainError>;
+    /// Evaluate the policies of a team at a stage against a text
+    async fn evaluate(
+        &self,
+        team_id: &str,
+        stage: PolicyStage,
+        text: &str,
+    ) -> Result<Vec<PolicyViolation>, DomainError>;
+}
+
+/// Trait for transform rule operations
+#[async_trait::async_trait]
+pub trait TransformRuleServiceTrait: Send + Sync {
+    /// Every rule, in the order they are applied
+    async fn list(&self) -> Result<Vec<TransformRule>, DomainError>;
+    /// Rule by ID
+    async fn get(&self, id: &str) -> Result<Option<TransformRule>, DomainError>;
+    /// Create a rule
+    async fn create(&self, rule: TransformRule) -> Result<TransformRule, DomainError>;
+    /// Replace the match and actions of an existing rule
+    async fn update(&self, rule: TransformRule) -> Result<TransformRule, DomainError>;
+    /// Delete a rule
+    async fn delete(&self, id: &str) -> Result<bool, DomainError>;
+    /// Changes the matching rules make to a request
+    async fn evaluate(
+        &self,
+        target: &TransformTarget<'_>,
+    ) -> Result<TransformOutcome, DomainError>;
+}
+
 /// Trait for budget service operations (state version to avoid name collision)
 #[async_trait::async_trait]
 pub trait BudgetServiceStateTrait: Send + Sync {
@@ -431,6 +898,14 @@ pub trait BudgetServiceStateTrait: Send + Sync {
         model_id: Option<&str>,
         estimated_cost_micros: i64,
     ) -> Result<BudgetCheckResult, DomainError>;
+    /// Record usage against applicable budgets (with team context)
+    async fn record_usage_with_team(
+        &self,
+        api_key_id: &str,
+        team_id: Option<&str>,
+        model_id: Option<&str>,
+        cost_micros: i64,
+    ) -> Result<Vec<AlertNotification>, DomainError>;
 }
 
 /// Trait for experiment service operations (A/B testing)
@@ -470,14 +945,33 @@ pub trait ExperimentServiceTrait: Send + Sync {
     async fn resume(&self, id: &str) -> Result<Experiment, DomainError>;
     /// Complete an experiment (Active/Paused -> Completed)
     async fn complete(&self, id: &str) -> Result<Experiment, DomainError>;
-    /// Assign a variant for a given model and API key
+    /// Complete active experiments whose sequential tests reached a decision
+    async fn evaluate_auto_stop(&self) -> Result<Vec<ExperimentResult>, DomainError>;
+    /// Roll back active rollouts whose treatment breached their guard
+    async fn evaluate_rollouts(&self) -> Result<Vec<ExperimentResult>, DomainError>;
+    /// Assign a variant for a given model, API key and end user
     async fn assign_variant(
         &self,
         model_id: &str,
         api_key_id: &str,
+        user: Option<&str>,
     ) -> Result<Option<AssignmentResult>, DomainError>;
+    /// Assign a variant for an execution of a workflow, API key and end user
+    async fn assign_workflow_variant(
+        &self,
+        workflow_id: &str,
+        api_key_id: &str,
+        user: Option<&str>,
+    ) -> Result<Option<WorkflowAssignment>, DomainError>;
     /// Record an experiment request
     async fn record(&self, params: RecordExperimentParams) -> Result<(), DomainError>;
+    /// Fold user feedback into the record of a completion
+    async fn record_feedback(
+        &self,
+        record_id: &str,
+        api_key_id: &str,
+        event: FeedbackEvent,
+    ) -> Result<ExperimentRecord, DomainError>;
     /// Get experiment results with statistical analysis
     async fn get_results(&self, id: &str) -> Result<ExperimentResult, DomainError>;
     /// Find experiments by status
@@ -508,6 +1002,13 @@ pub trait TestCaseServiceTrait: Send + Sync {
     async fn delete(&self, id: &str) -> Result<bool, DomainError>;
     /// Execute a test case
     async fn execute(&self, id: &str) -> Result<ExecuteTestCaseResponse, DomainError>;
+    /// Execute a test case against unsaved prompt or workflow versions
+    /// without recording a result
+    async fn evaluate(
+        &self,
+        id: &str,
+        overrides: &ExecutionOverrides,
+    ) -> Result<ExecuteTestCaseResponse, DomainError>;
     /// Get results for a test case
     async fn get_results(
         &self,
@@ -518,6 +1019,163 @@ pub trait TestCaseServiceTrait: Send + Sync {
     async fn get_latest_result(&self, id: &str) -> Result<Option<TestCaseResult>, DomainError>;
 }
 
+/// Trait for test suite service operations
+#[async_trait::async_trait]
+pub trait TestSuiteServiceTrait: Send + Sync {
+    /// Get a test suite by ID
+    async fn get(&self, id: &str) -> Result<Option<TestSuite>, DomainError>;
+    /// List every test suite
+    async fn list(&self) -> Result<Vec<TestSuite>, DomainError>;
+    /// Create a new test suite
+    async fn create(&self, request: CreateTestSuiteRequest) -> Result<TestSuite, DomainError>;
+    /// Update a test suite
+    async fn update(
+        &self,
+        id: &str,
+        request: UpdateTestSuiteRequest,
+    ) -> Result<TestSuite, DomainError>;
+    /// Delete a test suite and its runs
+    async fn delete(&self, id: &str) -> Result<bool, DomainError>;
+    /// Run every case of a suite, optionally overriding the pass threshold
+    async fn run(&self, id: &str, pass_threshold: Option<f64>)
+        -> Result<TestSuiteRun, DomainError>;
+    /// List the runs of a suite, newest first
+    async fn list_runs(
+        &self,
+        id: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<TestSuiteRun>, DomainError>;
+    /// Get a run report by ID
+    async fn get_run(&self, run_id: &str) -> Result<Option<TestSuiteRun>, DomainError>;
+}
+
+/// Trait for assistant service operations
+#[async_trait::async_trait]
+pub trait AssistantServiceTrait: Send + Sync {
+    /// Get an assistant by ID
+    async fn get(&self, id: &str) -> Result<Option<Assistant>, DomainError>;
+    /// List every assistant
+    async fn list(&self) -> Result<Vec<Assistant>, DomainError>;
+    /// Create an assistant
+    async fn create(&self, request: CreateAssistantRequest) -> Result<Assistant, DomainError>;
+    /// Update an assistant
+    async fn update(
+        &self,
+        id: &str,
+        request: UpdateAssistantRequest,
+    ) -> Result<Assistant, DomainError>;
+    /// Delete an assistant
+    async fn delete(&self, id: &str) -> Result<bool, DomainError>;
+    /// Passages of the bound knowledge bases matching a query, among the
+    /// documents the caller may read when given
+    async fn retrieve(
+        &self,
+        assistant: &Assistant,
+        query: &str,
+        caller: Option<&KnowledgeBaseCaller>,
+    ) -> Vec<String>;
+}
+
+/// Trait for MCP tool service operations
+#[async_trait::async_trait]
+pub trait McpToolServiceTrait: Send + Sync {
+    /// Get a tool by name
+    async fn get(&self, id: &str) -> Result<Option<McpTool>, DomainError>;
+    /// List every tool
+    async fn list(&self) -> Result<Vec<McpTool>, DomainError>;
+    /// Create a tool
+    async fn create(&self, request: CreateMcpToolRequest) -> Result<McpTool, DomainError>;
+    /// Update a tool
+    async fn update(&self, id: &str, request: UpdateMcpToolRequest)
+        -> Result<McpTool, DomainError>;
+    /// Delete a tool
+    async fn delete(&self, id: &str) -> Result<bool, DomainError>;
+    /// Search a knowledge base, among the documents the caller may read when
+    /// given
+    async fn search(
+        &self,
+        knowledge_base_id: &str,
+        query: &str,
+        top_k: u32,
+        caller: Option<&KnowledgeBaseCaller>,
+    ) -> Result<Vec<SearchResult>, DomainError>;
+}
+
+/// Trait for fine-tuning job operations
+#[async_trait::async_trait]
+pub trait FineTuneServiceTrait: Send + Sync {
+    /// Get a job by ID
+    async fn get(&self, id: &str) -> Result<Option<FineTuneJob>, DomainError>;
+    /// List every job, newest first
+    async fn list(&self) -> Result<Vec<FineTuneJob>, DomainError>;
+    /// Upload the dataset and start a provider job
+    async fn create(&self, request: CreateFineTuneJobRequest)
+        -> Result<FineTuneJob, DomainError>;
+    /// Fetch the job state from the provider
+    async fn refresh(&self, id: &str) -> Result<FineTuneJob, DomainError>;
+    /// Refresh every running job
+    async fn refresh_active(&self) -> Result<usize, DomainError>;
+    /// Cancel a running job
+    async fn cancel(&self, id: &str) -> Result<FineTuneJob, DomainError>;
+}
+
+/// Trait for dataset service operations
+#[async_trait::async_trait]
+pub trait DatasetServiceTrait: Send + Sync {
+    /// Get a dataset by ID
+    async fn get(&self, id: &str) -> Result<Option<Dataset>, DomainError>;
+    /// List every dataset
+    async fn list(&self) -> Result<Vec<Dataset>, DomainError>;
+    /// Create a dataset with its first version
+    async fn create(&self, request: CreateDatasetRequest) -> Result<Dataset, DomainError>;
+    /// Update the metadata of a dataset
+    async fn update(&self, id: &str, request: UpdateDatasetRequest)
+        -> Result<Dataset, DomainError>;
+    /// Delete a dataset and its versions
+    async fn delete(&self, id: &str) -> Result<bool, DomainError>;
+    /// Store rows as the next version of a dataset
+    async fn add_version(
+        &self,
+        id: &str,
+        rows: Vec<DatasetRow>,
+        note: Option<String>,
+    ) -> Result<DatasetVersion, DomainError>;
+    /// Get a version of a dataset
+    async fn get_version(&self, id: &str, version: u32)
+        -> Result<Option<DatasetVersion>, DomainError>;
+    /// List the versions of a dataset, newest first
+    async fn list_versions(&self, id: &str) -> Result<Vec<DatasetVersion>, DomainError>;
+    /// Pin a dataset reference, defaulting to the latest version
+    async fn resolve(&self, id: &str, version: Option<u32>) -> Result<DatasetRef, DomainError>;
+    /// Rows of a pinned dataset version
+    async fn rows(&self, reference: &DatasetRef) -> Result<Vec<DatasetRow>, DomainError>;
+}
+
+/// Trait for regression check operations
+#[async_trait::async_trait]
+pub trait RegressionServiceTrait: Send + Sync {
+    /// Compare the linked test cases of a prompt against new content
+    async fn check_prompt(
+        &self,
+        id: &str,
+        content: &str,
+        policy: &RegressionPolicy,
+    ) -> Result<Option<RegressionReport>, DomainError>;
+    /// Compare the linked test cases of a workflow against an update
+    async fn check_workflow(
+        &self,
+        id: &str,
+        request: UpdateWorkflowRequest,
+        policy: &RegressionPolicy,
+    ) -> Result<Option<RegressionReport>, DomainError>;
+    /// List the reports of a prompt or workflow, newest first
+    async fn list_reports(
+        &self,
+        subject: RegressionSubject,
+        resource_id: &str,
+    ) -> Result<Vec<RegressionReport>, DomainError>;
+}
+
 /// Trait for configuration service operations
 #[async_trait::async_trait]
 pub trait ConfigServiceTrait: Send + Sync {
@@ -558,6 +1216,14 @@ pub trait ExecutionLogServiceTrait: Send + Sync {
     async fn stats(&self, query: &ExecutionLogQuery) -> Result<ExecutionStats, DomainError>;
     /// Update an existing execution log
     async fn update(&self, log: &ExecutionLog) -> Result<(), DomainError>;
+    /// Receive every execution log recorded or updated from now on
+    fn subscribe(&self) -> tokio::sync::broadcast::Receiver<ExecutionLog>;
+    /// Record the payloads of a request when its team is sampled for payload capture
+    async fn capture_payload(
+        &self,
+        team_id: &str,
+        params: RecordExecutionParams,
+    ) -> Result<Option<ExecutionLog>, DomainError>;
     /// Record a pending ingestion operation
     async fn record_pending_ingestion(
         &self,
@@ -588,6 +1254,12 @@ pub trait WebhookServiceStateTrait: Send + Sync {
         limit: usize,
         offset: usize,
     ) -> Result<Vec<WebhookDelivery>, DomainError>;
+    /// Send the payload of a delivery again as a new delivery
+    async fn redeliver(
+        &self,
+        webhook_id: &str,
+        delivery_id: &str,
+    ) -> Result<WebhookDelivery, DomainError>;
     /// Reset a webhook's failure count
     async fn reset_webhook(&self, id: &str) -> Result<Webhook, DomainError>;
     /// Clean up old deliveries
@@ -649,6 +1321,24 @@ impl<S: Storage<Prompt> + 'static> PromptServiceTrait for PromptService<S> {
         PromptService::render_by_id(self, id, variables.clone()).await
     }
 
+    async fn render_variant(
+        &self,
+        id: &str,
+        version: Option<u32>,
+        locale: Option<&str>,
+        variables: &std::collections::HashMap<String, String>,
+    ) -> Result<String, DomainError> {
+        PromptService::render_variant(self, id, version, locale, variables.clone()).await
+    }
+
+    async fn variable_schema(&self, id: &str) -> Result<Vec<PromptVariable>, DomainError> {
+        PromptService::variable_schema(self, id).await
+    }
+
+    async fn expand_partials(&self, content: &str) -> Result<String, DomainError> {
+        PromptService::expand_partials(self, content).await
+    }
+
     async fn revert(&self, id: &str, version: u32) -> Result<Prompt, DomainError> {
         PromptService::revert(self, id, version).await
     }
@@ -656,8 +1346,12 @@ impl<S: Storage<Prompt> + 'static> PromptServiceTrait for PromptService<S> {
 
 #[async_trait::async_trait]
 impl<R: ApiKeyRepository + 'static> ApiKeyServiceTrait for ApiKeyService<R> {
-    async fn validate(&self, key: &str) -> Result<Option<ApiKey>, DomainError> {
-        ApiKeyService::validate(self, key).await
+    async fn verify(&self, key: &str) -> Result<Option<ApiKey>, DomainError> {
+        ApiKeyService::verify(self, key).await
+    }
+
+    async fn record_usage(&self, key: &ApiKey, client_ip: Option<&str>) {
+        ApiKeyService::record_usage(self, key, client_ip).await
     }
 
     async fn get(&self, id: &str) -> Result<Option<ApiKey>, DomainError> {
@@ -742,6 +1436,84 @@ impl<R: ApiKeyRepository + 'static> ApiKeyServiceTrait for ApiKeyService<R> {
         ApiKeyService::revoke(self, &key_id).await?;
         Ok(())
     }
+
+    async fn set_expiration(
+        &self,
+        id: &str,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<ApiKey, DomainError> {
+        let key_id = crate::domain::api_key::ApiKeyId::new(id)
+            .map_err(|e| DomainError::validation(e.to_string()))?;
+        ApiKeyService::set_expiration(self, &key_id, expires_at).await
+    }
+
+    async fn rotate(
+        &self,
+        id: &str,
+        grace_period_secs: u64,
+    ) -> Result<RotateApiKeyResult, DomainError> {
+        let key_id = crate::domain::api_key::ApiKeyId::new(id)
+            .map_err(|e| DomainError::validation(e.to_string()))?;
+        let new_id = crate::domain::api_key::ApiKeyId::new(uuid::Uuid::new_v4().to_string())
+            .map_err(|e| DomainError::validation(e.to_string()))?;
+        let grace_period = i64::try_from(grace_period_secs)
+            .ok()
+            .and_then(chrono::Duration::try_seconds)
+            .ok_or_else(|| DomainError::validation("Grace period is too large"))?;
+        ApiKeyService::rotate(self, &key_id, new_id, grace_period).await
+    }
+
+    async fn set_allowed_cidrs(
+        &self,
+        id: &str,
+        allowed_cidrs: Vec<IpNetwork>,
+    ) -> Result<ApiKey, DomainError> {
+        let key_id = crate::domain::api_key::ApiKeyId::new(id)
+            .map_err(|e| DomainError::validation(e.to_string()))?;
+        ApiKeyService::set_allowed_cidrs(self, &key_id, allowed_cidrs).await
+    }
+
+    async fn set_injection_action(
+        &self,
+        id: &str,
+        injection_action: Option<InjectionAction>,
+    ) -> Result<ApiKey, DomainError> {
+        let key_id = crate::domain::api_key::ApiKeyId::new(id)
+            .map_err(|e| DomainError::validation(e.to_string()))?;
+        ApiKeyService::set_injection_action(self, &key_id, injection_action).await
+    }
+
+    async fn set_no_log(&self, id: &str, no_log: bool) -> Result<ApiKey, DomainError> {
+        let key_id = crate::domain::api_key::ApiKeyId::new(id)
+            .map_err(|e| DomainError::validation(e.to_string()))?;
+        ApiKeyService::set_no_log(self, &key_id, no_log).await
+    }
+
+    async fn count_for_team(&self, team_id: &TeamId) -> Result<u64, DomainError> {
+        ApiKeyService::count_for_team(self, team_id).await
+    }
+
+    async fn set_rate_limits(
+        &self,
+        id: &str,
+        rate_limits: RateLimitConfig,
+    ) -> Result<ApiKey, DomainError> {
+        let key_id = crate::domain::api_key::ApiKeyId::new(id)
+            .map_err(|e| DomainError::validation(e.to_string()))?;
+        ApiKeyService::update_rate_limits(self, &key_id, rate_limits).await
+    }
+
+    async fn check_rate_limit(&self, key: &ApiKey) -> RateLimitResult {
+        ApiKeyService::check_rate_limit(self, key, None).await
+    }
+
+    async fn record_consumption(&self, key: &ApiKey, tokens: u32, cost_micros: i64) {
+        ApiKeyService::record_consumption(self, key, tokens, cost_micros).await
+    }
+
+    async fn quota_usage(&self, key: &ApiKey) -> Vec<QuotaWindow> {
+        ApiKeyService::quota_usage(self, key).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -769,8 +1541,72 @@ impl WorkflowServiceTrait for WorkflowService {
     async fn execute(&self, id: &str, input: Value) -> Result<WorkflowResult, DomainError> {
         WorkflowService::execute(self, id, input).await
     }
+
+    async fn execute_as(
+        &self,
+        id: &str,
+        input: Value,
+        caller: KnowledgeBaseCaller,
+    ) -> Result<WorkflowResult, DomainError> {
+        WorkflowService::execute_as(self, id, input, caller).await
+    }
+
+    async fn execute_cancellable(
+        &self,
+        id: &str,
+        input: Value,
+        caller: KnowledgeBaseCaller,
+        cancellation: CancellationToken,
+        step_progress: StepProgress,
+    ) -> Result<WorkflowResult, DomainError> {
+        WorkflowService::execute_cancellable(self, id, input, caller, cancellation, step_progress)
+            .await
+    }
+
+    async fn execute_variant(
+        &self,
+        id: &str,
+        step_overrides: &[StepOverride],
+        input: Value,
+        caller: KnowledgeBaseCaller,
+        cancellation: CancellationToken,
+        step_progress: StepProgress,
+    ) -> Result<WorkflowResult, DomainError> {
+        WorkflowService::execute_variant(
+            self,
+            id,
+            step_overrides,
+            input,
+            caller,
+            cancellation,
+            step_progress,
+        )
+        .await
+    }
+
+    async fn preview_update(
+        &self,
+        id: &str,
+        request: UpdateWorkflowRequest,
+    ) -> Result<Workflow, DomainError> {
+        WorkflowService::preview_update(self, id, request).await
+    }
+
+    async fn execute_definition(
+        &self,
+        workflow: &Workflow,
+        input: Value,
+    ) -> Result<WorkflowResult, DomainError> {
+        WorkflowService::execute_definition(self, workflow, input).await
+    }
+
+    async fn count_for_team(&self, team_id: &TeamId) -> Result<u64, DomainError> {
+        WorkflowService::count_for_team(self, team_id).await
+    }
 }
 
+// `OperationService` has no inherent methods: calls name the infrastructure
+// trait, as `OperationService::method` would resolve to this impl and recurse
 #[async_trait::async_trait]
 impl<R: OperationRepository + 'static> OperationServiceTrait for OperationService<R> {
     async fn create_pending(
@@ -779,40 +1615,81 @@ impl<R: OperationRepository + 'static> OperationServiceTrait for OperationServic
         input: Value,
         metadata: Value,
     ) -> Result<Operation, DomainError> {
-        OperationService::create_pending(self, op_type, input, metadata).await
+        crate::infrastructure::services::OperationServiceTrait::create_pending(self, op_type, input, metadata).await
     }
 
     async fn get(&self, id: &str) -> Result<Option<Operation>, DomainError> {
-        OperationService::get(self, id).await
+        crate::infrastructure::services::OperationServiceTrait::get(self, id).await
     }
 
     async fn get_batch(&self, ids: &[String]) -> Result<Vec<Operation>, DomainError> {
-        OperationService::get_batch(self, ids).await
+        crate::infrastructure::services::OperationServiceTrait::get_batch(self, ids).await
     }
 
     async fn mark_running(&self, id: &str) -> Result<Operation, DomainError> {
-        OperationService::mark_running(self, id).await
+        crate::infrastructure::services::OperationServiceTrait::mark_running(self, id).await
+    }
+
+    async fn update_progress(&self, id: &str, progress: Value) -> Result<Operation, DomainError> {
+        crate::infrastructure::services::OperationServiceTrait::update_progress(self, id, progress).await
+    }
+
+    async fn update_completion(
+        &self,
+        id: &str,
+        completed: u64,
+        total: u64,
+        unit: &str,
+    ) -> Result<Operation, DomainError> {
+        crate::infrastructure::services::OperationServiceTrait::update_completion(
+            self, id, completed, total, unit,
+        )
+        .await
     }
 
     async fn mark_completed(&self, id: &str, result: Value) -> Result<Operation, DomainError> {
-        OperationService::mark_completed(self, id, result).await
+        crate::infrastructure::services::OperationServiceTrait::mark_completed(self, id, result).await
     }
 
     async fn mark_failed(&self, id: &str, error: String) -> Result<Operation, DomainError> {
-        OperationService::mark_failed(self, id, error).await
+        crate::infrastructure::services::OperationServiceTrait::mark_failed(self, id, error).await
     }
 
     async fn cancel(&self, id: &str) -> Result<Operation, DomainError> {
-        OperationService::cancel(self, id).await
+        crate::infrastructure::services::OperationServiceTrait::cancel(self, id).await
     }
 
-    async fn cleanup_old(&self) -> Result<u64, DomainError> {
-        OperationService::cleanup_old(self).await
+    fn cancellation_token(&self, id: &str) -> CancellationToken {
+        crate::infrastructure::services::OperationServiceTrait::cancellation_token(self, id)
     }
-}
 
-#[async_trait::async_trait]
-impl<R: UserRepository + 'static, H: PasswordHasher + 'static> UserServiceTrait
+    async fn heartbeat(&self) -> Result<usize, DomainError> {
+        crate::infrastructure::services::OperationServiceTrait::heartbeat(self).await
+    }
+
+    async fn reap_orphaned(
+        &self,
+        requeueable: &[OperationType],
+    ) -> Result<ReapedOperations, DomainError> {
+        crate::infrastructure::services::OperationServiceTrait::reap_orphaned(self, requeueable)
+            .await
+    }
+
+    async fn cleanup_old(&self) -> Result<u64, DomainError> {
+        crate::infrastructure::services::OperationServiceTrait::cleanup_old(self).await
+    }
+
+    async fn list(&self) -> Result<Vec<Operation>, DomainError> {
+        crate::infrastructure::services::OperationServiceTrait::list(self).await
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        crate::infrastructure::services::OperationServiceTrait::delete(self, id).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<R: UserRepository + 'static, H: PasswordHasher + 'static> UserServiceTrait
     for UserService<R, H>
 {
     async fn authenticate(
@@ -823,6 +1700,15 @@ impl<R: UserRepository + 'static, H: PasswordHasher + 'static> UserServiceTrait
         UserService::authenticate(self, username, password).await
     }
 
+    async fn login(
+        &self,
+        username: &str,
+        password: &str,
+        mfa_code: Option<&str>,
+    ) -> Result<LoginOutcome, DomainError> {
+        UserService::login(self, username, password, mfa_code).await
+    }
+
     async fn get(&self, id: &str) -> Result<Option<User>, DomainError> {
         UserService::get(self, id).await
     }
@@ -859,6 +1745,38 @@ impl<R: UserRepository + 'static, H: PasswordHasher + 'static> UserServiceTrait
         UserService::activate(self, id).await
     }
 
+    async fn set_role(&self, id: &str, role_id: Option<RoleId>) -> Result<User, DomainError> {
+        UserService::set_role(self, id, role_id).await
+    }
+
+    async fn begin_mfa_enrollment(&self, id: &str) -> Result<MfaEnrollment, DomainError> {
+        UserService::begin_mfa_enrollment(self, id).await
+    }
+
+    async fn confirm_mfa_enrollment(
+        &self,
+        id: &str,
+        code: &str,
+    ) -> Result<Vec<String>, DomainError> {
+        UserService::confirm_mfa_enrollment(self, id, code).await
+    }
+
+    async fn regenerate_recovery_codes(
+        &self,
+        id: &str,
+        code: &str,
+    ) -> Result<Vec<String>, DomainError> {
+        UserService::regenerate_recovery_codes(self, id, code).await
+    }
+
+    async fn disable_mfa(&self, id: &str, code: &str) -> Result<User, DomainError> {
+        UserService::disable_mfa(self, id, code).await
+    }
+
+    async fn reset_mfa(&self, id: &str) -> Result<User, DomainError> {
+        UserService::reset_mfa(self, id).await
+    }
+
     async fn delete(&self, id: &str) -> Result<bool, DomainError> {
         UserService::delete(self, id).await
     }
@@ -869,6 +1787,14 @@ impl JwtServiceTrait for JwtService {
         JwtGenerator::generate(self, user)
     }
 
+    fn generate_for_service_account(
+        &self,
+        account: &ServiceAccount,
+        scopes: &[Permission],
+    ) -> Result<String, DomainError> {
+        JwtGenerator::generate_for_service_account(self, account, scopes)
+    }
+
     fn validate(&self, token: &str) -> Result<JwtClaims, DomainError> {
         JwtGenerator::validate(self, token)
     }
@@ -883,6 +1809,14 @@ impl JwtServiceTrait for JwksJwtService {
         JwtGenerator::generate(self, user)
     }
 
+    fn generate_for_service_account(
+        &self,
+        account: &ServiceAccount,
+        scopes: &[Permission],
+    ) -> Result<String, DomainError> {
+        JwtGenerator::generate_for_service_account(self, account, scopes)
+    }
+
     fn validate(&self, token: &str) -> Result<JwtClaims, DomainError> {
         JwtGenerator::validate(self, token)
     }
@@ -931,6 +1865,142 @@ impl<R: TeamRepository + 'static> TeamServiceTrait for TeamService<R> {
     }
 }
 
+#[async_trait::async_trait]
+impl<R: OrganizationRepository + 'static> OrganizationServiceTrait for OrganizationService<R> {
+    async fn get(&self, id: &str) -> Result<Option<Organization>, DomainError> {
+        OrganizationService::get(self, id).await
+    }
+
+    async fn list(&self) -> Result<Vec<Organization>, DomainError> {
+        OrganizationService::list(self).await
+    }
+
+    async fn create(
+        &self,
+        request: CreateOrganizationRequest,
+    ) -> Result<Organization, DomainError> {
+        OrganizationService::create(self, request).await
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        request: UpdateOrganizationRequest,
+    ) -> Result<Organization, DomainError> {
+        OrganizationService::update(self, id, request).await
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        OrganizationService::delete(self, id).await
+    }
+
+    async fn list_teams(&self, id: &str) -> Result<Vec<Team>, DomainError> {
+        OrganizationService::list_teams(self, id).await
+    }
+
+    async fn add_team(&self, id: &str, team_id: &str) -> Result<Team, DomainError> {
+        OrganizationService::add_team(self, id, team_id).await
+    }
+
+    async fn remove_team(&self, id: &str, team_id: &str) -> Result<Team, DomainError> {
+        OrganizationService::remove_team(self, id, team_id).await
+    }
+
+    async fn get_for_team(&self, team_id: &TeamId) -> Result<Option<Organization>, DomainError> {
+        OrganizationService::get_for_team(self, team_id).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<R: RoleRepository + 'static> RoleServiceTrait for RoleService<R> {
+    async fn get(&self, id: &str) -> Result<Option<Role>, DomainError> {
+        RoleService::get(self, id).await
+    }
+
+    async fn list(&self) -> Result<Vec<Role>, DomainError> {
+        RoleService::list(self).await
+    }
+
+    async fn create(&self, request: CreateRoleRequest) -> Result<Role, DomainError> {
+        RoleService::create(self, request).await
+    }
+
+    async fn update(&self, id: &str, request: UpdateRoleRequest) -> Result<Role, DomainError> {
+        RoleService::update(self, id, request).await
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        RoleService::delete(self, id).await
+    }
+
+    async fn resolve_for_user(&self, user: &User) -> Result<Role, DomainError> {
+        RoleService::resolve_for_user(self, user).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<R: ServiceAccountRepository + 'static> ServiceAccountServiceTrait
+    for ServiceAccountService<R>
+{
+    async fn get(&self, id: &str) -> Result<Option<ServiceAccount>, DomainError> {
+        ServiceAccountService::get(self, id).await
+    }
+
+    async fn list(&self) -> Result<Vec<ServiceAccount>, DomainError> {
+        ServiceAccountService::list(self).await
+    }
+
+    async fn create(
+        &self,
+        request: CreateServiceAccountRequest,
+    ) -> Result<ServiceAccountWithSecret, DomainError> {
+        ServiceAccountService::create(self, request).await
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        request: UpdateServiceAccountRequest,
+    ) -> Result<ServiceAccount, DomainError> {
+        ServiceAccountService::update(self, id, request).await
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        ServiceAccountService::delete(self, id).await
+    }
+
+    async fn rotate_secret(&self, id: &str) -> Result<ServiceAccountWithSecret, DomainError> {
+        ServiceAccountService::rotate_secret(self, id).await
+    }
+
+    async fn authenticate(
+        &self,
+        client_id: &str,
+        client_secret: &str,
+    ) -> Result<Option<ServiceAccount>, DomainError> {
+        ServiceAccountService::authenticate(self, client_id, client_secret).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<R: AuditLogRepository + 'static> AuditLogServiceTrait for AuditLogService<R> {
+    async fn record(&self, log: AuditLog) -> Result<AuditLog, DomainError> {
+        AuditLogService::record(self, log).await
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<AuditLog>, DomainError> {
+        AuditLogService::get(self, id).await
+    }
+
+    async fn list(&self, query: &AuditLogQuery) -> Result<Vec<AuditLog>, DomainError> {
+        AuditLogService::list(self, query).await
+    }
+
+    async fn count(&self, query: &AuditLogQuery) -> Result<usize, DomainError> {
+        AuditLogService::count(self, query).await
+    }
+}
+
 #[async_trait::async_trait]
 impl<R: StoredCredentialRepository + 'static> CredentialServiceTrait for CredentialService<R> {
     async fn get(&self, id: &str) -> Result<Option<StoredCredential>, DomainError> {
@@ -1028,6 +2098,10 @@ impl KnowledgeBaseServiceTrait for KnowledgeBaseService {
     async fn exists(&self, id: &str) -> Result<bool, DomainError> {
         KnowledgeBaseService::exists(self, id).await
     }
+
+    async fn count_for_team(&self, team_id: &TeamId) -> Result<u64, DomainError> {
+        KnowledgeBaseService::count_for_team(self, team_id).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -1040,6 +2114,15 @@ impl IngestionServiceTrait for IngestionService {
         IngestionService::ingest(self, kb_id, request).await
     }
 
+    async fn ingest_with_progress(
+        &self,
+        kb_id: &str,
+        request: IngestDocumentRequest,
+        on_stage: &(dyn Fn(IngestionStage, Option<usize>) + Send + Sync),
+    ) -> Result<crate::domain::ingestion::IngestionResult, DomainError> {
+        IngestionService::ingest_with_progress(self, kb_id, request, on_stage).await
+    }
+
     async fn list_sources(
         &self,
         kb_id: &str,
@@ -1059,14 +2142,38 @@ impl IngestionServiceTrait for IngestionService {
         IngestionService::document_count(self, kb_id).await
     }
 
+    async fn get_documents_by_filter(
+        &self,
+        kb_id: &str,
+        filter: MetadataFilter,
+    ) -> Result<Vec<StoredDocument>, DomainError> {
+        IngestionService::get_documents_by_filter(self, kb_id, filter).await
+    }
+
     async fn delete_by_source(&self, kb_id: &str, source: &str) -> Result<usize, DomainError> {
         IngestionService::delete_by_source(self, kb_id, source).await
     }
 
+    async fn delete_by_filter(
+        &self,
+        kb_id: &str,
+        filter: MetadataFilter,
+    ) -> Result<usize, DomainError> {
+        IngestionService::delete_by_filter(self, kb_id, filter).await
+    }
+
     async fn ensure_schema(&self, kb_id: &str) -> Result<(), DomainError> {
         IngestionService::ensure_schema(self, kb_id).await
     }
 
+    async fn index_status(&self, kb_id: &str) -> Result<Option<VectorIndexStatus>, DomainError> {
+        IngestionService::index_status(self, kb_id).await
+    }
+
+    async fn reindex(&self, kb_id: &str) -> Result<VectorIndexStatus, DomainError> {
+        IngestionService::reindex(self, kb_id).await
+    }
+
     async fn ingest_document(
         &self,
         kb_id: &str,
@@ -1118,6 +2225,18 @@ impl IngestionServiceTrait for IngestionService {
     ) -> Result<bool, DomainError> {
         IngestionService::enable_document(self, kb_id, document_id).await
     }
+
+    async fn document_usage(&self, kb_ids: &[String]) -> Result<DocumentUsage, DomainError> {
+        IngestionService::document_usage(self, kb_ids).await
+    }
+
+    async fn copy_documents(
+        &self,
+        source_kb_id: &str,
+        target_kb_id: &str,
+    ) -> Result<DocumentCopyResult, DomainError> {
+        IngestionService::copy_documents(self, source_kb_id, target_kb_id).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -1146,6 +2265,15 @@ impl<R: UsageRepository + 'static> UsageServiceTrait for UsageTrackingService<R>
         UsageTrackingServiceTrait::summary(self, query).await
     }
 
+    async fn timeseries(
+        &self,
+        query: &UsageQuery,
+        bucket: TimeBucket,
+        group_by: Option<UsageGroupBy>,
+    ) -> Result<Vec<UsageBucket>, DomainError> {
+        UsageTrackingServiceTrait::timeseries(self, query, bucket, group_by).await
+    }
+
     async fn delete_before(&self, timestamp: u64) -> Result<usize, DomainError> {
         UsageTrackingServiceTrait::delete_before(self, timestamp).await
     }
@@ -1154,8 +2282,12 @@ impl<R: UsageRepository + 'static> UsageServiceTrait for UsageTrackingService<R>
         UsageTrackingServiceTrait::delete_by_api_key(self, api_key_id).await
     }
 
+    async fn delete_by_tag(&self, key: &str, value: &str) -> Result<usize, DomainError> {
+        UsageTrackingServiceTrait::delete_by_tag(self, key, value).await
+    }
+
     fn get_pricing(&self, model_id: &str) -> Option<ModelPricing> {
-        UsageTrackingServiceTrait::get_pricing(self, model_id).cloned()
+        UsageTrackingServiceTrait::get_pricing(self, model_id)
     }
 
     fn calculate_cost(&self, model_id: &str, input_tokens: u32, output_tokens: u32) -> i64 {
@@ -1163,6 +2295,134 @@ impl<R: UsageRepository + 'static> UsageServiceTrait for UsageTrackingService<R>
     }
 }
 
+#[async_trait::async_trait]
+impl<R: PricingRepository + 'static> PricingServiceTrait for PricingService<R> {
+    fn list_current(&self) -> Vec<ModelPricing> {
+        PricingService::list_current(self)
+    }
+
+    fn history(&self, model_id: &str) -> Vec<ModelPricing> {
+        PricingService::history(self, model_id)
+    }
+
+    async fn set_pricing(&self, pricing: ModelPricing) -> Result<ModelPricing, DomainError> {
+        PricingService::set_pricing(self, pricing).await
+    }
+
+    async fn delete_version(&self, model_id: &str, id: &PricingId) -> Result<bool, DomainError> {
+        PricingService::delete_version(self, model_id, id).await
+    }
+
+    async fn delete_model(&self, model_id: &str) -> Result<usize, DomainError> {
+        PricingService::delete_model(self, model_id).await
+    }
+
+    async fn sync_from_feed(&self) -> Result<PricingSyncReport, DomainError> {
+        PricingService::sync_from_feed(self).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<R: SloRepository + 'static> SloServiceTrait for SloService<R> {
+    async fn list(&self) -> Result<Vec<Slo>, DomainError> {
+        SloService::list(self).await
+    }
+
+    async fn get(&self, model_id: &str) -> Result<Option<Slo>, DomainError> {
+        SloService::get(self, model_id).await
+    }
+
+    async fn set(&self, slo: Slo) -> Result<Slo, DomainError> {
+        SloService::set(self, slo).await
+    }
+
+    async fn delete(&self, model_id: &str) -> Result<bool, DomainError> {
+        SloService::delete(self, model_id).await
+    }
+
+    async fn evaluate(&self, model_id: &str) -> Result<Option<SloReport>, DomainError> {
+        SloService::evaluate(self, model_id, Utc::now().timestamp().max(0) as u64).await
+    }
+
+    async fn evaluate_all(&self) -> Result<Vec<SloReport>, DomainError> {
+        SloService::evaluate_all(self, Utc::now().timestamp().max(0) as u64).await
+    }
+
+    fn reports(&self) -> Vec<SloReport> {
+        SloService::reports(self)
+    }
+
+    fn report(&self, model_id: &str) -> Option<SloReport> {
+        SloService::report(self, model_id)
+    }
+
+    fn is_breached(&self, model_id: &str) -> bool {
+        SloService::is_breached(self, model_id)
+    }
+}
+
+#[async_trait::async_trait]
+impl ContentPolicyServiceTrait for ContentPolicyService {
+    async fn list(&self) -> Result<Vec<ContentPolicy>, DomainError> {
+        ContentPolicyService::list(self).await
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<ContentPolicy>, DomainError> {
+        ContentPolicyService::get(self, id).await
+    }
+
+    async fn create(&self, policy: ContentPolicy) -> Result<ContentPolicy, DomainError> {
+        ContentPolicyService::create(self, policy).await
+    }
+
+    async fn update(&self, policy: ContentPolicy) -> Result<ContentPolicy, DomainError> {
+        ContentPolicyService::update(self, policy).await
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        ContentPolicyService::delete(self, id).await
+    }
+
+    async fn evaluate(
+        &self,
+        team_id: &str,
+        stage: PolicyStage,
+        text: &str,
+    ) -> Result<Vec<PolicyViolation>, DomainError> {
+        ContentPolicyService::evaluate(self, team_id, stage, text).await
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformRuleServiceTrait for TransformRuleService {
+    async fn list(&self) -> Result<Vec<TransformRule>, DomainError> {
+        TransformRuleService::list(self).await
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<TransformRule>, DomainError> {
+        TransformRuleService::get(self, id).await
+    }
+
+    async fn create(&self, rule: TransformRule) -> Result<TransformRule, DomainError> {
+        TransformRuleService::create(self, rule).await
+    }
+
+    async fn update(&self, rule: TransformRule) -> Result<TransformRule, DomainError> {
+        TransformRuleService::update(self, rule).await
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        TransformRuleService::delete(self, id).await
+    }
+
+    async fn evaluate(
+        &self,
+        target: &TransformTarget<'_>,
+    ) -> Result<TransformOutcome, DomainError> {
+        TransformRuleService::evaluate(self, target).await
+    }
+}
+
 #[async_trait::async_trait]
 impl<R: BudgetRepository + 'static> BudgetServiceStateTrait for BudgetService<R> {
     async fn create(&self, budget: Budget) -> Result<Budget, DomainError> {
@@ -1214,6 +2474,17 @@ impl<R: BudgetRepository + 'static> BudgetServiceStateTrait for BudgetService<R>
         )
         .await
     }
+
+    async fn record_usage_with_team(
+        &self,
+        api_key_id: &str,
+        team_id: Option<&str>,
+        model_id: Option<&str>,
+        cost_micros: i64,
+    ) -> Result<Vec<AlertNotification>, DomainError> {
+        BudgetServiceTrait::record_usage_with_team(self, api_key_id, team_id, model_id, cost_micros)
+            .await
+    }
 }
 
 #[async_trait::async_trait]
@@ -1278,18 +2549,45 @@ impl<R: ExperimentRepository + 'static, RR: ExperimentRecordRepository + 'static
         ExperimentService::complete(self, id).await
     }
 
+    async fn evaluate_auto_stop(&self) -> Result<Vec<ExperimentResult>, DomainError> {
+        ExperimentService::evaluate_auto_stop(self).await
+    }
+
+    async fn evaluate_rollouts(&self) -> Result<Vec<ExperimentResult>, DomainError> {
+        ExperimentService::evaluate_rollouts(self).await
+    }
+
     async fn assign_variant(
         &self,
         model_id: &str,
         api_key_id: &str,
+        user: Option<&str>,
     ) -> Result<Option<AssignmentResult>, DomainError> {
-        ExperimentService::assign_variant(self, model_id, api_key_id).await
+        ExperimentService::assign_variant(self, model_id, api_key_id, user).await
+    }
+
+    async fn assign_workflow_variant(
+        &self,
+        workflow_id: &str,
+        api_key_id: &str,
+        user: Option<&str>,
+    ) -> Result<Option<WorkflowAssignment>, DomainError> {
+        ExperimentService::assign_workflow_variant(self, workflow_id, api_key_id, user).await
     }
 
     async fn record(&self, params: RecordExperimentParams) -> Result<(), DomainError> {
         ExperimentService::record(self, params).await
     }
 
+    async fn record_feedback(
+        &self,
+        record_id: &str,
+        api_key_id: &str,
+        event: FeedbackEvent,
+    ) -> Result<ExperimentRecord, DomainError> {
+        ExperimentService::record_feedback(self, record_id, api_key_id, event).await
+    }
+
     async fn get_results(&self, id: &str) -> Result<ExperimentResult, DomainError> {
         ExperimentService::get_results(self, id).await
     }
@@ -1338,6 +2636,14 @@ impl<R: TestCaseRepository + 'static, RR: TestCaseResultRepository + 'static> Te
         TestCaseService::execute(self, id).await
     }
 
+    async fn evaluate(
+        &self,
+        id: &str,
+        overrides: &ExecutionOverrides,
+    ) -> Result<ExecuteTestCaseResponse, DomainError> {
+        TestCaseService::evaluate(self, id, overrides).await
+    }
+
     async fn get_results(
         &self,
         id: &str,
@@ -1351,6 +2657,241 @@ impl<R: TestCaseRepository + 'static, RR: TestCaseResultRepository + 'static> Te
     }
 }
 
+#[async_trait::async_trait]
+impl<R: TestSuiteRepository + 'static> TestSuiteServiceTrait for TestSuiteService<R> {
+    async fn get(&self, id: &str) -> Result<Option<TestSuite>, DomainError> {
+        TestSuiteService::get(self, id).await
+    }
+
+    async fn list(&self) -> Result<Vec<TestSuite>, DomainError> {
+        TestSuiteService::list(self).await
+    }
+
+    async fn create(&self, request: CreateTestSuiteRequest) -> Result<TestSuite, DomainError> {
+        TestSuiteService::create(self, request).await
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        request: UpdateTestSuiteRequest,
+    ) -> Result<TestSuite, DomainError> {
+        TestSuiteService::update(self, id, request).await
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        TestSuiteService::delete(self, id).await
+    }
+
+    async fn run(
+        &self,
+        id: &str,
+        pass_threshold: Option<f64>,
+    ) -> Result<TestSuiteRun, DomainError> {
+        TestSuiteService::run(self, id, pass_threshold).await
+    }
+
+    async fn list_runs(
+        &self,
+        id: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<TestSuiteRun>, DomainError> {
+        TestSuiteService::list_runs(self, id, limit).await
+    }
+
+    async fn get_run(&self, run_id: &str) -> Result<Option<TestSuiteRun>, DomainError> {
+        TestSuiteService::get_run(self, run_id).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<R: AssistantRepository + 'static> AssistantServiceTrait for AssistantService<R> {
+    async fn get(&self, id: &str) -> Result<Option<Assistant>, DomainError> {
+        AssistantService::get(self, id).await
+    }
+
+    async fn list(&self) -> Result<Vec<Assistant>, DomainError> {
+        AssistantService::list(self).await
+    }
+
+    async fn create(&self, request: CreateAssistantRequest) -> Result<Assistant, DomainError> {
+        AssistantService::create(self, request).await
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        request: UpdateAssistantRequest,
+    ) -> Result<Assistant, DomainError> {
+        AssistantService::update(self, id, request).await
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        AssistantService::delete(self, id).await
+    }
+
+    async fn retrieve(
+        &self,
+        assistant: &Assistant,
+        query: &str,
+        caller: Option<&KnowledgeBaseCaller>,
+    ) -> Vec<String> {
+        AssistantService::retrieve(self, assistant, query, caller).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<R: McpToolRepository + 'static> McpToolServiceTrait for McpToolService<R> {
+    async fn get(&self, id: &str) -> Result<Option<McpTool>, DomainError> {
+        McpToolService::get(self, id).await
+    }
+
+    async fn list(&self) -> Result<Vec<McpTool>, DomainError> {
+        McpToolService::list(self).await
+    }
+
+    async fn create(&self, request: CreateMcpToolRequest) -> Result<McpTool, DomainError> {
+        McpToolService::create(self, request).await
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        request: UpdateMcpToolRequest,
+    ) -> Result<McpTool, DomainError> {
+        McpToolService::update(self, id, request).await
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        McpToolService::delete(self, id).await
+    }
+
+    async fn search(
+        &self,
+        knowledge_base_id: &str,
+        query: &str,
+        top_k: u32,
+        caller: Option<&KnowledgeBaseCaller>,
+    ) -> Result<Vec<SearchResult>, DomainError> {
+        McpToolService::search(self, knowledge_base_id, query, top_k, caller).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<R: FineTuneJobRepository + 'static> FineTuneServiceTrait for FineTuneService<R> {
+    async fn get(&self, id: &str) -> Result<Option<FineTuneJob>, DomainError> {
+        FineTuneService::get(self, id).await
+    }
+
+    async fn list(&self) -> Result<Vec<FineTuneJob>, DomainError> {
+        FineTuneService::list(self).await
+    }
+
+    async fn create(
+        &self,
+        request: CreateFineTuneJobRequest,
+    ) -> Result<FineTuneJob, DomainError> {
+        FineTuneService::create(self, request).await
+    }
+
+    async fn refresh(&self, id: &str) -> Result<FineTuneJob, DomainError> {
+        FineTuneService::refresh(self, id).await
+    }
+
+    async fn refresh_active(&self) -> Result<usize, DomainError> {
+        FineTuneService::refresh_active(self).await
+    }
+
+    async fn cancel(&self, id: &str) -> Result<FineTuneJob, DomainError> {
+        FineTuneService::cancel(self, id).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<R: DatasetRepository + 'static> DatasetServiceTrait for DatasetService<R> {
+    async fn get(&self, id: &str) -> Result<Option<Dataset>, DomainError> {
+        DatasetService::get(self, id).await
+    }
+
+    async fn list(&self) -> Result<Vec<Dataset>, DomainError> {
+        DatasetService::list(self).await
+    }
+
+    async fn create(&self, request: CreateDatasetRequest) -> Result<Dataset, DomainError> {
+        DatasetService::create(self, request).await
+    }
+
+    async fn update(
+        &self,
+        id: &str,
+        request: UpdateDatasetRequest,
+    ) -> Result<Dataset, DomainError> {
+        DatasetService::update(self, id, request).await
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        DatasetService::delete(self, id).await
+    }
+
+    async fn add_version(
+        &self,
+        id: &str,
+        rows: Vec<DatasetRow>,
+        note: Option<String>,
+    ) -> Result<DatasetVersion, DomainError> {
+        DatasetService::add_version(self, id, rows, note).await
+    }
+
+    async fn get_version(
+        &self,
+        id: &str,
+        version: u32,
+    ) -> Result<Option<DatasetVersion>, DomainError> {
+        DatasetService::get_version(self, id, version).await
+    }
+
+    async fn list_versions(&self, id: &str) -> Result<Vec<DatasetVersion>, DomainError> {
+        DatasetService::list_versions(self, id).await
+    }
+
+    async fn resolve(&self, id: &str, version: Option<u32>) -> Result<DatasetRef, DomainError> {
+        DatasetService::resolve(self, id, version).await
+    }
+
+    async fn rows(&self, reference: &DatasetRef) -> Result<Vec<DatasetRow>, DomainError> {
+        DatasetService::rows(self, reference).await
+    }
+}
+
+#[async_trait::async_trait]
+impl RegressionServiceTrait for RegressionService {
+    async fn check_prompt(
+        &self,
+        id: &str,
+        content: &str,
+        policy: &RegressionPolicy,
+    ) -> Result<Option<RegressionReport>, DomainError> {
+        RegressionService::check_prompt(self, id, content, policy).await
+    }
+
+    async fn check_workflow(
+        &self,
+        id: &str,
+        request: UpdateWorkflowRequest,
+        policy: &RegressionPolicy,
+    ) -> Result<Option<RegressionReport>, DomainError> {
+        RegressionService::check_workflow(self, id, request, policy).await
+    }
+
+    async fn list_reports(
+        &self,
+        subject: RegressionSubject,
+        resource_id: &str,
+    ) -> Result<Vec<RegressionReport>, DomainError> {
+        RegressionService::list_reports(self, subject, resource_id).await
+    }
+}
+
 #[async_trait::async_trait]
 impl ConfigServiceTrait for ConfigService {
     async fn list(&self) -> Result<Vec<ConfigEntry>, DomainError> {
@@ -1418,6 +2959,18 @@ impl ExecutionLogServiceTrait for ExecutionLogService {
         ExecutionLogService::update(self, log).await
     }
 
+    fn subscribe(&self) -> tokio::sync::broadcast::Receiver<ExecutionLog> {
+        ExecutionLogService::subscribe(self)
+    }
+
+    async fn capture_payload(
+        &self,
+        team_id: &str,
+        params: RecordExecutionParams,
+    ) -> Result<Option<ExecutionLog>, DomainError> {
+        ExecutionLogService::capture_payload(self, team_id, params).await
+    }
+
     async fn record_pending_ingestion(
         &self,
         kb_id: &str,
@@ -1463,6 +3016,14 @@ impl<W: WebhookRepository + 'static, D: WebhookDeliveryRepository + 'static>
         WebhookServiceTrait::get_deliveries(self, webhook_id, limit, offset).await
     }
 
+    async fn redeliver(
+        &self,
+        webhook_id: &str,
+        delivery_id: &str,
+    ) -> Result<WebhookDelivery, DomainError> {
+        WebhookServiceTrait::redeliver(self, webhook_id, delivery_id).await
+    }
+
     async fn reset_webhook(&self, id: &str) -> Result<Webhook, DomainError> {
         WebhookServiceTrait::reset_webhook(self, id).await
     }
@@ -1483,17 +3044,31 @@ impl AppState {
         operation_service: Arc<dyn OperationServiceTrait>,
         user_service: Arc<dyn UserServiceTrait>,
         team_service: Arc<dyn TeamServiceTrait>,
+        organization_service: Arc<dyn OrganizationServiceTrait>,
+        role_service: Arc<dyn RoleServiceTrait>,
+        service_account_service: Arc<dyn ServiceAccountServiceTrait>,
         jwt_service: Arc<dyn JwtServiceTrait>,
         credential_service: Arc<dyn CredentialServiceTrait>,
         external_api_service: Arc<dyn ExternalApiServiceTrait>,
         knowledge_base_service: Arc<dyn KnowledgeBaseServiceTrait>,
         ingestion_service: Arc<dyn IngestionServiceTrait>,
+        assistant_service: Arc<dyn AssistantServiceTrait>,
+        mcp_tool_service: Arc<dyn McpToolServiceTrait>,
+        fine_tune_service: Arc<dyn FineTuneServiceTrait>,
         usage_service: Arc<dyn UsageServiceTrait>,
         budget_service: Arc<dyn BudgetServiceStateTrait>,
+        pricing_service: Arc<dyn PricingServiceTrait>,
+        slo_service: Arc<dyn SloServiceTrait>,
+        content_policy_service: Arc<dyn ContentPolicyServiceTrait>,
+        transform_rule_service: Arc<dyn TransformRuleServiceTrait>,
         experiment_service: Arc<dyn ExperimentServiceTrait>,
         test_case_service: Arc<dyn TestCaseServiceTrait>,
+        test_suite_service: Arc<dyn TestSuiteServiceTrait>,
+        dataset_service: Arc<dyn DatasetServiceTrait>,
+        regression_service: Arc<dyn RegressionServiceTrait>,
         config_service: Arc<dyn ConfigServiceTrait>,
         execution_log_service: Arc<dyn ExecutionLogServiceTrait>,
+        audit_log_service: Arc<dyn AuditLogServiceTrait>,
         webhook_service: Arc<dyn WebhookServiceStateTrait>,
         llm_provider: Arc<dyn LlmProvider>,
         provider_router: Arc<ProviderRouter>,
@@ -1503,26 +3078,140 @@ impl AppState {
             prompt_service,
             api_key_service,
             workflow_service,
-            operation_service,
+            operation_service: operation_service.clone(),
             user_service,
             team_service,
+            organization_service,
+            role_service,
+            service_account_service,
             jwt_service,
             credential_service,
             external_api_service,
             knowledge_base_service,
             ingestion_service,
+            assistant_service,
+            mcp_tool_service,
+            fine_tune_service,
             usage_service,
             budget_service,
+            pricing_service,
+            slo_service,
+            content_policy_service,
+            transform_rule_service,
             experiment_service,
             test_case_service,
+            test_suite_service,
+            dataset_service,
+            regression_service,
             config_service,
             webhook_service,
             execution_log_service,
+            audit_log_service,
             llm_provider,
             provider_router,
+            client_ip_resolver: Arc::new(ClientIpResolver::default()),
+            team_concurrency: Arc::new(TeamConcurrencyLimiter::new()),
+            invoice_markup: Arc::new(InvoiceMarkup::default()),
+            notifications: Arc::new(NotificationDispatcher::new()),
+            usage_reconciler: None,
+            events: Arc::new(EventBus::new()),
+            dependency_prober: Arc::new(DependencyProber::default()),
+            canary_health: Arc::new(CanaryHealth::default()),
+            canary_runner: None,
+            injection_guard: Arc::new(InjectionGuard::default()),
+            secret_leak_guard: Arc::new(SecretLeakGuard::default()),
+            agent_limits: Arc::new(AgentLimits::default()),
+            request_traces: Arc::new(RequestTraceStore::default()),
+            model_capacity: Arc::new(ModelCapacityTracker::new()),
+            credential_cooldowns: Arc::new(CredentialCooldowns::new()),
+            upload_limits: Arc::new(UploadLimits::default()),
+            kb_access_tracker: Arc::new(KnowledgeBaseAccessTracker::new(Arc::new(
+                crate::infrastructure::storage::InMemoryStorage::new(),
+            ))),
+            ingestion_progress: Arc::new(IngestionProgressTracker::new(operation_service)),
+            response_cache: Arc::new(ResponseCacheService::default()),
         }
     }
 
+    /// Enable on-demand reconciliation of usage with provider usage APIs
+    pub fn with_usage_reconciler(mut self, reconciler: Arc<UsageReconciler>) -> Self {
+        self.usage_reconciler = Some(reconciler);
+        self
+    }
+
+    /// Run canary test cases on demand and route around models they degrade
+    pub fn with_canary_runner(mut self, runner: Arc<CanaryRunner>) -> Self {
+        self.canary_health = runner.health();
+        self.canary_runner = Some(runner);
+        self
+    }
+
+    /// Deliver budget alerts and provider outages through notification channels
+    pub fn with_notifications(mut self, notifications: Arc<NotificationDispatcher>) -> Self {
+        self.notifications = notifications;
+        self
+    }
+
+    /// Publish gateway events (completed requests, exceeded budgets) to a broker
+    pub fn with_event_bus(mut self, events: Arc<EventBus>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Probe PostgreSQL, Redis and provider credentials in `/ready`
+    pub fn with_dependency_prober(mut self, prober: DependencyProber) -> Self {
+        self.dependency_prober = Arc::new(prober);
+        self
+    }
+
+    /// Use custom prompt-injection detection settings
+    pub fn with_injection_guard(mut self, guard: InjectionGuard) -> Self {
+        self.injection_guard = Arc::new(guard);
+        self
+    }
+
+    /// Use custom outbound secret scanning settings
+    pub fn with_secret_leak_guard(mut self, guard: SecretLeakGuard) -> Self {
+        self.secret_leak_guard = Arc::new(guard);
+        self
+    }
+
+    /// Use custom ceilings for the agent runs of assistants
+    pub fn with_agent_limits(mut self, limits: AgentLimits) -> Self {
+        self.agent_limits = Arc::new(limits);
+        self
+    }
+
+    /// Set the limits of batch document uploads
+    pub fn with_upload_limits(mut self, limits: UploadLimits) -> Self {
+        self.upload_limits = Arc::new(limits);
+        self
+    }
+
+    /// Report on the searches counted by the given knowledge base access tracker
+    pub fn with_kb_access_tracker(mut self, tracker: Arc<KnowledgeBaseAccessTracker>) -> Self {
+        self.kb_access_tracker = tracker;
+        self
+    }
+
+    /// Use custom markup percentages for team invoices
+    pub fn with_invoice_markup(mut self, markup: InvoiceMarkup) -> Self {
+        self.invoice_markup = Arc::new(markup);
+        self
+    }
+
+    /// Serve chat completions from the given response caches
+    pub fn with_response_cache(mut self, cache: ResponseCacheService) -> Self {
+        self.response_cache = Arc::new(cache);
+        self
+    }
+
+    /// Use a custom client IP resolver (trusted proxies and headers)
+    pub fn with_client_ip_resolver(mut self, resolver: ClientIpResolver) -> Self {
+        self.client_ip_resolver = Arc::new(resolver);
+        self
+    }
+
     /// Get the webhook service
     pub fn webhook_service(&self) -> &Arc<dyn WebhookServiceStateTrait> {
         &self.webhook_service
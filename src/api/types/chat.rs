@@ -3,9 +3,10 @@
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 /// Role of a chat message
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ChatMessageRole {
     System,
@@ -38,7 +39,7 @@ impl From<crate::domain::llm::MessageRole> for ChatMessageRole {
 }
 
 /// Content part for multimodal messages
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ContentPart {
     Text { text: String },
@@ -46,7 +47,7 @@ pub enum ContentPart {
 }
 
 /// Image URL content
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ImageUrl {
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -54,7 +55,7 @@ pub struct ImageUrl {
 }
 
 /// A chat message in OpenAI format
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ChatMessage {
     pub role: ChatMessageRole,
 
@@ -88,7 +89,7 @@ pub struct ChatMessage {
 }
 
 /// Message content - can be text or array of content parts
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(untagged)]
 pub enum MessageContent {
     Text(String),
@@ -113,7 +114,7 @@ impl MessageContent {
 }
 
 /// Tool call made by the assistant
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ToolCall {
     pub id: String,
     #[serde(rename = "type")]
@@ -122,14 +123,49 @@ pub struct ToolCall {
 }
 
 /// Function call details
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct FunctionCall {
     pub name: String,
     pub arguments: String,
 }
 
+/// Fragment of a tool call in a streaming response; the first fragment of
+/// a call carries its ID and function name, the following ones pieces of
+/// the arguments
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ToolCallChunk {
+    pub index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub call_type: Option<String>,
+    pub function: FunctionCallChunk,
+}
+
+/// Function name and arguments fragment of a streamed tool call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FunctionCallChunk {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub arguments: String,
+}
+
+impl From<&crate::domain::llm::ToolCallDelta> for ToolCallChunk {
+    fn from(delta: &crate::domain::llm::ToolCallDelta) -> Self {
+        Self {
+            index: delta.index,
+            id: delta.id.clone(),
+            call_type: delta.id.as_ref().map(|_| "function".to_string()),
+            function: FunctionCallChunk {
+                name: delta.name.clone(),
+                arguments: delta.arguments.clone(),
+            },
+        }
+    }
+}
+
 /// Stream options for chat completions
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct StreamOptions {
     /// Include usage in the final stream chunk
     #[serde(default)]
@@ -137,9 +173,11 @@ pub struct StreamOptions {
 }
 
 /// Chat completion request (OpenAI format)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ChatCompletionRequest {
-    /// Model ID (can be gateway model ID or chain ID)
+    /// Model ID (can be gateway model ID or chain ID); empty or `default`
+    /// uses the default model of the key's team
+    #[serde(default)]
     pub model: String,
 
     /// Messages in the conversation
@@ -188,10 +226,35 @@ pub struct ChatCompletionRequest {
     /// Seed for deterministic sampling
     #[serde(skip_serializing_if = "Option::is_none")]
     pub seed: Option<i64>,
+
+    /// Cost attribution tags, merged over those of the `x-pmp-tags` header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+
+    // Gateway extension: system prompt rendered from a prompt entity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<SystemPromptRef>,
+
+    // Gateway extension: accepted locales of the referenced prompts,
+    // overriding the x-pmp-locale and Accept-Language headers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+/// Reference to a managed prompt rendered as the system prompt of a chat
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SystemPromptRef {
+    pub prompt_id: String,
+    /// Retained version of the prompt; the current one when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+    /// Values of the template variables
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, String>,
 }
 
 /// Stop sequence - can be string or array
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(untagged)]
 pub enum StopSequence {
     Single(String),
@@ -208,7 +271,7 @@ impl StopSequence {
 }
 
 /// Reason for completion finish
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum FinishReason {
     Stop,
@@ -219,7 +282,7 @@ pub enum FinishReason {
 }
 
 /// Token usage statistics
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct Usage {
     pub prompt_tokens: u32,
     pub completion_tokens: u32,
@@ -237,7 +300,7 @@ impl From<crate::domain::llm::Usage> for Usage {
 }
 
 /// A choice in the chat completion response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ChatCompletionChoice {
     pub index: u32,
     pub message: ChatMessage,
@@ -245,7 +308,7 @@ pub struct ChatCompletionChoice {
 }
 
 /// Chat completion response (OpenAI format)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ChatCompletionResponse {
     pub id: String,
     pub object: String,
@@ -293,18 +356,18 @@ impl ChatCompletionResponse {
 }
 
 /// Delta content for streaming
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct DeltaContent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub role: Option<ChatMessageRole>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tool_calls: Option<Vec<ToolCall>>,
+    pub tool_calls: Option<Vec<ToolCallChunk>>,
 }
 
 /// A choice in a streaming response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ChatCompletionStreamChoice {
     pub index: u32,
     pub delta: DeltaContent,
@@ -313,7 +376,7 @@ pub struct ChatCompletionStreamChoice {
 }
 
 /// Streaming chat completion response chunk
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ChatCompletionStreamResponse {
     pub id: String,
     pub object: String,
@@ -369,6 +432,21 @@ impl ChatCompletionStreamResponse {
         }
     }
 
+    /// Create a chunk of tool call fragments
+    pub fn tool_calls(
+        model: &str,
+        request_id: &str,
+        tool_calls: &[crate::domain::llm::ToolCallDelta],
+    ) -> Self {
+        let mut chunk = Self::content(model, request_id, "");
+        chunk.choices[0].delta = DeltaContent {
+            role: None,
+            content: None,
+            tool_calls: Some(tool_calls.iter().map(ToolCallChunk::from).collect()),
+        };
+        chunk
+    }
+
     /// Create a final chunk with finish reason
     pub fn finish(model: &str, request_id: &str, usage: Option<Usage>) -> Self {
         Self {
@@ -444,6 +522,27 @@ mod tests {
         assert!(!request.stream);
     }
 
+    #[test]
+    fn test_chat_request_system_prompt_ref() {
+        let request: ChatCompletionRequest = serde_json::from_str(
+            r#"{
+                "model": "gpt-4",
+                "system": {"prompt_id": "rag-system", "version": 3, "variables": {"tone": "formal"}},
+                "messages": [{"role": "user", "content": "Hello"}]
+            }"#,
+        )
+        .unwrap();
+
+        let system = request.system.unwrap();
+        assert_eq!(system.prompt_id, "rag-system");
+        assert_eq!(system.version, Some(3));
+        assert_eq!(system.variables.get("tone").map(String::as_str), Some("formal"));
+
+        let request: ChatCompletionRequest =
+            serde_json::from_str(r#"{"model": "gpt-4", "messages": []}"#).unwrap();
+        assert!(request.system.is_none());
+    }
+
     #[test]
     fn test_chat_response_serialization() {
         let response = ChatCompletionResponse {
@@ -494,4 +593,37 @@ mod tests {
             Some(FinishReason::Stop)
         );
     }
+
+    #[test]
+    fn test_stream_tool_call_chunk() {
+        use crate::domain::llm::ToolCallDelta;
+
+        let start = ChatCompletionStreamResponse::tool_calls(
+            "claude",
+            "123",
+            &[ToolCallDelta::start(0, "toolu_1", "get_weather")],
+        );
+        let json = serde_json::to_value(&start).unwrap();
+        assert_eq!(
+            json["choices"][0]["delta"]["tool_calls"][0],
+            serde_json::json!({
+                "index": 0,
+                "id": "toolu_1",
+                "type": "function",
+                "function": {"name": "get_weather", "arguments": ""}
+            })
+        );
+        assert!(json["choices"][0]["delta"].get("content").is_none());
+
+        let arguments = ChatCompletionStreamResponse::tool_calls(
+            "claude",
+            "123",
+            &[ToolCallDelta::arguments(0, "{\"city\"")],
+        );
+        let json = serde_json::to_value(&arguments).unwrap();
+        assert_eq!(
+            json["choices"][0]["delta"]["tool_calls"][0],
+            serde_json::json!({"index": 0, "function": {"arguments": "{\"city\""}})
+        );
+    }
 }
@@ -1,16 +1,18 @@
 //! OpenAI-compatible error types
 
 use axum::{
-    http::StatusCode,
+    http::{HeaderName, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-use crate::domain::DomainError;
+use crate::domain::llm::{GatewayErrorCode, ProviderFailure, ProviderRateLimit};
+use crate::domain::{DomainError, FieldViolation};
 
 /// Error types matching OpenAI API
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ApiErrorType {
     InvalidRequestError,
@@ -37,13 +39,13 @@ impl std::fmt::Display for ApiErrorType {
 }
 
 /// OpenAI-compatible error response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ApiErrorResponse {
     pub error: ApiErrorDetail,
 }
 
 /// Error detail structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ApiErrorDetail {
     pub message: String,
     #[serde(rename = "type")]
@@ -52,6 +54,9 @@ pub struct ApiErrorDetail {
     pub param: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub code: Option<String>,
+    /// Structured context of the error, e.g. the content policy violations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
 }
 
 /// API error with status code
@@ -76,6 +81,7 @@ impl ApiError {
                     error_type,
                     param: None,
                     code: None,
+                    details: None,
                 },
             },
         }
@@ -93,6 +99,12 @@ impl ApiError {
         self
     }
 
+    /// Add structured error context
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.response.error.details = Some(details);
+        self
+    }
+
     /// Bad request error
     pub fn bad_request(message: impl Into<String>) -> Self {
         Self::new(StatusCode::BAD_REQUEST, ApiErrorType::InvalidRequestError, message)
@@ -136,11 +148,114 @@ impl ApiError {
             message,
         )
     }
+
+    /// Request fields rejected by validation, listed in
+    /// `details.errors` so clients can point at each offending field
+    pub fn invalid_fields(violations: Vec<FieldViolation>) -> Self {
+        let message = violations
+            .iter()
+            .map(FieldViolation::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        let mut error = Self::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            ApiErrorType::InvalidRequestError,
+            format!("Invalid request: {}", message),
+        )
+        .with_code("validation_failed")
+        .with_details(serde_json::json!({ "errors": violations }));
+
+        if let Some(first) = violations.first() {
+            error = error.with_param(first.field.clone());
+        }
+
+        error
+    }
+
+    /// Provider failure, classified into a gateway error code (`code`) with
+    /// the provider's own error attached in `details.provider_error`. Rate
+    /// limit hints of the provider come back as `retry-after` and
+    /// `x-ratelimit-remaining-{requests,tokens}` headers.
+    pub fn provider_failure(
+        provider: &str,
+        message: &str,
+        rate_limit: Option<&ProviderRateLimit>,
+    ) -> Self {
+        let failure = ProviderFailure::classify(message);
+        let (status, error_type) = match failure.code {
+            GatewayErrorCode::RateLimited | GatewayErrorCode::QuotaExceeded => {
+                (StatusCode::TOO_MANY_REQUESTS, ApiErrorType::RateLimitError)
+            }
+            GatewayErrorCode::ContentFiltered
+            | GatewayErrorCode::ContextLengthExceeded
+            | GatewayErrorCode::InvalidRequest => {
+                (StatusCode::BAD_REQUEST, ApiErrorType::InvalidRequestError)
+            }
+            GatewayErrorCode::AuthenticationFailed => {
+                (StatusCode::BAD_GATEWAY, ApiErrorType::ServerError)
+            }
+            GatewayErrorCode::ProviderTimeout => {
+                (StatusCode::GATEWAY_TIMEOUT, ApiErrorType::ServiceUnavailableError)
+            }
+            GatewayErrorCode::ProviderUnavailable | GatewayErrorCode::ProviderError => {
+                (StatusCode::SERVICE_UNAVAILABLE, ApiErrorType::ServiceUnavailableError)
+            }
+        };
+
+        let mut details = serde_json::json!({
+            "provider": provider,
+            "provider_status": failure.status,
+            "provider_error": failure.detail,
+            "retryable": failure.code.is_retryable(),
+        });
+
+        if let Some(limit) = rate_limit {
+            let hints = [
+                ("retry_after", limit.retry_after_secs),
+                ("remaining_requests", limit.remaining_requests),
+                ("remaining_tokens", limit.remaining_tokens),
+            ];
+            for (field, value) in hints {
+                if let Some(value) = value {
+                    details[field] = value.into();
+                }
+            }
+        }
+
+        Self::new(status, error_type, format!("{}: {}", provider, failure.message))
+            .with_code(failure.code.as_str())
+            .with_details(details)
+    }
+
+    /// A single request field rejected by validation
+    pub fn invalid_field(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::invalid_fields(vec![FieldViolation::new(field, message)])
+    }
 }
 
+/// Response headers of the rate limit hints in the error details
+const RATE_LIMIT_HEADERS: [(&str, &str); 3] = [
+    ("retry_after", "retry-after"),
+    ("remaining_requests", "x-ratelimit-remaining-requests"),
+    ("remaining_tokens", "x-ratelimit-remaining-tokens"),
+];
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        (self.status, Json(self.response)).into_response()
+        let mut response = (self.status, Json(&self.response)).into_response();
+
+        if let Some(details) = &self.response.error.details {
+            for (field, header) in RATE_LIMIT_HEADERS {
+                if let Some(value) = details.get(field).and_then(serde_json::Value::as_u64) {
+                    response
+                        .headers_mut()
+                        .insert(HeaderName::from_static(header), HeaderValue::from(value));
+                }
+            }
+        }
+
+        response
     }
 }
 
@@ -149,13 +264,16 @@ impl From<DomainError> for ApiError {
         match &err {
             DomainError::NotFound { message } => Self::not_found(message),
             DomainError::Validation { message } => Self::bad_request(message),
+            DomainError::InvalidFields { violations } => Self::invalid_fields(violations.clone()),
             DomainError::InvalidId { message } => {
                 Self::bad_request(message).with_param("id")
             }
             DomainError::Credential { message } => Self::unauthorized(message),
-            DomainError::Provider { provider, message } => {
-                Self::unavailable(format!("{}: {}", provider, message))
-            }
+            DomainError::Provider {
+                provider,
+                message,
+                rate_limit,
+            } => Self::provider_failure(provider, message, rate_limit.as_deref()),
             DomainError::Configuration { message } => Self::internal(message),
             DomainError::Conflict { message } => Self::bad_request(message),
             DomainError::Internal { message } => Self::internal(message),
@@ -209,6 +327,23 @@ mod tests {
         assert_eq!(api_err.response.error.error_type, ApiErrorType::NotFoundError);
     }
 
+    #[test]
+    fn test_invalid_fields_conversion() {
+        let domain_err = DomainError::invalid_fields(vec![
+            FieldViolation::new("id", "Model ID cannot be empty"),
+            FieldViolation::new("config.temperature", "must be between 0 and 2"),
+        ]);
+        let api_err: ApiError = domain_err.into();
+
+        assert_eq!(api_err.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(api_err.response.error.code.as_deref(), Some("validation_failed"));
+        assert_eq!(api_err.response.error.param.as_deref(), Some("id"));
+        assert_eq!(
+            api_err.response.error.details.unwrap()["errors"][1]["field"],
+            "config.temperature"
+        );
+    }
+
     #[test]
     fn test_error_serialization() {
         let err = ApiError::unauthorized("Invalid API key");
@@ -228,4 +363,53 @@ mod tests {
         assert_eq!(ApiError::internal("").status, StatusCode::INTERNAL_SERVER_ERROR);
         assert_eq!(ApiError::unavailable("").status, StatusCode::SERVICE_UNAVAILABLE);
     }
+
+    #[test]
+    fn test_provider_error_conversion() {
+        let domain_err = DomainError::provider(
+            "anthropic",
+            r#"HTTP 429 Too Many Requests: {"type": "error", "error": {"type": "rate_limit_error", "message": "Number of requests has exceeded your rate limit"}}"#,
+        );
+        let api_err: ApiError = domain_err.into();
+
+        assert_eq!(api_err.status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(api_err.response.error.error_type, ApiErrorType::RateLimitError);
+        assert_eq!(api_err.response.error.code.as_deref(), Some("rate_limited"));
+        assert_eq!(
+            api_err.response.error.message,
+            "anthropic: Number of requests has exceeded your rate limit"
+        );
+
+        let details = api_err.response.error.details.unwrap();
+        assert_eq!(details["provider"], "anthropic");
+        assert_eq!(details["provider_status"], 429);
+        assert_eq!(details["provider_error"]["error"]["type"], "rate_limit_error");
+        assert_eq!(details["retryable"], true);
+
+        let api_err: ApiError = DomainError::provider("openai", "No choices in response").into();
+        assert_eq!(api_err.status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(api_err.response.error.code.as_deref(), Some("provider_error"));
+    }
+
+    #[test]
+    fn test_provider_error_rate_limit_headers() {
+        let domain_err = DomainError::provider("openai", "HTTP 429 Too Many Requests: {}")
+            .with_rate_limit(ProviderRateLimit {
+                retry_after_secs: Some(12),
+                remaining_requests: Some(0),
+                remaining_tokens: None,
+            });
+        let api_err: ApiError = domain_err.into();
+
+        assert_eq!(api_err.status, StatusCode::TOO_MANY_REQUESTS);
+        let details = api_err.response.error.details.as_ref().unwrap();
+        assert_eq!(details["retry_after"], 12);
+        assert_eq!(details["remaining_requests"], 0);
+
+        let response = api_err.into_response();
+        let headers = response.headers();
+        assert_eq!(headers.get("retry-after").unwrap(), "12");
+        assert_eq!(headers.get("x-ratelimit-remaining-requests").unwrap(), "0");
+        assert!(headers.get("x-ratelimit-remaining-tokens").is_none());
+    }
 }
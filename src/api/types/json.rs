@@ -8,12 +8,15 @@ use axum::{
 };
 use serde::de::DeserializeOwned;
 
-use super::error::{ApiErrorDetail, ApiErrorResponse, ApiErrorType};
+use super::error::{ApiError, ApiErrorDetail, ApiErrorResponse, ApiErrorType};
+use crate::domain::FieldViolation;
 
 /// Custom JSON extractor that converts all rejection errors to JSON format
 ///
 /// This wrapper around `axum::Json` ensures that deserialization errors
-/// are returned as JSON responses matching our API error format.
+/// are returned as JSON responses matching our API error format. Bodies that
+/// are valid JSON but do not match the expected shape are rejected with 422
+/// and the path of the offending field, e.g. `steps[0].name`.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Json<T>(pub T);
 
@@ -43,16 +46,22 @@ impl<T> std::ops::DerefMut for Json<T> {
 pub struct JsonRejection {
     status: StatusCode,
     message: String,
+    violation: Option<FieldViolation>,
 }
 
 impl IntoResponse for JsonRejection {
     fn into_response(self) -> Response {
+        if let Some(violation) = self.violation {
+            return ApiError::invalid_fields(vec![violation]).into_response();
+        }
+
         let response = ApiErrorResponse {
             error: ApiErrorDetail {
                 message: self.message,
                 error_type: ApiErrorType::InvalidRequestError,
                 param: None,
                 code: Some("json_parse_error".to_string()),
+                details: None,
             },
         };
 
@@ -68,18 +77,54 @@ where
     type Rejection = JsonRejection;
 
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
-        match AxumJson::<T>::from_request(req, state).await {
-            Ok(AxumJson(value)) => Ok(Json(value)),
+        let value = match AxumJson::<serde_json::Value>::from_request(req, state).await {
+            Ok(AxumJson(value)) => value,
             Err(rejection) => {
                 let message = format_rejection_message(&rejection);
                 let status = rejection.status();
 
-                Err(JsonRejection { status, message })
+                return Err(JsonRejection {
+                    status,
+                    message,
+                    violation: None,
+                });
             }
-        }
+        };
+
+        serde_path_to_error::deserialize(value)
+            .map(Json)
+            .map_err(|err| {
+                let violation = data_violation(&err);
+
+                JsonRejection {
+                    status: StatusCode::UNPROCESSABLE_ENTITY,
+                    message: format!("Invalid JSON data: {}", violation),
+                    violation: Some(violation),
+                }
+            })
     }
 }
 
+/// Field violation of a body that does not match the expected shape
+fn data_violation(err: &serde_path_to_error::Error<serde_json::Error>) -> FieldViolation {
+    let path = err.path().to_string();
+    let message = err.inner().to_string();
+
+    // Missing fields are reported against their parent, name them instead
+    let missing = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.strip_suffix('`'));
+
+    let field = match (path.as_str(), missing) {
+        (".", Some(name)) => name.to_string(),
+        (".", None) => "body".to_string(),
+        (parent, Some(name)) => format!("{}.{}", parent, name),
+        (parent, None) => parent.to_string(),
+    };
+
+    FieldViolation::new(field, message)
+}
+
 /// Format the rejection message to be more user-friendly
 fn format_rejection_message(rejection: &axum::extract::rejection::JsonRejection) -> String {
     use axum::extract::rejection::JsonRejection::*;
@@ -129,11 +174,72 @@ mod tests {
         name: String,
     }
 
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct TestConfig {
+        temperature: f32,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct TestRequest {
+        name: String,
+        config: TestConfig,
+        tags: Vec<String>,
+    }
+
+    async fn extract(body: &str) -> Result<Json<TestRequest>, JsonRejection> {
+        let request = Request::builder()
+            .method("POST")
+            .header("content-type", "application/json")
+            .body(axum::body::Body::from(body.to_string()))
+            .unwrap();
+
+        Json::<TestRequest>::from_request(request, &()).await
+    }
+
+    #[tokio::test]
+    async fn test_json_data_error_names_field() {
+        let field = |body: &'static str| async move {
+            extract(body).await.unwrap_err().violation.unwrap().field
+        };
+
+        assert_eq!(field(r#"{"config": {"temperature": 1}, "tags": []}"#).await, "name");
+        assert_eq!(
+            field(r#"{"name": "a", "config": {"temperature": "hot"}, "tags": []}"#).await,
+            "config.temperature"
+        );
+        assert_eq!(
+            field(r#"{"name": "a", "config": {}, "tags": []}"#).await,
+            "config.temperature"
+        );
+        assert_eq!(
+            field(r#"{"name": "a", "config": {"temperature": 1}, "tags": ["x", 2]}"#).await,
+            "tags[1]"
+        );
+        assert_eq!(field("[]").await, "body");
+
+        let rejection = extract(r#"{"name": 1}"#).await.unwrap_err();
+        assert_eq!(
+            rejection.into_response().status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+
+    #[tokio::test]
+    async fn test_json_syntax_error_is_bad_request() {
+        let rejection = extract("not json").await.unwrap_err();
+
+        assert!(rejection.violation.is_none());
+        assert_eq!(rejection.into_response().status(), StatusCode::BAD_REQUEST);
+    }
+
     #[test]
     fn test_json_rejection_into_response() {
         let rejection = JsonRejection {
             status: StatusCode::UNPROCESSABLE_ENTITY,
             message: "Test error".to_string(),
+            violation: None,
         };
 
         let response = rejection.into_response();
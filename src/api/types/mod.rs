@@ -11,10 +11,10 @@ pub mod operation;
 pub use chat::{
     ChatCompletionChoice, ChatCompletionRequest, ChatCompletionResponse,
     ChatCompletionStreamChoice, ChatCompletionStreamResponse, ChatMessage, ChatMessageRole,
-    ContentPart, DeltaContent, FinishReason, FunctionCall, MessageContent, StreamOptions,
-    StopSequence, ToolCall, Usage,
+    ContentPart, DeltaContent, FinishReason, FunctionCall, FunctionCallChunk, MessageContent,
+    StreamOptions, StopSequence, SystemPromptRef, ToolCall, ToolCallChunk, Usage,
 };
-pub use error::{ApiError, ApiErrorResponse};
+pub use error::{ApiError, ApiErrorDetail, ApiErrorResponse};
 pub use json::Json;
 pub use models::{Model as ApiModel, ModelsResponse};
 pub use operation::{
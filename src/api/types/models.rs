@@ -1,9 +1,10 @@
 //! OpenAI-compatible model types
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 /// Model information (OpenAI format)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Model {
     pub id: String,
     pub object: String,
@@ -24,7 +25,7 @@ impl Model {
 }
 
 /// List models response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ModelsResponse {
     pub object: String,
     pub data: Vec<Model>,
@@ -2,11 +2,13 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use utoipa::{IntoParams, ToSchema};
 
-use crate::domain::operation::{Operation, OperationStatus, OperationType};
+use crate::domain::operation::{Operation, OperationProgress, OperationStatus, OperationType};
 
 /// Query parameters for async operation support
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct AsyncQueryParams {
     /// Enable async mode - returns 202 Accepted with operation ID
     #[serde(default, rename = "async")]
@@ -14,7 +16,7 @@ pub struct AsyncQueryParams {
 }
 
 /// Response when an async operation is created (HTTP 202)
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct AsyncOperationCreated {
     pub operation_id: String,
     pub status: String,
@@ -34,7 +36,8 @@ impl AsyncOperationCreated {
 }
 
 /// Response for a single operation
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 pub struct OperationResponse {
     pub operation_id: String,
     pub operation_type: String,
@@ -43,6 +46,13 @@ pub struct OperationResponse {
     pub result: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Progress reported while running, kept once finished
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress: Option<Value>,
+    /// Units of work done, percentage and estimated time left, for
+    /// workflows and batch ingestions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion: Option<OperationProgress>,
     pub created_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub started_at: Option<String>,
@@ -58,6 +68,8 @@ impl From<&Operation> for OperationResponse {
             status: format_status(op.status()),
             result: op.result().cloned(),
             error: op.error().map(String::from),
+            progress: op.progress().cloned(),
+            completion: op.completion().cloned(),
             created_at: op.created_at().to_rfc3339(),
             started_at: op.started_at().map(|t| t.to_rfc3339()),
             completed_at: op.completed_at().map(|t| t.to_rfc3339()),
@@ -72,7 +84,7 @@ impl From<Operation> for OperationResponse {
 }
 
 /// Response for listing multiple operations
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct OperationsListResponse {
     pub operations: Vec<OperationResponse>,
 }
@@ -87,7 +99,8 @@ impl OperationsListResponse {
 }
 
 /// Query parameters for listing operations
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct OperationsQueryParams {
     /// Comma-separated list of operation IDs to fetch
     pub ids: Option<String>,
@@ -113,6 +126,8 @@ fn format_operation_type(op_type: OperationType) -> String {
     match op_type {
         OperationType::ChatCompletion => "chat_completion".to_string(),
         OperationType::WorkflowExecution => "workflow_execution".to_string(),
+        OperationType::TestSuiteRun => "test_suite_run".to_string(),
+        OperationType::KnowledgeBaseIngestion => "knowledge_base_ingestion".to_string(),
     }
 }
 
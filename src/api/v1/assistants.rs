@@ -0,0 +1,765 @@
+//! Assistant endpoints
+//!
+//! An assistant turn is a chat completion on the assistant's model, with its
+//! rendered system prompt and the passages its knowledge bases return for the
+//! latest user message. The turn then goes through the regular chat
+//! completion pipeline, so budgets, experiments, guardrails, streaming and
+//! usage tracking apply unchanged.
+//!
+//! An agent run executes the model→tool→model loop of an assistant on the
+//! gateway: the model decides at every turn whether to call one of the
+//! assistant's tool workflows or to answer, within the `[agent]` limits.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Instant;
+
+use async_trait::async_trait;
+use axum::{
+    extract::{Extension, OriginalUri, Path, Query, State},
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use futures::{StreamExt, stream};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{debug, info};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::chat::{
+    call_provider, convert_messages, create_chat_completion, get_provider_for_model,
+};
+use super::workflows::record_workflow_usage;
+use crate::api::middleware::{
+    BudgetSlot, RequestLocale, RequestTracing, RequireApiKey, TransformSlot, UsageTags,
+    enforce_budget, enforce_content_policies, enforce_data_residency, enforce_injection_guard,
+    estimate_cost, estimate_prompt_tokens, injection_blocked_error, policy_input_text,
+    record_request_usage,
+};
+use crate::api::state::AppState;
+use crate::api::types::{
+    ApiError, AsyncOperationCreated, AsyncQueryParams, ChatCompletionRequest,
+    ChatCompletionResponse, ChatCompletionStreamResponse, ChatMessage, ChatMessageRole, Json,
+    MessageContent,
+};
+use crate::domain::DomainError;
+use crate::domain::agent::{
+    AgentEvent, AgentModel, AgentModelTurn, AgentOutcome, AgentRuntime, AgentStopReason,
+    AgentToolOutput, AgentToolSpec, AgentTools, decision_response_format, tool_instructions,
+};
+use crate::domain::api_key::ApiKey;
+use crate::domain::knowledge_base::KnowledgeBaseCaller;
+use crate::domain::assistant::{Assistant, AssistantTool, system_prompt_with_context};
+use crate::domain::guardrail::PolicyStage;
+use crate::domain::llm::{LlmProvider, LlmRequest, LlmResponseFormat, Message};
+use crate::infrastructure::background::spawn_tracked;
+
+/// Request for one assistant turn
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AssistantChatRequest {
+    /// Conversation so far; system messages are replaced by the assistant's
+    pub messages: Vec<ChatMessage>,
+
+    /// Variables for the system prompt, merged over the assistant's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variables: Option<HashMap<String, String>>,
+
+    /// Accepted locales of the system prompt, overriding the `x-pmp-locale`
+    /// and `Accept-Language` headers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+
+    #[serde(default)]
+    pub stream: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+
+    /// End user identifier
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+
+    /// Cost attribution tags, merged over those of the `x-pmp-tags` header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+/// Request for an agent run of an assistant
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AssistantRunRequest {
+    /// Conversation so far; system messages are replaced by the assistant's
+    pub messages: Vec<ChatMessage>,
+
+    /// Variables for the system prompt, merged over the assistant's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variables: Option<HashMap<String, String>>,
+
+    /// Accepted locales of the system prompt, overriding the `x-pmp-locale`
+    /// and `Accept-Language` headers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+
+    /// Stream the tool calls and results as they happen
+    #[serde(default)]
+    pub stream: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    /// Completion tokens of each model call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+
+    /// Model calls of the run, capped by the gateway's `[agent]` limits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_iterations: Option<u32>,
+
+    /// Seconds each tool may run, capped by the gateway's `[agent]` limits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_timeout_secs: Option<u64>,
+
+    /// Cost of the run in micro-dollars, capped by the gateway's `[agent]`
+    /// limits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cost_micros: Option<i64>,
+
+    /// Cost attribution tags, merged over those of the `x-pmp-tags` header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+/// Result of an agent run
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
+pub struct AssistantRunResponse {
+    pub id: String,
+    pub assistant_id: String,
+    pub model: String,
+    /// Final answer, missing when a limit stopped the run first
+    pub answer: Option<String>,
+    pub stop_reason: AgentStopReason,
+    /// Model calls made
+    pub iterations: u32,
+    /// Tokens of the model calls and tool workflows
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    pub cost_micros: i64,
+    /// Tool calls, tool results and answer, in order
+    pub events: Vec<AgentEvent>,
+}
+
+impl AssistantRunResponse {
+    fn new(id: String, assistant_id: String, model: String, outcome: AgentOutcome) -> Self {
+        Self {
+            id,
+            assistant_id,
+            model,
+            answer: outcome.answer,
+            stop_reason: outcome.stop_reason,
+            iterations: outcome.iterations,
+            input_tokens: outcome.input_tokens,
+            output_tokens: outcome.output_tokens,
+            cost_micros: outcome.cost_micros,
+            events: outcome.events,
+        }
+    }
+}
+
+/// Public definition of an assistant
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
+pub struct AssistantInfo {
+    pub id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub model_id: String,
+    /// Workflows the client may run as tools, through
+    /// `/v1/workflows/{workflow_id}/execute`
+    pub tools: Vec<AssistantTool>,
+}
+
+impl From<Assistant> for AssistantInfo {
+    fn from(assistant: Assistant) -> Self {
+        Self {
+            id: assistant.id().to_string(),
+            name: assistant.name().to_string(),
+            description: assistant.description().map(String::from),
+            model_id: assistant.model_id().to_string(),
+            tools: assistant.tools().to_vec(),
+        }
+    }
+}
+
+/// Get assistant
+#[utoipa::path(
+    get,
+    path = "/v1/assistants/{assistant_id}",
+    tag = "v1",
+    operation_id = "v1_get_assistant",
+    params(("assistant_id" = String, Path, description = "Assistant ID")),
+    responses((status = 200, body = AssistantInfo)),
+)]
+pub async fn get_assistant(
+    State(state): State<AppState>,
+    RequireApiKey(_): RequireApiKey,
+    Path(assistant_id): Path<String>,
+) -> Result<Json<AssistantInfo>, ApiError> {
+    let assistant = get_enabled(&state, &assistant_id).await?;
+
+    Ok(Json(AssistantInfo::from(assistant)))
+}
+
+/// Chat with an assistant
+#[utoipa::path(
+    post,
+    path = "/v1/assistants/{assistant_id}/chat",
+    tag = "v1",
+    params(
+        ("assistant_id" = String, Path, description = "Assistant ID"),
+        AsyncQueryParams,
+    ),
+    request_body = AssistantChatRequest,
+    responses(
+        (
+            status = 200,
+            description = "Chat completion, streamed as `chat.completion.chunk` events when `stream` is set",
+            content(
+                (ChatCompletionResponse = "application/json"),
+                (ChatCompletionStreamResponse = "text/event-stream"),
+            )
+        ),
+        (status = 202, description = "Async operation created when `async=true`", body = AsyncOperationCreated),
+    ),
+)]
+#[allow(clippy::too_many_arguments)]
+pub async fn chat_with_assistant(
+    State(state): State<AppState>,
+    RequireApiKey(api_key): RequireApiKey,
+    tracing: RequestTracing,
+    budget_slot: Option<Extension<BudgetSlot>>,
+    transform_slot: Option<Extension<TransformSlot>>,
+    original_uri: OriginalUri,
+    usage_tags: UsageTags,
+    request_locale: RequestLocale,
+    Query(async_params): Query<AsyncQueryParams>,
+    Path(assistant_id): Path<String>,
+    Json(request): Json<AssistantChatRequest>,
+) -> Result<Response, ApiError> {
+    let assistant = get_enabled(&state, &assistant_id).await?;
+
+    if request.messages.is_empty() {
+        return Err(ApiError::bad_request("Messages cannot be empty").with_param("messages"));
+    }
+
+    let locale = request_locale.or_field(request.locale.as_deref());
+    let system_prompt = system_prompt(
+        &state,
+        &api_key,
+        &assistant,
+        request.variables,
+        locale.as_deref(),
+        &request.messages,
+    )
+    .await?;
+
+    debug!(
+        assistant_id = %assistant_id,
+        model = %assistant.model_id(),
+        "Processing assistant turn"
+    );
+
+    let mut messages = Vec::with_capacity(request.messages.len() + 1);
+    messages.push(system_message(system_prompt));
+    messages.extend(
+        request
+            .messages
+            .into_iter()
+            .filter(|message| message.role != ChatMessageRole::System),
+    );
+
+    let chat_request = ChatCompletionRequest {
+        model: assistant.model_id().to_string(),
+        messages,
+        temperature: request.temperature,
+        top_p: request.top_p,
+        n: None,
+        stream: request.stream,
+        stream_options: None,
+        stop: None,
+        max_tokens: request.max_tokens,
+        presence_penalty: None,
+        frequency_penalty: None,
+        user: request.user,
+        seed: None,
+        metadata: request.metadata,
+        system: None,
+        locale: locale.clone(),
+    };
+
+    create_chat_completion(
+        State(state),
+        RequireApiKey(api_key),
+        tracing,
+        budget_slot,
+        transform_slot,
+        original_uri,
+        usage_tags,
+        RequestLocale(locale),
+        Query(async_params),
+        Json(chat_request),
+    )
+    .await
+}
+
+/// Run an assistant as an agent
+#[utoipa::path(
+    post,
+    path = "/v1/assistants/{assistant_id}/run",
+    tag = "v1",
+    params(("assistant_id" = String, Path, description = "Assistant ID")),
+    request_body = AssistantRunRequest,
+    responses(
+        (
+            status = 200,
+            description = "Agent run; with `stream` set, every tool call, tool result and the answer are sent as events, then the result as a `done` event",
+            content(
+                (AssistantRunResponse = "application/json"),
+                (AgentEvent = "text/event-stream"),
+            )
+        ),
+    ),
+)]
+pub async fn run_assistant(
+    State(state): State<AppState>,
+    RequireApiKey(api_key): RequireApiKey,
+    budget_slot: Option<Extension<BudgetSlot>>,
+    usage_tags: UsageTags,
+    request_locale: RequestLocale,
+    Path(assistant_id): Path<String>,
+    Json(request): Json<AssistantRunRequest>,
+) -> Result<Response, ApiError> {
+    let assistant = get_enabled(&state, &assistant_id).await?;
+
+    if request.messages.is_empty() {
+        return Err(ApiError::bad_request("Messages cannot be empty").with_param("messages"));
+    }
+
+    let tags = usage_tags.merge(request.metadata.as_ref())?;
+    let tools = tool_specs(&state, &assistant).await?;
+
+    let locale = request_locale.or_field(request.locale.as_deref());
+    let system_prompt = system_prompt(
+        &state,
+        &api_key,
+        &assistant,
+        request.variables,
+        locale.as_deref(),
+        &request.messages,
+    )
+    .await?;
+    let system_prompt = format!("{}\n\n{}", system_prompt, tool_instructions(&tools));
+    let client_messages: Vec<ChatMessage> = request
+        .messages
+        .into_iter()
+        .filter(|message| message.role != ChatMessageRole::System)
+        .collect();
+    let mut messages = vec![Message::system(system_prompt)];
+    messages.extend(
+        convert_messages(&client_messages, None, locale.as_deref(), &state, None).await?,
+    );
+
+    // Later turns grow with tool outputs; budgets are checked on the first
+    let model = enforce_budget(
+        &state,
+        budget_slot.as_ref().map(|Extension(slot)| slot),
+        &api_key,
+        Some(assistant.model_id()),
+        estimate_prompt_tokens(&messages),
+    )
+    .await?
+    .unwrap_or_else(|| assistant.model_id().to_string());
+    enforce_data_residency(&state, &api_key, &model).await?;
+
+    if enforce_injection_guard(&state, &api_key, &model, &mut messages)
+        .await
+        .is_some_and(|detection| detection.is_blocked())
+    {
+        return Err(injection_blocked_error());
+    }
+
+    enforce_content_policies(
+        &state,
+        &api_key,
+        PolicyStage::Input,
+        &policy_input_text(&messages),
+    )
+    .await?;
+
+    let runtime = AgentRuntime::new(state.agent_limits.capped(
+        request.max_iterations,
+        request.tool_timeout_secs,
+        request.max_cost_micros,
+    ));
+    let run_id = format!("run-{}", Uuid::new_v4());
+
+    info!(
+        run_id = %run_id,
+        assistant_id = %assistant_id,
+        model = %model,
+        tools = tools.len(),
+        max_iterations = runtime.limits().max_iterations,
+        "Starting agent run"
+    );
+
+    let agent_model = GatewayAgentModel {
+        provider: get_provider_for_model(&state, &model).await,
+        state: state.clone(),
+        api_key: api_key.clone(),
+        model: model.clone(),
+        temperature: request.temperature,
+        max_tokens: request.max_tokens,
+        response_format: decision_response_format(&tools),
+        tags: tags.clone(),
+    };
+    let agent_tools = AssistantAgentTools {
+        state: state.clone(),
+        assistant: Arc::new(assistant),
+        api_key: api_key.clone(),
+        tags,
+    };
+
+    if !request.stream {
+        let outcome = runtime
+            .run(messages, &agent_model, &agent_tools, None)
+            .await?;
+        if let Some(answer) = &outcome.answer {
+            enforce_content_policies(&state, &api_key, PolicyStage::Output, answer).await?;
+        }
+
+        return Ok(Json(AssistantRunResponse::new(
+            run_id,
+            assistant_id,
+            model,
+            outcome,
+        ))
+        .into_response());
+    }
+
+    let (event_tx, event_rx) = mpsc::channel::<AgentEvent>(32);
+    let (done_tx, done_rx) = oneshot::channel();
+
+    spawn_tracked(async move {
+        let outcome = runtime
+            .run(messages, &agent_model, &agent_tools, Some(&event_tx))
+            .await;
+        drop(event_tx);
+
+        let done = match outcome {
+            Ok(outcome) => {
+                let policy = match &outcome.answer {
+                    Some(answer) => {
+                        enforce_content_policies(&state, &api_key, PolicyStage::Output, answer)
+                            .await
+                    }
+                    None => Ok(()),
+                };
+
+                match policy {
+                    Ok(()) => json!({
+                        "type": "done",
+                        "result": AssistantRunResponse::new(run_id, assistant_id, model, outcome),
+                    }),
+                    Err(e) => json!({"type": "error", "error": e.response.error}),
+                }
+            }
+            Err(e) => json!({"type": "error", "error": ApiError::from(e).response.error}),
+        };
+        let _ = done_tx.send(done);
+    });
+
+    let events = ReceiverStream::new(event_rx).map(|event| {
+        Ok::<_, Infallible>(
+            Event::default().data(serde_json::to_string(&event).unwrap_or_default()),
+        )
+    });
+    let done = stream::once(async move {
+        let done = done_rx.await.unwrap_or_else(
+            |_| json!({"type": "error", "error": {"message": "Agent run aborted"}}),
+        );
+        Ok(Event::default().data(done.to_string()))
+    });
+    let end = stream::once(async { Ok(Event::default().data("[DONE]")) });
+
+    Ok(Sse::new(events.chain(done).chain(end))
+        .keep_alive(KeepAlive::default())
+        .into_response())
+}
+
+/// Model of an agent run, called through the gateway's provider routing with
+/// the usage of every call recorded
+struct GatewayAgentModel {
+    state: AppState,
+    provider: Arc<dyn LlmProvider>,
+    api_key: ApiKey,
+    model: String,
+    temperature: Option<f32>,
+    max_tokens: Option<u32>,
+    response_format: LlmResponseFormat,
+    tags: HashMap<String, String>,
+}
+
+#[async_trait]
+impl AgentModel for GatewayAgentModel {
+    async fn complete(&self, messages: Vec<Message>) -> Result<AgentModelTurn, DomainError> {
+        let mut request = LlmRequest::new(messages);
+        request.temperature = self.temperature;
+        request.max_tokens = self.max_tokens;
+        request.response_format = Some(self.response_format.clone());
+
+        let start = Instant::now();
+        let response =
+            call_provider(&self.state, self.provider.as_ref(), &self.model, request).await?;
+        let (input_tokens, output_tokens) = response
+            .usage
+            .as_ref()
+            .map_or((0, 0), |u| (u.prompt_tokens, u.completion_tokens));
+
+        record_request_usage(
+            &self.state,
+            &self.api_key,
+            &self.model,
+            input_tokens,
+            output_tokens,
+            start.elapsed().as_millis() as u64,
+            &self.tags,
+        )
+        .await;
+
+        Ok(AgentModelTurn {
+            content: response.content().unwrap_or_default().to_string(),
+            input_tokens,
+            output_tokens,
+            cost_micros: estimate_cost(&self.state, &self.model, input_tokens, output_tokens).await,
+        })
+    }
+}
+
+/// Tool workflows of an assistant, with the usage of every execution recorded
+struct AssistantAgentTools {
+    state: AppState,
+    assistant: Arc<Assistant>,
+    api_key: ApiKey,
+    tags: HashMap<String, String>,
+}
+
+#[async_trait]
+impl AgentTools for AssistantAgentTools {
+    async fn call(&self, tool: &str, input: Value) -> Result<AgentToolOutput, DomainError> {
+        if !self.assistant.allows_tool(tool) {
+            return Err(DomainError::not_found(format!(
+                "Tool '{}' is not available",
+                tool
+            )));
+        }
+
+        let start = Instant::now();
+        let result = self
+            .state
+            .workflow_service
+            .execute_as(tool, input, KnowledgeBaseCaller::from_api_key(&self.api_key))
+            .await;
+
+        record_workflow_usage(
+            &self.state,
+            &self.api_key,
+            tool,
+            start.elapsed().as_millis() as u64,
+            &result,
+            self.tags.clone(),
+        )
+        .await;
+
+        let result = result?;
+        if !result.success {
+            return Err(DomainError::internal(
+                result
+                    .error
+                    .unwrap_or_else(|| "Workflow execution failed".to_string()),
+            ));
+        }
+
+        let (input_tokens, output_tokens) = result
+            .token_usage
+            .as_ref()
+            .map_or((0, 0), |u| (u.input_tokens, u.output_tokens));
+
+        Ok(AgentToolOutput {
+            output: result.output,
+            input_tokens,
+            output_tokens,
+            cost_micros: result.cost_micros.unwrap_or(0),
+        })
+    }
+}
+
+/// Rendered system prompt of an assistant, in the variant best matching the
+/// accepted locales, with the passages its knowledge bases return for the
+/// latest user message
+async fn system_prompt(
+    state: &AppState,
+    api_key: &ApiKey,
+    assistant: &Assistant,
+    variables: Option<HashMap<String, String>>,
+    locale: Option<&str>,
+    messages: &[ChatMessage],
+) -> Result<String, ApiError> {
+    let mut merged = assistant.prompt_variables().clone();
+    merged.extend(variables.unwrap_or_default());
+
+    let prompt = state
+        .prompt_service
+        .render_variant(assistant.prompt_id(), None, locale, &merged)
+        .await
+        .map_err(|e| {
+            ApiError::bad_request(format!(
+                "Failed to render the system prompt of assistant '{}': {}",
+                assistant.id(),
+                e
+            ))
+            .with_param("variables")
+        })?;
+
+    let passages = match last_user_text(messages) {
+        Some(query) => {
+            let caller = KnowledgeBaseCaller::from_api_key(api_key);
+            state
+                .assistant_service
+                .retrieve(assistant, &query, Some(&caller))
+                .await
+        }
+        None => Vec::new(),
+    };
+
+    Ok(system_prompt_with_context(&prompt, &passages))
+}
+
+/// Tools of an assistant as offered to the model, described by their
+/// workflows where the assistant does not
+async fn tool_specs(
+    state: &AppState,
+    assistant: &Assistant,
+) -> Result<Vec<AgentToolSpec>, ApiError> {
+    let mut specs = Vec::with_capacity(assistant.tools().len());
+
+    for tool in assistant.tools() {
+        let workflow = state.workflow_service.get(&tool.workflow_id).await?;
+
+        specs.push(AgentToolSpec {
+            name: tool.workflow_id.clone(),
+            description: tool.description.clone().or_else(|| {
+                workflow
+                    .as_ref()
+                    .and_then(|w| w.description().map(String::from))
+            }),
+            input_schema: workflow.and_then(|w| w.input_schema().cloned()),
+        });
+    }
+
+    Ok(specs)
+}
+
+/// The assistant, unless it is missing or disabled
+async fn get_enabled(state: &AppState, assistant_id: &str) -> Result<Assistant, ApiError> {
+    state
+        .assistant_service
+        .get(assistant_id)
+        .await?
+        .filter(Assistant::is_enabled)
+        .ok_or_else(|| ApiError::not_found(format!("Assistant '{}' not found", assistant_id)))
+}
+
+/// Text of the latest user message, the knowledge base query
+fn last_user_text(messages: &[ChatMessage]) -> Option<String> {
+    messages
+        .iter()
+        .rev()
+        .find(|message| message.role == ChatMessageRole::User)
+        .and_then(|message| message.content.as_ref())
+        .map(MessageContent::to_text)
+        .filter(|text| !text.trim().is_empty())
+}
+
+fn system_message(content: String) -> ChatMessage {
+    ChatMessage {
+        role: ChatMessageRole::System,
+        content: Some(MessageContent::Text(content)),
+        name: None,
+        tool_calls: None,
+        tool_call_id: None,
+        function_call: None,
+        prompt_id: None,
+        variables: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(role: ChatMessageRole, content: &str) -> ChatMessage {
+        ChatMessage {
+            role,
+            content: Some(MessageContent::Text(content.to_string())),
+            ..system_message(String::new())
+        }
+    }
+
+    #[test]
+    fn test_last_user_text() {
+        let messages = vec![
+            message(ChatMessageRole::User, "Where is my order?"),
+            message(ChatMessageRole::Assistant, "Which order?"),
+            message(ChatMessageRole::User, "Order 42"),
+        ];
+        assert_eq!(last_user_text(&messages).as_deref(), Some("Order 42"));
+
+        let messages = vec![message(ChatMessageRole::Assistant, "Hello")];
+        assert_eq!(last_user_text(&messages), None);
+    }
+
+    #[test]
+    fn test_chat_request_defaults() {
+        let request: AssistantChatRequest =
+            serde_json::from_str(r#"{"messages": [{"role": "user", "content": "Hi"}]}"#).unwrap();
+
+        assert!(!request.stream);
+        assert!(request.variables.is_none());
+        assert_eq!(request.messages.len(), 1);
+    }
+
+    #[test]
+    fn test_run_request_defaults() {
+        let request: AssistantRunRequest = serde_json::from_str(
+            r#"{"messages": [{"role": "user", "content": "Hi"}], "max_iterations": 3}"#,
+        )
+        .unwrap();
+
+        assert!(!request.stream);
+        assert_eq!(request.max_iterations, Some(3));
+        assert!(request.tool_timeout_secs.is_none());
+        assert!(request.max_cost_micros.is_none());
+    }
+}
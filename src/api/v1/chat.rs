@@ -1,8 +1,8 @@
 //! Chat completions endpoint handler
 
 use axum::{
-    extract::{Query, State},
-    http::StatusCode,
+    extract::{Extension, OriginalUri, Query, State},
+    http::{HeaderValue, StatusCode},
     response::{
         sse::{Event, Sse},
         IntoResponse, Response,
@@ -13,31 +13,91 @@ use crate::api::types::Json;
 use futures::stream::{Stream, StreamExt};
 use serde_json::json;
 use tokio_stream::wrappers::ReceiverStream;
-use tracing::{debug, error, info, warn};
-use uuid::Uuid;
-
-use std::time::Instant;
-
-use crate::api::middleware::RequireApiKey;
+use tracing::{debug, error, info, info_span, warn, Instrument, Span};
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::api::middleware::{
+    apply_team_request_defaults, apply_transform_rules, enforce_budget, enforce_content_policies,
+    enforce_data_residency, enforce_injection_guard, enforce_parameter_policy, enforce_secret_scan,
+    estimate_cost, estimate_prompt_tokens, injection_blocked_error, no_log_async_error,
+    policy_input_text, record_request_usage, record_secret_leaks, redact_sensitive_fields,
+    secret_leak_error, secret_stream_filter, team_request_defaults, zero_retention, BudgetSlot,
+    RequestLocale, RequestTracing, RequireApiKey, TransformSlot, UsageTags,
+};
 use crate::api::state::AppState;
 use crate::api::types::{
     ApiError, AsyncOperationCreated, AsyncQueryParams, ChatCompletionRequest,
     ChatCompletionResponse, ChatCompletionStreamResponse, ChatMessage, ChatMessageRole,
+    FinishReason, SystemPromptRef,
 };
-use crate::domain::experiment::AssignmentResult;
-use crate::domain::llm::{LlmProvider, LlmRequest, Message};
-use crate::domain::OperationType;
-use crate::infrastructure::services::RecordExperimentParams;
-
-/// POST /v1/chat/completions
+use crate::api::v1::shadow::{sample_shadow, spawn_shadow_request, ServedRequest};
+use crate::domain::api_key::ApiKey;
+use crate::domain::cache::{CacheMode, CachePolicy};
+use crate::domain::experiment::{AssignmentResult, ConfigOverrides};
+use crate::domain::guardrail::{InjectionDetection, PolicyStage, SecretLeakAction};
+use crate::domain::llm::{
+    FinishReason as DomainFinishReason, GatewayErrorCode, LlmProvider, LlmRequest, LlmResponse, Message, MessageRole, ProviderFailure,
+};
+use crate::domain::request_trace::RequestTraceStage;
+use crate::domain::usage::UsageRecord;
+use crate::domain::{
+    DomainError, Executor, OperationType, StreamAbortReason, StreamingMetrics,
+};
+use crate::infrastructure::background::spawn_tracked;
+use crate::infrastructure::llm::DEFAULT_CREDENTIAL_COOLDOWN;
+use crate::infrastructure::request_trace::RequestTracer;
+use crate::infrastructure::observability::{
+    provider_call_span, provider_error_status, record_error, record_llm_request,
+    record_token_usage, LlmRequestMetricParams, QueueDepthGuard,
+};
+use crate::infrastructure::services::{RecordExecutionParams, RecordExperimentParams};
+
+/// Response header naming the experiment a request was assigned to
+pub const EXPERIMENT_ID_HEADER: &str = "x-experiment-id";
+/// Response header naming the experiment variant a request was assigned to
+pub const EXPERIMENT_VARIANT_HEADER: &str = "x-experiment-variant";
+/// Response header naming the cache (`exact` or `semantic`) that served a
+/// request
+pub const CACHE_HEADER: &str = "x-pmp-cache";
+
+/// Create chat completion
+#[utoipa::path(
+    post,
+    path = "/v1/chat/completions",
+    tag = "v1",
+    params(AsyncQueryParams),
+    request_body = ChatCompletionRequest,
+    responses(
+        (
+            status = 200,
+            description = "Chat completion, streamed as `chat.completion.chunk` events when `stream` is set",
+            content(
+                (ChatCompletionResponse = "application/json"),
+                (ChatCompletionStreamResponse = "text/event-stream"),
+            )
+        ),
+        (status = 202, description = "Async operation created when `async=true`", body = AsyncOperationCreated),
+    ),
+)]
+#[allow(clippy::too_many_arguments)]
 pub async fn create_chat_completion(
     State(state): State<AppState>,
     RequireApiKey(api_key): RequireApiKey,
+    RequestTracing(tracer): RequestTracing,
+    budget_slot: Option<Extension<BudgetSlot>>,
+    transform_slot: Option<Extension<TransformSlot>>,
+    OriginalUri(uri): OriginalUri,
+    usage_tags: UsageTags,
+    request_locale: RequestLocale,
     Query(async_params): Query<AsyncQueryParams>,
-    Json(request): Json<ChatCompletionRequest>,
+    Json(mut request): Json<ChatCompletionRequest>,
 ) -> Result<Response, ApiError> {
-    let request_id = Uuid::new_v4().to_string();
+    let request_id = tracer.request_id().to_string();
     let api_key_id = api_key.id().as_str().to_string();
+    // Kept on the request so async executions render the same variants
+    request.locale = request_locale.or_field(request.locale.as_deref());
 
     info!(
         request_id = %request_id,
@@ -59,10 +119,63 @@ pub async fn create_chat_completion(
         ));
     }
 
+    // Zero-retention keys never have request or completion bodies stored
+    let no_log = zero_retention(&state, &api_key).await;
+    if no_log && async_params.is_async {
+        return Err(no_log_async_error());
+    }
+
+    // Rewrite the request with the matching transform rules
+    let transform = apply_transform_rules(
+        &state,
+        uri.path(),
+        &api_key,
+        &mut request,
+        transform_slot.as_ref().map(|Extension(slot)| slot),
+    )
+    .await?;
+    if !transform.is_empty() {
+        tracer.record_with(
+            RequestTraceStage::Routing,
+            format!("Applied transform rules: {}", transform.rule_ids.join(", ")),
+            json!({
+                "model": transform.model,
+                "system_messages": transform.system_messages.len(),
+                "tags": transform.tags,
+                "headers": transform.headers.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            }),
+        );
+    }
+
+    let tags = usage_tags.merge(request.metadata.as_ref())?;
+
+    // Check the parameters the client sent against the key's policy
+    enforce_parameter_policy(&api_key, &request).inspect_err(|e| {
+        trace_rejection(&tracer, RequestTraceStage::Guardrail, "API key parameter policy", e)
+    })?;
+
+    // Apply the team's default model and parameter limits
+    let team_defaults = team_request_defaults(&state, &api_key).await?;
+    let requested = (request.model.clone(), request.temperature, request.max_tokens);
+    apply_team_request_defaults(&team_defaults, &mut request).inspect_err(|e| {
+        trace_rejection(&tracer, RequestTraceStage::Guardrail, "Team parameter limits", e)
+    })?;
+    if requested != (request.model.clone(), request.temperature, request.max_tokens) {
+        tracer.record_with(
+            RequestTraceStage::Routing,
+            "Applied team request defaults",
+            json!({
+                "model": &request.model,
+                "temperature": request.temperature,
+                "max_tokens": request.max_tokens,
+            }),
+        );
+    }
+
     // Check for experiment assignment
     let experiment_assignment = state
         .experiment_service
-        .assign_variant(&request.model, &api_key_id)
+        .assign_variant(&request.model, &api_key_id, request.user.as_deref())
         .await
         .unwrap_or_else(|e| {
             warn!(error = %e, "Failed to check experiment assignment, proceeding without");
@@ -78,6 +191,18 @@ pub async fn create_chat_completion(
                 assigned_model = %assignment.model_id,
                 "Experiment assignment active"
             );
+            tracer.record_with(
+                RequestTraceStage::Routing,
+                format!(
+                    "Assigned to variant '{}' of experiment '{}'",
+                    assignment.variant_id, assignment.experiment_id
+                ),
+                json!({
+                    "experiment_id": &assignment.experiment_id,
+                    "variant_id": &assignment.variant_id,
+                    "model_id": &assignment.model_id,
+                }),
+            );
             (
                 assignment.model_id.clone(),
                 assignment.config_overrides.clone(),
@@ -86,49 +211,259 @@ pub async fn create_chat_completion(
         None => (request.model.clone(), None),
     };
 
-    // Convert messages to domain format
-    let messages = convert_messages(&request.messages, &state).await?;
+    // Convert messages to domain format, swapping in the variant's prompt
+    let mut messages = convert_messages(
+        &request.messages,
+        request.system.as_ref(),
+        request.locale.as_deref(),
+        &state,
+        config_overrides.as_ref(),
+    )
+    .await?;
+    team_defaults.prefix_system_prompt(&mut messages);
+
+    // Enforce budgets before calling the provider, possibly degrading to a cheaper model
+    let prompt_tokens = estimate_prompt_tokens(&messages);
+    let budget_model = enforce_budget(
+        &state,
+        budget_slot.as_ref().map(|Extension(slot)| slot),
+        &api_key,
+        Some(&effective_model),
+        prompt_tokens,
+    )
+    .await
+    .inspect_err(|e| trace_rejection(&tracer, RequestTraceStage::Routing, "Budget", e))?
+    .unwrap_or_else(|| effective_model.clone());
+    if budget_model != effective_model {
+        tracer.record(
+            RequestTraceStage::Routing,
+            format!(
+                "Budget exceeded for model '{}', routed to fallback model '{}'",
+                effective_model, budget_model
+            ),
+        );
+    }
+    let effective_model = route_around_degraded_model(&state, budget_model.clone()).await;
+    if effective_model != budget_model {
+        tracer.record(
+            RequestTraceStage::Routing,
+            format!(
+                "Model '{}' degraded by failing canaries or a breached SLO, routed to fallback model '{}'",
+                budget_model, effective_model
+            ),
+        );
+    }
+
+    // Keep provisioned deployments within their throughput, counting `max_tokens`
+    // like the providers do
+    let healthy_model = effective_model;
+    let requested_tokens = u64::from(prompt_tokens) + u64::from(request.max_tokens.unwrap_or(0));
+    let effective_model =
+        route_within_provisioned_capacity(&state, healthy_model.clone(), requested_tokens).await;
+    if effective_model != healthy_model {
+        tracer.record(
+            RequestTraceStage::Routing,
+            format!(
+                "Provisioned capacity of model '{}' exhausted, routed to fallback model '{}'",
+                healthy_model, effective_model
+            ),
+        );
+    }
+
+    // Stay off credentials a provider throttled until their cooldown is over
+    let unthrottled_model = effective_model;
+    let effective_model =
+        route_around_throttled_credential(&state, unthrottled_model.clone()).await;
+    if effective_model != unthrottled_model {
+        tracer.record(
+            RequestTraceStage::Routing,
+            format!(
+                "Credential of model '{}' throttled by its provider, routed to fallback model '{}'",
+                unthrottled_model, effective_model
+            ),
+        );
+    }
+
+    // Keep region-pinned teams on provider endpoints in their allowed regions
+    enforce_data_residency(&state, &api_key, &effective_model)
+        .await
+        .inspect_err(|e| {
+            trace_rejection(&tracer, RequestTraceStage::Guardrail, "Data residency", e)
+        })?;
+
+    // Scan user messages for prompt injection, sanitizing them if configured
+    let injection =
+        enforce_injection_guard(&state, &api_key, &effective_model, &mut messages).await;
+
+    if let Some(detection) = &injection {
+        tracer.record_with(
+            RequestTraceStage::Guardrail,
+            format!("Prompt injection detected (score {:.2})", detection.score),
+            serde_json::to_value(detection).unwrap_or_default(),
+        );
+    }
 
-    // Build LLM request with potential experiment overrides
-    let llm_request = build_llm_request_with_overrides(&request, messages, &config_overrides)?;
+    if let Some(detection) = injection.as_ref().filter(|d| d.is_blocked()) {
+        capture_payload(
+            &state,
+            &api_key,
+            &effective_model,
+            serde_json::to_value(&request).unwrap_or_default(),
+            Err("Blocked by the prompt injection guard".to_string()),
+            0,
+            Some(detection.clone()),
+            no_log,
+            None,
+            &tags,
+            &request_id,
+        );
+        return Err(injection_blocked_error());
+    }
+
+    // Evaluate the team's content policies on the user messages
+    let input_text = policy_input_text(&messages);
+    let input_verdict =
+        enforce_content_policies(&state, &api_key, PolicyStage::Input, &input_text).await;
+    trace_guardrail(&tracer, "Input content policies", &input_verdict);
+    if let Err(e) = input_verdict {
+        capture_payload(
+            &state,
+            &api_key,
+            &effective_model,
+            serde_json::to_value(&request).unwrap_or_default(),
+            Err(e.response.error.message.clone()),
+            0,
+            injection,
+            no_log,
+            None,
+            &tags,
+            &request_id,
+        );
+        return Err(e);
+    }
+
+    // Build LLM request with potential experiment overrides, held to the team
+    // and API key limits
+    let mut llm_request =
+        build_llm_request_with_overrides(&request, messages, &config_overrides)?;
+    team_defaults.clamp_request(&mut llm_request);
+    api_key.permissions().parameters.clamp_request(&mut llm_request);
 
     // Handle async mode
     if async_params.is_async {
-        return handle_async_chat_completion(
+        let variant_assignment = experiment_assignment.clone();
+        let mut response = handle_async_chat_completion(
             state,
             request,
             llm_request,
-            request_id,
+            tracer,
             effective_model,
-            api_key_id,
+            api_key,
             experiment_assignment,
+            tags,
+            injection,
         )
-        .await;
+        .await?;
+        tag_experiment_variant(&mut response, variant_assignment.as_ref());
+
+        return Ok(response);
     }
 
+    let request_payload = serde_json::to_value(&request).unwrap_or_default();
+
     if request.stream {
         // Streaming response - experiment recording is handled inside
+        let variant_assignment = experiment_assignment.clone();
         let stream = create_stream_response(
             state,
             llm_request,
+            request_payload,
             effective_model.clone(),
-            request_id,
-            api_key_id,
+            tracer,
+            api_key,
             experiment_assignment,
+            prompt_tokens,
+            tags,
+            injection,
+            no_log,
         )
         .await;
-        Ok(Sse::new(stream)
+        let mut response = Sse::new(stream)
             .keep_alive(axum::response::sse::KeepAlive::default())
-            .into_response())
+            .into_response();
+        tag_experiment_variant(&mut response, variant_assignment.as_ref());
+
+        Ok(response)
     } else {
+        // Serve the request from the cache its model or prompt policy selects
+        let cache_policy =
+            resolve_cache_policy(&state, &effective_model, &request, config_overrides.as_ref())
+                .await;
+        if let Some(hit) = state
+            .response_cache
+            .lookup(&effective_model, &llm_request, &cache_policy)
+            .await
+        {
+            tracer.record(
+                RequestTraceStage::Cache,
+                format!("Served from the {} cache", cache_mode_name(hit.mode)),
+            );
+
+            let chat_response =
+                ChatCompletionResponse::from_llm_response(&hit.response, &effective_model, &request_id);
+            capture_payload(
+                &state,
+                &api_key,
+                &effective_model,
+                request_payload,
+                Ok(serde_json::to_value(&chat_response).unwrap_or_default()),
+                0,
+                injection,
+                no_log,
+                None,
+                &tags,
+                &request_id,
+            );
+
+            let mut response = Json(chat_response).into_response();
+            response.headers_mut().insert(
+                CACHE_HEADER,
+                HeaderValue::from_static(cache_mode_name(hit.mode)),
+            );
+            tag_experiment_variant(&mut response, experiment_assignment.as_ref());
+
+            return Ok(response);
+        }
+
+        let cached_request = (cache_policy.mode != CacheMode::Disabled).then(|| {
+            tracer.record(
+                RequestTraceStage::Cache,
+                format!("Not in the {} cache", cache_mode_name(cache_policy.mode)),
+            );
+            llm_request.clone()
+        });
+
         // Non-streaming response with experiment tracking
         let start_time = Instant::now();
 
+        // Mirror a share of the model's requests to its shadow model
+        let shadow = sample_shadow(&state, &effective_model)
+            .await
+            .map(|shadow| (shadow, llm_request.clone()));
+
         // Get provider using router based on model configuration
         let provider = get_provider_for_model(&state, &effective_model).await;
-        let response_result = provider.chat(&effective_model, llm_request).await;
+        let response_result =
+            call_provider(&state, provider.as_ref(), &effective_model, llm_request).await;
 
         let latency_ms = start_time.elapsed().as_millis() as u64;
+        trace_provider_result(
+            &tracer,
+            provider.provider_name(),
+            &effective_model,
+            latency_ms,
+            &response_result,
+        );
 
         // Record experiment if assigned
         if let Some(assignment) = &experiment_assignment {
@@ -148,7 +483,9 @@ pub async fn create_chat_completion(
             record_experiment_result(
                 &state,
                 assignment,
+                &request_id,
                 &api_key_id,
+                &effective_model,
                 input_tokens,
                 output_tokens,
                 latency_ms,
@@ -158,7 +495,74 @@ pub async fn create_chat_completion(
             .await;
         }
 
-        let response = response_result.map_err(ApiError::from)?;
+        let mut response = match response_result {
+            Ok(response) => response,
+            Err(e) => {
+                capture_payload(
+                    &state,
+                    &api_key,
+                    &effective_model,
+                    request_payload,
+                    Err(e.to_string()),
+                    latency_ms,
+                    injection,
+                    no_log,
+                    None,
+                    &tags,
+                    &request_id,
+                );
+                return Err(e.into());
+            }
+        };
+
+        let (input_tokens, output_tokens) = response
+            .usage
+            .as_ref()
+            .map_or((prompt_tokens, 0), |u| (u.prompt_tokens, u.completion_tokens));
+        let usage_record = record_request_usage(
+            &state,
+            &api_key,
+            &effective_model,
+            input_tokens,
+            output_tokens,
+            latency_ms,
+            &tags,
+        )
+        .await;
+        trace_usage(&tracer, usage_record.as_ref());
+
+        // Redact leaked secrets, then evaluate the team's content policies
+        // on the answer
+        let guarded = match enforce_secret_scan(&state, &effective_model, &mut response).await {
+            Ok(()) => {
+                enforce_content_policies(
+                    &state,
+                    &api_key,
+                    PolicyStage::Output,
+                    response.content().unwrap_or_default(),
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        trace_guardrail(&tracer, "Output guardrails", &guarded);
+
+        if let Err(e) = guarded {
+            capture_payload(
+                &state,
+                &api_key,
+                &effective_model,
+                request_payload,
+                Err(e.response.error.message.clone()),
+                latency_ms,
+                injection,
+                no_log,
+                None,
+                &tags,
+                &request_id,
+            );
+            return Err(e);
+        }
 
         let chat_response = ChatCompletionResponse::from_llm_response(
             &response,
@@ -166,7 +570,97 @@ pub async fn create_chat_completion(
             &request_id,
         );
 
-        Ok(Json(chat_response).into_response())
+        // Only answers that passed the output guardrails are cached
+        if let Some(cached_request) = cached_request {
+            state
+                .response_cache
+                .store(&effective_model, &cached_request, response.clone(), &cache_policy)
+                .await;
+        }
+
+        if let Some((shadow, shadow_request)) = shadow {
+            let served = ServedRequest {
+                model_id: effective_model.clone(),
+                request: shadow_request,
+                payload: request_payload.clone(),
+                answer: response.content().unwrap_or_default().to_string(),
+                latency_ms,
+            };
+            spawn_shadow_request(&state, &api_key, shadow, served, &tags, &request_id, no_log);
+        }
+
+        capture_payload(
+            &state,
+            &api_key,
+            &effective_model,
+            request_payload,
+            Ok(serde_json::to_value(&chat_response).unwrap_or_default()),
+            latency_ms,
+            injection,
+            no_log,
+            None,
+            &tags,
+            &request_id,
+        );
+
+        let mut response = Json(chat_response).into_response();
+        tag_experiment_variant(&mut response, experiment_assignment.as_ref());
+
+        Ok(response)
+    }
+}
+
+/// Cache policy of a request: its model's and prompt's (the experiment
+/// variant's prompt, else the system or first message prompt), else the
+/// `[cache]` default
+async fn resolve_cache_policy(
+    state: &AppState,
+    model_id: &str,
+    request: &ChatCompletionRequest,
+    overrides: Option<&ConfigOverrides>,
+) -> CachePolicy {
+    let model = match state.model_service.get(model_id).await {
+        Ok(Some(model)) => model.config().cache_policy.clone(),
+        _ => None,
+    };
+
+    let prompt_id = overrides
+        .and_then(|o| o.prompt_id.as_deref())
+        .or_else(|| request.system.as_ref().map(|s| s.prompt_id.as_str()))
+        .or_else(|| request.messages.iter().find_map(|m| m.prompt_id.as_deref()));
+    let prompt = match prompt_id {
+        Some(prompt_id) => match state.prompt_service.get(prompt_id).await {
+            Ok(Some(prompt)) => prompt.cache_policy().cloned(),
+            _ => None,
+        },
+        None => None,
+    };
+
+    state.response_cache.policy(model.as_ref(), prompt.as_ref())
+}
+
+/// Name of a cache mode, as reported in the `x-pmp-cache` header
+fn cache_mode_name(mode: CacheMode) -> &'static str {
+    match mode {
+        CacheMode::Disabled => "disabled",
+        CacheMode::Exact => "exact",
+        CacheMode::Semantic => "semantic",
+    }
+}
+
+/// Tag a response with the experiment variant its request was assigned to
+fn tag_experiment_variant(response: &mut Response, assignment: Option<&AssignmentResult>) {
+    let Some(assignment) = assignment else {
+        return;
+    };
+
+    let headers = response.headers_mut();
+
+    if let Ok(value) = HeaderValue::from_str(&assignment.experiment_id) {
+        headers.insert(EXPERIMENT_ID_HEADER, value);
+    }
+    if let Ok(value) = HeaderValue::from_str(&assignment.variant_id) {
+        headers.insert(EXPERIMENT_VARIANT_HEADER, value);
     }
 }
 
@@ -174,22 +668,28 @@ pub async fn create_chat_completion(
 ///
 /// Returns a boxed future to avoid stack overflow from large future sizes
 /// caused by trait object indirection in AppState services.
+#[allow(clippy::too_many_arguments)]
 fn handle_async_chat_completion(
     state: AppState,
     request: ChatCompletionRequest,
     llm_request: LlmRequest,
-    request_id: String,
+    tracer: RequestTracer,
     effective_model: String,
-    api_key_id: String,
+    api_key: ApiKey,
     experiment_assignment: Option<AssignmentResult>,
+    tags: HashMap<String, String>,
+    injection: Option<InjectionDetection>,
 ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Response, ApiError>> + Send>> {
     Box::pin(async move {
+    let request_payload = serde_json::to_value(&request).unwrap_or(json!({}));
+    let request_id = tracer.request_id();
+
     // Create pending operation
     let operation = state
         .operation_service
         .create_pending(
             OperationType::ChatCompletion,
-            serde_json::to_value(&request).unwrap_or(json!({})),
+            request_payload.clone(),
             json!({ "model": &effective_model, "request_id": &request_id }),
         )
         .await
@@ -205,16 +705,34 @@ fn handle_async_chat_completion(
     // Clone values for the background task
     let op_id = operation_id.clone();
 
+    // The operation outlives the request, so it gets its own trace linked to the request's
+    let span = info_span!(parent: None, "chat.async_operation", operation.id = %op_id);
+    span.follows_from(Span::current());
+
+    // Counted in the queue depth until the operation finishes
+    let queued = QueueDepthGuard::enter("async_chat_completions");
+
     // Spawn the background work - the function returns a boxed future to avoid stack overflow
-    tokio::spawn(run_async_chat_completion(
-        state,
-        op_id,
-        effective_model,
-        llm_request,
-        request_id,
-        api_key_id,
-        experiment_assignment,
-    ));
+    spawn_tracked(
+        async move {
+            let _queued = queued;
+
+            run_async_chat_completion(
+                state,
+                op_id,
+                effective_model,
+                llm_request,
+                tracer,
+                api_key,
+                experiment_assignment,
+                tags,
+                request_payload,
+                injection,
+            )
+            .await
+        }
+        .instrument(span),
+    );
 
     // Return 202 Accepted
     Ok((
@@ -229,16 +747,24 @@ fn handle_async_chat_completion(
 ///
 /// Returns a boxed future to avoid stack overflow from large future sizes
 /// caused by trait object indirection in AppState.
+#[allow(clippy::too_many_arguments)]
 fn run_async_chat_completion(
     state: AppState,
     operation_id: String,
     model: String,
     llm_request: LlmRequest,
-    request_id: String,
-    api_key_id: String,
+    tracer: RequestTracer,
+    api_key: ApiKey,
     experiment_assignment: Option<AssignmentResult>,
+    tags: HashMap<String, String>,
+    request_payload: serde_json::Value,
+    injection: Option<InjectionDetection>,
 ) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>> {
     Box::pin(async move {
+    // Taken before running, so a cancellation either fails the transition
+    // or fires the token
+    let cancellation = state.operation_service.cancellation_token(&operation_id);
+
     // Mark as running
     if let Err(e) = state.operation_service.mark_running(&operation_id).await {
         warn!(
@@ -252,10 +778,26 @@ fn run_async_chat_completion(
     // Execute chat completion with timing
     let start_time = Instant::now();
 
-    // Get provider using router based on model configuration
+    // Get provider using router based on model configuration; a cancellation
+    // drops the provider call, closing its connection
+    let request_id = tracer.request_id();
     let provider = get_provider_for_model(&state, &model).await;
-    let response_result = provider.chat(&model, llm_request).await;
+    let response_result = tokio::select! {
+        biased;
+        _ = cancellation.cancelled() => {
+            info!(operation_id = %operation_id, "Async chat completion cancelled");
+            return;
+        }
+        result = call_provider(&state, provider.as_ref(), &model, llm_request) => result,
+    };
     let latency_ms = start_time.elapsed().as_millis() as u64;
+    trace_provider_result(
+        &tracer,
+        provider.provider_name(),
+        &model,
+        latency_ms,
+        &response_result,
+    );
 
     // Record experiment if assigned
     if let Some(ref assignment) = experiment_assignment {
@@ -275,7 +817,9 @@ fn run_async_chat_completion(
         record_experiment_result(
             &state,
             assignment,
-            &api_key_id,
+            request_id,
+            api_key.id().as_str(),
+            &model,
             input_tokens,
             output_tokens,
             latency_ms,
@@ -285,12 +829,65 @@ fn run_async_chat_completion(
         .await;
     }
 
-    match response_result {
-        Ok(response) => {
-            let chat_response =
-                ChatCompletionResponse::from_llm_response(&response, &model, &request_id);
+    let outcome = match response_result {
+        Ok(mut response) => {
+            if let Some(usage) = &response.usage {
+                let usage_record = record_request_usage(
+                    &state,
+                    &api_key,
+                    &model,
+                    usage.prompt_tokens,
+                    usage.completion_tokens,
+                    latency_ms,
+                    &tags,
+                )
+                .await;
+                trace_usage(&tracer, usage_record.as_ref());
+            }
+
+            // Redact leaked secrets, then evaluate the team's content
+            // policies on the answer
+            let guarded = match enforce_secret_scan(&state, &model, &mut response).await {
+                Ok(()) => {
+                    enforce_content_policies(
+                        &state,
+                        &api_key,
+                        PolicyStage::Output,
+                        response.content().unwrap_or_default(),
+                    )
+                    .await
+                }
+                Err(e) => Err(e),
+            };
+            trace_guardrail(&tracer, "Output guardrails", &guarded);
 
-            let result = serde_json::to_value(&chat_response).unwrap_or(json!({}));
+            match guarded {
+                Ok(()) => {
+                    let chat_response =
+                        ChatCompletionResponse::from_llm_response(&response, &model, request_id);
+                    Ok(serde_json::to_value(&chat_response).unwrap_or(json!({})))
+                }
+                Err(e) => Err(e.response.error.message),
+            }
+        }
+        Err(e) => Err(e.to_string()),
+    };
+
+    match outcome {
+        Ok(result) => {
+            capture_payload(
+                &state,
+                &api_key,
+                &model,
+                request_payload,
+                Ok(result.clone()),
+                latency_ms,
+                injection,
+                api_key.no_log(),
+                None,
+                &tags,
+                request_id,
+            );
 
             if let Err(e) = state
                 .operation_service
@@ -306,8 +903,20 @@ fn run_async_chat_completion(
                 info!(operation_id = %operation_id, "Async chat completion succeeded");
             }
         }
-        Err(e) => {
-            let error_msg = e.to_string();
+        Err(error_msg) => {
+            capture_payload(
+                &state,
+                &api_key,
+                &model,
+                request_payload,
+                Err(error_msg.clone()),
+                latency_ms,
+                injection,
+                api_key.no_log(),
+                None,
+                &tags,
+                request_id,
+            );
 
             if let Err(mark_err) = state
                 .operation_service
@@ -332,28 +941,41 @@ fn run_async_chat_completion(
 }
 
 /// Convert API messages to domain messages, resolving prompt references
-async fn convert_messages(
+///
+/// A `system` prompt reference is rendered as the first system message,
+/// ahead of those of the messages. The prompt of experiment `overrides`
+/// (at its pinned version, if any) is rendered in place of every referenced
+/// prompt, with the variables of the reference; requests without prompt
+/// references get it as their system message instead. Prompts are rendered
+/// in the variant best matching `locale`.
+pub(super) async fn convert_messages(
     messages: &[ChatMessage],
+    system: Option<&SystemPromptRef>,
+    locale: Option<&str>,
     state: &AppState,
+    overrides: Option<&ConfigOverrides>,
 ) -> Result<Vec<Message>, ApiError> {
-    let mut result = Vec::with_capacity(messages.len());
+    let mut result = Vec::with_capacity(messages.len() + 1);
+    let prompt_override = overrides.and_then(|o| o.prompt_id.as_deref());
+    let override_version = overrides.and_then(|o| o.prompt_version);
+
+    if let Some(system) = system {
+        let rendered =
+            render_system_prompt(state, system, locale, prompt_override, override_version).await?;
+        result.push(Message::system(rendered));
+    }
 
     for msg in messages {
         let content = if let Some(prompt_id) = &msg.prompt_id {
             // Resolve prompt reference
+            let (prompt_id, version) = match prompt_override {
+                Some(prompt_override) => (prompt_override, override_version),
+                None => (prompt_id.as_str(), None),
+            };
             debug!(prompt_id = %prompt_id, "Resolving prompt reference");
 
             let variables = msg.variables.clone().unwrap_or_default();
-            let rendered = state
-                .prompt_service
-                .render(prompt_id, &variables)
-                .await
-                .map_err(|e| {
-                    ApiError::bad_request(format!("Failed to render prompt '{}': {}", prompt_id, e))
-                        .with_param("prompt_id")
-                })?;
-
-            rendered
+            render_prompt(state, prompt_id, version, locale, &variables).await?
         } else if let Some(content) = &msg.content {
             content.to_text()
         } else {
@@ -371,14 +993,71 @@ async fn convert_messages(
         result.push(message);
     }
 
+    if let Some(prompt_id) = prompt_override
+        && system.is_none()
+        && messages.iter().all(|msg| msg.prompt_id.is_none())
+    {
+        let rendered =
+            render_prompt(state, prompt_id, override_version, locale, &HashMap::new()).await?;
+
+        result.retain(|message| message.role != MessageRole::System);
+        result.insert(0, Message::system(rendered));
+    }
+
     Ok(result)
 }
 
+/// Render the `system` prompt reference of a request
+///
+/// An experiment `prompt_override` is rendered in its place with the
+/// reference's variables, at the `override_version` of the variant; the
+/// version pinned by the reference only applies to the referenced prompt.
+/// Pinned versions render their base content whatever the locale.
+async fn render_system_prompt(
+    state: &AppState,
+    system: &SystemPromptRef,
+    locale: Option<&str>,
+    prompt_override: Option<&str>,
+    override_version: Option<u32>,
+) -> Result<String, ApiError> {
+    let (prompt_id, version) = match prompt_override {
+        Some(prompt_id) => (prompt_id, override_version),
+        None => (system.prompt_id.as_str(), system.version),
+    };
+    debug!(prompt_id = %prompt_id, version = ?version, "Rendering system prompt");
+
+    state
+        .prompt_service
+        .render_variant(prompt_id, version, locale, &system.variables)
+        .await
+        .map_err(|e| {
+            ApiError::bad_request(format!("Failed to render prompt '{}': {}", prompt_id, e))
+                .with_param("system.prompt_id")
+        })
+}
+
+async fn render_prompt(
+    state: &AppState,
+    prompt_id: &str,
+    version: Option<u32>,
+    locale: Option<&str>,
+    variables: &HashMap<String, String>,
+) -> Result<String, ApiError> {
+    state
+        .prompt_service
+        .render_variant(prompt_id, version, locale, variables)
+        .await
+        .map_err(|e| {
+            ApiError::bad_request(format!("Failed to render prompt '{}': {}", prompt_id, e))
+                .with_param("prompt_id")
+        })
+}
+
 /// Build LLM request from API request with experiment config overrides
 fn build_llm_request_with_overrides(
     request: &ChatCompletionRequest,
     messages: Vec<Message>,
-    config_overrides: &Option<crate::domain::experiment::ConfigOverrides>,
+    config_overrides: &Option<ConfigOverrides>,
 ) -> Result<LlmRequest, ApiError> {
     let mut builder = LlmRequest::builder().messages(messages);
 
@@ -466,11 +1145,16 @@ fn build_llm_request_with_overrides(
     Ok(builder.build())
 }
 
-/// Record experiment result
+/// Record experiment result, costed with the pricing of the model that
+/// served the request. The record takes the completion ID so feedback can
+/// refer to it.
+#[allow(clippy::too_many_arguments)]
 async fn record_experiment_result(
     state: &AppState,
     assignment: &AssignmentResult,
+    request_id: &str,
     api_key_id: &str,
+    model: &str,
     input_tokens: u32,
     output_tokens: u32,
     latency_ms: u64,
@@ -478,13 +1162,14 @@ async fn record_experiment_result(
     error: Option<String>,
 ) {
     let params = RecordExperimentParams {
+        record_id: Some(format!("chatcmpl-{}", request_id)),
         experiment_id: assignment.experiment_id.clone(),
         variant_id: assignment.variant_id.clone(),
         api_key_id: api_key_id.to_string(),
-        model_id: assignment.model_id.clone(),
+        model_id: model.to_string(),
         input_tokens,
         output_tokens,
-        cost_micros: 0, // Cost calculation would need model pricing integration
+        cost_micros: estimate_cost(state, model, input_tokens, output_tokens).await,
         latency_ms,
         success,
         error,
@@ -507,24 +1192,33 @@ async fn record_experiment_result(
 }
 
 /// Create streaming response
+#[allow(clippy::too_many_arguments)]
 async fn create_stream_response(
     state: AppState,
     request: LlmRequest,
+    request_payload: serde_json::Value,
     model: String,
-    request_id: String,
-    api_key_id: String,
+    tracer: RequestTracer,
+    api_key: ApiKey,
     experiment_assignment: Option<AssignmentResult>,
+    prompt_tokens: u32,
+    tags: HashMap<String, String>,
+    injection: Option<InjectionDetection>,
+    no_log: bool,
 ) -> impl Stream<Item = Result<Event, std::convert::Infallible>> {
     let (tx, rx) = tokio::sync::mpsc::channel::<Result<Event, std::convert::Infallible>>(32);
 
     // Get provider before spawning to avoid lifetime issues
     let provider = get_provider_for_model(&state, &model).await;
+    let span = provider_call_span(provider.provider_name(), &model, true);
+    let task_span = span.clone();
 
     tokio::spawn(Box::pin(async move {
         let start_time = Instant::now();
+        let request_id = tracer.request_id();
 
         // Send initial chunk with role
-        let initial = ChatCompletionStreamResponse::initial(&model, &request_id);
+        let initial = ChatCompletionStreamResponse::initial(&model, request_id);
         let _ = tx
             .send(Ok(Event::default().data(serde_json::to_string(&initial).unwrap())))
             .await;
@@ -532,28 +1226,107 @@ async fn create_stream_response(
         // Track success for experiment recording
         let mut stream_success = true;
         let mut stream_error: Option<String> = None;
+        let mut error_status: Option<String> = None;
+        let mut time_to_first_token = None;
+        let mut chunk_count: u32 = 0;
+        let mut client_disconnected = false;
+        let mut streamed_content = String::new();
+        let mut wants_tool_calls = false;
+        let mut policy_error: Option<String> = None;
+        let mut secret_filter = secret_stream_filter(&state).await;
+        let secret_action = state.secret_leak_guard.action;
+        let mut leaks_reported = 0;
+        let mut secret_error: Option<ApiError> = None;
+
+        // Mirror a share of the model's requests to its shadow model
+        let shadow = sample_shadow(&state, &model)
+            .await
+            .map(|shadow| (shadow, request.clone()));
 
         // Get streaming response from provider
-        match provider.chat_stream(&model, request).await {
+        let stream_result = provider.chat_stream(&model, request).await;
+        state
+            .notifications
+            .track_provider_result(provider.provider_name(), &stream_result);
+        if let Err(e) = &stream_result {
+            track_provider_throttling(&state, &model, e).await;
+        }
+
+        match stream_result {
             Ok(mut stream) => {
                 while let Some(chunk_result) = stream.next().await {
                     match chunk_result {
                         Ok(chunk) => {
-                            if let Some(content) = &chunk.delta {
-                                let content_chunk = ChatCompletionStreamResponse::content(
+                            if let Some(delta) = &chunk.delta {
+                                time_to_first_token.get_or_insert_with(|| start_time.elapsed());
+                                chunk_count += 1;
+
+                                // Secrets split across chunks are held back
+                                // until the filter has seen them whole
+                                let content = match secret_filter.as_mut() {
+                                    Some(filter) => filter.push(delta),
+                                    None => delta.clone(),
+                                };
+
+                                if let Some(filter) = &secret_filter
+                                    && filter.found().len() > leaks_reported
+                                {
+                                    record_secret_leaks(
+                                        &model,
+                                        secret_action,
+                                        &filter.found()[leaks_reported..],
+                                    );
+                                    leaks_reported = filter.found().len();
+
+                                    if secret_action == SecretLeakAction::Block {
+                                        secret_error = Some(secret_leak_error());
+                                        break;
+                                    }
+                                }
+
+                                if !content.is_empty() {
+                                    streamed_content.push_str(&content);
+                                    let content_chunk = ChatCompletionStreamResponse::content(
+                                        &model,
+                                        request_id,
+                                        &content,
+                                    );
+                                    let data = serde_json::to_string(&content_chunk).unwrap();
+
+                                    if tx.send(Ok(Event::default().data(data))).await.is_err() {
+                                        client_disconnected = true;
+                                        break;
+                                    }
+                                }
+                            }
+
+                            // Providers normalize their tool-use events into
+                            // OpenAI-style deltas, forwarded as they come
+                            if !chunk.tool_calls.is_empty() {
+                                time_to_first_token.get_or_insert_with(|| start_time.elapsed());
+                                chunk_count += 1;
+
+                                let tool_chunk = ChatCompletionStreamResponse::tool_calls(
                                     &model,
-                                    &request_id,
-                                    content,
+                                    request_id,
+                                    &chunk.tool_calls,
                                 );
-                                let data = serde_json::to_string(&content_chunk).unwrap();
+                                let data = serde_json::to_string(&tool_chunk).unwrap();
 
                                 if tx.send(Ok(Event::default().data(data))).await.is_err() {
+                                    client_disconnected = true;
                                     break;
                                 }
                             }
+
+                            if chunk.finish_reason == Some(DomainFinishReason::ToolCalls) {
+                                wants_tool_calls = true;
+                            }
                         }
                         Err(e) => {
                             error!("Stream error: {}", e);
+                            record_error(&span, &e);
+                            error_status = Some(provider_error_status(&e));
                             stream_success = false;
                             stream_error = Some(e.to_string());
                             break;
@@ -561,8 +1334,75 @@ async fn create_stream_response(
                     }
                 }
 
+                // Release the text the secret filter held back
+                if stream_error.is_none()
+                    && secret_error.is_none()
+                    && let Some(filter) = secret_filter.as_mut()
+                {
+                    let content = filter.finish();
+
+                    if filter.found().len() > leaks_reported {
+                        record_secret_leaks(
+                            &model,
+                            secret_action,
+                            &filter.found()[leaks_reported..],
+                        );
+                        if secret_action == SecretLeakAction::Block {
+                            secret_error = Some(secret_leak_error());
+                        }
+                    }
+
+                    if secret_error.is_none() && !content.is_empty() {
+                        streamed_content.push_str(&content);
+                        let content_chunk =
+                            ChatCompletionStreamResponse::content(&model, request_id, &content);
+                        let _ = tx
+                            .send(Ok(Event::default()
+                                .data(serde_json::to_string(&content_chunk).unwrap())))
+                            .await;
+                    }
+                }
+
+                // Evaluate the team's content policies on the answer; content
+                // already streamed cannot be taken back, so a blocked secret
+                // or a violation ends the stream with the error and a
+                // `content_filter` finish reason
+                let mut finish = ChatCompletionStreamResponse::finish(&model, request_id, None);
+                if wants_tool_calls {
+                    for choice in &mut finish.choices {
+                        choice.finish_reason = Some(FinishReason::ToolCalls);
+                    }
+                }
+
+                let guarded = match secret_error {
+                    Some(e) => Err(e),
+                    None if stream_error.is_none() => {
+                        enforce_content_policies(
+                            &state,
+                            &api_key,
+                            PolicyStage::Output,
+                            &streamed_content,
+                        )
+                        .await
+                    }
+                    None => Ok(()),
+                };
+                if stream_error.is_none() {
+                    trace_guardrail(&tracer, "Output guardrails", &guarded);
+                }
+
+                if let Err(e) = guarded {
+                    let _ = tx
+                        .send(Ok(Event::default()
+                            .data(serde_json::to_string(&e.response).unwrap_or_default())))
+                        .await;
+                    for choice in &mut finish.choices {
+                        choice.finish_reason = Some(FinishReason::ContentFilter);
+                    }
+                    policy_error = Some(e.response.error.message);
+                }
+
                 // Send final chunk
-                let finish = ChatCompletionStreamResponse::finish(&model, &request_id, None);
                 let _ = tx
                     .send(Ok(Event::default().data(serde_json::to_string(&finish).unwrap())))
                     .await;
@@ -572,38 +1412,448 @@ async fn create_stream_response(
             }
             Err(e) => {
                 error!("Failed to start stream: {}", e);
+                record_error(&span, &e);
+                error_status = Some(provider_error_status(&e));
                 stream_success = false;
                 stream_error = Some(e.to_string());
             }
         }
 
-        // Record experiment result (note: token counts not available for streaming)
         let latency_ms = start_time.elapsed().as_millis() as u64;
+        let streamed_chars = streamed_content.chars().count();
+        let output_tokens = u32::try_from(streamed_chars.div_ceil(4)).unwrap_or(u32::MAX);
+
+        match &stream_error {
+            None => tracer.record_with(
+                RequestTraceStage::Provider,
+                format!("Provider '{}' streamed the answer", provider.provider_name()),
+                json!({
+                    "provider": provider.provider_name(),
+                    "model": &model,
+                    "latency_ms": latency_ms,
+                    "chunks": chunk_count,
+                    "client_disconnected": client_disconnected,
+                }),
+            ),
+            Some(e) => tracer.record_with(
+                RequestTraceStage::Provider,
+                format!("Provider '{}' failed: {}", provider.provider_name(), e),
+                json!({
+                    "provider": provider.provider_name(),
+                    "model": &model,
+                    "latency_ms": latency_ms,
+                    "error_status": &error_status,
+                }),
+            ),
+        }
+
+        record_llm_request(LlmRequestMetricParams {
+            provider: provider.provider_name(),
+            model: &model,
+            duration: start_time.elapsed(),
+            time_to_first_token,
+            success: stream_success,
+            error_status: error_status.as_deref(),
+            input_tokens: Some(prompt_tokens as u64),
+            output_tokens: Some(output_tokens as u64),
+        });
+
+        // Providers don't report usage for streams; charge budgets an estimate
+        if stream_success || streamed_chars > 0 {
+            let usage_record = record_request_usage(
+                &state,
+                &api_key,
+                &model,
+                prompt_tokens,
+                output_tokens,
+                latency_ms,
+                &tags,
+            )
+            .await;
+            trace_usage(&tracer, usage_record.as_ref());
+        }
 
+        let abort_reason = if stream_error.is_some() {
+            Some(StreamAbortReason::ProviderError)
+        } else if client_disconnected {
+            Some(StreamAbortReason::ClientDisconnected)
+        } else if policy_error.is_some() {
+            Some(StreamAbortReason::ContentFilter)
+        } else {
+            None
+        };
+        let mut streaming = StreamingMetrics::new(
+            time_to_first_token,
+            start_time.elapsed(),
+            output_tokens,
+            chunk_count,
+        );
+
+        if let Some(reason) = abort_reason {
+            streaming = streaming.with_abort_reason(reason);
+        }
+
+        let output = match stream_error.as_ref().or(policy_error.as_ref()) {
+            None => Ok(json!({"content": streamed_content})),
+            Some(e) => Err(e.clone()),
+        };
+
+        // Only completed answers can be compared with the shadow model's
+        let completed = output.is_ok() && abort_reason.is_none();
+        if let Some((shadow, shadow_request)) = shadow.filter(|_| completed) {
+            let served = ServedRequest {
+                model_id: model.clone(),
+                request: shadow_request,
+                payload: request_payload.clone(),
+                answer: streamed_content.clone(),
+                latency_ms,
+            };
+            spawn_shadow_request(&state, &api_key, shadow, served, &tags, request_id, no_log);
+        }
+
+        capture_payload(
+            &state,
+            &api_key,
+            &model,
+            request_payload,
+            output,
+            latency_ms,
+            injection,
+            no_log,
+            Some(streaming),
+            &tags,
+            request_id,
+        );
+
+        // Record experiment result with the same token estimates as usage
         if let Some(ref assignment) = experiment_assignment {
             record_experiment_result(
                 &state,
                 assignment,
-                &api_key_id,
-                0, // Token counts not available for streaming
-                0,
+                request_id,
+                api_key.id().as_str(),
+                &model,
+                prompt_tokens,
+                output_tokens,
                 latency_ms,
                 stream_success,
                 stream_error,
             )
             .await;
         }
-    }));
+    }.instrument(task_span)));
 
     ReceiverStream::new(rx)
 }
 
+/// Capture the redacted request and response (or error) of a completion in the
+/// execution log, when the team of the key is sampled for payload capture or
+/// the request was flagged by the injection guard. Zero-retention requests
+/// are recorded without payloads.
+#[allow(clippy::too_many_arguments)]
+fn capture_payload(
+    state: &AppState,
+    api_key: &ApiKey,
+    model: &str,
+    request: serde_json::Value,
+    output: Result<serde_json::Value, String>,
+    latency_ms: u64,
+    injection: Option<InjectionDetection>,
+    no_log: bool,
+    streaming: Option<StreamingMetrics>,
+    tags: &HashMap<String, String>,
+    request_id: &str,
+) {
+    let team_id = api_key.team_id().as_str().to_string();
+    let executor = Executor::from_api_key(api_key.id().as_str()).with_team(&team_id);
+
+    let mut params = match output {
+        Ok(output) => RecordExecutionParams::chat_completion_success(model, latency_ms, executor)
+            .with_output(redact_sensitive_fields(output)),
+        Err(error) => {
+            RecordExecutionParams::chat_completion_failed(model, error, latency_ms, executor)
+        }
+    }
+    .with_input(redact_sensitive_fields(request))
+    .with_no_log(no_log)
+    .with_tags(tags.clone())
+    .with_request_id(request_id);
+
+    if let Some(injection) = injection {
+        params = params.with_injection(injection);
+    }
+
+    if let Some(streaming) = streaming {
+        params = params.with_streaming(streaming);
+    }
+
+    let execution_log_service = state.execution_log_service.clone();
+
+    spawn_tracked(async move {
+        if let Err(e) = execution_log_service.capture_payload(&team_id, params).await {
+            warn!(error = %e, "Failed to capture request payload");
+        }
+    });
+}
+
+/// Record a guardrail verdict on the request trace
+fn trace_guardrail(tracer: &RequestTracer, check: &str, verdict: &Result<(), ApiError>) {
+    match verdict {
+        Ok(()) => tracer.record(RequestTraceStage::Guardrail, format!("{} passed", check)),
+        Err(e) => trace_rejection(tracer, RequestTraceStage::Guardrail, check, e),
+    }
+}
+
+/// Record a check that rejected the request on its trace
+fn trace_rejection(
+    tracer: &RequestTracer,
+    stage: RequestTraceStage,
+    check: &str,
+    error: &ApiError,
+) {
+    tracer.record(
+        stage,
+        format!("{} rejected the request: {}", check, error.response.error.message),
+    );
+}
+
+/// Record the outcome of a provider call on the request trace
+pub(super) fn trace_provider_result(
+    tracer: &RequestTracer,
+    provider: &str,
+    model: &str,
+    latency_ms: u64,
+    result: &Result<LlmResponse, DomainError>,
+) {
+    match result {
+        Ok(response) => {
+            let usage = response.usage.as_ref();
+            tracer.record_with(
+                RequestTraceStage::Provider,
+                format!("Provider '{}' answered", provider),
+                json!({
+                    "provider": provider,
+                    "model": model,
+                    "latency_ms": latency_ms,
+                    "input_tokens": usage.map(|u| u.prompt_tokens),
+                    "output_tokens": usage.map(|u| u.completion_tokens),
+                }),
+            );
+        }
+        Err(e) => tracer.record_with(
+            RequestTraceStage::Provider,
+            format!("Provider '{}' failed: {}", provider, e),
+            json!({
+                "provider": provider,
+                "model": model,
+                "latency_ms": latency_ms,
+                "error_status": provider_error_status(e),
+            }),
+        ),
+    }
+}
+
+/// Record the usage charged for a request on its trace; the admin request
+/// lookup follows `usage_record_id` to the usage record
+pub(super) fn trace_usage(tracer: &RequestTracer, record: Option<&UsageRecord>) {
+    if let Some(record) = record {
+        tracer.record_with(
+            RequestTraceStage::Usage,
+            format!(
+                "Recorded {} tokens costing {} micro-dollars",
+                record.total_tokens, record.cost_micros
+            ),
+            json!({
+                "usage_record_id": record.id().as_str(),
+                "input_tokens": record.input_tokens,
+                "output_tokens": record.output_tokens,
+                "cost_micros": record.cost_micros,
+            }),
+        );
+    }
+}
+
+/// Call the provider of a model inside an `llm.provider_call` span, feeding
+/// the outcome to provider outage tracking and the LLM request metrics
+pub(super) async fn call_provider(
+    state: &AppState,
+    provider: &dyn LlmProvider,
+    model: &str,
+    request: LlmRequest,
+) -> Result<LlmResponse, DomainError> {
+    let span = provider_call_span(provider.provider_name(), model, false);
+    let start = Instant::now();
+    let result = provider.chat(model, request).instrument(span.clone()).await;
+
+    state
+        .notifications
+        .track_provider_result(provider.provider_name(), &result);
+    if let Err(e) = &result {
+        track_provider_throttling(state, model, e).await;
+    }
+
+    let usage = result.as_ref().ok().and_then(|r| r.usage.as_ref());
+    let error_status = result.as_ref().err().map(provider_error_status);
+
+    record_llm_request(LlmRequestMetricParams {
+        provider: provider.provider_name(),
+        model,
+        duration: start.elapsed(),
+        time_to_first_token: None,
+        success: result.is_ok(),
+        error_status: error_status.as_deref(),
+        input_tokens: usage.map(|u| u.prompt_tokens as u64),
+        output_tokens: usage.map(|u| u.completion_tokens as u64),
+    });
+
+    match &result {
+        Ok(response) => record_token_usage(&span, response.usage.as_ref()),
+        Err(e) => record_error(&span, e),
+    }
+
+    result
+}
+
+/// Route a request away from a model whose canary test cases are failing or
+/// whose SLO was breached at its latest evaluation, to its configured
+/// fallback model if that one is not degraded as well
+async fn route_around_degraded_model(state: &AppState, model_id: String) -> String {
+    let is_degraded = |model_id: &str| {
+        state.canary_health.is_degraded(model_id) || state.slo_service.is_breached(model_id)
+    };
+
+    if !is_degraded(&model_id) {
+        return model_id;
+    }
+
+    let fallback = match state.model_service.get(&model_id).await {
+        Ok(Some(model)) => model.config().fallback_model_id.clone(),
+        _ => None,
+    };
+
+    match fallback {
+        Some(fallback) if !is_degraded(&fallback) => {
+            warn!(
+                model_id = %model_id,
+                fallback_model_id = %fallback,
+                "Model degraded by failing canaries or a breached SLO, routing to fallback model"
+            );
+            fallback
+        }
+        _ => model_id,
+    }
+}
+
+/// Route a request away from a model whose provisioned throughput it would
+/// exceed, to its configured (on-demand) fallback model. Without a fallback
+/// the request goes to the model anyway and the provider throttles it.
+async fn route_within_provisioned_capacity(
+    state: &AppState,
+    model_id: String,
+    tokens: u64,
+) -> String {
+    let Ok(Some(model)) = state.model_service.get(&model_id).await else {
+        return model_id;
+    };
+    let Some(capacity) = &model.config().capacity else {
+        return model_id;
+    };
+
+    if state.model_capacity.try_reserve(&model_id, capacity, tokens) {
+        return model_id;
+    }
+
+    match &model.config().fallback_model_id {
+        Some(fallback) => {
+            warn!(
+                model_id = %model_id,
+                fallback_model_id = %fallback,
+                "Provisioned capacity exhausted, routing to fallback model"
+            );
+            fallback.clone()
+        }
+        None => {
+            warn!(
+                model_id = %model_id,
+                "Provisioned capacity exhausted and no fallback model configured"
+            );
+            model_id
+        }
+    }
+}
+
+/// Put the credential of a model on cooldown when its provider throttled a
+/// request, for as long as the provider asked (`retry-after` and reset hints)
+async fn track_provider_throttling(state: &AppState, model_id: &str, error: &DomainError) {
+    let DomainError::Provider { message, .. } = error else {
+        return;
+    };
+    let code = ProviderFailure::classify(message).code;
+    if !matches!(code, GatewayErrorCode::RateLimited | GatewayErrorCode::QuotaExceeded) {
+        return;
+    }
+    let Ok(Some(model)) = state.model_service.get(model_id).await else {
+        return;
+    };
+
+    let cooldown = error
+        .rate_limit()
+        .and_then(|limit| limit.retry_after_secs)
+        .map_or(DEFAULT_CREDENTIAL_COOLDOWN, Duration::from_secs);
+    warn!(
+        model_id = %model_id,
+        credential_id = %model.credential_id(),
+        cooldown_secs = cooldown.as_secs(),
+        "Provider throttled credential, cooling it down"
+    );
+    state
+        .credential_cooldowns
+        .record(model.credential_id(), cooldown);
+}
+
+/// Route a request away from a model whose credential a provider throttled,
+/// to its configured fallback model if that one's credential is not on
+/// cooldown as well
+async fn route_around_throttled_credential(state: &AppState, model_id: String) -> String {
+    let Ok(Some(model)) = state.model_service.get(&model_id).await else {
+        return model_id;
+    };
+    if state
+        .credential_cooldowns
+        .remaining(model.credential_id())
+        .is_none()
+    {
+        return model_id;
+    }
+
+    let Some(fallback) = model.config().fallback_model_id.clone() else {
+        return model_id;
+    };
+    let fallback_throttled = match state.model_service.get(&fallback).await {
+        Ok(Some(fallback_model)) => state
+            .credential_cooldowns
+            .remaining(fallback_model.credential_id())
+            .is_some(),
+        _ => true,
+    };
+    if fallback_throttled {
+        return model_id;
+    }
+
+    warn!(
+        model_id = %model_id,
+        fallback_model_id = %fallback,
+        "Credential throttled by its provider, routing to fallback model"
+    );
+    fallback
+}
+
 /// Get the appropriate LLM provider for a model
 ///
 /// This function tries to use the plugin router to get a provider based on
 /// the model's credential configuration. Falls back to the default provider
 /// if the model is not found or the router fails.
-async fn get_provider_for_model(
+pub(super) async fn get_provider_for_model(
     state: &AppState,
     model_id: &str,
 ) -> std::sync::Arc<dyn LlmProvider> {
@@ -679,6 +1929,9 @@ async fn get_provider_for_model(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::ModelConfig;
+    use crate::infrastructure::observability::live_stats;
+    use crate::infrastructure::services::CreateModelRequest;
 
     #[test]
     fn test_build_llm_request_basic() {
@@ -696,6 +1949,9 @@ mod tests {
             frequency_penalty: None,
             user: None,
             seed: None,
+            metadata: None,
+            system: None,
+            locale: None,
         };
 
         let messages = vec![Message::user("Hello")];
@@ -719,6 +1975,9 @@ mod tests {
             frequency_penalty: None,
             user: None,
             seed: None,
+            metadata: None,
+            system: None,
+            locale: None,
         };
 
         let messages = vec![Message::user("Hello")];
@@ -742,6 +2001,9 @@ mod tests {
             frequency_penalty: Some(-0.5),
             user: Some("user123".to_string()),
             seed: None,
+            metadata: None,
+            system: None,
+            locale: None,
         };
 
         let messages = vec![Message::user("Hello")];
@@ -751,8 +2013,6 @@ mod tests {
 
     #[test]
     fn test_build_llm_request_with_experiment_overrides() {
-        use crate::domain::experiment::ConfigOverrides;
-
         let request = ChatCompletionRequest {
             model: "gpt-4".to_string(),
             messages: vec![],
@@ -767,10 +2027,15 @@ mod tests {
             frequency_penalty: None,
             user: None,
             seed: None,
+            metadata: None,
+            system: None,
+            locale: None,
         };
 
         // Experiment overrides should take precedence
         let overrides = Some(ConfigOverrides {
+            prompt_id: None,
+            prompt_version: None,
             temperature: Some(0.3),
             max_tokens: Some(200),
             top_p: None,
@@ -787,4 +2052,204 @@ mod tests {
         assert_eq!(llm_request.temperature, Some(0.3));
         assert_eq!(llm_request.max_tokens, Some(200));
     }
+
+    #[test]
+    fn test_tag_experiment_variant() {
+        let mut response = StatusCode::OK.into_response();
+        tag_experiment_variant(&mut response, None);
+        assert!(response.headers().get(EXPERIMENT_ID_HEADER).is_none());
+
+        let assignment = AssignmentResult::new("exp-1", "treatment", "gpt-4");
+        tag_experiment_variant(&mut response, Some(&assignment));
+
+        assert_eq!(response.headers()[EXPERIMENT_ID_HEADER], "exp-1");
+        assert_eq!(response.headers()[EXPERIMENT_VARIANT_HEADER], "treatment");
+    }
+
+    fn create_model_request(id: &str, config: ModelConfig) -> CreateModelRequest {
+        CreateModelRequest {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: None,
+            provider: crate::domain::CredentialType::OpenAi,
+            provider_model: "gpt-4".to_string(),
+            credential_id: "openai".to_string(),
+            config: Some(config),
+            enabled: true,
+            lineage: None,
+        }
+    }
+
+    /// State serving `chat-model` (configured with `config`) from a mock
+    /// provider, with the secret of a full access API key
+    async fn chat_state(
+        config: ModelConfig,
+    ) -> (AppState, std::sync::Arc<crate::domain::llm::MockLlmProvider>, String) {
+        use crate::domain::api_key::ApiKeyPermissions;
+
+        let provider = std::sync::Arc::new(
+            crate::domain::llm::MockLlmProvider::new("mock").with_response(LlmResponse::new(
+                "resp-1".to_string(),
+                "gpt-4".to_string(),
+                Message::assistant("Hello!"),
+            )),
+        );
+        let state = crate::test_app_state(provider.clone()).await;
+
+        state
+            .model_service
+            .create(create_model_request("chat-model", config))
+            .await
+            .unwrap();
+        let (_, secret) = state
+            .api_key_service
+            .create("test", "team-1", ApiKeyPermissions::full_access())
+            .await
+            .unwrap();
+
+        (state, provider, secret)
+    }
+
+    async fn send_chat(state: &AppState, secret: &str) -> Response {
+        use tower::ServiceExt;
+
+        let request = axum::http::Request::post("/v1/chat/completions")
+            .header("authorization", format!("Bearer {}", secret))
+            .header("content-type", "application/json")
+            .body(axum::body::Body::from(
+                json!({
+                    "model": "chat-model",
+                    "messages": [{"role": "user", "content": "Hi"}],
+                })
+                .to_string(),
+            ))
+            .unwrap();
+
+        crate::api::router::create_router_with_state(state.clone())
+            .oneshot(request)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_served_from_cache() {
+        let (state, provider, secret) = chat_state(ModelConfig::new().with_cache_policy(CachePolicy::new(CacheMode::Exact))).await;
+
+        let first = send_chat(&state, &secret).await;
+        assert_eq!(first.status(), StatusCode::OK);
+        assert!(first.headers().get(CACHE_HEADER).is_none());
+
+        let now = || chrono::Utc::now().timestamp() as u64;
+        let hits = live_stats().snapshot(now()).cache_hits;
+        let second = send_chat(&state, &secret).await;
+        assert_eq!(second.status(), StatusCode::OK);
+        assert_eq!(second.headers()[CACHE_HEADER], "exact");
+        assert_eq!(provider.calls(), 1);
+        // Feeds the cache hit rate of `/admin/stats`
+        assert!(live_stats().snapshot(now()).cache_hits > hits);
+
+        let body = axum::body::to_bytes(second.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["choices"][0]["message"]["content"], "Hello!");
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_disabled_cache_policy() {
+        let (state, provider, secret) = chat_state(ModelConfig::new().with_cache_policy(CachePolicy::disabled())).await;
+
+        for _ in 0..2 {
+            let response = send_chat(&state, &secret).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            assert!(response.headers().get(CACHE_HEADER).is_none());
+        }
+
+        assert_eq!(provider.calls(), 2);
+    }
+
+    async fn assert_served_from_semantic_cache(backend: &str) {
+        let mut config = crate::config::AppConfig::default();
+        config.cache.semantic.enabled = true;
+        config.cache.semantic.backend = backend.to_string();
+        config.cache.semantic.redis_url = Some("redis://127.0.0.1:6379".to_string());
+        config.cache.semantic.dimensions = 8;
+        config.cache.semantic.namespace = format!("test:{}", uuid::Uuid::new_v4());
+        let embeddings =
+            std::sync::Arc::new(crate::domain::embedding::MockEmbeddingProvider::new("mock", 8));
+        let cache = crate::build_response_cache(&config, embeddings).await.unwrap();
+
+        let (state, provider, secret) =
+            chat_state(ModelConfig::new().with_cache_policy(CachePolicy::new(CacheMode::Semantic)))
+                .await;
+        let state = state.with_response_cache(cache);
+
+        assert!(send_chat(&state, &secret).await.headers().get(CACHE_HEADER).is_none());
+        let second = send_chat(&state, &secret).await;
+
+        assert_eq!(second.status(), StatusCode::OK);
+        assert_eq!(second.headers()[CACHE_HEADER], "semantic");
+        assert_eq!(provider.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_served_from_semantic_cache() {
+        assert_served_from_semantic_cache("memory").await;
+    }
+
+    #[tokio::test]
+    #[ignore = "Requires running Redis Stack instance"]
+    async fn test_chat_completion_served_from_redis_semantic_cache() {
+        assert_served_from_semantic_cache("redis").await;
+    }
+
+    #[tokio::test]
+    async fn test_breached_slo_routes_to_fallback_model() {
+        use crate::domain::slo::Slo;
+        use crate::domain::{ExecutionLog, ExecutionLogRepository, ExecutionType};
+        use crate::infrastructure::config::StorageExecutionLogRepository;
+        use crate::infrastructure::slo::{SloService, StorageSloRepository};
+        use crate::infrastructure::storage::InMemoryStorage;
+
+        let (mut state, _, secret) =
+            chat_state(ModelConfig::new().with_fallback_model_id("fallback-model")).await;
+        state
+            .model_service
+            .create(create_model_request("fallback-model", ModelConfig::new()))
+            .await
+            .unwrap();
+
+        let logs = std::sync::Arc::new(StorageExecutionLogRepository::new(std::sync::Arc::new(
+            InMemoryStorage::<ExecutionLog>::new(),
+        )));
+        logs.save(&ExecutionLog::failed(
+            ExecutionType::ChatCompletion,
+            "chat-model",
+            "Provider error",
+            100,
+            Executor::anonymous(),
+        ))
+        .await
+        .unwrap();
+        let slo_service = SloService::new(
+            std::sync::Arc::new(StorageSloRepository::new(std::sync::Arc::new(
+                InMemoryStorage::<Slo>::new(),
+            ))),
+            logs,
+        );
+        slo_service
+            .set(Slo::new("chat-model").with_error_budget(0.1))
+            .await
+            .unwrap();
+        slo_service
+            .evaluate_all(chrono::Utc::now().timestamp() as u64 + 1)
+            .await
+            .unwrap();
+        state.slo_service = std::sync::Arc::new(slo_service);
+
+        let response = send_chat(&state, &secret).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["model"], "fallback-model");
+    }
 }
@@ -0,0 +1,88 @@
+//! Feedback API endpoint for experiment quality metrics
+
+use axum::extract::State;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::api::middleware::RequireApiKey;
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::experiment::{ExperimentRecord, FeedbackEvent, RecordFeedback};
+
+/// Feedback on a completion served under an experiment
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct FeedbackRequest {
+    /// ID of the chat completion (`chatcmpl-...`)
+    #[serde(alias = "record_id")]
+    pub completion_id: String,
+    #[serde(flatten)]
+    pub event: FeedbackEvent,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeedbackResponse {
+    pub completion_id: String,
+    pub experiment_id: String,
+    pub variant_id: String,
+    /// Feedback of the completion after this event
+    pub feedback: RecordFeedback,
+}
+
+impl From<ExperimentRecord> for FeedbackResponse {
+    fn from(record: ExperimentRecord) -> Self {
+        Self {
+            completion_id: record.id().to_string(),
+            experiment_id: record.experiment_id,
+            variant_id: record.variant_id,
+            feedback: record.feedback,
+        }
+    }
+}
+
+/// Report a thumbs up/down, rating or conversion for a completion
+///
+/// Only completions served under an experiment are recorded.
+#[utoipa::path(
+    post,
+    path = "/v1/feedback",
+    tag = "v1",
+    request_body = FeedbackRequest,
+    responses((status = 200, body = FeedbackResponse)),
+)]
+pub async fn submit_feedback(
+    State(state): State<AppState>,
+    RequireApiKey(api_key): RequireApiKey,
+    Json(request): Json<FeedbackRequest>,
+) -> Result<Json<FeedbackResponse>, ApiError> {
+    let record = state
+        .experiment_service
+        .record_feedback(&request.completion_id, api_key.id().as_str(), request.event)
+        .await?;
+
+    Ok(Json(record.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feedback_request_deserialization() {
+        let request: FeedbackRequest =
+            serde_json::from_str(r#"{"completion_id": "chatcmpl-1", "type": "thumbs_up"}"#)
+                .unwrap();
+        assert_eq!(request.completion_id, "chatcmpl-1");
+        assert_eq!(request.event, FeedbackEvent::ThumbsUp);
+
+        let request: FeedbackRequest =
+            serde_json::from_str(r#"{"record_id": "expr-1", "type": "rating", "value": 4}"#)
+                .unwrap();
+        assert_eq!(request.completion_id, "expr-1");
+        assert_eq!(request.event, FeedbackEvent::Rating { value: 4.0 });
+
+        assert!(serde_json::from_str::<FeedbackRequest>(
+            r#"{"completion_id": "chatcmpl-1", "type": "stars"}"#
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,379 @@
+//! MCP server endpoint
+//!
+//! Speaks the JSON-RPC 2.0 messages of the Model Context Protocol over plain
+//! HTTP POST (the Streamable HTTP transport, without server-sent events).
+//! The tools are the enabled MCP tools managed under `/admin/mcp-tools`, so
+//! IDE agents and desktop clients call curated workflows and knowledge base
+//! searches with a gateway API key.
+
+use std::time::Instant;
+
+use axum::{
+    extract::{Extension, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tracing::debug;
+use utoipa::ToSchema;
+
+use super::workflows::record_workflow_usage;
+use crate::api::middleware::{BudgetSlot, RequireApiKey, UsageTags, enforce_budget};
+use crate::api::state::AppState;
+use crate::api::types::{ApiError, Json};
+use crate::domain::api_key::ApiKey;
+use crate::domain::knowledge_base::KnowledgeBaseCaller;
+use crate::domain::mcp::{
+    MAX_MCP_SEARCH_TOP_K, McpTool, McpToolTarget, knowledge_base_search_schema,
+};
+
+/// MCP protocol revision implemented by the endpoint
+pub const MCP_PROTOCOL_VERSION: &str = "2025-03-26";
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+/// JSON-RPC request or notification
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct JsonRpcRequest {
+    pub jsonrpc: String,
+    /// Absent on notifications
+    #[serde(default)]
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// JSON-RPC response
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct JsonRpcResponse {
+    pub jsonrpc: &'static str,
+    pub id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<JsonRpcError>,
+}
+
+/// JSON-RPC error object
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl JsonRpcResponse {
+    fn result(id: Value, result: Value) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    fn error(id: Value, code: i64, message: impl Into<String>) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: None,
+            error: Some(JsonRpcError {
+                code,
+                message: message.into(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ToolCallParams {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+/// Handle an MCP message
+#[utoipa::path(
+    post,
+    path = "/v1/mcp",
+    tag = "mcp",
+    request_body = JsonRpcRequest,
+    responses(
+        (status = 200, description = "JSON-RPC response", body = JsonRpcResponse),
+        (status = 202, description = "Notification accepted"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("api_key" = []))
+)]
+pub async fn handle_mcp(
+    State(state): State<AppState>,
+    RequireApiKey(api_key): RequireApiKey,
+    budget_slot: Option<Extension<BudgetSlot>>,
+    usage_tags: UsageTags,
+    Json(message): Json<Value>,
+) -> Result<Response, ApiError> {
+    let request: JsonRpcRequest = match serde_json::from_value(message) {
+        Ok(request) => request,
+        Err(e) => {
+            let response = JsonRpcResponse::error(Value::Null, PARSE_ERROR, e.to_string());
+            return Ok(Json(response).into_response());
+        }
+    };
+
+    debug!(
+        method = %request.method,
+        api_key_id = %api_key.id().as_str(),
+        "Handling MCP message"
+    );
+
+    // Notifications and client responses get no answer
+    let Some(id) = request.id else {
+        return Ok(StatusCode::ACCEPTED.into_response());
+    };
+
+    let result = match request.method.as_str() {
+        "initialize" => Ok(initialize_result()),
+        "ping" => Ok(json!({})),
+        "tools/list" => list_tools(&state, &api_key).await,
+        "tools/call" => {
+            // Workflow costs aren't known upfront; only exhausted budgets block
+            enforce_budget(
+                &state,
+                budget_slot.as_ref().map(|Extension(slot)| slot),
+                &api_key,
+                None,
+                0,
+            )
+            .await?;
+
+            call_tool(&state, &api_key, usage_tags, request.params).await
+        }
+        method => Err((METHOD_NOT_FOUND, format!("Method '{}' not found", method))),
+    };
+
+    let response = match result {
+        Ok(result) => JsonRpcResponse::result(id, result),
+        Err((code, message)) => JsonRpcResponse::error(id, code, message),
+    };
+
+    Ok(Json(response).into_response())
+}
+
+fn initialize_result() -> Value {
+    json!({
+        "protocolVersion": MCP_PROTOCOL_VERSION,
+        "capabilities": {"tools": {}},
+        "serverInfo": {
+            "name": "pmp-llm-gateway",
+            "version": env!("CARGO_PKG_VERSION")
+        }
+    })
+}
+
+type RpcResult = Result<Value, (i64, String)>;
+
+async fn list_tools(state: &AppState, api_key: &ApiKey) -> RpcResult {
+    let tools = state.mcp_tool_service.list().await.map_err(internal)?;
+    let mut listed = Vec::new();
+
+    for tool in tools.iter().filter(|tool| visible(tool, api_key)) {
+        let input_schema = match tool.target() {
+            McpToolTarget::Workflow { workflow_id } => state
+                .workflow_service
+                .get(workflow_id)
+                .await
+                .map_err(internal)?
+                .and_then(|workflow| workflow.input_schema().cloned())
+                .unwrap_or_else(|| json!({"type": "object"})),
+            McpToolTarget::KnowledgeBaseSearch { top_k, .. } => {
+                knowledge_base_search_schema(*top_k)
+            }
+        };
+
+        listed.push(json!({
+            "name": tool.id().as_str(),
+            "description": tool.description(),
+            "inputSchema": input_schema
+        }));
+    }
+
+    Ok(json!({"tools": listed}))
+}
+
+/// Whether the tool is offered to the key
+fn visible(tool: &McpTool, api_key: &ApiKey) -> bool {
+    if !tool.is_enabled() {
+        return false;
+    }
+
+    match tool.target() {
+        McpToolTarget::Workflow { .. } => true,
+        McpToolTarget::KnowledgeBaseSearch {
+            knowledge_base_id, ..
+        } => api_key
+            .permissions()
+            .can_access_knowledge_base(knowledge_base_id),
+    }
+}
+
+async fn call_tool(
+    state: &AppState,
+    api_key: &ApiKey,
+    usage_tags: UsageTags,
+    params: Value,
+) -> RpcResult {
+    let params: ToolCallParams = serde_json::from_value(params)
+        .map_err(|e| (INVALID_PARAMS, format!("Invalid tool call: {}", e)))?;
+    let tool = state
+        .mcp_tool_service
+        .get(&params.name)
+        .await
+        .ok()
+        .flatten()
+        .filter(|tool| visible(tool, api_key))
+        .ok_or_else(|| (INVALID_PARAMS, format!("Unknown tool '{}'", params.name)))?;
+
+    match tool.target() {
+        McpToolTarget::Workflow { workflow_id } => {
+            let start_time = Instant::now();
+            let result = state
+                .workflow_service
+                .execute_as(
+                    workflow_id,
+                    params.arguments,
+                    KnowledgeBaseCaller::from_api_key(api_key),
+                )
+                .await;
+
+            record_workflow_usage(
+                state,
+                api_key,
+                workflow_id,
+                start_time.elapsed().as_millis() as u64,
+                &result,
+                usage_tags.0,
+            )
+            .await;
+
+            Ok(match result {
+                Ok(result) if result.success => tool_result(text_of(&result.output), false),
+                Ok(result) => tool_result(
+                    result
+                        .error
+                        .unwrap_or_else(|| "Workflow failed".to_string()),
+                    true,
+                ),
+                Err(e) => tool_result(e.to_string(), true),
+            })
+        }
+        McpToolTarget::KnowledgeBaseSearch {
+            knowledge_base_id,
+            top_k,
+        } => {
+            let query = params
+                .arguments
+                .get("query")
+                .and_then(Value::as_str)
+                .filter(|query| !query.trim().is_empty())
+                .ok_or_else(|| (INVALID_PARAMS, "Argument 'query' is required".to_string()))?;
+            let top_k = params
+                .arguments
+                .get("top_k")
+                .and_then(Value::as_u64)
+                .map_or(*top_k, |requested| {
+                    (requested as u32).clamp(1, (*top_k).min(MAX_MCP_SEARCH_TOP_K))
+                });
+
+            Ok(
+                match state
+                    .mcp_tool_service
+                    .search(
+                        knowledge_base_id,
+                        query,
+                        top_k,
+                        Some(&KnowledgeBaseCaller::from_api_key(api_key)),
+                    )
+                    .await
+                {
+                    Ok(results) => {
+                        let passages: Vec<Value> = results
+                            .into_iter()
+                            .map(|result| {
+                                json!({
+                                    "content": result.content,
+                                    "score": result.score,
+                                    "source": result.source
+                                })
+                            })
+                            .collect();
+                        tool_result(Value::Array(passages).to_string(), false)
+                    }
+                    Err(e) => tool_result(e.to_string(), true),
+                },
+            )
+        }
+    }
+}
+
+/// Text content of a tool result; string outputs are sent as-is
+fn text_of(output: &Value) -> String {
+    match output {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn tool_result(text: String, is_error: bool) -> Value {
+    json!({
+        "content": [{"type": "text", "text": text}],
+        "isError": is_error
+    })
+}
+
+fn internal(e: impl std::fmt::Display) -> (i64, String) {
+    (INTERNAL_ERROR, e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_without_id_is_notification() {
+        let request: JsonRpcRequest = serde_json::from_value(
+            json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+        )
+        .unwrap();
+
+        assert!(request.id.is_none());
+        assert_eq!(request.params, Value::Null);
+    }
+
+    #[test]
+    fn test_error_response_serialization() {
+        let response = JsonRpcResponse::error(json!(7), METHOD_NOT_FOUND, "Method 'x' not found");
+
+        assert_eq!(
+            serde_json::to_value(response).unwrap(),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 7,
+                "error": {"code": -32601, "message": "Method 'x' not found"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_tool_result_text() {
+        assert_eq!(text_of(&json!("Ticket is urgent")), "Ticket is urgent");
+        assert_eq!(text_of(&json!({"priority": 1})), "{\"priority\":1}");
+        assert_eq!(
+            tool_result("boom".to_string(), true)["isError"],
+            json!(true)
+        );
+    }
+}
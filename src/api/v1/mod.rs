@@ -1,21 +1,43 @@
 //! OpenAI-compatible v1 API endpoints
 
+pub mod assistants;
 pub mod chat;
+pub mod feedback;
+pub mod mcp;
 pub mod models;
 pub mod operations;
+mod shadow;
 pub mod workflows;
 
 use axum::{
+    middleware,
     routing::{get, post},
     Router,
 };
 
+use super::middleware::{
+    budget_headers_middleware, concurrency_middleware, quota_headers_middleware,
+    request_trace_middleware, transform_headers_middleware,
+};
 use super::state::AppState;
 
+pub use operations::spawn_operation_reaper;
+
 /// Create v1 API router
 pub fn create_v1_router() -> Router<AppState> {
     Router::new()
         .route("/chat/completions", post(chat::create_chat_completion))
+        .route("/feedback", post(feedback::submit_feedback))
+        .route("/mcp", post(mcp::handle_mcp))
+        .route("/assistants/{assistant_id}", get(assistants::get_assistant))
+        .route(
+            "/assistants/{assistant_id}/chat",
+            post(assistants::chat_with_assistant),
+        )
+        .route(
+            "/assistants/{assistant_id}/run",
+            post(assistants::run_assistant),
+        )
         .route("/models", get(models::list_models))
         .route("/models/{model_id}", get(models::get_model))
         .route(
@@ -27,4 +49,9 @@ pub fn create_v1_router() -> Router<AppState> {
             "/operations/{operation_id}",
             get(operations::get_operation).delete(operations::cancel_operation),
         )
+        .layer(middleware::from_fn(concurrency_middleware))
+        .layer(middleware::from_fn(budget_headers_middleware))
+        .layer(middleware::from_fn(transform_headers_middleware))
+        .layer(middleware::from_fn(quota_headers_middleware))
+        .layer(middleware::from_fn(request_trace_middleware))
 }
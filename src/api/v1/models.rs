@@ -10,7 +10,14 @@ use crate::api::middleware::RequireApiKey;
 use crate::api::state::AppState;
 use crate::api::types::{ApiError, ApiModel, ModelsResponse};
 
-/// GET /v1/models
+/// List models
+#[utoipa::path(
+    get,
+    path = "/v1/models",
+    tag = "v1",
+    operation_id = "v1_list_models",
+    responses((status = 200, body = ModelsResponse)),
+)]
 pub async fn list_models(
     State(state): State<AppState>,
     RequireApiKey(_api_key): RequireApiKey,
@@ -32,7 +39,15 @@ pub async fn list_models(
     Ok(Json(ModelsResponse::new(api_models)))
 }
 
-/// GET /v1/models/:model_id
+/// Get model
+#[utoipa::path(
+    get,
+    path = "/v1/models/{model_id}",
+    tag = "v1",
+    operation_id = "v1_get_model",
+    params(("model_id" = String, Path, description = "Model ID")),
+    responses((status = 200, body = ApiModel)),
+)]
 pub async fn get_model(
     State(state): State<AppState>,
     RequireApiKey(_api_key): RequireApiKey,
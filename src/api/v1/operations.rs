@@ -1,19 +1,39 @@
 //! Operations API endpoints for async operation management
 
+use std::time::Duration;
+
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
 };
+use tracing::warn;
 
+use crate::api::admin::test_suites::resume_test_suite_run;
 use crate::api::middleware::RequireApiKey;
 use crate::api::state::AppState;
 use crate::api::types::{
     ApiError, OperationResponse, OperationsListResponse, OperationsQueryParams,
 };
+use crate::api::v1::workflows::resume_workflow_execution;
+use crate::domain::{DomainError, Operation, OperationType};
+use crate::infrastructure::leader::Leadership;
+
+/// Operations another replica can run again from their stored input; chat
+/// completions and batch ingestions depend on state of the replica that
+/// accepted them
+pub const REQUEUEABLE_OPERATIONS: &[OperationType] =
+    &[OperationType::WorkflowExecution, OperationType::TestSuiteRun];
 
-/// GET /v1/operations/:operation_id - Get a single operation by ID
+/// Get a single operation by ID
+#[utoipa::path(
+    get,
+    path = "/v1/operations/{operation_id}",
+    tag = "v1",
+    params(("operation_id" = String, Path, description = "Operation ID")),
+    responses((status = 200, body = OperationResponse)),
+)]
 pub async fn get_operation(
     State(state): State<AppState>,
     RequireApiKey(_api_key): RequireApiKey,
@@ -34,7 +54,14 @@ pub async fn get_operation(
     }
 }
 
-/// GET /v1/operations - List operations by IDs
+/// List operations by IDs
+#[utoipa::path(
+    get,
+    path = "/v1/operations",
+    tag = "v1",
+    params(OperationsQueryParams),
+    responses((status = 200, body = OperationsListResponse)),
+)]
 pub async fn list_operations(
     State(state): State<AppState>,
     RequireApiKey(_api_key): RequireApiKey,
@@ -55,7 +82,14 @@ pub async fn list_operations(
     Ok(Json(OperationsListResponse::from_operations(operations)))
 }
 
-/// DELETE /v1/operations/:operation_id - Cancel an operation
+/// Cancel an operation
+#[utoipa::path(
+    delete,
+    path = "/v1/operations/{operation_id}",
+    tag = "v1",
+    params(("operation_id" = String, Path, description = "Operation ID")),
+    responses((status = 200, body = OperationResponse)),
+)]
 pub async fn cancel_operation(
     State(state): State<AppState>,
     RequireApiKey(_api_key): RequireApiKey,
@@ -70,6 +104,74 @@ pub async fn cancel_operation(
     Ok((StatusCode::OK, Json(OperationResponse::from(operation))))
 }
 
+/// Refresh the heartbeat of the operations of this replica every
+/// `interval`; on the leader replica, also requeue the operations of
+/// replicas that stopped and run them here
+pub fn spawn_operation_reaper(state: AppState, interval: Duration, leadership: Leadership) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(e) = state.operation_service.heartbeat().await {
+                warn!(error = %e, "Failed to record operation heartbeats");
+            }
+
+            if !leadership.is_leader() {
+                continue;
+            }
+
+            let reaped = match state
+                .operation_service
+                .reap_orphaned(REQUEUEABLE_OPERATIONS)
+                .await
+            {
+                Ok(reaped) => reaped,
+                Err(e) => {
+                    warn!(error = %e, "Failed to reap orphaned operations");
+                    continue;
+                }
+            };
+
+            for operation in reaped.requeued {
+                if let Err(e) = resume_operation(&state, &operation).await {
+                    fail_unresumable(&state, &operation, e.to_string()).await;
+                }
+            }
+        }
+    });
+}
+
+/// Run a requeued operation on this replica
+async fn resume_operation(
+    state: &AppState,
+    operation: &Operation,
+) -> Result<(), DomainError> {
+    match operation.operation_type() {
+        OperationType::WorkflowExecution => resume_workflow_execution(state, operation).await,
+        OperationType::TestSuiteRun => resume_test_suite_run(state, operation),
+        other => Err(DomainError::validation(format!(
+            "Operations of type '{}' cannot be resumed",
+            other
+        ))),
+    }
+}
+
+/// Fail a requeued operation that cannot run again
+async fn fail_unresumable(state: &AppState, operation: &Operation, error: String) {
+    let id = operation.id().as_str();
+    warn!(operation_id = %id, error = %error, "Failed to resume requeued operation");
+
+    let failed = match state.operation_service.mark_running(id).await {
+        Ok(_) => state.operation_service.mark_failed(id, error).await.map(|_| ()),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = failed {
+        warn!(operation_id = %id, error = %e, "Failed to fail requeued operation");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::api::types::{OperationResponse, OperationsListResponse, OperationsQueryParams};
@@ -129,6 +231,8 @@ mod tests {
             status: "completed".to_string(),
             result: Some(serde_json::json!({"response": "Hello"})),
             error: None,
+            progress: None,
+            completion: None,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             started_at: Some("2024-01-01T00:00:01Z".to_string()),
             completed_at: Some("2024-01-01T00:00:10Z".to_string()),
@@ -148,6 +252,8 @@ mod tests {
             status: "failed".to_string(),
             result: None,
             error: Some("Connection refused".to_string()),
+            progress: None,
+            completion: None,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             started_at: Some("2024-01-01T00:00:01Z".to_string()),
             completed_at: Some("2024-01-01T00:00:05Z".to_string()),
@@ -166,6 +272,8 @@ mod tests {
             status: "pending".to_string(),
             result: None,
             error: None,
+            progress: None,
+            completion: None,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             started_at: None,
             completed_at: None,
@@ -197,6 +305,8 @@ mod tests {
                     status: "completed".to_string(),
                     result: None,
                     error: None,
+                    progress: None,
+                    completion: None,
                     created_at: "2024-01-01T00:00:00Z".to_string(),
                     started_at: None,
                     completed_at: Some("2024-01-01T00:00:10Z".to_string()),
@@ -207,6 +317,8 @@ mod tests {
                     status: "pending".to_string(),
                     result: None,
                     error: None,
+                    progress: None,
+                    completion: None,
                     created_at: "2024-01-01T00:00:20Z".to_string(),
                     started_at: None,
                     completed_at: None,
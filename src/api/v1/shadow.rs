@@ -0,0 +1,160 @@
+//! Shadow traffic: chat completions mirrored to candidate models
+//!
+//! Once a sampled request has been answered, its LLM request is sent again
+//! to the shadow model of the model that served it. The shadow answer is
+//! only recorded, as a `shadow` execution log carrying the judge's verdict.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use serde_json::json;
+use tracing::{debug, warn};
+
+use super::chat::{call_provider, get_provider_for_model};
+use crate::api::middleware::{enforce_data_residency, estimate_cost, redact_sensitive_fields};
+use crate::api::state::AppState;
+use crate::domain::api_key::ApiKey;
+use crate::domain::llm::LlmRequest;
+use crate::domain::model::{judge_request, parse_judge_verdict, ShadowConfig};
+use crate::domain::{ExecutionTokenUsage, Executor, ShadowComparison};
+use crate::infrastructure::background::spawn_tracked;
+use crate::infrastructure::services::RecordExecutionParams;
+
+/// Shadow configuration of a model, when the request falls in its mirrored
+/// share
+pub(super) async fn sample_shadow(state: &AppState, model_id: &str) -> Option<ShadowConfig> {
+    let model = state.model_service.get(model_id).await.ok().flatten()?;
+
+    model
+        .config()
+        .shadow
+        .clone()
+        .filter(|shadow| shadow.samples(rand::random::<f64>()))
+}
+
+/// Request answered by the primary model, to mirror to its shadow
+pub(super) struct ServedRequest {
+    pub model_id: String,
+    pub request: LlmRequest,
+    pub payload: serde_json::Value,
+    pub answer: String,
+    pub latency_ms: u64,
+}
+
+/// Mirror a served request to the shadow model in the background
+pub(super) fn spawn_shadow_request(
+    state: &AppState,
+    api_key: &ApiKey,
+    shadow: ShadowConfig,
+    served: ServedRequest,
+    tags: &HashMap<String, String>,
+    request_id: &str,
+    no_log: bool,
+) {
+    let state = state.clone();
+    let api_key = api_key.clone();
+    let tags = tags.clone();
+    let request_id = request_id.to_string();
+
+    spawn_tracked(async move {
+        // The shadow model may be hosted outside the team's allowed regions
+        if let Err(e) = enforce_data_residency(&state, &api_key, &shadow.model_id).await {
+            debug!(
+                model_id = %shadow.model_id,
+                error = %e.response.error.message,
+                "Skipping shadow request outside the allowed regions"
+            );
+            return;
+        }
+
+        let params = run_shadow_request(&state, &api_key, &shadow, served)
+            .await
+            .with_no_log(no_log)
+            .with_tags(tags)
+            .with_request_id(request_id);
+
+        if let Err(e) = state.execution_log_service.record(params).await {
+            warn!(model_id = %shadow.model_id, error = %e, "Failed to record shadow request");
+        }
+    });
+}
+
+/// Call the shadow model, then the judge, returning the execution to log
+async fn run_shadow_request(
+    state: &AppState,
+    api_key: &ApiKey,
+    shadow: &ShadowConfig,
+    served: ServedRequest,
+) -> RecordExecutionParams {
+    let team_id = api_key.team_id().as_str();
+    let executor = Executor::from_api_key(api_key.id().as_str()).with_team(team_id);
+    let mut comparison = ShadowComparison {
+        primary_model_id: served.model_id,
+        primary_latency_ms: served.latency_ms,
+        judge_model_id: shadow.judge_model_id.clone(),
+        verdict: None,
+        judge_error: None,
+    };
+
+    let provider = get_provider_for_model(state, &shadow.model_id).await;
+    let start_time = Instant::now();
+    let result = call_provider(
+        state,
+        provider.as_ref(),
+        &shadow.model_id,
+        served.request.clone(),
+    )
+    .await;
+    let latency_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = match result {
+        Ok(response) => response,
+        Err(e) => {
+            return RecordExecutionParams::shadow(
+                &shadow.model_id,
+                Err(e.to_string()),
+                latency_ms,
+                executor,
+            )
+            .with_input(redact_sensitive_fields(served.payload))
+            .with_shadow(comparison);
+        }
+    };
+    let candidate = response.content().unwrap_or_default();
+
+    if let Some(judge_model_id) = &shadow.judge_model_id {
+        let judge = get_provider_for_model(state, judge_model_id).await;
+        let request = judge_request(&served.request.messages, &served.answer, candidate);
+
+        match call_provider(state, judge.as_ref(), judge_model_id, request).await {
+            Ok(answer) => match parse_judge_verdict(answer.content().unwrap_or_default()) {
+                Ok(verdict) => comparison.verdict = Some(verdict),
+                Err(e) => comparison.judge_error = Some(e),
+            },
+            Err(e) => comparison.judge_error = Some(e.to_string()),
+        }
+    }
+
+    let mut params = RecordExecutionParams::shadow(&shadow.model_id, Ok(()), latency_ms, executor)
+        .with_input(redact_sensitive_fields(served.payload))
+        .with_output(redact_sensitive_fields(json!({"content": candidate})))
+        .with_shadow(comparison);
+
+    if let Some(usage) = &response.usage {
+        let cost = estimate_cost(
+            state,
+            &shadow.model_id,
+            usage.prompt_tokens,
+            usage.completion_tokens,
+        )
+        .await;
+        params = params
+            .with_token_usage(ExecutionTokenUsage::new(
+                usage.prompt_tokens,
+                usage.completion_tokens,
+            ))
+            .with_cost(cost);
+    }
+
+    params
+}
@@ -1,30 +1,57 @@
 //! Workflow execution endpoint
 
+use std::collections::HashMap;
+use std::time::Instant;
+
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{Extension, Path, Query, State},
+    http::{HeaderValue, StatusCode},
     response::{IntoResponse, Response},
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
+use utoipa::ToSchema;
 
-use crate::api::middleware::RequireApiKey;
+use crate::api::middleware::{
+    enforce_budget, no_log_async_error, zero_retention, BudgetSlot, RequestLocale, RequireApiKey,
+    UsageTags,
+};
 use crate::api::state::AppState;
 use crate::api::types::{ApiError, AsyncOperationCreated, AsyncQueryParams, Json};
-use crate::domain::workflow::StepExecutionResult;
-use crate::domain::OperationType;
+use crate::api::v1::chat::{EXPERIMENT_ID_HEADER, EXPERIMENT_VARIANT_HEADER};
+use crate::domain::api_key::ApiKey;
+use crate::domain::experiment::WorkflowAssignment;
+use crate::domain::knowledge_base::KnowledgeBaseCaller;
+use crate::domain::usage::UsageType;
+use crate::domain::workflow::{StepExecutionResult, StepProgress, WorkflowResult};
+use crate::domain::{DomainError, Operation, OperationType};
+use crate::infrastructure::background::spawn_tracked;
+use crate::infrastructure::observability::QueueDepthGuard;
+use crate::infrastructure::services::RecordExperimentParams;
+use crate::infrastructure::usage::RecordUsageParams;
 
 /// Request to execute a workflow
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct WorkflowExecuteRequest {
     /// Input data for the workflow
     #[serde(default)]
     pub input: serde_json::Value,
+
+    /// Cost attribution tags, merged over those of the `x-pmp-tags` header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+
+    /// End user the workflow runs for, assigning experiment variants when
+    /// experiments hash by user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
 }
 
 /// Response from workflow execution
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 pub struct WorkflowExecuteResponse {
     /// Whether the workflow completed successfully
     pub success: bool,
@@ -44,7 +71,8 @@ pub struct WorkflowExecuteResponse {
 }
 
 /// Summary of a step's execution
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[cfg_attr(feature = "client", derive(Deserialize))]
 pub struct StepExecutionSummary {
     /// Step name
     pub name: String,
@@ -80,13 +108,32 @@ impl From<&StepExecutionResult> for StepExecutionSummary {
     }
 }
 
-/// POST /v1/workflows/:workflow_id/execute
+/// Execute workflow
+#[utoipa::path(
+    post,
+    path = "/v1/workflows/{workflow_id}/execute",
+    tag = "v1",
+    operation_id = "v1_execute_workflow",
+    params(
+        ("workflow_id" = String, Path, description = "Workflow ID"),
+        AsyncQueryParams,
+    ),
+    request_body = WorkflowExecuteRequest,
+    responses(
+        (status = 200, body = WorkflowExecuteResponse),
+        (status = 202, description = "Async operation created when `async=true`", body = AsyncOperationCreated),
+    ),
+)]
+#[allow(clippy::too_many_arguments)]
 pub async fn execute_workflow(
     State(state): State<AppState>,
     RequireApiKey(api_key): RequireApiKey,
+    budget_slot: Option<Extension<BudgetSlot>>,
+    usage_tags: UsageTags,
+    request_locale: RequestLocale,
     Path(workflow_id): Path<String>,
     Query(async_params): Query<AsyncQueryParams>,
-    Json(request): Json<WorkflowExecuteRequest>,
+    Json(mut request): Json<WorkflowExecuteRequest>,
 ) -> Result<Response, ApiError> {
     debug!(
         workflow_id = %workflow_id,
@@ -95,16 +142,64 @@ pub async fn execute_workflow(
         "Executing workflow"
     );
 
+    let tags = usage_tags.merge(request.metadata.as_ref())?;
+    request_locale.fill_input(&mut request.input);
+
+    // Workflow costs aren't known upfront; only exhausted budgets block
+    enforce_budget(
+        &state,
+        budget_slot.as_ref().map(|Extension(slot)| slot),
+        &api_key,
+        None,
+        0,
+    )
+    .await?;
+
     // Handle async mode
     if async_params.is_async {
-        return handle_async_workflow_execution(state, workflow_id, request).await;
+        if zero_retention(&state, &api_key).await {
+            return Err(no_log_async_error());
+        }
+
+        return handle_async_workflow_execution(state, workflow_id, request, api_key, tags).await;
     }
 
-    let result = state
-        .workflow_service
-        .execute(&workflow_id, request.input)
-        .await
-        .map_err(ApiError::from)?;
+    let assignment =
+        assign_workflow_variant(&state, &workflow_id, &api_key, request.user.as_deref()).await;
+
+    let start_time = Instant::now();
+    let caller = KnowledgeBaseCaller::from_api_key(&api_key);
+    let (executed_id, result) = match &assignment {
+        Some(assignment) => (
+            assignment.workflow_id.as_str(),
+            state
+                .workflow_service
+                .execute_variant(
+                    &assignment.workflow_id,
+                    &assignment.step_overrides,
+                    request.input,
+                    caller,
+                    CancellationToken::new(),
+                    StepProgress::new(|_, _| {}),
+                )
+                .await,
+        ),
+        None => (
+            workflow_id.as_str(),
+            state
+                .workflow_service
+                .execute_as(&workflow_id, request.input, caller)
+                .await,
+        ),
+    };
+    let latency_ms = start_time.elapsed().as_millis() as u64;
+
+    record_workflow_usage(&state, &api_key, executed_id, latency_ms, &result, tags).await;
+    if let Some(assignment) = &assignment {
+        record_workflow_experiment(&state, assignment, &api_key, latency_ms, &result).await;
+    }
+
+    let result = result.map_err(ApiError::from)?;
 
     let response = WorkflowExecuteResponse {
         success: result.success,
@@ -114,7 +209,98 @@ pub async fn execute_workflow(
         error: result.error,
     };
 
-    Ok(Json(response).into_response())
+    let mut response = Json(response).into_response();
+    if let Some(assignment) = &assignment {
+        let headers = response.headers_mut();
+        if let Ok(value) = HeaderValue::from_str(&assignment.experiment_id) {
+            headers.insert(EXPERIMENT_ID_HEADER, value);
+        }
+        if let Ok(value) = HeaderValue::from_str(&assignment.variant_id) {
+            headers.insert(EXPERIMENT_VARIANT_HEADER, value);
+        }
+    }
+
+    Ok(response)
+}
+
+/// Assign the execution to a variant of an active experiment on the
+/// workflow, proceeding without one when the lookup fails
+async fn assign_workflow_variant(
+    state: &AppState,
+    workflow_id: &str,
+    api_key: &ApiKey,
+    user: Option<&str>,
+) -> Option<WorkflowAssignment> {
+    let assignment = state
+        .experiment_service
+        .assign_workflow_variant(workflow_id, api_key.id().as_str(), user)
+        .await
+        .unwrap_or_else(|e| {
+            warn!(error = %e, "Failed to check experiment assignment, proceeding without");
+            None
+        })?;
+
+    debug!(
+        experiment_id = %assignment.experiment_id,
+        variant_id = %assignment.variant_id,
+        assigned_workflow = %assignment.workflow_id,
+        "Experiment assignment active"
+    );
+
+    Some(assignment)
+}
+
+/// Record the outcome of an execution assigned to an experiment variant;
+/// the record takes the executed workflow in place of a model
+async fn record_workflow_experiment(
+    state: &AppState,
+    assignment: &WorkflowAssignment,
+    api_key: &ApiKey,
+    latency_ms: u64,
+    result: &Result<WorkflowResult, DomainError>,
+) {
+    let (input_tokens, output_tokens) = result
+        .as_ref()
+        .ok()
+        .and_then(|result| result.token_usage.as_ref())
+        .map_or((0, 0), |usage| (usage.input_tokens, usage.output_tokens));
+    let error = match result {
+        Ok(result) if result.success => None,
+        Ok(result) => Some(
+            result
+                .error
+                .clone()
+                .unwrap_or_else(|| "Workflow execution failed".to_string()),
+        ),
+        Err(e) => Some(e.to_string()),
+    };
+
+    let params = RecordExperimentParams {
+        record_id: None,
+        experiment_id: assignment.experiment_id.clone(),
+        variant_id: assignment.variant_id.clone(),
+        api_key_id: api_key.id().as_str().to_string(),
+        model_id: assignment.workflow_id.clone(),
+        input_tokens,
+        output_tokens,
+        cost_micros: result
+            .as_ref()
+            .ok()
+            .and_then(|result| result.cost_micros)
+            .unwrap_or(0),
+        latency_ms,
+        success: error.is_none(),
+        error,
+    };
+
+    if let Err(e) = state.experiment_service.record(params).await {
+        warn!(
+            experiment_id = %assignment.experiment_id,
+            variant_id = %assignment.variant_id,
+            error = %e,
+            "Failed to record experiment result"
+        );
+    }
 }
 
 /// Handle async workflow execution
@@ -122,14 +308,26 @@ async fn handle_async_workflow_execution(
     state: AppState,
     workflow_id: String,
     request: WorkflowExecuteRequest,
+    api_key: ApiKey,
+    tags: HashMap<String, String>,
 ) -> Result<Response, ApiError> {
+    let assignment =
+        assign_workflow_variant(&state, &workflow_id, &api_key, request.user.as_deref()).await;
+
     // Create pending operation
     let operation = state
         .operation_service
         .create_pending(
             OperationType::WorkflowExecution,
             serde_json::to_value(&request).unwrap_or(json!({})),
-            json!({ "workflow_id": &workflow_id }),
+            // The key, tags and variant let another replica resume the
+            // execution
+            json!({
+                "workflow_id": &workflow_id,
+                "api_key_id": api_key.id().as_str(),
+                "tags": &tags,
+                "experiment": &assignment,
+            }),
         )
         .await
         .map_err(ApiError::from)?;
@@ -144,7 +342,12 @@ async fn handle_async_workflow_execution(
     // Spawn background task - function returns a boxed future to avoid stack overflow
     let op_id = operation_id.clone();
     let input = request.input;
-    tokio::spawn(execute_async_workflow(state, op_id, workflow_id, input));
+    let queued = QueueDepthGuard::enter("async_workflows");
+    spawn_tracked(async move {
+        let _queued = queued;
+
+        execute_async_workflow(state, op_id, workflow_id, input, api_key, tags, assignment).await
+    });
 
     // Return 202 Accepted
     Ok((
@@ -154,6 +357,44 @@ async fn handle_async_workflow_execution(
         .into_response())
 }
 
+/// Run a requeued workflow execution again, from the workflow, key, tags
+/// and experiment variant recorded in its operation
+pub(crate) async fn resume_workflow_execution(
+    state: &AppState,
+    operation: &Operation,
+) -> Result<(), DomainError> {
+    let metadata = operation.metadata();
+    let field = |name: &str| {
+        metadata[name]
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| DomainError::validation(format!("Operation has no '{}'", name)))
+    };
+    let workflow_id = field("workflow_id")?;
+    let api_key = state
+        .api_key_service
+        .get(&field("api_key_id")?)
+        .await?
+        .ok_or_else(|| DomainError::not_found("API key of the operation no longer exists"))?;
+    let tags: HashMap<String, String> =
+        serde_json::from_value(metadata["tags"].clone()).unwrap_or_default();
+    let assignment: Option<WorkflowAssignment> =
+        serde_json::from_value(metadata["experiment"].clone()).unwrap_or_default();
+    let input = operation.input()["input"].clone();
+
+    let state = state.clone();
+    let operation_id = operation.id().to_string();
+    let queued = QueueDepthGuard::enter("async_workflows");
+    spawn_tracked(async move {
+        let _queued = queued;
+
+        execute_async_workflow(state, operation_id, workflow_id, input, api_key, tags, assignment)
+            .await
+    });
+
+    Ok(())
+}
+
 /// Execute workflow in background and update operation status
 ///
 /// Returns a boxed future to avoid stack overflow from large future sizes
@@ -163,8 +404,15 @@ fn execute_async_workflow(
     operation_id: String,
     workflow_id: String,
     input: serde_json::Value,
+    api_key: ApiKey,
+    tags: HashMap<String, String>,
+    assignment: Option<WorkflowAssignment>,
 ) -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>> {
     Box::pin(async move {
+    // Taken before running, so a cancellation either fails the transition
+    // or fires the token
+    let cancellation = state.operation_service.cancellation_token(&operation_id);
+
     // Mark as running
     if let Err(e) = state.operation_service.mark_running(&operation_id).await {
         warn!(
@@ -176,7 +424,52 @@ fn execute_async_workflow(
     }
 
     // Execute workflow
-    match state.workflow_service.execute(&workflow_id, input).await {
+    let start_time = Instant::now();
+    let caller = KnowledgeBaseCaller::from_api_key(&api_key);
+    let (steps, mut executed) = tokio::sync::mpsc::unbounded_channel();
+    let step_progress = StepProgress::new(move |done, total| {
+        let _ = steps.send((done, total));
+    });
+    let (executed_id, step_overrides) = match &assignment {
+        Some(assignment) => (assignment.workflow_id.as_str(), assignment.step_overrides.as_slice()),
+        None => (workflow_id.as_str(), &[][..]),
+    };
+    let execution = state.workflow_service.execute_variant(
+        executed_id,
+        step_overrides,
+        input,
+        caller,
+        cancellation.clone(),
+        step_progress,
+    );
+
+    // Record the steps executed as the completion of the operation
+    let report = async {
+        while let Some((done, total)) = executed.recv().await {
+            if let Err(e) = state
+                .operation_service
+                .update_completion(&operation_id, done as u64, total as u64, "steps")
+                .await
+            {
+                debug!(operation_id = %operation_id, error = %e, "Failed to record workflow progress");
+            }
+        }
+    };
+    let (result, ()) = tokio::join!(execution, report);
+
+    if cancellation.is_cancelled() {
+        info!(operation_id = %operation_id, "Async workflow execution cancelled");
+        return;
+    }
+
+    let latency_ms = start_time.elapsed().as_millis() as u64;
+
+    record_workflow_usage(&state, &api_key, executed_id, latency_ms, &result, tags).await;
+    if let Some(assignment) = &assignment {
+        record_workflow_experiment(&state, assignment, &api_key, latency_ms, &result).await;
+    }
+
+    match result {
         Ok(result) => {
             let response = WorkflowExecuteResponse {
                 success: result.success,
@@ -252,6 +545,48 @@ fn execute_async_workflow(
     })
 }
 
+/// Record the usage of a workflow execution with its cost attribution tags
+pub(super) async fn record_workflow_usage(
+    state: &AppState,
+    api_key: &ApiKey,
+    workflow_id: &str,
+    latency_ms: u64,
+    result: &Result<WorkflowResult, DomainError>,
+    tags: HashMap<String, String>,
+) {
+    let mut params = RecordUsageParams::new(UsageType::Workflow, api_key.id().as_str())
+        .with_team(api_key.team_id().as_str())
+        .with_latency(latency_ms)
+        .with_tags(tags);
+    params
+        .metadata
+        .insert("workflow_id".to_string(), workflow_id.to_string());
+
+    match result {
+        Ok(result) => {
+            if let Some(usage) = &result.token_usage {
+                params = params.with_tokens(usage.input_tokens, usage.output_tokens);
+            }
+
+            params = params.with_cost_micros(result.cost_micros.unwrap_or(0));
+
+            if !result.success {
+                params = params.with_error(
+                    result
+                        .error
+                        .clone()
+                        .unwrap_or_else(|| "Workflow execution failed".to_string()),
+                );
+            }
+        }
+        Err(e) => params = params.with_error(e.to_string()),
+    }
+
+    if let Err(e) = state.usage_service.record(params).await {
+        warn!(workflow_id = %workflow_id, error = %e, "Failed to record workflow usage");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -289,6 +624,8 @@ mod tests {
     fn test_execute_request_serialization() {
         let request = WorkflowExecuteRequest {
             input: json!({"key": "value"}),
+            metadata: None,
+            user: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
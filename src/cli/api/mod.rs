@@ -1,18 +1,22 @@
 //! API command - runs API server only (no UI)
 
-use std::net::SocketAddr;
+use std::time::Duration;
 
 use axum::middleware;
 use axum::routing::get;
 use axum::Router;
-use tokio::net::TcpListener;
-use tokio::signal;
+use futures::future::try_join_all;
 use tracing::info;
 
-use crate::api::middleware::{logging_middleware, metrics_middleware, security_headers_middleware};
+use crate::api::middleware::{
+    cors_layer, logging_middleware, make_request_span, metrics_middleware,
+    security_headers_middleware, trace_context_middleware, SecurityPolicy,
+};
 use crate::api::state::AppState;
 use crate::api::{admin, auth, health, v1};
-use crate::config::AppConfig;
+use crate::cli::listener::{self, configured_listeners};
+use crate::cli::shutdown::Shutdown;
+use crate::config::{AppConfig, ListenerSurface};
 use crate::infrastructure::logging;
 use crate::infrastructure::observability::{
     create_metrics_router, init_metrics, init_tracing, shutdown_tracing, PrometheusMetrics,
@@ -27,15 +31,24 @@ pub async fn run() -> anyhow::Result<()> {
 
     let state = crate::create_app_state_with_config(&config).await?;
     let metrics = init_metrics(&config.observability.metrics);
-    let app = create_api_router(state, metrics);
+    let shutdown = Shutdown::listen(Duration::from_secs(config.server.shutdown_timeout_secs));
+
+    let mut servers = Vec::new();
 
-    let addr = build_socket_addr(&config)?;
-    info!("Starting API server on {}", addr);
+    for listener in configured_listeners(&config.server)? {
+        let app = create_api_router(state.clone(), metrics.clone(), &config, listener.surface)?;
 
-    let listener = TcpListener::bind(addr).await?;
+        info!(surface = ?listener.surface, "Starting API server on {}", listener.addr);
+        servers.push(listener::serve(
+            listener.addr,
+            app,
+            &config.server.tls,
+            shutdown.signal(),
+        ));
+    }
 
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
+    shutdown
+        .run(async { try_join_all(servers).await.map(|_| ()) })
         .await?;
 
     shutdown_tracing();
@@ -54,65 +67,57 @@ fn init_observability(config: &AppConfig) {
     );
 }
 
-async fn shutdown_signal() {
-    let ctrl_c = async {
-        signal::ctrl_c()
-            .await
-            .expect("failed to install Ctrl+C handler");
-    };
-
-    #[cfg(unix)]
-    let terminate = async {
-        signal::unix::signal(signal::unix::SignalKind::terminate())
-            .expect("failed to install signal handler")
-            .recv()
-            .await;
-    };
-
-    #[cfg(not(unix))]
-    let terminate = std::future::pending::<()>();
-
-    tokio::select! {
-        _ = ctrl_c => {
-            info!("Received Ctrl+C, initiating graceful shutdown");
-        }
-        _ = terminate => {
-            info!("Received SIGTERM, initiating graceful shutdown");
-        }
-    }
-}
-
-fn build_socket_addr(config: &AppConfig) -> anyhow::Result<SocketAddr> {
-    Ok(SocketAddr::from((
-        config.server.host.parse::<std::net::IpAddr>()?,
-        config.server.port,
-    )))
-}
-
-/// Create API router (no UI)
-fn create_api_router(state: AppState, metrics: Option<PrometheusMetrics>) -> Router {
-    let mut router = Router::new()
-        // Health endpoints
+/// Create API router (no UI) serving the routes of `surface`
+fn create_api_router(
+    state: AppState,
+    metrics: Option<PrometheusMetrics>,
+    config: &AppConfig,
+    surface: ListenerSurface,
+) -> anyhow::Result<Router> {
+    let security_policy = SecurityPolicy::from_config(&config.security.csp)?;
+
+    // Health endpoints
+    let mut routes = Router::new()
         .route("/health", get(health::health_check))
         .route("/ready", get(health::ready_check))
-        .route("/live", get(health::live_check))
-        // Authentication endpoints
-        .nest("/auth", auth::create_auth_router())
+        .route("/live", get(health::live_check));
+
+    if surface.serves_public() {
         // OpenAI-compatible v1 API
-        .nest("/v1", v1::create_v1_router())
-        // Admin API
-        .nest("/admin", admin::create_admin_router())
+        routes = routes.nest("/v1", v1::create_v1_router());
+    }
+
+    if surface.serves_admin() {
+        routes = routes
+            // Authentication endpoints
+            .nest("/auth", auth::create_auth_router())
+            // Admin API
+            .nest("/admin", admin::create_admin_router());
+    }
+
+    let mut router = routes
         // Add state and middleware
         .with_state(state)
-        .layer(middleware::from_fn(security_headers_middleware))
+        .layer(middleware::from_fn_with_state(
+            security_policy,
+            security_headers_middleware,
+        ))
         .layer(middleware::from_fn(logging_middleware))
         .layer(middleware::from_fn(metrics_middleware))
-        .layer(tower_http::trace::TraceLayer::new_for_http());
+        .layer(middleware::from_fn(trace_context_middleware))
+        .layer(tower_http::trace::TraceLayer::new_for_http().make_span_with(make_request_span));
+
+    // Answer CORS preflights before authentication when origins are configured
+    if let Some(cors) = cors_layer(&config.security.cors)? {
+        router = router.layer(cors);
+    }
 
     // Add metrics endpoint if enabled
-    if let Some(m) = metrics {
+    if let Some(m) = metrics
+        && surface.serves_admin()
+    {
         router = router.merge(create_metrics_router(m));
     }
 
-    router
+    Ok(router)
 }
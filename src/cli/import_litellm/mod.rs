@@ -0,0 +1,86 @@
+//! Import LiteLLM command - migrates a LiteLLM proxy config into the gateway
+//!
+//! Point it at the same configuration and database as the gateway. Values the
+//! LiteLLM config reads with `os.environ/NAME` are taken from the environment
+//! of this command.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Args;
+
+use crate::api::admin::import::{ImportedEntity, LiteLlmImportResponse, import_litellm_config};
+use crate::config::AppConfig;
+use crate::domain::litellm::LiteLlmConfig;
+
+/// Arguments for the import-litellm command
+#[derive(Args, Clone)]
+pub struct ImportLitellmArgs {
+    /// Path of the LiteLLM proxy config.yaml
+    pub path: PathBuf,
+
+    /// Report what would be imported without creating anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Print the import report as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Import the models, credentials and budgets of a LiteLLM proxy config
+pub async fn run(args: ImportLitellmArgs) -> anyhow::Result<()> {
+    dotenvy::dotenv().ok();
+
+    let yaml = std::fs::read_to_string(&args.path)
+        .with_context(|| format!("Failed to read {}", args.path.display()))?;
+
+    let mut config = LiteLlmConfig::from_yaml(&yaml)?;
+    config.resolve_environment(|name| std::env::var(name).ok())?;
+
+    let app_config = AppConfig::load().unwrap_or_default();
+    let state = crate::create_app_state_with_config(&app_config).await?;
+
+    let report = import_litellm_config(&state, &config, args.dry_run)
+        .await
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to import LiteLLM config: {}",
+                e.response.error.message
+            )
+        })?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    Ok(())
+}
+
+fn print_report(report: &LiteLlmImportResponse) {
+    for (kind, entities) in [
+        ("Credentials", &report.credentials),
+        ("Models", &report.models),
+        ("Budgets", &report.budgets),
+    ] {
+        println!("{} ({})", kind, entities.len());
+
+        for ImportedEntity { id, name, status } in entities {
+            let status = serde_json::to_value(status)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_uppercase))
+                .unwrap_or_default();
+            println!("  {:<8} {} ({})", status, id, name);
+        }
+    }
+
+    for warning in &report.warnings {
+        println!("warning: {}", warning);
+    }
+
+    if report.dry_run {
+        println!("Dry run: nothing was created");
+    }
+}
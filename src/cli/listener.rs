@@ -0,0 +1,205 @@
+//! Listeners of the server commands
+//!
+//! Without `server.listeners` the server binds `server.host:server.port` and
+//! serves every route. Configured listeners replace it: each binds a TCP
+//! address or a Unix domain socket and serves its surface of the routes, so
+//! the admin surface can be firewalled apart from client traffic.
+
+use std::fmt;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+
+use axum::Router;
+use tokio::net::TcpListener;
+
+use crate::config::{ListenerSurface, ServerConfig, TlsConfig};
+use crate::infrastructure::tls;
+
+const UNIX_PREFIX: &str = "unix:";
+
+/// Address a listener binds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListenAddr {
+    Tcp(SocketAddr),
+    Unix(PathBuf),
+}
+
+impl ListenAddr {
+    /// Parse `host:port` or `unix:<path>`
+    pub fn parse(bind: &str) -> anyhow::Result<Self> {
+        if let Some(path) = bind.strip_prefix(UNIX_PREFIX) {
+            if path.is_empty() {
+                anyhow::bail!("Listener '{}' has no socket path", bind);
+            }
+
+            return Ok(Self::Unix(PathBuf::from(path)));
+        }
+
+        bind.parse::<SocketAddr>()
+            .map(Self::Tcp)
+            .map_err(|e| anyhow::anyhow!("Invalid listener address '{}': {}", bind, e))
+    }
+}
+
+impl fmt::Display for ListenAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "{}", addr),
+            Self::Unix(path) => write!(f, "{}{}", UNIX_PREFIX, path.display()),
+        }
+    }
+}
+
+/// Listener to start, with the routes it serves
+#[derive(Debug, Clone)]
+pub struct Listener {
+    pub addr: ListenAddr,
+    pub surface: ListenerSurface,
+}
+
+/// Listeners to start for the server configuration
+pub fn configured_listeners(config: &ServerConfig) -> anyhow::Result<Vec<Listener>> {
+    if config.listeners.is_empty() {
+        let addr = SocketAddr::from((config.host.parse::<IpAddr>()?, config.port));
+
+        return Ok(vec![Listener {
+            addr: ListenAddr::Tcp(addr),
+            surface: ListenerSurface::All,
+        }]);
+    }
+
+    config
+        .listeners
+        .iter()
+        .map(|listener| {
+            Ok(Listener {
+                addr: ListenAddr::parse(&listener.bind)?,
+                surface: listener.surface,
+            })
+        })
+        .collect()
+}
+
+/// Serve `app` on `addr` until `shutdown` resolves and connections drained.
+/// TLS, when enabled, terminates on TCP listeners; Unix sockets stay plain.
+pub async fn serve(
+    addr: ListenAddr,
+    app: Router,
+    tls_config: &TlsConfig,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> anyhow::Result<()> {
+    match addr {
+        ListenAddr::Tcp(addr) if tls_config.enabled => {
+            tls::serve_tls(addr, app, tls_config, shutdown).await
+        }
+        ListenAddr::Tcp(addr) => {
+            let listener = TcpListener::bind(addr).await?;
+
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .with_graceful_shutdown(shutdown)
+            .await?;
+
+            Ok(())
+        }
+        ListenAddr::Unix(path) => serve_unix(path, app, shutdown).await,
+    }
+}
+
+#[cfg(unix)]
+async fn serve_unix(
+    path: PathBuf,
+    app: Router,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> anyhow::Result<()> {
+    use tokio::net::UnixListener;
+
+    // A socket left behind by a previous run would fail the bind
+    if path.exists() {
+        std::fs::remove_file(&path)?;
+    }
+
+    let listener = UnixListener::bind(&path)?;
+
+    let result = axum::serve(listener, app.into_make_service())
+        .with_graceful_shutdown(shutdown)
+        .await;
+
+    let _ = std::fs::remove_file(&path);
+
+    Ok(result?)
+}
+
+#[cfg(not(unix))]
+async fn serve_unix(
+    path: PathBuf,
+    _app: Router,
+    _shutdown: impl Future<Output = ()> + Send + 'static,
+) -> anyhow::Result<()> {
+    anyhow::bail!(
+        "Unix domain socket listener '{}' is not supported on this platform",
+        path.display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ListenerConfig;
+
+    #[test]
+    fn test_parse_listen_addr() {
+        assert_eq!(
+            ListenAddr::parse("127.0.0.1:9090").unwrap(),
+            ListenAddr::Tcp("127.0.0.1:9090".parse().unwrap())
+        );
+        assert_eq!(
+            ListenAddr::parse("unix:/run/gateway/admin.sock").unwrap(),
+            ListenAddr::Unix(PathBuf::from("/run/gateway/admin.sock"))
+        );
+        assert_eq!(
+            ListenAddr::parse("unix:/run/gateway/admin.sock")
+                .unwrap()
+                .to_string(),
+            "unix:/run/gateway/admin.sock"
+        );
+
+        assert!(ListenAddr::parse("unix:").is_err());
+        assert!(ListenAddr::parse("localhost").is_err());
+    }
+
+    #[test]
+    fn test_configured_listeners() {
+        let mut config = ServerConfig::default();
+
+        let listeners = configured_listeners(&config).unwrap();
+        assert_eq!(listeners.len(), 1);
+        assert_eq!(
+            listeners[0].addr,
+            ListenAddr::Tcp("0.0.0.0:8080".parse().unwrap())
+        );
+        assert_eq!(listeners[0].surface, ListenerSurface::All);
+
+        config.listeners = vec![
+            ListenerConfig {
+                bind: "0.0.0.0:8080".to_string(),
+                surface: ListenerSurface::Public,
+            },
+            ListenerConfig {
+                bind: "unix:/run/gateway/admin.sock".to_string(),
+                surface: ListenerSurface::Admin,
+            },
+        ];
+
+        let listeners = configured_listeners(&config).unwrap();
+        assert_eq!(listeners.len(), 2);
+        assert_eq!(listeners[0].surface, ListenerSurface::Public);
+        assert_eq!(
+            listeners[1].addr,
+            ListenAddr::Unix(PathBuf::from("/run/gateway/admin.sock"))
+        );
+    }
+}
@@ -4,9 +4,15 @@
 //! - `serve`: API + UI combined (default)
 //! - `api`: API server only
 //! - `ui`: UI server with optional API proxy
+//! - `test`: run a test suite, exiting non-zero when it fails its gate
+//! - `import-litellm`: import the models, credentials and budgets of a LiteLLM proxy config
 
 pub mod api;
+pub mod import_litellm;
+mod listener;
 pub mod serve;
+mod shutdown;
+pub mod test;
 pub mod ui;
 
 use clap::{Parser, Subcommand};
@@ -30,4 +36,10 @@ pub enum Command {
 
     /// Run UI server with optional API proxy
     Ui(ui::UiArgs),
+
+    /// Run a test suite and exit with code 1 when it fails its pass-rate gate
+    Test(test::TestArgs),
+
+    /// Import the models, credentials and budgets of a LiteLLM proxy config
+    ImportLitellm(import_litellm::ImportLitellmArgs),
 }
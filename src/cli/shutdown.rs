@@ -0,0 +1,119 @@
+//! Graceful shutdown shared by the server commands
+//!
+//! On Ctrl+C or SIGTERM the server stops accepting connections and in-flight
+//! requests, streaming responses included, are drained. Background writes
+//! (usage, execution logs, events, webhook deliveries) are then waited for.
+//! Both phases share one deadline, `server.shutdown_timeout_secs` after the
+//! signal.
+
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::signal;
+use tokio::sync::watch;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use crate::infrastructure::background::{pending_background_tasks, wait_for_background_tasks};
+
+/// Shutdown of a server command, listening for signals once created
+pub struct Shutdown {
+    signalled: watch::Receiver<Option<Instant>>,
+    timeout: Duration,
+}
+
+impl Shutdown {
+    /// Listen for the shutdown signal, draining for at most `timeout` once
+    /// received
+    pub fn listen(timeout: Duration) -> Self {
+        let (tx, signalled) = watch::channel(None);
+
+        tokio::spawn(async move {
+            shutdown_signal().await;
+            let _ = tx.send(Some(Instant::now()));
+        });
+
+        Self { signalled, timeout }
+    }
+
+    /// Resolves once the signal was received, to stop accepting connections
+    pub fn signal(&self) -> impl Future<Output = ()> + Send + 'static {
+        let mut signalled = self.signalled.clone();
+
+        async move {
+            signalled_at(&mut signalled).await;
+        }
+    }
+
+    /// Run the server until it finished draining after the signal, then wait
+    /// for background writes with the time left
+    pub async fn run<F>(&self, server: F) -> anyhow::Result<()>
+    where
+        F: Future<Output = anyhow::Result<()>>,
+    {
+        tokio::pin!(server);
+        let mut signalled = self.signalled.clone();
+
+        let deadline = tokio::select! {
+            result = &mut server => return result,
+            at = signalled_at(&mut signalled) => at + self.timeout,
+        };
+
+        match tokio::time::timeout_at(deadline, server).await {
+            Ok(result) => result?,
+            Err(_) => warn!(
+                timeout_secs = self.timeout.as_secs(),
+                "Shutdown deadline reached with requests still in flight"
+            ),
+        }
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+
+        if !wait_for_background_tasks(remaining).await {
+            warn!(
+                pending = pending_background_tasks(),
+                "Shutdown deadline reached with background tasks still running"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+async fn signalled_at(signalled: &mut watch::Receiver<Option<Instant>>) -> Instant {
+    let at = signalled.wait_for(Option::is_some).await.map(|at| *at);
+
+    match at {
+        Ok(at) => at.unwrap_or_else(Instant::now),
+        // The listener never drops the sender before signalling
+        Err(_) => std::future::pending().await,
+    }
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("failed to install signal handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {
+            info!("Received Ctrl+C, initiating graceful shutdown");
+        }
+        _ = terminate => {
+            info!("Received SIGTERM, initiating graceful shutdown");
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Test command - runs a test suite and exits non-zero when its gate fails
+//!
+//! Meant for CI: point it at the same configuration and database as the
+//! gateway, change a prompt, run the suite and fail the pipeline when the pass
+//! rate drops below the threshold.
+
+use clap::Args;
+
+use crate::config::AppConfig;
+use crate::domain::test_case::TestSuiteRun;
+
+/// Arguments for the test command
+#[derive(Args, Clone)]
+pub struct TestArgs {
+    /// ID of the test suite to run
+    pub suite_id: String,
+
+    /// Pass rate (0-100) required to succeed, overrides the suite threshold
+    #[arg(long)]
+    pub min_pass_rate: Option<f64>,
+
+    /// Print the run report as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+/// Run a test suite, returning whether it passed its gate
+pub async fn run(args: TestArgs) -> anyhow::Result<bool> {
+    dotenvy::dotenv().ok();
+
+    let config = AppConfig::load().unwrap_or_default();
+    let state = crate::create_app_state_with_config(&config).await?;
+
+    let run = state
+        .test_suite_service
+        .run(&args.suite_id, args.min_pass_rate)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to run test suite '{}': {}", args.suite_id, e))?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&run)?);
+    } else {
+        print_report(&run);
+    }
+
+    Ok(run.gate_passed)
+}
+
+fn print_report(run: &TestSuiteRun) {
+    println!("Test suite '{}' (run {})", run.suite_id, run.id);
+
+    for case in &run.cases {
+        let status = if case.passed { "PASS" } else { "FAIL" };
+        println!(
+            "  {}  {} ({} ms)",
+            status, case.test_case_id, case.execution_time_ms
+        );
+
+        if let Some(error) = &case.error {
+            println!("        {}", error);
+        }
+    }
+
+    println!(
+        "{}/{} passed ({:.1}%), threshold {:.1}%: {}",
+        run.passed,
+        run.total,
+        run.pass_rate,
+        run.pass_threshold,
+        if run.gate_passed { "PASSED" } else { "FAILED" }
+    );
+}
@@ -1,6 +1,7 @@
 //! UI command - runs UI server with optional API proxy
 
 use std::net::SocketAddr;
+use std::time::Duration;
 
 use axum::body::Body;
 use axum::extract::State;
@@ -14,6 +15,7 @@ use tokio::net::TcpListener;
 use tower_http::services::{ServeDir, ServeFile};
 use tracing::{error, info};
 
+use crate::cli::shutdown::Shutdown;
 use crate::config::AppConfig;
 use crate::infrastructure::logging;
 
@@ -58,7 +60,18 @@ pub async fn run(args: UiArgs) -> anyhow::Result<()> {
     }
 
     let listener = TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    let shutdown = Shutdown::listen(Duration::from_secs(config.server.shutdown_timeout_secs));
+
+    shutdown
+        .run(async {
+            axum::serve(listener, app)
+                .with_graceful_shutdown(shutdown.signal())
+                .await
+                .map_err(anyhow::Error::from)
+        })
+        .await?;
+
+    info!("UI server shutdown complete");
 
     Ok(())
 }
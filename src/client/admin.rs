@@ -0,0 +1,330 @@
+//! Admin API endpoints of the client
+
+use reqwest::Method;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use super::{ClientError, GatewayClient};
+use crate::api::admin::api_keys::{
+    ApiKeyResponse, ApiKeyWithSecretResponse, CreateApiKeyRequest, ListApiKeysResponse,
+    UpdateApiKeyRequest,
+};
+use crate::api::admin::models::{
+    CloneModelRequest, CreateModelApiRequest, ListModelsResponse, ModelResponse,
+    UpdateModelApiRequest,
+};
+use crate::api::admin::prompts::{
+    ClonePromptRequest, CreatePromptApiRequest, ListPromptsResponse, PromptResponse,
+    PromptVariablesResponse, RenderPromptApiRequest, RenderPromptResponse,
+    UpdatePromptApiRequest,
+};
+use crate::api::admin::workflows::{
+    CloneWorkflowRequest, CreateWorkflowApiRequest, ListWorkflowsResponse,
+    UpdateWorkflowApiRequest, WorkflowResponse,
+};
+
+/// Admin endpoints, authenticating with an admin API key or JWT
+#[derive(Debug, Clone, Copy)]
+pub struct AdminClient<'a> {
+    client: &'a GatewayClient,
+}
+
+impl<'a> AdminClient<'a> {
+    pub(super) fn new(client: &'a GatewayClient) -> Self {
+        Self { client }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, ClientError> {
+        self.client
+            .request(Method::GET, &format!("/admin{}", path), None::<&()>)
+            .await
+    }
+
+    async fn send<B, T>(&self, method: Method, path: &str, body: &B) -> Result<T, ClientError>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        self.client
+            .request(method, &format!("/admin{}", path), Some(body))
+            .await
+    }
+
+    async fn post<T: DeserializeOwned>(&self, path: &str) -> Result<T, ClientError> {
+        self.client
+            .request(Method::POST, &format!("/admin{}", path), None::<&()>)
+            .await
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), ClientError> {
+        self.client
+            .request::<(), serde_json::Value>(Method::DELETE, &format!("/admin{}", path), None)
+            .await
+            .map(|_| ())
+    }
+
+    // ------------------------------------------------------------------------
+    // Models
+    // ------------------------------------------------------------------------
+
+    pub async fn list_models(&self) -> Result<ListModelsResponse, ClientError> {
+        self.get("/models").await
+    }
+
+    pub async fn get_model(&self, model_id: &str) -> Result<ModelResponse, ClientError> {
+        self.get(&format!("/models/{}", model_id)).await
+    }
+
+    pub async fn create_model(
+        &self,
+        request: &CreateModelApiRequest,
+    ) -> Result<ModelResponse, ClientError> {
+        self.send(Method::POST, "/models", request).await
+    }
+
+    pub async fn update_model(
+        &self,
+        model_id: &str,
+        request: &UpdateModelApiRequest,
+    ) -> Result<ModelResponse, ClientError> {
+        self.send(Method::PUT, &format!("/models/{}", model_id), request)
+            .await
+    }
+
+    pub async fn clone_model(
+        &self,
+        model_id: &str,
+        request: &CloneModelRequest,
+    ) -> Result<ModelResponse, ClientError> {
+        self.send(
+            Method::POST,
+            &format!("/models/{}/clone", model_id),
+            request,
+        )
+        .await
+    }
+
+    pub async fn delete_model(&self, model_id: &str) -> Result<(), ClientError> {
+        self.delete(&format!("/models/{}", model_id)).await
+    }
+
+    // ------------------------------------------------------------------------
+    // Prompts
+    // ------------------------------------------------------------------------
+
+    pub async fn list_prompts(&self) -> Result<ListPromptsResponse, ClientError> {
+        self.get("/prompts").await
+    }
+
+    pub async fn get_prompt(&self, prompt_id: &str) -> Result<PromptResponse, ClientError> {
+        self.get(&format!("/prompts/{}", prompt_id)).await
+    }
+
+    pub async fn create_prompt(
+        &self,
+        request: &CreatePromptApiRequest,
+    ) -> Result<PromptResponse, ClientError> {
+        self.send(Method::POST, "/prompts", request).await
+    }
+
+    pub async fn update_prompt(
+        &self,
+        prompt_id: &str,
+        request: &UpdatePromptApiRequest,
+    ) -> Result<PromptResponse, ClientError> {
+        self.send(Method::PUT, &format!("/prompts/{}", prompt_id), request)
+            .await
+    }
+
+    pub async fn clone_prompt(
+        &self,
+        prompt_id: &str,
+        request: &ClonePromptRequest,
+    ) -> Result<PromptResponse, ClientError> {
+        self.send(
+            Method::POST,
+            &format!("/prompts/{}/clone", prompt_id),
+            request,
+        )
+        .await
+    }
+
+    pub async fn render_prompt(
+        &self,
+        prompt_id: &str,
+        request: &RenderPromptApiRequest,
+    ) -> Result<RenderPromptResponse, ClientError> {
+        self.send(
+            Method::POST,
+            &format!("/prompts/{}/render", prompt_id),
+            request,
+        )
+        .await
+    }
+
+    pub async fn prompt_variables(
+        &self,
+        prompt_id: &str,
+    ) -> Result<PromptVariablesResponse, ClientError> {
+        self.get(&format!("/prompts/{}/variables", prompt_id)).await
+    }
+
+    pub async fn delete_prompt(&self, prompt_id: &str) -> Result<(), ClientError> {
+        self.delete(&format!("/prompts/{}", prompt_id)).await
+    }
+
+    // ------------------------------------------------------------------------
+    // API keys
+    // ------------------------------------------------------------------------
+
+    pub async fn list_api_keys(&self) -> Result<ListApiKeysResponse, ClientError> {
+        self.get("/api-keys").await
+    }
+
+    pub async fn get_api_key(&self, key_id: &str) -> Result<ApiKeyResponse, ClientError> {
+        self.get(&format!("/api-keys/{}", key_id)).await
+    }
+
+    /// Create an API key; the secret is only returned here
+    pub async fn create_api_key(
+        &self,
+        request: &CreateApiKeyRequest,
+    ) -> Result<ApiKeyWithSecretResponse, ClientError> {
+        self.send(Method::POST, "/api-keys", request).await
+    }
+
+    pub async fn update_api_key(
+        &self,
+        key_id: &str,
+        request: &UpdateApiKeyRequest,
+    ) -> Result<ApiKeyResponse, ClientError> {
+        self.send(Method::PUT, &format!("/api-keys/{}", key_id), request)
+            .await
+    }
+
+    pub async fn suspend_api_key(&self, key_id: &str) -> Result<ApiKeyResponse, ClientError> {
+        self.post(&format!("/api-keys/{}/suspend", key_id)).await
+    }
+
+    pub async fn activate_api_key(&self, key_id: &str) -> Result<ApiKeyResponse, ClientError> {
+        self.post(&format!("/api-keys/{}/activate", key_id)).await
+    }
+
+    pub async fn revoke_api_key(&self, key_id: &str) -> Result<ApiKeyResponse, ClientError> {
+        self.post(&format!("/api-keys/{}/revoke", key_id)).await
+    }
+
+    pub async fn delete_api_key(&self, key_id: &str) -> Result<(), ClientError> {
+        self.delete(&format!("/api-keys/{}", key_id)).await
+    }
+
+    // ------------------------------------------------------------------------
+    // Workflows
+    // ------------------------------------------------------------------------
+
+    pub async fn list_workflows(&self) -> Result<ListWorkflowsResponse, ClientError> {
+        self.get("/workflows").await
+    }
+
+    pub async fn get_workflow(&self, workflow_id: &str) -> Result<WorkflowResponse, ClientError> {
+        self.get(&format!("/workflows/{}", workflow_id)).await
+    }
+
+    pub async fn create_workflow(
+        &self,
+        request: &CreateWorkflowApiRequest,
+    ) -> Result<WorkflowResponse, ClientError> {
+        self.send(Method::POST, "/workflows", request).await
+    }
+
+    pub async fn update_workflow(
+        &self,
+        workflow_id: &str,
+        request: &UpdateWorkflowApiRequest,
+    ) -> Result<WorkflowResponse, ClientError> {
+        self.send(Method::PUT, &format!("/workflows/{}", workflow_id), request)
+            .await
+    }
+
+    pub async fn clone_workflow(
+        &self,
+        workflow_id: &str,
+        request: &CloneWorkflowRequest,
+    ) -> Result<WorkflowResponse, ClientError> {
+        self.send(
+            Method::POST,
+            &format!("/workflows/{}/clone", workflow_id),
+            request,
+        )
+        .await
+    }
+
+    pub async fn delete_workflow(&self, workflow_id: &str) -> Result<(), ClientError> {
+        self.delete(&format!("/workflows/{}", workflow_id)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_create_prompt() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/admin/prompts"))
+            .and(body_partial_json(json!({
+                "id": "greeting",
+                "content": "Hello ${var:name}"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "greeting",
+                "name": "Greeting",
+                "content": "Hello ${var:name}",
+                "variables": [{"name": "name", "required": true}],
+                "tags": [],
+                "version": 1,
+                "enabled": true,
+                "created_at": "2026-01-01T00:00:00Z",
+                "updated_at": "2026-01-01T00:00:00Z"
+            })))
+            .mount(&server)
+            .await;
+
+        let request: CreatePromptApiRequest = serde_json::from_value(json!({
+            "id": "greeting",
+            "name": "Greeting",
+            "content": "Hello ${var:name}"
+        }))
+        .unwrap();
+
+        let client = GatewayClient::new(server.uri(), "pk_test_admin");
+        let prompt = client.admin().create_prompt(&request).await.unwrap();
+
+        assert_eq!(prompt.id, "greeting");
+        assert_eq!(prompt.version, 1);
+        assert_eq!(prompt.variables.len(), 1);
+        assert_eq!(prompt.variables[0].name, "name");
+        assert!(prompt.variables[0].required);
+        assert_eq!(prompt.variables[0].default, None);
+    }
+
+    #[tokio::test]
+    async fn test_delete_model() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/admin/models/gpt-4"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"deleted": true})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = GatewayClient::new(server.uri(), "pk_test_admin");
+        client.admin().delete_model("gpt-4").await.unwrap();
+    }
+}
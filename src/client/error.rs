@@ -0,0 +1,35 @@
+//! Client errors
+
+use thiserror::Error;
+
+use crate::api::types::ApiErrorDetail;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// The request could not be sent or its response read
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// The gateway answered with an error
+    #[error("Gateway error ({status}): {}", .error.message)]
+    Api { status: u16, error: ApiErrorDetail },
+
+    /// An error response without a gateway error body, e.g. from a proxy
+    #[error("Unexpected HTTP status {status}: {body}")]
+    Status { status: u16, body: String },
+
+    /// The response body did not match the expected type
+    #[error("Invalid response body: {0}")]
+    Decode(#[from] serde_json::Error),
+}
+
+impl ClientError {
+    /// HTTP status of a gateway error
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Api { status, .. } | Self::Status { status, .. } => Some(*status),
+            Self::Http(e) => e.status().map(|s| s.as_u16()),
+            Self::Decode(_) => None,
+        }
+    }
+}
@@ -0,0 +1,374 @@
+//! Typed HTTP client for the gateway
+//!
+//! Compiled with the `client` feature. Requests and responses are the same
+//! types the handlers use, so the client cannot drift from the server. The
+//! v1 endpoints are methods of `GatewayClient`; the admin ones are reached
+//! through `GatewayClient::admin`. Endpoints without a typed method can be
+//! called with `GatewayClient::request`.
+
+mod admin;
+mod error;
+mod sse;
+
+pub use admin::AdminClient;
+pub use error::ClientError;
+
+use futures::Stream;
+use reqwest::{Method, RequestBuilder, Response};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::api::types::{
+    ApiErrorResponse, ApiModel, ChatCompletionRequest, ChatCompletionResponse,
+    ChatCompletionStreamResponse, ModelsResponse, OperationResponse,
+};
+use crate::api::v1::assistants::{
+    AssistantChatRequest, AssistantInfo, AssistantRunRequest, AssistantRunResponse,
+};
+use crate::api::v1::workflows::{WorkflowExecuteRequest, WorkflowExecuteResponse};
+
+/// Client of one gateway, authenticating with an API key (or an admin JWT)
+#[derive(Debug, Clone)]
+pub struct GatewayClient {
+    http: reqwest::Client,
+    base_url: String,
+    token: String,
+}
+
+impl GatewayClient {
+    /// Client of the gateway at `base_url` (e.g. `http://localhost:8080`)
+    pub fn new(base_url: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: token.into(),
+        }
+    }
+
+    /// Use a preconfigured HTTP client (timeouts, proxies, TLS roots)
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Admin API endpoints
+    pub fn admin(&self) -> AdminClient<'_> {
+        AdminClient::new(self)
+    }
+
+    // ------------------------------------------------------------------------
+    // v1 API
+    // ------------------------------------------------------------------------
+
+    pub async fn chat_completion(
+        &self,
+        request: &ChatCompletionRequest,
+    ) -> Result<ChatCompletionResponse, ClientError> {
+        let request = ChatCompletionRequest {
+            stream: false,
+            ..request.clone()
+        };
+
+        self.request(Method::POST, "/v1/chat/completions", Some(&request))
+            .await
+    }
+
+    /// Stream a chat completion, yielding the chunks until the gateway sends
+    /// `[DONE]`
+    pub async fn chat_completion_stream(
+        &self,
+        request: &ChatCompletionRequest,
+    ) -> Result<
+        impl Stream<Item = Result<ChatCompletionStreamResponse, ClientError>> + use<>,
+        ClientError,
+    > {
+        let request = ChatCompletionRequest {
+            stream: true,
+            ..request.clone()
+        };
+
+        let response = self
+            .send(
+                self.builder(Method::POST, "/v1/chat/completions")
+                    .json(&request),
+            )
+            .await?;
+
+        Ok(sse::events(response))
+    }
+
+    pub async fn list_models(&self) -> Result<ModelsResponse, ClientError> {
+        self.request(Method::GET, "/v1/models", None::<&()>).await
+    }
+
+    pub async fn get_model(&self, model_id: &str) -> Result<ApiModel, ClientError> {
+        self.request(
+            Method::GET,
+            &format!("/v1/models/{}", model_id),
+            None::<&()>,
+        )
+        .await
+    }
+
+    pub async fn execute_workflow(
+        &self,
+        workflow_id: &str,
+        request: &WorkflowExecuteRequest,
+    ) -> Result<WorkflowExecuteResponse, ClientError> {
+        self.request(
+            Method::POST,
+            &format!("/v1/workflows/{}/execute", workflow_id),
+            Some(request),
+        )
+        .await
+    }
+
+    pub async fn get_assistant(&self, assistant_id: &str) -> Result<AssistantInfo, ClientError> {
+        self.request(
+            Method::GET,
+            &format!("/v1/assistants/{}", assistant_id),
+            None::<&()>,
+        )
+        .await
+    }
+
+    /// One assistant turn, without streaming
+    pub async fn assistant_chat(
+        &self,
+        assistant_id: &str,
+        request: &AssistantChatRequest,
+    ) -> Result<ChatCompletionResponse, ClientError> {
+        let request = AssistantChatRequest {
+            stream: false,
+            ..request.clone()
+        };
+
+        self.request(
+            Method::POST,
+            &format!("/v1/assistants/{}/chat", assistant_id),
+            Some(&request),
+        )
+        .await
+    }
+
+    /// Agent run of an assistant, without streaming
+    pub async fn run_assistant(
+        &self,
+        assistant_id: &str,
+        request: &AssistantRunRequest,
+    ) -> Result<AssistantRunResponse, ClientError> {
+        let request = AssistantRunRequest {
+            stream: false,
+            ..request.clone()
+        };
+
+        self.request(
+            Method::POST,
+            &format!("/v1/assistants/{}/run", assistant_id),
+            Some(&request),
+        )
+        .await
+    }
+
+    pub async fn get_operation(
+        &self,
+        operation_id: &str,
+    ) -> Result<OperationResponse, ClientError> {
+        self.request(
+            Method::GET,
+            &format!("/v1/operations/{}", operation_id),
+            None::<&()>,
+        )
+        .await
+    }
+
+    pub async fn cancel_operation(
+        &self,
+        operation_id: &str,
+    ) -> Result<OperationResponse, ClientError> {
+        self.request(
+            Method::DELETE,
+            &format!("/v1/operations/{}", operation_id),
+            None::<&()>,
+        )
+        .await
+    }
+
+    // ------------------------------------------------------------------------
+    // Raw requests
+    // ------------------------------------------------------------------------
+
+    /// Send a JSON request to `path` and decode the JSON response
+    pub async fn request<B, T>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&B>,
+    ) -> Result<T, ClientError>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let mut builder = self.builder(method, path);
+
+        if let Some(body) = body {
+            builder = builder.json(body);
+        }
+
+        let bytes = self.send(builder).await?.bytes().await?;
+
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    fn builder(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http
+            .request(method, format!("{}{}", self.base_url, path))
+            .bearer_auth(&self.token)
+    }
+
+    /// Send the request, turning gateway error responses into `ClientError::Api`
+    async fn send(&self, builder: RequestBuilder) -> Result<Response, ClientError> {
+        let response = builder.send().await?;
+        let status = response.status();
+
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let bytes = response.bytes().await?;
+
+        match serde_json::from_slice::<ApiErrorResponse>(&bytes) {
+            Ok(body) => Err(ClientError::Api {
+                status: status.as_u16(),
+                error: body.error,
+            }),
+            Err(_) => Err(ClientError::Status {
+                status: status.as_u16(),
+                body: String::from_utf8_lossy(&bytes).into_owned(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use serde_json::json;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn chat_request() -> ChatCompletionRequest {
+        serde_json::from_value(json!({
+            "model": "gpt-4",
+            "messages": [{"role": "user", "content": "Hello"}]
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(header("authorization", "Bearer pk_test_key"))
+            .and(body_partial_json(
+                json!({"model": "gpt-4", "stream": false}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion",
+                "created": 1700000000,
+                "model": "gpt-4",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": "Hi!"},
+                    "finish_reason": "stop"
+                }],
+                "usage": {"prompt_tokens": 3, "completion_tokens": 2, "total_tokens": 5}
+            })))
+            .mount(&server)
+            .await;
+
+        let client = GatewayClient::new(server.uri(), "pk_test_key");
+        let response = client.chat_completion(&chat_request()).await.unwrap();
+
+        assert_eq!(response.id, "chatcmpl-1");
+        assert_eq!(response.choices.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_chat_completion_stream() {
+        let server = MockServer::start().await;
+        let chunk = |content: &str| {
+            json!({
+                "id": "chatcmpl-1",
+                "object": "chat.completion.chunk",
+                "created": 1700000000,
+                "model": "gpt-4",
+                "choices": [{"index": 0, "delta": {"content": content}, "finish_reason": null}]
+            })
+        };
+        let body = format!(
+            "data: {}\n\ndata: {}\n\ndata: [DONE]\n\n",
+            chunk("Hel"),
+            chunk("lo")
+        );
+
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .and(body_partial_json(json!({"stream": true})))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-type", "text/event-stream")
+                    .set_body_string(body),
+            )
+            .mount(&server)
+            .await;
+
+        let client = GatewayClient::new(server.uri(), "pk_test_key");
+        let chunks: Vec<_> = client
+            .chat_completion_stream(&chat_request())
+            .await
+            .unwrap()
+            .collect()
+            .await;
+
+        assert_eq!(chunks.len(), 2);
+        let content: String = chunks
+            .into_iter()
+            .map(|chunk| chunk.unwrap().choices[0].delta.content.clone().unwrap())
+            .collect();
+        assert_eq!(content, "Hello");
+    }
+
+    #[tokio::test]
+    async fn test_error_response() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/v1/models/missing"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+                "error": {
+                    "message": "Model 'missing' not found",
+                    "type": "not_found_error"
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let client = GatewayClient::new(format!("{}/", server.uri()), "pk_test_key");
+        let err = client.get_model("missing").await.unwrap_err();
+
+        assert_eq!(err.status(), Some(404));
+        assert!(matches!(
+            err,
+            ClientError::Api { ref error, .. } if error.message == "Model 'missing' not found"
+        ));
+    }
+}
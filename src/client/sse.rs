@@ -0,0 +1,98 @@
+//! Decoding of streamed chat completions
+//!
+//! The gateway streams one `data:` event per chunk and ends with
+//! `data: [DONE]`. Events can be split across network reads, so bytes are
+//! buffered until a blank line completes the event.
+
+use bytes::{Bytes, BytesMut};
+use futures::stream::{self, BoxStream};
+use futures::{Stream, StreamExt};
+use reqwest::Response;
+
+use super::ClientError;
+use crate::api::types::ChatCompletionStreamResponse;
+
+const DONE: &str = "[DONE]";
+
+struct EventReader {
+    bytes: BoxStream<'static, reqwest::Result<Bytes>>,
+    buffer: BytesMut,
+    done: bool,
+}
+
+impl EventReader {
+    /// Next complete event in the buffer, without its trailing blank line
+    fn next_event(&mut self) -> Option<String> {
+        let end = self.buffer.windows(2).position(|w| w == b"\n\n")?;
+        let event = self.buffer.split_to(end + 2);
+
+        Some(String::from_utf8_lossy(&event[..end]).into_owned())
+    }
+}
+
+/// Data of an event, joining multi-line `data:` fields
+fn event_data(event: &str) -> Option<String> {
+    let lines: Vec<&str> = event
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.strip_prefix(' ').unwrap_or(data))
+        .collect();
+
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+pub(super) fn events(
+    response: Response,
+) -> impl Stream<Item = Result<ChatCompletionStreamResponse, ClientError>> {
+    let reader = EventReader {
+        bytes: response.bytes_stream().boxed(),
+        buffer: BytesMut::new(),
+        done: false,
+    };
+
+    stream::unfold(reader, |mut reader| async move {
+        loop {
+            if reader.done {
+                return None;
+            }
+
+            if let Some(event) = reader.next_event() {
+                let Some(data) = event_data(&event) else {
+                    continue;
+                };
+
+                if data.trim() == DONE {
+                    return None;
+                }
+
+                let chunk = serde_json::from_str(&data).map_err(ClientError::from);
+                return Some((chunk, reader));
+            }
+
+            match reader.bytes.next().await {
+                Some(Ok(bytes)) => reader.buffer.extend_from_slice(&bytes),
+                Some(Err(e)) => {
+                    reader.done = true;
+                    return Some((Err(ClientError::from(e)), reader));
+                }
+                None => return None,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_data() {
+        assert_eq!(event_data("data: {\"a\":1}"), Some("{\"a\":1}".to_string()));
+        assert_eq!(event_data("data:[DONE]"), Some("[DONE]".to_string()));
+        assert_eq!(
+            event_data("event: ping\ndata: x\ndata: y"),
+            Some("x\ny".to_string())
+        );
+        assert_eq!(event_data(": keep-alive"), None);
+    }
+}
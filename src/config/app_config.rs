@@ -1,5 +1,9 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
 
+use crate::domain::cache::CacheMode;
+use crate::domain::guardrail::{InjectionAction, SecretLeakAction};
 use crate::infrastructure::observability::ObservabilityConfig;
 
 /// Application configuration
@@ -13,6 +17,1125 @@ pub struct AppConfig {
     pub observability: ObservabilityConfig,
     #[serde(default)]
     pub storage: StorageConfig,
+    #[serde(default)]
+    pub audit: AuditConfig,
+    #[serde(default)]
+    pub security: SecurityConfig,
+    #[serde(default)]
+    pub pricing: PricingConfig,
+    #[serde(default)]
+    pub usage_export: UsageExportConfig,
+    #[serde(default)]
+    pub billing: BillingConfig,
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+    #[serde(default)]
+    pub anomaly_detection: AnomalyDetectionConfig,
+    #[serde(default)]
+    pub reconciliation: ReconciliationConfig,
+    #[serde(default)]
+    pub events: EventsConfig,
+    #[serde(default)]
+    pub webhooks: WebhooksConfig,
+    #[serde(default)]
+    pub health: HealthConfig,
+    #[serde(default)]
+    pub slo: SloConfig,
+    #[serde(default)]
+    pub experiments: ExperimentsConfig,
+    #[serde(default)]
+    pub canary: CanaryConfig,
+    #[serde(default)]
+    pub injection_guard: InjectionGuardConfig,
+    #[serde(default)]
+    pub content_policy: ContentPolicyConfig,
+    #[serde(default)]
+    pub secret_scanner: SecretScannerConfig,
+    #[serde(default)]
+    pub leader_election: LeaderElectionConfig,
+    #[serde(default)]
+    pub agent: AgentConfig,
+    #[serde(default)]
+    pub fine_tunes: FineTunesConfig,
+    #[serde(default)]
+    pub uploads: UploadsConfig,
+    #[serde(default)]
+    pub ocr: OcrConfig,
+    #[serde(default)]
+    pub operations: OperationsConfig,
+    #[serde(default)]
+    pub cache: ResponseCacheConfig,
+}
+
+/// Browser-facing security configuration (CORS and Content Security Policy)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SecurityConfig {
+    #[serde(default)]
+    pub cors: CorsConfig,
+    #[serde(default)]
+    pub csp: CspConfig,
+}
+
+/// Cross-origin resource sharing configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct CorsConfig {
+    /// Origins allowed to call the gateway from a browser. Empty disables
+    /// CORS, "*" allows any origin (not combinable with credentials)
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    /// Allowed request methods, "*" mirrors the requested method
+    #[serde(default = "default_cors_allowed_methods")]
+    pub allowed_methods: Vec<String>,
+    /// Allowed request headers, "*" mirrors the requested headers
+    #[serde(default = "default_cors_allowed_headers")]
+    pub allowed_headers: Vec<String>,
+    /// Response headers exposed to browser scripts
+    #[serde(default)]
+    pub exposed_headers: Vec<String>,
+    /// Whether browsers may send cookies and authorization headers
+    #[serde(default)]
+    pub allow_credentials: bool,
+    /// How long browsers may cache preflight responses, in seconds
+    #[serde(default = "default_cors_max_age_secs")]
+    pub max_age_secs: u64,
+}
+
+fn default_cors_allowed_methods() -> Vec<String> {
+    ["GET", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+fn default_cors_allowed_headers() -> Vec<String> {
+    [
+        "authorization",
+        "content-type",
+        "x-api-key",
+        "x-request-id",
+        "traceparent",
+        "tracestate",
+    ]
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+fn default_cors_max_age_secs() -> u64 {
+    3600
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: Vec::new(),
+            allowed_methods: default_cors_allowed_methods(),
+            allowed_headers: default_cors_allowed_headers(),
+            exposed_headers: Vec::new(),
+            allow_credentials: false,
+            max_age_secs: default_cors_max_age_secs(),
+        }
+    }
+}
+
+/// Content Security Policy configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct CspConfig {
+    /// Policy sent with API responses
+    #[serde(default = "default_api_csp")]
+    pub api_policy: String,
+    /// Policy sent with the admin UI (`/ui`) and Swagger UI (`/swagger-ui`)
+    #[serde(default = "default_ui_csp")]
+    pub ui_policy: String,
+}
+
+fn default_api_csp() -> String {
+    "default-src 'none'; frame-ancestors 'none'".to_string()
+}
+
+fn default_ui_csp() -> String {
+    // UI needs scripts and styles from CDN and self
+    "default-src 'self'; \
+     script-src 'self' https://cdn.tailwindcss.com https://code.jquery.com 'unsafe-inline'; \
+     style-src 'self' 'unsafe-inline'; \
+     connect-src 'self'; \
+     img-src 'self' data:; \
+     frame-ancestors 'none'"
+        .to_string()
+}
+
+impl Default for CspConfig {
+    fn default() -> Self {
+        Self {
+            api_policy: default_api_csp(),
+            ui_policy: default_ui_csp(),
+        }
+    }
+}
+
+/// Audit log export configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct AuditConfig {
+    /// Export sink: "none", "log" (structured `audit` tracing events) or "http"
+    #[serde(default = "default_audit_sink")]
+    pub sink: String,
+    /// Endpoint receiving audit logs as JSON when the sink is "http"
+    #[serde(default)]
+    pub http_url: Option<String>,
+    /// Bearer token sent to the HTTP sink
+    #[serde(default)]
+    pub http_token: Option<String>,
+    /// HTTP sink request timeout in seconds
+    #[serde(default = "default_audit_http_timeout_secs")]
+    pub http_timeout_secs: u64,
+}
+
+fn default_audit_sink() -> String {
+    "none".to_string()
+}
+
+fn default_audit_http_timeout_secs() -> u64 {
+    10
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            sink: default_audit_sink(),
+            http_url: None,
+            http_token: None,
+            http_timeout_secs: default_audit_http_timeout_secs(),
+        }
+    }
+}
+
+/// Model pricing feed synchronisation
+#[derive(Debug, Clone, Deserialize)]
+pub struct PricingConfig {
+    /// URL of a pricing feed; synchronisation is disabled when unset
+    #[serde(default)]
+    pub sync_url: Option<String>,
+    /// Feed format: "native" or "litellm"
+    #[serde(default = "default_pricing_sync_format")]
+    pub sync_format: String,
+    /// Seconds between synchronisations
+    #[serde(default = "default_pricing_sync_interval_secs")]
+    pub sync_interval_secs: u64,
+    /// Feed request timeout in seconds
+    #[serde(default = "default_pricing_sync_timeout_secs")]
+    pub sync_timeout_secs: u64,
+    /// Model IDs to synchronise; empty synchronises every model in the feed
+    #[serde(default)]
+    pub sync_models: Vec<String>,
+}
+
+fn default_pricing_sync_format() -> String {
+    "native".to_string()
+}
+
+fn default_pricing_sync_interval_secs() -> u64 {
+    86_400
+}
+
+fn default_pricing_sync_timeout_secs() -> u64 {
+    30
+}
+
+impl Default for PricingConfig {
+    fn default() -> Self {
+        Self {
+            sync_url: None,
+            sync_format: default_pricing_sync_format(),
+            sync_interval_secs: default_pricing_sync_interval_secs(),
+            sync_timeout_secs: default_pricing_sync_timeout_secs(),
+            sync_models: Vec::new(),
+        }
+    }
+}
+
+/// Scheduled delivery of daily usage files to S3
+#[derive(Debug, Clone, Deserialize)]
+pub struct UsageExportConfig {
+    /// Bucket the daily files are written to; the export is disabled when unset
+    #[serde(default)]
+    pub s3_bucket: Option<String>,
+    /// Key prefix, files are written to `<prefix>/dt=YYYY-MM-DD/usage-YYYY-MM-DD.<ext>`
+    #[serde(default = "default_usage_export_s3_prefix")]
+    pub s3_prefix: String,
+    /// AWS region, defaults to the region of the environment
+    #[serde(default)]
+    pub s3_region: Option<String>,
+    /// Custom endpoint for S3-compatible storage (e.g. MinIO)
+    #[serde(default)]
+    pub s3_endpoint_url: Option<String>,
+    /// File format: "csv" or "parquet"
+    #[serde(default = "default_usage_export_format")]
+    pub format: String,
+    /// Hour of the day (UTC) the previous day is exported at
+    #[serde(default = "default_usage_export_hour_utc")]
+    pub hour_utc: u32,
+}
+
+fn default_usage_export_s3_prefix() -> String {
+    "usage".to_string()
+}
+
+fn default_usage_export_format() -> String {
+    "csv".to_string()
+}
+
+fn default_usage_export_hour_utc() -> u32 {
+    1
+}
+
+impl Default for UsageExportConfig {
+    fn default() -> Self {
+        Self {
+            s3_bucket: None,
+            s3_prefix: default_usage_export_s3_prefix(),
+            s3_region: None,
+            s3_endpoint_url: None,
+            format: default_usage_export_format(),
+            hour_utc: default_usage_export_hour_utc(),
+        }
+    }
+}
+
+/// Markup added to provider cost on team chargeback invoices
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BillingConfig {
+    /// Markup percentage applied to every team
+    #[serde(default)]
+    pub markup_percent: f64,
+    /// Markup percentage per team ID, replacing the default
+    #[serde(default)]
+    pub team_markup_percent: HashMap<String, f64>,
+    /// Markup percentage per model ID, taking precedence over team markups
+    #[serde(default)]
+    pub model_markup_percent: HashMap<String, f64>,
+}
+
+/// Operator notifications on budget alerts and provider outages
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotificationsConfig {
+    /// Consecutive provider errors after which an outage is reported
+    #[serde(default = "default_provider_failure_threshold")]
+    pub provider_failure_threshold: u32,
+    #[serde(default)]
+    pub slack: SlackNotificationConfig,
+    #[serde(default)]
+    pub pagerduty: PagerDutyNotificationConfig,
+    #[serde(default)]
+    pub email: EmailNotificationConfig,
+}
+
+fn default_provider_failure_threshold() -> u32 {
+    5
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            provider_failure_threshold: default_provider_failure_threshold(),
+            slack: SlackNotificationConfig::default(),
+            pagerduty: PagerDutyNotificationConfig::default(),
+            email: EmailNotificationConfig::default(),
+        }
+    }
+}
+
+/// Slack incoming webhook channel
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SlackNotificationConfig {
+    /// Incoming webhook URL; the channel is disabled when unset
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// Events delivered to the channel ("budget_alert", "provider_outage",
+    /// "provider_recovered"); empty delivers all of them
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Lowest budget alert threshold (percent) delivered to the channel
+    #[serde(default)]
+    pub min_budget_percent: u8,
+}
+
+/// PagerDuty Events API v2 channel
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PagerDutyNotificationConfig {
+    /// Integration key of the PagerDuty service; the channel is disabled when unset
+    #[serde(default)]
+    pub routing_key: Option<String>,
+    /// Events API endpoint, defaults to PagerDuty's
+    #[serde(default)]
+    pub events_url: Option<String>,
+    /// Events delivered to the channel; empty delivers all of them
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Lowest budget alert threshold (percent) delivered to the channel
+    #[serde(default)]
+    pub min_budget_percent: u8,
+}
+
+/// SMTP e-mail channel
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmailNotificationConfig {
+    /// SMTP relay host; the channel is disabled when unset
+    #[serde(default)]
+    pub smtp_host: Option<String>,
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+    #[serde(default)]
+    pub smtp_username: Option<String>,
+    #[serde(default)]
+    pub smtp_password: Option<String>,
+    /// Upgrade the connection with STARTTLS (disable only for local relays)
+    #[serde(default = "default_smtp_starttls")]
+    pub starttls: bool,
+    /// Sender address
+    #[serde(default = "default_notification_email_from")]
+    pub from: String,
+    /// Recipient addresses
+    #[serde(default)]
+    pub to: Vec<String>,
+    /// Events delivered to the channel; empty delivers all of them
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Lowest budget alert threshold (percent) delivered to the channel
+    #[serde(default)]
+    pub min_budget_percent: u8,
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+fn default_smtp_starttls() -> bool {
+    true
+}
+
+fn default_notification_email_from() -> String {
+    "llm-gateway@localhost".to_string()
+}
+
+impl Default for EmailNotificationConfig {
+    fn default() -> Self {
+        Self {
+            smtp_host: None,
+            smtp_port: default_smtp_port(),
+            smtp_username: None,
+            smtp_password: None,
+            starttls: default_smtp_starttls(),
+            from: default_notification_email_from(),
+            to: Vec::new(),
+            events: Vec::new(),
+            min_budget_percent: 0,
+        }
+    }
+}
+
+/// Background detection of API key and team usage spikes
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnomalyDetectionConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// How often usage is analyzed
+    #[serde(default = "default_anomaly_interval_secs")]
+    pub interval_secs: u64,
+    /// Length of the recent window compared against the baseline
+    #[serde(default = "default_anomaly_window_secs")]
+    pub window_secs: u64,
+    /// Hours before the window the baseline average is learned from
+    #[serde(default = "default_anomaly_baseline_hours")]
+    pub baseline_hours: u64,
+    /// Usage above the baseline times this factor is reported
+    #[serde(default = "default_anomaly_factor")]
+    pub factor: f64,
+    /// Request spikes below this many requests in the window are ignored
+    #[serde(default = "default_anomaly_min_requests")]
+    pub min_requests: u64,
+    /// Spend spikes below this amount (USD) in the window are ignored
+    #[serde(default = "default_anomaly_min_cost_usd")]
+    pub min_cost_usd: f64,
+    /// Seconds before the same key or team is reported again
+    #[serde(default = "default_anomaly_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+fn default_anomaly_interval_secs() -> u64 {
+    300
+}
+
+fn default_anomaly_window_secs() -> u64 {
+    3600
+}
+
+fn default_anomaly_baseline_hours() -> u64 {
+    168
+}
+
+fn default_anomaly_factor() -> f64 {
+    3.0
+}
+
+fn default_anomaly_min_requests() -> u64 {
+    100
+}
+
+fn default_anomaly_min_cost_usd() -> f64 {
+    5.0
+}
+
+fn default_anomaly_cooldown_secs() -> u64 {
+    3600
+}
+
+impl Default for AnomalyDetectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: default_anomaly_interval_secs(),
+            window_secs: default_anomaly_window_secs(),
+            baseline_hours: default_anomaly_baseline_hours(),
+            factor: default_anomaly_factor(),
+            min_requests: default_anomaly_min_requests(),
+            min_cost_usd: default_anomaly_min_cost_usd(),
+            cooldown_secs: default_anomaly_cooldown_secs(),
+        }
+    }
+}
+
+/// Daily reconciliation of gateway usage with provider usage APIs
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReconciliationConfig {
+    /// Hour of the day (UTC) the previous day is reconciled at
+    #[serde(default = "default_reconciliation_hour_utc")]
+    pub hour_utc: u32,
+    /// Token difference (percent) per model still counted as matched
+    #[serde(default = "default_reconciliation_tolerance_percent")]
+    pub tolerance_percent: f64,
+    /// OpenAI admin key for the organization usage API; enables OpenAI reconciliation
+    #[serde(default)]
+    pub openai_admin_key: Option<String>,
+    /// Anthropic admin key for the usage report API; enables Anthropic reconciliation
+    #[serde(default)]
+    pub anthropic_admin_key: Option<String>,
+}
+
+fn default_reconciliation_hour_utc() -> u32 {
+    6
+}
+
+fn default_reconciliation_tolerance_percent() -> f64 {
+    2.0
+}
+
+impl Default for ReconciliationConfig {
+    fn default() -> Self {
+        Self {
+            hour_utc: default_reconciliation_hour_utc(),
+            tolerance_percent: default_reconciliation_tolerance_percent(),
+            openai_admin_key: None,
+            anthropic_admin_key: None,
+        }
+    }
+}
+
+/// Streaming of gateway events to a message broker
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventsConfig {
+    /// Broker events are published to: "none", "kafka" or "nats"
+    #[serde(default = "default_events_backend")]
+    pub backend: String,
+    /// Kafka bootstrap servers ("host:port,...") or NATS server URL
+    #[serde(default)]
+    pub servers: Option<String>,
+    /// Events are published to `<topic_prefix>.<event_type>`
+    #[serde(default = "default_events_topic_prefix")]
+    pub topic_prefix: String,
+    /// Events published ("request_completed", "budget_exceeded",
+    /// "entity_changed", "webhook_failed"); empty publishes all of them
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Kafka delivery timeout in seconds
+    #[serde(default = "default_events_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_events_backend() -> String {
+    "none".to_string()
+}
+
+fn default_events_topic_prefix() -> String {
+    "llm_gateway".to_string()
+}
+
+fn default_events_timeout_secs() -> u64 {
+    5
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            backend: default_events_backend(),
+            servers: None,
+            topic_prefix: default_events_topic_prefix(),
+            events: Vec::new(),
+            timeout_secs: default_events_timeout_secs(),
+        }
+    }
+}
+
+/// Webhook delivery retries
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhooksConfig {
+    /// Seconds between checks for failed deliveries due for a retry; 0
+    /// disables automatic retries
+    #[serde(default = "default_webhook_retry_interval_secs")]
+    pub retry_interval_secs: u64,
+}
+
+fn default_webhook_retry_interval_secs() -> u64 {
+    30
+}
+
+impl Default for WebhooksConfig {
+    fn default() -> Self {
+        Self {
+            retry_interval_secs: default_webhook_retry_interval_secs(),
+        }
+    }
+}
+
+/// Dependency probes of the `/ready` endpoint
+#[derive(Debug, Clone, Deserialize)]
+pub struct HealthConfig {
+    /// Probe PostgreSQL and Redis (when `REDIS_URL` is set)
+    #[serde(default)]
+    pub probe_dependencies: bool,
+    /// Also list the models of every enabled OpenAI, Anthropic and Azure
+    /// OpenAI credential
+    #[serde(default)]
+    pub probe_providers: bool,
+    /// Timeout of each probe, in seconds
+    #[serde(default = "default_health_probe_timeout_secs")]
+    pub probe_timeout_secs: u64,
+}
+
+fn default_health_probe_timeout_secs() -> u64 {
+    5
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            probe_dependencies: false,
+            probe_providers: false,
+            probe_timeout_secs: default_health_probe_timeout_secs(),
+        }
+    }
+}
+
+/// Background evaluation of model SLOs
+#[derive(Debug, Clone, Deserialize)]
+pub struct SloConfig {
+    /// Seconds between evaluations of every enabled SLO; 0 disables
+    /// background evaluation
+    #[serde(default = "default_slo_evaluation_interval_secs")]
+    pub evaluation_interval_secs: u64,
+}
+
+fn default_slo_evaluation_interval_secs() -> u64 {
+    60
+}
+
+impl Default for SloConfig {
+    fn default() -> Self {
+        Self {
+            evaluation_interval_secs: default_slo_evaluation_interval_secs(),
+        }
+    }
+}
+
+/// Background sequential testing of experiments with auto-stop
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExperimentsConfig {
+    /// Seconds between checks of active experiments with auto-stop; 0
+    /// disables automatic completion
+    #[serde(default = "default_experiments_auto_stop_interval_secs")]
+    pub auto_stop_interval_secs: u64,
+}
+
+fn default_experiments_auto_stop_interval_secs() -> u64 {
+    60
+}
+
+impl Default for ExperimentsConfig {
+    fn default() -> Self {
+        Self {
+            auto_stop_interval_secs: default_experiments_auto_stop_interval_secs(),
+        }
+    }
+}
+
+/// Scheduled runs of canary test cases against live providers
+#[derive(Debug, Clone, Deserialize)]
+pub struct CanaryConfig {
+    /// Seconds between canary runs; 0 disables scheduled runs
+    #[serde(default = "default_canary_interval_secs")]
+    pub interval_secs: u64,
+    /// Consecutive failed runs after which a canary's model is degraded
+    #[serde(default = "default_canary_failure_threshold")]
+    pub failure_threshold: u32,
+}
+
+fn default_canary_interval_secs() -> u64 {
+    900
+}
+
+fn default_canary_failure_threshold() -> u32 {
+    2
+}
+
+impl Default for CanaryConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: default_canary_interval_secs(),
+            failure_threshold: default_canary_failure_threshold(),
+        }
+    }
+}
+
+/// Election of the replica that runs the scheduled jobs acting on shared
+/// state (webhook retries, experiment auto-stop, anomaly detection, daily
+/// reconciliation and export)
+#[derive(Debug, Clone, Deserialize)]
+pub struct LeaderElectionConfig {
+    /// Without election every replica runs the scheduled jobs
+    #[serde(default)]
+    pub enabled: bool,
+    /// Election backend: "postgres" (advisory lock) or "kubernetes" (Lease)
+    #[serde(default = "default_leader_election_backend")]
+    pub backend: String,
+    /// Key of the PostgreSQL advisory lock
+    #[serde(default = "default_leader_election_lock_id")]
+    pub lock_id: i64,
+    /// Name of the Kubernetes Lease
+    #[serde(default = "default_leader_election_lease_name")]
+    pub lease_name: String,
+    /// Namespace of the Lease, defaults to the pod's namespace
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// Seconds a Lease stays held without renewal
+    #[serde(default = "default_leader_election_lease_duration_secs")]
+    pub lease_duration_secs: u64,
+    /// Seconds between attempts to acquire or renew leadership
+    #[serde(default = "default_leader_election_renew_interval_secs")]
+    pub renew_interval_secs: u64,
+    /// Holder identity of this replica, defaults to `POD_NAME` or `HOSTNAME`
+    #[serde(default)]
+    pub identity: Option<String>,
+}
+
+fn default_leader_election_backend() -> String {
+    "postgres".to_string()
+}
+
+fn default_leader_election_lock_id() -> i64 {
+    // "llmgwldr"
+    0x6c6c_6d67_776c_6472
+}
+
+fn default_leader_election_lease_name() -> String {
+    "llm-gateway-leader".to_string()
+}
+
+fn default_leader_election_lease_duration_secs() -> u64 {
+    15
+}
+
+fn default_leader_election_renew_interval_secs() -> u64 {
+    5
+}
+
+impl Default for LeaderElectionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: default_leader_election_backend(),
+            lock_id: default_leader_election_lock_id(),
+            lease_name: default_leader_election_lease_name(),
+            namespace: None,
+            lease_duration_secs: default_leader_election_lease_duration_secs(),
+            renew_interval_secs: default_leader_election_renew_interval_secs(),
+            identity: None,
+        }
+    }
+}
+
+/// Inbound prompt-injection and jailbreak detection on chat completions
+#[derive(Debug, Clone, Deserialize)]
+pub struct InjectionGuardConfig {
+    /// Action for API keys without their own: off, flag, sanitize or block
+    #[serde(default)]
+    pub default_action: InjectionAction,
+    /// Score (0-1) at or above which messages count as an injection
+    #[serde(default = "default_injection_guard_threshold")]
+    pub threshold: f64,
+    /// Model asked to score messages alongside the heuristic rules
+    #[serde(default)]
+    pub classifier_model_id: Option<String>,
+}
+
+fn default_injection_guard_threshold() -> f64 {
+    0.5
+}
+
+impl Default for InjectionGuardConfig {
+    fn default() -> Self {
+        Self {
+            default_action: InjectionAction::default(),
+            threshold: default_injection_guard_threshold(),
+            classifier_model_id: None,
+        }
+    }
+}
+
+/// Content policy evaluation settings
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContentPolicyConfig {
+    /// OpenAI API key for the moderation API; enables `max_toxicity` rules
+    #[serde(default)]
+    pub moderation_api_key: Option<String>,
+    /// Moderation model scoring toxicity
+    #[serde(default = "default_moderation_model")]
+    pub moderation_model: String,
+}
+
+fn default_moderation_model() -> String {
+    "omni-moderation-latest".to_string()
+}
+
+impl Default for ContentPolicyConfig {
+    fn default() -> Self {
+        Self {
+            moderation_api_key: None,
+            moderation_model: default_moderation_model(),
+        }
+    }
+}
+
+/// Outbound secret leak scanning of completion answers
+#[derive(Debug, Clone, Deserialize)]
+pub struct SecretScannerConfig {
+    /// What to do with answers containing secrets: off, redact or block
+    #[serde(default)]
+    pub action: SecretLeakAction,
+    /// Also look for the values of the gateway's stored credentials
+    #[serde(default = "default_scan_stored_credentials")]
+    pub scan_stored_credentials: bool,
+}
+
+fn default_scan_stored_credentials() -> bool {
+    true
+}
+
+impl Default for SecretScannerConfig {
+    fn default() -> Self {
+        Self {
+            action: SecretLeakAction::default(),
+            scan_stored_credentials: default_scan_stored_credentials(),
+        }
+    }
+}
+
+/// Ceilings of the agent runs of assistants; requests may only lower them
+#[derive(Debug, Clone, Deserialize)]
+pub struct AgentConfig {
+    /// Model calls per run
+    #[serde(default = "default_agent_max_iterations")]
+    pub max_iterations: u32,
+    /// Seconds a tool workflow may run
+    #[serde(default = "default_agent_tool_timeout_secs")]
+    pub tool_timeout_secs: u64,
+    /// Cost per run in micro-dollars, unlimited if unset
+    #[serde(default)]
+    pub max_cost_micros: Option<i64>,
+}
+
+fn default_agent_max_iterations() -> u32 {
+    8
+}
+
+fn default_agent_tool_timeout_secs() -> u64 {
+    30
+}
+
+impl Default for AgentConfig {
+    fn default() -> Self {
+        Self {
+            max_iterations: default_agent_max_iterations(),
+            tool_timeout_secs: default_agent_tool_timeout_secs(),
+            max_cost_micros: None,
+        }
+    }
+}
+
+/// Tracking of provider fine-tuning jobs
+#[derive(Debug, Clone, Deserialize)]
+pub struct FineTunesConfig {
+    /// Seconds between status checks of running jobs; 0 disables polling,
+    /// leaving refreshes to the admin API
+    #[serde(default = "default_fine_tunes_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+}
+
+fn default_fine_tunes_poll_interval_secs() -> u64 {
+    60
+}
+
+impl Default for FineTunesConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval_secs: default_fine_tunes_poll_interval_secs(),
+        }
+    }
+}
+
+/// Liveness of async operations across replicas
+#[derive(Debug, Clone, Deserialize)]
+pub struct OperationsConfig {
+    /// Seconds between heartbeats of the running operations of a replica;
+    /// 0 disables heartbeats and the reaping of orphaned operations
+    #[serde(default = "default_operations_heartbeat_interval_secs")]
+    pub heartbeat_interval_secs: u64,
+    /// Seconds without heartbeat after which a running operation is orphaned
+    #[serde(default = "default_operations_lease_secs")]
+    pub lease_secs: u64,
+    /// Requeues of an orphaned operation before it is failed instead
+    #[serde(default = "default_operations_max_attempts")]
+    pub max_attempts: u32,
+}
+
+fn default_operations_heartbeat_interval_secs() -> u64 {
+    15
+}
+
+fn default_operations_lease_secs() -> u64 {
+    60
+}
+
+fn default_operations_max_attempts() -> u32 {
+    3
+}
+
+impl Default for OperationsConfig {
+    fn default() -> Self {
+        Self {
+            heartbeat_interval_secs: default_operations_heartbeat_interval_secs(),
+            lease_secs: default_operations_lease_secs(),
+            max_attempts: default_operations_max_attempts(),
+        }
+    }
+}
+
+/// Knowledge base document uploads
+#[derive(Debug, Clone, Deserialize)]
+pub struct UploadsConfig {
+    /// Largest accepted file, in bytes
+    #[serde(default = "default_uploads_max_file_bytes")]
+    pub max_file_bytes: u64,
+    /// Files accepted per batch upload
+    #[serde(default = "default_uploads_max_files")]
+    pub max_files: usize,
+    /// Directory uploads are spooled to until ingested, the system
+    /// temporary directory if unset
+    #[serde(default)]
+    pub temp_dir: Option<String>,
+}
+
+fn default_uploads_max_file_bytes() -> u64 {
+    50 * 1024 * 1024
+}
+
+fn default_uploads_max_files() -> usize {
+    100
+}
+
+impl Default for UploadsConfig {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: default_uploads_max_file_bytes(),
+            max_files: default_uploads_max_files(),
+            temp_dir: None,
+        }
+    }
+}
+
+/// Response caches of synchronous chat completions
+#[derive(Debug, Clone, Deserialize)]
+pub struct ResponseCacheConfig {
+    /// Cache of the requests whose model and prompt set no cache policy
+    #[serde(default = "default_cache_mode")]
+    pub default_mode: CacheMode,
+    /// Seconds responses are cached unless their policy sets a TTL
+    #[serde(default = "default_cache_ttl_secs")]
+    pub ttl_secs: u64,
+    /// Responses kept by the exact cache
+    #[serde(default = "default_cache_max_entries")]
+    pub max_entries: u64,
+    #[serde(default)]
+    pub semantic: SemanticResponseCacheConfig,
+}
+
+fn default_cache_mode() -> CacheMode {
+    CacheMode::Disabled
+}
+
+fn default_cache_ttl_secs() -> u64 {
+    3600
+}
+
+fn default_cache_max_entries() -> u64 {
+    10_000
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            default_mode: default_cache_mode(),
+            ttl_secs: default_cache_ttl_secs(),
+            max_entries: default_cache_max_entries(),
+            semantic: SemanticResponseCacheConfig::default(),
+        }
+    }
+}
+
+/// Semantic response cache, serving answers to similar questions
+#[derive(Debug, Clone, Deserialize)]
+pub struct SemanticResponseCacheConfig {
+    /// Embeds questions with the OpenAI embeddings API (`OPENAI_API_KEY`,
+    /// `OPENAI_BASE_URL`); semantic policies cache nothing when disabled
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_semantic_cache_embedding_model")]
+    pub embedding_model: String,
+    /// Cosine similarity a cached question needs to be served
+    #[serde(default = "default_semantic_cache_similarity_threshold")]
+    pub similarity_threshold: f32,
+    /// Entries kept before the oldest ones are evicted
+    #[serde(default = "default_semantic_cache_max_entries")]
+    pub max_entries: usize,
+    /// Store of the cached entries: "memory" (per replica) or "redis"
+    /// (RediSearch vector index shared by the replicas)
+    #[serde(default = "default_semantic_cache_backend")]
+    pub backend: String,
+    /// Redis of the "redis" backend; `REDIS_URL` when unset
+    #[serde(default)]
+    pub redis_url: Option<String>,
+    /// Prefix of the Redis keys and index name of the "redis" backend
+    #[serde(default = "default_semantic_cache_namespace")]
+    pub namespace: String,
+    /// Dimensions of the `embedding_model` embeddings, indexed by the
+    /// "redis" backend
+    #[serde(default = "default_semantic_cache_dimensions")]
+    pub dimensions: usize,
+}
+
+fn default_semantic_cache_embedding_model() -> String {
+    "text-embedding-3-small".to_string()
+}
+
+fn default_semantic_cache_similarity_threshold() -> f32 {
+    0.95
+}
+
+fn default_semantic_cache_max_entries() -> usize {
+    10_000
+}
+
+fn default_semantic_cache_backend() -> String {
+    "memory".to_string()
+}
+
+fn default_semantic_cache_namespace() -> String {
+    "semantic:llm".to_string()
+}
+
+fn default_semantic_cache_dimensions() -> usize {
+    1536
+}
+
+impl Default for SemanticResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            embedding_model: default_semantic_cache_embedding_model(),
+            similarity_threshold: default_semantic_cache_similarity_threshold(),
+            max_entries: default_semantic_cache_max_entries(),
+            backend: default_semantic_cache_backend(),
+            redis_url: None,
+            namespace: default_semantic_cache_namespace(),
+            dimensions: default_semantic_cache_dimensions(),
+        }
+    }
+}
+
+/// OCR of scanned PDFs and images during document ingestion
+#[derive(Debug, Clone, Deserialize)]
+pub struct OcrConfig {
+    /// Engine used when the ingestion request picks none: "tesseract" or
+    /// "vision_model"; without one only PDFs with a text layer are read
+    #[serde(default)]
+    pub engine: Option<String>,
+    /// Tesseract language codes, e.g. "eng+deu"
+    #[serde(default = "default_ocr_language")]
+    pub language: String,
+    /// Model transcribing pages for the "vision_model" engine
+    #[serde(default)]
+    pub model_id: Option<String>,
+    #[serde(default = "default_ocr_tesseract_path")]
+    pub tesseract_path: String,
+    #[serde(default = "default_ocr_pdftotext_path")]
+    pub pdftotext_path: String,
+    #[serde(default = "default_ocr_pdftoppm_path")]
+    pub pdftoppm_path: String,
+    /// Resolution scanned PDF pages are rendered at for OCR
+    #[serde(default = "default_ocr_dpi")]
+    pub dpi: u32,
+}
+
+fn default_ocr_language() -> String {
+    "eng".to_string()
+}
+
+fn default_ocr_tesseract_path() -> String {
+    "tesseract".to_string()
+}
+
+fn default_ocr_pdftotext_path() -> String {
+    "pdftotext".to_string()
+}
+
+fn default_ocr_pdftoppm_path() -> String {
+    "pdftoppm".to_string()
+}
+
+fn default_ocr_dpi() -> u32 {
+    300
+}
+
+impl Default for OcrConfig {
+    fn default() -> Self {
+        Self {
+            engine: None,
+            language: default_ocr_language(),
+            model_id: None,
+            tesseract_path: default_ocr_tesseract_path(),
+            pdftotext_path: default_ocr_pdftotext_path(),
+            pdftoppm_path: default_ocr_pdftoppm_path(),
+            dpi: default_ocr_dpi(),
+        }
+    }
 }
 
 /// Storage backend configuration
@@ -39,6 +1162,116 @@ impl Default for StorageConfig {
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
+    /// Proxies (CIDR or bare IP) whose forwarded client IP headers are trusted
+    #[serde(default)]
+    pub trusted_proxies: Vec<String>,
+    /// Headers carrying the original client IP, in order of preference
+    #[serde(default = "default_client_ip_headers")]
+    pub client_ip_headers: Vec<String>,
+    #[serde(default)]
+    pub tls: TlsConfig,
+    /// Seconds to wait on shutdown for in-flight requests (streams included)
+    /// and background writes such as usage records and webhook deliveries
+    #[serde(default = "default_shutdown_timeout_secs")]
+    pub shutdown_timeout_secs: u64,
+    /// Listeners replacing `host`/`port`, each serving part of the routes
+    #[serde(default)]
+    pub listeners: Vec<ListenerConfig>,
+}
+
+fn default_shutdown_timeout_secs() -> u64 {
+    25
+}
+
+fn default_client_ip_headers() -> Vec<String> {
+    vec!["x-forwarded-for".to_string(), "x-real-ip".to_string()]
+}
+
+/// Address the server accepts connections on
+#[derive(Debug, Clone, Deserialize)]
+pub struct ListenerConfig {
+    /// `host:port`, or `unix:<path>` for a Unix domain socket
+    pub bind: String,
+    /// Routes served on this listener
+    #[serde(default)]
+    pub surface: ListenerSurface,
+}
+
+/// Routes served by a listener
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ListenerSurface {
+    /// Every route
+    #[default]
+    All,
+    /// Client traffic: the OpenAI-compatible `/v1` API and health checks
+    Public,
+    /// Operators: admin API, authentication, UI, OpenAPI, metrics and
+    /// health checks
+    Admin,
+}
+
+impl ListenerSurface {
+    pub fn serves_public(&self) -> bool {
+        matches!(self, Self::All | Self::Public)
+    }
+
+    pub fn serves_admin(&self) -> bool {
+        matches!(self, Self::All | Self::Admin)
+    }
+}
+
+/// TLS termination configuration for the server listener
+#[derive(Debug, Clone, Deserialize)]
+pub struct TlsConfig {
+    /// Serve HTTPS instead of plain HTTP
+    #[serde(default)]
+    pub enabled: bool,
+    /// PEM certificate chain
+    #[serde(default)]
+    pub cert_path: Option<String>,
+    /// PEM private key
+    #[serde(default)]
+    pub key_path: Option<String>,
+    /// Seconds between checks for rotated certificate files (0 disables reloading)
+    #[serde(default = "default_tls_reload_interval_secs")]
+    pub reload_interval_secs: u64,
+    /// Client certificate verification mode (mTLS)
+    #[serde(default)]
+    pub client_auth: ClientAuthMode,
+    /// PEM bundle of CAs trusted to sign client certificates
+    #[serde(default)]
+    pub client_ca_path: Option<String>,
+}
+
+fn default_tls_reload_interval_secs() -> u64 {
+    60
+}
+
+impl Default for TlsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cert_path: None,
+            key_path: None,
+            reload_interval_secs: default_tls_reload_interval_secs(),
+            client_auth: ClientAuthMode::default(),
+            client_ca_path: None,
+        }
+    }
+}
+
+/// Client certificate verification mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ClientAuthMode {
+    /// Client certificates are not requested
+    #[default]
+    None,
+    /// Client certificates are verified when presented
+    Optional,
+    /// Connections without a valid client certificate are rejected
+    Required,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -87,6 +1320,30 @@ impl Default for AppConfig {
             auth: AuthConfig::default(),
             observability: ObservabilityConfig::default(),
             storage: StorageConfig::default(),
+            audit: AuditConfig::default(),
+            security: SecurityConfig::default(),
+            pricing: PricingConfig::default(),
+            usage_export: UsageExportConfig::default(),
+            billing: BillingConfig::default(),
+            notifications: NotificationsConfig::default(),
+            anomaly_detection: AnomalyDetectionConfig::default(),
+            reconciliation: ReconciliationConfig::default(),
+            events: EventsConfig::default(),
+            webhooks: WebhooksConfig::default(),
+            health: HealthConfig::default(),
+            slo: SloConfig::default(),
+            experiments: ExperimentsConfig::default(),
+            canary: CanaryConfig::default(),
+            injection_guard: InjectionGuardConfig::default(),
+            content_policy: ContentPolicyConfig::default(),
+            secret_scanner: SecretScannerConfig::default(),
+            leader_election: LeaderElectionConfig::default(),
+            agent: AgentConfig::default(),
+            fine_tunes: FineTunesConfig::default(),
+            uploads: UploadsConfig::default(),
+            ocr: OcrConfig::default(),
+            operations: OperationsConfig::default(),
+            cache: ResponseCacheConfig::default(),
         }
     }
 }
@@ -96,6 +1353,11 @@ impl Default for ServerConfig {
         Self {
             host: "0.0.0.0".to_string(),
             port: 8080,
+            trusted_proxies: Vec::new(),
+            client_ip_headers: default_client_ip_headers(),
+            tls: TlsConfig::default(),
+            shutdown_timeout_secs: default_shutdown_timeout_secs(),
+            listeners: Vec::new(),
         }
     }
 }
@@ -117,7 +1379,19 @@ impl AppConfig {
             .add_source(
                 config::Environment::with_prefix("APP")
                     .separator("__")
-                    .try_parsing(true),
+                    .try_parsing(true)
+                    .list_separator(",")
+                    .with_list_parse_key("server.trusted_proxies")
+                    .with_list_parse_key("server.client_ip_headers")
+                    .with_list_parse_key("security.cors.allowed_origins")
+                    .with_list_parse_key("security.cors.allowed_methods")
+                    .with_list_parse_key("security.cors.allowed_headers")
+                    .with_list_parse_key("security.cors.exposed_headers")
+                    .with_list_parse_key("pricing.sync_models")
+                    .with_list_parse_key("notifications.slack.events")
+                    .with_list_parse_key("notifications.pagerduty.events")
+                    .with_list_parse_key("notifications.email.events")
+                    .with_list_parse_key("notifications.email.to"),
             )
             .build()?;
 
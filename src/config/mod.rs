@@ -2,4 +2,9 @@
 
 mod app_config;
 
-pub use app_config::{AppConfig, LogFormat};
+pub use app_config::{
+    AgentConfig, AnomalyDetectionConfig, AppConfig, BillingConfig, CanaryConfig, ClientAuthMode, ContentPolicyConfig, CorsConfig, CspConfig, EmailNotificationConfig,
+    EventsConfig, ExperimentsConfig, FineTunesConfig, HealthConfig, InjectionGuardConfig, LeaderElectionConfig, ListenerConfig, ListenerSurface, LogFormat, NotificationsConfig, OperationsConfig, PagerDutyNotificationConfig, PricingConfig,
+    ReconciliationConfig, ResponseCacheConfig, SecretScannerConfig, SemanticResponseCacheConfig, ServerConfig, SlackNotificationConfig, SloConfig, TlsConfig, UsageExportConfig,
+    WebhooksConfig,
+};
@@ -0,0 +1,143 @@
+//! Decisions the model takes at each turn of an agent run
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::domain::DomainError;
+use crate::domain::llm::{LlmJsonSchema, LlmResponseFormat};
+
+/// Tool offered to the model, a workflow of the assistant
+#[derive(Debug, Clone, PartialEq)]
+pub struct AgentToolSpec {
+    /// Workflow ID, the name the model calls the tool by
+    pub name: String,
+    pub description: Option<String>,
+    /// Input schema of the workflow, if it declares one
+    pub input_schema: Option<Value>,
+}
+
+/// What the model decided to do at one turn
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum AgentDecision {
+    /// Run a tool and send its output back
+    ToolCall {
+        tool: String,
+        #[serde(default)]
+        input: Value,
+    },
+    /// Final answer of the run
+    Answer { content: String },
+}
+
+impl AgentDecision {
+    /// Parse the answer of the model, tolerating a Markdown code fence around
+    /// the JSON object
+    pub fn parse(text: &str) -> Result<Self, DomainError> {
+        let trimmed = text.trim();
+        let json = trimmed
+            .strip_prefix("```json")
+            .or_else(|| trimmed.strip_prefix("```"))
+            .and_then(|rest| rest.strip_suffix("```"))
+            .unwrap_or(trimmed);
+
+        serde_json::from_str(json.trim())
+            .map_err(|e| DomainError::validation(format!("Invalid agent decision: {}", e)))
+    }
+}
+
+/// Structured output format of the decisions, restricting tool names to the
+/// offered tools
+pub fn decision_response_format(tools: &[AgentToolSpec]) -> LlmResponseFormat {
+    let names: Vec<&str> = tools.iter().map(|tool| tool.name.as_str()).collect();
+
+    LlmResponseFormat::JsonSchema {
+        json_schema: LlmJsonSchema {
+            name: "agent_decision".to_string(),
+            strict: false,
+            schema: json!({
+                "type": "object",
+                "properties": {
+                    "action": {"type": "string", "enum": ["tool_call", "answer"]},
+                    "tool": {"type": "string", "enum": names},
+                    "input": {"type": "object"},
+                    "content": {"type": "string"}
+                },
+                "required": ["action"]
+            }),
+        },
+    }
+}
+
+/// Instructions appended to the system prompt, describing the decision
+/// format and the tools
+pub fn tool_instructions(tools: &[AgentToolSpec]) -> String {
+    let mut instructions = String::from(
+        "You can call tools to gather information before answering. \
+         Reply with a single JSON object and nothing else:\n\
+         - {\"action\": \"tool_call\", \"tool\": \"<tool name>\", \"input\": {...}} to call a tool; its output is sent back to you\n\
+         - {\"action\": \"answer\", \"content\": \"<answer>\"} to give the final answer\n\nTools:",
+    );
+
+    for tool in tools {
+        instructions.push_str(&format!("\n- {}", tool.name));
+        if let Some(description) = &tool.description {
+            instructions.push_str(&format!(": {}", description));
+        }
+        if let Some(schema) = &tool.input_schema {
+            instructions.push_str(&format!("\n  input schema: {}", schema));
+        }
+    }
+
+    instructions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_decisions() {
+        assert_eq!(
+            AgentDecision::parse(
+                r#"{"action": "tool_call", "tool": "lookup-order", "input": {"id": 42}}"#
+            )
+            .unwrap(),
+            AgentDecision::ToolCall {
+                tool: "lookup-order".to_string(),
+                input: json!({"id": 42}),
+            }
+        );
+        assert_eq!(
+            AgentDecision::parse(
+                "```json\n{\"action\": \"answer\", \"content\": \"Shipped\"}\n```"
+            )
+            .unwrap(),
+            AgentDecision::Answer {
+                content: "Shipped".to_string()
+            }
+        );
+        assert!(AgentDecision::parse("Your order has shipped").is_err());
+    }
+
+    #[test]
+    fn test_tool_instructions() {
+        let tools = vec![AgentToolSpec {
+            name: "lookup-order".to_string(),
+            description: Some("Find an order by ID".to_string()),
+            input_schema: Some(json!({"type": "object"})),
+        }];
+
+        let instructions = tool_instructions(&tools);
+        assert!(instructions.contains("- lookup-order: Find an order by ID"));
+        assert!(instructions.contains("input schema: {\"type\":\"object\"}"));
+
+        let LlmResponseFormat::JsonSchema { json_schema } = decision_response_format(&tools) else {
+            panic!("expected a JSON schema format");
+        };
+        assert_eq!(
+            json_schema.schema["properties"]["tool"]["enum"],
+            json!(["lookup-order"])
+        );
+    }
+}
@@ -0,0 +1,16 @@
+//! Agent domain
+//!
+//! An agent run is the model→tool→model loop of an assistant, executed by the
+//! gateway instead of the client. Providers are not asked for native function
+//! calls: each turn the model answers with a JSON decision, either a call of
+//! one of the assistant's tool workflows or the final answer, and the output
+//! of the tool is sent back to it as the next message.
+
+mod decision;
+mod runtime;
+
+pub use decision::{AgentDecision, AgentToolSpec, decision_response_format, tool_instructions};
+pub use runtime::{
+    AgentEvent, AgentLimits, AgentModel, AgentModelTurn, AgentOutcome, AgentRuntime,
+    AgentStopReason, AgentToolOutput, AgentTools,
+};
@@ -0,0 +1,488 @@
+//! Bounded model→tool→model loop
+
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::mpsc;
+use utoipa::ToSchema;
+
+use super::decision::AgentDecision;
+use crate::domain::DomainError;
+use crate::domain::llm::Message;
+
+/// Bounds of an agent run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AgentLimits {
+    /// Model calls after which the run stops without an answer
+    pub max_iterations: u32,
+    /// Time a tool may run before it counts as failed
+    pub tool_timeout: Duration,
+    /// Cost in micro-dollars after which no further model call is made
+    pub max_cost_micros: Option<i64>,
+}
+
+impl Default for AgentLimits {
+    fn default() -> Self {
+        Self {
+            max_iterations: 8,
+            tool_timeout: Duration::from_secs(30),
+            max_cost_micros: None,
+        }
+    }
+}
+
+impl AgentLimits {
+    /// Limits requested for one run, never above these ones
+    pub fn capped(
+        &self,
+        max_iterations: Option<u32>,
+        tool_timeout_secs: Option<u64>,
+        max_cost_micros: Option<i64>,
+    ) -> Self {
+        let max_cost_micros = match (self.max_cost_micros, max_cost_micros) {
+            (Some(ceiling), Some(requested)) => Some(ceiling.min(requested)),
+            (ceiling, requested) => ceiling.or(requested),
+        };
+
+        Self {
+            max_iterations: max_iterations
+                .map_or(self.max_iterations, |n| n.clamp(1, self.max_iterations)),
+            tool_timeout: tool_timeout_secs
+                .map(Duration::from_secs)
+                .map_or(self.tool_timeout, |t| t.min(self.tool_timeout)),
+            max_cost_micros,
+        }
+    }
+}
+
+/// Answer of one model call
+#[derive(Debug, Clone)]
+pub struct AgentModelTurn {
+    pub content: String,
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    pub cost_micros: i64,
+}
+
+/// Output of one tool call
+#[derive(Debug, Clone)]
+pub struct AgentToolOutput {
+    pub output: Value,
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    pub cost_micros: i64,
+}
+
+/// Model driving the run
+#[async_trait]
+pub trait AgentModel: Send + Sync {
+    async fn complete(&self, messages: Vec<Message>) -> Result<AgentModelTurn, DomainError>;
+}
+
+/// Tools the model may call
+#[async_trait]
+pub trait AgentTools: Send + Sync {
+    async fn call(&self, tool: &str, input: Value) -> Result<AgentToolOutput, DomainError>;
+}
+
+/// Step of a run, streamed to the client as it happens
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AgentEvent {
+    ToolCall {
+        iteration: u32,
+        tool: String,
+        input: Value,
+    },
+    ToolResult {
+        iteration: u32,
+        tool: String,
+        output: Value,
+        duration_ms: u64,
+    },
+    ToolError {
+        iteration: u32,
+        tool: String,
+        error: String,
+    },
+    Answer {
+        content: String,
+    },
+}
+
+/// Why a run stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentStopReason {
+    Answered,
+    MaxIterations,
+    CostLimit,
+}
+
+/// Result of a run
+#[derive(Debug, Clone)]
+pub struct AgentOutcome {
+    pub answer: Option<String>,
+    pub stop_reason: AgentStopReason,
+    /// Model calls made
+    pub iterations: u32,
+    pub input_tokens: u32,
+    pub output_tokens: u32,
+    pub cost_micros: i64,
+    pub events: Vec<AgentEvent>,
+}
+
+/// Runs the loop of an agent within its limits
+#[derive(Debug, Clone)]
+pub struct AgentRuntime {
+    limits: AgentLimits,
+}
+
+impl AgentRuntime {
+    pub fn new(limits: AgentLimits) -> Self {
+        Self { limits }
+    }
+
+    pub fn limits(&self) -> &AgentLimits {
+        &self.limits
+    }
+
+    /// Run the loop from `messages`, sending every event to `events` as it
+    /// happens. Answers that are not a decision are taken as the final
+    /// answer; failed and timed out tool calls are reported to the model,
+    /// which may retry or answer without them. A failed model call ends the
+    /// run with its error.
+    pub async fn run(
+        &self,
+        mut messages: Vec<Message>,
+        model: &dyn AgentModel,
+        tools: &dyn AgentTools,
+        events: Option<&mpsc::Sender<AgentEvent>>,
+    ) -> Result<AgentOutcome, DomainError> {
+        let mut outcome = AgentOutcome {
+            answer: None,
+            stop_reason: AgentStopReason::MaxIterations,
+            iterations: 0,
+            input_tokens: 0,
+            output_tokens: 0,
+            cost_micros: 0,
+            events: Vec::new(),
+        };
+
+        while outcome.iterations < self.limits.max_iterations {
+            if self
+                .limits
+                .max_cost_micros
+                .is_some_and(|max| outcome.cost_micros >= max)
+            {
+                outcome.stop_reason = AgentStopReason::CostLimit;
+                return Ok(outcome);
+            }
+
+            outcome.iterations += 1;
+            let iteration = outcome.iterations;
+
+            let turn = model.complete(messages.clone()).await?;
+            outcome.input_tokens += turn.input_tokens;
+            outcome.output_tokens += turn.output_tokens;
+            outcome.cost_micros += turn.cost_micros;
+
+            let (tool, input) = match AgentDecision::parse(&turn.content) {
+                Ok(AgentDecision::ToolCall { tool, input }) => (tool, input),
+                Ok(AgentDecision::Answer { content }) => {
+                    return Ok(answer(outcome, content, events).await);
+                }
+                Err(_) => return Ok(answer(outcome, turn.content, events).await),
+            };
+
+            messages.push(Message::assistant(turn.content));
+            emit(
+                &mut outcome,
+                events,
+                AgentEvent::ToolCall {
+                    iteration,
+                    tool: tool.clone(),
+                    input: input.clone(),
+                },
+            )
+            .await;
+
+            let start = Instant::now();
+            let result = tokio::time::timeout(self.limits.tool_timeout, tools.call(&tool, input))
+                .await
+                .unwrap_or_else(|_| {
+                    Err(DomainError::internal(format!(
+                        "Tool timed out after {}ms",
+                        self.limits.tool_timeout.as_millis()
+                    )))
+                });
+
+            let (message, event) = match result {
+                Ok(output) => {
+                    outcome.input_tokens += output.input_tokens;
+                    outcome.output_tokens += output.output_tokens;
+                    outcome.cost_micros += output.cost_micros;
+
+                    (
+                        format!("Output of tool '{}':\n{}", tool, output.output),
+                        AgentEvent::ToolResult {
+                            iteration,
+                            tool,
+                            output: output.output,
+                            duration_ms: start.elapsed().as_millis() as u64,
+                        },
+                    )
+                }
+                Err(e) => (
+                    format!("Tool '{}' failed: {}", tool, e),
+                    AgentEvent::ToolError {
+                        iteration,
+                        tool,
+                        error: e.to_string(),
+                    },
+                ),
+            };
+
+            messages.push(Message::user(message));
+            emit(&mut outcome, events, event).await;
+        }
+
+        Ok(outcome)
+    }
+}
+
+async fn answer(
+    mut outcome: AgentOutcome,
+    content: String,
+    events: Option<&mpsc::Sender<AgentEvent>>,
+) -> AgentOutcome {
+    emit(
+        &mut outcome,
+        events,
+        AgentEvent::Answer {
+            content: content.clone(),
+        },
+    )
+    .await;
+    outcome.answer = Some(content);
+    outcome.stop_reason = AgentStopReason::Answered;
+    outcome
+}
+
+/// Record an event, sending it to a listener still receiving them
+async fn emit(
+    outcome: &mut AgentOutcome,
+    events: Option<&mpsc::Sender<AgentEvent>>,
+    event: AgentEvent,
+) {
+    if let Some(events) = events {
+        let _ = events.send(event.clone()).await;
+    }
+    outcome.events.push(event);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    use serde_json::json;
+
+    use super::*;
+
+    /// Model replaying scripted answers
+    struct ScriptedModel {
+        answers: Mutex<VecDeque<&'static str>>,
+        cost_micros: i64,
+    }
+
+    impl ScriptedModel {
+        fn new(answers: &[&'static str], cost_micros: i64) -> Self {
+            Self {
+                answers: Mutex::new(answers.iter().copied().collect()),
+                cost_micros,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl AgentModel for ScriptedModel {
+        async fn complete(&self, messages: Vec<Message>) -> Result<AgentModelTurn, DomainError> {
+            let content = self.answers.lock().unwrap().pop_front().ok_or_else(|| {
+                DomainError::internal(format!("unexpected call with {} messages", messages.len()))
+            })?;
+
+            Ok(AgentModelTurn {
+                content: content.to_string(),
+                input_tokens: 10,
+                output_tokens: 5,
+                cost_micros: self.cost_micros,
+            })
+        }
+    }
+
+    struct OrderTools;
+
+    #[async_trait]
+    impl AgentTools for OrderTools {
+        async fn call(&self, tool: &str, input: Value) -> Result<AgentToolOutput, DomainError> {
+            match tool {
+                "lookup-order" => Ok(AgentToolOutput {
+                    output: json!({"id": input["id"], "status": "shipped"}),
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    cost_micros: 100,
+                }),
+                "slow" => {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    Ok(AgentToolOutput {
+                        output: Value::Null,
+                        input_tokens: 0,
+                        output_tokens: 0,
+                        cost_micros: 0,
+                    })
+                }
+                _ => Err(DomainError::not_found(format!("Tool '{}' not found", tool))),
+            }
+        }
+    }
+
+    const LOOKUP: &str = r#"{"action": "tool_call", "tool": "lookup-order", "input": {"id": 42}}"#;
+
+    #[tokio::test]
+    async fn test_run_calls_tools_until_answer() {
+        let model = ScriptedModel::new(
+            &[
+                LOOKUP,
+                r#"{"action": "tool_call", "tool": "refund"}"#,
+                r#"{"action": "answer", "content": "Order 42 has shipped"}"#,
+            ],
+            1_000,
+        );
+        let (sender, mut receiver) = mpsc::channel(16);
+
+        let outcome = AgentRuntime::new(AgentLimits::default())
+            .run(
+                vec![Message::user("Where is order 42?")],
+                &model,
+                &OrderTools,
+                Some(&sender),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.stop_reason, AgentStopReason::Answered);
+        assert_eq!(outcome.answer.as_deref(), Some("Order 42 has shipped"));
+        assert_eq!(outcome.iterations, 3);
+        assert_eq!(outcome.input_tokens, 30);
+        assert_eq!(outcome.cost_micros, 3_100);
+        assert!(matches!(
+            &outcome.events[1],
+            AgentEvent::ToolResult { output, .. } if output["status"] == "shipped"
+        ));
+        assert!(matches!(
+            &outcome.events[3],
+            AgentEvent::ToolError { tool, .. } if tool == "refund"
+        ));
+
+        drop(sender);
+        let mut streamed = Vec::new();
+        while let Some(event) = receiver.recv().await {
+            streamed.push(event);
+        }
+        assert_eq!(streamed, outcome.events);
+    }
+
+    #[tokio::test]
+    async fn test_run_takes_plain_text_as_answer() {
+        let model = ScriptedModel::new(&["Order 42 has shipped"], 0);
+
+        let outcome = AgentRuntime::new(AgentLimits::default())
+            .run(
+                vec![Message::user("Where is order 42?")],
+                &model,
+                &OrderTools,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.stop_reason, AgentStopReason::Answered);
+        assert_eq!(outcome.answer.as_deref(), Some("Order 42 has shipped"));
+    }
+
+    #[tokio::test]
+    async fn test_run_stops_at_limits() {
+        let limits = AgentLimits {
+            max_iterations: 2,
+            ..AgentLimits::default()
+        };
+        let model = ScriptedModel::new(&[LOOKUP, LOOKUP], 0);
+
+        let outcome = AgentRuntime::new(limits)
+            .run(vec![Message::user("Loop")], &model, &OrderTools, None)
+            .await
+            .unwrap();
+        assert_eq!(outcome.stop_reason, AgentStopReason::MaxIterations);
+        assert!(outcome.answer.is_none());
+
+        let limits = AgentLimits {
+            max_cost_micros: Some(1_500),
+            ..AgentLimits::default()
+        };
+        let model = ScriptedModel::new(&[LOOKUP, LOOKUP], 1_000);
+
+        let outcome = AgentRuntime::new(limits)
+            .run(vec![Message::user("Loop")], &model, &OrderTools, None)
+            .await
+            .unwrap();
+        assert_eq!(outcome.stop_reason, AgentStopReason::CostLimit);
+        assert_eq!(outcome.iterations, 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_times_out_tools() {
+        let limits = AgentLimits {
+            tool_timeout: Duration::from_millis(20),
+            ..AgentLimits::default()
+        };
+        let model = ScriptedModel::new(
+            &[
+                r#"{"action": "tool_call", "tool": "slow"}"#,
+                r#"{"action": "answer", "content": "Unavailable"}"#,
+            ],
+            0,
+        );
+
+        let outcome = AgentRuntime::new(limits)
+            .run(vec![Message::user("Hi")], &model, &OrderTools, None)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            &outcome.events[1],
+            AgentEvent::ToolError { error, .. } if error.contains("timed out after 20ms")
+        ));
+        assert_eq!(outcome.answer.as_deref(), Some("Unavailable"));
+    }
+
+    #[test]
+    fn test_capped_limits() {
+        let ceiling = AgentLimits {
+            max_iterations: 8,
+            tool_timeout: Duration::from_secs(30),
+            max_cost_micros: Some(50_000),
+        };
+
+        let limits = ceiling.capped(Some(20), Some(5), Some(100_000));
+        assert_eq!(limits.max_iterations, 8);
+        assert_eq!(limits.tool_timeout, Duration::from_secs(5));
+        assert_eq!(limits.max_cost_micros, Some(50_000));
+
+        let limits = AgentLimits::default().capped(Some(0), None, Some(10_000));
+        assert_eq!(limits.max_iterations, 1);
+        assert_eq!(limits.max_cost_micros, Some(10_000));
+    }
+}
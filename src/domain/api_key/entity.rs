@@ -5,7 +5,10 @@ use std::collections::HashSet;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::parameter_policy::ParameterPolicy;
 use super::validation::{validate_api_key_id, ApiKeyValidationError};
+use crate::domain::guardrail::InjectionAction;
+use crate::domain::network::IpNetwork;
 use crate::domain::storage::{StorageEntity, StorageKey};
 use crate::domain::team::TeamId;
 
@@ -151,6 +154,9 @@ pub struct ApiKeyPermissions {
     /// Whether admin operations are allowed
     #[serde(default)]
     pub admin: bool,
+    /// Limits on chat completion parameters
+    #[serde(default, skip_serializing_if = "ParameterPolicy::is_empty")]
+    pub parameters: ParameterPolicy,
 }
 
 impl ApiKeyPermissions {
@@ -167,6 +173,7 @@ impl ApiKeyPermissions {
             prompts: ResourcePermission::All,
             chains: ResourcePermission::All,
             admin: true,
+            parameters: ParameterPolicy::default(),
         }
     }
 
@@ -178,6 +185,7 @@ impl ApiKeyPermissions {
             prompts: ResourcePermission::All,
             chains: ResourcePermission::All,
             admin: false,
+            parameters: ParameterPolicy::default(),
         }
     }
 
@@ -211,6 +219,12 @@ impl ApiKeyPermissions {
         self
     }
 
+    /// Set chat completion parameter limits
+    pub fn with_parameters(mut self, parameters: ParameterPolicy) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
     /// Check if model access is allowed
     pub fn can_access_model(&self, model_id: &str) -> bool {
         self.models.allows(model_id)
@@ -247,6 +261,12 @@ pub struct RateLimitConfig {
     /// Maximum tokens per minute (for LLM requests)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tokens_per_minute: Option<u32>,
+    /// Maximum tokens per day (for LLM requests)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens_per_day: Option<u64>,
+    /// Maximum cost per 30 days in micro-dollars
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_per_month_micros: Option<i64>,
 }
 
 impl Default for RateLimitConfig {
@@ -257,6 +277,8 @@ impl Default for RateLimitConfig {
             requests_per_hour: 5000,
             requests_per_day: 50000,
             tokens_per_minute: None,
+            tokens_per_day: None,
+            cost_per_month_micros: None,
         }
     }
 }
@@ -270,6 +292,8 @@ impl RateLimitConfig {
             requests_per_hour: per_hour,
             requests_per_day: per_day,
             tokens_per_minute: None,
+            tokens_per_day: None,
+            cost_per_month_micros: None,
         }
     }
 
@@ -291,6 +315,18 @@ impl RateLimitConfig {
         self
     }
 
+    /// Set daily token quota
+    pub fn with_tokens_per_day(mut self, tokens: u64) -> Self {
+        self.tokens_per_day = Some(tokens);
+        self
+    }
+
+    /// Set monthly cost quota in USD
+    pub fn with_cost_per_month(mut self, usd: f64) -> Self {
+        self.cost_per_month_micros = Some((usd * 1_000_000.0) as i64);
+        self
+    }
+
     /// Create unlimited rate limits (effectively disabled)
     pub fn unlimited() -> Self {
         Self {
@@ -299,6 +335,8 @@ impl RateLimitConfig {
             requests_per_hour: u32::MAX,
             requests_per_day: u32::MAX,
             tokens_per_minute: None,
+            tokens_per_day: None,
+            cost_per_month_micros: None,
         }
     }
 
@@ -332,12 +370,27 @@ pub struct ApiKey {
     permissions: ApiKeyPermissions,
     /// Rate limit configuration
     rate_limits: RateLimitConfig,
+    /// Client networks allowed to use this key (empty = any)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    allowed_cidrs: Vec<IpNetwork>,
+    /// Action on prompt-injection detections (None = gateway default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    injection_action: Option<InjectionAction>,
+    /// Zero-retention mode: request and completion bodies are never stored
+    #[serde(default)]
+    no_log: bool,
     /// Expiration timestamp (None = never expires)
     #[serde(skip_serializing_if = "Option::is_none")]
     expires_at: Option<DateTime<Utc>>,
     /// Last time the key was used
     #[serde(skip_serializing_if = "Option::is_none")]
     last_used_at: Option<DateTime<Utc>>,
+    /// Client IP address of the last use
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_used_ip: Option<String>,
+    /// Key that replaced this one through rotation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    replaced_by: Option<ApiKeyId>,
     /// Creation timestamp
     created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -368,8 +421,13 @@ impl ApiKey {
             team_id,
             permissions: ApiKeyPermissions::default(),
             rate_limits: RateLimitConfig::default(),
+            allowed_cidrs: Vec::new(),
+            injection_action: None,
+            no_log: false,
             expires_at: None,
             last_used_at: None,
+            last_used_ip: None,
+            replaced_by: None,
             created_at: now,
             updated_at: now,
             created_by: None,
@@ -394,6 +452,24 @@ impl ApiKey {
         self
     }
 
+    /// Set allowed client networks
+    pub fn with_allowed_cidrs(mut self, allowed_cidrs: Vec<IpNetwork>) -> Self {
+        self.allowed_cidrs = allowed_cidrs;
+        self
+    }
+
+    /// Set the action on prompt-injection detections
+    pub fn with_injection_action(mut self, injection_action: Option<InjectionAction>) -> Self {
+        self.injection_action = injection_action;
+        self
+    }
+
+    /// Set zero-retention mode
+    pub fn with_no_log(mut self, no_log: bool) -> Self {
+        self.no_log = no_log;
+        self
+    }
+
     /// Set expiration
     pub fn with_expiration(mut self, expires_at: DateTime<Utc>) -> Self {
         self.expires_at = Some(expires_at);
@@ -440,6 +516,18 @@ impl ApiKey {
         &self.rate_limits
     }
 
+    pub fn allowed_cidrs(&self) -> &[IpNetwork] {
+        &self.allowed_cidrs
+    }
+
+    pub fn injection_action(&self) -> Option<InjectionAction> {
+        self.injection_action
+    }
+
+    pub fn no_log(&self) -> bool {
+        self.no_log
+    }
+
     pub fn expires_at(&self) -> Option<DateTime<Utc>> {
         self.expires_at
     }
@@ -448,6 +536,14 @@ impl ApiKey {
         self.last_used_at
     }
 
+    pub fn last_used_ip(&self) -> Option<&str> {
+        self.last_used_ip.as_deref()
+    }
+
+    pub fn replaced_by(&self) -> Option<&ApiKeyId> {
+        self.replaced_by.as_ref()
+    }
+
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
     }
@@ -522,6 +618,24 @@ impl ApiKey {
         self.touch();
     }
 
+    /// Update allowed client networks
+    pub fn set_allowed_cidrs(&mut self, allowed_cidrs: Vec<IpNetwork>) {
+        self.allowed_cidrs = allowed_cidrs;
+        self.touch();
+    }
+
+    /// Update the action on prompt-injection detections
+    pub fn set_injection_action(&mut self, injection_action: Option<InjectionAction>) {
+        self.injection_action = injection_action;
+        self.touch();
+    }
+
+    /// Enable or disable zero-retention mode
+    pub fn set_no_log(&mut self, no_log: bool) {
+        self.no_log = no_log;
+        self.touch();
+    }
+
     /// Update expiration
     pub fn set_expiration(&mut self, expires_at: Option<DateTime<Utc>>) {
         self.expires_at = expires_at;
@@ -533,6 +647,35 @@ impl ApiKey {
         self.last_used_at = Some(Utc::now());
     }
 
+    /// Record key usage from a client IP address
+    pub fn record_usage_from(&mut self, client_ip: impl Into<String>) {
+        self.record_usage();
+        self.last_used_ip = Some(client_ip.into());
+    }
+
+    /// Transition an active key past its expiration date to `Expired`.
+    /// Returns true if the status changed.
+    pub fn expire_if_due(&mut self) -> bool {
+        if self.status == ApiKeyStatus::Active && self.is_expired() {
+            self.status = ApiKeyStatus::Expired;
+            self.touch();
+            return true;
+        }
+
+        false
+    }
+
+    /// Mark the key as replaced by a rotated key. The key stays valid until
+    /// `grace_until` (or its existing expiration, whichever comes first).
+    pub fn mark_rotated(&mut self, replacement: ApiKeyId, grace_until: DateTime<Utc>) {
+        self.replaced_by = Some(replacement);
+        self.expires_at = Some(match self.expires_at {
+            Some(existing) if existing < grace_until => existing,
+            _ => grace_until,
+        });
+        self.touch();
+    }
+
     /// Suspend the key
     pub fn suspend(&mut self) {
         self.status = ApiKeyStatus::Suspended;
@@ -651,6 +794,22 @@ mod tests {
         assert_eq!(config.tokens_per_minute, Some(100000));
     }
 
+    #[test]
+    fn test_rate_limit_config_quotas() {
+        let config = RateLimitConfig::new(60, 1000, 10000)
+            .with_tokens_per_day(1_000_000)
+            .with_cost_per_month(25.5);
+
+        assert_eq!(config.tokens_per_day, Some(1_000_000));
+        assert_eq!(config.cost_per_month_micros, Some(25_500_000));
+
+        // Keys stored before quotas existed still deserialize
+        let json = r#"{"enabled": true, "requests_per_minute": 1, "requests_per_hour": 2, "requests_per_day": 3}"#;
+        let config: RateLimitConfig = serde_json::from_str(json).unwrap();
+        assert!(config.tokens_per_day.is_none());
+        assert!(config.cost_per_month_micros.is_none());
+    }
+
     #[test]
     fn test_api_key_creation() {
         let key = create_test_api_key("test-key", "Test Key")
@@ -716,6 +875,64 @@ mod tests {
         assert!(key.last_used_at().is_some());
     }
 
+    #[test]
+    fn test_api_key_record_usage_from() {
+        let mut key = create_test_api_key("test-key", "Test Key");
+
+        key.record_usage_from("10.0.0.1");
+        assert!(key.last_used_at().is_some());
+        assert_eq!(key.last_used_ip(), Some("10.0.0.1"));
+    }
+
+    #[test]
+    fn test_api_key_expire_if_due() {
+        let mut key = create_test_api_key("test-key", "Test Key");
+        assert!(!key.expire_if_due());
+        assert_eq!(key.status(), ApiKeyStatus::Active);
+
+        key.set_expiration(Some(Utc::now() - chrono::Duration::seconds(1)));
+        assert!(key.expire_if_due());
+        assert_eq!(key.status(), ApiKeyStatus::Expired);
+
+        // Already expired - no further transition
+        assert!(!key.expire_if_due());
+    }
+
+    #[test]
+    fn test_api_key_expire_if_due_ignores_revoked() {
+        let mut key = create_test_api_key("test-key", "Test Key")
+            .with_expiration(Utc::now() - chrono::Duration::seconds(1));
+        key.revoke();
+
+        assert!(!key.expire_if_due());
+        assert_eq!(key.status(), ApiKeyStatus::Revoked);
+    }
+
+    #[test]
+    fn test_api_key_mark_rotated() {
+        let mut key = create_test_api_key("test-key", "Test Key");
+        let grace_until = Utc::now() + chrono::Duration::hours(1);
+
+        key.mark_rotated(ApiKeyId::new("new-key").unwrap(), grace_until);
+
+        assert_eq!(key.replaced_by().unwrap().as_str(), "new-key");
+        assert_eq!(key.expires_at(), Some(grace_until));
+        assert!(key.is_valid());
+    }
+
+    #[test]
+    fn test_api_key_mark_rotated_keeps_earlier_expiration() {
+        let earlier = Utc::now() + chrono::Duration::minutes(5);
+        let mut key = create_test_api_key("test-key", "Test Key").with_expiration(earlier);
+
+        key.mark_rotated(
+            ApiKeyId::new("new-key").unwrap(),
+            Utc::now() + chrono::Duration::hours(1),
+        );
+
+        assert_eq!(key.expires_at(), Some(earlier));
+    }
+
     #[test]
     fn test_api_key_set_team_id() {
         let mut key = create_test_api_key("test-key", "Test Key");
@@ -724,4 +941,50 @@ mod tests {
         key.set_team_id(new_team_id);
         assert_eq!(key.team_id().as_str(), "new-team");
     }
+
+    #[test]
+    fn test_allowed_cidrs() {
+        let mut key = create_test_api_key("test-key", "Test Key");
+        assert!(key.allowed_cidrs().is_empty());
+
+        key.set_allowed_cidrs(vec!["10.0.0.0/8".parse().unwrap()]);
+        assert_eq!(key.allowed_cidrs().len(), 1);
+
+        let json = serde_json::to_string(&key).unwrap();
+        assert!(json.contains("10.0.0.0/8"));
+
+        let parsed: ApiKey = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.allowed_cidrs(), key.allowed_cidrs());
+    }
+
+    #[test]
+    fn test_injection_action() {
+        let mut key = create_test_api_key("test-key", "Test Key");
+        assert!(key.injection_action().is_none());
+        assert!(!serde_json::to_string(&key).unwrap().contains("injection_action"));
+
+        key.set_injection_action(Some(InjectionAction::Block));
+
+        let json = serde_json::to_string(&key).unwrap();
+        assert!(json.contains("\"injection_action\":\"block\""));
+
+        let parsed: ApiKey = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.injection_action(), Some(InjectionAction::Block));
+    }
+
+    #[test]
+    fn test_no_log() {
+        let mut key = create_test_api_key("test-key", "Test Key");
+        assert!(!key.no_log());
+
+        key.set_no_log(true);
+
+        let json = serde_json::to_string(&key).unwrap();
+        let parsed: ApiKey = serde_json::from_str(&json).unwrap();
+        assert!(parsed.no_log());
+
+        let legacy = json.replace(",\"no_log\":true", "");
+        let parsed: ApiKey = serde_json::from_str(&legacy).unwrap();
+        assert!(!parsed.no_log());
+    }
 }
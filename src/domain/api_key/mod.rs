@@ -4,11 +4,13 @@
 //! including key generation, validation, permissions, and rate limiting.
 
 mod entity;
+mod parameter_policy;
 mod repository;
 mod validation;
 
 pub use entity::{
     ApiKey, ApiKeyId, ApiKeyPermissions, ApiKeyStatus, RateLimitConfig, ResourcePermission,
 };
+pub use parameter_policy::{ParameterPolicy, ParameterPolicyViolation};
 pub use repository::ApiKeyRepository;
 pub use validation::{validate_api_key_id, ApiKeyValidationError};
@@ -0,0 +1,186 @@
+//! Chat completion parameter policy of an API key
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::llm::LlmRequest;
+use crate::domain::{DomainError, FieldViolations};
+
+/// Limits on the chat completion parameters an API key may send, for keys
+/// handed to tools that are only partly trusted. Requests outside them are
+/// rejected; unset fields allow any value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ParameterPolicy {
+    /// Highest `max_tokens`; requests without one are sent with it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Highest temperature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_temperature: Option<f32>,
+    /// Only streamed requests are allowed
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub streaming_only: bool,
+}
+
+/// A request parameter outside the policy of its API key
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{message}")]
+pub struct ParameterPolicyViolation {
+    pub param: &'static str,
+    pub message: String,
+}
+
+impl ParameterPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if the policy allows any parameter value
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn validate(&self) -> Result<(), DomainError> {
+        let mut violations = FieldViolations::new();
+
+        if self.max_tokens == Some(0) {
+            violations.push("parameters.max_tokens", "Max tokens must be greater than 0");
+        }
+
+        if self
+            .max_temperature
+            .is_some_and(|t| !(0.0..=2.0).contains(&t))
+        {
+            violations.push(
+                "parameters.max_temperature",
+                "Max temperature must be between 0 and 2",
+            );
+        }
+
+        violations.into_result()
+    }
+
+    /// Check the parameters sent by a request
+    pub fn check(
+        &self,
+        max_tokens: Option<u32>,
+        temperature: Option<f32>,
+        stream: bool,
+    ) -> Result<(), ParameterPolicyViolation> {
+        if let (Some(requested), Some(limit)) = (max_tokens, self.max_tokens)
+            && requested > limit
+        {
+            return Err(ParameterPolicyViolation {
+                param: "max_tokens",
+                message: format!(
+                    "max_tokens {} exceeds the limit of {} for this API key",
+                    requested, limit
+                ),
+            });
+        }
+
+        if let (Some(requested), Some(limit)) = (temperature, self.max_temperature)
+            && requested > limit
+        {
+            return Err(ParameterPolicyViolation {
+                param: "temperature",
+                message: format!(
+                    "temperature {} exceeds the limit of {} for this API key",
+                    requested, limit
+                ),
+            });
+        }
+
+        if self.streaming_only && !stream {
+            return Err(ParameterPolicyViolation {
+                param: "stream",
+                message: "This API key only allows streamed requests".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Hold a built request to the limits: requests without `max_tokens`
+    /// get the limit, and experiment overrides applied after the request
+    /// was checked are lowered to it
+    pub fn clamp_request(&self, request: &mut LlmRequest) {
+        if let Some(limit) = self.max_temperature {
+            request.temperature = request.temperature.map(|t| t.min(limit));
+        }
+
+        if let Some(limit) = self.max_tokens {
+            request.max_tokens = Some(request.max_tokens.map_or(limit, |m| m.min(limit)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let policy = ParameterPolicy {
+            max_tokens: Some(2000),
+            max_temperature: Some(1.0),
+            streaming_only: false,
+        };
+
+        assert!(policy.check(Some(2000), Some(1.0), false).is_ok());
+        assert!(policy.check(None, None, false).is_ok());
+
+        let err = policy.check(Some(4000), None, false).unwrap_err();
+        assert_eq!(err.param, "max_tokens");
+        assert_eq!(
+            err.to_string(),
+            "max_tokens 4000 exceeds the limit of 2000 for this API key"
+        );
+        assert_eq!(
+            policy.check(None, Some(1.5), false).unwrap_err().param,
+            "temperature"
+        );
+
+        let streaming = ParameterPolicy {
+            streaming_only: true,
+            ..Default::default()
+        };
+        assert!(streaming.check(None, None, true).is_ok());
+        assert_eq!(streaming.check(None, None, false).unwrap_err().param, "stream");
+    }
+
+    #[test]
+    fn test_clamp_request_fills_max_tokens() {
+        let policy = ParameterPolicy {
+            max_tokens: Some(512),
+            ..Default::default()
+        };
+
+        let mut request = LlmRequest::builder().user("Hi").temperature(1.9).build();
+        policy.clamp_request(&mut request);
+
+        assert_eq!(request.max_tokens, Some(512));
+        assert_eq!(request.temperature, Some(1.9));
+    }
+
+    #[test]
+    fn test_validate_and_serde() {
+        assert!(ParameterPolicy::new().is_empty());
+        assert!(ParameterPolicy {
+            max_tokens: Some(0),
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+
+        let policy: ParameterPolicy =
+            serde_json::from_value(serde_json::json!({"max_tokens": 2000, "streaming_only": true}))
+                .unwrap();
+        assert_eq!(policy.max_tokens, Some(2000));
+        assert!(policy.validate().is_ok());
+        assert_eq!(
+            serde_json::to_value(ParameterPolicy::new()).unwrap(),
+            serde_json::json!({})
+        );
+    }
+}
@@ -35,8 +35,12 @@ pub trait ApiKeyRepository: Send + Sync + Debug {
         Ok(self.get(id).await?.is_some())
     }
 
-    /// Record usage of an API key
-    async fn record_usage(&self, id: &ApiKeyId) -> Result<(), DomainError>;
+    /// Record usage of an API key, optionally from a client IP address
+    async fn record_usage(
+        &self,
+        id: &ApiKeyId,
+        client_ip: Option<&str>,
+    ) -> Result<(), DomainError>;
 
     /// Get API keys expiring before a given timestamp
     async fn get_expiring_before(
@@ -168,12 +172,19 @@ pub mod mock {
             Ok(count)
         }
 
-        async fn record_usage(&self, id: &ApiKeyId) -> Result<(), DomainError> {
+        async fn record_usage(
+            &self,
+            id: &ApiKeyId,
+            client_ip: Option<&str>,
+        ) -> Result<(), DomainError> {
             self.check_should_fail().await?;
             let mut keys = self.keys.write().await;
 
             if let Some(key) = keys.get_mut(id.as_str()) {
-                key.record_usage();
+                match client_ip {
+                    Some(ip) => key.record_usage_from(ip),
+                    None => key.record_usage(),
+                }
                 Ok(())
             } else {
                 Err(DomainError::not_found(format!("API key '{}' not found", id)))
@@ -297,7 +308,7 @@ pub mod mock {
 
             repo.create(key.clone()).await.unwrap();
 
-            repo.record_usage(key.id()).await.unwrap();
+            repo.record_usage(key.id(), None).await.unwrap();
 
             let retrieved = repo.get(key.id()).await.unwrap().unwrap();
             assert!(retrieved.last_used_at().is_some());
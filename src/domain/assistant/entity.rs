@@ -0,0 +1,474 @@
+//! Assistant entities
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::knowledge_base::validate_namespace;
+use crate::domain::storage::{StorageEntity, StorageKey};
+use crate::domain::{DomainError, FieldViolations, ModelValidationError, validate_model_id};
+
+/// Maximum number of passages retrieved from one bound knowledge base
+pub const MAX_ASSISTANT_TOP_K: u32 = 20;
+
+/// Heading of the retrieved passages appended to the system prompt
+const CONTEXT_HEADING: &str = "Relevant context:";
+
+/// Assistant identifier
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AssistantId(String);
+
+impl AssistantId {
+    pub fn new(id: impl Into<String>) -> Result<Self, ModelValidationError> {
+        let id = id.into();
+        validate_model_id(&id)?;
+        Ok(Self(id))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for AssistantId {
+    type Error = ModelValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<AssistantId> for String {
+    fn from(id: AssistantId) -> Self {
+        id.0
+    }
+}
+
+impl std::fmt::Display for AssistantId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StorageKey for AssistantId {
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Knowledge base searched with the latest user message before each turn
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct KnowledgeBaseBinding {
+    pub knowledge_base_id: String,
+    /// Passages to retrieve
+    #[serde(default = "default_top_k")]
+    pub top_k: u32,
+    /// Minimum similarity of the retrieved passages (0.0 - 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub similarity_threshold: Option<f32>,
+    /// Namespace of the knowledge base the passages are retrieved from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+}
+
+fn default_top_k() -> u32 {
+    3
+}
+
+impl KnowledgeBaseBinding {
+    pub fn new(knowledge_base_id: impl Into<String>) -> Self {
+        Self {
+            knowledge_base_id: knowledge_base_id.into(),
+            top_k: default_top_k(),
+            similarity_threshold: None,
+            namespace: None,
+        }
+    }
+
+    pub fn with_top_k(mut self, top_k: u32) -> Self {
+        self.top_k = top_k;
+        self
+    }
+
+    pub fn with_similarity_threshold(mut self, threshold: f32) -> Self {
+        self.similarity_threshold = Some(threshold);
+        self
+    }
+
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+}
+
+/// Workflow the assistant's clients are allowed to run as a tool
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AssistantTool {
+    pub workflow_id: String,
+    /// What the tool does, for the client deciding when to call it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl AssistantTool {
+    pub fn new(workflow_id: impl Into<String>) -> Self {
+        Self {
+            workflow_id: workflow_id.into(),
+            description: None,
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+/// A model, system prompt, bound knowledge bases and allowed tools exposed
+/// to applications under one ID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Assistant {
+    /// Unique identifier
+    id: AssistantId,
+    /// Display name
+    name: String,
+    /// What the assistant is for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    /// Model answering the conversation
+    model_id: String,
+    /// Prompt rendered as the system message
+    prompt_id: String,
+    /// Variables the system prompt is rendered with
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    prompt_variables: HashMap<String, String>,
+    /// Knowledge bases providing context
+    #[serde(default)]
+    knowledge_bases: Vec<KnowledgeBaseBinding>,
+    /// Workflows allowed as tools
+    #[serde(default)]
+    tools: Vec<AssistantTool>,
+    /// Whether the assistant can be chatted with
+    enabled: bool,
+    /// Creation timestamp
+    created_at: DateTime<Utc>,
+    /// Last update timestamp
+    updated_at: DateTime<Utc>,
+}
+
+impl Assistant {
+    pub fn new(
+        id: AssistantId,
+        name: impl Into<String>,
+        model_id: impl Into<String>,
+        prompt_id: impl Into<String>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id,
+            name: name.into(),
+            description: None,
+            model_id: model_id.into(),
+            prompt_id: prompt_id.into(),
+            prompt_variables: HashMap::new(),
+            knowledge_bases: Vec::new(),
+            tools: Vec::new(),
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    // Builder methods
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_prompt_variables(mut self, variables: HashMap<String, String>) -> Self {
+        self.prompt_variables = variables;
+        self
+    }
+
+    pub fn with_knowledge_bases(mut self, knowledge_bases: Vec<KnowledgeBaseBinding>) -> Self {
+        self.knowledge_bases = knowledge_bases;
+        self
+    }
+
+    pub fn with_tools(mut self, tools: Vec<AssistantTool>) -> Self {
+        self.tools = tools;
+        self
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    // Getters
+    pub fn id(&self) -> &AssistantId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn model_id(&self) -> &str {
+        &self.model_id
+    }
+
+    pub fn prompt_id(&self) -> &str {
+        &self.prompt_id
+    }
+
+    pub fn prompt_variables(&self) -> &HashMap<String, String> {
+        &self.prompt_variables
+    }
+
+    pub fn knowledge_bases(&self) -> &[KnowledgeBaseBinding] {
+        &self.knowledge_bases
+    }
+
+    pub fn tools(&self) -> &[AssistantTool] {
+        &self.tools
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    // Mutators
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.name = name.into();
+        self.touch();
+    }
+
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.description = description;
+        self.touch();
+    }
+
+    pub fn set_model_id(&mut self, model_id: impl Into<String>) {
+        self.model_id = model_id.into();
+        self.touch();
+    }
+
+    pub fn set_prompt_id(&mut self, prompt_id: impl Into<String>) {
+        self.prompt_id = prompt_id.into();
+        self.touch();
+    }
+
+    pub fn set_prompt_variables(&mut self, variables: HashMap<String, String>) {
+        self.prompt_variables = variables;
+        self.touch();
+    }
+
+    pub fn set_knowledge_bases(&mut self, knowledge_bases: Vec<KnowledgeBaseBinding>) {
+        self.knowledge_bases = knowledge_bases;
+        self.touch();
+    }
+
+    pub fn set_tools(&mut self, tools: Vec<AssistantTool>) {
+        self.tools = tools;
+        self.touch();
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.touch();
+    }
+
+    /// Check the definition, reporting every invalid field
+    pub fn validate(&self) -> Result<(), DomainError> {
+        let mut violations = FieldViolations::new();
+
+        if self.name.trim().is_empty() {
+            violations.push("name", "Name is required");
+        }
+        if self.model_id.trim().is_empty() {
+            violations.push("model_id", "Model ID is required");
+        }
+        if self.prompt_id.trim().is_empty() {
+            violations.push("prompt_id", "Prompt ID is required");
+        }
+
+        for (index, binding) in self.knowledge_bases.iter().enumerate() {
+            let field = |name: &str| format!("knowledge_bases[{}].{}", index, name);
+
+            if self.knowledge_bases[..index].iter().any(|other| {
+                other.knowledge_base_id == binding.knowledge_base_id
+                    && other.namespace == binding.namespace
+            }) {
+                violations.push(
+                    field("knowledge_base_id"),
+                    format!(
+                        "Knowledge base '{}' is bound more than once",
+                        binding.knowledge_base_id
+                    ),
+                );
+            }
+            if !(1..=MAX_ASSISTANT_TOP_K).contains(&binding.top_k) {
+                violations.push(
+                    field("top_k"),
+                    format!("top_k must be between 1 and {}", MAX_ASSISTANT_TOP_K),
+                );
+            }
+            if let Some(threshold) = binding.similarity_threshold
+                && !(0.0..=1.0).contains(&threshold)
+            {
+                violations.push(
+                    field("similarity_threshold"),
+                    "similarity_threshold must be between 0 and 1",
+                );
+            }
+            if let Some(namespace) = &binding.namespace
+                && let Err(e) = validate_namespace(namespace)
+            {
+                violations.push(field("namespace"), e.to_string());
+            }
+        }
+
+        for (index, tool) in self.tools.iter().enumerate() {
+            if self.tools[..index]
+                .iter()
+                .any(|other| other.workflow_id == tool.workflow_id)
+            {
+                violations.push(
+                    format!("tools[{}].workflow_id", index),
+                    format!("Tool '{}' is listed more than once", tool.workflow_id),
+                );
+            }
+        }
+
+        violations.into_result()
+    }
+
+    /// Whether clients may run the workflow as a tool of this assistant
+    pub fn allows_tool(&self, workflow_id: &str) -> bool {
+        self.tools
+            .iter()
+            .any(|tool| tool.workflow_id == workflow_id)
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+    }
+}
+
+impl StorageEntity for Assistant {
+    type Key = AssistantId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+/// Append retrieved passages to a rendered system prompt, numbered so the
+/// model can cite them
+pub fn system_prompt_with_context(system_prompt: &str, passages: &[String]) -> String {
+    if passages.is_empty() {
+        return system_prompt.to_string();
+    }
+
+    let context = passages
+        .iter()
+        .enumerate()
+        .map(|(index, passage)| format!("[{}] {}", index + 1, passage))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    format!("{}\n\n{}\n{}", system_prompt, CONTEXT_HEADING, context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assistant() -> Assistant {
+        Assistant::new(
+            AssistantId::new("support").unwrap(),
+            "Support",
+            "gpt-4",
+            "support-system",
+        )
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(assistant().validate().is_ok());
+
+        let invalid = assistant()
+            .with_knowledge_bases(vec![
+                KnowledgeBaseBinding::new("docs"),
+                KnowledgeBaseBinding::new("docs").with_top_k(0),
+                KnowledgeBaseBinding::new("faq").with_similarity_threshold(1.5),
+                KnowledgeBaseBinding::new("docs").with_namespace("v2"),
+                KnowledgeBaseBinding::new("guides").with_namespace("v 2"),
+            ])
+            .with_tools(vec![
+                AssistantTool::new("lookup"),
+                AssistantTool::new("lookup"),
+            ]);
+
+        let Err(DomainError::InvalidFields { violations }) = invalid.validate() else {
+            panic!("expected invalid fields");
+        };
+        let fields: Vec<&str> = violations.iter().map(|v| v.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "knowledge_bases[1].knowledge_base_id",
+                "knowledge_bases[1].top_k",
+                "knowledge_bases[2].similarity_threshold",
+                "knowledge_bases[4].namespace",
+                "tools[1].workflow_id",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_binding_defaults() {
+        let binding: KnowledgeBaseBinding =
+            serde_json::from_str(r#"{"knowledge_base_id": "docs"}"#).unwrap();
+        assert_eq!(binding, KnowledgeBaseBinding::new("docs"));
+        assert_eq!(binding.top_k, 3);
+    }
+
+    #[test]
+    fn test_system_prompt_with_context() {
+        assert_eq!(system_prompt_with_context("Be helpful", &[]), "Be helpful");
+        assert_eq!(
+            system_prompt_with_context(
+                "Be helpful",
+                &[
+                    "Refunds take 5 days".to_string(),
+                    "Ships worldwide".to_string()
+                ]
+            ),
+            "Be helpful\n\nRelevant context:\n[1] Refunds take 5 days\n\n[2] Ships worldwide"
+        );
+    }
+
+    #[test]
+    fn test_allows_tool() {
+        let assistant = assistant().with_tools(vec![AssistantTool::new("lookup-order")]);
+        assert!(assistant.allows_tool("lookup-order"));
+        assert!(!assistant.allows_tool("refund"));
+    }
+}
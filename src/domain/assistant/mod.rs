@@ -0,0 +1,8 @@
+//! Assistant domain module: a model, system prompt, knowledge bases and
+//! tools bundled behind one stable ID
+
+mod entity;
+mod repository;
+
+pub use entity::*;
+pub use repository::*;
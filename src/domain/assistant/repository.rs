@@ -0,0 +1,23 @@
+//! Assistant repository trait
+
+use async_trait::async_trait;
+use std::fmt::Debug;
+
+use super::{Assistant, AssistantId};
+use crate::domain::DomainError;
+
+/// Repository for assistants
+#[async_trait]
+pub trait AssistantRepository: Send + Sync + Debug {
+    /// Get an assistant by ID
+    async fn get(&self, id: &AssistantId) -> Result<Option<Assistant>, DomainError>;
+
+    /// List every assistant, ordered by name
+    async fn list(&self) -> Result<Vec<Assistant>, DomainError>;
+
+    /// Create or replace an assistant
+    async fn save(&self, assistant: Assistant) -> Result<Assistant, DomainError>;
+
+    /// Delete an assistant
+    async fn delete(&self, id: &AssistantId) -> Result<bool, DomainError>;
+}
@@ -0,0 +1,445 @@
+//! Audit log domain entities
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::storage::{StorageEntity, StorageKey};
+
+/// Audit log ID
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AuditLogId(String);
+
+impl StorageKey for AuditLogId {
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AuditLogId {
+    pub fn new(id: impl Into<String>) -> Result<Self, AuditLogValidationError> {
+        let id = id.into();
+        validate_audit_log_id(&id)?;
+        Ok(Self(id))
+    }
+
+    pub fn generate() -> Self {
+        Self(format!("audit-{}", Uuid::new_v4()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for AuditLogId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Kind of principal that performed an audited action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditActorType {
+    User,
+    ApiKey,
+    ServiceAccount,
+    System,
+}
+
+impl AuditActorType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditActorType::User => "user",
+            AuditActorType::ApiKey => "api_key",
+            AuditActorType::ServiceAccount => "service_account",
+            AuditActorType::System => "system",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "user" => Some(AuditActorType::User),
+            "api_key" => Some(AuditActorType::ApiKey),
+            "service_account" => Some(AuditActorType::ServiceAccount),
+            "system" => Some(AuditActorType::System),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for AuditActorType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Principal that performed an audited action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditActor {
+    pub actor_type: AuditActorType,
+    /// User ID, API key ID or service account ID
+    pub id: String,
+    /// Human readable name (username, API key or service account name)
+    pub name: Option<String>,
+}
+
+impl AuditActor {
+    pub fn user(id: impl Into<String>, username: impl Into<String>) -> Self {
+        Self {
+            actor_type: AuditActorType::User,
+            id: id.into(),
+            name: Some(username.into()),
+        }
+    }
+
+    pub fn api_key(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            actor_type: AuditActorType::ApiKey,
+            id: id.into(),
+            name: Some(name.into()),
+        }
+    }
+
+    pub fn service_account(id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            actor_type: AuditActorType::ServiceAccount,
+            id: id.into(),
+            name: Some(name.into()),
+        }
+    }
+
+    pub fn system() -> Self {
+        Self {
+            actor_type: AuditActorType::System,
+            id: "system".to_string(),
+            name: None,
+        }
+    }
+}
+
+/// Audit log entry describing a single admin mutation or security event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLog {
+    id: AuditLogId,
+    actor: AuditActor,
+    /// What was done (e.g. `create`, `update`, `delete`, `rotate`)
+    action: String,
+    /// Kind of entity affected (e.g. `api_keys`, `teams`)
+    entity_type: String,
+    entity_id: Option<String>,
+    /// Requested changes, with sensitive fields redacted
+    changes: Option<serde_json::Value>,
+    /// HTTP status code of the outcome
+    status_code: Option<u16>,
+    method: Option<String>,
+    path: Option<String>,
+    client_ip: Option<String>,
+    user_agent: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl StorageEntity for AuditLog {
+    type Key = AuditLogId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+impl AuditLog {
+    pub fn new(
+        actor: AuditActor,
+        action: impl Into<String>,
+        entity_type: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: AuditLogId::generate(),
+            actor,
+            action: action.into(),
+            entity_type: entity_type.into(),
+            entity_id: None,
+            changes: None,
+            status_code: None,
+            method: None,
+            path: None,
+            client_ip: None,
+            user_agent: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    // Getters
+
+    pub fn id(&self) -> &AuditLogId {
+        &self.id
+    }
+
+    pub fn actor(&self) -> &AuditActor {
+        &self.actor
+    }
+
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    pub fn entity_type(&self) -> &str {
+        &self.entity_type
+    }
+
+    pub fn entity_id(&self) -> Option<&str> {
+        self.entity_id.as_deref()
+    }
+
+    pub fn changes(&self) -> Option<&serde_json::Value> {
+        self.changes.as_ref()
+    }
+
+    pub fn status_code(&self) -> Option<u16> {
+        self.status_code
+    }
+
+    /// Whether the audited operation succeeded (2xx or no recorded status)
+    pub fn is_success(&self) -> bool {
+        self.status_code.is_none_or(|code| (200..300).contains(&code))
+    }
+
+    pub fn method(&self) -> Option<&str> {
+        self.method.as_deref()
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    pub fn client_ip(&self) -> Option<&str> {
+        self.client_ip.as_deref()
+    }
+
+    pub fn user_agent(&self) -> Option<&str> {
+        self.user_agent.as_deref()
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    // Builder methods
+
+    pub fn with_entity_id(mut self, entity_id: impl Into<String>) -> Self {
+        self.entity_id = Some(entity_id.into());
+        self
+    }
+
+    pub fn with_changes(mut self, changes: serde_json::Value) -> Self {
+        self.changes = Some(changes);
+        self
+    }
+
+    pub fn with_status_code(mut self, status_code: u16) -> Self {
+        self.status_code = Some(status_code);
+        self
+    }
+
+    pub fn with_request(mut self, method: impl Into<String>, path: impl Into<String>) -> Self {
+        self.method = Some(method.into());
+        self.path = Some(path.into());
+        self
+    }
+
+    pub fn with_client_ip(mut self, client_ip: impl Into<String>) -> Self {
+        self.client_ip = Some(client_ip.into());
+        self
+    }
+
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+}
+
+/// Audit log validation errors
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum AuditLogValidationError {
+    #[error("Invalid audit log ID: {0}")]
+    InvalidId(String),
+}
+
+fn validate_audit_log_id(id: &str) -> Result<(), AuditLogValidationError> {
+    if id.is_empty() {
+        return Err(AuditLogValidationError::InvalidId(
+            "ID cannot be empty".to_string(),
+        ));
+    }
+
+    if id.len() > 50 {
+        return Err(AuditLogValidationError::InvalidId(
+            "ID cannot exceed 50 characters".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Query parameters for listing audit logs
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogQuery {
+    pub actor_type: Option<AuditActorType>,
+    pub actor_id: Option<String>,
+    pub action: Option<String>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<String>,
+    pub from_date: Option<DateTime<Utc>>,
+    pub to_date: Option<DateTime<Utc>>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl AuditLogQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_actor(mut self, actor_type: AuditActorType, actor_id: impl Into<String>) -> Self {
+        self.actor_type = Some(actor_type);
+        self.actor_id = Some(actor_id.into());
+        self
+    }
+
+    pub fn with_action(mut self, action: impl Into<String>) -> Self {
+        self.action = Some(action.into());
+        self
+    }
+
+    pub fn with_entity(mut self, entity_type: impl Into<String>, entity_id: impl Into<String>) -> Self {
+        self.entity_type = Some(entity_type.into());
+        self.entity_id = Some(entity_id.into());
+        self
+    }
+
+    pub fn with_date_range(mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
+        self.from_date = Some(from);
+        self.to_date = Some(to);
+        self
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Check whether an audit log matches the filters of this query
+    pub fn matches(&self, log: &AuditLog) -> bool {
+        if let Some(actor_type) = self.actor_type
+            && log.actor().actor_type != actor_type
+        {
+            return false;
+        }
+
+        if let Some(actor_id) = &self.actor_id
+            && log.actor().id != *actor_id
+        {
+            return false;
+        }
+
+        if let Some(action) = &self.action
+            && log.action() != action
+        {
+            return false;
+        }
+
+        if let Some(entity_type) = &self.entity_type
+            && log.entity_type() != entity_type
+        {
+            return false;
+        }
+
+        if let Some(entity_id) = &self.entity_id
+            && log.entity_id() != Some(entity_id.as_str())
+        {
+            return false;
+        }
+
+        if let Some(from) = self.from_date
+            && log.created_at() < from
+        {
+            return false;
+        }
+
+        if let Some(to) = self.to_date
+            && log.created_at() > to
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_log_id() {
+        let id = AuditLogId::generate();
+        assert!(id.as_str().starts_with("audit-"));
+
+        assert!(AuditLogId::new("").is_err());
+        assert_eq!(AuditLogId::new("custom").unwrap().as_str(), "custom");
+    }
+
+    #[test]
+    fn test_audit_log_builder() {
+        let log = AuditLog::new(AuditActor::user("user-1", "alice"), "update", "api_keys")
+            .with_entity_id("key-1")
+            .with_changes(serde_json::json!({"expires_at": null}))
+            .with_status_code(200)
+            .with_request("PUT", "/admin/api-keys/key-1")
+            .with_client_ip("10.0.0.1");
+
+        assert_eq!(log.actor().actor_type, AuditActorType::User);
+        assert_eq!(log.action(), "update");
+        assert_eq!(log.entity_id(), Some("key-1"));
+        assert_eq!(log.method(), Some("PUT"));
+        assert_eq!(log.client_ip(), Some("10.0.0.1"));
+        assert!(log.is_success());
+    }
+
+    #[test]
+    fn test_audit_log_is_success() {
+        let log = AuditLog::new(AuditActor::system(), "delete", "teams").with_status_code(404);
+        assert!(!log.is_success());
+    }
+
+    #[test]
+    fn test_actor_type_roundtrip() {
+        for actor_type in [AuditActorType::User, AuditActorType::ApiKey, AuditActorType::System] {
+            assert_eq!(AuditActorType::parse(actor_type.as_str()), Some(actor_type));
+        }
+
+        assert!(AuditActorType::parse("robot").is_none());
+    }
+
+    #[test]
+    fn test_query_matches() {
+        let log = AuditLog::new(AuditActor::api_key("key-1", "CI"), "rotate", "api_keys")
+            .with_entity_id("key-2");
+
+        assert!(AuditLogQuery::new().matches(&log));
+        assert!(AuditLogQuery::new()
+            .with_actor(AuditActorType::ApiKey, "key-1")
+            .matches(&log));
+        assert!(AuditLogQuery::new().with_entity("api_keys", "key-2").matches(&log));
+        assert!(!AuditLogQuery::new().with_action("delete").matches(&log));
+        assert!(!AuditLogQuery::new()
+            .with_actor(AuditActorType::User, "key-1")
+            .matches(&log));
+    }
+}
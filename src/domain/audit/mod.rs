@@ -0,0 +1,12 @@
+//! Audit log domain module
+//!
+//! Audit logs record who changed what, when and from where for every admin
+//! mutation, plus security events such as IP allowlist violations.
+
+mod entity;
+mod repository;
+
+pub use entity::{
+    AuditActor, AuditActorType, AuditLog, AuditLogId, AuditLogQuery, AuditLogValidationError,
+};
+pub use repository::{AuditLogRepository, AuditSink};
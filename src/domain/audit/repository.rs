@@ -0,0 +1,29 @@
+//! Audit log repository and export sink traits
+
+use async_trait::async_trait;
+
+use super::entity::{AuditLog, AuditLogId, AuditLogQuery};
+use crate::domain::DomainError;
+
+/// Repository trait for audit logs
+#[async_trait]
+pub trait AuditLogRepository: Send + Sync + std::fmt::Debug {
+    /// Get an audit log by ID
+    async fn get(&self, id: &AuditLogId) -> Result<Option<AuditLog>, DomainError>;
+
+    /// List audit logs matching query, newest first
+    async fn list(&self, query: &AuditLogQuery) -> Result<Vec<AuditLog>, DomainError>;
+
+    /// Count audit logs matching query
+    async fn count(&self, query: &AuditLogQuery) -> Result<usize, DomainError>;
+
+    /// Save an audit log
+    async fn save(&self, log: &AuditLog) -> Result<(), DomainError>;
+}
+
+/// External destination audit logs are exported to (SIEM, log pipeline, ...)
+#[async_trait]
+pub trait AuditSink: Send + Sync + std::fmt::Debug {
+    /// Export a single audit log
+    async fn export(&self, log: &AuditLog) -> Result<(), DomainError>;
+}
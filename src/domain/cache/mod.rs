@@ -1,9 +1,11 @@
 //! Cache domain - Generic caching abstraction layer
 
 mod key;
+mod policy;
 mod repository;
 
 pub use key::{CacheKey, CacheKeyGenerator, CacheKeyParams, DefaultKeyGenerator};
+pub use policy::{CacheMode, CachePolicy};
 pub use repository::{Cache, CacheExt};
 
 #[cfg(test)]
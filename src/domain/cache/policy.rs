@@ -0,0 +1,175 @@
+//! Response caching policies of models and prompts
+//!
+//! Without a policy the response caches apply their global configuration.
+//! A policy attached to a model or a prompt picks the cache used for its
+//! requests, how long answers are kept, and up to which temperature they
+//! are cacheable at all.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::llm::LlmRequest;
+use crate::domain::FieldViolations;
+
+/// Cache serving the responses of a model or prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheMode {
+    /// Responses are never cached nor served from a cache
+    Disabled,
+    /// Only identical requests are served from the cache
+    Exact,
+    /// Semantically similar requests are served from the cache
+    Semantic,
+}
+
+/// Response caching policy attachable to a model or a prompt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CachePolicy {
+    pub mode: CacheMode,
+    /// Seconds a cached response is kept; the cache default when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_secs: Option<u64>,
+    /// Requests sampled above this temperature bypass the cache, since
+    /// their answers are meant to vary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_temperature: Option<f32>,
+}
+
+impl CachePolicy {
+    /// Policy of the given mode with the cache defaults
+    pub fn new(mode: CacheMode) -> Self {
+        Self {
+            mode,
+            ttl_secs: None,
+            max_temperature: None,
+        }
+    }
+
+    /// Policy never serving cached answers
+    pub fn disabled() -> Self {
+        Self::new(CacheMode::Disabled)
+    }
+
+    pub fn with_ttl_secs(mut self, ttl_secs: u64) -> Self {
+        self.ttl_secs = Some(ttl_secs);
+        self
+    }
+
+    pub fn with_max_temperature(mut self, max_temperature: f32) -> Self {
+        self.max_temperature = Some(max_temperature);
+        self
+    }
+
+    /// TTL of the cached responses, when the policy sets one
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl_secs.map(Duration::from_secs)
+    }
+
+    /// Whether `request` may be served by, and stored in, a cache of `mode`.
+    /// Requests without a temperature use the provider default and are
+    /// cacheable.
+    pub fn allows(&self, mode: CacheMode, request: &LlmRequest) -> bool {
+        if self.mode == CacheMode::Disabled || self.mode != mode {
+            return false;
+        }
+
+        match (self.max_temperature, request.temperature) {
+            (Some(max), Some(temperature)) => temperature <= max,
+            _ => true,
+        }
+    }
+
+    /// Policy applying to a request of a model using a prompt: disabling
+    /// the cache on either one wins, otherwise the prompt's policy is more
+    /// specific than the model's
+    pub fn resolve(model: Option<&Self>, prompt: Option<&Self>) -> Option<Self> {
+        let disabled = |p: &&Self| p.mode == CacheMode::Disabled;
+
+        model
+            .filter(disabled)
+            .or(prompt)
+            .or(model)
+            .cloned()
+    }
+
+    /// Record every invalid setting of the policy, under `prefix`
+    pub fn check(&self, prefix: &str, violations: &mut FieldViolations) {
+        if self.ttl_secs == Some(0) {
+            violations.push(
+                format!("{}.ttl_secs", prefix),
+                "TTL must be at least 1 second",
+            );
+        }
+        if let Some(max) = self.max_temperature.filter(|t| !(0.0..=2.0).contains(t)) {
+            violations.push(
+                format!("{}.max_temperature", prefix),
+                format!("{} is not between 0.0 and 2.0", max),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(temperature: Option<f32>) -> LlmRequest {
+        let mut request = LlmRequest::builder().user("Hello").build();
+        request.temperature = temperature;
+        request
+    }
+
+    #[test]
+    fn test_allows() {
+        let policy = CachePolicy::new(CacheMode::Exact).with_max_temperature(0.3);
+
+        assert!(policy.allows(CacheMode::Exact, &request(Some(0.2))));
+        assert!(policy.allows(CacheMode::Exact, &request(None)));
+        assert!(!policy.allows(CacheMode::Exact, &request(Some(0.9))));
+        assert!(!policy.allows(CacheMode::Semantic, &request(Some(0.2))));
+        assert!(!CachePolicy::disabled().allows(CacheMode::Disabled, &request(None)));
+    }
+
+    #[test]
+    fn test_resolve() {
+        let exact = CachePolicy::new(CacheMode::Exact);
+        let semantic = CachePolicy::new(CacheMode::Semantic).with_ttl_secs(60);
+        let disabled = CachePolicy::disabled();
+
+        assert_eq!(CachePolicy::resolve(None, None), None);
+        assert_eq!(CachePolicy::resolve(Some(&exact), None), Some(exact.clone()));
+        assert_eq!(
+            CachePolicy::resolve(Some(&exact), Some(&semantic)),
+            Some(semantic.clone())
+        );
+        assert_eq!(
+            CachePolicy::resolve(Some(&disabled), Some(&semantic)),
+            Some(disabled.clone())
+        );
+        assert_eq!(
+            CachePolicy::resolve(Some(&semantic), Some(&disabled)),
+            Some(disabled)
+        );
+    }
+
+    #[test]
+    fn test_serde_and_check() {
+        let policy: CachePolicy =
+            serde_json::from_value(json!({"mode": "semantic", "ttl_secs": 600})).unwrap();
+        assert_eq!(policy.ttl(), Some(Duration::from_secs(600)));
+
+        let mut violations = FieldViolations::new();
+        policy.check("cache_policy", &mut violations);
+        assert!(violations.is_empty());
+
+        CachePolicy::new(CacheMode::Exact)
+            .with_ttl_secs(0)
+            .with_max_temperature(3.0)
+            .check("cache_policy", &mut violations);
+        assert!(violations.into_result().is_err());
+    }
+}
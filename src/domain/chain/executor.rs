@@ -92,6 +92,19 @@ impl CircuitBreaker {
     }
 }
 
+/// Publish the circuit breaker state of a model as the
+/// `llm_circuit_breaker_state` gauge (0 closed, 1 half-open, 2 open)
+fn record_circuit_state(model_id: &ModelId, state: CircuitState) {
+    let value = match state {
+        CircuitState::Closed => 0.0,
+        CircuitState::HalfOpen => 1.0,
+        CircuitState::Open => 2.0,
+    };
+
+    metrics::gauge!("llm_circuit_breaker_state", "model" => model_id.as_str().to_string())
+        .set(value);
+}
+
 fn current_time_ms() -> u64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -273,6 +286,8 @@ impl<R: ProviderResolver> ChainExecutor<R> {
         let circuit_breaker = self.get_or_create_circuit_breaker(&model_id).await;
 
         if !circuit_breaker.is_available() {
+            record_circuit_state(&model_id, CircuitState::Open);
+
             return StepResult {
                 model_id,
                 success: false,
@@ -296,6 +311,7 @@ impl<R: ProviderResolver> ChainExecutor<R> {
             match self.try_execute_step(step, request).await {
                 Ok(response) => {
                     circuit_breaker.record_success();
+                    record_circuit_state(&model_id, circuit_breaker.state());
 
                     return StepResult {
                         model_id,
@@ -314,6 +330,7 @@ impl<R: ProviderResolver> ChainExecutor<R> {
 
         // All attempts failed
         circuit_breaker.record_failure();
+        record_circuit_state(&model_id, circuit_breaker.state());
 
         StepResult {
             model_id,
@@ -375,6 +375,28 @@ impl AppConfiguration {
             .unwrap_or(false)
     }
 
+    /// Percentage (0-100) of requests of opted-in teams whose payloads are captured
+    pub fn payload_capture_percent(&self) -> f64 {
+        self.get_value("persistence.payload_capture_percent")
+            .and_then(|v| v.as_float())
+            .unwrap_or(0.0)
+    }
+
+    /// Teams opted in to request/response payload capture
+    pub fn payload_capture_teams(&self) -> Vec<String> {
+        self.get_value("persistence.payload_capture_teams")
+            .and_then(|v| v.as_string_list())
+            .map(|s| s.to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Whether to capture the payloads of a request of a team, given a
+    /// uniform sample in `[0, 1)` drawn for the request
+    pub fn should_capture_payload(&self, team_id: &str, sample: f64) -> bool {
+        sample * 100.0 < self.payload_capture_percent()
+            && self.payload_capture_teams().iter().any(|t| t == team_id)
+    }
+
     pub fn should_log_model(&self, model_id: &str) -> bool {
         if !self.is_persistence_enabled() {
             return false;
@@ -592,6 +614,29 @@ mod tests {
         assert!(config.should_log_model("claude-3"));
     }
 
+    #[test]
+    fn test_should_capture_payload() {
+        let entries = vec![
+            ConfigEntry::new(
+                ConfigKey::new("persistence.payload_capture_percent").unwrap(),
+                ConfigValue::Float(25.0),
+                ConfigMetadata::new(ConfigCategory::Persistence, "Capture percentage"),
+            ),
+            ConfigEntry::new(
+                ConfigKey::new("persistence.payload_capture_teams").unwrap(),
+                ConfigValue::StringList(vec!["team-a".to_string()]),
+                ConfigMetadata::new(ConfigCategory::Persistence, "Capture teams"),
+            ),
+        ];
+
+        let config = AppConfiguration::from_entries(entries);
+
+        assert!(config.should_capture_payload("team-a", 0.1));
+        assert!(!config.should_capture_payload("team-a", 0.25));
+        assert!(!config.should_capture_payload("team-b", 0.1));
+        assert!(!AppConfiguration::from_entries(vec![]).should_capture_payload("team-a", 0.0));
+    }
+
     #[test]
     fn test_should_log_model_specific_list() {
         let entries = vec![
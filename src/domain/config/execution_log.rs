@@ -1,9 +1,15 @@
 //! Execution log domain entities
 
+use std::collections::HashMap;
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::domain::guardrail::InjectionDetection;
+use crate::domain::model::JudgeVerdict;
 use crate::domain::storage::{StorageEntity, StorageKey};
 
 /// Execution log ID
@@ -46,6 +52,8 @@ pub enum ExecutionType {
     Workflow,
     ChatCompletion,
     Ingestion,
+    /// Chat completion mirrored to a candidate model
+    Shadow,
 }
 
 impl ExecutionType {
@@ -55,6 +63,7 @@ impl ExecutionType {
             ExecutionType::Workflow => "workflow",
             ExecutionType::ChatCompletion => "chat_completion",
             ExecutionType::Ingestion => "ingestion",
+            ExecutionType::Shadow => "shadow",
         }
     }
 }
@@ -117,6 +126,12 @@ pub struct Executor {
     pub user_id: Option<String>,
     /// API key ID (if authenticated via API key)
     pub api_key_id: Option<String>,
+    /// Service account ID (if authenticated via a service account token)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_account_id: Option<String>,
+    /// Team of the API key (if authenticated via API key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<String>,
     /// IP address of the requester
     pub ip_address: Option<String>,
     /// User agent string
@@ -128,6 +143,8 @@ impl Executor {
         Self {
             user_id: None,
             api_key_id: Some(api_key_id.into()),
+            service_account_id: None,
+            team_id: None,
             ip_address: None,
             user_agent: None,
         }
@@ -137,6 +154,19 @@ impl Executor {
         Self {
             user_id: Some(user_id.into()),
             api_key_id: None,
+            service_account_id: None,
+            team_id: None,
+            ip_address: None,
+            user_agent: None,
+        }
+    }
+
+    pub fn from_service_account(service_account_id: impl Into<String>) -> Self {
+        Self {
+            user_id: None,
+            api_key_id: None,
+            service_account_id: Some(service_account_id.into()),
+            team_id: None,
             ip_address: None,
             user_agent: None,
         }
@@ -146,11 +176,18 @@ impl Executor {
         Self {
             user_id: None,
             api_key_id: None,
+            service_account_id: None,
+            team_id: None,
             ip_address: None,
             user_agent: None,
         }
     }
 
+    pub fn with_team(mut self, team_id: impl Into<String>) -> Self {
+        self.team_id = Some(team_id.into());
+        self
+    }
+
     pub fn with_ip(mut self, ip: impl Into<String>) -> Self {
         self.ip_address = Some(ip.into());
         self
@@ -180,6 +217,85 @@ impl TokenUsage {
     }
 }
 
+/// Why a streamed response ended before the provider finished it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamAbortReason {
+    /// The client went away mid-stream
+    ClientDisconnected,
+    /// The provider failed before or during the stream
+    ProviderError,
+    /// The secret scanner or a content policy ended the stream
+    ContentFilter,
+}
+
+/// Timing of a streamed response. A high time to first token points at the
+/// provider (queueing, prompt processing); a low token rate after it points
+/// at slow generation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StreamingMetrics {
+    /// Time from the provider call to the first content chunk
+    pub time_to_first_token_ms: Option<u64>,
+    /// Output tokens per second from the first content chunk to the end of
+    /// the stream
+    pub tokens_per_second: Option<f64>,
+    /// Output tokens streamed (estimated when the provider reports none)
+    pub output_tokens: u32,
+    /// Content chunks received from the provider
+    pub chunk_count: u32,
+    /// Whether the stream ended before the provider finished it
+    pub aborted: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abort_reason: Option<StreamAbortReason>,
+}
+
+impl StreamingMetrics {
+    /// Metrics of a stream that lasted `duration`, its first content chunk
+    /// arriving after `time_to_first_token`
+    pub fn new(
+        time_to_first_token: Option<Duration>,
+        duration: Duration,
+        output_tokens: u32,
+        chunk_count: u32,
+    ) -> Self {
+        let tokens_per_second = time_to_first_token
+            .map(|ttft| duration.saturating_sub(ttft).as_secs_f64())
+            .filter(|generation| *generation > 0.0 && output_tokens > 0)
+            .map(|generation| output_tokens as f64 / generation);
+
+        Self {
+            time_to_first_token_ms: time_to_first_token.map(|ttft| ttft.as_millis() as u64),
+            tokens_per_second,
+            output_tokens,
+            chunk_count,
+            aborted: false,
+            abort_reason: None,
+        }
+    }
+
+    pub fn with_abort_reason(mut self, reason: StreamAbortReason) -> Self {
+        self.aborted = true;
+        self.abort_reason = Some(reason);
+        self
+    }
+}
+
+/// How the answer of a shadow request compares to the answer served for
+/// the request it mirrored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ShadowComparison {
+    /// Model that served the mirrored request
+    pub primary_model_id: String,
+    pub primary_latency_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub judge_model_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verdict: Option<JudgeVerdict>,
+    /// Why the judge gave no verdict
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub judge_error: Option<String>,
+}
+
 /// Workflow step execution log
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowStepLog {
@@ -197,6 +313,9 @@ pub struct WorkflowStepLog {
     pub execution_time_ms: u64,
     /// Step status
     pub status: ExecutionStatus,
+    /// Streaming metrics, for steps whose provider call was streamed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub streaming: Option<StreamingMetrics>,
 }
 
 impl WorkflowStepLog {
@@ -209,6 +328,7 @@ impl WorkflowStepLog {
             error: None,
             execution_time_ms: 0,
             status: ExecutionStatus::Success,
+            streaming: None,
         }
     }
 
@@ -237,6 +357,11 @@ impl WorkflowStepLog {
         self.status = status;
         self
     }
+
+    pub fn with_streaming(mut self, streaming: StreamingMetrics) -> Self {
+        self.streaming = Some(streaming);
+        self
+    }
 }
 
 /// Execution log entry
@@ -261,6 +386,25 @@ pub struct ExecutionLog {
     /// Workflow step logs (only for workflow executions)
     #[serde(default)]
     workflow_steps: Option<Vec<WorkflowStepLog>>,
+    /// Whether the redacted request/response payloads were captured by
+    /// payload sampling
+    #[serde(default)]
+    payload_captured: bool,
+    /// Prompt-injection detection on the request's messages
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    injection: Option<InjectionDetection>,
+    /// Time to first token, token rate and abort of a streamed response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    streaming: Option<StreamingMetrics>,
+    /// Comparison with the request a shadow request mirrored
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    shadow: Option<ShadowComparison>,
+    /// Cost attribution tags of the request
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    tags: HashMap<String, String>,
+    /// ID of the v1 request, as returned in `x-pmp-request-id`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
 }
 
 impl StorageEntity for ExecutionLog {
@@ -295,6 +439,12 @@ impl ExecutionLog {
             created_at: Utc::now(),
             is_async: false,
             workflow_steps: None,
+            payload_captured: false,
+            injection: None,
+            streaming: None,
+            shadow: None,
+            tags: HashMap::new(),
+            request_id: None,
         }
     }
 
@@ -403,6 +553,30 @@ impl ExecutionLog {
         self.workflow_steps.as_ref()
     }
 
+    pub fn payload_captured(&self) -> bool {
+        self.payload_captured
+    }
+
+    pub fn injection(&self) -> Option<&InjectionDetection> {
+        self.injection.as_ref()
+    }
+
+    pub fn streaming(&self) -> Option<&StreamingMetrics> {
+        self.streaming.as_ref()
+    }
+
+    pub fn shadow(&self) -> Option<&ShadowComparison> {
+        self.shadow.as_ref()
+    }
+
+    pub fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
     // Builder methods
 
     pub fn with_resource_name(mut self, name: impl Into<String>) -> Self {
@@ -445,6 +619,36 @@ impl ExecutionLog {
         self
     }
 
+    pub fn with_payload_captured(mut self, payload_captured: bool) -> Self {
+        self.payload_captured = payload_captured;
+        self
+    }
+
+    pub fn with_injection(mut self, injection: InjectionDetection) -> Self {
+        self.injection = Some(injection);
+        self
+    }
+
+    pub fn with_streaming(mut self, streaming: StreamingMetrics) -> Self {
+        self.streaming = Some(streaming);
+        self
+    }
+
+    pub fn with_shadow(mut self, shadow: ShadowComparison) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+
+    pub fn with_tags(mut self, tags: HashMap<String, String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
     pub fn add_workflow_step(&mut self, step: WorkflowStepLog) {
         if self.workflow_steps.is_none() {
             self.workflow_steps = Some(Vec::new());
@@ -508,6 +712,17 @@ pub struct ExecutionLogQuery {
     pub status: Option<ExecutionStatus>,
     pub api_key_id: Option<String>,
     pub user_id: Option<String>,
+    pub team_id: Option<String>,
+    /// Only logs whose payloads were (or were not) captured by payload sampling
+    pub payload_captured: Option<bool>,
+    /// Only logs of requests flagged (or not) by the injection guard
+    pub injection_detected: Option<bool>,
+    /// Only logs of streamed responses that were (or were not) aborted
+    pub stream_aborted: Option<bool>,
+    /// Only logs carrying all of these tags
+    pub tags: HashMap<String, String>,
+    /// Only logs of this v1 request
+    pub request_id: Option<String>,
     pub from_date: Option<DateTime<Utc>>,
     pub to_date: Option<DateTime<Utc>>,
     pub limit: Option<usize>,
@@ -544,6 +759,36 @@ impl ExecutionLogQuery {
         self
     }
 
+    pub fn with_team_id(mut self, team_id: impl Into<String>) -> Self {
+        self.team_id = Some(team_id.into());
+        self
+    }
+
+    pub fn with_payload_captured(mut self, payload_captured: bool) -> Self {
+        self.payload_captured = Some(payload_captured);
+        self
+    }
+
+    pub fn with_injection_detected(mut self, injection_detected: bool) -> Self {
+        self.injection_detected = Some(injection_detected);
+        self
+    }
+
+    pub fn with_stream_aborted(mut self, stream_aborted: bool) -> Self {
+        self.stream_aborted = Some(stream_aborted);
+        self
+    }
+
+    pub fn with_tags(mut self, tags: HashMap<String, String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
     pub fn with_date_range(mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
         self.from_date = Some(from);
         self.to_date = Some(to);
@@ -559,6 +804,46 @@ impl ExecutionLogQuery {
         self.offset = Some(offset);
         self
     }
+
+    /// Whether a log passes the filters of the query (ignores limit and offset)
+    pub fn matches(&self, log: &ExecutionLog) -> bool {
+        let executor = log.executor();
+
+        self.execution_type.is_none_or(|t| log.execution_type() == t)
+            && self.resource_id.as_deref().is_none_or(|id| log.resource_id() == id)
+            && self.status.is_none_or(|s| log.status() == s)
+            && self
+                .api_key_id
+                .as_ref()
+                .is_none_or(|id| executor.api_key_id.as_ref() == Some(id))
+            && self
+                .user_id
+                .as_ref()
+                .is_none_or(|id| executor.user_id.as_ref() == Some(id))
+            && self
+                .team_id
+                .as_ref()
+                .is_none_or(|id| executor.team_id.as_ref() == Some(id))
+            && self
+                .payload_captured
+                .is_none_or(|captured| log.payload_captured() == captured)
+            && self
+                .injection_detected
+                .is_none_or(|detected| log.injection().is_some() == detected)
+            && self.stream_aborted.is_none_or(|aborted| {
+                log.streaming().is_some_and(|s| s.aborted) == aborted
+            })
+            && self
+                .tags
+                .iter()
+                .all(|(key, value)| log.tags().get(key) == Some(value))
+            && self
+                .request_id
+                .as_deref()
+                .is_none_or(|id| log.request_id() == Some(id))
+            && self.from_date.is_none_or(|from| log.created_at() >= from)
+            && self.to_date.is_none_or(|to| log.created_at() <= to)
+    }
 }
 
 /// Aggregated execution statistics
@@ -693,4 +978,85 @@ mod tests {
         assert_eq!(query.status, Some(ExecutionStatus::Success));
         assert_eq!(query.limit, Some(10));
     }
+
+    #[test]
+    fn test_execution_log_query_matches() {
+        let log = ExecutionLog::failed(
+            ExecutionType::Workflow,
+            "triage",
+            "Step failed",
+            120,
+            Executor::from_api_key("key-1").with_team("team-1"),
+        );
+
+        assert!(ExecutionLogQuery::new().matches(&log));
+        assert!(ExecutionLogQuery::new()
+            .with_execution_type(ExecutionType::Workflow)
+            .with_resource_id("triage")
+            .with_status(ExecutionStatus::Failed)
+            .with_team_id("team-1")
+            .with_api_key_id("key-1")
+            .matches(&log));
+
+        assert!(!ExecutionLogQuery::new().with_team_id("team-2").matches(&log));
+        assert!(!ExecutionLogQuery::new()
+            .with_status(ExecutionStatus::Success)
+            .matches(&log));
+        assert!(!ExecutionLogQuery::new().with_user_id("user-1").matches(&log));
+        assert!(!ExecutionLogQuery::new()
+            .with_payload_captured(true)
+            .matches(&log));
+        assert!(!ExecutionLogQuery::new()
+            .with_injection_detected(true)
+            .matches(&log));
+        assert!(!ExecutionLogQuery::new()
+            .with_stream_aborted(true)
+            .matches(&log));
+        assert!(ExecutionLogQuery::new()
+            .with_stream_aborted(false)
+            .matches(&log));
+        assert!(!ExecutionLogQuery::new()
+            .with_request_id("req-1")
+            .matches(&log));
+        assert!(ExecutionLogQuery::new()
+            .with_request_id("req-1")
+            .matches(&log.clone().with_request_id("req-1")));
+    }
+
+    #[test]
+    fn test_streaming_metrics() {
+        let metrics = StreamingMetrics::new(
+            Some(Duration::from_millis(500)),
+            Duration::from_millis(2500),
+            100,
+            40,
+        );
+
+        assert_eq!(metrics.time_to_first_token_ms, Some(500));
+        assert_eq!(metrics.tokens_per_second, Some(50.0));
+        assert!(!metrics.aborted);
+
+        // No content arrived: no first token and no rate
+        let metrics = StreamingMetrics::new(None, Duration::from_millis(300), 0, 0)
+            .with_abort_reason(StreamAbortReason::ProviderError);
+
+        assert_eq!(metrics.time_to_first_token_ms, None);
+        assert_eq!(metrics.tokens_per_second, None);
+        assert!(metrics.aborted);
+
+        let log = ExecutionLog::success(
+            ExecutionType::ChatCompletion,
+            "gpt-4",
+            300,
+            Executor::from_api_key("key-1"),
+        )
+        .with_streaming(metrics);
+
+        assert!(ExecutionLogQuery::new()
+            .with_stream_aborted(true)
+            .matches(&log));
+
+        let json = serde_json::to_value(&log).unwrap();
+        assert_eq!(json["streaming"]["abort_reason"], "provider_error");
+    }
 }
@@ -10,6 +10,7 @@ pub use entity::{
 };
 pub use execution_log::{
     ExecutionLog, ExecutionLogId, ExecutionLogQuery, ExecutionLogValidationError, ExecutionStats,
-    ExecutionStatus, ExecutionType, Executor, TokenUsage, WorkflowStepLog,
+    ExecutionStatus, ExecutionType, Executor, ShadowComparison, StreamAbortReason,
+    StreamingMetrics, TokenUsage, WorkflowStepLog,
 };
 pub use repository::{ConfigRepository, ExecutionLogRepository};
@@ -17,6 +17,9 @@ pub enum CredentialType {
     // HTTP API Credential
     /// API key for external HTTP APIs (used by HTTP Request workflow steps)
     HttpApiKey,
+    // Development
+    /// Builtin mock LLM provider returning canned responses
+    Mock,
     // Custom
     Custom(String),
 }
@@ -94,6 +97,7 @@ impl std::fmt::Display for CredentialType {
             CredentialType::AwsKnowledgeBase => write!(f, "aws_knowledge_base"),
             CredentialType::Pinecone => write!(f, "pinecone"),
             CredentialType::HttpApiKey => write!(f, "http_api_key"),
+            CredentialType::Mock => write!(f, "mock"),
             CredentialType::Custom(name) => write!(f, "custom:{}", name),
         }
     }
@@ -6,6 +6,8 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 use super::CredentialType;
+use crate::domain::organization::OrganizationId;
+use crate::domain::residency::Region;
 use crate::domain::storage::{StorageEntity, StorageKey};
 use crate::domain::DomainError;
 
@@ -98,6 +100,13 @@ pub struct StoredCredential {
     /// Header value template for HTTP API Key credentials (e.g., "Bearer ${api-key}")
     #[serde(skip_serializing_if = "Option::is_none")]
     header_value: Option<String>,
+    /// Organization sharing the credential with all of its teams
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    organization_id: Option<OrganizationId>,
+    /// Region the provider endpoint serves from, checked against the allowed
+    /// regions of pinned teams
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
     enabled: bool,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
@@ -120,6 +129,8 @@ impl StoredCredential {
             endpoint: None,
             deployment: None,
             header_value: None,
+            organization_id: None,
+            region: None,
             enabled: true,
             created_at: now,
             updated_at: now,
@@ -144,6 +155,18 @@ impl StoredCredential {
         self
     }
 
+    /// Set the owning organization
+    pub fn with_organization_id(mut self, organization_id: OrganizationId) -> Self {
+        self.organization_id = Some(organization_id);
+        self
+    }
+
+    /// Set the region the provider endpoint serves from
+    pub fn with_region(mut self, region: Region) -> Self {
+        self.region = Some(region);
+        self
+    }
+
     /// Set enabled status
     pub fn with_enabled(mut self, enabled: bool) -> Self {
         self.enabled = enabled;
@@ -179,6 +202,14 @@ impl StoredCredential {
         self.header_value.as_deref()
     }
 
+    pub fn organization_id(&self) -> Option<&OrganizationId> {
+        self.organization_id.as_ref()
+    }
+
+    pub fn region(&self) -> Option<&Region> {
+        self.region.as_ref()
+    }
+
     pub fn is_enabled(&self) -> bool {
         self.enabled
     }
@@ -227,6 +258,18 @@ impl StoredCredential {
         self.updated_at = Utc::now();
     }
 
+    /// Change the owning organization, `None` makes the credential global
+    pub fn set_organization_id(&mut self, organization_id: Option<OrganizationId>) {
+        self.organization_id = organization_id;
+        self.updated_at = Utc::now();
+    }
+
+    /// Change the region, `None` leaves it unknown
+    pub fn set_region(&mut self, region: Option<Region>) {
+        self.region = region;
+        self.updated_at = Utc::now();
+    }
+
     /// Convert to a Credential domain object for use with providers
     pub fn to_credential(&self) -> super::Credential {
         let mut cred = super::Credential::new(self.credential_type.clone(), self.api_key.clone());
@@ -371,4 +414,34 @@ mod tests {
         assert_eq!(cred.deployment(), Some("Authorization"));
         assert_eq!(cred.header_value(), Some("Bearer ${api-key}"));
     }
+
+    #[test]
+    fn test_stored_credential_organization() {
+        let id = CredentialId::new("shared").unwrap();
+        let mut cred = StoredCredential::new(id, "Shared", CredentialType::OpenAi, "key")
+            .with_organization_id(OrganizationId::new("acme").unwrap());
+
+        let json = serde_json::to_string(&cred).unwrap();
+        let parsed: StoredCredential = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.organization_id().map(|o| o.as_str()), Some("acme"));
+
+        cred.set_organization_id(None);
+        assert!(cred.organization_id().is_none());
+    }
+
+    #[test]
+    fn test_stored_credential_region() {
+        let id = CredentialId::new("openai-eu").unwrap();
+        let mut cred = StoredCredential::new(id, "EU", CredentialType::OpenAi, "key");
+        assert!(!serde_json::to_string(&cred).unwrap().contains("region"));
+
+        cred = cred.with_region(Region::new("eu-west-1").unwrap());
+
+        let json = serde_json::to_string(&cred).unwrap();
+        let parsed: StoredCredential = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.region().map(|r| r.as_str()), Some("eu-west-1"));
+
+        cred.set_region(None);
+        assert!(cred.region().is_none());
+    }
 }
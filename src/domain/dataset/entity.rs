@@ -0,0 +1,320 @@
+//! Dataset entities
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::storage::{StorageEntity, StorageKey};
+use crate::domain::{validate_model_id, DomainError, ModelValidationError};
+
+/// Maximum number of rows in a dataset version
+pub const MAX_DATASET_ROWS: usize = 10_000;
+
+/// Column or field holding the expected output of a row
+pub const EXPECTED_OUTPUT_FIELD: &str = "expected_output";
+
+/// Dataset identifier
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct DatasetId(String);
+
+impl DatasetId {
+    pub fn new(id: impl Into<String>) -> Result<Self, ModelValidationError> {
+        let id = id.into();
+        validate_model_id(&id)?;
+        Ok(Self(id))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for DatasetId {
+    type Error = ModelValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<DatasetId> for String {
+    fn from(id: DatasetId) -> Self {
+        id.0
+    }
+}
+
+impl std::fmt::Display for DatasetId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StorageKey for DatasetId {
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// A named collection of evaluation rows. Rows live in immutable versions;
+/// the dataset only tracks the latest one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dataset {
+    /// Unique identifier
+    id: DatasetId,
+    /// Display name
+    name: String,
+    /// Description of the data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    /// Number of the latest version, versions start at 1
+    latest_version: u32,
+    /// Number of rows in the latest version
+    row_count: usize,
+    /// Creation timestamp
+    created_at: DateTime<Utc>,
+    /// Last update timestamp
+    updated_at: DateTime<Utc>,
+}
+
+impl Dataset {
+    /// Create a dataset whose first version has `row_count` rows
+    pub fn new(id: DatasetId, name: impl Into<String>, row_count: usize) -> Self {
+        let now = Utc::now();
+        Self {
+            id,
+            name: name.into(),
+            description: None,
+            latest_version: 1,
+            row_count,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    // Builder methods
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    // Getters
+    pub fn id(&self) -> &DatasetId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn latest_version(&self) -> u32 {
+        self.latest_version
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    // Mutators
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.name = name.into();
+        self.touch();
+    }
+
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.description = description;
+        self.touch();
+    }
+
+    /// Move to a new version with `row_count` rows, returning its number
+    pub fn bump_version(&mut self, row_count: usize) -> u32 {
+        self.latest_version += 1;
+        self.row_count = row_count;
+        self.touch();
+        self.latest_version
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+    }
+}
+
+impl StorageEntity for Dataset {
+    type Key = DatasetId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+/// A row of a dataset: named inputs and the output expected for them
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DatasetRow {
+    /// Input values, used as prompt variables or workflow input fields
+    #[serde(default)]
+    pub input: HashMap<String, String>,
+    /// Output expected for the inputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_output: Option<String>,
+}
+
+/// Identifier of a dataset version: `<dataset_id>@<version>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DatasetVersionId(String);
+
+impl DatasetVersionId {
+    /// ID of a version of a dataset
+    pub fn for_version(dataset_id: &str, version: u32) -> Self {
+        Self(format!("{}@{}", dataset_id, version))
+    }
+
+    /// Get the inner string value
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for DatasetVersionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StorageKey for DatasetVersionId {
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Immutable snapshot of the rows of a dataset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetVersion {
+    pub id: DatasetVersionId,
+    pub dataset_id: DatasetId,
+    pub version: u32,
+    pub rows: Vec<DatasetRow>,
+    /// What changed in this version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DatasetVersion {
+    /// Create a version of a dataset
+    pub fn new(dataset_id: DatasetId, version: u32, rows: Vec<DatasetRow>) -> Self {
+        Self {
+            id: DatasetVersionId::for_version(dataset_id.as_str(), version),
+            dataset_id,
+            version,
+            rows,
+            note: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    /// Check the rows can be stored as a version
+    pub fn validate_rows(rows: &[DatasetRow]) -> Result<(), DomainError> {
+        if rows.is_empty() {
+            return Err(DomainError::validation(
+                "Dataset must contain at least one row",
+            ));
+        }
+
+        if rows.len() > MAX_DATASET_ROWS {
+            return Err(DomainError::validation(format!(
+                "Dataset exceeds the maximum of {} rows",
+                MAX_DATASET_ROWS
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl StorageEntity for DatasetVersion {
+    type Key = DatasetVersionId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+/// Reference to a pinned dataset version, so evaluation results can be
+/// reproduced against the exact rows they ran on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DatasetRef {
+    pub dataset_id: String,
+    pub version: u32,
+}
+
+impl DatasetRef {
+    pub fn new(dataset_id: impl Into<String>, version: u32) -> Self {
+        Self {
+            dataset_id: dataset_id.into(),
+            version,
+        }
+    }
+}
+
+impl std::fmt::Display for DatasetRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.dataset_id, self.version)
+    }
+}
+
+/// Dataset requested by a test case or experiment. Without a version the
+/// latest one is pinned when the reference is saved.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DatasetSelector {
+    pub dataset_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bump_version() {
+        let mut dataset = Dataset::new(DatasetId::new("faq").unwrap(), "FAQ", 3);
+        assert_eq!(dataset.latest_version(), 1);
+
+        assert_eq!(dataset.bump_version(5), 2);
+        assert_eq!(dataset.latest_version(), 2);
+        assert_eq!(dataset.row_count(), 5);
+    }
+
+    #[test]
+    fn test_version_id_and_ref() {
+        let version = DatasetVersion::new(DatasetId::new("faq").unwrap(), 2, vec![]);
+        assert_eq!(version.id.as_str(), "faq@2");
+        assert_eq!(DatasetRef::new("faq", 2).to_string(), "faq@2");
+    }
+
+    #[test]
+    fn test_validate_rows() {
+        assert!(DatasetVersion::validate_rows(&[]).is_err());
+        assert!(DatasetVersion::validate_rows(&[DatasetRow::default()]).is_ok());
+    }
+}
@@ -0,0 +1,161 @@
+//! Dataset import from CSV and JSONL
+//!
+//! CSV files need a header row; every column is an input except
+//! `expected_output`. JSONL files hold one object per line, either with an
+//! `input` object or with the inputs as top-level fields, plus an optional
+//! `expected_output`. Non-string JSON values are kept as their JSON text.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::entity::{DatasetRow, EXPECTED_OUTPUT_FIELD};
+use crate::domain::DomainError;
+
+/// Format of imported dataset content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DatasetFormat {
+    Csv,
+    Jsonl,
+}
+
+impl DatasetFormat {
+    /// Parse a format name
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "jsonl" | "ndjson" => Some(Self::Jsonl),
+            _ => None,
+        }
+    }
+}
+
+/// Parse dataset rows from CSV or JSONL content
+pub fn parse_rows(format: DatasetFormat, content: &str) -> Result<Vec<DatasetRow>, DomainError> {
+    match format {
+        DatasetFormat::Csv => parse_csv(content),
+        DatasetFormat::Jsonl => parse_jsonl(content),
+    }
+}
+
+fn parse_csv(content: &str) -> Result<Vec<DatasetRow>, DomainError> {
+    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let headers = reader
+        .headers()
+        .map_err(|e| DomainError::validation(format!("Invalid CSV header: {}", e)))?
+        .clone();
+
+    reader
+        .records()
+        .enumerate()
+        .map(|(index, record)| {
+            let record = record.map_err(|e| {
+                DomainError::validation(format!("Invalid CSV row {}: {}", index + 1, e))
+            })?;
+            let mut row = DatasetRow::default();
+
+            for (header, value) in headers.iter().zip(record.iter()) {
+                if header == EXPECTED_OUTPUT_FIELD {
+                    row.expected_output = Some(value.to_string());
+                } else {
+                    row.input.insert(header.to_string(), value.to_string());
+                }
+            }
+
+            Ok(row)
+        })
+        .collect()
+}
+
+fn parse_jsonl(content: &str) -> Result<Vec<DatasetRow>, DomainError> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let invalid = |message: String| {
+                DomainError::validation(format!("Invalid JSONL line {}: {}", index + 1, message))
+            };
+
+            let mut object = match serde_json::from_str::<Value>(line) {
+                Ok(Value::Object(object)) => object,
+                Ok(_) => return Err(invalid("expected a JSON object".to_string())),
+                Err(e) => return Err(invalid(e.to_string())),
+            };
+
+            let expected_output = object.remove(EXPECTED_OUTPUT_FIELD).map(value_to_string);
+            let fields = match object.remove("input") {
+                Some(Value::Object(input)) => input,
+                Some(_) => return Err(invalid("\"input\" must be an object".to_string())),
+                None => object,
+            };
+
+            Ok(DatasetRow {
+                input: fields
+                    .into_iter()
+                    .map(|(key, value)| (key, value_to_string(value)))
+                    .collect::<HashMap<_, _>>(),
+                expected_output,
+            })
+        })
+        .collect()
+}
+
+fn value_to_string(value: Value) -> String {
+    match value {
+        Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let rows = parse_rows(
+            DatasetFormat::Csv,
+            "question,expected_output\n\"Hi, there\",Hello\nBye,Goodbye\n",
+        )
+        .unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].input["question"], "Hi, there");
+        assert_eq!(rows[0].expected_output.as_deref(), Some("Hello"));
+        assert!(!rows[0].input.contains_key(EXPECTED_OUTPUT_FIELD));
+    }
+
+    #[test]
+    fn test_parse_csv_invalid_row() {
+        let result = parse_rows(DatasetFormat::Csv, "a,b\n1,2,3\n");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_jsonl() {
+        let content = r#"{"input": {"question": "Hi", "count": 2}, "expected_output": "Hello"}
+
+{"question": "Bye"}
+"#;
+        let rows = parse_rows(DatasetFormat::Jsonl, content).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].input["count"], "2");
+        assert_eq!(rows[0].expected_output.as_deref(), Some("Hello"));
+        assert_eq!(rows[1].input["question"], "Bye");
+        assert!(rows[1].expected_output.is_none());
+
+        assert!(parse_rows(DatasetFormat::Jsonl, "[1, 2]").is_err());
+        assert!(parse_rows(DatasetFormat::Jsonl, "{\"input\": 3}").is_err());
+    }
+
+    #[test]
+    fn test_format_parse() {
+        assert_eq!(DatasetFormat::parse("CSV"), Some(DatasetFormat::Csv));
+        assert_eq!(DatasetFormat::parse("ndjson"), Some(DatasetFormat::Jsonl));
+        assert_eq!(DatasetFormat::parse("xml"), None);
+    }
+}
@@ -0,0 +1,10 @@
+//! Dataset domain module for versioned evaluation data referenced by test
+//! cases and experiments
+
+mod entity;
+mod import;
+mod repository;
+
+pub use entity::*;
+pub use import::*;
+pub use repository::*;
@@ -0,0 +1,36 @@
+//! Dataset repository trait
+
+use async_trait::async_trait;
+use std::fmt::Debug;
+
+use super::{Dataset, DatasetId, DatasetVersion};
+use crate::domain::DomainError;
+
+/// Repository for datasets and their versions
+#[async_trait]
+pub trait DatasetRepository: Send + Sync + Debug {
+    /// Get a dataset by ID
+    async fn get(&self, id: &DatasetId) -> Result<Option<Dataset>, DomainError>;
+
+    /// List every dataset, ordered by name
+    async fn list(&self) -> Result<Vec<Dataset>, DomainError>;
+
+    /// Create or replace a dataset
+    async fn save(&self, dataset: Dataset) -> Result<Dataset, DomainError>;
+
+    /// Delete a dataset and all its versions
+    async fn delete(&self, id: &DatasetId) -> Result<bool, DomainError>;
+
+    /// Store a new version
+    async fn save_version(&self, version: DatasetVersion) -> Result<DatasetVersion, DomainError>;
+
+    /// Get a version of a dataset
+    async fn get_version(
+        &self,
+        id: &DatasetId,
+        version: u32,
+    ) -> Result<Option<DatasetVersion>, DomainError>;
+
+    /// List the versions of a dataset, newest first
+    async fn list_versions(&self, id: &DatasetId) -> Result<Vec<DatasetVersion>, DomainError>;
+}
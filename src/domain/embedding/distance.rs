@@ -0,0 +1,122 @@
+//! Distance metrics between embeddings
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::response::cosine_similarity;
+
+/// Embedding models known to return unit-length vectors, by model name prefix
+const NORMALIZED_MODEL_PREFIXES: &[&str] = &["text-embedding-", "amazon.titan-embed-text-v2"];
+
+/// Distance metric for vector similarity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DistanceMetric {
+    /// Cosine distance (1 - cosine similarity)
+    #[default]
+    Cosine,
+    /// Euclidean (L2) distance
+    #[serde(rename = "l2", alias = "euclidean")]
+    Euclidean,
+    /// Inner product (negative dot product for similarity)
+    InnerProduct,
+}
+
+impl DistanceMetric {
+    /// Convert distance to similarity score (0-1)
+    pub fn to_similarity(&self, distance: f64) -> f32 {
+        match self {
+            Self::Cosine => (1.0 - distance) as f32,
+            Self::Euclidean => {
+                // Convert to similarity: 1 / (1 + distance)
+                (1.0 / (1.0 + distance)) as f32
+            }
+            Self::InnerProduct => {
+                // Inner product is already a similarity (negated in pgvector)
+                (-distance) as f32
+            }
+        }
+    }
+
+    /// Similarity score of two vectors, on the scale of `to_similarity`
+    pub fn similarity(&self, a: &[f32], b: &[f32]) -> f32 {
+        if a.len() != b.len() || a.is_empty() {
+            return 0.0;
+        }
+
+        match self {
+            Self::Cosine => cosine_similarity(a, b),
+            Self::Euclidean => {
+                let distance: f32 = a
+                    .iter()
+                    .zip(b)
+                    .map(|(x, y)| (x - y) * (x - y))
+                    .sum::<f32>()
+                    .sqrt();
+                1.0 / (1.0 + distance)
+            }
+            Self::InnerProduct => a.iter().zip(b).map(|(x, y)| x * y).sum(),
+        }
+    }
+
+    /// Whether scores are only meaningful for unit-length embeddings; the
+    /// inner product of unnormalized vectors grows with their length
+    pub fn requires_normalized_embeddings(&self) -> bool {
+        matches!(self, Self::InnerProduct)
+    }
+
+    /// Whether the metric suits the embeddings of `model`
+    pub fn supports_model(&self, model: &str) -> bool {
+        !self.requires_normalized_embeddings()
+            || NORMALIZED_MODEL_PREFIXES
+                .iter()
+                .any(|prefix| model.starts_with(prefix))
+    }
+}
+
+impl std::fmt::Display for DistanceMetric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cosine => write!(f, "cosine"),
+            Self::Euclidean => write!(f, "l2"),
+            Self::InnerProduct => write!(f, "inner_product"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similarity() {
+        let a = [0.6, 0.8];
+        let b = [0.8, 0.6];
+
+        assert!((DistanceMetric::Cosine.similarity(&a, &a) - 1.0).abs() < 0.001);
+        assert!((DistanceMetric::InnerProduct.similarity(&a, &b) - 0.96).abs() < 0.001);
+        assert!((DistanceMetric::Euclidean.similarity(&a, &a) - 1.0).abs() < 0.001);
+        assert!(DistanceMetric::Euclidean.similarity(&a, &b) < 1.0);
+        assert_eq!(DistanceMetric::Euclidean.similarity(&a, &[1.0]), 0.0);
+    }
+
+    #[test]
+    fn test_supports_model() {
+        assert!(DistanceMetric::InnerProduct.supports_model("text-embedding-3-small"));
+        assert!(!DistanceMetric::InnerProduct.supports_model("nomic-embed-text"));
+        assert!(DistanceMetric::Euclidean.supports_model("nomic-embed-text"));
+    }
+
+    #[test]
+    fn test_serde() {
+        assert_eq!(
+            serde_json::from_str::<DistanceMetric>("\"euclidean\"").unwrap(),
+            DistanceMetric::Euclidean
+        );
+        assert_eq!(
+            serde_json::to_string(&DistanceMetric::InnerProduct).unwrap(),
+            "\"inner_product\""
+        );
+        assert_eq!(DistanceMetric::Euclidean.to_string(), "l2");
+    }
+}
@@ -1,9 +1,11 @@
 //! Embedding provider domain models and traits
 
+mod distance;
 mod provider;
 mod request;
 mod response;
 
+pub use distance::DistanceMetric;
 pub use provider::EmbeddingProvider;
 pub use request::{EmbeddingInput, EmbeddingRequest};
 pub use response::{cosine_similarity, Embedding, EmbeddingResponse, EmbeddingUsage};
@@ -1,4 +1,78 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::domain::llm::ProviderRateLimit;
+
+/// A request field rejected by validation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct FieldViolation {
+    /// Path of the field, e.g. `config.temperature` or `steps[0].name`
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldViolation {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for FieldViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Collects every field violation of a request before rejecting it, so
+/// callers can fix all of them in one round trip
+#[derive(Debug, Clone, Default)]
+pub struct FieldViolations {
+    violations: Vec<FieldViolation>,
+}
+
+impl FieldViolations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.violations.push(FieldViolation::new(field, message));
+    }
+
+    /// Record the error of a validation result against `field`
+    pub fn check<E: fmt::Display>(&mut self, field: impl Into<String>, result: Result<(), E>) {
+        if let Err(e) = result {
+            self.push(field, e.to_string());
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// `DomainError::InvalidFields` when any violation was recorded
+    pub fn into_result(self) -> Result<(), DomainError> {
+        if self.violations.is_empty() {
+            Ok(())
+        } else {
+            Err(DomainError::invalid_fields(self.violations))
+        }
+    }
+}
+
+fn join_violations(violations: &[FieldViolation]) -> String {
+    violations
+        .iter()
+        .map(FieldViolation::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
 
 /// Core domain errors
 #[derive(Debug, Error)]
@@ -9,6 +83,9 @@ pub enum DomainError {
     #[error("Validation error: {message}")]
     Validation { message: String },
 
+    #[error("Validation error: {}", join_violations(violations))]
+    InvalidFields { violations: Vec<FieldViolation> },
+
     #[error("Invalid ID format: {message}")]
     InvalidId { message: String },
 
@@ -16,7 +93,12 @@ pub enum DomainError {
     Credential { message: String },
 
     #[error("Provider error: {provider} - {message}")]
-    Provider { provider: String, message: String },
+    Provider {
+        provider: String,
+        message: String,
+        /// Rate limit hints of the failed provider response
+        rate_limit: Option<Box<ProviderRateLimit>>,
+    },
 
     #[error("Configuration error: {message}")]
     Configuration { message: String },
@@ -56,6 +138,14 @@ impl DomainError {
         }
     }
 
+    pub fn invalid_fields(violations: Vec<FieldViolation>) -> Self {
+        Self::InvalidFields { violations }
+    }
+
+    pub fn invalid_field(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::invalid_fields(vec![FieldViolation::new(field, message)])
+    }
+
     pub fn invalid_id(message: impl Into<String>) -> Self {
         Self::InvalidId {
             message: message.into(),
@@ -72,6 +162,47 @@ impl DomainError {
         Self::Provider {
             provider: provider.into(),
             message: message.into(),
+            rate_limit: None,
+        }
+    }
+
+    /// Attribute a provider error raised by a shared transport (the HTTP
+    /// client) to the provider that made the call; other errors are kept
+    pub fn with_provider(self, provider: impl Into<String>) -> Self {
+        match self {
+            Self::Provider {
+                message,
+                rate_limit,
+                ..
+            } => Self::Provider {
+                provider: provider.into(),
+                message,
+                rate_limit,
+            },
+            other => other,
+        }
+    }
+
+    /// Attach the rate limit hints of the provider response to a provider
+    /// error; other errors are kept
+    pub fn with_rate_limit(self, limit: ProviderRateLimit) -> Self {
+        match self {
+            Self::Provider {
+                provider, message, ..
+            } => Self::Provider {
+                provider,
+                message,
+                rate_limit: Some(Box::new(limit)),
+            },
+            other => other,
+        }
+    }
+
+    /// Rate limit hints of a provider error
+    pub fn rate_limit(&self) -> Option<&ProviderRateLimit> {
+        match self {
+            Self::Provider { rate_limit, .. } => rate_limit.as_deref(),
+            _ => None,
         }
     }
 
@@ -120,6 +251,26 @@ mod tests {
         assert_eq!(error.to_string(), "Validation error: Invalid input");
     }
 
+    #[test]
+    fn test_field_violations() {
+        assert!(FieldViolations::new().into_result().is_ok());
+
+        let mut violations = FieldViolations::new();
+        violations.check("name", Ok::<(), String>(()));
+        violations.check("config.temperature", Err("must be between 0 and 2"));
+        violations.push("steps", "at least one step is required");
+
+        let error = violations.into_result().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Validation error: config.temperature: must be between 0 and 2; steps: at least one step is required"
+        );
+        assert!(matches!(
+            error,
+            DomainError::InvalidFields { violations } if violations.len() == 2
+        ));
+    }
+
     #[test]
     fn test_conflict_error() {
         let error = DomainError::conflict("Resource already exists");
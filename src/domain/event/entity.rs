@@ -0,0 +1,208 @@
+//! Gateway event entities
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::audit::AuditLog;
+use crate::domain::webhook::WebhookDelivery;
+use crate::domain::DomainError;
+
+/// Kinds of gateway activity published to the event stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GatewayEventType {
+    /// A chat request was served and its usage recorded
+    RequestCompleted,
+    /// A request was rejected because a budget is exhausted
+    BudgetExceeded,
+    /// An admin API call created, changed or deleted an entity
+    EntityChanged,
+    /// A webhook delivery attempt failed
+    WebhookFailed,
+}
+
+impl GatewayEventType {
+    /// Returns the event type as a string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RequestCompleted => "request_completed",
+            Self::BudgetExceeded => "budget_exceeded",
+            Self::EntityChanged => "entity_changed",
+            Self::WebhookFailed => "webhook_failed",
+        }
+    }
+}
+
+impl fmt::Display for GatewayEventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for GatewayEventType {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "request_completed" => Ok(Self::RequestCompleted),
+            "budget_exceeded" => Ok(Self::BudgetExceeded),
+            "entity_changed" => Ok(Self::EntityChanged),
+            "webhook_failed" => Ok(Self::WebhookFailed),
+            other => Err(DomainError::validation(format!(
+                "Unknown event type '{}', expected 'request_completed', 'budget_exceeded', 'entity_changed' or 'webhook_failed'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Event published to the event stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayEvent {
+    /// Unique event ID
+    pub id: String,
+    /// Event type
+    pub event_type: GatewayEventType,
+    /// When the event occurred
+    pub timestamp: DateTime<Utc>,
+    /// Entity the event is about (API key, entity or webhook ID), used as
+    /// the message key so events of one subject stay ordered
+    pub subject: String,
+    /// Event-specific data
+    pub data: serde_json::Value,
+}
+
+impl GatewayEvent {
+    /// Creates a new event
+    pub fn new(
+        event_type: GatewayEventType,
+        subject: impl Into<String>,
+        data: serde_json::Value,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            event_type,
+            timestamp: Utc::now(),
+            subject: subject.into(),
+            data,
+        }
+    }
+
+    /// A chat request of an API key completed
+    pub fn request_completed(
+        api_key_id: &str,
+        team_id: &str,
+        model_id: &str,
+        input_tokens: u32,
+        output_tokens: u32,
+        cost_micros: i64,
+        latency_ms: u64,
+    ) -> Self {
+        Self::new(
+            GatewayEventType::RequestCompleted,
+            api_key_id,
+            serde_json::json!({
+                "api_key_id": api_key_id,
+                "team_id": team_id,
+                "model_id": model_id,
+                "input_tokens": input_tokens,
+                "output_tokens": output_tokens,
+                "cost_micros": cost_micros,
+                "latency_ms": latency_ms,
+            }),
+        )
+    }
+
+    /// A request of an API key was rejected by exhausted budgets
+    pub fn budget_exceeded(api_key_id: &str, model_id: Option<&str>, budget_ids: &[&str]) -> Self {
+        Self::new(
+            GatewayEventType::BudgetExceeded,
+            api_key_id,
+            serde_json::json!({
+                "api_key_id": api_key_id,
+                "model_id": model_id,
+                "budget_ids": budget_ids,
+            }),
+        )
+    }
+
+    /// An audited admin change succeeded
+    pub fn entity_changed(log: &AuditLog) -> Self {
+        let subject = match log.entity_id() {
+            Some(id) => format!("{}/{}", log.entity_type(), id),
+            None => log.entity_type().to_string(),
+        };
+
+        Self::new(
+            GatewayEventType::EntityChanged,
+            subject,
+            serde_json::json!({
+                "audit_log_id": log.id().as_str(),
+                "actor": log.actor(),
+                "action": log.action(),
+                "entity_type": log.entity_type(),
+                "entity_id": log.entity_id(),
+                "changes": log.changes(),
+            }),
+        )
+    }
+
+    /// A webhook delivery attempt failed
+    pub fn webhook_failed(delivery: &WebhookDelivery) -> Self {
+        Self::new(
+            GatewayEventType::WebhookFailed,
+            delivery.webhook_id.as_str(),
+            serde_json::json!({
+                "webhook_id": delivery.webhook_id.as_str(),
+                "delivery_id": delivery.id.as_str(),
+                "webhook_event": delivery.event_type.as_str(),
+                "status": delivery.status,
+                "attempts": delivery.attempts,
+                "response_status": delivery.response_status,
+                "error": delivery.error_message,
+                "next_retry_at": delivery.next_retry_at,
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::audit::AuditActor;
+
+    #[test]
+    fn test_event_type_round_trip() {
+        for event_type in [
+            GatewayEventType::RequestCompleted,
+            GatewayEventType::BudgetExceeded,
+            GatewayEventType::EntityChanged,
+            GatewayEventType::WebhookFailed,
+        ] {
+            assert_eq!(
+                event_type.as_str().parse::<GatewayEventType>().unwrap(),
+                event_type
+            );
+        }
+
+        assert!("request_failed".parse::<GatewayEventType>().is_err());
+    }
+
+    #[test]
+    fn test_entity_changed_event() {
+        let log = AuditLog::new(AuditActor::user("user-1", "alice"), "update", "teams")
+            .with_entity_id("team-1")
+            .with_changes(serde_json::json!({"name": "Platform"}));
+
+        let event = GatewayEvent::entity_changed(&log);
+        let json = serde_json::to_value(&event).unwrap();
+
+        assert_eq!(json["event_type"], "entity_changed");
+        assert_eq!(json["subject"], "teams/team-1");
+        assert_eq!(json["data"]["action"], "update");
+        assert_eq!(json["data"]["changes"]["name"], "Platform");
+    }
+}
@@ -0,0 +1,7 @@
+//! Event domain module for streaming gateway activity to message brokers
+
+mod entity;
+mod publisher;
+
+pub use entity::*;
+pub use publisher::*;
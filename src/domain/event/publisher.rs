@@ -0,0 +1,18 @@
+//! Event publisher trait
+
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+
+use super::GatewayEvent;
+use crate::domain::DomainError;
+
+/// Message broker gateway events are published to (Kafka, NATS)
+#[async_trait]
+pub trait EventPublisher: Send + Sync + Debug {
+    /// Broker name used in logs
+    fn name(&self) -> &str;
+
+    /// Publish an event to a topic (Kafka) or subject (NATS)
+    async fn publish(&self, topic: &str, event: &GatewayEvent) -> Result<(), DomainError>;
+}
@@ -2,9 +2,17 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::step_override::StepOverride;
+
 /// Configuration overrides that can be applied to a request
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ConfigOverrides {
+    /// Prompt override
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_id: Option<String>,
+    /// Version of the prompt override, the current one when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_version: Option<u32>,
     /// Temperature override
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
@@ -28,6 +36,18 @@ impl ConfigOverrides {
         Self::default()
     }
 
+    /// Set prompt override
+    pub fn with_prompt_id(mut self, prompt_id: impl Into<String>) -> Self {
+        self.prompt_id = Some(prompt_id.into());
+        self
+    }
+
+    /// Pin the version of the prompt override
+    pub fn with_prompt_version(mut self, prompt_version: u32) -> Self {
+        self.prompt_version = Some(prompt_version);
+        self
+    }
+
     /// Set temperature override
     pub fn with_temperature(mut self, temperature: f32) -> Self {
         self.temperature = Some(temperature);
@@ -60,7 +80,8 @@ impl ConfigOverrides {
 
     /// Check if any overrides are set
     pub fn has_overrides(&self) -> bool {
-        self.temperature.is_some()
+        self.prompt_id.is_some()
+            || self.temperature.is_some()
             || self.max_tokens.is_some()
             || self.top_p.is_some()
             || self.presence_penalty.is_some()
@@ -113,6 +134,13 @@ impl AssignmentResult {
             .unwrap_or(false)
     }
 
+    /// Get the prompt override if set
+    pub fn prompt_id(&self) -> Option<&str> {
+        self.config_overrides
+            .as_ref()
+            .and_then(|o| o.prompt_id.as_deref())
+    }
+
     /// Get the temperature override if set
     pub fn temperature(&self) -> Option<f32> {
         self.config_overrides.as_ref().and_then(|o| o.temperature)
@@ -143,6 +171,20 @@ impl AssignmentResult {
     }
 }
 
+/// Result of assigning a workflow execution to an experiment variant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowAssignment {
+    /// ID of the experiment
+    pub experiment_id: String,
+    /// ID of the assigned variant
+    pub variant_id: String,
+    /// ID of the workflow to run
+    pub workflow_id: String,
+    /// Changes made to the steps of the workflow
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub step_overrides: Vec<StepOverride>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -214,6 +256,7 @@ mod tests {
         #[test]
         fn test_override_accessors() {
             let overrides = ConfigOverrides::new()
+                .with_prompt_id("support-v2")
                 .with_temperature(0.7)
                 .with_max_tokens(500)
                 .with_top_p(0.95)
@@ -223,6 +266,7 @@ mod tests {
             let result =
                 AssignmentResult::new("exp-1", "treatment", "gpt-4").with_overrides(overrides);
 
+            assert_eq!(result.prompt_id(), Some("support-v2"));
             assert_eq!(result.temperature(), Some(0.7));
             assert_eq!(result.max_tokens(), Some(500));
             assert_eq!(result.top_p(), Some(0.95));
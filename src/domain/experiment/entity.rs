@@ -3,10 +3,14 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use utoipa::ToSchema;
 
+use super::rollout::{Rollback, RolloutGuard};
+use super::step_override::StepOverride;
 use super::validation::{
     validate_experiment_id, validate_variant_id, ExperimentValidationError,
 };
+use crate::domain::dataset::DatasetRef;
 use crate::domain::storage::{StorageEntity, StorageKey};
 
 // ============================================================================
@@ -173,11 +177,145 @@ impl fmt::Display for ExperimentStatus {
     }
 }
 
+// ============================================================================
+// AssignmentKey
+// ============================================================================
+
+/// What requests are hashed by when assigning them to a variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AssignmentKey {
+    /// Every request of an API key gets the same variant
+    #[default]
+    ApiKey,
+    /// Every request of an end user (the `user` request field) gets the same
+    /// variant; requests without a user fall back to their API key
+    User,
+}
+
+impl AssignmentKey {
+    /// Value hashed to assign a request
+    pub fn subject(&self, api_key_id: &str, user: Option<&str>) -> String {
+        match (self, user) {
+            (Self::User, Some(user)) if !user.is_empty() => format!("user:{}", user),
+            _ => api_key_id.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for AssignmentKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ApiKey => write!(f, "api_key"),
+            Self::User => write!(f, "user"),
+        }
+    }
+}
+
+// ============================================================================
+// AutoStop
+// ============================================================================
+
+/// Metrics a sequential test can watch
+pub const AUTO_STOP_METRICS: [&str; 5] = [
+    "latency_ms",
+    "cost_micros",
+    "rating",
+    "thumbs_up_rate",
+    "conversion_rate",
+];
+
+/// Sequential testing of a running experiment: it completes on its own once
+/// a treatment differs significantly from the control on `metric`, or once
+/// the difference is known to be too small to matter (futility)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AutoStop {
+    /// Metric the test watches, one of [`AUTO_STOP_METRICS`]
+    #[serde(default = "default_auto_stop_metric")]
+    pub metric: String,
+    /// Significance level, the chance of stopping on a difference that
+    /// isn't real however often results are checked
+    #[serde(default = "default_auto_stop_alpha")]
+    pub alpha: f64,
+    /// Records each variant needs before the experiment can stop
+    #[serde(default = "default_auto_stop_min_samples")]
+    pub min_samples: u64,
+    /// Smallest relative change (%) worth detecting; the experiment stops
+    /// for futility once the change is known to be smaller
+    #[serde(default = "default_auto_stop_min_effect_pct")]
+    pub min_effect_pct: f64,
+    /// Records per variant after which the experiment stops for futility
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_samples: Option<u64>,
+}
+
+fn default_auto_stop_metric() -> String {
+    "latency_ms".to_string()
+}
+
+fn default_auto_stop_alpha() -> f64 {
+    0.05
+}
+
+fn default_auto_stop_min_samples() -> u64 {
+    100
+}
+
+fn default_auto_stop_min_effect_pct() -> f64 {
+    5.0
+}
+
+impl Default for AutoStop {
+    fn default() -> Self {
+        Self {
+            metric: default_auto_stop_metric(),
+            alpha: default_auto_stop_alpha(),
+            min_samples: default_auto_stop_min_samples(),
+            min_effect_pct: default_auto_stop_min_effect_pct(),
+            max_samples: None,
+        }
+    }
+}
+
+impl AutoStop {
+    /// Whether lower values of the metric are better (latency and cost)
+    pub fn lower_is_better(&self) -> bool {
+        matches!(self.metric.as_str(), "latency_ms" | "cost_micros")
+    }
+
+    /// Validate the configuration
+    pub fn validate(&self) -> Result<(), ExperimentValidationError> {
+        let invalid = |message: String| Err(ExperimentValidationError::InvalidAutoStop(message));
+
+        if !AUTO_STOP_METRICS.contains(&self.metric.as_str()) {
+            return invalid(format!(
+                "metric must be one of {}",
+                AUTO_STOP_METRICS.join(", ")
+            ));
+        }
+        if self.alpha.is_nan() || self.alpha <= 0.0 || self.alpha >= 1.0 {
+            return invalid("alpha must be between 0 and 1".to_string());
+        }
+        if self.min_samples < 2 {
+            return invalid("min_samples must be at least 2".to_string());
+        }
+        if !self.min_effect_pct.is_finite() || self.min_effect_pct <= 0.0 {
+            return invalid("min_effect_pct must be positive".to_string());
+        }
+        if self.max_samples.is_some_and(|max| max < self.min_samples) {
+            return invalid("max_samples must not be below min_samples".to_string());
+        }
+
+        Ok(())
+    }
+}
+
 // ============================================================================
 // VariantConfig
 // ============================================================================
 
-/// Configuration for a variant - either a model reference or config override
+/// Configuration for a variant - a model reference or config override, or
+/// for experiments on workflow executions, the workflow to run
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum VariantConfig {
@@ -186,6 +324,13 @@ pub enum VariantConfig {
     /// Use the same model with different configuration parameters
     ConfigOverride {
         model_id: String,
+        /// Prompt rendered in place of the request's prompt
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prompt_id: Option<String>,
+        /// Retained version of `prompt_id` to render, the current one when
+        /// unset
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prompt_version: Option<u32>,
         #[serde(skip_serializing_if = "Option::is_none")]
         temperature: Option<f32>,
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -197,6 +342,13 @@ pub enum VariantConfig {
         #[serde(skip_serializing_if = "Option::is_none")]
         frequency_penalty: Option<f32>,
     },
+    /// Run a workflow, the experiment's own or an alternative one, with
+    /// some of its steps changed
+    Workflow {
+        workflow_id: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        step_overrides: Vec<StepOverride>,
+    },
 }
 
 impl VariantConfig {
@@ -211,6 +363,8 @@ impl VariantConfig {
     pub fn config_override(model_id: impl Into<String>) -> Self {
         Self::ConfigOverride {
             model_id: model_id.into(),
+            prompt_id: None,
+            prompt_version: None,
             temperature: None,
             max_tokens: None,
             top_p: None,
@@ -219,11 +373,44 @@ impl VariantConfig {
         }
     }
 
-    /// Get the model ID for this variant
-    pub fn model_id(&self) -> &str {
+    /// Create a workflow variant with no step overrides
+    pub fn workflow(workflow_id: impl Into<String>) -> Self {
+        Self::Workflow {
+            workflow_id: workflow_id.into(),
+            step_overrides: Vec::new(),
+        }
+    }
+
+    /// Get the model ID for this variant, `None` for workflow variants
+    pub fn model_id(&self) -> Option<&str> {
+        match self {
+            Self::ModelReference { model_id } => Some(model_id),
+            Self::ConfigOverride { model_id, .. } => Some(model_id),
+            Self::Workflow { .. } => None,
+        }
+    }
+
+    /// Get the workflow ID of a workflow variant
+    pub fn workflow_id(&self) -> Option<&str> {
+        match self {
+            Self::Workflow { workflow_id, .. } => Some(workflow_id),
+            _ => None,
+        }
+    }
+
+    /// Get the step overrides of a workflow variant
+    pub fn step_overrides(&self) -> &[StepOverride] {
+        match self {
+            Self::Workflow { step_overrides, .. } => step_overrides,
+            _ => &[],
+        }
+    }
+
+    /// Get the prompt ID swapped in by this variant
+    pub fn prompt_id(&self) -> Option<&str> {
         match self {
-            Self::ModelReference { model_id } => model_id,
-            Self::ConfigOverride { model_id, .. } => model_id,
+            Self::ConfigOverride { prompt_id, .. } => prompt_id.as_deref(),
+            _ => None,
         }
     }
 
@@ -328,8 +515,8 @@ impl Variant {
         self.control
     }
 
-    /// Get the model ID from the config
-    pub fn model_id(&self) -> &str {
+    /// Get the model ID from the config, `None` for workflow variants
+    pub fn model_id(&self) -> Option<&str> {
         self.config.model_id()
     }
 }
@@ -348,6 +535,16 @@ pub struct Experiment {
     status: ExperimentStatus,
     variants: Vec<Variant>,
     traffic_allocation: Vec<TrafficAllocation>,
+    #[serde(default)]
+    assignment_key: AssignmentKey,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auto_stop: Option<AutoStop>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rollout: Option<RolloutGuard>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rollback: Option<Rollback>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dataset: Option<DatasetRef>,
     #[serde(skip_serializing_if = "Option::is_none")]
     started_at: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -368,6 +565,11 @@ impl Experiment {
             status: ExperimentStatus::Draft,
             variants: Vec::new(),
             traffic_allocation: Vec::new(),
+            assignment_key: AssignmentKey::default(),
+            auto_stop: None,
+            rollout: None,
+            rollback: None,
+            dataset: None,
             started_at: None,
             completed_at: None,
             created_at: now,
@@ -396,6 +598,30 @@ impl Experiment {
         self
     }
 
+    /// Set what requests are hashed by
+    pub fn with_assignment_key(mut self, assignment_key: AssignmentKey) -> Self {
+        self.assignment_key = assignment_key;
+        self
+    }
+
+    /// Enable sequential testing
+    pub fn with_auto_stop(mut self, auto_stop: AutoStop) -> Self {
+        self.auto_stop = Some(auto_stop);
+        self
+    }
+
+    /// Guard the treatments as a canary rollout
+    pub fn with_rollout(mut self, rollout: RolloutGuard) -> Self {
+        self.rollout = Some(rollout);
+        self
+    }
+
+    /// Pin the dataset the experiment is evaluated against
+    pub fn with_dataset(mut self, dataset: DatasetRef) -> Self {
+        self.dataset = Some(dataset);
+        self
+    }
+
     /// Set enabled status
     pub fn with_enabled(mut self, enabled: bool) -> Self {
         self.enabled = enabled;
@@ -434,6 +660,31 @@ impl Experiment {
         &self.traffic_allocation
     }
 
+    /// Get what requests are hashed by
+    pub fn assignment_key(&self) -> AssignmentKey {
+        self.assignment_key
+    }
+
+    /// Get the sequential testing configuration
+    pub fn auto_stop(&self) -> Option<&AutoStop> {
+        self.auto_stop.as_ref()
+    }
+
+    /// Get the canary rollout guard
+    pub fn rollout(&self) -> Option<&RolloutGuard> {
+        self.rollout.as_ref()
+    }
+
+    /// Get why the rollout was rolled back, if it was
+    pub fn rollback(&self) -> Option<&Rollback> {
+        self.rollback.as_ref()
+    }
+
+    /// Get the pinned evaluation dataset
+    pub fn dataset(&self) -> Option<&DatasetRef> {
+        self.dataset.as_ref()
+    }
+
     /// Get when the experiment was started
     pub fn started_at(&self) -> Option<DateTime<Utc>> {
         self.started_at
@@ -485,6 +736,30 @@ impl Experiment {
         self.touch();
     }
 
+    /// Set what requests are hashed by
+    pub fn set_assignment_key(&mut self, assignment_key: AssignmentKey) {
+        self.assignment_key = assignment_key;
+        self.touch();
+    }
+
+    /// Set or disable sequential testing
+    pub fn set_auto_stop(&mut self, auto_stop: Option<AutoStop>) {
+        self.auto_stop = auto_stop;
+        self.touch();
+    }
+
+    /// Set or disable the canary rollout guard
+    pub fn set_rollout(&mut self, rollout: Option<RolloutGuard>) {
+        self.rollout = rollout;
+        self.touch();
+    }
+
+    /// Set or clear the pinned evaluation dataset
+    pub fn set_dataset(&mut self, dataset: Option<DatasetRef>) {
+        self.dataset = dataset;
+        self.touch();
+    }
+
     /// Set all traffic allocations
     pub fn set_traffic_allocation(&mut self, allocation: Vec<TrafficAllocation>) {
         self.traffic_allocation = allocation;
@@ -547,6 +822,22 @@ impl Experiment {
         Ok(())
     }
 
+    /// Complete the experiment because a treatment breached the rollout
+    /// guard, sending all traffic back to the baseline
+    pub fn roll_back(
+        &mut self,
+        variant_id: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Result<(), ExperimentValidationError> {
+        self.complete()?;
+        self.rollback = Some(Rollback {
+            variant_id: variant_id.into(),
+            reason: reason.into(),
+            rolled_back_at: Utc::now(),
+        });
+        Ok(())
+    }
+
     // Assignment
 
     /// Get a variant based on a hash value (0-99)
@@ -569,7 +860,12 @@ impl Experiment {
 
     /// Get all model IDs referenced by variants
     pub fn referenced_model_ids(&self) -> Vec<&str> {
-        self.variants.iter().map(|v| v.model_id()).collect()
+        self.variants.iter().filter_map(|v| v.model_id()).collect()
+    }
+
+    /// Get all workflow IDs run by variants
+    pub fn referenced_workflow_ids(&self) -> Vec<&str> {
+        self.variants.iter().filter_map(|v| v.config().workflow_id()).collect()
     }
 
     /// Get the control variant if one exists
@@ -677,13 +973,52 @@ mod tests {
         }
     }
 
+    mod auto_stop_tests {
+        use super::*;
+
+        #[test]
+        fn test_auto_stop_defaults() {
+            let auto_stop: AutoStop = serde_json::from_str(r#"{"metric": "rating"}"#).unwrap();
+            assert_eq!(auto_stop.alpha, 0.05);
+            assert_eq!(auto_stop.min_samples, 100);
+            assert!(!auto_stop.lower_is_better());
+            assert!(auto_stop.validate().is_ok());
+        }
+
+        #[test]
+        fn test_auto_stop_validation() {
+            let invalid = [
+                AutoStop {
+                    metric: "tokens".to_string(),
+                    ..AutoStop::default()
+                },
+                AutoStop {
+                    alpha: 1.0,
+                    ..AutoStop::default()
+                },
+                AutoStop {
+                    min_effect_pct: 0.0,
+                    ..AutoStop::default()
+                },
+                AutoStop {
+                    max_samples: Some(10),
+                    ..AutoStop::default()
+                },
+            ];
+
+            for auto_stop in invalid {
+                assert!(auto_stop.validate().is_err(), "{:?}", auto_stop);
+            }
+        }
+    }
+
     mod variant_config_tests {
         use super::*;
 
         #[test]
         fn test_model_reference() {
             let config = VariantConfig::model_reference("gpt-4");
-            assert_eq!(config.model_id(), "gpt-4");
+            assert_eq!(config.model_id(), Some("gpt-4"));
             assert!(!config.is_config_override());
         }
 
@@ -691,16 +1026,41 @@ mod tests {
         fn test_config_override() {
             let config = VariantConfig::ConfigOverride {
                 model_id: "gpt-4".to_string(),
+                prompt_id: Some("support-v2".to_string()),
+                prompt_version: Some(3),
                 temperature: Some(0.7),
                 max_tokens: Some(1000),
                 top_p: None,
                 presence_penalty: None,
                 frequency_penalty: None,
             };
-            assert_eq!(config.model_id(), "gpt-4");
+            assert_eq!(config.model_id(), Some("gpt-4"));
+            assert_eq!(config.prompt_id(), Some("support-v2"));
             assert!(config.is_config_override());
         }
 
+        #[test]
+        fn test_workflow_variant() {
+            let config: VariantConfig = serde_json::from_value(serde_json::json!({
+                "type": "workflow",
+                "workflow_id": "rag-answer",
+                "step_overrides": [{"step": "crag", "skip": true}]
+            }))
+            .unwrap();
+
+            assert_eq!(config.model_id(), None);
+            assert_eq!(config.workflow_id(), Some("rag-answer"));
+            assert_eq!(config.step_overrides(), &[StepOverride::skip("crag")]);
+        }
+
+        #[test]
+        fn test_assignment_key_subject() {
+            assert_eq!(AssignmentKey::ApiKey.subject("key-1", Some("alice")), "key-1");
+            assert_eq!(AssignmentKey::User.subject("key-1", Some("alice")), "user:alice");
+            assert_eq!(AssignmentKey::User.subject("key-1", Some("")), "key-1");
+            assert_eq!(AssignmentKey::User.subject("key-1", None), "key-1");
+        }
+
         #[test]
         fn test_variant_config_serialization() {
             let config = VariantConfig::model_reference("gpt-4");
@@ -786,6 +1146,21 @@ mod tests {
             assert!(exp.complete().is_err());
         }
 
+        #[test]
+        fn test_roll_back() {
+            let mut exp = create_test_experiment().with_rollout(RolloutGuard::default());
+            assert!(exp.roll_back("treatment", "error rate").is_err());
+            assert!(exp.rollback().is_none());
+
+            exp.start().unwrap();
+            exp.roll_back("treatment", "error rate").unwrap();
+
+            assert_eq!(exp.status(), ExperimentStatus::Completed);
+            let rollback = exp.rollback().unwrap();
+            assert_eq!(rollback.variant_id, "treatment");
+            assert_eq!(rollback.reason, "error rate");
+        }
+
         #[test]
         fn test_variant_assignment() {
             let exp = create_test_experiment();
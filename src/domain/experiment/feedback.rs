@@ -0,0 +1,168 @@
+//! User feedback on experiment records
+//!
+//! Applications report how good a completion was after the fact: a thumbs
+//! up or down, a 1-5 rating or a conversion. Feedback is folded into the
+//! record of the completion so experiments compare variants on quality, not
+//! only on latency and cost.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::record::ExperimentRecord;
+use crate::domain::DomainError;
+
+/// Lowest accepted rating
+pub const MIN_RATING: f64 = 1.0;
+
+/// Highest accepted rating
+pub const MAX_RATING: f64 = 5.0;
+
+/// Quality metrics compared across variants, higher is better
+pub const QUALITY_METRICS: [&str; 3] = ["rating", "thumbs_up_rate", "conversion_rate"];
+
+/// Feedback event reported for a completion
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeedbackEvent {
+    ThumbsUp,
+    ThumbsDown,
+    /// Rating between [`MIN_RATING`] and [`MAX_RATING`]
+    Rating {
+        value: f64,
+    },
+    /// Conversion caused by the completion, e.g. a purchase
+    Conversion {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        event: Option<String>,
+        /// Value of the conversion, e.g. revenue
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        value: Option<f64>,
+    },
+}
+
+impl FeedbackEvent {
+    /// Validates the event values
+    pub fn validate(&self) -> Result<(), DomainError> {
+        match self {
+            Self::Rating { value } if !(MIN_RATING..=MAX_RATING).contains(value) => {
+                Err(DomainError::validation(format!(
+                    "Rating must be between {} and {}",
+                    MIN_RATING, MAX_RATING
+                )))
+            }
+            Self::Conversion {
+                value: Some(value), ..
+            } if !value.is_finite() || *value < 0.0 => Err(DomainError::validation(
+                "Conversion value must be a non-negative number",
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Feedback folded into an experiment record. A new thumb or rating replaces
+/// the previous one; conversions add up.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RecordFeedback {
+    /// Latest thumb, `true` for up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbs_up: Option<bool>,
+    /// Latest rating
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<f64>,
+    /// Number of conversions
+    #[serde(default)]
+    pub conversions: u32,
+    /// Total value of the conversions
+    #[serde(default)]
+    pub conversion_value: f64,
+}
+
+impl RecordFeedback {
+    /// Fold an event into the feedback
+    pub fn apply(&mut self, event: &FeedbackEvent) {
+        match event {
+            FeedbackEvent::ThumbsUp => self.thumbs_up = Some(true),
+            FeedbackEvent::ThumbsDown => self.thumbs_up = Some(false),
+            FeedbackEvent::Rating { value } => self.rating = Some(*value),
+            FeedbackEvent::Conversion { value, .. } => {
+                self.conversions += 1;
+                self.conversion_value += value.unwrap_or(0.0);
+            }
+        }
+    }
+
+    /// Whether no feedback was reported
+    pub fn is_empty(&self) -> bool {
+        self.thumbs_up.is_none() && self.rating.is_none() && self.conversions == 0
+    }
+}
+
+/// Per-record samples of a quality metric, for significance testing: the
+/// ratings, 1/0 thumbs of records with a thumb, or 1/0 conversion of every
+/// record
+pub fn quality_samples<'a>(
+    records: impl IntoIterator<Item = &'a ExperimentRecord>,
+    metric: &str,
+) -> Vec<f64> {
+    let bool_sample = |value: bool| if value { 1.0 } else { 0.0 };
+
+    records
+        .into_iter()
+        .filter_map(|record| match metric {
+            "rating" => record.feedback.rating,
+            "thumbs_up_rate" => record.feedback.thumbs_up.map(bool_sample),
+            "conversion_rate" => Some(bool_sample(record.feedback.conversions > 0)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(FeedbackEvent::ThumbsUp.validate().is_ok());
+        assert!(FeedbackEvent::Rating { value: 4.5 }.validate().is_ok());
+        assert!(FeedbackEvent::Rating { value: 0.0 }.validate().is_err());
+        assert!(FeedbackEvent::Conversion {
+            event: None,
+            value: Some(-1.0)
+        }
+        .validate()
+        .is_err());
+
+        let event: FeedbackEvent =
+            serde_json::from_str(r#"{"type": "conversion", "event": "purchase", "value": 20}"#)
+                .unwrap();
+        assert!(event.validate().is_ok());
+    }
+
+    #[test]
+    fn test_apply_and_samples() {
+        let mut rated = ExperimentRecord::new("r1", "exp", "control", "key");
+        rated.feedback.apply(&FeedbackEvent::ThumbsDown);
+        rated.feedback.apply(&FeedbackEvent::ThumbsUp);
+        rated.feedback.apply(&FeedbackEvent::Rating { value: 4.0 });
+
+        let mut converted = ExperimentRecord::new("r2", "exp", "control", "key");
+        for _ in 0..2 {
+            converted.feedback.apply(&FeedbackEvent::Conversion {
+                event: None,
+                value: Some(5.0),
+            });
+        }
+
+        assert_eq!(rated.feedback.thumbs_up, Some(true));
+        assert_eq!(converted.feedback.conversions, 2);
+        assert_eq!(converted.feedback.conversion_value, 10.0);
+
+        let records = [rated, converted];
+        assert_eq!(quality_samples(&records, "rating"), vec![4.0]);
+        assert_eq!(quality_samples(&records, "thumbs_up_rate"), vec![1.0]);
+        assert_eq!(quality_samples(&records, "conversion_rate"), vec![0.0, 1.0]);
+        assert!(quality_samples(&records, "latency_ms").is_empty());
+    }
+}
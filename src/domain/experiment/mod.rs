@@ -5,22 +5,33 @@
 
 mod assignment;
 mod entity;
+mod feedback;
 mod record;
 mod repository;
 mod result;
+mod rollout;
+mod step_override;
 mod validation;
 
 // Re-export all public types
-pub use assignment::{AssignmentResult, ConfigOverrides};
+pub use assignment::{AssignmentResult, ConfigOverrides, WorkflowAssignment};
 pub use entity::{
-    Experiment, ExperimentId, ExperimentStatus, TrafficAllocation, Variant, VariantConfig,
-    VariantId,
+    AssignmentKey, AutoStop, Experiment, ExperimentId, ExperimentStatus, TrafficAllocation, Variant, VariantConfig,
+    VariantId, AUTO_STOP_METRICS,
+};
+pub use feedback::{
+    quality_samples, FeedbackEvent, RecordFeedback, MAX_RATING, MIN_RATING, QUALITY_METRICS,
 };
 pub use record::{ExperimentRecord, ExperimentRecordId};
 pub use repository::{
     ExperimentQuery, ExperimentRecordQuery, ExperimentRecordRepository, ExperimentRepository,
 };
-pub use result::{ExperimentResult, LatencyStats, StatisticalSignificance, VariantMetrics};
+pub use result::{
+    ExperimentResult, LatencyStats, SequentialDecision, SequentialTest, StatisticalSignificance,
+    VariantMetrics,
+};
+pub use rollout::{Rollback, RolloutGuard};
+pub use step_override::{apply_step_overrides, StepOverride};
 pub use validation::ExperimentValidationError;
 
 #[cfg(test)]
@@ -3,6 +3,7 @@
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
 
+use super::feedback::RecordFeedback;
 use crate::domain::storage::{StorageEntity, StorageKey};
 
 /// Unique identifier for an experiment record
@@ -88,6 +89,9 @@ pub struct ExperimentRecord {
     pub error: Option<String>,
     /// Unix timestamp when the request was made
     pub timestamp: u64,
+    /// Feedback reported by the application for this completion
+    #[serde(default, skip_serializing_if = "RecordFeedback::is_empty")]
+    pub feedback: RecordFeedback,
 }
 
 impl ExperimentRecord {
@@ -117,6 +121,7 @@ impl ExperimentRecord {
             success: true,
             error: None,
             timestamp: now,
+            feedback: RecordFeedback::default(),
         }
     }
 
@@ -147,6 +147,22 @@ pub trait ExperimentRepository: Send + Sync + Debug {
     /// Find active experiments that include the given model ID
     async fn find_active_for_model(&self, model_id: &str) -> Result<Vec<Experiment>, DomainError>;
 
+    /// Find active, enabled experiments with a variant running the given
+    /// workflow
+    async fn find_active_for_workflow(
+        &self,
+        workflow_id: &str,
+    ) -> Result<Vec<Experiment>, DomainError> {
+        let active = self
+            .list(&ExperimentQuery::new().with_status(ExperimentStatus::Active))
+            .await?;
+
+        Ok(active
+            .into_iter()
+            .filter(|e| e.is_enabled() && e.referenced_workflow_ids().contains(&workflow_id))
+            .collect())
+    }
+
     /// Check if an experiment exists
     async fn exists(&self, id: &ExperimentId) -> Result<bool, DomainError> {
         Ok(self.get(id).await?.is_some())
@@ -171,6 +187,9 @@ pub trait ExperimentRecordRepository: Send + Sync + Debug {
     /// Get a record by ID
     async fn get(&self, id: &ExperimentRecordId) -> Result<Option<ExperimentRecord>, DomainError>;
 
+    /// Replace an existing record, e.g. to attach feedback
+    async fn update(&self, record: ExperimentRecord) -> Result<(), DomainError>;
+
     /// Query records with filters
     async fn query(&self, query: &ExperimentRecordQuery) -> Result<Vec<ExperimentRecord>, DomainError>;
 
@@ -357,6 +376,13 @@ pub mod mock {
             Ok(records.get(id.as_str()).cloned())
         }
 
+        async fn update(&self, record: ExperimentRecord) -> Result<(), DomainError> {
+            self.check_should_fail()?;
+            let mut records = self.records.write().unwrap();
+            records.insert(record.id().to_string(), record);
+            Ok(())
+        }
+
         async fn query(
             &self,
             query: &ExperimentRecordQuery,
@@ -1,9 +1,11 @@
 //! Experiment result types for metrics and statistical analysis
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use super::entity::ExperimentStatus;
 use super::record::ExperimentRecord;
+use crate::domain::dataset::DatasetRef;
 
 // ============================================================================
 // LatencyStats
@@ -93,6 +95,30 @@ pub struct VariantMetrics {
     pub avg_cost_micros: f64,
     /// Latency statistics
     pub latency: LatencyStats,
+    /// Records with a thumbs up
+    #[serde(default)]
+    pub thumbs_up: u64,
+    /// Records with a thumbs down
+    #[serde(default)]
+    pub thumbs_down: u64,
+    /// Share of thumbs that are up, none without thumbs
+    #[serde(default)]
+    pub thumbs_up_rate: Option<f64>,
+    /// Number of rated records
+    #[serde(default)]
+    pub ratings: u64,
+    /// Average rating, none without ratings
+    #[serde(default)]
+    pub avg_rating: Option<f64>,
+    /// Records that led to at least one conversion
+    #[serde(default)]
+    pub conversions: u64,
+    /// Share of records that converted (0.0 - 1.0)
+    #[serde(default)]
+    pub conversion_rate: f64,
+    /// Total value of the conversions
+    #[serde(default)]
+    pub conversion_value: f64,
 }
 
 impl VariantMetrics {
@@ -120,6 +146,21 @@ impl VariantMetrics {
         self.total_tokens += record.total_tokens as u64;
         self.total_cost_micros += record.cost_micros;
 
+        match record.feedback.thumbs_up {
+            Some(true) => self.thumbs_up += 1,
+            Some(false) => self.thumbs_down += 1,
+            None => {}
+        }
+        if let Some(rating) = record.feedback.rating {
+            let avg = self.avg_rating.unwrap_or(0.0);
+            self.ratings += 1;
+            self.avg_rating = Some(avg + (rating - avg) / self.ratings as f64);
+        }
+        if record.feedback.conversions > 0 {
+            self.conversions += 1;
+            self.conversion_value += record.feedback.conversion_value;
+        }
+
         self.update_rates();
     }
 
@@ -128,7 +169,11 @@ impl VariantMetrics {
         if self.total_requests > 0 {
             self.success_rate = self.successful_requests as f64 / self.total_requests as f64;
             self.avg_cost_micros = self.total_cost_micros as f64 / self.total_requests as f64;
+            self.conversion_rate = self.conversions as f64 / self.total_requests as f64;
         }
+
+        let thumbs = self.thumbs_up + self.thumbs_down;
+        self.thumbs_up_rate = (thumbs > 0).then(|| self.thumbs_up as f64 / thumbs as f64);
     }
 
     /// Set latency statistics from samples
@@ -215,6 +260,56 @@ impl StatisticalSignificance {
     }
 }
 
+// ============================================================================
+// SequentialTest
+// ============================================================================
+
+/// Decision of a sequential test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SequentialDecision {
+    /// Not enough evidence yet, keep collecting records
+    Continue,
+    /// The treatment differs significantly from the control
+    Significant,
+    /// Any difference is too small to matter
+    Futile,
+}
+
+/// Result of a sequential (mSPRT) test of a treatment against the control.
+/// Unlike [`StatisticalSignificance`], the p-value stays valid however often
+/// it is checked while records come in.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SequentialTest {
+    /// ID of the control variant
+    pub control_variant_id: String,
+    /// ID of the treatment variant being compared
+    pub treatment_variant_id: String,
+    /// Name of the metric being compared
+    pub metric: String,
+    /// Number of control samples
+    pub control_samples: u64,
+    /// Number of treatment samples
+    pub treatment_samples: u64,
+    /// Mean value for the control variant
+    pub control_mean: f64,
+    /// Mean value for the treatment variant
+    pub treatment_mean: f64,
+    /// Relative change from control to treatment (percentage)
+    pub relative_change: f64,
+    /// Always-valid p-value
+    pub p_value: f64,
+    /// Decision at the configured significance level
+    pub decision: SequentialDecision,
+}
+
+impl SequentialTest {
+    /// Whether the test reached a decision
+    pub fn is_decided(&self) -> bool {
+        self.decision != SequentialDecision::Continue
+    }
+}
+
 // ============================================================================
 // ExperimentResult
 // ============================================================================
@@ -237,12 +332,18 @@ pub struct ExperimentResult {
     pub variant_metrics: Vec<VariantMetrics>,
     /// Statistical significance tests
     pub significance_tests: Vec<StatisticalSignificance>,
+    /// Sequential tests of experiments with auto-stop
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sequential_tests: Vec<SequentialTest>,
     /// ID of the winning variant (if determined)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub winner_variant_id: Option<String>,
     /// Recommendation based on results
     #[serde(skip_serializing_if = "Option::is_none")]
     pub recommendation: Option<String>,
+    /// Dataset version the experiment was evaluated against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dataset: Option<DatasetRef>,
 }
 
 impl ExperimentResult {
@@ -260,8 +361,10 @@ impl ExperimentResult {
             total_requests: 0,
             variant_metrics: Vec::new(),
             significance_tests: Vec::new(),
+            sequential_tests: Vec::new(),
             winner_variant_id: None,
             recommendation: None,
+            dataset: None,
         }
     }
 
@@ -307,6 +410,11 @@ impl ExperimentResult {
     pub fn has_significant_result(&self) -> bool {
         self.significance_tests.iter().any(|t| t.is_significant)
     }
+
+    /// Check if every sequential test reached a decision
+    pub fn sequential_tests_decided(&self) -> bool {
+        !self.sequential_tests.is_empty() && self.sequential_tests.iter().all(|t| t.is_decided())
+    }
 }
 
 #[cfg(test)]
@@ -415,6 +523,33 @@ mod tests {
             assert_eq!(metrics.failed_requests, 2);
             assert!((metrics.success_rate - 0.8).abs() < 0.001);
         }
+
+        #[test]
+        fn test_feedback_metrics() {
+            let mut metrics = VariantMetrics::new("control", "Control");
+
+            for (i, rating) in [Some(5.0), Some(2.0), None, None].into_iter().enumerate() {
+                let mut record =
+                    ExperimentRecord::new(format!("rec-{}", i), "exp-1", "control", "api-1");
+                record.feedback.rating = rating;
+                record.feedback.thumbs_up = rating.map(|r| r > 3.0);
+                if i == 0 {
+                    record.feedback.conversions = 2;
+                    record.feedback.conversion_value = 30.0;
+                }
+
+                metrics.add_record(&record);
+            }
+
+            assert_eq!(metrics.thumbs_up, 1);
+            assert_eq!(metrics.thumbs_down, 1);
+            assert_eq!(metrics.thumbs_up_rate, Some(0.5));
+            assert_eq!(metrics.ratings, 2);
+            assert_eq!(metrics.avg_rating, Some(3.5));
+            assert_eq!(metrics.conversions, 1);
+            assert_eq!(metrics.conversion_rate, 0.25);
+            assert_eq!(metrics.conversion_value, 30.0);
+        }
     }
 
     mod statistical_significance_tests {
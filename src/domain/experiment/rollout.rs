@@ -0,0 +1,231 @@
+//! Canary rollouts of experiments
+//!
+//! A rollout publishes a new model or prompt version as the treatment of an
+//! experiment, on the share of traffic allocated to it, with the control as
+//! the baseline. While the experiment runs, each treatment's error rate, p95
+//! latency and average rating (reported by users or judge models through
+//! feedback) are compared to the baseline; the first threshold breached
+//! rolls the experiment back, returning all traffic to the baseline.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::result::VariantMetrics;
+use super::validation::ExperimentValidationError;
+
+/// Thresholds a treatment must stay within to keep its share of traffic
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RolloutGuard {
+    /// Records the baseline and a treatment need before they are compared
+    #[serde(default = "default_rollout_min_samples")]
+    pub min_samples: u64,
+    /// Largest increase of the error rate over the baseline, in percentage
+    /// points
+    #[serde(default = "default_rollout_max_error_rate_increase")]
+    pub max_error_rate_increase: f64,
+    /// Largest increase of the p95 latency over the baseline, in percent
+    #[serde(default = "default_rollout_max_latency_increase_pct")]
+    pub max_latency_increase_pct: f64,
+    /// Largest drop of the average rating under the baseline, in percent;
+    /// ratings are not compared when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_rating_drop_pct: Option<f64>,
+    /// Ratings the baseline and a treatment need before their averages are
+    /// compared
+    #[serde(default = "default_rollout_min_ratings")]
+    pub min_ratings: u64,
+}
+
+fn default_rollout_min_samples() -> u64 {
+    50
+}
+
+fn default_rollout_max_error_rate_increase() -> f64 {
+    2.0
+}
+
+fn default_rollout_max_latency_increase_pct() -> f64 {
+    50.0
+}
+
+fn default_rollout_min_ratings() -> u64 {
+    20
+}
+
+impl Default for RolloutGuard {
+    fn default() -> Self {
+        Self {
+            min_samples: default_rollout_min_samples(),
+            max_error_rate_increase: default_rollout_max_error_rate_increase(),
+            max_latency_increase_pct: default_rollout_max_latency_increase_pct(),
+            max_rating_drop_pct: None,
+            min_ratings: default_rollout_min_ratings(),
+        }
+    }
+}
+
+impl RolloutGuard {
+    /// Validate the configuration
+    pub fn validate(&self) -> Result<(), ExperimentValidationError> {
+        let invalid =
+            |message: &str| Err(ExperimentValidationError::InvalidRollout(message.to_string()));
+
+        if self.min_samples == 0 {
+            return invalid("min_samples must be at least 1");
+        }
+        if !(0.0..=100.0).contains(&self.max_error_rate_increase) {
+            return invalid("max_error_rate_increase must be between 0 and 100");
+        }
+        if !self.max_latency_increase_pct.is_finite() || self.max_latency_increase_pct <= 0.0 {
+            return invalid("max_latency_increase_pct must be positive");
+        }
+        if self
+            .max_rating_drop_pct
+            .is_some_and(|drop| !(drop > 0.0 && drop <= 100.0))
+        {
+            return invalid("max_rating_drop_pct must be above 0 and at most 100");
+        }
+        if self.min_ratings == 0 {
+            return invalid("min_ratings must be at least 1");
+        }
+
+        Ok(())
+    }
+
+    /// First threshold the treatment breaches against the baseline, none
+    /// while either lacks samples
+    pub fn breach(&self, baseline: &VariantMetrics, treatment: &VariantMetrics) -> Option<String> {
+        if baseline.total_requests < self.min_samples || treatment.total_requests < self.min_samples
+        {
+            return None;
+        }
+
+        let error_rate = |metrics: &VariantMetrics| {
+            metrics.failed_requests as f64 / metrics.total_requests as f64 * 100.0
+        };
+        let (baseline_errors, treatment_errors) = (error_rate(baseline), error_rate(treatment));
+        if treatment_errors - baseline_errors > self.max_error_rate_increase {
+            return Some(format!(
+                "error rate {:.1}% exceeds the baseline's {:.1}% by more than {} points",
+                treatment_errors, baseline_errors, self.max_error_rate_increase
+            ));
+        }
+
+        let latency_limit =
+            baseline.latency.p95_ms as f64 * (1.0 + self.max_latency_increase_pct / 100.0);
+        if baseline.latency.p95_ms > 0 && treatment.latency.p95_ms as f64 > latency_limit {
+            return Some(format!(
+                "p95 latency {}ms exceeds the baseline's {}ms by more than {}%",
+                treatment.latency.p95_ms, baseline.latency.p95_ms, self.max_latency_increase_pct
+            ));
+        }
+
+        if let (Some(max_drop), Some(baseline_rating), Some(treatment_rating)) = (
+            self.max_rating_drop_pct,
+            baseline.avg_rating,
+            treatment.avg_rating,
+        ) && baseline.ratings >= self.min_ratings
+            && treatment.ratings >= self.min_ratings
+            && treatment_rating < baseline_rating * (1.0 - max_drop / 100.0)
+        {
+            return Some(format!(
+                "average rating {:.2} is more than {}% under the baseline's {:.2}",
+                treatment_rating, max_drop, baseline_rating
+            ));
+        }
+
+        None
+    }
+}
+
+/// Why and when a rollout was rolled back
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Rollback {
+    /// Treatment that breached a threshold
+    pub variant_id: String,
+    /// Threshold breached
+    pub reason: String,
+    #[schema(value_type = String, format = DateTime)]
+    pub rolled_back_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(total: u64, failed: u64, p95_ms: u64) -> VariantMetrics {
+        let mut metrics = VariantMetrics::new("v", "Variant");
+        metrics.total_requests = total;
+        metrics.failed_requests = failed;
+        metrics.successful_requests = total - failed;
+        metrics.latency.p95_ms = p95_ms;
+        metrics
+    }
+
+    #[test]
+    fn test_rollout_guard_validation() {
+        let guard: RolloutGuard = serde_json::from_str("{}").unwrap();
+        assert_eq!(guard, RolloutGuard::default());
+        assert!(guard.validate().is_ok());
+
+        let invalid = [
+            RolloutGuard {
+                min_samples: 0,
+                ..RolloutGuard::default()
+            },
+            RolloutGuard {
+                max_error_rate_increase: -1.0,
+                ..RolloutGuard::default()
+            },
+            RolloutGuard {
+                max_latency_increase_pct: f64::NAN,
+                ..RolloutGuard::default()
+            },
+            RolloutGuard {
+                max_rating_drop_pct: Some(0.0),
+                ..RolloutGuard::default()
+            },
+        ];
+
+        for guard in invalid {
+            assert!(guard.validate().is_err(), "{:?}", guard);
+        }
+    }
+
+    #[test]
+    fn test_breach() {
+        let guard = RolloutGuard::default();
+        let baseline = metrics(100, 1, 1000);
+
+        assert_eq!(guard.breach(&baseline, &metrics(10, 10, 1000)), None);
+        assert_eq!(guard.breach(&baseline, &metrics(100, 3, 1400)), None);
+
+        let errors = guard.breach(&baseline, &metrics(100, 4, 1000)).unwrap();
+        assert!(errors.contains("error rate 4.0%"), "{}", errors);
+
+        let latency = guard.breach(&baseline, &metrics(100, 1, 1600)).unwrap();
+        assert!(latency.contains("p95 latency 1600ms"), "{}", latency);
+    }
+
+    #[test]
+    fn test_rating_breach() {
+        let guard = RolloutGuard {
+            max_rating_drop_pct: Some(10.0),
+            min_ratings: 5,
+            ..RolloutGuard::default()
+        };
+        let mut baseline = metrics(100, 0, 1000);
+        baseline.ratings = 20;
+        baseline.avg_rating = Some(4.0);
+        let mut treatment = metrics(100, 0, 1000);
+        treatment.ratings = 4;
+        treatment.avg_rating = Some(3.0);
+
+        assert_eq!(guard.breach(&baseline, &treatment), None);
+
+        treatment.ratings = 5;
+        let rating = guard.breach(&baseline, &treatment).unwrap();
+        assert!(rating.contains("average rating 3.00"), "{}", rating);
+    }
+}
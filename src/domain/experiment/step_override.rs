@@ -0,0 +1,164 @@
+//! Step overrides of workflow experiment variants
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::domain::workflow::WorkflowStep;
+
+/// Fields a step override cannot change
+const PROTECTED_STEP_FIELDS: [&str; 2] = ["name", "type"];
+
+/// Change a workflow variant makes to one step of its workflow
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StepOverride {
+    /// Name of the step
+    pub step: String,
+    /// Leave the step out; later steps referencing its output need defaults
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skip: bool,
+    /// Fields merged into the step definition (`model_id`, `temperature`,
+    /// `threshold`, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub set: Option<Value>,
+}
+
+impl StepOverride {
+    /// Override leaving a step out
+    pub fn skip(step: impl Into<String>) -> Self {
+        Self {
+            step: step.into(),
+            skip: true,
+            set: None,
+        }
+    }
+
+    /// Override merging fields into a step
+    pub fn set(step: impl Into<String>, fields: Value) -> Self {
+        Self {
+            step: step.into(),
+            skip: false,
+            set: Some(fields),
+        }
+    }
+
+    /// Check the shape of the override; whether the step exists is only
+    /// known when applied
+    pub fn validate(&self) -> Result<(), String> {
+        if self.step.trim().is_empty() {
+            return Err("step override must name a step".to_string());
+        }
+
+        let fields = match (&self.set, self.skip) {
+            (None, true) => return Ok(()),
+            (Some(_), true) => {
+                return Err(format!(
+                    "override of step '{}' cannot both skip and set fields",
+                    self.step
+                ))
+            }
+            (None, false) => {
+                return Err(format!("override of step '{}' must skip it or set fields", self.step))
+            }
+            (Some(fields), false) => fields.as_object().ok_or_else(|| {
+                format!("fields set on step '{}' must be an object", self.step)
+            })?,
+        };
+
+        match PROTECTED_STEP_FIELDS.iter().find(|field| fields.contains_key(**field)) {
+            Some(field) => Err(format!("override of step '{}' cannot change its {}", self.step, field)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Steps of a workflow with the overrides of a variant applied
+pub fn apply_step_overrides(
+    steps: &[WorkflowStep],
+    overrides: &[StepOverride],
+) -> Result<Vec<WorkflowStep>, String> {
+    let mut steps = steps.to_vec();
+
+    for step_override in overrides {
+        step_override.validate()?;
+
+        let index = steps
+            .iter()
+            .position(|step| step.name() == step_override.step)
+            .ok_or_else(|| format!("workflow has no step '{}'", step_override.step))?;
+
+        let Some(fields) = step_override.set.as_ref().and_then(Value::as_object) else {
+            steps.remove(index);
+            continue;
+        };
+
+        let invalid = |e: serde_json::Error| {
+            format!("invalid override of step '{}': {}", step_override.step, e)
+        };
+        let mut definition = serde_json::to_value(&steps[index]).map_err(invalid)?;
+        if let Some(definition) = definition.as_object_mut() {
+            definition.extend(fields.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+        steps[index] = serde_json::from_value(definition).map_err(invalid)?;
+    }
+
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::workflow::{ChatCompletionStep, CragScoringStep, WorkflowStepType};
+    use serde_json::json;
+
+    fn steps() -> Vec<WorkflowStep> {
+        vec![
+            WorkflowStep::new(
+                "score",
+                WorkflowStepType::CragScoring(CragScoringStep::new("gpt-4", "scorer")),
+            ),
+            WorkflowStep::new(
+                "answer",
+                WorkflowStepType::ChatCompletion(ChatCompletionStep::new("gpt-4", "answerer")),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_apply_step_overrides() {
+        let overrides = vec![
+            StepOverride::skip("score"),
+            StepOverride::set("answer", json!({"model_id": "gpt-4o", "temperature": 0.2})),
+        ];
+
+        let steps = apply_step_overrides(&steps(), &overrides).unwrap();
+
+        assert_eq!(steps.len(), 1);
+        let WorkflowStepType::ChatCompletion(answer) = steps[0].step_type() else {
+            panic!("expected a chat completion step");
+        };
+        assert_eq!(answer.model_id, "gpt-4o");
+        assert_eq!(answer.temperature, Some(0.2));
+        assert_eq!(answer.prompt_id, "answerer");
+    }
+
+    #[test]
+    fn test_invalid_step_overrides() {
+        let unknown = apply_step_overrides(&steps(), &[StepOverride::skip("missing")]);
+        assert!(unknown.unwrap_err().contains("no step 'missing'"));
+
+        let bad_type = StepOverride::set("answer", json!({"temperature": "hot"}));
+        assert!(apply_step_overrides(&steps(), &[bad_type]).is_err());
+
+        assert!(StepOverride::set("answer", json!({"name": "other"})).validate().is_err());
+        assert!(StepOverride::set("answer", json!([1])).validate().is_err());
+        assert!(StepOverride {
+            step: "answer".to_string(),
+            skip: false,
+            set: None,
+        }
+        .validate()
+        .is_err());
+    }
+}
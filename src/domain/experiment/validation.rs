@@ -64,6 +64,12 @@ pub enum ExperimentValidationError {
 
     #[error("Traffic allocated to unknown variant: '{0}'")]
     UnknownVariantInAllocation(String),
+
+    #[error("Invalid auto-stop configuration: {0}")]
+    InvalidAutoStop(String),
+
+    #[error("Invalid rollout configuration: {0}")]
+    InvalidRollout(String),
 }
 
 /// Validate an experiment ID
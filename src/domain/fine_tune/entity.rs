@@ -0,0 +1,419 @@
+//! Fine-tuning job entities
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::credentials::CredentialType;
+use crate::domain::dataset::DatasetRef;
+use crate::domain::model::ModelLineage;
+use crate::domain::storage::{StorageEntity, StorageKey};
+use crate::domain::{ModelValidationError, validate_model_id};
+
+/// Fine-tuning job identifier
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct FineTuneJobId(String);
+
+impl FineTuneJobId {
+    pub fn new(id: impl Into<String>) -> Result<Self, ModelValidationError> {
+        let id = id.into();
+        validate_model_id(&id)?;
+        Ok(Self(id))
+    }
+
+    /// Generate a new job ID with UUID
+    pub fn generate() -> Self {
+        Self(format!("ft-{}", uuid::Uuid::new_v4()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for FineTuneJobId {
+    type Error = ModelValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<FineTuneJobId> for String {
+    fn from(id: FineTuneJobId) -> Self {
+        id.0
+    }
+}
+
+impl std::fmt::Display for FineTuneJobId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StorageKey for FineTuneJobId {
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Status of a fine-tuning job, as reported by the provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FineTuneStatus {
+    ValidatingFiles,
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl FineTuneStatus {
+    /// Parse a provider status; OpenAI and Azure share the names, Azure adds
+    /// `pending`, `notRunning` and `canceled`
+    pub fn from_provider(status: &str) -> Option<Self> {
+        match status {
+            "validating_files" => Some(Self::ValidatingFiles),
+            "queued" | "pending" | "notRunning" => Some(Self::Queued),
+            "running" => Some(Self::Running),
+            "succeeded" => Some(Self::Succeeded),
+            "failed" => Some(Self::Failed),
+            "cancelled" | "canceled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+
+    /// Whether the job will not change anymore
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Succeeded | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// Training hyperparameters; unset values are chosen by the provider
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct FineTuneHyperparameters {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub n_epochs: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub learning_rate_multiplier: Option<f64>,
+}
+
+impl FineTuneHyperparameters {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// State of a job on the provider side
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderFineTuneJob {
+    /// Provider job ID
+    pub id: String,
+    pub status: FineTuneStatus,
+    /// Name of the trained model, once the job succeeded
+    pub fine_tuned_model: Option<String>,
+    /// Failure reason
+    pub error: Option<String>,
+    pub trained_tokens: Option<u64>,
+}
+
+/// Provider fine-tuning job started by the gateway from a dataset. When the
+/// job succeeds the trained model is registered as a gateway model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FineTuneJob {
+    /// Unique identifier
+    id: FineTuneJobId,
+    /// Credential the job runs with
+    credential_id: String,
+    /// Provider of the credential
+    provider: CredentialType,
+    /// Provider model the job starts from
+    base_model: String,
+    /// Dataset version the training file was built from
+    dataset: DatasetRef,
+    /// Gateway model registered when the job succeeds
+    model_id: String,
+    /// Suffix of the trained model name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    suffix: Option<String>,
+    #[serde(default)]
+    hyperparameters: FineTuneHyperparameters,
+    /// Uploaded training file
+    training_file_id: String,
+    /// Provider job ID
+    provider_job_id: String,
+    status: FineTuneStatus,
+    /// Name of the trained model, once the job succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fine_tuned_model: Option<String>,
+    /// Failure reason, of the job or of the model registration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    trained_tokens: Option<u64>,
+    /// Whether the trained model was registered
+    #[serde(default)]
+    model_registered: bool,
+    /// Creation timestamp
+    created_at: DateTime<Utc>,
+    /// Last update timestamp
+    updated_at: DateTime<Utc>,
+    /// When the job reached a terminal status
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    finished_at: Option<DateTime<Utc>>,
+}
+
+/// Fields of a job submitted to a provider
+#[derive(Debug, Clone)]
+pub struct NewFineTuneJob {
+    pub credential_id: String,
+    pub provider: CredentialType,
+    pub base_model: String,
+    pub dataset: DatasetRef,
+    pub model_id: String,
+    pub suffix: Option<String>,
+    pub hyperparameters: FineTuneHyperparameters,
+    pub training_file_id: String,
+}
+
+impl FineTuneJob {
+    /// Track a job the provider accepted
+    pub fn new(job: NewFineTuneJob, submitted: ProviderFineTuneJob) -> Self {
+        let now = Utc::now();
+        let mut fine_tune = Self {
+            id: FineTuneJobId::generate(),
+            credential_id: job.credential_id,
+            provider: job.provider,
+            base_model: job.base_model,
+            dataset: job.dataset,
+            model_id: job.model_id,
+            suffix: job.suffix,
+            hyperparameters: job.hyperparameters,
+            training_file_id: job.training_file_id,
+            provider_job_id: submitted.id.clone(),
+            status: submitted.status,
+            fine_tuned_model: None,
+            error: None,
+            trained_tokens: None,
+            model_registered: false,
+            created_at: now,
+            updated_at: now,
+            finished_at: None,
+        };
+        fine_tune.apply(submitted);
+        fine_tune
+    }
+
+    // Getters
+    pub fn id(&self) -> &FineTuneJobId {
+        &self.id
+    }
+
+    pub fn credential_id(&self) -> &str {
+        &self.credential_id
+    }
+
+    pub fn provider(&self) -> &CredentialType {
+        &self.provider
+    }
+
+    pub fn base_model(&self) -> &str {
+        &self.base_model
+    }
+
+    pub fn dataset(&self) -> &DatasetRef {
+        &self.dataset
+    }
+
+    pub fn model_id(&self) -> &str {
+        &self.model_id
+    }
+
+    pub fn suffix(&self) -> Option<&str> {
+        self.suffix.as_deref()
+    }
+
+    pub fn hyperparameters(&self) -> &FineTuneHyperparameters {
+        &self.hyperparameters
+    }
+
+    pub fn training_file_id(&self) -> &str {
+        &self.training_file_id
+    }
+
+    pub fn provider_job_id(&self) -> &str {
+        &self.provider_job_id
+    }
+
+    pub fn status(&self) -> FineTuneStatus {
+        self.status
+    }
+
+    pub fn fine_tuned_model(&self) -> Option<&str> {
+        self.fine_tuned_model.as_deref()
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn trained_tokens(&self) -> Option<u64> {
+        self.trained_tokens
+    }
+
+    pub fn is_model_registered(&self) -> bool {
+        self.model_registered
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    pub fn finished_at(&self) -> Option<DateTime<Utc>> {
+        self.finished_at
+    }
+
+    /// Whether the trained model still has to be registered
+    pub fn needs_registration(&self) -> bool {
+        self.status == FineTuneStatus::Succeeded
+            && self.fine_tuned_model.is_some()
+            && !self.model_registered
+    }
+
+    /// Lineage recorded on the registered model
+    pub fn lineage(&self) -> ModelLineage {
+        ModelLineage {
+            fine_tune_job_id: self.id.to_string(),
+            provider_job_id: self.provider_job_id.clone(),
+            base_model: self.base_model.clone(),
+            dataset_id: self.dataset.dataset_id.clone(),
+            dataset_version: self.dataset.version,
+        }
+    }
+
+    // Mutators
+
+    /// Take the state reported by the provider, returning whether it changed
+    pub fn apply(&mut self, job: ProviderFineTuneJob) -> bool {
+        let changed = self.status != job.status
+            || self.fine_tuned_model != job.fine_tuned_model
+            || self.error != job.error
+            || self.trained_tokens != job.trained_tokens;
+
+        if !changed {
+            return false;
+        }
+
+        if job.status.is_terminal() && self.finished_at.is_none() {
+            self.finished_at = Some(Utc::now());
+        }
+
+        self.status = job.status;
+        self.fine_tuned_model = job.fine_tuned_model;
+        self.error = job.error;
+        self.trained_tokens = job.trained_tokens;
+        self.touch();
+        true
+    }
+
+    /// Record that the trained model was registered
+    pub fn mark_registered(&mut self) {
+        self.model_registered = true;
+        self.touch();
+    }
+
+    /// Record why the trained model could not be registered
+    pub fn set_registration_error(&mut self, error: impl Into<String>) {
+        self.error = Some(error.into());
+        self.touch();
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+    }
+}
+
+impl StorageEntity for FineTuneJob {
+    type Key = FineTuneJobId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider_job(status: FineTuneStatus, model: Option<&str>) -> ProviderFineTuneJob {
+        ProviderFineTuneJob {
+            id: "ftjob-1".to_string(),
+            status,
+            fine_tuned_model: model.map(String::from),
+            error: None,
+            trained_tokens: None,
+        }
+    }
+
+    fn create_job() -> FineTuneJob {
+        FineTuneJob::new(
+            NewFineTuneJob {
+                credential_id: "openai".to_string(),
+                provider: CredentialType::OpenAi,
+                base_model: "gpt-4o-mini-2024-07-18".to_string(),
+                dataset: DatasetRef::new("support", 2),
+                model_id: "support-bot".to_string(),
+                suffix: None,
+                hyperparameters: FineTuneHyperparameters::default(),
+                training_file_id: "file-1".to_string(),
+            },
+            provider_job(FineTuneStatus::ValidatingFiles, None),
+        )
+    }
+
+    #[test]
+    fn test_status_from_provider() {
+        assert_eq!(
+            FineTuneStatus::from_provider("canceled"),
+            Some(FineTuneStatus::Cancelled)
+        );
+        assert_eq!(
+            FineTuneStatus::from_provider("notRunning"),
+            Some(FineTuneStatus::Queued)
+        );
+        assert_eq!(FineTuneStatus::from_provider("exploded"), None);
+        assert!(FineTuneStatus::Failed.is_terminal());
+        assert!(!FineTuneStatus::Running.is_terminal());
+    }
+
+    #[test]
+    fn test_apply_provider_state() {
+        let mut job = create_job();
+        assert!(!job.apply(provider_job(FineTuneStatus::ValidatingFiles, None)));
+        assert!(job.apply(provider_job(FineTuneStatus::Running, None)));
+        assert!(job.finished_at().is_none());
+        assert!(!job.needs_registration());
+
+        assert!(job.apply(provider_job(
+            FineTuneStatus::Succeeded,
+            Some("ft:gpt-4o-mini:acme::abc")
+        )));
+        assert!(job.finished_at().is_some());
+        assert!(job.needs_registration());
+
+        job.mark_registered();
+        assert!(!job.needs_registration());
+
+        let lineage = job.lineage();
+        assert_eq!(lineage.provider_job_id, "ftjob-1");
+        assert_eq!(lineage.dataset_version, 2);
+    }
+}
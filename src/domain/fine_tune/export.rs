@@ -0,0 +1,241 @@
+//! Training examples built from recorded executions
+//!
+//! Successful chat completions become one conversation each: the request
+//! messages followed by the answer. Workflow executions give one
+//! conversation per successful chat completion step: its rendered prompt as
+//! the user message and the step answer. Every message is passed through
+//! secret and personal data redaction.
+
+use serde_json::{Value, json};
+
+use crate::domain::config::{ExecutionLog, ExecutionType};
+use crate::domain::guardrail::{SecretScanner, redact_pii};
+
+/// Step type of workflow chat completion steps in execution logs
+const CHAT_COMPLETION_STEP: &str = "chat_completion";
+
+/// Chat-format JSONL built from execution logs
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrainingExport {
+    /// One `{"messages": [...]}` conversation per line
+    pub content: String,
+    pub examples: usize,
+    /// Logs that gave no example: failed, recorded without payloads, or
+    /// with messages that are not plain system, user and assistant text
+    pub skipped_logs: usize,
+}
+
+/// Build a training file from execution logs. `system_prompt` is added to
+/// conversations that have no system message.
+pub fn build_training_export(logs: &[ExecutionLog], system_prompt: Option<&str>) -> TrainingExport {
+    let scanner = SecretScanner::new();
+    let mut export = TrainingExport::default();
+    let mut lines = Vec::new();
+
+    for log in logs {
+        let conversations = execution_log_conversations(log);
+
+        if conversations.is_empty() {
+            export.skipped_logs += 1;
+            continue;
+        }
+
+        for mut messages in conversations {
+            if let Some(system_prompt) = system_prompt
+                && messages.first().is_none_or(|(role, _)| role != "system")
+            {
+                messages.insert(0, ("system".to_string(), system_prompt.to_string()));
+            }
+
+            let messages: Vec<Value> = messages
+                .into_iter()
+                .map(|(role, content)| {
+                    json!({"role": role, "content": redact_training_text(&scanner, &content)})
+                })
+                .collect();
+
+            lines.push(json!({ "messages": messages }).to_string());
+        }
+    }
+
+    export.examples = lines.len();
+    export.content = lines.join("\n");
+    export
+}
+
+/// Conversations of a log as `(role, content)` messages ending with the
+/// assistant answer
+fn execution_log_conversations(log: &ExecutionLog) -> Vec<Vec<(String, String)>> {
+    if !log.status().is_success() {
+        return Vec::new();
+    }
+
+    match log.execution_type() {
+        ExecutionType::ChatCompletion => chat_completion_conversation(log)
+            .map(|messages| vec![messages])
+            .unwrap_or_default(),
+        ExecutionType::Workflow => log
+            .workflow_steps()
+            .into_iter()
+            .flatten()
+            .filter(|step| step.step_type == CHAT_COMPLETION_STEP && step.status.is_success())
+            .filter_map(|step| step.output.as_ref().and_then(chat_step_conversation))
+            .collect(),
+        ExecutionType::Model | ExecutionType::Ingestion | ExecutionType::Shadow => Vec::new(),
+    }
+}
+
+/// Request messages and answer of a chat completion. Requests with tool
+/// messages or prompt references without content are left out.
+fn chat_completion_conversation(log: &ExecutionLog) -> Option<Vec<(String, String)>> {
+    let mut messages = Vec::new();
+
+    for message in log.input()?.get("messages")?.as_array()? {
+        let role = message.get("role")?.as_str()?;
+        if !matches!(role, "system" | "user" | "assistant") {
+            return None;
+        }
+
+        messages.push((role.to_string(), message_text(message.get("content")?)?));
+    }
+
+    // Non-streamed answers are stored as the completion response, streamed
+    // ones as the accumulated `content`
+    let output = log.output()?;
+    let answer = output
+        .pointer("/choices/0/message/content")
+        .or_else(|| output.get("content"))
+        .and_then(message_text)?;
+
+    if messages.is_empty() {
+        return None;
+    }
+
+    messages.push(("assistant".to_string(), answer));
+    Some(messages)
+}
+
+/// Rendered prompt and answer of a workflow chat completion step
+fn chat_step_conversation(output: &Value) -> Option<Vec<(String, String)>> {
+    let prompt = output.pointer("/prompt/content")?.as_str()?;
+    let answer = output.get("content")?.as_str()?;
+
+    if prompt.is_empty() || answer.is_empty() {
+        return None;
+    }
+
+    Some(vec![
+        ("user".to_string(), prompt.to_string()),
+        ("assistant".to_string(), answer.to_string()),
+    ])
+}
+
+/// Text of message content: a string, or the text parts of a part array
+fn message_text(content: &Value) -> Option<String> {
+    let text = match content {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter(|part| part.get("type").and_then(Value::as_str) == Some("text"))
+            .filter_map(|part| part.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => return None,
+    };
+
+    (!text.is_empty()).then_some(text)
+}
+
+fn redact_training_text(scanner: &SecretScanner, text: &str) -> String {
+    redact_pii(&scanner.redact(text).0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::config::{Executor, WorkflowStepLog};
+
+    fn chat_log(input: Value, output: Value) -> ExecutionLog {
+        ExecutionLog::success(
+            ExecutionType::ChatCompletion,
+            "gpt-4o",
+            120,
+            Executor::from_api_key("key-1"),
+        )
+        .with_input(input)
+        .with_output(output)
+    }
+
+    #[test]
+    fn test_chat_completion_examples_are_redacted() {
+        let logs = vec![
+            chat_log(
+                json!({"model": "gpt-4o", "messages": [
+                    {"role": "user", "content": "My email is ann@example.com"}
+                ]}),
+                json!({"choices": [{"message": {"role": "assistant", "content": "Thanks Ann"}}]}),
+            ),
+            chat_log(
+                json!({"messages": [
+                    {"role": "system", "content": "Be brief"},
+                    {"role": "user", "content": [{"type": "text", "text": "Hi"}]}
+                ]}),
+                json!({"content": "Hello!"}),
+            ),
+            // Tool messages are not exported
+            chat_log(
+                json!({"messages": [{"role": "tool", "content": "42"}]}),
+                json!({"content": "The answer is 42"}),
+            ),
+        ];
+
+        let export = build_training_export(&logs, Some("You are a support agent"));
+        assert_eq!(export.examples, 2);
+        assert_eq!(export.skipped_logs, 1);
+
+        let lines: Vec<Value> = export
+            .content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines[0]["messages"][0]["content"], "You are a support agent");
+        assert_eq!(lines[0]["messages"][1]["content"], "My email is [email]");
+        assert_eq!(lines[0]["messages"][2]["content"], "Thanks Ann");
+        assert_eq!(lines[1]["messages"][0]["content"], "Be brief");
+        assert_eq!(lines[1]["messages"][2]["content"], "Hello!");
+    }
+
+    #[test]
+    fn test_workflow_examples_use_chat_steps() {
+        let log = ExecutionLog::success(
+            ExecutionType::Workflow,
+            "support-flow",
+            300,
+            Executor::from_api_key("key-1"),
+        )
+        .with_workflow_steps(vec![
+            WorkflowStepLog::new("search", "knowledge_base_search")
+                .with_output(json!({"documents": []})),
+            WorkflowStepLog::new("answer", "chat_completion").with_output(json!({
+                "content": "It ships tomorrow.",
+                "prompt": {"content": "Answer: where is my order?"},
+            })),
+            WorkflowStepLog::new("failed", "chat_completion").with_error("timeout"),
+        ]);
+        let failed = ExecutionLog::failed(
+            ExecutionType::Workflow,
+            "support-flow",
+            "boom",
+            10,
+            Executor::from_api_key("key-1"),
+        );
+
+        let export = build_training_export(&[log, failed], None);
+        assert_eq!(export.examples, 1);
+        assert_eq!(export.skipped_logs, 1);
+
+        let example: Value = serde_json::from_str(&export.content).unwrap();
+        assert_eq!(example["messages"][0]["role"], "user");
+        assert_eq!(example["messages"][1]["content"], "It ships tomorrow.");
+    }
+}
@@ -0,0 +1,15 @@
+//! Fine-tuning domain module: provider fine-tuning jobs trained on gateway
+//! datasets, whose models are registered as gateway models, and training
+//! data exported from execution logs
+
+mod entity;
+mod export;
+mod provider;
+mod repository;
+mod training;
+
+pub use entity::*;
+pub use export::*;
+pub use provider::*;
+pub use repository::*;
+pub use training::*;
@@ -0,0 +1,54 @@
+//! Fine-tuning provider traits
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::{FineTuneHyperparameters, ProviderFineTuneJob};
+use crate::domain::DomainError;
+use crate::domain::credentials::CredentialType;
+
+/// Job submitted to a provider
+#[derive(Debug, Clone)]
+pub struct FineTuneJobParams {
+    pub base_model: String,
+    pub training_file_id: String,
+    pub suffix: Option<String>,
+    pub hyperparameters: FineTuneHyperparameters,
+}
+
+/// Provider fine-tuning API
+#[async_trait]
+pub trait FineTuneProvider: Send + Sync + Debug {
+    /// Provider type of the jobs and of the trained models
+    fn provider_type(&self) -> CredentialType;
+
+    /// Upload a JSONL training file, returning its provider ID
+    async fn upload_training_file(
+        &self,
+        filename: &str,
+        content: String,
+    ) -> Result<String, DomainError>;
+
+    /// Start a job
+    async fn create_job(
+        &self,
+        params: FineTuneJobParams,
+    ) -> Result<ProviderFineTuneJob, DomainError>;
+
+    /// Get the current state of a job
+    async fn get_job(&self, job_id: &str) -> Result<ProviderFineTuneJob, DomainError>;
+
+    /// Cancel a running job
+    async fn cancel_job(&self, job_id: &str) -> Result<ProviderFineTuneJob, DomainError>;
+}
+
+/// Resolves the fine-tuning API of a stored credential
+#[async_trait]
+pub trait FineTuneProviderFactory: Send + Sync + Debug {
+    async fn for_credential(
+        &self,
+        credential_id: &str,
+    ) -> Result<Arc<dyn FineTuneProvider>, DomainError>;
+}
@@ -0,0 +1,20 @@
+//! Fine-tuning job repository trait
+
+use async_trait::async_trait;
+use std::fmt::Debug;
+
+use super::{FineTuneJob, FineTuneJobId};
+use crate::domain::DomainError;
+
+/// Repository for fine-tuning jobs
+#[async_trait]
+pub trait FineTuneJobRepository: Send + Sync + Debug {
+    /// Get a job by ID
+    async fn get(&self, id: &FineTuneJobId) -> Result<Option<FineTuneJob>, DomainError>;
+
+    /// List every job, newest first
+    async fn list(&self) -> Result<Vec<FineTuneJob>, DomainError>;
+
+    /// Create or replace a job
+    async fn save(&self, job: FineTuneJob) -> Result<FineTuneJob, DomainError>;
+}
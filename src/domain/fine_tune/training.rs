@@ -0,0 +1,107 @@
+//! Training files built from dataset rows
+
+use serde_json::json;
+
+use crate::domain::DomainError;
+use crate::domain::dataset::DatasetRow;
+
+/// Input field used as the user message as-is
+const PROMPT_FIELD: &str = "input";
+
+/// User message of a row: the `input` field alone, otherwise every input as
+/// `name: value` lines in name order
+pub fn training_prompt(row: &DatasetRow) -> String {
+    if row.input.len() == 1
+        && let Some(prompt) = row.input.get(PROMPT_FIELD)
+    {
+        return prompt.clone();
+    }
+
+    let mut fields: Vec<(&String, &String)> = row.input.iter().collect();
+    fields.sort_by(|a, b| a.0.cmp(b.0));
+
+    fields
+        .into_iter()
+        .map(|(name, value)| format!("{}: {}", name, value))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Chat-format JSONL training file: one conversation per row with an
+/// expected output, which becomes the assistant message. Rows without one
+/// are skipped.
+pub fn build_training_file(
+    rows: &[DatasetRow],
+    system_prompt: Option<&str>,
+) -> Result<String, DomainError> {
+    let mut lines = Vec::new();
+
+    for row in rows {
+        let Some(expected) = row.expected_output.as_deref() else {
+            continue;
+        };
+
+        let mut messages = Vec::new();
+        if let Some(system_prompt) = system_prompt {
+            messages.push(json!({"role": "system", "content": system_prompt}));
+        }
+        messages.push(json!({"role": "user", "content": training_prompt(row)}));
+        messages.push(json!({"role": "assistant", "content": expected}));
+
+        lines.push(json!({ "messages": messages }).to_string());
+    }
+
+    if lines.is_empty() {
+        return Err(DomainError::validation(
+            "Dataset has no rows with an expected output to train on",
+        ));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn row(input: &[(&str, &str)], expected: Option<&str>) -> DatasetRow {
+        DatasetRow {
+            input: input
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>(),
+            expected_output: expected.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_training_prompt() {
+        assert_eq!(
+            training_prompt(&row(&[("input", "Where is my order?")], None)),
+            "Where is my order?"
+        );
+        assert_eq!(
+            training_prompt(&row(&[("topic", "billing"), ("customer", "Ann")], None)),
+            "customer: Ann\ntopic: billing"
+        );
+    }
+
+    #[test]
+    fn test_build_training_file() {
+        let rows = vec![
+            row(&[("input", "Hi")], Some("Hello!")),
+            row(&[("input", "Skipped")], None),
+        ];
+
+        let file = build_training_file(&rows, Some("Be brief")).unwrap();
+        let lines: Vec<&str> = file.lines().collect();
+        assert_eq!(lines.len(), 1);
+
+        let example: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(example["messages"][0]["content"], "Be brief");
+        assert_eq!(example["messages"][2]["content"], "Hello!");
+
+        assert!(build_training_file(&rows[1..], None).is_err());
+    }
+}
@@ -0,0 +1,183 @@
+//! Lightweight language detection for content policies
+//!
+//! Non-Latin scripts map directly to a language; Latin-script text is
+//! attributed to the language with the most matching stopwords. Text too
+//! short or ambiguous to tell is left undetected rather than guessed.
+
+/// Fewest stopword hits needed to attribute Latin-script text to a language
+const MIN_STOPWORD_HITS: usize = 2;
+
+/// Share of letters a non-Latin script must cover to decide the language
+const SCRIPT_SHARE: f64 = 0.3;
+
+/// Languages detection can report, as ISO 639-1 codes
+pub const SUPPORTED_LANGUAGES: &[&str] = &[
+    "en", "es", "fr", "de", "it", "pt", "nl", "ru", "el", "ar", "he", "hi", "th", "zh", "ja", "ko",
+];
+
+const STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "is", "are", "of", "to", "in", "that", "it", "with", "for", "you",
+            "this", "was", "what", "how", "please",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "la", "los", "las", "y", "es", "de", "que", "en", "un", "una", "por", "para",
+            "con", "cómo", "qué", "está",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "et", "est", "de", "des", "que", "un", "une", "pour", "avec",
+            "dans", "vous", "je", "ce", "pas",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "ein", "eine", "ich", "zu", "mit", "sie",
+            "wie", "für", "auf", "den",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "il", "lo", "gli", "e", "è", "di", "che", "un", "una", "per", "con", "non", "sono",
+            "come", "della", "questo",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "o", "os", "as", "e", "é", "de", "que", "um", "uma", "para", "com", "não", "como",
+            "você", "está", "do", "da",
+        ],
+    ),
+    (
+        "nl",
+        &[
+            "de", "het", "een", "en", "is", "van", "dat", "niet", "ik", "je", "met", "voor", "op",
+            "zijn", "wat", "hoe",
+        ],
+    ),
+];
+
+/// Script-specific languages, checked by Unicode range
+fn script_language(c: char) -> Option<&'static str> {
+    match c {
+        '\u{0400}'..='\u{04FF}' => Some("ru"),
+        '\u{0370}'..='\u{03FF}' => Some("el"),
+        '\u{0600}'..='\u{06FF}' => Some("ar"),
+        '\u{0590}'..='\u{05FF}' => Some("he"),
+        '\u{0900}'..='\u{097F}' => Some("hi"),
+        '\u{0E00}'..='\u{0E7F}' => Some("th"),
+        '\u{3040}'..='\u{30FF}' => Some("ja"),
+        '\u{AC00}'..='\u{D7AF}' | '\u{1100}'..='\u{11FF}' => Some("ko"),
+        '\u{4E00}'..='\u{9FFF}' => Some("zh"),
+        _ => None,
+    }
+}
+
+/// Detect the dominant language of text as an ISO 639-1 code, `None` when
+/// it cannot be told
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    let letters: Vec<char> = text.chars().filter(|c| c.is_alphabetic()).collect();
+
+    if letters.is_empty() {
+        return None;
+    }
+
+    let mut script_counts: Vec<(&'static str, usize)> = Vec::new();
+    for language in letters.iter().filter_map(|c| script_language(*c)) {
+        match script_counts.iter_mut().find(|(l, _)| *l == language) {
+            Some((_, count)) => *count += 1,
+            None => script_counts.push((language, 1)),
+        }
+    }
+
+    // Japanese mixes kana with Han characters, so text with kana counts
+    // both towards Japanese
+    if script_counts.iter().any(|(l, _)| *l == "ja") {
+        let cjk: usize = script_counts
+            .iter()
+            .filter(|(l, _)| matches!(*l, "ja" | "zh"))
+            .map(|(_, count)| count)
+            .sum();
+        if cjk as f64 / letters.len() as f64 >= SCRIPT_SHARE {
+            return Some("ja");
+        }
+    }
+
+    if let Some((language, count)) = script_counts.iter().max_by_key(|(_, count)| *count)
+        && *count as f64 / letters.len() as f64 >= SCRIPT_SHARE
+    {
+        return Some(language);
+    }
+
+    let lowercase = text.to_lowercase();
+    let words: Vec<&str> = lowercase
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let mut best: Option<(&'static str, usize)> = None;
+    let mut tied = false;
+    for (language, stopwords) in STOPWORDS {
+        let hits = words.iter().filter(|w| stopwords.contains(w)).count();
+        match best {
+            Some((_, best_hits)) if hits == best_hits => tied = true,
+            Some((_, best_hits)) if hits < best_hits => {}
+            _ => {
+                best = Some((language, hits));
+                tied = false;
+            }
+        }
+    }
+
+    best.filter(|(_, hits)| !tied && *hits >= MIN_STOPWORD_HITS)
+        .map(|(language, _)| language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_latin_languages() {
+        assert_eq!(
+            detect_language("What is the capital of France and how big is it?"),
+            Some("en")
+        );
+        assert_eq!(
+            detect_language("¿Cuál es la capital de Francia y qué tan grande es?"),
+            Some("es")
+        );
+        assert_eq!(
+            detect_language("Quelle est la capitale de la France et est-ce que vous savez?"),
+            Some("fr")
+        );
+        assert_eq!(
+            detect_language("Was ist die Hauptstadt von Frankreich und wie groß ist sie?"),
+            Some("de")
+        );
+    }
+
+    #[test]
+    fn test_detect_script_languages() {
+        assert_eq!(detect_language("Какая столица Франции?"), Some("ru"));
+        assert_eq!(detect_language("フランスの首都はどこですか？"), Some("ja"));
+        assert_eq!(detect_language("法国的首都是哪里？"), Some("zh"));
+        assert_eq!(detect_language("프랑스의 수도는 어디입니까?"), Some("ko"));
+    }
+
+    #[test]
+    fn test_undetected_text() {
+        assert_eq!(detect_language("12345 !!!"), None);
+        assert_eq!(detect_language("Paris"), None);
+    }
+}
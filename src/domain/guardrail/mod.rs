@@ -0,0 +1,25 @@
+//! Guardrails applied to completion requests and their answers
+
+mod injection;
+mod language;
+mod moderation;
+mod pii;
+mod policy;
+mod secrets;
+
+pub use injection::{
+    INJECTION_CLASSIFIER_PROMPT, InjectionAction, InjectionDetection, InjectionGuard,
+    InjectionScan, SANITIZED_PLACEHOLDER, parse_classifier_score, sanitize_injection,
+    scan_injection,
+};
+pub use language::{SUPPORTED_LANGUAGES, detect_language};
+pub use moderation::ModerationProvider;
+pub use pii::redact_pii;
+pub use policy::{
+    BlockedTopic, ContentPolicy, ContentPolicyId, MAX_CONTENT_POLICY_ID_LENGTH, PolicyRule,
+    PolicyStage, PolicyViolation,
+};
+pub use secrets::{
+    KNOWN_CREDENTIAL_KIND, MIN_KNOWN_SECRET_LEN, REDACTED_PLACEHOLDER, SecretLeakAction,
+    SecretLeakGuard, SecretMatch, SecretScanner, SecretStreamFilter,
+};
@@ -0,0 +1,17 @@
+//! Moderation provider used to score toxicity for content policies
+
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+
+use crate::domain::DomainError;
+
+/// External moderation service scoring how toxic text is
+#[async_trait]
+pub trait ModerationProvider: Send + Sync + Debug {
+    /// Name of the provider, e.g. `openai`
+    fn provider(&self) -> &str;
+
+    /// Toxicity of the text, between 0 (benign) and 1 (toxic)
+    async fn toxicity(&self, text: &str) -> Result<f64, DomainError>;
+}
@@ -0,0 +1,73 @@
+//! Personal data redaction
+//!
+//! Email addresses, phone numbers, payment card numbers, US social security
+//! numbers and IP addresses are replaced with a placeholder naming their
+//! kind, e.g. `[email]`, so text keeps its shape once the values are gone.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+struct PiiPattern {
+    placeholder: &'static str,
+    pattern: Regex,
+}
+
+impl PiiPattern {
+    fn new(placeholder: &'static str, pattern: &str) -> Self {
+        Self {
+            placeholder,
+            pattern: Regex::new(pattern).unwrap(),
+        }
+    }
+}
+
+/// Patterns applied in order; card numbers and SSNs go before phone numbers,
+/// which would otherwise match parts of them
+static PII_PATTERNS: Lazy<Vec<PiiPattern>> = Lazy::new(|| {
+    vec![
+        PiiPattern::new(
+            "[email]",
+            r"\b[A-Za-z0-9._%+\-]+@[A-Za-z0-9.\-]+\.[A-Za-z]{2,}\b",
+        ),
+        PiiPattern::new("[card_number]", r"\b(?:\d[ \-]?){12,18}\d\b"),
+        PiiPattern::new("[ssn]", r"\b\d{3}-\d{2}-\d{4}\b"),
+        PiiPattern::new(
+            "[ip_address]",
+            r"\b(?:(?:25[0-5]|2[0-4]\d|1?\d?\d)\.){3}(?:25[0-5]|2[0-4]\d|1?\d?\d)\b",
+        ),
+        PiiPattern::new(
+            "[phone]",
+            r"(?:\+\d{1,3}[ .\-]?)?(?:\(\d{2,4}\)[ .\-]?|\b\d{2,4}[ .\-])\d{3,4}[ .\-]?\d{3,4}\b",
+        ),
+    ]
+});
+
+/// Replace the personal data found in the text with placeholders
+pub fn redact_pii(text: &str) -> String {
+    PII_PATTERNS
+        .iter()
+        .fold(text.to_string(), |text, pii| {
+            pii.pattern.replace_all(&text, pii.placeholder).into_owned()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_pii() {
+        assert_eq!(
+            redact_pii("Mail ann.lee+orders@example.co.uk or call +1 415-555-0132"),
+            "Mail [email] or call [phone]"
+        );
+        assert_eq!(
+            redact_pii("Card 4111 1111 1111 1111, SSN 123-45-6789, from 10.0.12.7"),
+            "Card [card_number], SSN [ssn], from [ip_address]"
+        );
+        assert_eq!(
+            redact_pii("Order 12345 ships in 3 days"),
+            "Order 12345 ships in 3 days"
+        );
+    }
+}
@@ -0,0 +1,466 @@
+//! Content policies evaluated on completion input and output
+//!
+//! A policy bundles rules - blocked topics, banned phrases, a toxicity
+//! ceiling scored by a moderation provider and allowed languages - applied
+//! to the user messages before the provider is called and to its answer
+//! afterwards. Policies without a team apply to every team.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::language::{SUPPORTED_LANGUAGES, detect_language};
+use crate::domain::DomainError;
+use crate::domain::storage::{StorageEntity, StorageKey};
+
+/// Longest allowed content policy ID
+pub const MAX_CONTENT_POLICY_ID_LENGTH: usize = 50;
+
+/// Content policy identifier - alphanumeric + hyphens, max 50 characters
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub struct ContentPolicyId(String);
+
+impl ContentPolicyId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// Get the inner string value
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for ContentPolicyId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StorageKey for ContentPolicyId {
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl StorageEntity for ContentPolicy {
+    type Key = ContentPolicyId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+/// Point of a completion a policy is evaluated at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyStage {
+    /// User messages, before the provider is called
+    Input,
+    /// Provider answer, after the completion
+    Output,
+}
+
+impl PolicyStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PolicyStage::Input => "input",
+            PolicyStage::Output => "output",
+        }
+    }
+}
+
+/// Rule of a policy a text violated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyRule {
+    BlockedTopic,
+    BannedPhrase,
+    Toxicity,
+    Language,
+}
+
+impl PolicyRule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PolicyRule::BlockedTopic => "blocked_topic",
+            PolicyRule::BannedPhrase => "banned_phrase",
+            PolicyRule::Toxicity => "toxicity",
+            PolicyRule::Language => "language",
+        }
+    }
+}
+
+/// A topic and the keywords that mention it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BlockedTopic {
+    pub name: String,
+    /// Words or phrases matched case-insensitively on word boundaries
+    pub keywords: Vec<String>,
+}
+
+/// A rule of a content policy broken by a text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PolicyViolation {
+    pub policy_id: String,
+    pub rule: PolicyRule,
+    pub stage: PolicyStage,
+    pub message: String,
+}
+
+impl PolicyViolation {
+    fn new(policy: &ContentPolicy, rule: PolicyRule, stage: PolicyStage, message: String) -> Self {
+        Self {
+            policy_id: policy.id.to_string(),
+            rule,
+            stage,
+            message,
+        }
+    }
+}
+
+/// Rules admins define to restrict what goes in and comes out of completions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ContentPolicy {
+    pub id: ContentPolicyId,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Team the policy applies to, every team when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<String>,
+    /// Stages the policy is evaluated at
+    #[serde(default = "default_stages")]
+    pub stages: Vec<PolicyStage>,
+    #[serde(default)]
+    pub blocked_topics: Vec<BlockedTopic>,
+    /// Phrases matched case-insensitively anywhere in the text
+    #[serde(default)]
+    pub banned_phrases: Vec<String>,
+    /// Highest toxicity score (0-1) of the moderation provider allowed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_toxicity: Option<f64>,
+    /// ISO 639-1 codes of the languages allowed, any language when empty
+    #[serde(default)]
+    pub allowed_languages: Vec<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Creation date (unix timestamp)
+    pub created_at: u64,
+    /// Last update date (unix timestamp)
+    pub updated_at: u64,
+}
+
+fn default_stages() -> Vec<PolicyStage> {
+    vec![PolicyStage::Input, PolicyStage::Output]
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl ContentPolicy {
+    /// Create a policy without rules, evaluated on input and output of
+    /// every team
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        let now = chrono::Utc::now().timestamp().max(0) as u64;
+
+        Self {
+            id: ContentPolicyId::new(id),
+            name: name.into(),
+            description: None,
+            team_id: None,
+            stages: default_stages(),
+            blocked_topics: Vec::new(),
+            banned_phrases: Vec::new(),
+            max_toxicity: None,
+            allowed_languages: Vec::new(),
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Restrict the policy to a team
+    pub fn with_team(mut self, team_id: impl Into<String>) -> Self {
+        self.team_id = Some(team_id.into());
+        self
+    }
+
+    /// Set the stages the policy is evaluated at
+    pub fn with_stages(mut self, stages: Vec<PolicyStage>) -> Self {
+        self.stages = stages;
+        self
+    }
+
+    /// Block a topic mentioned by any of the keywords
+    pub fn with_blocked_topic(mut self, name: impl Into<String>, keywords: Vec<String>) -> Self {
+        self.blocked_topics.push(BlockedTopic {
+            name: name.into(),
+            keywords,
+        });
+        self
+    }
+
+    /// Ban a phrase
+    pub fn with_banned_phrase(mut self, phrase: impl Into<String>) -> Self {
+        self.banned_phrases.push(phrase.into());
+        self
+    }
+
+    /// Set the highest toxicity allowed
+    pub fn with_max_toxicity(mut self, max_toxicity: f64) -> Self {
+        self.max_toxicity = Some(max_toxicity);
+        self
+    }
+
+    /// Set the languages allowed
+    pub fn with_allowed_languages(mut self, languages: Vec<String>) -> Self {
+        self.allowed_languages = languages;
+        self
+    }
+
+    /// Whether the policy is evaluated for a team at a stage
+    pub fn applies_to(&self, team_id: &str, stage: PolicyStage) -> bool {
+        self.enabled
+            && self.stages.contains(&stage)
+            && self.team_id.as_deref().is_none_or(|t| t == team_id)
+    }
+
+    /// Validates the ID and rules
+    pub fn validate(&self) -> Result<(), DomainError> {
+        let id = self.id.as_str();
+
+        if id.is_empty() || id.len() > MAX_CONTENT_POLICY_ID_LENGTH {
+            return Err(DomainError::validation(format!(
+                "Content policy ID must be 1-{} characters",
+                MAX_CONTENT_POLICY_ID_LENGTH
+            )));
+        }
+
+        if !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            || id.starts_with('-')
+            || id.ends_with('-')
+        {
+            return Err(DomainError::validation(
+                "Content policy ID must be alphanumeric with inner hyphens",
+            ));
+        }
+
+        if self.name.trim().is_empty() {
+            return Err(DomainError::validation("name is required"));
+        }
+
+        if self.stages.is_empty() {
+            return Err(DomainError::validation(
+                "stages must include input, output or both",
+            ));
+        }
+
+        if self.blocked_topics.is_empty()
+            && self.banned_phrases.is_empty()
+            && self.max_toxicity.is_none()
+            && self.allowed_languages.is_empty()
+        {
+            return Err(DomainError::validation(
+                "At least one of blocked_topics, banned_phrases, max_toxicity or allowed_languages is required",
+            ));
+        }
+
+        for topic in &self.blocked_topics {
+            if topic.name.trim().is_empty() {
+                return Err(DomainError::validation("Blocked topics require a name"));
+            }
+            if topic.keywords.iter().all(|k| k.trim().is_empty()) {
+                return Err(DomainError::validation(format!(
+                    "Blocked topic '{}' requires at least one keyword",
+                    topic.name
+                )));
+            }
+        }
+
+        if self.banned_phrases.iter().any(|p| p.trim().is_empty()) {
+            return Err(DomainError::validation("Banned phrases must not be empty"));
+        }
+
+        if self
+            .max_toxicity
+            .is_some_and(|max| !(0.0..=1.0).contains(&max))
+        {
+            return Err(DomainError::validation(
+                "max_toxicity must be between 0 and 1",
+            ));
+        }
+
+        if let Some(language) = self
+            .allowed_languages
+            .iter()
+            .find(|l| !SUPPORTED_LANGUAGES.contains(&l.as_str()))
+        {
+            return Err(DomainError::validation(format!(
+                "Unsupported language '{}', expected one of: {}",
+                language,
+                SUPPORTED_LANGUAGES.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Evaluate the rules that need no moderation provider against a text
+    pub fn check_text(&self, text: &str, stage: PolicyStage) -> Vec<PolicyViolation> {
+        let mut violations = Vec::new();
+        let lowercase = text.to_lowercase();
+
+        for topic in &self.blocked_topics {
+            if topic.keywords.iter().any(|k| mentions(&lowercase, k)) {
+                violations.push(PolicyViolation::new(
+                    self,
+                    PolicyRule::BlockedTopic,
+                    stage,
+                    format!("Mentions blocked topic '{}'", topic.name),
+                ));
+            }
+        }
+
+        for phrase in &self.banned_phrases {
+            if lowercase.contains(&phrase.to_lowercase()) {
+                violations.push(PolicyViolation::new(
+                    self,
+                    PolicyRule::BannedPhrase,
+                    stage,
+                    format!("Contains banned phrase '{}'", phrase),
+                ));
+            }
+        }
+
+        if !self.allowed_languages.is_empty()
+            && let Some(language) = detect_language(text)
+            && !self.allowed_languages.iter().any(|l| l == language)
+        {
+            violations.push(PolicyViolation::new(
+                self,
+                PolicyRule::Language,
+                stage,
+                format!(
+                    "Language '{}' is not allowed, expected one of: {}",
+                    language,
+                    self.allowed_languages.join(", ")
+                ),
+            ));
+        }
+
+        violations
+    }
+
+    /// Evaluate the toxicity ceiling against a moderation score
+    pub fn check_toxicity(&self, toxicity: f64, stage: PolicyStage) -> Option<PolicyViolation> {
+        let max = self.max_toxicity?;
+
+        (toxicity > max).then(|| {
+            PolicyViolation::new(
+                self,
+                PolicyRule::Toxicity,
+                stage,
+                format!("Toxicity {:.2} exceeds the maximum of {:.2}", toxicity, max),
+            )
+        })
+    }
+}
+
+/// Whether lowercase text mentions a keyword as whole words
+fn mentions(lowercase: &str, keyword: &str) -> bool {
+    let keyword = keyword.trim().to_lowercase();
+
+    if keyword.is_empty() {
+        return false;
+    }
+
+    Regex::new(&format!(r"\b{}\b", regex::escape(&keyword)))
+        .map(|pattern| pattern.is_match(lowercase))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> ContentPolicy {
+        ContentPolicy::new("support", "Support")
+            .with_blocked_topic("politics", vec!["election".to_string(), "vote".to_string()])
+            .with_banned_phrase("Acme Corp")
+            .with_allowed_languages(vec!["en".to_string()])
+            .with_max_toxicity(0.5)
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(policy().validate().is_ok());
+        assert!(ContentPolicy::new("empty", "Empty").validate().is_err());
+        assert!(
+            ContentPolicy::new("bad id", "Bad")
+                .with_banned_phrase("x")
+                .validate()
+                .is_err()
+        );
+        assert!(policy().with_max_toxicity(1.5).validate().is_err());
+        assert!(
+            policy()
+                .with_allowed_languages(vec!["xx".to_string()])
+                .validate()
+                .is_err()
+        );
+        assert!(policy().with_stages(vec![]).validate().is_err());
+    }
+
+    #[test]
+    fn test_check_text() {
+        let policy = policy();
+
+        let violations = policy.check_text(
+            "Who should I vote for? Acme corp says the other one.",
+            PolicyStage::Input,
+        );
+        let rules: Vec<PolicyRule> = violations.iter().map(|v| v.rule).collect();
+        assert_eq!(
+            rules,
+            vec![PolicyRule::BlockedTopic, PolicyRule::BannedPhrase]
+        );
+        assert_eq!(violations[0].policy_id, "support");
+
+        // Keywords match whole words only
+        assert!(
+            policy
+                .check_text("What does the word devote mean?", PolicyStage::Input)
+                .is_empty()
+        );
+
+        let violations = policy.check_text(
+            "¿Cuál es la capital de Francia y qué tan grande es?",
+            PolicyStage::Output,
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].rule, PolicyRule::Language);
+        assert_eq!(violations[0].stage, PolicyStage::Output);
+    }
+
+    #[test]
+    fn test_check_toxicity_and_scope() {
+        let policy = policy().with_team("team-a");
+
+        assert!(policy.check_toxicity(0.4, PolicyStage::Input).is_none());
+        assert_eq!(
+            policy
+                .check_toxicity(0.9, PolicyStage::Input)
+                .map(|v| v.rule),
+            Some(PolicyRule::Toxicity)
+        );
+
+        assert!(policy.applies_to("team-a", PolicyStage::Input));
+        assert!(!policy.applies_to("team-b", PolicyStage::Input));
+
+        let output_only = policy.with_stages(vec![PolicyStage::Output]);
+        assert!(!output_only.applies_to("team-a", PolicyStage::Input));
+        assert!(output_only.applies_to("team-a", PolicyStage::Output));
+    }
+}
@@ -0,0 +1,173 @@
+//! Chunk enrichment with LLM-generated titles, summaries and keywords
+//!
+//! Terse chunks (a table row, a heading and two lines) embed poorly on their
+//! own. Enrichment asks a model for a title, a one-sentence summary and
+//! keywords per chunk, stores them as chunk metadata and can prepend them to
+//! the text that is embedded.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use utoipa::ToSchema;
+
+use crate::domain::DomainError;
+
+/// Chunk metadata keys of the enrichment
+pub const CHUNK_TITLE_KEY: &str = "chunk_title";
+pub const CHUNK_SUMMARY_KEY: &str = "chunk_summary";
+pub const CHUNK_KEYWORDS_KEY: &str = "chunk_keywords";
+
+/// Enrichment step of an ingestion
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct ChunkEnrichmentConfig {
+    /// Model generating the enrichment
+    pub model_id: String,
+    /// Embed the enrichment together with the chunk text, where the gateway
+    /// computes the chunk embeddings
+    #[serde(default)]
+    pub embed: bool,
+    /// Chunks sent per model call
+    #[serde(default = "default_enrichment_batch_size")]
+    pub batch_size: usize,
+}
+
+fn default_enrichment_batch_size() -> usize {
+    8
+}
+
+impl ChunkEnrichmentConfig {
+    /// Enrich with the given model
+    pub fn new(model_id: impl Into<String>) -> Self {
+        Self {
+            model_id: model_id.into(),
+            embed: false,
+            batch_size: default_enrichment_batch_size(),
+        }
+    }
+
+    /// Embed the enrichment together with the chunk text
+    pub fn with_embed(mut self, embed: bool) -> Self {
+        self.embed = embed;
+        self
+    }
+
+    /// Set the chunks sent per model call
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Validate the configuration
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.model_id.trim().is_empty() {
+            return Err(DomainError::validation("Enrichment model_id is required"));
+        }
+        if !(1..=50).contains(&self.batch_size) {
+            return Err(DomainError::validation(
+                "Enrichment batch_size must be between 1 and 50",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Generated description of a chunk
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkEnrichment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+impl ChunkEnrichment {
+    /// Whether nothing was generated
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.summary.is_none() && self.keywords.is_empty()
+    }
+
+    /// Add the enrichment to chunk metadata
+    pub fn apply_to(&self, metadata: &mut HashMap<String, serde_json::Value>) {
+        if let Some(title) = &self.title {
+            metadata.insert(CHUNK_TITLE_KEY.to_string(), serde_json::json!(title));
+        }
+        if let Some(summary) = &self.summary {
+            metadata.insert(CHUNK_SUMMARY_KEY.to_string(), serde_json::json!(summary));
+        }
+        if !self.keywords.is_empty() {
+            metadata.insert(CHUNK_KEYWORDS_KEY.to_string(), serde_json::json!(self.keywords));
+        }
+    }
+
+    /// Text to embed for a chunk: the enrichment followed by the chunk text
+    pub fn embedding_text(&self, content: &str) -> String {
+        let mut text = String::new();
+
+        if let Some(title) = &self.title {
+            text.push_str(&format!("Title: {}\n", title));
+        }
+        if let Some(summary) = &self.summary {
+            text.push_str(&format!("Summary: {}\n", summary));
+        }
+        if !self.keywords.is_empty() {
+            text.push_str(&format!("Keywords: {}\n", self.keywords.join(", ")));
+        }
+        if !text.is_empty() {
+            text.push('\n');
+        }
+
+        text.push_str(content);
+        text
+    }
+}
+
+/// Generates the enrichment of chunks
+#[async_trait]
+pub trait ChunkEnricher: Send + Sync + Debug {
+    /// Enrich chunks, returning one enrichment per chunk in order
+    async fn enrich(
+        &self,
+        config: &ChunkEnrichmentConfig,
+        chunks: &[String],
+    ) -> Result<Vec<ChunkEnrichment>, DomainError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enrichment_metadata_and_embedding_text() {
+        let enrichment = ChunkEnrichment {
+            title: Some("Refund window".to_string()),
+            summary: Some("Refunds are accepted for 30 days.".to_string()),
+            keywords: vec!["refund".to_string(), "returns".to_string()],
+        };
+
+        let mut metadata = HashMap::new();
+        enrichment.apply_to(&mut metadata);
+        assert_eq!(metadata[CHUNK_TITLE_KEY], "Refund window");
+        assert_eq!(metadata[CHUNK_KEYWORDS_KEY], serde_json::json!(["refund", "returns"]));
+
+        assert_eq!(
+            enrichment.embedding_text("30 days"),
+            "Title: Refund window\nSummary: Refunds are accepted for 30 days.\n\
+             Keywords: refund, returns\n\n30 days"
+        );
+        assert_eq!(ChunkEnrichment::default().embedding_text("raw"), "raw");
+    }
+
+    #[test]
+    fn test_enrichment_config_validation() {
+        let config: ChunkEnrichmentConfig =
+            serde_json::from_str(r#"{"model_id": "gpt-4o-mini"}"#).unwrap();
+        assert_eq!(config, ChunkEnrichmentConfig::new("gpt-4o-mini"));
+        assert!(config.validate().is_ok());
+
+        assert!(ChunkEnrichmentConfig::new(" ").validate().is_err());
+        assert!(ChunkEnrichmentConfig::new("m").with_batch_size(0).validate().is_err());
+    }
+}
@@ -3,15 +3,23 @@
 //! This module provides:
 //! - `DocumentParser` trait for parsing various document formats
 //! - `ChunkingStrategy` trait for splitting documents into chunks
+//! - `OcrProvider` trait for reading scanned PDFs and images
+//! - `ChunkEnricher` trait for LLM-generated chunk titles, summaries and keywords
+//! - `IngestionProgress` of the documents of batch ingestions
 //! - Configuration and result types for the ingestion pipeline
 
 pub mod chunker;
+pub mod enrichment;
+pub mod ocr;
 pub mod parser;
 pub mod pipeline;
+pub mod progress;
 pub mod validation;
 
 // Re-export main types
 pub use chunker::{Chunk, ChunkingConfig, ChunkingStrategy, ChunkMetadata};
+pub use enrichment::{ChunkEnricher, ChunkEnrichment, ChunkEnrichmentConfig};
+pub use ocr::{OcrEngine, OcrProvider, OcrProviderResolver};
 pub use parser::{
     DocumentMetadata, DocumentParser, ParsedDocument, ParserContent, ParserInput,
 };
@@ -19,6 +27,9 @@ pub use pipeline::{
     BatchIngestionResult, ChunkingType, IngestionConfig, IngestionError, IngestionResult,
     ParserType,
 };
+pub use progress::{
+    DocumentProgress, IngestionProgress, IngestionProgressEvent, IngestionStage,
+};
 pub use validation::{
     detect_parser_from_filename, detect_parser_from_mime, validate_batch_size,
     validate_chunk_params, validate_document_id,
@@ -0,0 +1,88 @@
+//! OCR engine selection and traits
+//!
+//! Scanned PDFs and images carry no text a parser can read, so they are
+//! passed through an OCR engine first: the Tesseract command line tool, or a
+//! vision-capable model asked to transcribe the page.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::domain::DomainError;
+
+/// OCR engine used to extract text from scanned pages
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "engine", rename_all = "snake_case")]
+pub enum OcrEngine {
+    /// Local Tesseract installation
+    Tesseract {
+        /// Tesseract language codes, e.g. "eng" or "eng+deu"
+        #[serde(default = "default_ocr_language")]
+        language: String,
+    },
+    /// Vision-capable model asked to transcribe each page
+    VisionModel {
+        /// Configured model ID
+        model_id: String,
+    },
+}
+
+fn default_ocr_language() -> String {
+    "eng".to_string()
+}
+
+impl OcrEngine {
+    /// Tesseract with the given languages
+    pub fn tesseract(language: impl Into<String>) -> Self {
+        Self::Tesseract {
+            language: language.into(),
+        }
+    }
+
+    /// Vision model OCR with the given model
+    pub fn vision_model(model_id: impl Into<String>) -> Self {
+        Self::VisionModel {
+            model_id: model_id.into(),
+        }
+    }
+
+    /// Engine name, as recorded in chunk metadata
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Tesseract { .. } => "tesseract",
+            Self::VisionModel { .. } => "vision_model",
+        }
+    }
+}
+
+/// Extracts the text of a page image
+#[async_trait]
+pub trait OcrProvider: Send + Sync + Debug {
+    /// Recognize the text of an encoded image (PNG, JPEG, TIFF, ...)
+    async fn recognize(&self, image: &[u8], mime_type: &str) -> Result<String, DomainError>;
+}
+
+/// Creates the OCR provider of an engine
+#[async_trait]
+pub trait OcrProviderResolver: Send + Sync + Debug {
+    async fn resolve(&self, engine: &OcrEngine) -> Result<Arc<dyn OcrProvider>, DomainError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ocr_engine_serialization() {
+        let engine: OcrEngine = serde_json::from_str(r#"{"engine": "tesseract"}"#).unwrap();
+        assert_eq!(engine, OcrEngine::tesseract("eng"));
+
+        let engine: OcrEngine =
+            serde_json::from_str(r#"{"engine": "vision_model", "model_id": "gpt-4o"}"#).unwrap();
+        assert_eq!(engine, OcrEngine::vision_model("gpt-4o"));
+        assert_eq!(engine.name(), "vision_model");
+
+        assert!(serde_json::from_str::<OcrEngine>(r#"{"engine": "vision_model"}"#).is_err());
+    }
+}
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use super::chunker::ChunkingConfig;
+use super::enrichment::ChunkEnrichmentConfig;
+use super::ocr::OcrEngine;
 
 /// Type of document parser to use
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -17,8 +19,10 @@ pub enum ParserType {
     Html,
     /// JSON files (serializes entire content)
     Json,
-    /// PDF files (not yet implemented)
+    /// PDF files, OCR'd when they have no text layer
     Pdf,
+    /// Scanned pages and other images, always OCR'd
+    Image,
 }
 
 impl ParserType {
@@ -30,6 +34,7 @@ impl ParserType {
             Self::Html => &["html", "htm"],
             Self::Json => &["json"],
             Self::Pdf => &["pdf"],
+            Self::Image => &["png", "jpg", "jpeg", "tif", "tiff", "bmp", "gif", "webp"],
         }
     }
 
@@ -41,8 +46,21 @@ impl ParserType {
             Self::Html => &["text/html"],
             Self::Json => &["application/json"],
             Self::Pdf => &["application/pdf"],
+            Self::Image => &[
+                "image/png",
+                "image/jpeg",
+                "image/tiff",
+                "image/bmp",
+                "image/gif",
+                "image/webp",
+            ],
         }
     }
+
+    /// Whether documents of this type are binary and may need OCR
+    pub fn needs_ocr(&self) -> bool {
+        matches!(self, Self::Pdf | Self::Image)
+    }
 }
 
 /// Type of chunking strategy to use
@@ -81,6 +99,12 @@ pub struct IngestionConfig {
     /// Source identifier for the document
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_id: Option<String>,
+    /// OCR engine for scanned PDFs and images (rejected when None)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ocr: Option<OcrEngine>,
+    /// LLM enrichment of the chunks (skipped when None)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enrichment: Option<ChunkEnrichmentConfig>,
 }
 
 fn default_batch_size() -> usize {
@@ -96,6 +120,8 @@ impl Default for IngestionConfig {
             batch_size: default_batch_size(),
             metadata: HashMap::new(),
             source_id: None,
+            ocr: None,
+            enrichment: None,
         }
     }
 }
@@ -147,6 +173,18 @@ impl IngestionConfig {
         self.source_id = Some(source_id.into());
         self
     }
+
+    /// Set the OCR engine for scanned PDFs and images
+    pub fn with_ocr(mut self, engine: OcrEngine) -> Self {
+        self.ocr = Some(engine);
+        self
+    }
+
+    /// Enrich the chunks with an LLM
+    pub fn with_enrichment(mut self, enrichment: ChunkEnrichmentConfig) -> Self {
+        self.enrichment = Some(enrichment);
+        self
+    }
 }
 
 /// Error that occurred during ingestion of a specific chunk
@@ -292,6 +330,9 @@ mod tests {
         assert_eq!(ParserType::Markdown.extensions(), &["md", "markdown"]);
         assert_eq!(ParserType::Html.extensions(), &["html", "htm"]);
         assert_eq!(ParserType::Json.extensions(), &["json"]);
+        assert!(ParserType::Image.extensions().contains(&"tiff"));
+        assert!(ParserType::Pdf.needs_ocr());
+        assert!(!ParserType::Markdown.needs_ocr());
     }
 
     #[test]
@@ -357,12 +398,14 @@ mod tests {
     fn test_config_serialization() {
         let config = IngestionConfig::new()
             .with_parser_type(ParserType::Markdown)
-            .with_chunk_size(500);
+            .with_chunk_size(500)
+            .with_ocr(OcrEngine::tesseract("deu"));
 
         let json = serde_json::to_string(&config).unwrap();
         let parsed: IngestionConfig = serde_json::from_str(&json).unwrap();
 
         assert_eq!(parsed.parser_type, Some(ParserType::Markdown));
         assert_eq!(parsed.chunking_config.chunk_size, 500);
+        assert_eq!(parsed.ocr, Some(OcrEngine::tesseract("deu")));
     }
 }
@@ -0,0 +1,175 @@
+//! Per-document progress of batch ingestions
+//!
+//! A batch ingestion runs as an operation; each of its documents moves
+//! through the stages below and the operation record keeps the latest
+//! stage of every document, so progress survives restarts of the client
+//! following it.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Stage reached by a document of a batch ingestion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IngestionStage {
+    /// Uploaded, waiting to be processed
+    Queued,
+    /// Text extracted by the parser
+    Parsed,
+    /// Split into chunks
+    Chunked,
+    /// Chunks embedded by the knowledge base provider
+    Embedded,
+    /// Chunks stored in the knowledge base
+    Stored,
+    /// Ingestion failed, see the error
+    Failed,
+}
+
+impl IngestionStage {
+    /// Whether the document is done, stored or failed
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Stored | Self::Failed)
+    }
+}
+
+/// A document of a batch ingestion reaching a stage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct IngestionProgressEvent {
+    pub operation_id: String,
+    /// Filename of the document
+    pub document: String,
+    pub stage: IngestionStage,
+    /// Chunks of the document, once chunked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunks: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+impl IngestionProgressEvent {
+    pub fn new(
+        operation_id: impl Into<String>,
+        document: impl Into<String>,
+        stage: IngestionStage,
+    ) -> Self {
+        Self {
+            operation_id: operation_id.into(),
+            document: document.into(),
+            stage,
+            chunks: None,
+            error: None,
+            at: Utc::now(),
+        }
+    }
+
+    pub fn with_chunks(mut self, chunks: usize) -> Self {
+        self.chunks = Some(chunks);
+        self
+    }
+
+    pub fn with_error(mut self, error: impl Into<String>) -> Self {
+        self.error = Some(error.into());
+        self
+    }
+}
+
+/// Latest stage of one document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DocumentProgress {
+    pub document: String,
+    pub stage: IngestionStage,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunks: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Progress of every document of a batch ingestion, in upload order
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct IngestionProgress {
+    pub documents: Vec<DocumentProgress>,
+    /// Whether every document of the batch was uploaded, so the ingestion
+    /// is finished once they are all stored or failed
+    #[serde(default)]
+    pub sealed: bool,
+}
+
+impl IngestionProgress {
+    /// Record a document reaching a stage; the chunk count of earlier
+    /// stages is kept
+    pub fn apply(&mut self, event: &IngestionProgressEvent) {
+        match self
+            .documents
+            .iter_mut()
+            .find(|d| d.document == event.document)
+        {
+            Some(document) => {
+                document.stage = event.stage;
+                document.chunks = event.chunks.or(document.chunks);
+                document.error = event.error.clone();
+                document.updated_at = event.at;
+            }
+            None => self.documents.push(DocumentProgress {
+                document: event.document.clone(),
+                stage: event.stage,
+                chunks: event.chunks,
+                error: event.error.clone(),
+                updated_at: event.at,
+            }),
+        }
+    }
+
+    /// Documents stored in the knowledge base
+    pub fn stored(&self) -> usize {
+        self.count(IngestionStage::Stored)
+    }
+
+    /// Documents whose ingestion failed
+    pub fn failed(&self) -> usize {
+        self.count(IngestionStage::Failed)
+    }
+
+    /// Whether the batch is sealed and every document is stored or failed
+    pub fn is_finished(&self) -> bool {
+        self.sealed && self.documents.iter().all(|d| d.stage.is_terminal())
+    }
+
+    fn count(&self, stage: IngestionStage) -> usize {
+        self.documents.iter().filter(|d| d.stage == stage).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_and_finish() {
+        let mut progress = IngestionProgress::default();
+        progress.apply(&IngestionProgressEvent::new("op", "a.md", IngestionStage::Queued));
+        progress.apply(&IngestionProgressEvent::new("op", "b.md", IngestionStage::Queued));
+        progress.apply(
+            &IngestionProgressEvent::new("op", "a.md", IngestionStage::Chunked).with_chunks(3),
+        );
+        progress.apply(&IngestionProgressEvent::new("op", "a.md", IngestionStage::Stored));
+
+        assert_eq!(progress.documents.len(), 2);
+        assert_eq!(progress.documents[0].stage, IngestionStage::Stored);
+        assert_eq!(progress.documents[0].chunks, Some(3));
+        assert_eq!(progress.stored(), 1);
+
+        progress.apply(
+            &IngestionProgressEvent::new("op", "b.md", IngestionStage::Failed).with_error("empty"),
+        );
+        assert_eq!(progress.failed(), 1);
+
+        // Unsealed batches may still receive documents
+        assert!(!progress.is_finished());
+        progress.sealed = true;
+        assert!(progress.is_finished());
+    }
+}
@@ -14,6 +14,9 @@ pub fn detect_parser_from_filename(filename: &str) -> Option<ParserType> {
         "html" | "htm" => Some(ParserType::Html),
         "json" => Some(ParserType::Json),
         "pdf" => Some(ParserType::Pdf),
+        "png" | "jpg" | "jpeg" | "tif" | "tiff" | "bmp" | "gif" | "webp" => {
+            Some(ParserType::Image)
+        }
         _ => None,
     }
 }
@@ -42,6 +45,10 @@ pub fn detect_parser_from_mime(mime: &str) -> Option<ParserType> {
         return Some(ParserType::Pdf);
     }
 
+    if mime_lower.starts_with("image/") {
+        return Some(ParserType::Image);
+    }
+
     None
 }
 
@@ -124,6 +131,10 @@ mod tests {
             detect_parser_from_filename("doc.pdf"),
             Some(ParserType::Pdf)
         );
+        assert_eq!(
+            detect_parser_from_filename("scan.TIFF"),
+            Some(ParserType::Image)
+        );
         assert_eq!(detect_parser_from_filename("unknown.xyz"), None);
         assert_eq!(detect_parser_from_filename("noextension"), None);
     }
@@ -154,7 +165,11 @@ mod tests {
             detect_parser_from_mime("application/pdf"),
             Some(ParserType::Pdf)
         );
-        assert_eq!(detect_parser_from_mime("image/png"), None);
+        assert_eq!(
+            detect_parser_from_mime("image/png"),
+            Some(ParserType::Image)
+        );
+        assert_eq!(detect_parser_from_mime("audio/mpeg"), None);
     }
 
     #[test]
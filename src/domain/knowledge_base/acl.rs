@@ -0,0 +1,141 @@
+//! Document-level access control of knowledge base searches
+//!
+//! Documents may list the teams and roles allowed to read them in the
+//! `allowed_teams` and `allowed_roles` chunk metadata. A search made on
+//! behalf of a caller only sees the documents that allow its team (or list
+//! no teams) and one of its roles (or list no roles). Searches without a
+//! caller, such as the admin API's, see every document.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::filter::{FilterCondition, FilterValue, MetadataFilter};
+use crate::domain::api_key::ApiKey;
+
+/// Chunk metadata key holding the teams allowed to read a document
+pub const ALLOWED_TEAMS_METADATA_KEY: &str = "allowed_teams";
+
+/// Chunk metadata key holding the roles allowed to read a document
+pub const ALLOWED_ROLES_METADATA_KEY: &str = "allowed_roles";
+
+/// Team and roles on whose behalf a knowledge base is searched
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnowledgeBaseCaller {
+    pub team_id: String,
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+impl KnowledgeBaseCaller {
+    /// Caller of a team, without roles
+    pub fn new(team_id: impl Into<String>) -> Self {
+        Self {
+            team_id: team_id.into(),
+            roles: Vec::new(),
+        }
+    }
+
+    /// Caller authenticated with an API key: the key's team, API keys carry
+    /// no roles
+    pub fn from_api_key(api_key: &ApiKey) -> Self {
+        Self::new(api_key.team_id().as_str())
+    }
+
+    pub fn with_roles(mut self, roles: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.roles = roles.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Metadata filter matching the documents the caller may read
+    pub fn access_filter(&self) -> MetadataFilter {
+        let teams = MetadataFilter::or(vec![
+            MetadataFilter::condition(FilterCondition::not_exists(ALLOWED_TEAMS_METADATA_KEY)),
+            MetadataFilter::condition(FilterCondition::contains_any(
+                ALLOWED_TEAMS_METADATA_KEY,
+                vec![FilterValue::from(self.team_id.as_str())],
+            )),
+        ]);
+
+        let mut roles = vec![MetadataFilter::condition(FilterCondition::not_exists(
+            ALLOWED_ROLES_METADATA_KEY,
+        ))];
+        if !self.roles.is_empty() {
+            roles.push(MetadataFilter::condition(FilterCondition::contains_any(
+                ALLOWED_ROLES_METADATA_KEY,
+                self.roles
+                    .iter()
+                    .map(|r| FilterValue::from(r.as_str()))
+                    .collect(),
+            )));
+        }
+
+        MetadataFilter::and(vec![teams, MetadataFilter::or(roles)])
+    }
+
+    /// Whether the caller may read a document with the given metadata
+    pub fn can_read(&self, metadata: &HashMap<String, serde_json::Value>) -> bool {
+        let allows = |key: &str, values: &[&str]| match metadata.get(key) {
+            None => true,
+            Some(serde_json::Value::Array(allowed)) => allowed
+                .iter()
+                .filter_map(|v| v.as_str())
+                .any(|v| values.contains(&v)),
+            Some(_) => false,
+        };
+
+        let roles: Vec<&str> = self.roles.iter().map(String::as_str).collect();
+        allows(ALLOWED_TEAMS_METADATA_KEY, &[self.team_id.as_str()])
+            && allows(ALLOWED_ROLES_METADATA_KEY, &roles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn metadata(entries: &[(&str, serde_json::Value)]) -> HashMap<String, serde_json::Value> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_can_read() {
+        let caller = KnowledgeBaseCaller::new("support").with_roles(["agent"]);
+
+        assert!(caller.can_read(&HashMap::new()));
+        assert!(caller.can_read(&metadata(&[(
+            ALLOWED_TEAMS_METADATA_KEY,
+            json!(["support", "sales"])
+        )])));
+        assert!(!caller.can_read(&metadata(&[(ALLOWED_TEAMS_METADATA_KEY, json!(["sales"]))])));
+        assert!(caller.can_read(&metadata(&[(ALLOWED_ROLES_METADATA_KEY, json!(["agent"]))])));
+        assert!(!caller.can_read(&metadata(&[
+            (ALLOWED_TEAMS_METADATA_KEY, json!(["support"])),
+            (ALLOWED_ROLES_METADATA_KEY, json!(["manager"])),
+        ])));
+
+        // Callers without roles only read documents that list no roles
+        let caller = KnowledgeBaseCaller::new("support");
+        assert!(!caller.can_read(&metadata(&[(ALLOWED_ROLES_METADATA_KEY, json!(["agent"]))])));
+    }
+
+    #[test]
+    fn test_access_filter() {
+        let filter = KnowledgeBaseCaller::new("support").access_filter();
+
+        let MetadataFilter::Group { filters, .. } = filter else {
+            panic!("expected a group");
+        };
+        assert_eq!(filters.len(), 2);
+        assert_eq!(
+            filters[1],
+            MetadataFilter::or(vec![MetadataFilter::condition(
+                FilterCondition::not_exists(ALLOWED_ROLES_METADATA_KEY)
+            )])
+        );
+    }
+}
@@ -0,0 +1,327 @@
+//! Knowledge base access statistics and maintenance analytics
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::document::DocumentSummary;
+use super::entity::{KnowledgeBaseId, SearchResult};
+use crate::domain::storage::StorageEntity;
+
+/// Search result metadata key holding the ID of the document of a chunk
+pub const DOCUMENT_ID_METADATA_KEY: &str = "document_id";
+
+/// Zero-hit queries kept per knowledge base, the most recent ones
+pub const MAX_ZERO_HIT_QUERIES: usize = 20;
+
+/// Document a search result belongs to: its `document_id` metadata, else
+/// its source, else the chunk ID
+pub fn result_document_key(result: &SearchResult) -> String {
+    result
+        .metadata
+        .get(DOCUMENT_ID_METADATA_KEY)
+        .and_then(|v| v.as_str())
+        .or(result.source.as_deref())
+        .unwrap_or(&result.id)
+        .to_string()
+}
+
+/// Retrievals of one document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentAccess {
+    /// Searches that returned at least one chunk of the document
+    pub retrievals: u64,
+    pub last_retrieved_at: DateTime<Utc>,
+    /// Source of the document, for documents without a document ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// A search that returned no results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ZeroHitQuery {
+    pub query: String,
+    pub searched_at: DateTime<Utc>,
+}
+
+/// Search and retrieval counters of a knowledge base
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KnowledgeBaseAccessStats {
+    pub id: KnowledgeBaseId,
+    pub searches: u64,
+    pub zero_hit_searches: u64,
+    /// Retrievals by document key (see [`result_document_key`])
+    #[serde(default)]
+    pub documents: HashMap<String, DocumentAccess>,
+    /// Most recent zero-hit queries, oldest first
+    #[serde(default)]
+    pub recent_zero_hit_queries: Vec<ZeroHitQuery>,
+}
+
+impl StorageEntity for KnowledgeBaseAccessStats {
+    type Key = KnowledgeBaseId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+impl KnowledgeBaseAccessStats {
+    /// Empty statistics of a knowledge base
+    pub fn new(id: KnowledgeBaseId) -> Self {
+        Self {
+            id,
+            searches: 0,
+            zero_hit_searches: 0,
+            documents: HashMap::new(),
+            recent_zero_hit_queries: Vec::new(),
+        }
+    }
+
+    /// Count a search and the documents it retrieved
+    pub fn record_search(&mut self, query: &str, results: &[SearchResult], now: DateTime<Utc>) {
+        self.searches += 1;
+
+        if results.is_empty() {
+            self.zero_hit_searches += 1;
+            self.recent_zero_hit_queries.push(ZeroHitQuery {
+                query: query.to_string(),
+                searched_at: now,
+            });
+            let excess = self
+                .recent_zero_hit_queries
+                .len()
+                .saturating_sub(MAX_ZERO_HIT_QUERIES);
+            self.recent_zero_hit_queries.drain(..excess);
+            return;
+        }
+
+        // Several chunks of a document count as one retrieval
+        let mut counted: Vec<String> = Vec::new();
+        for result in results {
+            let key = result_document_key(result);
+            if counted.contains(&key) {
+                continue;
+            }
+
+            let access = self.documents.entry(key.clone()).or_insert(DocumentAccess {
+                retrievals: 0,
+                last_retrieved_at: now,
+                source: result.source.clone(),
+            });
+            access.retrievals += 1;
+            access.last_retrieved_at = now;
+            counted.push(key);
+        }
+    }
+
+    /// Share of searches that returned no results
+    pub fn zero_hit_rate(&self) -> f64 {
+        if self.searches == 0 {
+            0.0
+        } else {
+            self.zero_hit_searches as f64 / self.searches as f64
+        }
+    }
+}
+
+/// Retrievals of a document in the analytics report
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct DocumentRetrievalStats {
+    /// Document ID, or source / chunk ID of documents ingested without one
+    pub document_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    pub retrievals: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_retrieved_at: Option<DateTime<Utc>>,
+    /// Last update, for documents known to the knowledge base
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Maintenance report of a knowledge base
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct KnowledgeBaseAnalytics {
+    pub knowledge_base_id: String,
+    pub searches: u64,
+    pub zero_hit_searches: u64,
+    /// Share of searches that returned no results (0.0 - 1.0)
+    pub zero_hit_rate: f64,
+    /// Most recent zero-hit queries, newest first
+    pub recent_zero_hit_queries: Vec<ZeroHitQuery>,
+    /// Most retrieved documents, most retrievals first
+    pub most_retrieved: Vec<DocumentRetrievalStats>,
+    /// Least retrieved documents, never retrieved ones included
+    pub least_retrieved: Vec<DocumentRetrievalStats>,
+    /// Age in days past which a document is stale
+    pub stale_after_days: u32,
+    /// Documents not updated for `stale_after_days`, oldest first
+    pub stale_documents: Vec<DocumentRetrievalStats>,
+}
+
+impl KnowledgeBaseAnalytics {
+    /// Build the report from the access statistics and the documents of the
+    /// knowledge base, listing at most `limit` documents per ranking
+    pub fn build(
+        stats: &KnowledgeBaseAccessStats,
+        documents: &[DocumentSummary],
+        limit: usize,
+        stale_after_days: u32,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let mut entries: Vec<DocumentRetrievalStats> = documents
+            .iter()
+            .map(|document| {
+                let id = document.id.to_string();
+                let access = stats.documents.get(&id);
+                DocumentRetrievalStats {
+                    title: document.title.clone(),
+                    source: document.source_filename.clone(),
+                    retrievals: access.map_or(0, |a| a.retrievals),
+                    last_retrieved_at: access.map(|a| a.last_retrieved_at),
+                    updated_at: Some(document.updated_at),
+                    document_id: id,
+                }
+            })
+            .collect();
+
+        // Documents retrieved but not listed, e.g. ingested without the
+        // document schema or deleted since
+        for (key, access) in &stats.documents {
+            if !entries.iter().any(|e| &e.document_id == key) {
+                entries.push(DocumentRetrievalStats {
+                    document_id: key.clone(),
+                    title: None,
+                    source: access.source.clone(),
+                    retrievals: access.retrievals,
+                    last_retrieved_at: Some(access.last_retrieved_at),
+                    updated_at: None,
+                });
+            }
+        }
+
+        let mut most_retrieved: Vec<_> =
+            entries.iter().filter(|e| e.retrievals > 0).cloned().collect();
+        most_retrieved.sort_by(|a, b| {
+            b.retrievals
+                .cmp(&a.retrievals)
+                .then_with(|| a.document_id.cmp(&b.document_id))
+        });
+        most_retrieved.truncate(limit);
+
+        let mut least_retrieved = entries.clone();
+        least_retrieved.sort_by(|a, b| {
+            a.retrievals
+                .cmp(&b.retrievals)
+                .then_with(|| a.last_retrieved_at.cmp(&b.last_retrieved_at))
+                .then_with(|| a.document_id.cmp(&b.document_id))
+        });
+        least_retrieved.truncate(limit);
+
+        let stale_before = now - Duration::days(i64::from(stale_after_days));
+        let mut stale_documents: Vec<_> = entries
+            .into_iter()
+            .filter(|e| e.updated_at.is_some_and(|updated| updated < stale_before))
+            .collect();
+        stale_documents.sort_by_key(|e| e.updated_at);
+        stale_documents.truncate(limit);
+
+        Self {
+            knowledge_base_id: stats.id.as_str().to_string(),
+            searches: stats.searches,
+            zero_hit_searches: stats.zero_hit_searches,
+            zero_hit_rate: stats.zero_hit_rate(),
+            recent_zero_hit_queries: stats
+                .recent_zero_hit_queries
+                .iter()
+                .rev()
+                .cloned()
+                .collect(),
+            most_retrieved,
+            least_retrieved,
+            stale_after_days,
+            stale_documents,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn kb_id() -> KnowledgeBaseId {
+        KnowledgeBaseId::new("docs").unwrap()
+    }
+
+    fn chunk(id: &str, document_id: &str) -> SearchResult {
+        SearchResult::new(id, "content", 0.9)
+            .with_metadata(DOCUMENT_ID_METADATA_KEY, serde_json::json!(document_id))
+    }
+
+    #[test]
+    fn test_record_search() {
+        let now = Utc::now();
+        let mut stats = KnowledgeBaseAccessStats::new(kb_id());
+
+        let results = [chunk("c1", "d1"), chunk("c2", "d1"), chunk("c3", "d2")];
+        stats.record_search("refunds", &results, now);
+        stats.record_search("refunds", &[chunk("c1", "d1")], now);
+        stats.record_search("unknown", &[], now);
+        let legacy = SearchResult::new("c9", "x", 0.8).with_source("faq.md");
+        stats.record_search("legacy", &[legacy], now);
+
+        assert_eq!(stats.searches, 4);
+        assert_eq!(stats.zero_hit_searches, 1);
+        assert_eq!(stats.zero_hit_rate(), 0.25);
+        assert_eq!(stats.documents["d1"].retrievals, 2);
+        assert_eq!(stats.documents["d2"].retrievals, 1);
+        assert_eq!(stats.documents["faq.md"].retrievals, 1);
+        assert_eq!(stats.recent_zero_hit_queries[0].query, "unknown");
+
+        for _ in 0..MAX_ZERO_HIT_QUERIES {
+            stats.record_search("more", &[], now);
+        }
+        assert_eq!(stats.recent_zero_hit_queries.len(), MAX_ZERO_HIT_QUERIES);
+        assert!(stats.recent_zero_hit_queries.iter().all(|q| q.query == "more"));
+    }
+
+    #[test]
+    fn test_build_analytics() {
+        let now = Utc::now();
+        let summary = |title: &str, age_days: i64| DocumentSummary {
+            id: Uuid::new_v4(),
+            title: Some(title.to_string()),
+            source_filename: None,
+            original_size_bytes: None,
+            chunk_count: 1,
+            disabled: false,
+            created_at: now - Duration::days(age_days),
+            updated_at: now - Duration::days(age_days),
+        };
+        let popular = summary("popular", 10);
+        let unused = summary("unused", 400);
+        let documents = vec![popular.clone(), unused.clone()];
+
+        let mut stats = KnowledgeBaseAccessStats::new(kb_id());
+        let popular_id = popular.id.to_string();
+        stats.record_search("q", &[chunk("c1", &popular_id)], now);
+        stats.record_search("q", &[chunk("c1", &popular_id)], now);
+        stats.record_search("q", &[], now);
+
+        let analytics = KnowledgeBaseAnalytics::build(&stats, &documents, 10, 90, now);
+
+        assert_eq!(analytics.searches, 3);
+        assert_eq!(analytics.most_retrieved.len(), 1);
+        assert_eq!(analytics.most_retrieved[0].retrievals, 2);
+        assert_eq!(analytics.least_retrieved[0].title.as_deref(), Some("unused"));
+        assert_eq!(analytics.least_retrieved[0].retrievals, 0);
+        assert_eq!(analytics.stale_documents.len(), 1);
+        assert_eq!(analytics.stale_documents[0].document_id, unused.id.to_string());
+    }
+}
@@ -290,9 +290,11 @@ pub struct DocumentSummary {
     pub id: Uuid,
     pub title: Option<String>,
     pub source_filename: Option<String>,
+    pub original_size_bytes: Option<i64>,
     pub chunk_count: i32,
     pub disabled: bool,
     pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
 impl From<&KnowledgeBaseDocument> for DocumentSummary {
@@ -301,9 +303,11 @@ impl From<&KnowledgeBaseDocument> for DocumentSummary {
             id: doc.id,
             title: doc.title.clone(),
             source_filename: doc.source_filename.clone(),
+            original_size_bytes: doc.original_size_bytes,
             chunk_count: doc.chunk_count,
             disabled: doc.disabled,
             created_at: doc.created_at,
+            updated_at: doc.updated_at,
         }
     }
 }
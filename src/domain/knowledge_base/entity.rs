@@ -6,8 +6,10 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use super::validation::{validate_knowledge_base_id, KnowledgeBaseValidationError};
-use super::MetadataFilter;
+use super::{MetadataFilter, VectorIndexConfig};
+use crate::domain::embedding::DistanceMetric;
 use crate::domain::storage::{StorageEntity, StorageKey};
+use crate::domain::team::TeamId;
 
 /// Knowledge base identifier - alphanumeric + hyphens, max 50 characters
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -137,6 +139,12 @@ pub struct KnowledgeBaseConfig {
     /// Maximum content length to return per result
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_content_length: Option<usize>,
+    /// Distance metric of searches, and of the vector index
+    #[serde(default)]
+    pub distance_metric: DistanceMetric,
+    /// Vector index type and parameters; the shared default index when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_index: Option<VectorIndexConfig>,
 }
 
 fn default_top_k() -> u32 {
@@ -159,6 +167,8 @@ impl Default for KnowledgeBaseConfig {
             include_embeddings: false,
             include_metadata: true,
             max_content_length: None,
+            distance_metric: DistanceMetric::default(),
+            vector_index: None,
         }
     }
 }
@@ -192,6 +202,18 @@ impl KnowledgeBaseConfig {
         self.max_content_length = Some(length);
         self
     }
+
+    /// Set the distance metric
+    pub fn with_distance_metric(mut self, metric: DistanceMetric) -> Self {
+        self.distance_metric = metric;
+        self
+    }
+
+    /// Set the vector index
+    pub fn with_vector_index(mut self, index: VectorIndexConfig) -> Self {
+        self.vector_index = Some(index);
+        self
+    }
 }
 
 /// Knowledge base entity
@@ -215,6 +237,9 @@ pub struct KnowledgeBase {
     connection_config: Option<HashMap<String, String>>,
     /// Whether the knowledge base is enabled
     enabled: bool,
+    /// Team owning the knowledge base, for quota accounting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    team_id: Option<TeamId>,
     /// Creation timestamp
     created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -240,6 +265,7 @@ impl KnowledgeBase {
             config: KnowledgeBaseConfig::default(),
             connection_config: None,
             enabled: true,
+            team_id: None,
             created_at: now,
             updated_at: now,
         }
@@ -269,6 +295,12 @@ impl KnowledgeBase {
         self
     }
 
+    /// Set the owning team
+    pub fn with_team_id(mut self, team_id: TeamId) -> Self {
+        self.team_id = Some(team_id);
+        self
+    }
+
     // Getters
 
     pub fn id(&self) -> &KnowledgeBaseId {
@@ -303,6 +335,10 @@ impl KnowledgeBase {
         self.enabled
     }
 
+    pub fn team_id(&self) -> Option<&TeamId> {
+        self.team_id.as_ref()
+    }
+
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
     }
@@ -32,6 +32,8 @@ pub enum FilterOperator {
     Exists,
     /// Not exists (field is not present)
     NotExists,
+    /// Array field holds at least one of a list of values
+    ContainsAny,
 }
 
 impl std::fmt::Display for FilterOperator {
@@ -50,6 +52,7 @@ impl std::fmt::Display for FilterOperator {
             Self::NotIn => write!(f, "not_in"),
             Self::Exists => write!(f, "exists"),
             Self::NotExists => write!(f, "not_exists"),
+            Self::ContainsAny => write!(f, "contains_any"),
         }
     }
 }
@@ -220,6 +223,11 @@ impl FilterCondition {
     pub fn not_in_list(key: impl Into<String>, values: Vec<FilterValue>) -> Self {
         Self::new(key, FilterOperator::NotIn, FilterValue::List(values))
     }
+
+    /// Create a condition matching array fields holding any of the values
+    pub fn contains_any(key: impl Into<String>, values: Vec<FilterValue>) -> Self {
+        Self::new(key, FilterOperator::ContainsAny, FilterValue::List(values))
+    }
 }
 
 /// A metadata filter that can be a single condition or a group of conditions
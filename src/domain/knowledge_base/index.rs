@@ -0,0 +1,211 @@
+//! Vector index settings of knowledge bases
+//!
+//! The default index of the chunks table is built for small knowledge bases;
+//! large ones pick their index type and build parameters here. Changing the
+//! settings only takes effect on the next schema initialization or reindex.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::validation::KnowledgeBaseValidationError;
+
+/// Type and parameters of the vector index of a knowledge base
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum VectorIndexConfig {
+    /// Hierarchical navigable small world graph: better recall and latency,
+    /// slower to build and larger
+    Hnsw {
+        /// Connections per node
+        #[serde(default = "default_hnsw_m")]
+        m: u32,
+        /// Candidate list size while building
+        #[serde(default = "default_hnsw_ef_construction")]
+        ef_construction: u32,
+        /// Candidate list size while searching; the server default when unset
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ef_search: Option<u32>,
+    },
+    /// Inverted lists: fast to build, recall depends on the lists probed
+    #[serde(rename = "ivfflat")]
+    IvfFlat {
+        /// Number of lists, about rows / 1000 up to 1M rows
+        #[serde(default = "default_ivfflat_lists")]
+        lists: u32,
+        /// Lists probed while searching; the server default when unset
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        probes: Option<u32>,
+    },
+}
+
+fn default_hnsw_m() -> u32 {
+    16
+}
+
+fn default_hnsw_ef_construction() -> u32 {
+    64
+}
+
+fn default_ivfflat_lists() -> u32 {
+    100
+}
+
+impl VectorIndexConfig {
+    /// HNSW index with the default build parameters
+    pub fn hnsw() -> Self {
+        Self::Hnsw {
+            m: default_hnsw_m(),
+            ef_construction: default_hnsw_ef_construction(),
+            ef_search: None,
+        }
+    }
+
+    /// IVFFlat index with the given number of lists
+    pub fn ivfflat(lists: u32) -> Self {
+        Self::IvfFlat {
+            lists,
+            probes: None,
+        }
+    }
+
+    /// Index access method name
+    pub fn method(&self) -> &'static str {
+        match self {
+            Self::Hnsw { .. } => "hnsw",
+            Self::IvfFlat { .. } => "ivfflat",
+        }
+    }
+
+    /// Build parameters, as they appear in the index storage options
+    pub fn build_parameters(&self) -> Vec<(&'static str, u32)> {
+        match self {
+            Self::Hnsw {
+                m, ef_construction, ..
+            } => vec![("m", *m), ("ef_construction", *ef_construction)],
+            Self::IvfFlat { lists, .. } => vec![("lists", *lists)],
+        }
+    }
+
+    /// Search-time setting and its value, when one is configured
+    pub fn search_setting(&self) -> Option<(&'static str, u32)> {
+        match self {
+            Self::Hnsw { ef_search, .. } => ef_search.map(|v| ("hnsw.ef_search", v)),
+            Self::IvfFlat { probes, .. } => probes.map(|v| ("ivfflat.probes", v)),
+        }
+    }
+
+    /// Check the parameters against the ranges pgvector accepts
+    pub fn validate(&self) -> Result<(), KnowledgeBaseValidationError> {
+        let invalid = |parameter: &str, reason: String| {
+            Err(KnowledgeBaseValidationError::InvalidVectorIndex {
+                parameter: parameter.to_string(),
+                reason,
+            })
+        };
+
+        match *self {
+            Self::Hnsw {
+                m,
+                ef_construction,
+                ef_search,
+            } => {
+                if !(2..=100).contains(&m) {
+                    return invalid("m", format!("{} is not between 2 and 100", m));
+                }
+                if !(2 * m..=1000).contains(&ef_construction) {
+                    return invalid(
+                        "ef_construction",
+                        format!("{} is not between 2 * m ({}) and 1000", ef_construction, 2 * m),
+                    );
+                }
+                if let Some(ef_search) = ef_search.filter(|v| !(1..=1000).contains(v)) {
+                    return invalid("ef_search", format!("{} is not between 1 and 1000", ef_search));
+                }
+            }
+            Self::IvfFlat { lists, probes } => {
+                if !(1..=32768).contains(&lists) {
+                    return invalid("lists", format!("{} is not between 1 and 32768", lists));
+                }
+                if let Some(probes) = probes.filter(|v| !(1..=lists).contains(v)) {
+                    return invalid(
+                        "probes",
+                        format!("{} is not between 1 and lists ({})", probes, lists),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// State of the vector index serving a knowledge base
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct VectorIndexStatus {
+    /// Index name
+    pub name: String,
+    /// Access method, `hnsw` or `ivfflat`
+    pub method: String,
+    /// `CREATE INDEX` statement of the index
+    pub definition: String,
+    pub size_bytes: i64,
+    /// Whether the index is usable by searches; false while a concurrent
+    /// build is in progress or after a failed one
+    pub valid: bool,
+    /// Chunks of the knowledge base
+    pub chunks: usize,
+    /// Whether the index matches the configured type and parameters;
+    /// a reindex applies the configuration otherwise
+    pub up_to_date: bool,
+    /// Configured index, none for the shared default index
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub configured: Option<VectorIndexConfig>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_serde_defaults() {
+        let config: VectorIndexConfig = serde_json::from_value(json!({"type": "hnsw"})).unwrap();
+        assert_eq!(config, VectorIndexConfig::hnsw());
+        assert_eq!(config.build_parameters(), vec![("m", 16), ("ef_construction", 64)]);
+        assert_eq!(config.search_setting(), None);
+
+        let config: VectorIndexConfig =
+            serde_json::from_value(json!({"type": "ivfflat", "lists": 2000, "probes": 40}))
+                .unwrap();
+        assert_eq!(config.method(), "ivfflat");
+        assert_eq!(config.search_setting(), Some(("ivfflat.probes", 40)));
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            json!({"type": "ivfflat", "lists": 2000, "probes": 40})
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(VectorIndexConfig::hnsw().validate().is_ok());
+        assert!(VectorIndexConfig::ivfflat(1000).validate().is_ok());
+
+        let too_small_ef = VectorIndexConfig::Hnsw {
+            m: 32,
+            ef_construction: 40,
+            ef_search: None,
+        };
+        assert!(matches!(
+            too_small_ef.validate(),
+            Err(KnowledgeBaseValidationError::InvalidVectorIndex { parameter, .. })
+                if parameter == "ef_construction"
+        ));
+
+        assert!(VectorIndexConfig::ivfflat(0).validate().is_err());
+        let too_many_probes = VectorIndexConfig::IvfFlat {
+            lists: 10,
+            probes: Some(11),
+        };
+        assert!(too_many_probes.validate().is_err());
+    }
+}
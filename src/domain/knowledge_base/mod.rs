@@ -1,11 +1,20 @@
 //! Knowledge Base domain - Vector search and retrieval
 
+mod acl;
+mod analytics;
 mod document;
 mod entity;
 mod filter;
+mod index;
+mod namespace;
 mod provider;
 mod validation;
 
+pub use acl::{KnowledgeBaseCaller, ALLOWED_ROLES_METADATA_KEY, ALLOWED_TEAMS_METADATA_KEY};
+pub use analytics::{
+    result_document_key, DocumentAccess, DocumentRetrievalStats, KnowledgeBaseAccessStats,
+    KnowledgeBaseAnalytics, ZeroHitQuery, DOCUMENT_ID_METADATA_KEY, MAX_ZERO_HIT_QUERIES,
+};
 pub use document::{
     CreateChunkRequest, CreateDocumentRequest, DocumentChunk, DocumentSummary,
     KnowledgeBaseDocument,
@@ -17,11 +26,16 @@ pub use entity::{
 pub use filter::{
     FilterBuilder, FilterCondition, FilterConnector, FilterOperator, FilterValue, MetadataFilter,
 };
+pub use index::{VectorIndexConfig, VectorIndexStatus};
+pub use namespace::{scope_to_namespace, MAX_NAMESPACE_LENGTH, NAMESPACE_METADATA_KEY};
 pub use provider::{
     AddDocumentsResult, DeleteDocumentsResult, Document, KnowledgeBaseProvider, SearchParams,
     SourceInfo,
 };
-pub use validation::{validate_knowledge_base_id, KnowledgeBaseValidationError};
+pub use validation::{
+    check_knowledge_base_config, validate_dimensions, validate_distance_metric,
+    validate_knowledge_base_id, validate_namespace, KnowledgeBaseValidationError,
+};
 
 #[cfg(test)]
 pub use provider::mock::MockKnowledgeBaseProvider;
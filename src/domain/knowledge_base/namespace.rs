@@ -0,0 +1,49 @@
+//! Logical namespaces within a knowledge base
+//!
+//! A namespace (e.g. a product version) groups the chunks of a knowledge base
+//! so ingestion and searches can be scoped to it. It is stored as the
+//! `namespace` chunk metadata, so every provider filters on it like any other
+//! metadata; chunks ingested without a namespace are outside all namespaces.
+
+use super::filter::{FilterCondition, MetadataFilter};
+
+/// Chunk metadata key holding the namespace of a chunk
+pub const NAMESPACE_METADATA_KEY: &str = "namespace";
+
+/// Longest namespace name
+pub const MAX_NAMESPACE_LENGTH: usize = 64;
+
+/// Restrict a metadata filter to the chunks of a namespace
+pub fn scope_to_namespace(filter: Option<MetadataFilter>, namespace: &str) -> MetadataFilter {
+    let condition =
+        MetadataFilter::condition(FilterCondition::eq(NAMESPACE_METADATA_KEY, namespace));
+
+    match filter {
+        Some(filter) if !filter.is_empty() => MetadataFilter::and(vec![condition, filter]),
+        _ => condition,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_to_namespace() {
+        let scoped = scope_to_namespace(None, "v2");
+        assert_eq!(
+            scoped,
+            MetadataFilter::condition(FilterCondition::eq(NAMESPACE_METADATA_KEY, "v2"))
+        );
+
+        let filter = MetadataFilter::condition(FilterCondition::eq("category", "faq"));
+        let scoped = scope_to_namespace(Some(filter.clone()), "v2");
+        assert_eq!(
+            scoped,
+            MetadataFilter::and(vec![
+                MetadataFilter::condition(FilterCondition::eq(NAMESPACE_METADATA_KEY, "v2")),
+                filter,
+            ])
+        );
+    }
+}
@@ -8,7 +8,10 @@ use super::document::{
     CreateDocumentRequest, DocumentChunk, DocumentSummary, KnowledgeBaseDocument,
 };
 use super::entity::{KnowledgeBaseId, SearchResult};
+use super::acl::KnowledgeBaseCaller;
 use super::filter::MetadataFilter;
+use super::index::VectorIndexStatus;
+use super::namespace::scope_to_namespace;
 use crate::domain::error::DomainError;
 use uuid::Uuid;
 
@@ -69,6 +72,10 @@ pub struct SearchParams {
     pub similarity_threshold: f32,
     /// Optional metadata filter
     pub filter: Option<MetadataFilter>,
+    /// Optional namespace the search is restricted to
+    pub namespace: Option<String>,
+    /// Caller whose team and roles restrict the documents found
+    pub caller: Option<KnowledgeBaseCaller>,
     /// Whether to include embeddings in results
     pub include_embeddings: bool,
     /// Whether to include metadata in results
@@ -83,6 +90,8 @@ impl SearchParams {
             top_k: 10,
             similarity_threshold: 0.7,
             filter: None,
+            namespace: None,
+            caller: None,
             include_embeddings: false,
             include_metadata: true,
         }
@@ -106,6 +115,40 @@ impl SearchParams {
         self
     }
 
+    /// Restrict the search to a namespace
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Restrict the search to the documents a caller may read
+    pub fn with_caller(mut self, caller: KnowledgeBaseCaller) -> Self {
+        self.caller = Some(caller);
+        self
+    }
+
+    /// Metadata filter to apply, including the namespace restriction
+    pub fn namespaced_filter(&self) -> Option<MetadataFilter> {
+        match &self.namespace {
+            Some(namespace) => Some(scope_to_namespace(self.filter.clone(), namespace)),
+            None => self.filter.clone(),
+        }
+    }
+
+    /// Metadata filter to apply, including the namespace and caller access
+    /// restrictions
+    pub fn scoped_filter(&self) -> Option<MetadataFilter> {
+        let filter = self.namespaced_filter();
+
+        match &self.caller {
+            Some(caller) => Some(match filter {
+                Some(filter) => MetadataFilter::and(vec![caller.access_filter(), filter]),
+                None => caller.access_filter(),
+            }),
+            None => filter,
+        }
+    }
+
     /// Set whether to include embeddings
     pub fn with_include_embeddings(mut self, include: bool) -> Self {
         self.include_embeddings = include;
@@ -190,6 +233,9 @@ pub trait KnowledgeBaseProvider: Send + Sync + Debug {
     /// Delete documents matching a metadata filter
     async fn delete_by_filter(&self, filter: MetadataFilter) -> Result<DeleteDocumentsResult, DomainError>;
 
+    /// List documents matching a metadata filter
+    async fn list_by_filter(&self, filter: MetadataFilter) -> Result<Vec<SearchResult>, DomainError>;
+
     /// Get a document by ID
     async fn get_document(&self, id: &str) -> Result<Option<SearchResult>, DomainError>;
 
@@ -211,6 +257,20 @@ pub trait KnowledgeBaseProvider: Send + Sync + Debug {
     /// Ensure the storage schema exists (create tables/indexes)
     async fn ensure_schema(&self) -> Result<(), DomainError>;
 
+    /// Status of the vector index serving the knowledge base; none for
+    /// providers whose index is managed elsewhere
+    async fn index_status(&self) -> Result<Option<VectorIndexStatus>, DomainError> {
+        Ok(None)
+    }
+
+    /// Rebuild the vector index, applying its configured type and parameters
+    async fn reindex(&self) -> Result<VectorIndexStatus, DomainError> {
+        Err(DomainError::validation(format!(
+            "Knowledge base provider '{}' does not manage a vector index",
+            self.provider_type()
+        )))
+    }
+
     // ========================================================================
     // New document-based methods (for the new schema)
     // ========================================================================
@@ -400,6 +460,15 @@ pub mod mock {
             Ok(DeleteDocumentsResult::new(0, 0))
         }
 
+        async fn list_by_filter(
+            &self,
+            _filter: MetadataFilter,
+        ) -> Result<Vec<SearchResult>, DomainError> {
+            self.check_should_fail().await?;
+            // Simplified: just return empty result for mock
+            Ok(Vec::new())
+        }
+
         async fn get_document(&self, id: &str) -> Result<Option<SearchResult>, DomainError> {
             self.check_should_fail().await?;
 
@@ -5,6 +5,11 @@ use std::fmt;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+use super::namespace::MAX_NAMESPACE_LENGTH;
+use super::KnowledgeBaseConfig;
+use crate::domain::embedding::DistanceMetric;
+use crate::domain::FieldViolations;
+
 /// Maximum length for knowledge base IDs
 pub const MAX_KB_ID_LENGTH: usize = 50;
 
@@ -27,6 +32,12 @@ pub enum KnowledgeBaseValidationError {
     InvalidTopK { value: u32, min: u32, max: u32 },
     /// Invalid similarity threshold
     InvalidSimilarityThreshold { value: f32 },
+    /// Namespace is empty, too long or contains invalid characters
+    InvalidNamespace { namespace: String },
+    /// Vector index parameter out of range
+    InvalidVectorIndex { parameter: String, reason: String },
+    /// Distance metric unsuited to the embeddings of the model
+    UnsupportedDistanceMetric { metric: DistanceMetric, model: String },
 }
 
 impl fmt::Display for KnowledgeBaseValidationError {
@@ -68,6 +79,23 @@ impl fmt::Display for KnowledgeBaseValidationError {
                     value
                 )
             }
+            Self::InvalidNamespace { namespace } => {
+                write!(
+                    f,
+                    "Invalid namespace '{}': must be 1 to {} letters, digits, '.', '_' or '-'",
+                    namespace, MAX_NAMESPACE_LENGTH
+                )
+            }
+            Self::InvalidVectorIndex { parameter, reason } => {
+                write!(f, "Invalid vector index {}: {}", parameter, reason)
+            }
+            Self::UnsupportedDistanceMetric { metric, model } => {
+                write!(
+                    f,
+                    "Distance metric '{}' requires normalized embeddings, which model '{}' is not known to return",
+                    metric, model
+                )
+            }
         }
     }
 }
@@ -94,8 +122,24 @@ pub fn validate_knowledge_base_id(id: &str) -> Result<(), KnowledgeBaseValidatio
     Ok(())
 }
 
+/// Validate a knowledge base namespace
+pub fn validate_namespace(namespace: &str) -> Result<(), KnowledgeBaseValidationError> {
+    let valid = !namespace.is_empty()
+        && namespace.len() <= MAX_NAMESPACE_LENGTH
+        && namespace
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+
+    if !valid {
+        return Err(KnowledgeBaseValidationError::InvalidNamespace {
+            namespace: namespace.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
 /// Validate embedding dimensions
-#[allow(dead_code)]
 pub fn validate_dimensions(dims: u32) -> Result<(), KnowledgeBaseValidationError> {
     const MIN: u32 = 1;
     const MAX: u32 = 8192; // Most models have <= 4096 dims
@@ -112,7 +156,6 @@ pub fn validate_dimensions(dims: u32) -> Result<(), KnowledgeBaseValidationError
 }
 
 /// Validate top_k value
-#[allow(dead_code)]
 pub fn validate_top_k(top_k: u32) -> Result<(), KnowledgeBaseValidationError> {
     const MIN: u32 = 1;
     const MAX: u32 = 1000;
@@ -129,7 +172,6 @@ pub fn validate_top_k(top_k: u32) -> Result<(), KnowledgeBaseValidationError> {
 }
 
 /// Validate similarity threshold
-#[allow(dead_code)]
 pub fn validate_similarity_threshold(threshold: f32) -> Result<(), KnowledgeBaseValidationError> {
     if !(0.0..=1.0).contains(&threshold) {
         return Err(KnowledgeBaseValidationError::InvalidSimilarityThreshold {
@@ -140,6 +182,40 @@ pub fn validate_similarity_threshold(threshold: f32) -> Result<(), KnowledgeBase
     Ok(())
 }
 
+/// Validate that a distance metric suits the embeddings of a model
+pub fn validate_distance_metric(
+    metric: DistanceMetric,
+    embedding_model: &str,
+) -> Result<(), KnowledgeBaseValidationError> {
+    if !metric.supports_model(embedding_model) {
+        return Err(KnowledgeBaseValidationError::UnsupportedDistanceMetric {
+            metric,
+            model: embedding_model.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Record every invalid setting of a KnowledgeBaseConfig, under `prefix`
+pub fn check_knowledge_base_config(
+    config: &KnowledgeBaseConfig,
+    prefix: &str,
+    violations: &mut FieldViolations,
+) {
+    violations.check(
+        format!("{}.default_top_k", prefix),
+        validate_top_k(config.default_top_k),
+    );
+    violations.check(
+        format!("{}.default_similarity_threshold", prefix),
+        validate_similarity_threshold(config.default_similarity_threshold),
+    );
+    if let Some(index) = &config.vector_index {
+        violations.check(format!("{}.vector_index", prefix), index.validate());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,6 +252,32 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_namespace_validation() {
+        assert!(validate_namespace("v2.1").is_ok());
+        assert!(validate_namespace("product_docs-2024").is_ok());
+
+        assert!(validate_namespace("").is_err());
+        assert!(validate_namespace("has space").is_err());
+        assert!(matches!(
+            validate_namespace("a/b"),
+            Err(KnowledgeBaseValidationError::InvalidNamespace { .. })
+        ));
+        assert!(validate_namespace(&"a".repeat(MAX_NAMESPACE_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_distance_metric_validation() {
+        assert!(validate_distance_metric(DistanceMetric::Cosine, "nomic-embed-text").is_ok());
+        assert!(
+            validate_distance_metric(DistanceMetric::InnerProduct, "text-embedding-3-large").is_ok()
+        );
+        assert!(matches!(
+            validate_distance_metric(DistanceMetric::InnerProduct, "nomic-embed-text"),
+            Err(KnowledgeBaseValidationError::UnsupportedDistanceMetric { .. })
+        ));
+    }
+
     #[test]
     fn test_dimensions_validation() {
         assert!(validate_dimensions(1).is_ok());
@@ -0,0 +1,208 @@
+//! LiteLLM proxy configuration file
+//!
+//! Only the parts of the `config.yaml` of a LiteLLM proxy that have a gateway
+//! counterpart are read; unknown keys are ignored.
+
+use std::collections::{BTreeSet, HashMap};
+
+use serde::Deserialize;
+
+use crate::domain::DomainError;
+
+/// Prefix of values LiteLLM reads from an environment variable
+pub const ENV_REFERENCE_PREFIX: &str = "os.environ/";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LiteLlmConfig {
+    #[serde(default)]
+    pub model_list: Vec<LiteLlmDeployment>,
+    #[serde(default)]
+    pub litellm_settings: LiteLlmSettings,
+    #[serde(default)]
+    pub router_settings: LiteLlmRouterSettings,
+    /// Variables LiteLLM sets in its environment on startup
+    #[serde(default)]
+    pub environment_variables: HashMap<String, String>,
+}
+
+/// A deployment of the model list. Deployments sharing a `model_name` are
+/// load balanced by LiteLLM.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LiteLlmDeployment {
+    pub model_name: String,
+    pub litellm_params: LiteLlmParams,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LiteLlmParams {
+    /// Provider-prefixed model, e.g. `openai/gpt-4o` or `azure/my-deployment`
+    pub model: String,
+    pub api_key: Option<String>,
+    pub api_base: Option<String>,
+    pub api_version: Option<String>,
+    pub aws_access_key_id: Option<String>,
+    pub aws_secret_access_key: Option<String>,
+    pub aws_region_name: Option<String>,
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<u32>,
+    pub top_p: Option<f32>,
+    pub presence_penalty: Option<f32>,
+    pub frequency_penalty: Option<f32>,
+    /// Request timeout in seconds
+    pub timeout: Option<f64>,
+    pub max_retries: Option<u32>,
+    /// Spend limit of the deployment in USD
+    pub max_budget: Option<f64>,
+    pub budget_duration: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LiteLlmSettings {
+    /// Spend limit of the whole proxy in USD
+    pub max_budget: Option<f64>,
+    pub budget_duration: Option<String>,
+    pub num_retries: Option<u32>,
+    /// Request timeout in seconds
+    pub request_timeout: Option<f64>,
+    /// Model names to fall back to, keyed by model name
+    #[serde(default)]
+    pub fallbacks: Vec<HashMap<String, Vec<String>>>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LiteLlmRouterSettings {
+    /// Spend limits keyed by provider, e.g. `openai`
+    #[serde(default)]
+    pub provider_budget_config: HashMap<String, LiteLlmProviderBudget>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LiteLlmProviderBudget {
+    /// Limit in USD
+    pub budget_limit: f64,
+    pub time_period: Option<String>,
+}
+
+impl LiteLlmConfig {
+    /// Parse a LiteLLM proxy `config.yaml`
+    pub fn from_yaml(yaml: &str) -> Result<Self, DomainError> {
+        serde_yaml::from_str(yaml)
+            .map_err(|e| DomainError::validation(format!("Invalid LiteLLM config: {}", e)))
+    }
+
+    /// Replace `os.environ/NAME` references of the model list with their
+    /// values, looked up first in `lookup` and then in the config's own
+    /// `environment_variables`. Fails naming every variable that is not set.
+    pub fn resolve_environment(
+        &mut self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<(), DomainError> {
+        let defined = self.environment_variables.clone();
+        let mut missing = BTreeSet::new();
+
+        for deployment in &mut self.model_list {
+            let params = &mut deployment.litellm_params;
+
+            for value in [
+                &mut params.api_key,
+                &mut params.api_base,
+                &mut params.api_version,
+                &mut params.aws_access_key_id,
+                &mut params.aws_secret_access_key,
+                &mut params.aws_region_name,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                let Some(name) = value.strip_prefix(ENV_REFERENCE_PREFIX) else {
+                    continue;
+                };
+
+                match lookup(name).or_else(|| defined.get(name).cloned()) {
+                    Some(resolved) => *value = resolved,
+                    None => {
+                        missing.insert(name.to_string());
+                    }
+                }
+            }
+        }
+
+        if !missing.is_empty() {
+            return Err(DomainError::validation(format!(
+                "Environment variables referenced by the LiteLLM config are not set: {}",
+                missing.into_iter().collect::<Vec<_>>().join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+model_list:
+  - model_name: gpt-4o
+    litellm_params:
+      model: openai/gpt-4o
+      api_key: os.environ/OPENAI_API_KEY
+      temperature: 0.2
+      timeout: 30
+    model_info:
+      id: ignored
+  - model_name: claude
+    litellm_params:
+      model: anthropic/claude-3-5-sonnet-20240620
+      api_key: os.environ/ANTHROPIC_API_KEY
+litellm_settings:
+  max_budget: 100
+  budget_duration: 30d
+  fallbacks: [{"gpt-4o": ["claude"]}]
+general_settings:
+  master_key: sk-1234
+environment_variables:
+  ANTHROPIC_API_KEY: sk-ant
+"#;
+
+    #[test]
+    fn test_from_yaml() {
+        let config = LiteLlmConfig::from_yaml(CONFIG).unwrap();
+
+        assert_eq!(config.model_list.len(), 2);
+        let params = &config.model_list[0].litellm_params;
+        assert_eq!(params.model, "openai/gpt-4o");
+        assert_eq!(params.temperature, Some(0.2));
+        assert_eq!(params.timeout, Some(30.0));
+        assert_eq!(config.litellm_settings.max_budget, Some(100.0));
+        assert_eq!(
+            config.litellm_settings.fallbacks[0]["gpt-4o"],
+            vec!["claude"]
+        );
+
+        assert!(LiteLlmConfig::from_yaml("model_list: [{model_name: x}]").is_err());
+    }
+
+    #[test]
+    fn test_resolve_environment() {
+        let mut config = LiteLlmConfig::from_yaml(CONFIG).unwrap();
+        config
+            .resolve_environment(|name| (name == "OPENAI_API_KEY").then(|| "sk-openai".to_string()))
+            .unwrap();
+
+        assert_eq!(
+            config.model_list[0].litellm_params.api_key.as_deref(),
+            Some("sk-openai")
+        );
+        assert_eq!(
+            config.model_list[1].litellm_params.api_key.as_deref(),
+            Some("sk-ant")
+        );
+
+        let mut config = LiteLlmConfig::from_yaml(CONFIG).unwrap();
+        let error = config.resolve_environment(|_| None).unwrap_err();
+        assert!(error.to_string().contains("OPENAI_API_KEY"));
+        assert!(!error.to_string().contains("ANTHROPIC_API_KEY"));
+    }
+}
@@ -0,0 +1,524 @@
+//! Conversion of a LiteLLM proxy configuration into gateway entities
+//!
+//! Each deployment of the model list becomes a model; deployments sharing a
+//! provider, API key, endpoint and region share one credential. Spend limits
+//! of the proxy, of providers and of deployments become budgets. Anything that
+//! cannot be carried over is reported as a warning instead of failing.
+
+use std::collections::HashSet;
+
+use crate::domain::CredentialType;
+use crate::domain::litellm::{LiteLlmConfig, LiteLlmParams};
+use crate::domain::model::{MAX_MODEL_ID_LENGTH, ModelConfig};
+use crate::domain::residency::Region;
+use crate::domain::usage::{Budget, BudgetPeriod};
+
+/// Budget covering every request of the imported proxy
+pub const PROXY_BUDGET_ID: &str = "litellm-proxy";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedCredential {
+    pub id: String,
+    pub name: String,
+    pub credential_type: CredentialType,
+    pub api_key: String,
+    pub endpoint: Option<String>,
+    pub region: Option<Region>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportedModel {
+    pub id: String,
+    /// The LiteLLM `model_name` clients request
+    pub name: String,
+    pub provider: CredentialType,
+    pub provider_model: String,
+    pub credential_id: String,
+    pub config: ModelConfig,
+}
+
+/// Gateway entities converted from a LiteLLM config
+#[derive(Debug, Clone, Default)]
+pub struct LiteLlmImport {
+    pub credentials: Vec<ImportedCredential>,
+    pub models: Vec<ImportedModel>,
+    pub budgets: Vec<Budget>,
+    pub warnings: Vec<String>,
+}
+
+impl LiteLlmConfig {
+    /// Convert the config into gateway credentials, models and budgets
+    pub fn to_import(&self) -> LiteLlmImport {
+        let mut import = LiteLlmImport::default();
+        let mut model_ids = HashSet::new();
+        let mut model_names = HashSet::new();
+        // LiteLLM provider prefix of each imported model
+        let mut model_providers: Vec<(String, String)> = Vec::new();
+
+        for deployment in &self.model_list {
+            let params = &deployment.litellm_params;
+
+            let Some((provider, credential_type, provider_model)) = parse_model(&params.model)
+            else {
+                import.warnings.push(format!(
+                    "Skipped deployment '{}': provider of '{}' is not supported",
+                    deployment.model_name, params.model
+                ));
+                continue;
+            };
+
+            if credential_type == CredentialType::AzureOpenAi && params.api_base.is_none() {
+                import.warnings.push(format!(
+                    "Skipped deployment '{}': Azure deployments need an api_base",
+                    deployment.model_name
+                ));
+                continue;
+            }
+
+            let credential_id = import.credential_for(
+                &deployment.model_name,
+                &provider,
+                credential_type.clone(),
+                params,
+            );
+
+            let id = unique_id(&slug(&deployment.model_name), &mut model_ids);
+            if !model_names.insert(deployment.model_name.as_str()) {
+                import.warnings.push(format!(
+                    "Deployment '{}' is load balanced in LiteLLM and was imported as separate model '{}'",
+                    deployment.model_name, id
+                ));
+            }
+
+            let mut config = ModelConfig::new();
+            config.temperature = params.temperature;
+            config.max_tokens = params.max_tokens;
+            config.top_p = params.top_p;
+            config.presence_penalty = params.presence_penalty;
+            config.frequency_penalty = params.frequency_penalty;
+            config.timeout_ms = params
+                .timeout
+                .or(self.litellm_settings.request_timeout)
+                .map(|secs| (secs * 1000.0) as u64);
+            config.max_retries = params.max_retries.or(self.litellm_settings.num_retries);
+
+            if let Some(limit) = params.max_budget {
+                import.push_budget(
+                    format!("litellm-model-{}", id),
+                    format!("LiteLLM {}", deployment.model_name),
+                    limit,
+                    params.budget_duration.as_deref(),
+                    vec![id.clone()],
+                );
+            }
+
+            model_providers.push((provider, id.clone()));
+            import.models.push(ImportedModel {
+                id,
+                name: deployment.model_name.clone(),
+                provider: credential_type,
+                provider_model,
+                credential_id,
+                config,
+            });
+        }
+
+        import.apply_fallbacks(self);
+
+        let mut providers: Vec<_> = self.router_settings.provider_budget_config.iter().collect();
+        providers.sort_by_key(|(provider, _)| provider.as_str());
+
+        for (provider, budget) in providers {
+            let models: Vec<String> = model_providers
+                .iter()
+                .filter(|(p, _)| p == provider)
+                .map(|(_, id)| id.clone())
+                .collect();
+
+            if models.is_empty() {
+                import.warnings.push(format!(
+                    "Skipped budget of provider '{}': no imported model uses it",
+                    provider
+                ));
+                continue;
+            }
+
+            import.push_budget(
+                format!("litellm-provider-{}", slug(provider)),
+                format!("LiteLLM {} provider", provider),
+                budget.budget_limit,
+                budget.time_period.as_deref(),
+                models,
+            );
+        }
+
+        if let Some(limit) = self.litellm_settings.max_budget {
+            import.push_budget(
+                PROXY_BUDGET_ID.to_string(),
+                "LiteLLM proxy".to_string(),
+                limit,
+                self.litellm_settings.budget_duration.as_deref(),
+                Vec::new(),
+            );
+        }
+
+        import
+    }
+}
+
+impl LiteLlmImport {
+    /// ID of the credential serving a deployment, added when no imported
+    /// credential has the same provider, key, endpoint and region
+    fn credential_for(
+        &mut self,
+        model_name: &str,
+        provider: &str,
+        credential_type: CredentialType,
+        params: &LiteLlmParams,
+    ) -> String {
+        let region = params.aws_region_name.as_deref().and_then(|name| {
+            Region::new(name)
+                .inspect_err(|e| {
+                    self.warnings.push(format!(
+                        "Ignored region of deployment '{}': {}",
+                        model_name, e
+                    ))
+                })
+                .ok()
+        });
+
+        if credential_type == CredentialType::AwsBedrock && params.aws_access_key_id.is_some() {
+            self.warnings.push(format!(
+                "AWS keys of deployment '{}' were not imported; Bedrock uses the AWS credentials of the gateway",
+                model_name
+            ));
+        }
+
+        if credential_type == CredentialType::AzureOpenAi && params.api_version.is_some() {
+            self.warnings.push(format!(
+                "api_version of deployment '{}' was not imported; the gateway default is used",
+                model_name
+            ));
+        }
+
+        let api_key = params.api_key.clone().unwrap_or_default();
+        if api_key.is_empty() && credential_type != CredentialType::AwsBedrock {
+            self.warnings.push(format!(
+                "Deployment '{}' has no api_key; set the key of its credential before use",
+                model_name
+            ));
+        }
+
+        let candidate = ImportedCredential {
+            id: String::new(),
+            name: format!("LiteLLM {}", provider),
+            credential_type,
+            api_key,
+            endpoint: params.api_base.clone(),
+            region,
+        };
+
+        if let Some(existing) = self.credentials.iter().find(|c| {
+            c.credential_type == candidate.credential_type
+                && c.api_key == candidate.api_key
+                && c.endpoint == candidate.endpoint
+                && c.region == candidate.region
+        }) {
+            return existing.id.clone();
+        }
+
+        let mut used: HashSet<String> = self.credentials.iter().map(|c| c.id.clone()).collect();
+        let id = unique_id(&format!("litellm-{}", slug(provider)), &mut used);
+
+        self.credentials.push(ImportedCredential {
+            id: id.clone(),
+            ..candidate
+        });
+
+        id
+    }
+
+    /// Set the first imported fallback of each model name as the fallback
+    /// model of its deployments
+    fn apply_fallbacks(&mut self, config: &LiteLlmConfig) {
+        for fallbacks in &config.litellm_settings.fallbacks {
+            for (model_name, candidates) in fallbacks {
+                let fallback = candidates.iter().find_map(|candidate| {
+                    self.models
+                        .iter()
+                        .find(|m| &m.name == candidate)
+                        .map(|m| m.id.clone())
+                });
+
+                let Some(fallback) = fallback else {
+                    self.warnings.push(format!(
+                        "Skipped fallbacks of '{}': none of them was imported",
+                        model_name
+                    ));
+                    continue;
+                };
+
+                for model in self.models.iter_mut().filter(|m| &m.name == model_name) {
+                    model.config.fallback_model_id = Some(fallback.clone());
+                }
+            }
+        }
+    }
+
+    fn push_budget(
+        &mut self,
+        id: String,
+        name: String,
+        limit_usd: f64,
+        duration: Option<&str>,
+        model_ids: Vec<String>,
+    ) {
+        let Some(period) = budget_period(duration) else {
+            self.warnings.push(format!(
+                "Skipped budget '{}': duration '{}' has no matching budget period",
+                id,
+                duration.unwrap_or_default()
+            ));
+            return;
+        };
+
+        let budget = model_ids.into_iter().fold(
+            Budget::new(id, name, period)
+                .with_hard_limit(limit_usd)
+                .with_description("Imported from LiteLLM"),
+            Budget::with_model,
+        );
+
+        self.budgets.push(budget);
+    }
+}
+
+/// LiteLLM provider, gateway provider and provider model of a LiteLLM model
+/// string. Models without a provider prefix are matched on their name, as
+/// LiteLLM does for well-known models.
+fn parse_model(model: &str) -> Option<(String, CredentialType, String)> {
+    let (provider, provider_model) = match model.split_once('/') {
+        Some((provider, rest)) => (provider.to_string(), rest.to_string()),
+        None if ["gpt-", "o1", "o3", "o4", "chatgpt-", "text-embedding-"]
+            .iter()
+            .any(|prefix| model.starts_with(prefix)) =>
+        {
+            ("openai".to_string(), model.to_string())
+        }
+        None if model.starts_with("claude") => ("anthropic".to_string(), model.to_string()),
+        None => return None,
+    };
+
+    let credential_type = match provider.as_str() {
+        "openai" | "text-completion-openai" => CredentialType::OpenAi,
+        "anthropic" => CredentialType::Anthropic,
+        "azure" => CredentialType::AzureOpenAi,
+        "bedrock" => CredentialType::AwsBedrock,
+        _ => return None,
+    };
+
+    Some((provider, credential_type, provider_model))
+}
+
+/// Budget period of a LiteLLM budget duration; no duration never resets
+fn budget_period(duration: Option<&str>) -> Option<BudgetPeriod> {
+    match duration.map(str::trim) {
+        None | Some("") => Some(BudgetPeriod::Lifetime),
+        Some("1d" | "24h") => Some(BudgetPeriod::Daily),
+        Some("7d" | "1w") => Some(BudgetPeriod::Weekly),
+        Some("30d" | "1mo") => Some(BudgetPeriod::Monthly),
+        Some(_) => None,
+    }
+}
+
+/// Lowercase ID made of letters, digits and single hyphens
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    slug.trim_end_matches('-').to_string()
+}
+
+/// The ID, or the ID with a numeric suffix when already used, within the
+/// model ID length limit
+fn unique_id(base: &str, used: &mut HashSet<String>) -> String {
+    let base = if base.is_empty() { "model" } else { base };
+    let mut suffix = 1;
+
+    loop {
+        let tail = if suffix == 1 {
+            String::new()
+        } else {
+            format!("-{}", suffix)
+        };
+        let head = base[..base.len().min(MAX_MODEL_ID_LENGTH - tail.len())].trim_end_matches('-');
+        let id = format!("{}{}", head, tail);
+
+        if used.insert(id.clone()) {
+            return id;
+        }
+        suffix += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(yaml: &str) -> LiteLlmConfig {
+        LiteLlmConfig::from_yaml(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_to_import_models_and_credentials() {
+        let import = config(
+            r#"
+model_list:
+  - model_name: GPT 4o
+    litellm_params: {model: openai/gpt-4o, api_key: sk-a, temperature: 0.2, timeout: 30}
+  - model_name: gpt-4o-mini
+    litellm_params: {model: gpt-4o-mini, api_key: sk-a}
+  - model_name: azure-gpt
+    litellm_params: {model: azure/prod-gpt4, api_key: az, api_base: "https://x.openai.azure.com"}
+  - model_name: claude
+    litellm_params: {model: bedrock/anthropic.claude-v2, aws_region_name: us-east-1}
+  - model_name: gemini
+    litellm_params: {model: vertex_ai/gemini-pro}
+litellm_settings:
+  num_retries: 3
+"#,
+        )
+        .to_import();
+
+        let ids: Vec<&str> = import.models.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["gpt-4o", "gpt-4o-mini", "azure-gpt", "claude"]);
+
+        let gpt = &import.models[0];
+        assert_eq!(gpt.name, "GPT 4o");
+        assert_eq!(gpt.provider_model, "gpt-4o");
+        assert_eq!(gpt.config.temperature, Some(0.2));
+        assert_eq!(gpt.config.timeout_ms, Some(30_000));
+        assert_eq!(gpt.config.max_retries, Some(3));
+        assert_eq!(import.models[1].credential_id, gpt.credential_id);
+        assert_eq!(import.models[2].provider_model, "prod-gpt4");
+
+        let credential_ids: Vec<&str> = import.credentials.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(
+            credential_ids,
+            vec!["litellm-openai", "litellm-azure", "litellm-bedrock"]
+        );
+        assert_eq!(
+            import.credentials[2].region.as_ref().map(Region::as_str),
+            Some("us-east-1")
+        );
+
+        assert_eq!(import.warnings.len(), 1);
+        assert!(import.warnings[0].contains("vertex_ai/gemini-pro"));
+    }
+
+    #[test]
+    fn test_to_import_load_balanced_deployments_and_fallbacks() {
+        let import = config(
+            r#"
+model_list:
+  - model_name: gpt-4o
+    litellm_params: {model: openai/gpt-4o, api_key: sk-a}
+  - model_name: gpt-4o
+    litellm_params: {model: openai/gpt-4o, api_key: sk-b}
+  - model_name: claude
+    litellm_params: {model: anthropic/claude-3-haiku, api_key: sk-c}
+litellm_settings:
+  fallbacks: [{"gpt-4o": ["missing", "claude"]}, {"claude": ["missing"]}]
+"#,
+        )
+        .to_import();
+
+        assert_eq!(import.models[1].id, "gpt-4o-2");
+        assert_eq!(import.models[1].credential_id, "litellm-openai-2");
+        assert_eq!(
+            import.models[0].config.fallback_model_id.as_deref(),
+            Some("claude")
+        );
+        assert_eq!(
+            import.models[1].config.fallback_model_id.as_deref(),
+            Some("claude")
+        );
+        assert_eq!(import.models[2].config.fallback_model_id, None);
+        assert_eq!(import.warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_to_import_budgets() {
+        let import = config(
+            r#"
+model_list:
+  - model_name: gpt-4o
+    litellm_params: {model: openai/gpt-4o, api_key: sk-a, max_budget: 10, budget_duration: 1d}
+  - model_name: claude
+    litellm_params: {model: anthropic/claude-3-haiku, api_key: sk-c, max_budget: 5, budget_duration: 2h}
+litellm_settings:
+  max_budget: 100
+  budget_duration: 30d
+router_settings:
+  provider_budget_config:
+    openai: {budget_limit: 50, time_period: 7d}
+    groq: {budget_limit: 5, time_period: 1d}
+"#,
+        )
+        .to_import();
+
+        let budgets: Vec<(&str, BudgetPeriod, i64, &[String])> = import
+            .budgets
+            .iter()
+            .map(|b| {
+                (
+                    b.id().as_str(),
+                    b.period,
+                    b.hard_limit_micros,
+                    b.model_ids.as_slice(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            budgets,
+            vec![
+                (
+                    "litellm-model-gpt-4o",
+                    BudgetPeriod::Daily,
+                    10_000_000,
+                    &["gpt-4o".to_string()][..]
+                ),
+                (
+                    "litellm-provider-openai",
+                    BudgetPeriod::Weekly,
+                    50_000_000,
+                    &["gpt-4o".to_string()][..]
+                ),
+                (PROXY_BUDGET_ID, BudgetPeriod::Monthly, 100_000_000, &[][..]),
+            ]
+        );
+        assert_eq!(import.warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_unique_id() {
+        let mut used = HashSet::new();
+        assert_eq!(unique_id("gpt", &mut used), "gpt");
+        assert_eq!(unique_id("gpt", &mut used), "gpt-2");
+        assert_eq!(unique_id("", &mut used), "model");
+
+        let long = "a".repeat(60);
+        assert_eq!(unique_id(&long, &mut used).len(), MAX_MODEL_ID_LENGTH);
+        assert_eq!(unique_id(&long, &mut used), format!("{}-2", "a".repeat(48)));
+
+        assert_eq!(slug("  GPT-4o (Prod)!"), "gpt-4o-prod");
+    }
+}
@@ -0,0 +1,13 @@
+//! LiteLLM migration domain
+//!
+//! This module reads the configuration of a LiteLLM proxy and converts its
+//! model list and spend limits into gateway credentials, models and budgets.
+
+mod config;
+mod convert;
+
+pub use config::{
+    ENV_REFERENCE_PREFIX, LiteLlmConfig, LiteLlmDeployment, LiteLlmParams, LiteLlmProviderBudget,
+    LiteLlmRouterSettings, LiteLlmSettings,
+};
+pub use convert::{ImportedCredential, ImportedModel, LiteLlmImport, PROXY_BUDGET_ID};
@@ -83,6 +83,20 @@ impl Message {
         }
     }
 
+    /// Rewrite the text of the message, including every text part
+    pub fn map_text(&mut self, mut f: impl FnMut(&str) -> String) {
+        match &mut self.content {
+            MessageContent::Text { content } => *content = f(content),
+            MessageContent::Parts { content } => {
+                for part in content {
+                    if let ContentPart::Text { text } = part {
+                        *text = f(text);
+                    }
+                }
+            }
+        }
+    }
+
     pub fn content_parts(&self) -> Vec<&ContentPart> {
         match &self.content {
             MessageContent::Text { .. } => {
@@ -111,4 +125,22 @@ mod tests {
         assert!(json.contains("\"role\":\"assistant\""));
         assert!(json.contains("\"content\":\"Hi there!\""));
     }
+
+    #[test]
+    fn test_map_text() {
+        let mut msg = Message::user_with_parts(vec![
+            ContentPart::Text {
+                text: "hello".to_string(),
+            },
+            ContentPart::ImageUrl {
+                url: "https://example.com/a.png".to_string(),
+            },
+        ]);
+        msg.map_text(str::to_uppercase);
+        assert_eq!(msg.content_text(), Some("HELLO"));
+
+        let mut msg = Message::user("hi");
+        msg.map_text(|text| format!("{}!", text));
+        assert_eq!(msg.content_text(), Some("hi!"));
+    }
 }
@@ -2,15 +2,19 @@
 
 mod message;
 mod provider;
+mod provider_error;
 mod provider_resolver;
+mod rate_limit;
 mod request;
 mod response;
 
 pub use message::{ContentPart, Message, MessageRole};
 pub use provider::{LlmProvider, LlmStream};
+pub use provider_error::{GatewayErrorCode, ProviderFailure};
 pub use provider_resolver::{ProviderResolver, ResolvedModel, StaticProviderResolver};
+pub use rate_limit::ProviderRateLimit;
 pub use request::{LlmJsonSchema, LlmRequest, LlmRequestBuilder, LlmResponseFormat};
-pub use response::{FinishReason, LlmResponse, StreamChunk, Usage};
+pub use response::{FinishReason, LlmResponse, StreamChunk, ToolCallDelta, Usage};
 
 #[cfg(test)]
 pub use provider::mock::MockLlmProvider;
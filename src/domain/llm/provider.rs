@@ -34,12 +34,14 @@ pub trait LlmProvider: Send + Sync + Debug {
 pub mod mock {
     use super::*;
     use futures::stream;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     #[derive(Debug)]
     pub struct MockLlmProvider {
         name: &'static str,
         response: Option<LlmResponse>,
         error: Option<String>,
+        calls: AtomicUsize,
     }
 
     impl MockLlmProvider {
@@ -48,9 +50,15 @@ pub mod mock {
                 name,
                 response: None,
                 error: None,
+                calls: AtomicUsize::new(0),
             }
         }
 
+        /// Number of chat requests received so far
+        pub fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+
         pub fn with_response(mut self, response: LlmResponse) -> Self {
             self.response = Some(response);
             self
@@ -69,6 +77,8 @@ pub mod mock {
             _model: &str,
             _request: LlmRequest,
         ) -> Result<LlmResponse, DomainError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+
             if let Some(ref error) = self.error {
                 return Err(DomainError::provider(self.name, error));
             }
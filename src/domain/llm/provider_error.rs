@@ -0,0 +1,265 @@
+//! Gateway error taxonomy for provider failures
+//!
+//! Providers report failures in their own shapes: OpenAI and Azure OpenAI as
+//! `{"error": {"type", "code", "message"}}`, Anthropic as
+//! `{"type": "error", "error": {"type", "message"}}` and Bedrock as
+//! `{"__type", "message"}`. [`ProviderFailure::classify`] maps the message of a
+//! [`DomainError::Provider`](crate::domain::DomainError::Provider), which
+//! carries the upstream `HTTP <status>: <body>`, to one [`GatewayErrorCode`]
+//! clients can handle the same way whatever served the request.
+
+use serde::Serialize;
+
+/// Provider-independent error code of a failed completion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GatewayErrorCode {
+    /// The provider throttled the request, retry later
+    RateLimited,
+    /// The provider account ran out of credits or quota
+    QuotaExceeded,
+    /// The prompt or the answer was blocked by the provider's content filter
+    ContentFiltered,
+    /// The prompt does not fit in the model's context window
+    ContextLengthExceeded,
+    /// The provider rejected the request parameters
+    InvalidRequest,
+    /// The provider rejected the gateway's credentials
+    AuthenticationFailed,
+    /// The provider did not answer in time
+    ProviderTimeout,
+    /// The provider is down, overloaded or unreachable
+    ProviderUnavailable,
+    /// Any other provider failure
+    ProviderError,
+}
+
+impl GatewayErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::RateLimited => "rate_limited",
+            Self::QuotaExceeded => "quota_exceeded",
+            Self::ContentFiltered => "content_filtered",
+            Self::ContextLengthExceeded => "context_length_exceeded",
+            Self::InvalidRequest => "invalid_request",
+            Self::AuthenticationFailed => "authentication_failed",
+            Self::ProviderTimeout => "provider_timeout",
+            Self::ProviderUnavailable => "provider_unavailable",
+            Self::ProviderError => "provider_error",
+        }
+    }
+
+    /// Whether the same request may succeed when retried later
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::RateLimited | Self::ProviderTimeout | Self::ProviderUnavailable
+        )
+    }
+}
+
+impl std::fmt::Display for GatewayErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Classified provider failure
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderFailure {
+    pub code: GatewayErrorCode,
+    /// Upstream HTTP status, when the provider answered
+    pub status: Option<u16>,
+    /// Provider error body, as JSON when it parses
+    pub detail: serde_json::Value,
+    /// Provider error message, falling back to the whole error message
+    pub message: String,
+}
+
+impl ProviderFailure {
+    /// Classify the message of a provider error
+    pub fn classify(message: &str) -> Self {
+        let (status, body) = split_http_error(message);
+        let detail = body
+            .and_then(|body| serde_json::from_str::<serde_json::Value>(body).ok())
+            .unwrap_or_else(|| serde_json::Value::String(body.unwrap_or(message).to_string()));
+
+        let (kind, provider_message) = error_kind(&detail);
+        let provider_message = provider_message.unwrap_or_else(|| message.to_string());
+        let code = classify_code(status, &kind, &provider_message.to_lowercase());
+
+        Self {
+            code,
+            status,
+            detail,
+            message: provider_message,
+        }
+    }
+}
+
+/// Split `... HTTP 429 Too Many Requests: {body}` into status and body
+fn split_http_error(message: &str) -> (Option<u16>, Option<&str>) {
+    let Some(rest) = message.split("HTTP ").nth(1) else {
+        return (None, None);
+    };
+
+    let status = rest
+        .get(..3)
+        .filter(|code| code.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|code| code.parse().ok());
+    let body = rest.split_once(": ").map(|(_, body)| body.trim());
+
+    (status, body.filter(|body| !body.is_empty()))
+}
+
+/// Lowercased error type and code identifiers, and the message, of the known
+/// provider error shapes
+fn error_kind(detail: &serde_json::Value) -> (String, Option<String>) {
+    let error = match detail.get("error") {
+        Some(error) if error.is_object() => error,
+        _ => detail,
+    };
+
+    let mut kind = Vec::new();
+    for key in ["type", "code", "__type"] {
+        if let Some(value) = error.get(key).and_then(serde_json::Value::as_str) {
+            kind.push(value.to_lowercase());
+        }
+    }
+    // Azure OpenAI reports content filter results in an inner error
+    if let Some(inner) = error
+        .get("innererror")
+        .and_then(|inner| inner.get("code"))
+        .and_then(serde_json::Value::as_str)
+    {
+        kind.push(inner.to_lowercase());
+    }
+
+    let message = error
+        .get("message")
+        .or_else(|| error.get("Message"))
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string);
+
+    (kind.join(" "), message)
+}
+
+fn classify_code(status: Option<u16>, kind: &str, message: &str) -> GatewayErrorCode {
+    let has = |needles: &[&str]| {
+        needles
+            .iter()
+            .any(|needle| kind.contains(needle) || message.contains(needle))
+    };
+
+    if has(&[
+        "context_length_exceeded",
+        "maximum context length",
+        "prompt is too long",
+        "input is too long",
+        "too many input tokens",
+    ]) {
+        GatewayErrorCode::ContextLengthExceeded
+    } else if has(&["content_filter", "content_policy", "responsibleaipolicyviolation"]) {
+        GatewayErrorCode::ContentFiltered
+    } else if has(&["insufficient_quota", "servicequotaexceeded"]) {
+        GatewayErrorCode::QuotaExceeded
+    } else if status == Some(429) || has(&["rate_limit", "throttling", "too many requests"]) {
+        GatewayErrorCode::RateLimited
+    } else if matches!(status, Some(401 | 403))
+        || has(&["authentication", "accessdenied", "unrecognizedclient", "invalid_api_key"])
+    {
+        GatewayErrorCode::AuthenticationFailed
+    } else if matches!(status, Some(408 | 504)) || has(&["timeout", "timed out"]) {
+        GatewayErrorCode::ProviderTimeout
+    } else if matches!(status, Some(500..=599))
+        || has(&["overloaded", "serviceunavailable", "modelnotready", "request failed"])
+    {
+        GatewayErrorCode::ProviderUnavailable
+    } else if matches!(status, Some(400 | 404 | 413 | 422))
+        || has(&["invalid_request", "validationexception"])
+    {
+        GatewayErrorCode::InvalidRequest
+    } else {
+        GatewayErrorCode::ProviderError
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_openai_errors() {
+        let failure = ProviderFailure::classify(
+            r#"HTTP 400 Bad Request: {"error": {"message": "This model's maximum context length is 8192 tokens.", "type": "invalid_request_error", "param": "messages", "code": "context_length_exceeded"}}"#,
+        );
+        assert_eq!(failure.code, GatewayErrorCode::ContextLengthExceeded);
+        assert_eq!(failure.status, Some(400));
+        assert_eq!(failure.detail["error"]["param"], "messages");
+        assert!(failure.message.starts_with("This model's maximum context length"));
+
+        let failure = ProviderFailure::classify(
+            r#"HTTP 429 Too Many Requests: {"error": {"message": "You exceeded your current quota", "type": "insufficient_quota", "code": "insufficient_quota"}}"#,
+        );
+        assert_eq!(failure.code, GatewayErrorCode::QuotaExceeded);
+
+        let failure = ProviderFailure::classify(
+            r#"HTTP 429 Too Many Requests: {"error": {"message": "Rate limit reached", "type": "requests", "code": "rate_limit_exceeded"}}"#,
+        );
+        assert_eq!(failure.code, GatewayErrorCode::RateLimited);
+        assert!(failure.code.is_retryable());
+    }
+
+    #[test]
+    fn test_classify_azure_content_filter() {
+        let failure = ProviderFailure::classify(
+            r#"HTTP 400 Bad Request: {"error": {"message": "The response was filtered", "code": "content_filter", "innererror": {"code": "ResponsibleAIPolicyViolation"}}}"#,
+        );
+        assert_eq!(failure.code, GatewayErrorCode::ContentFiltered);
+        assert!(!failure.code.is_retryable());
+    }
+
+    #[test]
+    fn test_classify_anthropic_errors() {
+        let failure = ProviderFailure::classify(
+            r#"HTTP 529 <unknown status code>: {"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}"#,
+        );
+        assert_eq!(failure.code, GatewayErrorCode::ProviderUnavailable);
+        assert_eq!(failure.message, "Overloaded");
+
+        let failure = ProviderFailure::classify(
+            r#"HTTP 400 Bad Request: {"type": "error", "error": {"type": "invalid_request_error", "message": "prompt is too long: 210000 tokens > 200000 maximum"}}"#,
+        );
+        assert_eq!(failure.code, GatewayErrorCode::ContextLengthExceeded);
+
+        let failure = ProviderFailure::classify(
+            r#"HTTP 401 Unauthorized: {"type": "error", "error": {"type": "authentication_error", "message": "invalid x-api-key"}}"#,
+        );
+        assert_eq!(failure.code, GatewayErrorCode::AuthenticationFailed);
+    }
+
+    #[test]
+    fn test_classify_bedrock_and_transport_errors() {
+        let failure = ProviderFailure::classify(
+            r#"API error: HTTP 429: {"__type": "ThrottlingException", "message": "Too many requests, please wait before trying again."}"#,
+        );
+        assert_eq!(failure.code, GatewayErrorCode::RateLimited);
+        assert_eq!(failure.status, Some(429));
+
+        let failure = ProviderFailure::classify(
+            r#"API error: HTTP 400: {"__type": "ValidationException", "message": "Input is too long for requested model."}"#,
+        );
+        assert_eq!(failure.code, GatewayErrorCode::ContextLengthExceeded);
+
+        let failure = ProviderFailure::classify("Request failed: error sending request");
+        assert_eq!(failure.code, GatewayErrorCode::ProviderUnavailable);
+        assert_eq!(failure.status, None);
+        assert_eq!(
+            failure.detail,
+            serde_json::json!("Request failed: error sending request")
+        );
+
+        let failure = ProviderFailure::classify("No choices in response");
+        assert_eq!(failure.code, GatewayErrorCode::ProviderError);
+    }
+}
@@ -0,0 +1,191 @@
+//! Provider rate limit hints
+//!
+//! Throttled providers say when to retry and how much quota is left in
+//! their own headers: `retry-after` (seconds or an HTTP date) and
+//! `retry-after-ms`, OpenAI's `x-ratelimit-{remaining,reset}-{requests,tokens}`
+//! (resets as durations like `6m0s`) and Anthropic's
+//! `anthropic-ratelimit-{requests,tokens}-{remaining,reset}` (resets as
+//! RFC 3339 timestamps). [`ProviderRateLimit`] normalizes them.
+
+use chrono::{DateTime, Utc};
+
+/// Normalized rate limit hints of a provider response
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProviderRateLimit {
+    /// Seconds to wait before retrying
+    pub retry_after_secs: Option<u64>,
+    pub remaining_requests: Option<u64>,
+    pub remaining_tokens: Option<u64>,
+}
+
+impl ProviderRateLimit {
+    /// Hints of the response headers, `None` when there are none
+    pub fn from_headers<'a>(
+        headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Option<Self> {
+        Self::from_headers_at(headers, Utc::now())
+    }
+
+    fn from_headers_at<'a>(
+        headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+        now: DateTime<Utc>,
+    ) -> Option<Self> {
+        let mut limit = Self::default();
+        let mut retry_after_ms = None;
+        let mut retry_after = None;
+        let mut reset_secs: Option<u64> = None;
+
+        for (name, value) in headers {
+            let value = value.trim();
+            match name.to_ascii_lowercase().as_str() {
+                "retry-after-ms" => retry_after_ms = value.parse::<u64>().ok(),
+                "retry-after" => retry_after = parse_retry_after(value, now),
+                "x-ratelimit-remaining-requests" | "anthropic-ratelimit-requests-remaining" => {
+                    limit.remaining_requests = value.parse().ok()
+                }
+                "x-ratelimit-remaining-tokens" | "anthropic-ratelimit-tokens-remaining" => {
+                    limit.remaining_tokens = value.parse().ok()
+                }
+                "x-ratelimit-reset-requests" | "x-ratelimit-reset-tokens" => {
+                    if let Some(secs) = parse_reset_duration(value) {
+                        reset_secs = Some(reset_secs.map_or(secs, |s| s.max(secs)));
+                    }
+                }
+                "anthropic-ratelimit-requests-reset" | "anthropic-ratelimit-tokens-reset" => {
+                    if let Some(secs) = seconds_until(value, now) {
+                        reset_secs = Some(reset_secs.map_or(secs, |s| s.max(secs)));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Exhausted quotas reset at the latest of the announced resets
+        let exhausted = limit.remaining_requests == Some(0) || limit.remaining_tokens == Some(0);
+        limit.retry_after_secs = retry_after_ms
+            .map(|ms: u64| ms.div_ceil(1000))
+            .or(retry_after)
+            .or(reset_secs.filter(|_| exhausted));
+
+        (limit != Self::default()).then_some(limit)
+    }
+}
+
+/// `retry-after` as delay seconds or an HTTP date
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<u64> {
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(secs);
+    }
+
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    Some((at.with_timezone(&Utc) - now).num_seconds().max(0) as u64)
+}
+
+/// Seconds until an RFC 3339 timestamp, rounded up
+fn seconds_until(value: &str, now: DateTime<Utc>) -> Option<u64> {
+    let at = DateTime::parse_from_rfc3339(value).ok()?;
+    let millis = (at.with_timezone(&Utc) - now).num_milliseconds().max(0) as u64;
+    Some(millis.div_ceil(1000))
+}
+
+/// OpenAI reset durations (`20ms`, `1s`, `6m0s`, `1h2m3.5s`), rounded up to
+/// whole seconds
+fn parse_reset_duration(value: &str) -> Option<u64> {
+    let mut millis = 0.0;
+    let mut number = String::new();
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            continue;
+        }
+
+        let amount: f64 = number.parse().ok()?;
+        number.clear();
+        millis += match c {
+            'h' => amount * 3_600_000.0,
+            'm' if chars.peek() == Some(&'s') => {
+                chars.next();
+                amount
+            }
+            'm' => amount * 60_000.0,
+            's' => amount * 1000.0,
+            _ => return None,
+        };
+    }
+
+    if !number.is_empty() {
+        return None;
+    }
+
+    Some((millis / 1000.0).ceil() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_after_headers() {
+        let now = Utc::now();
+
+        let limit = ProviderRateLimit::from_headers_at([("Retry-After", "20")], now).unwrap();
+        assert_eq!(limit.retry_after_secs, Some(20));
+
+        let limit = ProviderRateLimit::from_headers_at(
+            [("retry-after", "20"), ("retry-after-ms", "1500")],
+            now,
+        )
+        .unwrap();
+        assert_eq!(limit.retry_after_secs, Some(2));
+
+        let at = (now + chrono::Duration::seconds(90)).to_rfc2822();
+        let limit =
+            ProviderRateLimit::from_headers_at([("retry-after", at.as_str())], now).unwrap();
+        assert!((89..=90).contains(&limit.retry_after_secs.unwrap()));
+
+        let headers = [("content-type", "text/plain")];
+        assert!(ProviderRateLimit::from_headers_at(headers, now).is_none());
+    }
+
+    #[test]
+    fn test_openai_rate_limit_headers() {
+        let limit = ProviderRateLimit::from_headers_at(
+            [
+                ("x-ratelimit-remaining-requests", "0"),
+                ("x-ratelimit-remaining-tokens", "1200"),
+                ("x-ratelimit-reset-requests", "6m0s"),
+                ("x-ratelimit-reset-tokens", "20ms"),
+            ],
+            Utc::now(),
+        )
+        .unwrap();
+
+        assert_eq!(limit.remaining_requests, Some(0));
+        assert_eq!(limit.remaining_tokens, Some(1200));
+        assert_eq!(limit.retry_after_secs, Some(360));
+
+        assert_eq!(parse_reset_duration("1h2m3.5s"), Some(3724));
+        assert_eq!(parse_reset_duration("soon"), None);
+    }
+
+    #[test]
+    fn test_anthropic_rate_limit_headers() {
+        let now = Utc::now();
+        let reset = (now + chrono::Duration::seconds(30)).to_rfc3339();
+        let limit = ProviderRateLimit::from_headers_at(
+            [
+                ("anthropic-ratelimit-requests-remaining", "4"),
+                ("anthropic-ratelimit-tokens-remaining", "0"),
+                ("anthropic-ratelimit-tokens-reset", reset.as_str()),
+            ],
+            now,
+        )
+        .unwrap();
+
+        assert_eq!(limit.remaining_requests, Some(4));
+        assert_eq!(limit.remaining_tokens, Some(0));
+        assert_eq!(limit.retry_after_secs, Some(30));
+    }
+}
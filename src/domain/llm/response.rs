@@ -67,12 +67,50 @@ impl LlmResponse {
     }
 }
 
+/// Fragment of a tool call streamed by a provider, in the shape of OpenAI
+/// `tool_calls` deltas whatever the provider's own events look like
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolCallDelta {
+    /// Position of the call among the tool calls of the answer
+    pub index: u32,
+    /// Call ID, on the first fragment of a call
+    pub id: Option<String>,
+    /// Function name, on the first fragment of a call
+    pub name: Option<String>,
+    /// Next piece of the JSON arguments
+    pub arguments: String,
+}
+
+impl ToolCallDelta {
+    /// First fragment of a call
+    pub fn start(index: u32, id: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            index,
+            id: Some(id.into()),
+            name: Some(name.into()),
+            arguments: String::new(),
+        }
+    }
+
+    /// Piece of the arguments of an already started call
+    pub fn arguments(index: u32, arguments: impl Into<String>) -> Self {
+        Self {
+            index,
+            id: None,
+            name: None,
+            arguments: arguments.into(),
+        }
+    }
+}
+
 /// Streaming chunk from an LLM provider
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StreamChunk {
     pub id: String,
     pub model: String,
     pub delta: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ToolCallDelta>,
     pub finish_reason: Option<FinishReason>,
     pub usage: Option<Usage>,
 }
@@ -83,11 +121,17 @@ impl StreamChunk {
             id,
             model,
             delta: None,
+            tool_calls: Vec::new(),
             finish_reason: None,
             usage: None,
         }
     }
 
+    pub fn with_tool_call(mut self, tool_call: ToolCallDelta) -> Self {
+        self.tool_calls.push(tool_call);
+        self
+    }
+
     pub fn with_delta(mut self, delta: impl Into<String>) -> Self {
         self.delta = Some(delta.into());
         self
@@ -0,0 +1,275 @@
+//! MCP tool entities
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use utoipa::ToSchema;
+
+use crate::domain::storage::{StorageEntity, StorageKey};
+use crate::domain::{DomainError, FieldViolations, ModelValidationError, validate_model_id};
+
+/// Maximum number of passages a knowledge base search tool returns
+pub const MAX_MCP_SEARCH_TOP_K: u32 = 20;
+
+/// Name of an MCP tool, as listed to MCP clients
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct McpToolId(String);
+
+impl McpToolId {
+    pub fn new(id: impl Into<String>) -> Result<Self, ModelValidationError> {
+        let id = id.into();
+        validate_model_id(&id)?;
+        Ok(Self(id))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for McpToolId {
+    type Error = ModelValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<McpToolId> for String {
+    fn from(id: McpToolId) -> Self {
+        id.0
+    }
+}
+
+impl std::fmt::Display for McpToolId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StorageKey for McpToolId {
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// What calling the tool does
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum McpToolTarget {
+    /// Execute a workflow with the tool arguments as its input
+    Workflow { workflow_id: String },
+    /// Search a knowledge base with the `query` argument
+    KnowledgeBaseSearch {
+        knowledge_base_id: String,
+        /// Passages returned unless the call asks for fewer
+        #[serde(default = "default_top_k")]
+        top_k: u32,
+    },
+}
+
+fn default_top_k() -> u32 {
+    5
+}
+
+impl McpToolTarget {
+    pub fn workflow(workflow_id: impl Into<String>) -> Self {
+        Self::Workflow {
+            workflow_id: workflow_id.into(),
+        }
+    }
+
+    pub fn knowledge_base_search(knowledge_base_id: impl Into<String>) -> Self {
+        Self::KnowledgeBaseSearch {
+            knowledge_base_id: knowledge_base_id.into(),
+            top_k: default_top_k(),
+        }
+    }
+}
+
+/// Input schema of knowledge base search tools
+pub fn knowledge_base_search_schema(top_k: u32) -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "query": {"type": "string", "description": "Text to search for"},
+            "top_k": {
+                "type": "integer",
+                "minimum": 1,
+                "maximum": top_k,
+                "description": "Passages to return"
+            }
+        },
+        "required": ["query"]
+    })
+}
+
+/// Gateway workflow or knowledge base search exposed to MCP clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpTool {
+    /// Tool name
+    id: McpToolId,
+    /// What the tool does, for the agent deciding when to call it
+    description: String,
+    /// Workflow or knowledge base behind the tool
+    target: McpToolTarget,
+    /// Whether the tool is listed to MCP clients
+    enabled: bool,
+    /// Creation timestamp
+    created_at: DateTime<Utc>,
+    /// Last update timestamp
+    updated_at: DateTime<Utc>,
+}
+
+impl McpTool {
+    pub fn new(id: McpToolId, description: impl Into<String>, target: McpToolTarget) -> Self {
+        let now = Utc::now();
+        Self {
+            id,
+            description: description.into(),
+            target,
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    // Builder methods
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    // Getters
+    pub fn id(&self) -> &McpToolId {
+        &self.id
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn target(&self) -> &McpToolTarget {
+        &self.target
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    // Mutators
+    pub fn set_description(&mut self, description: impl Into<String>) {
+        self.description = description.into();
+        self.touch();
+    }
+
+    pub fn set_target(&mut self, target: McpToolTarget) {
+        self.target = target;
+        self.touch();
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.touch();
+    }
+
+    /// Check the definition, reporting every invalid field
+    pub fn validate(&self) -> Result<(), DomainError> {
+        let mut violations = FieldViolations::new();
+
+        if self.description.trim().is_empty() {
+            violations.push("description", "Description is required");
+        }
+
+        match &self.target {
+            McpToolTarget::Workflow { workflow_id } => {
+                if workflow_id.trim().is_empty() {
+                    violations.push("target.workflow_id", "Workflow ID is required");
+                }
+            }
+            McpToolTarget::KnowledgeBaseSearch {
+                knowledge_base_id,
+                top_k,
+            } => {
+                if knowledge_base_id.trim().is_empty() {
+                    violations.push(
+                        "target.knowledge_base_id",
+                        "Knowledge base ID is required",
+                    );
+                }
+                if !(1..=MAX_MCP_SEARCH_TOP_K).contains(top_k) {
+                    violations.push(
+                        "target.top_k",
+                        format!("top_k must be between 1 and {}", MAX_MCP_SEARCH_TOP_K),
+                    );
+                }
+            }
+        }
+
+        violations.into_result()
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+    }
+}
+
+impl StorageEntity for McpTool {
+    type Key = McpToolId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let tool = McpTool::new(
+            McpToolId::new("search-docs").unwrap(),
+            "Search the product documentation",
+            McpToolTarget::knowledge_base_search("docs"),
+        );
+        assert!(tool.validate().is_ok());
+
+        let invalid = McpTool::new(
+            McpToolId::new("search-docs").unwrap(),
+            " ",
+            McpToolTarget::KnowledgeBaseSearch {
+                knowledge_base_id: "docs".to_string(),
+                top_k: 0,
+            },
+        );
+        let Err(DomainError::InvalidFields { violations }) = invalid.validate() else {
+            panic!("expected invalid fields");
+        };
+        let fields: Vec<&str> = violations.iter().map(|v| v.field.as_str()).collect();
+        assert_eq!(fields, vec!["description", "target.top_k"]);
+    }
+
+    #[test]
+    fn test_target_serialization() {
+        let target: McpToolTarget = serde_json::from_value(
+            json!({"type": "knowledge_base_search", "knowledge_base_id": "docs"}),
+        )
+        .unwrap();
+        assert_eq!(target, McpToolTarget::knowledge_base_search("docs"));
+
+        assert_eq!(
+            serde_json::to_value(McpToolTarget::workflow("triage")).unwrap(),
+            json!({"type": "workflow", "workflow_id": "triage"})
+        );
+    }
+}
@@ -0,0 +1,8 @@
+//! MCP domain module: gateway workflows and knowledge base searches curated
+//! as tools of the gateway's Model Context Protocol server
+
+mod entity;
+mod repository;
+
+pub use entity::*;
+pub use repository::*;
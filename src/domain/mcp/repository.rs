@@ -0,0 +1,23 @@
+//! MCP tool repository trait
+
+use async_trait::async_trait;
+use std::fmt::Debug;
+
+use super::{McpTool, McpToolId};
+use crate::domain::DomainError;
+
+/// Repository for MCP tools
+#[async_trait]
+pub trait McpToolRepository: Send + Sync + Debug {
+    /// Get a tool by name
+    async fn get(&self, id: &McpToolId) -> Result<Option<McpTool>, DomainError>;
+
+    /// List every tool, ordered by name
+    async fn list(&self) -> Result<Vec<McpTool>, DomainError>;
+
+    /// Create or replace a tool
+    async fn save(&self, tool: McpTool) -> Result<McpTool, DomainError>;
+
+    /// Delete a tool
+    async fn delete(&self, id: &McpToolId) -> Result<bool, DomainError>;
+}
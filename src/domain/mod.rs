@@ -1,27 +1,48 @@
 //! Domain layer - Core business logic and entities
 
+pub mod agent;
 pub mod api_key;
+pub mod assistant;
+pub mod audit;
 pub mod cache;
 pub mod chain;
 pub mod config;
 pub mod crag;
 pub mod credentials;
+pub mod dataset;
 pub mod embedding;
 pub mod error;
+pub mod event;
 pub mod experiment;
 pub mod external_api;
+pub mod fine_tune;
+pub mod guardrail;
 pub mod ingestion;
 pub mod knowledge_base;
+pub mod litellm;
 pub mod llm;
+pub mod mcp;
 pub mod model;
+pub mod network;
+pub mod notification;
 pub mod operation;
+pub mod organization;
 pub mod plugin;
+pub mod privacy;
 pub mod prompt;
+pub mod reconcile;
+pub mod reference;
+pub mod request_trace;
+pub mod residency;
+pub mod role;
 pub mod semantic_cache;
+pub mod service_account;
+pub mod slo;
 pub mod storage;
 pub mod team;
 pub mod test_case;
 pub mod traits;
+pub mod transform;
 pub mod usage;
 pub mod user;
 pub mod webhook;
@@ -35,30 +56,35 @@ pub use config::{
     AppConfiguration, ConfigCategory, ConfigEntry, ConfigKey, ConfigMetadata, ConfigRepository,
     ConfigValidationError, ConfigValue, ExecutionLog, ExecutionLogId, ExecutionLogQuery,
     ExecutionLogRepository, ExecutionLogValidationError, ExecutionStats, ExecutionStatus,
-    ExecutionType, Executor, TokenUsage as ExecutionTokenUsage, WorkflowStepLog,
+    ExecutionType, Executor, ShadowComparison, StreamAbortReason, StreamingMetrics,
+    TokenUsage as ExecutionTokenUsage, WorkflowStepLog,
 };
 pub use credentials::{
     Credential, CredentialId, CredentialProvider, CredentialType, StoredCredential,
     StoredCredentialRepository,
 };
-pub use error::DomainError;
+pub use error::{DomainError, FieldViolation, FieldViolations};
 pub use llm::{
     ContentPart, FinishReason, LlmJsonSchema, LlmProvider, LlmRequest, LlmRequestBuilder,
     LlmResponse, LlmResponseFormat, LlmStream, Message, MessageRole, ProviderResolver,
-    StaticProviderResolver, StreamChunk, Usage,
+    StaticProviderResolver, StreamChunk, ToolCallDelta, Usage,
 };
 pub use model::{
-    validate_model_config, validate_model_id, Model, ModelConfig, ModelId, ModelValidationError,
+    check_model_config, validate_model_config, validate_model_id, Model, ModelConfig, ModelId, ModelLineage,
+    ModelValidationError,
 };
 pub use operation::{
-    validate_operation_id, Operation, OperationError, OperationId, OperationRepository,
-    OperationStatus, OperationType,
+    validate_operation_id, Operation, OperationError, OperationId, OperationProgress,
+    OperationRepository, OperationStatus, OperationType,
 };
 pub use prompt::{
     Prompt, PromptId, PromptOutputSchema, PromptTemplate, PromptVariable, PromptVersion,
-    TemplateError,
+    TemplateError, VariableType,
+};
+pub use cache::{
+    Cache, CacheExt, CacheKey, CacheKeyGenerator, CacheKeyParams, CacheMode, CachePolicy,
+    DefaultKeyGenerator,
 };
-pub use cache::{Cache, CacheExt, CacheKey, CacheKeyGenerator, CacheKeyParams, DefaultKeyGenerator};
 pub use knowledge_base::{
     AddDocumentsResult, DeleteDocumentsResult, Document, EmbeddingConfig, FilterBuilder,
     FilterCondition, FilterConnector, FilterOperator, FilterValue, KnowledgeBase,
@@ -81,18 +107,18 @@ pub use api_key::{
 };
 pub use workflow::{
     ChatCompletionStep, Condition, ConditionalAction, ConditionalStep, ConditionOperator,
-    CragScoringStep, HttpMethod, HttpRequestStep, KnowledgeBaseSearchStep, OnErrorAction,
-    StepExecutionResult, VariableRef, Workflow, WorkflowContext, WorkflowError, WorkflowExecutor,
-    WorkflowId, WorkflowRepository, WorkflowResult, WorkflowStep, WorkflowStepType,
-    WorkflowTokenUsage,
+    ContextCompression, CragScoringStep, HttpMethod, HttpRequestStep, KnowledgeBaseSearchStep,
+    OnErrorAction, QueryTransform, StepExecutionResult, StepProgress, VariableRef, Workflow,
+    WorkflowContext, WorkflowError, WorkflowExecutor, WorkflowId, WorkflowRepository,
+    WorkflowResult, WorkflowStep, WorkflowStepType, WorkflowTokenUsage, MAX_QUERY_EXPANSIONS,
 };
 pub use user::{
     validate_password, validate_user_id, validate_username, User, UserId, UserRepository,
     UserStatus, UserValidationError,
 };
 pub use embedding::{
-    cosine_similarity, Embedding, EmbeddingInput, EmbeddingProvider, EmbeddingRequest,
-    EmbeddingResponse, EmbeddingUsage,
+    cosine_similarity, DistanceMetric, Embedding, EmbeddingInput, EmbeddingProvider,
+    EmbeddingRequest, EmbeddingResponse, EmbeddingUsage,
 };
 pub use external_api::{ExternalApi, ExternalApiId};
 pub use semantic_cache::{
@@ -105,11 +131,11 @@ pub use usage::{
     UsageRecordId, UsageRepository, UsageSummary, UsageType,
 };
 pub use experiment::{
-    AssignmentResult, ConfigOverrides, Experiment, ExperimentId, ExperimentQuery,
+    AssignmentKey, AssignmentResult, ConfigOverrides, Experiment, ExperimentId, ExperimentQuery,
     ExperimentRecord, ExperimentRecordId, ExperimentRecordQuery, ExperimentRecordRepository,
     ExperimentRepository, ExperimentResult, ExperimentStatus, ExperimentValidationError,
-    LatencyStats, StatisticalSignificance, TrafficAllocation, Variant, VariantConfig, VariantId,
-    VariantMetrics,
+    FeedbackEvent, LatencyStats, RecordFeedback, StatisticalSignificance, TrafficAllocation,
+    Variant, VariantConfig, VariantId, VariantMetrics,
 };
 pub use plugin::{
     CredentialProviderConfig, CredentialProviderPlugin, CredentialSourceType,
@@ -127,7 +153,22 @@ pub use team::{
     validate_team_id, validate_team_name, Team, TeamId, TeamQuery, TeamRepository, TeamRole,
     TeamStatus, TeamValidationError,
 };
+pub use organization::{
+    validate_organization_id, validate_organization_name, Organization, OrganizationId,
+    OrganizationRepository, OrganizationValidationError,
+};
+pub use role::{
+    validate_role_id, validate_role_name, Permission, PermissionAction, PermissionResource, Role,
+    RoleId, RoleRepository, RoleValidationError,
+};
+pub use service_account::{
+    validate_service_account_id, validate_service_account_name, ServiceAccount,
+    ServiceAccountId, ServiceAccountRepository, ServiceAccountStatus,
+    ServiceAccountValidationError,
+};
+pub use slo::{evaluate_slo, Slo, SloId, SloReport, SloRepository, SloStatus};
 pub use webhook::{
-    DeliveryStatus, Webhook, WebhookDelivery, WebhookDeliveryId, WebhookDeliveryRepository,
-    WebhookEvent, WebhookEventType, WebhookId, WebhookRepository, WebhookStatus,
+    render_payload_template, validate_payload_template, DeliveryStatus, Webhook, WebhookDelivery,
+    WebhookDeliveryId, WebhookDeliveryRepository, WebhookEvent, WebhookEventType, WebhookFilter,
+    WebhookId, WebhookRepository, WebhookStatus,
 };
@@ -2,8 +2,11 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
+use super::shadow::ShadowConfig;
 use super::validation::{validate_model_id, ModelValidationError};
+use crate::domain::cache::CachePolicy;
 use crate::domain::storage::{StorageEntity, StorageKey};
 use crate::domain::CredentialType;
 
@@ -98,6 +101,20 @@ pub struct ModelConfig {
     /// Fallback model ID to use if this model fails
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fallback_model_id: Option<String>,
+
+    /// Provisioned throughput of the deployment; overflow goes to the
+    /// fallback model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capacity: Option<ModelCapacity>,
+
+    /// Response caching of the model's requests; the global cache
+    /// configuration applies when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_policy: Option<CachePolicy>,
+
+    /// Candidate model a share of the chat completions is mirrored to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadow: Option<ShadowConfig>,
 }
 
 impl Default for ModelConfig {
@@ -114,6 +131,9 @@ impl Default for ModelConfig {
             max_retries: None,
             retry_delay_ms: None,
             fallback_model_id: None,
+            capacity: None,
+            cache_policy: None,
+            shadow: None,
         }
     }
 }
@@ -177,6 +197,63 @@ impl ModelConfig {
         self.fallback_model_id = Some(fallback_model_id.into());
         self
     }
+
+    pub fn with_capacity(mut self, capacity: ModelCapacity) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+
+    pub fn with_cache_policy(mut self, cache_policy: CachePolicy) -> Self {
+        self.cache_policy = Some(cache_policy);
+        self
+    }
+
+    pub fn with_shadow(mut self, shadow: ShadowConfig) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+}
+
+/// Provisioned throughput of a deployment (Azure PTU, Bedrock provisioned
+/// throughput). Unset limits are not tracked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ModelCapacity {
+    /// Tokens per minute, counting prompt tokens and `max_tokens`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens_per_minute: Option<u64>,
+    /// Requests per minute
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_per_minute: Option<u64>,
+}
+
+impl ModelCapacity {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_tokens_per_minute(mut self, tokens: u64) -> Self {
+        self.tokens_per_minute = Some(tokens);
+        self
+    }
+
+    pub fn with_requests_per_minute(mut self, requests: u64) -> Self {
+        self.requests_per_minute = Some(requests);
+        self
+    }
+}
+
+/// Where a fine-tuned model comes from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ModelLineage {
+    /// Gateway fine-tuning job that produced the model
+    pub fine_tune_job_id: String,
+    /// Provider job ID
+    pub provider_job_id: String,
+    /// Provider model the job started from
+    pub base_model: String,
+    /// Dataset the model was trained on
+    pub dataset_id: String,
+    pub dataset_version: u32,
 }
 
 /// Model entity representing a configured LLM model
@@ -210,6 +287,10 @@ pub struct Model {
     /// Whether the model is enabled
     enabled: bool,
 
+    /// Fine-tuning lineage, for models registered by a fine-tuning job
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    lineage: Option<ModelLineage>,
+
     /// Creation timestamp
     created_at: DateTime<Utc>,
 
@@ -237,6 +318,7 @@ impl Model {
             config: ModelConfig::default(),
             version: 1,
             enabled: true,
+            lineage: None,
             created_at: now,
             updated_at: now,
         }
@@ -260,6 +342,12 @@ impl Model {
         self
     }
 
+    /// Builder-style method to set the fine-tuning lineage
+    pub fn with_lineage(mut self, lineage: ModelLineage) -> Self {
+        self.lineage = Some(lineage);
+        self
+    }
+
     // Getters
 
     pub fn id(&self) -> &ModelId {
@@ -298,6 +386,10 @@ impl Model {
         self.enabled
     }
 
+    pub fn lineage(&self) -> Option<&ModelLineage> {
+        self.lineage.as_ref()
+    }
+
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
     }
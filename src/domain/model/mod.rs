@@ -1,7 +1,12 @@
 //! Model domain - Model configuration and management
 
 mod entity;
+mod shadow;
 mod validation;
 
-pub use entity::{Model, ModelConfig, ModelId};
-pub use validation::{validate_model_config, validate_model_id, ModelValidationError};
+pub use entity::{Model, ModelCapacity, ModelConfig, ModelId, ModelLineage};
+pub use shadow::{judge_request, parse_judge_verdict, JudgeVerdict, ShadowConfig};
+pub use validation::{
+    check_model_config, validate_capacity, validate_model_config, validate_model_id,
+    ModelValidationError, MAX_MODEL_ID_LENGTH,
+};
@@ -0,0 +1,197 @@
+//! Shadow traffic of models
+//!
+//! A model with a shadow configuration mirrors a percentage of its chat
+//! completions to a candidate model once the caller has been answered. The
+//! candidate's answers are never returned; they are logged next to the
+//! answer served and, when a judge model is configured, scored against it,
+//! so a new model can be evaluated on real traffic before a cutover.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::validation::validate_model_id;
+use crate::domain::llm::{LlmRequest, Message, MessageRole};
+use crate::domain::FieldViolations;
+
+/// Instructions of the judge comparing a candidate answer to the one served
+const JUDGE_INSTRUCTIONS: &str = "You compare two answers to the same conversation. \
+The reference answer was served to the user; the candidate answer comes from a model being \
+evaluated as its replacement. Rate the candidate from 0 to 10: 0 when it is much worse than \
+the reference, 5 when it is as good, 10 when it is much better. Judge correctness, \
+completeness and helpfulness, not style or length. Respond only with JSON of the form \
+{\"score\": <0-10>, \"reason\": \"<one sentence>\"}.";
+
+/// Longest conversation excerpt sent to the judge, in characters
+const MAX_JUDGED_CONVERSATION_CHARS: usize = 8_000;
+
+/// Candidate model mirroring a share of a model's chat completions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ShadowConfig {
+    /// Model receiving the mirrored requests
+    pub model_id: String,
+    /// Share of the requests mirrored, from 1 to 100
+    pub percentage: u8,
+    /// Model scoring each candidate answer against the answer served
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub judge_model_id: Option<String>,
+}
+
+impl ShadowConfig {
+    pub fn new(model_id: impl Into<String>, percentage: u8) -> Self {
+        Self {
+            model_id: model_id.into(),
+            percentage,
+            judge_model_id: None,
+        }
+    }
+
+    pub fn with_judge_model_id(mut self, judge_model_id: impl Into<String>) -> Self {
+        self.judge_model_id = Some(judge_model_id.into());
+        self
+    }
+
+    /// Whether a request drawing `sample` (uniform in `[0, 1)`) is mirrored
+    pub fn samples(&self, sample: f64) -> bool {
+        sample * 100.0 < f64::from(self.percentage)
+    }
+
+    /// Record every invalid setting under `prefix`
+    pub fn check(&self, prefix: &str, violations: &mut FieldViolations) {
+        violations.check(format!("{}.model_id", prefix), validate_model_id(&self.model_id));
+
+        if !(1..=100).contains(&self.percentage) {
+            violations.push(
+                format!("{}.percentage", prefix),
+                format!("{} is not between 1 and 100", self.percentage),
+            );
+        }
+
+        if let Some(judge_model_id) = &self.judge_model_id {
+            violations.check(
+                format!("{}.judge_model_id", prefix),
+                validate_model_id(judge_model_id),
+            );
+        }
+    }
+}
+
+/// Score a judge gave a candidate answer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct JudgeVerdict {
+    /// From 0 (much worse than the answer served) to 1 (much better);
+    /// 0.5 is as good
+    pub score: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Request asking a judge to compare the candidate answer of a conversation
+/// to the reference answer served
+pub fn judge_request(conversation: &[Message], reference: &str, candidate: &str) -> LlmRequest {
+    let mut transcript = String::new();
+    for message in conversation {
+        let role = match message.role {
+            MessageRole::System => "System",
+            MessageRole::User => "User",
+            MessageRole::Assistant => "Assistant",
+        };
+        let content = message.content_text().unwrap_or("[non-text content]");
+        transcript.push_str(&format!("{}: {}\n", role, content));
+    }
+    if transcript.len() > MAX_JUDGED_CONVERSATION_CHARS {
+        // Keep the end of the conversation, which the answers reply to
+        let mut start = transcript.len() - MAX_JUDGED_CONVERSATION_CHARS;
+        while !transcript.is_char_boundary(start) {
+            start += 1;
+        }
+        transcript.replace_range(..start, "...");
+    }
+
+    LlmRequest::builder()
+        .system(JUDGE_INSTRUCTIONS)
+        .user(format!(
+            "Conversation:\n{}\nReference answer:\n{}\n\nCandidate answer:\n{}",
+            transcript, reference, candidate
+        ))
+        .temperature(0.0)
+        .max_tokens(200)
+        .build()
+}
+
+/// Parse the answer of a judge, tolerating text around its JSON object
+pub fn parse_judge_verdict(answer: &str) -> Result<JudgeVerdict, String> {
+    #[derive(Deserialize)]
+    struct RawVerdict {
+        score: f64,
+        reason: Option<String>,
+    }
+
+    let json = match (answer.find('{'), answer.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &answer[start..=end],
+        _ => answer,
+    };
+    let raw: RawVerdict = serde_json::from_str(json)
+        .map_err(|e| format!("Invalid judge answer '{}': {}", answer, e))?;
+
+    if !(0.0..=10.0).contains(&raw.score) {
+        return Err(format!("Judge score {} is not between 0 and 10", raw.score));
+    }
+
+    Ok(JudgeVerdict {
+        score: raw.score / 10.0,
+        reason: raw.reason.filter(|reason| !reason.is_empty()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_shadow_config() {
+        let mut violations = FieldViolations::new();
+        ShadowConfig::new("gpt-4o", 10)
+            .with_judge_model_id("judge")
+            .check("config.shadow", &mut violations);
+        assert!(violations.into_result().is_ok());
+
+        let mut violations = FieldViolations::new();
+        ShadowConfig::new("not a model", 0).check("config.shadow", &mut violations);
+        let error = violations.into_result().unwrap_err().to_string();
+        assert!(error.contains("config.shadow.model_id"));
+        assert!(error.contains("config.shadow.percentage"));
+    }
+
+    #[test]
+    fn test_samples() {
+        let shadow = ShadowConfig::new("gpt-4o", 25);
+
+        assert!(shadow.samples(0.0));
+        assert!(shadow.samples(0.249));
+        assert!(!shadow.samples(0.25));
+        assert!(ShadowConfig::new("gpt-4o", 100).samples(0.999));
+    }
+
+    #[test]
+    fn test_parse_judge_verdict() {
+        let verdict =
+            parse_judge_verdict("Here you go: {\"score\": 7, \"reason\": \"More complete\"}")
+                .unwrap();
+        assert_eq!(verdict.score, 0.7);
+        assert_eq!(verdict.reason.as_deref(), Some("More complete"));
+
+        assert!(parse_judge_verdict("{\"score\": 11}").is_err());
+        assert!(parse_judge_verdict("better").is_err());
+    }
+
+    #[test]
+    fn test_judge_request() {
+        let conversation = vec![Message::user("x".repeat(10_000))];
+        let request = judge_request(&conversation, "reference", "candidate");
+
+        let prompt = request.messages[1].content_text().unwrap();
+        assert!(prompt.starts_with("Conversation:\n..."));
+        assert!(prompt.len() < 8_200);
+        assert!(prompt.ends_with("Candidate answer:\ncandidate"));
+    }
+}
@@ -31,6 +31,8 @@ pub enum ModelValidationError {
     InvalidFrequencyPenalty { value: f32, min: f32, max: f32 },
     /// Max tokens is zero
     InvalidMaxTokens,
+    /// Provisioned capacity limit is zero
+    InvalidCapacity,
 }
 
 impl fmt::Display for ModelValidationError {
@@ -76,6 +78,9 @@ impl fmt::Display for ModelValidationError {
                 )
             }
             Self::InvalidMaxTokens => write!(f, "max_tokens must be greater than 0"),
+            Self::InvalidCapacity => {
+                write!(f, "Provisioned capacity limits must be greater than 0")
+            }
         }
     }
 }
@@ -175,7 +180,17 @@ pub fn validate_max_tokens(max_tokens: u32) -> Result<(), ModelValidationError>
     Ok(())
 }
 
-use super::ModelConfig;
+/// Validate provisioned capacity limits
+pub fn validate_capacity(capacity: &ModelCapacity) -> Result<(), ModelValidationError> {
+    if capacity.tokens_per_minute == Some(0) || capacity.requests_per_minute == Some(0) {
+        return Err(ModelValidationError::InvalidCapacity);
+    }
+
+    Ok(())
+}
+
+use super::{ModelCapacity, ModelConfig};
+use crate::domain::FieldViolations;
 
 /// Validate a complete ModelConfig
 pub fn validate_model_config(config: &ModelConfig) -> Result<(), ModelValidationError> {
@@ -199,9 +214,50 @@ pub fn validate_model_config(config: &ModelConfig) -> Result<(), ModelValidation
         validate_max_tokens(max_tokens)?;
     }
 
+    if let Some(capacity) = &config.capacity {
+        validate_capacity(capacity)?;
+    }
+
     Ok(())
 }
 
+/// Record every invalid setting of a ModelConfig, under `prefix`
+pub fn check_model_config(config: &ModelConfig, prefix: &str, violations: &mut FieldViolations) {
+    let field = |name: &str| format!("{}.{}", prefix, name);
+
+    if let Some(temp) = config.temperature {
+        violations.check(field("temperature"), validate_temperature(temp));
+    }
+
+    if let Some(top_p) = config.top_p {
+        violations.check(field("top_p"), validate_top_p(top_p));
+    }
+
+    if let Some(penalty) = config.presence_penalty {
+        violations.check(field("presence_penalty"), validate_presence_penalty(penalty));
+    }
+
+    if let Some(penalty) = config.frequency_penalty {
+        violations.check(field("frequency_penalty"), validate_frequency_penalty(penalty));
+    }
+
+    if let Some(max_tokens) = config.max_tokens {
+        violations.check(field("max_tokens"), validate_max_tokens(max_tokens));
+    }
+
+    if let Some(capacity) = &config.capacity {
+        violations.check(field("capacity"), validate_capacity(capacity));
+    }
+
+    if let Some(cache_policy) = &config.cache_policy {
+        cache_policy.check(&field("cache_policy"), violations);
+    }
+
+    if let Some(shadow) = &config.shadow {
+        shadow.check(&field("shadow"), violations);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -306,6 +362,20 @@ mod tests {
         assert!(validate_max_tokens(0).is_err());
     }
 
+    #[test]
+    fn test_capacity_validation() {
+        let capacity = ModelCapacity::new().with_tokens_per_minute(50_000);
+        assert!(validate_capacity(&capacity).is_ok());
+        assert!(validate_capacity(&capacity.with_requests_per_minute(0)).is_err());
+
+        let config =
+            ModelConfig::new().with_capacity(ModelCapacity::new().with_tokens_per_minute(0));
+        assert!(matches!(
+            validate_model_config(&config),
+            Err(ModelValidationError::InvalidCapacity)
+        ));
+    }
+
     #[test]
     fn test_model_config_validation() {
         let valid_config = ModelConfig::new()
@@ -0,0 +1,219 @@
+//! CIDR network blocks
+
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors that can occur when parsing a CIDR block
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum CidrError {
+    #[error("Invalid IP address in '{0}'")]
+    InvalidAddress(String),
+
+    #[error("Invalid prefix length in '{0}'")]
+    InvalidPrefix(String),
+}
+
+/// An IPv4 or IPv6 network in CIDR notation (e.g. `10.0.0.0/8`, `2001:db8::/32`).
+///
+/// A bare address is treated as a single-host network (`/32` or `/128`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct IpNetwork {
+    address: IpAddr,
+    prefix: u8,
+}
+
+impl IpNetwork {
+    /// Create a network from an address and prefix length. Host bits are cleared.
+    pub fn new(address: IpAddr, prefix: u8) -> Result<Self, CidrError> {
+        let max = max_prefix(&address);
+
+        if prefix > max {
+            return Err(CidrError::InvalidPrefix(format!("{}/{}", address, prefix)));
+        }
+
+        Ok(Self {
+            address: mask(address, prefix),
+            prefix,
+        })
+    }
+
+    pub fn address(&self) -> IpAddr {
+        self.address
+    }
+
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    /// Check if the network contains an address. IPv4-mapped IPv6 addresses
+    /// are matched against IPv4 networks.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map(IpAddr::V4).unwrap_or(ip),
+            IpAddr::V4(_) => ip,
+        };
+
+        match (self.address, ip) {
+            (IpAddr::V4(_), IpAddr::V4(_)) | (IpAddr::V6(_), IpAddr::V6(_)) => {
+                mask(ip, self.prefix) == self.address
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Check an optional client IP against an allowlist. An empty allowlist allows
+/// everything; a non-empty allowlist rejects unknown client IPs.
+pub fn ip_allowed(allowlist: &[IpNetwork], ip: Option<IpAddr>) -> bool {
+    if allowlist.is_empty() {
+        return true;
+    }
+
+    ip.is_some_and(|ip| allowlist.iter().any(|network| network.contains(ip)))
+}
+
+fn max_prefix(address: &IpAddr) -> u8 {
+    match address {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+fn mask(address: IpAddr, prefix: u8) -> IpAddr {
+    match address {
+        IpAddr::V4(v4) => {
+            let bits = u32::from(v4);
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            IpAddr::V4((bits & mask).into())
+        }
+        IpAddr::V6(v6) => {
+            let bits = u128::from(v6);
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            IpAddr::V6((bits & mask).into())
+        }
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = CidrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (addr, prefix) = match s.split_once('/') {
+            Some((addr, prefix)) => (addr, Some(prefix)),
+            None => (s, None),
+        };
+
+        let address: IpAddr = addr
+            .parse()
+            .map_err(|_| CidrError::InvalidAddress(s.to_string()))?;
+
+        let prefix = match prefix {
+            Some(p) => p
+                .parse::<u8>()
+                .map_err(|_| CidrError::InvalidPrefix(s.to_string()))?,
+            None => max_prefix(&address),
+        };
+
+        Self::new(address, prefix).map_err(|_| CidrError::InvalidPrefix(s.to_string()))
+    }
+}
+
+impl TryFrom<String> for IpNetwork {
+    type Error = CidrError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<IpNetwork> for String {
+    fn from(network: IpNetwork) -> Self {
+        network.to_string()
+    }
+}
+
+impl fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_cidr() {
+        let network: IpNetwork = "10.1.2.3/8".parse().unwrap();
+        assert_eq!(network.to_string(), "10.0.0.0/8");
+
+        let host: IpNetwork = "192.168.1.10".parse().unwrap();
+        assert_eq!(host.to_string(), "192.168.1.10/32");
+
+        let v6: IpNetwork = "2001:db8::1/32".parse().unwrap();
+        assert_eq!(v6.to_string(), "2001:db8::/32");
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(matches!(
+            "not-an-ip".parse::<IpNetwork>(),
+            Err(CidrError::InvalidAddress(_))
+        ));
+        assert!(matches!(
+            "10.0.0.0/33".parse::<IpNetwork>(),
+            Err(CidrError::InvalidPrefix(_))
+        ));
+        assert!(matches!(
+            "10.0.0.0/x".parse::<IpNetwork>(),
+            Err(CidrError::InvalidPrefix(_))
+        ));
+    }
+
+    #[test]
+    fn test_contains() {
+        let network: IpNetwork = "10.0.0.0/8".parse().unwrap();
+        assert!(network.contains(ip("10.255.0.1")));
+        assert!(!network.contains(ip("11.0.0.1")));
+        assert!(!network.contains(ip("2001:db8::1")));
+        assert!(network.contains(ip("::ffff:10.0.0.1")));
+
+        let all: IpNetwork = "0.0.0.0/0".parse().unwrap();
+        assert!(all.contains(ip("203.0.113.7")));
+
+        let v6: IpNetwork = "2001:db8::/32".parse().unwrap();
+        assert!(v6.contains(ip("2001:db8:abcd::1")));
+        assert!(!v6.contains(ip("2001:db9::1")));
+    }
+
+    #[test]
+    fn test_ip_allowed() {
+        let allowlist: Vec<IpNetwork> = vec!["10.0.0.0/8".parse().unwrap()];
+
+        assert!(ip_allowed(&[], None));
+        assert!(ip_allowed(&allowlist, Some(ip("10.0.0.1"))));
+        assert!(!ip_allowed(&allowlist, Some(ip("192.168.0.1"))));
+        assert!(!ip_allowed(&allowlist, None));
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let network: IpNetwork = "172.16.0.0/12".parse().unwrap();
+        let json = serde_json::to_string(&network).unwrap();
+        assert_eq!(json, "\"172.16.0.0/12\"");
+
+        let parsed: IpNetwork = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, network);
+        assert!(serde_json::from_str::<IpNetwork>("\"bogus\"").is_err());
+    }
+}
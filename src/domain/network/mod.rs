@@ -0,0 +1,8 @@
+//! Network domain
+//!
+//! This module provides IP network (CIDR) types used for client IP allowlists
+//! and trusted proxy configuration.
+
+mod cidr;
+
+pub use cidr::{ip_allowed, CidrError, IpNetwork};
@@ -0,0 +1,18 @@
+//! Notification channel trait
+
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+
+use super::Notification;
+use crate::domain::DomainError;
+
+/// Destination notifications are delivered to (e-mail, chat, paging)
+#[async_trait]
+pub trait NotificationChannel: Send + Sync + Debug {
+    /// Channel name used in logs
+    fn name(&self) -> &str;
+
+    /// Deliver a notification
+    async fn send(&self, notification: &Notification) -> Result<(), DomainError>;
+}
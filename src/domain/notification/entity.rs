@@ -0,0 +1,335 @@
+//! Notification domain entities
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::usage::{AnomalyMetric, ReconciliationReport, UsageAnomaly};
+use crate::domain::DomainError;
+
+/// Kinds of events operators are notified about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    /// Budget usage crossed one of its alert thresholds
+    BudgetAlert,
+    /// A provider keeps failing requests
+    ProviderOutage,
+    /// A provider in outage served a request again
+    ProviderRecovered,
+    /// Spend or request volume of an API key or team spiked over its baseline
+    UsageAnomaly,
+    /// Gateway-recorded usage of a day disagrees with the provider's usage
+    UsageDiscrepancy,
+}
+
+impl NotificationEvent {
+    /// Returns the event as a string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BudgetAlert => "budget_alert",
+            Self::ProviderOutage => "provider_outage",
+            Self::ProviderRecovered => "provider_recovered",
+            Self::UsageAnomaly => "usage_anomaly",
+            Self::UsageDiscrepancy => "usage_discrepancy",
+        }
+    }
+}
+
+impl fmt::Display for NotificationEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for NotificationEvent {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "budget_alert" => Ok(Self::BudgetAlert),
+            "provider_outage" => Ok(Self::ProviderOutage),
+            "provider_recovered" => Ok(Self::ProviderRecovered),
+            "usage_anomaly" => Ok(Self::UsageAnomaly),
+            "usage_discrepancy" => Ok(Self::UsageDiscrepancy),
+            other => Err(DomainError::validation(format!(
+                "Unknown notification event '{}', expected 'budget_alert', 'provider_outage', 'provider_recovered', 'usage_anomaly' or 'usage_discrepancy'",
+                other
+            ))),
+        }
+    }
+}
+
+/// How urgently a notification needs attention
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl NotificationSeverity {
+    /// Returns the severity as a string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+/// Message delivered to notification channels
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Notification {
+    pub event: NotificationEvent,
+    pub severity: NotificationSeverity,
+    /// One-line summary
+    pub title: String,
+    /// Human readable description
+    pub message: String,
+    /// Identifies the condition, so an outage and its recovery share a key
+    pub dedup_key: String,
+    /// Threshold that fired, for budget alerts
+    pub budget_percent: Option<u8>,
+    /// Structured fields shown alongside the message
+    pub details: BTreeMap<String, String>,
+}
+
+impl Notification {
+    /// Budget usage crossed an alert threshold
+    pub fn budget_alert(
+        budget_id: &str,
+        budget_name: &str,
+        threshold_percent: u8,
+        usage_micros: i64,
+        limit_micros: i64,
+    ) -> Self {
+        let usage_percent = if limit_micros > 0 {
+            usage_micros as f64 / limit_micros as f64 * 100.0
+        } else {
+            100.0
+        };
+        let severity = if threshold_percent >= 100 {
+            NotificationSeverity::Critical
+        } else {
+            NotificationSeverity::Warning
+        };
+
+        Self {
+            event: NotificationEvent::BudgetAlert,
+            severity,
+            title: format!(
+                "Budget '{}' reached {}% of its limit",
+                budget_name, threshold_percent
+            ),
+            message: format!(
+                "Budget '{}' has used ${:.2} of ${:.2} ({:.1}%).",
+                budget_name,
+                micros_to_usd(usage_micros),
+                micros_to_usd(limit_micros),
+                usage_percent
+            ),
+            dedup_key: format!("budget-alert:{}:{}", budget_id, threshold_percent),
+            budget_percent: Some(threshold_percent),
+            details: BTreeMap::from([
+                ("budget_id".to_string(), budget_id.to_string()),
+                ("budget_name".to_string(), budget_name.to_string()),
+                (
+                    "threshold_percent".to_string(),
+                    threshold_percent.to_string(),
+                ),
+                (
+                    "usage_usd".to_string(),
+                    format!("{:.2}", micros_to_usd(usage_micros)),
+                ),
+                (
+                    "limit_usd".to_string(),
+                    format!("{:.2}", micros_to_usd(limit_micros)),
+                ),
+            ]),
+        }
+    }
+
+    /// A provider failed several requests in a row
+    pub fn provider_outage(provider: &str, consecutive_failures: u32, last_error: &str) -> Self {
+        Self {
+            event: NotificationEvent::ProviderOutage,
+            severity: NotificationSeverity::Critical,
+            title: format!("Provider '{}' is failing requests", provider),
+            message: format!(
+                "{} consecutive requests to '{}' failed. Last error: {}",
+                consecutive_failures, provider, last_error
+            ),
+            dedup_key: Self::outage_key(provider),
+            budget_percent: None,
+            details: BTreeMap::from([
+                ("provider".to_string(), provider.to_string()),
+                (
+                    "consecutive_failures".to_string(),
+                    consecutive_failures.to_string(),
+                ),
+                ("last_error".to_string(), last_error.to_string()),
+            ]),
+        }
+    }
+
+    /// A provider in outage served a request again
+    pub fn provider_recovered(provider: &str, outage_secs: u64) -> Self {
+        Self {
+            event: NotificationEvent::ProviderRecovered,
+            severity: NotificationSeverity::Info,
+            title: format!("Provider '{}' recovered", provider),
+            message: format!(
+                "Requests to '{}' succeed again after {}s of failures.",
+                provider, outage_secs
+            ),
+            dedup_key: Self::outage_key(provider),
+            budget_percent: None,
+            details: BTreeMap::from([
+                ("provider".to_string(), provider.to_string()),
+                ("outage_secs".to_string(), outage_secs.to_string()),
+            ]),
+        }
+    }
+
+    /// Usage of an API key or team spiked over its baseline
+    pub fn usage_anomaly(anomaly: &UsageAnomaly, window_secs: u64) -> Self {
+        let (observed, baseline) = match anomaly.metric {
+            AnomalyMetric::Requests => (
+                format!("{:.0} requests", anomaly.observed),
+                format!("{:.1} requests", anomaly.baseline),
+            ),
+            AnomalyMetric::Cost => (
+                format!("${:.2}", anomaly.observed / 1_000_000.0),
+                format!("${:.2}", anomaly.baseline / 1_000_000.0),
+            ),
+        };
+        let ratio = anomaly
+            .ratio()
+            .map(|r| format!("{:.1}x its baseline", r))
+            .unwrap_or_else(|| "no baseline usage".to_string());
+
+        Self {
+            event: NotificationEvent::UsageAnomaly,
+            severity: NotificationSeverity::Warning,
+            title: format!(
+                "Unusual {} for {} '{}'",
+                anomaly.metric, anomaly.scope, anomaly.group
+            ),
+            message: format!(
+                "{} '{}' used {} in the last {} minutes, expected about {} ({}).",
+                anomaly.scope,
+                anomaly.group,
+                observed,
+                window_secs / 60,
+                baseline,
+                ratio
+            ),
+            dedup_key: format!(
+                "usage-anomaly:{}:{}:{}",
+                anomaly.scope, anomaly.group, anomaly.metric
+            ),
+            budget_percent: None,
+            details: BTreeMap::from([
+                ("scope".to_string(), anomaly.scope.clone()),
+                ("group".to_string(), anomaly.group.clone()),
+                ("metric".to_string(), anomaly.metric.to_string()),
+                ("observed".to_string(), observed),
+                ("baseline".to_string(), baseline),
+                ("window_secs".to_string(), window_secs.to_string()),
+            ]),
+        }
+    }
+
+    /// Gateway usage of a day disagrees with the usage reported by the provider
+    pub fn usage_discrepancy(report: &ReconciliationReport) -> Self {
+        let discrepancies = report.discrepancies();
+        let models: Vec<String> = discrepancies
+            .iter()
+            .map(|line| {
+                format!(
+                    "{} (gateway {} tokens, provider {} tokens)",
+                    line.model, line.gateway_tokens, line.provider_tokens
+                )
+            })
+            .collect();
+
+        Self {
+            event: NotificationEvent::UsageDiscrepancy,
+            severity: NotificationSeverity::Warning,
+            title: format!(
+                "Usage of '{}' on {} does not match the provider",
+                report.provider, report.date
+            ),
+            message: format!(
+                "{} model(s) differ by more than {}%: {}.",
+                discrepancies.len(),
+                report.tolerance_percent,
+                models.join(", ")
+            ),
+            dedup_key: format!("usage-discrepancy:{}:{}", report.provider, report.date),
+            budget_percent: None,
+            details: BTreeMap::from([
+                ("provider".to_string(), report.provider.clone()),
+                ("date".to_string(), report.date.to_string()),
+                ("discrepancies".to_string(), discrepancies.len().to_string()),
+            ]),
+        }
+    }
+
+    /// Whether the notification resolves an earlier one with the same dedup key
+    pub fn is_resolution(&self) -> bool {
+        self.event == NotificationEvent::ProviderRecovered
+    }
+
+    fn outage_key(provider: &str) -> String {
+        format!("provider-outage:{}", provider)
+    }
+}
+
+fn micros_to_usd(micros: i64) -> f64 {
+    micros as f64 / 1_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_alert_notification() {
+        let notification =
+            Notification::budget_alert("b-1", "Research", 80, 85_000_000, 100_000_000);
+
+        assert_eq!(notification.event, NotificationEvent::BudgetAlert);
+        assert_eq!(notification.severity, NotificationSeverity::Warning);
+        assert_eq!(notification.budget_percent, Some(80));
+        assert_eq!(
+            notification.title,
+            "Budget 'Research' reached 80% of its limit"
+        );
+        assert!(notification.message.contains("$85.00 of $100.00 (85.0%)"));
+        assert_eq!(notification.details["limit_usd"], "100.00");
+
+        let exceeded = Notification::budget_alert("b-1", "Research", 100, 100_000_000, 100_000_000);
+        assert_eq!(exceeded.severity, NotificationSeverity::Critical);
+    }
+
+    #[test]
+    fn test_outage_and_recovery_share_dedup_key() {
+        let outage = Notification::provider_outage("openai", 5, "503 Service Unavailable");
+        let recovered = Notification::provider_recovered("openai", 120);
+
+        assert_eq!(outage.dedup_key, recovered.dedup_key);
+        assert!(!outage.is_resolution());
+        assert!(recovered.is_resolution());
+        assert_eq!(
+            "provider_outage".parse::<NotificationEvent>().unwrap(),
+            NotificationEvent::ProviderOutage
+        );
+        assert!("outage".parse::<NotificationEvent>().is_err());
+    }
+}
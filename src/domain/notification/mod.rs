@@ -0,0 +1,7 @@
+//! Notification domain module for alerting operators through external channels
+
+mod channel;
+mod entity;
+
+pub use channel::*;
+pub use entity::*;
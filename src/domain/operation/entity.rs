@@ -7,8 +7,10 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use utoipa::ToSchema;
 
 use super::error::OperationError;
+use super::progress::OperationProgress;
 use crate::domain::storage::{StorageEntity, StorageKey};
 
 /// Regex pattern for valid operation IDs: op-{uuid}
@@ -19,7 +21,7 @@ static ID_PATTERN: Lazy<Regex> =
 pub const MAX_ID_LENGTH: usize = 39; // "op-" + 36 char UUID
 
 /// Validated operation identifier
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 #[serde(try_from = "String", into = "String")]
 pub struct OperationId(String);
 
@@ -107,7 +109,7 @@ pub fn validate_operation_id(id: &str) -> Result<(), OperationError> {
 }
 
 /// Status of an async operation
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum OperationStatus {
     /// Operation is queued but not yet started
@@ -169,7 +171,7 @@ impl fmt::Display for OperationStatus {
 }
 
 /// Type of async operation
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum OperationType {
     /// Chat completion request
@@ -177,6 +179,12 @@ pub enum OperationType {
 
     /// Workflow execution
     WorkflowExecution,
+
+    /// Run of every case in a test suite
+    TestSuiteRun,
+
+    /// Batch ingestion of documents into a knowledge base
+    KnowledgeBaseIngestion,
 }
 
 impl fmt::Display for OperationType {
@@ -184,12 +192,14 @@ impl fmt::Display for OperationType {
         match self {
             Self::ChatCompletion => write!(f, "chat_completion"),
             Self::WorkflowExecution => write!(f, "workflow_execution"),
+            Self::TestSuiteRun => write!(f, "test_suite_run"),
+            Self::KnowledgeBaseIngestion => write!(f, "knowledge_base_ingestion"),
         }
     }
 }
 
 /// An async operation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Operation {
     /// Unique operation identifier
     id: OperationId,
@@ -211,6 +221,15 @@ pub struct Operation {
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<String>,
 
+    /// Progress reported while running (e.g. per-document ingestion stages)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    progress: Option<Value>,
+
+    /// Units of work done and the estimated time left, for operations
+    /// doing countable work
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    completion: Option<OperationProgress>,
+
     /// Additional metadata (model_id, workflow_id, etc.)
     metadata: Value,
 
@@ -224,6 +243,19 @@ pub struct Operation {
     /// When the operation completed/failed/cancelled
     #[serde(skip_serializing_if = "Option::is_none")]
     completed_at: Option<DateTime<Utc>>,
+
+    /// Replica running the operation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    owner: Option<String>,
+
+    /// Last sign of life from the owner while running; the operation is
+    /// orphaned once it is older than the lease
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    heartbeat_at: Option<DateTime<Utc>>,
+
+    /// Times the operation was requeued after its owner stopped
+    #[serde(default)]
+    attempts: u32,
 }
 
 impl Operation {
@@ -236,10 +268,15 @@ impl Operation {
             input,
             result: None,
             error: None,
+            progress: None,
+            completion: None,
             metadata,
             created_at: Utc::now(),
             started_at: None,
             completed_at: None,
+            owner: None,
+            heartbeat_at: None,
+            attempts: 0,
         }
     }
 
@@ -252,10 +289,15 @@ impl Operation {
             input,
             result: None,
             error: None,
+            progress: None,
+            completion: None,
             metadata: Value::Null,
             created_at: Utc::now(),
             started_at: None,
             completed_at: None,
+            owner: None,
+            heartbeat_at: None,
+            attempts: 0,
         }
     }
 
@@ -285,6 +327,14 @@ impl Operation {
         self.error.as_deref()
     }
 
+    pub fn progress(&self) -> Option<&Value> {
+        self.progress.as_ref()
+    }
+
+    pub fn completion(&self) -> Option<&OperationProgress> {
+        self.completion.as_ref()
+    }
+
     pub fn metadata(&self) -> &Value {
         &self.metadata
     }
@@ -301,10 +351,32 @@ impl Operation {
         self.completed_at
     }
 
+    pub fn owner(&self) -> Option<&str> {
+        self.owner.as_deref()
+    }
+
+    pub fn heartbeat_at(&self) -> Option<DateTime<Utc>> {
+        self.heartbeat_at
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
     pub fn is_terminal(&self) -> bool {
         self.status.is_terminal()
     }
 
+    /// Whether the operation runs but its owner sent no heartbeat within
+    /// `lease` of `now`
+    pub fn is_orphaned(&self, lease: chrono::Duration, now: DateTime<Utc>) -> bool {
+        self.status == OperationStatus::Running
+            && self
+                .heartbeat_at
+                .or(self.started_at)
+                .is_some_and(|alive| now - alive > lease)
+    }
+
     /// Mark operation as running
     pub fn mark_running(&mut self) -> Result<(), OperationError> {
         if !self.status.can_transition_to(OperationStatus::Running) {
@@ -316,6 +388,79 @@ impl Operation {
         }
         self.status = OperationStatus::Running;
         self.started_at = Some(Utc::now());
+        self.heartbeat_at = self.started_at;
+        Ok(())
+    }
+
+    /// Mark operation as running on the replica `owner`
+    pub fn mark_running_on(&mut self, owner: impl Into<String>) -> Result<(), OperationError> {
+        self.mark_running()?;
+        self.owner = Some(owner.into());
+        Ok(())
+    }
+
+    /// Record that the owner of a running operation is still alive
+    pub fn heartbeat(&mut self) -> Result<(), OperationError> {
+        if self.status != OperationStatus::Running {
+            return Err(OperationError::validation(format!(
+                "Operation in '{}' state has no heartbeat",
+                self.status
+            )));
+        }
+        self.heartbeat_at = Some(Utc::now());
+        Ok(())
+    }
+
+    /// Put a running operation whose owner stopped back to pending, so
+    /// another replica runs it from the start
+    pub fn requeue(&mut self) -> Result<(), OperationError> {
+        if self.status != OperationStatus::Running {
+            return Err(OperationError::validation(format!(
+                "Operation in '{}' state cannot be requeued",
+                self.status
+            )));
+        }
+        self.status = OperationStatus::Pending;
+        self.attempts += 1;
+        self.started_at = None;
+        self.owner = None;
+        self.heartbeat_at = None;
+        self.progress = None;
+        self.completion = None;
+        Ok(())
+    }
+
+    /// Replace the progress of a running operation
+    pub fn set_progress(&mut self, progress: Value) -> Result<(), OperationError> {
+        if self.status != OperationStatus::Running {
+            return Err(OperationError::validation(format!(
+                "Operation in '{}' state cannot report progress",
+                self.status
+            )));
+        }
+        self.progress = Some(progress);
+        Ok(())
+    }
+
+    /// Record the units of work done by a running operation, estimating
+    /// the time left from the rate since it started
+    pub fn set_completion(
+        &mut self,
+        completed: u64,
+        total: u64,
+        unit: &str,
+    ) -> Result<(), OperationError> {
+        if self.status != OperationStatus::Running {
+            return Err(OperationError::validation(format!(
+                "Operation in '{}' state cannot report progress",
+                self.status
+            )));
+        }
+        let mut completion = OperationProgress::new(completed, total, unit);
+        if let Some(started_at) = self.started_at {
+            completion = completion.with_estimate(started_at);
+        }
+        self.completion = Some(completion);
         Ok(())
     }
 
@@ -330,6 +475,9 @@ impl Operation {
         }
         self.status = OperationStatus::Completed;
         self.result = Some(result);
+        if let Some(completion) = &mut self.completion {
+            completion.finish();
+        }
         self.completed_at = Some(Utc::now());
         Ok(())
     }
@@ -463,6 +611,45 @@ mod tests {
         assert_eq!(op.result(), Some(&json!({"answer": "42"})));
     }
 
+    #[test]
+    fn test_operation_completion() {
+        let mut op = Operation::new(OperationType::WorkflowExecution, json!({}), json!({}));
+        assert!(op.set_completion(1, 4, "steps").is_err());
+
+        op.mark_running().unwrap();
+        op.set_completion(1, 4, "steps").unwrap();
+        let completion = op.completion().unwrap();
+        assert_eq!(completion.percent, 25.0);
+        assert!(completion.eta_seconds.is_some());
+
+        op.mark_completed(json!({})).unwrap();
+        assert_eq!(op.completion().unwrap().completed, 4);
+    }
+
+    #[test]
+    fn test_operation_orphaned_and_requeued() {
+        let lease = chrono::Duration::seconds(60);
+        let mut op = Operation::new(OperationType::WorkflowExecution, json!({}), json!({}));
+        assert!(!op.is_orphaned(lease, Utc::now()));
+        assert!(op.requeue().is_err());
+
+        op.mark_running_on("replica-a").unwrap();
+        assert_eq!(op.owner(), Some("replica-a"));
+        assert!(!op.is_orphaned(lease, Utc::now()));
+        assert!(op.is_orphaned(lease, Utc::now() + chrono::Duration::seconds(61)));
+
+        op.heartbeat().unwrap();
+        op.requeue().unwrap();
+        assert_eq!(op.status(), OperationStatus::Pending);
+        assert_eq!(op.attempts(), 1);
+        assert!(op.owner().is_none());
+        assert!(op.started_at().is_none());
+
+        // Requeued operations run again like new ones
+        op.mark_running_on("replica-b").unwrap();
+        assert_eq!(op.owner(), Some("replica-b"));
+    }
+
     #[test]
     fn test_operation_failure() {
         let mut op = Operation::new(
@@ -2,10 +2,12 @@
 
 mod entity;
 mod error;
+mod progress;
 pub mod repository;
 
 pub use entity::{
     validate_operation_id, Operation, OperationId, OperationStatus, OperationType, MAX_ID_LENGTH,
 };
 pub use error::OperationError;
+pub use progress::OperationProgress;
 pub use repository::OperationRepository;
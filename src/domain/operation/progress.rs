@@ -0,0 +1,102 @@
+//! Completion of running operations
+//!
+//! Operations doing countable work (workflow steps, ingested documents)
+//! report how many units are done out of how many; the time left is
+//! extrapolated from the rate since the operation started.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Units of work done by an operation, with the estimated time left
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct OperationProgress {
+    pub completed: u64,
+    pub total: u64,
+    /// What the units count, e.g. `steps` or `documents`
+    pub unit: String,
+    /// Completed share of the total, from 0 to 100
+    pub percent: f64,
+    /// Seconds left at the rate so far, once a unit is completed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eta_seconds: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_completion_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OperationProgress {
+    /// Progress of `completed` out of `total` units, capped at the total
+    pub fn new(completed: u64, total: u64, unit: impl Into<String>) -> Self {
+        let completed = completed.min(total);
+        let percent = if total == 0 {
+            0.0
+        } else {
+            (completed as f64 / total as f64 * 1000.0).round() / 10.0
+        };
+
+        Self {
+            completed,
+            total,
+            unit: unit.into(),
+            percent,
+            eta_seconds: None,
+            estimated_completion_at: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Estimate the time left from the rate since `started_at`
+    pub fn with_estimate(mut self, started_at: DateTime<Utc>) -> Self {
+        if self.completed == 0 {
+            return self;
+        }
+
+        let elapsed = (self.updated_at - started_at).num_milliseconds().max(0) as u64;
+        let remaining = self.total - self.completed;
+        let eta_ms = elapsed * remaining / self.completed;
+
+        self.eta_seconds = Some(eta_ms.div_ceil(1000));
+        self.estimated_completion_at =
+            Some(self.updated_at + Duration::milliseconds(eta_ms as i64));
+        self
+    }
+
+    /// Mark every unit as completed
+    pub fn finish(&mut self) {
+        self.completed = self.total;
+        self.percent = 100.0;
+        self.updated_at = Utc::now();
+        self.eta_seconds = Some(0);
+        self.estimated_completion_at = Some(self.updated_at);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_and_estimate() {
+        let progress = OperationProgress::new(1, 3, "steps");
+        assert_eq!(progress.percent, 33.3);
+
+        let started_at = progress.updated_at - Duration::seconds(10);
+        let progress = progress.with_estimate(started_at);
+        assert_eq!(progress.eta_seconds, Some(20));
+        assert_eq!(
+            progress.estimated_completion_at,
+            Some(progress.updated_at + Duration::seconds(20))
+        );
+
+        // Nothing completed yet, no rate to extrapolate
+        let progress = OperationProgress::new(0, 3, "steps").with_estimate(started_at);
+        assert_eq!(progress.eta_seconds, None);
+
+        let mut progress = OperationProgress::new(5, 4, "documents");
+        assert_eq!(progress.completed, 4);
+        assert_eq!(progress.percent, 100.0);
+        progress.finish();
+        assert_eq!(progress.eta_seconds, Some(0));
+    }
+}
@@ -0,0 +1,230 @@
+//! Organization entity
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::validation::{
+    validate_organization_id, validate_organization_name, OrganizationValidationError,
+};
+use crate::domain::storage::{StorageEntity, StorageKey};
+use crate::domain::user::UserId;
+
+/// Organization identifier - alphanumeric + hyphens, max 50 characters
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct OrganizationId(String);
+
+impl OrganizationId {
+    /// Create a new OrganizationId after validation
+    pub fn new(id: impl Into<String>) -> Result<Self, OrganizationValidationError> {
+        let id = id.into();
+        validate_organization_id(&id)?;
+        Ok(Self(id))
+    }
+
+    /// Get the inner string value
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for OrganizationId {
+    type Error = OrganizationValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<OrganizationId> for String {
+    fn from(id: OrganizationId) -> Self {
+        id.0
+    }
+}
+
+impl std::fmt::Display for OrganizationId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StorageKey for OrganizationId {
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Organization entity - groups the teams of a business unit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Organization {
+    /// Unique identifier
+    id: OrganizationId,
+    /// Display name
+    name: String,
+    /// Description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    /// Users allowed to manage the organization and its teams
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    admin_user_ids: Vec<UserId>,
+    /// Creation timestamp
+    created_at: DateTime<Utc>,
+    /// Last update timestamp
+    updated_at: DateTime<Utc>,
+}
+
+impl Organization {
+    /// Create a new organization
+    pub fn new(
+        id: OrganizationId,
+        name: impl Into<String>,
+    ) -> Result<Self, OrganizationValidationError> {
+        let name = name.into();
+        validate_organization_name(&name)?;
+        let now = Utc::now();
+
+        Ok(Self {
+            id,
+            name,
+            description: None,
+            admin_user_ids: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Set description (builder pattern)
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the organization admins (builder pattern)
+    pub fn with_admins(mut self, admin_user_ids: Vec<UserId>) -> Self {
+        self.admin_user_ids = admin_user_ids;
+        self
+    }
+
+    // Getters
+
+    pub fn id(&self) -> &OrganizationId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn admin_user_ids(&self) -> &[UserId] {
+        &self.admin_user_ids
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    /// Check if a user is an admin of this organization
+    pub fn is_admin(&self, user_id: &UserId) -> bool {
+        self.admin_user_ids.contains(user_id)
+    }
+
+    // Mutators
+
+    /// Update the name
+    pub fn set_name(&mut self, name: impl Into<String>) -> Result<(), OrganizationValidationError> {
+        let name = name.into();
+        validate_organization_name(&name)?;
+        self.name = name;
+        self.touch();
+        Ok(())
+    }
+
+    /// Update the description
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.description = description;
+        self.touch();
+    }
+
+    /// Replace the organization admins
+    pub fn set_admins(&mut self, admin_user_ids: Vec<UserId>) {
+        self.admin_user_ids = admin_user_ids;
+        self.touch();
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+    }
+}
+
+impl StorageEntity for Organization {
+    type Key = OrganizationId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_organization_id() {
+        let id = OrganizationId::new("acme").unwrap();
+        assert_eq!(id.as_str(), "acme");
+
+        assert!(OrganizationId::new("").is_err());
+        assert!(OrganizationId::new("acme_corp").is_err());
+    }
+
+    #[test]
+    fn test_organization_creation() {
+        let org = Organization::new(OrganizationId::new("acme").unwrap(), "Acme")
+            .unwrap()
+            .with_description("Acme business units");
+
+        assert_eq!(org.name(), "Acme");
+        assert_eq!(org.description(), Some("Acme business units"));
+        assert!(org.admin_user_ids().is_empty());
+
+        assert!(Organization::new(OrganizationId::new("acme").unwrap(), "").is_err());
+    }
+
+    #[test]
+    fn test_organization_admins() {
+        let alice = UserId::new("alice").unwrap();
+        let bob = UserId::new("bob").unwrap();
+
+        let mut org = Organization::new(OrganizationId::new("acme").unwrap(), "Acme")
+            .unwrap()
+            .with_admins(vec![alice.clone()]);
+
+        assert!(org.is_admin(&alice));
+        assert!(!org.is_admin(&bob));
+
+        org.set_admins(vec![bob.clone()]);
+        assert!(!org.is_admin(&alice));
+        assert!(org.is_admin(&bob));
+    }
+
+    #[test]
+    fn test_organization_serde() {
+        let org = Organization::new(OrganizationId::new("acme").unwrap(), "Acme")
+            .unwrap()
+            .with_admins(vec![UserId::new("alice").unwrap()]);
+
+        let json = serde_json::to_string(&org).unwrap();
+        let parsed: Organization = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.id(), org.id());
+        assert_eq!(parsed.admin_user_ids(), org.admin_user_ids());
+    }
+}
@@ -0,0 +1,15 @@
+//! Organization domain module
+//!
+//! Organizations group teams into business units. Budgets and stored
+//! credentials can be shared by all teams of an organization, and
+//! organization admins manage its teams without a global admin role.
+
+mod entity;
+mod repository;
+mod validation;
+
+pub use entity::{Organization, OrganizationId};
+pub use repository::OrganizationRepository;
+pub use validation::{
+    validate_organization_id, validate_organization_name, OrganizationValidationError,
+};
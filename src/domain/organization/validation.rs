@@ -0,0 +1,113 @@
+//! Organization validation
+
+use thiserror::Error;
+
+/// Errors that can occur during organization validation
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum OrganizationValidationError {
+    #[error("Organization ID cannot be empty")]
+    EmptyId,
+
+    #[error("Organization ID cannot exceed {0} characters")]
+    IdTooLong(usize),
+
+    #[error("Organization ID can only contain alphanumeric characters and hyphens")]
+    InvalidIdCharacters,
+
+    #[error("Organization ID cannot start or end with a hyphen")]
+    InvalidIdFormat,
+
+    #[error("Organization name cannot be empty")]
+    EmptyName,
+
+    #[error("Organization name cannot exceed {0} characters")]
+    NameTooLong(usize),
+}
+
+const MAX_ORGANIZATION_ID_LENGTH: usize = 50;
+const MAX_ORGANIZATION_NAME_LENGTH: usize = 100;
+
+/// Validate an organization ID
+pub fn validate_organization_id(id: &str) -> Result<(), OrganizationValidationError> {
+    if id.is_empty() {
+        return Err(OrganizationValidationError::EmptyId);
+    }
+
+    if id.len() > MAX_ORGANIZATION_ID_LENGTH {
+        return Err(OrganizationValidationError::IdTooLong(
+            MAX_ORGANIZATION_ID_LENGTH,
+        ));
+    }
+
+    if !id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+        return Err(OrganizationValidationError::InvalidIdCharacters);
+    }
+
+    if id.starts_with('-') || id.ends_with('-') {
+        return Err(OrganizationValidationError::InvalidIdFormat);
+    }
+
+    Ok(())
+}
+
+/// Validate an organization name
+pub fn validate_organization_name(name: &str) -> Result<(), OrganizationValidationError> {
+    if name.is_empty() {
+        return Err(OrganizationValidationError::EmptyName);
+    }
+
+    if name.len() > MAX_ORGANIZATION_NAME_LENGTH {
+        return Err(OrganizationValidationError::NameTooLong(
+            MAX_ORGANIZATION_NAME_LENGTH,
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_organization_id() {
+        assert!(validate_organization_id("acme").is_ok());
+        assert!(validate_organization_id("acme-retail-2").is_ok());
+    }
+
+    #[test]
+    fn test_invalid_organization_id() {
+        assert_eq!(
+            validate_organization_id(""),
+            Err(OrganizationValidationError::EmptyId)
+        );
+        assert_eq!(
+            validate_organization_id(&"a".repeat(51)),
+            Err(OrganizationValidationError::IdTooLong(50))
+        );
+        assert_eq!(
+            validate_organization_id("acme_retail"),
+            Err(OrganizationValidationError::InvalidIdCharacters)
+        );
+        assert_eq!(
+            validate_organization_id("-acme"),
+            Err(OrganizationValidationError::InvalidIdFormat)
+        );
+    }
+
+    #[test]
+    fn test_organization_name() {
+        assert!(validate_organization_name("Acme Retail").is_ok());
+        assert_eq!(
+            validate_organization_name(""),
+            Err(OrganizationValidationError::EmptyName)
+        );
+        assert_eq!(
+            validate_organization_name(&"a".repeat(101)),
+            Err(OrganizationValidationError::NameTooLong(100))
+        );
+    }
+}
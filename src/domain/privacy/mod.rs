@@ -0,0 +1,8 @@
+//! Privacy domain
+//!
+//! This module provides the data subject type used to locate, export and
+//! erase the records of an end user of client applications.
+
+mod subject;
+
+pub use subject::{DEFAULT_USER_METADATA_KEY, DataSubject};
@@ -0,0 +1,96 @@
+//! End users targeted by data subject requests
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::domain::DomainError;
+use crate::domain::usage::validate_usage_tags;
+
+/// Request metadata key identifying the end user when none is given
+pub const DEFAULT_USER_METADATA_KEY: &str = "user_id";
+
+/// An end user of a client application, identified by the value clients
+/// send under a metadata key of their requests (e.g. `metadata.user_id`).
+/// Request metadata becomes the usage tags of a request, so the key and
+/// identifier follow the usage tag rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataSubject {
+    metadata_key: String,
+    user_id: String,
+}
+
+impl DataSubject {
+    /// Create a data subject after validation
+    pub fn new(
+        metadata_key: impl Into<String>,
+        user_id: impl Into<String>,
+    ) -> Result<Self, DomainError> {
+        let metadata_key = metadata_key.into();
+        let user_id = user_id.into();
+
+        if user_id.trim().is_empty() {
+            return Err(DomainError::validation("User identifier cannot be empty"));
+        }
+
+        validate_usage_tags(&HashMap::from([(metadata_key.clone(), user_id.clone())]))?;
+
+        Ok(Self {
+            metadata_key,
+            user_id,
+        })
+    }
+
+    pub fn metadata_key(&self) -> &str {
+        &self.metadata_key
+    }
+
+    pub fn user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    /// Whether a captured chat request belongs to the subject: its metadata
+    /// carries the identifier under the key, or its `user` field is the
+    /// identifier
+    pub fn matches_request(&self, request: &Value) -> bool {
+        let in_metadata = request
+            .get("metadata")
+            .and_then(|metadata| metadata.get(&self.metadata_key))
+            .and_then(Value::as_str)
+            == Some(self.user_id.as_str());
+
+        in_metadata || request.get("user").and_then(Value::as_str) == Some(self.user_id.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_new_validates_subject() {
+        let subject = DataSubject::new(DEFAULT_USER_METADATA_KEY, "user-42").unwrap();
+        assert_eq!(subject.metadata_key(), "user_id");
+        assert_eq!(subject.user_id(), "user-42");
+
+        assert!(DataSubject::new("user_id", " ").is_err());
+        assert!(DataSubject::new("", "user-42").is_err());
+        assert!(DataSubject::new("user id", "user-42").is_err());
+    }
+
+    #[test]
+    fn test_matches_request() {
+        let subject = DataSubject::new("customer", "user-42").unwrap();
+
+        assert!(subject.matches_request(&json!({
+            "model": "gpt-4",
+            "metadata": {"customer": "user-42"}
+        })));
+        assert!(subject.matches_request(&json!({"model": "gpt-4", "user": "user-42"})));
+
+        assert!(!subject.matches_request(&json!({"metadata": {"customer": "user-7"}})));
+        assert!(!subject.matches_request(&json!({"metadata": {"user_id": "user-42"}})));
+        assert!(!subject.matches_request(&json!("user-42")));
+    }
+}
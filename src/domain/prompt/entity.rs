@@ -1,8 +1,13 @@
 //! Prompt entity and related types
 
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::locale::locale_fallbacks;
+use super::template::PromptVariable;
+use crate::domain::cache::CachePolicy;
 use crate::domain::storage::{StorageEntity, StorageKey};
 use crate::domain::{validate_model_id, ModelValidationError};
 
@@ -62,6 +67,9 @@ pub struct PromptVersion {
     created_at: DateTime<Utc>,
     /// Optional commit message describing the change
     message: Option<String>,
+    /// Who made the change that produced this version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    author: Option<String>,
 }
 
 impl PromptVersion {
@@ -72,6 +80,7 @@ impl PromptVersion {
             content: content.into(),
             created_at: Utc::now(),
             message: None,
+            author: None,
         }
     }
 
@@ -80,6 +89,11 @@ impl PromptVersion {
         self
     }
 
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+
     pub fn version(&self) -> u32 {
         self.version
     }
@@ -95,6 +109,10 @@ impl PromptVersion {
     pub fn message(&self) -> Option<&str> {
         self.message.as_deref()
     }
+
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
 }
 
 /// Structured output schema for prompts
@@ -139,6 +157,12 @@ pub struct Prompt {
     content: String,
     /// Current version number
     version: u32,
+    /// Change note of the current version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    change_note: Option<String>,
+    /// Who made the change that produced the current version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    author: Option<String>,
     /// Version history
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     history: Vec<PromptVersion>,
@@ -152,6 +176,16 @@ pub struct Prompt {
     /// Optional structured output schema
     #[serde(skip_serializing_if = "Option::is_none")]
     output_schema: Option<PromptOutputSchema>,
+    /// Response caching of requests using the prompt; takes precedence over
+    /// the model's policy unless that one disables caching
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cache_policy: Option<CachePolicy>,
+    /// Declared types, descriptions and requiredness of template variables
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    variables: Vec<PromptVariable>,
+    /// Content variants by normalized locale tag; they are not versioned
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    locales: BTreeMap<String, String>,
     /// Creation timestamp
     created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -168,11 +202,16 @@ impl Prompt {
             description: None,
             content: content.into(),
             version: 1,
+            change_note: None,
+            author: None,
             history: Vec::new(),
             max_history: 10, // Default to keeping 10 versions
             enabled: true,
             tags: Vec::new(),
             output_schema: None,
+            cache_policy: None,
+            variables: Vec::new(),
+            locales: BTreeMap::new(),
             created_at: now,
             updated_at: now,
         }
@@ -208,6 +247,21 @@ impl Prompt {
         self
     }
 
+    pub fn with_cache_policy(mut self, cache_policy: CachePolicy) -> Self {
+        self.cache_policy = Some(cache_policy);
+        self
+    }
+
+    pub fn with_variables(mut self, variables: Vec<PromptVariable>) -> Self {
+        self.variables = variables;
+        self
+    }
+
+    pub fn with_locales(mut self, locales: BTreeMap<String, String>) -> Self {
+        self.locales = locales;
+        self
+    }
+
     // Getters
 
     pub fn id(&self) -> &PromptId {
@@ -230,6 +284,14 @@ impl Prompt {
         self.version
     }
 
+    pub fn change_note(&self) -> Option<&str> {
+        self.change_note.as_deref()
+    }
+
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
     pub fn history(&self) -> &[PromptVersion] {
         &self.history
     }
@@ -250,6 +312,33 @@ impl Prompt {
         self.output_schema.as_ref()
     }
 
+    pub fn cache_policy(&self) -> Option<&CachePolicy> {
+        self.cache_policy.as_ref()
+    }
+
+    /// Declared variables; the template's own placeholders are the source
+    /// of the variable list
+    pub fn variables(&self) -> &[PromptVariable] {
+        &self.variables
+    }
+
+    pub fn locales(&self) -> &BTreeMap<String, String> {
+        &self.locales
+    }
+
+    /// Content of the variant best matching a requested locale list, with
+    /// the locale of the variant; the base content when none matches
+    pub fn localized_content(&self, requested: Option<&str>) -> (Option<&str>, &str) {
+        requested
+            .map(locale_fallbacks)
+            .unwrap_or_default()
+            .iter()
+            .find_map(|locale| self.locales.get_key_value(locale))
+            .map_or((None, self.content.as_str()), |(locale, content)| {
+                (Some(locale.as_str()), content.as_str())
+            })
+    }
+
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
     }
@@ -266,6 +355,24 @@ impl Prompt {
         self.history.iter().find(|v| v.version == version)
     }
 
+    /// Snapshot of any retained version, including the current one
+    pub fn version_snapshot(&self, version: u32) -> Option<PromptVersion> {
+        if version == self.version {
+            return Some(self.current_snapshot());
+        }
+        self.get_version(version).cloned()
+    }
+
+    fn current_snapshot(&self) -> PromptVersion {
+        PromptVersion {
+            version: self.version,
+            content: self.content.clone(),
+            created_at: self.updated_at,
+            message: self.change_note.clone(),
+            author: self.author.clone(),
+        }
+    }
+
     // Mutators
 
     pub fn set_name(&mut self, name: impl Into<String>) {
@@ -278,8 +385,19 @@ impl Prompt {
         self.touch();
     }
 
-    /// Update the content, creating a new version
+    /// Update the content, creating a new version with `message` as its
+    /// change note
     pub fn set_content(&mut self, content: impl Into<String>, message: Option<String>) {
+        self.set_content_by(content, message, None);
+    }
+
+    /// Update the content on behalf of `author`, creating a new version
+    pub fn set_content_by(
+        &mut self,
+        content: impl Into<String>,
+        message: Option<String>,
+        author: Option<String>,
+    ) {
         let new_content = content.into();
 
         // Don't create version if content is unchanged
@@ -289,12 +407,12 @@ impl Prompt {
 
         // Save current version to history
         let mut version = PromptVersion::new(self.version, self.content.clone());
-
-        if let Some(msg) = message {
-            version = version.with_message(msg);
-        }
+        version.message = self.change_note.take();
+        version.author = self.author.take();
 
         self.history.push(version);
+        self.change_note = message;
+        self.author = author;
 
         // Trim history if needed
         if self.max_history > 0 && self.history.len() > self.max_history {
@@ -323,6 +441,21 @@ impl Prompt {
         self.touch();
     }
 
+    pub fn set_cache_policy(&mut self, cache_policy: Option<CachePolicy>) {
+        self.cache_policy = cache_policy;
+        self.touch();
+    }
+
+    pub fn set_variables(&mut self, variables: Vec<PromptVariable>) {
+        self.variables = variables;
+        self.touch();
+    }
+
+    pub fn set_locales(&mut self, locales: BTreeMap<String, String>) {
+        self.locales = locales;
+        self.touch();
+    }
+
     pub fn add_tag(&mut self, tag: impl Into<String>) {
         let tag = tag.into();
 
@@ -425,12 +558,22 @@ mod tests {
         assert_eq!(prompt.history().len(), 1);
         assert_eq!(prompt.history()[0].version(), 1);
         assert_eq!(prompt.history()[0].content(), "Version 1 content");
-        assert_eq!(prompt.history()[0].message(), Some("Updated for clarity"));
+        assert_eq!(prompt.history()[0].message(), None);
+        assert_eq!(prompt.change_note(), Some("Updated for clarity"));
 
         // Update again
-        prompt.set_content("Version 3 content", None);
+        prompt.set_content_by("Version 3 content", None, Some("user:alice".to_string()));
         assert_eq!(prompt.version(), 3);
         assert_eq!(prompt.history().len(), 2);
+        assert_eq!(prompt.history()[1].message(), Some("Updated for clarity"));
+        assert_eq!(prompt.change_note(), None);
+        assert_eq!(prompt.author(), Some("user:alice"));
+
+        let current = prompt.version_snapshot(3).unwrap();
+        assert_eq!(current.content(), "Version 3 content");
+        assert_eq!(current.author(), Some("user:alice"));
+        assert_eq!(prompt.version_snapshot(2).unwrap().message(), Some("Updated for clarity"));
+        assert!(prompt.version_snapshot(9).is_none());
     }
 
     #[test]
@@ -506,6 +649,20 @@ mod tests {
         assert!(!not_found);
     }
 
+    #[test]
+    fn test_prompt_localized_content() {
+        let prompt = Prompt::new(create_prompt_id("greeting"), "Greeting", "Hello")
+            .with_locales(BTreeMap::from([
+                ("es".to_string(), "Hola".to_string()),
+                ("pt-br".to_string(), "Olá".to_string()),
+            ]));
+
+        assert_eq!(prompt.localized_content(Some("es-MX")), (Some("es"), "Hola"));
+        assert_eq!(prompt.localized_content(Some("fr, pt-BR;q=0.5")), (Some("pt-br"), "Olá"));
+        assert_eq!(prompt.localized_content(Some("pt")), (None, "Hello"));
+        assert_eq!(prompt.localized_content(None), (None, "Hello"));
+    }
+
     #[test]
     fn test_prompt_version_struct() {
         let version = PromptVersion::new(5, "Test content")
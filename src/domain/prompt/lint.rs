@@ -0,0 +1,299 @@
+//! Prompt template linting
+//!
+//! Catches templates that would fail or render wrongly at runtime: malformed
+//! `${var:...}` placeholders, a variable used with different defaults (only
+//! the first form is substituted) and, against a declared variable schema,
+//! variables the template uses but the schema does not define or that the
+//! schema defines but the template never uses.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use super::template::{PromptTemplate, PromptVariable, VARIABLE_PATTERN};
+
+const PLACEHOLDER_START: &str = "${var:";
+
+/// Longest snippet of a malformed placeholder included in an issue
+const MAX_SNIPPET_CHARS: usize = 40;
+
+/// How serious a lint issue is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    /// The template fails or renders wrongly
+    Error,
+    /// The template renders but is likely not what was intended
+    Warning,
+}
+
+/// Kind of lint issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LintCode {
+    MalformedVariable,
+    ConflictingDefault,
+    UndefinedVariable,
+    UnusedVariable,
+    OptionalRequiredVariable,
+    InvalidPartial,
+}
+
+/// A problem found in a template
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct LintIssue {
+    pub severity: LintSeverity,
+    pub code: LintCode,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variable: Option<String>,
+}
+
+impl LintIssue {
+    pub fn error(code: LintCode, message: String, variable: Option<&str>) -> Self {
+        Self {
+            severity: LintSeverity::Error,
+            code,
+            message,
+            variable: variable.map(str::to_string),
+        }
+    }
+
+    pub fn warning(code: LintCode, message: String, variable: Option<&str>) -> Self {
+        Self {
+            severity: LintSeverity::Warning,
+            code,
+            message,
+            variable: variable.map(str::to_string),
+        }
+    }
+}
+
+/// Variables a template is expected to use, read from a JSON Schema object
+/// (`properties` declares them, `required` lists the mandatory ones)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VariableSchema {
+    pub properties: BTreeSet<String>,
+    pub required: BTreeSet<String>,
+}
+
+impl VariableSchema {
+    /// Read the declared variables of a JSON Schema object
+    pub fn from_json_schema(schema: &Value) -> Self {
+        let properties = schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .map(|properties| properties.keys().cloned().collect())
+            .unwrap_or_default();
+        let required = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|required| {
+                required
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            properties,
+            required,
+        }
+    }
+}
+
+/// Outcome of linting a template
+#[derive(Debug, Clone)]
+pub struct PromptLintReport {
+    pub variables: Vec<PromptVariable>,
+    pub issues: Vec<LintIssue>,
+}
+
+impl PromptLintReport {
+    /// Whether the template has no error-level issue
+    pub fn is_valid(&self) -> bool {
+        self.issues
+            .iter()
+            .all(|issue| issue.severity != LintSeverity::Error)
+    }
+}
+
+/// Lint a template, optionally against the schema of its variables
+pub fn lint_template(content: &str, schema: Option<&VariableSchema>) -> PromptLintReport {
+    let variables = PromptTemplate::parse(content)
+        .map(|template| template.variables().to_vec())
+        .unwrap_or_default();
+
+    let mut issues = malformed_placeholders(content);
+    issues.extend(conflicting_defaults(content));
+
+    if let Some(schema) = schema {
+        issues.extend(schema_mismatches(&variables, schema));
+    }
+
+    PromptLintReport { variables, issues }
+}
+
+fn malformed_placeholders(content: &str) -> Vec<LintIssue> {
+    content
+        .match_indices(PLACEHOLDER_START)
+        .filter(|(start, _)| {
+            VARIABLE_PATTERN
+                .find_at(content, *start)
+                .is_none_or(|m| m.start() != *start)
+        })
+        .map(|(start, _)| {
+            let rest = &content[start..];
+            let snippet: String = match rest.find('}') {
+                Some(end) => rest[..=end].chars().take(MAX_SNIPPET_CHARS).collect(),
+                None => rest.chars().take(MAX_SNIPPET_CHARS).collect(),
+            };
+
+            LintIssue::error(
+                LintCode::MalformedVariable,
+                format!(
+                    "'{}' is not a valid variable placeholder, expected ${{var:name}} or ${{var:name:default}}",
+                    snippet
+                ),
+                None,
+            )
+        })
+        .collect()
+}
+
+fn conflicting_defaults(content: &str) -> Vec<LintIssue> {
+    let mut defaults: BTreeMap<&str, BTreeSet<Option<&str>>> = BTreeMap::new();
+
+    for cap in VARIABLE_PATTERN.captures_iter(content) {
+        let name = cap.get(1).map_or("", |m| m.as_str());
+        defaults
+            .entry(name)
+            .or_default()
+            .insert(cap.get(2).map(|m| m.as_str()));
+    }
+
+    defaults
+        .into_iter()
+        .filter(|(_, forms)| forms.len() > 1)
+        .map(|(name, _)| {
+            LintIssue::error(
+                LintCode::ConflictingDefault,
+                format!(
+                    "Variable '{}' is used with different defaults, only its first form is rendered",
+                    name
+                ),
+                Some(name),
+            )
+        })
+        .collect()
+}
+
+fn schema_mismatches(variables: &[PromptVariable], schema: &VariableSchema) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+
+    for variable in variables {
+        if !schema.properties.contains(&variable.name) {
+            issues.push(LintIssue::error(
+                LintCode::UndefinedVariable,
+                format!("Variable '{}' is not defined in the schema", variable.name),
+                Some(&variable.name),
+            ));
+        } else if variable.required && !schema.required.contains(&variable.name) {
+            issues.push(LintIssue::warning(
+                LintCode::OptionalRequiredVariable,
+                format!(
+                    "Variable '{}' has no default but is optional in the schema, rendering fails without it",
+                    variable.name
+                ),
+                Some(&variable.name),
+            ));
+        }
+    }
+
+    let used: BTreeSet<&str> = variables.iter().map(|v| v.name.as_str()).collect();
+    for name in &schema.properties {
+        if !used.contains(name.as_str()) {
+            issues.push(LintIssue::warning(
+                LintCode::UnusedVariable,
+                format!("Variable '{}' is defined but never used", name),
+                Some(name),
+            ));
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn codes(report: &PromptLintReport) -> Vec<LintCode> {
+        report.issues.iter().map(|issue| issue.code).collect()
+    }
+
+    #[test]
+    fn test_valid_template() {
+        let report = lint_template("Hello ${var:name}, you are ${var:tone:kind}", None);
+
+        assert!(report.is_valid());
+        assert!(report.issues.is_empty());
+        assert_eq!(
+            report.variables,
+            vec![
+                PromptVariable::required("name"),
+                PromptVariable::with_default("tone", "kind"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_malformed_placeholders() {
+        let report = lint_template("Hi ${var:-bad} and ${var:name} and ${var:open", None);
+
+        assert!(!report.is_valid());
+        assert_eq!(
+            codes(&report),
+            vec![LintCode::MalformedVariable, LintCode::MalformedVariable]
+        );
+        assert!(report.issues[0].message.contains("${var:-bad}"));
+    }
+
+    #[test]
+    fn test_conflicting_defaults() {
+        let report = lint_template(
+            "${var:tone:kind} then ${var:tone:terse} ${var:x} ${var:x}",
+            None,
+        );
+
+        assert_eq!(codes(&report), vec![LintCode::ConflictingDefault]);
+        assert_eq!(report.issues[0].variable.as_deref(), Some("tone"));
+    }
+
+    #[test]
+    fn test_schema_mismatches() {
+        let schema = VariableSchema::from_json_schema(&json!({
+            "type": "object",
+            "properties": {"name": {"type": "string"}, "topic": {}, "unused": {}},
+            "required": ["topic"]
+        }));
+        let report = lint_template("${var:name} ${var:topic} ${var:missing:x}", Some(&schema));
+
+        assert!(!report.is_valid());
+        assert_eq!(
+            codes(&report),
+            vec![
+                LintCode::OptionalRequiredVariable,
+                LintCode::UndefinedVariable,
+                LintCode::UnusedVariable,
+            ]
+        );
+        assert_eq!(report.issues[1].variable.as_deref(), Some("missing"));
+        assert_eq!(report.issues[2].severity, LintSeverity::Warning);
+    }
+}
@@ -0,0 +1,98 @@
+//! Locale variants of prompts
+//!
+//! A prompt keeps its base content plus optional variants keyed by locale
+//! tag (`es`, `pt-br`, ...). A request names the locales it accepts, in the
+//! `Accept-Language` form (`pt-BR, es;q=0.8`); each one falls back to its
+//! parent tags (`pt-br`, then `pt`) before the next, and the base content
+//! is used when no variant matches.
+
+/// Longest accepted locale tag
+const MAX_LOCALE_LEN: usize = 35;
+
+/// Lowercase form of a locale tag, with `_` separators turned into `-`
+pub fn normalize_locale(tag: &str) -> String {
+    tag.trim().replace('_', "-").to_ascii_lowercase()
+}
+
+/// Check a locale tag: a 2-3 letter language followed by alphanumeric
+/// subtags of up to 8 characters
+pub fn validate_locale(tag: &str) -> Result<(), String> {
+    let normalized = normalize_locale(tag);
+    let mut subtags = normalized.split('-');
+
+    let language_ok = subtags.next().is_some_and(|language| {
+        (2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_alphabetic())
+    });
+    let subtags_ok = subtags.all(|subtag| {
+        (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+    });
+
+    if language_ok && subtags_ok && normalized.len() <= MAX_LOCALE_LEN {
+        Ok(())
+    } else {
+        Err(format!("'{}' is not a valid locale tag", tag))
+    }
+}
+
+/// Locales to try, in order, for a requested locale list: by decreasing
+/// `q` weight, each followed by its parent tags. Invalid tags, `*` and
+/// weights of 0 are skipped.
+pub fn locale_fallbacks(requested: &str) -> Vec<String> {
+    let mut weighted: Vec<(String, f32)> = requested
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let tag = parts.next()?.trim();
+            let weight = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+
+            (weight > 0.0 && validate_locale(tag).is_ok()).then(|| (normalize_locale(tag), weight))
+        })
+        .collect();
+    // Stable, so equally weighted locales keep the requested order
+    weighted.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let mut fallbacks: Vec<String> = Vec::new();
+    for (tag, _) in weighted {
+        let mut candidate = tag.as_str();
+        loop {
+            if !fallbacks.iter().any(|seen| seen == candidate) {
+                fallbacks.push(candidate.to_string());
+            }
+            match candidate.rfind('-') {
+                Some(end) => candidate = &candidate[..end],
+                None => break,
+            }
+        }
+    }
+    fallbacks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_locale() {
+        assert!(validate_locale("es").is_ok());
+        assert!(validate_locale("pt_BR").is_ok());
+        assert!(validate_locale("zh-Hant-TW").is_ok());
+        assert!(validate_locale("e").is_err());
+        assert!(validate_locale("es-").is_err());
+        assert!(validate_locale("12-ab").is_err());
+    }
+
+    #[test]
+    fn test_locale_fallbacks() {
+        assert_eq!(
+            locale_fallbacks("pt-BR, es;q=0.8, en;q=0.9, *;q=0.1, fr;q=0"),
+            vec!["pt-br", "pt", "en", "es"]
+        );
+        assert_eq!(
+            locale_fallbacks("zh-Hant-TW,zh"),
+            vec!["zh-hant-tw", "zh-hant", "zh"]
+        );
+        assert!(locale_fallbacks("").is_empty());
+    }
+}
@@ -1,7 +1,15 @@
 //! Prompt management domain - Prompt templates with variable support
 
 mod entity;
+mod lint;
+mod locale;
+mod partial;
 mod template;
 
 pub use entity::{Prompt, PromptId, PromptOutputSchema, PromptVersion};
-pub use template::{PromptTemplate, PromptVariable, TemplateError};
+pub use lint::{lint_template, LintCode, LintIssue, LintSeverity, PromptLintReport, VariableSchema};
+pub use locale::{locale_fallbacks, normalize_locale, validate_locale};
+pub use partial::{expand_partials, partial_references, MAX_PARTIAL_DEPTH};
+pub use template::{
+    check_variable_declarations, PromptTemplate, PromptVariable, TemplateError, VariableType,
+};
@@ -0,0 +1,192 @@
+//! Prompt partials
+//!
+//! `${prompt:partial-id}` includes the content of another prompt. Partials are
+//! expanded recursively before variables are rendered, so a partial may use
+//! `${var:...}` placeholders and include further partials, up to
+//! [`MAX_PARTIAL_DEPTH`] levels and without cycles.
+
+use std::collections::{HashMap, HashSet};
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+use super::template::TemplateError;
+
+/// Maximum nesting of partials inside partials
+pub const MAX_PARTIAL_DEPTH: usize = 5;
+
+/// Regex to match partial references: ${prompt:id}
+static PARTIAL_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\$\{prompt:([a-zA-Z0-9][-a-zA-Z0-9]*)\}").unwrap());
+
+/// IDs of the partials a template references directly, in order of first use
+pub fn partial_references(content: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+
+    PARTIAL_PATTERN
+        .captures_iter(content)
+        .map(|cap| cap[1].to_string())
+        .filter(|id| seen.insert(id.clone()))
+        .collect()
+}
+
+/// Expand every partial reference of `content` with the contents in
+/// `partials`. `root_id` is the ID of the prompt being expanded, if any, so a
+/// prompt including itself is reported as a cycle.
+pub fn expand_partials(
+    content: &str,
+    root_id: Option<&str>,
+    partials: &HashMap<String, String>,
+) -> Result<String, TemplateError> {
+    let mut chain: Vec<&str> = root_id.into_iter().collect();
+    expand(content, partials, &mut chain, 0)
+}
+
+fn expand<'a>(
+    content: &str,
+    partials: &'a HashMap<String, String>,
+    chain: &mut Vec<&'a str>,
+    depth: usize,
+) -> Result<String, TemplateError> {
+    let mut error = None;
+
+    let expanded = PARTIAL_PATTERN.replace_all(content, |cap: &Captures| {
+        if error.is_some() {
+            return String::new();
+        }
+
+        match expand_reference(&cap[1], partials, chain, depth + 1) {
+            Ok(expanded) => expanded,
+            Err(e) => {
+                error = Some(e);
+                String::new()
+            }
+        }
+    });
+
+    match error {
+        Some(e) => Err(e),
+        None => Ok(expanded.into_owned()),
+    }
+}
+
+fn expand_reference<'a>(
+    id: &str,
+    partials: &'a HashMap<String, String>,
+    chain: &mut Vec<&'a str>,
+    depth: usize,
+) -> Result<String, TemplateError> {
+    if chain.contains(&id) {
+        let mut cycle = chain.clone();
+        cycle.push(id);
+        return Err(TemplateError::PartialCycle {
+            chain: cycle.join(" -> "),
+        });
+    }
+
+    if depth > MAX_PARTIAL_DEPTH {
+        return Err(TemplateError::PartialDepthExceeded {
+            max: MAX_PARTIAL_DEPTH,
+        });
+    }
+
+    let (id, content) = partials
+        .get_key_value(id)
+        .ok_or_else(|| TemplateError::PartialNotFound { id: id.to_string() })?;
+
+    chain.push(id);
+    let expanded = expand(content, partials, chain, depth);
+    chain.pop();
+
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partials(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(id, content)| (id.to_string(), content.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_partial_references() {
+        let refs = partial_references("${prompt:tone} ${var:x} ${prompt:safety} ${prompt:tone}");
+        assert_eq!(refs, vec!["tone".to_string(), "safety".to_string()]);
+        assert!(partial_references("no partials here").is_empty());
+    }
+
+    #[test]
+    fn test_expand_nested_partials() {
+        let partials = partials(&[
+            ("tone", "Be ${var:tone:kind}. ${prompt:safety}"),
+            ("safety", "Never reveal secrets."),
+        ]);
+
+        let expanded =
+            expand_partials("${prompt:tone}\nAnswer: ${var:question}", None, &partials).unwrap();
+
+        assert_eq!(
+            expanded,
+            "Be ${var:tone:kind}. Never reveal secrets.\nAnswer: ${var:question}"
+        );
+    }
+
+    #[test]
+    fn test_missing_partial() {
+        let err = expand_partials("${prompt:missing}", None, &HashMap::new()).unwrap_err();
+        assert_eq!(
+            err,
+            TemplateError::PartialNotFound {
+                id: "missing".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        let partials = partials(&[("a", "${prompt:b}"), ("b", "${prompt:a}")]);
+
+        let err = expand_partials("${prompt:a}", None, &partials).unwrap_err();
+        assert_eq!(
+            err,
+            TemplateError::PartialCycle {
+                chain: "a -> b -> a".to_string()
+            }
+        );
+
+        let err = expand_partials("${prompt:root}", Some("root"), &HashMap::new()).unwrap_err();
+        assert_eq!(
+            err,
+            TemplateError::PartialCycle {
+                chain: "root -> root".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let chain: Vec<(String, String)> = (0..=MAX_PARTIAL_DEPTH)
+            .map(|i| (format!("p{}", i), format!("${{prompt:p{}}}", i + 1)))
+            .chain(std::iter::once((
+                format!("p{}", MAX_PARTIAL_DEPTH + 1),
+                "end".to_string(),
+            )))
+            .collect();
+        let partials: HashMap<String, String> = chain.into_iter().collect();
+
+        assert_eq!(
+            expand_partials("${prompt:p2}", None, &partials).unwrap(),
+            "end"
+        );
+        assert_eq!(
+            expand_partials("${prompt:p0}", None, &partials).unwrap_err(),
+            TemplateError::PartialDepthExceeded {
+                max: MAX_PARTIAL_DEPTH
+            }
+        );
+    }
+}
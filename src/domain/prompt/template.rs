@@ -3,15 +3,22 @@
 //! Supports variable syntax: `${var:variable-name:default-value}`
 //! - `${var:name}` - Required variable, error if not provided
 //! - `${var:name:default}` - Optional variable with default value
+//!
+//! A prompt can declare its variables with a type, a description and
+//! whether they are required; rendering then checks the provided values.
 
 use std::collections::HashMap;
 
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::domain::FieldViolations;
 
 /// Regex to match variable patterns: ${var:name} or ${var:name:default}
-static VARIABLE_PATTERN: Lazy<Regex> = Lazy::new(|| {
+pub(super) static VARIABLE_PATTERN: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"\$\{var:([a-zA-Z0-9][-a-zA-Z0-9]*)(?::([^}]*))?\}").unwrap()
 });
 
@@ -21,22 +28,63 @@ pub enum TemplateError {
     #[error("Missing required variable: {name}")]
     MissingVariable { name: String },
 
+    #[error("Invalid value for variable {name}: {message}")]
+    InvalidValue { name: String, message: String },
+
     #[error("Invalid variable name: {name}")]
     InvalidVariableName { name: String },
 
     #[error("Template parsing error: {message}")]
     ParseError { message: String },
+
+    #[error("Partial prompt not found: {id}")]
+    PartialNotFound { id: String },
+
+    #[error("Partial prompts include each other: {chain}")]
+    PartialCycle { chain: String },
+
+    #[error("Partial prompts are nested deeper than {max} levels")]
+    PartialDepthExceeded { max: usize },
+}
+
+/// Type of the values of a prompt variable
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VariableType {
+    /// Any text
+    #[default]
+    String,
+    /// A decimal number
+    Number,
+    /// One of the declared `values`
+    Enum,
+    /// A JSON document
+    Json,
 }
 
-/// A parsed variable from a template
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// A variable of a template, parsed from its placeholders and possibly
+/// completed by the prompt's declaration of it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub struct PromptVariable {
     /// Variable name
     pub name: String,
     /// Default value if provided
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default: Option<String>,
     /// Whether the variable is required (no default)
+    #[serde(default = "default_required")]
     pub required: bool,
+    #[serde(rename = "type", default)]
+    pub var_type: VariableType,
+    /// Allowed values of an `enum` variable
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+fn default_required() -> bool {
+    true
 }
 
 impl PromptVariable {
@@ -46,15 +94,124 @@ impl PromptVariable {
             name: name.into(),
             default: None,
             required: true,
+            var_type: VariableType::String,
+            values: Vec::new(),
+            description: None,
         }
     }
 
     /// Create an optional variable with a default
     pub fn with_default(name: impl Into<String>, default: impl Into<String>) -> Self {
         Self {
-            name: name.into(),
             default: Some(default.into()),
             required: false,
+            ..Self::required(name)
+        }
+    }
+
+    /// Create a variable that renders empty when not provided
+    pub fn optional(name: impl Into<String>) -> Self {
+        Self {
+            required: false,
+            ..Self::required(name)
+        }
+    }
+
+    pub fn with_type(mut self, var_type: VariableType) -> Self {
+        self.var_type = var_type;
+        self
+    }
+
+    pub fn with_values(mut self, values: Vec<String>) -> Self {
+        self.values = values;
+        self
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Check a provided value against the type of the variable
+    pub fn validate(&self, value: &str) -> Result<(), TemplateError> {
+        let message = match self.var_type {
+            VariableType::String => return Ok(()),
+            VariableType::Number => match value.trim().parse::<f64>() {
+                Ok(number) if number.is_finite() => return Ok(()),
+                _ => format!("'{}' is not a number", value),
+            },
+            VariableType::Enum => {
+                if self.values.iter().any(|allowed| allowed == value) {
+                    return Ok(());
+                }
+                format!("'{}' is not one of {}", value, self.values.join(", "))
+            }
+            VariableType::Json => match serde_json::from_str::<serde_json::Value>(value) {
+                Ok(_) => return Ok(()),
+                Err(e) => format!("not valid JSON: {}", e),
+            },
+        };
+
+        Err(TemplateError::InvalidValue {
+            name: self.name.clone(),
+            message,
+        })
+    }
+
+    /// Record every invalid setting of a variable declaration, under `prefix`.
+    /// Defaults are written in the template, not declared.
+    pub fn check(&self, prefix: &str, violations: &mut FieldViolations) {
+        if !is_variable_name(&self.name) {
+            violations.push(
+                format!("{}.name", prefix),
+                TemplateError::InvalidVariableName {
+                    name: self.name.clone(),
+                }
+                .to_string(),
+            );
+        }
+        if self.default.is_some() {
+            violations.push(
+                format!("{}.default", prefix),
+                "Defaults are set in the template as ${var:name:default}",
+            );
+        }
+        match self.var_type {
+            VariableType::Enum if self.values.is_empty() => {
+                violations.push(format!("{}.values", prefix), "An enum needs allowed values");
+            }
+            VariableType::Enum => {}
+            _ if !self.values.is_empty() => {
+                violations.push(
+                    format!("{}.values", prefix),
+                    "Allowed values only apply to enum variables",
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether `name` can be used in a `${var:name}` placeholder
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphanumeric())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// Record every invalid variable declaration of a prompt, including names
+/// declared twice
+pub fn check_variable_declarations(variables: &[PromptVariable], violations: &mut FieldViolations) {
+    let mut seen = std::collections::HashSet::new();
+
+    for (index, variable) in variables.iter().enumerate() {
+        let prefix = format!("variables[{}]", index);
+        variable.check(&prefix, violations);
+        if !seen.insert(variable.name.as_str()) {
+            violations.push(
+                format!("{}.name", prefix),
+                format!("Variable '{}' is declared twice", variable.name),
+            );
         }
     }
 }
@@ -98,6 +255,24 @@ impl PromptTemplate {
         Ok(Self { content, variables })
     }
 
+    /// Complete the parsed variables with the prompt's declarations: their
+    /// type, allowed values and description, and whether a variable without
+    /// a default in the template is required. Declarations of variables the
+    /// template does not use are ignored.
+    pub fn with_declarations(mut self, declarations: &[PromptVariable]) -> Self {
+        for variable in &mut self.variables {
+            let Some(declared) = declarations.iter().find(|d| d.name == variable.name) else {
+                continue;
+            };
+
+            variable.var_type = declared.var_type;
+            variable.values = declared.values.clone();
+            variable.description = declared.description.clone();
+            variable.required = variable.default.is_none() && declared.required;
+        }
+        self
+    }
+
     /// Get the original template content
     pub fn content(&self) -> &str {
         &self.content
@@ -122,8 +297,18 @@ impl PromptTemplate {
     pub fn render(&self, values: &HashMap<String, String>) -> Result<String, TemplateError> {
         let mut result = self.content.clone();
 
+        // Optional variables without a default render empty
+        let empty = String::new();
+
         for var in &self.variables {
-            let value = values.get(&var.name).or(var.default.as_ref());
+            if let Some(value) = values.get(&var.name) {
+                var.validate(value)?;
+            }
+
+            let value = values
+                .get(&var.name)
+                .or(var.default.as_ref())
+                .or((!var.required).then_some(&empty));
 
             match value {
                 Some(v) => {
@@ -363,4 +548,101 @@ User context: ${var:context:No additional context provided}"#,
         assert_eq!(vars[2].name, "c");
         assert!(vars[2].required);
     }
+
+    #[test]
+    fn test_validate_typed_values() {
+        let number = PromptVariable::required("count").with_type(VariableType::Number);
+        assert!(number.validate(" 4.5 ").is_ok());
+        assert!(number.validate("four").is_err());
+
+        let json = PromptVariable::required("payload").with_type(VariableType::Json);
+        assert!(json.validate(r#"{"a": [1, 2]}"#).is_ok());
+        assert!(json.validate("{a:").is_err());
+
+        let level = PromptVariable::required("level")
+            .with_type(VariableType::Enum)
+            .with_values(vec!["low".to_string(), "high".to_string()]);
+        assert!(level.validate("high").is_ok());
+        assert_eq!(
+            level.validate("medium"),
+            Err(TemplateError::InvalidValue {
+                name: "level".to_string(),
+                message: "'medium' is not one of low, high".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_render_with_declarations() {
+        let template = PromptTemplate::parse("${var:count} items${var:note} (${var:unit:kg})")
+            .unwrap()
+            .with_declarations(&[
+                PromptVariable::required("count").with_type(VariableType::Number),
+                PromptVariable::optional("note"),
+                PromptVariable::optional("unit").with_description("Unit of weight"),
+            ]);
+
+        assert_eq!(template.required_variables().len(), 1);
+        assert_eq!(
+            template.variables()[2].description.as_deref(),
+            Some("Unit of weight")
+        );
+
+        let mut values = HashMap::from([("count".to_string(), "3".to_string())]);
+        assert_eq!(template.render(&values).unwrap(), "3 items (kg)");
+
+        values.insert("count".to_string(), "three".to_string());
+        assert!(matches!(
+            template.render(&values),
+            Err(TemplateError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_check_declarations() {
+        let mut violations = FieldViolations::new();
+        check_variable_declarations(
+            &[
+                PromptVariable::required("tone")
+                    .with_type(VariableType::Enum)
+                    .with_values(vec!["formal".to_string()]),
+                PromptVariable::optional("notes"),
+            ],
+            &mut violations,
+        );
+        assert!(violations.is_empty());
+
+        check_variable_declarations(
+            &[
+                PromptVariable::required("bad name"),
+                PromptVariable::required("size").with_values(vec!["s".to_string()]),
+                PromptVariable::optional("size"),
+            ],
+            &mut violations,
+        );
+        let message = violations.into_result().unwrap_err().to_string();
+        assert!(message.contains("variables[0].name"));
+        assert!(message.contains("variables[1].values"));
+        assert!(message.contains("variables[2].name"));
+    }
+
+    #[test]
+    fn test_variable_declaration_serde() {
+        let variable: PromptVariable = serde_json::from_value(serde_json::json!({
+            "name": "tone",
+            "type": "enum",
+            "values": ["formal", "casual"],
+            "description": "Register of the answer"
+        }))
+        .unwrap();
+
+        assert!(variable.required);
+        assert_eq!(variable.var_type, VariableType::Enum);
+
+        let json = serde_json::to_value(PromptVariable::with_default("role", "assistant")).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"name": "role", "default": "assistant", "required": false, "type": "string"})
+        );
+    }
 }
@@ -0,0 +1,346 @@
+//! Diffing declared entities against stored ones
+//!
+//! Entities are compared as JSON documents. Declared fields that are null or
+//! absent are not managed and never produce a change; objects are compared on
+//! the declared keys only, while arrays and scalars must match exactly.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use utoipa::ToSchema;
+
+use crate::domain::DomainError;
+
+/// Whether a reconcile only reports the changes or also performs them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconcileMode {
+    #[default]
+    Plan,
+    Apply,
+}
+
+/// Kinds of entities that can be declared, in the order they are created and
+/// updated so that references (e.g. a workflow step calling a model) resolve.
+/// Deletes run in the reverse order.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityKind {
+    Team,
+    ExternalApi,
+    Model,
+    Prompt,
+    Workflow,
+}
+
+impl EntityKind {
+    pub const APPLY_ORDER: [EntityKind; 5] = [
+        Self::Team,
+        Self::ExternalApi,
+        Self::Model,
+        Self::Prompt,
+        Self::Workflow,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Team => "team",
+            Self::ExternalApi => "external_api",
+            Self::Model => "model",
+            Self::Prompt => "prompt",
+            Self::Workflow => "workflow",
+        }
+    }
+}
+
+impl std::fmt::Display for EntityKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeAction {
+    Create,
+    Update,
+    Delete,
+}
+
+/// A field whose stored value differs from the declared one
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct FieldChange {
+    /// Dotted path of the field, e.g. `config.temperature`
+    pub field: String,
+    pub from: Value,
+    pub to: Value,
+}
+
+/// A change needed to converge one entity
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct EntityChange {
+    pub kind: EntityKind,
+    pub id: String,
+    pub action: ChangeAction,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<FieldChange>,
+}
+
+impl EntityChange {
+    /// Whether the change touches the top-level field or one of its children
+    pub fn touches(&self, field: &str) -> bool {
+        self.changes.iter().any(|change| {
+            change.field == field
+                || change
+                    .field
+                    .strip_prefix(field)
+                    .is_some_and(|rest| rest.starts_with('.'))
+        })
+    }
+}
+
+/// Changes converging the stored entities of a kind to the declared ones.
+/// Both sides are JSON objects carrying a string `id`. Declared entities are
+/// created or updated in the order given; stored entities that are not
+/// declared are deleted.
+pub fn diff_entities(
+    kind: EntityKind,
+    current: &[Value],
+    desired: &[Value],
+) -> Result<Vec<EntityChange>, DomainError> {
+    let mut declared = HashSet::new();
+    let mut changes = Vec::new();
+
+    for entity in desired {
+        let id = entity_id(entity).ok_or_else(|| {
+            DomainError::validation(format!("Every declared {} must have an id", kind))
+        })?;
+
+        if !declared.insert(id) {
+            return Err(DomainError::validation(format!(
+                "{} '{}' is declared more than once",
+                kind, id
+            )));
+        }
+
+        let stored = current.iter().find(|c| entity_id(c) == Some(id));
+        let mut fields = Vec::new();
+        collect_changes(
+            "",
+            stored.unwrap_or(&Value::Null),
+            &without_id(entity),
+            &mut fields,
+        );
+
+        let action = match stored {
+            None => ChangeAction::Create,
+            Some(_) if !fields.is_empty() => ChangeAction::Update,
+            Some(_) => continue,
+        };
+
+        changes.push(EntityChange {
+            kind,
+            id: id.to_string(),
+            action,
+            changes: fields,
+        });
+    }
+
+    for entity in current {
+        if let Some(id) = entity_id(entity)
+            && !declared.contains(id)
+        {
+            changes.push(EntityChange {
+                kind,
+                id: id.to_string(),
+                action: ChangeAction::Delete,
+                changes: Vec::new(),
+            });
+        }
+    }
+
+    Ok(changes)
+}
+
+/// The stored value with the declared fields laid over it, e.g. to build a
+/// full configuration from the declared subset
+pub fn overlay(current: &Value, desired: &Value) -> Value {
+    match (current, desired) {
+        (_, Value::Null) => current.clone(),
+        (Value::Object(current), Value::Object(desired)) => {
+            let mut merged = current.clone();
+            for (key, value) in desired.iter().filter(|(_, value)| !value.is_null()) {
+                let base = current.get(key).unwrap_or(&Value::Null);
+                merged.insert(key.clone(), overlay(base, value));
+            }
+            Value::Object(merged)
+        }
+        _ => desired.clone(),
+    }
+}
+
+fn entity_id(entity: &Value) -> Option<&str> {
+    entity.get("id").and_then(Value::as_str)
+}
+
+fn without_id(entity: &Value) -> Value {
+    match entity {
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .filter(|(key, _)| key.as_str() != "id")
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect::<Map<_, _>>(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn collect_changes(path: &str, current: &Value, desired: &Value, out: &mut Vec<FieldChange>) {
+    match desired {
+        Value::Null => {}
+        Value::Object(fields) => {
+            for (key, value) in fields {
+                let field = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                collect_changes(&field, current.get(key).unwrap_or(&Value::Null), value, out);
+            }
+        }
+        _ if values_match(current, desired) => {}
+        _ => out.push(FieldChange {
+            field: path.to_string(),
+            from: current.clone(),
+            to: desired.clone(),
+        }),
+    }
+}
+
+fn values_match(current: &Value, desired: &Value) -> bool {
+    match (current, desired) {
+        (_, Value::Null) => true,
+        (Value::Object(current), Value::Object(desired)) => desired
+            .iter()
+            .all(|(key, value)| values_match(current.get(key).unwrap_or(&Value::Null), value)),
+        (Value::Array(current), Value::Array(desired)) => {
+            current.len() == desired.len()
+                && current.iter().zip(desired).all(|(c, d)| values_match(c, d))
+        }
+        // Stored settings such as temperatures are single precision
+        (Value::Number(current), Value::Number(desired)) => {
+            current == desired
+                || match (current.as_f64(), desired.as_f64()) {
+                    (Some(c), Some(d)) => c as f32 == d as f32,
+                    _ => false,
+                }
+        }
+        _ => current == desired,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_plans_creates_updates_and_deletes() {
+        let current = vec![
+            json!({"id": "gpt-4", "name": "GPT-4", "enabled": true}),
+            json!({"id": "legacy", "name": "Legacy", "enabled": true}),
+            json!({"id": "claude", "name": "Claude", "enabled": true}),
+        ];
+        let desired = vec![
+            json!({"id": "claude", "name": "Claude"}),
+            json!({"id": "gpt-4", "name": "GPT-4", "enabled": false}),
+            json!({"id": "gpt-5", "name": "GPT-5"}),
+        ];
+
+        let changes = diff_entities(EntityKind::Model, &current, &desired).unwrap();
+
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].id, "gpt-4");
+        assert_eq!(changes[0].action, ChangeAction::Update);
+        assert_eq!(
+            changes[0].changes,
+            vec![FieldChange {
+                field: "enabled".to_string(),
+                from: json!(true),
+                to: json!(false),
+            }]
+        );
+
+        assert_eq!(changes[1].id, "gpt-5");
+        assert_eq!(changes[1].action, ChangeAction::Create);
+        assert_eq!(changes[1].changes[0].field, "name");
+        assert_eq!(changes[1].changes[0].from, Value::Null);
+
+        assert_eq!(changes[2].id, "legacy");
+        assert_eq!(changes[2].action, ChangeAction::Delete);
+        assert!(changes[2].changes.is_empty());
+    }
+
+    #[test]
+    fn test_diff_compares_declared_fields_only() {
+        let current = vec![json!({
+            "id": "gpt-4",
+            "config": {"temperature": 0.699999988079071, "max_tokens": 100},
+            "tags": ["a", "b"],
+            "updated_at": "2026-01-01T00:00:00Z"
+        })];
+
+        let unchanged = vec![json!({
+            "id": "gpt-4",
+            "config": {"temperature": 0.7, "top_p": null},
+            "tags": ["a", "b"]
+        })];
+        assert!(
+            diff_entities(EntityKind::Model, &current, &unchanged)
+                .unwrap()
+                .is_empty()
+        );
+
+        let changed = vec![json!({
+            "id": "gpt-4",
+            "config": {"max_tokens": 200},
+            "tags": ["a"]
+        })];
+        let changes = diff_entities(EntityKind::Model, &current, &changed).unwrap();
+        let fields: Vec<&str> = changes[0]
+            .changes
+            .iter()
+            .map(|c| c.field.as_str())
+            .collect();
+        assert_eq!(fields, vec!["config.max_tokens", "tags"]);
+        assert!(changes[0].touches("config"));
+        assert!(changes[0].touches("tags"));
+        assert!(!changes[0].touches("con"));
+    }
+
+    #[test]
+    fn test_diff_rejects_invalid_declarations() {
+        let duplicated = vec![json!({"id": "a"}), json!({"id": "a"})];
+        assert!(diff_entities(EntityKind::Prompt, &[], &duplicated).is_err());
+
+        let missing_id = vec![json!({"name": "a"})];
+        assert!(diff_entities(EntityKind::Prompt, &[], &missing_id).is_err());
+    }
+
+    #[test]
+    fn test_overlay() {
+        let current = json!({"temperature": 0.5, "max_tokens": 100, "top_p": null});
+        let desired = json!({"max_tokens": 200, "top_p": 0.9, "timeout_ms": null});
+
+        assert_eq!(
+            overlay(&current, &desired),
+            json!({"temperature": 0.5, "max_tokens": 200, "top_p": 0.9})
+        );
+        assert_eq!(overlay(&current, &Value::Null), current);
+        assert_eq!(overlay(&Value::Null, &json!({"a": 1})), json!({"a": 1}));
+    }
+}
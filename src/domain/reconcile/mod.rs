@@ -0,0 +1,11 @@
+//! Declarative state reconciliation domain
+//!
+//! This module compares the entities declared by a client (e.g. a Terraform
+//! provider or GitOps controller) with the ones stored in the gateway and
+//! plans the creates, updates and deletes that converge them.
+
+mod diff;
+
+pub use diff::{
+    ChangeAction, EntityChange, EntityKind, FieldChange, ReconcileMode, diff_entities, overlay,
+};
@@ -0,0 +1,447 @@
+//! References held by stored entities
+//!
+//! Only literal IDs are reported: workflow fields holding a `${...}`
+//! variable resolve at execution time and cannot be followed statically.
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::domain::DomainError;
+use crate::domain::assistant::Assistant;
+use crate::domain::experiment::{Experiment, VariantConfig};
+use crate::domain::knowledge_base::KnowledgeBase;
+use crate::domain::model::Model;
+use crate::domain::prompt::{Prompt, partial_references};
+use crate::domain::test_case::{TestCase, TestCaseInput};
+use crate::domain::workflow::{ContextCompression, Workflow, WorkflowStepType};
+
+/// Kinds of entities whose dependents can be looked up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferencedKind {
+    Prompt,
+    Model,
+    KnowledgeBase,
+    Credential,
+}
+
+impl ReferencedKind {
+    /// Parse a kind, accepting the singular or plural form and either
+    /// `snake_case` or the `kebab-case` of the admin routes
+    pub fn parse(value: &str) -> Result<Self, DomainError> {
+        match value.to_lowercase().replace('-', "_").as_str() {
+            "prompt" | "prompts" => Ok(Self::Prompt),
+            "model" | "models" => Ok(Self::Model),
+            "knowledge_base" | "knowledge_bases" => Ok(Self::KnowledgeBase),
+            "credential" | "credentials" => Ok(Self::Credential),
+            _ => Err(DomainError::validation(format!(
+                "Unknown entity type '{}', expected one of: prompt, model, knowledge_base, credential",
+                value
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Prompt => "prompt",
+            Self::Model => "model",
+            Self::KnowledgeBase => "knowledge_base",
+            Self::Credential => "credential",
+        }
+    }
+}
+
+impl std::fmt::Display for ReferencedKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Kinds of entities holding references
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferrerKind {
+    Workflow,
+    TestCase,
+    Experiment,
+    Model,
+    Prompt,
+    KnowledgeBase,
+    Assistant,
+}
+
+/// Reference from an entity to another one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub kind: ReferencedKind,
+    pub id: String,
+    /// Field of the referring entity holding the ID, e.g.
+    /// `steps.summarize.model_id`
+    pub field: String,
+}
+
+impl Reference {
+    fn new(kind: ReferencedKind, id: impl Into<String>, field: impl Into<String>) -> Self {
+        Self {
+            kind,
+            id: id.into(),
+            field: field.into(),
+        }
+    }
+}
+
+/// Push the reference unless the ID is a variable resolved at execution
+fn push_literal(references: &mut Vec<Reference>, kind: ReferencedKind, id: &str, field: String) {
+    if !id.contains("${") {
+        references.push(Reference::new(kind, id, field));
+    }
+}
+
+/// Models, prompts, knowledge bases and credentials used by the steps
+pub fn workflow_references(workflow: &Workflow) -> Vec<Reference> {
+    let mut references = Vec::new();
+
+    for step in workflow.steps() {
+        let field = |name: &str| format!("steps.{}.{}", step.name(), name);
+
+        match step.step_type() {
+            WorkflowStepType::ChatCompletion(chat) => {
+                push_literal(
+                    &mut references,
+                    ReferencedKind::Model,
+                    &chat.model_id,
+                    field("model_id"),
+                );
+                push_literal(
+                    &mut references,
+                    ReferencedKind::Prompt,
+                    &chat.prompt_id,
+                    field("prompt_id"),
+                );
+            }
+            WorkflowStepType::KnowledgeBaseSearch(kb) => {
+                push_literal(
+                    &mut references,
+                    ReferencedKind::KnowledgeBase,
+                    &kb.knowledge_base_id,
+                    field("knowledge_base_id"),
+                );
+                if let Some(transform) = &kb.query_transform {
+                    push_literal(
+                        &mut references,
+                        ReferencedKind::Model,
+                        &transform.model_id,
+                        field("query_transform.model_id"),
+                    );
+                }
+                if let Some(ContextCompression::Llm { model_id }) = &kb.compression {
+                    push_literal(
+                        &mut references,
+                        ReferencedKind::Model,
+                        model_id,
+                        field("compression.model_id"),
+                    );
+                }
+            }
+            WorkflowStepType::CragScoring(crag) => {
+                push_literal(
+                    &mut references,
+                    ReferencedKind::Model,
+                    &crag.model_id,
+                    field("model_id"),
+                );
+                push_literal(
+                    &mut references,
+                    ReferencedKind::Prompt,
+                    &crag.prompt_id,
+                    field("prompt_id"),
+                );
+            }
+            WorkflowStepType::HttpRequest(http) => {
+                if let Some(credential_id) = &http.credential_id {
+                    push_literal(
+                        &mut references,
+                        ReferencedKind::Credential,
+                        credential_id,
+                        field("credential_id"),
+                    );
+                }
+            }
+            WorkflowStepType::Conditional(_) => {}
+        }
+    }
+
+    references
+}
+
+/// Model and prompt of a model+prompt test case. Workflow test cases only
+/// reference their workflow.
+pub fn test_case_references(test_case: &TestCase) -> Vec<Reference> {
+    match test_case.input() {
+        TestCaseInput::ModelPrompt(input) => {
+            let mut references = vec![Reference::new(
+                ReferencedKind::Model,
+                &input.model_id,
+                "input.model_id",
+            )];
+
+            if let Some(prompt_id) = &input.prompt_id {
+                references.push(Reference::new(
+                    ReferencedKind::Prompt,
+                    prompt_id,
+                    "input.prompt_id",
+                ));
+            }
+
+            references
+        }
+        TestCaseInput::Workflow(_) => Vec::new(),
+    }
+}
+
+/// Models and prompt overrides of the variants
+pub fn experiment_references(experiment: &Experiment) -> Vec<Reference> {
+    let mut references = Vec::new();
+
+    for variant in experiment.variants() {
+        let field = |name: &str| format!("variants.{}.{}", variant.id().as_str(), name);
+
+        if let Some(model_id) = variant.model_id() {
+            references.push(Reference::new(
+                ReferencedKind::Model,
+                model_id,
+                field("model_id"),
+            ));
+        }
+
+        if let VariantConfig::ConfigOverride {
+            prompt_id: Some(prompt_id),
+            ..
+        } = variant.config()
+        {
+            references.push(Reference::new(
+                ReferencedKind::Prompt,
+                prompt_id,
+                field("prompt_id"),
+            ));
+        }
+    }
+
+    references
+}
+
+/// Credential the model calls its provider with
+pub fn model_references(model: &Model) -> Vec<Reference> {
+    vec![Reference::new(
+        ReferencedKind::Credential,
+        model.credential_id(),
+        "credential_id",
+    )]
+}
+
+/// Partials included by the prompt content
+pub fn prompt_references(prompt: &Prompt) -> Vec<Reference> {
+    partial_references(prompt.content())
+        .into_iter()
+        .map(|id| Reference::new(ReferencedKind::Prompt, id, "content"))
+        .collect()
+}
+
+/// Credential the knowledge base connects with
+pub fn knowledge_base_references(knowledge_base: &KnowledgeBase) -> Vec<Reference> {
+    knowledge_base
+        .connection_config()
+        .and_then(|config| config.get("credential_id"))
+        .map(|credential_id| {
+            vec![Reference::new(
+                ReferencedKind::Credential,
+                credential_id,
+                "connection_config.credential_id",
+            )]
+        })
+        .unwrap_or_default()
+}
+
+/// Model, system prompt and knowledge bases of an assistant
+pub fn assistant_references(assistant: &Assistant) -> Vec<Reference> {
+    let mut references = vec![
+        Reference::new(ReferencedKind::Model, assistant.model_id(), "model_id"),
+        Reference::new(ReferencedKind::Prompt, assistant.prompt_id(), "prompt_id"),
+    ];
+
+    for (index, binding) in assistant.knowledge_bases().iter().enumerate() {
+        references.push(Reference::new(
+            ReferencedKind::KnowledgeBase,
+            &binding.knowledge_base_id,
+            format!("knowledge_bases[{}].knowledge_base_id", index),
+        ));
+    }
+
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::assistant::{AssistantId, KnowledgeBaseBinding};
+    use crate::domain::experiment::{ExperimentId, Variant, VariantId};
+    use crate::domain::test_case::{ModelPromptInput, TestCaseId};
+    use crate::domain::workflow::{
+        ChatCompletionStep, HttpRequestStep, KnowledgeBaseSearchStep, WorkflowId, WorkflowStep,
+    };
+
+    #[test]
+    fn test_parse_referenced_kind() {
+        assert_eq!(
+            ReferencedKind::parse("prompt").unwrap(),
+            ReferencedKind::Prompt
+        );
+        assert_eq!(
+            ReferencedKind::parse("models").unwrap(),
+            ReferencedKind::Model
+        );
+        assert_eq!(
+            ReferencedKind::parse("knowledge-bases").unwrap(),
+            ReferencedKind::KnowledgeBase
+        );
+        assert_eq!(
+            ReferencedKind::parse("Credential").unwrap(),
+            ReferencedKind::Credential
+        );
+        assert!(ReferencedKind::parse("workflow").is_err());
+    }
+
+    #[test]
+    fn test_workflow_references_skip_variables() {
+        let workflow = Workflow::new(WorkflowId::new("support").unwrap(), "Support")
+            .with_step(WorkflowStep::new(
+                "search",
+                WorkflowStepType::KnowledgeBaseSearch(KnowledgeBaseSearchStep::new(
+                    "docs",
+                    "${request:question}",
+                )),
+            ))
+            .with_step(WorkflowStep::new(
+                "answer",
+                WorkflowStepType::ChatCompletion(ChatCompletionStep::new(
+                    "${request:model}",
+                    "answer-prompt",
+                )),
+            ))
+            .with_step(WorkflowStep::new(
+                "notify",
+                WorkflowStepType::HttpRequest(
+                    HttpRequestStep::new("slack").with_credential("slack-token"),
+                ),
+            ));
+
+        assert_eq!(
+            workflow_references(&workflow),
+            vec![
+                Reference::new(
+                    ReferencedKind::KnowledgeBase,
+                    "docs",
+                    "steps.search.knowledge_base_id"
+                ),
+                Reference::new(
+                    ReferencedKind::Prompt,
+                    "answer-prompt",
+                    "steps.answer.prompt_id"
+                ),
+                Reference::new(
+                    ReferencedKind::Credential,
+                    "slack-token",
+                    "steps.notify.credential_id"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_test_case_references() {
+        let test_case = TestCase::model_prompt(
+            TestCaseId::new("greeting-test").unwrap(),
+            "Greeting",
+            ModelPromptInput {
+                model_id: "gpt-4".to_string(),
+                prompt_id: Some("greeting".to_string()),
+                variables: Default::default(),
+                user_message: "Hi".to_string(),
+                temperature: None,
+                max_tokens: None,
+            },
+        );
+
+        let references = test_case_references(&test_case);
+        assert_eq!(references.len(), 2);
+        assert_eq!(references[0].kind, ReferencedKind::Model);
+        assert_eq!(references[1].id, "greeting");
+        assert_eq!(references[1].field, "input.prompt_id");
+    }
+
+    #[test]
+    fn test_experiment_references() {
+        let mut config = VariantConfig::config_override("gpt-4");
+
+        if let VariantConfig::ConfigOverride { prompt_id, .. } = &mut config {
+            *prompt_id = Some("support-v2".to_string());
+        }
+
+        let experiment = Experiment::new(ExperimentId::new("support-ab").unwrap(), "Support A/B")
+            .with_variant(Variant::new(
+                VariantId::new("control").unwrap(),
+                "Control",
+                VariantConfig::model_reference("gpt-3.5"),
+            ))
+            .with_variant(Variant::new(
+                VariantId::new("treatment").unwrap(),
+                "Treatment",
+                config,
+            ));
+
+        assert_eq!(
+            experiment_references(&experiment),
+            vec![
+                Reference::new(
+                    ReferencedKind::Model,
+                    "gpt-3.5",
+                    "variants.control.model_id"
+                ),
+                Reference::new(
+                    ReferencedKind::Model,
+                    "gpt-4",
+                    "variants.treatment.model_id"
+                ),
+                Reference::new(
+                    ReferencedKind::Prompt,
+                    "support-v2",
+                    "variants.treatment.prompt_id"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assistant_references() {
+        let assistant = Assistant::new(
+            AssistantId::new("support").unwrap(),
+            "Support",
+            "gpt-4",
+            "support-system",
+        )
+        .with_knowledge_bases(vec![KnowledgeBaseBinding::new("docs")]);
+
+        assert_eq!(
+            assistant_references(&assistant),
+            vec![
+                Reference::new(ReferencedKind::Model, "gpt-4", "model_id"),
+                Reference::new(ReferencedKind::Prompt, "support-system", "prompt_id"),
+                Reference::new(
+                    ReferencedKind::KnowledgeBase,
+                    "docs",
+                    "knowledge_bases[0].knowledge_base_id"
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,13 @@
+//! Cross-entity reference domain
+//!
+//! This module lists the prompts, models, knowledge bases and credentials
+//! each stored entity points to, so admins can see what depends on an entity
+//! before editing or deleting it.
+
+mod graph;
+
+pub use graph::{
+    Reference, ReferencedKind, ReferrerKind, assistant_references, experiment_references,
+    knowledge_base_references, model_references, prompt_references, test_case_references,
+    workflow_references,
+};
@@ -0,0 +1,9 @@
+//! Request trace domain
+//!
+//! This module holds what the gateway decided while serving a single v1
+//! request (routing, guardrail verdicts, provider calls and usage), so a
+//! request can be debugged from its `x-pmp-request-id`.
+
+mod trace;
+
+pub use trace::{RequestTrace, RequestTraceEvent, RequestTraceStage};
@@ -0,0 +1,88 @@
+//! Request trace entities
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Part of the request pipeline an event comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestTraceStage {
+    /// Experiment assignment, budget and canary fallbacks, provider selection
+    Routing,
+    /// Response cache lookups
+    Cache,
+    /// Injection guard, content policies and secret scanning
+    Guardrail,
+    /// Provider call outcome
+    Provider,
+    /// Recorded usage and cost
+    Usage,
+}
+
+/// A decision or outcome recorded while serving a request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RequestTraceEvent {
+    pub at: DateTime<Utc>,
+    pub stage: RequestTraceStage,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+/// Everything recorded about one request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RequestTrace {
+    pub request_id: String,
+    pub method: String,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_id: Option<String>,
+    /// Response status, once the handler returned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    pub started_at: DateTime<Utc>,
+    /// Time until the handler returned; streamed bodies continue after it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    pub events: Vec<RequestTraceEvent>,
+}
+
+impl RequestTrace {
+    pub fn new(
+        request_id: impl Into<String>,
+        method: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Self {
+        Self {
+            request_id: request_id.into(),
+            method: method.into(),
+            path: path.into(),
+            api_key_id: None,
+            status: None,
+            started_at: Utc::now(),
+            duration_ms: None,
+            events: Vec::new(),
+        }
+    }
+
+    /// Append an event
+    pub fn record(
+        &mut self,
+        stage: RequestTraceStage,
+        message: impl Into<String>,
+        data: Option<serde_json::Value>,
+    ) {
+        self.events.push(RequestTraceEvent {
+            at: Utc::now(),
+            stage,
+            message: message.into(),
+            data,
+        });
+    }
+
+    /// Events of a stage, in order
+    pub fn events_of(&self, stage: RequestTraceStage) -> impl Iterator<Item = &RequestTraceEvent> {
+        self.events.iter().filter(move |e| e.stage == stage)
+    }
+}
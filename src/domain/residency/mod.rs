@@ -0,0 +1,8 @@
+//! Data residency domain
+//!
+//! This module provides the region type used to pin teams to the provider
+//! endpoints of their allowed regions.
+
+mod region;
+
+pub use region::{Region, RegionError, region_allowed};
@@ -0,0 +1,149 @@
+//! Provider regions
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Longest region name accepted
+const MAX_REGION_LEN: usize = 32;
+
+/// Errors that can occur when parsing a region
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum RegionError {
+    #[error("Region cannot be empty")]
+    Empty,
+
+    #[error("Region '{0}' cannot exceed 32 characters")]
+    TooLong(String),
+
+    #[error("Region '{0}' can only contain letters, digits and hyphens")]
+    InvalidCharacters(String),
+}
+
+/// A geographic region a provider endpoint serves from (e.g. `eu`,
+/// `eu-west-1`, `us`). Names are case-insensitive and stored in lowercase;
+/// hyphenated segments narrow a region, so `eu` covers `eu-west-1`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Region(String);
+
+impl Region {
+    /// Create a region after validation
+    pub fn new(region: impl AsRef<str>) -> Result<Self, RegionError> {
+        let region = region.as_ref().trim().to_lowercase();
+
+        if region.is_empty() {
+            return Err(RegionError::Empty);
+        }
+
+        if region.len() > MAX_REGION_LEN {
+            return Err(RegionError::TooLong(region));
+        }
+
+        if !region
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+            || region.starts_with('-')
+            || region.ends_with('-')
+        {
+            return Err(RegionError::InvalidCharacters(region));
+        }
+
+        Ok(Self(region))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Check if this region is the other region or contains it
+    pub fn covers(&self, other: &Region) -> bool {
+        other.0 == self.0
+            || other
+                .0
+                .strip_prefix(self.0.as_str())
+                .is_some_and(|rest| rest.starts_with('-'))
+    }
+}
+
+impl FromStr for Region {
+    type Err = RegionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for Region {
+    type Error = RegionError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Region> for String {
+    fn from(region: Region) -> Self {
+        region.0
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Check the region of a provider endpoint against the allowed regions. An
+/// empty list allows everything; a non-empty list rejects endpoints without a
+/// known region.
+pub fn region_allowed(allowed: &[Region], region: Option<&Region>) -> bool {
+    if allowed.is_empty() {
+        return true;
+    }
+
+    region.is_some_and(|region| allowed.iter().any(|a| a.covers(region)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(s: &str) -> Region {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_region() {
+        assert_eq!(region(" EU-West-1 ").as_str(), "eu-west-1");
+        assert_eq!(Region::new(""), Err(RegionError::Empty));
+        assert!(Region::new("eu_west").is_err());
+        assert!(Region::new("-eu").is_err());
+        assert!(Region::new("a".repeat(33)).is_err());
+
+        let parsed: Region = serde_json::from_str("\"US\"").unwrap();
+        assert_eq!(parsed, region("us"));
+        assert!(serde_json::from_str::<Region>("\"u s\"").is_err());
+    }
+
+    #[test]
+    fn test_region_covers() {
+        assert!(region("eu").covers(&region("eu")));
+        assert!(region("eu").covers(&region("eu-west-1")));
+        assert!(!region("eu-west-1").covers(&region("eu")));
+        assert!(!region("eu").covers(&region("europe")));
+    }
+
+    #[test]
+    fn test_region_allowed() {
+        assert!(region_allowed(&[], None));
+        assert!(region_allowed(
+            &[region("eu")],
+            Some(&region("eu-central-1"))
+        ));
+        assert!(!region_allowed(&[region("eu")], Some(&region("us-east-1"))));
+        assert!(!region_allowed(&[region("eu")], None));
+    }
+}
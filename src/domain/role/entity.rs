@@ -0,0 +1,449 @@
+//! Role entity and related types
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::permission::{Permission, PermissionAction, PermissionResource};
+use super::validation::{validate_role_id, validate_role_name, RoleValidationError};
+use crate::domain::storage::{StorageEntity, StorageKey};
+use crate::domain::team::TeamRole;
+
+/// Role identifier - lowercase alphanumeric + hyphens, max 50 characters
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RoleId(String);
+
+impl RoleId {
+    /// Built-in role with every permission
+    pub const OWNER: &'static str = "owner";
+    /// Built-in role with every permission except role management
+    pub const ADMIN: &'static str = "admin";
+    /// Built-in role that can manage gateway resources but not access or billing
+    pub const EDITOR: &'static str = "editor";
+    /// Built-in read-only role
+    pub const VIEWER: &'static str = "viewer";
+    /// Built-in role managing usage and budgets
+    pub const BILLING_ADMIN: &'static str = "billing-admin";
+    /// Built-in role managing API keys
+    pub const KEY_MANAGER: &'static str = "key-manager";
+
+    /// Create a new RoleId after validation
+    pub fn new(id: impl Into<String>) -> Result<Self, RoleValidationError> {
+        let id = id.into();
+        validate_role_id(&id)?;
+        Ok(Self(id))
+    }
+
+    /// Get the inner string value
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Default role for users without an explicitly assigned role
+    pub fn for_team_role(team_role: TeamRole) -> Self {
+        let id = match team_role {
+            TeamRole::Owner => Self::OWNER,
+            TeamRole::Admin => Self::ADMIN,
+            TeamRole::Member => Self::EDITOR,
+        };
+
+        Self(id.to_string())
+    }
+}
+
+impl TryFrom<String> for RoleId {
+    type Error = RoleValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<RoleId> for String {
+    fn from(id: RoleId) -> Self {
+        id.0
+    }
+}
+
+impl std::fmt::Display for RoleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StorageKey for RoleId {
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Role entity - a named set of resource permissions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Role {
+    /// Unique identifier
+    id: RoleId,
+    /// Display name
+    name: String,
+    /// Description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    /// Granted permissions
+    permissions: Vec<Permission>,
+    /// Whether this is a built-in (immutable) role
+    #[serde(default)]
+    built_in: bool,
+    /// Creation timestamp
+    created_at: DateTime<Utc>,
+    /// Last update timestamp
+    updated_at: DateTime<Utc>,
+}
+
+impl Role {
+    /// Create a new custom role
+    pub fn new(
+        id: RoleId,
+        name: impl Into<String>,
+        permissions: Vec<Permission>,
+    ) -> Result<Self, RoleValidationError> {
+        let name = name.into();
+        validate_role_name(&name)?;
+        let now = Utc::now();
+
+        Ok(Self {
+            id,
+            name,
+            description: None,
+            permissions: dedup_permissions(permissions),
+            built_in: false,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    fn built_in(id: &str, name: &str, description: &str, permissions: Vec<Permission>) -> Self {
+        let now = Utc::now();
+
+        Self {
+            id: RoleId(id.to_string()),
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            permissions,
+            built_in: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// All built-in roles
+    pub fn builtin_roles() -> Vec<Role> {
+        use PermissionResource as R;
+
+        let admin_permissions = PermissionResource::all()
+            .iter()
+            .map(|r| match r {
+                R::Roles | R::AuditLogs => Permission::read(*r),
+                _ => Permission::write(*r),
+            })
+            .collect();
+
+        let editor_permissions = grant(PermissionAction::Write, &[
+            R::Models,
+            R::Prompts,
+            R::Workflows,
+            R::ExternalApis,
+            R::KnowledgeBases,
+            R::Experiments,
+            R::TestCases,
+        ])
+        .chain(grant(
+            PermissionAction::Read,
+            &[
+                R::Credentials,
+                R::Teams,
+                R::Organizations,
+                R::ExecutionLogs,
+                R::Webhooks,
+            ],
+        ))
+        .collect();
+
+        let billing_permissions = grant(PermissionAction::Write, &[R::Usage, R::Budgets])
+            .chain(grant(
+                PermissionAction::Read,
+                &[R::Teams, R::Organizations, R::ApiKeys, R::Models, R::ExecutionLogs],
+            ))
+            .collect();
+
+        let key_manager_permissions =
+            grant(PermissionAction::Write, &[R::ApiKeys, R::ServiceAccounts])
+                .chain(grant(
+                    PermissionAction::Read,
+                    &[R::Teams, R::Models, R::Prompts, R::Workflows],
+                ))
+                .collect();
+
+        vec![
+            Self::built_in(
+                RoleId::OWNER,
+                "Owner",
+                "Full access to every admin resource",
+                vec![Permission::write(R::All)],
+            ),
+            Self::built_in(
+                RoleId::ADMIN,
+                "Admin",
+                "Full access except managing role definitions",
+                admin_permissions,
+            ),
+            Self::built_in(
+                RoleId::EDITOR,
+                "Editor",
+                "Manage models, prompts, workflows and knowledge bases",
+                editor_permissions,
+            ),
+            Self::built_in(
+                RoleId::VIEWER,
+                "Viewer",
+                "Read-only access to every admin resource",
+                vec![Permission::read(R::All)],
+            ),
+            Self::built_in(
+                RoleId::BILLING_ADMIN,
+                "Billing Admin",
+                "Manage usage records and budgets",
+                billing_permissions,
+            ),
+            Self::built_in(
+                RoleId::KEY_MANAGER,
+                "Key Manager",
+                "Create, rotate and revoke API keys and service accounts",
+                key_manager_permissions,
+            ),
+        ]
+    }
+
+    /// Find a built-in role by ID
+    pub fn builtin(id: &str) -> Option<Role> {
+        Self::builtin_roles()
+            .into_iter()
+            .find(|role| role.id.as_str() == id)
+    }
+
+    /// Check if an ID belongs to a built-in role
+    pub fn is_builtin_id(id: &str) -> bool {
+        Self::builtin(id).is_some()
+    }
+
+    /// Set description (builder pattern)
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    // Getters
+
+    pub fn id(&self) -> &RoleId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn permissions(&self) -> &[Permission] {
+        &self.permissions
+    }
+
+    pub fn is_built_in(&self) -> bool {
+        self.built_in
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    /// Check if this role grants the required permission
+    pub fn allows(&self, required: &Permission) -> bool {
+        self.permissions.iter().any(|p| p.grants(required))
+    }
+
+    // Mutators
+
+    /// Update the name
+    pub fn set_name(&mut self, name: impl Into<String>) -> Result<(), RoleValidationError> {
+        let name = name.into();
+        validate_role_name(&name)?;
+        self.name = name;
+        self.touch();
+        Ok(())
+    }
+
+    /// Update the description
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.description = description;
+        self.touch();
+    }
+
+    /// Replace the granted permissions
+    pub fn set_permissions(&mut self, permissions: Vec<Permission>) {
+        self.permissions = dedup_permissions(permissions);
+        self.touch();
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+    }
+}
+
+fn grant(
+    action: PermissionAction,
+    resources: &[PermissionResource],
+) -> impl Iterator<Item = Permission> + '_ {
+    resources.iter().map(move |r| Permission::new(*r, action))
+}
+
+fn dedup_permissions(permissions: Vec<Permission>) -> Vec<Permission> {
+    let mut result: Vec<Permission> = Vec::with_capacity(permissions.len());
+
+    for permission in permissions {
+        if !result.contains(&permission) {
+            result.push(permission);
+        }
+    }
+
+    result
+}
+
+impl StorageEntity for Role {
+    type Key = RoleId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_id() {
+        assert_eq!(RoleId::new("viewer").unwrap().as_str(), "viewer");
+        assert!(RoleId::new("Bad_Role").is_err());
+    }
+
+    #[test]
+    fn test_role_id_for_team_role() {
+        assert_eq!(RoleId::for_team_role(TeamRole::Owner).as_str(), "owner");
+        assert_eq!(RoleId::for_team_role(TeamRole::Admin).as_str(), "admin");
+        assert_eq!(RoleId::for_team_role(TeamRole::Member).as_str(), "editor");
+    }
+
+    #[test]
+    fn test_custom_role() {
+        let role = Role::new(
+            RoleId::new("prompt-author").unwrap(),
+            "Prompt Author",
+            vec![
+                Permission::write(PermissionResource::Prompts),
+                Permission::write(PermissionResource::Prompts),
+                Permission::read(PermissionResource::Models),
+            ],
+        )
+        .unwrap();
+
+        assert!(!role.is_built_in());
+        assert_eq!(role.permissions().len(), 2);
+        assert!(role.allows(&Permission::write(PermissionResource::Prompts)));
+        assert!(role.allows(&Permission::read(PermissionResource::Models)));
+        assert!(!role.allows(&Permission::write(PermissionResource::Models)));
+        assert!(!role.allows(&Permission::read(PermissionResource::ApiKeys)));
+    }
+
+    #[test]
+    fn test_custom_role_invalid_name() {
+        assert!(Role::new(RoleId::new("x").unwrap(), "", vec![]).is_err());
+    }
+
+    #[test]
+    fn test_builtin_roles() {
+        let roles = Role::builtin_roles();
+        assert_eq!(roles.len(), 6);
+        assert!(roles.iter().all(|r| r.is_built_in()));
+
+        let owner = Role::builtin(RoleId::OWNER).unwrap();
+        for resource in PermissionResource::all() {
+            assert!(owner.allows(&Permission::write(*resource)));
+        }
+
+        let admin = Role::builtin(RoleId::ADMIN).unwrap();
+        assert!(admin.allows(&Permission::write(PermissionResource::Credentials)));
+        assert!(admin.allows(&Permission::read(PermissionResource::Roles)));
+        assert!(!admin.allows(&Permission::write(PermissionResource::Roles)));
+
+        let viewer = Role::builtin(RoleId::VIEWER).unwrap();
+        assert!(viewer.allows(&Permission::read(PermissionResource::Budgets)));
+        assert!(!viewer.allows(&Permission::new(
+            PermissionResource::Budgets,
+            PermissionAction::Write
+        )));
+
+        let editor = Role::builtin(RoleId::EDITOR).unwrap();
+        assert!(editor.allows(&Permission::write(PermissionResource::Workflows)));
+        assert!(!editor.allows(&Permission::write(PermissionResource::ApiKeys)));
+        assert!(!editor.allows(&Permission::read(PermissionResource::Budgets)));
+
+        let billing = Role::builtin(RoleId::BILLING_ADMIN).unwrap();
+        assert!(billing.allows(&Permission::write(PermissionResource::Budgets)));
+        assert!(!billing.allows(&Permission::write(PermissionResource::Models)));
+
+        let keys = Role::builtin(RoleId::KEY_MANAGER).unwrap();
+        assert!(keys.allows(&Permission::write(PermissionResource::ApiKeys)));
+        assert!(keys.allows(&Permission::write(PermissionResource::ServiceAccounts)));
+        assert!(!keys.allows(&Permission::write(PermissionResource::Credentials)));
+    }
+
+    #[test]
+    fn test_is_builtin_id() {
+        assert!(Role::is_builtin_id("viewer"));
+        assert!(!Role::is_builtin_id("custom"));
+    }
+
+    #[test]
+    fn test_role_mutators() {
+        let mut role = Role::new(RoleId::new("custom").unwrap(), "Custom", vec![]).unwrap();
+
+        role.set_name("Renamed").unwrap();
+        role.set_description(Some("desc".to_string()));
+        role.set_permissions(vec![Permission::read(PermissionResource::All)]);
+
+        assert_eq!(role.name(), "Renamed");
+        assert_eq!(role.description(), Some("desc"));
+        assert!(role.allows(&Permission::read(PermissionResource::Webhooks)));
+    }
+
+    #[test]
+    fn test_role_serialization() {
+        let role = Role::new(
+            RoleId::new("custom").unwrap(),
+            "Custom",
+            vec![Permission::write(PermissionResource::Models)],
+        )
+        .unwrap();
+
+        let json = serde_json::to_value(&role).unwrap();
+        assert_eq!(json["permissions"][0], "models:write");
+
+        let parsed: Role = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.id().as_str(), "custom");
+        assert_eq!(parsed.permissions().len(), 1);
+    }
+}
@@ -0,0 +1,15 @@
+//! Role domain module
+//!
+//! Roles are named sets of resource permissions used to authorize admin API
+//! access. A set of built-in roles is always available; custom roles can be
+//! composed by administrators.
+
+mod entity;
+mod permission;
+mod repository;
+mod validation;
+
+pub use entity::{Role, RoleId};
+pub use permission::{Permission, PermissionAction, PermissionResource};
+pub use repository::RoleRepository;
+pub use validation::{validate_role_id, validate_role_name, RoleValidationError};
@@ -0,0 +1,373 @@
+//! Resource permissions for admin API authorization
+
+use serde::{Deserialize, Serialize};
+
+use super::validation::RoleValidationError;
+
+/// Admin resource a permission applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PermissionResource {
+    /// Wildcard matching every resource
+    All,
+    Models,
+    Prompts,
+    Workflows,
+    Assistants,
+    McpTools,
+    ApiKeys,
+    ServiceAccounts,
+    Teams,
+    Organizations,
+    Users,
+    Roles,
+    Credentials,
+    ExternalApis,
+    KnowledgeBases,
+    Usage,
+    Budgets,
+    Pricing,
+    Slo,
+    Stats,
+    Experiments,
+    TestCases,
+    TestSuites,
+    Datasets,
+    FineTunes,
+    Canaries,
+    Playground,
+    ContentPolicies,
+    TransformRules,
+    Privacy,
+    Config,
+    ExecutionLogs,
+    Requests,
+    Webhooks,
+    AuditLogs,
+}
+
+impl PermissionResource {
+    /// All concrete resources (excluding the wildcard)
+    pub fn all() -> &'static [PermissionResource] {
+        &[
+            Self::Models,
+            Self::Prompts,
+            Self::Workflows,
+            Self::Assistants,
+            Self::McpTools,
+            Self::ApiKeys,
+            Self::ServiceAccounts,
+            Self::Teams,
+            Self::Organizations,
+            Self::Users,
+            Self::Roles,
+            Self::Credentials,
+            Self::ExternalApis,
+            Self::KnowledgeBases,
+            Self::Usage,
+            Self::Budgets,
+            Self::Pricing,
+            Self::Slo,
+            Self::Stats,
+            Self::Experiments,
+            Self::TestCases,
+            Self::TestSuites,
+            Self::Datasets,
+            Self::FineTunes,
+            Self::Canaries,
+            Self::Playground,
+            Self::ContentPolicies,
+            Self::TransformRules,
+            Self::Privacy,
+            Self::Config,
+            Self::ExecutionLogs,
+            Self::Requests,
+            Self::Webhooks,
+            Self::AuditLogs,
+        ]
+    }
+
+    /// Identifier used in permission strings
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::All => "*",
+            Self::Models => "models",
+            Self::Prompts => "prompts",
+            Self::Workflows => "workflows",
+            Self::Assistants => "assistants",
+            Self::McpTools => "mcp_tools",
+            Self::ApiKeys => "api_keys",
+            Self::ServiceAccounts => "service_accounts",
+            Self::Teams => "teams",
+            Self::Organizations => "organizations",
+            Self::Users => "users",
+            Self::Roles => "roles",
+            Self::Credentials => "credentials",
+            Self::ExternalApis => "external_apis",
+            Self::KnowledgeBases => "knowledge_bases",
+            Self::Usage => "usage",
+            Self::Budgets => "budgets",
+            Self::Pricing => "pricing",
+            Self::Slo => "slo",
+            Self::Stats => "stats",
+            Self::Experiments => "experiments",
+            Self::TestCases => "test_cases",
+            Self::TestSuites => "test_suites",
+            Self::Datasets => "datasets",
+            Self::FineTunes => "fine_tunes",
+            Self::Canaries => "canaries",
+            Self::Playground => "playground",
+            Self::ContentPolicies => "content_policies",
+            Self::TransformRules => "transform_rules",
+            Self::Privacy => "privacy",
+            Self::Config => "config",
+            Self::ExecutionLogs => "execution_logs",
+            Self::Requests => "requests",
+            Self::Webhooks => "webhooks",
+            Self::AuditLogs => "audit_logs",
+        }
+    }
+
+    /// Parse a resource identifier
+    pub fn parse(s: &str) -> Option<Self> {
+        if s == "*" {
+            return Some(Self::All);
+        }
+
+        Self::all().iter().copied().find(|r| r.as_str() == s)
+    }
+
+    /// Map the first segment of an admin route path (e.g. `api-keys`) to a resource
+    pub fn from_path_segment(segment: &str) -> Option<Self> {
+        let normalized = segment.replace('-', "_");
+        Self::all().iter().copied().find(|r| r.as_str() == normalized)
+    }
+}
+
+impl std::fmt::Display for PermissionResource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Action a permission allows on a resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PermissionAction {
+    /// Read-only access (GET requests)
+    Read,
+    /// Mutating access (create, update, delete, execute). Implies read.
+    Write,
+}
+
+impl PermissionAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Write => "write",
+        }
+    }
+
+    /// Check if this action covers the required action
+    pub fn covers(&self, required: PermissionAction) -> bool {
+        matches!(
+            (self, required),
+            (Self::Write, _) | (Self::Read, Self::Read)
+        )
+    }
+}
+
+impl std::fmt::Display for PermissionAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A permission on an admin resource, serialized as `<resource>:<action>`
+/// (e.g. `models:write`, `*:read`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Permission {
+    pub resource: PermissionResource,
+    pub action: PermissionAction,
+}
+
+impl Permission {
+    pub fn new(resource: PermissionResource, action: PermissionAction) -> Self {
+        Self { resource, action }
+    }
+
+    pub fn read(resource: PermissionResource) -> Self {
+        Self::new(resource, PermissionAction::Read)
+    }
+
+    pub fn write(resource: PermissionResource) -> Self {
+        Self::new(resource, PermissionAction::Write)
+    }
+
+    /// Parse a permission string such as `models:write`
+    pub fn parse(s: &str) -> Result<Self, RoleValidationError> {
+        let invalid = || RoleValidationError::InvalidPermission(s.to_string());
+        let (resource, action) = s.split_once(':').ok_or_else(invalid)?;
+
+        let resource = PermissionResource::parse(resource.trim()).ok_or_else(invalid)?;
+        let action = match action.trim() {
+            "read" => PermissionAction::Read,
+            "write" => PermissionAction::Write,
+            _ => return Err(invalid()),
+        };
+
+        Ok(Self::new(resource, action))
+    }
+
+    /// Check if this permission satisfies a required permission
+    pub fn grants(&self, required: &Permission) -> bool {
+        let resource_matches =
+            self.resource == PermissionResource::All || self.resource == required.resource;
+
+        resource_matches && self.action.covers(required.action)
+    }
+}
+
+impl std::fmt::Display for Permission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.resource, self.action)
+    }
+}
+
+impl TryFrom<String> for Permission {
+    type Error = RoleValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(&value)
+    }
+}
+
+impl From<Permission> for String {
+    fn from(permission: Permission) -> Self {
+        permission.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_permission() {
+        let p = Permission::parse("models:write").unwrap();
+        assert_eq!(p.resource, PermissionResource::Models);
+        assert_eq!(p.action, PermissionAction::Write);
+
+        let p = Permission::parse("*:read").unwrap();
+        assert_eq!(p.resource, PermissionResource::All);
+        assert_eq!(p.action, PermissionAction::Read);
+    }
+
+    #[test]
+    fn test_parse_invalid_permission() {
+        assert!(Permission::parse("models").is_err());
+        assert!(Permission::parse("models:delete").is_err());
+        assert!(Permission::parse("unknown:read").is_err());
+    }
+
+    #[test]
+    fn test_permission_roundtrip() {
+        for resource in PermissionResource::all() {
+            let p = Permission::write(*resource);
+            assert_eq!(Permission::parse(&p.to_string()).unwrap(), p);
+        }
+    }
+
+    #[test]
+    fn test_permission_serde() {
+        let p = Permission::read(PermissionResource::ApiKeys);
+        let json = serde_json::to_string(&p).unwrap();
+        assert_eq!(json, "\"api_keys:read\"");
+
+        let parsed: Permission = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, p);
+
+        assert!(serde_json::from_str::<Permission>("\"bogus\"").is_err());
+    }
+
+    #[test]
+    fn test_grants() {
+        let write = Permission::write(PermissionResource::Models);
+        assert!(write.grants(&Permission::read(PermissionResource::Models)));
+        assert!(write.grants(&Permission::write(PermissionResource::Models)));
+        assert!(!write.grants(&Permission::read(PermissionResource::Prompts)));
+
+        let read = Permission::read(PermissionResource::Models);
+        assert!(read.grants(&Permission::read(PermissionResource::Models)));
+        assert!(!read.grants(&Permission::write(PermissionResource::Models)));
+
+        let all_read = Permission::read(PermissionResource::All);
+        assert!(all_read.grants(&Permission::read(PermissionResource::Budgets)));
+        assert!(!all_read.grants(&Permission::write(PermissionResource::Budgets)));
+    }
+
+    #[test]
+    fn test_from_path_segment() {
+        assert_eq!(
+            PermissionResource::from_path_segment("api-keys"),
+            Some(PermissionResource::ApiKeys)
+        );
+        assert_eq!(
+            PermissionResource::from_path_segment("knowledge-bases"),
+            Some(PermissionResource::KnowledgeBases)
+        );
+        assert_eq!(
+            PermissionResource::from_path_segment("models"),
+            Some(PermissionResource::Models)
+        );
+        assert_eq!(
+            PermissionResource::from_path_segment("audit-logs"),
+            Some(PermissionResource::AuditLogs)
+        );
+        assert_eq!(
+            PermissionResource::from_path_segment("test-suites"),
+            Some(PermissionResource::TestSuites)
+        );
+        assert_eq!(
+            PermissionResource::from_path_segment("datasets"),
+            Some(PermissionResource::Datasets)
+        );
+        assert_eq!(
+            PermissionResource::from_path_segment("fine-tunes"),
+            Some(PermissionResource::FineTunes)
+        );
+        assert_eq!(
+            PermissionResource::from_path_segment("assistants"),
+            Some(PermissionResource::Assistants)
+        );
+        assert_eq!(
+            PermissionResource::from_path_segment("mcp-tools"),
+            Some(PermissionResource::McpTools)
+        );
+        assert_eq!(
+            PermissionResource::from_path_segment("canaries"),
+            Some(PermissionResource::Canaries)
+        );
+        assert_eq!(
+            PermissionResource::from_path_segment("playground"),
+            Some(PermissionResource::Playground)
+        );
+        assert_eq!(
+            PermissionResource::from_path_segment("content-policies"),
+            Some(PermissionResource::ContentPolicies)
+        );
+        assert_eq!(
+            PermissionResource::from_path_segment("transform-rules"),
+            Some(PermissionResource::TransformRules)
+        );
+        assert_eq!(
+            PermissionResource::from_path_segment("privacy"),
+            Some(PermissionResource::Privacy)
+        );
+        assert_eq!(
+            PermissionResource::from_path_segment("requests"),
+            Some(PermissionResource::Requests)
+        );
+        assert_eq!(PermissionResource::from_path_segment("unknown"), None);
+        assert_eq!(PermissionResource::from_path_segment("*"), None);
+    }
+}
@@ -0,0 +1,142 @@
+//! Role repository trait
+
+use async_trait::async_trait;
+
+use super::entity::{Role, RoleId};
+use crate::domain::DomainError;
+
+/// Repository for managing custom roles
+#[async_trait]
+pub trait RoleRepository: Send + Sync + std::fmt::Debug {
+    /// Get a role by ID
+    async fn get(&self, id: &RoleId) -> Result<Option<Role>, DomainError>;
+
+    /// Create a new role
+    async fn create(&self, role: Role) -> Result<Role, DomainError>;
+
+    /// Update an existing role
+    async fn update(&self, role: Role) -> Result<Role, DomainError>;
+
+    /// Delete a role by ID
+    async fn delete(&self, id: &RoleId) -> Result<bool, DomainError>;
+
+    /// List all roles
+    async fn list(&self) -> Result<Vec<Role>, DomainError>;
+
+    /// Check if a role exists
+    async fn exists(&self, id: &RoleId) -> Result<bool, DomainError>;
+}
+
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::RwLock;
+
+    /// Mock implementation for testing
+    #[derive(Debug, Default)]
+    pub struct MockRoleRepository {
+        roles: RwLock<HashMap<String, Role>>,
+    }
+
+    impl MockRoleRepository {
+        pub fn new() -> Self {
+            Self::default()
+        }
+    }
+
+    #[async_trait]
+    impl RoleRepository for MockRoleRepository {
+        async fn get(&self, id: &RoleId) -> Result<Option<Role>, DomainError> {
+            let roles = self.roles.read().unwrap();
+            Ok(roles.get(id.as_str()).cloned())
+        }
+
+        async fn create(&self, role: Role) -> Result<Role, DomainError> {
+            let mut roles = self.roles.write().unwrap();
+
+            if roles.contains_key(role.id().as_str()) {
+                return Err(DomainError::conflict(format!(
+                    "Role '{}' already exists",
+                    role.id()
+                )));
+            }
+
+            roles.insert(role.id().as_str().to_string(), role.clone());
+            Ok(role)
+        }
+
+        async fn update(&self, role: Role) -> Result<Role, DomainError> {
+            let mut roles = self.roles.write().unwrap();
+
+            if !roles.contains_key(role.id().as_str()) {
+                return Err(DomainError::not_found(format!(
+                    "Role '{}' not found",
+                    role.id()
+                )));
+            }
+
+            roles.insert(role.id().as_str().to_string(), role.clone());
+            Ok(role)
+        }
+
+        async fn delete(&self, id: &RoleId) -> Result<bool, DomainError> {
+            let mut roles = self.roles.write().unwrap();
+            Ok(roles.remove(id.as_str()).is_some())
+        }
+
+        async fn list(&self) -> Result<Vec<Role>, DomainError> {
+            let roles = self.roles.read().unwrap();
+            let mut result: Vec<Role> = roles.values().cloned().collect();
+            result.sort_by(|a, b| a.id().as_str().cmp(b.id().as_str()));
+            Ok(result)
+        }
+
+        async fn exists(&self, id: &RoleId) -> Result<bool, DomainError> {
+            let roles = self.roles.read().unwrap();
+            Ok(roles.contains_key(id.as_str()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::MockRoleRepository;
+    use super::*;
+
+    #[tokio::test]
+    async fn test_mock_create_and_get() {
+        let repo = MockRoleRepository::new();
+        let id = RoleId::new("custom").unwrap();
+        let role = Role::new(id.clone(), "Custom", vec![]).unwrap();
+
+        repo.create(role).await.unwrap();
+
+        let fetched = repo.get(&id).await.unwrap();
+        assert_eq!(fetched.unwrap().name(), "Custom");
+    }
+
+    #[tokio::test]
+    async fn test_mock_create_duplicate() {
+        let repo = MockRoleRepository::new();
+        let id = RoleId::new("custom").unwrap();
+
+        repo.create(Role::new(id.clone(), "A", vec![]).unwrap())
+            .await
+            .unwrap();
+        let result = repo.create(Role::new(id, "B", vec![]).unwrap()).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_delete() {
+        let repo = MockRoleRepository::new();
+        let id = RoleId::new("custom").unwrap();
+
+        repo.create(Role::new(id.clone(), "Custom", vec![]).unwrap())
+            .await
+            .unwrap();
+        assert!(repo.delete(&id).await.unwrap());
+        assert!(!repo.exists(&id).await.unwrap());
+    }
+}
@@ -0,0 +1,111 @@
+//! Role validation
+
+use thiserror::Error;
+
+/// Errors that can occur during role validation
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum RoleValidationError {
+    #[error("Role ID cannot be empty")]
+    EmptyId,
+
+    #[error("Role ID cannot exceed {0} characters")]
+    IdTooLong(usize),
+
+    #[error("Role ID can only contain lowercase alphanumeric characters and hyphens")]
+    InvalidIdCharacters,
+
+    #[error("Role ID cannot start or end with a hyphen")]
+    InvalidIdFormat,
+
+    #[error("Role name cannot be empty")]
+    EmptyName,
+
+    #[error("Role name cannot exceed {0} characters")]
+    NameTooLong(usize),
+
+    #[error("Invalid permission '{0}', expected '<resource>:<read|write>'")]
+    InvalidPermission(String),
+}
+
+const MAX_ROLE_ID_LENGTH: usize = 50;
+const MAX_ROLE_NAME_LENGTH: usize = 100;
+
+/// Validate a role ID
+pub fn validate_role_id(id: &str) -> Result<(), RoleValidationError> {
+    if id.is_empty() {
+        return Err(RoleValidationError::EmptyId);
+    }
+
+    if id.len() > MAX_ROLE_ID_LENGTH {
+        return Err(RoleValidationError::IdTooLong(MAX_ROLE_ID_LENGTH));
+    }
+
+    if !id
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err(RoleValidationError::InvalidIdCharacters);
+    }
+
+    if id.starts_with('-') || id.ends_with('-') {
+        return Err(RoleValidationError::InvalidIdFormat);
+    }
+
+    Ok(())
+}
+
+/// Validate a role name
+pub fn validate_role_name(name: &str) -> Result<(), RoleValidationError> {
+    if name.trim().is_empty() {
+        return Err(RoleValidationError::EmptyName);
+    }
+
+    if name.len() > MAX_ROLE_NAME_LENGTH {
+        return Err(RoleValidationError::NameTooLong(MAX_ROLE_NAME_LENGTH));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_role_id() {
+        assert!(validate_role_id("viewer").is_ok());
+        assert!(validate_role_id("billing-admin").is_ok());
+        assert!(validate_role_id("role-2").is_ok());
+    }
+
+    #[test]
+    fn test_invalid_role_id() {
+        assert_eq!(validate_role_id(""), Err(RoleValidationError::EmptyId));
+        assert_eq!(
+            validate_role_id("Viewer"),
+            Err(RoleValidationError::InvalidIdCharacters)
+        );
+        assert_eq!(
+            validate_role_id("my_role"),
+            Err(RoleValidationError::InvalidIdCharacters)
+        );
+        assert_eq!(
+            validate_role_id("-role"),
+            Err(RoleValidationError::InvalidIdFormat)
+        );
+        assert_eq!(
+            validate_role_id(&"a".repeat(51)),
+            Err(RoleValidationError::IdTooLong(50))
+        );
+    }
+
+    #[test]
+    fn test_role_name() {
+        assert!(validate_role_name("Viewer").is_ok());
+        assert_eq!(validate_role_name("  "), Err(RoleValidationError::EmptyName));
+        assert_eq!(
+            validate_role_name(&"a".repeat(101)),
+            Err(RoleValidationError::NameTooLong(100))
+        );
+    }
+}
@@ -4,6 +4,8 @@ use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 
+use crate::domain::embedding::DistanceMetric;
+
 /// Configuration for semantic caching
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SemanticCacheConfig {
@@ -28,6 +30,11 @@ pub struct SemanticCacheConfig {
     #[serde(default = "default_embedding_model")]
     pub embedding_model: String,
 
+    /// Metric comparing query embeddings, the similarity threshold is on
+    /// its scale
+    #[serde(default)]
+    pub distance_metric: DistanceMetric,
+
     /// Namespace prefix for cache entries
     #[serde(default = "default_namespace")]
     pub namespace: String,
@@ -81,6 +88,7 @@ impl Default for SemanticCacheConfig {
             max_entries: default_max_entries(),
             ttl_secs: default_ttl_secs(),
             embedding_model: default_embedding_model(),
+            distance_metric: DistanceMetric::default(),
             namespace: default_namespace(),
             cache_streaming: false,
             include_model_in_key: default_true(),
@@ -130,6 +138,12 @@ impl SemanticCacheConfig {
         self
     }
 
+    /// Set the distance metric
+    pub fn with_distance_metric(mut self, metric: DistanceMetric) -> Self {
+        self.distance_metric = metric;
+        self
+    }
+
     /// Set the namespace
     pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
         self.namespace = namespace.into();
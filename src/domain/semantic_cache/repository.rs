@@ -6,6 +6,7 @@ use std::time::{Duration, SystemTime};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
+use crate::domain::embedding::DistanceMetric;
 use crate::domain::DomainError;
 
 /// A cached entry in the semantic cache
@@ -144,6 +145,12 @@ impl CachedEntry {
         self.hit_count += 1;
     }
 
+    /// Set the hit count, for backends counting hits apart from the entry
+    pub fn with_hit_count(mut self, hit_count: u32) -> Self {
+        self.hit_count = hit_count;
+        self
+    }
+
     /// Deserialize the cached value
     pub fn deserialize_value<T: for<'de> Deserialize<'de>>(&self) -> Result<T, DomainError> {
         serde_json::from_str(&self.value).map_err(|e| {
@@ -207,6 +214,8 @@ pub struct SemanticSearchParams {
     pub min_similarity: f32,
     /// Maximum results to return
     pub limit: usize,
+    /// Metric the similarity is computed with
+    pub metric: DistanceMetric,
 }
 
 impl Default for SemanticSearchParams {
@@ -216,6 +225,7 @@ impl Default for SemanticSearchParams {
             temperature: None,
             min_similarity: 0.95,
             limit: 1,
+            metric: DistanceMetric::default(),
         }
     }
 }
@@ -246,6 +256,26 @@ impl SemanticSearchParams {
         self.limit = limit;
         self
     }
+
+    /// Set the distance metric
+    pub fn with_metric(mut self, metric: DistanceMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    /// Whether an entry passes the model and temperature filters; entries
+    /// stored without a temperature match any
+    pub fn matches(&self, entry: &CachedEntry) -> bool {
+        if self.model_id.is_some() && entry.model_id() != self.model_id.as_deref() {
+            return false;
+        }
+
+        // Temperatures match within a small tolerance
+        match (self.temperature, entry.temperature()) {
+            (Some(temp), Some(entry_temp)) => (entry_temp - temp).abs() <= 0.01,
+            _ => true,
+        }
+    }
 }
 
 /// Trait for semantic (vector-based) caching
@@ -0,0 +1,342 @@
+//! Service account entity
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::validation::{
+    validate_service_account_id, validate_service_account_name, ServiceAccountValidationError,
+};
+use crate::domain::role::Permission;
+use crate::domain::storage::{StorageEntity, StorageKey};
+use crate::domain::team::TeamId;
+
+/// Service account identifier, also used as the OAuth client ID -
+/// alphanumeric + hyphens, max 50 characters
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ServiceAccountId(String);
+
+impl ServiceAccountId {
+    /// Create a new ServiceAccountId after validation
+    pub fn new(id: impl Into<String>) -> Result<Self, ServiceAccountValidationError> {
+        let id = id.into();
+        validate_service_account_id(&id)?;
+        Ok(Self(id))
+    }
+
+    /// Get the inner string value
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for ServiceAccountId {
+    type Error = ServiceAccountValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<ServiceAccountId> for String {
+    fn from(id: ServiceAccountId) -> Self {
+        id.0
+    }
+}
+
+impl std::fmt::Display for ServiceAccountId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StorageKey for ServiceAccountId {
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Status of a service account
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceAccountStatus {
+    /// Service account can obtain and use tokens
+    #[default]
+    Active,
+    /// Service account is disabled; issued tokens are rejected
+    Disabled,
+}
+
+impl ServiceAccountStatus {
+    /// Check if the service account is active
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Active)
+    }
+}
+
+impl std::fmt::Display for ServiceAccountStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Active => write!(f, "active"),
+            Self::Disabled => write!(f, "disabled"),
+        }
+    }
+}
+
+/// Service account entity - a machine identity authenticating with a
+/// client secret and limited to a set of admin API scopes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceAccount {
+    /// Unique identifier (the OAuth client ID)
+    id: ServiceAccountId,
+    /// Display name
+    name: String,
+    /// Description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    /// Hash of the client secret
+    secret_hash: String,
+    /// Admin API permissions tokens may be issued for
+    scopes: Vec<Permission>,
+    /// Team owning the service account
+    team_id: TeamId,
+    /// Current status
+    #[serde(default)]
+    status: ServiceAccountStatus,
+    /// Last time a token was issued
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_used_at: Option<DateTime<Utc>>,
+    /// Creation timestamp
+    created_at: DateTime<Utc>,
+    /// Last update timestamp
+    updated_at: DateTime<Utc>,
+}
+
+impl ServiceAccount {
+    /// Create a new service account
+    pub fn new(
+        id: ServiceAccountId,
+        name: impl Into<String>,
+        secret_hash: impl Into<String>,
+        scopes: Vec<Permission>,
+        team_id: TeamId,
+    ) -> Result<Self, ServiceAccountValidationError> {
+        let name = name.into();
+        validate_service_account_name(&name)?;
+
+        if scopes.is_empty() {
+            return Err(ServiceAccountValidationError::NoScopes);
+        }
+
+        let now = Utc::now();
+
+        Ok(Self {
+            id,
+            name,
+            description: None,
+            secret_hash: secret_hash.into(),
+            scopes,
+            team_id,
+            status: ServiceAccountStatus::Active,
+            last_used_at: None,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Set description (builder pattern)
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    // Getters
+
+    pub fn id(&self) -> &ServiceAccountId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn secret_hash(&self) -> &str {
+        &self.secret_hash
+    }
+
+    pub fn scopes(&self) -> &[Permission] {
+        &self.scopes
+    }
+
+    pub fn team_id(&self) -> &TeamId {
+        &self.team_id
+    }
+
+    pub fn status(&self) -> ServiceAccountStatus {
+        self.status
+    }
+
+    pub fn last_used_at(&self) -> Option<DateTime<Utc>> {
+        self.last_used_at
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    /// Check if the service account can obtain and use tokens
+    pub fn is_active(&self) -> bool {
+        self.status.is_active()
+    }
+
+    /// Check if any scope of the service account grants a permission
+    pub fn allows(&self, required: &Permission) -> bool {
+        self.scopes.iter().any(|scope| scope.grants(required))
+    }
+
+    // Mutators
+
+    /// Update the name
+    pub fn set_name(
+        &mut self,
+        name: impl Into<String>,
+    ) -> Result<(), ServiceAccountValidationError> {
+        let name = name.into();
+        validate_service_account_name(&name)?;
+        self.name = name;
+        self.touch();
+        Ok(())
+    }
+
+    /// Update the description
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.description = description;
+        self.touch();
+    }
+
+    /// Replace the scopes
+    pub fn set_scopes(
+        &mut self,
+        scopes: Vec<Permission>,
+    ) -> Result<(), ServiceAccountValidationError> {
+        if scopes.is_empty() {
+            return Err(ServiceAccountValidationError::NoScopes);
+        }
+
+        self.scopes = scopes;
+        self.touch();
+        Ok(())
+    }
+
+    /// Update the status
+    pub fn set_status(&mut self, status: ServiceAccountStatus) {
+        self.status = status;
+        self.touch();
+    }
+
+    /// Replace the client secret hash
+    pub fn set_secret_hash(&mut self, secret_hash: impl Into<String>) {
+        self.secret_hash = secret_hash.into();
+        self.touch();
+    }
+
+    /// Record that a token was issued
+    pub fn record_use(&mut self) {
+        self.last_used_at = Some(Utc::now());
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+    }
+}
+
+impl StorageEntity for ServiceAccount {
+    type Key = ServiceAccountId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::role::PermissionResource;
+
+    fn create_account(scopes: Vec<Permission>) -> ServiceAccount {
+        ServiceAccount::new(
+            ServiceAccountId::new("ci-pipeline").unwrap(),
+            "CI pipeline",
+            "sha256$hash",
+            scopes,
+            TeamId::administrators(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_service_account_creation() {
+        let account = create_account(vec![Permission::write(PermissionResource::Prompts)]);
+
+        assert_eq!(account.id().as_str(), "ci-pipeline");
+        assert_eq!(account.name(), "CI pipeline");
+        assert!(account.is_active());
+        assert!(account.last_used_at().is_none());
+    }
+
+    #[test]
+    fn test_service_account_requires_scopes() {
+        let result = ServiceAccount::new(
+            ServiceAccountId::new("ci-pipeline").unwrap(),
+            "CI pipeline",
+            "sha256$hash",
+            vec![],
+            TeamId::administrators(),
+        );
+
+        assert_eq!(result.unwrap_err(), ServiceAccountValidationError::NoScopes);
+    }
+
+    #[test]
+    fn test_service_account_allows() {
+        let account = create_account(vec![
+            Permission::write(PermissionResource::Prompts),
+            Permission::read(PermissionResource::Models),
+        ]);
+
+        assert!(account.allows(&Permission::read(PermissionResource::Prompts)));
+        assert!(account.allows(&Permission::read(PermissionResource::Models)));
+        assert!(!account.allows(&Permission::write(PermissionResource::Models)));
+        assert!(!account.allows(&Permission::read(PermissionResource::Users)));
+    }
+
+    #[test]
+    fn test_service_account_status() {
+        let mut account = create_account(vec![Permission::write(PermissionResource::Workflows)]);
+
+        account.set_status(ServiceAccountStatus::Disabled);
+        assert!(!account.is_active());
+        assert_eq!(account.status().to_string(), "disabled");
+    }
+
+    #[test]
+    fn test_service_account_serialization_roundtrip() {
+        let account = create_account(vec![Permission::write(PermissionResource::Workflows)]);
+
+        let json = serde_json::to_string(&account).unwrap();
+        assert!(json.contains("\"scopes\":[\"workflows:write\"]"));
+
+        let parsed: ServiceAccount = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.scopes(), account.scopes());
+        assert_eq!(parsed.secret_hash(), "sha256$hash");
+    }
+}
@@ -0,0 +1,15 @@
+//! Service account domain module
+//!
+//! Service accounts are machine identities distinct from users. They
+//! authenticate with a client ID and secret through the OAuth
+//! `client_credentials` grant and receive JWTs restricted to their scopes.
+
+mod entity;
+mod repository;
+mod validation;
+
+pub use entity::{ServiceAccount, ServiceAccountId, ServiceAccountStatus};
+pub use repository::ServiceAccountRepository;
+pub use validation::{
+    validate_service_account_id, validate_service_account_name, ServiceAccountValidationError,
+};
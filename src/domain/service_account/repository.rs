@@ -0,0 +1,28 @@
+//! Service account repository trait
+
+use async_trait::async_trait;
+
+use super::entity::{ServiceAccount, ServiceAccountId};
+use crate::domain::DomainError;
+
+/// Repository for managing service accounts
+#[async_trait]
+pub trait ServiceAccountRepository: Send + Sync + std::fmt::Debug {
+    /// Get a service account by ID
+    async fn get(&self, id: &ServiceAccountId) -> Result<Option<ServiceAccount>, DomainError>;
+
+    /// Create a new service account
+    async fn create(&self, account: ServiceAccount) -> Result<ServiceAccount, DomainError>;
+
+    /// Update an existing service account
+    async fn update(&self, account: ServiceAccount) -> Result<ServiceAccount, DomainError>;
+
+    /// Delete a service account by ID
+    async fn delete(&self, id: &ServiceAccountId) -> Result<bool, DomainError>;
+
+    /// List all service accounts, sorted by name
+    async fn list(&self) -> Result<Vec<ServiceAccount>, DomainError>;
+
+    /// Check if a service account exists
+    async fn exists(&self, id: &ServiceAccountId) -> Result<bool, DomainError>;
+}
@@ -0,0 +1,113 @@
+//! Service account validation
+
+use thiserror::Error;
+
+/// Errors that can occur during service account validation
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum ServiceAccountValidationError {
+    #[error("Service account ID cannot be empty")]
+    EmptyId,
+
+    #[error("Service account ID cannot exceed {0} characters")]
+    IdTooLong(usize),
+
+    #[error("Service account ID can only contain alphanumeric characters and hyphens")]
+    InvalidIdCharacters,
+
+    #[error("Service account ID cannot start or end with a hyphen")]
+    InvalidIdFormat,
+
+    #[error("Service account name cannot be empty")]
+    EmptyName,
+
+    #[error("Service account name cannot exceed {0} characters")]
+    NameTooLong(usize),
+
+    #[error("Service account must have at least one scope")]
+    NoScopes,
+}
+
+const MAX_SERVICE_ACCOUNT_ID_LENGTH: usize = 50;
+const MAX_SERVICE_ACCOUNT_NAME_LENGTH: usize = 100;
+
+/// Validate a service account ID (also used as the OAuth client ID)
+pub fn validate_service_account_id(id: &str) -> Result<(), ServiceAccountValidationError> {
+    if id.is_empty() {
+        return Err(ServiceAccountValidationError::EmptyId);
+    }
+
+    if id.len() > MAX_SERVICE_ACCOUNT_ID_LENGTH {
+        return Err(ServiceAccountValidationError::IdTooLong(
+            MAX_SERVICE_ACCOUNT_ID_LENGTH,
+        ));
+    }
+
+    if !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(ServiceAccountValidationError::InvalidIdCharacters);
+    }
+
+    if id.starts_with('-') || id.ends_with('-') {
+        return Err(ServiceAccountValidationError::InvalidIdFormat);
+    }
+
+    Ok(())
+}
+
+/// Validate a service account name
+pub fn validate_service_account_name(name: &str) -> Result<(), ServiceAccountValidationError> {
+    if name.is_empty() {
+        return Err(ServiceAccountValidationError::EmptyName);
+    }
+
+    if name.len() > MAX_SERVICE_ACCOUNT_NAME_LENGTH {
+        return Err(ServiceAccountValidationError::NameTooLong(
+            MAX_SERVICE_ACCOUNT_NAME_LENGTH,
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_service_account_id() {
+        assert!(validate_service_account_id("ci-pipeline").is_ok());
+        assert!(validate_service_account_id("deploy2").is_ok());
+    }
+
+    #[test]
+    fn test_invalid_service_account_id() {
+        assert_eq!(
+            validate_service_account_id(""),
+            Err(ServiceAccountValidationError::EmptyId)
+        );
+        assert_eq!(
+            validate_service_account_id(&"a".repeat(51)),
+            Err(ServiceAccountValidationError::IdTooLong(50))
+        );
+        assert_eq!(
+            validate_service_account_id("ci_pipeline"),
+            Err(ServiceAccountValidationError::InvalidIdCharacters)
+        );
+        assert_eq!(
+            validate_service_account_id("-ci"),
+            Err(ServiceAccountValidationError::InvalidIdFormat)
+        );
+    }
+
+    #[test]
+    fn test_service_account_name() {
+        assert!(validate_service_account_name("CI pipeline").is_ok());
+        assert_eq!(
+            validate_service_account_name(""),
+            Err(ServiceAccountValidationError::EmptyName)
+        );
+        assert_eq!(
+            validate_service_account_name(&"a".repeat(101)),
+            Err(ServiceAccountValidationError::NameTooLong(100))
+        );
+    }
+}
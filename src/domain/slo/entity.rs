@@ -0,0 +1,174 @@
+//! Model SLO entity
+
+use serde::{Deserialize, Serialize};
+
+use crate::domain::storage::{StorageEntity, StorageKey};
+use crate::domain::DomainError;
+
+/// Default evaluation window of an SLO, one hour
+pub const DEFAULT_SLO_WINDOW_SECS: u64 = 3_600;
+
+/// SLO ID, the ID of the model it applies to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SloId(String);
+
+impl SloId {
+    /// Create the SLO ID of a model
+    pub fn new(model_id: impl Into<String>) -> Self {
+        Self(model_id.into())
+    }
+
+    /// Get the inner string value
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for SloId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StorageKey for SloId {
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl StorageEntity for Slo {
+    type Key = SloId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+/// Latency and error objectives of a model over a rolling window. A model
+/// has at most one SLO.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Slo {
+    pub id: SloId,
+    /// Model ID this SLO applies to
+    pub model_id: String,
+    /// Target p95 latency in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_p95_ms: Option<u64>,
+    /// Share of requests allowed to fail, e.g. `0.01` for a 99% success target
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_budget: Option<f64>,
+    /// Rolling window the objectives are evaluated over
+    #[serde(default = "default_window_secs")]
+    pub window_secs: u64,
+    /// Whether the SLO is evaluated
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Creation date (unix timestamp)
+    pub created_at: u64,
+    /// Last update date (unix timestamp)
+    pub updated_at: u64,
+}
+
+fn default_window_secs() -> u64 {
+    DEFAULT_SLO_WINDOW_SECS
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Slo {
+    /// Create an SLO without objectives
+    pub fn new(model_id: impl Into<String>) -> Self {
+        let model_id = model_id.into();
+        let now = chrono::Utc::now().timestamp().max(0) as u64;
+
+        Self {
+            id: SloId::new(model_id.clone()),
+            model_id,
+            latency_p95_ms: None,
+            error_budget: None,
+            window_secs: DEFAULT_SLO_WINDOW_SECS,
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Set the target p95 latency
+    pub fn with_latency_p95_ms(mut self, latency_ms: u64) -> Self {
+        self.latency_p95_ms = Some(latency_ms);
+        self
+    }
+
+    /// Set the share of requests allowed to fail
+    pub fn with_error_budget(mut self, error_budget: f64) -> Self {
+        self.error_budget = Some(error_budget);
+        self
+    }
+
+    /// Set the evaluation window
+    pub fn with_window_secs(mut self, window_secs: u64) -> Self {
+        self.window_secs = window_secs;
+        self
+    }
+
+    /// Enable or disable evaluation
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Validates the objectives
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.model_id.trim().is_empty() {
+            return Err(DomainError::validation("model_id is required"));
+        }
+
+        if self.latency_p95_ms.is_none() && self.error_budget.is_none() {
+            return Err(DomainError::validation(
+                "At least one of latency_p95_ms or error_budget is required",
+            ));
+        }
+
+        if self.latency_p95_ms == Some(0) {
+            return Err(DomainError::validation("latency_p95_ms must be positive"));
+        }
+
+        if self
+            .error_budget
+            .is_some_and(|budget| !(budget > 0.0 && budget < 1.0))
+        {
+            return Err(DomainError::validation(
+                "error_budget must be between 0 and 1 (exclusive)",
+            ));
+        }
+
+        if self.window_secs == 0 {
+            return Err(DomainError::validation("window_secs must be positive"));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let slo = Slo::new("gpt-4o");
+        assert!(slo.validate().is_err());
+
+        assert!(slo.clone().with_latency_p95_ms(2_000).validate().is_ok());
+        assert!(slo.clone().with_error_budget(0.01).validate().is_ok());
+        assert!(slo.clone().with_latency_p95_ms(0).validate().is_err());
+        assert!(slo.clone().with_error_budget(1.0).validate().is_err());
+        assert!(slo
+            .with_error_budget(0.01)
+            .with_window_secs(0)
+            .validate()
+            .is_err());
+    }
+}
@@ -0,0 +1,9 @@
+//! Service level objective domain module for per-model latency and error targets
+
+mod entity;
+mod report;
+mod repository;
+
+pub use entity::*;
+pub use report::*;
+pub use repository::*;
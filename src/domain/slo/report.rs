@@ -0,0 +1,243 @@
+//! SLO evaluation over execution logs
+//!
+//! Burn rates measure how fast an objective consumes its budget: 1.0 spends
+//! the budget exactly over the window, higher values exhaust it early. The
+//! error burn rate is the error rate divided by the error budget; the latency
+//! burn rate is the share of requests slower than the p95 target divided by
+//! the 5% such a target allows.
+
+use serde::{Deserialize, Serialize};
+
+use super::Slo;
+use crate::domain::{ExecutionLog, ExecutionStatus, ExecutionType};
+
+/// Share of requests a p95 latency target allows above it
+const LATENCY_BUDGET: f64 = 0.05;
+
+/// Burn rate from which an objective is reported at risk
+pub const AT_RISK_BURN_RATE: f64 = 0.75;
+
+/// Status of an SLO over its window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SloStatus {
+    /// No request of the model in the window
+    NoData,
+    Met,
+    /// Burning the budget at [`AT_RISK_BURN_RATE`] or more
+    AtRisk,
+    /// Budget burnt faster than it allows
+    Breached,
+}
+
+impl SloStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SloStatus::NoData => "no_data",
+            SloStatus::Met => "met",
+            SloStatus::AtRisk => "at_risk",
+            SloStatus::Breached => "breached",
+        }
+    }
+
+    fn from_burn_rate(burn_rate: f64) -> Self {
+        if burn_rate >= 1.0 {
+            SloStatus::Breached
+        } else if burn_rate >= AT_RISK_BURN_RATE {
+            SloStatus::AtRisk
+        } else {
+            SloStatus::Met
+        }
+    }
+}
+
+impl std::fmt::Display for SloStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Evaluation of a model SLO
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SloReport {
+    pub model_id: String,
+    pub window_secs: u64,
+    /// Evaluation date (unix timestamp)
+    pub evaluated_at: u64,
+    /// Completed requests in the window
+    pub requests: u64,
+    /// Failed or timed out requests in the window
+    pub errors: u64,
+    pub error_rate: f64,
+    /// Observed p95 latency, none without requests
+    pub p95_latency_ms: Option<u64>,
+    /// Error burn rate, none without an error budget or requests
+    pub error_burn_rate: Option<f64>,
+    /// Latency burn rate, none without a latency target or requests
+    pub latency_burn_rate: Option<f64>,
+    pub status: SloStatus,
+}
+
+impl SloReport {
+    /// Highest burn rate of the objectives
+    pub fn burn_rate(&self) -> Option<f64> {
+        match (self.error_burn_rate, self.latency_burn_rate) {
+            (Some(error), Some(latency)) => Some(error.max(latency)),
+            (error, latency) => error.or(latency),
+        }
+    }
+
+    /// Whether a budget is burnt faster than it allows
+    pub fn is_breached(&self) -> bool {
+        self.status == SloStatus::Breached
+    }
+}
+
+/// Evaluates an SLO over the model and chat completion logs of its model
+/// created in the window ending at `now`. Cancelled and unfinished
+/// executions are ignored.
+pub fn evaluate_slo(slo: &Slo, logs: &[ExecutionLog], now: u64) -> SloReport {
+    let from = now.saturating_sub(slo.window_secs) as i64;
+    let mut latencies = Vec::new();
+    let mut errors = 0u64;
+
+    for log in logs {
+        let created_at = log.created_at().timestamp();
+
+        if log.resource_id() != slo.model_id
+            || !matches!(
+                log.execution_type(),
+                ExecutionType::Model | ExecutionType::ChatCompletion
+            )
+            || created_at <= from
+            || created_at > now as i64
+        {
+            continue;
+        }
+
+        match log.status() {
+            ExecutionStatus::Success => {}
+            ExecutionStatus::Failed | ExecutionStatus::Timeout => errors += 1,
+            _ => continue,
+        }
+
+        latencies.push(log.execution_time_ms());
+    }
+
+    let requests = latencies.len() as u64;
+    let error_rate = if requests > 0 {
+        errors as f64 / requests as f64
+    } else {
+        0.0
+    };
+
+    let (p95_latency_ms, error_burn_rate, latency_burn_rate) = if requests > 0 {
+        latencies.sort_unstable();
+        let rank = ((requests as f64 * 0.95).ceil() as usize).max(1);
+
+        let latency_burn_rate = slo.latency_p95_ms.map(|target| {
+            let slow = latencies.iter().filter(|ms| **ms > target).count();
+            slow as f64 / requests as f64 / LATENCY_BUDGET
+        });
+
+        (
+            Some(latencies[rank - 1]),
+            slo.error_budget.map(|budget| error_rate / budget),
+            latency_burn_rate,
+        )
+    } else {
+        (None, None, None)
+    };
+
+    let mut report = SloReport {
+        model_id: slo.model_id.clone(),
+        window_secs: slo.window_secs,
+        evaluated_at: now,
+        requests,
+        errors,
+        error_rate,
+        p95_latency_ms,
+        error_burn_rate,
+        latency_burn_rate,
+        status: SloStatus::NoData,
+    };
+
+    if let Some(burn_rate) = report.burn_rate() {
+        report.status = SloStatus::from_burn_rate(burn_rate);
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Executor;
+
+    fn log(model_id: &str, status: ExecutionStatus, ms: u64) -> ExecutionLog {
+        ExecutionLog::new(
+            ExecutionType::ChatCompletion,
+            model_id,
+            status,
+            ms,
+            Executor::anonymous(),
+        )
+    }
+
+    fn now() -> u64 {
+        chrono::Utc::now().timestamp() as u64
+    }
+
+    #[test]
+    fn test_evaluate_without_requests() {
+        let slo = Slo::new("gpt-4o").with_error_budget(0.01);
+        let logs = [log("gpt-4o", ExecutionStatus::Success, 100)];
+
+        // Logs older than the window
+        let report = evaluate_slo(&slo, &logs, now() + 7_200);
+
+        assert_eq!(report.requests, 0);
+        assert_eq!(report.p95_latency_ms, None);
+        assert_eq!(report.status, SloStatus::NoData);
+    }
+
+    #[test]
+    fn test_evaluate_burn_rates() {
+        let slo = Slo::new("gpt-4o")
+            .with_error_budget(0.1)
+            .with_latency_p95_ms(1_000);
+
+        let mut logs: Vec<ExecutionLog> = (0..18)
+            .map(|i| log("gpt-4o", ExecutionStatus::Success, 100 + i))
+            .collect();
+        logs.push(log("gpt-4o", ExecutionStatus::Failed, 1_500));
+        logs.push(log("gpt-4o", ExecutionStatus::Timeout, 30_000));
+        logs.push(log("gpt-4o", ExecutionStatus::Cancelled, 10));
+        logs.push(log("claude-3", ExecutionStatus::Failed, 10));
+
+        let report = evaluate_slo(&slo, &logs, now());
+
+        assert_eq!(report.requests, 20);
+        assert_eq!(report.errors, 2);
+        assert!((report.error_rate - 0.1).abs() < f64::EPSILON);
+        assert_eq!(report.p95_latency_ms, Some(1_500));
+        assert!((report.error_burn_rate.unwrap() - 1.0).abs() < 1e-9);
+        assert!((report.latency_burn_rate.unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(report.burn_rate(), report.latency_burn_rate);
+        assert!(report.is_breached());
+    }
+
+    #[test]
+    fn test_evaluate_at_risk() {
+        let slo = Slo::new("gpt-4o").with_error_budget(0.25);
+        let mut logs: Vec<ExecutionLog> = (0..4)
+            .map(|_| log("gpt-4o", ExecutionStatus::Success, 100))
+            .collect();
+        logs.push(log("gpt-4o", ExecutionStatus::Failed, 100));
+
+        let report = evaluate_slo(&slo, &logs, now());
+
+        assert_eq!(report.latency_burn_rate, None);
+        assert_eq!(report.status, SloStatus::AtRisk);
+    }
+}
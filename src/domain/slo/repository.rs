@@ -0,0 +1,23 @@
+//! SLO repository trait
+
+use async_trait::async_trait;
+use std::fmt::Debug;
+
+use super::{Slo, SloId};
+use crate::domain::DomainError;
+
+/// Repository for model SLO definitions
+#[async_trait]
+pub trait SloRepository: Send + Sync + Debug {
+    /// Get the SLO of a model
+    async fn get(&self, id: &SloId) -> Result<Option<Slo>, DomainError>;
+
+    /// List every SLO
+    async fn list(&self) -> Result<Vec<Slo>, DomainError>;
+
+    /// Create or replace an SLO
+    async fn save(&self, slo: Slo) -> Result<Slo, DomainError>;
+
+    /// Delete an SLO
+    async fn delete(&self, id: &SloId) -> Result<bool, DomainError>;
+}
@@ -3,7 +3,12 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::quota::TeamQuota;
+use super::request_defaults::TeamRequestDefaults;
 use super::validation::{validate_team_id, validate_team_name, TeamValidationError};
+use crate::domain::network::IpNetwork;
+use crate::domain::organization::OrganizationId;
+use crate::domain::residency::Region;
 use crate::domain::storage::{StorageEntity, StorageKey};
 
 /// Team identifier - alphanumeric + hyphens, max 50 characters
@@ -148,6 +153,25 @@ pub struct Team {
     description: Option<String>,
     /// Current status
     status: TeamStatus,
+    /// Client networks allowed to use the team's API keys (empty = any)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    allowed_cidrs: Vec<IpNetwork>,
+    /// Provider regions the team's requests may be served from (empty = any)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    allowed_regions: Vec<Region>,
+    /// Zero-retention mode: request and completion bodies of the team's
+    /// keys are never stored
+    #[serde(default)]
+    no_log: bool,
+    /// Resource limits
+    #[serde(default, skip_serializing_if = "TeamQuota::is_unlimited")]
+    quota: TeamQuota,
+    /// Defaults and limits of chat completion parameters
+    #[serde(default, skip_serializing_if = "TeamRequestDefaults::is_empty")]
+    request_defaults: TeamRequestDefaults,
+    /// Organization the team belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    organization_id: Option<OrganizationId>,
     /// Creation timestamp
     created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -166,6 +190,12 @@ impl Team {
             name,
             description: None,
             status: TeamStatus::Active,
+            allowed_cidrs: Vec::new(),
+            allowed_regions: Vec::new(),
+            no_log: false,
+            quota: TeamQuota::default(),
+            request_defaults: TeamRequestDefaults::default(),
+            organization_id: None,
             created_at: now,
             updated_at: now,
         })
@@ -180,6 +210,12 @@ impl Team {
             name: "Administrators".to_string(),
             description: Some("Built-in administrators team".to_string()),
             status: TeamStatus::Active,
+            allowed_cidrs: Vec::new(),
+            allowed_regions: Vec::new(),
+            no_log: false,
+            quota: TeamQuota::default(),
+            request_defaults: TeamRequestDefaults::default(),
+            organization_id: None,
             created_at: now,
             updated_at: now,
         }
@@ -197,6 +233,30 @@ impl Team {
         &self.id
     }
 
+    pub fn allowed_cidrs(&self) -> &[IpNetwork] {
+        &self.allowed_cidrs
+    }
+
+    pub fn allowed_regions(&self) -> &[Region] {
+        &self.allowed_regions
+    }
+
+    pub fn no_log(&self) -> bool {
+        self.no_log
+    }
+
+    pub fn quota(&self) -> &TeamQuota {
+        &self.quota
+    }
+
+    pub fn request_defaults(&self) -> &TeamRequestDefaults {
+        &self.request_defaults
+    }
+
+    pub fn organization_id(&self) -> Option<&OrganizationId> {
+        self.organization_id.as_ref()
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -233,6 +293,42 @@ impl Team {
         Ok(())
     }
 
+    /// Update the allowed client networks
+    pub fn set_allowed_cidrs(&mut self, allowed_cidrs: Vec<IpNetwork>) {
+        self.allowed_cidrs = allowed_cidrs;
+        self.touch();
+    }
+
+    /// Update the allowed provider regions
+    pub fn set_allowed_regions(&mut self, allowed_regions: Vec<Region>) {
+        self.allowed_regions = allowed_regions;
+        self.touch();
+    }
+
+    /// Enable or disable zero-retention mode
+    pub fn set_no_log(&mut self, no_log: bool) {
+        self.no_log = no_log;
+        self.touch();
+    }
+
+    /// Update the resource limits
+    pub fn set_quota(&mut self, quota: TeamQuota) {
+        self.quota = quota;
+        self.touch();
+    }
+
+    /// Update the chat completion parameter defaults and limits
+    pub fn set_request_defaults(&mut self, request_defaults: TeamRequestDefaults) {
+        self.request_defaults = request_defaults;
+        self.touch();
+    }
+
+    /// Assign the team to an organization, or remove it with `None`
+    pub fn set_organization_id(&mut self, organization_id: Option<OrganizationId>) {
+        self.organization_id = organization_id;
+        self.touch();
+    }
+
     /// Update the description
     pub fn set_description(&mut self, description: Option<String>) {
         self.description = description;
@@ -395,4 +491,63 @@ mod tests {
         let id = TeamId::new("my-team").unwrap();
         assert!(Team::new(id, "").is_err());
     }
+
+    #[test]
+    fn test_team_allowed_cidrs() {
+        let id = TeamId::new("my-team").unwrap();
+        let mut team = Team::new(id, "My Team").unwrap();
+        assert!(team.allowed_cidrs().is_empty());
+
+        team.set_allowed_cidrs(vec!["192.168.0.0/16".parse().unwrap()]);
+        assert_eq!(team.allowed_cidrs()[0].to_string(), "192.168.0.0/16");
+
+        let json = serde_json::to_string(&team).unwrap();
+        let parsed: Team = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.allowed_cidrs(), team.allowed_cidrs());
+    }
+
+    #[test]
+    fn test_team_allowed_regions() {
+        let id = TeamId::new("my-team").unwrap();
+        let mut team = Team::new(id, "My Team").unwrap();
+        assert!(team.allowed_regions().is_empty());
+        assert!(!serde_json::to_string(&team).unwrap().contains("allowed_regions"));
+
+        team.set_allowed_regions(vec![Region::new("eu").unwrap()]);
+
+        let json = serde_json::to_string(&team).unwrap();
+        let parsed: Team = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.allowed_regions(), team.allowed_regions());
+    }
+
+    #[test]
+    fn test_team_no_log() {
+        let id = TeamId::new("my-team").unwrap();
+        let mut team = Team::new(id, "My Team").unwrap();
+        assert!(!team.no_log());
+
+        team.set_no_log(true);
+
+        let json = serde_json::to_string(&team).unwrap();
+        let parsed: Team = serde_json::from_str(&json).unwrap();
+        assert!(parsed.no_log());
+
+        let legacy = json.replace(",\"no_log\":true", "");
+        let parsed: Team = serde_json::from_str(&legacy).unwrap();
+        assert!(!parsed.no_log());
+    }
+
+    #[test]
+    fn test_team_organization() {
+        let id = TeamId::new("my-team").unwrap();
+        let mut team = Team::new(id, "My Team").unwrap();
+        assert!(team.organization_id().is_none());
+        assert!(!serde_json::to_string(&team).unwrap().contains("organization_id"));
+
+        team.set_organization_id(Some(OrganizationId::new("acme").unwrap()));
+
+        let json = serde_json::to_string(&team).unwrap();
+        let parsed: Team = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.organization_id().map(|o| o.as_str()), Some("acme"));
+    }
 }
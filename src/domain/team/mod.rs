@@ -4,9 +4,13 @@
 //! and API keys are owned by teams (not individual users).
 
 mod entity;
+mod quota;
 mod repository;
+mod request_defaults;
 mod validation;
 
 pub use entity::{Team, TeamId, TeamRole, TeamStatus};
+pub use quota::{QuotaExceededError, QuotaResource, QuotaUsage, TeamQuota};
 pub use repository::{TeamQuery, TeamRepository};
+pub use request_defaults::{ParameterLimitAction, ParameterLimitExceeded, TeamRequestDefaults};
 pub use validation::{validate_team_id, validate_team_name, TeamValidationError};
@@ -0,0 +1,183 @@
+//! Team resource quotas
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Resource limited by a team quota
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaResource {
+    ApiKeys,
+    KnowledgeBases,
+    Workflows,
+    KbDocuments,
+    KbStorageBytes,
+    ConcurrentRequests,
+}
+
+impl QuotaResource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ApiKeys => "api_keys",
+            Self::KnowledgeBases => "knowledge_bases",
+            Self::Workflows => "workflows",
+            Self::KbDocuments => "kb_documents",
+            Self::KbStorageBytes => "kb_storage_bytes",
+            Self::ConcurrentRequests => "concurrent_requests",
+        }
+    }
+}
+
+impl std::fmt::Display for QuotaResource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Resource limits of a team. Unset limits are unlimited.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TeamQuota {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_api_keys: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_knowledge_bases: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_workflows: Option<u64>,
+    /// Documents across all knowledge bases of the team
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_kb_documents: Option<u64>,
+    /// Original size of the documents across all knowledge bases of the team
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_kb_storage_bytes: Option<u64>,
+    /// In-flight API requests made with the team's API keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_requests: Option<u64>,
+}
+
+impl TeamQuota {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the limit of a resource (builder pattern)
+    pub fn with_limit(mut self, resource: QuotaResource, limit: u64) -> Self {
+        *self.limit_mut(resource) = Some(limit);
+        self
+    }
+
+    /// Get the limit of a resource, `None` if unlimited
+    pub fn limit(&self, resource: QuotaResource) -> Option<u64> {
+        match resource {
+            QuotaResource::ApiKeys => self.max_api_keys,
+            QuotaResource::KnowledgeBases => self.max_knowledge_bases,
+            QuotaResource::Workflows => self.max_workflows,
+            QuotaResource::KbDocuments => self.max_kb_documents,
+            QuotaResource::KbStorageBytes => self.max_kb_storage_bytes,
+            QuotaResource::ConcurrentRequests => self.max_concurrent_requests,
+        }
+    }
+
+    /// Check if no limits are set
+    pub fn is_unlimited(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Check that `requested` more units of a resource fit within the limit
+    pub fn check(
+        &self,
+        resource: QuotaResource,
+        current: u64,
+        requested: u64,
+    ) -> Result<(), QuotaExceededError> {
+        match self.limit(resource) {
+            Some(limit) if current.saturating_add(requested) > limit => Err(QuotaExceededError {
+                resource,
+                limit,
+                current,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn limit_mut(&mut self, resource: QuotaResource) -> &mut Option<u64> {
+        match resource {
+            QuotaResource::ApiKeys => &mut self.max_api_keys,
+            QuotaResource::KnowledgeBases => &mut self.max_knowledge_bases,
+            QuotaResource::Workflows => &mut self.max_workflows,
+            QuotaResource::KbDocuments => &mut self.max_kb_documents,
+            QuotaResource::KbStorageBytes => &mut self.max_kb_storage_bytes,
+            QuotaResource::ConcurrentRequests => &mut self.max_concurrent_requests,
+        }
+    }
+}
+
+/// Current resource usage of a team
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, ToSchema)]
+pub struct QuotaUsage {
+    pub api_keys: u64,
+    pub knowledge_bases: u64,
+    pub workflows: u64,
+    pub kb_documents: u64,
+    pub kb_storage_bytes: u64,
+    pub concurrent_requests: u64,
+}
+
+/// Error returned when an operation would exceed a team quota
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("quota exceeded for {resource} ({current} of {limit} used)")]
+pub struct QuotaExceededError {
+    pub resource: QuotaResource,
+    pub limit: u64,
+    pub current: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlimited_by_default() {
+        let quota = TeamQuota::new();
+
+        assert!(quota.is_unlimited());
+        assert!(quota.check(QuotaResource::ApiKeys, 1_000, 1).is_ok());
+    }
+
+    #[test]
+    fn test_check_limit() {
+        let quota = TeamQuota::new()
+            .with_limit(QuotaResource::Workflows, 2)
+            .with_limit(QuotaResource::KbStorageBytes, 1024);
+
+        assert!(!quota.is_unlimited());
+        assert_eq!(quota.limit(QuotaResource::Workflows), Some(2));
+        assert!(quota.check(QuotaResource::Workflows, 1, 1).is_ok());
+
+        let err = quota.check(QuotaResource::Workflows, 2, 1).unwrap_err();
+        assert_eq!(err.resource, QuotaResource::Workflows);
+        assert_eq!(err.limit, 2);
+
+        assert!(quota.check(QuotaResource::KbStorageBytes, 1000, 24).is_ok());
+        assert!(quota.check(QuotaResource::KbStorageBytes, 1000, 25).is_err());
+    }
+
+    #[test]
+    fn test_quota_serde_skips_unset_limits() {
+        let quota = TeamQuota::new().with_limit(QuotaResource::ApiKeys, 5);
+        let json = serde_json::to_value(&quota).unwrap();
+
+        assert_eq!(json, serde_json::json!({"max_api_keys": 5}));
+
+        let parsed: TeamQuota = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, quota);
+    }
+
+    #[test]
+    fn test_quota_exceeded_message() {
+        let err = TeamQuota::new()
+            .with_limit(QuotaResource::ApiKeys, 0)
+            .check(QuotaResource::ApiKeys, 0, 1)
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), "quota exceeded for api_keys (0 of 0 used)");
+    }
+}
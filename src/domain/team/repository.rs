@@ -10,6 +10,8 @@ use crate::domain::DomainError;
 pub struct TeamQuery {
     /// Filter by status
     pub status: Option<String>,
+    /// Filter by organization
+    pub organization_id: Option<String>,
     /// Maximum number of results
     pub limit: Option<usize>,
     /// Offset for pagination
@@ -26,6 +28,11 @@ impl TeamQuery {
         self
     }
 
+    pub fn with_organization_id(mut self, organization_id: impl Into<String>) -> Self {
+        self.organization_id = Some(organization_id.into());
+        self
+    }
+
     pub fn with_limit(mut self, limit: usize) -> Self {
         self.limit = Some(limit);
         self
@@ -129,6 +136,13 @@ pub mod mock {
                 result.retain(|t| t.status().to_string() == *status);
             }
 
+            // Filter by organization
+            if let Some(ref organization_id) = query.organization_id {
+                result.retain(|t| {
+                    t.organization_id().map(|o| o.as_str()) == Some(organization_id.as_str())
+                });
+            }
+
             // Sort by name
             result.sort_by(|a, b| a.name().cmp(b.name()));
 
@@ -150,14 +164,16 @@ pub mod mock {
 
         async fn count(&self, query: &TeamQuery) -> Result<usize, DomainError> {
             let teams = self.teams.read().unwrap();
-            let mut count = teams.len();
 
-            if let Some(ref status) = query.status {
-                count = teams
-                    .values()
-                    .filter(|t| t.status().to_string() == *status)
-                    .count();
-            }
+            let count = teams
+                .values()
+                .filter(|t| query.status.as_ref().is_none_or(|s| t.status().to_string() == *s))
+                .filter(|t| {
+                    query.organization_id.as_ref().is_none_or(|o| {
+                        t.organization_id().map(|id| id.as_str()) == Some(o.as_str())
+                    })
+                })
+                .count();
 
             Ok(count)
         }
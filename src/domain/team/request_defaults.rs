@@ -0,0 +1,300 @@
+//! Team defaults and limits for chat completion parameters
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::llm::{LlmRequest, Message, MessageRole};
+use crate::domain::{DomainError, FieldViolations};
+
+/// Highest temperature accepted by the chat completion API
+const MAX_TEMPERATURE: f32 = 2.0;
+
+/// What to do with a request parameter above a team limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ParameterLimitAction {
+    /// Lower the value to the limit
+    #[default]
+    Clamp,
+    /// Reject the request
+    Reject,
+}
+
+/// Defaults and limits applied to the chat completions of a team's API keys.
+/// Unset fields leave requests unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TeamRequestDefaults {
+    /// Model used when a request names no model (or `default`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_model: Option<String>,
+    /// Highest temperature requests may use
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_temperature: Option<f32>,
+    /// Highest `max_tokens` requests may use; also sent for requests
+    /// without `max_tokens`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Text put before the system prompt of every request, as a system
+    /// message of its own when the request has none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt_prefix: Option<String>,
+    /// Applied to parameters above a limit
+    #[serde(default)]
+    pub on_limit_exceeded: ParameterLimitAction,
+}
+
+/// A request parameter above a limit of the team
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("{param} {requested} exceeds the team limit of {limit}")]
+pub struct ParameterLimitExceeded {
+    pub param: &'static str,
+    pub requested: String,
+    pub limit: String,
+}
+
+impl TeamRequestDefaults {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if no default or limit is set
+    pub fn is_empty(&self) -> bool {
+        self.default_model.is_none()
+            && self.max_temperature.is_none()
+            && self.max_tokens.is_none()
+            && self.system_prompt_prefix.is_none()
+    }
+
+    pub fn validate(&self) -> Result<(), DomainError> {
+        let mut violations = FieldViolations::new();
+
+        if self
+            .default_model
+            .as_deref()
+            .is_some_and(|model| model.trim().is_empty())
+        {
+            violations.push("default_model", "Default model must not be empty");
+        }
+
+        if self
+            .max_temperature
+            .is_some_and(|t| !(0.0..=MAX_TEMPERATURE).contains(&t))
+        {
+            violations.push("max_temperature", "Max temperature must be between 0 and 2");
+        }
+
+        if self.max_tokens == Some(0) {
+            violations.push("max_tokens", "Max tokens must be greater than 0");
+        }
+
+        if self
+            .system_prompt_prefix
+            .as_deref()
+            .is_some_and(|prefix| prefix.trim().is_empty())
+        {
+            violations.push("system_prompt_prefix", "System prompt prefix must not be empty");
+        }
+
+        violations.into_result()
+    }
+
+    /// Model a request is served with: the team's default model when the
+    /// request names none
+    pub fn resolve_model<'a>(&'a self, requested: &'a str) -> &'a str {
+        let unnamed = requested.is_empty() || requested == "default";
+
+        match &self.default_model {
+            Some(default_model) if unnamed => default_model,
+            _ => requested,
+        }
+    }
+
+    /// Temperature a request is sent with
+    pub fn resolve_temperature(
+        &self,
+        requested: Option<f32>,
+    ) -> Result<Option<f32>, ParameterLimitExceeded> {
+        match (requested, self.max_temperature) {
+            (Some(requested), Some(limit)) if requested > limit => {
+                self.limit_exceeded("temperature", requested, limit)?;
+                Ok(Some(limit))
+            }
+            _ => Ok(requested),
+        }
+    }
+
+    /// `max_tokens` a request is sent with; requests without one get the
+    /// team's ceiling
+    pub fn resolve_max_tokens(
+        &self,
+        requested: Option<u32>,
+    ) -> Result<Option<u32>, ParameterLimitExceeded> {
+        match (requested, self.max_tokens) {
+            (Some(requested), Some(limit)) if requested > limit => {
+                self.limit_exceeded("max_tokens", requested, limit)?;
+                Ok(Some(limit))
+            }
+            (None, Some(limit)) => Ok(Some(limit)),
+            _ => Ok(requested),
+        }
+    }
+
+    /// Lower the temperature and `max_tokens` of a built request to the
+    /// limits, whatever the limit action: experiment overrides are applied
+    /// after the request values were checked
+    pub fn clamp_request(&self, request: &mut LlmRequest) {
+        if let Some(limit) = self.max_temperature {
+            request.temperature = request.temperature.map(|t| t.min(limit));
+        }
+
+        if let Some(limit) = self.max_tokens {
+            request.max_tokens = Some(request.max_tokens.map_or(limit, |m| m.min(limit)));
+        }
+    }
+
+    /// Put the system prompt prefix before the first system message, or in
+    /// a new system message at the start of the conversation
+    pub fn prefix_system_prompt(&self, messages: &mut Vec<Message>) {
+        let Some(prefix) = &self.system_prompt_prefix else {
+            return;
+        };
+
+        match messages
+            .iter_mut()
+            .find(|message| message.role == MessageRole::System)
+        {
+            Some(system) => system.map_text(|text| format!("{}\n\n{}", prefix, text)),
+            None => messages.insert(0, Message::system(prefix.clone())),
+        }
+    }
+
+    fn limit_exceeded(
+        &self,
+        param: &'static str,
+        requested: impl ToString,
+        limit: impl ToString,
+    ) -> Result<(), ParameterLimitExceeded> {
+        match self.on_limit_exceeded {
+            ParameterLimitAction::Clamp => Ok(()),
+            ParameterLimitAction::Reject => Err(ParameterLimitExceeded {
+                param,
+                requested: requested.to_string(),
+                limit: limit.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_defaults_leave_requests_unchanged() {
+        let defaults = TeamRequestDefaults::new();
+
+        assert!(defaults.is_empty());
+        assert_eq!(defaults.resolve_model("gpt-4o"), "gpt-4o");
+        assert_eq!(defaults.resolve_temperature(Some(1.8)).unwrap(), Some(1.8));
+        assert_eq!(defaults.resolve_max_tokens(None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_default_model_applies_to_unnamed_requests() {
+        let defaults = TeamRequestDefaults {
+            default_model: Some("gpt-4o-mini".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(defaults.resolve_model(""), "gpt-4o-mini");
+        assert_eq!(defaults.resolve_model("default"), "gpt-4o-mini");
+        assert_eq!(defaults.resolve_model("claude-3"), "claude-3");
+    }
+
+    #[test]
+    fn test_limits_clamp_or_reject() {
+        let mut defaults = TeamRequestDefaults {
+            max_temperature: Some(0.7),
+            max_tokens: Some(1000),
+            ..Default::default()
+        };
+
+        assert_eq!(defaults.resolve_temperature(Some(1.5)).unwrap(), Some(0.7));
+        assert_eq!(defaults.resolve_temperature(Some(0.2)).unwrap(), Some(0.2));
+        assert_eq!(defaults.resolve_temperature(None).unwrap(), None);
+        assert_eq!(defaults.resolve_max_tokens(Some(4000)).unwrap(), Some(1000));
+        assert_eq!(defaults.resolve_max_tokens(None).unwrap(), Some(1000));
+
+        defaults.on_limit_exceeded = ParameterLimitAction::Reject;
+        let err = defaults.resolve_max_tokens(Some(4000)).unwrap_err();
+        assert_eq!(err.param, "max_tokens");
+        assert_eq!(err.to_string(), "max_tokens 4000 exceeds the team limit of 1000");
+        assert_eq!(
+            defaults.resolve_temperature(Some(1.5)).unwrap_err().to_string(),
+            "temperature 1.5 exceeds the team limit of 0.7"
+        );
+        assert_eq!(defaults.resolve_max_tokens(Some(500)).unwrap(), Some(500));
+    }
+
+    #[test]
+    fn test_clamp_request() {
+        let defaults = TeamRequestDefaults {
+            max_temperature: Some(0.5),
+            max_tokens: Some(256),
+            on_limit_exceeded: ParameterLimitAction::Reject,
+            ..Default::default()
+        };
+
+        let mut request = LlmRequest::builder()
+            .user("Hi")
+            .temperature(1.2)
+            .max_tokens(1024)
+            .build();
+        defaults.clamp_request(&mut request);
+
+        assert_eq!(request.temperature, Some(0.5));
+        assert_eq!(request.max_tokens, Some(256));
+    }
+
+    #[test]
+    fn test_prefix_system_prompt() {
+        let defaults = TeamRequestDefaults {
+            system_prompt_prefix: Some("Follow the ACME style guide.".to_string()),
+            ..Default::default()
+        };
+
+        let mut messages = vec![Message::system("Be brief."), Message::user("Hi")];
+        defaults.prefix_system_prompt(&mut messages);
+        assert_eq!(
+            messages[0].content_text(),
+            Some("Follow the ACME style guide.\n\nBe brief.")
+        );
+
+        let mut messages = vec![Message::user("Hi")];
+        defaults.prefix_system_prompt(&mut messages);
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].role, MessageRole::System);
+    }
+
+    #[test]
+    fn test_validate() {
+        let defaults = TeamRequestDefaults {
+            default_model: Some(" ".to_string()),
+            max_temperature: Some(3.0),
+            max_tokens: Some(0),
+            ..Default::default()
+        };
+
+        match defaults.validate().unwrap_err() {
+            DomainError::InvalidFields { violations } => assert_eq!(violations.len(), 3),
+            other => panic!("unexpected error: {other}"),
+        }
+
+        assert!(TeamRequestDefaults {
+            max_temperature: Some(1.0),
+            ..Default::default()
+        }
+        .validate()
+        .is_ok());
+    }
+}
@@ -4,6 +4,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::domain::dataset::DatasetRef;
 use crate::domain::storage::{StorageEntity, StorageKey};
 use crate::domain::{validate_model_id, ModelValidationError};
 
@@ -262,6 +263,12 @@ pub struct TestCase {
     /// Tags for organization
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     tags: Vec<String>,
+    /// Dataset version to run against, once per row
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dataset: Option<DatasetRef>,
+    /// Whether the test case runs on the canary schedule against its model
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    canary: bool,
     /// Whether the test case is enabled
     enabled: bool,
     /// Creation timestamp
@@ -286,6 +293,8 @@ impl TestCase {
             input: TestCaseInput::ModelPrompt(input),
             assertions: Vec::new(),
             tags: Vec::new(),
+            dataset: None,
+            canary: false,
             enabled: true,
             created_at: now,
             updated_at: now,
@@ -303,6 +312,8 @@ impl TestCase {
             input: TestCaseInput::Workflow(input),
             assertions: Vec::new(),
             tags: Vec::new(),
+            dataset: None,
+            canary: false,
             enabled: true,
             created_at: now,
             updated_at: now,
@@ -330,6 +341,16 @@ impl TestCase {
         self
     }
 
+    pub fn with_dataset(mut self, dataset: DatasetRef) -> Self {
+        self.dataset = Some(dataset);
+        self
+    }
+
+    pub fn with_canary(mut self, canary: bool) -> Self {
+        self.canary = canary;
+        self
+    }
+
     pub fn with_enabled(mut self, enabled: bool) -> Self {
         self.enabled = enabled;
         self
@@ -364,6 +385,14 @@ impl TestCase {
         &self.tags
     }
 
+    pub fn dataset(&self) -> Option<&DatasetRef> {
+        self.dataset.as_ref()
+    }
+
+    pub fn is_canary(&self) -> bool {
+        self.canary
+    }
+
     pub fn is_enabled(&self) -> bool {
         self.enabled
     }
@@ -412,6 +441,16 @@ impl TestCase {
         self.touch();
     }
 
+    pub fn set_dataset(&mut self, dataset: Option<DatasetRef>) {
+        self.dataset = dataset;
+        self.touch();
+    }
+
+    pub fn set_canary(&mut self, canary: bool) {
+        self.canary = canary;
+        self.touch();
+    }
+
     pub fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
         self.touch();
@@ -1,21 +1,32 @@
 //! Test case domain - Test case definitions and execution results
 
 mod entity;
+mod regression;
 mod repository;
 mod result;
+mod suite;
 mod validation;
 
 pub use entity::{
     AssertionCriteria, AssertionOperator, ModelPromptInput, TestCase, TestCaseId, TestCaseInput,
     TestCaseType, WorkflowInput,
 };
+pub use regression::{
+    line_diff, CaseComparison, DiffLine, DiffOp, RegressionPolicy, RegressionReport,
+    RegressionReportId, RegressionSide, RegressionSubject,
+};
 pub use repository::{
     TestCaseQuery, TestCaseRepository, TestCaseResultQuery, TestCaseResultRepository,
+    TestSuiteRepository,
 };
 pub use result::{AssertionEvaluator, AssertionResult, TestCaseResult, TestCaseResultId, TokenUsage};
+pub use suite::{
+    TestSuite, TestSuiteCaseOutcome, TestSuiteId, TestSuiteRun, TestSuiteRunId,
+    DEFAULT_PASS_THRESHOLD, DEFAULT_SUITE_CONCURRENCY, MAX_SUITE_CONCURRENCY,
+};
 pub use validation::{
-    validate_assertion, validate_model_prompt_input, validate_test_case, validate_workflow_input,
-    TestCaseValidationError,
+    validate_assertion, validate_model_prompt_input, validate_test_case, validate_test_suite,
+    validate_workflow_input, TestCaseValidationError,
 };
 
 #[cfg(test)]
@@ -0,0 +1,381 @@
+//! Golden-output regression reports
+//!
+//! When a prompt or workflow changes, the test cases linked to it run
+//! against both the stored (baseline) and the updated (candidate) version.
+//! Each case is scored as the fraction of assertions it passed; a case
+//! regresses when its candidate score drops below the baseline score. The
+//! report keeps per-case score deltas and output diffs, and blocks the
+//! update when more cases regress than the policy allows.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::storage::{StorageEntity, StorageKey};
+
+/// Outputs longer than this many lines are diffed as a whole replacement
+const MAX_DIFF_LINES: usize = 2_000;
+
+/// Kind of resource a regression report was produced for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RegressionSubject {
+    Prompt,
+    Workflow,
+}
+
+impl std::fmt::Display for RegressionSubject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Prompt => write!(f, "prompt"),
+            Self::Workflow => write!(f, "workflow"),
+        }
+    }
+}
+
+/// How many regressed cases an update may introduce before it is blocked
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct RegressionPolicy {
+    #[serde(default)]
+    pub max_regressions: usize,
+}
+
+/// Operation of a diff line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffOp {
+    Equal,
+    Insert,
+    Delete,
+}
+
+/// A line of a diff between a baseline and a candidate output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DiffLine {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+impl DiffLine {
+    fn new(op: DiffOp, text: &str) -> Self {
+        Self {
+            op,
+            text: text.to_string(),
+        }
+    }
+}
+
+/// Line diff of two texts based on their longest common subsequence
+pub fn line_diff(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    if old.len() > MAX_DIFF_LINES || new.len() > MAX_DIFF_LINES {
+        return old
+            .iter()
+            .map(|line| DiffLine::new(DiffOp::Delete, line))
+            .chain(new.iter().map(|line| DiffLine::new(DiffOp::Insert, line)))
+            .collect();
+    }
+
+    // lcs[i][j] is the LCS length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            diff.push(DiffLine::new(DiffOp::Equal, old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(DiffLine::new(DiffOp::Delete, old[i]));
+            i += 1;
+        } else {
+            diff.push(DiffLine::new(DiffOp::Insert, new[j]));
+            j += 1;
+        }
+    }
+    diff.extend(old[i..].iter().map(|line| DiffLine::new(DiffOp::Delete, line)));
+    diff.extend(new[j..].iter().map(|line| DiffLine::new(DiffOp::Insert, line)));
+
+    diff
+}
+
+/// Result of one side of a comparison
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct RegressionSide {
+    pub passed: bool,
+    /// Fraction (0-1) of assertions passed; 0 when execution failed
+    pub score: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RegressionSide {
+    /// Score an execution from its assertion counts. A run without
+    /// assertions scores 1 unless it failed.
+    pub fn new(
+        output: Option<String>,
+        error: Option<String>,
+        assertions_passed: usize,
+        assertions_total: usize,
+    ) -> Self {
+        let score = if error.is_some() {
+            0.0
+        } else if assertions_total == 0 {
+            1.0
+        } else {
+            assertions_passed as f64 / assertions_total as f64
+        };
+
+        Self {
+            passed: error.is_none() && assertions_passed == assertions_total,
+            score,
+            output,
+            error,
+        }
+    }
+}
+
+/// Baseline and candidate results of one test case
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CaseComparison {
+    pub test_case_id: String,
+    pub test_case_name: String,
+    pub baseline: RegressionSide,
+    pub candidate: RegressionSide,
+    /// Candidate score minus baseline score
+    pub score_delta: f64,
+    pub regressed: bool,
+    pub improved: bool,
+    /// Diff of the outputs, empty when they are identical
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub output_diff: Vec<DiffLine>,
+}
+
+impl CaseComparison {
+    /// Compare the baseline and candidate results of a test case
+    pub fn new(
+        test_case_id: impl Into<String>,
+        test_case_name: impl Into<String>,
+        baseline: RegressionSide,
+        candidate: RegressionSide,
+    ) -> Self {
+        let score_delta = candidate.score - baseline.score;
+        let output_diff = match (&baseline.output, &candidate.output) {
+            (Some(old), Some(new)) if old == new => Vec::new(),
+            (old, new) => line_diff(
+                old.as_deref().unwrap_or_default(),
+                new.as_deref().unwrap_or_default(),
+            ),
+        };
+
+        Self {
+            test_case_id: test_case_id.into(),
+            test_case_name: test_case_name.into(),
+            regressed: score_delta < 0.0,
+            improved: score_delta > 0.0,
+            score_delta,
+            baseline,
+            candidate,
+            output_diff,
+        }
+    }
+}
+
+/// Unique identifier for a regression report
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub struct RegressionReportId(String);
+
+impl RegressionReportId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4().to_string())
+    }
+
+    pub fn from_string(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for RegressionReportId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for RegressionReportId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StorageKey for RegressionReportId {
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Comparison of the linked test cases of a prompt or workflow between
+/// its stored version and an update
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RegressionReport {
+    pub id: RegressionReportId,
+    pub subject: RegressionSubject,
+    pub resource_id: String,
+    pub baseline_version: u32,
+    /// Version the update creates
+    pub candidate_version: u32,
+    pub comparisons: Vec<CaseComparison>,
+    pub regressions: usize,
+    pub improvements: usize,
+    /// Mean score delta across cases
+    pub score_delta: f64,
+    /// Regressions allowed by the policy
+    pub max_regressions: usize,
+    /// Whether the update was blocked
+    pub blocked: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RegressionReport {
+    /// Aggregate case comparisons against a policy
+    pub fn new(
+        subject: RegressionSubject,
+        resource_id: impl Into<String>,
+        baseline_version: u32,
+        candidate_version: u32,
+        comparisons: Vec<CaseComparison>,
+        policy: &RegressionPolicy,
+    ) -> Self {
+        let regressions = comparisons.iter().filter(|c| c.regressed).count();
+        let improvements = comparisons.iter().filter(|c| c.improved).count();
+        let score_delta = if comparisons.is_empty() {
+            0.0
+        } else {
+            comparisons.iter().map(|c| c.score_delta).sum::<f64>() / comparisons.len() as f64
+        };
+
+        Self {
+            id: RegressionReportId::new(),
+            subject,
+            resource_id: resource_id.into(),
+            baseline_version,
+            candidate_version,
+            comparisons,
+            regressions,
+            improvements,
+            score_delta,
+            max_regressions: policy.max_regressions,
+            blocked: regressions > policy.max_regressions,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+impl StorageEntity for RegressionReport {
+    type Key = RegressionReportId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn side(output: &str, passed: usize, total: usize) -> RegressionSide {
+        RegressionSide::new(Some(output.to_string()), None, passed, total)
+    }
+
+    #[test]
+    fn test_line_diff() {
+        let diff = line_diff("a\nb\nc", "a\nc\nd");
+        let ops: Vec<(DiffOp, &str)> = diff.iter().map(|l| (l.op, l.text.as_str())).collect();
+
+        assert_eq!(
+            ops,
+            vec![
+                (DiffOp::Equal, "a"),
+                (DiffOp::Delete, "b"),
+                (DiffOp::Equal, "c"),
+                (DiffOp::Insert, "d"),
+            ]
+        );
+        assert!(line_diff("same", "same")
+            .iter()
+            .all(|l| l.op == DiffOp::Equal));
+    }
+
+    #[test]
+    fn test_side_score() {
+        assert_eq!(side("x", 0, 0).score, 1.0);
+        assert_eq!(side("x", 1, 4).score, 0.25);
+        assert!(!side("x", 1, 4).passed);
+
+        let failed = RegressionSide::new(None, Some("timeout".to_string()), 0, 0);
+        assert_eq!(failed.score, 0.0);
+        assert!(!failed.passed);
+    }
+
+    #[test]
+    fn test_comparison() {
+        let regressed = CaseComparison::new("a", "A", side("yes", 2, 2), side("no", 1, 2));
+        assert!(regressed.regressed);
+        assert_eq!(regressed.score_delta, -0.5);
+        assert_eq!(regressed.output_diff.len(), 2);
+
+        let unchanged = CaseComparison::new("b", "B", side("yes", 2, 2), side("yes", 2, 2));
+        assert!(!unchanged.regressed && !unchanged.improved);
+        assert!(unchanged.output_diff.is_empty());
+    }
+
+    #[test]
+    fn test_report_blocking() {
+        let comparisons = vec![
+            CaseComparison::new("a", "A", side("x", 2, 2), side("y", 1, 2)),
+            CaseComparison::new("b", "B", side("x", 1, 2), side("x", 2, 2)),
+            CaseComparison::new("c", "C", side("x", 1, 1), side("z", 0, 1)),
+        ];
+
+        let strict = RegressionReport::new(
+            RegressionSubject::Prompt,
+            "greeting",
+            1,
+            2,
+            comparisons.clone(),
+            &RegressionPolicy::default(),
+        );
+        assert_eq!(strict.regressions, 2);
+        assert_eq!(strict.improvements, 1);
+        assert!((strict.score_delta - (-1.0 / 3.0)).abs() < 1e-9);
+        assert!(strict.blocked);
+
+        let lenient = RegressionReport::new(
+            RegressionSubject::Prompt,
+            "greeting",
+            1,
+            2,
+            comparisons,
+            &RegressionPolicy { max_regressions: 2 },
+        );
+        assert!(!lenient.blocked);
+    }
+}
@@ -4,7 +4,10 @@ use async_trait::async_trait;
 
 use crate::domain::error::DomainError;
 
-use super::{TestCase, TestCaseId, TestCaseResult, TestCaseResultId, TestCaseType};
+use super::{
+    TestCase, TestCaseId, TestCaseResult, TestCaseResultId, TestCaseType, TestSuite, TestSuiteId,
+    TestSuiteRun, TestSuiteRunId,
+};
 
 /// Query parameters for listing test cases
 #[derive(Debug, Clone, Default)]
@@ -19,6 +22,8 @@ pub struct TestCaseQuery {
     pub model_id: Option<String>,
     /// Filter by workflow ID (for Workflow tests)
     pub workflow_id: Option<String>,
+    /// Filter by canary designation
+    pub canary: Option<bool>,
     /// Maximum number of results
     pub limit: Option<usize>,
     /// Offset for pagination
@@ -55,6 +60,11 @@ impl TestCaseQuery {
         self
     }
 
+    pub fn with_canary(mut self, canary: bool) -> Self {
+        self.canary = Some(canary);
+        self
+    }
+
     pub fn with_limit(mut self, limit: usize) -> Self {
         self.limit = Some(limit);
         self
@@ -143,6 +153,35 @@ pub trait TestCaseResultRepository: Send + Sync {
     async fn get_latest(&self, test_case_id: &TestCaseId) -> Result<Option<TestCaseResult>, DomainError>;
 }
 
+/// Repository trait for test suites and their run reports
+#[async_trait]
+pub trait TestSuiteRepository: Send + Sync {
+    /// Get a test suite by ID
+    async fn get(&self, id: &TestSuiteId) -> Result<Option<TestSuite>, DomainError>;
+
+    /// List every test suite, ordered by name
+    async fn list(&self) -> Result<Vec<TestSuite>, DomainError>;
+
+    /// Save a test suite (create or update)
+    async fn save(&self, suite: &TestSuite) -> Result<(), DomainError>;
+
+    /// Delete a test suite and its runs
+    async fn delete(&self, id: &TestSuiteId) -> Result<bool, DomainError>;
+
+    /// Save a run report
+    async fn save_run(&self, run: &TestSuiteRun) -> Result<(), DomainError>;
+
+    /// Get a run report by ID
+    async fn get_run(&self, id: &TestSuiteRunId) -> Result<Option<TestSuiteRun>, DomainError>;
+
+    /// List the runs of a suite, newest first
+    async fn list_runs(
+        &self,
+        suite_id: &TestSuiteId,
+        limit: Option<usize>,
+    ) -> Result<Vec<TestSuiteRun>, DomainError>;
+}
+
 #[cfg(test)]
 pub mod mock {
     use super::*;
@@ -204,6 +243,13 @@ pub mod mock {
                         }
                     }
 
+                    // Filter by canary
+                    if let Some(canary) = query.canary {
+                        if tc.is_canary() != canary {
+                            return false;
+                        }
+                    }
+
                     // Filter by tag
                     if let Some(ref tag) = query.tag {
                         if !tc.tags().contains(tag) {
@@ -7,6 +7,7 @@ use serde_json::Value;
 use uuid::Uuid;
 
 use super::{AssertionCriteria, AssertionOperator, TestCaseId};
+use crate::domain::dataset::DatasetRef;
 use crate::domain::storage::{StorageEntity, StorageKey};
 
 /// Unique identifier for a test case result
@@ -127,6 +128,9 @@ pub struct TestCaseResult {
     /// Error if execution failed
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<String>,
+    /// Dataset version the test ran against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dataset: Option<DatasetRef>,
     /// When the test was run
     executed_at: DateTime<Utc>,
 }
@@ -158,6 +162,7 @@ impl TestCaseResult {
             execution_time_ms,
             tokens_used: None,
             error: None,
+            dataset: None,
             executed_at: Utc::now(),
         }
     }
@@ -181,6 +186,7 @@ impl TestCaseResult {
             execution_time_ms,
             tokens_used: None,
             error: None,
+            dataset: None,
             executed_at: Utc::now(),
         }
     }
@@ -201,6 +207,7 @@ impl TestCaseResult {
             execution_time_ms,
             tokens_used: None,
             error: Some(error.into()),
+            dataset: None,
             executed_at: Utc::now(),
         }
     }
@@ -211,6 +218,11 @@ impl TestCaseResult {
         self
     }
 
+    pub fn with_dataset(mut self, dataset: DatasetRef) -> Self {
+        self.dataset = Some(dataset);
+        self
+    }
+
     // Getters
     pub fn id(&self) -> &TestCaseResultId {
         &self.id
@@ -244,6 +256,10 @@ impl TestCaseResult {
         self.tokens_used.as_ref()
     }
 
+    pub fn dataset(&self) -> Option<&DatasetRef> {
+        self.dataset.as_ref()
+    }
+
     pub fn error(&self) -> Option<&str> {
         self.error.as_deref()
     }
@@ -0,0 +1,399 @@
+//! Test suite entity and run reports
+//!
+//! A suite groups test cases so a prompt or model change can be checked as a
+//! whole: every case runs, the pass rate is compared against the suite
+//! threshold and the run passes or fails the gate.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::TestCaseId;
+use crate::domain::storage::{StorageEntity, StorageKey};
+use crate::domain::{validate_model_id, ModelValidationError};
+
+/// Default percentage of cases that must pass
+pub const DEFAULT_PASS_THRESHOLD: f64 = 100.0;
+
+/// Default number of cases executed at once
+pub const DEFAULT_SUITE_CONCURRENCY: usize = 4;
+
+/// Maximum number of cases executed at once
+pub const MAX_SUITE_CONCURRENCY: usize = 32;
+
+/// Test suite identifier
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TestSuiteId(String);
+
+impl TestSuiteId {
+    pub fn new(id: impl Into<String>) -> Result<Self, ModelValidationError> {
+        let id = id.into();
+        validate_model_id(&id)?;
+        Ok(Self(id))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for TestSuiteId {
+    type Error = ModelValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<TestSuiteId> for String {
+    fn from(id: TestSuiteId) -> Self {
+        id.0
+    }
+}
+
+impl std::fmt::Display for TestSuiteId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StorageKey for TestSuiteId {
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+fn default_pass_threshold() -> f64 {
+    DEFAULT_PASS_THRESHOLD
+}
+
+fn default_concurrency() -> usize {
+    DEFAULT_SUITE_CONCURRENCY
+}
+
+/// A group of test cases run and gated together
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestSuite {
+    /// Unique identifier
+    id: TestSuiteId,
+    /// Display name
+    name: String,
+    /// Description of what this suite covers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    /// Test cases in the suite, run in this order
+    test_case_ids: Vec<TestCaseId>,
+    /// Tags for organization
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    /// Percentage of cases (0-100) that must pass for a run to pass the gate
+    #[serde(default = "default_pass_threshold")]
+    pass_threshold: f64,
+    /// Number of cases executed at once
+    #[serde(default = "default_concurrency")]
+    concurrency: usize,
+    /// Creation timestamp
+    created_at: DateTime<Utc>,
+    /// Last update timestamp
+    updated_at: DateTime<Utc>,
+}
+
+impl TestSuite {
+    /// Create a new suite requiring every case to pass
+    pub fn new(id: TestSuiteId, name: impl Into<String>, test_case_ids: Vec<TestCaseId>) -> Self {
+        let now = Utc::now();
+        Self {
+            id,
+            name: name.into(),
+            description: None,
+            test_case_ids,
+            tags: Vec::new(),
+            pass_threshold: DEFAULT_PASS_THRESHOLD,
+            concurrency: DEFAULT_SUITE_CONCURRENCY,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    // Builder methods
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn with_pass_threshold(mut self, pass_threshold: f64) -> Self {
+        self.pass_threshold = pass_threshold;
+        self
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    // Getters
+    pub fn id(&self) -> &TestSuiteId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn test_case_ids(&self) -> &[TestCaseId] {
+        &self.test_case_ids
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    pub fn pass_threshold(&self) -> f64 {
+        self.pass_threshold
+    }
+
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    // Mutators
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.name = name.into();
+        self.touch();
+    }
+
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.description = description;
+        self.touch();
+    }
+
+    pub fn set_test_case_ids(&mut self, test_case_ids: Vec<TestCaseId>) {
+        self.test_case_ids = test_case_ids;
+        self.touch();
+    }
+
+    pub fn set_tags(&mut self, tags: Vec<String>) {
+        self.tags = tags;
+        self.touch();
+    }
+
+    pub fn set_pass_threshold(&mut self, pass_threshold: f64) {
+        self.pass_threshold = pass_threshold;
+        self.touch();
+    }
+
+    pub fn set_concurrency(&mut self, concurrency: usize) {
+        self.concurrency = concurrency;
+        self.touch();
+    }
+
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+    }
+}
+
+impl StorageEntity for TestSuite {
+    type Key = TestSuiteId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+/// Unique identifier for a test suite run
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TestSuiteRunId(String);
+
+impl TestSuiteRunId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4().to_string())
+    }
+
+    pub fn from_string(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for TestSuiteRunId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for TestSuiteRunId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StorageKey for TestSuiteRunId {
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Outcome of one case in a suite run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TestSuiteCaseOutcome {
+    pub test_case_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_case_name: Option<String>,
+    pub passed: bool,
+    pub execution_time_ms: u64,
+    /// Execution error, or the first failed assertion
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Aggregated report of a suite run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestSuiteRun {
+    pub id: TestSuiteRunId,
+    pub suite_id: TestSuiteId,
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    /// Percentage of passed cases (0-100)
+    pub pass_rate: f64,
+    /// Pass rate required by the suite when the run started
+    pub pass_threshold: f64,
+    /// Whether the pass rate reached the threshold
+    pub gate_passed: bool,
+    pub execution_time_ms: u64,
+    pub cases: Vec<TestSuiteCaseOutcome>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+}
+
+impl TestSuiteRun {
+    /// Aggregate case outcomes against a pass threshold
+    pub fn from_outcomes(
+        suite_id: TestSuiteId,
+        pass_threshold: f64,
+        cases: Vec<TestSuiteCaseOutcome>,
+        started_at: DateTime<Utc>,
+    ) -> Self {
+        let completed_at = Utc::now();
+        let total = cases.len();
+        let passed = cases.iter().filter(|c| c.passed).count();
+        let pass_rate = if total == 0 {
+            0.0
+        } else {
+            passed as f64 / total as f64 * 100.0
+        };
+
+        Self {
+            id: TestSuiteRunId::new(),
+            suite_id,
+            total,
+            passed,
+            failed: total - passed,
+            pass_rate,
+            pass_threshold,
+            gate_passed: total > 0 && pass_rate >= pass_threshold,
+            execution_time_ms: (completed_at - started_at).num_milliseconds().max(0) as u64,
+            cases,
+            started_at,
+            completed_at,
+        }
+    }
+
+    /// Outcomes of the cases that failed
+    pub fn failures(&self) -> impl Iterator<Item = &TestSuiteCaseOutcome> {
+        self.cases.iter().filter(|c| !c.passed)
+    }
+}
+
+impl StorageEntity for TestSuiteRun {
+    type Key = TestSuiteRunId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outcome(id: &str, passed: bool) -> TestSuiteCaseOutcome {
+        TestSuiteCaseOutcome {
+            test_case_id: id.to_string(),
+            test_case_name: None,
+            passed,
+            execution_time_ms: 10,
+            error: (!passed).then(|| "assertion failed".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_suite_defaults() {
+        let suite = TestSuite::new(
+            TestSuiteId::new("smoke").unwrap(),
+            "Smoke",
+            vec![TestCaseId::new("greeting").unwrap()],
+        );
+
+        assert_eq!(suite.pass_threshold(), DEFAULT_PASS_THRESHOLD);
+        assert_eq!(suite.concurrency(), DEFAULT_SUITE_CONCURRENCY);
+
+        let json = serde_json::json!({
+            "id": "smoke",
+            "name": "Smoke",
+            "test_case_ids": ["greeting"],
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-01T00:00:00Z"
+        });
+        let parsed: TestSuite = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.pass_threshold(), DEFAULT_PASS_THRESHOLD);
+        assert_eq!(parsed.test_case_ids().len(), 1);
+    }
+
+    #[test]
+    fn test_run_gate() {
+        let suite_id = TestSuiteId::new("smoke").unwrap();
+        let cases = vec![
+            outcome("a", true),
+            outcome("b", true),
+            outcome("c", true),
+            outcome("d", false),
+        ];
+
+        let run = TestSuiteRun::from_outcomes(suite_id.clone(), 75.0, cases.clone(), Utc::now());
+        assert_eq!(run.total, 4);
+        assert_eq!(run.passed, 3);
+        assert_eq!(run.failed, 1);
+        assert_eq!(run.pass_rate, 75.0);
+        assert!(run.gate_passed);
+        assert_eq!(run.failures().count(), 1);
+
+        let strict = TestSuiteRun::from_outcomes(suite_id.clone(), 100.0, cases, Utc::now());
+        assert!(!strict.gate_passed);
+
+        let empty = TestSuiteRun::from_outcomes(suite_id, 0.0, Vec::new(), Utc::now());
+        assert!(!empty.gate_passed);
+    }
+}
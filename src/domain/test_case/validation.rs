@@ -37,9 +37,25 @@ pub enum TestCaseValidationError {
 
     #[error("Test case already exists: {0}")]
     AlreadyExists(String),
+
+    #[error("Test suite must contain at least one test case")]
+    NoTestCases,
+
+    #[error("Test case '{0}' appears more than once in the suite")]
+    DuplicateTestCase(String),
+
+    #[error("Pass threshold must be between 0 and 100")]
+    InvalidPassThreshold,
+
+    #[error("Concurrency must be between 1 and {MAX_SUITE_CONCURRENCY}")]
+    InvalidConcurrency,
 }
 
-use super::{AssertionCriteria, AssertionOperator, ModelPromptInput, TestCase, TestCaseInput, WorkflowInput};
+use super::suite::MAX_SUITE_CONCURRENCY;
+use super::{
+    AssertionCriteria, AssertionOperator, ModelPromptInput, TestCase, TestCaseInput, TestSuite,
+    WorkflowInput,
+};
 use regex::Regex;
 
 /// Validate a test case
@@ -67,6 +83,38 @@ pub fn validate_test_case(test_case: &TestCase) -> Result<(), TestCaseValidation
     Ok(())
 }
 
+/// Validate a test suite
+pub fn validate_test_suite(suite: &TestSuite) -> Result<(), TestCaseValidationError> {
+    if suite.name().is_empty() {
+        return Err(TestCaseValidationError::NameRequired);
+    }
+
+    if suite.name().len() > 100 {
+        return Err(TestCaseValidationError::NameTooLong);
+    }
+
+    if suite.test_case_ids().is_empty() {
+        return Err(TestCaseValidationError::NoTestCases);
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    for id in suite.test_case_ids() {
+        if !seen.insert(id) {
+            return Err(TestCaseValidationError::DuplicateTestCase(id.to_string()));
+        }
+    }
+
+    if !(0.0..=100.0).contains(&suite.pass_threshold()) {
+        return Err(TestCaseValidationError::InvalidPassThreshold);
+    }
+
+    if !(1..=MAX_SUITE_CONCURRENCY).contains(&suite.concurrency()) {
+        return Err(TestCaseValidationError::InvalidConcurrency);
+    }
+
+    Ok(())
+}
+
 /// Validate model+prompt input
 pub fn validate_model_prompt_input(input: &ModelPromptInput) -> Result<(), TestCaseValidationError> {
     if input.model_id.is_empty() {
@@ -213,4 +261,38 @@ mod tests {
             Err(TestCaseValidationError::InvalidJsonPath(_))
         ));
     }
+
+    #[test]
+    fn test_validate_test_suite() {
+        use crate::domain::test_case::TestSuiteId;
+
+        let id = |s: &str| TestCaseId::new(s).unwrap();
+        let suite = TestSuite::new(TestSuiteId::new("smoke").unwrap(), "Smoke", vec![id("a")]);
+        assert!(validate_test_suite(&suite).is_ok());
+
+        let empty = TestSuite::new(TestSuiteId::new("smoke").unwrap(), "Smoke", vec![]);
+        assert_eq!(
+            validate_test_suite(&empty),
+            Err(TestCaseValidationError::NoTestCases)
+        );
+
+        let duplicated = TestSuite::new(
+            TestSuiteId::new("smoke").unwrap(),
+            "Smoke",
+            vec![id("a"), id("a")],
+        );
+        assert!(matches!(
+            validate_test_suite(&duplicated),
+            Err(TestCaseValidationError::DuplicateTestCase(_))
+        ));
+
+        assert_eq!(
+            validate_test_suite(&suite.clone().with_pass_threshold(120.0)),
+            Err(TestCaseValidationError::InvalidPassThreshold)
+        );
+        assert_eq!(
+            validate_test_suite(&suite.with_concurrency(0)),
+            Err(TestCaseValidationError::InvalidConcurrency)
+        );
+    }
 }
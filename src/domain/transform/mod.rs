@@ -0,0 +1,8 @@
+//! Request transform rules applied before routing
+
+mod rule;
+
+pub use rule::{
+    MAX_TRANSFORM_RULE_ID_LENGTH, TransformAction, TransformMatch, TransformOutcome,
+    TransformRule, TransformRuleId, TransformTarget,
+};
@@ -0,0 +1,425 @@
+//! Request transform rules evaluated at the edge
+//!
+//! A rule matches requests on their route, API key and model, and rewrites
+//! them before routing: it can replace the model, inject a system message,
+//! add metadata tags and set response headers. Rules are applied in order of
+//! `priority` (lowest first), so later rules override the model, tags and
+//! headers set by earlier ones.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::storage::{StorageEntity, StorageKey};
+use crate::domain::usage::validate_usage_tags;
+use crate::domain::DomainError;
+
+/// Longest allowed transform rule ID
+pub const MAX_TRANSFORM_RULE_ID_LENGTH: usize = 50;
+
+/// Headers a rule may not set, as the gateway owns them
+const RESERVED_HEADERS: &[&str] = &["content-length", "content-type", "transfer-encoding"];
+
+/// Transform rule identifier - alphanumeric + hyphens, max 50 characters
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub struct TransformRuleId(String);
+
+impl TransformRuleId {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// Get the inner string value
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for TransformRuleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StorageKey for TransformRuleId {
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl StorageEntity for TransformRule {
+    type Key = TransformRuleId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+/// Requests a rule applies to. Empty lists match anything.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TransformMatch {
+    /// Request paths, e.g. `/v1/chat/completions`
+    #[serde(default)]
+    pub routes: Vec<String>,
+    #[serde(default)]
+    pub api_key_ids: Vec<String>,
+    /// Requested model IDs; a trailing `*` matches a prefix (`gpt-4*`)
+    #[serde(default)]
+    pub models: Vec<String>,
+}
+
+impl TransformMatch {
+    fn matches(&self, target: &TransformTarget<'_>) -> bool {
+        (self.routes.is_empty() || self.routes.iter().any(|r| r == target.route))
+            && (self.api_key_ids.is_empty()
+                || self.api_key_ids.iter().any(|k| k == target.api_key_id))
+            && (self.models.is_empty()
+                || self.models.iter().any(|m| model_matches(m, target.model)))
+    }
+}
+
+fn model_matches(pattern: &str, model: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => model.starts_with(prefix),
+        None => pattern == model,
+    }
+}
+
+/// Change a rule makes to a matching request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TransformAction {
+    /// Set a header on the response
+    SetHeader { name: String, value: String },
+    /// Insert a system message at the start of the conversation
+    InjectSystemMessage { content: String },
+    /// Serve the request with another model
+    RewriteModel { model: String },
+    /// Add metadata tags, recorded with the request's usage
+    AddTags { tags: HashMap<String, String> },
+}
+
+/// Request a rule is evaluated against
+#[derive(Debug, Clone, Copy)]
+pub struct TransformTarget<'a> {
+    pub route: &'a str,
+    pub api_key_id: &'a str,
+    pub model: &'a str,
+}
+
+/// Changes of every rule matching a request
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransformOutcome {
+    /// Matching rules, in the order they were applied
+    pub rule_ids: Vec<String>,
+    pub model: Option<String>,
+    pub system_messages: Vec<String>,
+    pub tags: HashMap<String, String>,
+    pub headers: Vec<(String, String)>,
+}
+
+impl TransformOutcome {
+    /// Apply the matching rules among `rules`, which must be ordered by
+    /// priority
+    pub fn evaluate<'a>(
+        rules: impl IntoIterator<Item = &'a TransformRule>,
+        target: &TransformTarget<'_>,
+    ) -> Self {
+        let mut outcome = Self::default();
+
+        for rule in rules.into_iter().filter(|r| r.applies_to(target)) {
+            outcome.rule_ids.push(rule.id.to_string());
+
+            for action in &rule.actions {
+                outcome.apply(action);
+            }
+        }
+
+        outcome
+    }
+
+    /// Whether no rule matched
+    pub fn is_empty(&self) -> bool {
+        self.rule_ids.is_empty()
+    }
+
+    fn apply(&mut self, action: &TransformAction) {
+        match action {
+            TransformAction::SetHeader { name, value } => {
+                let name = name.to_ascii_lowercase();
+                self.headers.retain(|(existing, _)| *existing != name);
+                self.headers.push((name, value.clone()));
+            }
+            TransformAction::InjectSystemMessage { content } => {
+                self.system_messages.push(content.clone());
+            }
+            TransformAction::RewriteModel { model } => self.model = Some(model.clone()),
+            TransformAction::AddTags { tags } => {
+                self.tags
+                    .extend(tags.iter().map(|(k, v)| (k.clone(), v.clone())));
+            }
+        }
+    }
+}
+
+/// Rewrite of matching requests, managed by admins so common gateway tweaks
+/// need no code changes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TransformRule {
+    pub id: TransformRuleId,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Rules are applied lowest priority first
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(rename = "match", default)]
+    pub matcher: TransformMatch,
+    pub actions: Vec<TransformAction>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Creation date (unix timestamp)
+    pub created_at: u64,
+    /// Last update date (unix timestamp)
+    pub updated_at: u64,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl TransformRule {
+    /// Create a rule without actions, matching every request
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> Self {
+        let now = chrono::Utc::now().timestamp().max(0) as u64;
+
+        Self {
+            id: TransformRuleId::new(id),
+            name: name.into(),
+            description: None,
+            priority: 0,
+            matcher: TransformMatch::default(),
+            actions: Vec::new(),
+            enabled: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Set what the rule matches
+    pub fn with_match(mut self, matcher: TransformMatch) -> Self {
+        self.matcher = matcher;
+        self
+    }
+
+    /// Set the priority
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Add an action
+    pub fn with_action(mut self, action: TransformAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// Whether the rule rewrites a request
+    pub fn applies_to(&self, target: &TransformTarget<'_>) -> bool {
+        self.enabled && self.matcher.matches(target)
+    }
+
+    /// Validates the ID, match and actions
+    pub fn validate(&self) -> Result<(), DomainError> {
+        let id = self.id.as_str();
+
+        if id.is_empty() || id.len() > MAX_TRANSFORM_RULE_ID_LENGTH {
+            return Err(DomainError::validation(format!(
+                "Transform rule ID must be 1-{} characters",
+                MAX_TRANSFORM_RULE_ID_LENGTH
+            )));
+        }
+
+        if !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            || id.starts_with('-')
+            || id.ends_with('-')
+        {
+            return Err(DomainError::validation(
+                "Transform rule ID must be alphanumeric with inner hyphens",
+            ));
+        }
+
+        if self.name.trim().is_empty() {
+            return Err(DomainError::validation("name is required"));
+        }
+
+        if self.matcher.routes.iter().any(|r| !r.starts_with('/')) {
+            return Err(DomainError::validation("Routes must start with '/'"));
+        }
+
+        if self.actions.is_empty() {
+            return Err(DomainError::validation("At least one action is required"));
+        }
+
+        self.actions.iter().try_for_each(validate_action)
+    }
+}
+
+fn validate_action(action: &TransformAction) -> Result<(), DomainError> {
+    match action {
+        TransformAction::SetHeader { name, value } => {
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+            {
+                return Err(DomainError::validation(format!(
+                    "Invalid header name '{}'",
+                    name
+                )));
+            }
+
+            if RESERVED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                return Err(DomainError::validation(format!(
+                    "Header '{}' is set by the gateway",
+                    name
+                )));
+            }
+
+            if value.chars().any(|c| c.is_control()) {
+                return Err(DomainError::validation(format!(
+                    "Value of header '{}' must not contain control characters",
+                    name
+                )));
+            }
+        }
+        TransformAction::InjectSystemMessage { content } => {
+            if content.trim().is_empty() {
+                return Err(DomainError::validation("System messages must not be empty"));
+            }
+        }
+        TransformAction::RewriteModel { model } => {
+            if model.trim().is_empty() {
+                return Err(DomainError::validation("Rewritten model must not be empty"));
+            }
+        }
+        TransformAction::AddTags { tags } => validate_usage_tags(tags)?,
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TARGET: TransformTarget<'static> = TransformTarget {
+        route: "/v1/chat/completions",
+        api_key_id: "key-1",
+        model: "gpt-4o",
+    };
+
+    fn rewrite(id: &str, model: &str) -> TransformRule {
+        TransformRule::new(id, id).with_action(TransformAction::RewriteModel {
+            model: model.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(rewrite("to-mini", "gpt-4o-mini").validate().is_ok());
+        assert!(TransformRule::new("empty", "Empty").validate().is_err());
+        assert!(rewrite("bad id", "gpt-4o").validate().is_err());
+
+        let header = |name: &str| {
+            TransformRule::new("header", "Header").with_action(TransformAction::SetHeader {
+                name: name.to_string(),
+                value: "1".to_string(),
+            })
+        };
+        assert!(header("x-gateway-rule").validate().is_ok());
+        assert!(header("Content-Type").validate().is_err());
+        assert!(header("bad header").validate().is_err());
+
+        let tags = TransformRule::new("tags", "Tags").with_action(TransformAction::AddTags {
+            tags: HashMap::from([("bad key".to_string(), "x".to_string())]),
+        });
+        assert!(tags.validate().is_err());
+    }
+
+    #[test]
+    fn test_match() {
+        let rule = rewrite("to-mini", "gpt-4o-mini").with_match(TransformMatch {
+            routes: vec!["/v1/chat/completions".to_string()],
+            models: vec!["gpt-4*".to_string()],
+            ..Default::default()
+        });
+
+        assert!(rule.applies_to(&TARGET));
+        assert!(!rule.applies_to(&TransformTarget {
+            model: "claude-3",
+            ..TARGET
+        }));
+        assert!(!rule.applies_to(&TransformTarget {
+            route: "/v1/embeddings",
+            ..TARGET
+        }));
+
+        let other_key = rule.with_match(TransformMatch {
+            api_key_ids: vec!["key-2".to_string()],
+            ..Default::default()
+        });
+        assert!(!other_key.applies_to(&TARGET));
+    }
+
+    #[test]
+    fn test_evaluate_in_order() {
+        let mut disabled = rewrite("disabled", "never");
+        disabled.enabled = false;
+
+        let rules = vec![
+            rewrite("first", "gpt-4o-mini").with_action(TransformAction::SetHeader {
+                name: "X-Route".to_string(),
+                value: "first".to_string(),
+            }),
+            rewrite("second", "claude-3")
+                .with_action(TransformAction::SetHeader {
+                    name: "x-route".to_string(),
+                    value: "second".to_string(),
+                })
+                .with_action(TransformAction::InjectSystemMessage {
+                    content: "Be brief.".to_string(),
+                }),
+            disabled,
+        ];
+
+        let outcome = TransformOutcome::evaluate(&rules, &TARGET);
+        assert_eq!(outcome.rule_ids, vec!["first", "second"]);
+        assert_eq!(outcome.model.as_deref(), Some("claude-3"));
+        assert_eq!(
+            outcome.headers,
+            vec![("x-route".to_string(), "second".to_string())]
+        );
+        assert_eq!(outcome.system_messages, vec!["Be brief."]);
+
+        assert!(TransformOutcome::evaluate(&[], &TARGET).is_empty());
+    }
+
+    #[test]
+    fn test_serde() {
+        let rule: TransformRule = serde_json::from_value(serde_json::json!({
+            "id": "tag-batch",
+            "name": "Tag batch",
+            "match": {"api_key_ids": ["key-1"]},
+            "actions": [{"type": "add_tags", "tags": {"source": "batch"}}],
+            "created_at": 0,
+            "updated_at": 0,
+        }))
+        .unwrap();
+
+        assert!(rule.enabled);
+        assert_eq!(rule.matcher.api_key_ids, vec!["key-1"]);
+        assert!(rule.validate().is_ok());
+    }
+}
@@ -0,0 +1,208 @@
+//! Usage spike detection against a learned per-group baseline
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Serialize;
+
+use super::{UsageBucket, UsageGroupBy};
+
+/// Usage measure compared against its baseline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyMetric {
+    Requests,
+    Cost,
+}
+
+impl fmt::Display for AnomalyMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Requests => write!(f, "requests"),
+            Self::Cost => write!(f, "cost"),
+        }
+    }
+}
+
+/// When usage of a window counts as a spike
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnomalyThresholds {
+    /// Observed usage must exceed the baseline by this factor
+    pub factor: f64,
+    /// Request spikes below this many requests in the window are ignored
+    pub min_requests: u64,
+    /// Cost spikes below this many micro-dollars in the window are ignored
+    pub min_cost_micros: i64,
+}
+
+impl Default for AnomalyThresholds {
+    fn default() -> Self {
+        Self {
+            factor: 3.0,
+            min_requests: 100,
+            min_cost_micros: 5_000_000,
+        }
+    }
+}
+
+/// Usage of an API key or team deviating from its baseline
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UsageAnomaly {
+    /// "api_key" or "team"
+    pub scope: String,
+    /// API key or team ID
+    pub group: String,
+    pub metric: AnomalyMetric,
+    /// Usage in the window (requests, or micro-dollars for cost)
+    pub observed: f64,
+    /// Expected usage in a window of the same length
+    pub baseline: f64,
+}
+
+impl UsageAnomaly {
+    /// How many times the baseline was observed, `None` without a baseline
+    pub fn ratio(&self) -> Option<f64> {
+        (self.baseline > 0.0).then(|| self.observed / self.baseline)
+    }
+}
+
+/// Compare the usage of a window against the average of the baseline period.
+///
+/// `baseline` holds the buckets of the preceding `baseline_secs`, `current`
+/// those of the `window_secs` being checked. A group without baseline usage
+/// (e.g. a brand new key) is flagged as soon as it crosses the minimums.
+pub fn detect_usage_anomalies(
+    group_by: UsageGroupBy,
+    baseline: &[UsageBucket],
+    baseline_secs: u64,
+    current: &[UsageBucket],
+    window_secs: u64,
+    thresholds: &AnomalyThresholds,
+) -> Vec<UsageAnomaly> {
+    let windows = if window_secs == 0 {
+        0.0
+    } else {
+        baseline_secs as f64 / window_secs as f64
+    };
+    let baseline = totals_by_group(baseline);
+    let mut groups: Vec<(String, (u64, i64))> = totals_by_group(current).into_iter().collect();
+    groups.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut anomalies = Vec::new();
+
+    for (group, (requests, cost)) in groups {
+        let (baseline_requests, baseline_cost) = baseline.get(&group).copied().unwrap_or((0, 0));
+        let average = |total: f64| if windows > 0.0 { total / windows } else { 0.0 };
+
+        let checks = [
+            (
+                AnomalyMetric::Requests,
+                requests as f64,
+                average(baseline_requests as f64),
+                requests >= thresholds.min_requests,
+            ),
+            (
+                AnomalyMetric::Cost,
+                cost as f64,
+                average(baseline_cost as f64),
+                cost >= thresholds.min_cost_micros,
+            ),
+        ];
+
+        for (metric, observed, expected, above_minimum) in checks {
+            if above_minimum && observed > expected * thresholds.factor {
+                anomalies.push(UsageAnomaly {
+                    scope: group_by.to_string(),
+                    group: group.clone(),
+                    metric,
+                    observed,
+                    baseline: expected,
+                });
+            }
+        }
+    }
+
+    anomalies
+}
+
+/// Requests and cost per group, ignoring buckets without a group
+fn totals_by_group(buckets: &[UsageBucket]) -> HashMap<String, (u64, i64)> {
+    let mut totals: HashMap<String, (u64, i64)> = HashMap::new();
+
+    for bucket in buckets {
+        if let Some(group) = &bucket.group {
+            let entry = totals.entry(group.clone()).or_default();
+            entry.0 += bucket.requests;
+            entry.1 += bucket.cost_micros;
+        }
+    }
+
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bucket(group: &str, requests: u64, cost_micros: i64) -> UsageBucket {
+        UsageBucket {
+            group: Some(group.to_string()),
+            requests,
+            cost_micros,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_detects_spike_over_baseline() {
+        let thresholds = AnomalyThresholds {
+            factor: 3.0,
+            min_requests: 10,
+            min_cost_micros: 1_000_000,
+        };
+        // 24 hours averaging 20 requests and $1 per hour
+        let baseline = vec![
+            bucket("key-1", 480, 24_000_000),
+            bucket("key-2", 480, 24_000_000),
+        ];
+        let current = vec![
+            bucket("key-1", 100, 2_000_000),
+            bucket("key-2", 25, 1_500_000),
+        ];
+
+        let anomalies = detect_usage_anomalies(
+            UsageGroupBy::ApiKey,
+            &baseline,
+            86_400,
+            &current,
+            3_600,
+            &thresholds,
+        );
+
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].group, "key-1");
+        assert_eq!(anomalies[0].metric, AnomalyMetric::Requests);
+        assert_eq!(anomalies[0].scope, "api_key");
+        assert_eq!(anomalies[0].baseline, 20.0);
+        assert_eq!(anomalies[0].ratio(), Some(5.0));
+    }
+
+    #[test]
+    fn test_new_group_flagged_above_minimums_only() {
+        let thresholds = AnomalyThresholds::default();
+        let current = vec![bucket("new-key", 500, 1_000_000), bucket("quiet", 5, 100)];
+
+        let anomalies = detect_usage_anomalies(
+            UsageGroupBy::Team,
+            &[],
+            86_400,
+            &current,
+            3_600,
+            &thresholds,
+        );
+
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].group, "new-key");
+        assert_eq!(anomalies[0].ratio(), None);
+    }
+}
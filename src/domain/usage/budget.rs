@@ -71,7 +71,9 @@ pub enum BudgetScope {
     SpecificApiKeys,
     /// Budget applies to all API keys belonging to specific teams
     Teams,
-    /// Budget applies to specific API keys AND team-level budgets
+    /// Budget is shared by all teams of specific organizations
+    Organizations,
+    /// Budget combines more than one of API key, team and organization filters
     Mixed,
 }
 
@@ -192,8 +194,15 @@ pub struct Budget {
     pub api_key_ids: Vec<String>,
     /// Associated team IDs (used when scope is Teams or Mixed)
     pub team_ids: Vec<String>,
+    /// Associated organization IDs (used when scope is Organizations or Mixed)
+    #[serde(default)]
+    pub organization_ids: Vec<String>,
     /// Associated model IDs (empty = all models)
     pub model_ids: Vec<String>,
+    /// Cheaper model requests are routed to instead of being rejected when
+    /// the budget would be exceeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_model_id: Option<String>,
     /// Alert configurations
     pub alerts: Vec<BudgetAlert>,
     /// When the current period started
@@ -226,7 +235,9 @@ impl Budget {
             scope: BudgetScope::AllApiKeys,
             api_key_ids: Vec::new(),
             team_ids: Vec::new(),
+            organization_ids: Vec::new(),
             model_ids: Vec::new(),
+            fallback_model_id: None,
             alerts: Vec::new(),
             period_start: now,
             created_at: now,
@@ -287,13 +298,32 @@ impl Budget {
         self
     }
 
-    /// Update the scope based on current api_key_ids and team_ids
-    fn update_scope(&mut self) {
-        self.scope = match (!self.api_key_ids.is_empty(), !self.team_ids.is_empty()) {
-            (false, false) => BudgetScope::AllApiKeys,
-            (true, false) => BudgetScope::SpecificApiKeys,
-            (false, true) => BudgetScope::Teams,
-            (true, true) => BudgetScope::Mixed,
+    /// Add an organization filter (updates scope accordingly)
+    pub fn with_organization(mut self, organization_id: impl Into<String>) -> Self {
+        self.organization_ids.push(organization_id.into());
+        self.update_scope();
+        self
+    }
+
+    /// Set multiple organization filters (updates scope accordingly)
+    pub fn with_organizations(mut self, organization_ids: Vec<String>) -> Self {
+        self.organization_ids = organization_ids;
+        self.update_scope();
+        self
+    }
+
+    /// Update the scope based on current api_key_ids, team_ids and organization_ids
+    pub fn update_scope(&mut self) {
+        self.scope = match (
+            !self.api_key_ids.is_empty(),
+            !self.team_ids.is_empty(),
+            !self.organization_ids.is_empty(),
+        ) {
+            (false, false, false) => BudgetScope::AllApiKeys,
+            (true, false, false) => BudgetScope::SpecificApiKeys,
+            (false, true, false) => BudgetScope::Teams,
+            (false, false, true) => BudgetScope::Organizations,
+            _ => BudgetScope::Mixed,
         };
     }
 
@@ -303,6 +333,12 @@ impl Budget {
         self
     }
 
+    /// Set the model requests are degraded to when the budget would be exceeded
+    pub fn with_fallback_model(mut self, model_id: impl Into<String>) -> Self {
+        self.fallback_model_id = Some(model_id.into());
+        self
+    }
+
     /// Add an alert at threshold percentage
     pub fn with_alert_at(mut self, threshold_percent: u8) -> Self {
         self.alerts.push(BudgetAlert::at_percent(threshold_percent));
@@ -356,7 +392,8 @@ impl Budget {
             BudgetScope::SpecificApiKeys | BudgetScope::Mixed => {
                 self.api_key_ids.contains(&api_key_id.to_string())
             }
-            BudgetScope::Teams => false, // Team scope requires team context
+            // Team and organization scopes require team context
+            BudgetScope::Teams | BudgetScope::Organizations => false,
         }
     }
 
@@ -367,24 +404,33 @@ impl Budget {
             BudgetScope::Teams | BudgetScope::Mixed => {
                 self.team_ids.contains(&team_id.to_string())
             }
-            BudgetScope::SpecificApiKeys => false,
+            BudgetScope::SpecificApiKeys | BudgetScope::Organizations => false,
         }
     }
 
     /// Check if budget applies to the given API key with team context
     pub fn applies_to_api_key_with_team(&self, api_key_id: &str, team_id: Option<&str>) -> bool {
+        self.applies_to_api_key_in_organization(api_key_id, team_id, None)
+    }
+
+    /// Check if budget applies to the given API key with team and organization context
+    pub fn applies_to_api_key_in_organization(
+        &self,
+        api_key_id: &str,
+        team_id: Option<&str>,
+        organization_id: Option<&str>,
+    ) -> bool {
+        let key_matches = self.api_key_ids.iter().any(|k| k == api_key_id);
+        let team_matches = team_id.is_some_and(|t| self.team_ids.iter().any(|id| id == t));
+        let organization_matches =
+            organization_id.is_some_and(|o| self.organization_ids.iter().any(|id| id == o));
+
         match self.scope {
             BudgetScope::AllApiKeys => true,
-            BudgetScope::SpecificApiKeys => {
-                self.api_key_ids.contains(&api_key_id.to_string())
-            }
-            BudgetScope::Teams => {
-                team_id.map_or(false, |t| self.team_ids.contains(&t.to_string()))
-            }
-            BudgetScope::Mixed => {
-                self.api_key_ids.contains(&api_key_id.to_string())
-                    || team_id.map_or(false, |t| self.team_ids.contains(&t.to_string()))
-            }
+            BudgetScope::SpecificApiKeys => key_matches,
+            BudgetScope::Teams => team_matches,
+            BudgetScope::Organizations => organization_matches,
+            BudgetScope::Mixed => key_matches || team_matches || organization_matches,
         }
     }
 
@@ -648,6 +694,28 @@ mod tests {
         assert!(all_budget.applies_to_api_key_with_team("any-key", Some("any-team")));
     }
 
+    #[test]
+    fn test_budget_organization_scope() {
+        let budget = Budget::new("budget-1", "Test", BudgetPeriod::Monthly)
+            .with_organization("acme");
+
+        assert_eq!(budget.scope, BudgetScope::Organizations);
+        assert!(budget.applies_to_api_key_in_organization("any-key", Some("team-1"), Some("acme")));
+        assert!(!budget.applies_to_api_key_in_organization("any-key", Some("team-1"), Some("globex")));
+        assert!(!budget.applies_to_api_key_in_organization("any-key", Some("team-1"), None));
+        assert!(!budget.applies_to_api_key("any-key"));
+        assert!(!budget.applies_to_team("team-1"));
+
+        let mixed = Budget::new("budget-2", "Test", BudgetPeriod::Monthly)
+            .with_team("team-1")
+            .with_organization("acme");
+
+        assert_eq!(mixed.scope, BudgetScope::Mixed);
+        assert!(mixed.applies_to_api_key_in_organization("any-key", Some("team-1"), None));
+        assert!(mixed.applies_to_api_key_in_organization("any-key", Some("team-2"), Some("acme")));
+        assert!(!mixed.applies_to_api_key_in_organization("any-key", Some("team-2"), None));
+    }
+
     #[test]
     fn test_budget_with_multiple_teams() {
         let budget = Budget::new("budget-1", "Test", BudgetPeriod::Monthly)
@@ -0,0 +1,347 @@
+//! Chargeback invoices rolling monthly usage into per-team statements
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{Datelike, NaiveDate};
+
+use super::UsageRecord;
+use crate::domain::DomainError;
+
+/// Line key of records without the grouped tag
+pub const UNTAGGED: &str = "(untagged)";
+
+/// Calendar month (UTC) an invoice covers, written `YYYY-MM`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvoiceMonth {
+    first_day: NaiveDate,
+}
+
+impl InvoiceMonth {
+    /// Create from a year and month (1-12)
+    pub fn new(year: i32, month: u32) -> Result<Self, DomainError> {
+        NaiveDate::from_ymd_opt(year, month, 1)
+            .map(|first_day| Self { first_day })
+            .ok_or_else(|| {
+                DomainError::validation(format!("Invalid invoice month {}-{:02}", year, month))
+            })
+    }
+
+    /// Unix timestamp of the start of the month (inclusive)
+    pub fn start(&self) -> u64 {
+        Self::timestamp(self.first_day)
+    }
+
+    /// Unix timestamp of the start of the next month (exclusive)
+    pub fn end(&self) -> u64 {
+        let next = if self.first_day.month() == 12 {
+            NaiveDate::from_ymd_opt(self.first_day.year() + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd_opt(self.first_day.year(), self.first_day.month() + 1, 1)
+        };
+
+        next.map(Self::timestamp).unwrap_or(u64::MAX)
+    }
+
+    fn timestamp(date: NaiveDate) -> u64 {
+        date.and_hms_opt(0, 0, 0)
+            .map(|t| t.and_utc().timestamp().max(0) as u64)
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for InvoiceMonth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.first_day.format("%Y-%m"))
+    }
+}
+
+impl FromStr for InvoiceMonth {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid =
+            || DomainError::validation(format!("Invalid invoice month '{}', expected YYYY-MM", s));
+
+        let (year, month) = s.split_once('-').ok_or_else(invalid)?;
+
+        if year.len() != 4 || month.len() != 2 {
+            return Err(invalid());
+        }
+
+        let year: i32 = year.parse().map_err(|_| invalid())?;
+        let month: u32 = month.parse().map_err(|_| invalid())?;
+
+        Self::new(year, month).map_err(|_| invalid())
+    }
+}
+
+/// Markup percentages added on top of the provider cost
+#[derive(Debug, Clone, Default)]
+pub struct InvoiceMarkup {
+    /// Markup applied when no team or model override exists
+    pub default_percent: f64,
+    /// Markup per team ID
+    pub team_percent: HashMap<String, f64>,
+    /// Markup per model ID, takes precedence over the team markup
+    pub model_percent: HashMap<String, f64>,
+}
+
+impl InvoiceMarkup {
+    /// Markup percentage of a team, before model overrides
+    pub fn team_percent(&self, team_id: &str) -> f64 {
+        self.team_percent
+            .get(team_id)
+            .copied()
+            .unwrap_or(self.default_percent)
+    }
+
+    /// Markup percentage applied to usage of a model by a team
+    pub fn percent_for(&self, team_id: &str, model_id: Option<&str>) -> f64 {
+        model_id
+            .and_then(|model| self.model_percent.get(model))
+            .copied()
+            .unwrap_or_else(|| self.team_percent(team_id))
+    }
+
+    /// Markup in micro-dollars for a cost
+    pub fn markup_micros(&self, team_id: &str, model_id: Option<&str>, cost_micros: i64) -> i64 {
+        (cost_micros as f64 * self.percent_for(team_id, model_id) / 100.0).round() as i64
+    }
+}
+
+/// Usage and charges rolled up under one key (a model, an API key or a tag value)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InvoiceLine {
+    pub key: String,
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Provider cost in micro-dollars
+    pub cost_micros: i64,
+    /// Markup in micro-dollars
+    pub markup_micros: i64,
+}
+
+impl InvoiceLine {
+    fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            ..Self::default()
+        }
+    }
+
+    fn add(&mut self, record: &UsageRecord, markup_micros: i64) {
+        self.requests += 1;
+        self.input_tokens += record.input_tokens as u64;
+        self.output_tokens += record.output_tokens as u64;
+        self.cost_micros += record.cost_micros;
+        self.markup_micros += markup_micros;
+    }
+
+    /// Amount charged in micro-dollars (cost plus markup)
+    pub fn total_micros(&self) -> i64 {
+        self.cost_micros + self.markup_micros
+    }
+}
+
+/// Monthly chargeback statement of a team
+#[derive(Debug, Clone)]
+pub struct TeamInvoice {
+    pub team_id: String,
+    pub month: InvoiceMonth,
+    /// Markup percentage of the team, before model overrides
+    pub markup_percent: f64,
+    /// Totals of the whole month
+    pub total: InvoiceLine,
+    pub by_model: Vec<InvoiceLine>,
+    pub by_api_key: Vec<InvoiceLine>,
+    /// Lines per value of every tag key seen in the month
+    pub by_tag: BTreeMap<String, Vec<InvoiceLine>>,
+}
+
+impl TeamInvoice {
+    /// Roll up the usage records of a team for a month
+    pub fn build(
+        team_id: impl Into<String>,
+        month: InvoiceMonth,
+        records: &[UsageRecord],
+        markup: &InvoiceMarkup,
+    ) -> Self {
+        let team_id = team_id.into();
+        let (start, end) = (month.start(), month.end());
+        let records: Vec<&UsageRecord> = records
+            .iter()
+            .filter(|r| r.timestamp >= start && r.timestamp < end)
+            .collect();
+
+        let tag_keys: Vec<&String> = records
+            .iter()
+            .flat_map(|r| r.tags.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        let mut total = InvoiceLine::new(month.to_string());
+        let mut by_model: HashMap<String, InvoiceLine> = HashMap::new();
+        let mut by_api_key: HashMap<String, InvoiceLine> = HashMap::new();
+        let mut by_tag: BTreeMap<String, HashMap<String, InvoiceLine>> = BTreeMap::new();
+
+        for record in records {
+            let markup_micros =
+                markup.markup_micros(&team_id, record.model_id.as_deref(), record.cost_micros);
+            let model = record
+                .model_id
+                .clone()
+                .unwrap_or_else(|| record.usage_type.to_string());
+
+            total.add(record, markup_micros);
+            by_model
+                .entry(model.clone())
+                .or_insert_with(|| InvoiceLine::new(model))
+                .add(record, markup_micros);
+            by_api_key
+                .entry(record.api_key_id.clone())
+                .or_insert_with(|| InvoiceLine::new(&record.api_key_id))
+                .add(record, markup_micros);
+
+            for key in &tag_keys {
+                let value = record
+                    .tags
+                    .get(*key)
+                    .map(String::as_str)
+                    .unwrap_or(UNTAGGED);
+
+                by_tag
+                    .entry((*key).clone())
+                    .or_default()
+                    .entry(value.to_string())
+                    .or_insert_with(|| InvoiceLine::new(value))
+                    .add(record, markup_micros);
+            }
+        }
+
+        Self {
+            markup_percent: markup.team_percent(&team_id),
+            team_id,
+            month,
+            total,
+            by_model: sorted_lines(by_model),
+            by_api_key: sorted_lines(by_api_key),
+            by_tag: by_tag
+                .into_iter()
+                .map(|(key, lines)| (key, sorted_lines(lines)))
+                .collect(),
+        }
+    }
+
+    /// Invoice number, stable for a team and month
+    pub fn number(&self) -> String {
+        format!(
+            "INV-{}-{}",
+            self.team_id.to_uppercase(),
+            self.month.to_string().replace('-', "")
+        )
+    }
+}
+
+/// Lines with the highest charge first
+fn sorted_lines(lines: HashMap<String, InvoiceLine>) -> Vec<InvoiceLine> {
+    let mut lines: Vec<InvoiceLine> = lines.into_values().collect();
+    lines.sort_by(|a, b| {
+        b.total_micros()
+            .cmp(&a.total_micros())
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::usage::UsageType;
+
+    fn record(id: &str, key: &str, model: &str, cost: i64, tags: &[(&str, &str)]) -> UsageRecord {
+        let mut record = UsageRecord::new(id, UsageType::ChatCompletion, key)
+            .with_model_id(model)
+            .with_tokens(100, 50)
+            .with_cost_micros(cost)
+            .with_tags(
+                tags.iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            );
+        record.timestamp = 1_706_000_000; // 2024-01-23
+        record
+    }
+
+    #[test]
+    fn test_invoice_month_parse() {
+        let month: InvoiceMonth = "2024-01".parse().unwrap();
+        assert_eq!(month.to_string(), "2024-01");
+        assert_eq!(month.start(), 1_704_067_200);
+        assert_eq!(month.end(), 1_706_745_600);
+
+        let december: InvoiceMonth = "2023-12".parse().unwrap();
+        assert_eq!(december.end(), 1_704_067_200);
+
+        assert!("2024-13".parse::<InvoiceMonth>().is_err());
+        assert!("2024-1".parse::<InvoiceMonth>().is_err());
+        assert!("january".parse::<InvoiceMonth>().is_err());
+    }
+
+    #[test]
+    fn test_invoice_markup_precedence() {
+        let markup = InvoiceMarkup {
+            default_percent: 10.0,
+            team_percent: HashMap::from([("research".to_string(), 0.0)]),
+            model_percent: HashMap::from([("gpt-4".to_string(), 25.0)]),
+        };
+
+        assert_eq!(markup.percent_for("growth", Some("gpt-3.5")), 10.0);
+        assert_eq!(markup.percent_for("research", Some("gpt-3.5")), 0.0);
+        assert_eq!(markup.percent_for("research", Some("gpt-4")), 25.0);
+        assert_eq!(markup.markup_micros("growth", None, 1_000_000), 100_000);
+    }
+
+    #[test]
+    fn test_team_invoice_build() {
+        let month: InvoiceMonth = "2024-01".parse().unwrap();
+        let markup = InvoiceMarkup {
+            default_percent: 10.0,
+            ..Default::default()
+        };
+        let mut outside = record("r4", "key-1", "gpt-4", 5_000_000, &[]);
+        outside.timestamp = month.end();
+
+        let records = vec![
+            record("r1", "key-1", "gpt-4", 2_000_000, &[("customer", "acme")]),
+            record("r2", "key-2", "gpt-4", 1_000_000, &[("customer", "globex")]),
+            record("r3", "key-2", "claude-3", 500_000, &[]),
+            outside,
+        ];
+
+        let invoice = TeamInvoice::build("growth", month, &records, &markup);
+
+        assert_eq!(invoice.total.requests, 3);
+        assert_eq!(invoice.total.cost_micros, 3_500_000);
+        assert_eq!(invoice.total.markup_micros, 350_000);
+        assert_eq!(invoice.total.total_micros(), 3_850_000);
+
+        assert_eq!(invoice.by_model[0].key, "gpt-4");
+        assert_eq!(invoice.by_model[0].cost_micros, 3_000_000);
+        assert_eq!(invoice.by_api_key.len(), 2);
+
+        let customers = &invoice.by_tag["customer"];
+        assert_eq!(customers.len(), 3);
+        assert_eq!(customers[0].key, "acme");
+        assert_eq!(customers[2].key, UNTAGGED);
+        assert_eq!(
+            customers.iter().map(|l| l.total_micros()).sum::<i64>(),
+            invoice.total.total_micros()
+        );
+
+        assert_eq!(invoice.number(), "INV-GROWTH-202401");
+    }
+}
@@ -3,15 +3,34 @@
 //! Provides entities and traits for tracking LLM usage, calculating costs,
 //! and enforcing budgets.
 
+mod anomaly;
 mod budget;
+mod invoice;
 mod pricing;
+mod reconciliation;
 mod record;
 mod repository;
+mod tags;
+mod timeseries;
 
+pub use anomaly::{detect_usage_anomalies, AnomalyMetric, AnomalyThresholds, UsageAnomaly};
 pub use budget::{Budget, BudgetAlert, BudgetId, BudgetPeriod, BudgetScope, BudgetStatus};
-pub use pricing::{default_model_pricing, ModelPricing, PricingTier};
-pub use record::{DailyUsage, UsageAggregate, UsageRecord, UsageRecordId, UsageSummary, UsageType};
-pub use repository::{BudgetRepository, UsageQuery, UsageRepository};
+pub use invoice::{InvoiceLine, InvoiceMarkup, InvoiceMonth, TeamInvoice, UNTAGGED};
+pub use pricing::{default_model_pricing, ModelPricing, PricingId, PricingSource, PricingTier};
+pub use reconciliation::{
+    reconcile_usage, ProviderUsage, ProviderUsageSource, ReconciliationLine, ReconciliationReport,
+    ReconciliationStatus,
+};
+pub use record::{
+    DailyUsage, TagUsage, UsageAggregate, UsageRecord, UsageRecordId, UsageSummary, UsageType,
+};
+pub use repository::{BudgetRepository, PricingRepository, UsageQuery, UsageRepository};
+pub use timeseries::{
+    bucket_usage_records, usage_series, TimeBucket, UsageBucket, UsageGroupBy, UsageSeries,
+};
+pub use tags::{
+    parse_usage_tags, validate_usage_tags, MAX_TAG_KEY_LENGTH, MAX_TAG_VALUE_LENGTH, MAX_USAGE_TAGS,
+};
 
 /// Validate a budget ID
 pub fn validate_budget_id(id: &str) -> Result<(), BudgetValidationError> {
@@ -4,8 +4,75 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::domain::storage::{StorageEntity, StorageKey};
+
+/// Identifier of a pricing version: `<model_id>@<effective_from>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PricingId(String);
+
+impl PricingId {
+    /// Create a pricing ID from its string form
+    pub fn new(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// ID of the version of a model's pricing starting at `effective_from`
+    pub fn for_version(model_id: &str, effective_from: Option<u64>) -> Self {
+        Self(format!("{}@{}", model_id, effective_from.unwrap_or(0)))
+    }
+
+    /// Get the inner string value
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for PricingId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StorageKey for PricingId {
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl StorageEntity for ModelPricing {
+    type Key = PricingId;
+
+    fn key(&self) -> &Self::Key {
+        &self.id
+    }
+}
+
+/// Origin of a pricing version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PricingSource {
+    /// Built-in default prices
+    #[default]
+    Default,
+    /// Set through the admin API; never overwritten by synchronisation
+    Manual,
+    /// Pulled from a pricing feed
+    Sync,
+}
+
+impl std::fmt::Display for PricingSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::Manual => write!(f, "manual"),
+            Self::Sync => write!(f, "sync"),
+        }
+    }
+}
+
 /// Pricing tier for volume discounts
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PricingTier {
     /// Minimum tokens for this tier
     pub min_tokens: u64,
@@ -36,9 +103,13 @@ impl PricingTier {
     }
 }
 
-/// Pricing configuration for a model
+/// Pricing configuration for a model, valid between its effective dates.
+/// Price changes are recorded as new versions so that costs of past usage
+/// can still be attributed to the prices in effect at the time.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelPricing {
+    /// Version ID, derived from the model ID and the effective date
+    pub id: PricingId,
     /// Model ID this pricing applies to
     pub model_id: String,
     /// Provider name
@@ -60,6 +131,9 @@ pub struct ModelPricing {
     pub effective_from: Option<u64>,
     /// Expiry date (unix timestamp)
     pub effective_until: Option<u64>,
+    /// Where this version came from
+    #[serde(default)]
+    pub source: PricingSource,
 }
 
 fn default_currency() -> String {
@@ -78,8 +152,11 @@ impl ModelPricing {
         input_per_1k: f64,
         output_per_1k: f64,
     ) -> Self {
+        let model_id = model_id.into();
+
         Self {
-            model_id: model_id.into(),
+            id: PricingId::for_version(&model_id, None),
+            model_id,
             provider: provider.into(),
             input_price_per_1k_micros: (input_per_1k * 1_000_000.0) as i64,
             output_price_per_1k_micros: (output_per_1k * 1_000_000.0) as i64,
@@ -88,6 +165,7 @@ impl ModelPricing {
             active: true,
             effective_from: None,
             effective_until: None,
+            source: PricingSource::Default,
         }
     }
 
@@ -101,11 +179,30 @@ impl ModelPricing {
 
     /// Set effective date range
     pub fn with_effective_dates(mut self, from: u64, until: u64) -> Self {
-        self.effective_from = Some(from);
         self.effective_until = Some(until);
+        self.with_effective_from(from)
+    }
+
+    /// Set the date this version takes effect, without an end date
+    pub fn with_effective_from(mut self, from: u64) -> Self {
+        self.effective_from = Some(from);
+        self.id = PricingId::for_version(&self.model_id, Some(from));
         self
     }
 
+    /// Set the source of this version
+    pub fn with_source(mut self, source: PricingSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Check if two versions charge the same prices
+    pub fn same_prices(&self, other: &ModelPricing) -> bool {
+        self.input_price_per_1k_micros == other.input_price_per_1k_micros
+            && self.output_price_per_1k_micros == other.output_price_per_1k_micros
+            && self.tiers == other.tiers
+    }
+
     /// Get input price per 1K tokens in USD
     pub fn input_price_per_1k(&self) -> f64 {
         self.input_price_per_1k_micros as f64 / 1_000_000.0
@@ -282,6 +379,20 @@ mod tests {
         assert!(current.is_effective(now));
     }
 
+    #[test]
+    fn test_pricing_version_id() {
+        let pricing = ModelPricing::new("gpt-4o", "openai", 0.005, 0.015);
+        assert_eq!(pricing.id.as_str(), "gpt-4o@0");
+        assert_eq!(pricing.source, PricingSource::Default);
+
+        let versioned = pricing.clone().with_effective_from(1_700_000_000);
+        assert_eq!(versioned.id.as_str(), "gpt-4o@1700000000");
+        assert!(versioned.same_prices(&pricing));
+
+        let cheaper = ModelPricing::new("gpt-4o", "openai", 0.0025, 0.01);
+        assert!(!cheaper.same_prices(&pricing));
+    }
+
     #[test]
     fn test_default_pricing() {
         let pricing = default_model_pricing();
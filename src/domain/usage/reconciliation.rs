@@ -0,0 +1,238 @@
+//! Reconciliation of gateway-recorded usage with provider-reported usage
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::domain::DomainError;
+
+/// Usage of a model on a day as reported by the provider
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ProviderUsage {
+    /// Provider model name (e.g. "gpt-4o-2024-08-06")
+    pub model: String,
+    /// Request count, `None` when the provider does not report it
+    pub requests: Option<u64>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+/// Source of the usage a provider bills for
+#[async_trait]
+pub trait ProviderUsageSource: Send + Sync + Debug {
+    /// Provider the usage belongs to, as in `CredentialType` (e.g. "openai")
+    fn provider(&self) -> &str;
+
+    /// Usage per model of a UTC day
+    async fn daily_usage(&self, date: NaiveDate) -> Result<Vec<ProviderUsage>, DomainError>;
+}
+
+/// Outcome of comparing the usage of a model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconciliationStatus {
+    /// Both sides agree within the tolerance
+    Matched,
+    /// The provider reports more usage than the gateway recorded
+    Missing,
+    /// The gateway recorded more usage than the provider reports
+    Overcounted,
+}
+
+/// Gateway and provider usage of one provider model on a day
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ReconciliationLine {
+    pub model: String,
+    pub gateway_requests: u64,
+    pub gateway_tokens: u64,
+    pub provider_requests: Option<u64>,
+    pub provider_tokens: u64,
+    /// Gateway tokens minus provider tokens
+    pub token_diff: i64,
+    /// Token difference relative to the provider tokens, `None` when the
+    /// provider reports no tokens
+    pub diff_percent: Option<f64>,
+    pub status: ReconciliationStatus,
+}
+
+/// Daily comparison of the usage of one provider
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ReconciliationReport {
+    pub provider: String,
+    pub date: NaiveDate,
+    /// Token difference (percent) still counted as matched
+    pub tolerance_percent: f64,
+    pub lines: Vec<ReconciliationLine>,
+}
+
+impl ReconciliationReport {
+    /// Lines whose usage does not match
+    pub fn discrepancies(&self) -> Vec<&ReconciliationLine> {
+        self.lines
+            .iter()
+            .filter(|line| line.status != ReconciliationStatus::Matched)
+            .collect()
+    }
+}
+
+/// Compare the gateway usage of a day with the provider's usage.
+///
+/// `gateway` maps provider model names to the (requests, tokens) recorded by
+/// the gateway. Providers often report dated snapshots ("gpt-4o-2024-08-06")
+/// of the names models are configured with ("gpt-4o"), so a reported model
+/// without an exact match is attributed to the longest gateway name it
+/// extends with a `-` suffix.
+pub fn reconcile_usage(
+    provider: &str,
+    date: NaiveDate,
+    gateway: &HashMap<String, (u64, u64)>,
+    reported: &[ProviderUsage],
+    tolerance_percent: f64,
+) -> ReconciliationReport {
+    let mut provider_totals: BTreeMap<String, (Option<u64>, u64)> = BTreeMap::new();
+
+    for usage in reported {
+        let model = match_gateway_model(&usage.model, gateway).unwrap_or(&usage.model);
+        let entry = provider_totals.entry(model.clone()).or_default();
+
+        entry.0 = match (entry.0, usage.requests) {
+            (Some(total), Some(requests)) => Some(total + requests),
+            (None, requests) => requests,
+            (total, None) => total,
+        };
+        entry.1 += usage.input_tokens + usage.output_tokens;
+    }
+
+    for model in gateway.keys() {
+        provider_totals.entry(model.clone()).or_default();
+    }
+
+    let lines = provider_totals
+        .into_iter()
+        .map(|(model, (provider_requests, provider_tokens))| {
+            let (gateway_requests, gateway_tokens) = gateway.get(&model).copied().unwrap_or((0, 0));
+            let token_diff = gateway_tokens as i64 - provider_tokens as i64;
+            let diff_percent =
+                (provider_tokens > 0).then(|| token_diff as f64 / provider_tokens as f64 * 100.0);
+
+            let within_tolerance = match diff_percent {
+                Some(percent) => percent.abs() <= tolerance_percent,
+                None => gateway_tokens == 0,
+            };
+            let status = if within_tolerance {
+                ReconciliationStatus::Matched
+            } else if token_diff < 0 {
+                ReconciliationStatus::Missing
+            } else {
+                ReconciliationStatus::Overcounted
+            };
+
+            ReconciliationLine {
+                model,
+                gateway_requests,
+                gateway_tokens,
+                provider_requests,
+                provider_tokens,
+                token_diff,
+                diff_percent,
+                status,
+            }
+        })
+        .collect();
+
+    ReconciliationReport {
+        provider: provider.to_string(),
+        date,
+        tolerance_percent,
+        lines,
+    }
+}
+
+fn match_gateway_model<'a>(
+    reported: &str,
+    gateway: &'a HashMap<String, (u64, u64)>,
+) -> Option<&'a String> {
+    if let Some((model, _)) = gateway.get_key_value(reported) {
+        return Some(model);
+    }
+
+    gateway
+        .keys()
+        .filter(|model| {
+            reported
+                .strip_prefix(model.as_str())
+                .is_some_and(|rest| rest.starts_with('-'))
+        })
+        .max_by_key(|model| model.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(model: &str, requests: Option<u64>, input: u64, output: u64) -> ProviderUsage {
+        ProviderUsage {
+            model: model.to_string(),
+            requests,
+            input_tokens: input,
+            output_tokens: output,
+        }
+    }
+
+    #[test]
+    fn test_reconcile_usage_statuses() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let gateway = HashMap::from([
+            ("gpt-4o".to_string(), (10, 1_000)),
+            ("gpt-4o-mini".to_string(), (5, 500)),
+            ("o1".to_string(), (2, 400)),
+        ]);
+        let reported = vec![
+            usage("gpt-4o-2024-08-06", Some(8), 700, 290),
+            usage("gpt-4o-mini-2024-07-18", Some(7), 600, 200),
+            usage("gpt-3.5-turbo", Some(1), 10, 5),
+        ];
+
+        let report = reconcile_usage("openai", date, &gateway, &reported, 2.0);
+        let statuses: Vec<(&str, ReconciliationStatus)> = report
+            .lines
+            .iter()
+            .map(|line| (line.model.as_str(), line.status))
+            .collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                ("gpt-3.5-turbo", ReconciliationStatus::Missing),
+                ("gpt-4o", ReconciliationStatus::Matched),
+                ("gpt-4o-mini", ReconciliationStatus::Missing),
+                ("o1", ReconciliationStatus::Overcounted),
+            ]
+        );
+        assert_eq!(report.lines[1].provider_requests, Some(8));
+        assert_eq!(report.lines[2].token_diff, -300);
+        assert_eq!(report.lines[3].diff_percent, None);
+        assert_eq!(report.discrepancies().len(), 3);
+    }
+
+    #[test]
+    fn test_reconcile_usage_without_request_counts() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let gateway = HashMap::from([("claude-sonnet-4".to_string(), (3, 2_000))]);
+        let reported = vec![
+            usage("claude-sonnet-4-20250514", None, 1_500, 500),
+            usage("claude-sonnet-4-20250514", None, 0, 0),
+        ];
+
+        let report = reconcile_usage("anthropic", date, &gateway, &reported, 0.0);
+
+        assert_eq!(report.lines.len(), 1);
+        assert_eq!(report.lines[0].provider_requests, None);
+        assert_eq!(report.lines[0].diff_percent, Some(0.0));
+        assert!(report.discrepancies().is_empty());
+    }
+}
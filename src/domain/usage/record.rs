@@ -4,11 +4,12 @@ use std::collections::HashMap;
 use std::time::SystemTime;
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::domain::storage::{StorageEntity, StorageKey};
 
 /// Unique identifier for a usage record
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 pub struct UsageRecordId(String);
 
 impl UsageRecordId {
@@ -61,7 +62,7 @@ impl StorageEntity for UsageRecord {
 }
 
 /// Type of usage being recorded
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum UsageType {
     /// Chat completion request
@@ -86,7 +87,7 @@ impl std::fmt::Display for UsageType {
 }
 
 /// A single usage record
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UsageRecord {
     /// Unique ID
     id: UsageRecordId,
@@ -94,6 +95,9 @@ pub struct UsageRecord {
     pub usage_type: UsageType,
     /// API key ID that made the request
     pub api_key_id: String,
+    /// Team owning the API key when the request was made
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<String>,
     /// Model ID used (if applicable)
     pub model_id: Option<String>,
     /// Number of input/prompt tokens
@@ -114,6 +118,9 @@ pub struct UsageRecord {
     pub timestamp: u64,
     /// Additional metadata
     pub metadata: HashMap<String, String>,
+    /// Cost attribution tags supplied by the caller
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tags: HashMap<String, String>,
 }
 
 impl UsageRecord {
@@ -132,6 +139,7 @@ impl UsageRecord {
             id: id.into(),
             usage_type,
             api_key_id: api_key_id.into(),
+            team_id: None,
             model_id: None,
             input_tokens: 0,
             output_tokens: 0,
@@ -142,9 +150,16 @@ impl UsageRecord {
             error: None,
             timestamp: now,
             metadata: HashMap::new(),
+            tags: HashMap::new(),
         }
     }
 
+    /// Set the team owning the API key
+    pub fn with_team_id(mut self, team_id: impl Into<String>) -> Self {
+        self.team_id = Some(team_id.into());
+        self
+    }
+
     /// Set the model ID
     pub fn with_model_id(mut self, model_id: impl Into<String>) -> Self {
         self.model_id = Some(model_id.into());
@@ -190,6 +205,17 @@ impl UsageRecord {
         self
     }
 
+    /// Set cost attribution tags
+    pub fn with_tags(mut self, tags: HashMap<String, String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Check whether the record carries all of the given tags
+    pub fn has_tags(&self, tags: &HashMap<String, String>) -> bool {
+        tags.iter().all(|(key, value)| self.tags.get(key) == Some(value))
+    }
+
     /// Get cost in USD
     pub fn cost_usd(&self) -> f64 {
         self.cost_micros as f64 / 1_000_000.0
@@ -224,6 +250,20 @@ pub struct UsageAggregate {
     pub by_type: HashMap<UsageType, u64>,
     /// Usage breakdown by model
     pub by_model: HashMap<String, u64>,
+    /// Usage breakdown by tag key, then tag value
+    #[serde(default)]
+    pub by_tag: HashMap<String, HashMap<String, TagUsage>>,
+}
+
+/// Usage attributed to a single tag value
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TagUsage {
+    /// Request count
+    pub requests: u64,
+    /// Total tokens
+    pub tokens: u64,
+    /// Cost in micro-dollars
+    pub cost_micros: i64,
 }
 
 impl UsageAggregate {
@@ -257,6 +297,19 @@ impl UsageAggregate {
         if let Some(ref model_id) = record.model_id {
             *self.by_model.entry(model_id.clone()).or_insert(0) += 1;
         }
+
+        for (key, value) in &record.tags {
+            let usage = self
+                .by_tag
+                .entry(key.clone())
+                .or_default()
+                .entry(value.clone())
+                .or_default();
+
+            usage.requests += 1;
+            usage.tokens += record.total_tokens as u64;
+            usage.cost_micros += record.cost_micros;
+        }
     }
 
     /// Get total cost in USD
@@ -386,6 +439,48 @@ mod tests {
         assert!((aggregate.success_rate() - 0.5).abs() < 0.01);
     }
 
+    #[test]
+    fn test_usage_aggregate_by_tag() {
+        let mut aggregate = UsageAggregate::new();
+        let tags = |feature: &str| {
+            HashMap::from([
+                ("feature".to_string(), feature.to_string()),
+                ("customer".to_string(), "acme".to_string()),
+            ])
+        };
+
+        let onboarding = UsageRecord::new("rec-1", UsageType::ChatCompletion, "api-key-1")
+            .with_tokens(100, 50)
+            .with_cost_micros(1_000)
+            .with_tags(tags("onboarding"));
+        let search = UsageRecord::new("rec-2", UsageType::ChatCompletion, "api-key-1")
+            .with_tokens(10, 5)
+            .with_cost_micros(100)
+            .with_tags(tags("search"));
+        let untagged = UsageRecord::new("rec-3", UsageType::ChatCompletion, "api-key-1");
+
+        aggregate.add_record(&onboarding);
+        aggregate.add_record(&search);
+        aggregate.add_record(&untagged);
+
+        assert_eq!(
+            aggregate.by_tag["feature"]["onboarding"],
+            TagUsage {
+                requests: 1,
+                tokens: 150,
+                cost_micros: 1_000,
+            }
+        );
+        assert_eq!(aggregate.by_tag["customer"]["acme"].requests, 2);
+        assert_eq!(aggregate.by_tag["customer"]["acme"].cost_micros, 1_100);
+
+        assert!(onboarding.has_tags(&HashMap::from([(
+            "feature".to_string(),
+            "onboarding".to_string()
+        )])));
+        assert!(!untagged.has_tags(&tags("search")));
+    }
+
     #[test]
     fn test_usage_type_display() {
         assert_eq!(UsageType::ChatCompletion.to_string(), "chat_completion");
@@ -1,9 +1,13 @@
 //! Usage and budget repository traits
 
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::fmt::Debug;
 
-use super::{Budget, BudgetId, UsageAggregate, UsageRecord, UsageRecordId, UsageSummary};
+use super::{
+    Budget, BudgetId, ModelPricing, PricingId, TimeBucket, UsageAggregate, UsageBucket,
+    UsageGroupBy, UsageRecord, UsageRecordId, UsageSummary,
+};
 use crate::domain::DomainError;
 
 /// Query parameters for usage records
@@ -11,6 +15,8 @@ use crate::domain::DomainError;
 pub struct UsageQuery {
     /// Filter by API key ID
     pub api_key_id: Option<String>,
+    /// Filter by team ID
+    pub team_id: Option<String>,
     /// Filter by model ID
     pub model_id: Option<String>,
     /// Start timestamp (inclusive)
@@ -21,6 +27,8 @@ pub struct UsageQuery {
     pub limit: Option<usize>,
     /// Offset for pagination
     pub offset: Option<usize>,
+    /// Only include records carrying all of these tags
+    pub tags: HashMap<String, String>,
 }
 
 impl UsageQuery {
@@ -35,6 +43,12 @@ impl UsageQuery {
         self
     }
 
+    /// Filter by team
+    pub fn with_team(mut self, team_id: impl Into<String>) -> Self {
+        self.team_id = Some(team_id.into());
+        self
+    }
+
     /// Filter by model
     pub fn with_model(mut self, model_id: impl Into<String>) -> Self {
         self.model_id = Some(model_id.into());
@@ -48,6 +62,12 @@ impl UsageQuery {
         self
     }
 
+    /// Filter by tag
+    pub fn with_tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.insert(key.into(), value.into());
+        self
+    }
+
     /// Set limit
     pub fn with_limit(mut self, limit: usize) -> Self {
         self.limit = Some(limit);
@@ -82,11 +102,22 @@ pub trait UsageRepository: Send + Sync + Debug {
     /// Get usage summary with daily breakdown
     async fn summary(&self, query: &UsageQuery) -> Result<UsageSummary, DomainError>;
 
+    /// Get usage per time bucket (and group), ignoring limit and offset
+    async fn timeseries(
+        &self,
+        query: &UsageQuery,
+        bucket: TimeBucket,
+        group_by: Option<UsageGroupBy>,
+    ) -> Result<Vec<UsageBucket>, DomainError>;
+
     /// Delete records older than timestamp
     async fn delete_before(&self, timestamp: u64) -> Result<usize, DomainError>;
 
     /// Delete all records for an API key
     async fn delete_by_api_key(&self, api_key_id: &str) -> Result<usize, DomainError>;
+
+    /// Delete all records carrying a tag
+    async fn delete_by_tag(&self, key: &str, value: &str) -> Result<usize, DomainError>;
 }
 
 /// Repository for budgets
@@ -114,11 +145,12 @@ pub trait BudgetRepository: Send + Sync + Debug {
         model_id: Option<&str>,
     ) -> Result<Vec<Budget>, DomainError>;
 
-    /// Find budgets applicable to an API key with team context
+    /// Find budgets applicable to an API key with team and organization context
     async fn find_applicable_with_team(
         &self,
         api_key_id: &str,
         team_id: Option<&str>,
+        organization_id: Option<&str>,
         model_id: Option<&str>,
     ) -> Result<Vec<Budget>, DomainError>;
 
@@ -129,6 +161,22 @@ pub trait BudgetRepository: Send + Sync + Debug {
     async fn get_expired_periods(&self, now: u64) -> Result<Vec<Budget>, DomainError>;
 }
 
+/// Repository for model pricing versions
+#[async_trait]
+pub trait PricingRepository: Send + Sync + Debug {
+    /// Get a pricing version by ID
+    async fn get(&self, id: &PricingId) -> Result<Option<ModelPricing>, DomainError>;
+
+    /// List all pricing versions
+    async fn list(&self) -> Result<Vec<ModelPricing>, DomainError>;
+
+    /// Create or replace a pricing version
+    async fn save(&self, pricing: ModelPricing) -> Result<ModelPricing, DomainError>;
+
+    /// Delete a pricing version
+    async fn delete(&self, id: &PricingId) -> Result<bool, DomainError>;
+}
+
 #[cfg(test)]
 pub mod mock {
     use super::*;
@@ -228,6 +276,16 @@ pub mod mock {
             })
         }
 
+        async fn timeseries(
+            &self,
+            query: &UsageQuery,
+            bucket: TimeBucket,
+            group_by: Option<UsageGroupBy>,
+        ) -> Result<Vec<UsageBucket>, DomainError> {
+            let records = self.query(query).await?;
+            Ok(crate::domain::usage::bucket_usage_records(&records, bucket, group_by))
+        }
+
         async fn delete_before(&self, timestamp: u64) -> Result<usize, DomainError> {
             let mut records = self.records.write().unwrap();
             let before_count = records.len();
@@ -241,6 +299,13 @@ pub mod mock {
             records.retain(|_, r| r.api_key_id != api_key_id);
             Ok(before_count - records.len())
         }
+
+        async fn delete_by_tag(&self, key: &str, value: &str) -> Result<usize, DomainError> {
+            let mut records = self.records.write().unwrap();
+            let before_count = records.len();
+            records.retain(|_, r| r.tags.get(key).map(String::as_str) != Some(value));
+            Ok(before_count - records.len())
+        }
     }
 
     #[derive(Debug, Default)]
@@ -317,6 +382,7 @@ pub mod mock {
             &self,
             api_key_id: &str,
             team_id: Option<&str>,
+            organization_id: Option<&str>,
             model_id: Option<&str>,
         ) -> Result<Vec<Budget>, DomainError> {
             let budgets = self.budgets.read().unwrap();
@@ -324,7 +390,7 @@ pub mod mock {
                 .values()
                 .filter(|b| {
                     b.enabled
-                        && b.applies_to_api_key_with_team(api_key_id, team_id)
+                        && b.applies_to_api_key_in_organization(api_key_id, team_id, organization_id)
                         && model_id.map_or(true, |m| b.applies_to_model(m))
                 })
                 .cloned()
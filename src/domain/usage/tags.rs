@@ -0,0 +1,102 @@
+//! Cost attribution tags attached to usage records
+
+use std::collections::HashMap;
+
+use crate::domain::DomainError;
+
+/// Maximum number of tags on a single request
+pub const MAX_USAGE_TAGS: usize = 16;
+/// Maximum length of a tag key
+pub const MAX_TAG_KEY_LENGTH: usize = 64;
+/// Maximum length of a tag value
+pub const MAX_TAG_VALUE_LENGTH: usize = 128;
+
+/// Parse tags in `key=value,key=value` form (e.g. `feature=onboarding,customer=acme`)
+pub fn parse_usage_tags(value: &str) -> Result<HashMap<String, String>, DomainError> {
+    let mut tags = HashMap::new();
+
+    for pair in value.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (key, value) = pair.split_once('=').ok_or_else(|| {
+            DomainError::validation(format!("Invalid tag '{}', expected key=value", pair))
+        })?;
+
+        tags.insert(key.trim().to_string(), value.trim().to_string());
+    }
+
+    validate_usage_tags(&tags)?;
+    Ok(tags)
+}
+
+/// Validate the number and format of tags
+pub fn validate_usage_tags(tags: &HashMap<String, String>) -> Result<(), DomainError> {
+    if tags.len() > MAX_USAGE_TAGS {
+        return Err(DomainError::validation(format!(
+            "Too many tags: {} (max {})",
+            tags.len(),
+            MAX_USAGE_TAGS
+        )));
+    }
+
+    for (key, value) in tags {
+        if key.is_empty() || key.len() > MAX_TAG_KEY_LENGTH {
+            return Err(DomainError::validation(format!(
+                "Tag key '{}' must be 1-{} characters",
+                key, MAX_TAG_KEY_LENGTH
+            )));
+        }
+
+        if !key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(DomainError::validation(format!(
+                "Tag key '{}' must be alphanumeric with hyphens, underscores or dots",
+                key
+            )));
+        }
+
+        if value.len() > MAX_TAG_VALUE_LENGTH {
+            return Err(DomainError::validation(format!(
+                "Value of tag '{}' is longer than {} characters",
+                key, MAX_TAG_VALUE_LENGTH
+            )));
+        }
+
+        if value.contains(|c: char| c.is_control() || c == ',') {
+            return Err(DomainError::validation(format!(
+                "Value of tag '{}' cannot contain commas or control characters",
+                key
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_usage_tags() {
+        let tags = parse_usage_tags(" feature=onboarding, customer = acme ,").unwrap();
+
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags["feature"], "onboarding");
+        assert_eq!(tags["customer"], "acme");
+        assert!(parse_usage_tags("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_usage_tags_invalid() {
+        assert!(parse_usage_tags("feature").is_err());
+        assert!(parse_usage_tags("=acme").is_err());
+        assert!(parse_usage_tags("bad key=acme").is_err());
+
+        let too_many = (0..=MAX_USAGE_TAGS)
+            .map(|i| format!("k{}=v", i))
+            .collect::<Vec<_>>()
+            .join(",");
+        assert!(parse_usage_tags(&too_many).is_err());
+    }
+}
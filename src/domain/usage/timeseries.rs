@@ -0,0 +1,253 @@
+//! Time-bucketed usage series for cost dashboards
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use super::UsageRecord;
+use crate::domain::DomainError;
+
+/// Width of a time bucket (aligned to UTC)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeBucket {
+    Hour,
+    Day,
+}
+
+impl TimeBucket {
+    /// Bucket width in seconds
+    pub fn seconds(&self) -> u64 {
+        match self {
+            Self::Hour => 3_600,
+            Self::Day => 86_400,
+        }
+    }
+
+    /// Start of the bucket containing a timestamp
+    pub fn align(&self, timestamp: u64) -> u64 {
+        timestamp - timestamp % self.seconds()
+    }
+}
+
+impl fmt::Display for TimeBucket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hour => write!(f, "hour"),
+            Self::Day => write!(f, "day"),
+        }
+    }
+}
+
+impl FromStr for TimeBucket {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hour" => Ok(Self::Hour),
+            "day" => Ok(Self::Day),
+            other => Err(DomainError::validation(format!(
+                "Unknown bucket '{}', expected 'hour' or 'day'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Dimension usage series are split by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageGroupBy {
+    Team,
+    Model,
+    ApiKey,
+}
+
+impl UsageGroupBy {
+    /// Group key of a record
+    pub fn key_of<'a>(&self, record: &'a UsageRecord) -> Option<&'a str> {
+        match self {
+            Self::Team => record.team_id.as_deref(),
+            Self::Model => record.model_id.as_deref(),
+            Self::ApiKey => Some(record.api_key_id.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for UsageGroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Team => write!(f, "team"),
+            Self::Model => write!(f, "model"),
+            Self::ApiKey => write!(f, "api_key"),
+        }
+    }
+}
+
+impl FromStr for UsageGroupBy {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "team" => Ok(Self::Team),
+            "model" => Ok(Self::Model),
+            "api_key" => Ok(Self::ApiKey),
+            other => Err(DomainError::validation(format!(
+                "Unknown group_by '{}', expected 'team', 'model' or 'api_key'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Usage of one group within one time bucket
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageBucket {
+    /// Unix timestamp the bucket starts at
+    pub bucket_start: u64,
+    /// Group key, `None` when ungrouped or the record has no value for the group
+    pub group: Option<String>,
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub total_tokens: u64,
+    pub cost_micros: i64,
+}
+
+impl UsageBucket {
+    fn empty(bucket_start: u64, group: Option<String>) -> Self {
+        Self {
+            bucket_start,
+            group,
+            ..Self::default()
+        }
+    }
+}
+
+/// Zero-filled usage series of one group
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageSeries {
+    pub group: Option<String>,
+    pub points: Vec<UsageBucket>,
+}
+
+/// Bucket records in memory, for repositories without query-side aggregation
+pub fn bucket_usage_records<'a>(
+    records: impl IntoIterator<Item = &'a UsageRecord>,
+    bucket: TimeBucket,
+    group_by: Option<UsageGroupBy>,
+) -> Vec<UsageBucket> {
+    let mut buckets: BTreeMap<(u64, Option<String>), UsageBucket> = BTreeMap::new();
+
+    for record in records {
+        let start = bucket.align(record.timestamp);
+        let group = group_by.and_then(|g| g.key_of(record)).map(String::from);
+        let entry = buckets
+            .entry((start, group.clone()))
+            .or_insert_with(|| UsageBucket::empty(start, group));
+
+        entry.requests += 1;
+        entry.input_tokens += record.input_tokens as u64;
+        entry.output_tokens += record.output_tokens as u64;
+        entry.total_tokens += record.total_tokens as u64;
+        entry.cost_micros += record.cost_micros;
+    }
+
+    buckets.into_values().collect()
+}
+
+/// Split buckets into one series per group with a point for every bucket
+/// between `from` (inclusive) and `to` (exclusive)
+pub fn usage_series(
+    buckets: Vec<UsageBucket>,
+    bucket: TimeBucket,
+    from: u64,
+    to: u64,
+) -> Vec<UsageSeries> {
+    let mut groups: BTreeMap<Option<String>, BTreeMap<u64, UsageBucket>> = BTreeMap::new();
+
+    for point in buckets {
+        groups
+            .entry(point.group.clone())
+            .or_default()
+            .insert(point.bucket_start, point);
+    }
+
+    let starts: Vec<u64> = (bucket.align(from)..to)
+        .step_by(bucket.seconds() as usize)
+        .collect();
+
+    groups
+        .into_iter()
+        .map(|(group, mut points)| UsageSeries {
+            points: starts
+                .iter()
+                .map(|start| {
+                    points
+                        .remove(start)
+                        .unwrap_or_else(|| UsageBucket::empty(*start, group.clone()))
+                })
+                .collect(),
+            group,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::usage::UsageType;
+
+    fn record(id: &str, team: &str, model: &str, timestamp: u64, cost: i64) -> UsageRecord {
+        let mut record = UsageRecord::new(id, UsageType::ChatCompletion, "key-1")
+            .with_team_id(team)
+            .with_model_id(model)
+            .with_tokens(10, 5)
+            .with_cost_micros(cost);
+        record.timestamp = timestamp;
+        record
+    }
+
+    #[test]
+    fn test_time_bucket_align() {
+        assert_eq!(TimeBucket::Hour.align(1_704_070_799), 1_704_067_200);
+        assert_eq!(TimeBucket::Day.align(1_704_153_599), 1_704_067_200);
+        assert_eq!("day".parse::<TimeBucket>().unwrap(), TimeBucket::Day);
+        assert!("week".parse::<TimeBucket>().is_err());
+    }
+
+    #[test]
+    fn test_bucket_usage_records_by_team() {
+        let records = vec![
+            record("r1", "growth", "gpt-4", 1_704_067_200, 100),
+            record("r2", "growth", "gpt-4", 1_704_067_800, 200),
+            record("r3", "research", "gpt-4", 1_704_067_900, 50),
+            record("r4", "growth", "gpt-4", 1_704_070_800, 10),
+        ];
+
+        let buckets = bucket_usage_records(&records, TimeBucket::Hour, Some(UsageGroupBy::Team));
+
+        assert_eq!(buckets.len(), 3);
+        assert_eq!(buckets[0].group.as_deref(), Some("growth"));
+        assert_eq!(buckets[0].requests, 2);
+        assert_eq!(buckets[0].cost_micros, 300);
+        assert_eq!(buckets[0].total_tokens, 30);
+        assert_eq!(buckets[2].bucket_start, 1_704_070_800);
+    }
+
+    #[test]
+    fn test_usage_series_zero_fills() {
+        let records = vec![
+            record("r1", "growth", "gpt-4", 1_704_067_200, 100),
+            record("r2", "growth", "gpt-4", 1_704_078_000, 200),
+        ];
+        let buckets = bucket_usage_records(&records, TimeBucket::Hour, None);
+
+        let series = usage_series(buckets, TimeBucket::Hour, 1_704_067_200, 1_704_081_600);
+
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].group, None);
+        assert_eq!(series[0].points.len(), 4);
+        assert_eq!(series[0].points[1].requests, 0);
+        assert_eq!(series[0].points[1].bucket_start, 1_704_070_800);
+        assert_eq!(series[0].points[3].cost_micros, 200);
+    }
+}
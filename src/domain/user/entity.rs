@@ -3,7 +3,9 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use super::mfa::UserMfa;
 use super::validation::{validate_user_id, UserValidationError};
+use crate::domain::role::RoleId;
 use crate::domain::team::{TeamId, TeamRole};
 
 /// User identifier - alphanumeric + hyphens, max 50 characters
@@ -79,6 +81,12 @@ pub struct User {
     team_id: TeamId,
     /// User's role within the team
     team_role: TeamRole,
+    /// Admin API role; defaults to the role mapped from `team_role` when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    role_id: Option<RoleId>,
+    /// TOTP multi-factor authentication state - never exposed in serialization
+    #[serde(default, skip_serializing)]
+    mfa: Option<UserMfa>,
     /// Creation timestamp
     created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -106,6 +114,8 @@ impl User {
             status: UserStatus::Active,
             team_id,
             team_role,
+            role_id: None,
+            mfa: None,
             created_at: now,
             updated_at: now,
             last_login_at: None,
@@ -150,6 +160,26 @@ impl User {
         self.team_role
     }
 
+    pub fn role_id(&self) -> Option<&RoleId> {
+        self.role_id.as_ref()
+    }
+
+    /// Role used for admin API authorization
+    pub fn effective_role_id(&self) -> RoleId {
+        self.role_id
+            .clone()
+            .unwrap_or_else(|| RoleId::for_team_role(self.team_role))
+    }
+
+    pub fn mfa(&self) -> Option<&UserMfa> {
+        self.mfa.as_ref()
+    }
+
+    /// Check if the user must provide a second factor to log in
+    pub fn is_mfa_enabled(&self) -> bool {
+        self.mfa.as_ref().is_some_and(UserMfa::is_enabled)
+    }
+
     // Status checks
 
     /// Check if the user is active and can log in
@@ -209,6 +239,18 @@ impl User {
         self.touch();
     }
 
+    /// Assign an admin API role (None falls back to the team role default)
+    pub fn set_role_id(&mut self, role_id: Option<RoleId>) {
+        self.role_id = role_id;
+        self.touch();
+    }
+
+    /// Replace the MFA state (None disables MFA)
+    pub fn set_mfa(&mut self, mfa: Option<UserMfa>) {
+        self.mfa = mfa;
+        self.touch();
+    }
+
     fn touch(&mut self) {
         self.updated_at = Utc::now();
     }
@@ -335,4 +377,39 @@ mod tests {
         user.set_team_role(TeamRole::Admin);
         assert_eq!(user.team_role(), TeamRole::Admin);
     }
+
+    #[test]
+    fn test_user_role_id() {
+        let mut user = create_test_user("admin", "admin");
+
+        assert!(user.role_id().is_none());
+        assert_eq!(user.effective_role_id().as_str(), "editor");
+
+        user.set_role_id(Some(RoleId::new("viewer").unwrap()));
+        assert_eq!(user.effective_role_id().as_str(), "viewer");
+
+        user.set_role_id(None);
+        assert_eq!(user.effective_role_id().as_str(), "editor");
+    }
+
+    #[test]
+    fn test_user_mfa() {
+        let mut user = create_test_user("admin", "admin");
+        assert!(!user.is_mfa_enabled());
+
+        user.set_mfa(Some(UserMfa::pending("JBSWY3DPEHPK3PXP")));
+        assert!(user.mfa().is_some());
+        assert!(!user.is_mfa_enabled());
+
+        let mut mfa = user.mfa().unwrap().clone();
+        mfa.enable(vec![]);
+        user.set_mfa(Some(mfa));
+        assert!(user.is_mfa_enabled());
+
+        let json = serde_json::to_string(&user).unwrap();
+        assert!(!json.contains("JBSWY3DPEHPK3PXP"));
+
+        user.set_mfa(None);
+        assert!(!user.is_mfa_enabled());
+    }
 }
@@ -0,0 +1,134 @@
+//! Multi-factor authentication state for users
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// TOTP-based multi-factor authentication state
+///
+/// Created in a pending state when a user starts enrollment and enabled once
+/// the user proves possession of the secret with a valid code.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserMfa {
+    /// Base32-encoded TOTP shared secret
+    secret: String,
+    /// Whether enrollment has been confirmed
+    enabled: bool,
+    /// SHA-256 hashes of the unused recovery codes
+    #[serde(default)]
+    recovery_code_hashes: Vec<String>,
+    /// Last accepted TOTP time step, used to reject code replay
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_used_step: Option<u64>,
+    /// When enrollment was confirmed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    enabled_at: Option<DateTime<Utc>>,
+}
+
+impl UserMfa {
+    /// Start a pending enrollment with the given secret
+    pub fn pending(secret: impl Into<String>) -> Self {
+        Self {
+            secret: secret.into(),
+            enabled: false,
+            recovery_code_hashes: Vec::new(),
+            last_used_step: None,
+            enabled_at: None,
+        }
+    }
+
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn enabled_at(&self) -> Option<DateTime<Utc>> {
+        self.enabled_at
+    }
+
+    pub fn recovery_codes_remaining(&self) -> usize {
+        self.recovery_code_hashes.len()
+    }
+
+    /// Confirm enrollment, replacing any existing recovery codes
+    pub fn enable(&mut self, recovery_code_hashes: Vec<String>) {
+        self.enabled = true;
+        self.recovery_code_hashes = recovery_code_hashes;
+        self.enabled_at = Some(Utc::now());
+    }
+
+    /// Replace the recovery codes
+    pub fn set_recovery_code_hashes(&mut self, recovery_code_hashes: Vec<String>) {
+        self.recovery_code_hashes = recovery_code_hashes;
+    }
+
+    /// Accept a TOTP time step. Returns false if the step (or a later one)
+    /// was already used.
+    pub fn accept_step(&mut self, step: u64) -> bool {
+        if self.last_used_step.is_some_and(|last| step <= last) {
+            return false;
+        }
+
+        self.last_used_step = Some(step);
+        true
+    }
+
+    /// Consume a recovery code by hash. Returns false if it is unknown or used.
+    pub fn consume_recovery_code(&mut self, code_hash: &str) -> bool {
+        match self.recovery_code_hashes.iter().position(|h| h == code_hash) {
+            Some(index) => {
+                self.recovery_code_hashes.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_enrollment() {
+        let mfa = UserMfa::pending("JBSWY3DPEHPK3PXP");
+
+        assert_eq!(mfa.secret(), "JBSWY3DPEHPK3PXP");
+        assert!(!mfa.is_enabled());
+        assert!(mfa.enabled_at().is_none());
+        assert_eq!(mfa.recovery_codes_remaining(), 0);
+    }
+
+    #[test]
+    fn test_enable() {
+        let mut mfa = UserMfa::pending("JBSWY3DPEHPK3PXP");
+        mfa.enable(vec!["a".to_string(), "b".to_string()]);
+
+        assert!(mfa.is_enabled());
+        assert!(mfa.enabled_at().is_some());
+        assert_eq!(mfa.recovery_codes_remaining(), 2);
+    }
+
+    #[test]
+    fn test_accept_step_rejects_replay() {
+        let mut mfa = UserMfa::pending("JBSWY3DPEHPK3PXP");
+
+        assert!(mfa.accept_step(100));
+        assert!(!mfa.accept_step(100));
+        assert!(!mfa.accept_step(99));
+        assert!(mfa.accept_step(101));
+    }
+
+    #[test]
+    fn test_consume_recovery_code() {
+        let mut mfa = UserMfa::pending("JBSWY3DPEHPK3PXP");
+        mfa.enable(vec!["a".to_string(), "b".to_string()]);
+
+        assert!(mfa.consume_recovery_code("a"));
+        assert!(!mfa.consume_recovery_code("a"));
+        assert!(!mfa.consume_recovery_code("c"));
+        assert_eq!(mfa.recovery_codes_remaining(), 1);
+    }
+}
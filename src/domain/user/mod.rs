@@ -4,10 +4,12 @@
 //! including user entities, validation, and repository traits.
 
 mod entity;
+mod mfa;
 mod repository;
 mod validation;
 
 pub use entity::{User, UserId, UserStatus};
+pub use mfa::UserMfa;
 pub use repository::UserRepository;
 pub use validation::{
     validate_password, validate_user_id, validate_username, UserValidationError,
@@ -3,7 +3,9 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use utoipa::ToSchema;
 
+use super::{render_payload_template, WebhookFilter};
 use crate::domain::storage::{StorageEntity, StorageKey};
 
 /// Unique identifier for a webhook
@@ -53,7 +55,7 @@ impl StorageEntity for Webhook {
 }
 
 /// Types of events that can trigger webhooks
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum WebhookEventType {
     /// Budget threshold alert triggered
@@ -62,6 +64,8 @@ pub enum WebhookEventType {
     BudgetExceeded,
     /// Experiment completed
     ExperimentCompleted,
+    /// Canary rollout rolled back after breaching its guard
+    ExperimentRolledBack,
     /// Workflow execution failed
     WorkflowFailed,
     /// Workflow execution succeeded
@@ -74,6 +78,8 @@ pub enum WebhookEventType {
     ApiKeyRevoked,
     /// Test case failed
     TestCaseFailed,
+    /// Usage of an API key or team spiked over its baseline
+    UsageAnomaly,
 }
 
 impl WebhookEventType {
@@ -83,12 +89,14 @@ impl WebhookEventType {
             Self::BudgetAlert,
             Self::BudgetExceeded,
             Self::ExperimentCompleted,
+            Self::ExperimentRolledBack,
             Self::WorkflowFailed,
             Self::WorkflowSucceeded,
             Self::ModelFailed,
             Self::ApiKeySuspended,
             Self::ApiKeyRevoked,
             Self::TestCaseFailed,
+            Self::UsageAnomaly,
         ]
     }
 
@@ -98,12 +106,14 @@ impl WebhookEventType {
             Self::BudgetAlert => "budget_alert",
             Self::BudgetExceeded => "budget_exceeded",
             Self::ExperimentCompleted => "experiment_completed",
+            Self::ExperimentRolledBack => "experiment_rolled_back",
             Self::WorkflowFailed => "workflow_failed",
             Self::WorkflowSucceeded => "workflow_succeeded",
             Self::ModelFailed => "model_failed",
             Self::ApiKeySuspended => "api_key_suspended",
             Self::ApiKeyRevoked => "api_key_revoked",
             Self::TestCaseFailed => "test_case_failed",
+            Self::UsageAnomaly => "usage_anomaly",
         }
     }
 }
@@ -115,7 +125,7 @@ impl std::fmt::Display for WebhookEventType {
 }
 
 /// Status of a webhook
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum WebhookStatus {
     /// Webhook is active and will receive events
@@ -142,6 +152,12 @@ pub struct Webhook {
     pub secret: Option<String>,
     /// Event types to subscribe to
     pub events: Vec<WebhookEventType>,
+    /// Narrows the subscribed events by team, model, subtype or cost
+    #[serde(default, skip_serializing_if = "WebhookFilter::is_empty")]
+    pub filter: WebhookFilter,
+    /// JSON template of the delivered payload, the event itself if none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_template: Option<serde_json::Value>,
     /// Custom headers to include in requests
     pub headers: HashMap<String, String>,
     /// Current status
@@ -175,6 +191,8 @@ impl Webhook {
             url: url.into(),
             secret: None,
             events: Vec::new(),
+            filter: WebhookFilter::default(),
+            payload_template: None,
             headers: HashMap::new(),
             status: WebhookStatus::Active,
             failure_count: 0,
@@ -214,6 +232,18 @@ impl Webhook {
         self
     }
 
+    /// Sets the event filter
+    pub fn with_filter(mut self, filter: WebhookFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Sets the payload template
+    pub fn with_payload_template(mut self, template: serde_json::Value) -> Self {
+        self.payload_template = Some(template);
+        self
+    }
+
     /// Adds a custom header
     pub fn with_header(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.headers.insert(key.into(), value.into());
@@ -244,6 +274,19 @@ impl Webhook {
         self.events.contains(&event)
     }
 
+    /// Checks if an event is subscribed to and passes the filter
+    pub fn accepts(&self, event: &WebhookEvent) -> bool {
+        self.is_subscribed_to(event.event_type) && self.filter.matches(event)
+    }
+
+    /// Payload delivered for an event
+    pub fn payload_for(&self, event: &WebhookEvent) -> serde_json::Value {
+        match &self.payload_template {
+            Some(template) => render_payload_template(template, event),
+            None => serde_json::to_value(event).unwrap_or_default(),
+        }
+    }
+
     /// Checks if the webhook is active
     pub fn is_active(&self) -> bool {
         self.status == WebhookStatus::Active
@@ -327,7 +370,7 @@ impl StorageEntity for WebhookDelivery {
 }
 
 /// Status of a webhook delivery attempt
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum DeliveryStatus {
     /// Delivery is pending
@@ -336,8 +379,9 @@ pub enum DeliveryStatus {
     Success,
     /// Delivery failed, may retry
     Failed,
-    /// All retries exhausted
-    Exhausted,
+    /// All retries exhausted; kept for manual redelivery
+    #[serde(alias = "exhausted")]
+    DeadLetter,
 }
 
 /// Record of a webhook delivery attempt
@@ -367,8 +411,15 @@ pub struct WebhookDelivery {
     pub last_attempt_at: Option<DateTime<Utc>>,
     /// When the next retry is scheduled
     pub next_retry_at: Option<DateTime<Utc>>,
-    /// When the delivery completed (success or exhausted)
+    /// When the delivery completed (success or dead-lettered)
     pub completed_at: Option<DateTime<Utc>>,
+    /// Delivery this one manually replays
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redelivery_of: Option<WebhookDeliveryId>,
+    /// W3C `traceparent` of the request that triggered the delivery, sent
+    /// with every attempt so retries join the same trace
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traceparent: Option<String>,
 }
 
 impl WebhookDelivery {
@@ -393,9 +444,23 @@ impl WebhookDelivery {
             last_attempt_at: None,
             next_retry_at: None,
             completed_at: None,
+            redelivery_of: None,
+            traceparent: None,
         }
     }
 
+    /// Creates a new pending delivery replaying the payload of this one
+    pub fn redelivery(&self, id: impl Into<WebhookDeliveryId>) -> Self {
+        let mut delivery = Self::new(
+            id,
+            self.webhook_id.clone(),
+            self.event_type,
+            self.payload.clone(),
+        );
+        delivery.redelivery_of = Some(self.id.clone());
+        delivery
+    }
+
     /// Records a successful attempt
     pub fn record_success(&mut self, status: u16, body: Option<String>) {
         self.attempts += 1;
@@ -423,15 +488,13 @@ impl WebhookDelivery {
         self.last_attempt_at = Some(Utc::now());
 
         if self.attempts >= max_retries {
-            self.status = DeliveryStatus::Exhausted;
+            self.status = DeliveryStatus::DeadLetter;
             self.completed_at = Some(Utc::now());
             self.next_retry_at = None;
         } else {
             self.status = DeliveryStatus::Failed;
-            // Exponential backoff: delay * 2^(attempts-1)
-            let backoff = retry_delay_secs * 2u32.pow(self.attempts.saturating_sub(1));
-            self.next_retry_at =
-                Some(Utc::now() + chrono::Duration::seconds(backoff as i64));
+            let backoff = retry_backoff_secs(retry_delay_secs, self.attempts);
+            self.next_retry_at = Some(Utc::now() + chrono::Duration::seconds(backoff));
         }
     }
 
@@ -443,10 +506,26 @@ impl WebhookDelivery {
 
     /// Checks if the delivery is complete
     pub fn is_complete(&self) -> bool {
-        matches!(self.status, DeliveryStatus::Success | DeliveryStatus::Exhausted)
+        matches!(
+            self.status,
+            DeliveryStatus::Success | DeliveryStatus::DeadLetter
+        )
     }
 }
 
+/// Upper bound on the delay between two delivery attempts
+pub const MAX_RETRY_DELAY_SECS: i64 = 6 * 3600;
+
+/// Exponential backoff before the next attempt: `retry_delay_secs * 2^(attempts-1)`,
+/// capped at [`MAX_RETRY_DELAY_SECS`]
+pub fn retry_backoff_secs(retry_delay_secs: u32, attempts: u32) -> i64 {
+    let factor = 2i64.saturating_pow(attempts.saturating_sub(1));
+
+    (retry_delay_secs as i64)
+        .saturating_mul(factor)
+        .min(MAX_RETRY_DELAY_SECS)
+}
+
 /// Event payload sent to webhooks
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WebhookEvent {
@@ -498,6 +577,35 @@ mod tests {
         assert!(!webhook.is_subscribed_to(WebhookEventType::ExperimentCompleted));
     }
 
+    #[test]
+    fn test_webhook_accepts_filtered_events() {
+        let webhook = Webhook::new("hook-1", "My Webhook", "https://example.com/webhook")
+            .with_event(WebhookEventType::UsageAnomaly)
+            .with_filter(WebhookFilter::new().with_subtypes(vec!["cost".to_string()]))
+            .with_payload_template(serde_json::json!({"text": "{{data.subtype}} spike"}));
+
+        let cost = WebhookEvent::new(
+            WebhookEventType::UsageAnomaly,
+            serde_json::json!({"subtype": "cost"}),
+        );
+        let requests = WebhookEvent::new(
+            WebhookEventType::UsageAnomaly,
+            serde_json::json!({"subtype": "requests"}),
+        );
+        let alert = WebhookEvent::new(
+            WebhookEventType::BudgetAlert,
+            serde_json::json!({"subtype": "cost"}),
+        );
+
+        assert!(webhook.accepts(&cost));
+        assert!(!webhook.accepts(&requests));
+        assert!(!webhook.accepts(&alert));
+        assert_eq!(
+            webhook.payload_for(&cost),
+            serde_json::json!({"text": "cost spike"})
+        );
+    }
+
     #[test]
     fn test_webhook_failure_tracking() {
         let mut webhook = Webhook::new("hook-1", "My Webhook", "https://example.com/webhook");
@@ -574,7 +682,7 @@ mod tests {
     }
 
     #[test]
-    fn test_delivery_exhausted_after_max_retries() {
+    fn test_delivery_dead_lettered_after_max_retries() {
         let mut delivery = WebhookDelivery::new(
             "del-1",
             WebhookId::new("hook-1"),
@@ -586,14 +694,33 @@ mod tests {
             delivery.record_failure("Error", None, None, 3, 60);
         }
 
-        assert_eq!(delivery.status, DeliveryStatus::Exhausted);
+        assert_eq!(delivery.status, DeliveryStatus::DeadLetter);
         assert!(delivery.is_complete());
+
+        let redelivery = delivery.redelivery("del-2");
+        assert_eq!(redelivery.status, DeliveryStatus::Pending);
+        assert_eq!(redelivery.attempts, 0);
+        assert_eq!(
+            redelivery.redelivery_of,
+            Some(WebhookDeliveryId::new("del-1"))
+        );
+    }
+
+    #[test]
+    fn test_retry_backoff_secs() {
+        assert_eq!(retry_backoff_secs(60, 1), 60);
+        assert_eq!(retry_backoff_secs(60, 3), 240);
+        assert_eq!(retry_backoff_secs(60, 40), MAX_RETRY_DELAY_SECS);
+        assert_eq!(
+            serde_json::from_str::<DeliveryStatus>("\"exhausted\"").unwrap(),
+            DeliveryStatus::DeadLetter
+        );
     }
 
     #[test]
     fn test_webhook_event_type_all() {
         let all = WebhookEventType::all();
-        assert_eq!(all.len(), 9);
+        assert_eq!(all.len(), 11);
     }
 
     #[test]
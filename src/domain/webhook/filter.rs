@@ -0,0 +1,161 @@
+//! Webhook event filters
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::WebhookEvent;
+use crate::domain::DomainError;
+
+/// Narrows the events of a webhook subscription. Every criterion that is
+/// set must match; events lacking the field a criterion checks never match
+/// it. An empty filter matches every event.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct WebhookFilter {
+    /// Only events of these teams (`data.team_id`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub team_ids: Vec<String>,
+    /// Only events of these models (`data.model_id`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub model_ids: Vec<String>,
+    /// Only events of these subtypes (`data.subtype`, e.g. the `cost` or
+    /// `requests` metric of a usage anomaly)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subtypes: Vec<String>,
+    /// Only events costing at least this many micro-dollars
+    /// (`data.cost_micros`, or `data.current_usage_micros` for budgets)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_cost_micros: Option<i64>,
+}
+
+impl WebhookFilter {
+    /// Creates an empty filter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match events of these teams
+    pub fn with_team_ids(mut self, team_ids: Vec<String>) -> Self {
+        self.team_ids = team_ids;
+        self
+    }
+
+    /// Only match events of these models
+    pub fn with_model_ids(mut self, model_ids: Vec<String>) -> Self {
+        self.model_ids = model_ids;
+        self
+    }
+
+    /// Only match events of these subtypes
+    pub fn with_subtypes(mut self, subtypes: Vec<String>) -> Self {
+        self.subtypes = subtypes;
+        self
+    }
+
+    /// Only match events costing at least `micros`
+    pub fn with_min_cost_micros(mut self, micros: i64) -> Self {
+        self.min_cost_micros = Some(micros);
+        self
+    }
+
+    /// Whether no criterion is set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Validates the filter criteria
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.min_cost_micros.is_some_and(|micros| micros < 0) {
+            return Err(DomainError::validation(
+                "filter.min_cost_micros must not be negative",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Whether an event passes the filter
+    pub fn matches(&self, event: &WebhookEvent) -> bool {
+        let field = |key: &str| event.data.get(key).and_then(|v| v.as_str());
+        let listed = |values: &[String], key: &str| {
+            values.is_empty() || field(key).is_some_and(|v| values.iter().any(|x| x == v))
+        };
+
+        if !listed(&self.team_ids, "team_id")
+            || !listed(&self.model_ids, "model_id")
+            || !listed(&self.subtypes, "subtype")
+        {
+            return false;
+        }
+
+        match self.min_cost_micros {
+            Some(min) => ["cost_micros", "current_usage_micros"]
+                .iter()
+                .find_map(|key| event.data.get(*key).and_then(|v| v.as_i64()))
+                .is_some_and(|cost| cost >= min),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::WebhookEventType;
+
+    fn event(data: serde_json::Value) -> WebhookEvent {
+        WebhookEvent::new(WebhookEventType::UsageAnomaly, data)
+    }
+
+    #[test]
+    fn test_empty_filter_matches_everything() {
+        let filter = WebhookFilter::new();
+
+        assert!(filter.is_empty());
+        assert!(filter.matches(&event(serde_json::json!({}))));
+    }
+
+    #[test]
+    fn test_filter_criteria() {
+        let filter = WebhookFilter::new()
+            .with_team_ids(vec!["team-1".to_string()])
+            .with_subtypes(vec!["cost".to_string()])
+            .with_min_cost_micros(1_000_000);
+
+        assert!(filter.matches(&event(serde_json::json!({
+            "team_id": "team-1",
+            "subtype": "cost",
+            "cost_micros": 2_000_000,
+        }))));
+        assert!(!filter.matches(&event(serde_json::json!({
+            "team_id": "team-2",
+            "subtype": "cost",
+            "cost_micros": 2_000_000,
+        }))));
+        assert!(!filter.matches(&event(serde_json::json!({
+            "team_id": "team-1",
+            "subtype": "requests",
+            "cost_micros": 2_000_000,
+        }))));
+        assert!(!filter.matches(&event(serde_json::json!({
+            "team_id": "team-1",
+            "subtype": "cost",
+            "current_usage_micros": 500_000,
+        }))));
+        assert!(!filter.matches(&event(serde_json::json!({
+            "subtype": "cost",
+            "cost_micros": 2_000_000,
+        }))));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(WebhookFilter::new()
+            .with_min_cost_micros(0)
+            .validate()
+            .is_ok());
+        assert!(WebhookFilter::new()
+            .with_min_cost_micros(-1)
+            .validate()
+            .is_err());
+    }
+}
@@ -1,7 +1,11 @@
 //! Webhook domain module for HTTP callback notifications
 
 mod entity;
+mod filter;
 mod repository;
+mod template;
 
 pub use entity::*;
+pub use filter::*;
 pub use repository::*;
+pub use template::*;
@@ -0,0 +1,120 @@
+//! Webhook payload templates
+//!
+//! A payload template is a JSON document whose string values may contain
+//! `{{path}}` placeholders, where `path` is a dot-separated path into the
+//! serialized [`WebhookEvent`] (`event_type`, `id`, `timestamp`,
+//! `data.budget_name`, ...). A string that is exactly one placeholder is
+//! replaced with the referenced JSON value, keeping numbers and objects
+//! intact; placeholders embedded in text are interpolated, with missing
+//! values rendered as an empty string.
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+use super::WebhookEvent;
+use crate::domain::DomainError;
+
+/// Regex to match placeholders: {{data.budget_name}}
+static PLACEHOLDER_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*([a-zA-Z0-9_]+(?:\.[a-zA-Z0-9_]+)*)\s*\}\}").unwrap());
+
+/// Validates that a payload template is a JSON object or array
+pub fn validate_payload_template(template: &serde_json::Value) -> Result<(), DomainError> {
+    if !template.is_object() && !template.is_array() {
+        return Err(DomainError::validation(
+            "payload_template must be a JSON object or array",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Renders a payload template against an event
+pub fn render_payload_template(
+    template: &serde_json::Value,
+    event: &WebhookEvent,
+) -> serde_json::Value {
+    let event = serde_json::to_value(event).unwrap_or_default();
+    render_value(template, &event)
+}
+
+fn render_value(template: &serde_json::Value, event: &serde_json::Value) -> serde_json::Value {
+    match template {
+        serde_json::Value::String(text) => render_string(text, event),
+        serde_json::Value::Array(items) => items.iter().map(|v| render_value(v, event)).collect(),
+        serde_json::Value::Object(fields) => fields
+            .iter()
+            .map(|(key, value)| (key.clone(), render_value(value, event)))
+            .collect(),
+        other => other.clone(),
+    }
+}
+
+fn render_string(text: &str, event: &serde_json::Value) -> serde_json::Value {
+    if let Some(captures) = PLACEHOLDER_PATTERN.captures(text)
+        && captures[0].len() == text.len()
+    {
+        return lookup(event, &captures[1])
+            .cloned()
+            .unwrap_or(serde_json::Value::Null);
+    }
+
+    let rendered = PLACEHOLDER_PATTERN.replace_all(text, |captures: &Captures| {
+        match lookup(event, &captures[1]) {
+            Some(serde_json::Value::String(s)) => s.clone(),
+            Some(serde_json::Value::Null) | None => String::new(),
+            Some(other) => other.to_string(),
+        }
+    });
+
+    serde_json::Value::String(rendered.into_owned())
+}
+
+fn lookup<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    path.split('.')
+        .try_fold(value, |current, key| match current {
+            serde_json::Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => current.get(key),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::WebhookEventType;
+
+    #[test]
+    fn test_render_slack_payload() {
+        let event = WebhookEvent::new(
+            WebhookEventType::BudgetExceeded,
+            serde_json::json!({
+                "budget_name": "Platform",
+                "current_usage_dollars": 12.5,
+                "limits": [10, 20],
+            }),
+        );
+        let template = serde_json::json!({
+            "text": ":warning: {{ event_type }}: budget {{data.budget_name}} at ${{data.current_usage_dollars}}{{data.missing}}",
+            "usage": "{{data.current_usage_dollars}}",
+            "blocks": [{"limit": "{{data.limits.0}}", "unknown": "{{data.missing}}"}],
+            "mrkdwn": true,
+        });
+
+        let payload = render_payload_template(&template, &event);
+
+        assert_eq!(
+            payload["text"],
+            ":warning: budget_exceeded: budget Platform at $12.5"
+        );
+        assert_eq!(payload["usage"], 12.5);
+        assert_eq!(payload["blocks"][0]["limit"], 10);
+        assert!(payload["blocks"][0]["unknown"].is_null());
+        assert_eq!(payload["mrkdwn"], true);
+    }
+
+    #[test]
+    fn test_validate_payload_template() {
+        assert!(validate_payload_template(&serde_json::json!({"text": "{{id}}"})).is_ok());
+        assert!(validate_payload_template(&serde_json::json!("{{id}}")).is_err());
+    }
+}
@@ -7,12 +7,15 @@
 //! - `${step:step-name:field:default}` - Optional with default value
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde_json::Value;
+use tokio_util::sync::CancellationToken;
 
 use super::error::WorkflowError;
+use crate::domain::knowledge_base::KnowledgeBaseCaller;
 
 /// Regex for request variable: ${request:field} or ${request:field:default}
 /// Field can include dots for nested access (e.g., user.profile.name)
@@ -26,6 +29,28 @@ static STEP_PATTERN: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"\$\{step:([a-zA-Z0-9_-]+):([a-zA-Z0-9_.-]+)(?::([^}]*))?\}").unwrap()
 });
 
+/// Callback told the steps executed so far and the steps of the workflow
+/// each time a step finishes
+#[derive(Clone)]
+pub struct StepProgress(Arc<dyn Fn(usize, usize) + Send + Sync>);
+
+impl StepProgress {
+    pub fn new(on_step: impl Fn(usize, usize) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(on_step))
+    }
+
+    /// Report `executed` steps out of `total`
+    pub fn report(&self, executed: usize, total: usize) {
+        (self.0)(executed, total)
+    }
+}
+
+impl std::fmt::Debug for StepProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StepProgress").finish_non_exhaustive()
+    }
+}
+
 /// Workflow execution context holding request input and step outputs
 #[derive(Debug, Clone)]
 pub struct WorkflowContext {
@@ -34,6 +59,15 @@ pub struct WorkflowContext {
 
     /// Outputs from executed steps, keyed by step name
     step_outputs: HashMap<String, Value>,
+
+    /// Caller on whose behalf knowledge bases are searched, if any
+    caller: Option<KnowledgeBaseCaller>,
+
+    /// Token stopping the execution when fired, if any
+    cancellation: Option<CancellationToken>,
+
+    /// Callback told about each finished step, if any
+    step_progress: Option<StepProgress>,
 }
 
 impl WorkflowContext {
@@ -42,9 +76,47 @@ impl WorkflowContext {
         Self {
             request_input,
             step_outputs: HashMap::new(),
+            caller: None,
+            cancellation: None,
+            step_progress: None,
         }
     }
 
+    /// Run the workflow on behalf of a caller, restricting its knowledge base
+    /// searches to the documents the caller may read
+    pub fn with_caller(mut self, caller: KnowledgeBaseCaller) -> Self {
+        self.caller = Some(caller);
+        self
+    }
+
+    /// Get the caller the workflow runs on behalf of
+    pub fn caller(&self) -> Option<&KnowledgeBaseCaller> {
+        self.caller.as_ref()
+    }
+
+    /// Stop the execution, abandoning the step in flight, once the token
+    /// is fired
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = Some(cancellation);
+        self
+    }
+
+    /// Get the token stopping the execution
+    pub fn cancellation(&self) -> Option<&CancellationToken> {
+        self.cancellation.as_ref()
+    }
+
+    /// Report each finished step to `step_progress`
+    pub fn with_step_progress(mut self, step_progress: StepProgress) -> Self {
+        self.step_progress = Some(step_progress);
+        self
+    }
+
+    /// Get the callback told about each finished step
+    pub fn step_progress(&self) -> Option<&StepProgress> {
+        self.step_progress.as_ref()
+    }
+
     /// Get the request input
     pub fn request_input(&self) -> &Value {
         &self.request_input
@@ -6,10 +6,12 @@ use chrono::{DateTime, Utc};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use super::error::WorkflowError;
 use super::step_types::WorkflowStepType;
 use crate::domain::storage::{StorageEntity, StorageKey};
+use crate::domain::team::TeamId;
 
 /// Maximum length for workflow IDs
 pub const MAX_ID_LENGTH: usize = 50;
@@ -93,7 +95,7 @@ pub fn validate_workflow_id(id: &str) -> Result<(), WorkflowError> {
 }
 
 /// Action to take when a step fails
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum OnErrorAction {
     /// Stop the workflow and return an error
@@ -209,6 +211,10 @@ pub struct Workflow {
     /// Whether the workflow is enabled
     enabled: bool,
 
+    /// Team owning the workflow, for quota accounting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    team_id: Option<TeamId>,
+
     /// When the workflow was created
     created_at: DateTime<Utc>,
 
@@ -228,6 +234,7 @@ impl Workflow {
             steps: Vec::new(),
             version: 1,
             enabled: true,
+            team_id: None,
             created_at: now,
             updated_at: now,
         }
@@ -260,6 +267,11 @@ impl Workflow {
         self
     }
 
+    pub fn with_team_id(mut self, team_id: TeamId) -> Self {
+        self.team_id = Some(team_id);
+        self
+    }
+
     // Getters
 
     pub fn id(&self) -> &WorkflowId {
@@ -290,6 +302,10 @@ impl Workflow {
         self.enabled
     }
 
+    pub fn team_id(&self) -> Option<&TeamId> {
+        self.team_id.as_ref()
+    }
+
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
     }
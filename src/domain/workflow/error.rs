@@ -40,6 +40,9 @@ pub enum WorkflowError {
 
     #[error("Service unavailable: {0}")]
     ServiceUnavailable(String),
+
+    #[error("Workflow execution cancelled: {0}")]
+    Cancelled(String),
 }
 
 impl WorkflowError {
@@ -96,6 +99,10 @@ impl WorkflowError {
     pub fn service_unavailable(message: impl Into<String>) -> Self {
         Self::ServiceUnavailable(message.into())
     }
+
+    pub fn cancelled(id: impl Into<String>) -> Self {
+        Self::Cancelled(id.into())
+    }
 }
 
 #[cfg(test)]
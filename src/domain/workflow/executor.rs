@@ -3,9 +3,12 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tokio_util::sync::CancellationToken;
 
+use super::context::StepProgress;
 use super::entity::Workflow;
 use super::error::WorkflowError;
+use crate::domain::knowledge_base::KnowledgeBaseCaller;
 
 /// Token usage for a workflow or step
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -244,6 +247,33 @@ pub trait WorkflowExecutor: Send + Sync + std::fmt::Debug {
         workflow: &Workflow,
         input: Value,
     ) -> Result<WorkflowResult, WorkflowError>;
+
+    /// Execute a workflow on behalf of a caller, whose knowledge base searches
+    /// only return the documents it may read
+    async fn execute_as(
+        &self,
+        workflow: &Workflow,
+        input: Value,
+        caller: KnowledgeBaseCaller,
+    ) -> Result<WorkflowResult, WorkflowError>;
+
+    /// Execute a workflow on behalf of a caller until it finishes or
+    /// `cancellation` is fired, abandoning the step in flight; executors
+    /// tracking steps report them to `step_progress`
+    async fn execute_cancellable(
+        &self,
+        workflow: &Workflow,
+        input: Value,
+        caller: KnowledgeBaseCaller,
+        cancellation: CancellationToken,
+        _step_progress: StepProgress,
+    ) -> Result<WorkflowResult, WorkflowError> {
+        tokio::select! {
+            biased;
+            _ = cancellation.cancelled() => Err(WorkflowError::cancelled(workflow.id().as_str())),
+            result = self.execute_as(workflow, input, caller) => result,
+        }
+    }
 }
 
 #[cfg(test)]
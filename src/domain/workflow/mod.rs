@@ -22,7 +22,7 @@ mod executor;
 pub mod repository;
 mod step_types;
 
-pub use context::{VariableRef, WorkflowContext};
+pub use context::{StepProgress, VariableRef, WorkflowContext};
 pub use entity::{
     validate_workflow_id, OnErrorAction, Workflow, WorkflowId, WorkflowStep, MAX_ID_LENGTH,
 };
@@ -31,6 +31,6 @@ pub use executor::{StepExecutionResult, WorkflowExecutor, WorkflowResult, Workfl
 pub use repository::WorkflowRepository;
 pub use step_types::{
     ChatCompletionStep, Condition, ConditionalAction, ConditionalStep, ConditionOperator,
-    CragScoringStep, HttpMethod, HttpRequestStep, KnowledgeBaseSearchStep, ScoringStrategy,
-    WorkflowStepType,
+    ContextCompression, CragScoringStep, HttpMethod, HttpRequestStep, KnowledgeBaseSearchStep,
+    QueryTransform, ScoringStrategy, WorkflowStepType, MAX_QUERY_EXPANSIONS,
 };
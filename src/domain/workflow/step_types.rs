@@ -1,9 +1,10 @@
 //! Workflow step type definitions
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 /// Type of workflow step
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum WorkflowStepType {
     /// LLM chat completion step
@@ -36,7 +37,7 @@ impl WorkflowStepType {
 }
 
 /// Chat completion step configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct ChatCompletionStep {
     /// Model ID to use for completion
     pub model_id: String,
@@ -108,7 +109,7 @@ impl ChatCompletionStep {
 }
 
 /// Knowledge base search step configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct KnowledgeBaseSearchStep {
     /// Knowledge base ID to search
     pub knowledge_base_id: String,
@@ -127,12 +128,140 @@ pub struct KnowledgeBaseSearchStep {
     /// Optional metadata filter
     #[serde(skip_serializing_if = "Option::is_none")]
     pub filter: Option<serde_json::Value>,
+
+    /// Optional namespace the search is restricted to (can contain variable
+    /// references, e.g. `${request:version}`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+
+    /// Optional LLM transformation of the query before searching
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_transform: Option<QueryTransform>,
+
+    /// Optional reduction of the results to their query-relevant sentences
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<ContextCompression>,
 }
 
 fn default_top_k() -> u32 {
     10
 }
 
+/// Most alternative queries a multi-query expansion may generate
+pub const MAX_QUERY_EXPANSIONS: u32 = 5;
+
+/// LLM transformation of a knowledge base search query
+///
+/// Raw user queries are often short, conversational or use other words than
+/// the documents. The query can be rewritten into a standalone search query,
+/// expanded into alternative phrasings searched alongside it, and complemented
+/// by a hypothetical answer passage (HyDE) whose embedding sits closer to the
+/// relevant documents than the question's. Results of all searched queries
+/// are merged, keeping the best score per document.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct QueryTransform {
+    /// Model generating the transformed queries
+    pub model_id: String,
+
+    /// Rewrite the query into a standalone search query
+    #[serde(default)]
+    pub rewrite: bool,
+
+    /// Alternative phrasings searched alongside the query (0 to 5)
+    #[serde(default)]
+    pub expansions: u32,
+
+    /// Also search with a hypothetical document answering the query
+    #[serde(default)]
+    pub hyde: bool,
+}
+
+impl QueryTransform {
+    pub fn new(model_id: impl Into<String>) -> Self {
+        Self {
+            model_id: model_id.into(),
+            rewrite: false,
+            expansions: 0,
+            hyde: false,
+        }
+    }
+
+    pub fn with_rewrite(mut self) -> Self {
+        self.rewrite = true;
+        self
+    }
+
+    pub fn with_expansions(mut self, expansions: u32) -> Self {
+        self.expansions = expansions;
+        self
+    }
+
+    pub fn with_hyde(mut self) -> Self {
+        self.hyde = true;
+        self
+    }
+
+    /// Whether any transformation is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.rewrite || self.expansions > 0 || self.hyde
+    }
+}
+
+/// Compression of knowledge base search results
+///
+/// Large top-k retrievals put many loosely related sentences into the final
+/// prompt. Compression keeps only the sentences of each result relevant to
+/// the query, dropping results left empty, so fewer tokens reach the prompt.
+/// Kept sentences are copied verbatim, in their original order.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum ContextCompression {
+    /// A model picks the relevant sentences of each result
+    Llm {
+        /// Model selecting the sentences
+        model_id: String,
+    },
+    /// Sentences are kept by the similarity of their embedding to the
+    /// query's, embedded with the knowledge base's embedding model
+    Embedding {
+        /// Minimum cosine similarity of a kept sentence (0.0 - 1.0)
+        #[serde(default = "default_compression_threshold")]
+        threshold: f32,
+        /// Most sentences kept per result, the most similar first
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_sentences: Option<usize>,
+    },
+}
+
+fn default_compression_threshold() -> f32 {
+    0.5
+}
+
+impl ContextCompression {
+    /// Extractive compression by a model
+    pub fn llm(model_id: impl Into<String>) -> Self {
+        Self::Llm {
+            model_id: model_id.into(),
+        }
+    }
+
+    /// Embedding-based sentence pruning
+    pub fn embedding(threshold: f32) -> Self {
+        Self::Embedding {
+            threshold,
+            max_sentences: None,
+        }
+    }
+
+    /// Name of the strategy
+    pub fn strategy(&self) -> &'static str {
+        match self {
+            Self::Llm { .. } => "llm",
+            Self::Embedding { .. } => "embedding",
+        }
+    }
+}
+
 impl KnowledgeBaseSearchStep {
     pub fn new(knowledge_base_id: impl Into<String>, query: impl Into<String>) -> Self {
         Self {
@@ -141,6 +270,9 @@ impl KnowledgeBaseSearchStep {
             top_k: default_top_k(),
             similarity_threshold: None,
             filter: None,
+            namespace: None,
+            query_transform: None,
+            compression: None,
         }
     }
 
@@ -158,10 +290,25 @@ impl KnowledgeBaseSearchStep {
         self.filter = Some(filter);
         self
     }
+
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    pub fn with_query_transform(mut self, transform: QueryTransform) -> Self {
+        self.query_transform = Some(transform);
+        self
+    }
+
+    pub fn with_compression(mut self, compression: ContextCompression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
 }
 
 /// CRAG scoring step configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct CragScoringStep {
     /// Model ID to use for LLM-based scoring
     pub model_id: String,
@@ -203,7 +350,7 @@ fn default_strategy() -> ScoringStrategy {
 }
 
 /// CRAG scoring strategy
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ScoringStrategy {
     /// Score based on similarity threshold only
@@ -254,7 +401,7 @@ impl CragScoringStep {
 }
 
 /// Conditional branching step
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct ConditionalStep {
     /// Conditions to evaluate (in order, first match wins)
     pub conditions: Vec<Condition>,
@@ -284,7 +431,7 @@ impl ConditionalStep {
 }
 
 /// A single condition to evaluate
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct Condition {
     /// Field to evaluate (variable reference like ${step:search:documents})
     pub field: String,
@@ -321,7 +468,7 @@ impl Condition {
 }
 
 /// Condition comparison operators
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ConditionOperator {
     /// Equal to
@@ -426,7 +573,7 @@ fn ends_with(field: &serde_json::Value, value: &serde_json::Value) -> bool {
 }
 
 /// Action to take in conditional branching
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ConditionalAction {
     /// Continue to the next step
@@ -455,7 +602,7 @@ impl ConditionalAction {
 }
 
 /// HTTP request method
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default, ToSchema)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum HttpMethod {
     #[default]
@@ -469,7 +616,7 @@ pub enum HttpMethod {
 }
 
 /// HTTP request step configuration
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
 pub struct HttpRequestStep {
     /// External API ID to use (required) - provides base URL and base headers
     pub external_api_id: String,
@@ -630,6 +777,54 @@ mod tests {
         assert_eq!(step.query, "${request:query}");
         assert_eq!(step.top_k, 5);
         assert_eq!(step.similarity_threshold, Some(0.8));
+        assert!(step.query_transform.is_none());
+    }
+
+    #[test]
+    fn test_kb_search_step_query_transform() {
+        let json = json!({
+            "type": "knowledge_base_search",
+            "knowledge_base_id": "docs-kb",
+            "query": "${request:query}",
+            "query_transform": {"model_id": "gpt-4o-mini", "expansions": 3, "hyde": true}
+        });
+
+        let step: WorkflowStepType = serde_json::from_value(json).unwrap();
+        let WorkflowStepType::KnowledgeBaseSearch(step) = step else {
+            panic!("expected a knowledge base search step");
+        };
+        let transform = step.query_transform.unwrap();
+
+        assert_eq!(
+            transform,
+            QueryTransform::new("gpt-4o-mini").with_expansions(3).with_hyde()
+        );
+        assert!(transform.is_enabled());
+        assert!(!QueryTransform::new("gpt-4o-mini").is_enabled());
+    }
+
+    #[test]
+    fn test_kb_search_step_compression() {
+        let json = json!({
+            "type": "knowledge_base_search",
+            "knowledge_base_id": "docs-kb",
+            "query": "${request:query}",
+            "compression": {"strategy": "embedding", "max_sentences": 3}
+        });
+
+        let step: WorkflowStepType = serde_json::from_value(json).unwrap();
+        let WorkflowStepType::KnowledgeBaseSearch(step) = step else {
+            panic!("expected a knowledge base search step");
+        };
+
+        assert_eq!(
+            step.compression,
+            Some(ContextCompression::Embedding {
+                threshold: 0.5,
+                max_sentences: Some(3),
+            })
+        );
+        assert_eq!(ContextCompression::llm("gpt-4o-mini").strategy(), "llm");
     }
 
     #[test]
@@ -10,7 +10,7 @@ mod service;
 mod storage_repository;
 
 pub use generator::{ApiKeyGenerator, GeneratedApiKey};
-pub use rate_limiter::{RateLimitResult, RateLimiter};
+pub use rate_limiter::{LimitType, QuotaWindow, RateLimitResult, RateLimiter};
 pub use repository::InMemoryApiKeyRepository;
-pub use service::ApiKeyService;
+pub use service::{check_expiration, ApiKeyService, RotateApiKeyResult};
 pub use storage_repository::StorageApiKeyRepository;
@@ -1,6 +1,7 @@
 //! Rate limiter implementation
 //!
-//! Provides sliding window rate limiting for API keys.
+//! Provides sliding window rate limiting and usage quotas for API keys:
+//! requests per minute/hour/day, tokens per minute/day and cost per 30 days.
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -32,6 +33,29 @@ pub enum LimitType {
     PerHour,
     PerDay,
     TokensPerMinute,
+    TokensPerDay,
+    CostPerMonth,
+}
+
+impl LimitType {
+    /// Length of the sliding window in seconds
+    pub fn window_secs(&self) -> u64 {
+        match self {
+            Self::PerMinute | Self::TokensPerMinute => 60,
+            Self::PerHour => 3600,
+            Self::PerDay | Self::TokensPerDay => 86400,
+            Self::CostPerMonth => 30 * 86400,
+        }
+    }
+
+    /// Amount a record contributes to this limit
+    fn amount(&self, record: &RequestRecord) -> u64 {
+        match self {
+            Self::PerMinute | Self::PerHour | Self::PerDay => u64::from(record.requests),
+            Self::TokensPerMinute | Self::TokensPerDay => u64::from(record.tokens),
+            Self::CostPerMonth => record.cost_micros.max(0) as u64,
+        }
+    }
 }
 
 impl std::fmt::Display for LimitType {
@@ -41,15 +65,39 @@ impl std::fmt::Display for LimitType {
             Self::PerHour => write!(f, "per_hour"),
             Self::PerDay => write!(f, "per_day"),
             Self::TokensPerMinute => write!(f, "tokens_per_minute"),
+            Self::TokensPerDay => write!(f, "tokens_per_day"),
+            Self::CostPerMonth => write!(f, "cost_per_month"),
         }
     }
 }
 
+/// Consumption of one configured limit within its sliding window
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaWindow {
+    pub limit_type: LimitType,
+    /// Limit of the window (micro-dollars for cost)
+    pub limit: u64,
+    /// Amount consumed within the window (micro-dollars for cost)
+    pub used: u64,
+    /// Seconds until the oldest consumption leaves the window
+    pub reset_in_seconds: u64,
+}
+
+impl QuotaWindow {
+    /// Amount left before the limit is reached
+    pub fn remaining(&self) -> u64 {
+        self.limit.saturating_sub(self.used)
+    }
+}
+
 /// Request record for rate limiting
 #[derive(Debug, Clone)]
 struct RequestRecord {
     timestamp: Instant,
+    /// 1 for a request, 0 for consumption recorded after a request completed
+    requests: u32,
     tokens: u32,
+    cost_micros: i64,
 }
 
 /// Rate limiter for API keys
@@ -104,13 +152,16 @@ impl RateLimiter {
 
     /// Record a request
     pub async fn record(&self, key_id: &str, tokens: u32) {
-        let mut records = self.records.write().await;
-        let key_records = records.entry(key_id.to_string()).or_insert_with(Vec::new);
+        self.push(key_id, 1, tokens, 0).await;
+    }
 
-        key_records.push(RequestRecord {
-            timestamp: Instant::now(),
-            tokens,
-        });
+    /// Record tokens and cost consumed by a request that was already counted
+    pub async fn record_consumption(&self, key_id: &str, tokens: u32, cost_micros: i64) {
+        if tokens == 0 && cost_micros <= 0 {
+            return;
+        }
+
+        self.push(key_id, 0, tokens, cost_micros).await;
     }
 
     /// Check and record in one operation
@@ -143,19 +194,50 @@ impl RateLimiter {
             let key_records = records.entry(key_id.to_string()).or_insert_with(Vec::new);
             key_records.push(RequestRecord {
                 timestamp: now,
+                requests: 1,
                 tokens: tokens.unwrap_or(0),
+                cost_micros: 0,
             });
         }
 
         result
     }
 
+    /// Current consumption of every configured limit of a key.
+    /// Empty when rate limiting is disabled.
+    pub async fn usage(&self, key_id: &str, config: &RateLimitConfig) -> Vec<QuotaWindow> {
+        if !config.is_enabled() {
+            return Vec::new();
+        }
+
+        let now = Instant::now();
+        let records = self.records.read().await;
+        let key_records = records.get(key_id).map(Vec::as_slice).unwrap_or_default();
+
+        configured_limits(config)
+            .into_iter()
+            .map(|(limit_type, limit)| window(key_records, limit_type, limit, now))
+            .collect()
+    }
+
     /// Reset rate limits for a key
     pub async fn reset(&self, key_id: &str) {
         let mut records = self.records.write().await;
         records.remove(key_id);
     }
 
+    async fn push(&self, key_id: &str, requests: u32, tokens: u32, cost_micros: i64) {
+        let mut records = self.records.write().await;
+        let key_records = records.entry(key_id.to_string()).or_insert_with(Vec::new);
+
+        key_records.push(RequestRecord {
+            timestamp: Instant::now(),
+            requests,
+            tokens,
+            cost_micros,
+        });
+    }
+
     fn calculate_limits(
         &self,
         records: Option<&Vec<RequestRecord>>,
@@ -163,121 +245,43 @@ impl RateLimiter {
         tokens: Option<u32>,
         now: Instant,
     ) -> RateLimitResult {
-        let records = match records {
-            Some(r) => r,
-            None => {
-                return RateLimitResult {
-                    allowed: true,
-                    remaining: config.requests_per_minute.saturating_sub(1),
-                    limit: config.requests_per_minute,
-                    reset_in_seconds: 60,
-                    limit_type: None,
-                };
-            }
-        };
-
-        let minute_ago = now.checked_sub(Duration::from_secs(60)).unwrap_or(now);
-        let hour_ago = now.checked_sub(Duration::from_secs(3600)).unwrap_or(now);
-        let day_ago = now.checked_sub(Duration::from_secs(86400)).unwrap_or(now);
-
-        let minute_count = records.iter().filter(|r| r.timestamp >= minute_ago).count() as u32;
-        let hour_count = records.iter().filter(|r| r.timestamp >= hour_ago).count() as u32;
-        let day_count = records.iter().filter(|r| r.timestamp >= day_ago).count() as u32;
-
-        // Check per-minute limit
-        if minute_count >= config.requests_per_minute {
-            let oldest_in_window = records
-                .iter()
-                .filter(|r| r.timestamp >= minute_ago)
-                .map(|r| r.timestamp)
-                .min();
-
-            let reset_in = oldest_in_window
-                .map(|t| {
-                    let elapsed = now.duration_since(t);
-                    60u64.saturating_sub(elapsed.as_secs())
-                })
-                .unwrap_or(60);
+        let records = records.map(Vec::as_slice).unwrap_or_default();
+        let request_tokens = u64::from(tokens.unwrap_or(0));
 
-            return RateLimitResult {
-                allowed: false,
-                remaining: 0,
-                limit: config.requests_per_minute,
-                reset_in_seconds: reset_in,
-                limit_type: Some(LimitType::PerMinute),
-            };
-        }
-
-        // Check per-hour limit
-        if hour_count >= config.requests_per_hour {
-            let oldest_in_window = records
-                .iter()
-                .filter(|r| r.timestamp >= hour_ago)
-                .map(|r| r.timestamp)
-                .min();
-
-            let reset_in = oldest_in_window
-                .map(|t| {
-                    let elapsed = now.duration_since(t);
-                    3600u64.saturating_sub(elapsed.as_secs())
-                })
-                .unwrap_or(3600);
-
-            return RateLimitResult {
-                allowed: false,
-                remaining: 0,
-                limit: config.requests_per_hour,
-                reset_in_seconds: reset_in,
-                limit_type: Some(LimitType::PerHour),
-            };
-        }
-
-        // Check per-day limit
-        if day_count >= config.requests_per_day {
-            let oldest_in_window = records
-                .iter()
-                .filter(|r| r.timestamp >= day_ago)
-                .map(|r| r.timestamp)
-                .min();
-
-            let reset_in = oldest_in_window
-                .map(|t| {
-                    let elapsed = now.duration_since(t);
-                    86400u64.saturating_sub(elapsed.as_secs())
-                })
-                .unwrap_or(86400);
+        for (limit_type, limit) in configured_limits(config) {
+            let window = window(records, limit_type, limit, now);
 
-            return RateLimitResult {
-                allowed: false,
-                remaining: 0,
-                limit: config.requests_per_day,
-                reset_in_seconds: reset_in,
-                limit_type: Some(LimitType::PerDay),
+            let exceeded = match limit_type {
+                LimitType::TokensPerMinute | LimitType::TokensPerDay => {
+                    window.used >= limit || window.used + request_tokens > limit
+                }
+                _ => window.used >= limit,
             };
-        }
-
-        // Check token limit if applicable
-        if let (Some(token_limit), Some(request_tokens)) = (config.tokens_per_minute, tokens) {
-            let minute_tokens: u32 = records
-                .iter()
-                .filter(|r| r.timestamp >= minute_ago)
-                .map(|r| r.tokens)
-                .sum();
 
-            if minute_tokens + request_tokens > token_limit {
+            if exceeded {
                 return RateLimitResult {
                     allowed: false,
-                    remaining: token_limit.saturating_sub(minute_tokens),
-                    limit: token_limit,
-                    reset_in_seconds: 60,
-                    limit_type: Some(LimitType::TokensPerMinute),
+                    remaining: clamp_u32(window.remaining()),
+                    limit: clamp_u32(limit),
+                    reset_in_seconds: window.reset_in_seconds,
+                    limit_type: Some(limit_type),
                 };
             }
         }
 
+        let minute_count = window(
+            records,
+            LimitType::PerMinute,
+            u64::from(config.requests_per_minute),
+            now,
+        )
+        .used;
+
         RateLimitResult {
             allowed: true,
-            remaining: config.requests_per_minute.saturating_sub(minute_count + 1),
+            remaining: clamp_u32(
+                u64::from(config.requests_per_minute).saturating_sub(minute_count + 1),
+            ),
             limit: config.requests_per_minute,
             reset_in_seconds: 60,
             limit_type: None,
@@ -295,12 +299,17 @@ impl RateLimiter {
             *last = Instant::now();
 
             let now = Instant::now();
-            let cutoff = now.checked_sub(Duration::from_secs(86400)).unwrap_or(now);
+            let day = Duration::from_secs(LimitType::PerDay.window_secs());
+            let month = Duration::from_secs(LimitType::CostPerMonth.window_secs());
 
             let mut records = self.records.write().await;
 
+            // Only cost needs to be kept beyond a day
             for key_records in records.values_mut() {
-                key_records.retain(|r| r.timestamp >= cutoff);
+                key_records.retain(|r| {
+                    let age = now.duration_since(r.timestamp);
+                    age <= day || (r.cost_micros > 0 && age <= month)
+                });
             }
 
             records.retain(|_, v| !v.is_empty());
@@ -308,6 +317,60 @@ impl RateLimiter {
     }
 }
 
+/// Limits configured for a key, in the order they are checked
+fn configured_limits(config: &RateLimitConfig) -> Vec<(LimitType, u64)> {
+    let mut limits = vec![
+        (LimitType::PerMinute, u64::from(config.requests_per_minute)),
+        (LimitType::PerHour, u64::from(config.requests_per_hour)),
+        (LimitType::PerDay, u64::from(config.requests_per_day)),
+    ];
+
+    if let Some(tokens) = config.tokens_per_minute {
+        limits.push((LimitType::TokensPerMinute, u64::from(tokens)));
+    }
+    if let Some(tokens) = config.tokens_per_day {
+        limits.push((LimitType::TokensPerDay, tokens));
+    }
+    if let Some(cost) = config.cost_per_month_micros {
+        limits.push((LimitType::CostPerMonth, cost.max(0) as u64));
+    }
+
+    limits
+}
+
+fn window(records: &[RequestRecord], limit_type: LimitType, limit: u64, now: Instant) -> QuotaWindow {
+    let length = Duration::from_secs(limit_type.window_secs());
+    let in_window = records
+        .iter()
+        .filter(|r| now.duration_since(r.timestamp) <= length && limit_type.amount(r) > 0);
+
+    let mut used = 0u64;
+    let mut oldest: Option<Instant> = None;
+    for record in in_window {
+        used = used.saturating_add(limit_type.amount(record));
+        oldest = Some(oldest.map_or(record.timestamp, |t| t.min(record.timestamp)));
+    }
+
+    let reset_in_seconds = oldest
+        .map(|t| {
+            limit_type
+                .window_secs()
+                .saturating_sub(now.duration_since(t).as_secs())
+        })
+        .unwrap_or(0);
+
+    QuotaWindow {
+        limit_type,
+        limit,
+        used,
+        reset_in_seconds,
+    }
+}
+
+fn clamp_u32(value: u64) -> u32 {
+    value.try_into().unwrap_or(u32::MAX)
+}
+
 impl Default for RateLimiter {
     fn default() -> Self {
         Self::new()
@@ -414,6 +477,61 @@ mod tests {
         assert!(result.allowed);
     }
 
+    #[tokio::test]
+    async fn test_rate_limiter_daily_token_and_cost_quotas() {
+        let limiter = RateLimiter::new();
+        let config = RateLimitConfig::new(100, 1000, 10000)
+            .with_tokens_per_day(1000)
+            .with_cost_per_month(1.0);
+
+        limiter.check_and_record("key1", &config, None).await;
+        limiter.record_consumption("key1", 1000, 200_000).await;
+
+        // Consumption does not count as a request, but exhausts the daily tokens
+        let result = limiter.check("key1", &config, None).await;
+        assert!(!result.allowed);
+        assert_eq!(result.limit_type, Some(LimitType::TokensPerDay));
+
+        let config = config.with_tokens_per_day(10_000);
+        limiter.record_consumption("key1", 0, 800_000).await;
+
+        let result = limiter.check("key1", &config, None).await;
+        assert!(!result.allowed);
+        assert_eq!(result.limit_type, Some(LimitType::CostPerMonth));
+        assert!(result.reset_in_seconds > 29 * 86400);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_usage() {
+        let limiter = RateLimiter::new();
+        let config = RateLimitConfig::new(10, 100, 1000).with_cost_per_month(5.0);
+
+        limiter.check_and_record("key1", &config, None).await;
+        limiter.record_consumption("key1", 150, 1_500_000).await;
+
+        let usage = limiter.usage("key1", &config).await;
+        let types: Vec<_> = usage.iter().map(|w| w.limit_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                LimitType::PerMinute,
+                LimitType::PerHour,
+                LimitType::PerDay,
+                LimitType::CostPerMonth
+            ]
+        );
+
+        assert_eq!(usage[0].used, 1);
+        assert_eq!(usage[0].remaining(), 9);
+        assert_eq!(usage[3].used, 1_500_000);
+        assert_eq!(usage[3].remaining(), 3_500_000);
+
+        assert!(limiter
+            .usage("key1", &RateLimitConfig::default())
+            .await
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn test_rate_limiter_unlimited() {
         let limiter = RateLimiter::new();
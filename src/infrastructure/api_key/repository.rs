@@ -156,11 +156,18 @@ impl ApiKeyRepository for InMemoryApiKeyRepository {
         Ok(count)
     }
 
-    async fn record_usage(&self, id: &ApiKeyId) -> Result<(), DomainError> {
+    async fn record_usage(
+        &self,
+        id: &ApiKeyId,
+        client_ip: Option<&str>,
+    ) -> Result<(), DomainError> {
         let mut keys = self.keys.write().await;
 
         if let Some(key) = keys.get_mut(id.as_str()) {
-            key.record_usage();
+            match client_ip {
+                Some(ip) => key.record_usage_from(ip),
+                None => key.record_usage(),
+            }
             Ok(())
         } else {
             Err(DomainError::not_found(format!(
@@ -335,7 +342,7 @@ mod tests {
 
         assert!(repo.get(key.id()).await.unwrap().unwrap().last_used_at().is_none());
 
-        repo.record_usage(key.id()).await.unwrap();
+        repo.record_usage(key.id(), None).await.unwrap();
 
         assert!(repo.get(key.id()).await.unwrap().unwrap().last_used_at().is_some());
     }
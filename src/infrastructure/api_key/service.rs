@@ -4,16 +4,20 @@
 
 use std::sync::Arc;
 
+use chrono::{DateTime, Duration, Utc};
 use tracing::{debug, info, warn};
 
 use crate::domain::api_key::{
     ApiKey, ApiKeyId, ApiKeyPermissions, ApiKeyRepository, ApiKeyStatus, RateLimitConfig,
 };
-use crate::domain::team::TeamId;
+use crate::domain::guardrail::InjectionAction;
+use crate::domain::network::IpNetwork;
+use crate::domain::team::{QuotaResource, TeamId};
 use crate::domain::DomainError;
+use crate::infrastructure::team::TeamQuotaGuard;
 
 use super::generator::ApiKeyGenerator;
-use super::rate_limiter::{RateLimitResult, RateLimiter};
+use super::rate_limiter::{QuotaWindow, RateLimitResult, RateLimiter};
 
 /// Result of creating a new API key
 #[derive(Debug)]
@@ -24,6 +28,26 @@ pub struct CreateApiKeyResult {
     pub secret: String,
 }
 
+/// Result of rotating an API key
+#[derive(Debug)]
+pub struct RotateApiKeyResult {
+    /// The replacement API key entity
+    pub api_key: ApiKey,
+    /// The full secret of the replacement key (only returned once)
+    pub secret: String,
+    /// The previous key, valid until the end of the grace period
+    pub previous: ApiKey,
+}
+
+/// Check that an API key expiration lies in the future
+pub fn check_expiration(expires_at: DateTime<Utc>) -> Result<(), DomainError> {
+    if expires_at <= Utc::now() {
+        return Err(DomainError::validation("Expiration must be in the future"));
+    }
+
+    Ok(())
+}
+
 /// API Key service for managing API keys
 #[derive(Debug)]
 pub struct ApiKeyService<R>
@@ -33,6 +57,7 @@ where
     repository: Arc<R>,
     generator: ApiKeyGenerator,
     rate_limiter: Arc<RateLimiter>,
+    quota_guard: Option<TeamQuotaGuard>,
 }
 
 impl<R: ApiKeyRepository> ApiKeyService<R> {
@@ -42,6 +67,7 @@ impl<R: ApiKeyRepository> ApiKeyService<R> {
             repository,
             generator: ApiKeyGenerator::production(),
             rate_limiter: Arc::new(RateLimiter::new()),
+            quota_guard: None,
         }
     }
 
@@ -57,6 +83,36 @@ impl<R: ApiKeyRepository> ApiKeyService<R> {
         self
     }
 
+    /// Enforce team API key quotas on creation
+    pub fn with_quota_guard(mut self, quota_guard: TeamQuotaGuard) -> Self {
+        self.quota_guard = Some(quota_guard);
+        self
+    }
+
+    /// Count the usable (not revoked or expired) API keys of a team
+    pub async fn count_for_team(&self, team_id: &TeamId) -> Result<u64, DomainError> {
+        let keys = self.repository.list(None).await?;
+
+        Ok(keys
+            .iter()
+            .filter(|k| k.team_id() == team_id)
+            .filter(|k| matches!(k.status(), ApiKeyStatus::Active | ApiKeyStatus::Suspended))
+            .count() as u64)
+    }
+
+    async fn ensure_quota(&self, team_id: &TeamId) -> Result<(), DomainError> {
+        match &self.quota_guard {
+            Some(guard) => {
+                guard
+                    .ensure(team_id, QuotaResource::ApiKeys, 1, || {
+                        self.count_for_team(team_id)
+                    })
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+
     /// Create a new API key
     pub async fn create(
         &self,
@@ -69,6 +125,8 @@ impl<R: ApiKeyRepository> ApiKeyService<R> {
         let name = name.into();
         info!("Creating API key: id={}, name={}, team={}", id, name, team_id);
 
+        self.ensure_quota(&team_id).await?;
+
         let generated = self.generator.generate();
 
         let api_key = ApiKey::new(id.clone(), &name, &generated.hash, &generated.prefix, team_id)
@@ -103,6 +161,8 @@ impl<R: ApiKeyRepository> ApiKeyService<R> {
             id, name, team_id
         );
 
+        self.ensure_quota(&team_id).await?;
+
         let generated = self.generator.from_secret(secret);
 
         let api_key = ApiKey::new(id.clone(), &name, &generated.hash, &generated.prefix, team_id)
@@ -121,38 +181,98 @@ impl<R: ApiKeyRepository> ApiKeyService<R> {
 
     /// Get an API key by ID
     pub async fn get(&self, id: &ApiKeyId) -> Result<Option<ApiKey>, DomainError> {
-        self.repository.get(id).await
+        match self.repository.get(id).await? {
+            Some(key) => Ok(Some(self.refresh_expiration(key).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Persist the `Expired` status for a key past its expiration date
+    async fn refresh_expiration(&self, mut key: ApiKey) -> Result<ApiKey, DomainError> {
+        if key.expire_if_due() {
+            info!("API key expired: id={}", key.id());
+            return self.repository.update(&key).await;
+        }
+
+        Ok(key)
+    }
+
+    /// Transition all active keys past their expiration date to `Expired`
+    pub async fn expire_due_keys(&self) -> Result<usize, DomainError> {
+        let due = self.repository.get_expiring_before(Utc::now()).await?;
+        let mut expired = 0;
+
+        for mut key in due {
+            if key.expire_if_due() {
+                self.repository.update(&key).await?;
+                expired += 1;
+            }
+        }
+
+        if expired > 0 {
+            info!("Expired {} API key(s)", expired);
+        }
+
+        Ok(expired)
     }
 
     /// Validate an API key and check permissions
     pub async fn validate(&self, key_secret: &str) -> Result<Option<ApiKey>, DomainError> {
+        self.validate_from(key_secret, None).await
+    }
+
+    /// Validate an API key, recording the client IP address on success
+    pub async fn validate_from(
+        &self,
+        key_secret: &str,
+        client_ip: Option<&str>,
+    ) -> Result<Option<ApiKey>, DomainError> {
+        let api_key = self.verify(key_secret).await?;
+
+        if let Some(ref key) = api_key {
+            self.record_usage(key, client_ip).await;
+        }
+
+        Ok(api_key)
+    }
+
+    /// Find the active API key of a secret without recording its usage, so
+    /// callers can apply further access checks first
+    pub async fn verify(&self, key_secret: &str) -> Result<Option<ApiKey>, DomainError> {
         let prefix = ApiKeyGenerator::extract_prefix(key_secret)
             .ok_or_else(|| DomainError::validation("Invalid API key format"))?;
 
         debug!("Validating API key with prefix: {}", prefix);
 
-        let api_key = self.repository.get_by_prefix(prefix).await?;
+        let Some(key) = self.repository.get_by_prefix(prefix).await? else {
+            return Ok(None);
+        };
 
-        if let Some(ref key) = api_key {
-            // Verify the key hash
-            if !self.generator.verify_key(key_secret, key.secret_hash()) {
-                debug!("API key hash verification failed");
-                return Ok(None);
-            }
+        // Verify the key hash
+        if !self.generator.verify_key(key_secret, key.secret_hash()) {
+            debug!("API key hash verification failed");
+            return Ok(None);
+        }
 
-            // Check if key is valid
-            if !key.is_valid() {
-                debug!("API key is not valid: status={:?}", key.status());
-                return Ok(None);
-            }
+        // Check if key is valid
+        if !key.is_valid() {
+            debug!("API key is not valid: status={:?}", key.status());
 
-            // Record usage
-            if let Err(e) = self.repository.record_usage(key.id()).await {
-                warn!("Failed to record API key usage: {}", e);
+            if let Err(e) = self.refresh_expiration(key).await {
+                warn!("Failed to persist API key expiration: {}", e);
             }
+
+            return Ok(None);
         }
 
-        Ok(api_key)
+        Ok(Some(key))
+    }
+
+    /// Record that an accepted request used an API key, from `client_ip`
+    pub async fn record_usage(&self, key: &ApiKey, client_ip: Option<&str>) {
+        if let Err(e) = self.repository.record_usage(key.id(), client_ip).await {
+            warn!("Failed to record API key usage: {}", e);
+        }
     }
 
     /// Check rate limits for an API key
@@ -166,6 +286,22 @@ impl<R: ApiKeyRepository> ApiKeyService<R> {
             .await
     }
 
+    /// Charge tokens and cost of a completed request to the quotas of a key
+    pub async fn record_consumption(&self, key: &ApiKey, tokens: u32, cost_micros: i64) {
+        if key.rate_limits().is_enabled() {
+            self.rate_limiter
+                .record_consumption(key.id().as_str(), tokens, cost_micros)
+                .await;
+        }
+    }
+
+    /// Current consumption of the rate limits and quotas of a key
+    pub async fn quota_usage(&self, key: &ApiKey) -> Vec<QuotaWindow> {
+        self.rate_limiter
+            .usage(key.id().as_str(), key.rate_limits())
+            .await
+    }
+
     /// Update an API key
     pub async fn update(&self, api_key: &ApiKey) -> Result<ApiKey, DomainError> {
         info!("Updating API key: id={}", api_key.id());
@@ -234,8 +370,149 @@ impl<R: ApiKeyRepository> ApiKeyService<R> {
         self.repository.delete(id).await
     }
 
+    /// Rotate an API key: issue a replacement with the same team, permissions
+    /// and rate limits, and keep the old key valid for `grace_period`.
+    pub async fn rotate(
+        &self,
+        id: &ApiKeyId,
+        new_id: ApiKeyId,
+        grace_period: Duration,
+    ) -> Result<RotateApiKeyResult, DomainError> {
+        info!("Rotating API key: id={}, new_id={}", id, new_id);
+
+        if grace_period < Duration::zero() {
+            return Err(DomainError::validation("Grace period cannot be negative"));
+        }
+
+        let mut previous = self
+            .repository
+            .get(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("API key '{}' not found", id)))?;
+
+        if !previous.is_valid() {
+            return Err(DomainError::validation(format!(
+                "API key '{}' is not active and cannot be rotated",
+                id
+            )));
+        }
+
+        let generated = self.generator.generate();
+
+        let mut api_key = ApiKey::new(
+            new_id.clone(),
+            previous.name(),
+            &generated.hash,
+            &generated.prefix,
+            previous.team_id().clone(),
+        )
+        .with_permissions(previous.permissions().clone())
+        .with_rate_limits(previous.rate_limits().clone())
+        .with_allowed_cidrs(previous.allowed_cidrs().to_vec())
+        .with_injection_action(previous.injection_action())
+        .with_no_log(previous.no_log());
+
+        if let Some(description) = previous.description() {
+            api_key = api_key.with_description(description);
+        }
+
+        if let Some(expires_at) = previous.expires_at() {
+            api_key = api_key.with_expiration(expires_at);
+        }
+
+        if let Some(created_by) = previous.created_by() {
+            api_key = api_key.with_created_by(created_by);
+        }
+
+        let created = self.repository.create(api_key).await?;
+
+        previous.mark_rotated(new_id, Utc::now() + grace_period);
+        let previous = self.repository.update(&previous).await?;
+
+        info!("API key rotated: id={}, replaced_by={}", id, created.id());
+
+        Ok(RotateApiKeyResult {
+            api_key: created,
+            secret: generated.key,
+            previous,
+        })
+    }
+
+    /// Set or clear the expiration of an API key
+    pub async fn set_expiration(
+        &self,
+        id: &ApiKeyId,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<ApiKey, DomainError> {
+        info!("Updating expiration for API key: id={}", id);
+
+        if let Some(expires_at) = expires_at {
+            check_expiration(expires_at)?;
+        }
+
+        let mut key = self
+            .repository
+            .get(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("API key '{}' not found", id)))?;
+
+        key.set_expiration(expires_at);
+        self.repository.update(&key).await
+    }
+
+    /// Set the client networks allowed to use an API key (empty = any)
+    pub async fn set_allowed_cidrs(
+        &self,
+        id: &ApiKeyId,
+        allowed_cidrs: Vec<IpNetwork>,
+    ) -> Result<ApiKey, DomainError> {
+        info!("Updating allowed CIDRs for API key: id={}", id);
+
+        let mut key = self
+            .repository
+            .get(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("API key '{}' not found", id)))?;
+
+        key.set_allowed_cidrs(allowed_cidrs);
+        self.repository.update(&key).await
+    }
+
+    /// Set the action on prompt-injection detections (None = gateway default)
+    pub async fn set_injection_action(
+        &self,
+        id: &ApiKeyId,
+        injection_action: Option<InjectionAction>,
+    ) -> Result<ApiKey, DomainError> {
+        info!("Updating injection action for API key: id={}", id);
+
+        let mut key = self
+            .repository
+            .get(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("API key '{}' not found", id)))?;
+
+        key.set_injection_action(injection_action);
+        self.repository.update(&key).await
+    }
+
+    /// Enable or disable zero-retention mode
+    pub async fn set_no_log(&self, id: &ApiKeyId, no_log: bool) -> Result<ApiKey, DomainError> {
+        info!("Updating zero-retention mode for API key: id={}", id);
+
+        let mut key = self
+            .repository
+            .get(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("API key '{}' not found", id)))?;
+
+        key.set_no_log(no_log);
+        self.repository.update(&key).await
+    }
+
     /// List all API keys
     pub async fn list(&self, status: Option<ApiKeyStatus>) -> Result<Vec<ApiKey>, DomainError> {
+        self.expire_due_keys().await?;
         self.repository.list(status).await
     }
 
@@ -439,6 +716,34 @@ mod tests {
         assert!(!result3.allowed);
     }
 
+    #[tokio::test]
+    async fn test_quota_usage() {
+        let service = create_service();
+        let id = ApiKeyId::new("test-key").unwrap();
+        let rate_limits = RateLimitConfig::new(10, 100, 1000).with_tokens_per_day(500);
+
+        let created = service
+            .create(
+                id,
+                "Test Key",
+                admin_team(),
+                ApiKeyPermissions::read_only(),
+                Some(rate_limits),
+            )
+            .await
+            .unwrap();
+
+        service.check_rate_limit(&created.api_key, None).await;
+        service
+            .record_consumption(&created.api_key, 500, 1_000)
+            .await;
+
+        let usage = service.quota_usage(&created.api_key).await;
+        assert_eq!(usage[0].used, 1);
+        assert_eq!(usage[3].used, 500);
+        assert!(!service.check_rate_limit(&created.api_key, None).await.allowed);
+    }
+
     #[tokio::test]
     async fn test_permission_checking() {
         let service = create_service();
@@ -527,4 +832,242 @@ mod tests {
         let found = service.get(&id).await.unwrap();
         assert!(found.is_none());
     }
+
+    #[tokio::test]
+    async fn test_validate_records_client_ip() {
+        let service = create_service();
+        let id = ApiKeyId::new("test-key").unwrap();
+
+        let created = service
+            .create(id.clone(), "Test Key", admin_team(), ApiKeyPermissions::read_only(), None)
+            .await
+            .unwrap();
+
+        service
+            .validate_from(&created.secret, Some("203.0.113.7"))
+            .await
+            .unwrap()
+            .unwrap();
+
+        let key = service.get(&id).await.unwrap().unwrap();
+        assert!(key.last_used_at().is_some());
+        assert_eq!(key.last_used_ip(), Some("203.0.113.7"));
+    }
+
+    #[tokio::test]
+    async fn test_verify_does_not_record_usage() {
+        let service = create_service();
+        let id = ApiKeyId::new("test-key").unwrap();
+
+        let created = service
+            .create(id.clone(), "Test Key", admin_team(), ApiKeyPermissions::read_only(), None)
+            .await
+            .unwrap();
+
+        let key = service.verify(&created.secret).await.unwrap().unwrap();
+        assert!(service.get(&id).await.unwrap().unwrap().last_used_at().is_none());
+
+        service.record_usage(&key, Some("203.0.113.7")).await;
+        let key = service.get(&id).await.unwrap().unwrap();
+        assert!(key.last_used_at().is_some());
+        assert_eq!(key.last_used_ip(), Some("203.0.113.7"));
+    }
+
+    #[tokio::test]
+    async fn test_expired_key_transitions_status() {
+        let service = create_service();
+        let id = ApiKeyId::new("test-key").unwrap();
+
+        let created = service
+            .create(id.clone(), "Test Key", admin_team(), ApiKeyPermissions::read_only(), None)
+            .await
+            .unwrap();
+
+        let mut key = created.api_key.clone();
+        key.set_expiration(Some(Utc::now() - Duration::seconds(1)));
+        service.update(&key).await.unwrap();
+
+        let validated = service.validate(&created.secret).await.unwrap();
+        assert!(validated.is_none());
+
+        let key = service.get(&id).await.unwrap().unwrap();
+        assert_eq!(key.status(), ApiKeyStatus::Expired);
+    }
+
+    #[tokio::test]
+    async fn test_expire_due_keys() {
+        let service = create_service();
+
+        for name in ["key-1", "key-2"] {
+            service
+                .create(
+                    ApiKeyId::new(name).unwrap(),
+                    name,
+                    admin_team(),
+                    ApiKeyPermissions::new(),
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+
+        let mut key = service.get(&ApiKeyId::new("key-1").unwrap()).await.unwrap().unwrap();
+        key.set_expiration(Some(Utc::now() - Duration::seconds(1)));
+        service.update(&key).await.unwrap();
+
+        assert_eq!(service.expire_due_keys().await.unwrap(), 1);
+        assert_eq!(service.expire_due_keys().await.unwrap(), 0);
+
+        let expired = service.list(Some(ApiKeyStatus::Expired)).await.unwrap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].id().as_str(), "key-1");
+    }
+
+    #[tokio::test]
+    async fn test_set_expiration() {
+        let service = create_service();
+        let id = ApiKeyId::new("test-key").unwrap();
+
+        service
+            .create(id.clone(), "Test Key", admin_team(), ApiKeyPermissions::new(), None)
+            .await
+            .unwrap();
+
+        let expires_at = Utc::now() + Duration::days(30);
+        let key = service.set_expiration(&id, Some(expires_at)).await.unwrap();
+        assert_eq!(key.expires_at(), Some(expires_at));
+
+        let past = Utc::now() - Duration::days(1);
+        assert!(service.set_expiration(&id, Some(past)).await.is_err());
+
+        let key = service.set_expiration(&id, None).await.unwrap();
+        assert!(key.expires_at().is_none());
+    }
+
+    #[test]
+    fn test_check_expiration() {
+        assert!(check_expiration(Utc::now() + Duration::minutes(1)).is_ok());
+        assert!(check_expiration(Utc::now() - Duration::minutes(1)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rotate_with_grace_period() {
+        let service = create_service();
+        let id = ApiKeyId::new("old-key").unwrap();
+
+        let created = service
+            .create(id.clone(), "Rotating Key", admin_team(), ApiKeyPermissions::full_access(), None)
+            .await
+            .unwrap();
+
+        let rotated = service
+            .rotate(&id, ApiKeyId::new("new-key").unwrap(), Duration::hours(1))
+            .await
+            .unwrap();
+
+        assert_ne!(rotated.secret, created.secret);
+        assert_eq!(rotated.api_key.name(), "Rotating Key");
+        assert!(rotated.api_key.permissions().admin);
+        assert_eq!(rotated.previous.replaced_by().unwrap().as_str(), "new-key");
+
+        // Both keys remain valid during the grace period
+        assert!(service.validate(&created.secret).await.unwrap().is_some());
+        assert!(service.validate(&rotated.secret).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_rotate_without_grace_period() {
+        let service = create_service();
+        let id = ApiKeyId::new("old-key").unwrap();
+
+        let created = service
+            .create(id.clone(), "Rotating Key", admin_team(), ApiKeyPermissions::new(), None)
+            .await
+            .unwrap();
+
+        let rotated = service
+            .rotate(&id, ApiKeyId::new("new-key").unwrap(), Duration::zero())
+            .await
+            .unwrap();
+
+        assert!(service.validate(&created.secret).await.unwrap().is_none());
+        assert!(service.validate(&rotated.secret).await.unwrap().is_some());
+
+        let previous = service.get(&id).await.unwrap().unwrap();
+        assert_eq!(previous.status(), ApiKeyStatus::Expired);
+    }
+
+    #[tokio::test]
+    async fn test_rotate_revoked_key_fails() {
+        let service = create_service();
+        let id = ApiKeyId::new("old-key").unwrap();
+
+        service
+            .create(id.clone(), "Key", admin_team(), ApiKeyPermissions::new(), None)
+            .await
+            .unwrap();
+        service.revoke(&id).await.unwrap();
+
+        let result = service
+            .rotate(&id, ApiKeyId::new("new-key").unwrap(), Duration::hours(1))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_set_allowed_cidrs_carried_over_rotation() {
+        let service = create_service();
+        let id = ApiKeyId::new("old-key").unwrap();
+
+        service
+            .create(id.clone(), "Key", admin_team(), ApiKeyPermissions::new(), None)
+            .await
+            .unwrap();
+
+        let key = service
+            .set_allowed_cidrs(&id, vec!["10.0.0.0/8".parse().unwrap()])
+            .await
+            .unwrap();
+        assert_eq!(key.allowed_cidrs().len(), 1);
+
+        let rotated = service
+            .rotate(&id, ApiKeyId::new("new-key").unwrap(), Duration::hours(1))
+            .await
+            .unwrap();
+        assert_eq!(rotated.api_key.allowed_cidrs(), key.allowed_cidrs());
+    }
+
+    #[tokio::test]
+    async fn test_create_enforces_team_quota() {
+        use crate::domain::team::{Team, TeamQuota, TeamRepository};
+        use crate::infrastructure::storage::InMemoryStorage;
+        use crate::infrastructure::team::StorageTeamRepository;
+
+        let teams = StorageTeamRepository::new(Arc::new(InMemoryStorage::<Team>::new()));
+        let mut team = Team::new(TeamId::new("team-a").unwrap(), "Team A").unwrap();
+        team.set_quota(TeamQuota::new().with_limit(QuotaResource::ApiKeys, 1));
+        teams.create(team).await.unwrap();
+
+        let service = create_service().with_quota_guard(TeamQuotaGuard::new(Arc::new(teams)));
+        let team_id = TeamId::new("team-a").unwrap();
+
+        let first = service
+            .create(ApiKeyId::new("key-1").unwrap(), "Key", team_id.clone(), ApiKeyPermissions::new(), None)
+            .await
+            .unwrap();
+
+        let result = service
+            .create(ApiKeyId::new("key-2").unwrap(), "Key", team_id.clone(), ApiKeyPermissions::new(), None)
+            .await;
+        assert!(matches!(result, Err(DomainError::Conflict { .. })));
+
+        // Revoked keys no longer count against the quota
+        service.revoke(first.api_key.id()).await.unwrap();
+        assert_eq!(service.count_for_team(&team_id).await.unwrap(), 0);
+
+        assert!(service
+            .create(ApiKeyId::new("key-2").unwrap(), "Key", team_id, ApiKeyPermissions::new(), None)
+            .await
+            .is_ok());
+    }
 }
@@ -83,14 +83,22 @@ impl ApiKeyRepository for StorageApiKeyRepository {
         }
     }
 
-    async fn record_usage(&self, id: &ApiKeyId) -> Result<(), DomainError> {
+    async fn record_usage(
+        &self,
+        id: &ApiKeyId,
+        client_ip: Option<&str>,
+    ) -> Result<(), DomainError> {
         let mut key = self
             .storage
             .get(id)
             .await?
             .ok_or_else(|| DomainError::not_found(format!("API key '{}' not found", id)))?;
 
-        key.record_usage();
+        match client_ip {
+            Some(ip) => key.record_usage_from(ip),
+            None => key.record_usage(),
+        }
+
         self.storage.update(key).await?;
         Ok(())
     }
@@ -223,9 +231,21 @@ mod tests {
         let key = create_test_key("test-1");
         repo.create(key.clone()).await.unwrap();
 
-        repo.record_usage(key.id()).await.unwrap();
+        repo.record_usage(key.id(), None).await.unwrap();
 
         let fetched = repo.get(key.id()).await.unwrap().unwrap();
         assert!(fetched.last_used_at().is_some());
     }
+
+    #[tokio::test]
+    async fn test_record_usage_with_client_ip() {
+        let repo = create_repo();
+        let key = create_test_key("test-1");
+        repo.create(key.clone()).await.unwrap();
+
+        repo.record_usage(key.id(), Some("192.168.1.10")).await.unwrap();
+
+        let fetched = repo.get(key.id()).await.unwrap().unwrap();
+        assert_eq!(fetched.last_used_ip(), Some("192.168.1.10"));
+    }
 }
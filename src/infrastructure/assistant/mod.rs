@@ -0,0 +1,7 @@
+//! Assistant infrastructure implementations
+
+mod service;
+mod storage_repository;
+
+pub use service::{AssistantService, CreateAssistantRequest, UpdateAssistantRequest};
+pub use storage_repository::StorageAssistantRepository;
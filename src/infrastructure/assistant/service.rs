@@ -0,0 +1,320 @@
+//! Assistant management and knowledge base retrieval
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tracing::{info, warn};
+
+use crate::domain::DomainError;
+use crate::domain::assistant::{
+    Assistant, AssistantId, AssistantRepository, AssistantTool, KnowledgeBaseBinding,
+};
+use crate::domain::knowledge_base::{KnowledgeBaseCaller, SearchParams};
+use crate::infrastructure::knowledge_base::{
+    KnowledgeBaseAccessTracker, KnowledgeBaseProviderRegistryTrait,
+};
+
+/// Request to create an assistant
+#[derive(Debug, Clone)]
+pub struct CreateAssistantRequest {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub model_id: String,
+    pub prompt_id: String,
+    pub prompt_variables: HashMap<String, String>,
+    pub knowledge_bases: Vec<KnowledgeBaseBinding>,
+    pub tools: Vec<AssistantTool>,
+    pub enabled: bool,
+}
+
+/// Request to update an assistant; unset fields are left unchanged
+#[derive(Debug, Clone, Default)]
+pub struct UpdateAssistantRequest {
+    pub name: Option<String>,
+    pub description: Option<Option<String>>,
+    pub model_id: Option<String>,
+    pub prompt_id: Option<String>,
+    pub prompt_variables: Option<HashMap<String, String>>,
+    pub knowledge_bases: Option<Vec<KnowledgeBaseBinding>>,
+    pub tools: Option<Vec<AssistantTool>>,
+    pub enabled: Option<bool>,
+}
+
+/// Manages assistants and retrieves the context of their knowledge bases
+pub struct AssistantService<R: AssistantRepository> {
+    repository: Arc<R>,
+    kb_registry: Arc<dyn KnowledgeBaseProviderRegistryTrait>,
+    access_tracker: Option<Arc<KnowledgeBaseAccessTracker>>,
+}
+
+impl<R: AssistantRepository> AssistantService<R> {
+    /// Create a new assistant service
+    pub fn new(
+        repository: Arc<R>,
+        kb_registry: Arc<dyn KnowledgeBaseProviderRegistryTrait>,
+    ) -> Self {
+        Self {
+            repository,
+            kb_registry,
+            access_tracker: None,
+        }
+    }
+
+    /// Count the knowledge base searches of assistants
+    pub fn with_access_tracker(mut self, tracker: Arc<KnowledgeBaseAccessTracker>) -> Self {
+        self.access_tracker = Some(tracker);
+        self
+    }
+
+    /// Every assistant, ordered by name
+    pub async fn list(&self) -> Result<Vec<Assistant>, DomainError> {
+        self.repository.list().await
+    }
+
+    /// Get an assistant by ID
+    pub async fn get(&self, id: &str) -> Result<Option<Assistant>, DomainError> {
+        self.repository.get(&parse_id(id)?).await
+    }
+
+    /// Get an assistant by ID, returning an error if not found
+    pub async fn get_required(&self, id: &str) -> Result<Assistant, DomainError> {
+        self.get(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("Assistant '{}' not found", id)))
+    }
+
+    /// Create an assistant
+    pub async fn create(&self, request: CreateAssistantRequest) -> Result<Assistant, DomainError> {
+        let assistant_id = parse_id(&request.id)?;
+
+        if self.repository.get(&assistant_id).await?.is_some() {
+            return Err(DomainError::conflict(format!(
+                "Assistant '{}' already exists",
+                request.id
+            )));
+        }
+
+        let mut assistant = Assistant::new(
+            assistant_id,
+            request.name,
+            request.model_id,
+            request.prompt_id,
+        )
+        .with_prompt_variables(request.prompt_variables)
+        .with_knowledge_bases(request.knowledge_bases)
+        .with_tools(request.tools)
+        .with_enabled(request.enabled);
+        if let Some(description) = request.description {
+            assistant = assistant.with_description(description);
+        }
+
+        assistant.validate()?;
+        let assistant = self.repository.save(assistant).await?;
+
+        info!(assistant_id = %assistant.id(), model_id = %assistant.model_id(), "Created assistant");
+        Ok(assistant)
+    }
+
+    /// Update an assistant
+    pub async fn update(
+        &self,
+        id: &str,
+        request: UpdateAssistantRequest,
+    ) -> Result<Assistant, DomainError> {
+        let mut assistant = self.get_required(id).await?;
+
+        if let Some(name) = request.name {
+            assistant.set_name(name);
+        }
+        if let Some(description) = request.description {
+            assistant.set_description(description);
+        }
+        if let Some(model_id) = request.model_id {
+            assistant.set_model_id(model_id);
+        }
+        if let Some(prompt_id) = request.prompt_id {
+            assistant.set_prompt_id(prompt_id);
+        }
+        if let Some(variables) = request.prompt_variables {
+            assistant.set_prompt_variables(variables);
+        }
+        if let Some(knowledge_bases) = request.knowledge_bases {
+            assistant.set_knowledge_bases(knowledge_bases);
+        }
+        if let Some(tools) = request.tools {
+            assistant.set_tools(tools);
+        }
+        if let Some(enabled) = request.enabled {
+            assistant.set_enabled(enabled);
+        }
+
+        assistant.validate()?;
+        self.repository.save(assistant).await
+    }
+
+    /// Delete an assistant
+    pub async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        self.repository.delete(&parse_id(id)?).await
+    }
+
+    /// Search the bound knowledge bases for `query`, returning the passages
+    /// in binding order. A knowledge base that cannot be searched is logged
+    /// and skipped so the conversation continues without its context. With a
+    /// caller, only the documents it may read are returned.
+    pub async fn retrieve(
+        &self,
+        assistant: &Assistant,
+        query: &str,
+        caller: Option<&KnowledgeBaseCaller>,
+    ) -> Vec<String> {
+        let mut passages = Vec::new();
+
+        for binding in assistant.knowledge_bases() {
+            let mut params = SearchParams::new(query).with_top_k(binding.top_k);
+            if let Some(threshold) = binding.similarity_threshold {
+                params = params.with_similarity_threshold(threshold);
+            }
+            if let Some(namespace) = &binding.namespace {
+                params = params.with_namespace(namespace);
+            }
+            if let Some(caller) = caller {
+                params = params.with_caller(caller.clone());
+            }
+
+            let results = match self
+                .kb_registry
+                .get_required(&binding.knowledge_base_id)
+                .await
+            {
+                Ok(provider) => provider.search(params).await,
+                Err(e) => Err(e),
+            };
+
+            if let (Ok(results), Some(tracker)) = (&results, &self.access_tracker) {
+                tracker.track(&binding.knowledge_base_id, query, results);
+            }
+
+            match results {
+                Ok(results) => passages.extend(
+                    results
+                        .into_iter()
+                        .take(binding.top_k as usize)
+                        .map(|result| result.content),
+                ),
+                Err(e) => warn!(
+                    assistant_id = %assistant.id(),
+                    kb_id = %binding.knowledge_base_id,
+                    error = %e,
+                    "Assistant knowledge base search failed, continuing without its context"
+                ),
+            }
+        }
+
+        passages
+    }
+}
+
+fn parse_id(id: &str) -> Result<AssistantId, DomainError> {
+    AssistantId::new(id).map_err(|e| DomainError::validation(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::knowledge_base::{KnowledgeBaseId, MockKnowledgeBaseProvider, SearchResult};
+    use crate::infrastructure::assistant::StorageAssistantRepository;
+    use crate::infrastructure::knowledge_base::KnowledgeBaseProviderRegistry;
+    use crate::infrastructure::storage::InMemoryStorage;
+
+    fn create_service(
+        registry: Arc<KnowledgeBaseProviderRegistry>,
+    ) -> AssistantService<StorageAssistantRepository> {
+        AssistantService::new(
+            Arc::new(StorageAssistantRepository::new(Arc::new(
+                InMemoryStorage::<Assistant>::new(),
+            ))),
+            registry,
+        )
+    }
+
+    fn create_request() -> CreateAssistantRequest {
+        CreateAssistantRequest {
+            id: "support".to_string(),
+            name: "Support".to_string(),
+            description: None,
+            model_id: "gpt-4".to_string(),
+            prompt_id: "support-system".to_string(),
+            prompt_variables: HashMap::new(),
+            knowledge_bases: vec![KnowledgeBaseBinding::new("docs").with_top_k(1)],
+            tools: Vec::new(),
+            enabled: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_update_delete() {
+        let service = create_service(Arc::new(KnowledgeBaseProviderRegistry::new()));
+        service.create(create_request()).await.unwrap();
+
+        let duplicate = service.create(create_request()).await;
+        assert!(matches!(duplicate, Err(DomainError::Conflict { .. })));
+
+        let updated = service
+            .update(
+                "support",
+                UpdateAssistantRequest {
+                    model_id: Some("gpt-4o".to_string()),
+                    tools: Some(vec![AssistantTool::new("lookup-order")]),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.model_id(), "gpt-4o");
+        assert!(updated.allows_tool("lookup-order"));
+
+        let invalid = service
+            .update(
+                "support",
+                UpdateAssistantRequest {
+                    prompt_id: Some(String::new()),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(matches!(invalid, Err(DomainError::InvalidFields { .. })));
+        assert_eq!(
+            service.get_required("support").await.unwrap().prompt_id(),
+            "support-system"
+        );
+
+        assert!(service.delete("support").await.unwrap());
+        assert!(service.get("support").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_retrieve_skips_unavailable_knowledge_bases() {
+        let registry = Arc::new(KnowledgeBaseProviderRegistry::new());
+        registry
+            .register(Arc::new(
+                MockKnowledgeBaseProvider::new(KnowledgeBaseId::new("docs").unwrap())
+                    .with_search_results(vec![
+                        SearchResult::new("a", "Refunds take 5 days", 0.9),
+                        SearchResult::new("b", "Ships worldwide", 0.8),
+                    ]),
+            ))
+            .await;
+        let service = create_service(registry);
+
+        let mut request = create_request();
+        request
+            .knowledge_bases
+            .push(KnowledgeBaseBinding::new("missing"));
+        let assistant = service.create(request).await.unwrap();
+
+        let passages = service.retrieve(&assistant, "refund", None).await;
+
+        assert_eq!(passages, vec!["Refunds take 5 days".to_string()]);
+    }
+}
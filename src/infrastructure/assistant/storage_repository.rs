@@ -0,0 +1,43 @@
+//! Storage-backed assistant repository
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::DomainError;
+use crate::domain::assistant::{Assistant, AssistantId, AssistantRepository};
+use crate::domain::storage::Storage;
+
+/// Assistant repository backed by a generic storage
+#[derive(Debug)]
+pub struct StorageAssistantRepository {
+    storage: Arc<dyn Storage<Assistant>>,
+}
+
+impl StorageAssistantRepository {
+    /// Create a new storage-backed repository
+    pub fn new(storage: Arc<dyn Storage<Assistant>>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl AssistantRepository for StorageAssistantRepository {
+    async fn get(&self, id: &AssistantId) -> Result<Option<Assistant>, DomainError> {
+        self.storage.get(id).await
+    }
+
+    async fn list(&self) -> Result<Vec<Assistant>, DomainError> {
+        let mut assistants = self.storage.list().await?;
+        assistants.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(assistants)
+    }
+
+    async fn save(&self, assistant: Assistant) -> Result<Assistant, DomainError> {
+        self.storage.save(assistant).await
+    }
+
+    async fn delete(&self, id: &AssistantId) -> Result<bool, DomainError> {
+        self.storage.delete(id).await
+    }
+}
@@ -0,0 +1,9 @@
+//! Audit log infrastructure implementations
+
+mod repository;
+mod service;
+mod sink;
+
+pub use repository::StorageAuditLogRepository;
+pub use service::AuditLogService;
+pub use sink::{HttpAuditSink, LogAuditSink};
@@ -0,0 +1,109 @@
+//! Storage-backed audit log repository implementation
+
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::audit::{AuditLog, AuditLogId, AuditLogQuery, AuditLogRepository};
+use crate::domain::storage::Storage;
+use crate::domain::DomainError;
+
+/// Storage-backed implementation of AuditLogRepository
+#[derive(Debug)]
+pub struct StorageAuditLogRepository {
+    storage: Arc<dyn Storage<AuditLog>>,
+}
+
+impl StorageAuditLogRepository {
+    /// Create a new storage-backed repository
+    pub fn new(storage: Arc<dyn Storage<AuditLog>>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl AuditLogRepository for StorageAuditLogRepository {
+    async fn get(&self, id: &AuditLogId) -> Result<Option<AuditLog>, DomainError> {
+        self.storage.get(id).await
+    }
+
+    async fn list(&self, query: &AuditLogQuery) -> Result<Vec<AuditLog>, DomainError> {
+        let mut result: Vec<_> = self
+            .storage
+            .list()
+            .await?
+            .into_iter()
+            .filter(|log| query.matches(log))
+            .collect();
+
+        // Sort by created_at descending (newest first)
+        result.sort_by_key(|log| std::cmp::Reverse(log.created_at()));
+
+        let offset = query.offset.unwrap_or(0);
+        let limit = query.limit.unwrap_or(usize::MAX);
+
+        Ok(result.into_iter().skip(offset).take(limit).collect())
+    }
+
+    async fn count(&self, query: &AuditLogQuery) -> Result<usize, DomainError> {
+        let all_logs = self.storage.list().await?;
+        Ok(all_logs.iter().filter(|log| query.matches(log)).count())
+    }
+
+    async fn save(&self, log: &AuditLog) -> Result<(), DomainError> {
+        self.storage.create(log.clone()).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::audit::{AuditActor, AuditActorType};
+    use crate::infrastructure::storage::InMemoryStorage;
+
+    fn create_repo() -> StorageAuditLogRepository {
+        let storage = Arc::new(InMemoryStorage::<AuditLog>::new());
+        StorageAuditLogRepository::new(storage)
+    }
+
+    #[tokio::test]
+    async fn test_save_and_get() {
+        let repo = create_repo();
+        let log = AuditLog::new(AuditActor::user("user-1", "alice"), "create", "teams")
+            .with_entity_id("team-a");
+
+        repo.save(&log).await.unwrap();
+
+        let fetched = repo.get(log.id()).await.unwrap().unwrap();
+        assert_eq!(fetched.entity_id(), Some("team-a"));
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_and_paginates() {
+        let repo = create_repo();
+
+        for i in 0..3 {
+            let log = AuditLog::new(AuditActor::user("user-1", "alice"), "update", "models")
+                .with_entity_id(format!("model-{}", i));
+            repo.save(&log).await.unwrap();
+        }
+
+        let log = AuditLog::new(AuditActor::api_key("key-1", "CI"), "delete", "models");
+        repo.save(&log).await.unwrap();
+
+        let query = AuditLogQuery {
+            actor_type: Some(AuditActorType::User),
+            ..Default::default()
+        };
+        assert_eq!(repo.count(&query).await.unwrap(), 3);
+
+        let page = repo.list(&query.with_limit(2)).await.unwrap();
+        assert_eq!(page.len(), 2);
+
+        let deletes = repo
+            .list(&AuditLogQuery::new().with_action("delete"))
+            .await
+            .unwrap();
+        assert_eq!(deletes.len(), 1);
+    }
+}
@@ -0,0 +1,149 @@
+//! Audit log service - Records, exports and queries audit logs
+
+use std::sync::Arc;
+
+use tracing::warn;
+
+use crate::domain::audit::{AuditLog, AuditLogId, AuditLogQuery, AuditLogRepository, AuditSink};
+use crate::domain::event::GatewayEvent;
+use crate::domain::DomainError;
+use crate::infrastructure::background::spawn_tracked;
+use crate::infrastructure::event::EventBus;
+
+/// Audit log service. Logs are persisted first and then exported to the
+/// optional sink in the background, so a slow or failing sink never blocks
+/// or fails the audited request.
+#[derive(Debug)]
+pub struct AuditLogService<R: AuditLogRepository> {
+    repository: Arc<R>,
+    sink: Option<Arc<dyn AuditSink>>,
+    events: Option<Arc<EventBus>>,
+}
+
+impl<R: AuditLogRepository> AuditLogService<R> {
+    /// Create a new audit log service
+    pub fn new(repository: Arc<R>) -> Self {
+        Self {
+            repository,
+            sink: None,
+            events: None,
+        }
+    }
+
+    /// Export recorded logs to an external sink
+    pub fn with_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
+    /// Publish successful changes as `entity_changed` events
+    pub fn with_events(mut self, events: Arc<EventBus>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Persist an audit log, export it to the configured sink and publish
+    /// it when the change succeeded
+    pub async fn record(&self, log: AuditLog) -> Result<AuditLog, DomainError> {
+        self.repository.save(&log).await?;
+
+        if let Some(sink) = self.sink.clone() {
+            let exported = log.clone();
+
+            spawn_tracked(async move {
+                if let Err(e) = sink.export(&exported).await {
+                    warn!(audit_log_id = %exported.id(), error = %e, "Failed to export audit log");
+                }
+            });
+        }
+
+        if let Some(events) = &self.events
+            && log.is_success()
+        {
+            events.spawn_publish(GatewayEvent::entity_changed(&log));
+        }
+
+        Ok(log)
+    }
+
+    /// Get an audit log by ID
+    pub async fn get(&self, id: &str) -> Result<Option<AuditLog>, DomainError> {
+        let log_id = AuditLogId::new(id).map_err(|e| DomainError::invalid_id(e.to_string()))?;
+        self.repository.get(&log_id).await
+    }
+
+    /// List audit logs matching a query
+    pub async fn list(&self, query: &AuditLogQuery) -> Result<Vec<AuditLog>, DomainError> {
+        self.repository.list(query).await
+    }
+
+    /// Count audit logs matching a query
+    pub async fn count(&self, query: &AuditLogQuery) -> Result<usize, DomainError> {
+        self.repository.count(query).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use crate::domain::audit::AuditActor;
+    use crate::infrastructure::audit::StorageAuditLogRepository;
+    use crate::infrastructure::storage::InMemoryStorage;
+
+    #[derive(Debug, Default)]
+    struct RecordingSink {
+        exported: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl AuditSink for RecordingSink {
+        async fn export(&self, log: &AuditLog) -> Result<(), DomainError> {
+            self.exported.lock().unwrap().push(log.id().to_string());
+            Ok(())
+        }
+    }
+
+    fn create_service() -> AuditLogService<StorageAuditLogRepository> {
+        let storage = Arc::new(InMemoryStorage::<AuditLog>::new());
+        AuditLogService::new(Arc::new(StorageAuditLogRepository::new(storage)))
+    }
+
+    #[tokio::test]
+    async fn test_record_and_get() {
+        let service = create_service();
+        let log = AuditLog::new(AuditActor::user("user-1", "alice"), "delete", "roles");
+
+        let recorded = service.record(log).await.unwrap();
+
+        let fetched = service.get(recorded.id().as_str()).await.unwrap();
+        assert_eq!(fetched.unwrap().action(), "delete");
+        assert_eq!(service.count(&AuditLogQuery::new()).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_record_exports_to_sink() {
+        let sink = Arc::new(RecordingSink::default());
+        let service = create_service().with_sink(sink.clone());
+
+        let recorded = service
+            .record(AuditLog::new(AuditActor::system(), "update", "config"))
+            .await
+            .unwrap();
+
+        for _ in 0..50 {
+            if !sink.exported.lock().unwrap().is_empty() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+
+        assert_eq!(
+            sink.exported.lock().unwrap().as_slice(),
+            [recorded.id().to_string()]
+        );
+    }
+}
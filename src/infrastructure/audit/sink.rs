@@ -0,0 +1,81 @@
+//! Audit log export sinks
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use tracing::info;
+
+use crate::domain::audit::{AuditLog, AuditSink};
+use crate::domain::DomainError;
+
+/// Sink emitting audit logs as structured events on the `audit` tracing target,
+/// for collection by an external log pipeline
+#[derive(Debug, Default)]
+pub struct LogAuditSink;
+
+impl LogAuditSink {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl AuditSink for LogAuditSink {
+    async fn export(&self, log: &AuditLog) -> Result<(), DomainError> {
+        let payload = serde_json::to_string(log)
+            .map_err(|e| DomainError::internal(format!("Failed to serialize audit log: {}", e)))?;
+
+        info!(target: "audit", audit_log = %payload, "Audit log recorded");
+        Ok(())
+    }
+}
+
+/// Sink posting each audit log as JSON to an HTTP endpoint
+#[derive(Debug)]
+pub struct HttpAuditSink {
+    url: String,
+    token: Option<String>,
+    http_client: Client,
+}
+
+impl HttpAuditSink {
+    /// Create a sink posting to `url`, optionally authenticated with a bearer token
+    pub fn new(url: impl Into<String>, token: Option<String>, timeout: Duration) -> Self {
+        let http_client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            url: url.into(),
+            token,
+            http_client,
+        }
+    }
+}
+
+#[async_trait]
+impl AuditSink for HttpAuditSink {
+    async fn export(&self, log: &AuditLog) -> Result<(), DomainError> {
+        let mut request = self.http_client.post(&self.url).json(log);
+
+        if let Some(ref token) = self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| DomainError::internal(format!("Audit export failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(DomainError::internal(format!(
+                "Audit export failed with HTTP status {}",
+                response.status().as_u16()
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -8,21 +8,32 @@ use rsa::{BigUint, RsaPrivateKey};
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
+use crate::domain::role::Permission;
+use crate::domain::service_account::ServiceAccount;
 use crate::domain::user::User;
 use crate::domain::DomainError;
 use rsa::pkcs1::EncodeRsaPublicKey;
 
+/// Lifetime of tokens issued to service accounts, in seconds
+pub const SERVICE_ACCOUNT_TOKEN_EXPIRATION_SECS: u64 = 3600;
+
 /// JWT claims structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JwtClaims {
-    /// Subject (user ID)
+    /// Subject (user ID or service account ID)
     pub sub: String,
-    /// Username
+    /// Username (service account name for service account tokens)
     pub username: String,
     /// Issued at timestamp (Unix epoch)
     pub iat: i64,
     /// Expiration timestamp (Unix epoch)
     pub exp: i64,
+    /// OAuth client ID, only set on service account tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    /// Space-separated permissions granted to a service account token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
 }
 
 impl JwtClaims {
@@ -36,9 +47,46 @@ impl JwtClaims {
             username: user.username().to_string(),
             iat: now.timestamp(),
             exp: exp.timestamp(),
+            client_id: None,
+            scope: None,
         }
     }
 
+    /// Create new claims for a service account, restricted to the given scopes
+    pub fn for_service_account(account: &ServiceAccount, scopes: &[Permission]) -> Self {
+        let now = Utc::now();
+        let exp = now + Duration::seconds(SERVICE_ACCOUNT_TOKEN_EXPIRATION_SECS as i64);
+        let scope = scopes
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Self {
+            sub: account.id().as_str().to_string(),
+            username: account.name().to_string(),
+            iat: now.timestamp(),
+            exp: exp.timestamp(),
+            client_id: Some(account.id().as_str().to_string()),
+            scope: Some(scope),
+        }
+    }
+
+    /// Check if the token was issued to a service account
+    pub fn is_service_account(&self) -> bool {
+        self.client_id.is_some()
+    }
+
+    /// Permissions granted by the token scope. Unknown scopes are ignored.
+    pub fn scopes(&self) -> Vec<Permission> {
+        self.scope
+            .as_deref()
+            .unwrap_or_default()
+            .split_whitespace()
+            .filter_map(|s| Permission::parse(s).ok())
+            .collect()
+    }
+
     /// Check if the token has expired
     pub fn is_expired(&self) -> bool {
         Utc::now().timestamp() >= self.exp
@@ -83,6 +131,13 @@ pub trait JwtGenerator: Send + Sync + Debug {
     /// Generate a JWT token for a user
     fn generate(&self, user: &User) -> Result<String, DomainError>;
 
+    /// Generate a JWT token for a service account, restricted to the given scopes
+    fn generate_for_service_account(
+        &self,
+        account: &ServiceAccount,
+        scopes: &[Permission],
+    ) -> Result<String, DomainError>;
+
     /// Validate a JWT token and return the claims
     fn validate(&self, token: &str) -> Result<JwtClaims, DomainError>;
 
@@ -127,12 +182,24 @@ impl JwtService {
     }
 }
 
+impl JwtService {
+    fn encode_claims(&self, claims: &JwtClaims) -> Result<String, DomainError> {
+        encode(&Header::default(), claims, &self.encoding_key)
+            .map_err(|e| DomainError::validation(format!("Failed to generate JWT: {}", e)))
+    }
+}
+
 impl JwtGenerator for JwtService {
     fn generate(&self, user: &User) -> Result<String, DomainError> {
-        let claims = JwtClaims::new(user, self.config.expiration_hours);
+        self.encode_claims(&JwtClaims::new(user, self.config.expiration_hours))
+    }
 
-        encode(&Header::default(), &claims, &self.encoding_key)
-            .map_err(|e| DomainError::validation(format!("Failed to generate JWT: {}", e)))
+    fn generate_for_service_account(
+        &self,
+        account: &ServiceAccount,
+        scopes: &[Permission],
+    ) -> Result<String, DomainError> {
+        self.encode_claims(&JwtClaims::for_service_account(account, scopes))
     }
 
     fn validate(&self, token: &str) -> Result<JwtClaims, DomainError> {
@@ -402,16 +469,28 @@ fn decode_base64url(s: &str, field: &str) -> Result<Vec<u8>, DomainError> {
         .map_err(|e| DomainError::validation(format!("Invalid base64url in '{}': {}", field, e)))
 }
 
-impl JwtGenerator for JwksJwtService {
-    fn generate(&self, user: &User) -> Result<String, DomainError> {
-        let claims = JwtClaims::new(user, self.expiration_hours);
-
+impl JwksJwtService {
+    fn encode_claims(&self, claims: &JwtClaims) -> Result<String, DomainError> {
         let mut header = Header::new(self.algorithm);
         header.kid = Some(self.key_id.clone());
 
-        encode(&header, &claims, &self.encoding_key)
+        encode(&header, claims, &self.encoding_key)
             .map_err(|e| DomainError::validation(format!("Failed to generate JWT: {}", e)))
     }
+}
+
+impl JwtGenerator for JwksJwtService {
+    fn generate(&self, user: &User) -> Result<String, DomainError> {
+        self.encode_claims(&JwtClaims::new(user, self.expiration_hours))
+    }
+
+    fn generate_for_service_account(
+        &self,
+        account: &ServiceAccount,
+        scopes: &[Permission],
+    ) -> Result<String, DomainError> {
+        self.encode_claims(&JwtClaims::for_service_account(account, scopes))
+    }
 
     fn validate(&self, token: &str) -> Result<JwtClaims, DomainError> {
         let mut validation = Validation::new(self.algorithm);
@@ -497,6 +576,8 @@ mod tests {
             username: user.username().to_string(),
             iat: (past_time - chrono::Duration::hours(2)).timestamp(),
             exp: past_time.timestamp(), // Already expired
+            client_id: None,
+            scope: None,
         };
 
         // Generate token with expired claims
@@ -521,6 +602,42 @@ mod tests {
         assert_eq!(claims.user_id(), "test-user");
     }
 
+    #[test]
+    fn test_service_account_token() {
+        use crate::domain::role::PermissionResource;
+        use crate::domain::service_account::ServiceAccountId;
+
+        let service = create_service();
+        let account = ServiceAccount::new(
+            ServiceAccountId::new("ci-pipeline").unwrap(),
+            "CI pipeline",
+            "sha256$hash",
+            vec![Permission::write(PermissionResource::Prompts)],
+            admin_team(),
+        )
+        .unwrap();
+        let scopes = vec![
+            Permission::write(PermissionResource::Prompts),
+            Permission::read(PermissionResource::Models),
+        ];
+
+        let token = service
+            .generate_for_service_account(&account, &scopes)
+            .unwrap();
+        let claims = service.validate(&token).unwrap();
+
+        assert!(claims.is_service_account());
+        assert_eq!(claims.sub, "ci-pipeline");
+        assert_eq!(claims.scope.as_deref(), Some("prompts:write models:read"));
+        assert_eq!(claims.scopes(), scopes);
+        assert!(claims.exp - claims.iat <= SERVICE_ACCOUNT_TOKEN_EXPIRATION_SECS as i64);
+
+        // User tokens carry no client ID or scope
+        let claims = service.validate(&service.generate(&create_test_user()).unwrap()).unwrap();
+        assert!(!claims.is_service_account());
+        assert!(claims.scopes().is_empty());
+    }
+
     #[test]
     fn test_expiration_hours() {
         let service = JwtService::new(JwtConfig::new("secret", 48));
@@ -1,7 +1,11 @@
 //! Authentication infrastructure module
 //!
-//! This module provides JWT token management for user authentication.
+//! This module provides JWT token management for user and service account
+//! authentication.
 
 mod jwt;
 
-pub use jwt::{JwtClaims, JwtConfig, JwtGenerator, JwksJwtService, JwtService};
+pub use jwt::{
+    JwtClaims, JwtConfig, JwtGenerator, JwksJwtService, JwtService,
+    SERVICE_ACCOUNT_TOKEN_EXPIRATION_SECS,
+};
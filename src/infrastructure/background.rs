@@ -0,0 +1,102 @@
+//! Background work finished before the process exits
+//!
+//! Writes spawned while serving requests (async operations, ingestions,
+//! assistant runs, execution log payloads, events, notifications, audit
+//! exports) are tracked so shutdown can wait for them once the server
+//! stopped accepting connections.
+
+use std::future::Future;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use tokio::task::JoinHandle;
+use tokio_util::task::TaskTracker;
+
+static BACKGROUND_TASKS: Lazy<TaskTracker> = Lazy::new(TaskTracker::new);
+
+/// Spawn a task that shutdown waits for
+pub fn spawn_tracked<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    BACKGROUND_TASKS.spawn(future)
+}
+
+/// Number of tracked tasks still running
+pub fn pending_background_tasks() -> usize {
+    BACKGROUND_TASKS.len()
+}
+
+/// Wait up to `timeout` for the tracked tasks; false when some are still
+/// running. Tasks spawned meanwhile are waited for too.
+pub async fn wait_for_background_tasks(timeout: Duration) -> bool {
+    BACKGROUND_TASKS.close();
+
+    tokio::time::timeout(timeout, BACKGROUND_TASKS.wait())
+        .await
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_wait_for_background_tasks() {
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        spawn_tracked(async move {
+            let _ = rx.await;
+        });
+
+        assert!(pending_background_tasks() >= 1);
+        assert!(!wait_for_background_tasks(Duration::from_millis(10)).await);
+
+        tx.send(()).unwrap();
+        assert!(wait_for_background_tasks(Duration::from_secs(5)).await);
+    }
+
+    /// Handlers spawning with `tokio::spawn`, which shutdown does not wait
+    /// for, and how many times
+    const UNTRACKED_SPAWNS: &[(&str, usize)] = &[
+        // Operation heartbeat and reaper loop, running until the process exits
+        ("src/api/v1/operations.rs", 1),
+        // SSE chat stream, ending with its connection
+        ("src/api/v1/chat.rs", 1),
+    ];
+
+    fn rust_files(dir: &std::path::Path, files: &mut Vec<std::path::PathBuf>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path.is_dir() {
+                rust_files(&path, files);
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                files.push(path);
+            }
+        }
+    }
+
+    #[test]
+    fn test_request_path_spawns_are_tracked() {
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+        let mut files = Vec::new();
+        rust_files(&root.join("src/api"), &mut files);
+
+        for file in files {
+            let source = std::fs::read_to_string(&file).unwrap();
+            let spawns = source.matches("tokio::spawn(").count();
+            let name = file.strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/");
+            let allowed = UNTRACKED_SPAWNS
+                .iter()
+                .find(|(path, _)| *path == name)
+                .map_or(0, |(_, count)| *count);
+
+            assert_eq!(
+                spawns, allowed,
+                "{} spawns request work with tokio::spawn, use spawn_tracked",
+                name
+            );
+        }
+    }
+}
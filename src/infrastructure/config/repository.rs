@@ -219,6 +219,18 @@ fn create_default_entries() -> Vec<ConfigEntry> {
             ConfigCategory::Persistence,
             "Whether to log full input/output (may contain sensitive data)",
         ),
+        create_entry(
+            "persistence.payload_capture_percent",
+            ConfigValue::Float(0.0),
+            ConfigCategory::Persistence,
+            "Percentage (0-100) of requests of opted-in teams whose redacted request/response payloads are captured",
+        ),
+        create_entry(
+            "persistence.payload_capture_teams",
+            ConfigValue::StringList(vec![]),
+            ConfigCategory::Persistence,
+            "Team IDs opted in to payload capture",
+        ),
         // Logging settings
         create_entry(
             "logging.level",
@@ -430,51 +442,7 @@ fn filter_logs<'a>(
     logs: impl Iterator<Item = &'a ExecutionLog>,
     query: &ExecutionLogQuery,
 ) -> impl Iterator<Item = &'a ExecutionLog> {
-    logs.filter(move |log| {
-        if let Some(exec_type) = &query.execution_type {
-            if log.execution_type() != *exec_type {
-                return false;
-            }
-        }
-
-        if let Some(resource_id) = &query.resource_id {
-            if log.resource_id() != resource_id {
-                return false;
-            }
-        }
-
-        if let Some(status) = &query.status {
-            if log.status() != *status {
-                return false;
-            }
-        }
-
-        if let Some(api_key_id) = &query.api_key_id {
-            if log.executor().api_key_id.as_deref() != Some(api_key_id.as_str()) {
-                return false;
-            }
-        }
-
-        if let Some(user_id) = &query.user_id {
-            if log.executor().user_id.as_deref() != Some(user_id.as_str()) {
-                return false;
-            }
-        }
-
-        if let Some(from_date) = &query.from_date {
-            if log.created_at() < *from_date {
-                return false;
-            }
-        }
-
-        if let Some(to_date) = &query.to_date {
-            if log.created_at() > *to_date {
-                return false;
-            }
-        }
-
-        true
-    })
+    logs.filter(move |log| query.matches(log))
 }
 
 #[cfg(test)]
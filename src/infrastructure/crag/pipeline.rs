@@ -4,13 +4,14 @@
 
 use std::sync::Arc;
 
-use tracing::{debug, info};
+use tracing::{debug, info, Instrument};
 
 use crate::domain::crag::{
     CragConfig, CragFilter, CragResult, DocumentScorer, ScoringInput, ScoringStrategy,
 };
 use crate::domain::knowledge_base::{KnowledgeBaseProvider, SearchParams};
 use crate::domain::DomainError;
+use crate::infrastructure::observability::{crag_scoring_span, kb_search_span, record_result};
 
 use super::{LlmDocumentScorer, ThresholdDocumentScorer};
 use crate::domain::llm::LlmProvider;
@@ -58,7 +59,18 @@ where
 
         // Step 1: Search the knowledge base
         let query_text = params.query.clone();
-        let search_results = self.knowledge_base.search(params).await?;
+        let search_span = kb_search_span(
+            self.knowledge_base.knowledge_base_id().as_str(),
+            params.top_k,
+        );
+        let search_results = record_result(
+            &search_span,
+            self.knowledge_base
+                .search(params)
+                .instrument(search_span.clone())
+                .await,
+        )?;
+        search_span.record("kb.results", search_results.len());
 
         debug!(
             "Retrieved {} documents from knowledge base",
@@ -70,10 +82,38 @@ where
         }
 
         // Step 2: Score documents based on strategy
+        let strategy = match self.config.strategy {
+            ScoringStrategy::LlmBased => "llm_based",
+            ScoringStrategy::ThresholdBased => "threshold_based",
+            ScoringStrategy::Hybrid => "hybrid",
+        };
+        let scoring_span = crag_scoring_span(strategy, search_results.len());
+        let scored_documents = record_result(&scoring_span, self.score(&query_text, search_results).instrument(scoring_span.clone()).await)?;
+
+        // Step 3: Filter and categorize results
+        let filter = CragFilter::new(self.config.clone());
+        let result = filter.filter(scored_documents);
+
+        let summary = result.summary();
+        scoring_span.record("crag.relevant", summary.correct + summary.ambiguous);
+        info!(
+            "CRAG complete: total={}, correct={}, ambiguous={}, incorrect={}",
+            summary.total, summary.correct, summary.ambiguous, summary.incorrect
+        );
+
+        Ok(result)
+    }
+
+    /// Score documents with the configured strategy
+    async fn score(
+        &self,
+        query_text: &str,
+        search_results: Vec<crate::domain::knowledge_base::SearchResult>,
+    ) -> Result<Vec<crate::domain::crag::ScoredDocument>, DomainError> {
         let scored_documents = match self.config.strategy {
             ScoringStrategy::ThresholdBased => {
                 let scorer = ThresholdDocumentScorer::new(self.config.clone());
-                let input = ScoringInput::new(&query_text, search_results);
+                let input = ScoringInput::new(query_text, search_results);
                 scorer.score_documents(input).await?
             }
             ScoringStrategy::LlmBased => {
@@ -88,26 +128,16 @@ where
                     .clone()
                     .unwrap_or_else(|| "gpt-4".to_string());
                 let scorer = LlmDocumentScorer::new(llm.clone(), model, self.config.clone());
-                let input = ScoringInput::new(&query_text, search_results);
+                let input = ScoringInput::new(query_text, search_results);
                 scorer.score_documents(input).await?
             }
             ScoringStrategy::Hybrid => {
                 // First filter by threshold, then use LLM for borderline cases
-                self.hybrid_scoring(&query_text, search_results).await?
+                self.hybrid_scoring(query_text, search_results).await?
             }
         };
 
-        // Step 3: Filter and categorize results
-        let filter = CragFilter::new(self.config.clone());
-        let result = filter.filter(scored_documents);
-
-        let summary = result.summary();
-        info!(
-            "CRAG complete: total={}, correct={}, ambiguous={}, incorrect={}",
-            summary.total, summary.correct, summary.ambiguous, summary.incorrect
-        );
-
-        Ok(result)
+        Ok(scored_documents)
     }
 
     /// Hybrid scoring: threshold first, then LLM for ambiguous
@@ -0,0 +1,91 @@
+//! Invalidation of clients built from stored credentials
+//!
+//! Components caching provider clients subscribe to the bus. The credential
+//! service publishes every credential it updates or deletes, so the next
+//! request builds its client with the current secret instead of waiting for
+//! a restart. The bus is per process: other replicas pick up the change once
+//! their own caches are rebuilt.
+
+use std::fmt::Debug;
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use tracing::debug;
+
+/// Holder of clients built from stored credentials
+#[async_trait]
+pub trait CredentialInvalidationListener: Send + Sync + Debug {
+    /// Drop everything built from the credential
+    async fn credential_changed(&self, credential_id: &str);
+}
+
+/// Fans credential changes out to the subscribed listeners
+#[derive(Debug, Clone, Default)]
+pub struct CredentialInvalidationBus {
+    listeners: Arc<RwLock<Vec<Arc<dyn CredentialInvalidationListener>>>>,
+}
+
+impl CredentialInvalidationBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe(&self, listener: Arc<dyn CredentialInvalidationListener>) {
+        self.listeners
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(listener);
+    }
+
+    /// Notify every listener, returning once they all dropped their clients
+    pub async fn publish(&self, credential_id: &str) {
+        let listeners = self
+            .listeners
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+
+        debug!(
+            credential_id = %credential_id,
+            listeners = listeners.len(),
+            "Invalidating clients built from credential"
+        );
+
+        for listener in listeners {
+            listener.credential_changed(credential_id).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct RecordingListener {
+        changed: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl CredentialInvalidationListener for RecordingListener {
+        async fn credential_changed(&self, credential_id: &str) {
+            self.changed.lock().unwrap().push(credential_id.to_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_reaches_every_listener() {
+        let bus = CredentialInvalidationBus::new();
+        let first = Arc::new(RecordingListener::default());
+        let second = Arc::new(RecordingListener::default());
+
+        bus.publish("before-subscribing").await;
+        bus.subscribe(first.clone());
+        bus.clone().subscribe(second.clone());
+        bus.publish("openai-prod").await;
+
+        assert_eq!(*first.changed.lock().unwrap(), vec!["openai-prod"]);
+        assert_eq!(*second.changed.lock().unwrap(), vec!["openai-prod"]);
+    }
+}
@@ -4,6 +4,7 @@ mod aws_secrets_provider;
 mod cached_provider;
 mod env_provider;
 mod factory;
+mod invalidation;
 mod repository;
 mod service;
 mod storage_repository;
@@ -13,6 +14,7 @@ pub use aws_secrets_provider::AwsSecretsCredentialProvider;
 pub use cached_provider::CachedCredentialProvider;
 pub use env_provider::EnvCredentialProvider;
 pub use factory::{CredentialProviderFactory, ProviderConfig};
+pub use invalidation::{CredentialInvalidationBus, CredentialInvalidationListener};
 pub use repository::InMemoryStoredCredentialRepository;
 pub use service::{
     CreateCredentialRequest, CredentialService, CredentialServiceTrait, UpdateCredentialRequest,
@@ -5,8 +5,12 @@ use std::sync::Arc;
 use crate::domain::credentials::{
     CredentialId, CredentialType, StoredCredential, StoredCredentialRepository,
 };
+use crate::domain::organization::OrganizationId;
+use crate::domain::residency::Region;
 use crate::domain::DomainError;
 
+use super::CredentialInvalidationBus;
+
 /// Request to create a new credential
 #[derive(Debug, Clone)]
 pub struct CreateCredentialRequest {
@@ -20,6 +24,10 @@ pub struct CreateCredentialRequest {
     pub deployment: Option<String>,
     /// Header value template for HTTP API Key credentials (e.g., "Bearer ${api-key}")
     pub header_value: Option<String>,
+    /// Organization sharing the credential with its teams
+    pub organization_id: Option<OrganizationId>,
+    /// Region the provider endpoint serves from
+    pub region: Option<Region>,
 }
 
 /// Request to update a credential
@@ -30,6 +38,8 @@ pub struct UpdateCredentialRequest {
     pub endpoint: Option<Option<String>>,
     pub deployment: Option<Option<String>>,
     pub header_value: Option<Option<String>>,
+    pub organization_id: Option<Option<OrganizationId>>,
+    pub region: Option<Option<Region>>,
     pub enabled: Option<bool>,
 }
 
@@ -46,12 +56,22 @@ pub trait CredentialServiceTrait: Send + Sync + std::fmt::Debug {
 #[derive(Debug)]
 pub struct CredentialService<R: StoredCredentialRepository> {
     repository: Arc<R>,
+    invalidation: CredentialInvalidationBus,
 }
 
 impl<R: StoredCredentialRepository> CredentialService<R> {
     /// Create a new credential service
     pub fn new(repository: Arc<R>) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            invalidation: CredentialInvalidationBus::new(),
+        }
+    }
+
+    /// Publish updated and deleted credentials on `bus`
+    pub fn with_invalidation_bus(mut self, bus: CredentialInvalidationBus) -> Self {
+        self.invalidation = bus;
+        self
     }
 
     /// Create a new credential
@@ -80,6 +100,14 @@ impl<R: StoredCredentialRepository> CredentialService<R> {
             credential = credential.with_header_value(header_value);
         }
 
+        if let Some(organization_id) = request.organization_id {
+            credential = credential.with_organization_id(organization_id);
+        }
+
+        if let Some(region) = request.region {
+            credential = credential.with_region(region);
+        }
+
         self.repository.create(credential).await
     }
 
@@ -125,13 +153,27 @@ impl<R: StoredCredentialRepository> CredentialService<R> {
             request.enabled,
         );
 
-        self.repository.update(credential).await
+        if let Some(organization_id) = request.organization_id {
+            credential.set_organization_id(organization_id);
+        }
+
+        if let Some(region) = request.region {
+            credential.set_region(region);
+        }
+
+        let credential = self.repository.update(credential).await?;
+        self.invalidation.publish(id).await;
+
+        Ok(credential)
     }
 
     /// Delete a credential
     pub async fn delete(&self, id: &str) -> Result<(), DomainError> {
         let credential_id = CredentialId::new(id)?;
-        self.repository.delete(&credential_id).await
+        self.repository.delete(&credential_id).await?;
+        self.invalidation.publish(id).await;
+
+        Ok(())
     }
 
     /// Check if a credential exists
@@ -208,6 +250,8 @@ mod tests {
             endpoint: None,
             deployment: None,
             header_value: None,
+            organization_id: None,
+            region: None,
         };
 
         let created = service.create(request).await.unwrap();
@@ -227,6 +271,8 @@ mod tests {
             endpoint: None,
             deployment: None,
             header_value: None,
+            organization_id: None,
+            region: None,
         };
         service.create(request).await.unwrap();
 
@@ -248,6 +294,8 @@ mod tests {
             endpoint: None,
             deployment: None,
             header_value: None,
+            organization_id: None,
+            region: None,
         };
         service.create(request).await.unwrap();
 
@@ -0,0 +1,7 @@
+//! Dataset infrastructure implementations
+
+mod service;
+mod storage_repository;
+
+pub use service::{CreateDatasetRequest, DatasetService, UpdateDatasetRequest};
+pub use storage_repository::StorageDatasetRepository;
@@ -0,0 +1,293 @@
+//! Dataset management and version pinning
+
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::domain::dataset::{
+    Dataset, DatasetId, DatasetRef, DatasetRepository, DatasetRow, DatasetVersion,
+};
+use crate::domain::DomainError;
+
+/// Request to create a dataset with its first version
+#[derive(Debug, Clone)]
+pub struct CreateDatasetRequest {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub rows: Vec<DatasetRow>,
+    pub note: Option<String>,
+}
+
+/// Request to update the metadata of a dataset. Rows only change through
+/// new versions.
+#[derive(Debug, Clone, Default)]
+pub struct UpdateDatasetRequest {
+    pub name: Option<String>,
+    pub description: Option<Option<String>>,
+}
+
+/// Manages datasets and their immutable versions
+pub struct DatasetService<R: DatasetRepository> {
+    repository: Arc<R>,
+}
+
+impl<R: DatasetRepository> DatasetService<R> {
+    /// Create a new dataset service
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+
+    /// Every dataset, ordered by name
+    pub async fn list(&self) -> Result<Vec<Dataset>, DomainError> {
+        self.repository.list().await
+    }
+
+    /// Get a dataset by ID
+    pub async fn get(&self, id: &str) -> Result<Option<Dataset>, DomainError> {
+        self.repository.get(&parse_id(id)?).await
+    }
+
+    /// Get a dataset by ID, returning an error if not found
+    pub async fn get_required(&self, id: &str) -> Result<Dataset, DomainError> {
+        self.get(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("Dataset '{}' not found", id)))
+    }
+
+    /// Create a dataset and store its rows as version 1
+    pub async fn create(&self, request: CreateDatasetRequest) -> Result<Dataset, DomainError> {
+        let dataset_id = parse_id(&request.id)?;
+
+        if request.name.trim().is_empty() {
+            return Err(DomainError::validation("Dataset name is required"));
+        }
+
+        if self.repository.get(&dataset_id).await?.is_some() {
+            return Err(DomainError::conflict(format!(
+                "Dataset '{}' already exists",
+                request.id
+            )));
+        }
+
+        DatasetVersion::validate_rows(&request.rows)?;
+
+        let mut dataset = Dataset::new(dataset_id.clone(), request.name, request.rows.len());
+        if let Some(description) = request.description {
+            dataset = dataset.with_description(description);
+        }
+
+        self.save_version(dataset_id, 1, request.rows, request.note)
+            .await?;
+        let dataset = self.repository.save(dataset).await?;
+
+        info!(dataset_id = %dataset.id(), rows = dataset.row_count(), "Created dataset");
+        Ok(dataset)
+    }
+
+    /// Update the name or description of a dataset
+    pub async fn update(
+        &self,
+        id: &str,
+        request: UpdateDatasetRequest,
+    ) -> Result<Dataset, DomainError> {
+        let mut dataset = self.get_required(id).await?;
+
+        if let Some(name) = request.name {
+            if name.trim().is_empty() {
+                return Err(DomainError::validation("Dataset name is required"));
+            }
+            dataset.set_name(name);
+        }
+
+        if let Some(description) = request.description {
+            dataset.set_description(description);
+        }
+
+        self.repository.save(dataset).await
+    }
+
+    /// Delete a dataset and its versions
+    pub async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        self.repository.delete(&parse_id(id)?).await
+    }
+
+    /// Store rows as the next version of a dataset. Earlier versions are
+    /// kept untouched so pinned references stay reproducible.
+    pub async fn add_version(
+        &self,
+        id: &str,
+        rows: Vec<DatasetRow>,
+        note: Option<String>,
+    ) -> Result<DatasetVersion, DomainError> {
+        DatasetVersion::validate_rows(&rows)?;
+
+        let mut dataset = self.get_required(id).await?;
+        let version = dataset.bump_version(rows.len());
+        let saved = self
+            .save_version(dataset.id().clone(), version, rows, note)
+            .await?;
+        self.repository.save(dataset).await?;
+
+        info!(dataset_id = %id, version = version, "Created dataset version");
+        Ok(saved)
+    }
+
+    /// Get a version of a dataset
+    pub async fn get_version(
+        &self,
+        id: &str,
+        version: u32,
+    ) -> Result<Option<DatasetVersion>, DomainError> {
+        self.repository.get_version(&parse_id(id)?, version).await
+    }
+
+    /// List the versions of a dataset, newest first
+    pub async fn list_versions(&self, id: &str) -> Result<Vec<DatasetVersion>, DomainError> {
+        let dataset = self.get_required(id).await?;
+        self.repository.list_versions(dataset.id()).await
+    }
+
+    /// Pin a dataset reference, defaulting to the latest version
+    pub async fn resolve(
+        &self,
+        id: &str,
+        version: Option<u32>,
+    ) -> Result<DatasetRef, DomainError> {
+        let dataset = self
+            .get(id)
+            .await?
+            .ok_or_else(|| DomainError::validation(format!("Dataset '{}' not found", id)))?;
+
+        let version = match version {
+            Some(version) => {
+                if self.repository.get_version(dataset.id(), version).await?.is_none() {
+                    return Err(DomainError::validation(format!(
+                        "Dataset '{}' has no version {}",
+                        id, version
+                    )));
+                }
+                version
+            }
+            None => dataset.latest_version(),
+        };
+
+        Ok(DatasetRef::new(id, version))
+    }
+
+    /// Rows of a pinned dataset version
+    pub async fn rows(&self, reference: &DatasetRef) -> Result<Vec<DatasetRow>, DomainError> {
+        self.get_version(&reference.dataset_id, reference.version)
+            .await?
+            .map(|version| version.rows)
+            .ok_or_else(|| {
+                DomainError::not_found(format!("Dataset version '{}' not found", reference))
+            })
+    }
+
+    async fn save_version(
+        &self,
+        dataset_id: DatasetId,
+        version: u32,
+        rows: Vec<DatasetRow>,
+        note: Option<String>,
+    ) -> Result<DatasetVersion, DomainError> {
+        let mut dataset_version = DatasetVersion::new(dataset_id, version, rows);
+        if let Some(note) = note {
+            dataset_version = dataset_version.with_note(note);
+        }
+
+        self.repository.save_version(dataset_version).await
+    }
+}
+
+fn parse_id(id: &str) -> Result<DatasetId, DomainError> {
+    DatasetId::new(id).map_err(|e| DomainError::validation(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::dataset::StorageDatasetRepository;
+    use crate::infrastructure::storage::InMemoryStorage;
+
+    fn create_service() -> DatasetService<StorageDatasetRepository> {
+        DatasetService::new(Arc::new(StorageDatasetRepository::new(
+            Arc::new(InMemoryStorage::<Dataset>::new()),
+            Arc::new(InMemoryStorage::<DatasetVersion>::new()),
+        )))
+    }
+
+    fn row(question: &str) -> DatasetRow {
+        DatasetRow {
+            input: [("question".to_string(), question.to_string())].into(),
+            expected_output: None,
+        }
+    }
+
+    fn create_request(rows: Vec<DatasetRow>) -> CreateDatasetRequest {
+        CreateDatasetRequest {
+            id: "faq".to_string(),
+            name: "FAQ".to_string(),
+            description: None,
+            rows,
+            note: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_versions_are_immutable() {
+        let service = create_service();
+        service.create(create_request(vec![row("a")])).await.unwrap();
+
+        let v2 = service
+            .add_version("faq", vec![row("b"), row("c")], Some("more".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(v2.version, 2);
+
+        let dataset = service.get_required("faq").await.unwrap();
+        assert_eq!(dataset.latest_version(), 2);
+        assert_eq!(dataset.row_count(), 2);
+
+        let v1 = service.rows(&DatasetRef::new("faq", 1)).await.unwrap();
+        assert_eq!(v1, vec![row("a")]);
+
+        let versions = service.list_versions("faq").await.unwrap();
+        assert_eq!(
+            versions.iter().map(|v| v.version).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+
+        assert!(service.delete("faq").await.unwrap());
+        assert!(service.get_version("faq", 1).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resolve() {
+        let service = create_service();
+        service.create(create_request(vec![row("a")])).await.unwrap();
+        service.add_version("faq", vec![row("b")], None).await.unwrap();
+
+        assert_eq!(
+            service.resolve("faq", None).await.unwrap(),
+            DatasetRef::new("faq", 2)
+        );
+        assert_eq!(
+            service.resolve("faq", Some(1)).await.unwrap(),
+            DatasetRef::new("faq", 1)
+        );
+        assert!(service.resolve("faq", Some(3)).await.is_err());
+        assert!(service.resolve("missing", None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_requires_rows() {
+        let service = create_service();
+        assert!(service.create(create_request(Vec::new())).await.is_err());
+
+        service.create(create_request(vec![row("a")])).await.unwrap();
+        let duplicate = service.create(create_request(vec![row("a")])).await;
+        assert!(matches!(duplicate, Err(DomainError::Conflict { .. })));
+    }
+}
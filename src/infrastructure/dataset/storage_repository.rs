@@ -0,0 +1,83 @@
+//! Storage-backed dataset repository
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::dataset::{
+    Dataset, DatasetId, DatasetRepository, DatasetVersion, DatasetVersionId,
+};
+use crate::domain::storage::Storage;
+use crate::domain::DomainError;
+
+/// Dataset repository backed by generic storages for datasets and versions
+#[derive(Debug)]
+pub struct StorageDatasetRepository {
+    storage: Arc<dyn Storage<Dataset>>,
+    version_storage: Arc<dyn Storage<DatasetVersion>>,
+}
+
+impl StorageDatasetRepository {
+    /// Create a new storage-backed repository
+    pub fn new(
+        storage: Arc<dyn Storage<Dataset>>,
+        version_storage: Arc<dyn Storage<DatasetVersion>>,
+    ) -> Self {
+        Self {
+            storage,
+            version_storage,
+        }
+    }
+}
+
+#[async_trait]
+impl DatasetRepository for StorageDatasetRepository {
+    async fn get(&self, id: &DatasetId) -> Result<Option<Dataset>, DomainError> {
+        self.storage.get(id).await
+    }
+
+    async fn list(&self) -> Result<Vec<Dataset>, DomainError> {
+        let mut datasets = self.storage.list().await?;
+        datasets.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(datasets)
+    }
+
+    async fn save(&self, dataset: Dataset) -> Result<Dataset, DomainError> {
+        self.storage.save(dataset).await
+    }
+
+    async fn delete(&self, id: &DatasetId) -> Result<bool, DomainError> {
+        for version in self.list_versions(id).await? {
+            self.version_storage.delete(&version.id).await?;
+        }
+
+        self.storage.delete(id).await
+    }
+
+    async fn save_version(&self, version: DatasetVersion) -> Result<DatasetVersion, DomainError> {
+        self.version_storage.create(version).await
+    }
+
+    async fn get_version(
+        &self,
+        id: &DatasetId,
+        version: u32,
+    ) -> Result<Option<DatasetVersion>, DomainError> {
+        self.version_storage
+            .get(&DatasetVersionId::for_version(id.as_str(), version))
+            .await
+    }
+
+    async fn list_versions(&self, id: &DatasetId) -> Result<Vec<DatasetVersion>, DomainError> {
+        let mut versions: Vec<DatasetVersion> = self
+            .version_storage
+            .list()
+            .await?
+            .into_iter()
+            .filter(|version| &version.dataset_id == id)
+            .collect();
+
+        versions.sort_by_key(|version| std::cmp::Reverse(version.version));
+        Ok(versions)
+    }
+}
@@ -0,0 +1,237 @@
+//! Publishes gateway events to the configured broker
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tracing::warn;
+
+use super::{KafkaEventPublisher, NatsEventPublisher};
+use crate::config::EventsConfig;
+use crate::domain::event::{EventPublisher, GatewayEvent, GatewayEventType};
+use crate::domain::DomainError;
+use crate::infrastructure::background::spawn_tracked;
+
+/// Routes gateway events to a broker topic per event type. Without a
+/// publisher every event is dropped.
+#[derive(Debug)]
+pub struct EventBus {
+    publisher: Option<Arc<dyn EventPublisher>>,
+    topic_prefix: String,
+    /// Events published, empty publishes every event
+    events: Vec<GatewayEventType>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self {
+            publisher: None,
+            topic_prefix: "llm_gateway".to_string(),
+            events: Vec::new(),
+        }
+    }
+}
+
+impl EventBus {
+    /// Create a bus without a publisher
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the publisher of the `[events]` configuration
+    pub async fn from_config(config: &EventsConfig) -> Result<Self, DomainError> {
+        let events = config
+            .events
+            .iter()
+            .map(|event| {
+                event
+                    .parse()
+                    .map_err(|e: DomainError| DomainError::configuration(e.to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let bus = Self::new()
+            .with_topic_prefix(&config.topic_prefix)
+            .with_events(events);
+
+        let backend = config.backend.to_lowercase();
+        let servers = || {
+            config.servers.as_deref().ok_or_else(|| {
+                DomainError::configuration(format!(
+                    "events.servers is required for the {} backend",
+                    backend
+                ))
+            })
+        };
+
+        let publisher: Arc<dyn EventPublisher> = match backend.as_str() {
+            "none" | "" => return Ok(bus),
+            "kafka" => Arc::new(KafkaEventPublisher::new(
+                servers()?,
+                Duration::from_secs(config.timeout_secs.max(1)),
+            )?),
+            "nats" => Arc::new(NatsEventPublisher::connect(servers()?).await?),
+            other => {
+                return Err(DomainError::configuration(format!(
+                    "Unknown events backend '{}', expected 'none', 'kafka' or 'nats'",
+                    other
+                )))
+            }
+        };
+
+        Ok(bus.with_publisher(publisher))
+    }
+
+    /// Set the broker events are published to (builder pattern)
+    pub fn with_publisher(mut self, publisher: Arc<dyn EventPublisher>) -> Self {
+        self.publisher = Some(publisher);
+        self
+    }
+
+    /// Set the prefix of topic names (builder pattern)
+    pub fn with_topic_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.topic_prefix = prefix.into().trim_end_matches('.').to_string();
+        self
+    }
+
+    /// Only publish these events (builder pattern)
+    pub fn with_events(mut self, events: Vec<GatewayEventType>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Name of the configured broker
+    pub fn publisher_name(&self) -> Option<&str> {
+        self.publisher.as_ref().map(|p| p.name())
+    }
+
+    /// Topic (Kafka) or subject (NATS) of an event type
+    pub fn topic(&self, event_type: GatewayEventType) -> String {
+        if self.topic_prefix.is_empty() {
+            return event_type.as_str().to_string();
+        }
+
+        format!("{}.{}", self.topic_prefix, event_type)
+    }
+
+    /// Whether events of a type are published
+    pub fn accepts(&self, event_type: GatewayEventType) -> bool {
+        self.publisher.is_some() && (self.events.is_empty() || self.events.contains(&event_type))
+    }
+
+    /// Publish an event, returning whether it was published. Failures are
+    /// logged, not returned.
+    pub async fn publish(&self, event: &GatewayEvent) -> bool {
+        let Some(publisher) = self
+            .publisher
+            .as_ref()
+            .filter(|_| self.accepts(event.event_type))
+        else {
+            return false;
+        };
+
+        let topic = self.topic(event.event_type);
+
+        match publisher.publish(&topic, event).await {
+            Ok(()) => true,
+            Err(e) => {
+                warn!(
+                    broker = publisher.name(),
+                    topic = %topic,
+                    event_id = %event.id,
+                    error = %e,
+                    "Failed to publish event"
+                );
+                false
+            }
+        }
+    }
+
+    /// Publish an event in the background
+    pub fn spawn_publish(self: &Arc<Self>, event: GatewayEvent) {
+        if !self.accepts(event.event_type) {
+            return;
+        }
+
+        let bus = self.clone();
+
+        spawn_tracked(async move {
+            bus.publish(&event).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct RecordingPublisher {
+        published: Mutex<Vec<(String, String)>>,
+    }
+
+    #[async_trait]
+    impl EventPublisher for RecordingPublisher {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn publish(&self, topic: &str, event: &GatewayEvent) -> Result<(), DomainError> {
+            self.published
+                .lock()
+                .unwrap()
+                .push((topic.to_string(), event.subject.clone()));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_routes_events_to_topics() {
+        let publisher = Arc::new(RecordingPublisher::default());
+        let bus = EventBus::new()
+            .with_publisher(publisher.clone())
+            .with_topic_prefix("gateway.")
+            .with_events(vec![GatewayEventType::BudgetExceeded]);
+
+        assert!(
+            bus.publish(&GatewayEvent::budget_exceeded("key-1", None, &["budget-1"]))
+                .await
+        );
+        assert!(
+            !bus.publish(&GatewayEvent::request_completed(
+                "key-1", "team-1", "gpt-4o", 10, 5, 100, 250
+            ))
+            .await
+        );
+
+        assert_eq!(
+            *publisher.published.lock().unwrap(),
+            vec![("gateway.budget_exceeded".to_string(), "key-1".to_string())]
+        );
+        assert!(
+            !EventBus::new()
+                .publish(&GatewayEvent::budget_exceeded("key-1", None, &[]))
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_from_config() {
+        let mut config = EventsConfig::default();
+        let bus = EventBus::from_config(&config).await.unwrap();
+        assert_eq!(bus.publisher_name(), None);
+        assert_eq!(
+            bus.topic(GatewayEventType::EntityChanged),
+            "llm_gateway.entity_changed"
+        );
+
+        config.backend = "kafka".to_string();
+        assert!(EventBus::from_config(&config).await.is_err());
+
+        config.backend = "none".to_string();
+        config.events = vec!["request_failed".to_string()];
+        assert!(EventBus::from_config(&config).await.is_err());
+    }
+}
@@ -0,0 +1,66 @@
+//! Kafka event publisher
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rdkafka::config::ClientConfig;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+
+use crate::domain::event::{EventPublisher, GatewayEvent};
+use crate::domain::DomainError;
+
+/// Publishes events as JSON messages keyed by their subject
+pub struct KafkaEventPublisher {
+    producer: FutureProducer,
+    brokers: String,
+    timeout: Duration,
+}
+
+impl std::fmt::Debug for KafkaEventPublisher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KafkaEventPublisher")
+            .field("brokers", &self.brokers)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl KafkaEventPublisher {
+    /// Create a producer for comma-separated bootstrap servers. Messages not
+    /// delivered within the timeout fail.
+    pub fn new(brokers: impl Into<String>, timeout: Duration) -> Result<Self, DomainError> {
+        let brokers = brokers.into();
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", &brokers)
+            .set("message.timeout.ms", timeout.as_millis().to_string())
+            .create()
+            .map_err(|e| DomainError::configuration(format!("Invalid Kafka producer: {}", e)))?;
+
+        Ok(Self {
+            producer,
+            brokers,
+            timeout,
+        })
+    }
+}
+
+#[async_trait]
+impl EventPublisher for KafkaEventPublisher {
+    fn name(&self) -> &str {
+        "kafka"
+    }
+
+    async fn publish(&self, topic: &str, event: &GatewayEvent) -> Result<(), DomainError> {
+        let payload = serde_json::to_vec(event)
+            .map_err(|e| DomainError::internal(format!("Failed to serialize event: {}", e)))?;
+        let record = FutureRecord::to(topic)
+            .key(event.subject.as_str())
+            .payload(&payload);
+
+        self.producer
+            .send(record, self.timeout)
+            .await
+            .map(|_| ())
+            .map_err(|(e, _)| DomainError::internal(format!("Kafka publish failed: {}", e)))
+    }
+}
@@ -0,0 +1,9 @@
+//! Gateway event publishing to Kafka and NATS
+
+mod bus;
+mod kafka;
+mod nats;
+
+pub use bus::EventBus;
+pub use kafka::KafkaEventPublisher;
+pub use nats::NatsEventPublisher;
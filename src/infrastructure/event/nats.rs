@@ -0,0 +1,48 @@
+//! NATS event publisher
+
+use async_trait::async_trait;
+
+use crate::domain::event::{EventPublisher, GatewayEvent};
+use crate::domain::DomainError;
+
+/// Publishes events as JSON messages with core NATS
+#[derive(Debug, Clone)]
+pub struct NatsEventPublisher {
+    client: async_nats::Client,
+}
+
+impl NatsEventPublisher {
+    /// Wrap a connected client
+    pub fn new(client: async_nats::Client) -> Self {
+        Self { client }
+    }
+
+    /// Connect to a NATS server. The connection is established in the
+    /// background, so an unreachable server does not prevent startup.
+    pub async fn connect(url: &str) -> Result<Self, DomainError> {
+        let client = async_nats::ConnectOptions::new()
+            .retry_on_initial_connect()
+            .connect(url)
+            .await
+            .map_err(|e| DomainError::configuration(format!("Invalid NATS server: {}", e)))?;
+
+        Ok(Self::new(client))
+    }
+}
+
+#[async_trait]
+impl EventPublisher for NatsEventPublisher {
+    fn name(&self) -> &str {
+        "nats"
+    }
+
+    async fn publish(&self, topic: &str, event: &GatewayEvent) -> Result<(), DomainError> {
+        let payload = serde_json::to_vec(event)
+            .map_err(|e| DomainError::internal(format!("Failed to serialize event: {}", e)))?;
+
+        self.client
+            .publish(topic.to_string(), payload.into())
+            .await
+            .map_err(|e| DomainError::internal(format!("NATS publish failed: {}", e)))
+    }
+}
@@ -83,6 +83,23 @@ impl ExperimentRecordRepository for InMemoryExperimentRecordRepository {
         Ok(records.get(id).cloned())
     }
 
+    async fn update(&self, record: ExperimentRecord) -> Result<(), DomainError> {
+        let mut records = self
+            .records
+            .write()
+            .map_err(|e| DomainError::internal(format!("Failed to acquire write lock: {}", e)))?;
+
+        if !records.contains_key(record.id()) {
+            return Err(DomainError::not_found(format!(
+                "Experiment record '{}' not found",
+                record.id()
+            )));
+        }
+        records.insert(record.id().clone(), record);
+
+        Ok(())
+    }
+
     async fn query(
         &self,
         query: &ExperimentRecordQuery,
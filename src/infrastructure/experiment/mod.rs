@@ -13,7 +13,8 @@ pub use consistent_hashing::ConsistentHasher;
 pub use in_memory_record_repo::InMemoryExperimentRecordRepository;
 pub use in_memory_repository::InMemoryExperimentRepository;
 pub use statistical::{
-    calculate_all_significance, calculate_significance, mean, std_dev, variance, welch_t_test,
+    calculate_all_significance, calculate_significance, mean, msprt, msprt_half_width,
+    sequential_test, std_dev, variance, welch_t_test,
 };
 pub use storage_record_repository::StorageExperimentRecordRepository;
 pub use storage_repository::StorageExperimentRepository;
@@ -1,8 +1,12 @@
 //! Statistical analysis functions for A/B testing
 //!
-//! Provides statistical significance testing using Welch's t-test.
+//! Provides statistical significance testing using Welch's t-test, and
+//! sequential testing using the mixture sequential probability ratio test
+//! (mSPRT) for experiments that stop on their own.
 
-use crate::domain::experiment::StatisticalSignificance;
+use crate::domain::experiment::{
+    AutoStop, SequentialDecision, SequentialTest, StatisticalSignificance,
+};
 
 /// Calculate p-value using Welch's t-test for two independent samples
 ///
@@ -201,6 +205,119 @@ pub fn calculate_all_significance(
     results
 }
 
+/// Mixture sequential probability ratio test (mSPRT) for the difference in
+/// means of two samples, mixing over effects with a normal prior of variance
+/// `tau_sq`
+///
+/// # Returns
+/// * `Some((p_value, difference))` with the always-valid p-value and the
+///   treatment minus control difference in means
+/// * `None` if either sample has fewer than 2 elements or no variance
+pub fn msprt(control: &[f64], treatment: &[f64], tau_sq: f64) -> Option<(f64, f64)> {
+    let v = msprt_variance(control, treatment)?;
+
+    if tau_sq <= 0.0 {
+        return None;
+    }
+
+    let difference = mean(treatment) - mean(control);
+    let log_ratio = 0.5 * (v / (v + tau_sq)).ln()
+        + tau_sq * difference.powi(2) / (2.0 * v * (v + tau_sq));
+
+    Some(((-log_ratio).exp().min(1.0), difference))
+}
+
+/// Half-width of the always-valid confidence interval of the mSPRT
+/// difference at level `alpha`
+pub fn msprt_half_width(control: &[f64], treatment: &[f64], tau_sq: f64, alpha: f64) -> Option<f64> {
+    let v = msprt_variance(control, treatment)?;
+
+    if tau_sq <= 0.0 {
+        return None;
+    }
+
+    let bound = (1.0 / alpha).ln() + 0.5 * ((v + tau_sq) / v).ln();
+    Some((2.0 * v * (v + tau_sq) / tau_sq * bound).sqrt())
+}
+
+/// Variance of the difference in means
+fn msprt_variance(control: &[f64], treatment: &[f64]) -> Option<f64> {
+    if control.len() < 2 || treatment.len() < 2 {
+        return None;
+    }
+
+    let v = variance(control) / control.len() as f64 + variance(treatment) / treatment.len() as f64;
+    (v > 0.0).then_some(v)
+}
+
+/// Run the sequential test of an auto-stopping experiment
+///
+/// The mixture prior is centred on the smallest effect worth detecting. The
+/// test is significant once the always-valid p-value drops below `alpha`,
+/// and futile once the confidence interval of the difference lies within
+/// `min_effect_pct` of the control mean or both variants reached
+/// `max_samples`. No decision is made before both reach `min_samples`.
+///
+/// # Returns
+/// * `Some(SequentialTest)` with the decision if calculation succeeds
+/// * `None` if samples are too small or have no variance
+pub fn sequential_test(
+    control_samples: &[f64],
+    treatment_samples: &[f64],
+    control_id: &str,
+    treatment_id: &str,
+    auto_stop: &AutoStop,
+) -> Option<SequentialTest> {
+    let control_mean = mean(control_samples);
+    let treatment_mean = mean(treatment_samples);
+    let min_effect = control_mean.abs() * auto_stop.min_effect_pct / 100.0;
+
+    let tau_sq = if min_effect > 0.0 {
+        min_effect.powi(2)
+    } else {
+        (variance(control_samples) + variance(treatment_samples)) / 2.0
+    };
+
+    let (p_value, difference) = msprt(control_samples, treatment_samples, tau_sq)?;
+    let half_width = msprt_half_width(control_samples, treatment_samples, tau_sq, auto_stop.alpha)?;
+
+    let control_n = control_samples.len() as u64;
+    let treatment_n = treatment_samples.len() as u64;
+
+    let decision = if control_n.min(treatment_n) < auto_stop.min_samples {
+        SequentialDecision::Continue
+    } else if p_value < auto_stop.alpha {
+        SequentialDecision::Significant
+    } else if (min_effect > 0.0 && difference.abs() + half_width < min_effect)
+        || auto_stop
+            .max_samples
+            .is_some_and(|max| control_n.min(treatment_n) >= max)
+    {
+        SequentialDecision::Futile
+    } else {
+        SequentialDecision::Continue
+    };
+
+    let relative_change = if control_mean != 0.0 {
+        difference / control_mean * 100.0
+    } else {
+        0.0
+    };
+
+    Some(SequentialTest {
+        control_variant_id: control_id.to_string(),
+        treatment_variant_id: treatment_id.to_string(),
+        metric: auto_stop.metric.clone(),
+        control_samples: control_n,
+        treatment_samples: treatment_n,
+        control_mean,
+        treatment_mean,
+        relative_change,
+        p_value,
+        decision,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -342,4 +459,82 @@ mod tests {
         assert!(results.iter().any(|s| s.metric == "latency_ms"));
         assert!(results.iter().any(|s| s.metric == "cost_micros"));
     }
+
+    fn samples(center: f64, n: usize) -> Vec<f64> {
+        (0..n).map(|i| center + (i % 5) as f64 - 2.0).collect()
+    }
+
+    #[test]
+    fn test_msprt() {
+        assert!(msprt(&[1.0], &[1.0, 2.0], 1.0).is_none());
+        assert!(msprt(&[1.0, 1.0], &[1.0, 1.0], 1.0).is_none());
+
+        let (p_same, diff) = msprt(&samples(100.0, 50), &samples(100.0, 50), 25.0).unwrap();
+        assert_eq!(diff, 0.0);
+        assert!(p_same > 0.9);
+
+        let (p_diff, diff) = msprt(&samples(100.0, 50), &samples(90.0, 50), 25.0).unwrap();
+        assert_eq!(diff, -10.0);
+        assert!(p_diff < 0.001);
+    }
+
+    #[test]
+    fn test_sequential_test_decisions() {
+        let auto_stop = AutoStop {
+            min_samples: 20,
+            ..AutoStop::default()
+        };
+
+        // Too few samples to decide on an obvious difference
+        let early = sequential_test(
+            &samples(100.0, 10),
+            &samples(80.0, 10),
+            "control",
+            "treatment",
+            &auto_stop,
+        )
+        .unwrap();
+        assert_eq!(early.decision, SequentialDecision::Continue);
+
+        let significant = sequential_test(
+            &samples(100.0, 50),
+            &samples(80.0, 50),
+            "control",
+            "treatment",
+            &auto_stop,
+        )
+        .unwrap();
+        assert_eq!(significant.decision, SequentialDecision::Significant);
+        assert!((significant.relative_change + 20.0).abs() < 0.001);
+
+        let futile = sequential_test(
+            &samples(100.0, 200),
+            &samples(100.0, 200),
+            "control",
+            "treatment",
+            &auto_stop,
+        )
+        .unwrap();
+        assert_eq!(futile.decision, SequentialDecision::Futile);
+    }
+
+    #[test]
+    fn test_sequential_test_max_samples() {
+        let auto_stop = AutoStop {
+            min_samples: 2,
+            min_effect_pct: 0.1,
+            max_samples: Some(5),
+            ..AutoStop::default()
+        };
+
+        let test = sequential_test(
+            &samples(100.0, 5),
+            &samples(100.0, 5),
+            "control",
+            "treatment",
+            &auto_stop,
+        )
+        .unwrap();
+        assert_eq!(test.decision, SequentialDecision::Futile);
+    }
 }
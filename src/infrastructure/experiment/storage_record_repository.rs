@@ -80,6 +80,11 @@ impl ExperimentRecordRepository for StorageExperimentRecordRepository {
         self.storage.get(id).await
     }
 
+    async fn update(&self, record: ExperimentRecord) -> Result<(), DomainError> {
+        self.storage.update(record).await?;
+        Ok(())
+    }
+
     async fn query(
         &self,
         query: &ExperimentRecordQuery,
@@ -0,0 +1,14 @@
+//! Fine-tuning infrastructure implementations
+
+mod openai;
+mod service;
+mod storage_repository;
+
+pub use openai::{
+    AZURE_FINE_TUNE_API_VERSION, CredentialFineTuneProviderFactory, OpenAiFineTuneProvider,
+    parse_job_response, provider_for_credential,
+};
+pub use service::{
+    CreateFineTuneJobRequest, FineTuneService, MAX_FINE_TUNE_SUFFIX_LENGTH, spawn_fine_tune_polling,
+};
+pub use storage_repository::StorageFineTuneJobRepository;
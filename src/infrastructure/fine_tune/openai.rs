@@ -0,0 +1,374 @@
+//! OpenAI and Azure OpenAI fine-tuning APIs
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::{Client, RequestBuilder};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::api::state::CredentialServiceTrait;
+use crate::domain::DomainError;
+use crate::domain::credentials::{CredentialType, StoredCredential};
+use crate::domain::fine_tune::{
+    FineTuneJobParams, FineTuneProvider, FineTuneProviderFactory, FineTuneStatus,
+    ProviderFineTuneJob,
+};
+
+/// Base URL of the OpenAI API
+pub const OPENAI_API_URL: &str = "https://api.openai.com";
+
+/// Azure OpenAI API version with fine-tuning support
+pub const AZURE_FINE_TUNE_API_VERSION: &str = "2024-10-21";
+
+#[derive(Debug, Deserialize)]
+struct FileResponse {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JobResponse {
+    id: String,
+    status: String,
+    #[serde(default)]
+    fine_tuned_model: Option<String>,
+    #[serde(default)]
+    error: Option<JobError>,
+    #[serde(default)]
+    trained_tokens: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JobError {
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// Read a fine-tuning job of the OpenAI or Azure OpenAI API
+pub fn parse_job_response(body: &str) -> Result<ProviderFineTuneJob, DomainError> {
+    let job: JobResponse = serde_json::from_str(body).map_err(|e| {
+        DomainError::provider("openai", format!("Invalid fine-tuning job response: {}", e))
+    })?;
+
+    let status = FineTuneStatus::from_provider(&job.status).ok_or_else(|| {
+        DomainError::provider(
+            "openai",
+            format!("Unknown fine-tuning job status '{}'", job.status),
+        )
+    })?;
+
+    Ok(ProviderFineTuneJob {
+        id: job.id,
+        status,
+        fine_tuned_model: job.fine_tuned_model.filter(|model| !model.is_empty()),
+        error: job.error.and_then(|error| error.message),
+        trained_tokens: job.trained_tokens,
+    })
+}
+
+/// `multipart/form-data` body uploading a training file, returning the body
+/// and its content type
+fn training_file_form(filename: &str, content: &str) -> (String, String) {
+    let boundary = format!("pmp-{}", uuid::Uuid::new_v4().simple());
+    let body = format!(
+        "--{b}\r\nContent-Disposition: form-data; name=\"purpose\"\r\n\r\nfine-tune\r\n\
+         --{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{filename}\"\r\n\
+         Content-Type: application/jsonl\r\n\r\n{content}\r\n--{b}--\r\n",
+        b = boundary,
+    );
+
+    (body, format!("multipart/form-data; boundary={}", boundary))
+}
+
+/// Fine-tuning API of an OpenAI or Azure OpenAI credential
+#[derive(Debug)]
+pub struct OpenAiFineTuneProvider {
+    provider_type: CredentialType,
+    api_key: String,
+    base_url: String,
+    /// Set for Azure, which versions its API with a query parameter
+    api_version: Option<String>,
+    http_client: Client,
+}
+
+impl OpenAiFineTuneProvider {
+    /// Provider for the OpenAI API
+    pub fn openai(api_key: impl Into<String>) -> Self {
+        Self::new(CredentialType::OpenAi, api_key, OPENAI_API_URL, None)
+    }
+
+    /// Provider for an Azure OpenAI resource
+    pub fn azure(endpoint: impl Into<String>, api_key: impl Into<String>) -> Self {
+        Self::new(
+            CredentialType::AzureOpenAi,
+            api_key,
+            endpoint,
+            Some(AZURE_FINE_TUNE_API_VERSION.to_string()),
+        )
+    }
+
+    fn new(
+        provider_type: CredentialType,
+        api_key: impl Into<String>,
+        base_url: impl Into<String>,
+        api_version: Option<String>,
+    ) -> Self {
+        Self {
+            provider_type,
+            api_key: api_key.into(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            api_version,
+            http_client: Client::builder()
+                .timeout(Duration::from_secs(60))
+                .build()
+                .expect("Failed to create HTTP client"),
+        }
+    }
+
+    /// Override the API base URL (builder pattern)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    fn url(&self, path: &str) -> String {
+        match &self.api_version {
+            Some(version) => format!("{}/openai/{}?api-version={}", self.base_url, path, version),
+            None => format!("{}/v1/{}", self.base_url, path),
+        }
+    }
+
+    fn authorized(&self, request: RequestBuilder) -> RequestBuilder {
+        match self.provider_type {
+            CredentialType::AzureOpenAi => request.header("api-key", &self.api_key),
+            _ => request.bearer_auth(&self.api_key),
+        }
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<String, DomainError> {
+        let response = self.authorized(request).send().await.map_err(|e| {
+            DomainError::provider("openai", format!("Fine-tuning request failed: {}", e))
+        })?;
+
+        let status = response.status();
+        let body = response.text().await.map_err(|e| {
+            DomainError::provider(
+                "openai",
+                format!("Failed to read fine-tuning response: {}", e),
+            )
+        })?;
+
+        if !status.is_success() {
+            return Err(DomainError::provider(
+                "openai",
+                format!(
+                    "Fine-tuning API returned HTTP {}: {}",
+                    status.as_u16(),
+                    body
+                ),
+            ));
+        }
+
+        Ok(body)
+    }
+}
+
+#[async_trait]
+impl FineTuneProvider for OpenAiFineTuneProvider {
+    fn provider_type(&self) -> CredentialType {
+        self.provider_type.clone()
+    }
+
+    async fn upload_training_file(
+        &self,
+        filename: &str,
+        content: String,
+    ) -> Result<String, DomainError> {
+        let (body, content_type) = training_file_form(filename, &content);
+        let body = self
+            .send(
+                self.http_client
+                    .post(self.url("files"))
+                    .header("Content-Type", content_type)
+                    .body(body),
+            )
+            .await?;
+
+        let file: FileResponse = serde_json::from_str(&body).map_err(|e| {
+            DomainError::provider("openai", format!("Invalid file upload response: {}", e))
+        })?;
+        Ok(file.id)
+    }
+
+    async fn create_job(
+        &self,
+        params: FineTuneJobParams,
+    ) -> Result<ProviderFineTuneJob, DomainError> {
+        let mut request = json!({
+            "model": params.base_model,
+            "training_file": params.training_file_id,
+        });
+
+        if let Some(suffix) = params.suffix {
+            request["suffix"] = Value::String(suffix);
+        }
+        if !params.hyperparameters.is_empty() {
+            request["hyperparameters"] = json!(params.hyperparameters);
+        }
+
+        let body = self
+            .send(
+                self.http_client
+                    .post(self.url("fine_tuning/jobs"))
+                    .json(&request),
+            )
+            .await?;
+        parse_job_response(&body)
+    }
+
+    async fn get_job(&self, job_id: &str) -> Result<ProviderFineTuneJob, DomainError> {
+        let body = self
+            .send(
+                self.http_client
+                    .get(self.url(&format!("fine_tuning/jobs/{}", job_id))),
+            )
+            .await?;
+        parse_job_response(&body)
+    }
+
+    async fn cancel_job(&self, job_id: &str) -> Result<ProviderFineTuneJob, DomainError> {
+        let body = self
+            .send(
+                self.http_client
+                    .post(self.url(&format!("fine_tuning/jobs/{}/cancel", job_id))),
+            )
+            .await?;
+        parse_job_response(&body)
+    }
+}
+
+/// Fine-tuning API of a stored OpenAI or Azure OpenAI credential
+pub struct CredentialFineTuneProviderFactory {
+    credentials: Arc<dyn CredentialServiceTrait>,
+}
+
+impl std::fmt::Debug for CredentialFineTuneProviderFactory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CredentialFineTuneProviderFactory")
+            .finish_non_exhaustive()
+    }
+}
+
+impl CredentialFineTuneProviderFactory {
+    pub fn new(credentials: Arc<dyn CredentialServiceTrait>) -> Self {
+        Self { credentials }
+    }
+}
+
+/// Fine-tuning API of a credential
+pub fn provider_for_credential(
+    credential: &StoredCredential,
+) -> Result<OpenAiFineTuneProvider, DomainError> {
+    match credential.credential_type() {
+        CredentialType::OpenAi => {
+            let provider = OpenAiFineTuneProvider::openai(credential.api_key());
+
+            Ok(match credential.endpoint() {
+                Some(endpoint) => provider.with_base_url(endpoint),
+                None => provider,
+            })
+        }
+        CredentialType::AzureOpenAi => {
+            let endpoint = credential.endpoint().ok_or_else(|| {
+                DomainError::validation(format!(
+                    "Credential '{}' has no Azure OpenAI endpoint",
+                    credential.id()
+                ))
+            })?;
+
+            Ok(OpenAiFineTuneProvider::azure(
+                endpoint,
+                credential.api_key(),
+            ))
+        }
+        _ => Err(DomainError::validation(format!(
+            "Credential '{}' does not support fine-tuning; use an OpenAI or Azure OpenAI credential",
+            credential.id()
+        ))),
+    }
+}
+
+#[async_trait]
+impl FineTuneProviderFactory for CredentialFineTuneProviderFactory {
+    async fn for_credential(
+        &self,
+        credential_id: &str,
+    ) -> Result<Arc<dyn FineTuneProvider>, DomainError> {
+        let credential = self.credentials.get(credential_id).await?.ok_or_else(|| {
+            DomainError::validation(format!("Credential '{}' not found", credential_id))
+        })?;
+
+        Ok(Arc::new(provider_for_credential(&credential)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_job_response() {
+        let job = parse_job_response(
+            r#"{"id": "ftjob-1", "status": "succeeded", "fine_tuned_model": "ft:gpt-4o-mini:acme::abc", "trained_tokens": 1200, "error": {}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(job.status, FineTuneStatus::Succeeded);
+        assert_eq!(
+            job.fine_tuned_model.as_deref(),
+            Some("ft:gpt-4o-mini:acme::abc")
+        );
+        assert_eq!(job.trained_tokens, Some(1200));
+        assert!(job.error.is_none());
+
+        let failed = parse_job_response(
+            r#"{"id": "ftjob-2", "status": "failed", "error": {"message": "Invalid file"}}"#,
+        )
+        .unwrap();
+        assert_eq!(failed.error.as_deref(), Some("Invalid file"));
+
+        assert!(parse_job_response(r#"{"id": "ftjob-3", "status": "melting"}"#).is_err());
+    }
+
+    #[test]
+    fn test_urls() {
+        let openai = OpenAiFineTuneProvider::openai("sk-test");
+        assert_eq!(
+            openai.url("fine_tuning/jobs"),
+            "https://api.openai.com/v1/fine_tuning/jobs"
+        );
+
+        let azure = OpenAiFineTuneProvider::azure("https://acme.openai.azure.com/", "key");
+        assert_eq!(
+            azure.url("files"),
+            format!(
+                "https://acme.openai.azure.com/openai/files?api-version={}",
+                AZURE_FINE_TUNE_API_VERSION
+            )
+        );
+    }
+
+    #[test]
+    fn test_training_file_form() {
+        let (body, content_type) = training_file_form("train.jsonl", "{\"messages\": []}");
+        let boundary = content_type
+            .strip_prefix("multipart/form-data; boundary=")
+            .unwrap();
+
+        assert!(body.starts_with(&format!("--{}\r\n", boundary)));
+        assert!(body.contains("name=\"purpose\"\r\n\r\nfine-tune\r\n"));
+        assert!(body.contains("filename=\"train.jsonl\""));
+        assert!(body.ends_with(&format!("--{}--\r\n", boundary)));
+    }
+}
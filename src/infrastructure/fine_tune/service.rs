@@ -0,0 +1,516 @@
+//! Fine-tuning job submission, tracking and model registration
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tracing::{info, warn};
+
+use crate::api::state::{DatasetServiceTrait, FineTuneServiceTrait, ModelServiceTrait};
+use crate::domain::dataset::DatasetSelector;
+use crate::domain::fine_tune::{
+    FineTuneHyperparameters, FineTuneJob, FineTuneJobId, FineTuneJobParams, FineTuneJobRepository,
+    FineTuneProviderFactory, NewFineTuneJob, build_training_file,
+};
+use crate::domain::{DomainError, FieldViolations, validate_model_id};
+use crate::infrastructure::leader::Leadership;
+use crate::infrastructure::services::CreateModelRequest;
+
+/// Maximum length of the suffix of trained model names
+pub const MAX_FINE_TUNE_SUFFIX_LENGTH: usize = 64;
+
+/// Request to start a fine-tuning job
+#[derive(Debug, Clone)]
+pub struct CreateFineTuneJobRequest {
+    /// OpenAI or Azure OpenAI credential the job runs with
+    pub credential_id: String,
+    /// Provider model to fine-tune
+    pub base_model: String,
+    /// Dataset whose rows with an expected output are the training examples
+    pub dataset: DatasetSelector,
+    /// Gateway model registered when the job succeeds
+    pub model_id: String,
+    /// System message of every training example
+    pub system_prompt: Option<String>,
+    pub suffix: Option<String>,
+    pub hyperparameters: FineTuneHyperparameters,
+}
+
+/// Starts provider fine-tuning jobs from datasets and registers their models
+pub struct FineTuneService<R: FineTuneJobRepository> {
+    repository: Arc<R>,
+    datasets: Arc<dyn DatasetServiceTrait>,
+    models: Arc<dyn ModelServiceTrait>,
+    providers: Arc<dyn FineTuneProviderFactory>,
+}
+
+impl<R: FineTuneJobRepository> std::fmt::Debug for FineTuneService<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FineTuneService")
+            .field("repository", &self.repository)
+            .field("providers", &self.providers)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: FineTuneJobRepository> FineTuneService<R> {
+    /// Create a new fine-tuning service
+    pub fn new(
+        repository: Arc<R>,
+        datasets: Arc<dyn DatasetServiceTrait>,
+        models: Arc<dyn ModelServiceTrait>,
+        providers: Arc<dyn FineTuneProviderFactory>,
+    ) -> Self {
+        Self {
+            repository,
+            datasets,
+            models,
+            providers,
+        }
+    }
+
+    /// Every job, newest first
+    pub async fn list(&self) -> Result<Vec<FineTuneJob>, DomainError> {
+        self.repository.list().await
+    }
+
+    /// Get a job by ID
+    pub async fn get(&self, id: &str) -> Result<Option<FineTuneJob>, DomainError> {
+        self.repository.get(&parse_id(id)?).await
+    }
+
+    /// Get a job by ID, returning an error if not found
+    pub async fn get_required(&self, id: &str) -> Result<FineTuneJob, DomainError> {
+        self.get(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("Fine-tuning job '{}' not found", id)))
+    }
+
+    /// Upload the training file built from the dataset and start the job
+    pub async fn create(
+        &self,
+        request: CreateFineTuneJobRequest,
+    ) -> Result<FineTuneJob, DomainError> {
+        validate_request(&request)?;
+
+        if self.models.get(&request.model_id).await?.is_some() {
+            return Err(DomainError::conflict(format!(
+                "Model '{}' already exists",
+                request.model_id
+            )));
+        }
+
+        let provider = self
+            .providers
+            .for_credential(&request.credential_id)
+            .await?;
+        let dataset = self
+            .datasets
+            .resolve(&request.dataset.dataset_id, request.dataset.version)
+            .await?;
+        let rows = self.datasets.rows(&dataset).await?;
+        let training_file = build_training_file(&rows, request.system_prompt.as_deref())?;
+
+        let training_file_id = provider
+            .upload_training_file(
+                &format!("{}-v{}.jsonl", dataset.dataset_id, dataset.version),
+                training_file,
+            )
+            .await?;
+
+        let submitted = provider
+            .create_job(FineTuneJobParams {
+                base_model: request.base_model.clone(),
+                training_file_id: training_file_id.clone(),
+                suffix: request.suffix.clone(),
+                hyperparameters: request.hyperparameters.clone(),
+            })
+            .await?;
+
+        let mut job = FineTuneJob::new(
+            NewFineTuneJob {
+                credential_id: request.credential_id,
+                provider: provider.provider_type(),
+                base_model: request.base_model,
+                dataset,
+                model_id: request.model_id,
+                suffix: request.suffix,
+                hyperparameters: request.hyperparameters,
+                training_file_id,
+            },
+            submitted,
+        );
+
+        if job.needs_registration() {
+            self.register_model(&mut job).await;
+        }
+
+        let job = self.repository.save(job).await?;
+
+        info!(
+            job_id = %job.id(),
+            provider_job_id = %job.provider_job_id(),
+            "Started fine-tuning job"
+        );
+        Ok(job)
+    }
+
+    /// Fetch the state of a job from the provider, registering the trained
+    /// model once it succeeded. Retries a failed registration.
+    pub async fn refresh(&self, id: &str) -> Result<FineTuneJob, DomainError> {
+        let mut job = self.get_required(id).await?;
+        let mut changed = false;
+
+        if !job.status().is_terminal() {
+            let provider = self.providers.for_credential(job.credential_id()).await?;
+            changed = job.apply(provider.get_job(job.provider_job_id()).await?);
+        }
+
+        if job.needs_registration() {
+            self.register_model(&mut job).await;
+            changed = true;
+        }
+
+        if changed {
+            job = self.repository.save(job).await?;
+        }
+
+        Ok(job)
+    }
+
+    /// Refresh every job still running, returning how many were checked
+    pub async fn refresh_active(&self) -> Result<usize, DomainError> {
+        let active: Vec<FineTuneJob> = self
+            .repository
+            .list()
+            .await?
+            .into_iter()
+            .filter(|job| !job.status().is_terminal())
+            .collect();
+
+        for job in &active {
+            if let Err(e) = self.refresh(job.id().as_str()).await {
+                warn!(job_id = %job.id(), error = %e, "Failed to refresh fine-tuning job");
+            }
+        }
+
+        Ok(active.len())
+    }
+
+    /// Cancel a running job
+    pub async fn cancel(&self, id: &str) -> Result<FineTuneJob, DomainError> {
+        let mut job = self.get_required(id).await?;
+
+        if job.status().is_terminal() {
+            return Err(DomainError::conflict(format!(
+                "Fine-tuning job '{}' already finished",
+                id
+            )));
+        }
+
+        let provider = self.providers.for_credential(job.credential_id()).await?;
+        job.apply(provider.cancel_job(job.provider_job_id()).await?);
+
+        info!(job_id = %job.id(), "Cancelled fine-tuning job");
+        self.repository.save(job).await
+    }
+
+    /// Register the trained model of a succeeded job, recording the error
+    /// on the job when the model cannot be created
+    async fn register_model(&self, job: &mut FineTuneJob) {
+        let Some(fine_tuned_model) = job.fine_tuned_model().map(String::from) else {
+            return;
+        };
+
+        let request = CreateModelRequest {
+            id: job.model_id().to_string(),
+            name: job.model_id().to_string(),
+            description: Some(format!(
+                "{} fine-tuned on dataset {}",
+                job.base_model(),
+                job.dataset()
+            )),
+            provider: job.provider().clone(),
+            provider_model: fine_tuned_model,
+            credential_id: job.credential_id().to_string(),
+            config: None,
+            enabled: true,
+            lineage: Some(job.lineage()),
+        };
+
+        match self.models.create(request).await {
+            Ok(model) => {
+                info!(job_id = %job.id(), model_id = %model.id(), "Registered fine-tuned model");
+                job.mark_registered();
+            }
+            Err(e) => {
+                warn!(job_id = %job.id(), error = %e, "Failed to register fine-tuned model");
+                job.set_registration_error(format!("Model registration failed: {}", e));
+            }
+        }
+    }
+}
+
+fn validate_request(request: &CreateFineTuneJobRequest) -> Result<(), DomainError> {
+    let mut violations = FieldViolations::new();
+
+    violations.check("model_id", validate_model_id(&request.model_id));
+
+    if request.credential_id.trim().is_empty() {
+        violations.push("credential_id", "Credential ID is required");
+    }
+    if request.base_model.trim().is_empty() {
+        violations.push("base_model", "Base model is required");
+    }
+    if let Some(suffix) = &request.suffix
+        && (suffix.is_empty() || suffix.len() > MAX_FINE_TUNE_SUFFIX_LENGTH)
+    {
+        violations.push(
+            "suffix",
+            format!(
+                "Suffix must be 1 to {} characters",
+                MAX_FINE_TUNE_SUFFIX_LENGTH
+            ),
+        );
+    }
+
+    let hyperparameters = &request.hyperparameters;
+    if hyperparameters.n_epochs == Some(0) {
+        violations.push("hyperparameters.n_epochs", "n_epochs must be positive");
+    }
+    if hyperparameters.batch_size == Some(0) {
+        violations.push("hyperparameters.batch_size", "batch_size must be positive");
+    }
+    if let Some(multiplier) = hyperparameters.learning_rate_multiplier
+        && multiplier <= 0.0
+    {
+        violations.push(
+            "hyperparameters.learning_rate_multiplier",
+            "learning_rate_multiplier must be positive",
+        );
+    }
+
+    violations.into_result()
+}
+
+fn parse_id(id: &str) -> Result<FineTuneJobId, DomainError> {
+    FineTuneJobId::new(id).map_err(|e| DomainError::validation(e.to_string()))
+}
+
+/// Poll running fine-tuning jobs on the leader replica
+pub fn spawn_fine_tune_polling(
+    service: Arc<dyn FineTuneServiceTrait>,
+    interval: Duration,
+    leadership: Leadership,
+) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            if !leadership.is_leader() {
+                continue;
+            }
+
+            if let Err(e) = service.refresh_active().await {
+                warn!(error = %e, "Fine-tuning job polling failed");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use crate::domain::Model;
+    use crate::domain::credentials::CredentialType;
+    use crate::domain::dataset::{Dataset, DatasetRow, DatasetVersion};
+    use crate::domain::fine_tune::{FineTuneProvider, FineTuneStatus, ProviderFineTuneJob};
+    use crate::infrastructure::dataset::{
+        CreateDatasetRequest, DatasetService, StorageDatasetRepository,
+    };
+    use crate::infrastructure::fine_tune::StorageFineTuneJobRepository;
+    use crate::infrastructure::services::ModelService;
+    use crate::infrastructure::storage::InMemoryStorage;
+
+    #[derive(Debug, Default)]
+    struct FakeProvider {
+        status: Mutex<Option<ProviderFineTuneJob>>,
+        uploads: Mutex<Vec<String>>,
+    }
+
+    impl FakeProvider {
+        fn set(&self, status: FineTuneStatus, model: Option<&str>) {
+            *self.status.lock().unwrap() = Some(ProviderFineTuneJob {
+                id: "ftjob-1".to_string(),
+                status,
+                fine_tuned_model: model.map(String::from),
+                error: None,
+                trained_tokens: None,
+            });
+        }
+
+        fn current(&self) -> ProviderFineTuneJob {
+            self.status.lock().unwrap().clone().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl FineTuneProvider for FakeProvider {
+        fn provider_type(&self) -> CredentialType {
+            CredentialType::OpenAi
+        }
+
+        async fn upload_training_file(
+            &self,
+            _filename: &str,
+            content: String,
+        ) -> Result<String, DomainError> {
+            self.uploads.lock().unwrap().push(content);
+            Ok("file-1".to_string())
+        }
+
+        async fn create_job(
+            &self,
+            _params: FineTuneJobParams,
+        ) -> Result<ProviderFineTuneJob, DomainError> {
+            self.set(FineTuneStatus::ValidatingFiles, None);
+            Ok(self.current())
+        }
+
+        async fn get_job(&self, _job_id: &str) -> Result<ProviderFineTuneJob, DomainError> {
+            Ok(self.current())
+        }
+
+        async fn cancel_job(&self, _job_id: &str) -> Result<ProviderFineTuneJob, DomainError> {
+            self.set(FineTuneStatus::Cancelled, None);
+            Ok(self.current())
+        }
+    }
+
+    #[derive(Debug)]
+    struct FakeFactory(Arc<FakeProvider>);
+
+    #[async_trait]
+    impl FineTuneProviderFactory for FakeFactory {
+        async fn for_credential(
+            &self,
+            _credential_id: &str,
+        ) -> Result<Arc<dyn FineTuneProvider>, DomainError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    async fn create_service(
+        provider: Arc<FakeProvider>,
+    ) -> (
+        FineTuneService<StorageFineTuneJobRepository>,
+        Arc<dyn ModelServiceTrait>,
+    ) {
+        let datasets: Arc<dyn DatasetServiceTrait> = Arc::new(DatasetService::new(Arc::new(
+            StorageDatasetRepository::new(
+                Arc::new(InMemoryStorage::<Dataset>::new()),
+                Arc::new(InMemoryStorage::<DatasetVersion>::new()),
+            ),
+        )));
+        datasets
+            .create(CreateDatasetRequest {
+                id: "support".to_string(),
+                name: "Support".to_string(),
+                description: None,
+                rows: vec![DatasetRow {
+                    input: [("input".to_string(), "Where is my order?".to_string())].into(),
+                    expected_output: Some("It ships tomorrow.".to_string()),
+                }],
+                note: None,
+            })
+            .await
+            .unwrap();
+
+        let models: Arc<dyn ModelServiceTrait> =
+            Arc::new(ModelService::new(Arc::new(InMemoryStorage::<Model>::new())));
+        let service = FineTuneService::new(
+            Arc::new(StorageFineTuneJobRepository::new(Arc::new(
+                InMemoryStorage::<FineTuneJob>::new(),
+            ))),
+            datasets,
+            models.clone(),
+            Arc::new(FakeFactory(provider)),
+        );
+
+        (service, models)
+    }
+
+    fn create_request() -> CreateFineTuneJobRequest {
+        CreateFineTuneJobRequest {
+            credential_id: "openai".to_string(),
+            base_model: "gpt-4o-mini-2024-07-18".to_string(),
+            dataset: DatasetSelector {
+                dataset_id: "support".to_string(),
+                version: None,
+            },
+            model_id: "support-bot".to_string(),
+            system_prompt: None,
+            suffix: Some("support".to_string()),
+            hyperparameters: FineTuneHyperparameters::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_job_registers_model_on_success() {
+        let provider = Arc::new(FakeProvider::default());
+        let (service, models) = create_service(provider.clone()).await;
+
+        let job = service.create(create_request()).await.unwrap();
+        assert_eq!(job.status(), FineTuneStatus::ValidatingFiles);
+        assert_eq!(job.dataset().version, 1);
+        assert!(provider.uploads.lock().unwrap()[0].contains("It ships tomorrow."));
+
+        provider.set(FineTuneStatus::Running, None);
+        assert_eq!(service.refresh_active().await.unwrap(), 1);
+        assert!(models.get("support-bot").await.unwrap().is_none());
+
+        provider.set(
+            FineTuneStatus::Succeeded,
+            Some("ft:gpt-4o-mini:acme:support:abc"),
+        );
+        let job = service.refresh(job.id().as_str()).await.unwrap();
+        assert!(job.is_model_registered());
+
+        let model = models.get("support-bot").await.unwrap().unwrap();
+        assert_eq!(model.provider_model(), "ft:gpt-4o-mini:acme:support:abc");
+        assert_eq!(model.lineage().unwrap().dataset_id, "support");
+
+        assert_eq!(service.refresh_active().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_create_validation_and_cancel() {
+        let provider = Arc::new(FakeProvider::default());
+        let (service, _) = create_service(provider).await;
+
+        let invalid = service
+            .create(CreateFineTuneJobRequest {
+                base_model: String::new(),
+                suffix: Some(String::new()),
+                ..create_request()
+            })
+            .await;
+        let Err(DomainError::InvalidFields { violations }) = invalid else {
+            panic!("expected invalid fields");
+        };
+        let fields: Vec<&str> = violations.iter().map(|v| v.field.as_str()).collect();
+        assert_eq!(fields, vec!["base_model", "suffix"]);
+
+        let job = service.create(create_request()).await.unwrap();
+        let cancelled = service.cancel(job.id().as_str()).await.unwrap();
+        assert_eq!(cancelled.status(), FineTuneStatus::Cancelled);
+
+        let again = service.cancel(job.id().as_str()).await;
+        assert!(matches!(again, Err(DomainError::Conflict { .. })));
+    }
+}
@@ -0,0 +1,39 @@
+//! Storage-backed fine-tuning job repository
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::DomainError;
+use crate::domain::fine_tune::{FineTuneJob, FineTuneJobId, FineTuneJobRepository};
+use crate::domain::storage::Storage;
+
+/// Fine-tuning job repository backed by a generic storage
+#[derive(Debug)]
+pub struct StorageFineTuneJobRepository {
+    storage: Arc<dyn Storage<FineTuneJob>>,
+}
+
+impl StorageFineTuneJobRepository {
+    /// Create a new storage-backed repository
+    pub fn new(storage: Arc<dyn Storage<FineTuneJob>>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl FineTuneJobRepository for StorageFineTuneJobRepository {
+    async fn get(&self, id: &FineTuneJobId) -> Result<Option<FineTuneJob>, DomainError> {
+        self.storage.get(id).await
+    }
+
+    async fn list(&self) -> Result<Vec<FineTuneJob>, DomainError> {
+        let mut jobs = self.storage.list().await?;
+        jobs.sort_by_key(|job| std::cmp::Reverse(job.created_at()));
+        Ok(jobs)
+    }
+
+    async fn save(&self, job: FineTuneJob) -> Result<FineTuneJob, DomainError> {
+        self.storage.save(job).await
+    }
+}
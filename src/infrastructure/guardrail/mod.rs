@@ -0,0 +1,9 @@
+//! Guardrail infrastructure implementations
+
+mod moderation;
+mod policy_service;
+
+pub use moderation::{
+    DEFAULT_MODERATION_MODEL, OPENAI_API_URL, OpenAiModerationProvider, parse_moderation_response,
+};
+pub use policy_service::ContentPolicyService;
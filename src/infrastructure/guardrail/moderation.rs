@@ -0,0 +1,143 @@
+//! OpenAI moderation API used to score toxicity
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::domain::DomainError;
+use crate::domain::guardrail::ModerationProvider;
+
+/// Base URL of the OpenAI API
+pub const OPENAI_API_URL: &str = "https://api.openai.com";
+
+/// Moderation model used unless configured otherwise
+pub const DEFAULT_MODERATION_MODEL: &str = "omni-moderation-latest";
+
+#[derive(Debug, Deserialize)]
+struct ModerationResponse {
+    #[serde(default)]
+    results: Vec<ModerationResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModerationResult {
+    #[serde(default)]
+    category_scores: std::collections::HashMap<String, f64>,
+}
+
+/// Read the toxicity of a moderation API response: the highest category
+/// score of its first result
+pub fn parse_moderation_response(body: &str) -> Result<f64, DomainError> {
+    let response: ModerationResponse = serde_json::from_str(body).map_err(|e| {
+        DomainError::provider("openai", format!("Invalid moderation response: {}", e))
+    })?;
+
+    let result = response
+        .results
+        .into_iter()
+        .next()
+        .ok_or_else(|| DomainError::provider("openai", "No results in moderation response"))?;
+
+    Ok(result
+        .category_scores
+        .into_values()
+        .fold(0.0, f64::max)
+        .clamp(0.0, 1.0))
+}
+
+/// Toxicity scored by the OpenAI moderation API
+#[derive(Debug)]
+pub struct OpenAiModerationProvider {
+    api_key: String,
+    model: String,
+    base_url: String,
+    http_client: Client,
+}
+
+impl OpenAiModerationProvider {
+    /// Create a provider using an OpenAI API key and the default model
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            model: DEFAULT_MODERATION_MODEL.to_string(),
+            base_url: OPENAI_API_URL.to_string(),
+            http_client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .expect("Failed to create HTTP client"),
+        }
+    }
+
+    /// Use another moderation model (builder pattern)
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Override the API base URL (builder pattern)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+}
+
+#[async_trait]
+impl ModerationProvider for OpenAiModerationProvider {
+    fn provider(&self) -> &str {
+        "openai"
+    }
+
+    async fn toxicity(&self, text: &str) -> Result<f64, DomainError> {
+        let response = self
+            .http_client
+            .post(format!("{}/v1/moderations", self.base_url))
+            .bearer_auth(&self.api_key)
+            .json(&json!({ "model": self.model, "input": text }))
+            .send()
+            .await
+            .map_err(|e| {
+                DomainError::provider("openai", format!("Moderation request failed: {}", e))
+            })?;
+
+        let status = response.status();
+        let body = response.text().await.map_err(|e| {
+            DomainError::provider(
+                "openai",
+                format!("Failed to read moderation response: {}", e),
+            )
+        })?;
+
+        if !status.is_success() {
+            return Err(DomainError::provider(
+                "openai",
+                format!("Moderation API returned HTTP {}: {}", status.as_u16(), body),
+            ));
+        }
+
+        parse_moderation_response(&body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_moderation_response() {
+        let body = r#"{
+            "id": "modr-1",
+            "model": "omni-moderation-latest",
+            "results": [{
+                "flagged": true,
+                "category_scores": {"harassment": 0.81, "violence": 0.12, "hate": 0.03}
+            }]
+        }"#;
+        assert_eq!(parse_moderation_response(body).unwrap(), 0.81);
+
+        assert!(parse_moderation_response(r#"{"results": []}"#).is_err());
+        assert!(parse_moderation_response("not json").is_err());
+    }
+}
@@ -0,0 +1,214 @@
+//! Content policy management and evaluation
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use tracing::warn;
+
+use crate::domain::DomainError;
+use crate::domain::guardrail::{
+    ContentPolicy, ContentPolicyId, ModerationProvider, PolicyStage, PolicyViolation,
+};
+use crate::domain::storage::Storage;
+
+/// Manages content policies and evaluates the ones of a team against
+/// completion input and output
+#[derive(Debug)]
+pub struct ContentPolicyService {
+    storage: Arc<dyn Storage<ContentPolicy>>,
+    moderation: Option<Arc<dyn ModerationProvider>>,
+}
+
+impl ContentPolicyService {
+    /// Create a new content policy service without a moderation provider
+    pub fn new(storage: Arc<dyn Storage<ContentPolicy>>) -> Self {
+        Self {
+            storage,
+            moderation: None,
+        }
+    }
+
+    /// Score toxicity with a moderation provider (builder pattern)
+    pub fn with_moderation(mut self, moderation: Arc<dyn ModerationProvider>) -> Self {
+        self.moderation = Some(moderation);
+        self
+    }
+
+    /// Every policy, ordered by ID
+    pub async fn list(&self) -> Result<Vec<ContentPolicy>, DomainError> {
+        let mut policies = self.storage.list().await?;
+        policies.sort_by(|a, b| a.id.as_str().cmp(b.id.as_str()));
+        Ok(policies)
+    }
+
+    /// Policy by ID
+    pub async fn get(&self, id: &str) -> Result<Option<ContentPolicy>, DomainError> {
+        self.storage.get(&ContentPolicyId::new(id)).await
+    }
+
+    /// Create a policy
+    pub async fn create(&self, policy: ContentPolicy) -> Result<ContentPolicy, DomainError> {
+        self.validate(&policy)?;
+
+        if self.storage.exists(&policy.id).await? {
+            return Err(DomainError::conflict(format!(
+                "Content policy '{}' already exists",
+                policy.id
+            )));
+        }
+
+        self.storage.create(policy).await
+    }
+
+    /// Replace the rules of an existing policy
+    pub async fn update(&self, mut policy: ContentPolicy) -> Result<ContentPolicy, DomainError> {
+        self.validate(&policy)?;
+
+        let existing = self.storage.get(&policy.id).await?.ok_or_else(|| {
+            DomainError::not_found(format!("Content policy '{}' not found", policy.id))
+        })?;
+        policy.created_at = existing.created_at;
+        policy.updated_at = Utc::now().timestamp().max(0) as u64;
+
+        self.storage.update(policy).await
+    }
+
+    /// Delete a policy
+    pub async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        self.storage.delete(&ContentPolicyId::new(id)).await
+    }
+
+    /// Evaluate the policies of a team at a stage against a text. Toxicity is
+    /// scored once for every policy with a ceiling; moderation failures are
+    /// logged and skip the toxicity rules rather than failing the request.
+    pub async fn evaluate(
+        &self,
+        team_id: &str,
+        stage: PolicyStage,
+        text: &str,
+    ) -> Result<Vec<PolicyViolation>, DomainError> {
+        if text.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let policies: Vec<ContentPolicy> = self
+            .list()
+            .await?
+            .into_iter()
+            .filter(|p| p.applies_to(team_id, stage))
+            .collect();
+
+        let mut violations: Vec<PolicyViolation> = policies
+            .iter()
+            .flat_map(|p| p.check_text(text, stage))
+            .collect();
+
+        if let Some(moderation) = &self.moderation
+            && policies.iter().any(|p| p.max_toxicity.is_some())
+        {
+            match moderation.toxicity(text).await {
+                Ok(toxicity) => violations.extend(
+                    policies
+                        .iter()
+                        .filter_map(|p| p.check_toxicity(toxicity, stage)),
+                ),
+                Err(e) => warn!(
+                    provider = moderation.provider(),
+                    error = %e,
+                    "Failed to score toxicity for content policies"
+                ),
+            }
+        }
+
+        Ok(violations)
+    }
+
+    fn validate(&self, policy: &ContentPolicy) -> Result<(), DomainError> {
+        policy.validate()?;
+
+        if policy.max_toxicity.is_some() && self.moderation.is_none() {
+            return Err(DomainError::validation(
+                "max_toxicity requires a moderation provider ([content_policy].moderation_api_key)",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::storage::InMemoryStorage;
+    use async_trait::async_trait;
+
+    #[derive(Debug)]
+    struct FixedModeration(f64);
+
+    #[async_trait]
+    impl ModerationProvider for FixedModeration {
+        fn provider(&self) -> &str {
+            "fixed"
+        }
+
+        async fn toxicity(&self, _text: &str) -> Result<f64, DomainError> {
+            Ok(self.0)
+        }
+    }
+
+    fn service() -> ContentPolicyService {
+        ContentPolicyService::new(Arc::new(InMemoryStorage::<ContentPolicy>::new()))
+    }
+
+    #[tokio::test]
+    async fn test_create_update_and_validate() {
+        let service = service();
+        let policy = ContentPolicy::new("no-acme", "No Acme").with_banned_phrase("acme");
+
+        let created = service.create(policy.clone()).await.unwrap();
+        assert!(service.create(policy).await.is_err());
+
+        let updated = service
+            .update(created.clone().with_banned_phrase("globex"))
+            .await
+            .unwrap();
+        assert_eq!(updated.created_at, created.created_at);
+        assert_eq!(updated.banned_phrases.len(), 2);
+
+        // Toxicity ceilings need a moderation provider
+        let toxic = ContentPolicy::new("toxic", "Toxic").with_max_toxicity(0.5);
+        assert!(service.create(toxic).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_team_policies() {
+        let service = service().with_moderation(Arc::new(FixedModeration(0.9)));
+
+        service
+            .create(ContentPolicy::new("global", "Global").with_banned_phrase("acme"))
+            .await
+            .unwrap();
+        service
+            .create(
+                ContentPolicy::new("team-b", "Team B")
+                    .with_team("team-b")
+                    .with_max_toxicity(0.5),
+            )
+            .await
+            .unwrap();
+
+        let violations = service
+            .evaluate("team-a", PolicyStage::Input, "Tell me about Acme")
+            .await
+            .unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].policy_id, "global");
+
+        let violations = service
+            .evaluate("team-b", PolicyStage::Output, "Tell me about Acme")
+            .await
+            .unwrap();
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[1].policy_id, "team-b");
+    }
+}
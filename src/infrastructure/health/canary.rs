@@ -0,0 +1,338 @@
+//! Scheduled canary runs of test cases against live providers
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use serde::Serialize;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::api::state::{CredentialServiceTrait, ModelServiceTrait, TestCaseServiceTrait};
+use crate::domain::test_case::{TestCase, TestCaseInput, TestCaseQuery, DEFAULT_SUITE_CONCURRENCY};
+use crate::domain::DomainError;
+use crate::infrastructure::notification::NotificationDispatcher;
+use crate::infrastructure::plugin::ProviderRouter;
+
+/// Outcome of one canary execution
+#[derive(Debug, Clone)]
+pub struct CanaryRun {
+    pub test_case_id: String,
+    pub model_id: String,
+    pub provider: Option<String>,
+    pub passed: bool,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+/// Latest state of a canary test case
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CanaryStatus {
+    pub test_case_id: String,
+    pub model_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    pub passed: bool,
+    pub consecutive_failures: u32,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Whether the canary failed enough runs in a row to degrade its model
+    pub degraded: bool,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Tracks canary outcomes and marks a model as degraded once one of its
+/// canaries failed `failure_threshold` runs in a row, until it passes again
+#[derive(Debug)]
+pub struct CanaryHealth {
+    failure_threshold: u32,
+    statuses: Mutex<HashMap<String, CanaryStatus>>,
+}
+
+impl CanaryHealth {
+    /// Create a tracker; a threshold of 0 is treated as 1
+    pub fn new(failure_threshold: u32) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            statuses: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record a canary run, returning the updated status
+    pub fn record(&self, run: CanaryRun) -> CanaryStatus {
+        let mut statuses = self.statuses.lock().unwrap();
+        let previous_failures = statuses
+            .get(&run.test_case_id)
+            .map_or(0, |status| status.consecutive_failures);
+        let consecutive_failures = if run.passed {
+            0
+        } else {
+            previous_failures + 1
+        };
+
+        let status = CanaryStatus {
+            test_case_id: run.test_case_id,
+            model_id: run.model_id,
+            provider: run.provider,
+            passed: run.passed,
+            consecutive_failures,
+            latency_ms: run.latency_ms,
+            error: run.error,
+            degraded: consecutive_failures >= self.failure_threshold,
+            checked_at: Utc::now(),
+        };
+        statuses.insert(status.test_case_id.clone(), status.clone());
+
+        status
+    }
+
+    /// Latest status of every canary, ordered by test case ID
+    pub fn statuses(&self) -> Vec<CanaryStatus> {
+        let mut statuses: Vec<CanaryStatus> =
+            self.statuses.lock().unwrap().values().cloned().collect();
+        statuses.sort_by(|a, b| a.test_case_id.cmp(&b.test_case_id));
+        statuses
+    }
+
+    /// Whether any canary of a model is degraded
+    pub fn is_degraded(&self, model_id: &str) -> bool {
+        self.statuses
+            .lock()
+            .unwrap()
+            .values()
+            .any(|status| status.degraded && status.model_id == model_id)
+    }
+
+    /// Models with at least one degraded canary
+    pub fn degraded_models(&self) -> Vec<String> {
+        let models: HashSet<String> = self
+            .statuses
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|status| status.degraded)
+            .map(|status| status.model_id.clone())
+            .collect();
+        let mut models: Vec<String> = models.into_iter().collect();
+        models.sort();
+        models
+    }
+
+    /// Forget canaries that are no longer scheduled
+    pub fn retain(&self, test_case_ids: &HashSet<String>) {
+        self.statuses
+            .lock()
+            .unwrap()
+            .retain(|id, _| test_case_ids.contains(id));
+    }
+}
+
+impl Default for CanaryHealth {
+    fn default() -> Self {
+        Self::new(2)
+    }
+}
+
+/// Executes the enabled canary test cases, feeding execution errors into
+/// provider outage tracking and outcomes into [`CanaryHealth`]
+pub struct CanaryRunner {
+    test_case_service: Arc<dyn TestCaseServiceTrait>,
+    model_service: Arc<dyn ModelServiceTrait>,
+    credential_service: Arc<dyn CredentialServiceTrait>,
+    provider_router: Arc<ProviderRouter>,
+    notifications: Arc<NotificationDispatcher>,
+    health: Arc<CanaryHealth>,
+}
+
+impl CanaryRunner {
+    /// Create a new canary runner
+    pub fn new(
+        test_case_service: Arc<dyn TestCaseServiceTrait>,
+        model_service: Arc<dyn ModelServiceTrait>,
+        credential_service: Arc<dyn CredentialServiceTrait>,
+        provider_router: Arc<ProviderRouter>,
+        notifications: Arc<NotificationDispatcher>,
+        health: Arc<CanaryHealth>,
+    ) -> Self {
+        Self {
+            test_case_service,
+            model_service,
+            credential_service,
+            provider_router,
+            notifications,
+            health,
+        }
+    }
+
+    /// Canary health shared with request routing
+    pub fn health(&self) -> Arc<CanaryHealth> {
+        self.health.clone()
+    }
+
+    /// Run every enabled canary test case once
+    pub async fn run_all(&self) -> Result<Vec<CanaryStatus>, DomainError> {
+        let query = TestCaseQuery::new().with_canary(true).with_enabled(true);
+        let test_cases = self.test_case_service.list(&query).await?;
+
+        self.health
+            .retain(&test_cases.iter().map(|t| t.id().as_str().to_string()).collect());
+
+        let statuses: Vec<CanaryStatus> = stream::iter(test_cases)
+            .map(|test_case| async move { self.run_one(&test_case).await })
+            .buffer_unordered(DEFAULT_SUITE_CONCURRENCY)
+            .filter_map(|status| async move { status })
+            .collect()
+            .await;
+
+        let failed = statuses.iter().filter(|s| !s.passed).count();
+        info!(total = statuses.len(), failed, "Canary run completed");
+
+        Ok(statuses)
+    }
+
+    async fn run_one(&self, test_case: &TestCase) -> Option<CanaryStatus> {
+        let TestCaseInput::ModelPrompt(input) = test_case.input() else {
+            return None;
+        };
+        let id = test_case.id().as_str();
+
+        let (passed, latency_ms, error) = match self.test_case_service.execute(id).await {
+            Ok(response) => (response.passed, response.execution_time_ms, response.error),
+            Err(e) => (false, 0, Some(e.to_string())),
+        };
+
+        let provider = self.provider_name(&input.model_id).await;
+
+        if let Some(provider) = &provider {
+            // Assertion failures do not indicate an outage, only execution errors do
+            let result = match &error {
+                Some(message) => Err(DomainError::provider(provider.as_str(), message.as_str())),
+                None => Ok(()),
+            };
+            self.notifications.track_provider_result(provider, &result);
+        }
+
+        let status = self.health.record(CanaryRun {
+            test_case_id: id.to_string(),
+            model_id: input.model_id.clone(),
+            provider,
+            passed,
+            latency_ms,
+            error,
+        });
+
+        metrics::gauge!(
+            "llm_canary_degraded",
+            "model" => status.model_id.clone(),
+            "test_case" => status.test_case_id.clone()
+        )
+        .set(if status.degraded { 1.0 } else { 0.0 });
+
+        if !status.passed {
+            warn!(
+                test_case_id = %status.test_case_id,
+                model_id = %status.model_id,
+                consecutive_failures = status.consecutive_failures,
+                degraded = status.degraded,
+                error = ?status.error,
+                "Canary test case failed"
+            );
+        }
+
+        Some(status)
+    }
+
+    async fn provider_name(&self, model_id: &str) -> Option<String> {
+        let model = self.model_service.get(model_id).await.ok().flatten()?;
+        let credential = self
+            .credential_service
+            .get(model.credential_id())
+            .await
+            .ok()
+            .flatten()?;
+        let provider = self
+            .provider_router
+            .get_provider(&model, &credential.to_credential())
+            .await
+            .ok()?;
+
+        Some(provider.provider_name().to_string())
+    }
+}
+
+/// Run the canaries on a fixed interval in the background
+pub fn spawn_canary_runs(runner: Arc<CanaryRunner>, interval: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(e) = runner.run_all().await {
+                warn!(error = %e, "Canary run failed");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(test_case_id: &str, model_id: &str, passed: bool) -> CanaryRun {
+        CanaryRun {
+            test_case_id: test_case_id.to_string(),
+            model_id: model_id.to_string(),
+            provider: Some("openai".to_string()),
+            passed,
+            latency_ms: 120,
+            error: (!passed).then(|| "timeout".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_degrades_after_threshold() {
+        let health = CanaryHealth::new(2);
+
+        let status = health.record(run("canary-1", "gpt-4", false));
+        assert_eq!(status.consecutive_failures, 1);
+        assert!(!status.degraded);
+        assert!(!health.is_degraded("gpt-4"));
+
+        let status = health.record(run("canary-1", "gpt-4", false));
+        assert!(status.degraded);
+        assert!(health.is_degraded("gpt-4"));
+        assert!(!health.is_degraded("claude"));
+        assert_eq!(health.degraded_models(), vec!["gpt-4".to_string()]);
+    }
+
+    #[test]
+    fn test_recovers_on_pass() {
+        let health = CanaryHealth::new(1);
+
+        health.record(run("canary-1", "gpt-4", false));
+        assert!(health.is_degraded("gpt-4"));
+
+        let status = health.record(run("canary-1", "gpt-4", true));
+        assert_eq!(status.consecutive_failures, 0);
+        assert!(!health.is_degraded("gpt-4"));
+        assert!(health.degraded_models().is_empty());
+    }
+
+    #[test]
+    fn test_retain_drops_unscheduled_canaries() {
+        let health = CanaryHealth::new(1);
+        health.record(run("canary-1", "gpt-4", false));
+        health.record(run("canary-2", "claude", true));
+
+        health.retain(&HashSet::from(["canary-2".to_string()]));
+
+        let statuses = health.statuses();
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].test_case_id, "canary-2");
+        assert!(!health.is_degraded("gpt-4"));
+    }
+}
@@ -0,0 +1,7 @@
+//! Dependency probes for readiness checks and canary health of models
+
+mod canary;
+mod probe;
+
+pub use canary::{spawn_canary_runs, CanaryHealth, CanaryRun, CanaryRunner, CanaryStatus};
+pub use probe::{DependencyProbe, DependencyProber};
@@ -0,0 +1,331 @@
+//! Readiness probes of downstream dependencies
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use reqwest::{Client, RequestBuilder};
+use sqlx::PgPool;
+
+use crate::config::HealthConfig;
+use crate::domain::credentials::{CredentialType, StoredCredential};
+use crate::domain::DomainError;
+
+const OPENAI_API_URL: &str = "https://api.openai.com";
+const ANTHROPIC_API_URL: &str = "https://api.anthropic.com";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const AZURE_API_VERSION: &str = "2024-02-01";
+
+/// Outcome of probing one dependency
+#[derive(Debug, Clone)]
+pub struct DependencyProbe {
+    /// Dependency name (`postgres`, `redis` or `provider:<credential id>`)
+    pub name: String,
+    /// Failure reason, none if the dependency answered
+    pub error: Option<String>,
+    pub latency_ms: u64,
+}
+
+impl DependencyProbe {
+    /// Whether the dependency answered
+    pub fn is_healthy(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Probes PostgreSQL, Redis and provider credentials for `/ready`. Without
+/// any dependency configured no probe runs.
+#[derive(Debug, Clone)]
+pub struct DependencyProber {
+    postgres: Option<PgPool>,
+    redis: Option<redis::Client>,
+    probe_providers: bool,
+    http_client: Client,
+    timeout: Duration,
+}
+
+impl Default for DependencyProber {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(5))
+    }
+}
+
+impl DependencyProber {
+    /// Create a prober without dependencies
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            postgres: None,
+            redis: None,
+            probe_providers: false,
+            http_client: Client::new(),
+            timeout,
+        }
+    }
+
+    /// Build the prober of the `[health]` configuration
+    pub fn from_config(
+        config: &HealthConfig,
+        postgres: PgPool,
+        redis_url: Option<&str>,
+    ) -> Result<Self, DomainError> {
+        let mut prober = Self::new(Duration::from_secs(config.probe_timeout_secs.max(1)))
+            .with_provider_probes(config.probe_providers);
+
+        if config.probe_dependencies {
+            prober = prober.with_postgres(postgres);
+
+            if let Some(url) = redis_url {
+                prober = prober.with_redis(url)?;
+            }
+        }
+
+        Ok(prober)
+    }
+
+    /// Probe a PostgreSQL pool (builder pattern)
+    pub fn with_postgres(mut self, pool: PgPool) -> Self {
+        self.postgres = Some(pool);
+        self
+    }
+
+    /// Probe the Redis server at `url` (builder pattern)
+    pub fn with_redis(mut self, url: &str) -> Result<Self, DomainError> {
+        let client = redis::Client::open(url)
+            .map_err(|e| DomainError::configuration(format!("Invalid Redis URL: {}", e)))?;
+
+        self.redis = Some(client);
+        Ok(self)
+    }
+
+    /// List the models of enabled provider credentials (builder pattern)
+    pub fn with_provider_probes(mut self, enabled: bool) -> Self {
+        self.probe_providers = enabled;
+        self
+    }
+
+    /// Whether PostgreSQL or Redis are probed
+    pub fn probes_infrastructure(&self) -> bool {
+        self.postgres.is_some() || self.redis.is_some()
+    }
+
+    /// Whether provider credentials are probed
+    pub fn probes_providers(&self) -> bool {
+        self.probe_providers
+    }
+
+    /// Probe PostgreSQL and Redis, when configured
+    pub async fn probe_infrastructure(&self) -> Vec<DependencyProbe> {
+        let postgres = async {
+            let pool = self.postgres.as_ref()?;
+
+            Some(
+                self.run("postgres", async {
+                    sqlx::query("SELECT 1")
+                        .execute(pool)
+                        .await
+                        .map(|_| ())
+                        .map_err(|e| e.to_string())
+                })
+                .await,
+            )
+        };
+
+        let redis = async {
+            let client = self.redis.as_ref()?;
+
+            Some(
+                self.run("redis", async {
+                    let mut conn = client
+                        .get_multiplexed_async_connection()
+                        .await
+                        .map_err(|e| e.to_string())?;
+
+                    redis::cmd("PING")
+                        .query_async::<String>(&mut conn)
+                        .await
+                        .map(|_| ())
+                        .map_err(|e| e.to_string())
+                })
+                .await,
+            )
+        };
+
+        let (postgres, redis) = tokio::join!(postgres, redis);
+        postgres.into_iter().chain(redis).collect()
+    }
+
+    /// List the models of each enabled OpenAI, Anthropic and Azure OpenAI
+    /// credential. Other credential types are not probed.
+    pub async fn probe_providers(&self, credentials: &[StoredCredential]) -> Vec<DependencyProbe> {
+        if !self.probe_providers {
+            return Vec::new();
+        }
+
+        let probes = credentials
+            .iter()
+            .filter(|credential| credential.is_enabled())
+            .filter_map(|credential| {
+                let request = models_request(&self.http_client, credential)?;
+                let name = format!("provider:{}", credential.id().as_str());
+
+                Some(async move {
+                    self.run(&name, async {
+                        let response = request.send().await.map_err(|e| e.to_string())?;
+
+                        if response.status().is_success() {
+                            Ok(())
+                        } else {
+                            Err(format!("HTTP status {}", response.status().as_u16()))
+                        }
+                    })
+                    .await
+                })
+            });
+
+        futures::future::join_all(probes).await
+    }
+
+    async fn run<F>(&self, name: &str, probe: F) -> DependencyProbe
+    where
+        F: Future<Output = Result<(), String>>,
+    {
+        let start = Instant::now();
+        let error = match tokio::time::timeout(self.timeout, probe).await {
+            Ok(result) => result.err(),
+            Err(_) => Some(format!("Timed out after {}s", self.timeout.as_secs_f64())),
+        };
+
+        DependencyProbe {
+            name: name.to_string(),
+            error,
+            latency_ms: start.elapsed().as_millis() as u64,
+        }
+    }
+}
+
+/// Cheap authenticated request listing the models of a provider credential
+fn models_request(client: &Client, credential: &StoredCredential) -> Option<RequestBuilder> {
+    let base_url = |default: &str| {
+        credential
+            .endpoint()
+            .unwrap_or(default)
+            .trim_end_matches('/')
+            .to_string()
+    };
+
+    match credential.credential_type() {
+        CredentialType::OpenAi => Some(
+            client
+                .get(format!("{}/v1/models", base_url(OPENAI_API_URL)))
+                .bearer_auth(credential.api_key()),
+        ),
+        CredentialType::Anthropic => Some(
+            client
+                .get(format!("{}/v1/models?limit=1", base_url(ANTHROPIC_API_URL)))
+                .header("x-api-key", credential.api_key())
+                .header("anthropic-version", ANTHROPIC_VERSION),
+        ),
+        CredentialType::AzureOpenAi => {
+            let endpoint = credential.endpoint()?.trim_end_matches('/');
+
+            Some(
+                client
+                    .get(format!(
+                        "{}/openai/models?api-version={}",
+                        endpoint, AZURE_API_VERSION
+                    ))
+                    .header("api-key", credential.api_key()),
+            )
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::credentials::CredentialId;
+
+    fn credential(credential_type: CredentialType) -> StoredCredential {
+        StoredCredential::new(
+            CredentialId::new("cred-1").unwrap(),
+            "Credential",
+            credential_type,
+            "sk-test",
+        )
+    }
+
+    #[test]
+    fn test_models_request() {
+        let client = Client::new();
+
+        let openai = models_request(&client, &credential(CredentialType::OpenAi))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(openai.url().as_str(), "https://api.openai.com/v1/models");
+        assert_eq!(openai.headers()["authorization"], "Bearer sk-test");
+
+        let anthropic = models_request(&client, &credential(CredentialType::Anthropic))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            anthropic.url().as_str(),
+            "https://api.anthropic.com/v1/models?limit=1"
+        );
+        assert_eq!(anthropic.headers()["x-api-key"], "sk-test");
+
+        let azure = credential(CredentialType::AzureOpenAi);
+        assert!(models_request(&client, &azure).is_none());
+
+        let azure = models_request(
+            &client,
+            &azure.with_endpoint("https://example.openai.azure.com/"),
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        assert_eq!(
+            azure.url().as_str(),
+            "https://example.openai.azure.com/openai/models?api-version=2024-02-01"
+        );
+
+        assert!(models_request(&client, &credential(CredentialType::Pinecone)).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_probe_providers() {
+        let prober = DependencyProber::new(Duration::from_secs(2));
+        let unreachable = credential(CredentialType::OpenAi).with_endpoint("http://127.0.0.1:1");
+        let disabled = credential(CredentialType::Anthropic).with_enabled(false);
+
+        assert!(prober
+            .probe_providers(std::slice::from_ref(&unreachable))
+            .await
+            .is_empty());
+
+        let probes = prober
+            .with_provider_probes(true)
+            .probe_providers(&[unreachable, disabled])
+            .await;
+
+        assert_eq!(probes.len(), 1);
+        assert_eq!(probes[0].name, "provider:cred-1");
+        assert!(!probes[0].is_healthy());
+    }
+
+    #[tokio::test]
+    async fn test_probe_infrastructure() {
+        let prober = DependencyProber::default();
+        assert!(!prober.probes_infrastructure());
+        assert!(prober.probe_infrastructure().await.is_empty());
+
+        let prober = prober.with_redis("redis://127.0.0.1:1").unwrap();
+        let probes = prober.probe_infrastructure().await;
+
+        assert_eq!(probes.len(), 1);
+        assert_eq!(probes[0].name, "redis");
+        assert!(!probes[0].is_healthy());
+        assert!(DependencyProber::default().with_redis("not a url").is_err());
+    }
+}
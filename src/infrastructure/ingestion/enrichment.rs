@@ -0,0 +1,171 @@
+//! Chunk enrichment through a configured model
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::domain::ingestion::{ChunkEnricher, ChunkEnrichment, ChunkEnrichmentConfig};
+use crate::domain::llm::{LlmRequest, LlmResponseFormat, ProviderResolver};
+use crate::domain::DomainError;
+
+/// Keywords kept per chunk
+const MAX_KEYWORDS: usize = 10;
+
+const ENRICHMENT_PROMPT: &str = "You describe passages of a document for a search index. \
+For every passage, write a title of at most 8 words, a one-sentence summary and up to 8 \
+search keywords, in the language of the passage. Reply with JSON only: \
+{\"chunks\": [{\"index\": <passage index>, \"title\": \"...\", \"summary\": \"...\", \
+\"keywords\": [\"...\"]}]}";
+
+/// Enriches chunks by asking a model about a batch of chunks per call
+#[derive(Debug)]
+pub struct LlmChunkEnricher {
+    models: Arc<dyn ProviderResolver>,
+}
+
+impl LlmChunkEnricher {
+    /// Create an enricher calling models through `models`
+    pub fn new(models: Arc<dyn ProviderResolver>) -> Self {
+        Self { models }
+    }
+}
+
+#[async_trait]
+impl ChunkEnricher for LlmChunkEnricher {
+    async fn enrich(
+        &self,
+        config: &ChunkEnrichmentConfig,
+        chunks: &[String],
+    ) -> Result<Vec<ChunkEnrichment>, DomainError> {
+        let resolved = self.models.resolve_with_model(&config.model_id).await?;
+        let mut enrichments = Vec::with_capacity(chunks.len());
+
+        for batch in chunks.chunks(config.batch_size.max(1)) {
+            let request = LlmRequest::builder()
+                .system(ENRICHMENT_PROMPT)
+                .user(batch_prompt(batch))
+                .temperature(0.0)
+                .response_format(LlmResponseFormat::JsonObject)
+                .build();
+
+            let response = resolved
+                .provider
+                .chat(&resolved.provider_model, request)
+                .await?;
+
+            enrichments.extend(parse_enrichments(
+                response.content().unwrap_or_default(),
+                batch.len(),
+            )?);
+        }
+
+        Ok(enrichments)
+    }
+}
+
+fn batch_prompt(batch: &[String]) -> String {
+    batch
+        .iter()
+        .enumerate()
+        .map(|(index, chunk)| format!("<passage index=\"{}\">\n{}\n</passage>", index, chunk))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[derive(Deserialize)]
+struct EnrichmentReply {
+    chunks: Vec<ReplyChunk>,
+}
+
+#[derive(Deserialize)]
+struct ReplyChunk {
+    index: usize,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    keywords: Vec<String>,
+}
+
+/// Enrichments of a batch from the model reply, empty for passages the
+/// model skipped
+fn parse_enrichments(reply: &str, count: usize) -> Result<Vec<ChunkEnrichment>, DomainError> {
+    // Models sometimes wrap the JSON in a code fence
+    let json = match (reply.find('{'), reply.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &reply[start..=end],
+        _ => reply,
+    };
+    let reply: EnrichmentReply = serde_json::from_str(json).map_err(|e| {
+        DomainError::provider("enrichment", format!("Invalid enrichment reply: {}", e))
+    })?;
+
+    let mut enrichments = vec![ChunkEnrichment::default(); count];
+    for chunk in reply.chunks {
+        let Some(enrichment) = enrichments.get_mut(chunk.index) else {
+            continue;
+        };
+
+        let non_empty = |text: Option<String>| {
+            text.map(|t| t.trim().to_string()).filter(|t| !t.is_empty())
+        };
+        let mut keywords: Vec<String> = Vec::new();
+        for keyword in chunk.keywords {
+            let keyword = keyword.trim().to_string();
+            if !keyword.is_empty() && !keywords.contains(&keyword) {
+                keywords.push(keyword);
+            }
+        }
+        keywords.truncate(MAX_KEYWORDS);
+
+        *enrichment = ChunkEnrichment {
+            title: non_empty(chunk.title),
+            summary: non_empty(chunk.summary),
+            keywords,
+        };
+    }
+
+    Ok(enrichments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::llm::{LlmResponse, Message, MockLlmProvider, StaticProviderResolver};
+
+    #[test]
+    fn test_parse_enrichments() {
+        let reply = "```json\n{\"chunks\": [{\"index\": 1, \"title\": \" Pricing \", \
+                     \"summary\": \"\", \"keywords\": [\"price\", \"price\", \" \"]}, \
+                     {\"index\": 7, \"title\": \"Out of range\"}]}\n```";
+
+        let enrichments = parse_enrichments(reply, 2).unwrap();
+
+        assert_eq!(enrichments[0], ChunkEnrichment::default());
+        assert_eq!(enrichments[1].title.as_deref(), Some("Pricing"));
+        assert_eq!(enrichments[1].summary, None);
+        assert_eq!(enrichments[1].keywords, vec!["price".to_string()]);
+
+        assert!(parse_enrichments("no json here", 1).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_enrich_in_batches() {
+        let reply = r#"{"chunks": [{"index": 0, "title": "T", "summary": "S", "keywords": ["k"]}]}"#;
+        let provider = MockLlmProvider::new("mock").with_response(LlmResponse::new(
+            "id".to_string(),
+            "model".to_string(),
+            Message::assistant(reply),
+        ));
+        let enricher = LlmChunkEnricher::new(Arc::new(StaticProviderResolver::new(Arc::new(
+            provider,
+        ))));
+        let config = ChunkEnrichmentConfig::new("model").with_batch_size(1);
+        let chunks = vec!["first".to_string(), "second".to_string()];
+
+        let enrichments = enricher.enrich(&config, &chunks).await.unwrap();
+
+        assert_eq!(enrichments.len(), 2);
+        assert!(enrichments.iter().all(|e| e.title.as_deref() == Some("T")));
+    }
+}
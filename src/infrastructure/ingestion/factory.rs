@@ -8,23 +8,25 @@ use crate::domain::ingestion::{
 use crate::domain::DomainError;
 
 use super::chunkers::{FixedSizeChunker, ParagraphChunker, RecursiveChunker, SentenceChunker};
-use super::parsers::{HtmlParser, JsonParser, MarkdownParser, PlainTextParser};
+use super::ocr::PdfTools;
+use super::parsers::{HtmlParser, JsonParser, MarkdownParser, OcrParser, PlainTextParser};
 
 /// Factory for creating document parsers
 #[derive(Debug, Default)]
 pub struct ParserFactory;
 
 impl ParserFactory {
-    /// Create a parser for the given type
+    /// Create a parser for the given type; the PDF and image parser has no
+    /// OCR engine, see [`OcrParser::with_ocr`]
     pub fn create(parser_type: ParserType) -> Result<Arc<dyn DocumentParser>, DomainError> {
         match parser_type {
             ParserType::PlainText => Ok(Arc::new(PlainTextParser::new())),
             ParserType::Markdown => Ok(Arc::new(MarkdownParser::new())),
             ParserType::Html => Ok(Arc::new(HtmlParser::new())),
             ParserType::Json => Ok(Arc::new(JsonParser::new())),
-            ParserType::Pdf => Err(DomainError::validation(
-                "PDF parsing is not yet implemented",
-            )),
+            ParserType::Pdf | ParserType::Image => {
+                Ok(Arc::new(OcrParser::new(PdfTools::default())))
+            }
         }
     }
 
@@ -40,7 +42,10 @@ impl ParserFactory {
 
     /// Get a list of all supported file extensions
     pub fn supported_extensions() -> Vec<&'static str> {
-        vec!["txt", "text", "md", "markdown", "html", "htm", "json"]
+        vec![
+            "txt", "text", "md", "markdown", "html", "htm", "json", "pdf", "png", "jpg", "jpeg",
+            "tif", "tiff", "bmp", "gif", "webp",
+        ]
     }
 
     /// Get a list of all supported MIME types
@@ -51,6 +56,13 @@ impl ParserFactory {
             "text/x-markdown",
             "text/html",
             "application/json",
+            "application/pdf",
+            "image/png",
+            "image/jpeg",
+            "image/tiff",
+            "image/bmp",
+            "image/gif",
+            "image/webp",
         ]
     }
 }
@@ -110,9 +122,12 @@ mod tests {
     }
 
     #[test]
-    fn test_parser_factory_pdf_not_implemented() {
-        let result = ParserFactory::create(ParserType::Pdf);
-        assert!(result.is_err());
+    fn test_parser_factory_pdf_and_image() {
+        let parser = ParserFactory::create(ParserType::Pdf).unwrap();
+        assert!(parser.supports_file("scan.pdf"));
+
+        let parser = ParserFactory::create(ParserType::Image).unwrap();
+        assert!(parser.supports_file("scan.tiff"));
     }
 
     #[test]
@@ -4,12 +4,21 @@
 //! and the ingestion pipeline.
 
 pub mod chunkers;
+pub mod enrichment;
 pub mod factory;
+pub mod ocr;
 pub mod parsers;
 pub mod pipeline;
+pub mod upload;
 
 // Re-export parsers
-pub use parsers::{HtmlParser, JsonParser, MarkdownParser, PlainTextParser};
+pub use parsers::{HtmlParser, JsonParser, MarkdownParser, OcrParser, PlainTextParser};
+
+// Re-export chunk enrichment
+pub use enrichment::LlmChunkEnricher;
+
+// Re-export OCR engines
+pub use ocr::{DefaultOcrResolver, PdfTools, TesseractOcr, VisionModelOcr};
 
 // Re-export chunkers
 pub use chunkers::{FixedSizeChunker, ParagraphChunker, RecursiveChunker, SentenceChunker};
@@ -19,3 +28,6 @@ pub use factory::{ChunkerFactory, ParserFactory};
 
 // Re-export pipeline
 pub use pipeline::IngestionPipeline;
+
+// Re-export upload spooling
+pub use upload::{SpooledFile, UploadLimits, UploadSpool};
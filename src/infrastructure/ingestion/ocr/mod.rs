@@ -0,0 +1,54 @@
+//! OCR engines for scanned PDFs and images
+
+mod pdf;
+mod tesseract;
+mod vision;
+
+pub use pdf::PdfTools;
+pub use tesseract::TesseractOcr;
+pub use vision::VisionModelOcr;
+
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::ingestion::{OcrEngine, OcrProvider, OcrProviderResolver};
+use crate::domain::llm::ProviderResolver;
+use crate::domain::DomainError;
+
+/// Resolves OCR engines to the local Tesseract binary or to a configured
+/// vision model
+#[derive(Debug)]
+pub struct DefaultOcrResolver {
+    tesseract_binary: String,
+    models: Arc<dyn ProviderResolver>,
+}
+
+impl DefaultOcrResolver {
+    /// Create a resolver running `tesseract_binary` and calling models
+    /// through `models`
+    pub fn new(tesseract_binary: impl Into<String>, models: Arc<dyn ProviderResolver>) -> Self {
+        Self {
+            tesseract_binary: tesseract_binary.into(),
+            models,
+        }
+    }
+}
+
+#[async_trait]
+impl OcrProviderResolver for DefaultOcrResolver {
+    async fn resolve(&self, engine: &OcrEngine) -> Result<Arc<dyn OcrProvider>, DomainError> {
+        match engine {
+            OcrEngine::Tesseract { language } => Ok(Arc::new(TesseractOcr::new(
+                self.tesseract_binary.clone(),
+                language.clone(),
+            ))),
+            OcrEngine::VisionModel { model_id } => {
+                let resolved = self.models.resolve_with_model(model_id).await?;
+                Ok(Arc::new(VisionModelOcr::new(
+                    resolved.provider,
+                    resolved.provider_model,
+                )))
+            }
+        }
+    }
+}
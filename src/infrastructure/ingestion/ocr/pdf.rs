@@ -0,0 +1,128 @@
+//! PDF text extraction and page rendering with the poppler tools
+
+use std::path::{Path, PathBuf};
+
+use tokio::process::Command;
+
+use crate::domain::DomainError;
+
+/// Paths and settings of the poppler command line tools
+#[derive(Debug, Clone)]
+pub struct PdfTools {
+    /// `pdftotext` binary, extracting the text layer
+    pub pdftotext: String,
+    /// `pdftoppm` binary, rendering pages for OCR
+    pub pdftoppm: String,
+    /// Resolution pages are rendered at
+    pub dpi: u32,
+    /// Directory for the working files
+    pub temp_dir: PathBuf,
+}
+
+impl Default for PdfTools {
+    fn default() -> Self {
+        Self {
+            pdftotext: "pdftotext".to_string(),
+            pdftoppm: "pdftoppm".to_string(),
+            dpi: 300,
+            temp_dir: std::env::temp_dir(),
+        }
+    }
+}
+
+impl PdfTools {
+    /// Text layer of a PDF, empty for scanned documents
+    pub async fn extract_text(&self, pdf: &[u8]) -> Result<String, DomainError> {
+        let workspace = Workspace::create(&self.temp_dir, pdf).await?;
+        let output = run(
+            Command::new(&self.pdftotext)
+                .arg("-layout")
+                .arg(workspace.pdf())
+                .arg("-"),
+            &self.pdftotext,
+        )
+        .await?;
+
+        Ok(String::from_utf8_lossy(&output).into_owned())
+    }
+
+    /// Render every page as a PNG image, in page order
+    pub async fn render_pages(&self, pdf: &[u8]) -> Result<Vec<Vec<u8>>, DomainError> {
+        let workspace = Workspace::create(&self.temp_dir, pdf).await?;
+        run(
+            Command::new(&self.pdftoppm)
+                .args(["-r", &self.dpi.to_string(), "-png"])
+                .arg(workspace.pdf())
+                .arg(workspace.dir.join("page")),
+            &self.pdftoppm,
+        )
+        .await?;
+
+        let mut pages = Vec::new();
+        let mut entries = tokio::fs::read_dir(&workspace.dir).await.map_err(io_error)?;
+        while let Some(entry) = entries.next_entry().await.map_err(io_error)? {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "png") {
+                pages.push(path);
+            }
+        }
+        // pdftoppm zero-pads page numbers to the width of the page count
+        pages.sort();
+
+        let mut images = Vec::with_capacity(pages.len());
+        for page in pages {
+            images.push(tokio::fs::read(page).await.map_err(io_error)?);
+        }
+
+        Ok(images)
+    }
+}
+
+/// Temporary directory holding the PDF, removed when dropped
+struct Workspace {
+    dir: PathBuf,
+}
+
+impl Workspace {
+    async fn create(temp_dir: &Path, pdf: &[u8]) -> Result<Self, DomainError> {
+        let dir = temp_dir.join(format!("pmp-ocr-{}", uuid::Uuid::new_v4()));
+        tokio::fs::create_dir(&dir).await.map_err(io_error)?;
+
+        let workspace = Self { dir };
+        tokio::fs::write(workspace.pdf(), pdf).await.map_err(io_error)?;
+
+        Ok(workspace)
+    }
+
+    fn pdf(&self) -> PathBuf {
+        self.dir.join("document.pdf")
+    }
+}
+
+impl Drop for Workspace {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+async fn run(command: &mut Command, binary: &str) -> Result<Vec<u8>, DomainError> {
+    let output = command
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| DomainError::internal(format!("Failed to run '{}': {}", binary, e)))?;
+
+    if !output.status.success() {
+        return Err(DomainError::validation(format!(
+            "'{}' could not read the PDF: {}",
+            binary,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(output.stdout)
+}
+
+fn io_error(e: std::io::Error) -> DomainError {
+    DomainError::internal(format!("PDF workspace error: {}", e))
+}
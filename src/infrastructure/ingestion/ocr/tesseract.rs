@@ -0,0 +1,65 @@
+//! Tesseract OCR through the command line tool
+
+use async_trait::async_trait;
+use std::process::Stdio;
+
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::domain::ingestion::OcrProvider;
+use crate::domain::DomainError;
+
+/// OCR with a local `tesseract` binary, reading the image from stdin
+#[derive(Debug, Clone)]
+pub struct TesseractOcr {
+    binary: String,
+    language: String,
+}
+
+impl TesseractOcr {
+    /// Create a Tesseract OCR running `binary` with the given languages
+    pub fn new(binary: impl Into<String>, language: impl Into<String>) -> Self {
+        Self {
+            binary: binary.into(),
+            language: language.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl OcrProvider for TesseractOcr {
+    async fn recognize(&self, image: &[u8], _mime_type: &str) -> Result<String, DomainError> {
+        let mut child = Command::new(&self.binary)
+            .args(["stdin", "stdout", "-l", &self.language])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                DomainError::internal(format!("Failed to run '{}': {}", self.binary, e))
+            })?;
+
+        // Dropping stdin closes it, so tesseract sees the end of the image
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(image)
+                .await
+                .map_err(|e| DomainError::internal(format!("Failed to send image to OCR: {}", e)))?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .await
+            .map_err(|e| DomainError::internal(format!("Tesseract failed: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(DomainError::validation(format!(
+                "Tesseract failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
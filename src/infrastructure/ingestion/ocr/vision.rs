@@ -0,0 +1,52 @@
+//! OCR with a vision-capable model
+
+use async_trait::async_trait;
+use base64::Engine as _;
+use std::sync::Arc;
+
+use crate::domain::ingestion::OcrProvider;
+use crate::domain::llm::{ContentPart, LlmProvider, LlmRequest, Message};
+use crate::domain::DomainError;
+
+const TRANSCRIBE_PROMPT: &str = "Transcribe all text in this image exactly as written, \
+keeping the reading order, headings, lists and table rows. Reply with the text only, \
+without comments. Reply with nothing if the image has no text.";
+
+/// OCR asking a vision model to transcribe each page
+#[derive(Debug, Clone)]
+pub struct VisionModelOcr {
+    provider: Arc<dyn LlmProvider>,
+    model: String,
+}
+
+impl VisionModelOcr {
+    /// Create a vision OCR calling `model` (the provider model name)
+    pub fn new(provider: Arc<dyn LlmProvider>, model: impl Into<String>) -> Self {
+        Self {
+            provider,
+            model: model.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl OcrProvider for VisionModelOcr {
+    async fn recognize(&self, image: &[u8], mime_type: &str) -> Result<String, DomainError> {
+        let request = LlmRequest::builder()
+            .message(Message::user_with_parts(vec![
+                ContentPart::Text {
+                    text: TRANSCRIBE_PROMPT.to_string(),
+                },
+                ContentPart::ImageBase64 {
+                    data: base64::engine::general_purpose::STANDARD.encode(image),
+                    media_type: mime_type.to_string(),
+                },
+            ]))
+            .temperature(0.0)
+            .build();
+
+        let response = self.provider.chat(&self.model, request).await?;
+
+        Ok(response.content().unwrap_or_default().to_string())
+    }
+}
@@ -3,9 +3,11 @@
 mod html;
 mod json;
 mod markdown;
+mod ocr;
 mod plain_text;
 
 pub use html::HtmlParser;
 pub use json::JsonParser;
 pub use markdown::MarkdownParser;
+pub use ocr::OcrParser;
 pub use plain_text::PlainTextParser;
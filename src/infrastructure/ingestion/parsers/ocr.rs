@@ -0,0 +1,197 @@
+//! PDF and image parser with OCR
+
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::ingestion::{
+    DocumentMetadata, DocumentParser, OcrEngine, OcrProvider, ParsedDocument, ParserContent,
+    ParserInput,
+};
+use crate::domain::DomainError;
+use crate::infrastructure::ingestion::ocr::PdfTools;
+
+/// Text layers shorter than this (ignoring whitespace) are treated as a
+/// scanned PDF, covering page numbers or stamps added by scanners
+const MIN_TEXT_LAYER_CHARS: usize = 16;
+
+/// Parser for PDFs and images
+///
+/// PDFs are read from their text layer; PDFs without one and images are
+/// passed through the OCR engine, page by page.
+#[derive(Debug, Clone)]
+pub struct OcrParser {
+    pdf: PdfTools,
+    ocr: Option<(&'static str, Arc<dyn OcrProvider>)>,
+}
+
+impl OcrParser {
+    /// Create a parser reading only PDFs with a text layer
+    pub fn new(pdf: PdfTools) -> Self {
+        Self { pdf, ocr: None }
+    }
+
+    /// OCR scanned PDFs and images with the engine's provider
+    pub fn with_ocr(mut self, engine: &OcrEngine, provider: Arc<dyn OcrProvider>) -> Self {
+        self.ocr = Some((engine.name(), provider));
+        self
+    }
+
+    fn ocr(&self) -> Result<(&'static str, &dyn OcrProvider), DomainError> {
+        self.ocr
+            .as_ref()
+            .map(|(name, provider)| (*name, provider.as_ref()))
+            .ok_or_else(|| {
+                DomainError::validation(
+                    "Scanned PDFs and images need an OCR engine, none is configured",
+                )
+            })
+    }
+
+    async fn parse_pdf(&self, pdf: &[u8]) -> Result<(String, DocumentMetadata), DomainError> {
+        let text = self.pdf.extract_text(pdf).await?;
+        let metadata = DocumentMetadata::new().with_mime_type("application/pdf");
+
+        let text_chars = text.chars().filter(|c| !c.is_whitespace()).count();
+        if text_chars >= MIN_TEXT_LAYER_CHARS {
+            return Ok((text, metadata.with_custom("ocr", serde_json::json!(false))));
+        }
+
+        let (engine, ocr) = self.ocr()?;
+        let pages = self.pdf.render_pages(pdf).await?;
+        let mut texts = Vec::with_capacity(pages.len());
+        for page in &pages {
+            texts.push(ocr.recognize(page, "image/png").await?.trim().to_string());
+        }
+
+        let metadata = metadata
+            .with_custom("ocr", serde_json::json!(true))
+            .with_custom("ocr_engine", serde_json::json!(engine))
+            .with_custom("page_count", serde_json::json!(pages.len()));
+
+        Ok((texts.join("\n\n"), metadata))
+    }
+
+    async fn parse_image(&self, image: &[u8]) -> Result<(String, DocumentMetadata), DomainError> {
+        let mime_type = image_mime_type(image).ok_or_else(|| {
+            DomainError::validation(
+                "Unsupported image format, expected PNG, JPEG, TIFF, BMP, GIF or WebP",
+            )
+        })?;
+
+        let (engine, ocr) = self.ocr()?;
+        let text = ocr.recognize(image, mime_type).await?;
+
+        let metadata = DocumentMetadata::new()
+            .with_mime_type(mime_type)
+            .with_custom("ocr", serde_json::json!(true))
+            .with_custom("ocr_engine", serde_json::json!(engine));
+
+        Ok((text.trim().to_string(), metadata))
+    }
+}
+
+#[async_trait]
+impl DocumentParser for OcrParser {
+    fn supported_extensions(&self) -> &[&str] {
+        &["pdf", "png", "jpg", "jpeg", "tif", "tiff", "bmp", "gif", "webp"]
+    }
+
+    fn supported_mime_types(&self) -> &[&str] {
+        &[
+            "application/pdf",
+            "image/png",
+            "image/jpeg",
+            "image/tiff",
+            "image/bmp",
+            "image/gif",
+            "image/webp",
+        ]
+    }
+
+    async fn parse(&self, input: ParserInput) -> Result<ParsedDocument, DomainError> {
+        let bytes = match input.content {
+            ParserContent::Bytes(bytes) => bytes,
+            ParserContent::Text(text) => text.into_bytes(),
+        };
+
+        let (content, mut metadata) = if bytes.starts_with(b"%PDF") {
+            self.parse_pdf(&bytes).await?
+        } else {
+            self.parse_image(&bytes).await?
+        };
+
+        if let Some(filename) = input.filename {
+            metadata = metadata.with_source(filename);
+        }
+
+        for (key, value) in input.metadata {
+            metadata = metadata.with_custom(key, value);
+        }
+
+        Ok(ParsedDocument::new(content, metadata))
+    }
+}
+
+/// MIME type of an image, from its signature
+fn image_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(b"\xff\xd8\xff") {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"II*\0") || bytes.starts_with(b"MM\0*") {
+        Some("image/tiff")
+    } else if bytes.starts_with(b"BM") {
+        Some("image/bmp")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct EchoOcr;
+
+    #[async_trait]
+    impl OcrProvider for EchoOcr {
+        async fn recognize(&self, image: &[u8], mime_type: &str) -> Result<String, DomainError> {
+            Ok(format!(" {} bytes of {}\n", image.len(), mime_type))
+        }
+    }
+
+    fn tesseract_parser() -> OcrParser {
+        OcrParser::new(PdfTools::default())
+            .with_ocr(&OcrEngine::tesseract("eng"), Arc::new(EchoOcr))
+    }
+
+    #[tokio::test]
+    async fn test_parse_image_with_ocr() {
+        let parser = tesseract_parser();
+        let input =
+            ParserInput::from_bytes(b"\x89PNG\r\n\x1a\nrest".to_vec()).with_filename("scan.png");
+
+        let result = parser.parse(input).await.unwrap();
+
+        assert_eq!(result.content, "12 bytes of image/png");
+        assert_eq!(result.metadata.mime_type.as_deref(), Some("image/png"));
+        assert_eq!(result.metadata.source.as_deref(), Some("scan.png"));
+        assert_eq!(result.metadata.custom["ocr_engine"], "tesseract");
+    }
+
+    #[tokio::test]
+    async fn test_parse_image_requires_ocr_and_known_format() {
+        let parser = OcrParser::new(PdfTools::default());
+        let input = ParserInput::from_bytes(b"\xff\xd8\xff\xe0".to_vec());
+        assert!(parser.parse(input).await.is_err());
+
+        let parser = tesseract_parser();
+        let input = ParserInput::from_bytes(b"not an image".to_vec());
+        assert!(parser.parse(input).await.is_err());
+    }
+}
@@ -4,15 +4,18 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::domain::ingestion::{
-    detect_parser_from_filename, BatchIngestionResult, Chunk, ChunkingConfig, ChunkingStrategy,
-    ChunkingType, DocumentParser, IngestionConfig, IngestionError, IngestionResult, ParserInput,
-    ParserType,
+    detect_parser_from_filename, BatchIngestionResult, Chunk, ChunkEnricher, ChunkEnrichment,
+    ChunkingConfig, ChunkingStrategy,
+    ChunkingType, DocumentParser, IngestionConfig, IngestionError, IngestionResult,
+    OcrProviderResolver, ParserInput, ParserType,
 };
 use crate::domain::knowledge_base::{Document, KnowledgeBaseProvider};
 use crate::domain::DomainError;
+use tracing::warn;
 
 use super::chunkers::FixedSizeChunker;
-use super::parsers::PlainTextParser;
+use super::ocr::PdfTools;
+use super::parsers::{OcrParser, PlainTextParser};
 
 /// Ingestion pipeline for processing documents into knowledge bases
 #[derive(Debug)]
@@ -21,12 +24,33 @@ where
     K: KnowledgeBaseProvider,
 {
     knowledge_base: Arc<K>,
+    ocr_resolver: Option<Arc<dyn OcrProviderResolver>>,
+    pdf_tools: PdfTools,
+    enricher: Option<Arc<dyn ChunkEnricher>>,
 }
 
 impl<K: KnowledgeBaseProvider> IngestionPipeline<K> {
     /// Create a new ingestion pipeline
     pub fn new(knowledge_base: Arc<K>) -> Self {
-        Self { knowledge_base }
+        Self {
+            knowledge_base,
+            ocr_resolver: None,
+            pdf_tools: PdfTools::default(),
+            enricher: None,
+        }
+    }
+
+    /// OCR scanned PDFs and images with the engine of `IngestionConfig.ocr`
+    pub fn with_ocr(mut self, resolver: Arc<dyn OcrProviderResolver>, pdf_tools: PdfTools) -> Self {
+        self.ocr_resolver = Some(resolver);
+        self.pdf_tools = pdf_tools;
+        self
+    }
+
+    /// Enrich chunks as configured by `IngestionConfig.enrichment`
+    pub fn with_chunk_enricher(mut self, enricher: Arc<dyn ChunkEnricher>) -> Self {
+        self.enricher = Some(enricher);
+        self
     }
 
     /// Ingest a single document
@@ -37,7 +61,7 @@ impl<K: KnowledgeBaseProvider> IngestionPipeline<K> {
     ) -> Result<IngestionResult, DomainError> {
         let document_id = self.generate_document_id(&input, config);
 
-        let parser = self.get_parser(&input, config)?;
+        let parser = self.get_parser(&input, config).await?;
         let parsed = match parser.parse(input).await {
             Ok(p) => p,
             Err(e) => {
@@ -69,9 +93,11 @@ impl<K: KnowledgeBaseProvider> IngestionPipeline<K> {
             return Ok(IngestionResult::success(&document_id, 0));
         }
 
+        let enrichments = self.enrich_chunks(&chunks, config).await?;
         let documents = self.create_documents(
             &document_id,
             &chunks,
+            &enrichments,
             &parsed.metadata.to_json_map(),
             &config.metadata,
         );
@@ -147,7 +173,7 @@ impl<K: KnowledgeBaseProvider> IngestionPipeline<K> {
         uuid::Uuid::new_v4().to_string()
     }
 
-    fn get_parser(
+    async fn get_parser(
         &self,
         input: &ParserInput,
         config: &IngestionConfig,
@@ -163,9 +189,15 @@ impl<K: KnowledgeBaseProvider> IngestionPipeline<K> {
             ParserType::Markdown => Ok(Box::new(super::parsers::MarkdownParser::new())),
             ParserType::Html => Ok(Box::new(super::parsers::HtmlParser::new())),
             ParserType::Json => Ok(Box::new(super::parsers::JsonParser::new())),
-            ParserType::Pdf => Err(DomainError::validation(
-                "PDF parsing is not yet implemented",
-            )),
+            ParserType::Pdf | ParserType::Image => {
+                let mut parser = OcrParser::new(self.pdf_tools.clone());
+
+                if let (Some(resolver), Some(engine)) = (&self.ocr_resolver, &config.ocr) {
+                    parser = parser.with_ocr(engine, resolver.resolve(engine).await?);
+                }
+
+                Ok(Box::new(parser))
+            }
         }
     }
 
@@ -178,16 +210,50 @@ impl<K: KnowledgeBaseProvider> IngestionPipeline<K> {
         }
     }
 
+    /// Enrichment of each chunk, empty when not configured or when the
+    /// enricher fails
+    async fn enrich_chunks(
+        &self,
+        chunks: &[Chunk],
+        config: &IngestionConfig,
+    ) -> Result<Vec<ChunkEnrichment>, DomainError> {
+        let unenriched = vec![ChunkEnrichment::default(); chunks.len()];
+
+        let Some(enrichment) = &config.enrichment else {
+            return Ok(unenriched);
+        };
+        enrichment.validate()?;
+        let enricher = self
+            .enricher
+            .as_ref()
+            .ok_or_else(|| DomainError::validation("Chunk enrichment is not available"))?;
+
+        let contents: Vec<String> = chunks.iter().map(|c| c.content.clone()).collect();
+        match enricher.enrich(enrichment, &contents).await {
+            Ok(enrichments) if enrichments.len() == chunks.len() => Ok(enrichments),
+            Ok(_) => {
+                warn!("Chunk enrichment count mismatch, storing chunks without enrichment");
+                Ok(unenriched)
+            }
+            Err(e) => {
+                warn!(error = %e, "Chunk enrichment failed, storing chunks without enrichment");
+                Ok(unenriched)
+            }
+        }
+    }
+
     fn create_documents(
         &self,
         document_id: &str,
         chunks: &[Chunk],
+        enrichments: &[ChunkEnrichment],
         doc_metadata: &HashMap<String, serde_json::Value>,
         custom_metadata: &HashMap<String, serde_json::Value>,
     ) -> Vec<Document> {
         chunks
             .iter()
-            .map(|chunk| {
+            .zip(enrichments)
+            .map(|(chunk, enrichment)| {
                 let chunk_id = format!("{}_chunk_{}", document_id, chunk.metadata.chunk_index);
 
                 let mut metadata = doc_metadata.clone();
@@ -196,6 +262,8 @@ impl<K: KnowledgeBaseProvider> IngestionPipeline<K> {
                     metadata.insert(key.clone(), value.clone());
                 }
 
+                enrichment.apply_to(&mut metadata);
+
                 for (key, value) in custom_metadata {
                     metadata.insert(key.clone(), value.clone());
                 }
@@ -247,6 +315,52 @@ mod tests {
         assert_eq!(result.chunks_created, 1);
     }
 
+    #[derive(Debug)]
+    struct TitleEnricher;
+
+    #[async_trait::async_trait]
+    impl ChunkEnricher for TitleEnricher {
+        async fn enrich(
+            &self,
+            _config: &crate::domain::ingestion::ChunkEnrichmentConfig,
+            chunks: &[String],
+        ) -> Result<Vec<ChunkEnrichment>, DomainError> {
+            Ok(chunks
+                .iter()
+                .map(|chunk| ChunkEnrichment {
+                    title: Some(format!("About {}", chunk)),
+                    ..Default::default()
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ingest_with_enrichment() {
+        use crate::domain::ingestion::ChunkEnrichmentConfig;
+
+        let kb = create_mock_kb();
+        let config = IngestionConfig::new()
+            .with_source_id("doc")
+            .with_enrichment(ChunkEnrichmentConfig::new("gpt-4o-mini"));
+
+        let result = IngestionPipeline::new(kb.clone())
+            .ingest(ParserInput::from_text("Refunds"), &config)
+            .await;
+        assert!(result.is_err());
+
+        let pipeline =
+            IngestionPipeline::new(kb.clone()).with_chunk_enricher(Arc::new(TitleEnricher));
+        let result = pipeline
+            .ingest(ParserInput::from_text("Refunds"), &config)
+            .await
+            .unwrap();
+        assert_eq!(result.chunks_created, 1);
+
+        let stored = kb.list_by_source("doc").await.unwrap();
+        assert_eq!(stored[0].metadata["chunk_title"], "About Refunds");
+    }
+
     #[tokio::test]
     async fn test_ingest_with_source_id() {
         let kb = create_mock_kb();
@@ -0,0 +1,222 @@
+//! Spooling of uploaded documents to temporary files
+//!
+//! Batch uploads are written to disk chunk by chunk as they arrive, so a
+//! large file never sits whole in memory and a slow disk slows the upload
+//! down instead of buffering it. Each file is checked against the size limit
+//! and, unless it is a binary document (PDF or image), for valid UTF-8 while
+//! it is written.
+
+use std::path::PathBuf;
+
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+
+use crate::domain::DomainError;
+
+/// Limits of batch document uploads
+#[derive(Debug, Clone)]
+pub struct UploadLimits {
+    /// Largest accepted file, in bytes
+    pub max_file_bytes: u64,
+    /// Files accepted per batch, further files are rejected
+    pub max_files: usize,
+    /// Directory the files are spooled to until they are ingested
+    pub temp_dir: PathBuf,
+}
+
+impl Default for UploadLimits {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: 50 * 1024 * 1024,
+            max_files: 100,
+            temp_dir: std::env::temp_dir(),
+        }
+    }
+}
+
+/// An uploaded file on disk, removed when dropped
+#[derive(Debug)]
+pub struct SpooledFile {
+    path: PathBuf,
+    size: u64,
+}
+
+impl SpooledFile {
+    /// Size of the file in bytes
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Read the file as text, removing it afterwards
+    pub async fn into_text(self) -> Result<String, DomainError> {
+        tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(|e| DomainError::internal(format!("Failed to read uploaded file: {}", e)))
+    }
+
+    /// Read the file as bytes, removing it afterwards
+    pub async fn into_bytes(self) -> Result<Vec<u8>, DomainError> {
+        tokio::fs::read(&self.path)
+            .await
+            .map_err(|e| DomainError::internal(format!("Failed to read uploaded file: {}", e)))
+    }
+}
+
+impl Drop for SpooledFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// A file being uploaded
+#[derive(Debug)]
+pub struct UploadSpool {
+    file: File,
+    spooled: SpooledFile,
+    max_bytes: u64,
+    /// Whether the file may be binary, skipping the UTF-8 check
+    binary: bool,
+    /// Trailing bytes of a UTF-8 sequence split across chunks
+    utf8_tail: Vec<u8>,
+}
+
+impl UploadSpool {
+    /// Start spooling a file to the temporary directory
+    pub async fn create(limits: &UploadLimits) -> Result<Self, DomainError> {
+        let path = limits
+            .temp_dir
+            .join(format!("pmp-upload-{}", uuid::Uuid::new_v4()));
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .await
+            .map_err(|e| DomainError::internal(format!("Failed to create upload file: {}", e)))?;
+
+        Ok(Self {
+            file,
+            spooled: SpooledFile { path, size: 0 },
+            max_bytes: limits.max_file_bytes,
+            binary: false,
+            utf8_tail: Vec::new(),
+        })
+    }
+
+    /// Accept binary content, for PDFs and images
+    pub fn binary(mut self) -> Self {
+        self.binary = true;
+        self
+    }
+
+    /// Append a chunk, failing once the file exceeds the size limit or a
+    /// text file is not UTF-8
+    pub async fn write(&mut self, chunk: &[u8]) -> Result<(), DomainError> {
+        self.spooled.size += chunk.len() as u64;
+        if self.spooled.size > self.max_bytes {
+            return Err(DomainError::validation(format!(
+                "File exceeds the upload limit of {} bytes",
+                self.max_bytes
+            )));
+        }
+
+        if !self.binary {
+            self.check_utf8(chunk)?;
+        }
+
+        self.file
+            .write_all(chunk)
+            .await
+            .map_err(|e| DomainError::internal(format!("Failed to write upload file: {}", e)))
+    }
+
+    /// Finish the upload
+    pub async fn finish(mut self) -> Result<SpooledFile, DomainError> {
+        if !self.utf8_tail.is_empty() {
+            return Err(not_utf8());
+        }
+        if self.spooled.size == 0 {
+            return Err(DomainError::validation("File is empty"));
+        }
+
+        self.file
+            .flush()
+            .await
+            .map_err(|e| DomainError::internal(format!("Failed to write upload file: {}", e)))?;
+
+        Ok(self.spooled)
+    }
+
+    fn check_utf8(&mut self, chunk: &[u8]) -> Result<(), DomainError> {
+        let mut bytes = std::mem::take(&mut self.utf8_tail);
+        bytes.extend_from_slice(chunk);
+
+        match std::str::from_utf8(&bytes) {
+            Ok(_) => Ok(()),
+            // A sequence cut at the end of the chunk continues in the next one
+            Err(e) if e.error_len().is_none() => {
+                self.utf8_tail = bytes[e.valid_up_to()..].to_vec();
+                Ok(())
+            }
+            Err(_) => Err(not_utf8()),
+        }
+    }
+}
+
+fn not_utf8() -> DomainError {
+    DomainError::validation("File is not UTF-8 text")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(max_file_bytes: u64) -> UploadLimits {
+        UploadLimits {
+            max_file_bytes,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spool_text_across_chunks() {
+        let text = "Grüße aus Köln";
+        let bytes = text.as_bytes();
+        // Split inside the two-byte "ü"
+        let split = text.find('ü').unwrap() + 1;
+
+        let mut spool = UploadSpool::create(&limits(1024)).await.unwrap();
+        spool.write(&bytes[..split]).await.unwrap();
+        spool.write(&bytes[split..]).await.unwrap();
+        let file = spool.finish().await.unwrap();
+
+        assert_eq!(file.size(), bytes.len() as u64);
+        let path = file.path.clone();
+        assert_eq!(file.into_text().await.unwrap(), text);
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_spool_rejects_large_binary_and_empty_files() {
+        let mut spool = UploadSpool::create(&limits(8)).await.unwrap();
+        spool.write(b"12345").await.unwrap();
+        assert!(spool.write(b"6789").await.is_err());
+        let path = spool.spooled.path.clone();
+        drop(spool);
+        assert!(!path.exists());
+
+        let mut spool = UploadSpool::create(&limits(8)).await.unwrap();
+        assert!(spool.write(&[0x66, 0xff, 0x66]).await.is_err());
+
+        let mut spool = UploadSpool::create(&limits(8)).await.unwrap();
+        spool.write(&[0xc3]).await.unwrap();
+        assert!(spool.finish().await.is_err());
+
+        let spool = UploadSpool::create(&limits(8)).await.unwrap();
+        assert!(spool.finish().await.is_err());
+
+        let mut spool = UploadSpool::create(&limits(8)).await.unwrap().binary();
+        spool.write(&[0x25, 0xff, 0xc3]).await.unwrap();
+        let file = spool.finish().await.unwrap();
+        assert_eq!(file.into_bytes().await.unwrap(), vec![0x25, 0xff, 0xc3]);
+    }
+}
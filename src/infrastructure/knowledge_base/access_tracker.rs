@@ -0,0 +1,102 @@
+//! Knowledge base access statistics
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::domain::knowledge_base::{KnowledgeBaseAccessStats, KnowledgeBaseId, SearchResult};
+use crate::domain::storage::Storage;
+use crate::domain::DomainError;
+use crate::infrastructure::background::spawn_tracked;
+
+/// Counts the searches of knowledge bases and the documents they retrieve
+#[derive(Debug)]
+pub struct KnowledgeBaseAccessTracker {
+    storage: Arc<dyn Storage<KnowledgeBaseAccessStats>>,
+    /// Serializes the read-modify-write of the statistics
+    write_lock: Mutex<()>,
+}
+
+impl KnowledgeBaseAccessTracker {
+    /// Create a tracker keeping the statistics in `storage`
+    pub fn new(storage: Arc<dyn Storage<KnowledgeBaseAccessStats>>) -> Self {
+        Self {
+            storage,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Record a search in the background, without delaying the caller
+    pub fn track(self: &Arc<Self>, kb_id: &str, query: &str, results: &[SearchResult]) {
+        let tracker = self.clone();
+        let kb_id = kb_id.to_string();
+        let query = query.to_string();
+        let results = results.to_vec();
+
+        spawn_tracked(async move {
+            if let Err(e) = tracker.record(&kb_id, &query, &results).await {
+                warn!(kb_id = %kb_id, error = %e, "Failed to record knowledge base access");
+            }
+        });
+    }
+
+    /// Record a search and the documents it retrieved
+    pub async fn record(
+        &self,
+        kb_id: &str,
+        query: &str,
+        results: &[SearchResult],
+    ) -> Result<(), DomainError> {
+        let id = parse_id(kb_id)?;
+        let _guard = self.write_lock.lock().await;
+
+        let mut stats = self
+            .storage
+            .get(&id)
+            .await?
+            .unwrap_or_else(|| KnowledgeBaseAccessStats::new(id));
+        stats.record_search(query, results, Utc::now());
+        self.storage.save(stats).await?;
+
+        Ok(())
+    }
+
+    /// Statistics of a knowledge base, empty when it was never searched
+    pub async fn stats(&self, kb_id: &str) -> Result<KnowledgeBaseAccessStats, DomainError> {
+        let id = parse_id(kb_id)?;
+
+        Ok(self
+            .storage
+            .get(&id)
+            .await?
+            .unwrap_or_else(|| KnowledgeBaseAccessStats::new(id)))
+    }
+}
+
+fn parse_id(kb_id: &str) -> Result<KnowledgeBaseId, DomainError> {
+    KnowledgeBaseId::new(kb_id).map_err(|e| DomainError::validation(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::storage::InMemoryStorage;
+
+    #[tokio::test]
+    async fn test_record_and_read_stats() {
+        let tracker = KnowledgeBaseAccessTracker::new(Arc::new(InMemoryStorage::new()));
+        let result = SearchResult::new("chunk-1", "content", 0.9).with_source("faq.md");
+
+        tracker.record("docs", "refunds", &[result]).await.unwrap();
+        tracker.record("docs", "nothing", &[]).await.unwrap();
+
+        let stats = tracker.stats("docs").await.unwrap();
+        assert_eq!(stats.searches, 2);
+        assert_eq!(stats.zero_hit_searches, 1);
+        assert_eq!(stats.documents["faq.md"].retrievals, 1);
+
+        assert_eq!(tracker.stats("other").await.unwrap().searches, 0);
+    }
+}
@@ -217,10 +217,12 @@ impl KnowledgeBaseProvider for AwsKnowledgeBase {
         let mut vector_config_builder = KnowledgeBaseVectorSearchConfiguration::builder()
             .number_of_results(params.top_k as i32);
 
-        if let Some(filter) = &params.filter {
-            if let Some(retrieval_filter) = self.build_retrieval_filter(filter) {
-                vector_config_builder = vector_config_builder.filter(retrieval_filter);
-            }
+        // Bedrock filters cannot match absent attributes, so document access
+        // is checked on the results instead
+        if let Some(filter) = &params.namespaced_filter()
+            && let Some(retrieval_filter) = self.build_retrieval_filter(filter)
+        {
+            vector_config_builder = vector_config_builder.filter(retrieval_filter);
         }
 
         let vector_config = vector_config_builder.build();
@@ -303,6 +305,10 @@ impl KnowledgeBaseProvider for AwsKnowledgeBase {
             results.push(result);
         }
 
+        if let Some(caller) = &params.caller {
+            results.retain(|r| caller.can_read(&r.metadata));
+        }
+
         Ok(results)
     }
 
@@ -342,6 +348,17 @@ impl KnowledgeBaseProvider for AwsKnowledgeBase {
         ))
     }
 
+    async fn list_by_filter(
+        &self,
+        _filter: MetadataFilter,
+    ) -> Result<Vec<SearchResult>, DomainError> {
+        Err(DomainError::knowledge_base(
+            "AWS Knowledge Base does not support listing documents by metadata. \
+             Use AWS console or S3 browser instead."
+                .to_string(),
+        ))
+    }
+
     async fn get_document(&self, _id: &str) -> Result<Option<SearchResult>, DomainError> {
         // AWS Knowledge Bases don't provide direct document access
         Err(DomainError::knowledge_base(
@@ -54,14 +54,15 @@ impl KnowledgeBaseProvider for InMemoryKnowledgeBaseProvider {
         let docs = self.documents.read().await;
         let query_lower = params.query.to_lowercase();
 
+        let filter = params.scoped_filter();
+
         // Simple text-based search (substring matching) with optional metadata filtering
         let results: Vec<SearchResult> = docs
             .iter()
             .filter(|doc| doc.content.to_lowercase().contains(&query_lower))
             .filter(|doc| {
                 // Apply metadata filter if provided
-                params
-                    .filter
+                filter
                     .as_ref()
                     .map(|f| matches_filter(doc, f))
                     .unwrap_or(true)
@@ -133,6 +134,30 @@ impl KnowledgeBaseProvider for InMemoryKnowledgeBaseProvider {
         Ok(DeleteDocumentsResult::new(deleted, 0))
     }
 
+    async fn list_by_filter(&self, filter: MetadataFilter) -> Result<Vec<SearchResult>, DomainError> {
+        let docs = self.documents.read().await;
+
+        let results: Vec<SearchResult> = docs
+            .iter()
+            .filter(|doc| matches_filter(doc, &filter))
+            .map(|doc| {
+                let mut result = SearchResult::new(&doc.id, &doc.content, 1.0);
+
+                for (key, value) in &doc.metadata {
+                    result = result.with_metadata(key, value.clone());
+                }
+
+                if let Some(src) = &doc.source {
+                    result = result.with_source(src);
+                }
+
+                result
+            })
+            .collect();
+
+        Ok(results)
+    }
+
     async fn get_document(&self, id: &str) -> Result<Option<SearchResult>, DomainError> {
         let docs = self.documents.read().await;
 
@@ -345,6 +370,12 @@ fn matches_condition(doc: &StoredDoc, condition: &FilterCondition) -> bool {
         }),
         FilterOperator::Exists => doc_value.is_some(),
         FilterOperator::NotExists => doc_value.is_none(),
+        FilterOperator::ContainsAny => match (doc_value, &condition.value) {
+            (Some(serde_json::Value::Array(items)), Some(FilterValue::List(values))) => items
+                .iter()
+                .any(|item| values.iter().any(|val| compare_eq(Some(item), val))),
+            _ => false,
+        },
     }
 }
 
@@ -549,4 +580,90 @@ mod tests {
                 .unwrap_or(false)
         }));
     }
+
+    #[tokio::test]
+    async fn test_search_within_namespace() {
+        let id = KnowledgeBaseId::new("test-kb").unwrap();
+        let provider = InMemoryKnowledgeBaseProvider::new(id);
+
+        let docs = vec![
+            Document::new("doc1", "Rust install guide")
+                .with_metadata("namespace", serde_json::json!("v1"))
+                .with_metadata("category", serde_json::json!("setup")),
+            Document::new("doc2", "Rust install guide")
+                .with_metadata("namespace", serde_json::json!("v2"))
+                .with_metadata("category", serde_json::json!("setup")),
+            Document::new("doc3", "Rust release notes")
+                .with_metadata("namespace", serde_json::json!("v2")),
+            Document::new("doc4", "Rust glossary"),
+        ];
+
+        provider.add_documents(docs).await.unwrap();
+
+        let results = provider
+            .search(SearchParams::new("Rust").with_namespace("v2"))
+            .await
+            .unwrap();
+        let mut ids: Vec<&str> = results.iter().map(|r| r.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["doc2", "doc3"]);
+
+        // The namespace combines with the metadata filter
+        use crate::domain::knowledge_base::FilterBuilder;
+
+        let filter = FilterBuilder::new()
+            .eq("category", "setup")
+            .build()
+            .expect("filter should be present");
+
+        let params = SearchParams::new("Rust")
+            .with_filter(filter)
+            .with_namespace("v1");
+        let results = provider.search(params).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "doc1");
+    }
+
+    #[tokio::test]
+    async fn test_search_as_caller() {
+        use crate::domain::knowledge_base::KnowledgeBaseCaller;
+
+        let id = KnowledgeBaseId::new("test-kb").unwrap();
+        let provider = InMemoryKnowledgeBaseProvider::new(id);
+
+        let docs = vec![
+            Document::new("doc1", "Rust handbook"),
+            Document::new("doc2", "Rust handbook")
+                .with_metadata("allowed_teams", serde_json::json!(["support"])),
+            Document::new("doc3", "Rust handbook")
+                .with_metadata("allowed_teams", serde_json::json!(["sales"])),
+            Document::new("doc4", "Rust handbook")
+                .with_metadata("allowed_roles", serde_json::json!(["manager"])),
+        ];
+
+        provider.add_documents(docs).await.unwrap();
+
+        let search = |caller: KnowledgeBaseCaller| {
+            let provider = &provider;
+            async move {
+                let results = provider
+                    .search(SearchParams::new("Rust").with_caller(caller))
+                    .await
+                    .unwrap();
+                let mut ids: Vec<String> = results.into_iter().map(|r| r.id).collect();
+                ids.sort();
+                ids
+            }
+        };
+
+        assert_eq!(search(KnowledgeBaseCaller::new("support")).await, vec!["doc1", "doc2"]);
+        assert_eq!(
+            search(KnowledgeBaseCaller::new("sales").with_roles(["manager"])).await,
+            vec!["doc1", "doc3", "doc4"]
+        );
+
+        // Searches without a caller see every document
+        let results = provider.search(SearchParams::new("Rust")).await.unwrap();
+        assert_eq!(results.len(), 4);
+    }
 }
@@ -0,0 +1,251 @@
+//! Live and persisted progress of batch ingestions
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::json;
+use tokio::sync::{broadcast, Mutex};
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use crate::api::state::OperationServiceTrait;
+use crate::domain::ingestion::{IngestionProgress, IngestionProgressEvent};
+use crate::domain::DomainError;
+
+/// Updates kept for subscribers that fall behind
+const LIVE_UPDATE_CAPACITY: usize = 1024;
+
+/// Change to the progress of a batch ingestion
+#[derive(Debug, Clone)]
+pub enum IngestionUpdate {
+    /// A document reached a stage
+    Document(IngestionProgressEvent),
+    /// Every document of the batch is stored or failed
+    Finished {
+        operation_id: String,
+        progress: IngestionProgress,
+    },
+}
+
+impl IngestionUpdate {
+    pub fn operation_id(&self) -> &str {
+        match self {
+            Self::Document(event) => &event.operation_id,
+            Self::Finished { operation_id, .. } => operation_id,
+        }
+    }
+}
+
+/// A running batch
+struct Batch {
+    progress: IngestionProgress,
+    /// Fired when the operation is cancelled; the operation record is then
+    /// left as the cancellation set it
+    cancellation: CancellationToken,
+}
+
+/// Records the per-document progress of batch ingestions in their operation
+/// and publishes it to live subscribers
+pub struct IngestionProgressTracker {
+    operations: Arc<dyn OperationServiceTrait>,
+    live: broadcast::Sender<IngestionUpdate>,
+    /// Progress of the running batches; updates are applied and persisted
+    /// one at a time
+    batches: Mutex<HashMap<String, Batch>>,
+}
+
+impl std::fmt::Debug for IngestionProgressTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IngestionProgressTracker").finish_non_exhaustive()
+    }
+}
+
+impl IngestionProgressTracker {
+    /// Create a tracker persisting progress through `operations`
+    pub fn new(operations: Arc<dyn OperationServiceTrait>) -> Self {
+        Self {
+            operations,
+            live: broadcast::channel(LIVE_UPDATE_CAPACITY).0,
+            batches: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Receive the updates of every batch from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<IngestionUpdate> {
+        self.live.subscribe()
+    }
+
+    /// Start tracking a pending batch ingestion operation
+    pub async fn start(&self, operation_id: &str) -> Result<(), DomainError> {
+        let mut batches = self.batches.lock().await;
+        let cancellation = self.operations.cancellation_token(operation_id);
+        self.operations.mark_running(operation_id).await?;
+        batches.insert(
+            operation_id.to_string(),
+            Batch {
+                progress: IngestionProgress::default(),
+                cancellation,
+            },
+        );
+        Ok(())
+    }
+
+    /// Record a document of a batch reaching a stage
+    pub async fn record(&self, event: IngestionProgressEvent) {
+        let mut batches = self.batches.lock().await;
+        let Some(batch) = batches.get_mut(&event.operation_id) else {
+            return;
+        };
+
+        batch.progress.apply(&event);
+        let operation_id = event.operation_id.clone();
+        self.persist(&operation_id, batch).await;
+        let _ = self.live.send(IngestionUpdate::Document(event));
+
+        self.finish_if_done(&mut batches, &operation_id).await;
+    }
+
+    /// Mark every document of a batch as uploaded, so it finishes once they
+    /// are all stored or failed
+    pub async fn seal(&self, operation_id: &str) {
+        let mut batches = self.batches.lock().await;
+        let Some(batch) = batches.get_mut(operation_id) else {
+            return;
+        };
+
+        batch.progress.sealed = true;
+        self.persist(operation_id, batch).await;
+
+        self.finish_if_done(&mut batches, operation_id).await;
+    }
+
+    async fn persist(&self, operation_id: &str, batch: &Batch) {
+        if batch.cancellation.is_cancelled() {
+            return;
+        }
+        let value = serde_json::to_value(&batch.progress).unwrap_or_default();
+        if let Err(e) = self.operations.update_progress(operation_id, value).await {
+            warn!(operation_id = %operation_id, error = %e, "Failed to persist ingestion progress");
+        }
+
+        // Documents still uploading are not counted until they are queued
+        let progress = &batch.progress;
+        let done = (progress.stored() + progress.failed()) as u64;
+        if let Err(e) = self
+            .operations
+            .update_completion(operation_id, done, progress.documents.len() as u64, "documents")
+            .await
+        {
+            warn!(operation_id = %operation_id, error = %e, "Failed to persist ingestion completion");
+        }
+    }
+
+    /// Complete the operation of a finished batch, failed when no document
+    /// was stored
+    async fn finish_if_done(&self, batches: &mut HashMap<String, Batch>, operation_id: &str) {
+        if !batches
+            .get(operation_id)
+            .is_some_and(|b| b.progress.is_finished())
+        {
+            return;
+        }
+        let Some(Batch {
+            progress,
+            cancellation,
+        }) = batches.remove(operation_id)
+        else {
+            return;
+        };
+
+        let result = if cancellation.is_cancelled() {
+            Ok(())
+        } else if progress.stored() == 0 {
+            self.operations
+                .mark_failed(operation_id, "No document was stored".to_string())
+                .await
+                .map(|_| ())
+        } else {
+            self.operations
+                .mark_completed(
+                    operation_id,
+                    json!({
+                        "documents": progress.documents.len(),
+                        "stored": progress.stored(),
+                        "failed": progress.failed(),
+                    }),
+                )
+                .await
+                .map(|_| ())
+        };
+        if let Err(e) = result {
+            warn!(operation_id = %operation_id, error = %e, "Failed to complete ingestion operation");
+        }
+
+        let _ = self.live.send(IngestionUpdate::Finished {
+            operation_id: operation_id.to_string(),
+            progress,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ingestion::IngestionStage;
+    use crate::domain::operation::{OperationStatus, OperationType};
+    use crate::infrastructure::operation::InMemoryOperationRepository;
+    use crate::infrastructure::services::OperationService;
+
+    #[tokio::test]
+    async fn test_tracks_batch_until_finished() {
+        let operations: Arc<dyn OperationServiceTrait> = Arc::new(OperationService::new(
+            Arc::new(InMemoryOperationRepository::new()),
+        ));
+        let tracker = IngestionProgressTracker::new(operations.clone());
+        let mut updates = tracker.subscribe();
+
+        let operation = operations
+            .create_pending(OperationType::KnowledgeBaseIngestion, json!({}), json!({}))
+            .await
+            .unwrap();
+        let id = operation.id().as_str();
+        tracker.start(id).await.unwrap();
+
+        for (document, stage) in [
+            ("a.md", IngestionStage::Queued),
+            ("b.md", IngestionStage::Queued),
+            ("a.md", IngestionStage::Stored),
+        ] {
+            tracker
+                .record(IngestionProgressEvent::new(id, document, stage))
+                .await;
+        }
+        tracker.seal(id).await;
+
+        let running = operations.get(id).await.unwrap().unwrap();
+        assert_eq!(running.status(), OperationStatus::Running);
+        let progress: IngestionProgress =
+            serde_json::from_value(running.progress().cloned().unwrap()).unwrap();
+        assert_eq!(progress.stored(), 1);
+        assert!(progress.sealed);
+        let completion = running.completion().unwrap();
+        assert_eq!((completion.completed, completion.total), (1, 2));
+        assert_eq!(completion.percent, 50.0);
+
+        tracker
+            .record(
+                IngestionProgressEvent::new(id, "b.md", IngestionStage::Failed).with_error("empty"),
+            )
+            .await;
+
+        let completed = operations.get(id).await.unwrap().unwrap();
+        assert_eq!(completed.status(), OperationStatus::Completed);
+        assert_eq!(completed.result(), Some(&json!({"documents": 2, "stored": 1, "failed": 1})));
+
+        let mut last = None;
+        while let Ok(update) = updates.try_recv() {
+            last = Some(update);
+        }
+        assert!(matches!(last, Some(IngestionUpdate::Finished { .. })));
+    }
+}
@@ -1,16 +1,18 @@
 //! Lazy knowledge base provider registry that auto-creates providers on demand
 
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use async_trait::async_trait;
 use sqlx::PgPool;
+use tokio::sync::RwLock;
 
 use super::{KnowledgeBaseProviderRegistry, KnowledgeBaseProviderRegistryTrait, PgvectorConfig};
 use crate::domain::knowledge_base::{KnowledgeBaseId, KnowledgeBaseProvider, KnowledgeBaseType};
 use crate::domain::model::ModelId;
 use crate::domain::storage::Storage;
 use crate::domain::{DomainError, KnowledgeBase, Model, StoredCredential};
-use crate::infrastructure::credentials::CredentialServiceTrait;
+use crate::infrastructure::credentials::{CredentialInvalidationListener, CredentialServiceTrait};
 use crate::infrastructure::embedding::{HttpClient, OpenAiEmbeddingProvider};
 
 /// Configuration for lazy provider creation
@@ -87,12 +89,17 @@ impl super::EmbeddingProvider for PgvectorEmbeddingAdapter {
 /// - Model storage (to get embedding model configuration)
 /// - Credential service (to get embedding API keys)
 /// - PostgreSQL pool (for pgvector providers)
+///
+/// Cached providers are dropped when the credential of their embedding model
+/// changes, see [`CredentialInvalidationListener`].
 pub struct LazyKnowledgeBaseProviderRegistry {
     inner: Arc<KnowledgeBaseProviderRegistry>,
     kb_storage: Arc<dyn Storage<KnowledgeBase>>,
     model_storage: Arc<dyn Storage<Model>>,
     credential_service: Arc<dyn CredentialServiceTrait>,
     config: LazyRegistryConfig,
+    /// KB IDs whose provider was built from each credential ID
+    credential_dependents: RwLock<HashMap<String, HashSet<String>>>,
 }
 
 impl std::fmt::Debug for LazyKnowledgeBaseProviderRegistry {
@@ -118,6 +125,7 @@ impl LazyKnowledgeBaseProviderRegistry {
             model_storage,
             credential_service,
             config,
+            credential_dependents: RwLock::new(HashMap::new()),
         }
     }
 
@@ -194,8 +202,19 @@ impl LazyKnowledgeBaseProviderRegistry {
         // Create embedding provider from model and credential
         let embedding_provider = self.create_embedding_provider(&model, &credential, kb)?;
 
+        self.credential_dependents
+            .write()
+            .await
+            .entry(model.credential_id().to_string())
+            .or_default()
+            .insert(kb.id().as_str().to_string());
+
         // Create pgvector config
-        let pgvector_config = PgvectorConfig::new(kb.embedding().dimensions);
+        let mut pgvector_config = PgvectorConfig::new(kb.embedding().dimensions)
+            .with_distance_metric(kb.config().distance_metric);
+        if let Some(index) = &kb.config().vector_index {
+            pgvector_config = pgvector_config.with_index(index.clone());
+        }
 
         // Create the provider
         // Note: Tables are created via migrations (db/migrations/20260112000001_create_knowledge_base_documents.sql)
@@ -440,6 +459,23 @@ impl KnowledgeBaseProviderRegistryTrait for LazyKnowledgeBaseProviderRegistry {
     }
 }
 
+#[async_trait]
+impl CredentialInvalidationListener for LazyKnowledgeBaseProviderRegistry {
+    async fn credential_changed(&self, credential_id: &str) {
+        let dependents = self.credential_dependents.write().await.remove(credential_id);
+
+        for kb_id in dependents.into_iter().flatten() {
+            if self.inner.unregister(&kb_id).await.is_some() {
+                tracing::info!(
+                    kb_id = %kb_id,
+                    credential_id = %credential_id,
+                    "Dropped knowledge base provider after credential change"
+                );
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -584,4 +620,64 @@ mod tests {
         let result = registry.get("test-kb").await;
         assert!(result.is_some());
     }
+
+    #[tokio::test]
+    async fn test_lazy_registry_drops_provider_on_credential_change() {
+        let inner = Arc::new(KnowledgeBaseProviderRegistry::new());
+        let kb_storage = Arc::new(MockStorage::<KnowledgeBase>::new());
+        let model_storage = Arc::new(MockStorage::<Model>::new());
+        let cred_service = Arc::new(MockCredentialService::new());
+        // Lazy pools only connect on first query
+        let config = LazyRegistryConfig::new()
+            .with_pg_pool(PgPool::connect_lazy("postgres://localhost/gateway").unwrap());
+
+        let registry = LazyKnowledgeBaseProviderRegistry::new(
+            inner.clone(),
+            kb_storage.clone(),
+            model_storage.clone(),
+            cred_service.clone(),
+            config,
+        );
+
+        let mut conn_config = HashMap::new();
+        conn_config.insert(
+            "embedding_model_id".to_string(),
+            "text-embedding-3-small".to_string(),
+        );
+        let kb = KnowledgeBase::new(
+            KnowledgeBaseId::new("test-kb").unwrap(),
+            "Test KB",
+            KnowledgeBaseType::Pgvector,
+            EmbeddingConfig::new("text-embedding-3-small", 1536),
+        )
+        .with_connection_config(conn_config);
+        kb_storage.save(kb).await.unwrap();
+
+        let model = Model::new(
+            ModelId::new("text-embedding-3-small").unwrap(),
+            "Text Embedding 3 Small",
+            CredentialType::OpenAi,
+            "text-embedding-3-small",
+            "test-cred",
+        );
+        model_storage.save(model).await.unwrap();
+
+        cred_service
+            .add_credential(StoredCredential::new(
+                CredentialId::new("test-cred").unwrap(),
+                "Test Credential",
+                CredentialType::OpenAi,
+                "sk-test-key",
+            ))
+            .await;
+
+        assert!(registry.get("test-kb").await.is_some());
+        assert!(inner.has_provider("test-kb").await);
+
+        registry.credential_changed("other-cred").await;
+        assert!(inner.has_provider("test-kb").await);
+
+        registry.credential_changed("test-cred").await;
+        assert!(!inner.has_provider("test-kb").await);
+    }
 }
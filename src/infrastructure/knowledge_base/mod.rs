@@ -1,17 +1,21 @@
 //! Knowledge base provider implementations
 
+mod access_tracker;
 mod aws;
 mod factory;
 mod in_memory;
+mod ingestion_progress;
 mod lazy_registry;
 mod pgvector;
 mod registry;
 
+pub use access_tracker::KnowledgeBaseAccessTracker;
 pub use aws::{AwsKnowledgeBase, AwsKnowledgeBaseConfig};
 pub use factory::{KnowledgeBaseFactory, KnowledgeBaseProviderConfig};
 pub use in_memory::InMemoryKnowledgeBaseProvider;
+pub use ingestion_progress::{IngestionProgressTracker, IngestionUpdate};
 pub use lazy_registry::{LazyKnowledgeBaseProviderRegistry, LazyRegistryConfig};
-pub use pgvector::{DistanceMetric, EmbeddingProvider, PgvectorConfig, PgvectorKnowledgeBase};
+pub use pgvector::{EmbeddingProvider, PgvectorConfig, PgvectorKnowledgeBase};
 pub use registry::{KnowledgeBaseProviderRegistry, KnowledgeBaseProviderRegistryTrait};
 
 #[cfg(test)]
@@ -4,16 +4,16 @@ use std::collections::HashMap;
 use std::fmt::Debug;
 
 use async_trait::async_trait;
-use sqlx::postgres::PgPool;
+use sqlx::postgres::{PgPool, PgRow};
 use sqlx::Row;
 
 use crate::domain::knowledge_base::{
     AddDocumentsResult, CreateDocumentRequest, DeleteDocumentsResult, Document, DocumentChunk,
     DocumentSummary, FilterCondition, FilterConnector, FilterOperator, FilterValue,
     KnowledgeBaseDocument, KnowledgeBaseId, KnowledgeBaseProvider, MetadataFilter, SearchParams,
-    SearchResult, SourceInfo,
+    SearchResult, SourceInfo, VectorIndexConfig, VectorIndexStatus, DOCUMENT_ID_METADATA_KEY,
 };
-use crate::domain::DomainError;
+use crate::domain::{DistanceMetric, DomainError};
 use uuid::Uuid;
 
 /// Configuration for pgvector knowledge base
@@ -25,6 +25,9 @@ pub struct PgvectorConfig {
     pub table_name: String,
     /// Distance metric to use
     pub distance_metric: DistanceMetric,
+    /// Dedicated vector index of the knowledge base; searches use the
+    /// shared index of the table when unset
+    pub index: Option<VectorIndexConfig>,
 }
 
 impl PgvectorConfig {
@@ -34,6 +37,7 @@ impl PgvectorConfig {
             dimensions,
             table_name: "knowledge_base_document_chunks".to_string(),
             distance_metric: DistanceMetric::Cosine,
+            index: None,
         }
     }
 
@@ -48,21 +52,24 @@ impl PgvectorConfig {
         self.distance_metric = metric;
         self
     }
-}
 
-/// Distance metric for vector similarity
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum DistanceMetric {
-    /// Cosine distance (1 - cosine similarity)
-    Cosine,
-    /// Euclidean (L2) distance
-    Euclidean,
-    /// Inner product (negative dot product for similarity)
-    InnerProduct,
+    /// Set the dedicated vector index
+    pub fn with_index(mut self, index: VectorIndexConfig) -> Self {
+        self.index = Some(index);
+        self
+    }
 }
 
-impl DistanceMetric {
+/// pgvector SQL for a distance metric
+trait PgvectorMetric {
     /// Get the pgvector operator for this metric
+    fn operator(&self) -> &'static str;
+
+    /// Get the index operator class for this metric
+    fn operator_class(&self) -> &'static str;
+}
+
+impl PgvectorMetric for DistanceMetric {
     fn operator(&self) -> &'static str {
         match self {
             Self::Cosine => "<=>",
@@ -71,18 +78,11 @@ impl DistanceMetric {
         }
     }
 
-    /// Convert distance to similarity score (0-1)
-    fn to_similarity(&self, distance: f64) -> f32 {
+    fn operator_class(&self) -> &'static str {
         match self {
-            Self::Cosine => (1.0 - distance) as f32,
-            Self::Euclidean => {
-                // Convert to similarity: 1 / (1 + distance)
-                (1.0 / (1.0 + distance)) as f32
-            }
-            Self::InnerProduct => {
-                // Inner product is already a similarity (negated in pgvector)
-                (-distance) as f32
-            }
+            Self::Cosine => "vector_cosine_ops",
+            Self::Euclidean => "vector_l2_ops",
+            Self::InnerProduct => "vector_ip_ops",
         }
     }
 }
@@ -185,9 +185,152 @@ impl<E: EmbeddingProvider> PgvectorKnowledgeBase<E> {
         // IVFFlat requires some data to build, so ignore errors
         let _ = sqlx::query(&vector_index).execute(&self.pool).await;
 
+        if let Some(index) = &self.config.index {
+            match self.find_index(&self.index_name()).await? {
+                Some(existing)
+                    if existing.valid && existing.matches(index, self.config.distance_metric) => {}
+                Some(_) => self.rebuild_index(index).await?,
+                None => self.create_index(index, &self.index_name()).await?,
+            }
+        }
+
         Ok(())
     }
 
+    /// Name of the dedicated vector index of the knowledge base, quoted
+    /// where used since knowledge base IDs may hold hyphens and capitals
+    fn index_name(&self) -> String {
+        format!("kb_vec_{}", self.id.as_str())
+    }
+
+    /// Build the dedicated vector index, a partial index over the chunks of
+    /// the knowledge base, without blocking writes
+    async fn create_index(&self, index: &VectorIndexConfig, name: &str) -> Result<(), DomainError> {
+        let parameters = index
+            .build_parameters()
+            .iter()
+            .map(|(key, value)| format!("{} = {}", key, value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let query = format!(
+            r#"CREATE INDEX CONCURRENTLY IF NOT EXISTS "{}" ON {} USING {} (embedding {}) WITH ({}) WHERE kb_id = '{}'"#,
+            name,
+            self.config.table_name,
+            index.method(),
+            self.config.distance_metric.operator_class(),
+            parameters,
+            self.id.as_str()
+        );
+
+        tracing::info!(kb_id = self.id.as_str(), index = name, "Building vector index");
+        sqlx::query(&query).execute(&self.pool).await.map_err(|e| {
+            DomainError::knowledge_base(format!("Failed to build vector index '{}': {}", name, e))
+        })?;
+
+        Ok(())
+    }
+
+    /// Replace the dedicated vector index; searches keep using the old one
+    /// until the new one is built
+    async fn rebuild_index(&self, index: &VectorIndexConfig) -> Result<(), DomainError> {
+        let name = self.index_name();
+        let staging = format!("{}_new", name);
+
+        // Leftover of an interrupted rebuild
+        self.drop_index(&staging).await?;
+        self.create_index(index, &staging).await?;
+        self.drop_index(&name).await?;
+
+        sqlx::query(&format!(r#"ALTER INDEX "{}" RENAME TO "{}""#, staging, name))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                DomainError::knowledge_base(format!("Failed to rename vector index '{}': {}", staging, e))
+            })?;
+
+        Ok(())
+    }
+
+    async fn drop_index(&self, name: &str) -> Result<(), DomainError> {
+        sqlx::query(&format!(r#"DROP INDEX CONCURRENTLY IF EXISTS "{}""#, name))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                DomainError::knowledge_base(format!("Failed to drop vector index '{}': {}", name, e))
+            })?;
+
+        Ok(())
+    }
+
+    /// Look up a vector index by name
+    async fn find_index(&self, name: &str) -> Result<Option<ExistingIndex>, DomainError> {
+        self.query_index("i.relname = $1", name).await
+    }
+
+    /// Look up the vector index shared by every knowledge base of the table
+    async fn find_shared_index(&self) -> Result<Option<ExistingIndex>, DomainError> {
+        self.query_index(
+            "t.relname = $1 AND ix.indpred IS NULL AND am.amname IN ('hnsw', 'ivfflat')",
+            &self.config.table_name,
+        )
+        .await
+    }
+
+    async fn query_index(&self, condition: &str, value: &str) -> Result<Option<ExistingIndex>, DomainError> {
+        let query = format!(
+            r#"
+            SELECT
+                i.relname::text AS name,
+                am.amname::text AS method,
+                COALESCE(i.reloptions, '{{}}')::text[] AS options,
+                pg_get_indexdef(i.oid) AS definition,
+                pg_relation_size(i.oid) AS size_bytes,
+                ix.indisvalid AS valid
+            FROM pg_class i
+            JOIN pg_index ix ON ix.indexrelid = i.oid
+            JOIN pg_class t ON t.oid = ix.indrelid
+            JOIN pg_am am ON am.oid = i.relam
+            WHERE {}
+            ORDER BY i.relname
+            LIMIT 1
+            "#,
+            condition
+        );
+
+        let row = sqlx::query(&query)
+            .bind(value)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| DomainError::knowledge_base(format!("Failed to read vector index: {}", e)))?;
+
+        Ok(row.map(|row| ExistingIndex {
+            name: row.get("name"),
+            method: row.get("method"),
+            options: row.get("options"),
+            definition: row.get("definition"),
+            size_bytes: row.get("size_bytes"),
+            valid: row.get("valid"),
+        }))
+    }
+
+    /// Run a search query, with the configured search-time index setting
+    /// applied to it alone
+    async fn fetch_search_rows(&self, query: &str) -> Result<Vec<PgRow>, sqlx::Error> {
+        let Some((setting, value)) = self.config.index.as_ref().and_then(|i| i.search_setting())
+        else {
+            return sqlx::query(query).fetch_all(&self.pool).await;
+        };
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(&format!("SET LOCAL {} = {}", setting, value))
+            .execute(&mut *tx)
+            .await?;
+        let rows = sqlx::query(query).fetch_all(&mut *tx).await?;
+        tx.commit().await?;
+
+        Ok(rows)
+    }
+
     /// Convert a filter to SQL WHERE clause
     /// Values are embedded directly in the SQL string for simplicity
     /// `table_alias` is an optional table alias prefix (e.g., "c" for "c.metadata")
@@ -220,7 +363,8 @@ impl<E: EmbeddingProvider> PgvectorKnowledgeBase<E> {
         condition: &FilterCondition,
         table_alias: Option<&str>,
     ) -> String {
-        let key = &condition.key;
+        // Escape single quotes for SQL
+        let key = condition.key.replace('\'', "''");
         let metadata_col = match table_alias {
             Some(alias) => format!("{}.metadata", alias),
             None => "metadata".to_string(),
@@ -234,6 +378,26 @@ impl<E: EmbeddingProvider> PgvectorKnowledgeBase<E> {
             FilterOperator::NotExists => {
                 format!("NOT ({} ? '{}')", metadata_col, key)
             }
+            FilterOperator::ContainsAny => {
+                let values: Vec<String> = match &condition.value {
+                    Some(FilterValue::List(items)) => items
+                        .iter()
+                        .filter_map(|v| match v {
+                            FilterValue::String(s) => {
+                                Some(format!("'{}'", s.replace('\'', "''")))
+                            }
+                            _ => None,
+                        })
+                        .collect(),
+                    _ => Vec::new(),
+                };
+
+                if values.is_empty() {
+                    "FALSE".to_string()
+                } else {
+                    format!("{} ?| array[{}]", json_path, values.join(", "))
+                }
+            }
             _ => {
                 if let Some(value) = &condition.value {
                     let (op, val_sql) =
@@ -254,11 +418,11 @@ impl<E: EmbeddingProvider> PgvectorKnowledgeBase<E> {
         match op {
             FilterOperator::Eq => {
                 let val = self.filter_value_to_json(value);
-                ("=", format!("'{}'::jsonb", val))
+                ("=", format!("'{}'::jsonb", val.replace('\'', "''")))
             }
             FilterOperator::Ne => {
                 let val = self.filter_value_to_json(value);
-                ("!=", format!("'{}'::jsonb", val))
+                ("!=", format!("'{}'::jsonb", val.replace('\'', "''")))
             }
             FilterOperator::Gt => {
                 let val = self.filter_value_to_numeric_string(value);
@@ -350,7 +514,7 @@ impl<E: EmbeddingProvider> PgvectorKnowledgeBase<E> {
                 }
                 ("=", "NULL".to_string())
             }
-            FilterOperator::Exists | FilterOperator::NotExists => {
+            FilterOperator::Exists | FilterOperator::NotExists | FilterOperator::ContainsAny => {
                 // Handled separately in condition_to_sql
                 ("=", "NULL".to_string())
             }
@@ -359,7 +523,7 @@ impl<E: EmbeddingProvider> PgvectorKnowledgeBase<E> {
 
     fn filter_value_to_json(&self, value: &FilterValue) -> String {
         match value {
-            FilterValue::String(s) => format!("\"{}\"", s),
+            FilterValue::String(s) => serde_json::Value::String(s.clone()).to_string(),
             FilterValue::Integer(n) => n.to_string(),
             FilterValue::Float(f) => f.to_string(),
             FilterValue::Boolean(b) => b.to_string(),
@@ -403,6 +567,7 @@ impl<E: EmbeddingProvider + 'static> KnowledgeBaseProvider for PgvectorKnowledge
             top_k = params.top_k,
             similarity_threshold = params.similarity_threshold,
             has_filter = params.filter.is_some(),
+            namespace = ?params.namespace,
             "Starting KB search"
         );
 
@@ -427,7 +592,7 @@ impl<E: EmbeddingProvider + 'static> KnowledgeBaseProvider for PgvectorKnowledge
 
         // Build filter SQL if filter is provided (use "c" as table alias for chunks table)
         let filter_sql = params
-            .filter
+            .scoped_filter()
             .as_ref()
             .map(|f| self.filter_to_sql(f, Some("c")))
             .filter(|s| !s.is_empty())
@@ -452,6 +617,7 @@ impl<E: EmbeddingProvider + 'static> KnowledgeBaseProvider for PgvectorKnowledge
                 c.embedding {} '{}' as distance,
                 c.metadata,
                 d.source_filename as source,
+                c.document_id::text as document_id,
                 c.embedding::text as embedding
             FROM knowledge_base_document_chunks c
             JOIN knowledge_base_documents d ON c.document_id = d.id
@@ -463,8 +629,8 @@ impl<E: EmbeddingProvider + 'static> KnowledgeBaseProvider for PgvectorKnowledge
             op, embedding_str, self.id.as_str(), filter_sql, params.top_k
         );
 
-        let rows = sqlx::query(&query)
-            .fetch_all(&self.pool)
+        let rows = self
+            .fetch_search_rows(&query)
             .await
             .map_err(|e| {
                 tracing::error!(
@@ -499,8 +665,12 @@ impl<E: EmbeddingProvider + 'static> KnowledgeBaseProvider for PgvectorKnowledge
                 continue;
             }
 
-            let metadata_map: HashMap<String, serde_json::Value> =
+            let mut metadata_map: HashMap<String, serde_json::Value> =
                 serde_json::from_value(metadata).unwrap_or_default();
+            let document_id: String = row.get("document_id");
+            metadata_map
+                .entry(DOCUMENT_ID_METADATA_KEY.to_string())
+                .or_insert(serde_json::json!(document_id));
 
             let mut result = SearchResult::new(&id, &content, score).with_all_metadata(metadata_map);
 
@@ -651,6 +821,52 @@ impl<E: EmbeddingProvider + 'static> KnowledgeBaseProvider for PgvectorKnowledge
         Ok(DeleteDocumentsResult::new(result.rows_affected() as usize, 0))
     }
 
+    async fn list_by_filter(
+        &self,
+        filter: MetadataFilter,
+    ) -> Result<Vec<SearchResult>, DomainError> {
+        let filter_sql = self.filter_to_sql(&filter, None);
+
+        if filter_sql.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query = format!(
+            "SELECT id, content, metadata, source FROM {} WHERE kb_id = $1 AND {} ORDER BY id",
+            self.config.table_name, filter_sql
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(self.id.as_str())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                DomainError::knowledge_base(format!("Failed to list documents by filter: {}", e))
+            })?;
+
+        let mut results = Vec::with_capacity(rows.len());
+
+        for row in rows {
+            let id: String = row.get("id");
+            let content: String = row.get("content");
+            let metadata: serde_json::Value = row.get("metadata");
+            let source: Option<String> = row.get("source");
+
+            let metadata_map: HashMap<String, serde_json::Value> =
+                serde_json::from_value(metadata).unwrap_or_default();
+
+            let mut result = SearchResult::new(&id, &content, 1.0).with_all_metadata(metadata_map);
+
+            if let Some(src) = source {
+                result = result.with_source(src);
+            }
+
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+
     async fn get_document(&self, id: &str) -> Result<Option<SearchResult>, DomainError> {
         let query = format!(
             "SELECT id, content, metadata, source, embedding FROM {} WHERE kb_id = $1 AND id = $2",
@@ -802,6 +1018,63 @@ impl<E: EmbeddingProvider + 'static> KnowledgeBaseProvider for PgvectorKnowledge
         self.ensure_table().await
     }
 
+    async fn index_status(&self) -> Result<Option<VectorIndexStatus>, DomainError> {
+        let configured = self.config.index.clone();
+        let dedicated = match &configured {
+            Some(_) => self.find_index(&self.index_name()).await?,
+            None => None,
+        };
+        let up_to_date = match (&configured, &dedicated) {
+            (Some(index), Some(existing)) => existing.matches(index, self.config.distance_metric),
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+
+        let Some(existing) = dedicated.or(self.find_shared_index().await?) else {
+            return Ok(None);
+        };
+        let chunks = self.document_count().await?;
+
+        Ok(Some(VectorIndexStatus {
+            name: existing.name,
+            method: existing.method,
+            definition: existing.definition,
+            size_bytes: existing.size_bytes,
+            valid: existing.valid,
+            chunks,
+            up_to_date,
+            configured,
+        }))
+    }
+
+    async fn reindex(&self) -> Result<VectorIndexStatus, DomainError> {
+        match &self.config.index {
+            Some(index) => self.rebuild_index(index).await?,
+            None => {
+                // The shared index serves every knowledge base of the table
+                let shared = self.find_shared_index().await?.ok_or_else(|| {
+                    DomainError::not_found(format!(
+                        "No vector index on '{}', initialize the schema first",
+                        self.config.table_name
+                    ))
+                })?;
+                sqlx::query(&format!(r#"REINDEX INDEX CONCURRENTLY "{}""#, shared.name))
+                    .execute(&self.pool)
+                    .await
+                    .map_err(|e| {
+                        DomainError::knowledge_base(format!(
+                            "Failed to reindex '{}': {}",
+                            shared.name, e
+                        ))
+                    })?;
+            }
+        }
+
+        self.index_status().await?.ok_or_else(|| {
+            DomainError::knowledge_base("Vector index missing after reindex".to_string())
+        })
+    }
+
     // ========================================================================
     // New document-based methods (for the new schema)
     // ========================================================================
@@ -908,7 +1181,8 @@ impl<E: EmbeddingProvider + 'static> KnowledgeBaseProvider for PgvectorKnowledge
     async fn list_documents(&self) -> Result<Vec<DocumentSummary>, DomainError> {
         let rows = sqlx::query(
             r#"
-            SELECT id, title, source_filename, chunk_count, disabled, created_at
+            SELECT id, title, source_filename, original_size_bytes, chunk_count, disabled, created_at,
+                   updated_at
             FROM knowledge_base_documents
             WHERE kb_id = $1
             ORDER BY created_at DESC
@@ -925,9 +1199,11 @@ impl<E: EmbeddingProvider + 'static> KnowledgeBaseProvider for PgvectorKnowledge
                 id: row.get("id"),
                 title: row.get("title"),
                 source_filename: row.get("source_filename"),
+                original_size_bytes: row.get("original_size_bytes"),
                 chunk_count: row.get("chunk_count"),
                 disabled: row.get("disabled"),
                 created_at: row.get("created_at"),
+                updated_at: row.get("updated_at"),
             })
             .collect())
     }
@@ -1011,6 +1287,37 @@ impl<E: EmbeddingProvider + 'static> KnowledgeBaseProvider for PgvectorKnowledge
 }
 
 /// Parse a pgvector string representation back to a Vec<f32>
+/// Vector index found in the catalog
+#[derive(Debug, Clone)]
+struct ExistingIndex {
+    name: String,
+    method: String,
+    /// Storage options, as `key=value`
+    options: Vec<String>,
+    definition: String,
+    size_bytes: i64,
+    valid: bool,
+}
+
+impl ExistingIndex {
+    /// Whether the index was built with the configured type, parameters and
+    /// the operator class of the distance metric
+    fn matches(&self, index: &VectorIndexConfig, metric: DistanceMetric) -> bool {
+        let mut expected: Vec<String> = index
+            .build_parameters()
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+        let mut options = self.options.clone();
+        expected.sort();
+        options.sort();
+
+        self.method == index.method()
+            && options == expected
+            && self.definition.contains(metric.operator_class())
+    }
+}
+
 fn parse_pgvector(s: &str) -> Result<Vec<f32>, DomainError> {
     let trimmed = s.trim_start_matches('[').trim_end_matches(']');
     let values: Result<Vec<f32>, _> = trimmed.split(',').map(|v| v.trim().parse::<f32>()).collect();
@@ -1105,6 +1412,36 @@ mod tests {
         assert!(euclidean.to_similarity(1.0) > 0.0);
     }
 
+    #[test]
+    fn test_existing_index_matches() {
+        let existing = ExistingIndex {
+            name: "kb_vec_docs".to_string(),
+            method: "hnsw".to_string(),
+            options: vec!["m=16".to_string(), "ef_construction=64".to_string()],
+            definition: "CREATE INDEX kb_vec_docs ON public.knowledge_base_document_chunks USING hnsw (embedding vector_cosine_ops) WITH (m='16', ef_construction='64') WHERE ((kb_id)::text = 'docs'::text)".to_string(),
+            size_bytes: 8192,
+            valid: true,
+        };
+
+        assert!(existing.matches(&VectorIndexConfig::hnsw(), DistanceMetric::Cosine));
+        assert!(!existing.matches(&VectorIndexConfig::hnsw(), DistanceMetric::Euclidean));
+        assert!(!existing.matches(&VectorIndexConfig::ivfflat(100), DistanceMetric::Cosine));
+
+        // The search-time setting does not require a rebuild
+        let tuned = VectorIndexConfig::Hnsw {
+            m: 16,
+            ef_construction: 64,
+            ef_search: Some(100),
+        };
+        assert!(existing.matches(&tuned, DistanceMetric::Cosine));
+        let rebuilt = VectorIndexConfig::Hnsw {
+            m: 32,
+            ef_construction: 64,
+            ef_search: None,
+        };
+        assert!(!existing.matches(&rebuilt, DistanceMetric::Cosine));
+    }
+
     #[test]
     fn test_parse_pgvector() {
         let result = parse_pgvector("[0.1, 0.2, 0.3]").unwrap();
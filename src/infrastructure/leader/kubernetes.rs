@@ -0,0 +1,325 @@
+//! Leader election with a Kubernetes `coordination.k8s.io/v1` Lease
+//!
+//! The replica holding the Lease renews it before `leaseDurationSeconds`
+//! elapse; any replica may take over a Lease that was not renewed in time.
+//! Writes carry the read `resourceVersion`, so of two replicas racing for
+//! the Lease only one succeeds.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::LeaderElector;
+use crate::domain::DomainError;
+
+const SERVICE_ACCOUNT_DIR: &str = "/var/run/secrets/kubernetes.io/serviceaccount";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LeaseSpec {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    holder_identity: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    lease_duration_seconds: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    acquire_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    renew_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    lease_transitions: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LeaseMetadata {
+    resource_version: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Lease {
+    metadata: LeaseMetadata,
+    #[serde(default)]
+    spec: LeaseSpec,
+}
+
+/// Leads while holding a Lease, using the pod's service account. The
+/// account needs `get`, `create` and `update` on `leases`.
+#[derive(Debug)]
+pub struct KubernetesLeaseElector {
+    client: Client,
+    api_url: String,
+    namespace: String,
+    lease_name: String,
+    identity: String,
+    lease_duration_secs: u64,
+}
+
+impl KubernetesLeaseElector {
+    /// Elector talking to the API server of the cluster the pod runs in.
+    /// `namespace` defaults to the pod's namespace.
+    pub fn in_cluster(
+        lease_name: impl Into<String>,
+        namespace: Option<String>,
+        identity: impl Into<String>,
+        lease_duration_secs: u64,
+    ) -> Result<Self, DomainError> {
+        let host = std::env::var("KUBERNETES_SERVICE_HOST").map_err(|_| {
+            DomainError::configuration(
+                "Kubernetes leader election requires running in a cluster (KUBERNETES_SERVICE_HOST is not set)",
+            )
+        })?;
+        let port = std::env::var("KUBERNETES_SERVICE_PORT").unwrap_or_else(|_| "443".to_string());
+
+        let namespace = match namespace {
+            Some(namespace) => namespace,
+            None => read_service_account_file("namespace")?.trim().to_string(),
+        };
+
+        let ca = reqwest::Certificate::from_pem(read_service_account_file("ca.crt")?.as_bytes())
+            .map_err(|e| {
+                DomainError::configuration(format!("Invalid service account CA: {}", e))
+            })?;
+
+        let client = Client::builder()
+            .add_root_certificate(ca)
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| DomainError::configuration(format!("Failed to build client: {}", e)))?;
+
+        Ok(Self {
+            client,
+            api_url: format!("https://{}:{}", host, port),
+            namespace,
+            lease_name: lease_name.into(),
+            identity: identity.into(),
+            lease_duration_secs,
+        })
+    }
+
+    fn leases_url(&self) -> String {
+        format!(
+            "{}/apis/coordination.k8s.io/v1/namespaces/{}/leases",
+            self.api_url, self.namespace
+        )
+    }
+
+    fn lease_body(&self, spec: LeaseSpec, resource_version: Option<String>) -> serde_json::Value {
+        let mut metadata = json!({
+            "name": self.lease_name,
+            "namespace": self.namespace,
+        });
+
+        if let Some(resource_version) = resource_version {
+            metadata["resourceVersion"] = json!(resource_version);
+        }
+
+        json!({
+            "apiVersion": "coordination.k8s.io/v1",
+            "kind": "Lease",
+            "metadata": metadata,
+            "spec": spec,
+        })
+    }
+
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, DomainError> {
+        // Projected service account tokens are rotated, so read on every call
+        let token = read_service_account_file("token")?;
+
+        request
+            .bearer_auth(token.trim())
+            .send()
+            .await
+            .map_err(|e| DomainError::internal(format!("Kubernetes API request failed: {}", e)))
+    }
+}
+
+#[async_trait]
+impl LeaderElector for KubernetesLeaseElector {
+    async fn try_acquire(&self) -> Result<bool, DomainError> {
+        let url = format!("{}/{}", self.leases_url(), self.lease_name);
+        let response = self.send(self.client.get(&url)).await?;
+
+        let (request, action) = match response.status() {
+            StatusCode::NOT_FOUND => {
+                let spec = claim(None, &self.identity, self.lease_duration_secs, Utc::now())
+                    .unwrap_or_default();
+                (
+                    self.client
+                        .post(self.leases_url())
+                        .json(&self.lease_body(spec, None)),
+                    "create",
+                )
+            }
+            status if status.is_success() => {
+                let lease: Lease = response.json().await.map_err(|e| {
+                    DomainError::internal(format!("Invalid Lease '{}': {}", self.lease_name, e))
+                })?;
+
+                let Some(spec) = claim(
+                    Some(&lease.spec),
+                    &self.identity,
+                    self.lease_duration_secs,
+                    Utc::now(),
+                ) else {
+                    return Ok(false);
+                };
+
+                (
+                    self.client
+                        .put(&url)
+                        .json(&self.lease_body(spec, Some(lease.metadata.resource_version))),
+                    "update",
+                )
+            }
+            status => {
+                return Err(DomainError::internal(format!(
+                    "Failed to read Lease '{}': HTTP {}",
+                    self.lease_name, status
+                )));
+            }
+        };
+
+        let response = self.send(request).await?;
+
+        match response.status() {
+            // Another replica wrote the Lease first
+            StatusCode::CONFLICT => Ok(false),
+            status if status.is_success() => Ok(true),
+            status => Err(DomainError::internal(format!(
+                "Failed to {} Lease '{}': HTTP {}",
+                action, self.lease_name, status
+            ))),
+        }
+    }
+}
+
+fn read_service_account_file(name: &str) -> Result<String, DomainError> {
+    let path = format!("{}/{}", SERVICE_ACCOUNT_DIR, name);
+
+    std::fs::read_to_string(&path)
+        .map_err(|e| DomainError::configuration(format!("Failed to read {}: {}", path, e)))
+}
+
+/// Spec to write to hold the Lease, or `None` while another holder's Lease
+/// has not expired
+fn claim(
+    current: Option<&LeaseSpec>,
+    identity: &str,
+    lease_duration_secs: u64,
+    now: DateTime<Utc>,
+) -> Option<LeaseSpec> {
+    let now_str = now.to_rfc3339_opts(SecondsFormat::Micros, true);
+    let held_by = current.and_then(|spec| spec.holder_identity.as_deref());
+
+    if let Some(spec) = current
+        && held_by == Some(identity)
+    {
+        return Some(LeaseSpec {
+            lease_duration_seconds: Some(lease_duration_secs as i64),
+            renew_time: Some(now_str),
+            ..spec.clone()
+        });
+    }
+
+    if let Some(spec) = current
+        && held_by.is_some_and(|holder| !holder.is_empty())
+    {
+        let duration = spec
+            .lease_duration_seconds
+            .unwrap_or(lease_duration_secs as i64);
+        let renewed = spec
+            .renew_time
+            .as_deref()
+            .and_then(|time| DateTime::parse_from_rfc3339(time).ok());
+
+        if renewed.is_some_and(|renewed| renewed + chrono::Duration::seconds(duration) > now) {
+            return None;
+        }
+    }
+
+    let transitions = current.and_then(|spec| spec.lease_transitions).unwrap_or(0);
+
+    Some(LeaseSpec {
+        holder_identity: Some(identity.to_string()),
+        lease_duration_seconds: Some(lease_duration_secs as i64),
+        acquire_time: Some(now_str.clone()),
+        renew_time: Some(now_str),
+        lease_transitions: Some(if held_by.is_some() {
+            transitions + 1
+        } else {
+            transitions
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_767_225_600 + secs, 0).unwrap()
+    }
+
+    fn held_by(identity: &str, renewed: i64) -> LeaseSpec {
+        LeaseSpec {
+            holder_identity: Some(identity.to_string()),
+            lease_duration_seconds: Some(15),
+            acquire_time: Some(at(0).to_rfc3339_opts(SecondsFormat::Micros, true)),
+            renew_time: Some(at(renewed).to_rfc3339_opts(SecondsFormat::Micros, true)),
+            lease_transitions: Some(2),
+        }
+    }
+
+    #[test]
+    fn test_claim_new_lease() {
+        let spec = claim(None, "pod-a", 15, at(0)).unwrap();
+
+        assert_eq!(spec.holder_identity.as_deref(), Some("pod-a"));
+        assert_eq!(
+            spec.renew_time.as_deref(),
+            Some("2026-01-01T00:00:00.000000Z")
+        );
+        assert_eq!(spec.lease_transitions, Some(0));
+    }
+
+    #[test]
+    fn test_claim_renews_own_lease() {
+        let spec = claim(Some(&held_by("pod-a", 5)), "pod-a", 15, at(10)).unwrap();
+
+        assert_eq!(spec.holder_identity.as_deref(), Some("pod-a"));
+        assert_eq!(spec.acquire_time, held_by("pod-a", 5).acquire_time);
+        assert_eq!(
+            spec.renew_time.as_deref(),
+            Some("2026-01-01T00:00:10.000000Z")
+        );
+        assert_eq!(spec.lease_transitions, Some(2));
+    }
+
+    #[test]
+    fn test_claim_respects_and_takes_over_other_holders() {
+        assert!(claim(Some(&held_by("pod-a", 5)), "pod-b", 15, at(19)).is_none());
+
+        let spec = claim(Some(&held_by("pod-a", 5)), "pod-b", 15, at(20)).unwrap();
+        assert_eq!(spec.holder_identity.as_deref(), Some("pod-b"));
+        assert_eq!(
+            spec.acquire_time.as_deref(),
+            Some("2026-01-01T00:00:20.000000Z")
+        );
+        assert_eq!(spec.lease_transitions, Some(3));
+
+        // A released Lease has no holder
+        let released = LeaseSpec {
+            holder_identity: Some(String::new()),
+            ..held_by("", 5)
+        };
+        assert!(claim(Some(&released), "pod-b", 15, at(6)).is_some());
+    }
+}
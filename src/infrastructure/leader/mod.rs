@@ -0,0 +1,114 @@
+//! Leader election among gateway replicas
+//!
+//! Scheduled jobs acting on shared state (webhook retries, experiment
+//! auto-stop, usage anomaly detection, daily reconciliation and export) only
+//! run on the replica holding leadership, so they are not repeated by every
+//! replica. Jobs feeding per-replica state (SLO evaluation, canaries, pricing
+//! sync) keep running everywhere.
+
+mod kubernetes;
+mod postgres;
+
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use metrics::gauge;
+use tracing::{info, warn};
+
+use crate::domain::DomainError;
+
+pub use kubernetes::KubernetesLeaseElector;
+pub use postgres::PostgresAdvisoryLockElector;
+
+/// Whether this replica currently leads, shared by the election loop and the
+/// scheduled jobs
+#[derive(Debug, Clone)]
+pub struct Leadership {
+    leader: Arc<AtomicBool>,
+}
+
+impl Leadership {
+    /// Leadership of a replica that is not elected, e.g. without election
+    pub fn always() -> Self {
+        Self {
+            leader: Arc::new(AtomicBool::new(true)),
+        }
+    }
+
+    /// Leadership of an elected replica, not held until won
+    pub fn elected() -> Self {
+        Self {
+            leader: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.leader.load(Ordering::Relaxed)
+    }
+
+    /// Record the outcome of an election round; true when it changed
+    fn set(&self, leader: bool) -> bool {
+        self.leader.swap(leader, Ordering::Relaxed) != leader
+    }
+}
+
+/// Backend electing one leader among the replicas
+#[async_trait]
+pub trait LeaderElector: Send + Sync + Debug {
+    /// Acquire leadership, or renew it when already held. `Ok(false)` while
+    /// another replica leads.
+    async fn try_acquire(&self) -> Result<bool, DomainError>;
+}
+
+/// Acquire or renew leadership every `interval`. Failing to reach the
+/// backend steps down, since leadership can no longer be guaranteed.
+pub fn spawn_leader_election(
+    elector: Arc<dyn LeaderElector>,
+    leadership: Leadership,
+    interval: Duration,
+) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            let leader = match elector.try_acquire().await {
+                Ok(leader) => leader,
+                Err(e) => {
+                    warn!(error = %e, "Leader election failed");
+                    false
+                }
+            };
+
+            if leadership.set(leader) {
+                info!(leader, "Leadership changed");
+            }
+
+            gauge!("llm_gateway_leader").set(if leader { 1.0 } else { 0.0 });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leadership() {
+        assert!(Leadership::always().is_leader());
+
+        let leadership = Leadership::elected();
+        let shared = leadership.clone();
+        assert!(!shared.is_leader());
+
+        assert!(leadership.set(true));
+        assert!(shared.is_leader());
+        assert!(!leadership.set(true));
+        assert!(leadership.set(false));
+        assert!(!shared.is_leader());
+    }
+}
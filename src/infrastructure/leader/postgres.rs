@@ -0,0 +1,66 @@
+//! Leader election with a PostgreSQL advisory lock
+
+use async_trait::async_trait;
+use sqlx::{Connection, PgConnection, PgPool};
+use tokio::sync::Mutex;
+
+use super::LeaderElector;
+use crate::domain::DomainError;
+
+/// Leads while holding a session-level advisory lock. The lock lives on a
+/// connection taken out of the pool, so it is released by PostgreSQL as soon
+/// as the connection (or this replica) goes away.
+#[derive(Debug)]
+pub struct PostgresAdvisoryLockElector {
+    pool: PgPool,
+    lock_id: i64,
+    connection: Mutex<Option<PgConnection>>,
+}
+
+impl PostgresAdvisoryLockElector {
+    pub fn new(pool: PgPool, lock_id: i64) -> Self {
+        Self {
+            pool,
+            lock_id,
+            connection: Mutex::new(None),
+        }
+    }
+}
+
+#[async_trait]
+impl LeaderElector for PostgresAdvisoryLockElector {
+    async fn try_acquire(&self) -> Result<bool, DomainError> {
+        let mut held = self.connection.lock().await;
+
+        if let Some(connection) = held.as_mut() {
+            return match connection.ping().await {
+                Ok(()) => Ok(true),
+                Err(e) => {
+                    // Dropping the connection releases the lock if the
+                    // server still holds it
+                    *held = None;
+                    Err(DomainError::storage(format!(
+                        "Lost the leader election connection: {}",
+                        e
+                    )))
+                }
+            };
+        }
+
+        let mut connection = self.pool.acquire().await.map_err(|e| {
+            DomainError::storage(format!("Failed to connect for leader election: {}", e))
+        })?;
+
+        let acquired: bool = sqlx::query_scalar("SELECT pg_try_advisory_lock($1)")
+            .bind(self.lock_id)
+            .fetch_one(&mut *connection)
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to take the leader lock: {}", e)))?;
+
+        if acquired {
+            *held = Some(connection.detach());
+        }
+
+        Ok(acquired)
+    }
+}
@@ -3,10 +3,11 @@ use bytes::Bytes;
 use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 
+use super::anthropic_stream::{parse_stop_reason, AnthropicStreamNormalizer};
 use super::http_client::HttpClientTrait;
 use crate::domain::{
     DomainError, FinishReason, LlmProvider, LlmRequest, LlmResponse, LlmStream, Message,
-    MessageRole, StreamChunk, Usage,
+    MessageRole, Usage,
 };
 
 const DEFAULT_ANTHROPIC_BASE_URL: &str = "https://api.anthropic.com";
@@ -137,7 +138,12 @@ impl<C: HttpClientTrait> AnthropicProvider<C> {
 
         let mut llm_response = LlmResponse::new(response.id, response.model, message);
 
-        llm_response = llm_response.with_finish_reason(parse_stop_reason(&response.stop_reason));
+        llm_response = llm_response.with_finish_reason(
+            response
+                .stop_reason
+                .as_deref()
+                .map_or(FinishReason::Stop, parse_stop_reason),
+        );
 
         llm_response = llm_response.with_usage(Usage::new(
             response.usage.input_tokens,
@@ -159,7 +165,8 @@ impl<C: HttpClientTrait> LlmProvider for AnthropicProvider<C> {
         let response = self
             .client
             .post_json(&url, self.headers(), &body)
-            .await?;
+            .await
+            .map_err(|e| e.with_provider(self.provider_name()))?;
 
         self.parse_response(response)
     }
@@ -177,20 +184,17 @@ impl<C: HttpClientTrait> LlmProvider for AnthropicProvider<C> {
         let byte_stream = self
             .client
             .post_json_stream(&url, self.headers(), &body)
-            .await?;
-
-        let model_clone = model.to_string();
-        let stream = byte_stream.filter_map(move |result: Result<Bytes, DomainError>| {
-            let model = model_clone.clone();
-            async move {
-                match result {
-                    Ok(bytes) => {
-                        let text = String::from_utf8_lossy(&bytes);
-                        parse_sse_event(&text, &model)
-                    }
-                    Err(e) => Some(Err(e)),
-                }
-            }
+            .await
+            .map_err(|e| e.with_provider(self.provider_name()))?;
+
+        // Events can span reads, so one normalizer follows the whole answer
+        let mut normalizer = AnthropicStreamNormalizer::new(self.provider_name(), model);
+        let stream = byte_stream.flat_map(move |result: Result<Bytes, DomainError>| {
+            let chunks = match result {
+                Ok(bytes) => normalizer.push_sse(&String::from_utf8_lossy(&bytes)),
+                Err(e) => vec![Err(e)],
+            };
+            futures::stream::iter(chunks)
         });
 
         Ok(Box::pin(stream))
@@ -211,56 +215,6 @@ impl<C: HttpClientTrait> LlmProvider for AnthropicProvider<C> {
     }
 }
 
-fn parse_sse_event(text: &str, model: &str) -> Option<Result<StreamChunk, DomainError>> {
-    for line in text.lines() {
-        if let Some(data) = line.strip_prefix("data: ") {
-            if let Ok(event) = serde_json::from_str::<AnthropicStreamEvent>(data) {
-                match event.event_type.as_str() {
-                    "content_block_delta" => {
-                        if let Some(delta) = event.delta {
-                            if delta.delta_type == "text_delta" {
-                                if let Some(text) = delta.text {
-                                    return Some(Ok(
-                                        StreamChunk::new("".to_string(), model.to_string())
-                                            .with_delta(text),
-                                    ));
-                                }
-                            }
-                        }
-                    }
-                    "message_stop" => {
-                        return Some(Ok(
-                            StreamChunk::new("".to_string(), model.to_string())
-                                .with_finish_reason(FinishReason::Stop),
-                        ));
-                    }
-                    "message_delta" => {
-                        if let Some(delta) = event.delta {
-                            if let Some(reason) = delta.stop_reason {
-                                return Some(Ok(
-                                    StreamChunk::new("".to_string(), model.to_string())
-                                        .with_finish_reason(parse_stop_reason(&Some(reason))),
-                                ));
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-    }
-    None
-}
-
-fn parse_stop_reason(reason: &Option<String>) -> FinishReason {
-    match reason.as_deref() {
-        Some("end_turn") | Some("stop_sequence") => FinishReason::Stop,
-        Some("max_tokens") => FinishReason::Length,
-        Some("tool_use") => FinishReason::ToolCalls,
-        _ => FinishReason::Stop,
-    }
-}
-
 // Anthropic API types
 
 #[derive(Debug, Serialize)]
@@ -306,21 +260,6 @@ struct AnthropicUsage {
     output_tokens: u32,
 }
 
-#[derive(Debug, Deserialize)]
-struct AnthropicStreamEvent {
-    #[serde(rename = "type")]
-    event_type: String,
-    delta: Option<StreamDelta>,
-}
-
-#[derive(Debug, Deserialize)]
-struct StreamDelta {
-    #[serde(rename = "type", default)]
-    delta_type: String,
-    text: Option<String>,
-    stop_reason: Option<String>,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,233 @@
+//! Normalization of Anthropic Messages streaming events
+//!
+//! The Anthropic API (as server-sent events) and Claude models on Bedrock
+//! (as response stream payloads) emit the same events. Text arrives as
+//! `text_delta`s; a tool call is a `tool_use` content block whose JSON input
+//! follows as `input_json_delta` fragments. Both become [`StreamChunk`]s, the
+//! tool calls as OpenAI-style deltas indexed among the tool calls only.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::domain::{DomainError, FinishReason, StreamChunk, ToolCallDelta};
+
+/// Turns the streaming events of one answer into stream chunks
+#[derive(Debug)]
+pub(crate) struct AnthropicStreamNormalizer {
+    provider: &'static str,
+    model: String,
+    /// Partial server-sent event line left over from the last bytes
+    buffer: String,
+    /// Tool call index of each `tool_use` content block
+    tool_calls: HashMap<u32, u32>,
+    /// Whether a finish reason was already emitted; `message_stop` follows
+    /// the `message_delta` carrying the stop reason
+    finished: bool,
+}
+
+impl AnthropicStreamNormalizer {
+    pub(crate) fn new(provider: &'static str, model: impl Into<String>) -> Self {
+        Self {
+            provider,
+            model: model.into(),
+            buffer: String::new(),
+            tool_calls: HashMap::new(),
+            finished: false,
+        }
+    }
+
+    /// Chunks of the complete `data:` lines of server-sent event bytes; a
+    /// trailing partial line is kept for the next bytes
+    pub(crate) fn push_sse(&mut self, text: &str) -> Vec<Result<StreamChunk, DomainError>> {
+        self.buffer.push_str(text);
+        let Some(end) = self.buffer.rfind('\n') else {
+            return Vec::new();
+        };
+        let complete: String = self.buffer.drain(..=end).collect();
+
+        complete
+            .lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .filter_map(|data| self.push_event(data.trim()))
+            .collect()
+    }
+
+    /// Chunk of one JSON event, if it carries anything for the client
+    pub(crate) fn push_event(&mut self, data: &str) -> Option<Result<StreamChunk, DomainError>> {
+        let event: StreamEvent = serde_json::from_str(data).ok()?;
+
+        match event.event_type.as_str() {
+            "content_block_start" => {
+                let block = event.content_block?;
+                if block.block_type != "tool_use" {
+                    return None;
+                }
+                let index = self.tool_calls.len() as u32;
+                self.tool_calls.insert(event.index?, index);
+
+                Some(Ok(self.chunk().with_tool_call(ToolCallDelta::start(
+                    index,
+                    block.id.unwrap_or_default(),
+                    block.name.unwrap_or_default(),
+                ))))
+            }
+            "content_block_delta" => {
+                let delta = event.delta?;
+                match delta.delta_type.as_str() {
+                    "text_delta" => Some(Ok(self.chunk().with_delta(delta.text?))),
+                    "input_json_delta" => {
+                        let index = *self.tool_calls.get(&event.index?)?;
+                        let arguments = delta.partial_json.filter(|json| !json.is_empty())?;
+
+                        Some(Ok(self
+                            .chunk()
+                            .with_tool_call(ToolCallDelta::arguments(index, arguments))))
+                    }
+                    _ => None,
+                }
+            }
+            "message_delta" => {
+                let reason = event.delta?.stop_reason?;
+                self.finish(parse_stop_reason(&reason))
+            }
+            "message_stop" => self.finish(FinishReason::Stop),
+            "error" => {
+                let message = event
+                    .error
+                    .map(|e| format!("{}: {}", e.error_type, e.message))
+                    .unwrap_or_else(|| "Stream error".to_string());
+
+                Some(Err(DomainError::provider(self.provider, message)))
+            }
+            _ => None,
+        }
+    }
+
+    fn chunk(&self) -> StreamChunk {
+        StreamChunk::new(String::new(), self.model.clone())
+    }
+
+    fn finish(&mut self, reason: FinishReason) -> Option<Result<StreamChunk, DomainError>> {
+        if std::mem::replace(&mut self.finished, true) {
+            return None;
+        }
+
+        Some(Ok(self.chunk().with_finish_reason(reason)))
+    }
+}
+
+/// Finish reason of an Anthropic `stop_reason`
+pub(crate) fn parse_stop_reason(reason: &str) -> FinishReason {
+    match reason {
+        "max_tokens" => FinishReason::Length,
+        "tool_use" => FinishReason::ToolCalls,
+        _ => FinishReason::Stop,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    /// Content block the event belongs to
+    index: Option<u32>,
+    content_block: Option<StreamContentBlock>,
+    delta: Option<StreamDelta>,
+    error: Option<StreamError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamContentBlock {
+    #[serde(rename = "type")]
+    block_type: String,
+    id: Option<String>,
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamDelta {
+    #[serde(rename = "type", default)]
+    delta_type: String,
+    text: Option<String>,
+    partial_json: Option<String>,
+    stop_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamError {
+    #[serde(rename = "type", default)]
+    error_type: String,
+    #[serde(default)]
+    message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn events() -> Vec<&'static str> {
+        vec![
+            r#"{"type":"message_start","message":{"id":"msg_1"}}"#,
+            r#"{"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+            r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Let me check."}}"#,
+            r#"{"type":"content_block_stop","index":0}"#,
+            r#"{"type":"content_block_start","index":1,"content_block":{"type":"tool_use","id":"toolu_1","name":"get_weather","input":{}}}"#,
+            r#"{"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":""}}"#,
+            r#"{"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"{\"city\": "}}"#,
+            r#"{"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"\"Paris\"}"}}"#,
+            r#"{"type":"content_block_stop","index":1}"#,
+            r#"{"type":"message_delta","delta":{"stop_reason":"tool_use"},"usage":{"output_tokens":20}}"#,
+            r#"{"type":"message_stop"}"#,
+        ]
+    }
+
+    #[test]
+    fn test_tool_use_events() {
+        let mut normalizer = AnthropicStreamNormalizer::new("bedrock", "claude");
+        let chunks: Vec<StreamChunk> = events()
+            .into_iter()
+            .filter_map(|event| normalizer.push_event(event))
+            .map(Result::unwrap)
+            .collect();
+
+        assert_eq!(chunks.len(), 5);
+        assert_eq!(chunks[0].delta.as_deref(), Some("Let me check."));
+        assert_eq!(
+            chunks[1].tool_calls,
+            vec![ToolCallDelta::start(0, "toolu_1", "get_weather")]
+        );
+        assert_eq!(
+            chunks[2].tool_calls,
+            vec![ToolCallDelta::arguments(0, "{\"city\": ")]
+        );
+        assert_eq!(chunks[3].tool_calls[0].arguments, "\"Paris\"}");
+        assert_eq!(chunks[4].finish_reason, Some(FinishReason::ToolCalls));
+    }
+
+    #[test]
+    fn test_sse_split_across_reads() {
+        let mut normalizer = AnthropicStreamNormalizer::new("anthropic", "claude");
+        let sse: String = events()
+            .into_iter()
+            .map(|event| format!("event: x\ndata: {}\n\n", event))
+            .collect();
+        let (first, second) = sse.split_at(sse.len() / 2);
+
+        let mut chunks = normalizer.push_sse(first);
+        chunks.extend(normalizer.push_sse(second));
+
+        assert_eq!(chunks.len(), 5);
+        assert!(normalizer.buffer.is_empty());
+    }
+
+    #[test]
+    fn test_error_event() {
+        let mut normalizer = AnthropicStreamNormalizer::new("anthropic", "claude");
+        let result = normalizer
+            .push_event(r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#)
+            .unwrap();
+
+        assert!(result.unwrap_err().to_string().contains("overloaded_error: Overloaded"));
+    }
+}
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use super::http_client::HttpClientTrait;
 use crate::domain::{
     DomainError, FinishReason, LlmProvider, LlmRequest, LlmResponse, LlmStream, Message,
-    MessageRole, StreamChunk, Usage,
+    MessageRole, StreamChunk, ToolCallDelta, Usage,
 };
 
 /// Azure OpenAI API configuration
@@ -141,7 +141,8 @@ impl<C: HttpClientTrait> LlmProvider for AzureOpenAiProvider<C> {
         let response = self
             .client
             .post_json(&url, self.headers(), &body)
-            .await?;
+            .await
+            .map_err(|e| e.with_provider(self.provider_name()))?;
 
         self.parse_response(response)
     }
@@ -160,7 +161,8 @@ impl<C: HttpClientTrait> LlmProvider for AzureOpenAiProvider<C> {
         let byte_stream = self
             .client
             .post_json_stream(&url, self.headers(), &body)
-            .await?;
+            .await
+            .map_err(|e| e.with_provider(self.provider_name()))?;
 
         let model_clone = model.to_string();
         let stream = byte_stream.filter_map(move |result: Result<Bytes, DomainError>| {
@@ -206,6 +208,15 @@ fn parse_sse_chunks(text: &str, model: &str) -> Option<Result<StreamChunk, Domai
                         stream_chunk = stream_chunk.with_delta(delta);
                     }
 
+                    for call in choice.delta.tool_calls {
+                        stream_chunk = stream_chunk.with_tool_call(ToolCallDelta {
+                            index: call.index,
+                            id: call.id,
+                            name: call.function.name,
+                            arguments: call.function.arguments.unwrap_or_default(),
+                        });
+                    }
+
                     if let Some(reason) = choice.finish_reason {
                         stream_chunk =
                             stream_chunk.with_finish_reason(parse_finish_reason(&reason));
@@ -293,6 +304,22 @@ struct AzureStreamChoice {
 #[derive(Debug, Deserialize)]
 struct AzureDelta {
     content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<AzureToolCallDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AzureToolCallDelta {
+    index: u32,
+    id: Option<String>,
+    #[serde(default)]
+    function: AzureFunctionDelta,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AzureFunctionDelta {
+    name: Option<String>,
+    arguments: Option<String>,
 }
 
 #[cfg(test)]
@@ -1,13 +1,21 @@
 //! AWS Bedrock LLM provider implementation
 
+use std::pin::Pin;
+
 use async_trait::async_trait;
+use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
 
+use super::anthropic_stream::AnthropicStreamNormalizer;
+use crate::domain::llm::ProviderRateLimit;
 use crate::domain::{
     DomainError, FinishReason, LlmProvider, LlmRequest, LlmResponse, LlmStream, Message,
     MessageRole, Usage,
 };
 
+/// Payloads of a Bedrock response stream, one model event each
+pub type BedrockPayloadStream = Pin<Box<dyn Stream<Item = Result<Vec<u8>, DomainError>> + Send>>;
+
 /// AWS Bedrock client trait for dependency injection
 #[async_trait]
 pub trait BedrockClientTrait: Send + Sync + std::fmt::Debug {
@@ -16,6 +24,12 @@ pub trait BedrockClientTrait: Send + Sync + std::fmt::Debug {
         model_id: &str,
         body: Vec<u8>,
     ) -> Result<Vec<u8>, DomainError>;
+
+    async fn invoke_model_stream(
+        &self,
+        model_id: &str,
+        body: Vec<u8>,
+    ) -> Result<BedrockPayloadStream, DomainError>;
 }
 
 /// AWS Bedrock API provider
@@ -186,14 +200,34 @@ impl<C: BedrockClientTrait> LlmProvider for BedrockProvider<C> {
     async fn chat_stream(
         &self,
         model: &str,
-        _request: LlmRequest,
+        request: LlmRequest,
     ) -> Result<LlmStream, DomainError> {
-        // Bedrock streaming requires InvokeModelWithResponseStream
-        // For now, return an error indicating streaming is not yet implemented
-        Err(DomainError::provider(
-            "bedrock",
-            format!("Streaming not yet implemented for model: {}", model),
-        ))
+        // Claude models stream the Anthropic Messages events; the other
+        // model families have their own event formats
+        if !self.is_claude_model(model) {
+            return Err(DomainError::provider(
+                "bedrock",
+                format!("Streaming not yet implemented for model: {}", model),
+            ));
+        }
+
+        let body = self.build_claude_request(&request);
+        let body_bytes = serde_json::to_vec(&body).map_err(|e| {
+            DomainError::provider("bedrock", format!("Failed to serialize request: {}", e))
+        })?;
+
+        let payloads = self.client.invoke_model_stream(model, body_bytes).await?;
+
+        let mut normalizer = AnthropicStreamNormalizer::new("bedrock", model);
+        let stream = payloads.filter_map(move |result| {
+            let chunk = match result {
+                Ok(payload) => normalizer.push_event(&String::from_utf8_lossy(&payload)),
+                Err(e) => Some(Err(e)),
+            };
+            futures::future::ready(chunk)
+        });
+
+        Ok(Box::pin(stream))
     }
 
     fn provider_name(&self) -> &'static str {
@@ -369,10 +403,100 @@ impl BedrockClientTrait for BedrockClient {
             .content_type("application/json")
             .send()
             .await
-            .map_err(|e| DomainError::provider("bedrock", format!("API error: {}", e)))?;
+            .map_err(|e| {
+                let error = DomainError::provider("bedrock", invoke_error_message(&e));
+                let rate_limit = e.raw_response().and_then(|response| {
+                    ProviderRateLimit::from_headers(response.headers().iter())
+                });
+                match rate_limit {
+                    Some(limit) => error.with_rate_limit(limit),
+                    None => error,
+                }
+            })?;
 
         Ok(response.body.into_inner())
     }
+
+    async fn invoke_model_stream(
+        &self,
+        model_id: &str,
+        body: Vec<u8>,
+    ) -> Result<BedrockPayloadStream, DomainError> {
+        use aws_sdk_bedrockruntime::error::DisplayErrorContext;
+        use aws_sdk_bedrockruntime::types::ResponseStream;
+
+        let blob = aws_sdk_bedrockruntime::primitives::Blob::new(body);
+
+        let response = self
+            .client
+            .invoke_model_with_response_stream()
+            .model_id(model_id)
+            .body(blob)
+            .content_type("application/json")
+            .send()
+            .await
+            .map_err(|e| {
+                let error = DomainError::provider("bedrock", invoke_error_message(&e));
+                let rate_limit = e.raw_response().and_then(|response| {
+                    ProviderRateLimit::from_headers(response.headers().iter())
+                });
+                match rate_limit {
+                    Some(limit) => error.with_rate_limit(limit),
+                    None => error,
+                }
+            })?;
+
+        let stream = futures::stream::unfold(Some(response.body), |receiver| async move {
+            let mut receiver = receiver?;
+            loop {
+                match receiver.recv().await {
+                    Ok(Some(ResponseStream::Chunk(part))) => {
+                        let Some(bytes) = part.bytes else { continue };
+                        return Some((Ok(bytes.into_inner()), Some(receiver)));
+                    }
+                    Ok(Some(_)) => continue,
+                    Ok(None) => return None,
+                    Err(e) => {
+                        let error = DomainError::provider(
+                            "bedrock",
+                            format!("Stream error: {}", DisplayErrorContext(&e)),
+                        );
+                        return Some((Err(error), None));
+                    }
+                }
+            }
+        });
+
+        Ok(Box::pin(stream))
+    }
+}
+
+/// Error message in the `HTTP <status>: <body>` form of the HTTP providers,
+/// with the AWS error type as `__type`, so failures can be classified
+fn invoke_error_message<E>(
+    error: &aws_sdk_bedrockruntime::error::SdkError<
+        E,
+        aws_sdk_bedrockruntime::config::http::HttpResponse,
+    >,
+) -> String
+where
+    E: aws_sdk_bedrockruntime::error::ProvideErrorMetadata + std::error::Error + 'static,
+{
+    use aws_sdk_bedrockruntime::error::DisplayErrorContext;
+
+    match error.as_service_error() {
+        Some(service_error) => {
+            let body = serde_json::json!({
+                "__type": service_error.code(),
+                "message": service_error.message(),
+            });
+            match error.raw_response().map(|response| response.status().as_u16()) {
+                Some(status) => format!("API error: HTTP {}: {}", status, body),
+                None => format!("API error: {}", body),
+            }
+        }
+        None => format!("API error: {}", DisplayErrorContext(error)),
+    }
 }
 
 #[cfg(test)]
@@ -384,6 +508,7 @@ pub mod mock {
     #[derive(Debug, Default)]
     pub struct MockBedrockClient {
         responses: Mutex<HashMap<String, Vec<u8>>>,
+        streams: Mutex<HashMap<String, Vec<serde_json::Value>>>,
         errors: Mutex<HashMap<String, String>>,
     }
 
@@ -401,6 +526,15 @@ pub mod mock {
             self
         }
 
+        /// Events streamed for the model, one payload each
+        pub fn with_stream(self, model_id: &str, events: Vec<serde_json::Value>) -> Self {
+            self.streams
+                .lock()
+                .unwrap()
+                .insert(model_id.to_string(), events);
+            self
+        }
+
         pub fn with_error(self, model_id: &str, error: &str) -> Self {
             self.errors
                 .lock()
@@ -430,6 +564,31 @@ pub mod mock {
                     DomainError::provider("bedrock", format!("No mock response for {}", model_id))
                 })
         }
+
+        async fn invoke_model_stream(
+            &self,
+            model_id: &str,
+            _body: Vec<u8>,
+        ) -> Result<BedrockPayloadStream, DomainError> {
+            if let Some(error) = self.errors.lock().unwrap().get(model_id) {
+                return Err(DomainError::provider("bedrock", error.clone()));
+            }
+
+            let events = self
+                .streams
+                .lock()
+                .unwrap()
+                .get(model_id)
+                .cloned()
+                .ok_or_else(|| {
+                    DomainError::provider("bedrock", format!("No mock stream for {}", model_id))
+                })?;
+            let payloads = events
+                .into_iter()
+                .map(|event| Ok(serde_json::to_vec(&event).unwrap()));
+
+            Ok(Box::pin(futures::stream::iter(payloads)))
+        }
     }
 }
 
@@ -514,4 +673,53 @@ mod tests {
         let result = provider.chat(model_id, request).await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_bedrock_claude_stream_tool_calls() {
+        use crate::domain::ToolCallDelta;
+        use serde_json::json;
+
+        let model_id = "anthropic.claude-3-5-sonnet-20241022-v2:0";
+        let client = MockBedrockClient::new().with_stream(
+            model_id,
+            vec![
+                json!({"type": "message_start", "message": {"id": "msg_1"}}),
+                json!({"type": "content_block_start", "index": 0,
+                    "content_block": {"type": "tool_use", "id": "toolu_1", "name": "search"}}),
+                json!({"type": "content_block_delta", "index": 0,
+                    "delta": {"type": "input_json_delta", "partial_json": "{\"q\": \"rust\"}"}}),
+                json!({"type": "message_delta", "delta": {"stop_reason": "tool_use"}}),
+                json!({"type": "message_stop"}),
+            ],
+        );
+        let provider = BedrockProvider::new(client);
+
+        let request = LlmRequest::builder().user("Search for rust").build();
+        let chunks: Vec<_> = provider
+            .chat_stream(model_id, request)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(
+            chunks[0].tool_calls,
+            vec![ToolCallDelta::start(0, "toolu_1", "search")]
+        );
+        assert_eq!(chunks[1].tool_calls[0].arguments, "{\"q\": \"rust\"}");
+        assert_eq!(chunks[2].finish_reason, Some(FinishReason::ToolCalls));
+    }
+
+    #[tokio::test]
+    async fn test_bedrock_titan_stream_unsupported() {
+        let provider = BedrockProvider::new(MockBedrockClient::new());
+        let request = LlmRequest::builder().user("Hello!").build();
+
+        let result = provider
+            .chat_stream("amazon.titan-text-express-v1", request)
+            .await;
+        assert!(result.is_err());
+    }
 }
@@ -0,0 +1,136 @@
+//! Provisioned throughput tracking
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::domain::model::ModelCapacity;
+
+/// Window provisioned throughput is measured over
+const CAPACITY_WINDOW: Duration = Duration::from_secs(60);
+
+/// Requests and tokens sent to a model in the last minute
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, ToSchema)]
+pub struct CapacityUsage {
+    pub requests: u64,
+    pub tokens: u64,
+}
+
+/// Tracks the traffic sent to models with provisioned throughput, so the
+/// router can spill requests that would exceed it to on-demand fallbacks.
+///
+/// Usage is counted per replica: with several replicas, provision each
+/// model's limits divided by the replica count.
+#[derive(Debug, Default)]
+pub struct ModelCapacityTracker {
+    windows: Mutex<HashMap<String, VecDeque<(Instant, u64)>>>,
+}
+
+impl ModelCapacityTracker {
+    /// Create a new tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count a request of `tokens` against the model's capacity. Returns
+    /// `false`, counting nothing, if it would exceed a limit.
+    pub fn try_reserve(&self, model_id: &str, capacity: &ModelCapacity, tokens: u64) -> bool {
+        self.try_reserve_at(model_id, capacity, tokens, Instant::now())
+    }
+
+    /// Traffic counted against a model in the last minute
+    pub fn usage(&self, model_id: &str) -> CapacityUsage {
+        let mut windows = self.windows.lock().unwrap();
+
+        match windows.get_mut(model_id) {
+            Some(window) => {
+                expire(window, Instant::now());
+                window_usage(window)
+            }
+            None => CapacityUsage::default(),
+        }
+    }
+
+    fn try_reserve_at(
+        &self,
+        model_id: &str,
+        capacity: &ModelCapacity,
+        tokens: u64,
+        now: Instant,
+    ) -> bool {
+        let mut windows = self.windows.lock().unwrap();
+        let window = windows.entry(model_id.to_string()).or_default();
+        expire(window, now);
+
+        let usage = window_usage(window);
+
+        if capacity
+            .requests_per_minute
+            .is_some_and(|limit| usage.requests >= limit)
+            || capacity
+                .tokens_per_minute
+                .is_some_and(|limit| usage.tokens + tokens > limit)
+        {
+            return false;
+        }
+
+        window.push_back((now, tokens));
+        true
+    }
+}
+
+fn expire(window: &mut VecDeque<(Instant, u64)>, now: Instant) {
+    while window
+        .front()
+        .is_some_and(|(at, _)| now.duration_since(*at) >= CAPACITY_WINDOW)
+    {
+        window.pop_front();
+    }
+}
+
+fn window_usage(window: &VecDeque<(Instant, u64)>) -> CapacityUsage {
+    CapacityUsage {
+        requests: window.len() as u64,
+        tokens: window.iter().map(|(_, tokens)| tokens).sum(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_per_minute() {
+        let tracker = ModelCapacityTracker::new();
+        let capacity = ModelCapacity::new().with_tokens_per_minute(1000);
+        let now = Instant::now();
+
+        assert!(tracker.try_reserve_at("ptu", &capacity, 600, now));
+        assert!(!tracker.try_reserve_at("ptu", &capacity, 600, now));
+        assert!(tracker.try_reserve_at("ptu", &capacity, 400, now));
+        assert_eq!(
+            tracker.usage("ptu"),
+            CapacityUsage {
+                requests: 2,
+                tokens: 1000
+            }
+        );
+
+        assert!(tracker.try_reserve_at("ptu", &capacity, 600, now + CAPACITY_WINDOW));
+    }
+
+    #[test]
+    fn test_requests_per_minute() {
+        let tracker = ModelCapacityTracker::new();
+        let capacity = ModelCapacity::new().with_requests_per_minute(2);
+        let now = Instant::now();
+
+        assert!(tracker.try_reserve_at("ptu", &capacity, 10, now));
+        assert!(tracker.try_reserve_at("ptu", &capacity, 10, now));
+        assert!(!tracker.try_reserve_at("ptu", &capacity, 10, now));
+        assert!(tracker.try_reserve_at("other", &capacity, 10, now));
+    }
+}
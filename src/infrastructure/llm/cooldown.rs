@@ -0,0 +1,85 @@
+//! Throttled credential tracking
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Cooldown of a throttled credential that did not say when to retry
+pub const DEFAULT_CREDENTIAL_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Longest cooldown honoured, whatever the provider announces
+const MAX_CREDENTIAL_COOLDOWN: Duration = Duration::from_secs(600);
+
+/// Tracks credentials a provider throttled, so the router can send requests
+/// to fallback models on other credentials until the cooldown is over.
+///
+/// Cooldowns are kept per replica.
+#[derive(Debug, Default)]
+pub struct CredentialCooldowns {
+    until: Mutex<HashMap<String, Instant>>,
+}
+
+impl CredentialCooldowns {
+    /// Create a new tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Put a credential on cooldown, keeping a longer running one
+    pub fn record(&self, credential_id: &str, cooldown: Duration) {
+        self.record_at(credential_id, cooldown, Instant::now());
+    }
+
+    /// Time left on the cooldown of a credential
+    pub fn remaining(&self, credential_id: &str) -> Option<Duration> {
+        self.remaining_at(credential_id, Instant::now())
+    }
+
+    fn record_at(&self, credential_id: &str, cooldown: Duration, now: Instant) {
+        let until = now + cooldown.min(MAX_CREDENTIAL_COOLDOWN);
+        let mut cooldowns = self.until.lock().unwrap();
+
+        cooldowns.retain(|_, until| *until > now);
+        let entry = cooldowns.entry(credential_id.to_string()).or_insert(until);
+        *entry = (*entry).max(until);
+    }
+
+    fn remaining_at(&self, credential_id: &str, now: Instant) -> Option<Duration> {
+        let cooldowns = self.until.lock().unwrap();
+
+        cooldowns
+            .get(credential_id)
+            .filter(|until| **until > now)
+            .map(|until| *until - now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooldown_window() {
+        let cooldowns = CredentialCooldowns::new();
+        let now = Instant::now();
+
+        cooldowns.record_at("openai-prod", Duration::from_secs(20), now);
+        cooldowns.record_at("openai-prod", Duration::from_secs(5), now);
+
+        assert_eq!(
+            cooldowns.remaining_at("openai-prod", now + Duration::from_secs(10)),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            cooldowns.remaining_at("openai-prod", now + Duration::from_secs(20)),
+            None
+        );
+        assert_eq!(cooldowns.remaining_at("anthropic", now), None);
+
+        cooldowns.record_at("anthropic", Duration::from_secs(86_400), now);
+        assert_eq!(
+            cooldowns.remaining_at("anthropic", now),
+            Some(MAX_CREDENTIAL_COOLDOWN)
+        );
+    }
+}
@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use super::bedrock::{BedrockClient, BedrockProvider};
 use super::http_client::HttpClient;
+use super::mock::{MockProvider, MockProviderConfig};
 use super::{AnthropicProvider, AzureOpenAiProvider, OpenAiProvider};
 use crate::domain::{Credential, CredentialType, DomainError, LlmProvider};
 use crate::infrastructure::llm::azure_openai::AzureOpenAiConfig;
@@ -22,6 +23,11 @@ pub enum LlmProviderConfig {
         #[serde(default)]
         region: Option<String>,
     },
+    Mock {
+        /// Query-string options, e.g. `latency_ms=250&error_rate=0.1`
+        #[serde(default)]
+        options: Option<String>,
+    },
 }
 
 fn default_api_version() -> String {
@@ -72,6 +78,18 @@ impl LlmProviderFactory {
                     "Bedrock provider requires async initialization. Use create_bedrock_async instead.",
                 ))
             }
+
+            LlmProviderConfig::Mock { options } => {
+                Self::validate_credential_type(credential, &CredentialType::Mock)?;
+
+                let mut config = MockProviderConfig::default();
+
+                if let Some(options) = options {
+                    config = config.with_options(options)?;
+                }
+
+                Ok(Arc::new(MockProvider::new(config)))
+            }
         }
     }
 
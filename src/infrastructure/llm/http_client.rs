@@ -3,7 +3,9 @@ use bytes::Bytes;
 use futures::Stream;
 use std::pin::Pin;
 
+use crate::domain::llm::ProviderRateLimit;
 use crate::domain::DomainError;
+use crate::infrastructure::observability::current_trace_headers;
 
 /// Stream type for HTTP responses
 pub type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, DomainError>> + Send>>;
@@ -55,6 +57,24 @@ impl Default for HttpClient {
     }
 }
 
+/// Provider error of a failed response, with its rate limit hints
+async fn error_response(response: reqwest::Response) -> DomainError {
+    let status = response.status();
+    let rate_limit = ProviderRateLimit::from_headers(
+        response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?))),
+    );
+    let error_body = response.text().await.unwrap_or_default();
+    let error = DomainError::provider("http", format!("HTTP {}: {}", status, error_body));
+
+    match rate_limit {
+        Some(limit) => error.with_rate_limit(limit),
+        None => error,
+    }
+}
+
 #[async_trait]
 impl HttpClientTrait for HttpClient {
     async fn post_json(
@@ -63,7 +83,7 @@ impl HttpClientTrait for HttpClient {
         headers: Vec<(&str, &str)>,
         body: &serde_json::Value,
     ) -> Result<serde_json::Value, DomainError> {
-        let mut request = self.client.post(url);
+        let mut request = self.client.post(url).headers(current_trace_headers());
 
         for (key, value) in headers {
             request = request.header(key, value);
@@ -76,12 +96,7 @@ impl HttpClientTrait for HttpClient {
             .map_err(|e| DomainError::provider("http", format!("Request failed: {}", e)))?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let error_body = response.text().await.unwrap_or_default();
-            return Err(DomainError::provider(
-                "http",
-                format!("HTTP {}: {}", status, error_body),
-            ));
+            return Err(error_response(response).await);
         }
 
         response
@@ -96,7 +111,7 @@ impl HttpClientTrait for HttpClient {
         headers: Vec<(&str, &str)>,
         body: &serde_json::Value,
     ) -> Result<ByteStream, DomainError> {
-        let mut request = self.client.post(url);
+        let mut request = self.client.post(url).headers(current_trace_headers());
 
         for (key, value) in headers {
             request = request.header(key, value);
@@ -109,12 +124,7 @@ impl HttpClientTrait for HttpClient {
             .map_err(|e| DomainError::provider("http", format!("Request failed: {}", e)))?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let error_body = response.text().await.unwrap_or_default();
-            return Err(DomainError::provider(
-                "http",
-                format!("HTTP {}: {}", status, error_body),
-            ));
+            return Err(error_response(response).await);
         }
 
         use futures::StreamExt;
@@ -0,0 +1,343 @@
+//! Mock LLM provider
+//!
+//! Answers without calling any upstream API, so integrations can be built
+//! and tested against the gateway without spending tokens. Responses are
+//! deterministic: a fixed text when configured, otherwise an echo of the last
+//! user message. Latency and failures can be injected to exercise timeouts,
+//! retries and fallbacks.
+
+use async_trait::async_trait;
+use futures::stream;
+use rand::Rng;
+use std::time::Duration;
+
+use crate::domain::{
+    DomainError, FinishReason, LlmProvider, LlmRequest, LlmResponse, LlmStream, Message,
+    MessageRole, StreamChunk, Usage,
+};
+
+const PROVIDER_NAME: &str = "mock";
+const DEFAULT_ERROR_MESSAGE: &str = "Injected mock provider error";
+
+/// Behaviour of a mock provider
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockProviderConfig {
+    /// Fixed response content; the last user message is echoed when unset
+    pub response: Option<String>,
+    /// Delay before answering (before the first chunk when streaming)
+    pub latency: Duration,
+    /// Probability, between 0 and 1, of a request failing
+    pub error_rate: f64,
+    /// Message of the injected errors
+    pub error_message: String,
+}
+
+impl Default for MockProviderConfig {
+    fn default() -> Self {
+        Self {
+            response: None,
+            latency: Duration::ZERO,
+            error_rate: 0.0,
+            error_message: DEFAULT_ERROR_MESSAGE.to_string(),
+        }
+    }
+}
+
+impl MockProviderConfig {
+    /// Set one option: `response`, `latency_ms`, `error_rate` or
+    /// `error_message`
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), DomainError> {
+        match key {
+            "response" => self.response = Some(value.to_string()),
+            "latency_ms" => {
+                let millis = value.parse::<u64>().map_err(|_| {
+                    DomainError::configuration(format!(
+                        "Mock option latency_ms must be a number of milliseconds, got '{}'",
+                        value
+                    ))
+                })?;
+                self.latency = Duration::from_millis(millis);
+            }
+            "error_rate" => {
+                let rate = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|rate| (0.0..=1.0).contains(rate))
+                    .ok_or_else(|| {
+                        DomainError::configuration(format!(
+                            "Mock option error_rate must be between 0 and 1, got '{}'",
+                            value
+                        ))
+                    })?;
+                self.error_rate = rate;
+            }
+            "error_message" => self.error_message = value.to_string(),
+            other => {
+                return Err(DomainError::configuration(format!(
+                    "Unknown mock option '{}'",
+                    other
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply options written as a query string, e.g.
+    /// `latency_ms=250&error_rate=0.1&response=Hello%20world`; anything up to
+    /// a `?` is ignored, so `mock://local?latency_ms=250` works too
+    pub fn with_options(mut self, options: &str) -> Result<Self, DomainError> {
+        let query = options.split_once('?').map_or(options, |(_, query)| query);
+        let url = reqwest::Url::parse(&format!("mock:///?{}", query))
+            .map_err(|e| DomainError::configuration(format!("Invalid mock options: {}", e)))?;
+
+        for (key, value) in url.query_pairs() {
+            self.set(&key, &value)?;
+        }
+
+        Ok(self)
+    }
+}
+
+/// LLM provider returning canned responses
+#[derive(Debug, Clone, Default)]
+pub struct MockProvider {
+    config: MockProviderConfig,
+}
+
+impl MockProvider {
+    pub fn new(config: MockProviderConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &MockProviderConfig {
+        &self.config
+    }
+
+    /// Wait the configured latency, then fail if an error is injected
+    async fn simulate(&self) -> Result<(), DomainError> {
+        if !self.config.latency.is_zero() {
+            tokio::time::sleep(self.config.latency).await;
+        }
+
+        if self.config.error_rate > 0.0
+            && rand::thread_rng().r#gen::<f64>() < self.config.error_rate
+        {
+            return Err(DomainError::provider(
+                PROVIDER_NAME,
+                &self.config.error_message,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Response content and finish reason, cut to `max_tokens` words
+    fn content(&self, request: &LlmRequest) -> (String, FinishReason) {
+        let content = match &self.config.response {
+            Some(response) => response.clone(),
+            None => {
+                let last_user = request
+                    .messages
+                    .iter()
+                    .rev()
+                    .find(|m| m.role == MessageRole::User)
+                    .and_then(Message::content_text)
+                    .unwrap_or_default();
+
+                format!("Mock response to: {}", last_user)
+            }
+        };
+
+        match request.max_tokens {
+            Some(max) if count_tokens(&content) > max => {
+                let truncated: Vec<&str> = content.split_whitespace().take(max as usize).collect();
+                (truncated.join(" "), FinishReason::Length)
+            }
+            _ => (content, FinishReason::Stop),
+        }
+    }
+
+    fn usage(request: &LlmRequest, content: &str) -> Usage {
+        let prompt_tokens = request
+            .messages
+            .iter()
+            .filter_map(Message::content_text)
+            .map(count_tokens)
+            .sum();
+
+        Usage::new(prompt_tokens, count_tokens(content))
+    }
+}
+
+/// Whitespace-separated words, standing in for tokens
+fn count_tokens(text: &str) -> u32 {
+    text.split_whitespace().count() as u32
+}
+
+fn response_id() -> String {
+    format!("mock-{}", uuid::Uuid::new_v4())
+}
+
+#[async_trait]
+impl LlmProvider for MockProvider {
+    async fn chat(&self, model: &str, request: LlmRequest) -> Result<LlmResponse, DomainError> {
+        self.simulate().await?;
+
+        let (content, finish_reason) = self.content(&request);
+        let usage = Self::usage(&request, &content);
+
+        Ok(LlmResponse::new(
+            response_id(),
+            model.to_string(),
+            Message::assistant(content),
+        )
+        .with_finish_reason(finish_reason)
+        .with_usage(usage))
+    }
+
+    async fn chat_stream(
+        &self,
+        model: &str,
+        request: LlmRequest,
+    ) -> Result<LlmStream, DomainError> {
+        self.simulate().await?;
+
+        let (content, finish_reason) = self.content(&request);
+        let usage = Self::usage(&request, &content);
+        let id = response_id();
+        let model = model.to_string();
+
+        // One chunk per word, keeping the separating whitespace
+        let mut chunks: Vec<Result<StreamChunk, DomainError>> = content
+            .split_inclusive(char::is_whitespace)
+            .map(|word| Ok(StreamChunk::new(id.clone(), model.clone()).with_delta(word)))
+            .collect();
+
+        chunks.push(Ok(StreamChunk::new(id, model)
+            .with_finish_reason(finish_reason)
+            .with_usage(usage)));
+
+        Ok(Box::pin(stream::iter(chunks)))
+    }
+
+    fn provider_name(&self) -> &'static str {
+        PROVIDER_NAME
+    }
+
+    fn available_models(&self) -> Vec<&'static str> {
+        vec!["mock-model"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    #[tokio::test]
+    async fn test_chat_echoes_last_user_message() {
+        let provider = MockProvider::default();
+        let request = LlmRequest::new(vec![
+            Message::system("Be brief"),
+            Message::user("Hello there"),
+        ]);
+
+        let response = provider.chat("any-model", request).await.unwrap();
+
+        assert_eq!(response.content(), Some("Mock response to: Hello there"));
+        assert_eq!(response.model, "any-model");
+        assert_eq!(response.finish_reason, Some(FinishReason::Stop));
+
+        let usage = response.usage.unwrap();
+        assert_eq!(usage.prompt_tokens, 4);
+        assert_eq!(usage.completion_tokens, 5);
+    }
+
+    #[tokio::test]
+    async fn test_chat_fixed_response_truncated_to_max_tokens() {
+        let config = MockProviderConfig {
+            response: Some("one two three four".to_string()),
+            ..Default::default()
+        };
+        let provider = MockProvider::new(config);
+        let request = LlmRequest::builder()
+            .message(Message::user("Count"))
+            .max_tokens(2)
+            .build();
+
+        let response = provider.chat("mock-model", request).await.unwrap();
+
+        assert_eq!(response.content(), Some("one two"));
+        assert_eq!(response.finish_reason, Some(FinishReason::Length));
+    }
+
+    #[tokio::test]
+    async fn test_chat_stream_chunks_by_word() {
+        let config = MockProviderConfig {
+            response: Some("Hello mock world".to_string()),
+            ..Default::default()
+        };
+        let provider = MockProvider::new(config);
+        let request = LlmRequest::new(vec![Message::user("Hi")]);
+
+        let chunks: Vec<StreamChunk> = provider
+            .chat_stream("mock-model", request)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(chunks.len(), 4);
+
+        let content: String = chunks.iter().filter_map(|c| c.delta.clone()).collect();
+        assert_eq!(content, "Hello mock world");
+
+        let last = chunks.last().unwrap();
+        assert_eq!(last.finish_reason, Some(FinishReason::Stop));
+        assert_eq!(last.usage.as_ref().unwrap().completion_tokens, 3);
+    }
+
+    #[tokio::test]
+    async fn test_error_injection() {
+        let config = MockProviderConfig::default()
+            .with_options("error_rate=1&error_message=Upstream%20down")
+            .unwrap();
+        let provider = MockProvider::new(config);
+
+        let err = provider
+            .chat("mock-model", LlmRequest::new(vec![Message::user("Hi")]))
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("Upstream down"));
+    }
+
+    #[test]
+    fn test_with_options() {
+        let config = MockProviderConfig::default()
+            .with_options("mock://local?latency_ms=250&response=Hi%20there")
+            .unwrap();
+
+        assert_eq!(config.latency, Duration::from_millis(250));
+        assert_eq!(config.response.as_deref(), Some("Hi there"));
+        assert_eq!(config.error_rate, 0.0);
+
+        assert!(
+            MockProviderConfig::default()
+                .with_options("error_rate=2")
+                .is_err()
+        );
+        assert!(
+            MockProviderConfig::default()
+                .with_options("latency_ms=soon")
+                .is_err()
+        );
+        assert!(
+            MockProviderConfig::default()
+                .with_options("colour=blue")
+                .is_err()
+        );
+    }
+}
@@ -1,17 +1,24 @@
 //! LLM provider implementations
 
 mod anthropic;
+mod anthropic_stream;
 mod azure_openai;
 mod bedrock;
+mod capacity;
+mod cooldown;
 mod factory;
 mod http_client;
+mod mock;
 mod openai;
 
 pub use anthropic::AnthropicProvider;
 pub use azure_openai::{AzureOpenAiConfig, AzureOpenAiProvider};
-pub use bedrock::{BedrockClient, BedrockClientTrait, BedrockProvider};
+pub use bedrock::{BedrockClient, BedrockClientTrait, BedrockPayloadStream, BedrockProvider};
+pub use capacity::{CapacityUsage, ModelCapacityTracker};
+pub use cooldown::{CredentialCooldowns, DEFAULT_CREDENTIAL_COOLDOWN};
 pub use factory::{LlmProviderConfig, LlmProviderFactory};
 pub use http_client::{HttpClient, HttpClientTrait};
+pub use mock::{MockProvider, MockProviderConfig};
 pub use openai::OpenAiProvider;
 
 #[cfg(test)]
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 use super::http_client::HttpClientTrait;
 use crate::domain::{
     DomainError, FinishReason, LlmProvider, LlmRequest, LlmResponse, LlmStream, Message,
-    MessageRole, StreamChunk, Usage,
+    MessageRole, StreamChunk, ToolCallDelta, Usage,
 };
 use crate::domain::llm::LlmResponseFormat;
 
@@ -158,7 +158,8 @@ impl<C: HttpClientTrait> LlmProvider for OpenAiProvider<C> {
         let response = self
             .client
             .post_json(&url, self.headers(), &body)
-            .await?;
+            .await
+            .map_err(|e| e.with_provider(self.provider_name()))?;
 
         self.parse_response(response)
     }
@@ -176,7 +177,8 @@ impl<C: HttpClientTrait> LlmProvider for OpenAiProvider<C> {
         let byte_stream = self
             .client
             .post_json_stream(&url, self.headers(), &body)
-            .await?;
+            .await
+            .map_err(|e| e.with_provider(self.provider_name()))?;
 
         let model_clone = model.to_string();
         let stream = byte_stream.filter_map(move |result: Result<Bytes, DomainError>| {
@@ -227,6 +229,15 @@ fn parse_sse_chunks(text: &str, model: &str) -> Option<Result<StreamChunk, Domai
                         stream_chunk = stream_chunk.with_delta(delta);
                     }
 
+                    for call in choice.delta.tool_calls {
+                        stream_chunk = stream_chunk.with_tool_call(ToolCallDelta {
+                            index: call.index,
+                            id: call.id,
+                            name: call.function.name,
+                            arguments: call.function.arguments.unwrap_or_default(),
+                        });
+                    }
+
                     if let Some(reason) = choice.finish_reason {
                         stream_chunk =
                             stream_chunk.with_finish_reason(parse_finish_reason(&reason));
@@ -314,6 +325,22 @@ struct OpenAiStreamChoice {
 #[derive(Debug, Deserialize)]
 struct OpenAiDelta {
     content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<OpenAiToolCallDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiToolCallDelta {
+    index: u32,
+    id: Option<String>,
+    #[serde(default)]
+    function: OpenAiFunctionDelta,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OpenAiFunctionDelta {
+    name: Option<String>,
+    arguments: Option<String>,
 }
 
 #[cfg(test)]
@@ -393,4 +420,15 @@ mod tests {
 
         assert_eq!(response.id, "chatcmpl-custom");
     }
+
+    #[test]
+    fn test_parse_tool_call_delta() {
+        let data = r#"data: {"id":"c1","model":"gpt-4o","choices":[{"delta":{"tool_calls":[{"index":0,"id":"call_1","type":"function","function":{"name":"get_weather","arguments":""}}]},"finish_reason":null}]}"#;
+        let chunk = parse_sse_chunks(data, "gpt-4o").unwrap().unwrap();
+        assert_eq!(chunk.tool_calls, vec![ToolCallDelta::start(0, "call_1", "get_weather")]);
+
+        let data = r#"data: {"id":"c1","model":"gpt-4o","choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"city\""}}]},"finish_reason":null}]}"#;
+        let chunk = parse_sse_chunks(data, "gpt-4o").unwrap().unwrap();
+        assert_eq!(chunk.tool_calls, vec![ToolCallDelta::arguments(0, "{\"city\"")]);
+    }
 }
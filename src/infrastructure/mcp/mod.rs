@@ -0,0 +1,7 @@
+//! MCP tool infrastructure implementations
+
+mod service;
+mod storage_repository;
+
+pub use service::{CreateMcpToolRequest, McpToolService, UpdateMcpToolRequest};
+pub use storage_repository::StorageMcpToolRepository;
@@ -0,0 +1,227 @@
+//! MCP tool management and knowledge base search
+
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::domain::DomainError;
+use crate::domain::knowledge_base::{KnowledgeBaseCaller, SearchParams, SearchResult};
+use crate::domain::mcp::{McpTool, McpToolId, McpToolRepository, McpToolTarget};
+use crate::infrastructure::knowledge_base::{
+    KnowledgeBaseAccessTracker, KnowledgeBaseProviderRegistryTrait,
+};
+
+/// Request to create an MCP tool
+#[derive(Debug, Clone)]
+pub struct CreateMcpToolRequest {
+    pub id: String,
+    pub description: String,
+    pub target: McpToolTarget,
+    pub enabled: bool,
+}
+
+/// Request to update an MCP tool; unset fields are left unchanged
+#[derive(Debug, Clone, Default)]
+pub struct UpdateMcpToolRequest {
+    pub description: Option<String>,
+    pub target: Option<McpToolTarget>,
+    pub enabled: Option<bool>,
+}
+
+/// Manages MCP tools and runs their knowledge base searches
+pub struct McpToolService<R: McpToolRepository> {
+    repository: Arc<R>,
+    kb_registry: Arc<dyn KnowledgeBaseProviderRegistryTrait>,
+    access_tracker: Option<Arc<KnowledgeBaseAccessTracker>>,
+}
+
+impl<R: McpToolRepository> McpToolService<R> {
+    /// Create a new MCP tool service
+    pub fn new(repository: Arc<R>, kb_registry: Arc<dyn KnowledgeBaseProviderRegistryTrait>) -> Self {
+        Self {
+            repository,
+            kb_registry,
+            access_tracker: None,
+        }
+    }
+
+    /// Count the searches of the knowledge base tools
+    pub fn with_access_tracker(mut self, tracker: Arc<KnowledgeBaseAccessTracker>) -> Self {
+        self.access_tracker = Some(tracker);
+        self
+    }
+
+    /// Every tool, ordered by name
+    pub async fn list(&self) -> Result<Vec<McpTool>, DomainError> {
+        self.repository.list().await
+    }
+
+    /// Get a tool by name
+    pub async fn get(&self, id: &str) -> Result<Option<McpTool>, DomainError> {
+        self.repository.get(&parse_id(id)?).await
+    }
+
+    /// Get a tool by name, returning an error if not found
+    pub async fn get_required(&self, id: &str) -> Result<McpTool, DomainError> {
+        self.get(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("MCP tool '{}' not found", id)))
+    }
+
+    /// Create a tool
+    pub async fn create(&self, request: CreateMcpToolRequest) -> Result<McpTool, DomainError> {
+        let tool_id = parse_id(&request.id)?;
+
+        if self.repository.get(&tool_id).await?.is_some() {
+            return Err(DomainError::conflict(format!(
+                "MCP tool '{}' already exists",
+                request.id
+            )));
+        }
+
+        let tool = McpTool::new(tool_id, request.description, request.target)
+            .with_enabled(request.enabled);
+
+        tool.validate()?;
+        let tool = self.repository.save(tool).await?;
+
+        info!(tool = %tool.id(), "Created MCP tool");
+        Ok(tool)
+    }
+
+    /// Update a tool
+    pub async fn update(
+        &self,
+        id: &str,
+        request: UpdateMcpToolRequest,
+    ) -> Result<McpTool, DomainError> {
+        let mut tool = self.get_required(id).await?;
+
+        if let Some(description) = request.description {
+            tool.set_description(description);
+        }
+        if let Some(target) = request.target {
+            tool.set_target(target);
+        }
+        if let Some(enabled) = request.enabled {
+            tool.set_enabled(enabled);
+        }
+
+        tool.validate()?;
+        self.repository.save(tool).await
+    }
+
+    /// Delete a tool
+    pub async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        self.repository.delete(&parse_id(id)?).await
+    }
+
+    /// Search a knowledge base for the passages most similar to `query`,
+    /// among the documents the caller may read when given
+    pub async fn search(
+        &self,
+        knowledge_base_id: &str,
+        query: &str,
+        top_k: u32,
+        caller: Option<&KnowledgeBaseCaller>,
+    ) -> Result<Vec<SearchResult>, DomainError> {
+        let provider = self.kb_registry.get_required(knowledge_base_id).await?;
+        let mut params = SearchParams::new(query).with_top_k(top_k);
+        if let Some(caller) = caller {
+            params = params.with_caller(caller.clone());
+        }
+        let mut results = provider.search(params).await?;
+
+        results.truncate(top_k as usize);
+        if let Some(tracker) = &self.access_tracker {
+            tracker.track(knowledge_base_id, query, &results);
+        }
+        Ok(results)
+    }
+}
+
+fn parse_id(id: &str) -> Result<McpToolId, DomainError> {
+    McpToolId::new(id).map_err(|e| DomainError::validation(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::knowledge_base::{KnowledgeBaseId, MockKnowledgeBaseProvider};
+    use crate::infrastructure::knowledge_base::KnowledgeBaseProviderRegistry;
+    use crate::infrastructure::mcp::StorageMcpToolRepository;
+    use crate::infrastructure::storage::InMemoryStorage;
+
+    fn create_service(
+        registry: Arc<KnowledgeBaseProviderRegistry>,
+    ) -> McpToolService<StorageMcpToolRepository> {
+        McpToolService::new(
+            Arc::new(StorageMcpToolRepository::new(Arc::new(
+                InMemoryStorage::<McpTool>::new(),
+            ))),
+            registry,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_create_update_delete() {
+        let service = create_service(Arc::new(KnowledgeBaseProviderRegistry::new()));
+        let request = CreateMcpToolRequest {
+            id: "triage-ticket".to_string(),
+            description: "Classify a support ticket".to_string(),
+            target: McpToolTarget::workflow("triage"),
+            enabled: true,
+        };
+        service.create(request.clone()).await.unwrap();
+
+        let duplicate = service.create(request).await;
+        assert!(matches!(duplicate, Err(DomainError::Conflict { .. })));
+
+        let updated = service
+            .update(
+                "triage-ticket",
+                UpdateMcpToolRequest {
+                    enabled: Some(false),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert!(!updated.is_enabled());
+
+        let invalid = service
+            .update(
+                "triage-ticket",
+                UpdateMcpToolRequest {
+                    description: Some(String::new()),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(matches!(invalid, Err(DomainError::InvalidFields { .. })));
+
+        assert!(service.delete("triage-ticket").await.unwrap());
+        assert!(service.get("triage-ticket").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_search() {
+        let registry = Arc::new(KnowledgeBaseProviderRegistry::new());
+        registry
+            .register(Arc::new(
+                MockKnowledgeBaseProvider::new(KnowledgeBaseId::new("docs").unwrap())
+                    .with_search_results(vec![
+                        SearchResult::new("a", "Refunds take 5 days", 0.9),
+                        SearchResult::new("b", "Ships worldwide", 0.8),
+                    ]),
+            ))
+            .await;
+        let service = create_service(registry);
+
+        let results = service.search("docs", "refund", 1, None).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].content, "Refunds take 5 days");
+
+        assert!(service.search("missing", "refund", 1, None).await.is_err());
+    }
+}
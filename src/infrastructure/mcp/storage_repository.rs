@@ -0,0 +1,43 @@
+//! Storage-backed MCP tool repository
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::DomainError;
+use crate::domain::mcp::{McpTool, McpToolId, McpToolRepository};
+use crate::domain::storage::Storage;
+
+/// MCP tool repository backed by a generic storage
+#[derive(Debug)]
+pub struct StorageMcpToolRepository {
+    storage: Arc<dyn Storage<McpTool>>,
+}
+
+impl StorageMcpToolRepository {
+    /// Create a new storage-backed repository
+    pub fn new(storage: Arc<dyn Storage<McpTool>>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl McpToolRepository for StorageMcpToolRepository {
+    async fn get(&self, id: &McpToolId) -> Result<Option<McpTool>, DomainError> {
+        self.storage.get(id).await
+    }
+
+    async fn list(&self) -> Result<Vec<McpTool>, DomainError> {
+        let mut tools = self.storage.list().await?;
+        tools.sort_by(|a, b| a.id().as_str().cmp(b.id().as_str()));
+        Ok(tools)
+    }
+
+    async fn save(&self, tool: McpTool) -> Result<McpTool, DomainError> {
+        self.storage.save(tool).await
+    }
+
+    async fn delete(&self, id: &McpToolId) -> Result<bool, DomainError> {
+        self.storage.delete(id).await
+    }
+}
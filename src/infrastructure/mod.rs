@@ -1,26 +1,44 @@
 //! Infrastructure layer - External service implementations
 
 pub mod api_key;
+pub mod assistant;
+pub mod audit;
 pub mod auth;
+pub mod background;
 pub mod cache;
 pub mod config;
 pub mod crag;
 pub mod credentials;
+pub mod dataset;
 pub mod embedding;
+pub mod event;
 pub mod experiment;
 pub mod external_api;
+pub mod fine_tune;
+pub mod guardrail;
+pub mod health;
 pub mod ingestion;
 pub mod knowledge_base;
+pub mod leader;
 pub mod llm;
+pub mod mcp;
 pub mod logging;
+pub mod notification;
 pub mod observability;
 pub mod operation;
+pub mod organization;
 pub mod plugin;
+pub mod request_trace;
+pub mod role;
 pub mod semantic_cache;
+pub mod service_account;
 pub mod services;
+pub mod slo;
 pub mod storage;
 pub mod team;
 pub mod test_case;
+pub mod tls;
+pub mod transform;
 pub mod user;
 pub mod usage;
 pub mod webhook;
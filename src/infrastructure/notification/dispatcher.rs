@@ -0,0 +1,329 @@
+//! Routes notifications to the configured channels
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use futures::future::join_all;
+use tracing::{info, warn};
+
+use super::{EmailChannel, PagerDutyChannel, ProviderOutageTracker, SlackChannel};
+use crate::config::NotificationsConfig;
+use crate::domain::notification::{Notification, NotificationChannel, NotificationEvent};
+use crate::domain::DomainError;
+use crate::infrastructure::background::spawn_tracked;
+use crate::infrastructure::usage::AlertNotification;
+
+/// A channel and the notifications delivered to it
+#[derive(Debug, Clone)]
+pub struct NotificationRoute {
+    channel: Arc<dyn NotificationChannel>,
+    /// Events delivered to the channel, empty delivers every event
+    events: Vec<NotificationEvent>,
+    /// Lowest budget alert threshold delivered to the channel
+    min_budget_percent: u8,
+}
+
+impl NotificationRoute {
+    /// Route every notification to a channel
+    pub fn new(channel: Arc<dyn NotificationChannel>) -> Self {
+        Self {
+            channel,
+            events: Vec::new(),
+            min_budget_percent: 0,
+        }
+    }
+
+    /// Only deliver these events (builder pattern)
+    pub fn with_events(mut self, events: Vec<NotificationEvent>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Only deliver budget alerts at or above a threshold (builder pattern)
+    pub fn with_min_budget_percent(mut self, percent: u8) -> Self {
+        self.min_budget_percent = percent;
+        self
+    }
+
+    /// Whether the notification is delivered to the channel
+    pub fn accepts(&self, notification: &Notification) -> bool {
+        if !self.events.is_empty() && !self.events.contains(&notification.event) {
+            return false;
+        }
+
+        notification
+            .budget_percent
+            .is_none_or(|percent| percent >= self.min_budget_percent)
+    }
+}
+
+/// Delivers budget alerts and provider outage notifications to channels
+#[derive(Debug, Default)]
+pub struct NotificationDispatcher {
+    routes: Vec<NotificationRoute>,
+    outages: ProviderOutageTracker,
+}
+
+impl NotificationDispatcher {
+    /// Create a dispatcher without channels
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the channels of the `[notifications]` configuration
+    pub fn from_config(config: &NotificationsConfig) -> Result<Self, DomainError> {
+        let mut dispatcher = Self::new().with_outage_tracker(ProviderOutageTracker::new(
+            config.provider_failure_threshold,
+        ));
+
+        if let Some(url) = &config.slack.webhook_url {
+            dispatcher = dispatcher.with_route(
+                NotificationRoute::new(Arc::new(SlackChannel::new(url)))
+                    .with_events(parse_events(&config.slack.events)?)
+                    .with_min_budget_percent(config.slack.min_budget_percent),
+            );
+        }
+
+        if let Some(routing_key) = &config.pagerduty.routing_key {
+            let mut channel = PagerDutyChannel::new(routing_key);
+
+            if let Some(url) = &config.pagerduty.events_url {
+                channel = channel.with_events_url(url);
+            }
+
+            dispatcher = dispatcher.with_route(
+                NotificationRoute::new(Arc::new(channel))
+                    .with_events(parse_events(&config.pagerduty.events)?)
+                    .with_min_budget_percent(config.pagerduty.min_budget_percent),
+            );
+        }
+
+        if let Some(channel) = EmailChannel::from_config(&config.email)? {
+            dispatcher = dispatcher.with_route(
+                NotificationRoute::new(Arc::new(channel))
+                    .with_events(parse_events(&config.email.events)?)
+                    .with_min_budget_percent(config.email.min_budget_percent),
+            );
+        }
+
+        Ok(dispatcher)
+    }
+
+    /// Add a channel (builder pattern)
+    pub fn with_route(mut self, route: NotificationRoute) -> Self {
+        self.routes.push(route);
+        self
+    }
+
+    /// Replace the provider outage tracker (builder pattern)
+    pub fn with_outage_tracker(mut self, outages: ProviderOutageTracker) -> Self {
+        self.outages = outages;
+        self
+    }
+
+    /// Names of the configured channels
+    pub fn channel_names(&self) -> Vec<&str> {
+        self.routes.iter().map(|r| r.channel.name()).collect()
+    }
+
+    /// Providers currently reported as down
+    pub fn provider_outages(&self) -> Vec<String> {
+        self.outages.outages()
+    }
+
+    /// Deliver a notification to every channel routed to it, returning how
+    /// many deliveries succeeded. Failures are logged, not returned.
+    pub async fn notify(&self, notification: &Notification) -> usize {
+        let routes: Vec<&NotificationRoute> = self
+            .routes
+            .iter()
+            .filter(|route| route.accepts(notification))
+            .collect();
+
+        let results = join_all(routes.iter().map(|route| route.channel.send(notification))).await;
+        let mut delivered = 0;
+
+        for (route, result) in routes.iter().zip(results) {
+            match result {
+                Ok(()) => delivered += 1,
+                Err(e) => warn!(
+                    channel = route.channel.name(),
+                    event = %notification.event,
+                    error = %e,
+                    "Failed to deliver notification"
+                ),
+            }
+        }
+
+        delivered
+    }
+
+    /// Deliver newly triggered budget alerts in the background
+    pub fn spawn_budget_alerts(self: &Arc<Self>, alerts: Vec<AlertNotification>) {
+        for alert in alerts {
+            self.spawn_notify(Notification::budget_alert(
+                alert.budget_id.as_str(),
+                &alert.budget_name,
+                alert.alert.threshold_percent,
+                alert.current_usage_micros,
+                alert.limit_micros,
+            ));
+        }
+    }
+
+    /// Track the outcome of a provider call, notifying in the background when
+    /// an outage starts or ends. Only provider errors count as failures.
+    pub fn track_provider_result<T>(
+        self: &Arc<Self>,
+        provider: &str,
+        result: &Result<T, DomainError>,
+    ) {
+        let now = Utc::now().timestamp().max(0) as u64;
+
+        let notification = match result {
+            Ok(_) => self.outages.record_success(provider, now),
+            Err(DomainError::Provider { message, .. }) => {
+                self.outages.record_failure(provider, message, now)
+            }
+            Err(_) => None,
+        };
+
+        if let Some(notification) = notification {
+            info!(provider = provider, event = %notification.event, "Provider health changed");
+            self.spawn_notify(notification);
+        }
+    }
+
+    fn spawn_notify(self: &Arc<Self>, notification: Notification) {
+        if self.routes.is_empty() {
+            return;
+        }
+
+        let dispatcher = self.clone();
+
+        spawn_tracked(async move {
+            dispatcher.notify(&notification).await;
+        });
+    }
+}
+
+fn parse_events(events: &[String]) -> Result<Vec<NotificationEvent>, DomainError> {
+    events
+        .iter()
+        .map(|event| {
+            event
+                .parse()
+                .map_err(|e: DomainError| DomainError::configuration(e.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct RecordingChannel {
+        sent: Mutex<Vec<Notification>>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl NotificationChannel for RecordingChannel {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn send(&self, notification: &Notification) -> Result<(), DomainError> {
+            if self.fail {
+                return Err(DomainError::internal("channel down"));
+            }
+
+            self.sent.lock().unwrap().push(notification.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_notify_routes_by_event_and_threshold() {
+        let finance = Arc::new(RecordingChannel::default());
+        let oncall = Arc::new(RecordingChannel::default());
+        let broken = Arc::new(RecordingChannel {
+            fail: true,
+            ..Default::default()
+        });
+
+        let dispatcher = NotificationDispatcher::new()
+            .with_route(
+                NotificationRoute::new(finance.clone())
+                    .with_events(vec![NotificationEvent::BudgetAlert]),
+            )
+            .with_route(NotificationRoute::new(oncall.clone()).with_min_budget_percent(100))
+            .with_route(NotificationRoute::new(broken));
+
+        let warning = Notification::budget_alert("b-1", "Research", 80, 80, 100);
+        assert_eq!(dispatcher.notify(&warning).await, 1);
+
+        let exceeded = Notification::budget_alert("b-1", "Research", 100, 100, 100);
+        assert_eq!(dispatcher.notify(&exceeded).await, 2);
+
+        let outage = Notification::provider_outage("openai", 5, "timeout");
+        assert_eq!(dispatcher.notify(&outage).await, 1);
+
+        assert_eq!(finance.sent.lock().unwrap().len(), 2);
+        let oncall_events: Vec<_> = oncall
+            .sent
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|n| n.event)
+            .collect();
+        assert_eq!(
+            oncall_events,
+            vec![
+                NotificationEvent::BudgetAlert,
+                NotificationEvent::ProviderOutage
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_track_provider_result_counts_provider_errors_only() {
+        let dispatcher = Arc::new(
+            NotificationDispatcher::new().with_outage_tracker(ProviderOutageTracker::new(1)),
+        );
+
+        let not_found: Result<(), DomainError> = Err(DomainError::not_found("model"));
+        dispatcher.track_provider_result("openai", &not_found);
+        assert!(dispatcher.provider_outages().is_empty());
+
+        let failed: Result<(), DomainError> = Err(DomainError::provider("openai", "503"));
+        dispatcher.track_provider_result("openai", &failed);
+        assert_eq!(dispatcher.provider_outages(), vec!["openai".to_string()]);
+
+        dispatcher.track_provider_result("openai", &Ok(()));
+        assert!(dispatcher.provider_outages().is_empty());
+    }
+
+    #[test]
+    fn test_from_config() {
+        let mut config = NotificationsConfig::default();
+        assert!(NotificationDispatcher::from_config(&config)
+            .unwrap()
+            .channel_names()
+            .is_empty());
+
+        config.slack.webhook_url = Some("https://hooks.slack.com/services/T/B/X".to_string());
+        config.pagerduty.routing_key = Some("routing-key".to_string());
+        config.pagerduty.events = vec!["provider_outage".to_string()];
+        let dispatcher = NotificationDispatcher::from_config(&config).unwrap();
+        assert_eq!(dispatcher.channel_names(), vec!["slack", "pagerduty"]);
+
+        config.slack.events = vec!["budget".to_string()];
+        assert!(NotificationDispatcher::from_config(&config).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+//! SMTP e-mail notification channel
+
+use async_trait::async_trait;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use crate::config::EmailNotificationConfig;
+use crate::domain::notification::{Notification, NotificationChannel};
+use crate::domain::DomainError;
+
+/// Sends notifications as plain text e-mails over SMTP
+#[derive(Debug, Clone)]
+pub struct EmailChannel {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    to: Vec<Mailbox>,
+}
+
+impl EmailChannel {
+    /// Create a channel from the `[notifications.email]` configuration,
+    /// `None` when no SMTP host is configured
+    pub fn from_config(config: &EmailNotificationConfig) -> Result<Option<Self>, DomainError> {
+        let Some(host) = config.smtp_host.as_deref() else {
+            return Ok(None);
+        };
+
+        if config.to.is_empty() {
+            return Err(DomainError::configuration(
+                "notifications.email.to must list at least one recipient",
+            ));
+        }
+
+        let builder = if config.starttls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host).map_err(|e| {
+                DomainError::configuration(format!("Invalid SMTP host '{}': {}", host, e))
+            })?
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host)
+        };
+
+        let mut builder = builder.port(config.smtp_port);
+
+        if let (Some(username), Some(password)) = (&config.smtp_username, &config.smtp_password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Some(Self {
+            transport: builder.build(),
+            from: parse_mailbox(&config.from)?,
+            to: config
+                .to
+                .iter()
+                .map(|address| parse_mailbox(address))
+                .collect::<Result<_, _>>()?,
+        }))
+    }
+}
+
+fn parse_mailbox(address: &str) -> Result<Mailbox, DomainError> {
+    address.parse().map_err(|e| {
+        DomainError::configuration(format!("Invalid e-mail address '{}': {}", address, e))
+    })
+}
+
+/// Plain text body: the message followed by one `key: value` line per detail
+fn email_body(notification: &Notification) -> String {
+    let mut body = format!("{}\n\n", notification.message);
+
+    for (key, value) in &notification.details {
+        body.push_str(&format!("{}: {}\n", key, value));
+    }
+
+    body
+}
+
+#[async_trait]
+impl NotificationChannel for EmailChannel {
+    fn name(&self) -> &str {
+        "email"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), DomainError> {
+        let mut message = Message::builder().from(self.from.clone()).subject(format!(
+            "[{}] {}",
+            notification.severity.as_str().to_uppercase(),
+            notification.title
+        ));
+
+        for recipient in &self.to {
+            message = message.to(recipient.clone());
+        }
+
+        let message = message
+            .header(ContentType::TEXT_PLAIN)
+            .body(email_body(notification))
+            .map_err(|e| DomainError::internal(format!("Failed to build e-mail: {}", e)))?;
+
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| DomainError::internal(format!("SMTP delivery failed: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_body_lists_details() {
+        let notification = Notification::provider_recovered("anthropic", 90);
+        let body = email_body(&notification);
+
+        assert!(body.starts_with("Requests to 'anthropic' succeed again after 90s of failures."));
+        assert!(body.contains("outage_secs: 90\n"));
+        assert!(body.contains("provider: anthropic\n"));
+    }
+
+    #[test]
+    fn test_from_config_disabled_without_host() {
+        let config = EmailNotificationConfig::default();
+        assert!(EmailChannel::from_config(&config).unwrap().is_none());
+
+        let config = EmailNotificationConfig {
+            smtp_host: Some("smtp.example.com".to_string()),
+            ..Default::default()
+        };
+        assert!(EmailChannel::from_config(&config).is_err());
+    }
+}
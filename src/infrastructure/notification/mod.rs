@@ -0,0 +1,13 @@
+//! Notification channels and dispatching
+
+mod dispatcher;
+mod email;
+mod outage;
+mod pagerduty;
+mod slack;
+
+pub use dispatcher::{NotificationDispatcher, NotificationRoute};
+pub use email::EmailChannel;
+pub use outage::ProviderOutageTracker;
+pub use pagerduty::{PagerDutyChannel, PAGERDUTY_EVENTS_URL};
+pub use slack::SlackChannel;
@@ -0,0 +1,126 @@
+//! Provider outage detection from consecutive request failures
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::domain::notification::Notification;
+
+#[derive(Debug, Default)]
+struct ProviderHealth {
+    consecutive_failures: u32,
+    /// Unix timestamp the outage was reported at
+    outage_since: Option<u64>,
+}
+
+/// Reports a provider as down after `failure_threshold` failed requests in a
+/// row, and as recovered on its next successful request
+#[derive(Debug)]
+pub struct ProviderOutageTracker {
+    failure_threshold: u32,
+    providers: Mutex<HashMap<String, ProviderHealth>>,
+}
+
+impl ProviderOutageTracker {
+    /// Create a tracker; a threshold of 0 is treated as 1
+    pub fn new(failure_threshold: u32) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            providers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record a failed request, returning the outage notification when the
+    /// provider just crossed the threshold
+    pub fn record_failure(&self, provider: &str, error: &str, now: u64) -> Option<Notification> {
+        let mut providers = self.providers.lock().unwrap();
+        let health = providers.entry(provider.to_string()).or_default();
+
+        health.consecutive_failures += 1;
+
+        if health.outage_since.is_some() || health.consecutive_failures < self.failure_threshold {
+            return None;
+        }
+
+        health.outage_since = Some(now);
+
+        Some(Notification::provider_outage(
+            provider,
+            health.consecutive_failures,
+            error,
+        ))
+    }
+
+    /// Record a successful request, returning the recovery notification when
+    /// the provider was in outage
+    pub fn record_success(&self, provider: &str, now: u64) -> Option<Notification> {
+        let health = self.providers.lock().unwrap().remove(provider)?;
+
+        health
+            .outage_since
+            .map(|since| Notification::provider_recovered(provider, now.saturating_sub(since)))
+    }
+
+    /// Providers currently reported as down
+    pub fn outages(&self) -> Vec<String> {
+        let mut outages: Vec<String> = self
+            .providers
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, health)| health.outage_since.is_some())
+            .map(|(provider, _)| provider.clone())
+            .collect();
+        outages.sort();
+        outages
+    }
+}
+
+impl Default for ProviderOutageTracker {
+    fn default() -> Self {
+        Self::new(5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::notification::NotificationEvent;
+
+    #[test]
+    fn test_outage_reported_once_at_threshold() {
+        let tracker = ProviderOutageTracker::new(3);
+
+        assert!(tracker.record_failure("openai", "timeout", 100).is_none());
+        assert!(tracker.record_failure("openai", "timeout", 101).is_none());
+
+        let outage = tracker.record_failure("openai", "503", 102).unwrap();
+        assert_eq!(outage.event, NotificationEvent::ProviderOutage);
+        assert_eq!(outage.details["consecutive_failures"], "3");
+        assert_eq!(outage.details["last_error"], "503");
+
+        assert!(tracker.record_failure("openai", "503", 103).is_none());
+        assert_eq!(tracker.outages(), vec!["openai".to_string()]);
+    }
+
+    #[test]
+    fn test_recovery_after_outage() {
+        let tracker = ProviderOutageTracker::new(1);
+
+        assert!(tracker.record_success("openai", 50).is_none());
+        tracker.record_failure("openai", "timeout", 100).unwrap();
+
+        let recovered = tracker.record_success("openai", 160).unwrap();
+        assert_eq!(recovered.event, NotificationEvent::ProviderRecovered);
+        assert_eq!(recovered.details["outage_secs"], "60");
+        assert!(tracker.outages().is_empty());
+    }
+
+    #[test]
+    fn test_success_resets_failure_streak() {
+        let tracker = ProviderOutageTracker::new(2);
+
+        assert!(tracker.record_failure("openai", "timeout", 100).is_none());
+        assert!(tracker.record_success("openai", 101).is_none());
+        assert!(tracker.record_failure("openai", "timeout", 102).is_none());
+    }
+}
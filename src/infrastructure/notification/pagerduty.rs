@@ -0,0 +1,114 @@
+//! PagerDuty Events API v2 notification channel
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{json, Value};
+
+use crate::domain::notification::{Notification, NotificationChannel};
+use crate::domain::DomainError;
+
+/// Default PagerDuty Events API v2 endpoint
+pub const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// Triggers (and resolves) PagerDuty incidents through the Events API v2
+#[derive(Debug, Clone)]
+pub struct PagerDutyChannel {
+    routing_key: String,
+    events_url: String,
+    http_client: Client,
+}
+
+impl PagerDutyChannel {
+    /// Create a channel for the integration key of a PagerDuty service
+    pub fn new(routing_key: impl Into<String>) -> Self {
+        Self {
+            routing_key: routing_key.into(),
+            events_url: PAGERDUTY_EVENTS_URL.to_string(),
+            http_client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Send events to a different endpoint (builder pattern)
+    pub fn with_events_url(mut self, events_url: impl Into<String>) -> Self {
+        self.events_url = events_url.into();
+        self
+    }
+}
+
+/// Event resolving the incident of a recovery, triggering one otherwise
+fn pagerduty_event(routing_key: &str, notification: &Notification) -> Value {
+    let mut custom_details: serde_json::Map<String, Value> = notification
+        .details
+        .iter()
+        .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+        .collect();
+    custom_details.insert(
+        "message".to_string(),
+        Value::String(notification.message.clone()),
+    );
+
+    json!({
+        "routing_key": routing_key,
+        "event_action": if notification.is_resolution() { "resolve" } else { "trigger" },
+        "dedup_key": notification.dedup_key,
+        "payload": {
+            "summary": notification.title,
+            "source": "pmp-llm-gateway",
+            "severity": notification.severity.as_str(),
+            "component": notification.event.as_str(),
+            "custom_details": custom_details,
+        }
+    })
+}
+
+#[async_trait]
+impl NotificationChannel for PagerDutyChannel {
+    fn name(&self) -> &str {
+        "pagerduty"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), DomainError> {
+        let response = self
+            .http_client
+            .post(&self.events_url)
+            .json(&pagerduty_event(&self.routing_key, notification))
+            .send()
+            .await
+            .map_err(|e| DomainError::internal(format!("PagerDuty request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(DomainError::internal(format!(
+                "PagerDuty returned HTTP status {}",
+                response.status()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pagerduty_event_trigger_and_resolve() {
+        let outage = pagerduty_event(
+            "key",
+            &Notification::provider_outage("openai", 5, "timeout"),
+        );
+        assert_eq!(outage["event_action"], "trigger");
+        assert_eq!(outage["dedup_key"], "provider-outage:openai");
+        assert_eq!(outage["payload"]["severity"], "critical");
+        assert_eq!(outage["payload"]["custom_details"]["last_error"], "timeout");
+
+        let recovered = pagerduty_event("key", &Notification::provider_recovered("openai", 60));
+        assert_eq!(recovered["event_action"], "resolve");
+        assert_eq!(recovered["dedup_key"], outage["dedup_key"]);
+    }
+}
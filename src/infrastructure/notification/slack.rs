@@ -0,0 +1,102 @@
+//! Slack incoming-webhook notification channel
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{json, Value};
+
+use crate::domain::notification::{Notification, NotificationChannel, NotificationSeverity};
+use crate::domain::DomainError;
+
+/// Posts notifications to a Slack incoming webhook
+#[derive(Debug, Clone)]
+pub struct SlackChannel {
+    webhook_url: String,
+    http_client: Client,
+}
+
+impl SlackChannel {
+    /// Create a channel posting to an incoming webhook URL
+    pub fn new(webhook_url: impl Into<String>) -> Self {
+        Self {
+            webhook_url: webhook_url.into(),
+            http_client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Slack message with the details as attachment fields
+fn slack_payload(notification: &Notification) -> Value {
+    let color = match notification.severity {
+        NotificationSeverity::Info => "good",
+        NotificationSeverity::Warning => "warning",
+        NotificationSeverity::Critical => "danger",
+    };
+    let fields: Vec<Value> = notification
+        .details
+        .iter()
+        .map(|(key, value)| json!({ "title": key, "value": value, "short": true }))
+        .collect();
+
+    json!({
+        "text": notification.title,
+        "attachments": [{
+            "color": color,
+            "text": notification.message,
+            "fields": fields,
+            "footer": notification.event.as_str(),
+        }]
+    })
+}
+
+#[async_trait]
+impl NotificationChannel for SlackChannel {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), DomainError> {
+        let response = self
+            .http_client
+            .post(&self.webhook_url)
+            .json(&slack_payload(notification))
+            .send()
+            .await
+            .map_err(|e| DomainError::internal(format!("Slack request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(DomainError::internal(format!(
+                "Slack webhook returned HTTP status {}",
+                response.status()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slack_payload() {
+        let notification = Notification::provider_outage("openai", 5, "timeout");
+        let payload = slack_payload(&notification);
+
+        assert_eq!(payload["text"], "Provider 'openai' is failing requests");
+        assert_eq!(payload["attachments"][0]["color"], "danger");
+        assert_eq!(payload["attachments"][0]["footer"], "provider_outage");
+        assert_eq!(
+            payload["attachments"][0]["fields"]
+                .as_array()
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+}
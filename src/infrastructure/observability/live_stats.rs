@@ -0,0 +1,444 @@
+//! In-process live counters behind `/admin/stats`
+//!
+//! Prometheus metrics need a scraper and PromQL to answer "what is the
+//! gateway doing right now". These counters keep the last minute of traffic
+//! in one-second buckets so the admin UI and external dashboards can poll a
+//! single cheap endpoint instead.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use chrono::Utc;
+use once_cell::sync::Lazy;
+
+/// Rolling window of the live counters
+pub const LIVE_STATS_WINDOW_SECS: u64 = 60;
+
+/// Routes tracked per second before the rest are counted as [`OTHER_ROUTE`]
+const MAX_ROUTES: usize = 100;
+
+/// Route of requests past [`MAX_ROUTES`]
+pub const OTHER_ROUTE: &str = "other";
+
+static LIVE_STATS: Lazy<LiveStats> = Lazy::new(LiveStats::default);
+
+/// Process-wide live counters, fed by the metric recorders
+pub fn live_stats() -> &'static LiveStats {
+    &LIVE_STATS
+}
+
+fn unix_now() -> u64 {
+    Utc::now().timestamp().max(0) as u64
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Counts {
+    requests: u64,
+    errors: u64,
+}
+
+impl Counts {
+    fn record(&mut self, error: bool) {
+        self.requests += 1;
+
+        if error {
+            self.errors += 1;
+        }
+    }
+
+    fn add(&mut self, other: &Counts) {
+        self.requests += other.requests;
+        self.errors += other.errors;
+    }
+}
+
+/// Counters of one second
+#[derive(Debug, Default)]
+struct Bucket {
+    second: u64,
+    routes: HashMap<String, Counts>,
+    providers: HashMap<String, Counts>,
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_hits: u64,
+    cache_misses: u64,
+}
+
+/// Live request, token, cache and provider counters over the last
+/// [`LIVE_STATS_WINDOW_SECS`]. Recording methods take the current unix
+/// timestamp so tests control the clock.
+#[derive(Debug)]
+pub struct LiveStats {
+    started_at: u64,
+    active_requests: AtomicU64,
+    total_requests: AtomicU64,
+    buckets: Mutex<Vec<Bucket>>,
+}
+
+impl Default for LiveStats {
+    fn default() -> Self {
+        Self::new(unix_now())
+    }
+}
+
+impl LiveStats {
+    /// Create empty counters started at `now`
+    pub fn new(now: u64) -> Self {
+        Self {
+            started_at: now,
+            active_requests: AtomicU64::new(0),
+            total_requests: AtomicU64::new(0),
+            buckets: Mutex::new(
+                (0..LIVE_STATS_WINDOW_SECS)
+                    .map(|_| Bucket::default())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Count a request in flight until the guard is dropped
+    pub fn enter_request(&self) -> ActiveRequestGuard<'_> {
+        self.active_requests.fetch_add(1, Ordering::Relaxed);
+        ActiveRequestGuard { stats: self }
+    }
+
+    /// Record a completed HTTP request; 5xx responses count as errors
+    pub fn record_request(&self, route: &str, status: u16, now: u64) {
+        self.total_requests.fetch_add(1, Ordering::Relaxed);
+
+        self.with_bucket(now, |bucket| {
+            let route = if bucket.routes.len() < MAX_ROUTES || bucket.routes.contains_key(route) {
+                route
+            } else {
+                OTHER_ROUTE
+            };
+
+            bucket
+                .routes
+                .entry(route.to_string())
+                .or_default()
+                .record(status >= 500);
+        });
+    }
+
+    /// Record a provider call and the tokens it consumed
+    pub fn record_llm_request(
+        &self,
+        provider: &str,
+        success: bool,
+        input_tokens: u64,
+        output_tokens: u64,
+        now: u64,
+    ) {
+        self.with_bucket(now, |bucket| {
+            bucket
+                .providers
+                .entry(provider.to_string())
+                .or_default()
+                .record(!success);
+            bucket.input_tokens += input_tokens;
+            bucket.output_tokens += output_tokens;
+        });
+    }
+
+    /// Record a response cache lookup
+    pub fn record_cache_lookup(&self, hit: bool, now: u64) {
+        self.with_bucket(now, |bucket| {
+            if hit {
+                bucket.cache_hits += 1;
+            } else {
+                bucket.cache_misses += 1;
+            }
+        });
+    }
+
+    fn with_bucket(&self, now: u64, record: impl FnOnce(&mut Bucket)) {
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = &mut buckets[(now % LIVE_STATS_WINDOW_SECS) as usize];
+
+        if bucket.second != now {
+            *bucket = Bucket {
+                second: now,
+                ..Bucket::default()
+            };
+        }
+
+        record(bucket);
+    }
+
+    /// Aggregate the counters of the window ending at `now`. Rates are per
+    /// second over the window, or over the uptime when shorter.
+    pub fn snapshot(&self, now: u64) -> LiveStatsSnapshot {
+        let uptime_secs = now.saturating_sub(self.started_at);
+        let elapsed = uptime_secs.saturating_add(1).min(LIVE_STATS_WINDOW_SECS) as f64;
+
+        let mut routes: HashMap<String, Counts> = HashMap::new();
+        let mut providers: HashMap<String, Counts> = HashMap::new();
+        let mut snapshot = LiveStatsSnapshot {
+            generated_at: now,
+            window_secs: LIVE_STATS_WINDOW_SECS,
+            uptime_secs,
+            active_requests: self.active_requests.load(Ordering::Relaxed),
+            total_requests: self.total_requests.load(Ordering::Relaxed),
+            requests: 0,
+            errors: 0,
+            requests_per_second: 0.0,
+            routes: Vec::new(),
+            input_tokens: 0,
+            output_tokens: 0,
+            tokens_per_minute: 0.0,
+            cache_hits: 0,
+            cache_misses: 0,
+            providers: Vec::new(),
+        };
+
+        for bucket in self.buckets.lock().unwrap().iter() {
+            if bucket.second > now || now - bucket.second >= LIVE_STATS_WINDOW_SECS {
+                continue;
+            }
+
+            for (route, counts) in &bucket.routes {
+                routes.entry(route.clone()).or_default().add(counts);
+            }
+            for (provider, counts) in &bucket.providers {
+                providers.entry(provider.clone()).or_default().add(counts);
+            }
+
+            snapshot.input_tokens += bucket.input_tokens;
+            snapshot.output_tokens += bucket.output_tokens;
+            snapshot.cache_hits += bucket.cache_hits;
+            snapshot.cache_misses += bucket.cache_misses;
+        }
+
+        snapshot.routes = routes
+            .into_iter()
+            .map(|(route, counts)| RouteStats {
+                route,
+                requests: counts.requests,
+                errors: counts.errors,
+                requests_per_second: counts.requests as f64 / elapsed,
+            })
+            .collect();
+        snapshot
+            .routes
+            .sort_by(|a, b| b.requests.cmp(&a.requests).then(a.route.cmp(&b.route)));
+
+        snapshot.providers = providers
+            .into_iter()
+            .map(|(provider, counts)| ProviderStats {
+                provider,
+                requests: counts.requests,
+                errors: counts.errors,
+            })
+            .collect();
+        snapshot
+            .providers
+            .sort_by(|a, b| a.provider.cmp(&b.provider));
+
+        snapshot.requests = snapshot.routes.iter().map(|r| r.requests).sum();
+        snapshot.errors = snapshot.routes.iter().map(|r| r.errors).sum();
+        snapshot.requests_per_second = snapshot.requests as f64 / elapsed;
+        snapshot.tokens_per_minute =
+            (snapshot.input_tokens + snapshot.output_tokens) as f64 * 60.0 / elapsed;
+
+        snapshot
+    }
+}
+
+/// Request in flight, counted in [`LiveStatsSnapshot::active_requests`]
+/// until dropped
+#[must_use = "the request stops being active when the guard is dropped"]
+pub struct ActiveRequestGuard<'a> {
+    stats: &'a LiveStats,
+}
+
+impl Drop for ActiveRequestGuard<'_> {
+    fn drop(&mut self) {
+        self.stats.active_requests.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Requests of one route over the window
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteStats {
+    /// `METHOD /matched/path`
+    pub route: String,
+    pub requests: u64,
+    /// 5xx responses
+    pub errors: u64,
+    pub requests_per_second: f64,
+}
+
+/// Calls to one provider over the window
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderStats {
+    pub provider: String,
+    pub requests: u64,
+    pub errors: u64,
+}
+
+impl ProviderStats {
+    /// Share of failed calls
+    pub fn error_rate(&self) -> f64 {
+        if self.requests > 0 {
+            self.errors as f64 / self.requests as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Live counters over the window ending at `generated_at`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveStatsSnapshot {
+    pub generated_at: u64,
+    pub window_secs: u64,
+    pub uptime_secs: u64,
+    /// Requests in flight
+    pub active_requests: u64,
+    /// Requests completed since startup
+    pub total_requests: u64,
+    /// Requests completed in the window
+    pub requests: u64,
+    /// 5xx responses in the window
+    pub errors: u64,
+    pub requests_per_second: f64,
+    /// Busiest routes first
+    pub routes: Vec<RouteStats>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub tokens_per_minute: f64,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    /// Ordered by provider
+    pub providers: Vec<ProviderStats>,
+}
+
+impl LiveStatsSnapshot {
+    /// Share of 5xx responses
+    pub fn error_rate(&self) -> f64 {
+        if self.requests > 0 {
+            self.errors as f64 / self.requests as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// Share of cache lookups served from cache, none without lookups
+    pub fn cache_hit_rate(&self) -> Option<f64> {
+        let lookups = self.cache_hits + self.cache_misses;
+
+        if lookups > 0 {
+            Some(self.cache_hits as f64 / lookups as f64)
+        } else {
+            None
+        }
+    }
+}
+
+/// Record a completed HTTP request in the process-wide counters
+pub(crate) fn record_live_request(route: &str, status: u16) {
+    live_stats().record_request(route, status, unix_now());
+}
+
+/// Record a provider call in the process-wide counters
+pub(crate) fn record_live_llm_request(
+    provider: &str,
+    success: bool,
+    input_tokens: u64,
+    output_tokens: u64,
+) {
+    live_stats().record_llm_request(provider, success, input_tokens, output_tokens, unix_now());
+}
+
+/// Record a cache lookup in the process-wide counters
+pub(crate) fn record_live_cache_lookup(hit: bool) {
+    live_stats().record_cache_lookup(hit, unix_now());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_aggregates_window() {
+        let stats = LiveStats::new(1_000);
+
+        stats.record_request("GET /v1/models", 200, 1_000);
+        stats.record_request("POST /v1/chat/completions", 200, 1_010);
+        stats.record_request("POST /v1/chat/completions", 502, 1_019);
+        stats.record_llm_request("openai", true, 100, 50, 1_010);
+        stats.record_llm_request("openai", false, 10, 0, 1_019);
+        stats.record_cache_lookup(true, 1_010);
+        stats.record_cache_lookup(false, 1_010);
+        stats.record_cache_lookup(false, 1_019);
+
+        let snapshot = stats.snapshot(1_019);
+
+        assert_eq!(snapshot.uptime_secs, 19);
+        assert_eq!(snapshot.requests, 3);
+        assert_eq!(snapshot.errors, 1);
+        assert!((snapshot.requests_per_second - 3.0 / 20.0).abs() < 1e-9);
+        assert_eq!(snapshot.routes[0].route, "POST /v1/chat/completions");
+        assert_eq!(snapshot.routes[0].requests, 2);
+        assert!((snapshot.tokens_per_minute - 160.0 * 3.0).abs() < 1e-9);
+        assert!((snapshot.cache_hit_rate().unwrap() - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(snapshot.providers.len(), 1);
+        assert!((snapshot.providers[0].error_rate() - 0.5).abs() < 1e-9);
+
+        // The first second left the window, the total keeps it
+        let snapshot = stats.snapshot(1_060);
+
+        assert_eq!(snapshot.requests, 2);
+        assert_eq!(snapshot.total_requests, 3);
+        assert!((snapshot.requests_per_second - 2.0 / 60.0).abs() < 1e-9);
+
+        let snapshot = stats.snapshot(2_000);
+
+        assert_eq!(snapshot.requests, 0);
+        assert_eq!(snapshot.error_rate(), 0.0);
+        assert_eq!(snapshot.cache_hit_rate(), None);
+        assert!(snapshot.providers.is_empty());
+    }
+
+    #[test]
+    fn test_reused_bucket_is_reset() {
+        let stats = LiveStats::new(0);
+
+        stats.record_request("GET /health", 200, 5);
+        stats.record_request("GET /health", 200, 5 + LIVE_STATS_WINDOW_SECS);
+
+        assert_eq!(stats.snapshot(5 + LIVE_STATS_WINDOW_SECS).requests, 1);
+    }
+
+    #[test]
+    fn test_route_cardinality_is_capped() {
+        let stats = LiveStats::new(0);
+
+        for i in 0..MAX_ROUTES + 5 {
+            stats.record_request(&format!("GET /route/{}", i), 200, 1);
+        }
+
+        let snapshot = stats.snapshot(1);
+
+        assert_eq!(snapshot.routes.len(), MAX_ROUTES + 1);
+        assert_eq!(snapshot.routes[0].route, OTHER_ROUTE);
+        assert_eq!(snapshot.routes[0].requests, 5);
+    }
+
+    #[test]
+    fn test_active_requests() {
+        let stats = LiveStats::new(0);
+        let first = stats.enter_request();
+
+        {
+            let _second = stats.enter_request();
+            assert_eq!(stats.snapshot(0).active_requests, 2);
+        }
+
+        assert_eq!(stats.snapshot(0).active_requests, 1);
+        drop(first);
+        assert_eq!(stats.snapshot(0).active_requests, 0);
+    }
+}
@@ -5,9 +5,17 @@ use std::time::Duration;
 
 use axum::{extract::State, response::IntoResponse, routing::get, Router};
 use metrics::{counter, gauge, histogram};
-use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
 
 use super::config::MetricsConfig;
+use super::live_stats::{record_live_cache_lookup, record_live_llm_request, record_live_request};
+use crate::domain::DomainError;
+
+/// Buckets (seconds) of the LLM latency histograms, from sub-second cache
+/// hits to multi-minute completions
+const LLM_LATENCY_BUCKETS: &[f64] = &[
+    0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 30.0, 60.0, 120.0, 300.0,
+];
 
 /// Prometheus metrics handle for serving metrics endpoint
 #[derive(Clone)]
@@ -29,7 +37,15 @@ pub fn init_metrics(config: &MetricsConfig) -> Option<PrometheusMetrics> {
         return None;
     }
 
-    let builder = PrometheusBuilder::new();
+    let builder = match PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Prefix("llm_".to_string()), LLM_LATENCY_BUCKETS)
+    {
+        Ok(builder) => builder,
+        Err(e) => {
+            tracing::error!("Invalid Prometheus histogram buckets: {}", e);
+            return None;
+        }
+    };
 
     match builder.install_recorder() {
         Ok(handle) => {
@@ -51,6 +67,7 @@ pub fn init_metrics(config: &MetricsConfig) -> Option<PrometheusMetrics> {
 fn register_default_metrics() {
     // Register default metrics with initial values
     gauge!("llm_gateway_info", "version" => env!("CARGO_PKG_VERSION")).set(1.0);
+    gauge!("http_requests_in_flight").set(0.0);
 }
 
 /// Create the metrics router
@@ -66,10 +83,13 @@ async fn metrics_handler(State(metrics): State<PrometheusMetrics>) -> impl IntoR
 
 /// Record an HTTP request metric
 pub fn record_http_request(method: &str, path: &str, status: u16, duration: Duration) {
+    let path = sanitize_path(path);
+    record_live_request(&format!("{} {}", method, path), status);
+
     let status_str = status.to_string();
     let labels = [
         ("method", method.to_string()),
-        ("path", sanitize_path(path)),
+        ("path", path),
         ("status", status_str),
     ];
 
@@ -84,6 +104,17 @@ pub fn record_http_request(method: &str, path: &str, status: u16, duration: Dura
 
 /// Record an LLM request metric
 pub fn record_llm_request(params: LlmRequestMetricParams) {
+    record_live_llm_request(
+        params.provider,
+        params.success,
+        params.input_tokens.unwrap_or(0),
+        params.output_tokens.unwrap_or(0),
+    );
+
+    let model_labels = [
+        ("provider", params.provider.to_string()),
+        ("model", params.model.to_string()),
+    ];
     let labels = [
         ("provider", params.provider.to_string()),
         ("model", params.model.to_string()),
@@ -93,16 +124,26 @@ pub fn record_llm_request(params: LlmRequestMetricParams) {
     counter!("llm_requests_total", &labels).increment(1);
     histogram!("llm_request_duration_seconds", &labels).record(params.duration.as_secs_f64());
 
+    if let Some(ttft) = params.time_to_first_token {
+        histogram!("llm_time_to_first_token_seconds", &model_labels).record(ttft.as_secs_f64());
+    }
+
     if let Some(tokens) = params.input_tokens {
-        counter!("llm_input_tokens_total", &labels).increment(tokens);
+        counter!("llm_input_tokens_total", &model_labels).increment(tokens);
     }
 
     if let Some(tokens) = params.output_tokens {
-        counter!("llm_output_tokens_total", &labels).increment(tokens);
+        counter!("llm_output_tokens_total", &model_labels).increment(tokens);
     }
 
     if !params.success {
         counter!("llm_errors_total", &labels).increment(1);
+
+        let error_labels = [
+            ("provider", params.provider.to_string()),
+            ("status", params.error_status.unwrap_or("unknown").to_string()),
+        ];
+        counter!("llm_provider_errors_total", &error_labels).increment(1);
     }
 }
 
@@ -111,11 +152,120 @@ pub struct LlmRequestMetricParams<'a> {
     pub provider: &'a str,
     pub model: &'a str,
     pub duration: Duration,
+    /// Time until the first streamed chunk, `None` for non-streaming requests
+    pub time_to_first_token: Option<Duration>,
     pub success: bool,
+    /// Status label of a failed request, see [`provider_error_status`]
+    pub error_status: Option<&'a str>,
     pub input_tokens: Option<u64>,
     pub output_tokens: Option<u64>,
 }
 
+/// Status label of a provider failure: the upstream HTTP status code when
+/// the error carries one ("429"), otherwise the kind of failure
+pub fn provider_error_status(error: &DomainError) -> String {
+    match error {
+        DomainError::Provider { message, .. } => message
+            .split("HTTP ")
+            .nth(1)
+            .and_then(|rest| rest.get(..3))
+            .filter(|code| code.bytes().all(|b| b.is_ascii_digit()))
+            .map(str::to_string)
+            .unwrap_or_else(|| "provider_error".to_string()),
+        DomainError::Validation { .. } | DomainError::InvalidFields { .. } => {
+            "invalid_request".to_string()
+        }
+        DomainError::Credential { .. } => "credential_error".to_string(),
+        _ => "gateway_error".to_string(),
+    }
+}
+
+/// Record a response cache lookup; the hit ratio of a cache is
+/// `llm_cache_lookups_total{result="hit"} / llm_cache_lookups_total`.
+/// Called by the exact and semantic cache services on every lookup, which
+/// chat completions make when their cache policy selects a cache.
+pub fn record_cache_lookup(cache: &str, hit: bool) {
+    record_live_cache_lookup(hit);
+
+    let labels = [
+        ("cache", cache.to_string()),
+        ("result", if hit { "hit" } else { "miss" }.to_string()),
+    ];
+
+    counter!("llm_cache_lookups_total", &labels).increment(1);
+}
+
+/// Record a chat request whose messages scored as a prompt injection, with
+/// the action taken on it
+pub fn record_injection_detection(model: &str, action: &str) {
+    let labels = [
+        ("model", model.to_string()),
+        ("action", action.to_string()),
+    ];
+
+    counter!("llm_injection_detections_total", &labels).increment(1);
+}
+
+/// Record a content policy rule broken at a stage of a completion
+pub fn record_policy_violation(policy_id: &str, stage: &str, rule: &str) {
+    let labels = [
+        ("policy", policy_id.to_string()),
+        ("stage", stage.to_string()),
+        ("rule", rule.to_string()),
+    ];
+
+    counter!("llm_content_policy_violations_total", &labels).increment(1);
+}
+
+/// Record a secret found in a completion answer, with the action taken on it
+pub fn record_secret_leak(model: &str, kind: &str, action: &str) {
+    let labels = [
+        ("model", model.to_string()),
+        ("kind", kind.to_string()),
+        ("action", action.to_string()),
+    ];
+
+    counter!("llm_secret_leaks_total", &labels).increment(1);
+}
+
+/// Entry in the `llm_queue_depth` gauge of a queue, removed when dropped
+#[must_use = "the entry leaves the queue when the guard is dropped"]
+pub struct QueueDepthGuard {
+    queue: &'static str,
+}
+
+impl QueueDepthGuard {
+    /// Count one more entry in `queue`
+    pub fn enter(queue: &'static str) -> Self {
+        gauge!("llm_queue_depth", "queue" => queue).increment(1.0);
+        Self { queue }
+    }
+}
+
+impl Drop for QueueDepthGuard {
+    fn drop(&mut self) {
+        gauge!("llm_queue_depth", "queue" => self.queue).decrement(1.0);
+    }
+}
+
+/// Request counted in the `http_requests_in_flight` gauge of this replica
+/// until dropped, e.g. as an HPA custom metric
+#[must_use = "the request stops being in flight when the guard is dropped"]
+pub struct InFlightRequestGuard;
+
+impl InFlightRequestGuard {
+    pub fn enter() -> Self {
+        gauge!("http_requests_in_flight").increment(1.0);
+        Self
+    }
+}
+
+impl Drop for InFlightRequestGuard {
+    fn drop(&mut self) {
+        gauge!("http_requests_in_flight").decrement(1.0);
+    }
+}
+
 /// Sanitize URL path for metric labels (remove IDs, limit cardinality)
 fn sanitize_path(path: &str) -> String {
     // Replace UUIDs and numeric IDs with placeholders
@@ -173,7 +323,9 @@ mod tests {
             provider: "openai",
             model: "gpt-4",
             duration: Duration::from_millis(500),
+            time_to_first_token: None,
             success: true,
+            error_status: None,
             input_tokens: Some(100),
             output_tokens: Some(50),
         };
@@ -182,4 +334,28 @@ mod tests {
         assert_eq!(params.model, "gpt-4");
         assert!(params.success);
     }
+
+    #[test]
+    fn test_provider_error_status() {
+        let rate_limited = DomainError::provider(
+            "openai",
+            "HTTP 429 Too Many Requests: {\"error\": \"rate limited\"}",
+        );
+        assert_eq!(provider_error_status(&rate_limited), "429");
+
+        let parse = DomainError::provider("openai", "Failed to parse response: EOF");
+        assert_eq!(provider_error_status(&parse), "provider_error");
+
+        let short = DomainError::provider("openai", "HTTP 5");
+        assert_eq!(provider_error_status(&short), "provider_error");
+
+        assert_eq!(
+            provider_error_status(&DomainError::validation("bad request")),
+            "invalid_request"
+        );
+        assert_eq!(
+            provider_error_status(&DomainError::internal("boom")),
+            "gateway_error"
+        );
+    }
 }
@@ -1,12 +1,26 @@
 //! Observability infrastructure - Tracing, Metrics, and Logging
 
 mod config;
+mod live_stats;
 mod metrics;
+mod trace_context;
 mod tracing_setup;
 
 pub use config::{MetricsConfig, ObservabilityConfig, TracingConfig};
+pub use live_stats::{
+    live_stats, ActiveRequestGuard, LiveStats, LiveStatsSnapshot, ProviderStats, RouteStats,
+    LIVE_STATS_WINDOW_SECS, OTHER_ROUTE,
+};
 pub use metrics::{
-    create_metrics_router, init_metrics, record_http_request, record_llm_request,
-    PrometheusMetrics,
+    create_metrics_router, init_metrics, provider_error_status, record_cache_lookup,
+    record_http_request, record_injection_detection, record_llm_request, record_policy_violation,
+    record_secret_leak, InFlightRequestGuard, LlmRequestMetricParams, PrometheusMetrics,
+    QueueDepthGuard,
+};
+pub use trace_context::{
+    auth_span, cache_lookup_span, crag_scoring_span, current_trace_headers, current_traceparent,
+    extract_trace_context, extract_trace_context_with, inject_trace_context,
+    inject_trace_context_with, kb_search_span, provider_call_span, record_error, record_result,
+    record_token_usage, trace_id, workflow_step_span, TRACEPARENT_HEADER, TRACE_ID_HEADER,
 };
 pub use tracing_setup::{init_tracing, shutdown_tracing};
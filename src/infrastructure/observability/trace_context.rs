@@ -0,0 +1,270 @@
+//! W3C trace context propagation and pipeline span helpers
+
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use opentelemetry::propagation::{Extractor, Injector, TextMapPropagator};
+use opentelemetry::trace::TraceContextExt;
+use opentelemetry::Context;
+use tracing::{field, info_span, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::domain::llm::Usage;
+
+/// Response header carrying the trace ID of the request
+pub const TRACE_ID_HEADER: &str = "x-trace-id";
+/// W3C header carrying the trace and parent span IDs
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|k| k.as_str()).collect()
+    }
+}
+
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
+
+/// Trace context sent by the caller (`traceparent`/`tracestate` headers)
+pub fn extract_trace_context(headers: &HeaderMap) -> Context {
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        extract_trace_context_with(propagator, headers)
+    })
+}
+
+/// [`extract_trace_context`] with a given propagator instead of the global one
+pub fn extract_trace_context_with(
+    propagator: &dyn TextMapPropagator,
+    headers: &HeaderMap,
+) -> Context {
+    propagator.extract(&HeaderExtractor(headers))
+}
+
+/// Write the trace context of a span as `traceparent`/`tracestate` headers
+pub fn inject_trace_context(span: &Span, headers: &mut HeaderMap) {
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        inject_trace_context_with(propagator, span, headers)
+    });
+}
+
+/// [`inject_trace_context`] with a given propagator instead of the global one
+pub fn inject_trace_context_with(
+    propagator: &dyn TextMapPropagator,
+    span: &Span,
+    headers: &mut HeaderMap,
+) {
+    propagator.inject_context(&span.context(), &mut HeaderInjector(headers));
+}
+
+/// `traceparent`/`tracestate` headers continuing the trace of the current
+/// span on an outgoing request (provider calls, HTTP workflow steps). Empty
+/// when OpenTelemetry export is disabled.
+pub fn current_trace_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    inject_trace_context(&Span::current(), &mut headers);
+    headers
+}
+
+/// `traceparent` of the current span, for work that outlives it such as
+/// webhook delivery retries
+pub fn current_traceparent() -> Option<String> {
+    current_trace_headers()
+        .get(TRACEPARENT_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from)
+}
+
+/// Hex trace ID of a span, `None` when OpenTelemetry export is disabled
+pub fn trace_id(span: &Span) -> Option<String> {
+    let cx = span.context();
+    let span_context = cx.span().span_context().clone();
+
+    span_context
+        .is_valid()
+        .then(|| span_context.trace_id().to_string())
+}
+
+/// Span of API key authentication; the key and team are recorded once known
+pub fn auth_span() -> Span {
+    info_span!(
+        "auth.api_key",
+        otel.status_code = field::Empty,
+        otel.status_message = field::Empty,
+        api_key.id = field::Empty,
+        team.id = field::Empty,
+    )
+}
+
+/// Span of a response cache lookup (`kind` is "exact" or "semantic"); the
+/// outcome is recorded as `cache.hit`
+pub fn cache_lookup_span(kind: &str, model: &str) -> Span {
+    info_span!(
+        "cache.lookup",
+        otel.status_code = field::Empty,
+        otel.status_message = field::Empty,
+        cache.kind = %kind,
+        gen_ai.request.model = %model,
+        cache.hit = field::Empty,
+    )
+}
+
+/// Span of a call to an LLM provider, following the OpenTelemetry GenAI
+/// semantic conventions. Token counts are recorded by [`record_token_usage`].
+pub fn provider_call_span(provider: &str, model: &str, stream: bool) -> Span {
+    info_span!(
+        "llm.provider_call",
+        otel.kind = "client",
+        otel.status_code = field::Empty,
+        otel.status_message = field::Empty,
+        gen_ai.system = %provider,
+        gen_ai.request.model = %model,
+        llm.stream = stream,
+        gen_ai.usage.input_tokens = field::Empty,
+        gen_ai.usage.output_tokens = field::Empty,
+    )
+}
+
+/// Span of one workflow step
+pub fn workflow_step_span(
+    workflow_id: &str,
+    step_name: &str,
+    step_type: &str,
+    index: usize,
+) -> Span {
+    info_span!(
+        "workflow.step",
+        otel.name = %format!("workflow.step {}", step_type),
+        otel.status_code = field::Empty,
+        otel.status_message = field::Empty,
+        workflow.id = %workflow_id,
+        workflow.step.name = %step_name,
+        workflow.step.type = %step_type,
+        workflow.step.index = index,
+    )
+}
+
+/// Span of a knowledge base search; the result count is recorded as `kb.results`
+pub fn kb_search_span(knowledge_base_id: &str, top_k: u32) -> Span {
+    info_span!(
+        "kb.search",
+        otel.status_code = field::Empty,
+        otel.status_message = field::Empty,
+        kb.id = %knowledge_base_id,
+        kb.top_k = top_k,
+        kb.results = field::Empty,
+    )
+}
+
+/// Span of CRAG relevance scoring; the relevant document count is recorded
+/// as `crag.relevant`
+pub fn crag_scoring_span(strategy: &str, documents: usize) -> Span {
+    info_span!(
+        "crag.scoring",
+        otel.status_code = field::Empty,
+        otel.status_message = field::Empty,
+        crag.strategy = %strategy,
+        crag.documents = documents,
+        crag.relevant = field::Empty,
+    )
+}
+
+/// Record the token usage of a provider response on its span
+pub fn record_token_usage(span: &Span, usage: Option<&Usage>) {
+    if let Some(usage) = usage {
+        span.record("gen_ai.usage.input_tokens", usage.prompt_tokens);
+        span.record("gen_ai.usage.output_tokens", usage.completion_tokens);
+    }
+}
+
+/// Mark a span created by this module as failed
+pub fn record_error(span: &Span, error: &dyn std::fmt::Display) {
+    span.record("otel.status_code", "ERROR");
+    span.record("otel.status_message", error.to_string());
+}
+
+/// Record the outcome of an operation on its span, passing the result through
+pub fn record_result<T, E: std::fmt::Display>(span: &Span, result: Result<T, E>) -> Result<T, E> {
+    if let Err(e) = &result {
+        record_error(span, e);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+
+    use super::*;
+
+    #[test]
+    fn test_header_extractor_and_injector_round_trip() {
+        let propagator = TraceContextPropagator::new();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            HeaderValue::from_static("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+        );
+
+        let cx = propagator.extract(&HeaderExtractor(&headers));
+        let span_context = cx.span().span_context().clone();
+        assert!(span_context.is_remote());
+        assert_eq!(
+            span_context.trace_id().to_string(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+
+        let mut response_headers = HeaderMap::new();
+        propagator.inject_context(&cx, &mut HeaderInjector(&mut response_headers));
+        assert_eq!(
+            response_headers["traceparent"],
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+        );
+    }
+
+    #[test]
+    fn test_trace_id_without_opentelemetry_layer() {
+        assert_eq!(trace_id(&Span::none()), None);
+        assert!(current_trace_headers().is_empty());
+        assert_eq!(current_traceparent(), None);
+    }
+
+    #[test]
+    fn test_inject_trace_context_continues_span_trace() {
+        use opentelemetry::trace::TracerProvider as _;
+        use opentelemetry_sdk::trace::TracerProvider;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        // A local propagator, as setting the global one would leak into
+        // the other tests of the process
+        let propagator = TraceContextPropagator::new();
+        let tracer = TracerProvider::builder().build().tracer("test");
+        let subscriber =
+            tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = info_span!("llm.provider_call");
+            let _guard = span.enter();
+            let trace_id = trace_id(&span).unwrap();
+
+            let mut headers = HeaderMap::new();
+            inject_trace_context_with(&propagator, &Span::current(), &mut headers);
+            let traceparent = headers[TRACEPARENT_HEADER].to_str().unwrap();
+            assert!(traceparent.starts_with(&format!("00-{}-", trace_id)));
+        });
+    }
+}
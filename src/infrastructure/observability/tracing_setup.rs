@@ -3,6 +3,7 @@
 use opentelemetry::{trace::TracerProvider as _, KeyValue};
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_sdk::{
+    propagation::TraceContextPropagator,
     runtime,
     trace::{RandomIdGenerator, Sampler, TracerProvider},
     Resource,
@@ -20,6 +21,9 @@ use crate::infrastructure::logging::LoggingConfig;
 
 /// Initialize tracing with optional OpenTelemetry export
 pub fn init_tracing(logging_config: &LoggingConfig, tracing_config: &TracingConfig) {
+    // Continue traces of callers sending W3C `traceparent` headers
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+
     let filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new(&logging_config.level));
 
@@ -150,6 +154,9 @@ fn init_otel_tracing(
         .with_batch_exporter(exporter, runtime::Tokio)
         .build();
 
+    // Registered globally so that `shutdown_tracing` flushes pending spans
+    opentelemetry::global::set_tracer_provider(provider.clone());
+
     Ok(provider)
 }
 
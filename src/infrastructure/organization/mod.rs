@@ -0,0 +1,7 @@
+//! Organization infrastructure implementations
+
+mod repository;
+mod service;
+
+pub use repository::StorageOrganizationRepository;
+pub use service::{CreateOrganizationRequest, OrganizationService, UpdateOrganizationRequest};
@@ -0,0 +1,115 @@
+//! Storage-backed organization repository implementation
+
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::organization::{Organization, OrganizationId, OrganizationRepository};
+use crate::domain::storage::Storage;
+use crate::domain::DomainError;
+
+/// Storage-backed implementation of OrganizationRepository
+#[derive(Debug)]
+pub struct StorageOrganizationRepository {
+    storage: Arc<dyn Storage<Organization>>,
+}
+
+impl StorageOrganizationRepository {
+    /// Create a new storage-backed repository
+    pub fn new(storage: Arc<dyn Storage<Organization>>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl OrganizationRepository for StorageOrganizationRepository {
+    async fn get(&self, id: &OrganizationId) -> Result<Option<Organization>, DomainError> {
+        self.storage.get(id).await
+    }
+
+    async fn create(&self, organization: Organization) -> Result<Organization, DomainError> {
+        if self.storage.exists(organization.id()).await? {
+            return Err(DomainError::conflict(format!(
+                "Organization '{}' already exists",
+                organization.id()
+            )));
+        }
+
+        self.storage.create(organization).await
+    }
+
+    async fn update(&self, organization: Organization) -> Result<Organization, DomainError> {
+        if !self.storage.exists(organization.id()).await? {
+            return Err(DomainError::not_found(format!(
+                "Organization '{}' not found",
+                organization.id()
+            )));
+        }
+
+        self.storage.update(organization).await
+    }
+
+    async fn delete(&self, id: &OrganizationId) -> Result<bool, DomainError> {
+        self.storage.delete(id).await
+    }
+
+    async fn list(&self) -> Result<Vec<Organization>, DomainError> {
+        let mut organizations = self.storage.list().await?;
+        organizations.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(organizations)
+    }
+
+    async fn exists(&self, id: &OrganizationId) -> Result<bool, DomainError> {
+        self.storage.exists(id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::storage::InMemoryStorage;
+
+    fn create_repo() -> StorageOrganizationRepository {
+        StorageOrganizationRepository::new(Arc::new(InMemoryStorage::<Organization>::new()))
+    }
+
+    fn create_organization(id: &str, name: &str) -> Organization {
+        Organization::new(OrganizationId::new(id).unwrap(), name).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_and_get() {
+        let repo = create_repo();
+        let org = create_organization("acme", "Acme");
+
+        repo.create(org.clone()).await.unwrap();
+
+        let fetched = repo.get(org.id()).await.unwrap().unwrap();
+        assert_eq!(fetched.name(), "Acme");
+        assert!(repo.create(org).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_missing() {
+        let repo = create_repo();
+        let result = repo.update(create_organization("acme", "Acme")).await;
+
+        assert!(matches!(result, Err(DomainError::NotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_list_sorted_by_name() {
+        let repo = create_repo();
+
+        repo.create(create_organization("zeta", "Zeta")).await.unwrap();
+        repo.create(create_organization("acme", "Acme")).await.unwrap();
+
+        let names: Vec<_> = repo
+            .list()
+            .await
+            .unwrap()
+            .iter()
+            .map(|o| o.name().to_string())
+            .collect();
+        assert_eq!(names, vec!["Acme", "Zeta"]);
+    }
+}
@@ -0,0 +1,320 @@
+//! Organization service for organization management
+
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::domain::organization::{
+    validate_organization_name, Organization, OrganizationId, OrganizationRepository,
+};
+use crate::domain::team::{Team, TeamId, TeamQuery, TeamRepository};
+use crate::domain::user::UserId;
+use crate::domain::DomainError;
+
+/// Request for creating a new organization
+#[derive(Debug, Clone)]
+pub struct CreateOrganizationRequest {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub admin_user_ids: Vec<String>,
+}
+
+/// Request for updating an organization
+#[derive(Debug, Clone, Default)]
+pub struct UpdateOrganizationRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub admin_user_ids: Option<Vec<String>>,
+}
+
+/// Organization service for managing organizations and their teams
+#[derive(Debug)]
+pub struct OrganizationService<R: OrganizationRepository> {
+    repository: Arc<R>,
+    teams: Arc<dyn TeamRepository>,
+}
+
+impl<R: OrganizationRepository> OrganizationService<R> {
+    /// Create a new organization service
+    pub fn new(repository: Arc<R>, teams: Arc<dyn TeamRepository>) -> Self {
+        Self { repository, teams }
+    }
+
+    /// Create a new organization
+    pub async fn create(
+        &self,
+        request: CreateOrganizationRequest,
+    ) -> Result<Organization, DomainError> {
+        info!(id = %request.id, name = %request.name, "Creating organization");
+
+        validate_organization_name(&request.name)
+            .map_err(|e| DomainError::validation(e.to_string()))?;
+
+        let id = parse_organization_id(&request.id)?;
+
+        if self.repository.exists(&id).await? {
+            return Err(DomainError::conflict(format!(
+                "Organization '{}' already exists",
+                request.id
+            )));
+        }
+
+        let mut organization = Organization::new(id, &request.name)
+            .map_err(|e| DomainError::validation(e.to_string()))?
+            .with_admins(parse_user_ids(&request.admin_user_ids)?);
+
+        if let Some(desc) = request.description {
+            organization.set_description(Some(desc));
+        }
+
+        self.repository.create(organization).await
+    }
+
+    /// Get an organization by ID
+    pub async fn get(&self, id: &str) -> Result<Option<Organization>, DomainError> {
+        self.repository.get(&parse_organization_id(id)?).await
+    }
+
+    /// List all organizations
+    pub async fn list(&self) -> Result<Vec<Organization>, DomainError> {
+        self.repository.list().await
+    }
+
+    /// Update an organization
+    pub async fn update(
+        &self,
+        id: &str,
+        request: UpdateOrganizationRequest,
+    ) -> Result<Organization, DomainError> {
+        info!(id = %id, "Updating organization");
+
+        let mut organization = self.get_existing(id).await?;
+
+        if let Some(name) = request.name {
+            organization
+                .set_name(&name)
+                .map_err(|e| DomainError::validation(e.to_string()))?;
+        }
+
+        if let Some(desc) = request.description {
+            organization.set_description(Some(desc));
+        }
+
+        if let Some(admin_user_ids) = request.admin_user_ids {
+            organization.set_admins(parse_user_ids(&admin_user_ids)?);
+        }
+
+        self.repository.update(organization).await
+    }
+
+    /// Delete an organization. Fails while teams are still assigned to it.
+    pub async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        info!(id = %id, "Deleting organization");
+
+        let organization_id = parse_organization_id(id)?;
+        let teams = self
+            .teams
+            .count(&TeamQuery::new().with_organization_id(id))
+            .await?;
+
+        if teams > 0 {
+            return Err(DomainError::conflict(format!(
+                "Organization '{}' still has {} team(s) assigned",
+                id, teams
+            )));
+        }
+
+        self.repository.delete(&organization_id).await
+    }
+
+    /// List the teams of an organization
+    pub async fn list_teams(&self, id: &str) -> Result<Vec<Team>, DomainError> {
+        let organization = self.get_existing(id).await?;
+
+        self.teams
+            .list(&TeamQuery::new().with_organization_id(organization.id().as_str()))
+            .await
+    }
+
+    /// Assign a team to an organization. A team belongs to at most one
+    /// organization; it must be removed from its current one first.
+    pub async fn add_team(&self, id: &str, team_id: &str) -> Result<Team, DomainError> {
+        info!(id = %id, team_id = %team_id, "Adding team to organization");
+
+        let organization = self.get_existing(id).await?;
+        let mut team = self.get_team(team_id).await?;
+
+        match team.organization_id() {
+            Some(current) if current == organization.id() => return Ok(team),
+            Some(current) => {
+                return Err(DomainError::conflict(format!(
+                    "Team '{}' already belongs to organization '{}'",
+                    team_id, current
+                )));
+            }
+            None => {}
+        }
+
+        team.set_organization_id(Some(organization.id().clone()));
+        self.teams.update(team).await
+    }
+
+    /// Remove a team from an organization
+    pub async fn remove_team(&self, id: &str, team_id: &str) -> Result<Team, DomainError> {
+        info!(id = %id, team_id = %team_id, "Removing team from organization");
+
+        let organization = self.get_existing(id).await?;
+        let mut team = self.get_team(team_id).await?;
+
+        if team.organization_id() != Some(organization.id()) {
+            return Err(DomainError::validation(format!(
+                "Team '{}' does not belong to organization '{}'",
+                team_id, id
+            )));
+        }
+
+        team.set_organization_id(None);
+        self.teams.update(team).await
+    }
+
+    /// Get the organization a team belongs to, if any
+    pub async fn get_for_team(&self, team_id: &TeamId) -> Result<Option<Organization>, DomainError> {
+        let Some(organization_id) = self
+            .teams
+            .get(team_id)
+            .await?
+            .and_then(|team| team.organization_id().cloned())
+        else {
+            return Ok(None);
+        };
+
+        self.repository.get(&organization_id).await
+    }
+
+    async fn get_existing(&self, id: &str) -> Result<Organization, DomainError> {
+        self.get(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("Organization '{}' not found", id)))
+    }
+
+    async fn get_team(&self, team_id: &str) -> Result<Team, DomainError> {
+        let id = TeamId::new(team_id).map_err(|e| DomainError::invalid_id(e.to_string()))?;
+
+        self.teams
+            .get(&id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("Team '{}' not found", team_id)))
+    }
+}
+
+fn parse_organization_id(id: &str) -> Result<OrganizationId, DomainError> {
+    OrganizationId::new(id).map_err(|e| DomainError::invalid_id(e.to_string()))
+}
+
+fn parse_user_ids(ids: &[String]) -> Result<Vec<UserId>, DomainError> {
+    ids.iter()
+        .map(|id| UserId::new(id).map_err(|e| DomainError::validation(e.to_string())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::organization::StorageOrganizationRepository;
+    use crate::infrastructure::storage::InMemoryStorage;
+    use crate::infrastructure::team::StorageTeamRepository;
+
+    async fn create_service() -> OrganizationService<StorageOrganizationRepository> {
+        let teams = StorageTeamRepository::new(Arc::new(InMemoryStorage::<Team>::new()));
+
+        for id in ["team-a", "team-b"] {
+            teams
+                .create(Team::new(TeamId::new(id).unwrap(), id).unwrap())
+                .await
+                .unwrap();
+        }
+
+        let repository = StorageOrganizationRepository::new(Arc::new(
+            InMemoryStorage::<Organization>::new(),
+        ));
+
+        OrganizationService::new(Arc::new(repository), Arc::new(teams))
+    }
+
+    fn create_request(id: &str) -> CreateOrganizationRequest {
+        CreateOrganizationRequest {
+            id: id.to_string(),
+            name: format!("Org {}", id),
+            description: None,
+            admin_user_ids: vec!["alice".to_string()],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_and_update() {
+        let service = create_service().await;
+
+        let created = service.create(create_request("acme")).await.unwrap();
+        assert!(created.is_admin(&UserId::new("alice").unwrap()));
+        assert!(service.create(create_request("acme")).await.is_err());
+
+        let updated = service
+            .update(
+                "acme",
+                UpdateOrganizationRequest {
+                    name: Some("Acme".to_string()),
+                    admin_user_ids: Some(vec!["bob".to_string()]),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.name(), "Acme");
+        assert!(!updated.is_admin(&UserId::new("alice").unwrap()));
+        assert!(updated.is_admin(&UserId::new("bob").unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_add_and_remove_team() {
+        let service = create_service().await;
+        service.create(create_request("acme")).await.unwrap();
+        service.create(create_request("globex")).await.unwrap();
+
+        let team = service.add_team("acme", "team-a").await.unwrap();
+        assert_eq!(team.organization_id().map(|o| o.as_str()), Some("acme"));
+
+        // A team belongs to a single organization
+        let result = service.add_team("globex", "team-a").await;
+        assert!(matches!(result, Err(DomainError::Conflict { .. })));
+
+        let teams = service.list_teams("acme").await.unwrap();
+        assert_eq!(teams.len(), 1);
+
+        let org = service
+            .get_for_team(&TeamId::new("team-a").unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(org.id().as_str(), "acme");
+
+        assert!(service.remove_team("globex", "team-a").await.is_err());
+
+        let team = service.remove_team("acme", "team-a").await.unwrap();
+        assert!(team.organization_id().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_requires_no_teams() {
+        let service = create_service().await;
+        service.create(create_request("acme")).await.unwrap();
+        service.add_team("acme", "team-b").await.unwrap();
+
+        let result = service.delete("acme").await;
+        assert!(matches!(result, Err(DomainError::Conflict { .. })));
+
+        service.remove_team("acme", "team-b").await.unwrap();
+        assert!(service.delete("acme").await.unwrap());
+    }
+}
@@ -0,0 +1,232 @@
+//! Mock Plugin
+//!
+//! Built-in plugin for the mock LLM provider, used for local development and
+//! integration tests. Defaults come from `[providers.mock.settings]`; the
+//! endpoint of a `mock` credential overrides them per credential.
+
+use crate::domain::credentials::CredentialType;
+use crate::domain::llm::LlmProvider;
+use crate::domain::plugin::{
+    ExtensionType, LlmProviderConfig, LlmProviderPlugin, Plugin, PluginContext, PluginError,
+    PluginMetadata, PluginState,
+};
+use crate::infrastructure::llm::{MockProvider, MockProviderConfig};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+const PLUGIN_ID: &str = "mock";
+
+/// Mock plugin implementation
+#[derive(Debug)]
+pub struct MockPlugin {
+    metadata: PluginMetadata,
+    state: AtomicU8,
+    defaults: MockProviderConfig,
+}
+
+impl MockPlugin {
+    /// Create a new mock plugin
+    pub fn new() -> Self {
+        Self {
+            metadata: PluginMetadata::new(PLUGIN_ID, "Mock", "1.0.0")
+                .with_description("Mock LLM provider plugin returning canned responses")
+                .with_author("PMP LLM Gateway"),
+            state: AtomicU8::new(0),
+            defaults: MockProviderConfig::default(),
+        }
+    }
+
+    /// Create a mock plugin with defaults from the provider settings
+    pub fn from_settings(settings: &HashMap<String, toml::Value>) -> Result<Self, PluginError> {
+        let mut defaults = MockProviderConfig::default();
+
+        for (key, value) in settings {
+            let value = match value {
+                toml::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+
+            defaults
+                .set(key, &value)
+                .map_err(|e| PluginError::configuration(PLUGIN_ID, e.to_string()))?;
+        }
+
+        Ok(Self {
+            defaults,
+            ..Self::new()
+        })
+    }
+
+    fn get_state(&self) -> PluginState {
+        match self.state.load(Ordering::SeqCst) {
+            0 => PluginState::Registered,
+            1 => PluginState::Initializing,
+            2 => PluginState::Ready,
+            3 => PluginState::Error,
+            4 => PluginState::ShuttingDown,
+            5 => PluginState::Stopped,
+            _ => PluginState::Error,
+        }
+    }
+
+    fn set_state(&self, state: PluginState) {
+        let value = match state {
+            PluginState::Registered => 0,
+            PluginState::Initializing => 1,
+            PluginState::Ready => 2,
+            PluginState::Error => 3,
+            PluginState::ShuttingDown => 4,
+            PluginState::Stopped => 5,
+        };
+        self.state.store(value, Ordering::SeqCst);
+    }
+}
+
+impl Default for MockPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Plugin for MockPlugin {
+    fn metadata(&self) -> &PluginMetadata {
+        &self.metadata
+    }
+
+    fn extension_types(&self) -> Vec<ExtensionType> {
+        vec![ExtensionType::LlmProvider]
+    }
+
+    async fn initialize(&self, _context: PluginContext) -> Result<(), PluginError> {
+        self.set_state(PluginState::Initializing);
+        self.set_state(PluginState::Ready);
+        Ok(())
+    }
+
+    async fn health_check(&self) -> Result<bool, PluginError> {
+        Ok(self.get_state().is_ready())
+    }
+
+    async fn shutdown(&self) -> Result<(), PluginError> {
+        self.set_state(PluginState::ShuttingDown);
+        self.set_state(PluginState::Stopped);
+        Ok(())
+    }
+
+    fn state(&self) -> PluginState {
+        self.get_state()
+    }
+}
+
+#[async_trait]
+impl LlmProviderPlugin for MockPlugin {
+    fn supported_credential_types(&self) -> Vec<CredentialType> {
+        vec![CredentialType::Mock]
+    }
+
+    async fn create_llm_provider(
+        &self,
+        config: LlmProviderConfig,
+    ) -> Result<Arc<dyn LlmProvider>, PluginError> {
+        if !self.supports_credential_type(&config.credential_type) {
+            return Err(PluginError::unsupported_credential_type(
+                PLUGIN_ID,
+                format!("{:?}", config.credential_type),
+            ));
+        }
+
+        // The credential endpoint carries the per-credential options
+        let mock_config = match config.base_url() {
+            Some(options) => self
+                .defaults
+                .clone()
+                .with_options(options)
+                .map_err(|e| PluginError::provider_creation_failed(PLUGIN_ID, e.to_string()))?,
+            None => self.defaults.clone(),
+        };
+
+        Ok(Arc::new(MockProvider::new(mock_config)))
+    }
+
+    fn available_models(&self) -> Vec<&'static str> {
+        vec!["mock-model"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::llm::{LlmRequest, Message};
+    use std::time::Duration;
+
+    #[test]
+    fn test_mock_plugin_metadata() {
+        let plugin = MockPlugin::new();
+        let metadata = plugin.metadata();
+
+        assert_eq!(metadata.id, "mock");
+        assert_eq!(metadata.name, "Mock");
+        assert!(plugin.supports_credential_type(&CredentialType::Mock));
+        assert!(!plugin.supports_credential_type(&CredentialType::OpenAi));
+    }
+
+    #[test]
+    fn test_mock_plugin_from_settings() {
+        let settings: HashMap<String, toml::Value> = toml::from_str(
+            r#"
+latency_ms = 100
+error_rate = 0.5
+response = "Canned"
+"#,
+        )
+        .unwrap();
+
+        let plugin = MockPlugin::from_settings(&settings).unwrap();
+
+        assert_eq!(plugin.defaults.latency, Duration::from_millis(100));
+        assert_eq!(plugin.defaults.error_rate, 0.5);
+        assert_eq!(plugin.defaults.response.as_deref(), Some("Canned"));
+
+        let invalid: HashMap<String, toml::Value> = toml::from_str("error_rate = 3").unwrap();
+        assert!(matches!(
+            MockPlugin::from_settings(&invalid),
+            Err(PluginError::Configuration { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_mock_plugin_credential_options_override_defaults() {
+        let settings: HashMap<String, toml::Value> =
+            toml::from_str(r#"response = "From settings""#).unwrap();
+        let plugin = MockPlugin::from_settings(&settings).unwrap();
+        plugin.initialize(PluginContext::new()).await.unwrap();
+
+        let config = LlmProviderConfig::new(CredentialType::Mock, "mock-cred", "")
+            .with_param("base_url", "response=From%20credential");
+
+        let provider = plugin.create_llm_provider(config).await.unwrap();
+        let response = provider
+            .chat("mock-model", LlmRequest::new(vec![Message::user("Hi")]))
+            .await
+            .unwrap();
+
+        assert_eq!(provider.provider_name(), "mock");
+        assert_eq!(response.content(), Some("From credential"));
+    }
+
+    #[tokio::test]
+    async fn test_mock_plugin_unsupported_credential_type() {
+        let plugin = MockPlugin::new();
+        let config = LlmProviderConfig::new(CredentialType::OpenAi, "test-cred", "sk-test");
+
+        let result = plugin.create_llm_provider(config).await;
+
+        assert!(matches!(
+            result,
+            Err(PluginError::UnsupportedCredentialType { .. })
+        ));
+    }
+}
@@ -5,11 +5,13 @@
 mod anthropic;
 mod azure;
 mod bedrock;
+mod mock;
 mod openai;
 
 pub use anthropic::AnthropicPlugin;
 pub use azure::AzureOpenAiPlugin;
 pub use bedrock::BedrockPlugin;
+pub use mock::MockPlugin;
 pub use openai::OpenAiPlugin;
 
 use crate::domain::plugin::{LlmProviderPlugin, PluginContext, PluginError};
@@ -53,6 +55,15 @@ pub async fn register_builtin_plugins_with_config(
             BuiltinProvider::Anthropic => Arc::new(AnthropicPlugin::new()),
             BuiltinProvider::AzureOpenAi => Arc::new(AzureOpenAiPlugin::new()),
             BuiltinProvider::Bedrock => Arc::new(BedrockPlugin::new()),
+            BuiltinProvider::Mock => {
+                match MockPlugin::from_settings(config.get_provider_settings(provider)) {
+                    Ok(plugin) => Arc::new(plugin),
+                    Err(e) => {
+                        errors.push(e);
+                        continue;
+                    }
+                }
+            }
         };
 
         let plugin_name = provider.name();
@@ -105,13 +116,14 @@ mod tests {
 
         // Verify all plugins are registered
         let plugins = registry.list_plugins().await;
-        assert_eq!(plugins.len(), 4);
+        assert_eq!(plugins.len(), 5);
 
         let plugin_ids: Vec<_> = plugins.iter().map(|p| p.id.as_str()).collect();
         assert!(plugin_ids.contains(&"openai"));
         assert!(plugin_ids.contains(&"anthropic"));
         assert!(plugin_ids.contains(&"azure_openai"));
         assert!(plugin_ids.contains(&"aws_bedrock"));
+        assert!(plugin_ids.contains(&"mock"));
     }
 
     #[tokio::test]
@@ -122,7 +134,7 @@ mod tests {
         register_builtin_plugins(&registry, &router).await.unwrap();
 
         let ready_plugins = registry.list_ready_plugins().await;
-        assert_eq!(ready_plugins.len(), 4);
+        assert_eq!(ready_plugins.len(), 5);
     }
 
     #[tokio::test]
@@ -137,6 +149,7 @@ mod tests {
         assert!(credential_types.contains(&"anthropic".to_string()));
         assert!(credential_types.contains(&"azure_openai".to_string()));
         assert!(credential_types.contains(&"aws_bedrock".to_string()));
+        assert!(credential_types.contains(&"mock".to_string()));
     }
 
     #[tokio::test]
@@ -152,6 +165,9 @@ enabled = false
 
 [providers.bedrock]
 enabled = false
+
+[providers.mock]
+enabled = false
 "#,
         )
         .unwrap();
@@ -208,6 +224,9 @@ enabled = false
 
 [providers.bedrock]
 enabled = false
+
+[providers.mock]
+enabled = false
 "#,
         )
         .unwrap();
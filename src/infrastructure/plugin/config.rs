@@ -84,6 +84,10 @@ pub struct ProviderConfigs {
     /// AWS Bedrock provider configuration
     #[serde(default)]
     pub bedrock: ProviderEntry,
+
+    /// Mock provider configuration (canned responses for development)
+    #[serde(default)]
+    pub mock: ProviderEntry,
 }
 
 /// Configuration for a single provider
@@ -204,6 +208,7 @@ impl PluginConfig {
             BuiltinProvider::Anthropic => self.providers.anthropic.enabled,
             BuiltinProvider::AzureOpenAi => self.providers.azure_openai.enabled,
             BuiltinProvider::Bedrock => self.providers.bedrock.enabled,
+            BuiltinProvider::Mock => self.providers.mock.enabled,
         }
     }
 
@@ -214,6 +219,7 @@ impl PluginConfig {
             BuiltinProvider::Anthropic => &self.providers.anthropic.settings,
             BuiltinProvider::AzureOpenAi => &self.providers.azure_openai.settings,
             BuiltinProvider::Bedrock => &self.providers.bedrock.settings,
+            BuiltinProvider::Mock => &self.providers.mock.settings,
         }
     }
 
@@ -241,6 +247,10 @@ impl PluginConfig {
             providers.push(BuiltinProvider::Bedrock);
         }
 
+        if self.providers.mock.enabled {
+            providers.push(BuiltinProvider::Mock);
+        }
+
         providers
     }
 }
@@ -252,6 +262,7 @@ pub enum BuiltinProvider {
     Anthropic,
     AzureOpenAi,
     Bedrock,
+    Mock,
 }
 
 impl BuiltinProvider {
@@ -262,6 +273,7 @@ impl BuiltinProvider {
             BuiltinProvider::Anthropic,
             BuiltinProvider::AzureOpenAi,
             BuiltinProvider::Bedrock,
+            BuiltinProvider::Mock,
         ]
     }
 
@@ -272,6 +284,7 @@ impl BuiltinProvider {
             BuiltinProvider::Anthropic => "anthropic",
             BuiltinProvider::AzureOpenAi => "azure_openai",
             BuiltinProvider::Bedrock => "bedrock",
+            BuiltinProvider::Mock => "mock",
         }
     }
 }
@@ -290,6 +303,7 @@ mod tests {
         assert!(config.providers.anthropic.enabled);
         assert!(config.providers.azure_openai.enabled);
         assert!(config.providers.bedrock.enabled);
+        assert!(config.providers.mock.enabled);
     }
 
     #[test]
@@ -287,6 +287,7 @@ fn credential_type_to_string(cred_type: &CredentialType) -> String {
         CredentialType::AwsKnowledgeBase => "aws_knowledge_base".to_string(),
         CredentialType::Pinecone => "pinecone".to_string(),
         CredentialType::HttpApiKey => "http_api_key".to_string(),
+        CredentialType::Mock => "mock".to_string(),
         CredentialType::Custom(name) => format!("custom_{}", name),
     }
 }
@@ -6,6 +6,8 @@ use crate::domain::credentials::{Credential, CredentialType};
 use crate::domain::llm::LlmProvider;
 use crate::domain::plugin::{LlmProviderConfig, LlmProviderPlugin, PluginError};
 use crate::domain::Model;
+use crate::infrastructure::credentials::CredentialInvalidationListener;
+use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -170,6 +172,16 @@ impl ProviderRouter {
         cache.insert(key, provider);
     }
 
+    /// Drop the cached providers built from a credential, returning how many
+    pub async fn invalidate_credential(&self, credential_id: &str) -> usize {
+        let mut cache = self.provider_cache.write().await;
+        let before = cache.len();
+
+        cache.retain(|key, _| key.credential_id != credential_id);
+
+        before - cache.len()
+    }
+
     /// Clear the provider cache
     pub async fn clear_cache(&self) {
         let mut cache = self.provider_cache.write().await;
@@ -199,6 +211,21 @@ impl ProviderRouter {
     }
 }
 
+#[async_trait]
+impl CredentialInvalidationListener for ProviderRouter {
+    async fn credential_changed(&self, credential_id: &str) {
+        let dropped = self.invalidate_credential(credential_id).await;
+
+        if dropped > 0 {
+            info!(
+                credential_id = %credential_id,
+                dropped,
+                "Dropped cached providers after credential change"
+            );
+        }
+    }
+}
+
 impl Default for ProviderRouter {
     fn default() -> Self {
         Self::new()
@@ -223,6 +250,7 @@ fn credential_type_to_key(cred_type: &CredentialType) -> String {
         CredentialType::AwsKnowledgeBase => "aws_knowledge_base".to_string(),
         CredentialType::Pinecone => "pinecone".to_string(),
         CredentialType::HttpApiKey => "http_api_key".to_string(),
+        CredentialType::Mock => "mock".to_string(),
         CredentialType::Custom(name) => format!("custom_{}", name),
     }
 }
@@ -275,4 +303,29 @@ mod tests {
         let stats = router.cache_stats().await;
         assert_eq!(stats.max_size, 50);
     }
+
+    #[tokio::test]
+    async fn test_credential_change_drops_cached_providers() {
+        use crate::domain::llm::MockLlmProvider;
+
+        let router = ProviderRouter::new();
+
+        for (credential_type, credential_id) in [
+            (CredentialType::OpenAi, "openai-prod"),
+            (CredentialType::Anthropic, "openai-prod"),
+            (CredentialType::OpenAi, "openai-dev"),
+        ] {
+            router
+                .cache_provider(
+                    ProviderCacheKey::new(&credential_type, credential_id),
+                    Arc::new(MockLlmProvider::new("mock")),
+                )
+                .await;
+        }
+
+        router.credential_changed("openai-prod").await;
+
+        assert_eq!(router.cache_stats().await.size, 1);
+        assert_eq!(router.invalidate_credential("openai-dev").await, 1);
+    }
 }
@@ -0,0 +1,5 @@
+//! Request trace infrastructure implementations
+
+mod store;
+
+pub use store::{DEFAULT_REQUEST_TRACE_CAPACITY, RequestTraceStore, RequestTracer};
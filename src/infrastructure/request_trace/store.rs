@@ -0,0 +1,172 @@
+//! In-memory store of recent request traces
+//!
+//! Traces live on the replica that served the request and only the most
+//! recent ones are kept, which is enough to debug a request shortly after a
+//! client reports its `x-pmp-request-id`.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use crate::domain::request_trace::{RequestTrace, RequestTraceStage};
+
+/// Traces kept by default before the oldest are dropped
+pub const DEFAULT_REQUEST_TRACE_CAPACITY: usize = 10_000;
+
+/// Shared handle on the trace of a request in flight. Events recorded after
+/// the trace was stored (e.g. at the end of a stream) show up in it.
+#[derive(Debug, Clone)]
+pub struct RequestTracer {
+    request_id: String,
+    trace: Arc<Mutex<RequestTrace>>,
+}
+
+impl RequestTracer {
+    pub fn new(
+        request_id: impl Into<String>,
+        method: impl Into<String>,
+        path: impl Into<String>,
+    ) -> Self {
+        let request_id = request_id.into();
+
+        Self {
+            trace: Arc::new(Mutex::new(RequestTrace::new(
+                request_id.clone(),
+                method,
+                path,
+            ))),
+            request_id,
+        }
+    }
+
+    pub fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    /// Append an event to the trace
+    pub fn record(&self, stage: RequestTraceStage, message: impl Into<String>) {
+        self.trace.lock().unwrap().record(stage, message, None);
+    }
+
+    /// Append an event with structured details
+    pub fn record_with(
+        &self,
+        stage: RequestTraceStage,
+        message: impl Into<String>,
+        data: serde_json::Value,
+    ) {
+        self.trace.lock().unwrap().record(stage, message, Some(data));
+    }
+
+    /// Set the API key the request authenticated with
+    pub fn set_api_key(&self, api_key_id: impl Into<String>) {
+        self.trace.lock().unwrap().api_key_id = Some(api_key_id.into());
+    }
+
+    /// Set the response status and handler duration
+    pub fn finish(&self, status: u16, duration_ms: u64) {
+        let mut trace = self.trace.lock().unwrap();
+        trace.status = Some(status);
+        trace.duration_ms = Some(duration_ms);
+    }
+
+    /// Copy of the trace as recorded so far
+    pub fn snapshot(&self) -> RequestTrace {
+        self.trace.lock().unwrap().clone()
+    }
+}
+
+#[derive(Debug, Default)]
+struct StoredTraces {
+    by_id: HashMap<String, RequestTracer>,
+    order: VecDeque<String>,
+}
+
+/// Keeps the traces of the most recent requests
+#[derive(Debug)]
+pub struct RequestTraceStore {
+    capacity: usize,
+    traces: Mutex<StoredTraces>,
+}
+
+impl Default for RequestTraceStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_REQUEST_TRACE_CAPACITY)
+    }
+}
+
+impl RequestTraceStore {
+    /// Create a store; a capacity of 0 is treated as 1
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            traces: Mutex::new(StoredTraces::default()),
+        }
+    }
+
+    /// Keep a trace, dropping the oldest one when full
+    pub fn insert(&self, tracer: RequestTracer) {
+        let mut traces = self.traces.lock().unwrap();
+        let id = tracer.request_id().to_string();
+
+        if traces.by_id.insert(id.clone(), tracer).is_some() {
+            return;
+        }
+
+        traces.order.push_back(id);
+
+        while traces.order.len() > self.capacity {
+            if let Some(oldest) = traces.order.pop_front() {
+                traces.by_id.remove(&oldest);
+            }
+        }
+    }
+
+    /// Trace of a request, if still kept
+    pub fn get(&self, request_id: &str) -> Option<RequestTrace> {
+        self.traces
+            .lock()
+            .unwrap()
+            .by_id
+            .get(request_id)
+            .map(RequestTracer::snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_keeps_recent_traces() {
+        let store = RequestTraceStore::new(2);
+
+        for id in ["req-1", "req-2", "req-3"] {
+            store.insert(RequestTracer::new(id, "POST", "/v1/chat/completions"));
+        }
+
+        assert!(store.get("req-1").is_none());
+        assert_eq!(store.get("req-3").unwrap().path, "/v1/chat/completions");
+    }
+
+    #[test]
+    fn test_events_recorded_after_insert_are_visible() {
+        let store = RequestTraceStore::default();
+        let tracer = RequestTracer::new("req-1", "POST", "/v1/chat/completions");
+        store.insert(tracer.clone());
+
+        tracer.set_api_key("key-1");
+        tracer.record(RequestTraceStage::Routing, "Served by model 'gpt-4o'");
+        tracer.record_with(
+            RequestTraceStage::Usage,
+            "Recorded usage",
+            serde_json::json!({"input_tokens": 12}),
+        );
+        tracer.finish(200, 35);
+
+        let trace = store.get("req-1").unwrap();
+        assert_eq!(trace.api_key_id.as_deref(), Some("key-1"));
+        assert_eq!(trace.status, Some(200));
+        assert_eq!(trace.events.len(), 2);
+        assert_eq!(trace.events_of(RequestTraceStage::Usage).count(), 1);
+    }
+}
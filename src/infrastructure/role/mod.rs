@@ -0,0 +1,7 @@
+//! Role infrastructure implementations
+
+mod repository;
+mod service;
+
+pub use repository::StorageRoleRepository;
+pub use service::{CreateRoleRequest, RoleService, UpdateRoleRequest};
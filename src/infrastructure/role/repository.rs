@@ -0,0 +1,126 @@
+//! Storage-backed role repository implementation
+
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::role::{Role, RoleId, RoleRepository};
+use crate::domain::storage::Storage;
+use crate::domain::DomainError;
+
+/// Storage-backed implementation of RoleRepository
+#[derive(Debug)]
+pub struct StorageRoleRepository {
+    storage: Arc<dyn Storage<Role>>,
+}
+
+impl StorageRoleRepository {
+    /// Create a new storage-backed repository
+    pub fn new(storage: Arc<dyn Storage<Role>>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl RoleRepository for StorageRoleRepository {
+    async fn get(&self, id: &RoleId) -> Result<Option<Role>, DomainError> {
+        self.storage.get(id).await
+    }
+
+    async fn create(&self, role: Role) -> Result<Role, DomainError> {
+        if self.storage.exists(role.id()).await? {
+            return Err(DomainError::conflict(format!(
+                "Role '{}' already exists",
+                role.id().as_str()
+            )));
+        }
+
+        self.storage.create(role).await
+    }
+
+    async fn update(&self, role: Role) -> Result<Role, DomainError> {
+        if !self.storage.exists(role.id()).await? {
+            return Err(DomainError::not_found(format!(
+                "Role '{}' not found",
+                role.id().as_str()
+            )));
+        }
+
+        self.storage.update(role).await
+    }
+
+    async fn delete(&self, id: &RoleId) -> Result<bool, DomainError> {
+        self.storage.delete(id).await
+    }
+
+    async fn list(&self) -> Result<Vec<Role>, DomainError> {
+        let mut roles = self.storage.list().await?;
+        roles.sort_by(|a, b| a.id().as_str().cmp(b.id().as_str()));
+        Ok(roles)
+    }
+
+    async fn exists(&self, id: &RoleId) -> Result<bool, DomainError> {
+        self.storage.exists(id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::storage::InMemoryStorage;
+
+    fn create_repo() -> StorageRoleRepository {
+        let storage = Arc::new(InMemoryStorage::<Role>::new());
+        StorageRoleRepository::new(storage)
+    }
+
+    fn create_role(id: &str) -> Role {
+        Role::new(RoleId::new(id).unwrap(), id, vec![]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_and_get() {
+        let repo = create_repo();
+        let role = create_role("custom");
+
+        repo.create(role.clone()).await.unwrap();
+
+        let retrieved = repo.get(role.id()).await.unwrap();
+        assert!(retrieved.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_create_duplicate() {
+        let repo = create_repo();
+
+        repo.create(create_role("custom")).await.unwrap();
+        assert!(repo.create(create_role("custom")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_nonexistent() {
+        let repo = create_repo();
+        assert!(repo.update(create_role("custom")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_sorted() {
+        let repo = create_repo();
+
+        repo.create(create_role("zeta")).await.unwrap();
+        repo.create(create_role("alpha")).await.unwrap();
+
+        let roles = repo.list().await.unwrap();
+        assert_eq!(roles.len(), 2);
+        assert_eq!(roles[0].id().as_str(), "alpha");
+    }
+
+    #[tokio::test]
+    async fn test_delete() {
+        let repo = create_repo();
+        let role = create_role("custom");
+
+        repo.create(role.clone()).await.unwrap();
+        assert!(repo.delete(role.id()).await.unwrap());
+        assert!(!repo.exists(role.id()).await.unwrap());
+    }
+}
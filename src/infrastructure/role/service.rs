@@ -0,0 +1,315 @@
+//! Role service for role management and permission resolution
+
+use std::sync::Arc;
+
+use tracing::info;
+
+use crate::domain::role::{Permission, Role, RoleId, RoleRepository};
+use crate::domain::user::User;
+use crate::domain::DomainError;
+
+/// Request for creating a new custom role
+#[derive(Debug, Clone)]
+pub struct CreateRoleRequest {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<String>,
+}
+
+/// Request for updating a custom role
+#[derive(Debug, Clone)]
+pub struct UpdateRoleRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub permissions: Option<Vec<String>>,
+}
+
+/// Role service. Built-in roles are defined in code and cannot be modified;
+/// only custom roles are persisted.
+#[derive(Debug)]
+pub struct RoleService<R: RoleRepository> {
+    repository: Arc<R>,
+}
+
+impl<R: RoleRepository> RoleService<R> {
+    /// Create a new role service
+    pub fn new(repository: Arc<R>) -> Self {
+        Self { repository }
+    }
+
+    /// Create a new custom role
+    pub async fn create(&self, request: CreateRoleRequest) -> Result<Role, DomainError> {
+        info!(id = %request.id, name = %request.name, "Creating role");
+
+        let role_id =
+            RoleId::new(&request.id).map_err(|e| DomainError::invalid_id(e.to_string()))?;
+
+        if Role::is_builtin_id(role_id.as_str()) || self.repository.exists(&role_id).await? {
+            return Err(DomainError::conflict(format!(
+                "Role '{}' already exists",
+                request.id
+            )));
+        }
+
+        let permissions = parse_permissions(&request.permissions)?;
+        let mut role = Role::new(role_id, &request.name, permissions)
+            .map_err(|e| DomainError::validation(e.to_string()))?;
+
+        if let Some(desc) = request.description {
+            role = role.with_description(desc);
+        }
+
+        self.repository.create(role).await
+    }
+
+    /// Get a role by ID (built-in or custom)
+    pub async fn get(&self, id: &str) -> Result<Option<Role>, DomainError> {
+        let role_id = RoleId::new(id).map_err(|e| DomainError::invalid_id(e.to_string()))?;
+        self.get_by_id(&role_id).await
+    }
+
+    /// Get a role by ID (RoleId)
+    pub async fn get_by_id(&self, id: &RoleId) -> Result<Option<Role>, DomainError> {
+        if let Some(role) = Role::builtin(id.as_str()) {
+            return Ok(Some(role));
+        }
+
+        self.repository.get(id).await
+    }
+
+    /// List all roles, built-in roles first
+    pub async fn list(&self) -> Result<Vec<Role>, DomainError> {
+        let mut roles = Role::builtin_roles();
+        roles.extend(self.repository.list().await?);
+        Ok(roles)
+    }
+
+    /// Update a custom role
+    pub async fn update(&self, id: &str, request: UpdateRoleRequest) -> Result<Role, DomainError> {
+        info!(id = %id, "Updating role");
+
+        let role_id = RoleId::new(id).map_err(|e| DomainError::invalid_id(e.to_string()))?;
+
+        if Role::is_builtin_id(id) {
+            return Err(DomainError::validation(format!(
+                "Built-in role '{}' cannot be modified",
+                id
+            )));
+        }
+
+        let mut role = self
+            .repository
+            .get(&role_id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("Role '{}' not found", id)))?;
+
+        if let Some(name) = request.name {
+            role.set_name(name)
+                .map_err(|e| DomainError::validation(e.to_string()))?;
+        }
+
+        if let Some(desc) = request.description {
+            role.set_description(Some(desc));
+        }
+
+        if let Some(permissions) = request.permissions {
+            role.set_permissions(parse_permissions(&permissions)?);
+        }
+
+        self.repository.update(role).await
+    }
+
+    /// Delete a custom role
+    pub async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        info!(id = %id, "Deleting role");
+
+        let role_id = RoleId::new(id).map_err(|e| DomainError::invalid_id(e.to_string()))?;
+
+        if Role::is_builtin_id(id) {
+            return Err(DomainError::validation(format!(
+                "Built-in role '{}' cannot be deleted",
+                id
+            )));
+        }
+
+        self.repository.delete(&role_id).await
+    }
+
+    /// Resolve the effective role of a user. Falls back to the team role
+    /// default if the assigned role no longer exists.
+    pub async fn resolve_for_user(&self, user: &User) -> Result<Role, DomainError> {
+        if let Some(role_id) = user.role_id()
+            && let Some(role) = self.get_by_id(role_id).await?
+        {
+            return Ok(role);
+        }
+
+        let fallback = RoleId::for_team_role(user.team_role());
+
+        Role::builtin(fallback.as_str()).ok_or_else(|| {
+            DomainError::internal(format!("Built-in role '{}' is missing", fallback))
+        })
+    }
+}
+
+fn parse_permissions(permissions: &[String]) -> Result<Vec<Permission>, DomainError> {
+    permissions
+        .iter()
+        .map(|p| Permission::parse(p).map_err(|e| DomainError::validation(e.to_string())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::role::PermissionResource;
+    use crate::domain::team::{TeamId, TeamRole};
+    use crate::domain::user::UserId;
+    use crate::infrastructure::role::StorageRoleRepository;
+    use crate::infrastructure::storage::InMemoryStorage;
+
+    fn create_service() -> RoleService<StorageRoleRepository> {
+        let storage = Arc::new(InMemoryStorage::<Role>::new());
+        let repository = Arc::new(StorageRoleRepository::new(storage));
+        RoleService::new(repository)
+    }
+
+    fn create_request(id: &str, permissions: &[&str]) -> CreateRoleRequest {
+        CreateRoleRequest {
+            id: id.to_string(),
+            name: "Custom Role".to_string(),
+            description: None,
+            permissions: permissions.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    fn create_user(team_role: TeamRole) -> User {
+        User::new(
+            UserId::new("user-1").unwrap(),
+            "user",
+            "hash",
+            TeamId::administrators(),
+            team_role,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_create_role() {
+        let service = create_service();
+
+        let role = service
+            .create(create_request("prompt-author", &["prompts:write", "models:read"]))
+            .await
+            .unwrap();
+
+        assert_eq!(role.id().as_str(), "prompt-author");
+        assert_eq!(role.permissions().len(), 2);
+        assert!(!role.is_built_in());
+    }
+
+    #[tokio::test]
+    async fn test_create_role_invalid_permission() {
+        let service = create_service();
+
+        let result = service
+            .create(create_request("custom", &["prompts:delete"]))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_role_conflicts_with_builtin() {
+        let service = create_service();
+
+        let result = service.create(create_request("viewer", &[])).await;
+        assert!(matches!(result, Err(DomainError::Conflict { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_get_builtin_and_custom() {
+        let service = create_service();
+        service.create(create_request("custom", &[])).await.unwrap();
+
+        assert!(service.get("owner").await.unwrap().unwrap().is_built_in());
+        assert!(!service.get("custom").await.unwrap().unwrap().is_built_in());
+        assert!(service.get("missing").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_list_roles() {
+        let service = create_service();
+        service.create(create_request("custom", &[])).await.unwrap();
+
+        let roles = service.list().await.unwrap();
+        assert_eq!(roles.len(), Role::builtin_roles().len() + 1);
+        assert_eq!(roles[0].id().as_str(), "owner");
+    }
+
+    #[tokio::test]
+    async fn test_update_role() {
+        let service = create_service();
+        service.create(create_request("custom", &[])).await.unwrap();
+
+        let updated = service
+            .update(
+                "custom",
+                UpdateRoleRequest {
+                    name: Some("Renamed".to_string()),
+                    description: None,
+                    permissions: Some(vec!["budgets:write".to_string()]),
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.name(), "Renamed");
+        assert!(updated.allows(&Permission::write(PermissionResource::Budgets)));
+    }
+
+    #[tokio::test]
+    async fn test_builtin_roles_are_immutable() {
+        let service = create_service();
+
+        let update = UpdateRoleRequest {
+            name: Some("Hacked".to_string()),
+            description: None,
+            permissions: None,
+        };
+
+        assert!(service.update("viewer", update).await.is_err());
+        assert!(service.delete("owner").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_delete_role() {
+        let service = create_service();
+        service.create(create_request("custom", &[])).await.unwrap();
+
+        assert!(service.delete("custom").await.unwrap());
+        assert!(service.get("custom").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_for_user() {
+        let service = create_service();
+        service
+            .create(create_request("custom", &["usage:read"]))
+            .await
+            .unwrap();
+
+        let user = create_user(TeamRole::Owner);
+        let role = service.resolve_for_user(&user).await.unwrap();
+        assert_eq!(role.id().as_str(), "owner");
+
+        let mut user = create_user(TeamRole::Member);
+        user.set_role_id(Some(RoleId::new("custom").unwrap()));
+        let role = service.resolve_for_user(&user).await.unwrap();
+        assert_eq!(role.id().as_str(), "custom");
+
+        // Dangling role assignments fall back to the team role default
+        user.set_role_id(Some(RoleId::new("deleted").unwrap()));
+        let role = service.resolve_for_user(&user).await.unwrap();
+        assert_eq!(role.id().as_str(), "editor");
+    }
+}
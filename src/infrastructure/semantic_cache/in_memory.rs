@@ -6,7 +6,6 @@ use std::sync::RwLock;
 
 use async_trait::async_trait;
 
-use crate::domain::embedding::cosine_similarity;
 use crate::domain::semantic_cache::{
     CachedEntry, SemanticCache, SemanticCacheStats, SemanticSearchParams, SemanticSearchResult,
 };
@@ -15,7 +14,8 @@ use crate::domain::DomainError;
 /// In-memory semantic cache using linear search
 ///
 /// Suitable for development and small-scale deployments.
-/// For production with large cache sizes, use PgvectorSemanticCache.
+/// Entries are local to the process; use RedisSemanticCache to share
+/// them across replicas.
 #[derive(Debug)]
 pub struct InMemorySemanticCache {
     entries: RwLock<HashMap<String, CachedEntry>>,
@@ -58,27 +58,6 @@ impl InMemorySemanticCache {
         }
     }
 
-    /// Check if entry matches the filter criteria
-    fn matches_filter(entry: &CachedEntry, params: &SemanticSearchParams) -> bool {
-        // Check model ID filter
-        if let Some(ref model_id) = params.model_id {
-            if entry.model_id() != Some(model_id.as_str()) {
-                return false;
-            }
-        }
-
-        // Check temperature filter (with small tolerance)
-        if let Some(temp) = params.temperature {
-            if let Some(entry_temp) = entry.temperature() {
-                if (entry_temp - temp).abs() > 0.01 {
-                    return false;
-                }
-            }
-        }
-
-        true
-    }
-
     #[allow(dead_code)]
     fn update_avg_similarity(&self, similarity: f32) {
         let mut total = self.total_similarity.write().unwrap();
@@ -113,9 +92,9 @@ impl SemanticCache for InMemorySemanticCache {
         let mut results: Vec<SemanticSearchResult> = entries
             .values()
             .filter(|entry| !entry.is_expired())
-            .filter(|entry| Self::matches_filter(entry, params))
+            .filter(|entry| params.matches(entry))
             .map(|entry| {
-                let similarity = cosine_similarity(embedding, entry.embedding());
+                let similarity = params.metric.similarity(embedding, entry.embedding());
                 SemanticSearchResult::new(entry.clone(), similarity)
             })
             .filter(|result| result.similarity >= params.min_similarity)
@@ -264,6 +243,7 @@ impl SemanticCache for InMemorySemanticCache {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::DistanceMetric;
     use std::time::Duration;
 
     fn create_entry(id: &str, embedding: Vec<f32>, model_id: Option<&str>) -> CachedEntry {
@@ -332,6 +312,23 @@ mod tests {
         assert_eq!(results[0].entry.id(), "similar");
     }
 
+    #[tokio::test]
+    async fn test_search_with_metric() {
+        let cache = InMemorySemanticCache::new(100);
+
+        // Same direction, twice the length
+        cache
+            .store(create_entry("scaled", vec![2.0, 0.0, 0.0], None))
+            .await
+            .unwrap();
+
+        let cosine = SemanticSearchParams::new(0.95);
+        assert_eq!(cache.search(&[1.0, 0.0, 0.0], &cosine).await.unwrap().len(), 1);
+
+        let l2 = SemanticSearchParams::new(0.95).with_metric(DistanceMetric::Euclidean);
+        assert!(cache.search(&[1.0, 0.0, 0.0], &l2).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_search_with_model_filter() {
         let cache = InMemorySemanticCache::new(100);
@@ -0,0 +1,767 @@
+//! Redis semantic cache implementation
+//!
+//! Entries are hashes indexed by a RediSearch vector index, so every replica
+//! sharing the Redis instance sees the same entries and they survive
+//! restarts. Two sorted sets track the entries by creation and expiration
+//! time for max-entry eviction and expiry bookkeeping; Redis expires the
+//! hashes themselves.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::{Client, Value};
+
+use crate::domain::embedding::DistanceMetric;
+use crate::domain::semantic_cache::{
+    CachedEntry, SemanticCache, SemanticCacheStats, SemanticSearchParams, SemanticSearchResult,
+};
+use crate::domain::DomainError;
+
+/// Nearest neighbours fetched per requested result; temperature filtering
+/// happens after the vector search
+const CANDIDATES_PER_RESULT: usize = 4;
+
+/// Keys deleted per round when removing entries in bulk
+const DELETE_BATCH_SIZE: usize = 1000;
+
+/// Increments the hit count of an entry unless it expired meanwhile, so the
+/// hash is not recreated without a TTL
+const RECORD_HIT_SCRIPT: &str = r#"
+if redis.call('EXISTS', KEYS[1]) == 1 then
+    return redis.call('HINCRBY', KEYS[1], 'hit_count', 1)
+end
+return 0
+"#;
+
+/// Configuration for the Redis semantic cache
+#[derive(Debug, Clone)]
+pub struct RedisSemanticCacheConfig {
+    /// Redis connection URL; the server needs the RediSearch module
+    /// (Redis Stack or Redis 8)
+    pub url: String,
+    /// Prefix of every key and of the index name
+    pub namespace: String,
+    /// Dimensions of the cached embeddings
+    pub dimensions: usize,
+    /// Entries kept before the oldest ones are evicted
+    pub max_entries: usize,
+    /// Metric of the vector index
+    pub metric: DistanceMetric,
+}
+
+impl Default for RedisSemanticCacheConfig {
+    fn default() -> Self {
+        Self {
+            url: "redis://127.0.0.1:6379".to_string(),
+            namespace: "semantic:llm".to_string(),
+            dimensions: 1536,
+            max_entries: 10000,
+            metric: DistanceMetric::default(),
+        }
+    }
+}
+
+impl RedisSemanticCacheConfig {
+    /// Creates a new configuration with the given URL
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the namespace
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = namespace.into();
+        self
+    }
+
+    /// Sets the embedding dimensions
+    pub fn with_dimensions(mut self, dimensions: usize) -> Self {
+        self.dimensions = dimensions;
+        self
+    }
+
+    /// Sets the maximum number of entries
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Sets the metric of the vector index
+    pub fn with_metric(mut self, metric: DistanceMetric) -> Self {
+        self.metric = metric;
+        self
+    }
+
+    fn index_name(&self) -> String {
+        format!("{}:idx", self.namespace)
+    }
+
+    fn entry_prefix(&self) -> String {
+        format!("{}:entry:", self.namespace)
+    }
+
+    fn entry_key(&self, id: &str) -> String {
+        format!("{}{}", self.entry_prefix(), id)
+    }
+
+    fn created_key(&self) -> String {
+        format!("{}:created", self.namespace)
+    }
+
+    fn expires_key(&self) -> String {
+        format!("{}:expires", self.namespace)
+    }
+
+    fn stats_key(&self) -> String {
+        format!("{}:stats", self.namespace)
+    }
+}
+
+/// Semantic cache shared across replicas through Redis
+///
+/// Features:
+/// - HNSW vector index (RediSearch) filtered by model
+/// - TTL per entry
+/// - Eviction of the oldest entries beyond `max_entries`
+/// - Hit, miss and eviction counters shared by every replica
+#[derive(Clone)]
+pub struct RedisSemanticCache {
+    connection: ConnectionManager,
+    config: RedisSemanticCacheConfig,
+}
+
+impl fmt::Debug for RedisSemanticCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisSemanticCache")
+            .field("config", &self.config)
+            .field("connection", &"<ConnectionManager>")
+            .finish()
+    }
+}
+
+impl RedisSemanticCache {
+    /// Connects to Redis and creates the vector index if missing
+    pub async fn new(config: RedisSemanticCacheConfig) -> Result<Self, DomainError> {
+        let client = Client::open(config.url.as_str())
+            .map_err(|e| DomainError::cache(format!("Failed to create Redis client: {}", e)))?;
+
+        let connection = ConnectionManager::new(client)
+            .await
+            .map_err(|e| DomainError::cache(format!("Failed to connect to Redis: {}", e)))?;
+
+        let cache = Self { connection, config };
+        cache.ensure_index().await?;
+
+        Ok(cache)
+    }
+
+    async fn ensure_index(&self) -> Result<(), DomainError> {
+        let mut conn = self.connection.clone();
+
+        let result: redis::RedisResult<()> = redis::cmd("FT.CREATE")
+            .arg(self.config.index_name())
+            .arg("ON")
+            .arg("HASH")
+            .arg("PREFIX")
+            .arg(1)
+            .arg(self.config.entry_prefix())
+            .arg("SCHEMA")
+            .arg("embedding")
+            .arg("VECTOR")
+            .arg("HNSW")
+            .arg(6)
+            .arg("TYPE")
+            .arg("FLOAT32")
+            .arg("DIM")
+            .arg(self.config.dimensions)
+            .arg("DISTANCE_METRIC")
+            .arg(index_metric(self.config.metric))
+            .arg("model_id")
+            .arg("TAG")
+            .query_async(&mut conn)
+            .await;
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(e) if e.to_string().contains("Index already exists") => Ok(()),
+            Err(e) => Err(DomainError::cache(format!(
+                "Failed to create semantic cache index '{}': {}",
+                self.config.index_name(),
+                e
+            ))),
+        }
+    }
+
+    /// Remove the given entries and their bookkeeping
+    async fn remove_entries(
+        &self,
+        conn: &mut ConnectionManager,
+        ids: &[String],
+    ) -> Result<usize, DomainError> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let keys: Vec<String> = ids.iter().map(|id| self.config.entry_key(id)).collect();
+        let (deleted, _, _): (usize, usize, usize) = redis::pipe()
+            .atomic()
+            .cmd("DEL")
+            .arg(&keys)
+            .cmd("ZREM")
+            .arg(self.config.created_key())
+            .arg(ids)
+            .cmd("ZREM")
+            .arg(self.config.expires_key())
+            .arg(ids)
+            .query_async(conn)
+            .await
+            .map_err(|e| DomainError::cache(format!("Failed to delete cache entries: {}", e)))?;
+
+        Ok(deleted)
+    }
+
+    /// Forget the entries Redis expired, returning how many there were
+    async fn remove_expired(&self, conn: &mut ConnectionManager) -> Result<usize, DomainError> {
+        let expired: Vec<String> = redis::cmd("ZRANGEBYSCORE")
+            .arg(self.config.expires_key())
+            .arg("-inf")
+            .arg(now_secs())
+            .query_async(conn)
+            .await
+            .map_err(|e| DomainError::cache(format!("Failed to list expired entries: {}", e)))?;
+
+        self.remove_entries(conn, &expired).await?;
+        Ok(expired.len())
+    }
+
+    /// Evict the oldest entries beyond `max_entries`
+    async fn evict_if_needed(&self, conn: &mut ConnectionManager) -> Result<(), DomainError> {
+        let size: usize = redis::cmd("ZCARD")
+            .arg(self.config.created_key())
+            .query_async(conn)
+            .await
+            .map_err(|e| DomainError::cache(format!("Failed to count cache entries: {}", e)))?;
+
+        let excess = size.saturating_sub(self.config.max_entries);
+        if excess == 0 {
+            return Ok(());
+        }
+
+        // Popping is atomic, so replicas evicting at once take distinct entries
+        let popped: Vec<(String, f64)> = redis::cmd("ZPOPMIN")
+            .arg(self.config.created_key())
+            .arg(excess)
+            .query_async(conn)
+            .await
+            .map_err(|e| DomainError::cache(format!("Failed to evict cache entries: {}", e)))?;
+        let ids: Vec<String> = popped.into_iter().map(|(id, _)| id).collect();
+
+        self.remove_entries(conn, &ids).await?;
+        self.increment_stat(conn, "evictions", ids.len()).await
+    }
+
+    async fn increment_stat(
+        &self,
+        conn: &mut ConnectionManager,
+        field: &str,
+        by: usize,
+    ) -> Result<(), DomainError> {
+        let _: i64 = redis::cmd("HINCRBY")
+            .arg(self.config.stats_key())
+            .arg(field)
+            .arg(by)
+            .query_async(conn)
+            .await
+            .map_err(|e| DomainError::cache(format!("Failed to record {}: {}", field, e)))?;
+
+        Ok(())
+    }
+
+    async fn count_live(&self, conn: &mut ConnectionManager) -> Result<usize, DomainError> {
+        redis::cmd("ZCOUNT")
+            .arg(self.config.expires_key())
+            .arg(format!("({}", now_secs()))
+            .arg("+inf")
+            .query_async(conn)
+            .await
+            .map_err(|e| DomainError::cache(format!("Failed to count cache entries: {}", e)))
+    }
+}
+
+#[async_trait]
+impl SemanticCache for RedisSemanticCache {
+    async fn search(
+        &self,
+        embedding: &[f32],
+        params: &SemanticSearchParams,
+    ) -> Result<Vec<SemanticSearchResult>, DomainError> {
+        if params.limit == 0 {
+            return Ok(Vec::new());
+        }
+        let mut conn = self.connection.clone();
+
+        let candidates = params.limit * CANDIDATES_PER_RESULT;
+        let reply: Value = redis::cmd("FT.SEARCH")
+            .arg(self.config.index_name())
+            .arg(knn_query(params.model_id.as_deref()))
+            .arg("PARAMS")
+            .arg(4)
+            .arg("vec")
+            .arg(encode_embedding(embedding))
+            .arg("k")
+            .arg(candidates)
+            .arg("RETURN")
+            .arg(2)
+            .arg("entry")
+            .arg("hit_count")
+            .arg("LIMIT")
+            .arg(0)
+            .arg(candidates)
+            .arg("DIALECT")
+            .arg(2)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| DomainError::cache(format!("Failed to search semantic cache: {}", e)))?;
+
+        // Scores are recomputed with the requested metric, which may differ
+        // from the metric of the index
+        let mut results: Vec<SemanticSearchResult> = parse_entries(&reply)?
+            .into_iter()
+            .filter(|entry| !entry.is_expired())
+            .filter(|entry| params.matches(entry))
+            .map(|entry| {
+                let similarity = params.metric.similarity(embedding, entry.embedding());
+                SemanticSearchResult::new(entry, similarity)
+            })
+            .filter(|result| result.similarity >= params.min_similarity)
+            .collect();
+
+        results.sort_by(|a, b| {
+            b.similarity
+                .partial_cmp(&a.similarity)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        results.truncate(params.limit);
+
+        Ok(results)
+    }
+
+    async fn store(&self, entry: CachedEntry) -> Result<(), DomainError> {
+        if entry.embedding().len() != self.config.dimensions {
+            return Err(DomainError::validation(format!(
+                "Embedding has {} dimensions, the semantic cache index expects {}",
+                entry.embedding().len(),
+                self.config.dimensions
+            )));
+        }
+        let mut conn = self.connection.clone();
+
+        let key = self.config.entry_key(entry.id());
+        let json = serde_json::to_string(&entry).map_err(|e| {
+            DomainError::internal(format!("Failed to serialize cache entry: {}", e))
+        })?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .cmd("DEL")
+            .arg(&key)
+            .ignore()
+            .cmd("HSET")
+            .arg(&key)
+            .arg("embedding")
+            .arg(encode_embedding(entry.embedding()))
+            .arg("entry")
+            .arg(json)
+            .arg("hit_count")
+            .arg(entry.hit_count())
+            .ignore();
+        if let Some(model_id) = entry.model_id() {
+            pipe.cmd("HSET")
+                .arg(&key)
+                .arg("model_id")
+                .arg(model_id)
+                .ignore();
+        }
+        pipe.cmd("EXPIREAT")
+            .arg(&key)
+            .arg(entry.expires_at())
+            .ignore()
+            .cmd("ZADD")
+            .arg(self.config.created_key())
+            .arg(entry.created_at())
+            .arg(entry.id())
+            .ignore()
+            .cmd("ZADD")
+            .arg(self.config.expires_key())
+            .arg(entry.expires_at())
+            .arg(entry.id())
+            .ignore();
+
+        let _: () = pipe.query_async(&mut conn).await.map_err(|e| {
+            DomainError::cache(format!("Failed to store cache entry '{}': {}", entry.id(), e))
+        })?;
+
+        self.remove_expired(&mut conn).await?;
+        self.evict_if_needed(&mut conn).await
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<CachedEntry>, DomainError> {
+        let mut conn = self.connection.clone();
+
+        let (json, hit_count): (Option<String>, Option<u32>) = redis::cmd("HMGET")
+            .arg(self.config.entry_key(id))
+            .arg("entry")
+            .arg("hit_count")
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| DomainError::cache(format!("Failed to get cache entry '{}': {}", id, e)))?;
+
+        let Some(json) = json else {
+            return Ok(None);
+        };
+        let entry = decode_entry(&json, hit_count.unwrap_or_default())?;
+
+        Ok(Some(entry).filter(|e| !e.is_expired()))
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        let mut conn = self.connection.clone();
+
+        Ok(self.remove_entries(&mut conn, &[id.to_string()]).await? > 0)
+    }
+
+    async fn delete_by_model(&self, model_id: &str) -> Result<usize, DomainError> {
+        let mut conn = self.connection.clone();
+        let prefix = self.config.entry_prefix();
+        let mut deleted = 0;
+
+        loop {
+            let reply: Value = redis::cmd("FT.SEARCH")
+                .arg(self.config.index_name())
+                .arg(format!("@model_id:{{{}}}", escape_tag(model_id)))
+                .arg("NOCONTENT")
+                .arg("LIMIT")
+                .arg(0)
+                .arg(DELETE_BATCH_SIZE)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| {
+                    DomainError::cache(format!("Failed to find entries of '{}': {}", model_id, e))
+                })?;
+
+            let ids: Vec<String> = parse_keys(&reply)
+                .into_iter()
+                .filter_map(|key| key.strip_prefix(&prefix).map(str::to_string))
+                .collect();
+            if ids.is_empty() {
+                return Ok(deleted);
+            }
+
+            deleted += self.remove_entries(&mut conn, &ids).await?;
+        }
+    }
+
+    async fn clear(&self) -> Result<(), DomainError> {
+        let mut conn = self.connection.clone();
+        let pattern = format!("{}*", self.config.entry_prefix());
+        let mut cursor: u64 = 0;
+
+        loop {
+            let (next_cursor, keys): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(DELETE_BATCH_SIZE)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| DomainError::cache(format!("Failed to scan cache entries: {}", e)))?;
+
+            if !keys.is_empty() {
+                let _: usize = redis::cmd("DEL")
+                    .arg(&keys)
+                    .query_async(&mut conn)
+                    .await
+                    .map_err(|e| {
+                        DomainError::cache(format!("Failed to delete cache entries: {}", e))
+                    })?;
+            }
+
+            cursor = next_cursor;
+            if cursor == 0 {
+                break;
+            }
+        }
+
+        let _: usize = redis::cmd("DEL")
+            .arg(self.config.created_key())
+            .arg(self.config.expires_key())
+            .arg(self.config.stats_key())
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| DomainError::cache(format!("Failed to clear semantic cache: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn stats(&self) -> Result<SemanticCacheStats, DomainError> {
+        let mut conn = self.connection.clone();
+
+        let counters: HashMap<String, u64> = redis::cmd("HGETALL")
+            .arg(self.config.stats_key())
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| DomainError::cache(format!("Failed to get cache stats: {}", e)))?;
+        let counter = |name: &str| counters.get(name).copied().unwrap_or_default();
+
+        Ok(SemanticCacheStats {
+            total_entries: self.count_live(&mut conn).await?,
+            hits: counter("hits"),
+            misses: counter("misses"),
+            evictions: counter("evictions"),
+            avg_hit_similarity: 0.0,
+        })
+    }
+
+    async fn size(&self) -> Result<usize, DomainError> {
+        let mut conn = self.connection.clone();
+
+        self.count_live(&mut conn).await
+    }
+
+    async fn record_hit(&self, id: &str) -> Result<(), DomainError> {
+        let mut conn = self.connection.clone();
+
+        self.increment_stat(&mut conn, "hits", 1).await?;
+
+        let _: i64 = redis::Script::new(RECORD_HIT_SCRIPT)
+            .key(self.config.entry_key(id))
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| DomainError::cache(format!("Failed to record hit on '{}': {}", id, e)))?;
+
+        Ok(())
+    }
+
+    async fn record_miss(&self) -> Result<(), DomainError> {
+        let mut conn = self.connection.clone();
+
+        self.increment_stat(&mut conn, "misses", 1).await
+    }
+
+    async fn cleanup_expired(&self) -> Result<usize, DomainError> {
+        let mut conn = self.connection.clone();
+
+        self.remove_expired(&mut conn).await
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs()
+}
+
+/// RediSearch name of a distance metric
+fn index_metric(metric: DistanceMetric) -> &'static str {
+    match metric {
+        DistanceMetric::Cosine => "COSINE",
+        DistanceMetric::Euclidean => "L2",
+        DistanceMetric::InnerProduct => "IP",
+    }
+}
+
+/// Embedding as the little-endian FLOAT32 blob RediSearch indexes
+fn encode_embedding(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+/// Escape the punctuation RediSearch treats as syntax in tag values
+fn escape_tag(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        if !c.is_alphanumeric() && c != '_' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+/// KNN query of the `k` nearest entries, of one model when given
+fn knn_query(model_id: Option<&str>) -> String {
+    let filter = match model_id {
+        Some(model_id) => format!("(@model_id:{{{}}})", escape_tag(model_id)),
+        None => "*".to_string(),
+    };
+
+    format!("{}=>[KNN $k @embedding $vec AS distance]", filter)
+}
+
+fn decode_entry(json: &str, hit_count: u32) -> Result<CachedEntry, DomainError> {
+    let entry: CachedEntry = serde_json::from_str(json).map_err(|e| {
+        DomainError::internal(format!("Failed to deserialize cache entry: {}", e))
+    })?;
+
+    Ok(entry.with_hit_count(hit_count))
+}
+
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::BulkString(bytes) => String::from_utf8(bytes.clone()).ok(),
+        Value::SimpleString(s) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+/// Keys of an `FT.SEARCH ... NOCONTENT` reply: `[total, key, key, ...]`
+fn parse_keys(reply: &Value) -> Vec<String> {
+    match reply {
+        Value::Array(items) => items.iter().skip(1).filter_map(value_to_string).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Entries of an `FT.SEARCH` reply: `[total, key, [field, value, ...], ...]`
+fn parse_entries(reply: &Value) -> Result<Vec<CachedEntry>, DomainError> {
+    let Value::Array(items) = reply else {
+        return Err(DomainError::cache("Unexpected semantic cache search reply"));
+    };
+
+    let mut entries = Vec::new();
+
+    for fields in items.iter().skip(2).step_by(2) {
+        let Value::Array(fields) = fields else {
+            continue;
+        };
+        let fields: HashMap<String, String> = fields
+            .chunks(2)
+            .filter_map(|pair| match pair {
+                [name, value] => Some((value_to_string(name)?, value_to_string(value)?)),
+                _ => None,
+            })
+            .collect();
+
+        // Entries expired between the search and the read have no fields left
+        let Some(json) = fields.get("entry") else {
+            continue;
+        };
+        let hit_count = fields
+            .get("hit_count")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default();
+        entries.push(decode_entry(json, hit_count)?);
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bulk(s: &str) -> Value {
+        Value::BulkString(s.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_encode_embedding() {
+        let bytes = encode_embedding(&[1.0, -2.5]);
+
+        assert_eq!(bytes.len(), 8);
+        assert_eq!(&bytes[..4], &1.0f32.to_le_bytes());
+        assert_eq!(&bytes[4..], &(-2.5f32).to_le_bytes());
+    }
+
+    #[test]
+    fn test_knn_query() {
+        assert_eq!(knn_query(None), "*=>[KNN $k @embedding $vec AS distance]");
+        assert_eq!(
+            knn_query(Some("gpt-4o.mini")),
+            r"(@model_id:{gpt\-4o\.mini})=>[KNN $k @embedding $vec AS distance]"
+        );
+    }
+
+    #[test]
+    fn test_parse_entries() {
+        let entry = CachedEntry::new(
+            "e1",
+            vec![1.0, 0.0],
+            "hello",
+            "\"hi\"",
+            Duration::from_secs(60),
+        )
+        .with_model_id("gpt-4");
+        let json = serde_json::to_string(&entry).unwrap();
+
+        let reply = Value::Array(vec![
+            Value::Int(2),
+            bulk("semantic:llm:entry:e1"),
+            Value::Array(vec![
+                bulk("hit_count"),
+                bulk("3"),
+                bulk("entry"),
+                bulk(&json),
+            ]),
+            bulk("semantic:llm:entry:gone"),
+            Value::Array(vec![]),
+        ]);
+
+        let entries = parse_entries(&reply).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id(), "e1");
+        assert_eq!(entries[0].model_id(), Some("gpt-4"));
+        assert_eq!(entries[0].hit_count(), 3);
+
+        assert_eq!(parse_keys(&reply)[0], "semantic:llm:entry:e1");
+        assert!(parse_entries(&Value::Nil).is_err());
+    }
+
+    #[test]
+    fn test_config_keys() {
+        let config = RedisSemanticCacheConfig::default().with_namespace("cache:sem");
+
+        assert_eq!(config.index_name(), "cache:sem:idx");
+        assert_eq!(config.entry_key("abc"), "cache:sem:entry:abc");
+        assert_eq!(index_metric(DistanceMetric::InnerProduct), "IP");
+    }
+
+    #[tokio::test]
+    #[ignore = "Requires running Redis Stack instance"]
+    async fn test_redis_semantic_cache_evicts_and_searches() {
+        let cache = RedisSemanticCache::new(
+            RedisSemanticCacheConfig::new("redis://127.0.0.1:6379")
+                .with_namespace("test:semantic")
+                .with_dimensions(2)
+                .with_max_entries(2),
+        )
+        .await
+        .unwrap();
+        cache.clear().await.unwrap();
+
+        for (id, embedding) in [("a", [1.0, 0.0]), ("b", [0.0, 1.0]), ("c", [0.7, 0.7])] {
+            let entry = CachedEntry::new(id, embedding.to_vec(), id, "\"v\"", Duration::from_secs(60));
+            cache.store(entry).await.unwrap();
+        }
+
+        let stats = cache.stats().await.unwrap();
+        assert_eq!(stats.total_entries, 2);
+        assert_eq!(stats.evictions, 1);
+
+        let hit = cache
+            .find_similar(&[0.0, 1.0], &SemanticSearchParams::new(0.9))
+            .await
+            .unwrap();
+        assert_eq!(hit.map(|r| r.entry.id().to_string()), Some("b".to_string()));
+
+        cache.clear().await.unwrap();
+    }
+}
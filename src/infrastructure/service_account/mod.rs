@@ -0,0 +1,10 @@
+//! Service account infrastructure implementations
+
+mod repository;
+mod service;
+
+pub use repository::StorageServiceAccountRepository;
+pub use service::{
+    CreateServiceAccountRequest, ServiceAccountService, ServiceAccountWithSecret,
+    UpdateServiceAccountRequest,
+};
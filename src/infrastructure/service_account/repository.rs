@@ -0,0 +1,124 @@
+//! Storage-backed service account repository implementation
+
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::service_account::{ServiceAccount, ServiceAccountId, ServiceAccountRepository};
+use crate::domain::storage::Storage;
+use crate::domain::DomainError;
+
+/// Storage-backed implementation of ServiceAccountRepository
+#[derive(Debug)]
+pub struct StorageServiceAccountRepository {
+    storage: Arc<dyn Storage<ServiceAccount>>,
+}
+
+impl StorageServiceAccountRepository {
+    /// Create a new storage-backed repository
+    pub fn new(storage: Arc<dyn Storage<ServiceAccount>>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl ServiceAccountRepository for StorageServiceAccountRepository {
+    async fn get(&self, id: &ServiceAccountId) -> Result<Option<ServiceAccount>, DomainError> {
+        self.storage.get(id).await
+    }
+
+    async fn create(&self, account: ServiceAccount) -> Result<ServiceAccount, DomainError> {
+        if self.storage.exists(account.id()).await? {
+            return Err(DomainError::conflict(format!(
+                "Service account '{}' already exists",
+                account.id()
+            )));
+        }
+
+        self.storage.create(account).await
+    }
+
+    async fn update(&self, account: ServiceAccount) -> Result<ServiceAccount, DomainError> {
+        if !self.storage.exists(account.id()).await? {
+            return Err(DomainError::not_found(format!(
+                "Service account '{}' not found",
+                account.id()
+            )));
+        }
+
+        self.storage.update(account).await
+    }
+
+    async fn delete(&self, id: &ServiceAccountId) -> Result<bool, DomainError> {
+        self.storage.delete(id).await
+    }
+
+    async fn list(&self) -> Result<Vec<ServiceAccount>, DomainError> {
+        let mut accounts = self.storage.list().await?;
+        accounts.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(accounts)
+    }
+
+    async fn exists(&self, id: &ServiceAccountId) -> Result<bool, DomainError> {
+        self.storage.exists(id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::role::{Permission, PermissionResource};
+    use crate::domain::team::TeamId;
+    use crate::infrastructure::storage::InMemoryStorage;
+
+    fn create_repo() -> StorageServiceAccountRepository {
+        StorageServiceAccountRepository::new(Arc::new(InMemoryStorage::<ServiceAccount>::new()))
+    }
+
+    fn create_account(id: &str, name: &str) -> ServiceAccount {
+        ServiceAccount::new(
+            ServiceAccountId::new(id).unwrap(),
+            name,
+            "sha256$hash",
+            vec![Permission::write(PermissionResource::Prompts)],
+            TeamId::administrators(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_and_get() {
+        let repo = create_repo();
+        let account = create_account("ci", "CI");
+
+        repo.create(account.clone()).await.unwrap();
+
+        let fetched = repo.get(account.id()).await.unwrap().unwrap();
+        assert_eq!(fetched.name(), "CI");
+        assert!(repo.create(account).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_missing() {
+        let repo = create_repo();
+        let result = repo.update(create_account("ci", "CI")).await;
+
+        assert!(matches!(result, Err(DomainError::NotFound { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_list_sorted_by_name() {
+        let repo = create_repo();
+
+        repo.create(create_account("zeta", "Zeta")).await.unwrap();
+        repo.create(create_account("deploy", "Deploy")).await.unwrap();
+
+        let names: Vec<_> = repo
+            .list()
+            .await
+            .unwrap()
+            .iter()
+            .map(|a| a.name().to_string())
+            .collect();
+        assert_eq!(names, vec!["Deploy", "Zeta"]);
+    }
+}
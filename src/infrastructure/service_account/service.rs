@@ -0,0 +1,324 @@
+//! Service account service for machine identity management
+
+use std::sync::Arc;
+
+use tracing::{info, warn};
+
+use crate::domain::role::Permission;
+use crate::domain::service_account::{
+    ServiceAccount, ServiceAccountId, ServiceAccountRepository, ServiceAccountStatus,
+};
+use crate::domain::team::TeamId;
+use crate::domain::DomainError;
+use crate::infrastructure::api_key::ApiKeyGenerator;
+
+/// Prefix of generated client secrets
+const CLIENT_SECRET_PREFIX: &str = "sas_";
+
+/// Request for creating a new service account
+#[derive(Debug, Clone)]
+pub struct CreateServiceAccountRequest {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub scopes: Vec<Permission>,
+    pub team_id: TeamId,
+}
+
+/// Request for updating a service account
+#[derive(Debug, Clone, Default)]
+pub struct UpdateServiceAccountRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub scopes: Option<Vec<Permission>>,
+    pub status: Option<ServiceAccountStatus>,
+}
+
+/// A service account together with its plaintext client secret. The secret
+/// is only available when the account is created or its secret rotated.
+#[derive(Debug, Clone)]
+pub struct ServiceAccountWithSecret {
+    pub account: ServiceAccount,
+    pub client_secret: String,
+}
+
+/// Service account service for managing machine identities and verifying
+/// their client credentials
+#[derive(Debug)]
+pub struct ServiceAccountService<R: ServiceAccountRepository> {
+    repository: Arc<R>,
+    generator: ApiKeyGenerator,
+}
+
+impl<R: ServiceAccountRepository> ServiceAccountService<R> {
+    /// Create a new service account service
+    pub fn new(repository: Arc<R>) -> Self {
+        Self {
+            repository,
+            generator: ApiKeyGenerator::new(CLIENT_SECRET_PREFIX),
+        }
+    }
+
+    /// Create a new service account with a freshly generated client secret
+    pub async fn create(
+        &self,
+        request: CreateServiceAccountRequest,
+    ) -> Result<ServiceAccountWithSecret, DomainError> {
+        info!(id = %request.id, name = %request.name, "Creating service account");
+
+        let id = parse_service_account_id(&request.id)?;
+
+        if self.repository.exists(&id).await? {
+            return Err(DomainError::conflict(format!(
+                "Service account '{}' already exists",
+                request.id
+            )));
+        }
+
+        let secret = self.generator.generate();
+        let mut account = ServiceAccount::new(
+            id,
+            &request.name,
+            secret.hash,
+            request.scopes,
+            request.team_id,
+        )
+        .map_err(|e| DomainError::validation(e.to_string()))?;
+
+        if let Some(desc) = request.description {
+            account = account.with_description(desc);
+        }
+
+        let account = self.repository.create(account).await?;
+
+        Ok(ServiceAccountWithSecret {
+            account,
+            client_secret: secret.key,
+        })
+    }
+
+    /// Get a service account by ID
+    pub async fn get(&self, id: &str) -> Result<Option<ServiceAccount>, DomainError> {
+        self.repository.get(&parse_service_account_id(id)?).await
+    }
+
+    /// List all service accounts
+    pub async fn list(&self) -> Result<Vec<ServiceAccount>, DomainError> {
+        self.repository.list().await
+    }
+
+    /// Update a service account
+    pub async fn update(
+        &self,
+        id: &str,
+        request: UpdateServiceAccountRequest,
+    ) -> Result<ServiceAccount, DomainError> {
+        info!(id = %id, "Updating service account");
+
+        let mut account = self.get_existing(id).await?;
+
+        if let Some(name) = request.name {
+            account
+                .set_name(name)
+                .map_err(|e| DomainError::validation(e.to_string()))?;
+        }
+
+        if let Some(desc) = request.description {
+            account.set_description(Some(desc));
+        }
+
+        if let Some(scopes) = request.scopes {
+            account
+                .set_scopes(scopes)
+                .map_err(|e| DomainError::validation(e.to_string()))?;
+        }
+
+        if let Some(status) = request.status {
+            account.set_status(status);
+        }
+
+        self.repository.update(account).await
+    }
+
+    /// Delete a service account
+    pub async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        info!(id = %id, "Deleting service account");
+
+        self.repository.delete(&parse_service_account_id(id)?).await
+    }
+
+    /// Replace the client secret. The previous secret stops working
+    /// immediately; tokens already issued stay valid until they expire.
+    pub async fn rotate_secret(&self, id: &str) -> Result<ServiceAccountWithSecret, DomainError> {
+        info!(id = %id, "Rotating service account secret");
+
+        let mut account = self.get_existing(id).await?;
+        let secret = self.generator.generate();
+        account.set_secret_hash(secret.hash);
+
+        let account = self.repository.update(account).await?;
+
+        Ok(ServiceAccountWithSecret {
+            account,
+            client_secret: secret.key,
+        })
+    }
+
+    /// Verify client credentials. Returns the service account if the secret
+    /// matches and the account is active, recording the use.
+    pub async fn authenticate(
+        &self,
+        client_id: &str,
+        client_secret: &str,
+    ) -> Result<Option<ServiceAccount>, DomainError> {
+        let Ok(id) = ServiceAccountId::new(client_id) else {
+            return Ok(None);
+        };
+
+        let Some(mut account) = self.repository.get(&id).await? else {
+            return Ok(None);
+        };
+
+        if !self
+            .generator
+            .verify_key(client_secret, account.secret_hash())
+        {
+            warn!(client_id = %client_id, "Invalid service account secret");
+            return Ok(None);
+        }
+
+        if !account.is_active() {
+            warn!(client_id = %client_id, "Disabled service account requested a token");
+            return Ok(None);
+        }
+
+        account.record_use();
+        self.repository.update(account).await.map(Some)
+    }
+
+    async fn get_existing(&self, id: &str) -> Result<ServiceAccount, DomainError> {
+        self.get(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("Service account '{}' not found", id)))
+    }
+}
+
+fn parse_service_account_id(id: &str) -> Result<ServiceAccountId, DomainError> {
+    ServiceAccountId::new(id).map_err(|e| DomainError::invalid_id(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::role::PermissionResource;
+    use crate::infrastructure::service_account::StorageServiceAccountRepository;
+    use crate::infrastructure::storage::InMemoryStorage;
+
+    fn create_service() -> ServiceAccountService<StorageServiceAccountRepository> {
+        ServiceAccountService::new(Arc::new(StorageServiceAccountRepository::new(Arc::new(
+            InMemoryStorage::<ServiceAccount>::new(),
+        ))))
+    }
+
+    fn create_request(id: &str) -> CreateServiceAccountRequest {
+        CreateServiceAccountRequest {
+            id: id.to_string(),
+            name: "CI pipeline".to_string(),
+            description: None,
+            scopes: vec![
+                Permission::write(PermissionResource::Prompts),
+                Permission::write(PermissionResource::Workflows),
+            ],
+            team_id: TeamId::administrators(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_and_authenticate() {
+        let service = create_service();
+
+        let created = service.create(create_request("ci")).await.unwrap();
+        assert!(created.client_secret.starts_with(CLIENT_SECRET_PREFIX));
+        assert!(service.create(create_request("ci")).await.is_err());
+
+        let account = service
+            .authenticate("ci", &created.client_secret)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(account.last_used_at().is_some());
+
+        assert!(service.authenticate("ci", "sas_wrong").await.unwrap().is_none());
+        assert!(service
+            .authenticate("unknown", &created.client_secret)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(service
+            .authenticate("not valid!", "secret")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_disabled_account_cannot_authenticate() {
+        let service = create_service();
+        let created = service.create(create_request("ci")).await.unwrap();
+
+        service
+            .update(
+                "ci",
+                UpdateServiceAccountRequest {
+                    status: Some(ServiceAccountStatus::Disabled),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        assert!(service
+            .authenticate("ci", &created.client_secret)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rotate_secret() {
+        let service = create_service();
+        let created = service.create(create_request("ci")).await.unwrap();
+
+        let rotated = service.rotate_secret("ci").await.unwrap();
+        assert_ne!(rotated.client_secret, created.client_secret);
+
+        assert!(service
+            .authenticate("ci", &created.client_secret)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(service
+            .authenticate("ci", &rotated.client_secret)
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_update_rejects_empty_scopes() {
+        let service = create_service();
+        service.create(create_request("ci")).await.unwrap();
+
+        let result = service
+            .update(
+                "ci",
+                UpdateServiceAccountRequest {
+                    scopes: Some(vec![]),
+                    ..Default::default()
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Err(DomainError::Validation { .. })));
+    }
+}
@@ -1,11 +1,15 @@
 //! Execution log service - Records and queries execution history
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use tokio::sync::broadcast;
+
+use crate::domain::guardrail::InjectionDetection;
 use crate::domain::{
     ConfigRepository, DomainError, ExecutionLog, ExecutionLogId, ExecutionLogQuery,
     ExecutionLogRepository, ExecutionStats, ExecutionStatus, ExecutionType, Executor,
-    ExecutionTokenUsage, WorkflowStepLog,
+    ExecutionTokenUsage, ShadowComparison, StreamingMetrics, WorkflowStepLog,
 };
 
 /// Parameters for recording an execution
@@ -26,6 +30,18 @@ pub struct RecordExecutionParams {
     pub is_async: bool,
     /// Workflow step logs (only for workflow executions)
     pub workflow_steps: Option<Vec<WorkflowStepLog>>,
+    /// Prompt-injection detection on the request's messages
+    pub injection: Option<InjectionDetection>,
+    /// Time to first token, token rate and abort of a streamed response
+    pub streaming: Option<StreamingMetrics>,
+    /// Comparison with the request a shadow request mirrored
+    pub shadow: Option<ShadowComparison>,
+    /// Zero-retention mode: record metadata only, never the payloads
+    pub no_log: bool,
+    /// Cost attribution tags of the request
+    pub tags: HashMap<String, String>,
+    /// ID of the v1 request
+    pub request_id: Option<String>,
 }
 
 impl RecordExecutionParams {
@@ -49,6 +65,12 @@ impl RecordExecutionParams {
             token_usage: None,
             is_async: false,
             workflow_steps: None,
+            injection: None,
+            streaming: None,
+            shadow: None,
+            no_log: false,
+            tags: HashMap::new(),
+            request_id: None,
         }
     }
 
@@ -73,6 +95,12 @@ impl RecordExecutionParams {
             token_usage: None,
             is_async: false,
             workflow_steps: None,
+            injection: None,
+            streaming: None,
+            shadow: None,
+            no_log: false,
+            tags: HashMap::new(),
+            request_id: None,
         }
     }
 
@@ -96,6 +124,12 @@ impl RecordExecutionParams {
             token_usage: None,
             is_async: false,
             workflow_steps: None,
+            injection: None,
+            streaming: None,
+            shadow: None,
+            no_log: false,
+            tags: HashMap::new(),
+            request_id: None,
         }
     }
 
@@ -120,6 +154,55 @@ impl RecordExecutionParams {
             token_usage: None,
             is_async: false,
             workflow_steps: None,
+            injection: None,
+            streaming: None,
+            shadow: None,
+            no_log: false,
+            tags: HashMap::new(),
+            request_id: None,
+        }
+    }
+
+    /// Create params for a successful chat completion
+    pub fn chat_completion_success(
+        model_id: impl Into<String>,
+        execution_time_ms: u64,
+        executor: Executor,
+    ) -> Self {
+        Self {
+            execution_type: ExecutionType::ChatCompletion,
+            ..Self::model_success(model_id, execution_time_ms, executor)
+        }
+    }
+
+    /// Create params for a failed chat completion
+    pub fn chat_completion_failed(
+        model_id: impl Into<String>,
+        error: impl Into<String>,
+        execution_time_ms: u64,
+        executor: Executor,
+    ) -> Self {
+        Self {
+            execution_type: ExecutionType::ChatCompletion,
+            ..Self::model_failed(model_id, error, execution_time_ms, executor)
+        }
+    }
+
+    /// Create params for a chat completion mirrored to a shadow model
+    pub fn shadow(
+        model_id: impl Into<String>,
+        result: Result<(), String>,
+        execution_time_ms: u64,
+        executor: Executor,
+    ) -> Self {
+        let params = match result {
+            Ok(()) => Self::model_success(model_id, execution_time_ms, executor),
+            Err(error) => Self::model_failed(model_id, error, execution_time_ms, executor),
+        };
+
+        Self {
+            execution_type: ExecutionType::Shadow,
+            ..params
         }
     }
 
@@ -139,6 +222,12 @@ impl RecordExecutionParams {
             token_usage: None,
             is_async: true,
             workflow_steps: None,
+            injection: None,
+            streaming: None,
+            shadow: None,
+            no_log: false,
+            tags: HashMap::new(),
+            request_id: None,
         }
     }
 
@@ -162,6 +251,12 @@ impl RecordExecutionParams {
             token_usage: None,
             is_async: true,
             workflow_steps: None,
+            injection: None,
+            streaming: None,
+            shadow: None,
+            no_log: false,
+            tags: HashMap::new(),
+            request_id: None,
         }
     }
 
@@ -186,6 +281,12 @@ impl RecordExecutionParams {
             token_usage: None,
             is_async: true,
             workflow_steps: None,
+            injection: None,
+            streaming: None,
+            shadow: None,
+            no_log: false,
+            tags: HashMap::new(),
+            request_id: None,
         }
     }
 
@@ -223,12 +324,46 @@ impl RecordExecutionParams {
         self.workflow_steps = Some(steps);
         self
     }
+
+    pub fn with_injection(mut self, injection: InjectionDetection) -> Self {
+        self.injection = Some(injection);
+        self
+    }
+
+    pub fn with_streaming(mut self, streaming: StreamingMetrics) -> Self {
+        self.streaming = Some(streaming);
+        self
+    }
+
+    pub fn with_shadow(mut self, shadow: ShadowComparison) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+
+    pub fn with_no_log(mut self, no_log: bool) -> Self {
+        self.no_log = no_log;
+        self
+    }
+
+    pub fn with_tags(mut self, tags: HashMap<String, String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
 }
 
+/// Logs buffered per live subscriber before it starts skipping
+const LIVE_LOG_CAPACITY: usize = 256;
+
 /// Execution log service for recording and querying execution history
 pub struct ExecutionLogService {
     repository: Arc<dyn ExecutionLogRepository>,
     config_repository: Arc<dyn ConfigRepository>,
+    live: broadcast::Sender<ExecutionLog>,
 }
 
 impl ExecutionLogService {
@@ -240,7 +375,24 @@ impl ExecutionLogService {
         Self {
             repository,
             config_repository,
+            live: broadcast::channel(LIVE_LOG_CAPACITY).0,
+        }
+    }
+
+    /// Receive every log recorded or updated from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<ExecutionLog> {
+        self.live.subscribe()
+    }
+
+    /// Save a log and hand it to live subscribers
+    async fn save(&self, log: &ExecutionLog) -> Result<(), DomainError> {
+        self.repository.save(log).await?;
+
+        if self.live.receiver_count() > 0 {
+            let _ = self.live.send(log.clone());
         }
+
+        Ok(())
     }
 
     /// Record an execution (if logging is enabled for this resource)
@@ -253,62 +405,46 @@ impl ExecutionLogService {
                 config.should_log_model(&params.resource_id)
             }
             ExecutionType::Workflow => config.should_log_workflow(&params.resource_id),
-            // Always log ingestion operations
-            ExecutionType::Ingestion => true,
+            // Always log ingestion operations, and shadow requests, whose
+            // logs are their only outcome
+            ExecutionType::Ingestion | ExecutionType::Shadow => true,
         };
 
         if !should_log {
             return Ok(None);
         }
 
-        // Create the execution log
-        let mut log = ExecutionLog::new(
-            params.execution_type,
-            params.resource_id,
-            params.status,
-            params.execution_time_ms,
-            params.executor,
-        );
-
-        if let Some(name) = params.resource_name {
-            log = log.with_resource_name(name);
-        }
-
-        // Only log input/output if sensitive data logging is enabled
-        if config.log_sensitive_data() {
-            if let Some(input) = params.input {
-                log = log.with_input(input);
-            }
+        // Only log input/output if sensitive data logging is enabled and the
+        // caller is not in zero-retention mode
+        let include_payloads = config.log_sensitive_data() && !params.no_log;
+        let log = build_log(params, include_payloads);
 
-            if let Some(output) = params.output {
-                log = log.with_output(output);
-            }
-        }
-
-        if let Some(error) = params.error {
-            log = log.with_error(error);
-        }
-
-        if let Some(cost) = params.cost_micros {
-            log = log.with_cost(cost);
-        }
+        // Save the log
+        self.save(&log).await?;
 
-        if let Some(usage) = params.token_usage {
-            log = log.with_token_usage(usage);
-        }
+        Ok(Some(log))
+    }
 
-        // Set async flag
-        log = log.with_async(params.is_async);
+    /// Record an execution with its input/output when the team opted in to
+    /// payload capture and the request falls in the sampled percentage.
+    /// Requests flagged by the injection guard are always recorded, with
+    /// their payloads only when sampled. Sampled zero-retention requests are
+    /// recorded without payloads. Payloads must already be redacted.
+    pub async fn capture_payload(
+        &self,
+        team_id: &str,
+        params: RecordExecutionParams,
+    ) -> Result<Option<ExecutionLog>, DomainError> {
+        let config = self.config_repository.get().await?;
+        let sampled = config.should_capture_payload(team_id, rand::random::<f64>());
 
-        // Add workflow steps if present (and sensitive data logging is enabled)
-        if config.log_sensitive_data() {
-            if let Some(steps) = params.workflow_steps {
-                log = log.with_workflow_steps(steps);
-            }
+        if !sampled && params.injection.is_none() {
+            return Ok(None);
         }
 
-        // Save the log
-        self.repository.save(&log).await?;
+        let include_payloads = sampled && !params.no_log;
+        let log = build_log(params, include_payloads).with_payload_captured(include_payloads);
+        self.save(&log).await?;
 
         Ok(Some(log))
     }
@@ -356,7 +492,7 @@ impl ExecutionLogService {
 
     /// Update an existing execution log (used for async operations)
     pub async fn update(&self, log: &ExecutionLog) -> Result<(), DomainError> {
-        self.repository.save(log).await
+        self.save(log).await
     }
 
     /// Record a pending ingestion and return the log
@@ -379,6 +515,66 @@ impl ExecutionLogService {
     }
 }
 
+/// Create the execution log of recorded params, keeping input/output and
+/// workflow steps only when `include_payloads` is set
+fn build_log(params: RecordExecutionParams, include_payloads: bool) -> ExecutionLog {
+    let mut log = ExecutionLog::new(
+        params.execution_type,
+        params.resource_id,
+        params.status,
+        params.execution_time_ms,
+        params.executor,
+    );
+
+    if let Some(name) = params.resource_name {
+        log = log.with_resource_name(name);
+    }
+
+    if include_payloads {
+        if let Some(input) = params.input {
+            log = log.with_input(input);
+        }
+
+        if let Some(output) = params.output {
+            log = log.with_output(output);
+        }
+
+        if let Some(steps) = params.workflow_steps {
+            log = log.with_workflow_steps(steps);
+        }
+    }
+
+    if let Some(error) = params.error {
+        log = log.with_error(error);
+    }
+
+    if let Some(cost) = params.cost_micros {
+        log = log.with_cost(cost);
+    }
+
+    if let Some(usage) = params.token_usage {
+        log = log.with_token_usage(usage);
+    }
+
+    if let Some(injection) = params.injection {
+        log = log.with_injection(injection);
+    }
+
+    if let Some(streaming) = params.streaming {
+        log = log.with_streaming(streaming);
+    }
+
+    if let Some(shadow) = params.shadow {
+        log = log.with_shadow(shadow);
+    }
+
+    if let Some(request_id) = params.request_id {
+        log = log.with_request_id(request_id);
+    }
+
+    log.with_tags(params.tags).with_async(params.is_async)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -409,6 +605,28 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[tokio::test]
+    async fn test_record_shadow_when_disabled() {
+        let (service, _config) = create_service();
+        let comparison = ShadowComparison {
+            primary_model_id: "gpt-4".to_string(),
+            primary_latency_ms: 300,
+            judge_model_id: None,
+            verdict: None,
+            judge_error: None,
+        };
+
+        let params =
+            RecordExecutionParams::shadow("gpt-4o", Ok(()), 200, Executor::anonymous())
+                .with_shadow(comparison.clone());
+        let log = service.record(params).await.unwrap().unwrap();
+
+        // Shadow requests are logged although logging is disabled
+        assert_eq!(log.execution_type(), ExecutionType::Shadow);
+        assert_eq!(log.status(), ExecutionStatus::Success);
+        assert_eq!(log.shadow(), Some(&comparison));
+    }
+
     #[tokio::test]
     async fn test_record_when_enabled() {
         let (service, config_repo) = create_service();
@@ -427,6 +645,23 @@ mod tests {
         assert_eq!(log.status(), ExecutionStatus::Success);
     }
 
+    #[tokio::test]
+    async fn test_subscribe_receives_recorded_logs() {
+        let (service, config_repo) = create_service();
+        let key = crate::domain::ConfigKey::new("persistence.enabled").unwrap();
+        config_repo.set(&key, ConfigValue::Boolean(true)).await.unwrap();
+
+        let mut live = service.subscribe();
+
+        let params = RecordExecutionParams::model_success("gpt-4", 100, Executor::anonymous());
+        let log = service.record(params).await.unwrap().unwrap();
+        service.update(&log).await.unwrap();
+
+        assert_eq!(live.recv().await.unwrap().id(), log.id());
+        assert_eq!(live.recv().await.unwrap().id(), log.id());
+        assert!(live.try_recv().is_err());
+    }
+
     #[tokio::test]
     async fn test_record_respects_model_filter() {
         let (service, config_repo) = create_service();
@@ -479,6 +714,130 @@ mod tests {
         let result = service.record(params).await.unwrap().unwrap();
         assert!(result.input().is_some()); // Logged
         assert!(result.output().is_some()); // Logged
+
+        // Zero-retention callers never have payloads logged
+        let params = RecordExecutionParams::model_success("gpt-4", 100, Executor::anonymous())
+            .with_input(serde_json::json!({"prompt": "secret"}))
+            .with_output(serde_json::json!({"response": "answer"}))
+            .with_no_log(true);
+
+        let result = service.record(params).await.unwrap().unwrap();
+        assert!(result.input().is_none());
+        assert!(result.output().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_capture_payload() {
+        let (service, config_repo) = create_service();
+
+        let params = || {
+            RecordExecutionParams::chat_completion_success(
+                "gpt-4",
+                100,
+                Executor::from_api_key("key-1").with_team("team-a"),
+            )
+            .with_input(serde_json::json!({"messages": [{"role": "user", "content": "hi"}]}))
+            .with_output(serde_json::json!({"choices": []}))
+        };
+
+        // Nothing is captured by default
+        assert!(service.capture_payload("team-a", params()).await.unwrap().is_none());
+
+        let key = crate::domain::ConfigKey::new("persistence.payload_capture_percent").unwrap();
+        config_repo.set(&key, ConfigValue::Float(100.0)).await.unwrap();
+        let key = crate::domain::ConfigKey::new("persistence.payload_capture_teams").unwrap();
+        config_repo
+            .set(&key, ConfigValue::StringList(vec!["team-a".to_string()]))
+            .await
+            .unwrap();
+
+        assert!(service.capture_payload("team-b", params()).await.unwrap().is_none());
+
+        // Captured payloads are kept even though persistence is disabled
+        let log = service.capture_payload("team-a", params()).await.unwrap().unwrap();
+        assert!(log.payload_captured());
+        assert!(log.input().is_some());
+        assert!(log.output().is_some());
+
+        let captured = service
+            .list(
+                &ExecutionLogQuery::new()
+                    .with_team_id("team-a")
+                    .with_payload_captured(true),
+            )
+            .await
+            .unwrap();
+        assert_eq!(captured.len(), 1);
+        assert_eq!(captured[0].execution_type(), ExecutionType::ChatCompletion);
+    }
+
+    #[tokio::test]
+    async fn test_capture_payload_no_log() {
+        let (service, config_repo) = create_service();
+
+        let key = crate::domain::ConfigKey::new("persistence.payload_capture_percent").unwrap();
+        config_repo.set(&key, ConfigValue::Float(100.0)).await.unwrap();
+        let key = crate::domain::ConfigKey::new("persistence.payload_capture_teams").unwrap();
+        config_repo
+            .set(&key, ConfigValue::StringList(vec!["team-a".to_string()]))
+            .await
+            .unwrap();
+
+        let params = RecordExecutionParams::chat_completion_success(
+            "gpt-4",
+            100,
+            Executor::from_api_key("key-1").with_team("team-a"),
+        )
+        .with_input(serde_json::json!({"messages": [{"role": "user", "content": "hi"}]}))
+        .with_output(serde_json::json!({"choices": []}))
+        .with_streaming(StreamingMetrics::new(
+            Some(std::time::Duration::from_millis(40)),
+            std::time::Duration::from_millis(100),
+            12,
+            6,
+        ))
+        .with_no_log(true);
+
+        // Sampled, but only the metadata is kept
+        let log = service.capture_payload("team-a", params).await.unwrap().unwrap();
+        assert!(!log.payload_captured());
+        assert!(log.input().is_none());
+        assert!(log.output().is_none());
+        assert_eq!(log.execution_time_ms(), 100);
+        assert_eq!(log.streaming().unwrap().time_to_first_token_ms, Some(40));
+    }
+
+    #[tokio::test]
+    async fn test_capture_payload_records_injection_detections() {
+        use crate::domain::guardrail::{InjectionAction, InjectionDetection};
+
+        let (service, _) = create_service();
+        let detection = InjectionDetection {
+            action: InjectionAction::Flag,
+            score: 0.8,
+            heuristic_score: 0.8,
+            classifier_score: None,
+            rules: vec!["ignore_instructions".to_string()],
+        };
+        let params = RecordExecutionParams::chat_completion_success(
+            "gpt-4",
+            100,
+            Executor::from_api_key("key-1").with_team("team-a"),
+        )
+        .with_input(serde_json::json!({"messages": []}))
+        .with_injection(detection.clone());
+
+        // Recorded without payloads although the team is not sampled
+        let log = service.capture_payload("team-a", params).await.unwrap().unwrap();
+        assert!(!log.payload_captured());
+        assert!(log.input().is_none());
+        assert_eq!(log.injection(), Some(&detection));
+
+        let flagged = service
+            .list(&ExecutionLogQuery::new().with_injection_detected(true))
+            .await
+            .unwrap();
+        assert_eq!(flagged.len(), 1);
     }
 
     #[tokio::test]
@@ -5,18 +5,24 @@
 
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use crate::api::state::{DatasetServiceTrait, ExperimentServiceTrait};
+use crate::domain::dataset::{DatasetRef, DatasetSelector};
 use crate::domain::experiment::{
-    AssignmentResult, ConfigOverrides, Experiment, ExperimentId, ExperimentQuery,
-    ExperimentRecord, ExperimentRecordQuery, ExperimentRecordRepository, ExperimentRepository,
-    ExperimentResult, ExperimentStatus, TrafficAllocation, Variant, VariantConfig, VariantId,
-    VariantMetrics,
+    quality_samples, AssignmentKey, AssignmentResult, AutoStop, ConfigOverrides, Experiment,
+    ExperimentId, ExperimentQuery, ExperimentRecord, ExperimentRecordId, ExperimentRecordQuery,
+    ExperimentRecordRepository, ExperimentRepository, ExperimentResult, ExperimentStatus,
+    FeedbackEvent, RolloutGuard, SequentialDecision, TrafficAllocation, Variant, VariantConfig,
+    VariantId, VariantMetrics, WorkflowAssignment, QUALITY_METRICS,
 };
-use crate::domain::DomainError;
-use crate::infrastructure::experiment::{calculate_significance, ConsistentHasher};
+use crate::domain::{DomainError, WebhookEvent, WebhookEventType};
+use crate::infrastructure::experiment::{calculate_significance, sequential_test, ConsistentHasher};
+use crate::infrastructure::leader::Leadership;
+use crate::infrastructure::webhook::WebhookServiceTrait;
 
 // ============================================================================
 // Request Types
@@ -30,6 +36,10 @@ pub struct CreateExperimentRequest {
     pub description: Option<String>,
     pub variants: Vec<CreateVariantRequest>,
     pub traffic_allocation: Vec<(String, u8)>,
+    pub assignment_key: AssignmentKey,
+    pub auto_stop: Option<AutoStop>,
+    pub rollout: Option<RolloutGuard>,
+    pub dataset: Option<DatasetSelector>,
     pub enabled: bool,
 }
 
@@ -50,12 +60,19 @@ pub struct UpdateExperimentRequest {
     pub description: Option<Option<String>>,
     pub variants: Option<Vec<CreateVariantRequest>>,
     pub traffic_allocation: Option<Vec<(String, u8)>>,
+    pub assignment_key: Option<AssignmentKey>,
+    pub auto_stop: Option<Option<AutoStop>>,
+    pub rollout: Option<Option<RolloutGuard>>,
+    pub dataset: Option<Option<DatasetSelector>>,
     pub enabled: Option<bool>,
 }
 
 /// Parameters for recording an experiment result
 #[derive(Debug, Clone)]
 pub struct RecordExperimentParams {
+    /// Record ID, e.g. the completion ID so feedback can refer to it;
+    /// generated when unset
+    pub record_id: Option<String>,
     pub experiment_id: String,
     pub variant_id: String,
     pub api_key_id: String,
@@ -73,10 +90,22 @@ pub struct RecordExperimentParams {
 // ============================================================================
 
 /// Service for managing A/B testing experiments
-#[derive(Debug)]
 pub struct ExperimentService<R: ExperimentRepository, RR: ExperimentRecordRepository> {
     repository: Arc<R>,
     record_repository: Arc<RR>,
+    webhooks: Option<Arc<dyn WebhookServiceTrait>>,
+    datasets: Option<Arc<dyn DatasetServiceTrait>>,
+}
+
+impl<R: ExperimentRepository, RR: ExperimentRecordRepository> std::fmt::Debug
+    for ExperimentService<R, RR>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExperimentService")
+            .field("webhooks", &self.webhooks.is_some())
+            .field("datasets", &self.datasets.is_some())
+            .finish_non_exhaustive()
+    }
 }
 
 impl<R: ExperimentRepository, RR: ExperimentRecordRepository> ExperimentService<R, RR> {
@@ -85,9 +114,24 @@ impl<R: ExperimentRepository, RR: ExperimentRecordRepository> ExperimentService<
         Self {
             repository,
             record_repository,
+            webhooks: None,
+            datasets: None,
         }
     }
 
+    /// Send completed experiments as `experiment_completed` webhook events,
+    /// and rolled back ones as `experiment_rolled_back` (builder pattern)
+    pub fn with_webhooks(mut self, webhooks: Arc<dyn WebhookServiceTrait>) -> Self {
+        self.webhooks = Some(webhooks);
+        self
+    }
+
+    /// Allow experiments to pin an evaluation dataset (builder pattern)
+    pub fn with_datasets(mut self, datasets: Arc<dyn DatasetServiceTrait>) -> Self {
+        self.datasets = Some(datasets);
+        self
+    }
+
     // ========================================================================
     // CRUD Operations
     // ========================================================================
@@ -127,7 +171,21 @@ impl<R: ExperimentRepository, RR: ExperimentRecordRepository> ExperimentService<
             experiment = experiment.with_description(desc);
         }
 
-        experiment = experiment.with_enabled(request.enabled);
+        experiment = experiment
+            .with_assignment_key(request.assignment_key)
+            .with_enabled(request.enabled);
+
+        if let Some(auto_stop) = request.auto_stop {
+            experiment = experiment.with_auto_stop(auto_stop);
+        }
+
+        if let Some(rollout) = request.rollout {
+            experiment = experiment.with_rollout(rollout);
+        }
+
+        if let Some(ref selector) = request.dataset {
+            experiment = experiment.with_dataset(self.resolve_dataset(selector).await?);
+        }
 
         for variant_req in request.variants {
             let variant = self.build_variant(&variant_req)?;
@@ -182,6 +240,38 @@ impl<R: ExperimentRepository, RR: ExperimentRecordRepository> ExperimentService<
             experiment.set_enabled(enabled);
         }
 
+        if let Some(assignment_key) = request.assignment_key {
+            experiment.set_assignment_key(assignment_key);
+        }
+
+        if let Some(auto_stop) = request.auto_stop {
+            if let Some(config) = &auto_stop {
+                config
+                    .validate()
+                    .map_err(|e| DomainError::validation(e.to_string()))?;
+            }
+
+            experiment.set_auto_stop(auto_stop);
+        }
+
+        if let Some(rollout) = request.rollout {
+            if let Some(config) = &rollout {
+                config
+                    .validate()
+                    .map_err(|e| DomainError::validation(e.to_string()))?;
+            }
+
+            experiment.set_rollout(rollout);
+        }
+
+        if let Some(selector) = request.dataset {
+            let dataset = match selector {
+                Some(selector) => Some(self.resolve_dataset(&selector).await?),
+                None => None,
+            };
+            experiment.set_dataset(dataset);
+        }
+
         if let Some(variants) = request.variants {
             let mut new_variants = Vec::new();
 
@@ -329,18 +419,113 @@ impl<R: ExperimentRepository, RR: ExperimentRecordRepository> ExperimentService<
         let updated = self.repository.update(experiment).await?;
         info!(experiment_id = %id, "Experiment completed");
 
+        self.notify_completed(id, "manual").await;
+
         Ok(updated)
     }
 
+    /// Complete every active experiment with auto-stop whose sequential
+    /// tests all reached a decision, returning their results
+    pub async fn evaluate_auto_stop(&self) -> Result<Vec<ExperimentResult>, DomainError> {
+        let query = ExperimentQuery::new().with_status(ExperimentStatus::Active);
+        let experiments = self.repository.list(&query).await?;
+        let mut completed = Vec::new();
+
+        for mut experiment in experiments {
+            if experiment.auto_stop().is_none() {
+                continue;
+            }
+
+            let id = experiment.id().as_str().to_string();
+            let result = self.get_results(&id).await?;
+
+            if !result.sequential_tests_decided() {
+                continue;
+            }
+
+            experiment
+                .complete()
+                .map_err(|e| DomainError::validation(e.to_string()))?;
+            self.repository.update(experiment).await?;
+
+            info!(
+                experiment_id = %id,
+                winner = ?result.winner_variant_id,
+                "Experiment auto-stopped"
+            );
+
+            self.notify_completed(&id, "auto_stop").await;
+            completed.push(self.get_results(&id).await?);
+        }
+
+        Ok(completed)
+    }
+
+    /// Roll back active rollouts whose treatment breached a threshold of
+    /// their guard against the control
+    pub async fn evaluate_rollouts(&self) -> Result<Vec<ExperimentResult>, DomainError> {
+        let query = ExperimentQuery::new().with_status(ExperimentStatus::Active);
+        let experiments = self.repository.list(&query).await?;
+        let mut rolled_back = Vec::new();
+
+        for mut experiment in experiments {
+            let (Some(guard), Some(control)) = (experiment.rollout(), experiment.control_variant())
+            else {
+                continue;
+            };
+            let control_id = control.id().as_str().to_string();
+
+            let id = experiment.id().as_str().to_string();
+            let result = self.get_results(&id).await?;
+
+            let Some(baseline) = result.variant_metrics.iter().find(|m| m.variant_id == control_id)
+            else {
+                continue;
+            };
+            let breach = result
+                .variant_metrics
+                .iter()
+                .filter(|m| m.variant_id != control_id)
+                .find_map(|m| {
+                    guard
+                        .breach(baseline, m)
+                        .map(|reason| (m.variant_id.clone(), reason))
+                });
+
+            let Some((variant_id, reason)) = breach else {
+                continue;
+            };
+
+            experiment
+                .roll_back(&variant_id, &reason)
+                .map_err(|e| DomainError::validation(e.to_string()))?;
+            self.repository.update(experiment).await?;
+
+            warn!(
+                experiment_id = %id,
+                variant_id = %variant_id,
+                reason = %reason,
+                "Rollout rolled back"
+            );
+
+            self.notify_rolled_back(&id).await;
+            rolled_back.push(self.get_results(&id).await?);
+        }
+
+        Ok(rolled_back)
+    }
+
     // ========================================================================
     // Assignment
     // ========================================================================
 
-    /// Assign a variant to a request based on API key and model
+    /// Assign a variant to a request for a model, hashing its API key or end
+    /// user depending on the experiment's assignment key
     pub async fn assign_variant(
         &self,
         model_id: &str,
         api_key_id: &str,
+        user: Option<&str>,
     ) -> Result<Option<AssignmentResult>, DomainError> {
         let experiments = self.repository.find_active_for_model(model_id).await?;
 
@@ -349,25 +534,30 @@ impl<R: ExperimentRepository, RR: ExperimentRecordRepository> ExperimentService<
                 continue;
             }
 
-            let hash = ConsistentHasher::hash_assignment(api_key_id, experiment.id().as_str());
+            let subject = experiment.assignment_key().subject(api_key_id, user);
+            let hash = ConsistentHasher::hash_assignment(&subject, experiment.id().as_str());
 
             if let Some(variant) = experiment.get_variant_for_hash(hash) {
+                let config = variant.config();
+                let Some(model_id) = config.model_id() else {
+                    continue;
+                };
+
                 debug!(
                     experiment_id = %experiment.id(),
                     variant_id = %variant.id(),
-                    api_key_id = %api_key_id,
+                    subject = %subject,
                     hash = hash,
                     "Assigned variant to request"
                 );
 
-                let config = variant.config();
                 let overrides = self.extract_config_overrides(config);
 
                 return Ok(Some(
                     AssignmentResult::new(
                         experiment.id().as_str(),
                         variant.id().as_str(),
-                        config.model_id(),
+                        model_id,
                     )
                     .with_overrides(overrides),
                 ));
@@ -377,13 +567,55 @@ impl<R: ExperimentRepository, RR: ExperimentRecordRepository> ExperimentService<
         Ok(None)
     }
 
+    /// Assign a variant to an execution of a workflow, hashing its API key
+    /// or end user like requests for models
+    pub async fn assign_workflow_variant(
+        &self,
+        workflow_id: &str,
+        api_key_id: &str,
+        user: Option<&str>,
+    ) -> Result<Option<WorkflowAssignment>, DomainError> {
+        let experiments = self.repository.find_active_for_workflow(workflow_id).await?;
+
+        for experiment in experiments {
+            let subject = experiment.assignment_key().subject(api_key_id, user);
+            let hash = ConsistentHasher::hash_assignment(&subject, experiment.id().as_str());
+
+            let Some(variant) = experiment.get_variant_for_hash(hash) else {
+                continue;
+            };
+            let Some(variant_workflow_id) = variant.config().workflow_id() else {
+                continue;
+            };
+
+            debug!(
+                experiment_id = %experiment.id(),
+                variant_id = %variant.id(),
+                workflow_id = %variant_workflow_id,
+                subject = %subject,
+                "Assigned variant to workflow execution"
+            );
+
+            return Ok(Some(WorkflowAssignment {
+                experiment_id: experiment.id().as_str().to_string(),
+                variant_id: variant.id().as_str().to_string(),
+                workflow_id: variant_workflow_id.to_string(),
+                step_overrides: variant.config().step_overrides().to_vec(),
+            }));
+        }
+
+        Ok(None)
+    }
+
     // ========================================================================
     // Recording
     // ========================================================================
 
     /// Record an experiment result
     pub async fn record(&self, params: RecordExperimentParams) -> Result<(), DomainError> {
-        let record_id = format!("expr-{}", Uuid::new_v4());
+        let record_id = params
+            .record_id
+            .unwrap_or_else(|| format!("expr-{}", Uuid::new_v4()));
 
         let mut record = ExperimentRecord::new(
             record_id,
@@ -417,6 +649,38 @@ impl<R: ExperimentRepository, RR: ExperimentRecordRepository> ExperimentService<
         Ok(())
     }
 
+    /// Fold feedback into the record of a completion. Records of other API
+    /// keys are reported as not found.
+    pub async fn record_feedback(
+        &self,
+        record_id: &str,
+        api_key_id: &str,
+        event: FeedbackEvent,
+    ) -> Result<ExperimentRecord, DomainError> {
+        event.validate()?;
+
+        let mut record = self
+            .record_repository
+            .get(&ExperimentRecordId::new(record_id))
+            .await?
+            .filter(|record| record.api_key_id == api_key_id)
+            .ok_or_else(|| {
+                DomainError::not_found(format!("Experiment record '{}' not found", record_id))
+            })?;
+
+        record.feedback.apply(&event);
+        self.record_repository.update(record.clone()).await?;
+
+        debug!(
+            record_id = %record_id,
+            experiment_id = %record.experiment_id,
+            variant_id = %record.variant_id,
+            "Recorded experiment feedback"
+        );
+
+        Ok(record)
+    }
+
     // ========================================================================
     // Results
     // ========================================================================
@@ -437,6 +701,7 @@ impl<R: ExperimentRepository, RR: ExperimentRecordRepository> ExperimentService<
         let records = self.record_repository.query(&query).await?;
 
         let mut result = ExperimentResult::new(id, experiment.name(), experiment.status());
+        result.dataset = experiment.dataset().cloned();
 
         // Calculate duration
         if let (Some(started), completed) = (experiment.started_at(), experiment.completed_at()) {
@@ -514,16 +779,89 @@ impl<R: ExperimentRepository, RR: ExperimentRecordRepository> ExperimentService<
                 ) {
                     result.significance_tests.push(significance);
                 }
+
+                // Quality metrics from user feedback
+                let control_records = variant_records.get(control_id).cloned().unwrap_or_default();
+                let treatment_records =
+                    variant_records.get(treatment_id).cloned().unwrap_or_default();
+
+                for metric in QUALITY_METRICS {
+                    if let Some(significance) = calculate_significance(
+                        &quality_samples(control_records.iter().copied(), metric),
+                        &quality_samples(treatment_records.iter().copied(), metric),
+                        control_id,
+                        treatment_id,
+                        metric,
+                        0.95,
+                    ) {
+                        result.significance_tests.push(significance);
+                    }
+                }
+
+                if let Some(auto_stop) = experiment.auto_stop() {
+                    let test = sequential_test(
+                        &metric_samples(&control_records, &auto_stop.metric),
+                        &metric_samples(&treatment_records, &auto_stop.metric),
+                        control_id,
+                        treatment_id,
+                        auto_stop,
+                    );
+
+                    if let Some(test) = test {
+                        result.sequential_tests.push(test);
+                    }
+                }
             }
         }
 
+        // Sequential tests decide the winner of experiments with auto-stop
+        if let (Some(auto_stop), Some(control)) = (experiment.auto_stop(), control_variant)
+            && experiment.status() == ExperimentStatus::Completed
+            && result.sequential_tests_decided()
+        {
+            apply_sequential_winner(&mut result, auto_stop, control.id().as_str());
+        }
+
+        // A rolled back rollout keeps its baseline
+        if let (Some(rollback), Some(control)) = (experiment.rollback(), control_variant) {
+            result.winner_variant_id = Some(control.id().as_str().to_string());
+            result.recommendation = Some(format!(
+                "Variant '{}' was rolled back: {}",
+                rollback.variant_id, rollback.reason
+            ));
+        }
+
         // Determine winner if completed and significant
-        if experiment.status() == ExperimentStatus::Completed && result.has_significant_result() {
-            // Find the variant with lowest latency that's significant
-            if let Some(best_sig) = result
+        if experiment.status() == ExperimentStatus::Completed
+            && result.winner_variant_id.is_none()
+            && result.recommendation.is_none()
+            && result.has_significant_result()
+        {
+            // Quality reported by users outweighs latency
+            let best_quality = result
+                .significance_tests
+                .iter()
+                .filter(|s| QUALITY_METRICS.contains(&s.metric.as_str()))
+                .filter(|s| s.treatment_is_better_higher())
+                .max_by(|a, b| {
+                    a.relative_change
+                        .partial_cmp(&b.relative_change)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+
+            if let Some(best_sig) = best_quality {
+                result.winner_variant_id = Some(best_sig.treatment_variant_id.clone());
+                result.recommendation = Some(format!(
+                    "Variant '{}' shows significant improvement ({:.1}% higher {})",
+                    best_sig.treatment_variant_id,
+                    best_sig.relative_change.abs(),
+                    best_sig.metric.replace('_', " ")
+                ));
+            } else if let Some(best_sig) = result
                 .significance_tests
                 .iter()
-                .filter(|s| s.is_significant && s.treatment_is_better_lower())
+                // Otherwise the variant with lowest latency that's significant
+                .filter(|s| s.metric == "latency_ms" && s.treatment_is_better_lower())
                 .min_by(|a, b| {
                     a.treatment_mean
                         .partial_cmp(&b.treatment_mean)
@@ -546,14 +884,107 @@ impl<R: ExperimentRepository, RR: ExperimentRecordRepository> ExperimentService<
     // Private Helpers
     // ========================================================================
 
+    /// Send the results of a completed experiment to the webhooks
+    async fn notify_completed(&self, id: &str, reason: &str) {
+        let Some(webhooks) = &self.webhooks else {
+            return;
+        };
+
+        let result = match self.get_results(id).await {
+            Ok(result) => result,
+            Err(e) => {
+                warn!(experiment_id = %id, error = %e, "Failed to get completed experiment results");
+                return;
+            }
+        };
+
+        let event = WebhookEvent::new(
+            WebhookEventType::ExperimentCompleted,
+            serde_json::json!({
+                "experiment_id": result.experiment_id,
+                "experiment_name": result.experiment_name,
+                "subtype": reason,
+                "winner_variant_id": result.winner_variant_id,
+                "recommendation": result.recommendation,
+                "total_requests": result.total_requests,
+                "duration_hours": result.duration_hours,
+                "sequential_tests": result.sequential_tests,
+            }),
+        );
+
+        if let Err(e) = webhooks.send_event(event).await {
+            warn!(experiment_id = %id, error = %e, "Failed to send experiment completed webhook");
+        }
+    }
+
+    /// Alert the webhooks that a rollout was rolled back
+    async fn notify_rolled_back(&self, id: &str) {
+        let Some(webhooks) = &self.webhooks else {
+            return;
+        };
+
+        let experiment = match self.get(id).await {
+            Ok(Some(experiment)) => experiment,
+            Ok(None) => return,
+            Err(e) => {
+                warn!(experiment_id = %id, error = %e, "Failed to get rolled back experiment");
+                return;
+            }
+        };
+        let Some(rollback) = experiment.rollback() else {
+            return;
+        };
+
+        let event = WebhookEvent::new(
+            WebhookEventType::ExperimentRolledBack,
+            serde_json::json!({
+                "experiment_id": id,
+                "experiment_name": experiment.name(),
+                "variant_id": rollback.variant_id,
+                "baseline_variant_id": experiment.control_variant().map(|v| v.id().as_str()),
+                "reason": rollback.reason,
+                "rolled_back_at": rollback.rolled_back_at.to_rfc3339(),
+            }),
+        );
+
+        if let Err(e) = webhooks.send_event(event).await {
+            warn!(experiment_id = %id, error = %e, "Failed to send experiment rolled back webhook");
+        }
+    }
+
     fn parse_id(&self, id: &str) -> Result<ExperimentId, DomainError> {
         ExperimentId::new(id).map_err(|e| DomainError::validation(e.to_string()))
     }
 
+    async fn resolve_dataset(&self, selector: &DatasetSelector) -> Result<DatasetRef, DomainError> {
+        let datasets = self
+            .datasets
+            .as_ref()
+            .ok_or_else(|| DomainError::validation("Datasets are not available"))?;
+
+        datasets.resolve(&selector.dataset_id, selector.version).await
+    }
+
     fn build_variant(&self, request: &CreateVariantRequest) -> Result<Variant, DomainError> {
         let variant_id =
             VariantId::new(&request.id).map_err(|e| DomainError::validation(e.to_string()))?;
 
+        for step_override in request.config.step_overrides() {
+            step_override.validate().map_err(DomainError::validation)?;
+        }
+
+        if let VariantConfig::ConfigOverride {
+            prompt_id: None,
+            prompt_version: Some(_),
+            ..
+        } = &request.config
+        {
+            return Err(DomainError::validation(format!(
+                "Variant '{}' pins a prompt version without a prompt_id",
+                request.id
+            )));
+        }
+
         let mut variant = Variant::new(variant_id, &request.name, request.config.clone());
 
         if let Some(ref desc) = request.description {
@@ -568,6 +999,8 @@ impl<R: ExperimentRepository, RR: ExperimentRecordRepository> ExperimentService<
     fn extract_config_overrides(&self, config: &VariantConfig) -> ConfigOverrides {
         match config {
             VariantConfig::ConfigOverride {
+                prompt_id,
+                prompt_version,
                 temperature,
                 max_tokens,
                 top_p,
@@ -575,6 +1008,8 @@ impl<R: ExperimentRepository, RR: ExperimentRecordRepository> ExperimentService<
                 frequency_penalty,
                 ..
             } => ConfigOverrides {
+                prompt_id: prompt_id.clone(),
+                prompt_version: *prompt_version,
                 temperature: *temperature,
                 max_tokens: *max_tokens,
                 top_p: *top_p,
@@ -595,6 +1030,26 @@ impl<R: ExperimentRepository, RR: ExperimentRecordRepository> ExperimentService<
             ));
         }
 
+        ensure_single_target(request.variants.iter().map(|v| &v.config))?;
+
+        if let Some(auto_stop) = &request.auto_stop {
+            auto_stop
+                .validate()
+                .map_err(|e| DomainError::validation(e.to_string()))?;
+        }
+
+        if let Some(rollout) = &request.rollout {
+            rollout
+                .validate()
+                .map_err(|e| DomainError::validation(e.to_string()))?;
+
+            if !request.variants.iter().any(|v| v.control) {
+                return Err(DomainError::validation(
+                    "Rollouts need a control variant as their baseline",
+                ));
+            }
+        }
+
         let total_percentage: u8 = request.traffic_allocation.iter().map(|(_, p)| *p).sum();
 
         if total_percentage != 100 {
@@ -638,6 +1093,14 @@ impl<R: ExperimentRepository, RR: ExperimentRecordRepository> ExperimentService<
             ));
         }
 
+        ensure_single_target(experiment.variants().iter().map(Variant::config))?;
+
+        if experiment.rollout().is_some() && experiment.control_variant().is_none() {
+            return Err(DomainError::validation(
+                "Rollouts need a control variant as their baseline",
+            ));
+        }
+
         let total_percentage: u8 = experiment
             .traffic_allocation()
             .iter()
@@ -655,10 +1118,117 @@ impl<R: ExperimentRepository, RR: ExperimentRecordRepository> ExperimentService<
     }
 }
 
+/// Reject experiments mixing model and workflow variants, as requests for a
+/// model and executions of a workflow are assigned separately
+fn ensure_single_target<'a>(
+    configs: impl Iterator<Item = &'a VariantConfig>,
+) -> Result<(), DomainError> {
+    let workflows: Vec<bool> = configs.map(|c| c.workflow_id().is_some()).collect();
+
+    if workflows.iter().any(|w| *w) && !workflows.iter().all(|w| *w) {
+        return Err(DomainError::validation(
+            "Variants must either all reference models or all run workflows",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Per-record samples of a metric sequential tests can watch
+fn metric_samples(records: &[&ExperimentRecord], metric: &str) -> Vec<f64> {
+    match metric {
+        "latency_ms" => records.iter().map(|r| r.latency_ms as f64).collect(),
+        "cost_micros" => records.iter().map(|r| r.cost_micros as f64).collect(),
+        _ => quality_samples(records.iter().copied(), metric),
+    }
+}
+
+/// Pick the winner of decided sequential tests: the treatment improving most
+/// on the metric, else the control when a treatment is significantly worse.
+/// Futile tests leave no winner.
+fn apply_sequential_winner(result: &mut ExperimentResult, auto_stop: &AutoStop, control_id: &str) {
+    let improvement = |change: f64| if auto_stop.lower_is_better() { -change } else { change };
+    let significant = result
+        .sequential_tests
+        .iter()
+        .filter(|t| t.decision == SequentialDecision::Significant);
+
+    let best = significant
+        .clone()
+        .filter(|t| improvement(t.relative_change) > 0.0)
+        .max_by(|a, b| {
+            improvement(a.relative_change)
+                .partial_cmp(&improvement(b.relative_change))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+    let metric = auto_stop.metric.replace('_', " ");
+
+    if let Some(best) = best {
+        result.winner_variant_id = Some(best.treatment_variant_id.clone());
+        result.recommendation = Some(format!(
+            "Variant '{}' shows significant improvement ({:.1}% {} {})",
+            best.treatment_variant_id,
+            best.relative_change.abs(),
+            if auto_stop.lower_is_better() { "lower" } else { "higher" },
+            metric
+        ));
+    } else if significant.count() > 0 {
+        result.winner_variant_id = Some(control_id.to_string());
+        result.recommendation = Some(format!(
+            "Control '{}' is significantly better on {}",
+            control_id, metric
+        ));
+    } else {
+        result.recommendation = Some(format!(
+            "No variant differs from control by {}% or more on {}",
+            auto_stop.min_effect_pct, metric
+        ));
+    }
+}
+
+/// Check active experiments with auto-stop or a rollout guard every
+/// `interval`, on the leader replica only
+pub fn spawn_experiment_auto_stop(
+    service: Arc<dyn ExperimentServiceTrait>,
+    interval: Duration,
+    leadership: Leadership,
+) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            if !leadership.is_leader() {
+                continue;
+            }
+
+            // Breached rollouts are rolled back before any could auto-stop
+            match service.evaluate_rollouts().await {
+                Ok(rolled_back) if !rolled_back.is_empty() => {
+                    warn!(count = rolled_back.len(), "Rolled back rollouts");
+                }
+                Ok(_) => {}
+                Err(e) => warn!(error = %e, "Rollout evaluation failed"),
+            }
+
+            match service.evaluate_auto_stop().await {
+                Ok(completed) if !completed.is_empty() => {
+                    info!(count = completed.len(), "Auto-stopped experiments");
+                }
+                Ok(_) => {}
+                Err(e) => warn!(error = %e, "Experiment auto-stop evaluation failed"),
+            }
+        }
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::experiment::{MockExperimentRecordRepository, MockExperimentRepository};
+    use crate::domain::experiment::{
+        MockExperimentRecordRepository, MockExperimentRepository, StepOverride,
+    };
 
     fn create_service(
     ) -> ExperimentService<MockExperimentRepository, MockExperimentRecordRepository> {
@@ -692,6 +1262,10 @@ mod tests {
                 ("control".to_string(), 50),
                 ("treatment".to_string(), 50),
             ],
+            assignment_key: AssignmentKey::ApiKey,
+            auto_stop: None,
+            rollout: None,
+            dataset: None,
             enabled: true,
         }
     }
@@ -871,7 +1445,7 @@ mod tests {
         service.create(request).await.unwrap();
         service.start("test-exp").await.unwrap();
 
-        let assignment = service.assign_variant("gpt-4", "api-key-1").await.unwrap();
+        let assignment = service.assign_variant("gpt-4", "api-key-1", None).await.unwrap();
 
         assert!(assignment.is_some());
         let result = assignment.unwrap();
@@ -887,12 +1461,12 @@ mod tests {
         service.start("test-exp").await.unwrap();
 
         let assignment1 = service
-            .assign_variant("gpt-4", "api-key-1")
+            .assign_variant("gpt-4", "api-key-1", None)
             .await
             .unwrap()
             .unwrap();
         let assignment2 = service
-            .assign_variant("gpt-4", "api-key-1")
+            .assign_variant("gpt-4", "api-key-1", None)
             .await
             .unwrap()
             .unwrap();
@@ -900,6 +1474,59 @@ mod tests {
         assert_eq!(assignment1.variant_id, assignment2.variant_id);
     }
 
+    #[tokio::test]
+    async fn test_assign_variant_by_user() {
+        let service = create_service();
+        let mut request = create_valid_request("test-exp");
+        request.assignment_key = AssignmentKey::User;
+        request.variants[1].config = VariantConfig::ConfigOverride {
+            model_id: "gpt-4".to_string(),
+            prompt_id: Some("support-v2".to_string()),
+            prompt_version: None,
+            temperature: Some(0.2),
+            max_tokens: None,
+            top_p: None,
+            presence_penalty: None,
+            frequency_penalty: None,
+        };
+        service.create(request).await.unwrap();
+        service.start("test-exp").await.unwrap();
+
+        // The same user gets the same variant whatever key it calls with
+        let users: Vec<String> = (0..20).map(|i| format!("user-{}", i)).collect();
+
+        for user in &users {
+            let first = service
+                .assign_variant("gpt-4", "api-key-1", Some(user))
+                .await
+                .unwrap()
+                .unwrap();
+            let second = service
+                .assign_variant("gpt-4", "api-key-2", Some(user))
+                .await
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(first.variant_id, second.variant_id);
+        }
+
+        let treatment_user = users
+            .iter()
+            .find(|user| {
+                ConsistentHasher::hash_assignment(&format!("user:{}", user), "test-exp") >= 50
+            })
+            .unwrap();
+        let assignment = service
+            .assign_variant("gpt-4", "api-key-1", Some(treatment_user))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(assignment.variant_id, "treatment");
+        assert_eq!(assignment.prompt_id(), Some("support-v2"));
+        assert_eq!(assignment.temperature(), Some(0.2));
+    }
+
     #[tokio::test]
     async fn test_assign_variant_no_active_experiment() {
         let service = create_service();
@@ -907,10 +1534,61 @@ mod tests {
         service.create(request).await.unwrap();
 
         // Not started, so no assignment
-        let assignment = service.assign_variant("gpt-4", "api-key-1").await.unwrap();
+        let assignment = service.assign_variant("gpt-4", "api-key-1", None).await.unwrap();
         assert!(assignment.is_none());
     }
 
+    #[tokio::test]
+    async fn test_assign_workflow_variant() {
+        let service = create_service();
+        let mut request = create_valid_request("crag-exp");
+        request.variants[0].config = VariantConfig::workflow("rag");
+        request.variants[1].config = VariantConfig::Workflow {
+            workflow_id: "rag".to_string(),
+            step_overrides: vec![StepOverride::skip("crag-score")],
+        };
+        service.create(request).await.unwrap();
+        service.start("crag-exp").await.unwrap();
+
+        let key = (0..20)
+            .map(|i| format!("api-key-{}", i))
+            .find(|key| {
+                ConsistentHasher::hash_assignment(key, "crag-exp") >= 50
+            })
+            .unwrap();
+        let assignment = service
+            .assign_workflow_variant("rag", &key, None)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(assignment.variant_id, "treatment");
+        assert_eq!(assignment.workflow_id, "rag");
+        assert_eq!(assignment.step_overrides, vec![StepOverride::skip("crag-score")]);
+
+        // Neither other workflows nor models are assigned by it
+        assert!(service.assign_workflow_variant("other", &key, None).await.unwrap().is_none());
+        assert!(service.assign_variant("rag", &key, None).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_create_workflow_experiment_invalid() {
+        let service = create_service();
+
+        let mut mixed = create_valid_request("mixed-exp");
+        mixed.variants[1].config = VariantConfig::workflow("rag");
+        let error = service.create(mixed).await.unwrap_err();
+        assert!(error.to_string().contains("all reference models"));
+
+        let mut unnamed = create_valid_request("unnamed-exp");
+        unnamed.variants[0].config = VariantConfig::workflow("rag");
+        unnamed.variants[1].config = VariantConfig::Workflow {
+            workflow_id: "rag".to_string(),
+            step_overrides: vec![StepOverride::skip("")],
+        };
+        assert!(service.create(unnamed).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_record_experiment() {
         let service = create_service();
@@ -919,6 +1597,7 @@ mod tests {
         service.start("test-exp").await.unwrap();
 
         let params = RecordExperimentParams {
+            record_id: None,
             experiment_id: "test-exp".to_string(),
             variant_id: "control".to_string(),
             api_key_id: "api-key-1".to_string(),
@@ -951,6 +1630,7 @@ mod tests {
             let latency = if i < 5 { 200 } else { 150 }; // Treatment is faster
 
             let params = RecordExperimentParams {
+                record_id: None,
                 experiment_id: "test-exp".to_string(),
                 variant_id: variant_id.to_string(),
                 api_key_id: "api-key-1".to_string(),
@@ -979,4 +1659,199 @@ mod tests {
         let treatment_metrics = results.get_variant_metrics("treatment").unwrap();
         assert_eq!(treatment_metrics.total_requests, 5);
     }
+
+    #[tokio::test]
+    async fn test_record_feedback() {
+        let service = create_service();
+        service.create(create_valid_request("test-exp")).await.unwrap();
+        service.start("test-exp").await.unwrap();
+
+        // Control is rated 2-3 and treatment 4-5
+        for i in 0..10 {
+            let variant_id = if i < 5 { "control" } else { "treatment" };
+
+            service
+                .record(RecordExperimentParams {
+                    record_id: Some(format!("chatcmpl-{}", i)),
+                    experiment_id: "test-exp".to_string(),
+                    variant_id: variant_id.to_string(),
+                    api_key_id: "api-key-1".to_string(),
+                    model_id: "gpt-4".to_string(),
+                    input_tokens: 100,
+                    output_tokens: 50,
+                    cost_micros: 1500,
+                    latency_ms: 200,
+                    success: true,
+                    error: None,
+                })
+                .await
+                .unwrap();
+
+            let rating = if i < 5 { 2.0 } else { 4.0 } + (i % 2) as f64;
+            service
+                .record_feedback(
+                    &format!("chatcmpl-{}", i),
+                    "api-key-1",
+                    FeedbackEvent::Rating { value: rating },
+                )
+                .await
+                .unwrap();
+        }
+
+        let record = service
+            .record_feedback("chatcmpl-9", "api-key-1", FeedbackEvent::ThumbsUp)
+            .await
+            .unwrap();
+        assert_eq!(record.feedback.rating, Some(5.0));
+        assert_eq!(record.feedback.thumbs_up, Some(true));
+
+        // Other API keys can't see the record, ratings are validated
+        assert!(service
+            .record_feedback("chatcmpl-9", "api-key-2", FeedbackEvent::ThumbsUp)
+            .await
+            .is_err());
+        assert!(service
+            .record_feedback("chatcmpl-9", "api-key-1", FeedbackEvent::Rating { value: 9.0 })
+            .await
+            .is_err());
+
+        service.complete("test-exp").await.unwrap();
+        let results = service.get_results("test-exp").await.unwrap();
+
+        let treatment = results.get_variant_metrics("treatment").unwrap();
+        assert_eq!(treatment.ratings, 5);
+        assert_eq!(treatment.thumbs_up, 1);
+
+        let rating = results
+            .significance_tests
+            .iter()
+            .find(|s| s.metric == "rating")
+            .unwrap();
+        assert!(rating.treatment_is_better_higher());
+        assert_eq!(results.winner_variant_id.as_deref(), Some("treatment"));
+        assert!(results.recommendation.unwrap().contains("higher rating"));
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_auto_stop() {
+        let service = create_service();
+        let mut request = create_valid_request("test-exp");
+        request.auto_stop = Some(AutoStop {
+            min_samples: 20,
+            ..AutoStop::default()
+        });
+        service.create(request).await.unwrap();
+        service.start("test-exp").await.unwrap();
+
+        let record = |i: u64| {
+            let variant_id = if i % 2 == 0 { "control" } else { "treatment" };
+            let latency = if i % 2 == 0 { 200 } else { 150 } + i % 7;
+
+            service.record(RecordExperimentParams {
+                record_id: None,
+                experiment_id: "test-exp".to_string(),
+                variant_id: variant_id.to_string(),
+                api_key_id: "api-key-1".to_string(),
+                model_id: "gpt-4".to_string(),
+                input_tokens: 100,
+                output_tokens: 50,
+                cost_micros: 1500,
+                latency_ms: latency,
+                success: true,
+                error: None,
+            })
+        };
+
+        // Too few records per variant to stop
+        for i in 0..20 {
+            record(i).await.unwrap();
+        }
+        assert!(service.evaluate_auto_stop().await.unwrap().is_empty());
+
+        for i in 20..60 {
+            record(i).await.unwrap();
+        }
+        let completed = service.evaluate_auto_stop().await.unwrap();
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].status, ExperimentStatus::Completed);
+        assert_eq!(completed[0].winner_variant_id.as_deref(), Some("treatment"));
+        assert!(completed[0].recommendation.as_deref().unwrap().contains("lower latency ms"));
+
+        let experiment = service.get("test-exp").await.unwrap().unwrap();
+        assert_eq!(experiment.status(), ExperimentStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_rollouts() {
+        let service = create_service();
+        let mut request = create_valid_request("test-exp");
+        request.rollout = Some(RolloutGuard {
+            min_samples: 10,
+            ..RolloutGuard::default()
+        });
+        service.create(request).await.unwrap();
+        service.start("test-exp").await.unwrap();
+
+        // The treatment fails one request in five
+        for i in 0..40u64 {
+            let variant_id = if i % 2 == 0 { "control" } else { "treatment" };
+
+            service
+                .record(RecordExperimentParams {
+                    record_id: None,
+                    experiment_id: "test-exp".to_string(),
+                    variant_id: variant_id.to_string(),
+                    api_key_id: "api-key-1".to_string(),
+                    model_id: "gpt-4".to_string(),
+                    input_tokens: 100,
+                    output_tokens: 50,
+                    cost_micros: 1500,
+                    latency_ms: 200,
+                    success: i % 10 != 1,
+                    error: None,
+                })
+                .await
+                .unwrap();
+        }
+
+        let rolled_back = service.evaluate_rollouts().await.unwrap();
+        assert_eq!(rolled_back.len(), 1);
+        assert_eq!(rolled_back[0].winner_variant_id.as_deref(), Some("control"));
+        assert!(rolled_back[0].recommendation.as_deref().unwrap().contains("error rate 20.0%"));
+
+        let experiment = service.get("test-exp").await.unwrap().unwrap();
+        assert_eq!(experiment.status(), ExperimentStatus::Completed);
+        assert_eq!(experiment.rollback().unwrap().variant_id, "treatment");
+        assert!(service.evaluate_rollouts().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_rollout_without_control() {
+        let service = create_service();
+        let mut request = create_valid_request("test-exp");
+        request.rollout = Some(RolloutGuard::default());
+        request.variants[0].control = false;
+
+        let error = service.create(request).await.unwrap_err();
+        assert!(error.to_string().contains("control variant"));
+    }
+
+    #[tokio::test]
+    async fn test_create_prompt_version_without_prompt() {
+        let service = create_service();
+        let mut request = create_valid_request("test-exp");
+        request.variants[1].config = VariantConfig::ConfigOverride {
+            model_id: "gpt-4".to_string(),
+            prompt_id: None,
+            prompt_version: Some(2),
+            temperature: None,
+            max_tokens: None,
+            top_p: None,
+            presence_penalty: None,
+            frequency_penalty: None,
+        };
+
+        let error = service.create(request).await.unwrap_err();
+        assert!(error.to_string().contains("without a prompt_id"));
+    }
 }
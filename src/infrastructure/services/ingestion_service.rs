@@ -3,27 +3,40 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use serde::Serialize;
+use tracing::warn;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::domain::embedding::{EmbeddingProvider, EmbeddingRequest};
-use crate::domain::ingestion::{ChunkingConfig, ChunkingType, IngestionResult, ParserInput, ParserType};
+use crate::domain::ingestion::{
+    ChunkEnricher, ChunkEnrichment, ChunkEnrichmentConfig, ChunkingConfig, ChunkingType,
+    DocumentParser, IngestionResult, IngestionStage, OcrEngine, OcrProviderResolver, ParserInput,
+    ParserType,
+};
 use crate::domain::knowledge_base::{
     CreateChunkRequest, CreateDocumentRequest, Document, DocumentChunk, DocumentSummary,
-    KnowledgeBaseDocument, SourceInfo,
+    validate_namespace, KnowledgeBaseDocument, MetadataFilter, SearchResult, SourceInfo,
+    VectorIndexStatus, ALLOWED_ROLES_METADATA_KEY, ALLOWED_TEAMS_METADATA_KEY,
+    NAMESPACE_METADATA_KEY,
 };
 use crate::domain::model::ModelId;
 use crate::domain::storage::Storage;
 use crate::domain::knowledge_base::KnowledgeBaseId;
-use crate::domain::{DomainError, KnowledgeBase, Model};
+use crate::domain::team::QuotaResource;
+use crate::domain::{DistanceMetric, DomainError, KnowledgeBase, Model};
 use crate::infrastructure::credentials::CredentialServiceTrait;
 use crate::infrastructure::embedding::{HttpClient, OpenAiEmbeddingProvider};
-use crate::infrastructure::ingestion::{ChunkerFactory, ParserFactory};
+use crate::infrastructure::ingestion::{ChunkerFactory, OcrParser, ParserFactory, PdfTools};
 use crate::infrastructure::knowledge_base::KnowledgeBaseProviderRegistryTrait;
+use crate::infrastructure::team::TeamQuotaGuard;
 
 /// Request to ingest a document into a knowledge base
 #[derive(Debug, Clone)]
 pub struct IngestDocumentRequest {
     pub content: String,
+    /// Binary content (PDFs and images), parsed instead of `content`
+    pub bytes: Option<Vec<u8>>,
     pub filename: Option<String>,
     pub metadata: HashMap<String, serde_json::Value>,
     pub source_id: Option<String>,
@@ -31,12 +44,23 @@ pub struct IngestDocumentRequest {
     pub chunking_type: Option<ChunkingType>,
     pub chunk_size: Option<usize>,
     pub chunk_overlap: Option<usize>,
+    /// OCR engine for scanned PDFs and images, the service default if None
+    pub ocr: Option<OcrEngine>,
+    /// LLM enrichment of the chunks
+    pub enrichment: Option<ChunkEnrichmentConfig>,
+    /// Namespace of the chunks within the knowledge base
+    pub namespace: Option<String>,
+    /// Teams allowed to find the chunks in searches, every team if empty
+    pub allowed_teams: Vec<String>,
+    /// Roles allowed to find the chunks in searches, every role if empty
+    pub allowed_roles: Vec<String>,
 }
 
 impl Default for IngestDocumentRequest {
     fn default() -> Self {
         Self {
             content: String::new(),
+            bytes: None,
             filename: None,
             metadata: HashMap::new(),
             source_id: None,
@@ -44,6 +68,11 @@ impl Default for IngestDocumentRequest {
             chunking_type: None,
             chunk_size: None,
             chunk_overlap: None,
+            ocr: None,
+            enrichment: None,
+            namespace: None,
+            allowed_teams: Vec::new(),
+            allowed_roles: Vec::new(),
         }
     }
 }
@@ -56,6 +85,23 @@ impl IngestDocumentRequest {
         }
     }
 
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> Self {
+        Self {
+            bytes: Some(bytes.into()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_ocr(mut self, engine: OcrEngine) -> Self {
+        self.ocr = Some(engine);
+        self
+    }
+
+    pub fn with_enrichment(mut self, enrichment: ChunkEnrichmentConfig) -> Self {
+        self.enrichment = Some(enrichment);
+        self
+    }
+
     pub fn with_filename(mut self, filename: impl Into<String>) -> Self {
         self.filename = Some(filename.into());
         self
@@ -70,6 +116,21 @@ impl IngestDocumentRequest {
         self.source_id = Some(source_id.into());
         self
     }
+
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    pub fn with_allowed_teams(mut self, teams: Vec<String>) -> Self {
+        self.allowed_teams = teams;
+        self
+    }
+
+    pub fn with_allowed_roles(mut self, roles: Vec<String>) -> Self {
+        self.allowed_roles = roles;
+        self
+    }
 }
 
 /// Request to ingest a document using the new schema (with proper document/chunk separation)
@@ -85,6 +146,14 @@ pub struct IngestDocumentV2Request {
     pub chunking_type: Option<ChunkingType>,
     pub chunk_size: Option<usize>,
     pub chunk_overlap: Option<usize>,
+    /// LLM enrichment of the chunks
+    pub enrichment: Option<ChunkEnrichmentConfig>,
+    /// Namespace of the document within the knowledge base
+    pub namespace: Option<String>,
+    /// Teams allowed to find the document in searches, every team if empty
+    pub allowed_teams: Vec<String>,
+    /// Roles allowed to find the document in searches, every role if empty
+    pub allowed_roles: Vec<String>,
 }
 
 impl Default for IngestDocumentV2Request {
@@ -100,6 +169,10 @@ impl Default for IngestDocumentV2Request {
             chunking_type: None,
             chunk_size: None,
             chunk_overlap: None,
+            enrichment: None,
+            namespace: None,
+            allowed_teams: Vec::new(),
+            allowed_roles: Vec::new(),
         }
     }
 }
@@ -136,10 +209,69 @@ impl IngestDocumentV2Request {
         self.metadata.insert(key.into(), value);
         self
     }
+
+    pub fn with_enrichment(mut self, enrichment: ChunkEnrichmentConfig) -> Self {
+        self.enrichment = Some(enrichment);
+        self
+    }
+
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    pub fn with_allowed_teams(mut self, teams: Vec<String>) -> Self {
+        self.allowed_teams = teams;
+        self
+    }
+
+    pub fn with_allowed_roles(mut self, roles: Vec<String>) -> Self {
+        self.allowed_roles = roles;
+        self
+    }
+}
+
+/// Store the namespace of an ingestion as the `namespace` metadata of its chunks
+fn apply_namespace(
+    namespace: Option<&str>,
+    metadata: &mut HashMap<String, serde_json::Value>,
+) -> Result<(), DomainError> {
+    if let Some(namespace) = namespace {
+        validate_namespace(namespace).map_err(|e| DomainError::validation(e.to_string()))?;
+        metadata.insert(
+            NAMESPACE_METADATA_KEY.to_string(),
+            serde_json::json!(namespace),
+        );
+    }
+
+    Ok(())
+}
+
+/// Store the teams and roles allowed to read an ingested document as the
+/// `allowed_teams` and `allowed_roles` metadata of its chunks
+fn apply_access(
+    allowed_teams: &[String],
+    allowed_roles: &[String],
+    metadata: &mut HashMap<String, serde_json::Value>,
+) -> Result<(), DomainError> {
+    for (key, values) in [
+        (ALLOWED_TEAMS_METADATA_KEY, allowed_teams),
+        (ALLOWED_ROLES_METADATA_KEY, allowed_roles),
+    ] {
+        if values.is_empty() {
+            continue;
+        }
+        if values.iter().any(|v| v.trim().is_empty()) {
+            return Err(DomainError::validation(format!("{} cannot contain empty values", key)));
+        }
+        metadata.insert(key.to_string(), serde_json::json!(values));
+    }
+
+    Ok(())
 }
 
 /// Stored document information (returned by list operations)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct StoredDocument {
     pub id: String,
     pub content: String,
@@ -149,7 +281,31 @@ pub struct StoredDocument {
     pub total_chunks: usize,
 }
 
+fn stored_document(r: SearchResult) -> StoredDocument {
+    let chunk_index = r
+        .metadata
+        .get("chunk_index")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(0) as usize;
+
+    let total_chunks = r
+        .metadata
+        .get("total_chunks")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(1) as usize;
+
+    StoredDocument {
+        id: r.id,
+        content: r.content,
+        metadata: r.metadata,
+        source: r.source,
+        chunk_index,
+        total_chunks,
+    }
+}
+
 /// Configuration for dynamic embedding provider creation
+#[derive(Clone)]
 pub struct EmbeddingConfig {
     kb_storage: Arc<dyn Storage<KnowledgeBase>>,
     model_storage: Arc<dyn Storage<Model>>,
@@ -184,6 +340,27 @@ impl EmbeddingConfig {
             .ok_or_else(|| DomainError::not_found(format!("KnowledgeBase '{}'", kb_id)))
     }
 
+    /// Embed texts with the embedding model of a knowledge base
+    pub async fn embed_for_knowledge_base(
+        &self,
+        kb_id: &str,
+        texts: Vec<String>,
+    ) -> Result<Vec<Vec<f32>>, DomainError> {
+        let kb = self.get_kb(kb_id).await?;
+        self.create_embedding_provider(&kb)
+            .await?
+            .embed_texts(texts)
+            .await
+    }
+
+    /// Distance metric of a knowledge base, to compare its embeddings
+    pub async fn distance_metric_for_knowledge_base(
+        &self,
+        kb_id: &str,
+    ) -> Result<DistanceMetric, DomainError> {
+        Ok(self.get_kb(kb_id).await?.config().distance_metric)
+    }
+
     async fn create_embedding_provider(
         &self,
         kb: &KnowledgeBase,
@@ -307,6 +484,36 @@ pub struct IngestionService {
     provider_registry: Arc<dyn KnowledgeBaseProviderRegistryTrait>,
     embedding_config: Option<EmbeddingConfig>,
     embedding_provider: Option<Arc<dyn EmbeddingProvider>>,
+    quota: Option<DocumentQuota>,
+    ocr: Option<DocumentOcr>,
+    enricher: Option<Arc<dyn ChunkEnricher>>,
+}
+
+/// OCR of scanned PDFs and images
+struct DocumentOcr {
+    resolver: Arc<dyn OcrProviderResolver>,
+    pdf_tools: PdfTools,
+    default_engine: Option<OcrEngine>,
+}
+
+/// Team quota enforcement for document ingestion
+struct DocumentQuota {
+    guard: TeamQuotaGuard,
+    knowledge_bases: Arc<dyn Storage<KnowledgeBase>>,
+}
+
+/// Documents stored across a set of knowledge bases
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DocumentUsage {
+    pub documents: u64,
+    pub storage_bytes: u64,
+}
+
+/// Documents copied from one knowledge base into another
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct DocumentCopyResult {
+    pub documents_copied: usize,
+    pub chunks_copied: usize,
 }
 
 impl std::fmt::Debug for IngestionService {
@@ -314,6 +521,9 @@ impl std::fmt::Debug for IngestionService {
         f.debug_struct("IngestionService")
             .field("has_embedding_config", &self.embedding_config.is_some())
             .field("has_static_embedding_provider", &self.embedding_provider.is_some())
+            .field("enforces_quotas", &self.quota.is_some())
+            .field("has_ocr", &self.ocr.is_some())
+            .field("has_chunk_enricher", &self.enricher.is_some())
             .finish()
     }
 }
@@ -324,6 +534,9 @@ impl IngestionService {
             provider_registry,
             embedding_config: None,
             embedding_provider: None,
+            quota: None,
+            ocr: None,
+            enricher: None,
         }
     }
 
@@ -336,6 +549,9 @@ impl IngestionService {
             provider_registry,
             embedding_config: Some(embedding_config),
             embedding_provider: None,
+            quota: None,
+            ocr: None,
+            enricher: None,
         }
     }
 
@@ -348,6 +564,9 @@ impl IngestionService {
             provider_registry,
             embedding_config: None,
             embedding_provider: Some(embedding_provider),
+            quota: None,
+            ocr: None,
+            enricher: None,
         }
     }
 
@@ -356,17 +575,271 @@ impl IngestionService {
         self.embedding_provider = Some(provider);
     }
 
+    /// Enforce the document quotas of the team owning each knowledge base
+    pub fn with_quota_guard(
+        mut self,
+        guard: TeamQuotaGuard,
+        knowledge_bases: Arc<dyn Storage<KnowledgeBase>>,
+    ) -> Self {
+        self.quota = Some(DocumentQuota {
+            guard,
+            knowledge_bases,
+        });
+        self
+    }
+
+    /// OCR scanned PDFs and images, with `default_engine` unless the
+    /// request picks one
+    pub fn with_ocr(
+        mut self,
+        resolver: Arc<dyn OcrProviderResolver>,
+        pdf_tools: PdfTools,
+        default_engine: Option<OcrEngine>,
+    ) -> Self {
+        self.ocr = Some(DocumentOcr {
+            resolver,
+            pdf_tools,
+            default_engine,
+        });
+        self
+    }
+
+    /// Enrich chunks on request with LLM-generated titles, summaries and
+    /// keywords
+    pub fn with_chunk_enricher(mut self, enricher: Arc<dyn ChunkEnricher>) -> Self {
+        self.enricher = Some(enricher);
+        self
+    }
+
+    /// Enrichment of each chunk, empty when none is requested; a failed
+    /// enrichment is logged and the chunks are stored without it
+    async fn enrich_chunks(
+        &self,
+        config: Option<&ChunkEnrichmentConfig>,
+        chunks: &[String],
+    ) -> Result<Vec<ChunkEnrichment>, DomainError> {
+        let unenriched = || vec![ChunkEnrichment::default(); chunks.len()];
+
+        let Some(config) = config else {
+            return Ok(unenriched());
+        };
+        config.validate()?;
+        let enricher = self
+            .enricher
+            .as_ref()
+            .ok_or_else(|| DomainError::validation("Chunk enrichment is not available"))?;
+
+        match enricher.enrich(config, chunks).await {
+            Ok(enrichments) if enrichments.len() == chunks.len() => Ok(enrichments),
+            Ok(enrichments) => {
+                warn!(
+                    expected = chunks.len(),
+                    got = enrichments.len(),
+                    "Chunk enrichment count mismatch, storing chunks without enrichment"
+                );
+                Ok(unenriched())
+            }
+            Err(e) => {
+                warn!(
+                    model_id = %config.model_id,
+                    error = %e,
+                    "Chunk enrichment failed, storing chunks without enrichment"
+                );
+                Ok(unenriched())
+            }
+        }
+    }
+
+    /// Parser for a document type, with the OCR engine for PDFs and images
+    async fn parser_for(
+        &self,
+        parser_type: ParserType,
+        engine: Option<&OcrEngine>,
+    ) -> Result<Arc<dyn DocumentParser>, DomainError> {
+        let Some(ocr) = self.ocr.as_ref().filter(|_| parser_type.needs_ocr()) else {
+            return ParserFactory::create(parser_type);
+        };
+
+        let mut parser = OcrParser::new(ocr.pdf_tools.clone());
+        if let Some(engine) = engine.or(ocr.default_engine.as_ref()) {
+            parser = parser.with_ocr(engine, ocr.resolver.resolve(engine).await?);
+        }
+
+        Ok(Arc::new(parser))
+    }
+
+    /// Count the documents and their original size across knowledge bases
+    pub async fn document_usage(&self, kb_ids: &[String]) -> Result<DocumentUsage, DomainError> {
+        let mut usage = DocumentUsage::default();
+
+        for kb_id in kb_ids {
+            let documents = self.list_documents_v2(kb_id).await?;
+
+            usage.documents += documents.len() as u64;
+            usage.storage_bytes += documents
+                .iter()
+                .filter_map(|d| d.original_size_bytes)
+                .map(|size| size.max(0) as u64)
+                .sum::<u64>();
+        }
+
+        Ok(usage)
+    }
+
+    /// Copy every document of a knowledge base into another, reusing the
+    /// chunks and their embeddings; both must use the same embedding model
+    pub async fn copy_documents(
+        &self,
+        source_kb_id: &str,
+        target_kb_id: &str,
+    ) -> Result<DocumentCopyResult, DomainError> {
+        let source = self.provider_registry.get_required(source_kb_id).await?;
+        let target = self.provider_registry.get_required(target_kb_id).await?;
+        let mut result = DocumentCopyResult::default();
+
+        for summary in source.list_documents().await? {
+            // Deleted since listed
+            let Some(document) = source.get_document_by_id(summary.id).await? else {
+                continue;
+            };
+
+            let chunks = source.get_document_chunks(summary.id).await?;
+            let original_content = chunks
+                .iter()
+                .map(DocumentChunk::content)
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            let size_bytes = summary
+                .original_size_bytes
+                .map(|size| size.max(0) as u64)
+                .unwrap_or(original_content.len() as u64);
+
+            self.ensure_document_quota(target_kb_id, size_bytes).await?;
+
+            let chunk_requests = chunks
+                .iter()
+                .map(|chunk| {
+                    let embedding = chunk.embedding().filter(|e| !e.is_empty()).ok_or_else(|| {
+                        DomainError::knowledge_base(format!(
+                            "Chunk {} of document '{}' has no embedding to copy",
+                            chunk.chunk_index(),
+                            summary.id
+                        ))
+                    })?;
+
+                    Ok(CreateChunkRequest {
+                        content: chunk.content().to_string(),
+                        embedding: embedding.to_vec(),
+                        chunk_index: chunk.chunk_index(),
+                        token_count: chunk.token_count(),
+                        metadata: chunk.metadata().clone(),
+                    })
+                })
+                .collect::<Result<Vec<_>, DomainError>>()?;
+
+            let copy = target
+                .create_document(CreateDocumentRequest {
+                    title: document.title().map(String::from),
+                    description: document.description().map(String::from),
+                    source_filename: document.source_filename().map(String::from),
+                    content_type: document.content_type().map(String::from),
+                    original_content,
+                    metadata: document.metadata().clone(),
+                    chunks: chunk_requests,
+                })
+                .await?;
+
+            if document.is_disabled() {
+                target.disable_document(copy.id()).await?;
+            }
+
+            result.documents_copied += 1;
+            result.chunks_copied += chunks.len();
+        }
+
+        Ok(result)
+    }
+
+    /// Ensure the team owning a knowledge base can store one more document
+    /// of `size_bytes`
+    async fn ensure_document_quota(&self, kb_id: &str, size_bytes: u64) -> Result<(), DomainError> {
+        let Some(quota) = &self.quota else {
+            return Ok(());
+        };
+
+        let kb_id = KnowledgeBaseId::new(kb_id).map_err(|e| DomainError::invalid_id(e.to_string()))?;
+        let Some(team_id) = quota
+            .knowledge_bases
+            .get(&kb_id)
+            .await?
+            .and_then(|kb| kb.team_id().cloned())
+        else {
+            return Ok(());
+        };
+
+        let team_kb_ids = || async {
+            let knowledge_bases = quota.knowledge_bases.list().await?;
+
+            Ok::<_, DomainError>(
+                knowledge_bases
+                    .iter()
+                    .filter(|kb| kb.team_id() == Some(&team_id))
+                    .map(|kb| kb.id().as_str().to_string())
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        quota
+            .guard
+            .ensure(&team_id, QuotaResource::KbDocuments, 1, || async {
+                Ok(self.document_usage(&team_kb_ids().await?).await?.documents)
+            })
+            .await?;
+
+        quota
+            .guard
+            .ensure(&team_id, QuotaResource::KbStorageBytes, size_bytes, || async {
+                Ok(self.document_usage(&team_kb_ids().await?).await?.storage_bytes)
+            })
+            .await
+    }
+
     /// Ingest a document into a knowledge base
     pub async fn ingest(
         &self,
         kb_id: &str,
         request: IngestDocumentRequest,
     ) -> Result<IngestionResult, DomainError> {
+        self.ingest_with_progress(kb_id, request, &|_, _| {}).await
+    }
+
+    /// Ingest a document into a knowledge base, reporting each stage it
+    /// reaches with the chunk count once chunked. Providers embed and store
+    /// chunks in one call, so `Embedded` and `Stored` are reported together
+    /// once it returns.
+    pub async fn ingest_with_progress(
+        &self,
+        kb_id: &str,
+        mut request: IngestDocumentRequest,
+        on_stage: &(dyn Fn(IngestionStage, Option<usize>) + Send + Sync),
+    ) -> Result<IngestionResult, DomainError> {
+        apply_namespace(request.namespace.as_deref(), &mut request.metadata)?;
+        apply_access(&request.allowed_teams, &request.allowed_roles, &mut request.metadata)?;
+
+        let size_bytes = request
+            .bytes
+            .as_ref()
+            .map_or(request.content.len(), Vec::len);
+        self.ensure_document_quota(kb_id, size_bytes as u64).await?;
+
         // Get the provider for this knowledge base
         let provider = self.provider_registry.get_required(kb_id).await?;
 
         // Build parser input
-        let mut parser_input = ParserInput::from_text(request.content.clone());
+        let mut parser_input = match &request.bytes {
+            Some(bytes) => ParserInput::from_bytes(bytes.clone()),
+            None => ParserInput::from_text(request.content.clone()),
+        };
 
         if let Some(filename) = &request.filename {
             parser_input = parser_input.with_filename(filename);
@@ -394,17 +867,24 @@ impl IngestionService {
         let chunking_type = request.chunking_type.unwrap_or(ChunkingType::FixedSize);
 
         // Parse the document
-        let parser = ParserFactory::create(parser_type)?;
+        let parser = self.parser_for(parser_type, request.ocr.as_ref()).await?;
         let parsed = parser
             .parse(parser_input.clone())
             .await
             .map_err(|e| DomainError::validation(format!("Failed to parse document: {}", e)))?;
+        on_stage(IngestionStage::Parsed, None);
 
         // Chunk the document
         let chunker = ChunkerFactory::create(chunking_type);
         let chunks = chunker
             .chunk(&parsed.content, &chunking_config)
             .map_err(|e| DomainError::validation(format!("Failed to chunk document: {}", e)))?;
+        on_stage(IngestionStage::Chunked, Some(chunks.len()));
+
+        let chunk_contents: Vec<String> = chunks.iter().map(|c| c.content.clone()).collect();
+        let enrichments = self
+            .enrich_chunks(request.enrichment.as_ref(), &chunk_contents)
+            .await?;
 
         // Generate document/source ID
         let source_id = request
@@ -417,9 +897,11 @@ impl IngestionService {
         let total_chunks = chunks.len();
         let documents: Vec<Document> = chunks
             .into_iter()
+            .zip(enrichments)
             .enumerate()
-            .map(|(idx, chunk)| {
+            .map(|(idx, (chunk, enrichment))| {
                 let mut metadata = request.metadata.clone();
+                enrichment.apply_to(&mut metadata);
                 metadata.insert("chunk_index".to_string(), serde_json::json!(idx));
                 metadata.insert("total_chunks".to_string(), serde_json::json!(total_chunks));
                 metadata.insert(
@@ -450,6 +932,8 @@ impl IngestionService {
 
         // Add documents to the knowledge base via the provider
         let result = provider.add_documents(documents).await?;
+        on_stage(IngestionStage::Embedded, None);
+        on_stage(IngestionStage::Stored, None);
 
         Ok(IngestionResult {
             document_id: source_id,
@@ -478,31 +962,19 @@ impl IngestionService {
         let provider = self.provider_registry.get_required(kb_id).await?;
         let results = provider.list_by_source(source).await?;
 
-        Ok(results
-            .into_iter()
-            .map(|r| {
-                let chunk_index = r
-                    .metadata
-                    .get("chunk_index")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(0) as usize;
-
-                let total_chunks = r
-                    .metadata
-                    .get("total_chunks")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(1) as usize;
-
-                StoredDocument {
-                    id: r.id,
-                    content: r.content,
-                    metadata: r.metadata,
-                    source: r.source,
-                    chunk_index,
-                    total_chunks,
-                }
-            })
-            .collect())
+        Ok(results.into_iter().map(stored_document).collect())
+    }
+
+    /// Get documents matching a metadata filter
+    pub async fn get_documents_by_filter(
+        &self,
+        kb_id: &str,
+        filter: MetadataFilter,
+    ) -> Result<Vec<StoredDocument>, DomainError> {
+        let provider = self.provider_registry.get_required(kb_id).await?;
+        let results = provider.list_by_filter(filter).await?;
+
+        Ok(results.into_iter().map(stored_document).collect())
     }
 
     /// Get document count for a knowledge base
@@ -518,12 +990,36 @@ impl IngestionService {
         Ok(result.deleted)
     }
 
+    /// Delete documents matching a metadata filter
+    pub async fn delete_by_filter(
+        &self,
+        kb_id: &str,
+        filter: MetadataFilter,
+    ) -> Result<usize, DomainError> {
+        let provider = self.provider_registry.get_required(kb_id).await?;
+        let result = provider.delete_by_filter(filter).await?;
+        Ok(result.deleted)
+    }
+
     /// Initialize/ensure schema for a knowledge base (create tables, indexes)
     pub async fn ensure_schema(&self, kb_id: &str) -> Result<(), DomainError> {
         let provider = self.provider_registry.get_required(kb_id).await?;
         provider.ensure_schema().await
     }
 
+    /// Status of the vector index of a knowledge base
+    pub async fn index_status(&self, kb_id: &str) -> Result<Option<VectorIndexStatus>, DomainError> {
+        let provider = self.provider_registry.get_required(kb_id).await?;
+        provider.index_status().await
+    }
+
+    /// Rebuild the vector index of a knowledge base with its configured
+    /// type and parameters
+    pub async fn reindex(&self, kb_id: &str) -> Result<VectorIndexStatus, DomainError> {
+        let provider = self.provider_registry.get_required(kb_id).await?;
+        provider.reindex().await
+    }
+
     // ========================================================================
     // New schema methods (document/chunk separation)
     // ========================================================================
@@ -532,8 +1028,14 @@ impl IngestionService {
     pub async fn ingest_document(
         &self,
         kb_id: &str,
-        request: IngestDocumentV2Request,
+        mut request: IngestDocumentV2Request,
     ) -> Result<KnowledgeBaseDocument, DomainError> {
+        apply_namespace(request.namespace.as_deref(), &mut request.metadata)?;
+        apply_access(&request.allowed_teams, &request.allowed_roles, &mut request.metadata)?;
+
+        self.ensure_document_quota(kb_id, request.content.len() as u64)
+            .await?;
+
         // Get the KB provider
         let provider = self.provider_registry.get_required(kb_id).await?;
 
@@ -585,7 +1087,7 @@ impl IngestionService {
         let chunking_type = request.chunking_type.unwrap_or(ChunkingType::FixedSize);
 
         // Parse the document
-        let parser = ParserFactory::create(parser_type)?;
+        let parser = self.parser_for(parser_type, None).await?;
         let parsed = parser
             .parse(parser_input.clone())
             .await
@@ -597,8 +1099,20 @@ impl IngestionService {
             .chunk(&parsed.content, &chunking_config)
             .map_err(|e| DomainError::validation(format!("Failed to chunk document: {}", e)))?;
 
-        // Generate embeddings for all chunks
         let chunk_contents: Vec<String> = chunks.iter().map(|c| c.content.clone()).collect();
+        let enrichments = self
+            .enrich_chunks(request.enrichment.as_ref(), &chunk_contents)
+            .await?;
+
+        // Generate embeddings for all chunks, with their enrichment if requested
+        let chunk_contents: Vec<String> = match &request.enrichment {
+            Some(enrichment) if enrichment.embed => chunks
+                .iter()
+                .zip(&enrichments)
+                .map(|(chunk, enrichment)| enrichment.embedding_text(&chunk.content))
+                .collect(),
+            _ => chunk_contents,
+        };
 
         let embeddings: Vec<Vec<f32>> = if let Some(dp) = &dynamic_provider {
             // Use dynamic provider
@@ -637,9 +1151,11 @@ impl IngestionService {
         let chunk_requests: Vec<CreateChunkRequest> = chunks
             .into_iter()
             .zip(embeddings.into_iter())
+            .zip(enrichments)
             .enumerate()
-            .map(|(idx, (chunk, embedding))| {
+            .map(|(idx, ((chunk, embedding), enrichment))| {
                 let mut chunk_metadata = request.metadata.clone();
+                enrichment.apply_to(&mut chunk_metadata);
                 chunk_metadata.insert(
                     "char_start".to_string(),
                     serde_json::json!(chunk.metadata.char_start),
@@ -760,6 +1276,10 @@ pub trait IngestionServiceTrait: Send + Sync + std::fmt::Debug {
 
     async fn ensure_schema(&self, kb_id: &str) -> Result<(), DomainError>;
 
+    async fn index_status(&self, kb_id: &str) -> Result<Option<VectorIndexStatus>, DomainError>;
+
+    async fn reindex(&self, kb_id: &str) -> Result<VectorIndexStatus, DomainError>;
+
     // New schema methods
     async fn ingest_document(
         &self,
@@ -822,11 +1342,22 @@ impl IngestionServiceTrait for IngestionService {
         IngestionService::ensure_schema(self, kb_id).await
     }
 
+    async fn index_status(&self, kb_id: &str) -> Result<Option<VectorIndexStatus>, DomainError> {
+        IngestionService::index_status(self, kb_id).await
+    }
+
+    async fn reindex(&self, kb_id: &str) -> Result<VectorIndexStatus, DomainError> {
+        IngestionService::reindex(self, kb_id).await
+    }
+
     async fn ingest_document(
         &self,
         kb_id: &str,
-        request: IngestDocumentV2Request,
+        mut request: IngestDocumentV2Request,
     ) -> Result<KnowledgeBaseDocument, DomainError> {
+        apply_namespace(request.namespace.as_deref(), &mut request.metadata)?;
+        apply_access(&request.allowed_teams, &request.allowed_roles, &mut request.metadata)?;
+
         IngestionService::ingest_document(self, kb_id, request).await
     }
 
@@ -944,4 +1475,43 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_ingest_enforces_team_storage_quota() {
+        use crate::domain::knowledge_base::EmbeddingConfig as KbEmbeddingConfig;
+        use crate::domain::knowledge_base::KnowledgeBaseType;
+        use crate::domain::team::{Team, TeamId, TeamQuota, TeamRepository};
+        use crate::infrastructure::storage::InMemoryStorage;
+        use crate::infrastructure::team::StorageTeamRepository;
+
+        let team_id = TeamId::new("team-a").unwrap();
+        let teams = StorageTeamRepository::new(Arc::new(InMemoryStorage::<Team>::new()));
+        let mut team = Team::new(team_id.clone(), "Team A").unwrap();
+        team.set_quota(TeamQuota::new().with_limit(QuotaResource::KbStorageBytes, 10));
+        teams.create(team).await.unwrap();
+
+        let knowledge_bases = Arc::new(InMemoryStorage::<KnowledgeBase>::new());
+        let kb = KnowledgeBase::new(
+            KnowledgeBaseId::new("test-kb").unwrap(),
+            "Test KB",
+            KnowledgeBaseType::Pgvector,
+            KbEmbeddingConfig::new("text-embedding-3-small", 1536),
+        )
+        .with_team_id(team_id);
+        knowledge_bases.create(kb).await.unwrap();
+
+        let service = create_service()
+            .await
+            .with_quota_guard(TeamQuotaGuard::new(Arc::new(teams)), knowledge_bases);
+
+        let result = service
+            .ingest("test-kb", IngestDocumentRequest::new("Small"))
+            .await;
+        assert!(result.is_ok());
+
+        let result = service
+            .ingest("test-kb", IngestDocumentRequest::new("This document is too large"))
+            .await;
+        assert!(matches!(result, Err(DomainError::Conflict { .. })));
+    }
 }
@@ -3,10 +3,16 @@
 use std::sync::Arc;
 
 use crate::domain::storage::Storage;
+use crate::domain::team::{QuotaResource, TeamId};
+use crate::domain::knowledge_base::{
+    check_knowledge_base_config, validate_dimensions, validate_distance_metric,
+    validate_knowledge_base_id,
+};
 use crate::domain::{
-    DomainError, EmbeddingConfig, KnowledgeBase, KnowledgeBaseConfig, KnowledgeBaseId,
-    KnowledgeBaseType, KnowledgeBaseValidationError,
+    DomainError, EmbeddingConfig, FieldViolations, KnowledgeBase, KnowledgeBaseConfig,
+    KnowledgeBaseId, KnowledgeBaseType, KnowledgeBaseValidationError,
 };
+use crate::infrastructure::team::TeamQuotaGuard;
 
 /// Request to create a new knowledge base
 #[derive(Debug, Clone)]
@@ -20,6 +26,8 @@ pub struct CreateKnowledgeBaseRequest {
     pub credential_id: String,
     pub config: Option<KnowledgeBaseConfig>,
     pub enabled: bool,
+    /// Owning team, counted against its knowledge base quota
+    pub team_id: Option<TeamId>,
 }
 
 /// Request to update an existing knowledge base
@@ -34,6 +42,7 @@ pub struct UpdateKnowledgeBaseRequest {
 /// Knowledge Base service for CRUD operations
 pub struct KnowledgeBaseService {
     storage: Arc<dyn Storage<KnowledgeBase>>,
+    quota_guard: Option<TeamQuotaGuard>,
 }
 
 impl std::fmt::Debug for KnowledgeBaseService {
@@ -45,7 +54,16 @@ impl std::fmt::Debug for KnowledgeBaseService {
 impl KnowledgeBaseService {
     /// Create a new KnowledgeBaseService with the given storage
     pub fn new(storage: Arc<dyn Storage<KnowledgeBase>>) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            quota_guard: None,
+        }
+    }
+
+    /// Enforce team knowledge base quotas on creation
+    pub fn with_quota_guard(mut self, quota_guard: TeamQuotaGuard) -> Self {
+        self.quota_guard = Some(quota_guard);
+        self
     }
 
     /// Get a knowledge base by ID
@@ -66,11 +84,38 @@ impl KnowledgeBaseService {
         self.storage.list().await
     }
 
+    /// Count the knowledge bases owned by a team
+    pub async fn count_for_team(&self, team_id: &TeamId) -> Result<u64, DomainError> {
+        let knowledge_bases = self.storage.list().await?;
+
+        Ok(knowledge_bases
+            .iter()
+            .filter(|kb| kb.team_id() == Some(team_id))
+            .count() as u64)
+    }
+
     /// Create a new knowledge base
     pub async fn create(
         &self,
         request: CreateKnowledgeBaseRequest,
     ) -> Result<KnowledgeBase, DomainError> {
+        let mut violations = FieldViolations::new();
+        violations.check("id", validate_knowledge_base_id(&request.id));
+        violations.check(
+            "embedding_dimensions",
+            validate_dimensions(request.embedding_dimensions),
+        );
+
+        if let Some(ref config) = request.config {
+            check_knowledge_base_config(config, "config", &mut violations);
+            violations.check(
+                "config.distance_metric",
+                validate_distance_metric(config.distance_metric, &request.embedding_model),
+            );
+        }
+
+        violations.into_result()?;
+
         let kb_id = self.parse_kb_id(&request.id)?;
 
         // Check for duplicate
@@ -81,6 +126,14 @@ impl KnowledgeBaseService {
             )));
         }
 
+        if let (Some(guard), Some(team_id)) = (&self.quota_guard, &request.team_id) {
+            guard
+                .ensure(team_id, QuotaResource::KnowledgeBases, 1, || {
+                    self.count_for_team(team_id)
+                })
+                .await?;
+        }
+
         // Create embedding config
         let embedding = EmbeddingConfig::new(request.embedding_model, request.embedding_dimensions);
 
@@ -101,6 +154,10 @@ impl KnowledgeBaseService {
         kb = kb.with_connection_config(connection_config);
         kb = kb.with_enabled(request.enabled);
 
+        if let Some(team_id) = request.team_id {
+            kb = kb.with_team_id(team_id);
+        }
+
         self.storage.save(kb.clone()).await?;
         Ok(kb)
     }
@@ -130,6 +187,14 @@ impl KnowledgeBaseService {
         }
 
         if let Some(config) = request.config {
+            let mut violations = FieldViolations::new();
+            check_knowledge_base_config(&config, "config", &mut violations);
+            violations.check(
+                "config.distance_metric",
+                validate_distance_metric(config.distance_metric, &kb.embedding().model),
+            );
+            violations.into_result()?;
+
             kb.set_config(config);
         }
 
@@ -168,6 +233,7 @@ impl KnowledgeBaseService {
 mod tests {
     use super::*;
     use crate::domain::storage::mock::MockStorage;
+    use crate::domain::DistanceMetric;
 
     fn create_service() -> KnowledgeBaseService {
         KnowledgeBaseService::new(Arc::new(MockStorage::new()))
@@ -188,6 +254,7 @@ mod tests {
                     .with_default_similarity_threshold(0.7),
             ),
             enabled: true,
+            team_id: None,
         }
     }
 
@@ -206,6 +273,24 @@ mod tests {
         assert!(kb.is_enabled());
     }
 
+    #[tokio::test]
+    async fn test_create_validates_distance_metric() {
+        let service = create_service();
+
+        let mut request = create_request("inner-product");
+        request.config =
+            Some(KnowledgeBaseConfig::new().with_distance_metric(DistanceMetric::InnerProduct));
+        let kb = service.create(request).await.unwrap();
+        assert_eq!(kb.config().distance_metric, DistanceMetric::InnerProduct);
+
+        let mut request = create_request("unnormalized");
+        request.embedding_model = "nomic-embed-text".to_string();
+        request.config =
+            Some(KnowledgeBaseConfig::new().with_distance_metric(DistanceMetric::InnerProduct));
+        let error = service.create(request).await.unwrap_err();
+        assert!(error.to_string().contains("config.distance_metric"));
+    }
+
     #[tokio::test]
     async fn test_create_duplicate_knowledge_base() {
         let service = create_service();
@@ -280,4 +365,32 @@ mod tests {
         let kbs = service.list().await.unwrap();
         assert_eq!(kbs.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_create_enforces_team_quota() {
+        use crate::domain::team::{Team, TeamQuota, TeamRepository};
+        use crate::infrastructure::storage::InMemoryStorage;
+        use crate::infrastructure::team::StorageTeamRepository;
+
+        let teams = StorageTeamRepository::new(Arc::new(InMemoryStorage::<Team>::new()));
+        let mut team = Team::new(TeamId::new("team-a").unwrap(), "Team A").unwrap();
+        team.set_quota(TeamQuota::new().with_limit(QuotaResource::KnowledgeBases, 1));
+        teams.create(team).await.unwrap();
+
+        let service = create_service().with_quota_guard(TeamQuotaGuard::new(Arc::new(teams)));
+        let team_id = TeamId::new("team-a").unwrap();
+
+        let mut request = create_request("kb-1");
+        request.team_id = Some(team_id.clone());
+        let kb = service.create(request).await.unwrap();
+        assert_eq!(kb.team_id(), Some(&team_id));
+
+        let mut request = create_request("kb-2");
+        request.team_id = Some(team_id.clone());
+        assert!(service.create(request).await.is_err());
+
+        // Knowledge bases without an owning team are not limited
+        assert!(service.create(create_request("kb-3")).await.is_ok());
+        assert_eq!(service.count_for_team(&team_id).await.unwrap(), 1);
+    }
 }
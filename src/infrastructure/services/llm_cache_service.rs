@@ -3,9 +3,14 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use crate::domain::cache::{Cache, CacheExt, CacheKeyGenerator, CacheKeyParams, DefaultKeyGenerator};
+use tracing::Instrument;
+
+use crate::domain::cache::{
+    Cache, CacheExt, CacheKeyGenerator, CacheKeyParams, CacheMode, CachePolicy, DefaultKeyGenerator,
+};
 use crate::domain::llm::{LlmRequest, LlmResponse};
 use crate::domain::DomainError;
+use crate::infrastructure::observability::{cache_lookup_span, record_cache_lookup, record_result};
 
 /// Configuration for LLM response caching
 #[derive(Debug, Clone)]
@@ -169,8 +174,12 @@ impl LlmCacheService {
             return Ok(None);
         }
 
+        let span = cache_lookup_span("exact", model_id);
         let key = self.generate_cache_key(model_id, request);
-        let result: Option<CachedLlmResponse> = self.cache.get(&key).await?;
+        let result: Option<CachedLlmResponse> =
+            record_result(&span, self.cache.get(&key).instrument(span.clone()).await)?;
+        span.record("cache.hit", result.is_some());
+        record_cache_lookup("exact", result.is_some());
 
         // Update hit count if found
         if let Some(cached) = result {
@@ -227,6 +236,40 @@ impl LlmCacheService {
         self.cache.set(&key, &cached, ttl).await
     }
 
+    /// Tries to get a cached response, unless the request's cache policy
+    /// (see [`CachePolicy::resolve`]) rules out the exact cache
+    pub async fn get_with_policy(
+        &self,
+        model_id: &str,
+        request: &LlmRequest,
+        policy: Option<&CachePolicy>,
+    ) -> Result<Option<CachedLlmResponse>, DomainError> {
+        if policy.is_some_and(|p| !p.allows(CacheMode::Exact, request)) {
+            return Ok(None);
+        }
+
+        self.get(model_id, request).await
+    }
+
+    /// Caches a response as the request's cache policy allows, for the
+    /// policy's TTL when it sets one
+    pub async fn set_with_policy(
+        &self,
+        model_id: &str,
+        request: &LlmRequest,
+        response: LlmResponse,
+        policy: Option<&CachePolicy>,
+    ) -> Result<(), DomainError> {
+        match policy {
+            Some(p) if !p.allows(CacheMode::Exact, request) => Ok(()),
+            Some(p) => {
+                let ttl = p.ttl().unwrap_or(self.config.default_ttl);
+                self.set_with_ttl(model_id, request, response, ttl).await
+            }
+            None => self.set(model_id, request, response).await,
+        }
+    }
+
     /// Invalidates a specific cached response
     pub async fn invalidate(
         &self,
@@ -369,6 +412,49 @@ mod tests {
         assert!(cached.is_none());
     }
 
+    #[tokio::test]
+    async fn test_cache_policy() {
+        let cache = Arc::new(MockCache::new());
+        let service = LlmCacheService::new(cache);
+
+        let mut request = create_test_request();
+        request.temperature = Some(0.9);
+        let response = create_test_response();
+
+        // Semantic-only and disabled policies keep the exact cache out
+        for policy in [CachePolicy::new(CacheMode::Semantic), CachePolicy::disabled()] {
+            service
+                .set_with_policy("gpt-4", &request, response.clone(), Some(&policy))
+                .await
+                .unwrap();
+            assert!(service.get("gpt-4", &request).await.unwrap().is_none());
+        }
+
+        // Too hot for the policy's temperature limit
+        let exact = CachePolicy::new(CacheMode::Exact).with_max_temperature(0.5);
+        service
+            .set_with_policy("gpt-4", &request, response.clone(), Some(&exact))
+            .await
+            .unwrap();
+        assert!(service.get("gpt-4", &request).await.unwrap().is_none());
+
+        request.temperature = Some(0.2);
+        service
+            .set_with_policy("gpt-4", &request, response, Some(&exact))
+            .await
+            .unwrap();
+        assert!(service
+            .get_with_policy("gpt-4", &request, Some(&exact))
+            .await
+            .unwrap()
+            .is_some());
+        assert!(service
+            .get_with_policy("gpt-4", &request, Some(&CachePolicy::disabled()))
+            .await
+            .unwrap()
+            .is_none());
+    }
+
     #[tokio::test]
     async fn test_cache_key_differs_by_model() {
         let cache = Arc::new(MockCache::new());
@@ -9,35 +9,44 @@ mod llm_cache_service;
 mod model_service;
 mod operation_service;
 mod prompt_service;
+mod regression_service;
+mod response_cache_service;
 mod semantic_llm_cache_service;
 mod test_case_service;
+mod test_suite_service;
 mod workflow_service;
 
 pub use config_service::ConfigService;
 pub use execution_log_service::{ExecutionLogService, RecordExecutionParams};
 pub use experiment_service::{
-    CreateExperimentRequest, CreateVariantRequest, ExperimentService, RecordExperimentParams,
-    UpdateExperimentRequest,
+    spawn_experiment_auto_stop, CreateExperimentRequest, CreateVariantRequest, ExperimentService,
+    RecordExperimentParams, UpdateExperimentRequest,
 };
 pub use ingestion_service::{
-    EmbeddingConfig, IngestDocumentRequest, IngestDocumentV2Request, IngestionService,
-    IngestionServiceTrait, StoredDocument,
+    DocumentCopyResult, DocumentUsage, EmbeddingConfig, IngestDocumentRequest, IngestDocumentV2Request,
+    IngestionService, IngestionServiceTrait, StoredDocument,
 };
 pub use knowledge_base_service::{
     CreateKnowledgeBaseRequest, KnowledgeBaseService, UpdateKnowledgeBaseRequest,
 };
 pub use llm_cache_service::{CacheStats, CachedLlmResponse, LlmCacheConfig, LlmCacheService};
 pub use model_service::{CreateModelRequest, ModelService, UpdateModelRequest};
-pub use operation_service::{OperationService, OperationServiceConfig, OperationServiceTrait};
+pub use operation_service::{
+    OperationService, OperationServiceConfig, OperationServiceTrait, ReapedOperations,
+};
 pub use prompt_service::{
-    CreatePromptRequest, PromptService, RenderPromptRequest, RenderedPrompt, UpdatePromptRequest,
+    resolve_partials, CreatePromptRequest, PromptService, RenderPromptRequest, RenderedPrompt,
+    UpdatePromptRequest,
 };
+pub use regression_service::RegressionService;
+pub use response_cache_service::{CacheHit, ResponseCacheService};
 pub use semantic_llm_cache_service::{
     CachedLlmResponse as SemanticCachedLlmResponse, SemanticLlmCacheService,
     SemanticLlmCacheServiceTrait,
 };
 pub use test_case_service::{
-    AssertionResultResponse, CreateTestCaseRequest, ExecuteTestCaseResponse, TestCaseInputRequest,
-    TestCaseService, TestCaseServiceDeps, UpdateTestCaseRequest,
+    AssertionResultResponse, CreateTestCaseRequest, ExecuteTestCaseResponse, ExecutionOverrides,
+    TestCaseInputRequest, TestCaseService, TestCaseServiceDeps, UpdateTestCaseRequest,
 };
+pub use test_suite_service::{CreateTestSuiteRequest, TestSuiteService, UpdateTestSuiteRequest};
 pub use workflow_service::{CreateWorkflowRequest, UpdateWorkflowRequest, WorkflowService};
@@ -4,8 +4,8 @@ use std::sync::Arc;
 
 use crate::domain::storage::Storage;
 use crate::domain::{
-    validate_model_config, CredentialType, DomainError, Model, ModelConfig, ModelId,
-    ModelValidationError,
+    check_model_config, validate_model_id, CredentialType, DomainError, FieldViolations, Model,
+    ModelConfig, ModelId, ModelLineage, ModelValidationError,
 };
 
 /// Request to create a new model
@@ -19,6 +19,8 @@ pub struct CreateModelRequest {
     pub credential_id: String,
     pub config: Option<ModelConfig>,
     pub enabled: bool,
+    /// Fine-tuning lineage, set when a fine-tuning job registers the model
+    pub lineage: Option<ModelLineage>,
 }
 
 /// Request to update an existing model
@@ -70,6 +72,16 @@ impl<S: Storage<Model>> ModelService<S> {
 
     /// Create a new model
     pub async fn create(&self, request: CreateModelRequest) -> Result<Model, DomainError> {
+        let mut violations = FieldViolations::new();
+        violations.check("id", validate_model_id(&request.id));
+
+        if let Some(ref config) = request.config {
+            check_model_config(config, "config", &mut violations);
+            check_shadow_target(&request.id, config, &mut violations);
+        }
+
+        violations.into_result()?;
+
         let model_id = self.parse_model_id(&request.id)?;
 
         // Check for duplicate
@@ -80,11 +92,6 @@ impl<S: Storage<Model>> ModelService<S> {
             )));
         }
 
-        // Validate config if provided
-        if let Some(ref config) = request.config {
-            self.validate_config(config)?;
-        }
-
         // Build the model
         let mut model = Model::new(
             model_id,
@@ -104,6 +111,10 @@ impl<S: Storage<Model>> ModelService<S> {
 
         model = model.with_enabled(request.enabled);
 
+        if let Some(lineage) = request.lineage {
+            model = model.with_lineage(lineage);
+        }
+
         self.storage.create(model).await
     }
 
@@ -140,7 +151,11 @@ impl<S: Storage<Model>> ModelService<S> {
         }
 
         if let Some(config) = request.config {
-            self.validate_config(&config)?;
+            let mut violations = FieldViolations::new();
+            check_model_config(&config, "config", &mut violations);
+            check_shadow_target(id, &config, &mut violations);
+            violations.into_result()?;
+
             model.set_config(config);
         }
 
@@ -188,20 +203,23 @@ impl<S: Storage<Model>> ModelService<S> {
         ModelId::new(id).map_err(|e| self.validation_error_to_domain(e))
     }
 
-    /// Validate a model configuration
-    fn validate_config(&self, config: &ModelConfig) -> Result<(), DomainError> {
-        validate_model_config(config).map_err(|e| self.validation_error_to_domain(e))
-    }
-
     /// Convert ModelValidationError to DomainError
     fn validation_error_to_domain(&self, error: ModelValidationError) -> DomainError {
         DomainError::validation(error.to_string())
     }
 }
 
+/// A model cannot mirror its requests to itself
+fn check_shadow_target(model_id: &str, config: &ModelConfig, violations: &mut FieldViolations) {
+    if config.shadow.as_ref().is_some_and(|shadow| shadow.model_id == model_id) {
+        violations.push("config.shadow.model_id", "A model cannot shadow itself");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::model::ShadowConfig;
     use crate::domain::storage::mock::MockStorage;
 
     fn create_service() -> ModelService<MockStorage<Model>> {
@@ -218,6 +236,7 @@ mod tests {
             credential_id: "openai-cred".to_string(),
             config: Some(ModelConfig::new().with_temperature(0.7)),
             enabled: true,
+            lineage: None,
         }
     }
 
@@ -258,12 +277,26 @@ mod tests {
             credential_id: "openai-cred".to_string(),
             config: None,
             enabled: true,
+            lineage: None,
         };
 
         let result = service.create(request).await;
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_create_model_shadowing_itself() {
+        let service = create_service();
+        let mut request = create_request("primary");
+        request.config = Some(ModelConfig::new().with_shadow(ShadowConfig::new("primary", 10)));
+
+        let error = service.create(request.clone()).await.unwrap_err();
+        assert!(error.to_string().contains("config.shadow.model_id"));
+
+        request.config = Some(ModelConfig::new().with_shadow(ShadowConfig::new("candidate", 10)));
+        assert!(service.create(request).await.is_ok());
+    }
+
     #[tokio::test]
     async fn test_create_model_invalid_config() {
         let service = create_service();
@@ -276,12 +309,34 @@ mod tests {
             credential_id: "openai-cred".to_string(),
             config: Some(ModelConfig::new().with_temperature(5.0)), // Invalid temp
             enabled: true,
+            lineage: None,
         };
 
         let result = service.create(request).await;
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_create_model_reports_every_invalid_field() {
+        let service = create_service();
+        let mut request = create_request("invalid_id!");
+        request.config = Some(
+            ModelConfig::new()
+                .with_temperature(5.0)
+                .with_top_p(1.5),
+        );
+
+        let fields = match service.create(request).await.unwrap_err() {
+            DomainError::InvalidFields { violations } => violations
+                .into_iter()
+                .map(|v| v.field)
+                .collect::<Vec<_>>(),
+            other => panic!("unexpected error: {other}"),
+        };
+
+        assert_eq!(fields, vec!["id", "config.temperature", "config.top_p"]);
+    }
+
     #[tokio::test]
     async fn test_get_model() {
         let service = create_service();
@@ -1,28 +1,42 @@
 //! Operation service for managing async operations
 
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use async_trait::async_trait;
 use chrono::Utc;
 use serde_json::Value;
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, instrument, warn};
 
 use crate::domain::error::DomainError;
-use crate::domain::operation::{Operation, OperationId, OperationRepository, OperationType};
+use crate::domain::operation::{
+    Operation, OperationId, OperationRepository, OperationStatus, OperationType,
+};
 
 /// Operation service configuration
 #[derive(Debug, Clone)]
 pub struct OperationServiceConfig {
     /// How long to keep completed operations before cleanup
     pub retention_duration: Duration,
+    /// Identity of this replica, recorded as the owner of the operations it runs
+    pub replica_id: String,
+    /// How long a running operation may go without heartbeat before it is
+    /// considered orphaned
+    pub lease_duration: Duration,
+    /// Requeues of an orphaned operation before it is failed instead
+    pub max_attempts: u32,
 }
 
 impl Default for OperationServiceConfig {
     fn default() -> Self {
         Self {
             retention_duration: Duration::from_secs(3600), // 1 hour
+            replica_id: format!("replica-{}", uuid::Uuid::new_v4()),
+            lease_duration: Duration::from_secs(60),
+            max_attempts: 3,
         }
     }
 }
@@ -30,10 +44,36 @@ impl Default for OperationServiceConfig {
 impl OperationServiceConfig {
     /// Create config with custom retention duration
     pub fn with_retention(retention_duration: Duration) -> Self {
-        Self { retention_duration }
+        Self {
+            retention_duration,
+            ..Self::default()
+        }
+    }
+
+    /// Run operations as the replica `replica_id`
+    pub fn with_replica_id(mut self, replica_id: impl Into<String>) -> Self {
+        self.replica_id = replica_id.into();
+        self
+    }
+
+    /// Consider operations orphaned after `lease_duration` without heartbeat,
+    /// requeuing them up to `max_attempts` times
+    pub fn with_lease(mut self, lease_duration: Duration, max_attempts: u32) -> Self {
+        self.lease_duration = lease_duration;
+        self.max_attempts = max_attempts;
+        self
     }
 }
 
+/// Outcome of a reaping of orphaned operations
+#[derive(Debug, Clone, Default)]
+pub struct ReapedOperations {
+    /// Operations put back to pending, to be run again
+    pub requeued: Vec<Operation>,
+    /// Operations failed, not requeueable or out of attempts
+    pub failed: Vec<Operation>,
+}
+
 /// Trait for operation service (for dynamic dispatch in AppState)
 #[async_trait]
 pub trait OperationServiceTrait: Send + Sync + Debug {
@@ -54,17 +94,55 @@ pub trait OperationServiceTrait: Send + Sync + Debug {
     /// Mark an operation as running
     async fn mark_running(&self, id: &str) -> Result<Operation, DomainError>;
 
-    /// Mark an operation as completed with result
+    /// Replace the progress of a running operation
+    async fn update_progress(&self, id: &str, progress: Value) -> Result<Operation, DomainError>;
+
+    /// Record the units of work done by a running operation
+    async fn update_completion(
+        &self,
+        id: &str,
+        completed: u64,
+        total: u64,
+        unit: &str,
+    ) -> Result<Operation, DomainError>;
+
+    /// Mark an operation as completed with result; cancelled operations
+    /// stay cancelled
     async fn mark_completed(&self, id: &str, result: Value) -> Result<Operation, DomainError>;
 
-    /// Mark an operation as failed with error message
+    /// Mark an operation as failed with error message; cancelled
+    /// operations stay cancelled
     async fn mark_failed(&self, id: &str, error: String) -> Result<Operation, DomainError>;
 
-    /// Cancel an operation
+    /// Cancel an operation, firing its cancellation token on this replica.
+    /// Tokens are per replica: another replica running the operation fires
+    /// its own at its next `heartbeat`.
     async fn cancel(&self, id: &str) -> Result<Operation, DomainError>;
 
+    /// Token fired when the operation is cancelled; the work of an
+    /// operation stops at its next check of the token
+    fn cancellation_token(&self, id: &str) -> CancellationToken;
+
+    /// Refresh the heartbeat of the operations this replica runs, returning
+    /// how many are still running. Operations found cancelled have their
+    /// cancellation token fired.
+    async fn heartbeat(&self) -> Result<usize, DomainError>;
+
+    /// Requeue the orphaned operations of the `requeueable` types, failing
+    /// the others and those out of attempts
+    async fn reap_orphaned(
+        &self,
+        requeueable: &[OperationType],
+    ) -> Result<ReapedOperations, DomainError>;
+
     /// Clean up old completed operations
     async fn cleanup_old(&self) -> Result<u64, DomainError>;
+
+    /// List every operation, whatever its status
+    async fn list(&self) -> Result<Vec<Operation>, DomainError>;
+
+    /// Delete an operation
+    async fn delete(&self, id: &str) -> Result<bool, DomainError>;
 }
 
 /// Operation service implementation
@@ -72,20 +150,62 @@ pub trait OperationServiceTrait: Send + Sync + Debug {
 pub struct OperationService<R: OperationRepository> {
     repository: Arc<R>,
     config: OperationServiceConfig,
+    /// Cancellation tokens of the unfinished operations, by operation ID
+    cancellations: Mutex<HashMap<String, CancellationToken>>,
+    /// Operations running on this replica, kept alive by `heartbeat`
+    running: Mutex<HashSet<String>>,
 }
 
 impl<R: OperationRepository> OperationService<R> {
     /// Create a new operation service
     pub fn new(repository: Arc<R>) -> Self {
+        Self::with_config(repository, OperationServiceConfig::default())
+    }
+
+    /// Create with custom configuration
+    pub fn with_config(repository: Arc<R>, config: OperationServiceConfig) -> Self {
         Self {
             repository,
-            config: OperationServiceConfig::default(),
+            config,
+            cancellations: Mutex::new(HashMap::new()),
+            running: Mutex::new(HashSet::new()),
         }
     }
 
-    /// Create with custom configuration
-    pub fn with_config(repository: Arc<R>, config: OperationServiceConfig) -> Self {
-        Self { repository, config }
+    /// Forget the cancellation token of a finished operation and stop its
+    /// heartbeat
+    fn release_token(&self, id: &str) -> Option<CancellationToken> {
+        self.running_ids().remove(id);
+        self.cancellations
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(id)
+    }
+
+    /// Stop the work of a cancelled operation running on this replica
+    fn fire_cancellation(&self, id: &str) {
+        if let Some(token) = self.release_token(id) {
+            token.cancel();
+        }
+    }
+
+    /// Refuse to finish an operation cancelled meanwhile, possibly through
+    /// another replica, stopping its work here
+    fn ensure_not_cancelled(&self, operation: &Operation) -> Result<(), DomainError> {
+        if operation.status() != OperationStatus::Cancelled {
+            return Ok(());
+        }
+
+        let id = operation.id().as_str();
+        self.fire_cancellation(id);
+        Err(DomainError::validation(format!(
+            "Operation '{}' was cancelled",
+            id
+        )))
+    }
+
+    fn running_ids(&self) -> std::sync::MutexGuard<'_, HashSet<String>> {
+        self.running.lock().unwrap_or_else(|e| e.into_inner())
     }
 
     /// Parse an operation ID string
@@ -146,24 +266,50 @@ impl<R: OperationRepository> OperationServiceTrait for OperationService<R> {
         let mut operation = self.get_required(id).await?;
 
         operation
-            .mark_running()
+            .mark_running_on(&self.config.replica_id)
             .map_err(|e| DomainError::validation(e.to_string()))?;
 
         let updated = self.repository.update(&operation).await?;
+        self.running_ids().insert(id.to_string());
         debug!(operation_id = %id, "Marked operation as running");
 
         Ok(updated)
     }
 
+    #[instrument(skip(self, progress))]
+    async fn update_progress(&self, id: &str, progress: Value) -> Result<Operation, DomainError> {
+        let mut operation = self.get_required(id).await?;
+        operation
+            .set_progress(progress)
+            .map_err(|e| DomainError::validation(e.to_string()))?;
+        self.repository.update(&operation).await
+    }
+
+    async fn update_completion(
+        &self,
+        id: &str,
+        completed: u64,
+        total: u64,
+        unit: &str,
+    ) -> Result<Operation, DomainError> {
+        let mut operation = self.get_required(id).await?;
+        operation
+            .set_completion(completed, total, unit)
+            .map_err(|e| DomainError::validation(e.to_string()))?;
+        self.repository.update(&operation).await
+    }
+
     #[instrument(skip(self, result))]
     async fn mark_completed(&self, id: &str, result: Value) -> Result<Operation, DomainError> {
         let mut operation = self.get_required(id).await?;
+        self.ensure_not_cancelled(&operation)?;
 
         operation
             .mark_completed(result)
             .map_err(|e| DomainError::validation(e.to_string()))?;
 
         let updated = self.repository.update(&operation).await?;
+        self.release_token(id);
         info!(operation_id = %id, "Marked operation as completed");
 
         Ok(updated)
@@ -172,12 +318,14 @@ impl<R: OperationRepository> OperationServiceTrait for OperationService<R> {
     #[instrument(skip(self))]
     async fn mark_failed(&self, id: &str, error: String) -> Result<Operation, DomainError> {
         let mut operation = self.get_required(id).await?;
+        self.ensure_not_cancelled(&operation)?;
 
         operation
             .mark_failed(&error)
             .map_err(|e| DomainError::validation(e.to_string()))?;
 
         let updated = self.repository.update(&operation).await?;
+        self.release_token(id);
         warn!(operation_id = %id, error = %error, "Marked operation as failed");
 
         Ok(updated)
@@ -192,11 +340,97 @@ impl<R: OperationRepository> OperationServiceTrait for OperationService<R> {
             .map_err(|e| DomainError::validation(e.to_string()))?;
 
         let updated = self.repository.update(&operation).await?;
+        self.fire_cancellation(id);
         info!(operation_id = %id, "Cancelled operation");
 
         Ok(updated)
     }
 
+    fn cancellation_token(&self, id: &str) -> CancellationToken {
+        self.cancellations
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(id.to_string())
+            .or_default()
+            .clone()
+    }
+
+    async fn heartbeat(&self) -> Result<usize, DomainError> {
+        let ids: Vec<String> = self.running_ids().iter().cloned().collect();
+
+        for id in &ids {
+            let beat = match self.get_required(id).await {
+                // Cancelled through another replica
+                Ok(operation) if operation.status() == OperationStatus::Cancelled => {
+                    info!(operation_id = %id, "Operation cancelled, stopping its work");
+                    self.fire_cancellation(id);
+                    Ok(false)
+                }
+                Ok(mut operation) if operation.owner() == Some(&self.config.replica_id) => {
+                    match operation.heartbeat() {
+                        Ok(()) => self.repository.update(&operation).await.map(|_| true),
+                        Err(_) => Ok(false),
+                    }
+                }
+                // Finished, requeued or deleted elsewhere
+                Ok(_) | Err(DomainError::NotFound { .. }) => Ok(false),
+                Err(e) => Err(e),
+            };
+
+            match beat {
+                Ok(true) => {}
+                Ok(false) => {
+                    self.running_ids().remove(id);
+                }
+                Err(e) => warn!(operation_id = %id, error = %e, "Failed to record operation heartbeat"),
+            }
+        }
+
+        Ok(self.running_ids().len())
+    }
+
+    #[instrument(skip(self))]
+    async fn reap_orphaned(
+        &self,
+        requeueable: &[OperationType],
+    ) -> Result<ReapedOperations, DomainError> {
+        let lease = chrono::Duration::from_std(self.config.lease_duration)
+            .unwrap_or(chrono::Duration::seconds(60));
+        let now = Utc::now();
+        let mut reaped = ReapedOperations::default();
+
+        for mut operation in self.repository.list_by_status(OperationStatus::Running).await? {
+            let id = operation.id().to_string();
+            if !operation.is_orphaned(lease, now) || self.running_ids().contains(&id) {
+                continue;
+            }
+            let owner = operation.owner().unwrap_or("unknown").to_string();
+
+            if requeueable.contains(&operation.operation_type())
+                && operation.attempts() < self.config.max_attempts
+            {
+                operation
+                    .requeue()
+                    .map_err(|e| DomainError::validation(e.to_string()))?;
+                let requeued = self.repository.update(&operation).await?;
+                warn!(operation_id = %id, owner = %owner, attempts = requeued.attempts(), "Requeued orphaned operation");
+                reaped.requeued.push(requeued);
+            } else {
+                operation
+                    .mark_failed(format!(
+                        "Operation orphaned: replica '{}' stopped running it",
+                        owner
+                    ))
+                    .map_err(|e| DomainError::validation(e.to_string()))?;
+                let failed = self.repository.update(&operation).await?;
+                warn!(operation_id = %id, owner = %owner, "Failed orphaned operation");
+                reaped.failed.push(failed);
+            }
+        }
+
+        Ok(reaped)
+    }
+
     #[instrument(skip(self))]
     async fn cleanup_old(&self) -> Result<u64, DomainError> {
         let cutoff = Utc::now() - chrono::Duration::from_std(self.config.retention_duration)
@@ -210,12 +444,41 @@ impl<R: OperationRepository> OperationServiceTrait for OperationService<R> {
 
         Ok(deleted)
     }
+
+    #[instrument(skip(self))]
+    async fn list(&self) -> Result<Vec<Operation>, DomainError> {
+        let mut operations = Vec::new();
+
+        for status in [
+            OperationStatus::Pending,
+            OperationStatus::Running,
+            OperationStatus::Completed,
+            OperationStatus::Failed,
+            OperationStatus::Cancelled,
+        ] {
+            operations.extend(self.repository.list_by_status(status).await?);
+        }
+
+        Ok(operations)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        let op_id = self.parse_id(id)?;
+        let deleted = self.repository.delete(&op_id).await?;
+        self.release_token(id);
+
+        if deleted {
+            debug!(operation_id = %id, "Deleted operation");
+        }
+
+        Ok(deleted)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::operation::OperationStatus;
     use crate::infrastructure::operation::InMemoryOperationRepository;
     use serde_json::json;
 
@@ -325,6 +588,30 @@ mod tests {
         assert_eq!(completed.result(), Some(&json!({"response": "hello"})));
     }
 
+    #[tokio::test]
+    async fn test_update_progress() {
+        let service = create_test_service();
+        let created = service
+            .create_pending(OperationType::KnowledgeBaseIngestion, json!({}), json!({}))
+            .await
+            .unwrap();
+        let id = created.id().as_str();
+
+        // Pending operations have no progress to report yet
+        assert!(service.update_progress(id, json!({"done": 0})).await.is_err());
+
+        service.mark_running(id).await.unwrap();
+        let updated = service
+            .update_progress(id, json!({"done": 1}))
+            .await
+            .expect("update_progress should succeed");
+        assert_eq!(updated.progress(), Some(&json!({"done": 1})));
+
+        // Progress is kept once completed
+        let completed = service.mark_completed(id, json!({})).await.unwrap();
+        assert_eq!(completed.progress(), Some(&json!({"done": 1})));
+    }
+
     #[tokio::test]
     async fn test_mark_failed() {
         let service = create_test_service();
@@ -379,6 +666,136 @@ mod tests {
         assert_eq!(cancelled.status(), OperationStatus::Cancelled);
     }
 
+    #[tokio::test]
+    async fn test_cancel_fires_token() {
+        let service = create_test_service();
+
+        let created = service
+            .create_pending(OperationType::ChatCompletion, json!({}), json!({}))
+            .await
+            .unwrap();
+        let id = created.id().as_str();
+
+        let token = service.cancellation_token(id);
+        service.mark_running(id).await.unwrap();
+        assert!(!token.is_cancelled());
+
+        service.cancel(id).await.unwrap();
+        assert!(token.is_cancelled());
+
+        // Finished operations release their token without firing it
+        let other = service
+            .create_pending(OperationType::ChatCompletion, json!({}), json!({}))
+            .await
+            .unwrap();
+        let token = service.cancellation_token(other.id().as_str());
+        service.mark_running(other.id().as_str()).await.unwrap();
+        service
+            .mark_completed(other.id().as_str(), json!({}))
+            .await
+            .unwrap();
+        assert!(!token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_through_another_replica() {
+        let repo = Arc::new(InMemoryOperationRepository::new());
+        let runner = OperationService::with_config(
+            repo.clone(),
+            OperationServiceConfig::default().with_replica_id("replica-1"),
+        );
+        let other = OperationService::with_config(
+            repo,
+            OperationServiceConfig::default().with_replica_id("replica-2"),
+        );
+
+        let created = runner
+            .create_pending(OperationType::WorkflowExecution, json!({}), json!({}))
+            .await
+            .unwrap();
+        let id = created.id().as_str();
+        let token = runner.cancellation_token(id);
+        runner.mark_running(id).await.unwrap();
+
+        other.cancel(id).await.unwrap();
+        assert!(!token.is_cancelled());
+
+        // Finishing the work keeps the operation cancelled
+        assert!(runner.mark_completed(id, json!({})).await.is_err());
+        assert!(token.is_cancelled());
+        assert_eq!(
+            runner.get(id).await.unwrap().unwrap().status(),
+            OperationStatus::Cancelled
+        );
+
+        // The heartbeat fires the token of work still running
+        let created = runner
+            .create_pending(OperationType::WorkflowExecution, json!({}), json!({}))
+            .await
+            .unwrap();
+        let id = created.id().as_str();
+        let token = runner.cancellation_token(id);
+        runner.mark_running(id).await.unwrap();
+
+        other.cancel(id).await.unwrap();
+        assert_eq!(runner.heartbeat().await.unwrap(), 0);
+        assert!(token.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_reap_orphaned() {
+        let repo = Arc::new(InMemoryOperationRepository::new());
+        let config = OperationServiceConfig::default().with_lease(Duration::ZERO, 1);
+        let dead = OperationService::with_config(
+            repo.clone(),
+            config.clone().with_replica_id("replica-dead"),
+        );
+        let leader = OperationService::with_config(repo, config.with_replica_id("replica-leader"));
+
+        let workflow = dead
+            .create_pending(OperationType::WorkflowExecution, json!({}), json!({}))
+            .await
+            .unwrap();
+        let chat = dead
+            .create_pending(OperationType::ChatCompletion, json!({}), json!({}))
+            .await
+            .unwrap();
+        let workflow_id = workflow.id().as_str();
+        dead.mark_running(workflow_id).await.unwrap();
+        dead.mark_running(chat.id().as_str()).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        // Operations running on the reaping replica are alive
+        assert!(dead
+            .reap_orphaned(&[OperationType::WorkflowExecution])
+            .await
+            .unwrap()
+            .requeued
+            .is_empty());
+
+        let reaped = leader
+            .reap_orphaned(&[OperationType::WorkflowExecution])
+            .await
+            .unwrap();
+        assert_eq!(reaped.requeued.len(), 1);
+        assert_eq!(reaped.requeued[0].status(), OperationStatus::Pending);
+        assert_eq!(reaped.failed.len(), 1);
+        assert_eq!(reaped.failed[0].id(), chat.id());
+
+        // The dead replica no longer owns them
+        assert_eq!(dead.heartbeat().await.unwrap(), 0);
+
+        // Out of attempts once orphaned again
+        dead.mark_running(workflow_id).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let reaped = leader
+            .reap_orphaned(&[OperationType::WorkflowExecution])
+            .await
+            .unwrap();
+        assert!(reaped.requeued.is_empty());
+        assert_eq!(reaped.failed[0].status(), OperationStatus::Failed);
+    }
+
     #[tokio::test]
     async fn test_cannot_cancel_completed() {
         let service = create_test_service();
@@ -433,4 +850,28 @@ mod tests {
         let deleted = service.cleanup_old().await.expect("cleanup should succeed");
         assert_eq!(deleted, 0);
     }
+
+    #[tokio::test]
+    async fn test_list_and_delete() {
+        let service = create_test_service();
+
+        let pending = service
+            .create_pending(OperationType::ChatCompletion, json!({}), json!({}))
+            .await
+            .unwrap();
+        let running = service
+            .create_pending(OperationType::WorkflowExecution, json!({}), json!({}))
+            .await
+            .unwrap();
+        service.mark_running(running.id().as_str()).await.unwrap();
+
+        assert_eq!(service.list().await.unwrap().len(), 2);
+
+        assert!(service.delete(pending.id().as_str()).await.unwrap());
+        assert!(!service.delete(pending.id().as_str()).await.unwrap());
+
+        let remaining = service.list().await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id(), running.id());
+    }
 }
@@ -1,12 +1,17 @@
 //! Prompt service - CRUD operations and rendering for prompts
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
+use crate::domain::cache::CachePolicy;
+use crate::domain::prompt::{
+    check_variable_declarations, expand_partials, normalize_locale, partial_references,
+    validate_locale, MAX_PARTIAL_DEPTH,
+};
 use crate::domain::storage::Storage;
 use crate::domain::{
-    DomainError, ModelValidationError, Prompt, PromptId, PromptOutputSchema, PromptTemplate,
-    TemplateError,
+    DomainError, FieldViolations, ModelValidationError, Prompt, PromptId, PromptOutputSchema,
+    PromptTemplate, PromptVariable, TemplateError,
 };
 
 /// Request to create a new prompt
@@ -20,6 +25,10 @@ pub struct CreatePromptRequest {
     pub enabled: bool,
     pub max_history: Option<usize>,
     pub output_schema: Option<PromptOutputSchema>,
+    pub cache_policy: Option<CachePolicy>,
+    pub variables: Vec<PromptVariable>,
+    /// Content variants by locale tag
+    pub locales: BTreeMap<String, String>,
 }
 
 /// Request to update an existing prompt
@@ -29,15 +38,26 @@ pub struct UpdatePromptRequest {
     pub description: Option<String>,
     pub content: Option<String>,
     pub content_message: Option<String>,
+    /// Who is changing the content, recorded on the new version
+    pub content_author: Option<String>,
     pub tags: Option<Vec<String>>,
     pub enabled: Option<bool>,
     pub output_schema: Option<PromptOutputSchema>,
+    pub cache_policy: Option<CachePolicy>,
+    pub variables: Option<Vec<PromptVariable>>,
+    /// Replaces the locale variants as a whole
+    pub locales: Option<BTreeMap<String, String>>,
 }
 
 /// Request to render a prompt
 #[derive(Debug, Clone)]
 pub struct RenderPromptRequest {
     pub prompt_id: String,
+    /// Retained version to render instead of the current one
+    pub version: Option<u32>,
+    /// Accepted locales, `Accept-Language` style; ignored with a version,
+    /// as locale variants are not versioned
+    pub locale: Option<String>,
     pub variables: HashMap<String, String>,
 }
 
@@ -46,10 +66,77 @@ pub struct RenderPromptRequest {
 pub struct RenderedPrompt {
     pub prompt_id: String,
     pub version: u32,
+    /// Locale of the rendered variant, `None` for the base content
+    pub locale: Option<String>,
     pub content: String,
     pub variables_used: Vec<String>,
 }
 
+/// Expand the `${prompt:id}` partials of `content` with the content of the
+/// referenced prompts. Missing and disabled partials, cycles (including
+/// `root_id`, the prompt being expanded) and nesting deeper than
+/// [`MAX_PARTIAL_DEPTH`] are validation errors.
+pub async fn resolve_partials<S: Storage<Prompt> + ?Sized>(
+    storage: &S,
+    root_id: Option<&str>,
+    content: &str,
+) -> Result<String, DomainError> {
+    let mut partials = HashMap::new();
+    let mut pending = partial_references(content);
+
+    // One level past the limit is fetched so the expansion reports it
+    for _ in 0..=MAX_PARTIAL_DEPTH {
+        let mut next = Vec::new();
+
+        for id in pending {
+            if partials.contains_key(&id) || root_id == Some(id.as_str()) {
+                continue;
+            }
+
+            let Ok(prompt_id) = PromptId::new(&id) else {
+                continue;
+            };
+
+            if let Some(prompt) = storage.get(&prompt_id).await?.filter(Prompt::is_enabled) {
+                next.extend(partial_references(prompt.content()));
+                partials.insert(id, prompt.content().to_string());
+            }
+        }
+
+        if next.is_empty() {
+            break;
+        }
+
+        pending = next;
+    }
+
+    expand_partials(content, root_id, &partials).map_err(|e| DomainError::validation(e.to_string()))
+}
+
+/// Check the locale tags and template syntax of locale variants, keyed
+/// `locales.{tag}`; tags equal once normalized are duplicates
+fn check_locale_variants(locales: &BTreeMap<String, String>, violations: &mut FieldViolations) {
+    let mut seen = Vec::new();
+    for (locale, content) in locales {
+        let field = format!("locales.{}", locale);
+        violations.check(&field, validate_locale(locale));
+        violations.check(&field, PromptTemplate::parse(content).map(drop));
+
+        let normalized = normalize_locale(locale);
+        if seen.contains(&normalized) {
+            violations.push(&field, format!("duplicate locale '{}'", normalized));
+        }
+        seen.push(normalized);
+    }
+}
+
+fn normalize_locale_keys(locales: BTreeMap<String, String>) -> BTreeMap<String, String> {
+    locales
+        .into_iter()
+        .map(|(locale, content)| (normalize_locale(&locale), content))
+        .collect()
+}
+
 /// Prompt service for CRUD and rendering operations
 #[derive(Debug)]
 pub struct PromptService<S: Storage<Prompt>> {
@@ -94,6 +181,16 @@ impl<S: Storage<Prompt>> PromptService<S> {
 
     /// Create a new prompt
     pub async fn create(&self, request: CreatePromptRequest) -> Result<Prompt, DomainError> {
+        let mut violations = FieldViolations::new();
+        violations.check("id", PromptId::new(request.id.as_str()).map(drop));
+        violations.check("content", PromptTemplate::parse(&request.content).map(drop));
+        if let Some(cache_policy) = &request.cache_policy {
+            cache_policy.check("cache_policy", &mut violations);
+        }
+        check_variable_declarations(&request.variables, &mut violations);
+        check_locale_variants(&request.locales, &mut violations);
+        violations.into_result()?;
+
         let prompt_id = self.parse_prompt_id(&request.id)?;
 
         // Check for duplicate
@@ -104,8 +201,11 @@ impl<S: Storage<Prompt>> PromptService<S> {
             )));
         }
 
-        // Validate template syntax
-        self.validate_template(&request.content)?;
+        // Validate partials
+        resolve_partials(self.storage.as_ref(), Some(&request.id), &request.content).await?;
+        for content in request.locales.values() {
+            resolve_partials(self.storage.as_ref(), Some(&request.id), content).await?;
+        }
 
         // Build the prompt
         let mut prompt = Prompt::new(prompt_id, request.name, request.content);
@@ -124,6 +224,14 @@ impl<S: Storage<Prompt>> PromptService<S> {
             prompt = prompt.with_output_schema(output_schema);
         }
 
+        if let Some(cache_policy) = request.cache_policy {
+            prompt = prompt.with_cache_policy(cache_policy);
+        }
+
+        prompt = prompt
+            .with_variables(request.variables)
+            .with_locales(normalize_locale_keys(request.locales));
+
         self.storage.create(prompt).await
     }
 
@@ -152,9 +260,10 @@ impl<S: Storage<Prompt>> PromptService<S> {
         }
 
         if let Some(content) = request.content {
-            // Validate template syntax
+            // Validate template syntax and partials
             self.validate_template(&content)?;
-            prompt.set_content(content, request.content_message);
+            resolve_partials(self.storage.as_ref(), Some(id), &content).await?;
+            prompt.set_content_by(content, request.content_message, request.content_author);
         }
 
         if let Some(tags) = request.tags {
@@ -169,6 +278,30 @@ impl<S: Storage<Prompt>> PromptService<S> {
             prompt.set_output_schema(Some(output_schema));
         }
 
+        if let Some(cache_policy) = request.cache_policy {
+            let mut violations = FieldViolations::new();
+            cache_policy.check("cache_policy", &mut violations);
+            violations.into_result()?;
+            prompt.set_cache_policy(Some(cache_policy));
+        }
+
+        if let Some(variables) = request.variables {
+            let mut violations = FieldViolations::new();
+            check_variable_declarations(&variables, &mut violations);
+            violations.into_result()?;
+            prompt.set_variables(variables);
+        }
+
+        if let Some(locales) = request.locales {
+            let mut violations = FieldViolations::new();
+            check_locale_variants(&locales, &mut violations);
+            violations.into_result()?;
+            for content in locales.values() {
+                resolve_partials(self.storage.as_ref(), Some(id), content).await?;
+            }
+            prompt.set_locales(normalize_locale_keys(locales));
+        }
+
         self.storage.update(prompt).await
     }
 
@@ -189,8 +322,27 @@ impl<S: Storage<Prompt>> PromptService<S> {
             )));
         }
 
-        let template = PromptTemplate::parse(prompt.content())
-            .map_err(|e| DomainError::validation(e.to_string()))?;
+        let (version, locale, content) = match request.version {
+            Some(version) => {
+                let snapshot = prompt.version_snapshot(version).ok_or_else(|| {
+                    DomainError::not_found(format!(
+                        "Version {} of prompt '{}' not found",
+                        version, request.prompt_id
+                    ))
+                })?;
+                (version, None, snapshot.content().to_string())
+            }
+            None => {
+                let (locale, content) = prompt.localized_content(request.locale.as_deref());
+                (prompt.version(), locale.map(String::from), content.to_string())
+            }
+        };
+
+        let content =
+            resolve_partials(self.storage.as_ref(), Some(&request.prompt_id), &content).await?;
+        let template = PromptTemplate::parse(content)
+            .map_err(|e| DomainError::validation(e.to_string()))?
+            .with_declarations(prompt.variables());
 
         let variables_used: Vec<String> = template
             .variables()
@@ -204,7 +356,8 @@ impl<S: Storage<Prompt>> PromptService<S> {
 
         Ok(RenderedPrompt {
             prompt_id: request.prompt_id,
-            version: prompt.version(),
+            version,
+            locale,
             content,
             variables_used,
         })
@@ -215,10 +368,24 @@ impl<S: Storage<Prompt>> PromptService<S> {
         &self,
         id: &str,
         variables: HashMap<String, String>,
+    ) -> Result<String, DomainError> {
+        self.render_variant(id, None, None, variables).await
+    }
+
+    /// Render a retained version of a prompt, or the current one in the
+    /// variant best matching the accepted locales
+    pub async fn render_variant(
+        &self,
+        id: &str,
+        version: Option<u32>,
+        locale: Option<&str>,
+        variables: HashMap<String, String>,
     ) -> Result<String, DomainError> {
         let result = self
             .render(RenderPromptRequest {
                 prompt_id: id.to_string(),
+                version,
+                locale: locale.map(String::from),
                 variables,
             })
             .await?;
@@ -226,11 +393,17 @@ impl<S: Storage<Prompt>> PromptService<S> {
         Ok(result.content)
     }
 
-    /// Get variables from a prompt
+    /// Expand the partials of ad-hoc template content
+    pub async fn expand_partials(&self, content: &str) -> Result<String, DomainError> {
+        resolve_partials(self.storage.as_ref(), None, content).await
+    }
+
+    /// Get variables from a prompt, including those of its partials
     pub async fn get_variables(&self, id: &str) -> Result<Vec<String>, DomainError> {
         let prompt = self.get_required(id).await?;
+        let content = resolve_partials(self.storage.as_ref(), Some(id), prompt.content()).await?;
 
-        let template = PromptTemplate::parse(prompt.content())
+        let template = PromptTemplate::parse(content)
             .map_err(|e| DomainError::validation(e.to_string()))?;
 
         Ok(template
@@ -240,6 +413,19 @@ impl<S: Storage<Prompt>> PromptService<S> {
             .collect())
     }
 
+    /// Variables of a prompt, including those of its partials, completed by
+    /// the prompt's declarations
+    pub async fn variable_schema(&self, id: &str) -> Result<Vec<PromptVariable>, DomainError> {
+        let prompt = self.get_required(id).await?;
+        let content = resolve_partials(self.storage.as_ref(), Some(id), prompt.content()).await?;
+
+        let template = PromptTemplate::parse(content)
+            .map_err(|e| DomainError::validation(e.to_string()))?
+            .with_declarations(prompt.variables());
+
+        Ok(template.variables().to_vec())
+    }
+
     /// Revert a prompt to a previous version
     pub async fn revert(&self, id: &str, version: u32) -> Result<Prompt, DomainError> {
         let prompt_id = self.parse_prompt_id(id)?;
@@ -269,9 +455,13 @@ impl<S: Storage<Prompt>> PromptService<S> {
                 description: None,
                 content: None,
                 content_message: None,
+                content_author: None,
                 tags: None,
                 enabled: Some(true),
                 output_schema: None,
+                cache_policy: None,
+                variables: None,
+                locales: None,
             },
         )
         .await
@@ -286,9 +476,13 @@ impl<S: Storage<Prompt>> PromptService<S> {
                 description: None,
                 content: None,
                 content_message: None,
+                content_author: None,
                 tags: None,
                 enabled: Some(false),
                 output_schema: None,
+                cache_policy: None,
+                variables: None,
+                locales: None,
             },
         )
         .await
@@ -340,6 +534,9 @@ mod tests {
             enabled: true,
             max_history: Some(5),
             output_schema: None,
+            cache_policy: None,
+            variables: Vec::new(),
+            locales: BTreeMap::new(),
         }
     }
 
@@ -379,6 +576,9 @@ mod tests {
             enabled: true,
             max_history: None,
             output_schema: None,
+            cache_policy: None,
+            variables: Vec::new(),
+            locales: BTreeMap::new(),
         };
 
         let result = service.create(request).await;
@@ -396,6 +596,8 @@ mod tests {
         let rendered = service
             .render(RenderPromptRequest {
                 prompt_id: "render-test".to_string(),
+                version: None,
+                locale: None,
                 variables,
             })
             .await
@@ -413,6 +615,8 @@ mod tests {
         let rendered = service
             .render(RenderPromptRequest {
                 prompt_id: "default-test".to_string(),
+                version: None,
+                locale: None,
                 variables: HashMap::new(),
             })
             .await
@@ -421,6 +625,160 @@ mod tests {
         assert_eq!(rendered.content, "You are a helpful assistant.");
     }
 
+    #[tokio::test]
+    async fn test_render_prompt_version() {
+        let service = create_service();
+        service.create(create_request("versioned")).await.unwrap();
+
+        service
+            .update(
+                "versioned",
+                UpdatePromptRequest {
+                    name: None,
+                    description: None,
+                    content: Some("You are a terse ${var:role:assistant}.".to_string()),
+                    content_message: None,
+                    content_author: None,
+                    tags: None,
+                    enabled: None,
+                    output_schema: None,
+                    cache_policy: None,
+                    variables: None,
+                    locales: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let variables = HashMap::from([("role".to_string(), "critic".to_string())]);
+        let current = service
+            .render_variant("versioned", None, None, variables.clone())
+            .await
+            .unwrap();
+        let first = service
+            .render_variant("versioned", Some(1), None, variables.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(current, "You are a terse critic.");
+        assert_eq!(first, "You are a helpful critic.");
+        assert!(service
+            .render_variant("versioned", Some(7), None, variables)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_render_prompt_locale() {
+        let service = create_service();
+        let mut request = create_request("localized");
+        request.locales = BTreeMap::from([
+            ("es".to_string(), "Eres un ${var:role:asistente} útil.".to_string()),
+            ("pt_BR".to_string(), "Você é um ${var:role:assistente} útil.".to_string()),
+        ]);
+        let prompt = service.create(request).await.unwrap();
+        assert!(prompt.locales().contains_key("pt-br"));
+
+        let render = |locale: Option<&str>, version: Option<u32>| RenderPromptRequest {
+            prompt_id: "localized".to_string(),
+            version,
+            locale: locale.map(String::from),
+            variables: HashMap::new(),
+        };
+
+        let rendered = service.render(render(Some("es-AR"), None)).await.unwrap();
+        assert_eq!(rendered.locale.as_deref(), Some("es"));
+        assert_eq!(rendered.content, "Eres un asistente útil.");
+
+        let rendered = service.render(render(Some("fr, pt-BR;q=0.7"), None)).await.unwrap();
+        assert_eq!(rendered.content, "Você é um assistente útil.");
+
+        let rendered = service.render(render(Some("de"), None)).await.unwrap();
+        assert_eq!(rendered.locale, None);
+        assert_eq!(rendered.content, "You are a helpful assistant.");
+
+        let rendered = service.render(render(Some("es"), Some(1))).await.unwrap();
+        assert_eq!(rendered.content, "You are a helpful assistant.");
+    }
+
+    #[tokio::test]
+    async fn test_create_prompt_invalid_locales() {
+        let service = create_service();
+        let mut request = create_request("bad-locales");
+        request.locales = BTreeMap::from([
+            ("e".to_string(), "Hola".to_string()),
+            ("pt-BR".to_string(), "Olá".to_string()),
+            ("pt_br".to_string(), "Olá".to_string()),
+        ]);
+
+        let err = service.create(request).await.unwrap_err().to_string();
+        assert!(err.contains("locales.e"));
+        assert!(err.contains("locales.pt_br"));
+        assert!(err.contains("duplicate locale 'pt-br'"));
+    }
+
+    #[tokio::test]
+    async fn test_render_typed_variables() {
+        use crate::domain::VariableType;
+
+        let service = create_service();
+        let mut request = create_request("typed");
+        request.content = "Reply in ${var:tone} with at most ${var:words} words.${var:extra}".to_string();
+        request.variables = vec![
+            PromptVariable::required("tone")
+                .with_type(VariableType::Enum)
+                .with_values(vec!["formal".to_string(), "casual".to_string()])
+                .with_description("Register of the answer"),
+            PromptVariable::required("words").with_type(VariableType::Number),
+            PromptVariable::optional("extra"),
+        ];
+        service.create(request).await.unwrap();
+
+        let variables = |tone: &str, words: &str| {
+            HashMap::from([
+                ("tone".to_string(), tone.to_string()),
+                ("words".to_string(), words.to_string()),
+            ])
+        };
+
+        let rendered = service
+            .render_by_id("typed", variables("formal", "50"))
+            .await
+            .unwrap();
+        assert_eq!(rendered, "Reply in formal with at most 50 words.");
+
+        let err = service
+            .render_by_id("typed", variables("rude", "50"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("'rude' is not one of formal, casual"));
+        assert!(service
+            .render_by_id("typed", variables("casual", "many"))
+            .await
+            .is_err());
+
+        let schema = service.variable_schema("typed").await.unwrap();
+        assert_eq!(schema[0].var_type, VariableType::Enum);
+        assert_eq!(schema[0].description.as_deref(), Some("Register of the answer"));
+        assert!(!schema[2].required);
+    }
+
+    #[tokio::test]
+    async fn test_create_prompt_invalid_variables() {
+        use crate::domain::VariableType;
+
+        let service = create_service();
+        let mut request = create_request("bad-vars");
+        request.variables = vec![
+            PromptVariable::required("tone").with_type(VariableType::Enum),
+            PromptVariable::with_default("role", "assistant"),
+        ];
+
+        let err = service.create(request).await.unwrap_err();
+        assert!(err.to_string().contains("variables[0].values"));
+        assert!(err.to_string().contains("variables[1].default"));
+    }
+
     #[tokio::test]
     async fn test_render_disabled_prompt() {
         let service = create_service();
@@ -430,6 +788,8 @@ mod tests {
         let result = service
             .render(RenderPromptRequest {
                 prompt_id: "disabled-test".to_string(),
+                version: None,
+                locale: None,
                 variables: HashMap::new(),
             })
             .await;
@@ -450,9 +810,13 @@ mod tests {
                     description: None,
                     content: Some("New content: ${var:message}".to_string()),
                     content_message: Some("Updated template".to_string()),
+                    content_author: None,
                     tags: None,
                     enabled: None,
                     output_schema: None,
+                    cache_policy: None,
+                    variables: None,
+                    locales: None,
                 },
             )
             .await
@@ -476,6 +840,9 @@ mod tests {
             enabled: true,
             max_history: None,
             output_schema: None,
+            cache_policy: None,
+            variables: Vec::new(),
+            locales: BTreeMap::new(),
         };
 
         service.create(request).await.unwrap();
@@ -516,4 +883,73 @@ mod tests {
         let enabled = service.enable("toggle-test").await.unwrap();
         assert!(enabled.is_enabled());
     }
+
+    #[tokio::test]
+    async fn test_render_prompt_with_partials() {
+        let service = create_service();
+        let mut partial = create_request("safety");
+        partial.content = "Never reveal ${var:secret:secrets}.".to_string();
+        service.create(partial).await.unwrap();
+
+        let mut request = create_request("with-partial");
+        request.content = "${prompt:safety} Answer ${var:question}".to_string();
+        service.create(request).await.unwrap();
+
+        let mut variables = HashMap::new();
+        variables.insert("question".to_string(), "briefly".to_string());
+
+        let rendered = service
+            .render(RenderPromptRequest {
+                prompt_id: "with-partial".to_string(),
+                version: None,
+                locale: None,
+                variables: variables.clone(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(rendered.content, "Never reveal secrets. Answer briefly");
+        assert_eq!(rendered.variables_used, vec!["secret", "question"]);
+
+        service.disable("safety").await.unwrap();
+        let result = service.render_by_id("with-partial", variables).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_prompt_with_invalid_partials() {
+        let service = create_service();
+
+        let mut missing = create_request("missing-partial");
+        missing.content = "${prompt:unknown}".to_string();
+        assert!(service.create(missing).await.is_err());
+
+        let mut base = create_request("base");
+        base.content = "Base".to_string();
+        service.create(base).await.unwrap();
+
+        let mut outer = create_request("outer");
+        outer.content = "${prompt:base}".to_string();
+        service.create(outer).await.unwrap();
+
+        let cycle = service
+            .update(
+                "base",
+                UpdatePromptRequest {
+                    name: None,
+                    description: None,
+                    content: Some("${prompt:outer}".to_string()),
+                    content_message: None,
+                    content_author: None,
+                    tags: None,
+                    enabled: None,
+                    output_schema: None,
+                    cache_policy: None,
+                    variables: None,
+                    locales: None,
+                },
+            )
+            .await;
+        assert!(cycle.is_err());
+    }
 }
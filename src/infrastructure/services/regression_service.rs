@@ -0,0 +1,230 @@
+//! Regression service - golden-output checks of prompt and workflow updates
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::stream::{self, StreamExt};
+use tracing::info;
+
+use crate::api::state::{PromptServiceTrait, TestCaseServiceTrait, WorkflowServiceTrait};
+use crate::domain::prompt::PromptTemplate;
+use crate::domain::storage::Storage;
+use crate::domain::test_case::{
+    CaseComparison, RegressionPolicy, RegressionReport, RegressionSide, RegressionSubject,
+    TestCase, TestCaseInput, TestCaseQuery, TestCaseType, DEFAULT_SUITE_CONCURRENCY,
+};
+use crate::domain::DomainError;
+
+use super::{ExecuteTestCaseResponse, ExecutionOverrides, UpdateWorkflowRequest};
+
+/// Runs the test cases linked to a prompt or workflow against its stored
+/// version and a pending update, and records the comparison
+pub struct RegressionService {
+    test_case_service: Arc<dyn TestCaseServiceTrait>,
+    prompt_service: Arc<dyn PromptServiceTrait>,
+    workflow_service: Arc<dyn WorkflowServiceTrait>,
+    storage: Arc<dyn Storage<RegressionReport>>,
+}
+
+impl RegressionService {
+    /// Create a new regression service
+    pub fn new(
+        test_case_service: Arc<dyn TestCaseServiceTrait>,
+        prompt_service: Arc<dyn PromptServiceTrait>,
+        workflow_service: Arc<dyn WorkflowServiceTrait>,
+        storage: Arc<dyn Storage<RegressionReport>>,
+    ) -> Self {
+        Self {
+            test_case_service,
+            prompt_service,
+            workflow_service,
+            storage,
+        }
+    }
+
+    /// Compare the linked test cases of a prompt between its stored content
+    /// and `content`. Returns `None` when the content is unchanged or no
+    /// enabled test case uses the prompt.
+    pub async fn check_prompt(
+        &self,
+        id: &str,
+        content: &str,
+        policy: &RegressionPolicy,
+    ) -> Result<Option<RegressionReport>, DomainError> {
+        let prompt = self
+            .prompt_service
+            .get(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("Prompt '{}' not found", id)))?;
+
+        if prompt.content() == content {
+            return Ok(None);
+        }
+
+        PromptTemplate::parse(content).map_err(|e| DomainError::validation(e.to_string()))?;
+
+        let query = TestCaseQuery::new()
+            .with_test_type(TestCaseType::ModelPrompt)
+            .with_enabled(true);
+        let test_cases: Vec<TestCase> = self
+            .test_case_service
+            .list(&query)
+            .await?
+            .into_iter()
+            .filter(|test_case| match test_case.input() {
+                TestCaseInput::ModelPrompt(input) => input.prompt_id.as_deref() == Some(id),
+                TestCaseInput::Workflow(_) => false,
+            })
+            .collect();
+
+        let overrides = ExecutionOverrides {
+            prompts: HashMap::from([(id.to_string(), content.to_string())]),
+            ..Default::default()
+        };
+
+        self.compare(
+            RegressionSubject::Prompt,
+            id,
+            prompt.version(),
+            prompt.version() + 1,
+            test_cases,
+            &overrides,
+            policy,
+        )
+        .await
+    }
+
+    /// Compare the linked test cases of a workflow between its stored
+    /// definition and the definition after `request`. Returns `None` when
+    /// the update does not change the steps or no enabled test case runs the
+    /// workflow.
+    pub async fn check_workflow(
+        &self,
+        id: &str,
+        request: UpdateWorkflowRequest,
+        policy: &RegressionPolicy,
+    ) -> Result<Option<RegressionReport>, DomainError> {
+        let workflow = self
+            .workflow_service
+            .get(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("Workflow '{}' not found", id)))?;
+        let candidate = self.workflow_service.preview_update(id, request).await?;
+
+        if candidate.version() == workflow.version() {
+            return Ok(None);
+        }
+
+        let query = TestCaseQuery::new()
+            .with_test_type(TestCaseType::Workflow)
+            .with_workflow_id(id)
+            .with_enabled(true);
+        let test_cases = self.test_case_service.list(&query).await?;
+
+        let candidate_version = candidate.version();
+        let overrides = ExecutionOverrides {
+            workflows: HashMap::from([(id.to_string(), candidate)]),
+            ..Default::default()
+        };
+
+        self.compare(
+            RegressionSubject::Workflow,
+            id,
+            workflow.version(),
+            candidate_version,
+            test_cases,
+            &overrides,
+            policy,
+        )
+        .await
+    }
+
+    /// List the reports of a prompt or workflow, newest first
+    pub async fn list_reports(
+        &self,
+        subject: RegressionSubject,
+        resource_id: &str,
+    ) -> Result<Vec<RegressionReport>, DomainError> {
+        let mut reports: Vec<RegressionReport> = self
+            .storage
+            .list()
+            .await?
+            .into_iter()
+            .filter(|report| report.subject == subject && report.resource_id == resource_id)
+            .collect();
+
+        reports.sort_by_key(|report| Reverse(report.created_at));
+        Ok(reports)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn compare(
+        &self,
+        subject: RegressionSubject,
+        resource_id: &str,
+        baseline_version: u32,
+        candidate_version: u32,
+        test_cases: Vec<TestCase>,
+        overrides: &ExecutionOverrides,
+        policy: &RegressionPolicy,
+    ) -> Result<Option<RegressionReport>, DomainError> {
+        if test_cases.is_empty() {
+            return Ok(None);
+        }
+
+        let comparisons: Vec<CaseComparison> = stream::iter(test_cases)
+            .map(|test_case| async move { self.compare_case(&test_case, overrides).await })
+            .buffered(DEFAULT_SUITE_CONCURRENCY)
+            .collect()
+            .await;
+
+        let report = RegressionReport::new(
+            subject,
+            resource_id,
+            baseline_version,
+            candidate_version,
+            comparisons,
+            policy,
+        );
+        self.storage.create(report.clone()).await?;
+
+        info!(
+            subject = %subject,
+            resource_id = %resource_id,
+            report_id = %report.id,
+            regressions = report.regressions,
+            blocked = report.blocked,
+            "Regression check completed"
+        );
+
+        Ok(Some(report))
+    }
+
+    async fn compare_case(
+        &self,
+        test_case: &TestCase,
+        overrides: &ExecutionOverrides,
+    ) -> CaseComparison {
+        let id = test_case.id().as_str();
+        let baseline = self
+            .test_case_service
+            .evaluate(id, &ExecutionOverrides::default())
+            .await;
+        let candidate = self.test_case_service.evaluate(id, overrides).await;
+
+        CaseComparison::new(id, test_case.name(), to_side(baseline), to_side(candidate))
+    }
+}
+
+fn to_side(result: Result<ExecuteTestCaseResponse, DomainError>) -> RegressionSide {
+    match result {
+        Ok(response) => RegressionSide::new(
+            response.output,
+            response.error,
+            response.assertion_results.iter().filter(|a| a.passed).count(),
+            response.assertion_results.len(),
+        ),
+        Err(e) => RegressionSide::new(None, Some(e.to_string()), 0, 0),
+    }
+}
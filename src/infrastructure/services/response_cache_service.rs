@@ -0,0 +1,211 @@
+//! Response caching of chat completions
+//!
+//! Combines the exact and semantic LLM caches behind the cache policies of
+//! models and prompts: a request is served from, and stored in, the cache
+//! its policy selects, or the `[cache]` default one when neither its model
+//! nor its prompt sets a policy.
+
+use std::sync::Arc;
+
+use tracing::warn;
+
+use crate::domain::cache::{CacheMode, CachePolicy};
+use crate::domain::llm::{LlmRequest, LlmResponse};
+use crate::infrastructure::cache::InMemoryCache;
+
+use super::llm_cache_service::LlmCacheService;
+use super::semantic_llm_cache_service::SemanticLlmCacheServiceTrait;
+
+/// Cached answer to a request
+#[derive(Debug, Clone)]
+pub struct CacheHit {
+    pub response: LlmResponse,
+    /// Cache that served it
+    pub mode: CacheMode,
+}
+
+/// Exact and semantic response caches applying cache policies
+#[derive(Debug)]
+pub struct ResponseCacheService {
+    exact: LlmCacheService,
+    semantic: Option<Arc<dyn SemanticLlmCacheServiceTrait>>,
+    default_policy: CachePolicy,
+}
+
+impl ResponseCacheService {
+    /// Cache service over the given exact cache, without semantic cache,
+    /// caching nothing unless a model or prompt policy asks for it
+    pub fn new(exact: LlmCacheService) -> Self {
+        Self {
+            exact,
+            semantic: None,
+            default_policy: CachePolicy::disabled(),
+        }
+    }
+
+    /// Serve `semantic` policies from the given semantic cache
+    pub fn with_semantic(mut self, semantic: Arc<dyn SemanticLlmCacheServiceTrait>) -> Self {
+        self.semantic = Some(semantic);
+        self
+    }
+
+    /// Policy of the requests whose model and prompt set none
+    pub fn with_default_policy(mut self, policy: CachePolicy) -> Self {
+        self.default_policy = policy;
+        self
+    }
+
+    /// Policy applying to a request of a model using a prompt (see
+    /// [`CachePolicy::resolve`]), else the default one
+    pub fn policy(&self, model: Option<&CachePolicy>, prompt: Option<&CachePolicy>) -> CachePolicy {
+        CachePolicy::resolve(model, prompt).unwrap_or_else(|| self.default_policy.clone())
+    }
+
+    /// Cached answer to the request, when its policy allows one. Cache
+    /// failures are logged and count as misses.
+    pub async fn lookup(
+        &self,
+        model_id: &str,
+        request: &LlmRequest,
+        policy: &CachePolicy,
+    ) -> Option<CacheHit> {
+        let result = match policy.mode {
+            CacheMode::Disabled => return None,
+            CacheMode::Exact => self
+                .exact
+                .get_with_policy(model_id, request, Some(policy))
+                .await
+                .map(|cached| cached.map(|c| c.response)),
+            CacheMode::Semantic => match &self.semantic {
+                Some(semantic) => semantic
+                    .get_with_policy(model_id, request, Some(policy))
+                    .await
+                    .map(|cached| cached.map(|c| c.response)),
+                None => return None,
+            },
+        };
+
+        match result {
+            Ok(response) => response.map(|response| CacheHit {
+                response,
+                mode: policy.mode,
+            }),
+            Err(e) => {
+                warn!(model_id = %model_id, error = %e, "Response cache lookup failed");
+                None
+            }
+        }
+    }
+
+    /// Cache the answer to a request as its policy allows. Failures are
+    /// logged, the answer was served anyway.
+    pub async fn store(
+        &self,
+        model_id: &str,
+        request: &LlmRequest,
+        response: LlmResponse,
+        policy: &CachePolicy,
+    ) {
+        let result = match (policy.mode, &self.semantic) {
+            (CacheMode::Exact, _) => {
+                self.exact
+                    .set_with_policy(model_id, request, response, Some(policy))
+                    .await
+            }
+            (CacheMode::Semantic, Some(semantic)) => {
+                semantic
+                    .set_with_policy(model_id, request, response, Some(policy))
+                    .await
+            }
+            _ => return,
+        };
+
+        if let Err(e) = result {
+            warn!(model_id = %model_id, error = %e, "Failed to cache response");
+        }
+    }
+}
+
+impl Default for ResponseCacheService {
+    fn default() -> Self {
+        Self::new(LlmCacheService::new(Arc::new(InMemoryCache::new())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::embedding::MockEmbeddingProvider;
+    use crate::domain::llm::Message;
+    use crate::domain::semantic_cache::SemanticCacheConfig;
+    use crate::infrastructure::semantic_cache::InMemorySemanticCache;
+    use crate::infrastructure::services::SemanticLlmCacheService;
+
+    fn request(user: &str) -> LlmRequest {
+        LlmRequest::builder().user(user).build()
+    }
+
+    fn response() -> LlmResponse {
+        LlmResponse::new(
+            "resp-1".to_string(),
+            "gpt-4".to_string(),
+            Message::assistant("Hello!"),
+        )
+    }
+
+    #[test]
+    fn test_policy_falls_back_to_default() {
+        let exact = CachePolicy::new(CacheMode::Exact);
+        let service =
+            ResponseCacheService::default().with_default_policy(CachePolicy::new(CacheMode::Semantic));
+
+        assert_eq!(service.policy(None, None).mode, CacheMode::Semantic);
+        assert_eq!(service.policy(Some(&exact), None), exact);
+        assert_eq!(
+            ResponseCacheService::default().policy(None, None),
+            CachePolicy::disabled()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lookup_and_store() {
+        let service = ResponseCacheService::default();
+        let exact = CachePolicy::new(CacheMode::Exact);
+
+        assert!(service.lookup("gpt-4", &request("Hi"), &exact).await.is_none());
+        service.store("gpt-4", &request("Hi"), response(), &exact).await;
+
+        let hit = service.lookup("gpt-4", &request("Hi"), &exact).await.unwrap();
+        assert_eq!(hit.mode, CacheMode::Exact);
+        assert_eq!(hit.response.id, "resp-1");
+        assert!(service
+            .lookup("gpt-4", &request("Hi"), &CachePolicy::disabled())
+            .await
+            .is_none());
+
+        // Without a semantic cache, semantic policies cache nothing
+        let semantic = CachePolicy::new(CacheMode::Semantic);
+        service.store("gpt-4", &request("Hey"), response(), &semantic).await;
+        assert!(service.lookup("gpt-4", &request("Hey"), &semantic).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_semantic_lookup() {
+        let semantic_cache = SemanticLlmCacheService::with_config(
+            Arc::new(InMemorySemanticCache::new(100)),
+            Arc::new(MockEmbeddingProvider::new("mock", 8)),
+            SemanticCacheConfig::default(),
+        );
+        let service = ResponseCacheService::default().with_semantic(Arc::new(semantic_cache));
+        let semantic = CachePolicy::new(CacheMode::Semantic);
+
+        service.store("gpt-4", &request("Hi"), response(), &semantic).await;
+
+        let hit = service.lookup("gpt-4", &request("Hi"), &semantic).await.unwrap();
+        assert_eq!(hit.mode, CacheMode::Semantic);
+        assert!(service
+            .lookup("gpt-4", &request("Hi"), &CachePolicy::new(CacheMode::Exact))
+            .await
+            .is_none());
+    }
+}
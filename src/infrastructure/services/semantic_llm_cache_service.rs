@@ -4,16 +4,19 @@
 //! for semantically similar queries rather than requiring exact matches.
 
 use std::sync::Arc;
+use std::time::Duration;
 
-use tracing::{debug, warn};
+use tracing::{debug, warn, Instrument};
 use uuid::Uuid;
 
+use crate::domain::cache::{CacheMode, CachePolicy};
 use crate::domain::embedding::{EmbeddingProvider, EmbeddingRequest};
 use crate::domain::llm::{LlmRequest, LlmResponse};
 use crate::domain::semantic_cache::{
     CachedEntry, SemanticCache, SemanticCacheConfig, SemanticCacheStats, SemanticSearchParams,
 };
 use crate::domain::DomainError;
+use crate::infrastructure::observability::{cache_lookup_span, record_cache_lookup, record_result};
 
 /// Semantic LLM cache service that uses embeddings for similarity matching
 #[derive(Debug)]
@@ -99,8 +102,28 @@ impl SemanticLlmCacheService {
             return Ok(None);
         }
 
+        let span = cache_lookup_span("semantic", model_id);
+        let result = record_result(
+            &span,
+            self.find_similar_response(model_id, request, &query_text)
+                .instrument(span.clone())
+                .await,
+        )?;
+        span.record("cache.hit", result.is_some());
+        record_cache_lookup("semantic", result.is_some());
+
+        Ok(result)
+    }
+
+    /// Look up the response of the most similar cached query
+    async fn find_similar_response(
+        &self,
+        model_id: &str,
+        request: &LlmRequest,
+        query_text: &str,
+    ) -> Result<Option<CachedLlmResponse>, DomainError> {
         // Generate embedding for the query
-        let embedding = match self.generate_embedding(&query_text).await {
+        let embedding = match self.generate_embedding(query_text).await {
             Ok(emb) => emb,
             Err(e) => {
                 warn!("Failed to generate embedding for cache lookup: {}", e);
@@ -110,7 +133,8 @@ impl SemanticLlmCacheService {
         };
 
         // Build search params
-        let mut params = SemanticSearchParams::new(self.config.similarity_threshold);
+        let mut params = SemanticSearchParams::new(self.config.similarity_threshold)
+            .with_metric(self.config.distance_metric);
 
         if self.config.include_model_in_key {
             params = params.with_model_id(model_id);
@@ -157,12 +181,57 @@ impl SemanticLlmCacheService {
         }
     }
 
+    /// Try to get a cached response, unless the request's cache policy
+    /// (see [`CachePolicy::resolve`]) rules out the semantic cache
+    pub async fn get_with_policy(
+        &self,
+        model_id: &str,
+        request: &LlmRequest,
+        policy: Option<&CachePolicy>,
+    ) -> Result<Option<CachedLlmResponse>, DomainError> {
+        if policy.is_some_and(|p| !p.allows(CacheMode::Semantic, request)) {
+            return Ok(None);
+        }
+
+        self.get(model_id, request).await
+    }
+
     /// Cache a response for the given request
     pub async fn set(
         &self,
         model_id: &str,
         request: &LlmRequest,
         response: LlmResponse,
+    ) -> Result<(), DomainError> {
+        self.store_response(model_id, request, response, self.config.ttl())
+            .await
+    }
+
+    /// Cache a response as the request's cache policy allows, for the
+    /// policy's TTL when it sets one
+    pub async fn set_with_policy(
+        &self,
+        model_id: &str,
+        request: &LlmRequest,
+        response: LlmResponse,
+        policy: Option<&CachePolicy>,
+    ) -> Result<(), DomainError> {
+        match policy {
+            Some(p) if !p.allows(CacheMode::Semantic, request) => Ok(()),
+            Some(p) => {
+                let ttl = p.ttl().unwrap_or_else(|| self.config.ttl());
+                self.store_response(model_id, request, response, ttl).await
+            }
+            None => self.set(model_id, request, response).await,
+        }
+    }
+
+    async fn store_response(
+        &self,
+        model_id: &str,
+        request: &LlmRequest,
+        response: LlmResponse,
+        ttl: Duration,
     ) -> Result<(), DomainError> {
         if !self.config.enabled {
             return Ok(());
@@ -195,7 +264,7 @@ impl SemanticLlmCacheService {
 
         // Create cache entry
         let entry_id = format!("sem:{}", Uuid::new_v4());
-        let mut entry = CachedEntry::new(entry_id, embedding, query_text, value, self.config.ttl());
+        let mut entry = CachedEntry::new(entry_id, embedding, query_text, value, ttl);
 
         if self.config.include_model_in_key {
             entry = entry.with_model_id(model_id);
@@ -264,6 +333,15 @@ pub trait SemanticLlmCacheServiceTrait: Send + Sync + std::fmt::Debug {
         request: &LlmRequest,
     ) -> Result<Option<CachedLlmResponse>, DomainError>;
 
+    /// Try to get a cached response, unless the request's cache policy
+    /// rules out the semantic cache
+    async fn get_with_policy(
+        &self,
+        model_id: &str,
+        request: &LlmRequest,
+        policy: Option<&CachePolicy>,
+    ) -> Result<Option<CachedLlmResponse>, DomainError>;
+
     /// Cache a response for the given request
     async fn set(
         &self,
@@ -272,6 +350,15 @@ pub trait SemanticLlmCacheServiceTrait: Send + Sync + std::fmt::Debug {
         response: LlmResponse,
     ) -> Result<(), DomainError>;
 
+    /// Cache a response as the request's cache policy allows
+    async fn set_with_policy(
+        &self,
+        model_id: &str,
+        request: &LlmRequest,
+        response: LlmResponse,
+        policy: Option<&CachePolicy>,
+    ) -> Result<(), DomainError>;
+
     /// Invalidate all cached responses for a model
     async fn invalidate_model(&self, model_id: &str) -> Result<usize, DomainError>;
 
@@ -296,6 +383,15 @@ impl SemanticLlmCacheServiceTrait for SemanticLlmCacheService {
         SemanticLlmCacheService::get(self, model_id, request).await
     }
 
+    async fn get_with_policy(
+        &self,
+        model_id: &str,
+        request: &LlmRequest,
+        policy: Option<&CachePolicy>,
+    ) -> Result<Option<CachedLlmResponse>, DomainError> {
+        SemanticLlmCacheService::get_with_policy(self, model_id, request, policy).await
+    }
+
     async fn set(
         &self,
         model_id: &str,
@@ -305,6 +401,16 @@ impl SemanticLlmCacheServiceTrait for SemanticLlmCacheService {
         SemanticLlmCacheService::set(self, model_id, request, response).await
     }
 
+    async fn set_with_policy(
+        &self,
+        model_id: &str,
+        request: &LlmRequest,
+        response: LlmResponse,
+        policy: Option<&CachePolicy>,
+    ) -> Result<(), DomainError> {
+        SemanticLlmCacheService::set_with_policy(self, model_id, request, response, policy).await
+    }
+
     async fn invalidate_model(&self, model_id: &str) -> Result<usize, DomainError> {
         SemanticLlmCacheService::invalidate_model(self, model_id).await
     }
@@ -1,19 +1,43 @@
 //! Test case service - CRUD operations and execution for test cases
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 
 use serde::{Deserialize, Serialize};
 
+use serde_json::Value;
+
+use crate::domain::dataset::{DatasetRef, DatasetRow, DatasetSelector, EXPECTED_OUTPUT_FIELD};
+use crate::domain::prompt::PromptTemplate;
 use crate::domain::test_case::{
-    AssertionCriteria, AssertionEvaluator, ModelPromptInput, TestCase, TestCaseId, TestCaseInput,
-    TestCaseQuery, TestCaseRepository, TestCaseResult, TestCaseResultQuery, TestCaseResultRepository,
-    TokenUsage, WorkflowInput,
+    AssertionCriteria, AssertionEvaluator, AssertionResult, ModelPromptInput, TestCase, TestCaseId,
+    TestCaseInput, TestCaseQuery, TestCaseRepository, TestCaseResult, TestCaseResultQuery,
+    TestCaseResultRepository, TokenUsage, WorkflowInput,
 };
-use crate::domain::{DomainError, LlmRequest, Message};
+use crate::domain::{DomainError, LlmRequest, Message, Workflow};
 
 use super::super::plugin::ProviderRouter;
-use crate::api::state::{CredentialServiceTrait, ModelServiceTrait, PromptServiceTrait, WorkflowServiceTrait};
+use crate::api::state::{
+    CredentialServiceTrait, DatasetServiceTrait, ModelServiceTrait, PromptServiceTrait,
+    WorkflowServiceTrait,
+};
+
+/// Output, token usage, error and assertion results of an execution
+type ExecutionOutcome = (
+    Option<String>,
+    Option<TokenUsage>,
+    Option<String>,
+    Vec<AssertionResult>,
+);
+
+/// Unsaved prompt contents and workflow definitions, keyed by ID, to run
+/// test cases against instead of the stored versions
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionOverrides {
+    pub prompts: HashMap<String, String>,
+    pub workflows: HashMap<String, Workflow>,
+}
 
 /// Request to create a new test case
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +49,12 @@ pub struct CreateTestCaseRequest {
     pub assertions: Vec<AssertionCriteria>,
     pub tags: Vec<String>,
     pub enabled: bool,
+    /// Dataset to run against, once per row
+    #[serde(default)]
+    pub dataset: Option<DatasetSelector>,
+    /// Run on the canary schedule against the model
+    #[serde(default)]
+    pub canary: bool,
 }
 
 /// Input configuration for a test case request
@@ -44,6 +74,8 @@ pub struct UpdateTestCaseRequest {
     pub assertions: Option<Vec<AssertionCriteria>>,
     pub tags: Option<Vec<String>>,
     pub enabled: Option<bool>,
+    pub dataset: Option<Option<DatasetSelector>>,
+    pub canary: Option<bool>,
 }
 
 /// Response from executing a test case
@@ -57,6 +89,8 @@ pub struct ExecuteTestCaseResponse {
     pub execution_time_ms: u64,
     pub tokens_used: Option<TokenUsage>,
     pub error: Option<String>,
+    /// Dataset version the test ran against
+    pub dataset: Option<DatasetRef>,
 }
 
 /// Assertion result in response
@@ -84,6 +118,7 @@ pub struct TestCaseService<R, RR> {
     repository: Arc<R>,
     result_repository: Arc<RR>,
     deps: TestCaseServiceDeps,
+    datasets: Option<Arc<dyn DatasetServiceTrait>>,
 }
 
 impl<R: TestCaseRepository, RR: TestCaseResultRepository> TestCaseService<R, RR> {
@@ -97,9 +132,16 @@ impl<R: TestCaseRepository, RR: TestCaseResultRepository> TestCaseService<R, RR>
             repository,
             result_repository,
             deps,
+            datasets: None,
         }
     }
 
+    /// Allow test cases to run against datasets
+    pub fn with_datasets(mut self, datasets: Arc<dyn DatasetServiceTrait>) -> Self {
+        self.datasets = Some(datasets);
+        self
+    }
+
     /// Get a test case by ID
     pub async fn get(&self, id: &str) -> Result<Option<TestCase>, DomainError> {
         let test_case_id = self.parse_id(id)?;
@@ -150,7 +192,13 @@ impl<R: TestCaseRepository, RR: TestCaseResultRepository> TestCaseService<R, RR>
             }
         };
 
-        let test_case = self.apply_common_fields(test_case, &request);
+        let mut test_case = self.apply_common_fields(test_case, &request);
+        validate_canary(&test_case)?;
+
+        if let Some(ref selector) = request.dataset {
+            test_case = test_case.with_dataset(self.resolve_dataset(selector).await?);
+        }
+
         self.repository.save(&test_case).await?;
         Ok(test_case)
     }
@@ -166,6 +214,7 @@ impl<R: TestCaseRepository, RR: TestCaseResultRepository> TestCaseService<R, RR>
         test_case = test_case.with_assertions(request.assertions.clone());
         test_case = test_case.with_tags(request.tags.clone());
         test_case = test_case.with_enabled(request.enabled);
+        test_case = test_case.with_canary(request.canary);
         test_case
     }
 
@@ -211,6 +260,19 @@ impl<R: TestCaseRepository, RR: TestCaseResultRepository> TestCaseService<R, RR>
             test_case.set_enabled(enabled);
         }
 
+        if let Some(selector) = request.dataset {
+            let dataset = match selector {
+                Some(selector) => Some(self.resolve_dataset(&selector).await?),
+                None => None,
+            };
+            test_case.set_dataset(dataset);
+        }
+
+        if let Some(canary) = request.canary {
+            test_case.set_canary(canary);
+        }
+        validate_canary(&test_case)?;
+
         self.repository.save(&test_case).await?;
         Ok(test_case)
     }
@@ -229,6 +291,28 @@ impl<R: TestCaseRepository, RR: TestCaseResultRepository> TestCaseService<R, RR>
 
     /// Execute a test case and return results
     pub async fn execute(&self, id: &str) -> Result<ExecuteTestCaseResponse, DomainError> {
+        let test_case = self.get_enabled(id).await?;
+        let (response, result) = self.run(&test_case, &ExecutionOverrides::default()).await?;
+
+        self.result_repository.save(&result).await?;
+
+        Ok(response)
+    }
+
+    /// Execute a test case against unsaved prompt or workflow versions.
+    /// The result is not recorded.
+    pub async fn evaluate(
+        &self,
+        id: &str,
+        overrides: &ExecutionOverrides,
+    ) -> Result<ExecuteTestCaseResponse, DomainError> {
+        let test_case = self.get_enabled(id).await?;
+        let (response, _) = self.run(&test_case, overrides).await?;
+
+        Ok(response)
+    }
+
+    async fn get_enabled(&self, id: &str) -> Result<TestCase, DomainError> {
         let test_case = self.get_required(id).await?;
 
         if !test_case.is_enabled() {
@@ -238,22 +322,35 @@ impl<R: TestCaseRepository, RR: TestCaseResultRepository> TestCaseService<R, RR>
             )));
         }
 
+        Ok(test_case)
+    }
+
+    async fn run(
+        &self,
+        test_case: &TestCase,
+        overrides: &ExecutionOverrides,
+    ) -> Result<(ExecuteTestCaseResponse, TestCaseResult), DomainError> {
         let start = Instant::now();
 
-        let (output, tokens, error) = match test_case.input() {
-            TestCaseInput::ModelPrompt(input) => self.execute_model_prompt(input).await,
-            TestCaseInput::Workflow(input) => self.execute_workflow(input).await,
-        };
+        let (output, tokens, error, assertion_results) = match test_case.dataset() {
+            Some(dataset) => self.execute_dataset(test_case, dataset, overrides).await?,
+            None => {
+                let (output, tokens, error) =
+                    self.execute_input(test_case.input(), overrides).await;
 
-        let execution_time_ms = start.elapsed().as_millis() as u64;
+                // Evaluate assertions if we have output
+                let assertion_results = if let Some(ref output_str) = output {
+                    AssertionEvaluator::evaluate_all(test_case.assertions(), output_str)
+                } else {
+                    Vec::new()
+                };
 
-        // Evaluate assertions if we have output
-        let assertion_results = if let Some(ref output_str) = output {
-            AssertionEvaluator::evaluate_all(test_case.assertions(), output_str)
-        } else {
-            Vec::new()
+                (output, tokens, error, assertion_results)
+            }
         };
 
+        let execution_time_ms = start.elapsed().as_millis() as u64;
+
         // Determine if passed
         let passed = error.is_none() && assertion_results.iter().all(|r| r.passed);
 
@@ -272,8 +369,10 @@ impl<R: TestCaseRepository, RR: TestCaseResultRepository> TestCaseService<R, RR>
             }
             result
         };
-
-        self.result_repository.save(&result).await?;
+        let result = match test_case.dataset() {
+            Some(dataset) => result.with_dataset(dataset.clone()),
+            None => result,
+        };
 
         // Build response
         let assertion_responses: Vec<AssertionResultResponse> = assertion_results
@@ -288,7 +387,7 @@ impl<R: TestCaseRepository, RR: TestCaseResultRepository> TestCaseService<R, RR>
             })
             .collect();
 
-        Ok(ExecuteTestCaseResponse {
+        let response = ExecuteTestCaseResponse {
             test_case_id: test_case.id().to_string(),
             test_case_name: test_case.name().to_string(),
             passed,
@@ -297,12 +396,87 @@ impl<R: TestCaseRepository, RR: TestCaseResultRepository> TestCaseService<R, RR>
             execution_time_ms,
             tokens_used: tokens,
             error,
-        })
+            dataset: test_case.dataset().cloned(),
+        };
+
+        Ok((response, result))
+    }
+
+    async fn execute_input(
+        &self,
+        input: &TestCaseInput,
+        overrides: &ExecutionOverrides,
+    ) -> (Option<String>, Option<TokenUsage>, Option<String>) {
+        match input {
+            TestCaseInput::ModelPrompt(input) => self.execute_model_prompt(input, overrides).await,
+            TestCaseInput::Workflow(input) => self.execute_workflow(input, overrides).await,
+        }
+    }
+
+    /// Run the test case once per dataset row. Assertions are prefixed with
+    /// the row number, rows with an expected output also assert the output
+    /// contains it, and the output is the JSON array of row outputs.
+    async fn execute_dataset(
+        &self,
+        test_case: &TestCase,
+        dataset: &DatasetRef,
+        overrides: &ExecutionOverrides,
+    ) -> Result<ExecutionOutcome, DomainError> {
+        let rows = self.datasets()?.rows(dataset).await?;
+
+        let mut outputs = Vec::with_capacity(rows.len());
+        let mut tokens: Option<TokenUsage> = None;
+        let mut errors = Vec::new();
+        let mut assertion_results = Vec::new();
+
+        for (index, row) in rows.iter().enumerate() {
+            let label = format!("row {}", index + 1);
+
+            let (output, row_tokens, error) = match row_input(test_case.input(), row) {
+                Ok(input) => self.execute_input(&input, overrides).await,
+                Err(e) => (None, None, Some(e.to_string())),
+            };
+
+            if let Some(error) = error {
+                errors.push(format!("{}: {}", label, error));
+            }
+
+            if let Some(row_tokens) = row_tokens {
+                tokens = Some(match tokens {
+                    Some(total) => TokenUsage {
+                        prompt_tokens: total.prompt_tokens + row_tokens.prompt_tokens,
+                        completion_tokens: total.completion_tokens + row_tokens.completion_tokens,
+                        total_tokens: total.total_tokens + row_tokens.total_tokens,
+                    },
+                    None => row_tokens,
+                });
+            }
+
+            if let Some(ref output_str) = output {
+                let mut criteria = test_case.assertions().to_vec();
+                if let Some(ref expected) = row.expected_output {
+                    criteria.push(AssertionCriteria::contains(EXPECTED_OUTPUT_FIELD, expected));
+                }
+
+                for mut result in AssertionEvaluator::evaluate_all(&criteria, output_str) {
+                    result.name = format!("{}: {}", label, result.name);
+                    assertion_results.push(result);
+                }
+            }
+
+            outputs.push(output);
+        }
+
+        let output = serde_json::to_string_pretty(&outputs).ok();
+        let error = (!errors.is_empty()).then(|| errors.join("; "));
+
+        Ok((output, tokens, error, assertion_results))
     }
 
     async fn execute_model_prompt(
         &self,
         input: &ModelPromptInput,
+        overrides: &ExecutionOverrides,
     ) -> (Option<String>, Option<TokenUsage>, Option<String>) {
         // Get model
         let model = match self.deps.model_service.get(&input.model_id).await {
@@ -342,12 +516,22 @@ impl<R: TestCaseRepository, RR: TestCaseResultRepository> TestCaseService<R, RR>
 
         // Render system prompt if provided
         let system_message = if let Some(ref prompt_id) = input.prompt_id {
-            match self
-                .deps
-                .prompt_service
-                .render(prompt_id, &input.variables)
-                .await
-            {
+            let rendered = match overrides.prompts.get(prompt_id) {
+                Some(content) => match self.deps.prompt_service.expand_partials(content).await {
+                    Ok(content) => PromptTemplate::parse(content)
+                        .and_then(|template| template.render(&input.variables))
+                        .map_err(|e| DomainError::validation(e.to_string())),
+                    Err(e) => Err(e),
+                },
+                None => {
+                    self.deps
+                        .prompt_service
+                        .render(prompt_id, &input.variables)
+                        .await
+                }
+            };
+
+            match rendered {
                 Ok(rendered) => Some(rendered),
                 Err(e) => {
                     return (
@@ -407,13 +591,23 @@ impl<R: TestCaseRepository, RR: TestCaseResultRepository> TestCaseService<R, RR>
     async fn execute_workflow(
         &self,
         input: &WorkflowInput,
+        overrides: &ExecutionOverrides,
     ) -> (Option<String>, Option<TokenUsage>, Option<String>) {
-        match self
-            .deps
-            .workflow_service
-            .execute(&input.workflow_id, input.input.clone())
-            .await
-        {
+        let workflow_service = &self.deps.workflow_service;
+        let result = match overrides.workflows.get(&input.workflow_id) {
+            Some(workflow) => {
+                workflow_service
+                    .execute_definition(workflow, input.input.clone())
+                    .await
+            }
+            None => {
+                workflow_service
+                    .execute(&input.workflow_id, input.input.clone())
+                    .await
+            }
+        };
+
+        match result {
             Ok(result) => {
                 let output = serde_json::to_string_pretty(&result.output)
                     .unwrap_or_else(|_| "{}".to_string());
@@ -446,6 +640,18 @@ impl<R: TestCaseRepository, RR: TestCaseResultRepository> TestCaseService<R, RR>
         TestCaseId::new(id).map_err(|e| DomainError::validation(e.to_string()))
     }
 
+    fn datasets(&self) -> Result<&Arc<dyn DatasetServiceTrait>, DomainError> {
+        self.datasets
+            .as_ref()
+            .ok_or_else(|| DomainError::validation("Datasets are not available"))
+    }
+
+    async fn resolve_dataset(&self, selector: &DatasetSelector) -> Result<DatasetRef, DomainError> {
+        self.datasets()?
+            .resolve(&selector.dataset_id, selector.version)
+            .await
+    }
+
     async fn validate_model_prompt_input(&self, input: &ModelPromptInput) -> Result<(), DomainError> {
         // Check model exists
         if self.deps.model_service.get(&input.model_id).await?.is_none() {
@@ -481,11 +687,58 @@ impl<R: TestCaseRepository, RR: TestCaseResultRepository> TestCaseService<R, RR>
     }
 }
 
+/// Canaries probe a model, so they must be model+prompt test cases
+fn validate_canary(test_case: &TestCase) -> Result<(), DomainError> {
+    if test_case.is_canary() && !matches!(test_case.input(), TestCaseInput::ModelPrompt(_)) {
+        return Err(DomainError::validation(
+            "Only model_prompt test cases can be canaries",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Input of a test case for one dataset row. Row values override prompt
+/// variables and are rendered into the user message, or are merged into the
+/// workflow input object.
+fn row_input(input: &TestCaseInput, row: &DatasetRow) -> Result<TestCaseInput, DomainError> {
+    match input {
+        TestCaseInput::ModelPrompt(input) => {
+            let mut input = input.clone();
+            input.variables.extend(row.input.clone());
+            input.user_message = PromptTemplate::parse(input.user_message.as_str())
+                .and_then(|template| template.render(&input.variables))
+                .map_err(|e| DomainError::validation(e.to_string()))?;
+            Ok(TestCaseInput::ModelPrompt(input))
+        }
+        TestCaseInput::Workflow(input) => {
+            let mut input = input.clone();
+            let fields = row
+                .input
+                .iter()
+                .map(|(key, value)| (key.clone(), Value::String(value.clone())));
+
+            match &mut input.input {
+                Value::Object(object) => object.extend(fields),
+                Value::Null => input.input = Value::Object(fields.collect()),
+                _ => {
+                    return Err(DomainError::validation(
+                        "Workflow input must be an object to merge dataset rows",
+                    ));
+                }
+            }
+
+            Ok(TestCaseInput::Workflow(input))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::knowledge_base::KnowledgeBaseCaller;
     use crate::domain::test_case::TestCaseType;
-    use crate::domain::{Model, Prompt, StoredCredential, Workflow, WorkflowResult};
+    use crate::domain::{Model, Prompt, PromptVariable, StoredCredential, Workflow, WorkflowResult};
     use crate::infrastructure::services::{
         CreateModelRequest, CreatePromptRequest, CreateWorkflowRequest, UpdateModelRequest,
         UpdatePromptRequest, UpdateWorkflowRequest,
@@ -611,6 +864,24 @@ mod tests {
             }
         }
 
+        async fn render_variant(
+            &self,
+            id: &str,
+            _version: Option<u32>,
+            _locale: Option<&str>,
+            variables: &HashMap<String, String>,
+        ) -> Result<String, DomainError> {
+            self.render(id, variables).await
+        }
+
+        async fn variable_schema(&self, _id: &str) -> Result<Vec<PromptVariable>, DomainError> {
+            unimplemented!()
+        }
+
+        async fn expand_partials(&self, content: &str) -> Result<String, DomainError> {
+            Ok(content.to_string())
+        }
+
         async fn revert(&self, _id: &str, _version: u32) -> Result<Prompt, DomainError> {
             unimplemented!()
         }
@@ -674,6 +945,42 @@ mod tests {
                 0,
             ))
         }
+
+        async fn execute_as(
+            &self,
+            id: &str,
+            input: Value,
+            _caller: KnowledgeBaseCaller,
+        ) -> Result<WorkflowResult, DomainError> {
+            self.execute(id, input).await
+        }
+
+        async fn preview_update(
+            &self,
+            _id: &str,
+            _request: UpdateWorkflowRequest,
+        ) -> Result<Workflow, DomainError> {
+            unimplemented!()
+        }
+
+        async fn execute_definition(
+            &self,
+            workflow: &Workflow,
+            _input: Value,
+        ) -> Result<WorkflowResult, DomainError> {
+            Ok(WorkflowResult::success(
+                serde_json::json!({"result": "success", "version": workflow.version()}),
+                Vec::new(),
+                0,
+            ))
+        }
+
+        async fn count_for_team(
+            &self,
+            _team_id: &crate::domain::team::TeamId,
+        ) -> Result<u64, DomainError> {
+            unimplemented!()
+        }
     }
 
     // Mock credential service
@@ -749,6 +1056,8 @@ mod tests {
             assertions: vec![AssertionCriteria::contains("check", "hello")],
             tags: vec!["test".to_string()],
             enabled: true,
+            dataset: None,
+            canary: false,
         };
 
         let result = service.create(request).await.unwrap();
@@ -787,6 +1096,8 @@ mod tests {
             assertions: vec![],
             tags: vec![],
             enabled: true,
+            dataset: None,
+            canary: false,
         };
 
         let result = service.create(request).await.unwrap();
@@ -816,6 +1127,8 @@ mod tests {
             assertions: vec![],
             tags: vec![],
             enabled: true,
+            dataset: None,
+            canary: false,
         };
 
         let result = service.create(request).await;
@@ -857,6 +1170,8 @@ mod tests {
             assertions: vec![],
             tags: vec![],
             enabled: true,
+            dataset: None,
+            canary: false,
         };
         service.create(create_request).await.unwrap();
 
@@ -909,6 +1224,8 @@ mod tests {
             assertions: vec![],
             tags: vec![],
             enabled: true,
+            dataset: None,
+            canary: false,
         };
         service.create(create_request).await.unwrap();
 
@@ -920,4 +1237,171 @@ mod tests {
         let found = service.get("test-1").await.unwrap();
         assert!(found.is_none());
     }
+
+    #[tokio::test]
+    async fn test_execute_against_dataset() {
+        use crate::domain::dataset::{Dataset, DatasetVersion};
+        use crate::infrastructure::dataset::{
+            CreateDatasetRequest, DatasetService, StorageDatasetRepository,
+        };
+        use crate::infrastructure::storage::InMemoryStorage;
+
+        let datasets = Arc::new(DatasetService::new(Arc::new(StorageDatasetRepository::new(
+            Arc::new(InMemoryStorage::<Dataset>::new()),
+            Arc::new(InMemoryStorage::<DatasetVersion>::new()),
+        ))));
+        let row = |query: &str, expected: &str| DatasetRow {
+            input: [("query".to_string(), query.to_string())].into(),
+            expected_output: Some(expected.to_string()),
+        };
+        datasets
+            .create(CreateDatasetRequest {
+                id: "queries".to_string(),
+                name: "Queries".to_string(),
+                description: None,
+                rows: vec![row("a", "success"), row("b", "failure")],
+                note: None,
+            })
+            .await
+            .unwrap();
+
+        let workflow_service = Arc::new(MockWorkflowService::new());
+        workflow_service.add_workflow("my-workflow");
+
+        let deps = TestCaseServiceDeps {
+            workflow_service,
+            ..create_deps()
+        };
+        let service = TestCaseService::new(
+            Arc::new(InMemoryTestCaseRepository::new()),
+            Arc::new(InMemoryTestCaseResultRepository::new()),
+            deps,
+        )
+        .with_datasets(datasets.clone());
+
+        let test_case = service
+            .create(CreateTestCaseRequest {
+                id: "dataset-test".to_string(),
+                name: "Dataset Test".to_string(),
+                description: None,
+                input: TestCaseInputRequest::Workflow(WorkflowInput {
+                    workflow_id: "my-workflow".to_string(),
+                    input: serde_json::json!({"mode": "fast"}),
+                }),
+                assertions: vec![],
+                tags: vec![],
+                enabled: true,
+                dataset: Some(DatasetSelector {
+                    dataset_id: "queries".to_string(),
+                    version: None,
+                }),
+                canary: false,
+            })
+            .await
+            .unwrap();
+        assert_eq!(test_case.dataset(), Some(&DatasetRef::new("queries", 1)));
+
+        // A new version does not change what the test case is pinned to
+        datasets
+            .add_version("queries", vec![row("c", "success")], None)
+            .await
+            .unwrap();
+
+        let response = service.execute("dataset-test").await.unwrap();
+
+        assert!(!response.passed);
+        assert_eq!(response.dataset, Some(DatasetRef::new("queries", 1)));
+        assert_eq!(response.assertion_results.len(), 2);
+        assert_eq!(response.assertion_results[0].name, "row 1: expected_output");
+        assert!(response.assertion_results[0].passed);
+        assert!(!response.assertion_results[1].passed);
+
+        let outputs: Vec<Value> = serde_json::from_str(&response.output.unwrap()).unwrap();
+        assert_eq!(outputs.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_with_workflow_override() {
+        let workflow_service = Arc::new(MockWorkflowService::new());
+        workflow_service.add_workflow("my-workflow");
+        let candidate = workflow_service.get("my-workflow").await.unwrap().unwrap();
+
+        let deps = TestCaseServiceDeps {
+            workflow_service,
+            ..create_deps()
+        };
+        let service = TestCaseService::new(
+            Arc::new(InMemoryTestCaseRepository::new()),
+            Arc::new(InMemoryTestCaseResultRepository::new()),
+            deps,
+        );
+
+        service
+            .create(CreateTestCaseRequest {
+                id: "workflow-test".to_string(),
+                name: "Workflow Test".to_string(),
+                description: None,
+                input: TestCaseInputRequest::Workflow(WorkflowInput {
+                    workflow_id: "my-workflow".to_string(),
+                    input: serde_json::json!({}),
+                }),
+                assertions: vec![AssertionCriteria::contains("versioned", "version")],
+                tags: vec![],
+                enabled: true,
+                dataset: None,
+                canary: false,
+            })
+            .await
+            .unwrap();
+
+        let baseline = service
+            .evaluate("workflow-test", &ExecutionOverrides::default())
+            .await
+            .unwrap();
+        assert!(!baseline.passed);
+
+        let overrides = ExecutionOverrides {
+            workflows: HashMap::from([("my-workflow".to_string(), candidate)]),
+            ..Default::default()
+        };
+        let response = service.evaluate("workflow-test", &overrides).await.unwrap();
+        assert!(response.passed);
+
+        // Evaluations are not recorded as results
+        assert!(service
+            .get_latest_result("workflow-test")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_row_input() {
+        let row = DatasetRow {
+            input: [("name".to_string(), "Ada".to_string())].into(),
+            expected_output: None,
+        };
+        let input = TestCaseInput::ModelPrompt(ModelPromptInput {
+            model_id: "gpt-4".to_string(),
+            prompt_id: None,
+            variables: HashMap::new(),
+            user_message: "Hello ${var:name}".to_string(),
+            temperature: None,
+            max_tokens: None,
+        });
+
+        match row_input(&input, &row).unwrap() {
+            TestCaseInput::ModelPrompt(input) => {
+                assert_eq!(input.user_message, "Hello Ada");
+                assert_eq!(input.variables["name"], "Ada");
+            }
+            TestCaseInput::Workflow(_) => panic!("expected model prompt input"),
+        }
+
+        let workflow = TestCaseInput::Workflow(WorkflowInput {
+            workflow_id: "wf".to_string(),
+            input: Value::Array(vec![]),
+        });
+        assert!(row_input(&workflow, &row).is_err());
+    }
 }
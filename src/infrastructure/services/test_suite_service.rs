@@ -0,0 +1,446 @@
+//! Test suite service - CRUD operations and gated runs of test case groups
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::api::state::TestCaseServiceTrait;
+use crate::domain::test_case::{
+    validate_test_suite, TestCaseId, TestSuite, TestSuiteCaseOutcome, TestSuiteId,
+    TestSuiteRepository, TestSuiteRun, TestSuiteRunId,
+};
+use crate::domain::DomainError;
+
+/// Request to create a new test suite
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTestSuiteRequest {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub test_case_ids: Vec<String>,
+    pub tags: Vec<String>,
+    pub pass_threshold: Option<f64>,
+    pub concurrency: Option<usize>,
+}
+
+/// Request to update an existing test suite
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateTestSuiteRequest {
+    pub name: Option<String>,
+    pub description: Option<Option<String>>,
+    pub test_case_ids: Option<Vec<String>>,
+    pub tags: Option<Vec<String>>,
+    pub pass_threshold: Option<f64>,
+    pub concurrency: Option<usize>,
+}
+
+/// Test suite service for CRUD operations and runs
+pub struct TestSuiteService<R> {
+    repository: Arc<R>,
+    test_case_service: Arc<dyn TestCaseServiceTrait>,
+}
+
+impl<R: TestSuiteRepository> TestSuiteService<R> {
+    /// Create a new test suite service
+    pub fn new(repository: Arc<R>, test_case_service: Arc<dyn TestCaseServiceTrait>) -> Self {
+        Self {
+            repository,
+            test_case_service,
+        }
+    }
+
+    /// Get a test suite by ID
+    pub async fn get(&self, id: &str) -> Result<Option<TestSuite>, DomainError> {
+        let suite_id = self.parse_id(id)?;
+        self.repository.get(&suite_id).await
+    }
+
+    /// Get a test suite by ID, returning an error if not found
+    pub async fn get_required(&self, id: &str) -> Result<TestSuite, DomainError> {
+        self.get(id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("Test suite '{}' not found", id)))
+    }
+
+    /// List all test suites
+    pub async fn list(&self) -> Result<Vec<TestSuite>, DomainError> {
+        self.repository.list().await
+    }
+
+    /// Create a new test suite
+    pub async fn create(&self, request: CreateTestSuiteRequest) -> Result<TestSuite, DomainError> {
+        let suite_id = self.parse_id(&request.id)?;
+
+        if self.repository.get(&suite_id).await?.is_some() {
+            return Err(DomainError::conflict(format!(
+                "Test suite '{}' already exists",
+                request.id
+            )));
+        }
+
+        let test_case_ids = self.resolve_test_cases(&request.test_case_ids).await?;
+        let mut suite = TestSuite::new(suite_id, request.name, test_case_ids).with_tags(request.tags);
+
+        if let Some(description) = request.description {
+            suite = suite.with_description(description);
+        }
+
+        if let Some(pass_threshold) = request.pass_threshold {
+            suite = suite.with_pass_threshold(pass_threshold);
+        }
+
+        if let Some(concurrency) = request.concurrency {
+            suite = suite.with_concurrency(concurrency);
+        }
+
+        validate_test_suite(&suite).map_err(|e| DomainError::validation(e.to_string()))?;
+        self.repository.save(&suite).await?;
+        Ok(suite)
+    }
+
+    /// Update an existing test suite
+    pub async fn update(
+        &self,
+        id: &str,
+        request: UpdateTestSuiteRequest,
+    ) -> Result<TestSuite, DomainError> {
+        let mut suite = self.get_required(id).await?;
+
+        if let Some(name) = request.name {
+            suite.set_name(name);
+        }
+
+        if let Some(description) = request.description {
+            suite.set_description(description);
+        }
+
+        if let Some(ids) = request.test_case_ids {
+            suite.set_test_case_ids(self.resolve_test_cases(&ids).await?);
+        }
+
+        if let Some(tags) = request.tags {
+            suite.set_tags(tags);
+        }
+
+        if let Some(pass_threshold) = request.pass_threshold {
+            suite.set_pass_threshold(pass_threshold);
+        }
+
+        if let Some(concurrency) = request.concurrency {
+            suite.set_concurrency(concurrency);
+        }
+
+        validate_test_suite(&suite).map_err(|e| DomainError::validation(e.to_string()))?;
+        self.repository.save(&suite).await?;
+        Ok(suite)
+    }
+
+    /// Delete a test suite and its run reports
+    pub async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        let suite_id = self.parse_id(id)?;
+        self.repository.delete(&suite_id).await
+    }
+
+    /// Execute every case of a suite concurrently and store the aggregated
+    /// report. `pass_threshold` overrides the suite threshold for this run.
+    pub async fn run(
+        &self,
+        id: &str,
+        pass_threshold: Option<f64>,
+    ) -> Result<TestSuiteRun, DomainError> {
+        let suite = self.get_required(id).await?;
+        let pass_threshold = pass_threshold.unwrap_or(suite.pass_threshold());
+
+        if !(0.0..=100.0).contains(&pass_threshold) {
+            return Err(DomainError::validation(
+                "Pass threshold must be between 0 and 100",
+            ));
+        }
+
+        let started_at = Utc::now();
+        let outcomes: Vec<TestSuiteCaseOutcome> = stream::iter(suite.test_case_ids().to_vec())
+            .map(|test_case_id| async move { self.run_case(&test_case_id).await })
+            .buffered(suite.concurrency().max(1))
+            .collect()
+            .await;
+
+        let run = TestSuiteRun::from_outcomes(
+            suite.id().clone(),
+            pass_threshold,
+            outcomes,
+            started_at,
+        );
+        self.repository.save_run(&run).await?;
+
+        info!(
+            suite_id = %suite.id(),
+            run_id = %run.id,
+            passed = run.passed,
+            total = run.total,
+            gate_passed = run.gate_passed,
+            "Test suite run completed"
+        );
+
+        Ok(run)
+    }
+
+    /// List the run reports of a suite, newest first
+    pub async fn list_runs(
+        &self,
+        id: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<TestSuiteRun>, DomainError> {
+        let suite = self.get_required(id).await?;
+        self.repository.list_runs(suite.id(), limit).await
+    }
+
+    /// Get a run report by ID
+    pub async fn get_run(&self, run_id: &str) -> Result<Option<TestSuiteRun>, DomainError> {
+        self.repository
+            .get_run(&TestSuiteRunId::from_string(run_id))
+            .await
+    }
+
+    async fn run_case(&self, test_case_id: &TestCaseId) -> TestSuiteCaseOutcome {
+        match self.test_case_service.execute(test_case_id.as_str()).await {
+            Ok(result) => {
+                let error = result.error.or_else(|| {
+                    result
+                        .assertion_results
+                        .iter()
+                        .find(|a| !a.passed)
+                        .map(|a| format!("Assertion '{}' failed", a.name))
+                });
+
+                TestSuiteCaseOutcome {
+                    test_case_id: result.test_case_id,
+                    test_case_name: Some(result.test_case_name),
+                    passed: result.passed,
+                    execution_time_ms: result.execution_time_ms,
+                    error,
+                }
+            }
+            Err(e) => TestSuiteCaseOutcome {
+                test_case_id: test_case_id.to_string(),
+                test_case_name: None,
+                passed: false,
+                execution_time_ms: 0,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+
+    async fn resolve_test_cases(&self, ids: &[String]) -> Result<Vec<TestCaseId>, DomainError> {
+        let mut test_case_ids = Vec::with_capacity(ids.len());
+
+        for id in ids {
+            if self.test_case_service.get(id).await?.is_none() {
+                return Err(DomainError::validation(format!(
+                    "Test case '{}' not found",
+                    id
+                )));
+            }
+
+            test_case_ids
+                .push(TestCaseId::new(id).map_err(|e| DomainError::validation(e.to_string()))?);
+        }
+
+        Ok(test_case_ids)
+    }
+
+    fn parse_id(&self, id: &str) -> Result<TestSuiteId, DomainError> {
+        TestSuiteId::new(id).map_err(|e| DomainError::validation(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::storage::Storage;
+    use crate::domain::test_case::{
+        ModelPromptInput, TestCase, TestCaseQuery, TestCaseResult, TestCaseResultQuery,
+    };
+    use crate::infrastructure::services::{
+        CreateTestCaseRequest, ExecuteTestCaseResponse, ExecutionOverrides, UpdateTestCaseRequest,
+    };
+    use crate::infrastructure::storage::InMemoryStorage;
+    use crate::infrastructure::test_case::StorageTestSuiteRepository;
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+
+    /// Test case service whose cases pass unless their ID starts with `fail`
+    struct MockTestCaseService {
+        test_cases: HashMap<String, TestCase>,
+    }
+
+    impl MockTestCaseService {
+        fn with_cases(ids: &[&str]) -> Self {
+            let test_cases = ids
+                .iter()
+                .map(|id| {
+                    let test_case = TestCase::model_prompt(
+                        TestCaseId::new(*id).unwrap(),
+                        format!("Case {}", id),
+                        ModelPromptInput {
+                            model_id: "gpt-4".to_string(),
+                            prompt_id: None,
+                            variables: HashMap::new(),
+                            user_message: "Hello".to_string(),
+                            temperature: None,
+                            max_tokens: None,
+                        },
+                    );
+                    (id.to_string(), test_case)
+                })
+                .collect();
+
+            Self { test_cases }
+        }
+    }
+
+    #[async_trait]
+    impl TestCaseServiceTrait for MockTestCaseService {
+        async fn get(&self, id: &str) -> Result<Option<TestCase>, DomainError> {
+            Ok(self.test_cases.get(id).cloned())
+        }
+
+        async fn list(&self, _query: &TestCaseQuery) -> Result<Vec<TestCase>, DomainError> {
+            Ok(self.test_cases.values().cloned().collect())
+        }
+
+        async fn count(&self, _query: &TestCaseQuery) -> Result<usize, DomainError> {
+            Ok(self.test_cases.len())
+        }
+
+        async fn create(&self, _request: CreateTestCaseRequest) -> Result<TestCase, DomainError> {
+            unimplemented!()
+        }
+
+        async fn update(
+            &self,
+            _id: &str,
+            _request: UpdateTestCaseRequest,
+        ) -> Result<TestCase, DomainError> {
+            unimplemented!()
+        }
+
+        async fn delete(&self, _id: &str) -> Result<bool, DomainError> {
+            unimplemented!()
+        }
+
+        async fn execute(&self, id: &str) -> Result<ExecuteTestCaseResponse, DomainError> {
+            let test_case = self
+                .test_cases
+                .get(id)
+                .ok_or_else(|| DomainError::not_found(format!("Test case '{}' not found", id)))?;
+            let passed = !id.starts_with("fail");
+
+            Ok(ExecuteTestCaseResponse {
+                test_case_id: id.to_string(),
+                test_case_name: test_case.name().to_string(),
+                passed,
+                output: Some("Hello".to_string()),
+                assertion_results: Vec::new(),
+                execution_time_ms: 5,
+                tokens_used: None,
+                error: (!passed).then(|| "Unexpected output".to_string()),
+                dataset: None,
+            })
+        }
+
+        async fn evaluate(
+            &self,
+            id: &str,
+            _overrides: &ExecutionOverrides,
+        ) -> Result<ExecuteTestCaseResponse, DomainError> {
+            self.execute(id).await
+        }
+
+        async fn get_results(
+            &self,
+            _id: &str,
+            _query: &TestCaseResultQuery,
+        ) -> Result<Vec<TestCaseResult>, DomainError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_latest_result(
+            &self,
+            _id: &str,
+        ) -> Result<Option<TestCaseResult>, DomainError> {
+            Ok(None)
+        }
+    }
+
+    fn create_service(ids: &[&str]) -> TestSuiteService<StorageTestSuiteRepository> {
+        let suites: Arc<dyn Storage<TestSuite>> = Arc::new(InMemoryStorage::new());
+        let runs: Arc<dyn Storage<TestSuiteRun>> = Arc::new(InMemoryStorage::new());
+
+        TestSuiteService::new(
+            Arc::new(StorageTestSuiteRepository::new(suites, runs)),
+            Arc::new(MockTestCaseService::with_cases(ids)),
+        )
+    }
+
+    fn create_request(id: &str, test_case_ids: &[&str]) -> CreateTestSuiteRequest {
+        CreateTestSuiteRequest {
+            id: id.to_string(),
+            name: "Smoke".to_string(),
+            description: None,
+            test_case_ids: test_case_ids.iter().map(|s| s.to_string()).collect(),
+            tags: Vec::new(),
+            pass_threshold: None,
+            concurrency: Some(2),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_requires_existing_cases() {
+        let service = create_service(&["greeting"]);
+
+        let result = service
+            .create(create_request("smoke", &["greeting", "missing"]))
+            .await;
+        assert!(matches!(result, Err(DomainError::Validation { .. })));
+
+        service
+            .create(create_request("smoke", &["greeting"]))
+            .await
+            .unwrap();
+        let duplicate = service.create(create_request("smoke", &["greeting"])).await;
+        assert!(matches!(duplicate, Err(DomainError::Conflict { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_run_aggregates_and_gates() {
+        let service = create_service(&["a", "b", "c", "fail-d"]);
+        service
+            .create(create_request("smoke", &["a", "b", "c", "fail-d"]))
+            .await
+            .unwrap();
+
+        let run = service.run("smoke", None).await.unwrap();
+        assert_eq!(run.total, 4);
+        assert_eq!(run.passed, 3);
+        assert_eq!(run.pass_rate, 75.0);
+        assert!(!run.gate_passed);
+        assert_eq!(
+            run.cases.iter().map(|c| c.test_case_id.as_str()).collect::<Vec<_>>(),
+            vec!["a", "b", "c", "fail-d"]
+        );
+        assert_eq!(
+            run.failures().next().and_then(|c| c.error.as_deref()),
+            Some("Unexpected output")
+        );
+
+        let lenient = service.run("smoke", Some(75.0)).await.unwrap();
+        assert!(lenient.gate_passed);
+
+        let runs = service.list_runs("smoke", None).await.unwrap();
+        assert_eq!(runs.len(), 2);
+        assert!(service.get_run(lenient.id.as_str()).await.unwrap().is_some());
+    }
+}
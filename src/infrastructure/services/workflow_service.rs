@@ -2,11 +2,17 @@
 
 use std::sync::Arc;
 
+use tokio_util::sync::CancellationToken;
+
+use crate::domain::experiment::{apply_step_overrides, StepOverride};
+use crate::domain::knowledge_base::{validate_namespace, KnowledgeBaseCaller};
 use crate::domain::storage::Storage;
+use crate::domain::team::{QuotaResource, TeamId};
 use crate::domain::{
-    DomainError, Workflow, WorkflowExecutor, WorkflowId, WorkflowResult,
-    WorkflowStep, WorkflowStepType,
+    DomainError, FieldViolations, StepProgress, Workflow, WorkflowExecutor, WorkflowId,
+    WorkflowResult, WorkflowStep, WorkflowStepType,
 };
+use crate::infrastructure::team::TeamQuotaGuard;
 
 /// Request to create a new workflow
 #[derive(Debug, Clone)]
@@ -17,6 +23,8 @@ pub struct CreateWorkflowRequest {
     pub input_schema: Option<serde_json::Value>,
     pub steps: Vec<WorkflowStep>,
     pub enabled: bool,
+    /// Owning team, counted against its workflow quota
+    pub team_id: Option<TeamId>,
 }
 
 impl CreateWorkflowRequest {
@@ -28,6 +36,7 @@ impl CreateWorkflowRequest {
             input_schema: None,
             steps: Vec::new(),
             enabled: true,
+            team_id: None,
         }
     }
 
@@ -55,6 +64,11 @@ impl CreateWorkflowRequest {
         self.enabled = enabled;
         self
     }
+
+    pub fn with_team_id(mut self, team_id: TeamId) -> Self {
+        self.team_id = Some(team_id);
+        self
+    }
 }
 
 /// Request to update an existing workflow
@@ -102,6 +116,7 @@ impl UpdateWorkflowRequest {
 pub struct WorkflowService {
     storage: Arc<dyn Storage<Workflow>>,
     executor: Arc<dyn WorkflowExecutor>,
+    quota_guard: Option<TeamQuotaGuard>,
 }
 
 impl std::fmt::Debug for WorkflowService {
@@ -113,7 +128,17 @@ impl std::fmt::Debug for WorkflowService {
 impl WorkflowService {
     /// Create a new workflow service
     pub fn new(storage: Arc<dyn Storage<Workflow>>, executor: Arc<dyn WorkflowExecutor>) -> Self {
-        Self { storage, executor }
+        Self {
+            storage,
+            executor,
+            quota_guard: None,
+        }
+    }
+
+    /// Enforce team workflow quotas on creation
+    pub fn with_quota_guard(mut self, quota_guard: TeamQuotaGuard) -> Self {
+        self.quota_guard = Some(quota_guard);
+        self
     }
 
     /// Get a workflow by ID
@@ -133,9 +158,20 @@ impl WorkflowService {
         Ok(workflows.into_iter().filter(|w| w.is_enabled()).collect())
     }
 
+    /// Count the workflows owned by a team
+    pub async fn count_for_team(&self, team_id: &TeamId) -> Result<u64, DomainError> {
+        let workflows = self.storage.list().await?;
+
+        Ok(workflows
+            .iter()
+            .filter(|w| w.team_id() == Some(team_id))
+            .count() as u64)
+    }
+
     /// Create a new workflow
     pub async fn create(&self, request: CreateWorkflowRequest) -> Result<Workflow, DomainError> {
-        let workflow_id = self.parse_id(&request.id)?;
+        let workflow_id = WorkflowId::new(request.id.as_str())
+            .map_err(|e| DomainError::invalid_field("id", e.to_string()))?;
 
         // Check for duplicate
         if self.storage.exists(&workflow_id).await? {
@@ -148,6 +184,12 @@ impl WorkflowService {
         // Validate steps
         self.validate_steps(&request.steps)?;
 
+        if let (Some(guard), Some(team_id)) = (&self.quota_guard, &request.team_id) {
+            guard
+                .ensure(team_id, QuotaResource::Workflows, 1, || self.count_for_team(team_id))
+                .await?;
+        }
+
         // Build workflow
         let mut workflow = Workflow::new(workflow_id, request.name);
 
@@ -161,6 +203,10 @@ impl WorkflowService {
 
         workflow = workflow.with_steps(request.steps).with_enabled(request.enabled);
 
+        if let Some(team_id) = request.team_id {
+            workflow = workflow.with_team_id(team_id);
+        }
+
         self.storage.create(workflow).await
     }
 
@@ -169,6 +215,16 @@ impl WorkflowService {
         &self,
         id: &str,
         request: UpdateWorkflowRequest,
+    ) -> Result<Workflow, DomainError> {
+        let workflow = self.preview_update(id, request).await?;
+        self.storage.update(workflow).await
+    }
+
+    /// Apply an update to a copy of a workflow without saving it
+    pub async fn preview_update(
+        &self,
+        id: &str,
+        request: UpdateWorkflowRequest,
     ) -> Result<Workflow, DomainError> {
         let workflow_id = self.parse_id(id)?;
 
@@ -200,7 +256,7 @@ impl WorkflowService {
             workflow.set_enabled(enabled);
         }
 
-        self.storage.update(workflow).await
+        Ok(workflow)
     }
 
     /// Delete a workflow
@@ -229,6 +285,76 @@ impl WorkflowService {
 
     /// Execute a workflow with the given input
     pub async fn execute(&self, id: &str, input: serde_json::Value) -> Result<WorkflowResult, DomainError> {
+        let workflow = self.get_enabled(id).await?;
+
+        self.executor
+            .execute(&workflow, input)
+            .await
+            .map_err(|e| DomainError::internal(e.to_string()))
+    }
+
+    /// Execute a workflow on behalf of a caller, whose knowledge base
+    /// searches only return the documents it may read
+    pub async fn execute_as(
+        &self,
+        id: &str,
+        input: serde_json::Value,
+        caller: KnowledgeBaseCaller,
+    ) -> Result<WorkflowResult, DomainError> {
+        let workflow = self.get_enabled(id).await?;
+
+        self.executor
+            .execute_as(&workflow, input, caller)
+            .await
+            .map_err(|e| DomainError::internal(e.to_string()))
+    }
+
+    /// Execute a workflow on behalf of a caller until it finishes or
+    /// `cancellation` is fired, reporting each step to `step_progress`
+    pub async fn execute_cancellable(
+        &self,
+        id: &str,
+        input: serde_json::Value,
+        caller: KnowledgeBaseCaller,
+        cancellation: CancellationToken,
+        step_progress: StepProgress,
+    ) -> Result<WorkflowResult, DomainError> {
+        let workflow = self.get_enabled(id).await?;
+
+        self.executor
+            .execute_cancellable(&workflow, input, caller, cancellation, step_progress)
+            .await
+            .map_err(|e| DomainError::internal(e.to_string()))
+    }
+
+    /// Execute a workflow with the step overrides of an experiment variant
+    /// applied; the saved workflow is not changed
+    pub async fn execute_variant(
+        &self,
+        id: &str,
+        step_overrides: &[StepOverride],
+        input: serde_json::Value,
+        caller: KnowledgeBaseCaller,
+        cancellation: CancellationToken,
+        step_progress: StepProgress,
+    ) -> Result<WorkflowResult, DomainError> {
+        let mut workflow = self.get_enabled(id).await?;
+
+        if !step_overrides.is_empty() {
+            let steps = apply_step_overrides(workflow.steps(), step_overrides)
+                .map_err(DomainError::validation)?;
+            self.validate_steps(&steps)?;
+            workflow.set_steps(steps);
+        }
+
+        self.executor
+            .execute_cancellable(&workflow, input, caller, cancellation, step_progress)
+            .await
+            .map_err(|e| DomainError::internal(e.to_string()))
+    }
+
+    /// Get a workflow that can be executed
+    async fn get_enabled(&self, id: &str) -> Result<Workflow, DomainError> {
         let workflow_id = self.parse_id(id)?;
 
         let workflow = self
@@ -244,8 +370,18 @@ impl WorkflowService {
             )));
         }
 
+        Ok(workflow)
+    }
+
+    /// Execute a workflow definition that may not be saved, such as a
+    /// pending update
+    pub async fn execute_definition(
+        &self,
+        workflow: &Workflow,
+        input: serde_json::Value,
+    ) -> Result<WorkflowResult, DomainError> {
         self.executor
-            .execute(&workflow, input)
+            .execute(workflow, input)
             .await
             .map_err(|e| DomainError::internal(e.to_string()))
     }
@@ -257,28 +393,41 @@ impl WorkflowService {
 
     /// Validate workflow steps
     fn validate_steps(&self, steps: &[WorkflowStep]) -> Result<(), DomainError> {
+        let mut violations = FieldViolations::new();
+        self.check_steps(steps, &mut violations)?;
+        violations.into_result()
+    }
+
+    /// Record every invalid step, under `steps[<index>]`
+    fn check_steps(
+        &self,
+        steps: &[WorkflowStep],
+        violations: &mut FieldViolations,
+    ) -> Result<(), DomainError> {
         // Check for empty steps
         if steps.is_empty() {
-            return Err(DomainError::validation(
-                "Workflow must have at least one step",
-            ));
+            violations.push("steps", "Workflow must have at least one step");
+            return Ok(());
         }
 
         // Check for unique step names
         let mut seen_names = std::collections::HashSet::new();
 
-        for step in steps {
+        for (index, step) in steps.iter().enumerate() {
+            let field = format!("steps[{}]", index);
+
             if !seen_names.insert(step.name()) {
-                return Err(DomainError::validation(format!(
-                    "Duplicate step name: '{}'",
-                    step.name()
-                )));
+                violations.push(
+                    format!("{}.name", field),
+                    format!("Duplicate step name: '{}'", step.name()),
+                );
             }
-        }
 
-        // Validate each step
-        for step in steps {
-            self.validate_step(step)?;
+            match self.validate_step(step) {
+                Ok(()) => {}
+                Err(DomainError::Validation { message }) => violations.push(field, message),
+                Err(e) => return Err(e),
+            }
         }
 
         Ok(())
@@ -337,6 +486,14 @@ impl WorkflowService {
                 if kb_step.query.is_empty() {
                     return Err(DomainError::validation("KnowledgeBaseSearch step requires query"));
                 }
+
+                // Namespaces given as variables are checked when resolved
+                if let Some(namespace) = &kb_step.namespace
+                    && !namespace.contains("${")
+                {
+                    validate_namespace(namespace)
+                        .map_err(|e| DomainError::validation(e.to_string()))?;
+                }
             }
             WorkflowStepType::CragScoring(crag_step) => {
                 if crag_step.model_id.is_empty() {
@@ -441,6 +598,15 @@ mod tests {
                 0,
             ))
         }
+
+        async fn execute_as(
+            &self,
+            workflow: &Workflow,
+            input: serde_json::Value,
+            _caller: KnowledgeBaseCaller,
+        ) -> Result<WorkflowResult, WorkflowError> {
+            self.execute(workflow, input).await
+        }
     }
 
     fn create_mock_executor() -> Arc<MockExecutor> {
@@ -549,6 +715,41 @@ mod tests {
         assert!(!exists);
     }
 
+    #[tokio::test]
+    async fn test_execute_variant() {
+        let workflow = Workflow::new(WorkflowId::new("test").unwrap(), "Test")
+            .with_step(create_chat_step("s1"))
+            .with_step(create_chat_step("s2"));
+        let storage = Arc::new(MockStorage::<Workflow>::new().with_entity(workflow));
+        let service = WorkflowService::new(storage, create_mock_executor());
+        let execute = |overrides: Vec<StepOverride>| {
+            let service = &service;
+            async move {
+                service
+                    .execute_variant(
+                        "test",
+                        &overrides,
+                        serde_json::json!({}),
+                        KnowledgeBaseCaller::new("team"),
+                        CancellationToken::new(),
+                        StepProgress::new(|_, _| {}),
+                    )
+                    .await
+            }
+        };
+
+        assert!(execute(vec![StepOverride::skip("s2")]).await.unwrap().success);
+
+        let unknown = execute(vec![StepOverride::skip("s3")]).await.unwrap_err();
+        assert!(unknown.to_string().contains("no step 's3'"));
+
+        let invalid = StepOverride::set("s1", serde_json::json!({"model_id": ""}));
+        assert!(execute(vec![invalid]).await.is_err());
+
+        // The saved workflow keeps its steps
+        assert_eq!(service.get("test").await.unwrap().unwrap().step_count(), 2);
+    }
+
     #[tokio::test]
     async fn test_enable_disable() {
         let workflow = Workflow::new(WorkflowId::new("test").unwrap(), "Test").with_enabled(true);
@@ -674,6 +875,32 @@ mod tests {
             .contains("requires knowledge_base_id"));
     }
 
+    #[tokio::test]
+    async fn test_validate_kb_step_namespace() {
+        let storage = Arc::new(MockStorage::<Workflow>::new());
+        let executor = create_mock_executor();
+        let service = WorkflowService::new(storage, executor);
+
+        let step = WorkflowStep::new(
+            "test",
+            WorkflowStepType::KnowledgeBaseSearch(
+                KnowledgeBaseSearchStep::new("docs", "query").with_namespace("v 2"),
+            ),
+        );
+        let request = CreateWorkflowRequest::new("test1", "Test").with_step(step);
+        let result = service.create(request).await;
+        assert!(result.unwrap_err().to_string().contains("Invalid namespace"));
+
+        let step = WorkflowStep::new(
+            "test",
+            WorkflowStepType::KnowledgeBaseSearch(
+                KnowledgeBaseSearchStep::new("docs", "query").with_namespace("${request:version}"),
+            ),
+        );
+        let request = CreateWorkflowRequest::new("test2", "Test").with_step(step);
+        assert!(service.create(request).await.is_ok());
+    }
+
     #[tokio::test]
     async fn test_validate_resource_ids_not_variables() {
         let storage = Arc::new(MockStorage::<Workflow>::new());
@@ -839,4 +1066,40 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
+
+    #[tokio::test]
+    async fn test_create_enforces_team_quota() {
+        use crate::domain::team::{Team, TeamQuota, TeamRepository};
+        use crate::infrastructure::storage::InMemoryStorage;
+        use crate::infrastructure::team::StorageTeamRepository;
+
+        let teams = StorageTeamRepository::new(Arc::new(InMemoryStorage::<Team>::new()));
+        let mut team = Team::new(TeamId::new("team-a").unwrap(), "Team A").unwrap();
+        team.set_quota(TeamQuota::new().with_limit(QuotaResource::Workflows, 1));
+        teams.create(team).await.unwrap();
+
+        let storage = Arc::new(MockStorage::<Workflow>::new());
+        let service = WorkflowService::new(storage, create_mock_executor())
+            .with_quota_guard(TeamQuotaGuard::new(Arc::new(teams)));
+        let team_id = TeamId::new("team-a").unwrap();
+
+        let workflow = service
+            .create(
+                CreateWorkflowRequest::new("wf-1", "First")
+                    .with_step(create_chat_step("s1"))
+                    .with_team_id(team_id.clone()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(workflow.team_id(), Some(&team_id));
+
+        let result = service
+            .create(
+                CreateWorkflowRequest::new("wf-2", "Second")
+                    .with_step(create_chat_step("s1"))
+                    .with_team_id(team_id),
+            )
+            .await;
+        assert!(result.unwrap_err().to_string().contains("quota exceeded"));
+    }
 }
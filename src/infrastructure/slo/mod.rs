@@ -0,0 +1,7 @@
+//! Model SLO infrastructure implementations
+
+mod service;
+mod storage_repository;
+
+pub use service::{spawn_slo_evaluation, SloService};
+pub use storage_repository::StorageSloRepository;
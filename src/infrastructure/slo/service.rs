@@ -0,0 +1,277 @@
+//! Model SLO management and evaluation
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use chrono::{TimeZone, Utc};
+use tracing::{info, warn};
+
+use crate::domain::slo::{evaluate_slo, Slo, SloId, SloReport, SloRepository, SloStatus};
+use crate::domain::{DomainError, ExecutionLogQuery, ExecutionLogRepository};
+
+/// Manages model SLOs and evaluates them over execution logs. The latest
+/// report of each SLO is kept so routing can skip models burning their
+/// budgets without querying the logs per request.
+pub struct SloService<R: SloRepository> {
+    repository: Arc<R>,
+    execution_logs: Arc<dyn ExecutionLogRepository>,
+    reports: RwLock<HashMap<String, SloReport>>,
+}
+
+impl<R: SloRepository> SloService<R> {
+    /// Create a new SLO service
+    pub fn new(repository: Arc<R>, execution_logs: Arc<dyn ExecutionLogRepository>) -> Self {
+        Self {
+            repository,
+            execution_logs,
+            reports: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Every SLO, ordered by model
+    pub async fn list(&self) -> Result<Vec<Slo>, DomainError> {
+        self.repository.list().await
+    }
+
+    /// SLO of a model
+    pub async fn get(&self, model_id: &str) -> Result<Option<Slo>, DomainError> {
+        self.repository.get(&SloId::new(model_id)).await
+    }
+
+    /// Create or replace the SLO of a model, discarding its previous report
+    pub async fn set(&self, mut slo: Slo) -> Result<Slo, DomainError> {
+        slo.validate()?;
+
+        if let Some(existing) = self.repository.get(&slo.id).await? {
+            slo.created_at = existing.created_at;
+        }
+        slo.updated_at = Utc::now().timestamp().max(0) as u64;
+
+        let saved = self.repository.save(slo).await?;
+        self.reports.write().unwrap().remove(&saved.model_id);
+
+        Ok(saved)
+    }
+
+    /// Delete the SLO of a model
+    pub async fn delete(&self, model_id: &str) -> Result<bool, DomainError> {
+        let deleted = self.repository.delete(&SloId::new(model_id)).await?;
+        self.reports.write().unwrap().remove(model_id);
+
+        Ok(deleted)
+    }
+
+    /// Evaluate the SLO of a model at `now`, none if the model has no SLO
+    pub async fn evaluate(
+        &self,
+        model_id: &str,
+        now: u64,
+    ) -> Result<Option<SloReport>, DomainError> {
+        match self.get(model_id).await? {
+            Some(slo) => Ok(Some(self.evaluate_slo(&slo, now).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Evaluate every enabled SLO at `now`
+    pub async fn evaluate_all(&self, now: u64) -> Result<Vec<SloReport>, DomainError> {
+        let mut reports = Vec::new();
+
+        for slo in self.repository.list().await? {
+            if slo.enabled {
+                reports.push(self.evaluate_slo(&slo, now).await?);
+            } else {
+                self.reports.write().unwrap().remove(&slo.model_id);
+            }
+        }
+
+        Ok(reports)
+    }
+
+    async fn evaluate_slo(&self, slo: &Slo, now: u64) -> Result<SloReport, DomainError> {
+        let from = Utc
+            .timestamp_opt(now.saturating_sub(slo.window_secs) as i64, 0)
+            .single()
+            .unwrap_or_default();
+        let to = Utc
+            .timestamp_opt(now as i64, 0)
+            .single()
+            .unwrap_or_else(Utc::now);
+
+        let logs = self
+            .execution_logs
+            .list(
+                &ExecutionLogQuery::new()
+                    .with_resource_id(&slo.model_id)
+                    .with_date_range(from, to),
+            )
+            .await?;
+        let report = evaluate_slo(slo, &logs, now);
+
+        let previous = self
+            .reports
+            .write()
+            .unwrap()
+            .insert(slo.model_id.clone(), report.clone());
+
+        if report.is_breached() && previous.is_none_or(|p| !p.is_breached()) {
+            warn!(
+                model_id = %report.model_id,
+                error_rate = report.error_rate,
+                p95_latency_ms = ?report.p95_latency_ms,
+                burn_rate = ?report.burn_rate(),
+                "Model SLO breached"
+            );
+        }
+
+        Ok(report)
+    }
+
+    /// Latest report of every evaluated SLO, ordered by model
+    pub fn reports(&self) -> Vec<SloReport> {
+        let mut reports: Vec<_> = self.reports.read().unwrap().values().cloned().collect();
+        reports.sort_by(|a, b| a.model_id.cmp(&b.model_id));
+        reports
+    }
+
+    /// Latest report of a model's SLO
+    pub fn report(&self, model_id: &str) -> Option<SloReport> {
+        self.reports.read().unwrap().get(model_id).cloned()
+    }
+
+    /// Latest status of a model's SLO, none if it was never evaluated
+    pub fn status(&self, model_id: &str) -> Option<SloStatus> {
+        self.reports
+            .read()
+            .unwrap()
+            .get(model_id)
+            .map(|report| report.status)
+    }
+
+    /// Whether a model burnt its latency or error budget at the latest
+    /// evaluation. Routing strategies use this to prefer healthy models.
+    pub fn is_breached(&self, model_id: &str) -> bool {
+        self.status(model_id) == Some(SloStatus::Breached)
+    }
+}
+
+/// Evaluate every enabled SLO every `interval`
+pub fn spawn_slo_evaluation<R: SloRepository + 'static>(
+    service: Arc<SloService<R>>,
+    interval: Duration,
+) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            match service
+                .evaluate_all(Utc::now().timestamp().max(0) as u64)
+                .await
+            {
+                Ok(reports) => {
+                    let breached = reports.iter().filter(|r| r.is_breached()).count();
+
+                    if breached > 0 {
+                        info!(breached, total = reports.len(), "Evaluated model SLOs");
+                    }
+                }
+                Err(e) => warn!(error = %e, "SLO evaluation failed"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{ExecutionLog, ExecutionType, Executor};
+    use crate::infrastructure::config::StorageExecutionLogRepository;
+    use crate::infrastructure::slo::StorageSloRepository;
+    use crate::infrastructure::storage::InMemoryStorage;
+
+    fn create_service() -> (
+        SloService<StorageSloRepository>,
+        Arc<StorageExecutionLogRepository>,
+    ) {
+        let logs = Arc::new(StorageExecutionLogRepository::new(Arc::new(
+            InMemoryStorage::<ExecutionLog>::new(),
+        )));
+        let repository = Arc::new(StorageSloRepository::new(Arc::new(
+            InMemoryStorage::<Slo>::new(),
+        )));
+
+        (SloService::new(repository, logs.clone()), logs)
+    }
+
+    #[tokio::test]
+    async fn test_set_validates_and_keeps_creation_date() {
+        let (service, _) = create_service();
+
+        assert!(service.set(Slo::new("gpt-4o")).await.is_err());
+
+        let mut slo = Slo::new("gpt-4o").with_error_budget(0.01);
+        slo.created_at = 100;
+        service.set(slo).await.unwrap();
+
+        let updated = service
+            .set(Slo::new("gpt-4o").with_latency_p95_ms(2_000))
+            .await
+            .unwrap();
+
+        assert_eq!(updated.created_at, 100);
+        assert_eq!(updated.error_budget, None);
+        assert_eq!(service.list().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_all_tracks_breached_models() {
+        let (service, logs) = create_service();
+        let now = Utc::now().timestamp() as u64;
+
+        service
+            .set(Slo::new("gpt-4o").with_error_budget(0.1))
+            .await
+            .unwrap();
+        service
+            .set(
+                Slo::new("claude-3")
+                    .with_error_budget(0.1)
+                    .with_enabled(false),
+            )
+            .await
+            .unwrap();
+
+        logs.save(&ExecutionLog::success(
+            ExecutionType::ChatCompletion,
+            "gpt-4o",
+            100,
+            Executor::anonymous(),
+        ))
+        .await
+        .unwrap();
+        logs.save(&ExecutionLog::failed(
+            ExecutionType::ChatCompletion,
+            "gpt-4o",
+            "Provider error",
+            100,
+            Executor::anonymous(),
+        ))
+        .await
+        .unwrap();
+
+        let reports = service.evaluate_all(now + 1).await.unwrap();
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].requests, 2);
+        assert!(service.is_breached("gpt-4o"));
+        assert!(!service.is_breached("claude-3"));
+        assert_eq!(service.reports().len(), 1);
+
+        assert!(service.delete("gpt-4o").await.unwrap());
+        assert!(service.report("gpt-4o").is_none());
+        assert!(service.evaluate("gpt-4o", now).await.unwrap().is_none());
+    }
+}
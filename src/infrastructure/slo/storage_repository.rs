@@ -0,0 +1,43 @@
+//! Storage-backed SLO repository
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::domain::slo::{Slo, SloId, SloRepository};
+use crate::domain::storage::Storage;
+use crate::domain::DomainError;
+
+/// SLO repository backed by a generic storage
+#[derive(Debug)]
+pub struct StorageSloRepository {
+    storage: Arc<dyn Storage<Slo>>,
+}
+
+impl StorageSloRepository {
+    /// Create a new storage-backed repository
+    pub fn new(storage: Arc<dyn Storage<Slo>>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl SloRepository for StorageSloRepository {
+    async fn get(&self, id: &SloId) -> Result<Option<Slo>, DomainError> {
+        self.storage.get(id).await
+    }
+
+    async fn list(&self) -> Result<Vec<Slo>, DomainError> {
+        let mut slos = self.storage.list().await?;
+        slos.sort_by(|a, b| a.model_id.cmp(&b.model_id));
+        Ok(slos)
+    }
+
+    async fn save(&self, slo: Slo) -> Result<Slo, DomainError> {
+        self.storage.save(slo).await
+    }
+
+    async fn delete(&self, id: &SloId) -> Result<bool, DomainError> {
+        self.storage.delete(id).await
+    }
+}
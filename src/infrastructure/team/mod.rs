@@ -1,7 +1,9 @@
 //! Team infrastructure implementations
 
+mod quota;
 mod repository;
 mod service;
 
+pub use quota::{ConcurrencyPermit, TeamConcurrencyLimiter, TeamQuotaGuard};
 pub use repository::StorageTeamRepository;
 pub use service::{CreateTeamRequest, TeamService, UpdateTeamRequest};
@@ -0,0 +1,206 @@
+//! Team quota enforcement
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use crate::domain::team::{QuotaResource, TeamId, TeamRepository};
+use crate::domain::DomainError;
+
+/// Checks resource creation against the quota of the owning team.
+///
+/// Services own the usage computation of their resources; the guard only
+/// computes it when the team actually limits the resource.
+#[derive(Debug, Clone)]
+pub struct TeamQuotaGuard {
+    teams: Arc<dyn TeamRepository>,
+}
+
+impl TeamQuotaGuard {
+    /// Create a new quota guard backed by the team repository
+    pub fn new(teams: Arc<dyn TeamRepository>) -> Self {
+        Self { teams }
+    }
+
+    /// Get the limit of a resource for a team, `None` if unlimited or the
+    /// team does not exist
+    pub async fn limit(
+        &self,
+        team_id: &TeamId,
+        resource: QuotaResource,
+    ) -> Result<Option<u64>, DomainError> {
+        let team = self.teams.get(team_id).await?;
+        Ok(team.and_then(|t| t.quota().limit(resource)))
+    }
+
+    /// Ensure the team can take `requested` more units of a resource
+    pub async fn ensure<F, Fut>(
+        &self,
+        team_id: &TeamId,
+        resource: QuotaResource,
+        requested: u64,
+        usage: F,
+    ) -> Result<(), DomainError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<u64, DomainError>>,
+    {
+        let Some(team) = self.teams.get(team_id).await? else {
+            return Ok(());
+        };
+
+        if team.quota().limit(resource).is_none() {
+            return Ok(());
+        }
+
+        let current = usage().await?;
+
+        team.quota()
+            .check(resource, current, requested)
+            .map_err(|e| DomainError::conflict(format!("Team '{}' {}", team_id, e)))
+    }
+}
+
+/// Tracks in-flight requests per team and enforces concurrency limits
+#[derive(Debug, Default)]
+pub struct TeamConcurrencyLimiter {
+    in_flight: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+impl TeamConcurrencyLimiter {
+    /// Create a new limiter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Acquire a slot for a request of the team. Returns `None` if the team
+    /// already has `limit` requests in flight.
+    pub fn try_acquire(&self, team_id: &TeamId, limit: Option<u64>) -> Option<ConcurrencyPermit> {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        let count = in_flight.entry(team_id.as_str().to_string()).or_insert(0);
+
+        if limit.is_some_and(|limit| *count >= limit) {
+            return None;
+        }
+
+        *count += 1;
+
+        Some(ConcurrencyPermit {
+            team_id: team_id.as_str().to_string(),
+            in_flight: self.in_flight.clone(),
+        })
+    }
+
+    /// Number of requests of the team currently in flight
+    pub fn in_flight(&self, team_id: &TeamId) -> u64 {
+        self.in_flight
+            .lock()
+            .unwrap()
+            .get(team_id.as_str())
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// Slot held by an in-flight request, released on drop
+#[derive(Debug)]
+pub struct ConcurrencyPermit {
+    team_id: String,
+    in_flight: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+impl Drop for ConcurrencyPermit {
+    fn drop(&mut self) {
+        let mut in_flight = self.in_flight.lock().unwrap();
+
+        if let Some(count) = in_flight.get_mut(&self.team_id) {
+            *count = count.saturating_sub(1);
+
+            if *count == 0 {
+                in_flight.remove(&self.team_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::team::{Team, TeamQuota};
+    use crate::infrastructure::storage::InMemoryStorage;
+    use crate::infrastructure::team::StorageTeamRepository;
+
+    async fn create_guard(quota: TeamQuota) -> TeamQuotaGuard {
+        let repository = StorageTeamRepository::new(Arc::new(InMemoryStorage::<Team>::new()));
+        let mut team = Team::new(TeamId::new("team-a").unwrap(), "Team A").unwrap();
+        team.set_quota(quota);
+        repository.create(team).await.unwrap();
+
+        TeamQuotaGuard::new(Arc::new(repository))
+    }
+
+    #[tokio::test]
+    async fn test_ensure_within_and_over_limit() {
+        let guard = create_guard(TeamQuota::new().with_limit(QuotaResource::Workflows, 2)).await;
+        let team_id = TeamId::new("team-a").unwrap();
+
+        assert!(guard
+            .ensure(&team_id, QuotaResource::Workflows, 1, || async { Ok(1) })
+            .await
+            .is_ok());
+
+        let result = guard
+            .ensure(&team_id, QuotaResource::Workflows, 1, || async { Ok(2) })
+            .await;
+        assert!(matches!(result, Err(DomainError::Conflict { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_ensure_skips_usage_when_unlimited() {
+        let guard = create_guard(TeamQuota::new()).await;
+
+        let known = TeamId::new("team-a").unwrap();
+        let unknown = TeamId::new("team-b").unwrap();
+
+        for team_id in [known, unknown] {
+            let result = guard
+                .ensure(&team_id, QuotaResource::ApiKeys, 1, || async {
+                    Err(DomainError::internal("usage should not be computed"))
+                })
+                .await;
+            assert!(result.is_ok());
+        }
+    }
+
+    #[test]
+    fn test_concurrency_limiter() {
+        let limiter = TeamConcurrencyLimiter::new();
+        let team_id = TeamId::new("team-a").unwrap();
+
+        let first = limiter.try_acquire(&team_id, Some(2)).unwrap();
+        let second = limiter.try_acquire(&team_id, Some(2)).unwrap();
+        assert!(limiter.try_acquire(&team_id, Some(2)).is_none());
+        assert_eq!(limiter.in_flight(&team_id), 2);
+
+        drop(first);
+        assert_eq!(limiter.in_flight(&team_id), 1);
+        assert!(limiter.try_acquire(&team_id, Some(2)).is_some());
+
+        drop(second);
+        assert_eq!(limiter.in_flight(&team_id), 0);
+    }
+
+    #[test]
+    fn test_concurrency_limiter_unlimited() {
+        let limiter = TeamConcurrencyLimiter::new();
+        let team_id = TeamId::new("team-a").unwrap();
+
+        let permits: Vec<_> = (0..10)
+            .map(|_| limiter.try_acquire(&team_id, None).unwrap())
+            .collect();
+        assert_eq!(limiter.in_flight(&team_id), 10);
+
+        drop(permits);
+        assert_eq!(limiter.in_flight(&team_id), 0);
+    }
+}
@@ -96,6 +96,12 @@ fn filter_teams<'a>(
             }
         }
 
+        if let Some(ref organization_id) = query.organization_id
+            && team.organization_id().map(|o| o.as_str()) != Some(organization_id.as_str())
+        {
+            return false;
+        }
+
         true
     })
 }
@@ -103,6 +109,7 @@ fn filter_teams<'a>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::organization::OrganizationId;
     use crate::infrastructure::storage::InMemoryStorage;
 
     fn create_repo() -> StorageTeamRepository {
@@ -217,6 +224,22 @@ mod tests {
         assert_eq!(count, 2);
     }
 
+    #[tokio::test]
+    async fn test_filter_by_organization() {
+        let repo = create_repo();
+
+        let mut team = create_team("team-a", "Team A");
+        team.set_organization_id(Some(OrganizationId::new("acme").unwrap()));
+        repo.create(team).await.unwrap();
+        repo.create(create_team("team-b", "Team B")).await.unwrap();
+
+        let query = TeamQuery::new().with_organization_id("acme");
+        let teams = repo.list(&query).await.unwrap();
+        assert_eq!(teams.len(), 1);
+        assert_eq!(teams[0].id().as_str(), "team-a");
+        assert_eq!(repo.count(&query).await.unwrap(), 1);
+    }
+
     #[tokio::test]
     async fn test_exists() {
         let repo = create_repo();
@@ -4,7 +4,12 @@ use std::sync::Arc;
 
 use tracing::{debug, info};
 
-use crate::domain::team::{Team, TeamId, TeamQuery, TeamRepository, TeamStatus, validate_team_name};
+use crate::domain::network::IpNetwork;
+use crate::domain::residency::Region;
+use crate::domain::team::{
+    Team, TeamId, TeamQuery, TeamQuota, TeamRepository, TeamRequestDefaults, TeamStatus,
+    validate_team_name,
+};
 use crate::domain::DomainError;
 
 /// Request for creating a new team
@@ -20,6 +25,11 @@ pub struct CreateTeamRequest {
 pub struct UpdateTeamRequest {
     pub name: Option<String>,
     pub description: Option<String>,
+    pub allowed_cidrs: Option<Vec<IpNetwork>>,
+    pub allowed_regions: Option<Vec<Region>>,
+    pub no_log: Option<bool>,
+    pub quota: Option<TeamQuota>,
+    pub request_defaults: Option<TeamRequestDefaults>,
 }
 
 /// Team service for managing teams
@@ -107,6 +117,27 @@ impl<R: TeamRepository> TeamService<R> {
             team.set_description(Some(desc));
         }
 
+        if let Some(allowed_cidrs) = request.allowed_cidrs {
+            team.set_allowed_cidrs(allowed_cidrs);
+        }
+
+        if let Some(allowed_regions) = request.allowed_regions {
+            team.set_allowed_regions(allowed_regions);
+        }
+
+        if let Some(no_log) = request.no_log {
+            team.set_no_log(no_log);
+        }
+
+        if let Some(quota) = request.quota {
+            team.set_quota(quota);
+        }
+
+        if let Some(request_defaults) = request.request_defaults {
+            request_defaults.validate()?;
+            team.set_request_defaults(request_defaults);
+        }
+
         self.repository.update(team).await
     }
 
@@ -197,7 +228,7 @@ impl<R: TeamRepository> TeamService<R> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::team::Team;
+    use crate::domain::team::{QuotaResource, Team};
     use crate::infrastructure::storage::InMemoryStorage;
     use crate::infrastructure::team::StorageTeamRepository;
 
@@ -291,11 +322,24 @@ mod tests {
         let update = UpdateTeamRequest {
             name: Some("Updated Team".to_string()),
             description: Some("New description".to_string()),
+            allowed_cidrs: Some(vec!["10.0.0.0/8".parse().unwrap()]),
+            allowed_regions: Some(vec!["eu".parse().unwrap()]),
+            no_log: Some(true),
+            quota: Some(TeamQuota::new().with_limit(QuotaResource::Workflows, 3)),
+            request_defaults: Some(TeamRequestDefaults {
+                max_tokens: Some(2000),
+                ..Default::default()
+            }),
         };
 
         let updated = service.update("test-team", update).await.unwrap();
         assert_eq!(updated.name(), "Updated Team");
         assert_eq!(updated.description(), Some("New description"));
+        assert_eq!(updated.allowed_cidrs().len(), 1);
+        assert_eq!(updated.allowed_regions()[0].as_str(), "eu");
+        assert!(updated.no_log());
+        assert_eq!(updated.quota().limit(QuotaResource::Workflows), Some(3));
+        assert_eq!(updated.request_defaults().max_tokens, Some(2000));
     }
 
     #[tokio::test]
@@ -4,4 +4,6 @@ mod repository;
 mod storage_repository;
 
 pub use repository::{InMemoryTestCaseRepository, InMemoryTestCaseResultRepository};
-pub use storage_repository::{StorageTestCaseRepository, StorageTestCaseResultRepository};
+pub use storage_repository::{
+    StorageTestCaseRepository, StorageTestCaseResultRepository, StorageTestSuiteRepository,
+};
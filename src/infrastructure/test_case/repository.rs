@@ -63,6 +63,11 @@ impl TestCaseRepository for InMemoryTestCaseRepository {
                     }
                 }
 
+                // Filter by canary
+                if query.canary.is_some_and(|canary| tc.is_canary() != canary) {
+                    return false;
+                }
+
                 // Filter by tag
                 if let Some(ref tag) = query.tag {
                     if !tc.tags().contains(tag) {
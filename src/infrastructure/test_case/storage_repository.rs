@@ -6,7 +6,8 @@ use std::sync::Arc;
 use crate::domain::storage::Storage;
 use crate::domain::test_case::{
     TestCase, TestCaseId, TestCaseInput, TestCaseQuery, TestCaseRepository, TestCaseResult,
-    TestCaseResultId, TestCaseResultQuery, TestCaseResultRepository,
+    TestCaseResultId, TestCaseResultQuery, TestCaseResultRepository, TestSuite, TestSuiteId,
+    TestSuiteRepository, TestSuiteRun, TestSuiteRunId,
 };
 use crate::domain::DomainError;
 
@@ -49,6 +50,11 @@ impl TestCaseRepository for StorageTestCaseRepository {
                     }
                 }
 
+                // Filter by canary
+                if query.canary.is_some_and(|canary| tc.is_canary() != canary) {
+                    return false;
+                }
+
                 // Filter by tag
                 if let Some(ref tag) = query.tag {
                     if !tc.tags().contains(tag) {
@@ -205,6 +211,83 @@ impl TestCaseResultRepository for StorageTestCaseResultRepository {
     }
 }
 
+/// Storage-backed implementation of TestSuiteRepository
+#[derive(Debug)]
+pub struct StorageTestSuiteRepository {
+    storage: Arc<dyn Storage<TestSuite>>,
+    run_storage: Arc<dyn Storage<TestSuiteRun>>,
+}
+
+impl StorageTestSuiteRepository {
+    /// Create a new storage-backed repository
+    pub fn new(
+        storage: Arc<dyn Storage<TestSuite>>,
+        run_storage: Arc<dyn Storage<TestSuiteRun>>,
+    ) -> Self {
+        Self {
+            storage,
+            run_storage,
+        }
+    }
+}
+
+#[async_trait]
+impl TestSuiteRepository for StorageTestSuiteRepository {
+    async fn get(&self, id: &TestSuiteId) -> Result<Option<TestSuite>, DomainError> {
+        self.storage.get(id).await
+    }
+
+    async fn list(&self) -> Result<Vec<TestSuite>, DomainError> {
+        let mut suites = self.storage.list().await?;
+        suites.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(suites)
+    }
+
+    async fn save(&self, suite: &TestSuite) -> Result<(), DomainError> {
+        self.storage.save(suite.clone()).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &TestSuiteId) -> Result<bool, DomainError> {
+        for run in self.run_storage.list().await? {
+            if &run.suite_id == id {
+                self.run_storage.delete(&run.id).await?;
+            }
+        }
+
+        self.storage.delete(id).await
+    }
+
+    async fn save_run(&self, run: &TestSuiteRun) -> Result<(), DomainError> {
+        self.run_storage.save(run.clone()).await?;
+        Ok(())
+    }
+
+    async fn get_run(&self, id: &TestSuiteRunId) -> Result<Option<TestSuiteRun>, DomainError> {
+        self.run_storage.get(id).await
+    }
+
+    async fn list_runs(
+        &self,
+        suite_id: &TestSuiteId,
+        limit: Option<usize>,
+    ) -> Result<Vec<TestSuiteRun>, DomainError> {
+        let mut runs: Vec<TestSuiteRun> = self
+            .run_storage
+            .list()
+            .await?
+            .into_iter()
+            .filter(|run| &run.suite_id == suite_id)
+            .collect();
+
+        // Newest first
+        runs.sort_by_key(|run| std::cmp::Reverse(run.started_at));
+        runs.truncate(limit.unwrap_or(usize::MAX));
+
+        Ok(runs)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -298,4 +381,34 @@ mod tests {
         let retrieved = repo.get(test_case.id()).await.unwrap().unwrap();
         assert!(!retrieved.is_enabled());
     }
+
+    #[tokio::test]
+    async fn test_suite_runs_deleted_with_suite() {
+        use chrono::Utc;
+
+        let repo = StorageTestSuiteRepository::new(
+            Arc::new(InMemoryStorage::<TestSuite>::new()),
+            Arc::new(InMemoryStorage::<TestSuiteRun>::new()),
+        );
+        let suite_id = TestSuiteId::new("smoke").unwrap();
+        let suite = TestSuite::new(
+            suite_id.clone(),
+            "Smoke",
+            vec![TestCaseId::new("test-1").unwrap()],
+        );
+        repo.save(&suite).await.unwrap();
+
+        for _ in 0..3 {
+            let run = TestSuiteRun::from_outcomes(suite_id.clone(), 100.0, Vec::new(), Utc::now());
+            repo.save_run(&run).await.unwrap();
+        }
+
+        let runs = repo.list_runs(&suite_id, Some(2)).await.unwrap();
+        assert_eq!(runs.len(), 2);
+        assert!(runs[0].started_at >= runs[1].started_at);
+
+        assert!(repo.delete(&suite_id).await.unwrap());
+        assert!(repo.get(&suite_id).await.unwrap().is_none());
+        assert!(repo.list_runs(&suite_id, None).await.unwrap().is_empty());
+    }
 }
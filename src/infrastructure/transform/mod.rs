@@ -0,0 +1,5 @@
+//! Transform rule infrastructure
+
+mod service;
+
+pub use service::TransformRuleService;
@@ -0,0 +1,150 @@
+//! Transform rule management and evaluation
+
+use std::sync::Arc;
+
+use chrono::Utc;
+
+use crate::domain::storage::Storage;
+use crate::domain::transform::{TransformOutcome, TransformRule, TransformRuleId, TransformTarget};
+use crate::domain::DomainError;
+
+/// Manages transform rules and evaluates them against requests
+#[derive(Debug)]
+pub struct TransformRuleService {
+    storage: Arc<dyn Storage<TransformRule>>,
+}
+
+impl TransformRuleService {
+    /// Create a new transform rule service
+    pub fn new(storage: Arc<dyn Storage<TransformRule>>) -> Self {
+        Self { storage }
+    }
+
+    /// Every rule, in the order they are applied (priority, then ID)
+    pub async fn list(&self) -> Result<Vec<TransformRule>, DomainError> {
+        let mut rules = self.storage.list().await?;
+        rules.sort_by(|a, b| {
+            a.priority
+                .cmp(&b.priority)
+                .then_with(|| a.id.as_str().cmp(b.id.as_str()))
+        });
+        Ok(rules)
+    }
+
+    /// Rule by ID
+    pub async fn get(&self, id: &str) -> Result<Option<TransformRule>, DomainError> {
+        self.storage.get(&TransformRuleId::new(id)).await
+    }
+
+    /// Create a rule
+    pub async fn create(&self, rule: TransformRule) -> Result<TransformRule, DomainError> {
+        rule.validate()?;
+
+        if self.storage.exists(&rule.id).await? {
+            return Err(DomainError::conflict(format!(
+                "Transform rule '{}' already exists",
+                rule.id
+            )));
+        }
+
+        self.storage.create(rule).await
+    }
+
+    /// Replace the match and actions of an existing rule
+    pub async fn update(&self, mut rule: TransformRule) -> Result<TransformRule, DomainError> {
+        rule.validate()?;
+
+        let existing = self.storage.get(&rule.id).await?.ok_or_else(|| {
+            DomainError::not_found(format!("Transform rule '{}' not found", rule.id))
+        })?;
+        rule.created_at = existing.created_at;
+        rule.updated_at = Utc::now().timestamp().max(0) as u64;
+
+        self.storage.update(rule).await
+    }
+
+    /// Delete a rule
+    pub async fn delete(&self, id: &str) -> Result<bool, DomainError> {
+        self.storage.delete(&TransformRuleId::new(id)).await
+    }
+
+    /// Changes the matching rules make to a request
+    pub async fn evaluate(
+        &self,
+        target: &TransformTarget<'_>,
+    ) -> Result<TransformOutcome, DomainError> {
+        let rules = self.list().await?;
+        Ok(TransformOutcome::evaluate(&rules, target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::transform::{TransformAction, TransformMatch};
+    use crate::infrastructure::storage::InMemoryStorage;
+
+    fn service() -> TransformRuleService {
+        TransformRuleService::new(Arc::new(InMemoryStorage::<TransformRule>::new()))
+    }
+
+    fn rewrite(id: &str, priority: i32, model: &str) -> TransformRule {
+        TransformRule::new(id, id)
+            .with_priority(priority)
+            .with_action(TransformAction::RewriteModel {
+                model: model.to_string(),
+            })
+    }
+
+    #[tokio::test]
+    async fn test_create_update_and_validate() {
+        let service = service();
+        let rule = rewrite("to-mini", 0, "gpt-4o-mini");
+
+        let created = service.create(rule.clone()).await.unwrap();
+        assert!(service.create(rule).await.is_err());
+        assert!(
+            service
+                .create(TransformRule::new("no-actions", "No actions"))
+                .await
+                .is_err()
+        );
+
+        let updated = service
+            .update(created.clone().with_priority(5))
+            .await
+            .unwrap();
+        assert_eq!(updated.created_at, created.created_at);
+        assert_eq!(updated.priority, 5);
+
+        assert!(service.update(rewrite("missing", 0, "x")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_by_priority() {
+        let service = service();
+        service.create(rewrite("late", 10, "claude-3")).await.unwrap();
+        service.create(rewrite("early", -1, "gpt-4o-mini")).await.unwrap();
+        service
+            .create(
+                rewrite("other-key", 20, "never").with_match(TransformMatch {
+                    api_key_ids: vec!["key-2".to_string()],
+                    ..Default::default()
+                }),
+            )
+            .await
+            .unwrap();
+
+        let outcome = service
+            .evaluate(&TransformTarget {
+                route: "/v1/chat/completions",
+                api_key_id: "key-1",
+                model: "gpt-4o",
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.rule_ids, vec!["early", "late"]);
+        assert_eq!(outcome.model.as_deref(), Some("claude-3"));
+    }
+}
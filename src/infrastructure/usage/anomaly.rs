@@ -0,0 +1,280 @@
+//! Background detection of API key and team usage spikes
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use tracing::{info, warn};
+
+use super::UsageTrackingServiceTrait;
+use crate::config::AnomalyDetectionConfig;
+use crate::domain::notification::Notification;
+use crate::domain::usage::{
+    detect_usage_anomalies, AnomalyMetric, AnomalyThresholds, TimeBucket, UsageAnomaly,
+    UsageGroupBy, UsageQuery,
+};
+use crate::domain::{DomainError, WebhookEvent, WebhookEventType};
+use crate::infrastructure::leader::Leadership;
+use crate::infrastructure::notification::NotificationDispatcher;
+use crate::infrastructure::webhook::WebhookServiceTrait;
+
+/// Compares recent usage of every API key and team with its baseline and
+/// reports spikes as notifications and webhook events
+pub struct UsageAnomalyDetector {
+    usage: Arc<dyn UsageTrackingServiceTrait>,
+    notifications: Arc<NotificationDispatcher>,
+    webhooks: Option<Arc<dyn WebhookServiceTrait>>,
+    thresholds: AnomalyThresholds,
+    window_secs: u64,
+    baseline_secs: u64,
+    cooldown_secs: u64,
+    /// Last time each (scope, group, metric) was reported
+    reported: Mutex<HashMap<(String, String, AnomalyMetric), u64>>,
+}
+
+impl UsageAnomalyDetector {
+    /// Create a detector checking the last hour against the previous week
+    pub fn new(
+        usage: Arc<dyn UsageTrackingServiceTrait>,
+        notifications: Arc<NotificationDispatcher>,
+    ) -> Self {
+        Self {
+            usage,
+            notifications,
+            webhooks: None,
+            thresholds: AnomalyThresholds::default(),
+            window_secs: 3_600,
+            baseline_secs: 7 * 86_400,
+            cooldown_secs: 3_600,
+            reported: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create a detector from the `[anomaly_detection]` configuration
+    pub fn from_config(
+        config: &AnomalyDetectionConfig,
+        usage: Arc<dyn UsageTrackingServiceTrait>,
+        notifications: Arc<NotificationDispatcher>,
+    ) -> Self {
+        Self::new(usage, notifications)
+            .with_thresholds(AnomalyThresholds {
+                factor: config.factor,
+                min_requests: config.min_requests,
+                min_cost_micros: (config.min_cost_usd * 1_000_000.0).round() as i64,
+            })
+            .with_window(config.window_secs, config.baseline_hours * 3_600)
+            .with_cooldown_secs(config.cooldown_secs)
+    }
+
+    /// Also send anomalies as `usage_anomaly` webhook events (builder pattern)
+    pub fn with_webhooks(mut self, webhooks: Arc<dyn WebhookServiceTrait>) -> Self {
+        self.webhooks = Some(webhooks);
+        self
+    }
+
+    /// Set when usage counts as a spike (builder pattern)
+    pub fn with_thresholds(mut self, thresholds: AnomalyThresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+
+    /// Set the checked window and the baseline period before it (builder pattern)
+    pub fn with_window(mut self, window_secs: u64, baseline_secs: u64) -> Self {
+        self.window_secs = window_secs.max(60);
+        self.baseline_secs = baseline_secs.max(self.window_secs);
+        self
+    }
+
+    /// Set how long a reported anomaly stays quiet (builder pattern)
+    pub fn with_cooldown_secs(mut self, cooldown_secs: u64) -> Self {
+        self.cooldown_secs = cooldown_secs;
+        self
+    }
+
+    /// Check the window ending at `now`, reporting and returning anomalies
+    /// that are not in their cooldown
+    pub async fn analyze(&self, now: u64) -> Result<Vec<UsageAnomaly>, DomainError> {
+        let window_start = now.saturating_sub(self.window_secs);
+        let baseline_start = window_start.saturating_sub(self.baseline_secs);
+        let mut reported = Vec::new();
+
+        for group_by in [UsageGroupBy::ApiKey, UsageGroupBy::Team] {
+            let baseline = self
+                .usage
+                .timeseries(
+                    &UsageQuery::new().with_time_range(baseline_start, window_start),
+                    TimeBucket::Day,
+                    Some(group_by),
+                )
+                .await?;
+            let current = self
+                .usage
+                .timeseries(
+                    &UsageQuery::new().with_time_range(window_start, now),
+                    TimeBucket::Hour,
+                    Some(group_by),
+                )
+                .await?;
+
+            for anomaly in detect_usage_anomalies(
+                group_by,
+                &baseline,
+                self.baseline_secs,
+                &current,
+                self.window_secs,
+                &self.thresholds,
+            ) {
+                if self.should_report(&anomaly, now) {
+                    self.report(&anomaly).await;
+                    reported.push(anomaly);
+                }
+            }
+        }
+
+        Ok(reported)
+    }
+
+    fn should_report(&self, anomaly: &UsageAnomaly, now: u64) -> bool {
+        let key = (anomaly.scope.clone(), anomaly.group.clone(), anomaly.metric);
+        let mut reported = self.reported.lock().unwrap();
+
+        if reported
+            .get(&key)
+            .is_some_and(|at| now.saturating_sub(*at) < self.cooldown_secs)
+        {
+            return false;
+        }
+
+        reported.insert(key, now);
+        true
+    }
+
+    async fn report(&self, anomaly: &UsageAnomaly) {
+        warn!(
+            scope = %anomaly.scope,
+            group = %anomaly.group,
+            metric = %anomaly.metric,
+            observed = anomaly.observed,
+            baseline = anomaly.baseline,
+            "Usage anomaly detected"
+        );
+
+        self.notifications
+            .notify(&Notification::usage_anomaly(anomaly, self.window_secs))
+            .await;
+
+        if let Some(webhooks) = &self.webhooks {
+            let mut data = serde_json::json!({
+                "scope": anomaly.scope,
+                "group": anomaly.group,
+                "metric": anomaly.metric,
+                "subtype": anomaly.metric.to_string(),
+                "observed": anomaly.observed,
+                "baseline": anomaly.baseline,
+                "ratio": anomaly.ratio(),
+                "window_secs": self.window_secs,
+            });
+
+            // Expose the group under the keys webhook filters match on
+            data[format!("{}_id", anomaly.scope)] = anomaly.group.clone().into();
+
+            if anomaly.metric == AnomalyMetric::Cost {
+                data["cost_micros"] = (anomaly.observed as i64).into();
+            }
+
+            let event = WebhookEvent::new(WebhookEventType::UsageAnomaly, data);
+
+            if let Err(e) = webhooks.send_event(event).await {
+                warn!(error = %e, "Failed to send usage anomaly webhook");
+            }
+        }
+    }
+}
+
+/// Run the detector every `interval`, on the leader replica only
+pub fn spawn_usage_anomaly_detection(
+    detector: Arc<UsageAnomalyDetector>,
+    interval: Duration,
+    leadership: Leadership,
+) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            if !leadership.is_leader() {
+                continue;
+            }
+
+            match detector.analyze(Utc::now().timestamp().max(0) as u64).await {
+                Ok(anomalies) if !anomalies.is_empty() => {
+                    info!(count = anomalies.len(), "Reported usage anomalies");
+                }
+                Ok(_) => {}
+                Err(e) => warn!(error = %e, "Usage anomaly detection failed"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::usage::{UsageRecord, UsageRepository, UsageType};
+    use crate::infrastructure::usage::{InMemoryUsageRepository, UsageTrackingService};
+
+    const NOW: u64 = 1_704_067_200;
+
+    async fn record(repository: &InMemoryUsageRepository, id: String, at: u64) {
+        let mut record = UsageRecord::new(id, UsageType::ChatCompletion, "key-1")
+            .with_team_id("growth")
+            .with_cost_micros(1_000);
+        record.timestamp = at;
+        repository.record(record).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_analyze_reports_spike_once_per_cooldown() {
+        let repository = Arc::new(InMemoryUsageRepository::default());
+
+        // One request per hour over the previous day, then 20 in the last hour
+        for hour in 1..=24 {
+            record(
+                &repository,
+                format!("b-{}", hour),
+                NOW - 3_600 - hour * 3_600,
+            )
+            .await;
+        }
+        for minute in 0..20 {
+            record(
+                &repository,
+                format!("c-{}", minute),
+                NOW - 3_000 + minute * 60,
+            )
+            .await;
+        }
+
+        let service = Arc::new(UsageTrackingService::new(repository));
+        let detector = UsageAnomalyDetector::new(service, Arc::new(NotificationDispatcher::new()))
+            .with_thresholds(AnomalyThresholds {
+                factor: 5.0,
+                min_requests: 10,
+                min_cost_micros: i64::MAX,
+            })
+            .with_window(3_600, 86_400);
+
+        let anomalies = detector.analyze(NOW).await.unwrap();
+        let groups: Vec<(&str, &str)> = anomalies
+            .iter()
+            .map(|a| (a.scope.as_str(), a.group.as_str()))
+            .collect();
+        assert_eq!(groups, vec![("api_key", "key-1"), ("team", "growth")]);
+        assert_eq!(anomalies[0].observed, 20.0);
+        assert_eq!(anomalies[0].baseline, 1.0);
+
+        assert!(detector.analyze(NOW + 60).await.unwrap().is_empty());
+    }
+}
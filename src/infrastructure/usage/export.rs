@@ -0,0 +1,283 @@
+//! Usage record export to CSV and Parquet files
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use arrow_array::{
+    ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, StringArray, UInt32Array,
+    UInt64Array,
+};
+use arrow_schema::{DataType, Field, Schema};
+use chrono::DateTime;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+
+use crate::domain::usage::UsageRecord;
+use crate::domain::DomainError;
+
+/// Columns of an exported usage file, in order
+pub const USAGE_EXPORT_COLUMNS: [&str; 16] = [
+    "id",
+    "timestamp",
+    "time",
+    "usage_type",
+    "api_key_id",
+    "model_id",
+    "input_tokens",
+    "output_tokens",
+    "total_tokens",
+    "cost_micros",
+    "cost_usd",
+    "latency_ms",
+    "success",
+    "error",
+    "tags",
+    "metadata",
+];
+
+/// File format of a usage export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UsageExportFormat {
+    #[default]
+    Csv,
+    Parquet,
+}
+
+impl UsageExportFormat {
+    /// MIME type of the exported file
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::Parquet => "application/vnd.apache.parquet",
+        }
+    }
+
+    /// File extension of the exported file
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Parquet => "parquet",
+        }
+    }
+}
+
+impl fmt::Display for UsageExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+impl FromStr for UsageExportFormat {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "parquet" => Ok(Self::Parquet),
+            other => Err(DomainError::validation(format!(
+                "Unknown usage export format '{}', expected 'csv' or 'parquet'",
+                other
+            ))),
+        }
+    }
+}
+
+/// Encode usage records as a file of the given format
+pub fn encode_usage_records(
+    records: &[UsageRecord],
+    format: UsageExportFormat,
+) -> Result<Vec<u8>, DomainError> {
+    match format {
+        UsageExportFormat::Csv => encode_csv(records),
+        UsageExportFormat::Parquet => encode_parquet(records),
+    }
+}
+
+fn encode_csv(records: &[UsageRecord]) -> Result<Vec<u8>, DomainError> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+
+    writer
+        .write_record(USAGE_EXPORT_COLUMNS)
+        .map_err(|e| DomainError::internal(format!("Failed to write usage CSV: {}", e)))?;
+
+    for record in records {
+        writer
+            .write_record([
+                record.id().to_string(),
+                record.timestamp.to_string(),
+                format_time(record.timestamp),
+                record.usage_type.to_string(),
+                record.api_key_id.clone(),
+                record.model_id.clone().unwrap_or_default(),
+                record.input_tokens.to_string(),
+                record.output_tokens.to_string(),
+                record.total_tokens.to_string(),
+                record.cost_micros.to_string(),
+                record.cost_usd().to_string(),
+                record.latency_ms.to_string(),
+                record.success.to_string(),
+                record.error.clone().unwrap_or_default(),
+                sorted_json(&record.tags),
+                sorted_json(&record.metadata),
+            ])
+            .map_err(|e| DomainError::internal(format!("Failed to write usage CSV: {}", e)))?;
+    }
+
+    writer
+        .into_inner()
+        .map_err(|e| DomainError::internal(format!("Failed to write usage CSV: {}", e)))
+}
+
+fn encode_parquet(records: &[UsageRecord]) -> Result<Vec<u8>, DomainError> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("timestamp", DataType::UInt64, false),
+        Field::new("time", DataType::Utf8, false),
+        Field::new("usage_type", DataType::Utf8, false),
+        Field::new("api_key_id", DataType::Utf8, false),
+        Field::new("model_id", DataType::Utf8, true),
+        Field::new("input_tokens", DataType::UInt32, false),
+        Field::new("output_tokens", DataType::UInt32, false),
+        Field::new("total_tokens", DataType::UInt32, false),
+        Field::new("cost_micros", DataType::Int64, false),
+        Field::new("cost_usd", DataType::Float64, false),
+        Field::new("latency_ms", DataType::UInt64, false),
+        Field::new("success", DataType::Boolean, false),
+        Field::new("error", DataType::Utf8, true),
+        Field::new("tags", DataType::Utf8, false),
+        Field::new("metadata", DataType::Utf8, false),
+    ]));
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| r.id().to_string()),
+        )),
+        Arc::new(UInt64Array::from_iter_values(records.iter().map(|r| r.timestamp))),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| format_time(r.timestamp)),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| r.usage_type.to_string()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| r.api_key_id.as_str()),
+        )),
+        Arc::new(StringArray::from_iter(
+            records.iter().map(|r| r.model_id.as_deref()),
+        )),
+        Arc::new(UInt32Array::from_iter_values(records.iter().map(|r| r.input_tokens))),
+        Arc::new(UInt32Array::from_iter_values(records.iter().map(|r| r.output_tokens))),
+        Arc::new(UInt32Array::from_iter_values(records.iter().map(|r| r.total_tokens))),
+        Arc::new(Int64Array::from_iter_values(records.iter().map(|r| r.cost_micros))),
+        Arc::new(Float64Array::from_iter_values(records.iter().map(|r| r.cost_usd()))),
+        Arc::new(UInt64Array::from_iter_values(records.iter().map(|r| r.latency_ms))),
+        Arc::new(BooleanArray::from_iter(records.iter().map(|r| Some(r.success)))),
+        Arc::new(StringArray::from_iter(records.iter().map(|r| r.error.as_deref()))),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| sorted_json(&r.tags)),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| sorted_json(&r.metadata)),
+        )),
+    ];
+
+    let batch = RecordBatch::try_new(schema.clone(), columns)
+        .map_err(|e| DomainError::internal(format!("Failed to build usage batch: {}", e)))?;
+
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut buffer = Vec::new();
+    let mut writer = ArrowWriter::try_new(&mut buffer, schema, Some(props))
+        .map_err(|e| DomainError::internal(format!("Failed to write usage Parquet: {}", e)))?;
+
+    writer
+        .write(&batch)
+        .map_err(|e| DomainError::internal(format!("Failed to write usage Parquet: {}", e)))?;
+    writer
+        .close()
+        .map_err(|e| DomainError::internal(format!("Failed to write usage Parquet: {}", e)))?;
+
+    Ok(buffer)
+}
+
+/// RFC 3339 time of a unix timestamp
+fn format_time(timestamp: u64) -> String {
+    DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_default()
+}
+
+/// JSON object with keys in a stable order, so exports are reproducible
+fn sorted_json(map: &HashMap<String, String>) -> String {
+    let sorted: BTreeMap<&String, &String> = map.iter().collect();
+    serde_json::to_string(&sorted).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::usage::UsageType;
+
+    fn records() -> Vec<UsageRecord> {
+        vec![
+            UsageRecord::new("rec-1", UsageType::ChatCompletion, "key-1")
+                .with_model_id("gpt-4")
+                .with_tokens(100, 50)
+                .with_cost_micros(4_500)
+                .with_tags(HashMap::from([
+                    ("team".to_string(), "growth".to_string()),
+                    ("customer".to_string(), "acme, inc".to_string()),
+                ])),
+            UsageRecord::new("rec-2", UsageType::Workflow, "key-2").with_error("boom"),
+        ]
+    }
+
+    #[test]
+    fn test_usage_export_format_parse() {
+        assert_eq!("CSV".parse::<UsageExportFormat>().unwrap(), UsageExportFormat::Csv);
+        assert_eq!(
+            "parquet".parse::<UsageExportFormat>().unwrap(),
+            UsageExportFormat::Parquet
+        );
+        assert!("xlsx".parse::<UsageExportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_encode_csv() {
+        let bytes = encode_usage_records(&records(), UsageExportFormat::Csv).unwrap();
+        let mut reader = csv::Reader::from_reader(bytes.as_slice());
+
+        let headers = reader.headers().unwrap().clone();
+        assert_eq!(headers.iter().collect::<Vec<_>>(), USAGE_EXPORT_COLUMNS);
+
+        let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(&rows[0][0], "rec-1");
+        assert_eq!(&rows[0][5], "gpt-4");
+        assert_eq!(&rows[0][9], "4500");
+        assert_eq!(&rows[0][14], r#"{"customer":"acme, inc","team":"growth"}"#);
+        assert_eq!(&rows[1][5], "");
+        assert_eq!(&rows[1][12], "false");
+        assert_eq!(&rows[1][13], "boom");
+    }
+
+    #[test]
+    fn test_encode_parquet() {
+        let bytes = encode_usage_records(&records(), UsageExportFormat::Parquet).unwrap();
+
+        let reader = parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::try_new(
+            bytes::Bytes::from(bytes),
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        let batches: Vec<RecordBatch> = reader.map(Result::unwrap).collect();
+
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 2);
+        assert_eq!(batches[0].num_columns(), USAGE_EXPORT_COLUMNS.len());
+    }
+}
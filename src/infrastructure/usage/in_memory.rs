@@ -6,8 +6,9 @@ use std::sync::RwLock;
 use async_trait::async_trait;
 
 use crate::domain::usage::{
-    Budget, BudgetId, BudgetPeriod, BudgetRepository, UsageAggregate, UsageQuery, UsageRecord,
-    UsageRecordId, UsageRepository, UsageSummary,
+    bucket_usage_records, Budget, BudgetId, BudgetPeriod, BudgetRepository, TimeBucket,
+    UsageAggregate, UsageBucket, UsageGroupBy, UsageQuery, UsageRecord, UsageRecordId,
+    UsageRepository, UsageSummary,
 };
 use crate::domain::usage::DailyUsage;
 use crate::domain::DomainError;
@@ -86,6 +87,12 @@ impl UsageRepository for InMemoryUsageRepository {
                     }
                 }
 
+                if let Some(ref team) = query.team_id
+                    && r.team_id.as_ref() != Some(team)
+                {
+                    return false;
+                }
+
                 if let Some(ref model) = query.model_id {
                     if r.model_id.as_ref() != Some(model) {
                         return false;
@@ -104,7 +111,7 @@ impl UsageRepository for InMemoryUsageRepository {
                     }
                 }
 
-                true
+                r.has_tags(&query.tags)
             })
             .cloned()
             .collect();
@@ -173,6 +180,22 @@ impl UsageRepository for InMemoryUsageRepository {
         })
     }
 
+    async fn timeseries(
+        &self,
+        query: &UsageQuery,
+        bucket: TimeBucket,
+        group_by: Option<UsageGroupBy>,
+    ) -> Result<Vec<UsageBucket>, DomainError> {
+        let query = UsageQuery {
+            limit: None,
+            offset: None,
+            ..query.clone()
+        };
+        let records = self.query(&query).await?;
+
+        Ok(bucket_usage_records(&records, bucket, group_by))
+    }
+
     async fn delete_before(&self, timestamp: u64) -> Result<usize, DomainError> {
         let mut records = self.records.write().map_err(|e| {
             DomainError::internal(format!("Failed to acquire write lock: {}", e))
@@ -194,6 +217,17 @@ impl UsageRepository for InMemoryUsageRepository {
 
         Ok(before_count - records.len())
     }
+
+    async fn delete_by_tag(&self, key: &str, value: &str) -> Result<usize, DomainError> {
+        let mut records = self.records.write().map_err(|e| {
+            DomainError::internal(format!("Failed to acquire write lock: {}", e))
+        })?;
+
+        let before_count = records.len();
+        records.retain(|_, r| r.tags.get(key).map(String::as_str) != Some(value));
+
+        Ok(before_count - records.len())
+    }
 }
 
 /// In-memory budget repository
@@ -285,6 +319,7 @@ impl BudgetRepository for InMemoryBudgetRepository {
         &self,
         api_key_id: &str,
         team_id: Option<&str>,
+        organization_id: Option<&str>,
         model_id: Option<&str>,
     ) -> Result<Vec<Budget>, DomainError> {
         let budgets = self.budgets.read().map_err(|e| {
@@ -295,7 +330,7 @@ impl BudgetRepository for InMemoryBudgetRepository {
             .values()
             .filter(|b| {
                 b.enabled
-                    && b.applies_to_api_key_with_team(api_key_id, team_id)
+                    && b.applies_to_api_key_in_organization(api_key_id, team_id, organization_id)
                     && model_id.map_or(true, |m| b.applies_to_model(m))
             })
             .cloned()
@@ -375,6 +410,26 @@ mod tests {
         assert_eq!(results.len(), 5);
     }
 
+    #[tokio::test]
+    async fn test_usage_repository_query_by_tag() {
+        let repo = InMemoryUsageRepository::new(100);
+
+        for (i, feature) in ["onboarding", "onboarding", "search"].iter().enumerate() {
+            let record = UsageRecord::new(format!("rec-{}", i), UsageType::ChatCompletion, "key-1")
+                .with_tags(HashMap::from([("feature".to_string(), feature.to_string())]));
+            repo.record(record).await.unwrap();
+        }
+        repo.record(UsageRecord::new("rec-untagged", UsageType::ChatCompletion, "key-1"))
+            .await
+            .unwrap();
+
+        let query = UsageQuery::new().with_tag("feature", "onboarding");
+        assert_eq!(repo.query(&query).await.unwrap().len(), 2);
+
+        let aggregate = repo.aggregate(&UsageQuery::new()).await.unwrap();
+        assert_eq!(aggregate.by_tag["feature"]["search"].requests, 1);
+    }
+
     #[tokio::test]
     async fn test_usage_repository_aggregate() {
         let repo = InMemoryUsageRepository::new(100);
@@ -498,28 +553,28 @@ mod tests {
 
         // key-1 with team-1 should match team budget, key budget, and global
         let applicable = repo
-            .find_applicable_with_team("key-1", Some("team-1"), None)
+            .find_applicable_with_team("key-1", Some("team-1"), None, None)
             .await
             .unwrap();
         assert_eq!(applicable.len(), 3);
 
         // key-2 with team-1 should match team budget and global
         let applicable = repo
-            .find_applicable_with_team("key-2", Some("team-1"), None)
+            .find_applicable_with_team("key-2", Some("team-1"), None, None)
             .await
             .unwrap();
         assert_eq!(applicable.len(), 2);
 
         // key-1 without team should match key budget and global
         let applicable = repo
-            .find_applicable_with_team("key-1", None, None)
+            .find_applicable_with_team("key-1", None, None, None)
             .await
             .unwrap();
         assert_eq!(applicable.len(), 2);
 
         // key-2 with team-2 should only match global
         let applicable = repo
-            .find_applicable_with_team("key-2", Some("team-2"), None)
+            .find_applicable_with_team("key-2", Some("team-2"), None, None)
             .await
             .unwrap();
         assert_eq!(applicable.len(), 1);
@@ -574,21 +629,21 @@ mod tests {
 
         // Should match key-1 regardless of team
         let applicable = repo
-            .find_applicable_with_team("key-1", None, None)
+            .find_applicable_with_team("key-1", None, None, None)
             .await
             .unwrap();
         assert_eq!(applicable.len(), 1);
 
         // Should match any key with team-1
         let applicable = repo
-            .find_applicable_with_team("key-2", Some("team-1"), None)
+            .find_applicable_with_team("key-2", Some("team-1"), None, None)
             .await
             .unwrap();
         assert_eq!(applicable.len(), 1);
 
         // Should not match key-2 with team-2
         let applicable = repo
-            .find_applicable_with_team("key-2", Some("team-2"), None)
+            .find_applicable_with_team("key-2", Some("team-2"), None, None)
             .await
             .unwrap();
         assert_eq!(applicable.len(), 0);
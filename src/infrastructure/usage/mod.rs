@@ -1,12 +1,33 @@
 //! Usage tracking infrastructure implementations
 
+mod anomaly;
+mod export;
 mod in_memory;
+mod postgres_aggregation;
+mod pricing;
+mod pricing_feed;
+mod provider_usage;
+mod reconciliation;
+mod s3_export;
 mod service;
 mod storage_repository;
 
+pub use anomaly::{spawn_usage_anomaly_detection, UsageAnomalyDetector};
+pub use export::{encode_usage_records, UsageExportFormat, USAGE_EXPORT_COLUMNS};
 pub use in_memory::{InMemoryBudgetRepository, InMemoryUsageRepository};
+pub use postgres_aggregation::PostgresUsageAggregator;
+pub use pricing::{spawn_pricing_sync, PricingCatalog, PricingService, PricingSyncReport};
+pub use pricing_feed::{parse_pricing_feed, PricingFeed, PricingFeedFormat};
+pub use provider_usage::{
+    parse_anthropic_usage, parse_openai_usage, AnthropicUsageSource, OpenAiUsageSource,
+    ANTHROPIC_API_URL, OPENAI_API_URL,
+};
+pub use reconciliation::{spawn_daily_usage_reconciliation, UsageReconciler};
+pub use s3_export::{spawn_daily_usage_export, usage_export_key, S3UsageExporter};
 pub use service::{
-    AlertNotification, BudgetCheckResult, BudgetService, BudgetServiceTrait, RecordUsageParams,
+    AlertNotification, BudgetCheckResult, BudgetHeadroom, BudgetService, BudgetServiceTrait, RecordUsageParams,
     UsageTrackingService, UsageTrackingServiceTrait,
 };
-pub use storage_repository::{StorageBudgetRepository, StorageUsageRepository};
+pub use storage_repository::{
+    StorageBudgetRepository, StoragePricingRepository, StorageUsageRepository,
+};
@@ -0,0 +1,118 @@
+//! Usage aggregation pushed down to PostgreSQL
+
+use sqlx::types::Json;
+use sqlx::{PgPool, Row};
+
+use crate::domain::usage::{TimeBucket, UsageBucket, UsageGroupBy, UsageQuery};
+use crate::domain::DomainError;
+
+/// Aggregates usage records inside PostgreSQL instead of loading them
+#[derive(Debug, Clone)]
+pub struct PostgresUsageAggregator {
+    pool: PgPool,
+    table_name: String,
+}
+
+impl PostgresUsageAggregator {
+    /// Create an aggregator over the usage records table
+    pub fn new(pool: PgPool, table_name: impl Into<String>) -> Self {
+        Self {
+            pool,
+            table_name: table_name.into(),
+        }
+    }
+
+    /// Usage per time bucket (and group) matching a query
+    pub async fn timeseries(
+        &self,
+        query: &UsageQuery,
+        bucket: TimeBucket,
+        group_by: Option<UsageGroupBy>,
+    ) -> Result<Vec<UsageBucket>, DomainError> {
+        let sql = timeseries_sql(&self.table_name, group_by);
+
+        let rows = sqlx::query(&sql)
+            .bind(bucket.seconds() as i64)
+            .bind(query.from_timestamp.map(|t| t as i64))
+            .bind(query.to_timestamp.map(|t| t as i64))
+            .bind(query.api_key_id.as_deref())
+            .bind(query.team_id.as_deref())
+            .bind(query.model_id.as_deref())
+            .bind(Json(&query.tags))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::storage(format!("Failed to aggregate usage: {}", e)))?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(UsageBucket {
+                    bucket_start: get_i64(row, "bucket_start")? as u64,
+                    group: row
+                        .try_get("group_key")
+                        .map_err(|e| column_error("group_key", e))?,
+                    requests: get_i64(row, "requests")? as u64,
+                    input_tokens: get_i64(row, "input_tokens")? as u64,
+                    output_tokens: get_i64(row, "output_tokens")? as u64,
+                    total_tokens: get_i64(row, "total_tokens")? as u64,
+                    cost_micros: get_i64(row, "cost_micros")?,
+                })
+            })
+            .collect()
+    }
+}
+
+fn get_i64(row: &sqlx::postgres::PgRow, column: &str) -> Result<i64, DomainError> {
+    row.try_get(column).map_err(|e| column_error(column, e))
+}
+
+fn column_error(column: &str, e: sqlx::Error) -> DomainError {
+    DomainError::storage(format!("Failed to read usage aggregate column '{}': {}", column, e))
+}
+
+/// Bucketed aggregation query; `$1` is the bucket width in seconds, `$2..$7`
+/// the optional time range, API key, team, model and required tags
+fn timeseries_sql(table_name: &str, group_by: Option<UsageGroupBy>) -> String {
+    let group = match group_by {
+        Some(UsageGroupBy::Team) => "data->>'team_id'",
+        Some(UsageGroupBy::Model) => "data->>'model_id'",
+        Some(UsageGroupBy::ApiKey) => "data->>'api_key_id'",
+        None => "NULL::text",
+    };
+
+    format!(
+        r#"
+        SELECT ((data->>'timestamp')::bigint / $1) * $1 AS bucket_start,
+               {group} AS group_key,
+               COUNT(*) AS requests,
+               COALESCE(SUM((data->>'input_tokens')::bigint), 0)::bigint AS input_tokens,
+               COALESCE(SUM((data->>'output_tokens')::bigint), 0)::bigint AS output_tokens,
+               COALESCE(SUM((data->>'total_tokens')::bigint), 0)::bigint AS total_tokens,
+               COALESCE(SUM((data->>'cost_micros')::bigint), 0)::bigint AS cost_micros
+        FROM {table_name}
+        WHERE ($2::bigint IS NULL OR (data->>'timestamp')::bigint >= $2)
+          AND ($3::bigint IS NULL OR (data->>'timestamp')::bigint < $3)
+          AND ($4::text IS NULL OR data->>'api_key_id' = $4)
+          AND ($5::text IS NULL OR data->>'team_id' = $5)
+          AND ($6::text IS NULL OR data->>'model_id' = $6)
+          AND COALESCE(data->'tags', '{{}}'::jsonb) @> $7::jsonb
+        GROUP BY 1, 2
+        ORDER BY 1, 2
+        "#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timeseries_sql_grouping() {
+        let sql = timeseries_sql("usage_records", Some(UsageGroupBy::Team));
+        assert!(sql.contains("data->>'team_id' AS group_key"));
+        assert!(sql.contains("FROM usage_records"));
+        assert!(sql.contains("'{}'::jsonb"));
+
+        let sql = timeseries_sql("usage_records", None);
+        assert!(sql.contains("NULL::text AS group_key"));
+    }
+}
@@ -0,0 +1,453 @@
+//! Pricing catalog and pricing management service
+//!
+//! The catalog keeps every pricing version in memory so that cost lookups
+//! stay synchronous. The service persists versions, keeps the catalog in
+//! sync and pulls price changes from an optional pricing feed.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use tracing::{info, warn};
+
+use crate::domain::usage::{
+    default_model_pricing, ModelPricing, PricingId, PricingRepository, PricingSource,
+};
+use crate::domain::DomainError;
+
+use super::pricing_feed::PricingFeed;
+
+/// In-memory view of all pricing versions, grouped by model
+#[derive(Debug, Default)]
+pub struct PricingCatalog {
+    versions: RwLock<HashMap<String, Vec<ModelPricing>>>,
+}
+
+impl PricingCatalog {
+    /// Create a catalog holding the given pricing versions
+    pub fn new(pricing: impl IntoIterator<Item = ModelPricing>) -> Self {
+        let catalog = Self::default();
+        catalog.replace_all(pricing);
+        catalog
+    }
+
+    /// Pricing of a model in effect now
+    pub fn current(&self, model_id: &str) -> Option<ModelPricing> {
+        self.pricing_at(model_id, unix_now())
+    }
+
+    /// Pricing of a model in effect at a unix timestamp. When versions
+    /// overlap, the one that took effect last wins.
+    pub fn pricing_at(&self, model_id: &str, timestamp: u64) -> Option<ModelPricing> {
+        let versions = self.versions.read().unwrap();
+
+        versions
+            .get(model_id)?
+            .iter()
+            .filter(|p| p.is_effective(timestamp))
+            .max_by_key(|p| p.effective_from.unwrap_or(0))
+            .cloned()
+    }
+
+    /// All versions of a model, oldest first
+    pub fn versions(&self, model_id: &str) -> Vec<ModelPricing> {
+        self.versions
+            .read()
+            .unwrap()
+            .get(model_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// IDs of all models with pricing, sorted
+    pub fn model_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self.versions.read().unwrap().keys().cloned().collect();
+        ids.sort();
+        ids
+    }
+
+    /// Add or replace a pricing version
+    pub fn upsert(&self, pricing: ModelPricing) {
+        let mut versions = self.versions.write().unwrap();
+        let model_versions = versions.entry(pricing.model_id.clone()).or_default();
+
+        model_versions.retain(|p| p.id != pricing.id);
+        model_versions.push(pricing);
+        model_versions.sort_by_key(|p| p.effective_from.unwrap_or(0));
+    }
+
+    /// Remove a pricing version
+    pub fn remove(&self, model_id: &str, id: &PricingId) {
+        let mut versions = self.versions.write().unwrap();
+
+        if let Some(model_versions) = versions.get_mut(model_id) {
+            model_versions.retain(|p| &p.id != id);
+            if model_versions.is_empty() {
+                versions.remove(model_id);
+            }
+        }
+    }
+
+    /// Replace the whole catalog
+    pub fn replace_all(&self, pricing: impl IntoIterator<Item = ModelPricing>) {
+        self.versions.write().unwrap().clear();
+        for p in pricing {
+            self.upsert(p);
+        }
+    }
+}
+
+/// Outcome of a pricing feed synchronisation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PricingSyncReport {
+    /// Models without pricing before the sync
+    pub added: usize,
+    /// Models whose prices changed
+    pub updated: usize,
+    /// Models whose prices were already current
+    pub unchanged: usize,
+    /// Models with manually set prices, left untouched
+    pub skipped_manual: usize,
+}
+
+/// Pricing management service
+#[derive(Debug)]
+pub struct PricingService<R: PricingRepository> {
+    repository: Arc<R>,
+    catalog: Arc<PricingCatalog>,
+    feed: Option<PricingFeed>,
+}
+
+impl<R: PricingRepository> PricingService<R> {
+    /// Create a new pricing service backed by the given catalog
+    pub fn new(repository: Arc<R>, catalog: Arc<PricingCatalog>) -> Self {
+        Self {
+            repository,
+            catalog,
+            feed: None,
+        }
+    }
+
+    /// Synchronise prices from a pricing feed
+    pub fn with_feed(mut self, feed: PricingFeed) -> Self {
+        self.feed = Some(feed);
+        self
+    }
+
+    /// Catalog used for cost lookups
+    pub fn catalog(&self) -> &Arc<PricingCatalog> {
+        &self.catalog
+    }
+
+    /// Load stored pricing into the catalog, seeding the built-in defaults
+    /// when nothing has been stored yet
+    pub async fn load(&self) -> Result<(), DomainError> {
+        let mut stored = self.repository.list().await?;
+
+        if stored.is_empty() {
+            info!("Seeding default model pricing");
+            for pricing in default_model_pricing().into_values() {
+                stored.push(self.repository.save(pricing).await?);
+            }
+        }
+
+        self.catalog.replace_all(stored);
+        Ok(())
+    }
+
+    /// Current pricing of every model
+    pub fn list_current(&self) -> Vec<ModelPricing> {
+        let now = unix_now();
+
+        self.catalog
+            .model_ids()
+            .iter()
+            .filter_map(|model_id| self.catalog.pricing_at(model_id, now))
+            .collect()
+    }
+
+    /// All pricing versions of a model, oldest first
+    pub fn history(&self, model_id: &str) -> Vec<ModelPricing> {
+        self.catalog.versions(model_id)
+    }
+
+    /// Add a pricing version, effective now unless `effective_from` is set.
+    /// The version previously in effect at that date is closed the second
+    /// before, and the new version ends where a later scheduled version starts.
+    pub async fn set_pricing(&self, pricing: ModelPricing) -> Result<ModelPricing, DomainError> {
+        if pricing.model_id.is_empty() {
+            return Err(DomainError::validation("Pricing model ID cannot be empty"));
+        }
+
+        if pricing.input_price_per_1k_micros < 0
+            || pricing.output_price_per_1k_micros < 0
+            || pricing
+                .tiers
+                .iter()
+                .any(|t| t.input_price_per_1k_micros < 0 || t.output_price_per_1k_micros < 0)
+        {
+            return Err(DomainError::validation("Prices cannot be negative"));
+        }
+
+        let from = pricing.effective_from.unwrap_or_else(unix_now);
+        let mut pricing = pricing.with_effective_from(from);
+        pricing.effective_until = None;
+
+        for mut version in self.catalog.versions(&pricing.model_id) {
+            let version_from = version.effective_from.unwrap_or(0);
+
+            if version.id == pricing.id {
+                continue;
+            }
+
+            if version_from < from && version.effective_until.is_none_or(|until| until >= from) {
+                version.effective_until = Some(from - 1);
+                self.store(version).await?;
+            } else if version_from > from {
+                pricing.effective_until = Some(
+                    pricing
+                        .effective_until
+                        .map_or(version_from - 1, |until| until.min(version_from - 1)),
+                );
+            }
+        }
+
+        info!(
+            model_id = %pricing.model_id,
+            effective_from = from,
+            source = %pricing.source,
+            "Setting model pricing"
+        );
+
+        self.store(pricing).await
+    }
+
+    /// Delete a pricing version. The preceding version is extended to cover
+    /// the period of the deleted one.
+    pub async fn delete_version(&self, model_id: &str, id: &PricingId) -> Result<bool, DomainError> {
+        let versions = self.catalog.versions(model_id);
+        let Some(deleted) = versions.iter().find(|p| &p.id == id) else {
+            return Ok(false);
+        };
+
+        let deleted_from = deleted.effective_from.unwrap_or(0);
+        let previous = versions
+            .iter()
+            .filter(|p| p.effective_from.unwrap_or(0) < deleted_from)
+            .max_by_key(|p| p.effective_from.unwrap_or(0));
+
+        if let Some(previous) = previous
+            && previous.effective_until == Some(deleted_from.saturating_sub(1))
+        {
+            let mut previous = previous.clone();
+            previous.effective_until = deleted.effective_until;
+            self.store(previous).await?;
+        }
+
+        self.repository.delete(id).await?;
+        self.catalog.remove(model_id, id);
+        Ok(true)
+    }
+
+    /// Delete every pricing version of a model
+    pub async fn delete_model(&self, model_id: &str) -> Result<usize, DomainError> {
+        let versions = self.catalog.versions(model_id);
+
+        for version in &versions {
+            self.repository.delete(&version.id).await?;
+            self.catalog.remove(model_id, &version.id);
+        }
+
+        Ok(versions.len())
+    }
+
+    /// Apply prices from a feed. Models with manually set current prices are
+    /// skipped; changed prices become new versions.
+    pub async fn sync(&self, feed: Vec<ModelPricing>) -> Result<PricingSyncReport, DomainError> {
+        let mut report = PricingSyncReport::default();
+
+        for pricing in feed {
+            match self.catalog.current(&pricing.model_id) {
+                Some(current) if current.source == PricingSource::Manual => {
+                    report.skipped_manual += 1;
+                    continue;
+                }
+                Some(current) if current.same_prices(&pricing) => {
+                    report.unchanged += 1;
+                    continue;
+                }
+                Some(_) => report.updated += 1,
+                None => report.added += 1,
+            }
+
+            let pricing = pricing.with_source(PricingSource::Sync);
+            self.set_pricing(pricing).await?;
+        }
+
+        info!(
+            added = report.added,
+            updated = report.updated,
+            unchanged = report.unchanged,
+            skipped_manual = report.skipped_manual,
+            "Synchronised model pricing"
+        );
+
+        Ok(report)
+    }
+
+    /// Fetch the configured pricing feed and apply it
+    pub async fn sync_from_feed(&self) -> Result<PricingSyncReport, DomainError> {
+        let feed = self
+            .feed
+            .as_ref()
+            .ok_or_else(|| DomainError::validation("No pricing feed is configured"))?;
+
+        let pricing = feed.fetch().await?;
+        self.sync(pricing).await
+    }
+
+    async fn store(&self, pricing: ModelPricing) -> Result<ModelPricing, DomainError> {
+        let saved = self.repository.save(pricing).await?;
+        self.catalog.upsert(saved.clone());
+        Ok(saved)
+    }
+}
+
+/// Periodically synchronise prices from the pricing feed of the service
+pub fn spawn_pricing_sync<R: PricingRepository + 'static>(
+    service: Arc<PricingService<R>>,
+    interval: Duration,
+) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(e) = service.sync_from_feed().await {
+                warn!(error = %e, "Pricing sync failed");
+            }
+        }
+    });
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::storage::InMemoryStorage;
+    use crate::infrastructure::usage::StoragePricingRepository;
+
+    fn create_service() -> PricingService<StoragePricingRepository> {
+        PricingService::new(
+            Arc::new(StoragePricingRepository::new(Arc::new(
+                InMemoryStorage::<ModelPricing>::new(),
+            ))),
+            Arc::new(PricingCatalog::default()),
+        )
+    }
+
+    fn manual(model_id: &str, input: f64, from: u64) -> ModelPricing {
+        ModelPricing::new(model_id, "openai", input, input * 2.0)
+            .with_effective_from(from)
+            .with_source(PricingSource::Manual)
+    }
+
+    #[tokio::test]
+    async fn test_load_seeds_defaults() {
+        let service = create_service();
+        service.load().await.unwrap();
+
+        assert!(service.catalog().current("gpt-4o").is_some());
+        assert_eq!(service.list_current().len(), default_model_pricing().len());
+    }
+
+    #[tokio::test]
+    async fn test_set_pricing_versions() {
+        let service = create_service();
+
+        service.set_pricing(manual("gpt-4o", 0.005, 1_000)).await.unwrap();
+        service.set_pricing(manual("gpt-4o", 0.0025, 2_000)).await.unwrap();
+
+        let history = service.history("gpt-4o");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].effective_until, Some(1_999));
+        assert_eq!(history[1].effective_until, None);
+
+        let catalog = service.catalog();
+        assert_eq!(catalog.pricing_at("gpt-4o", 1_500).unwrap().input_price_per_1k_micros, 5_000);
+        assert_eq!(catalog.pricing_at("gpt-4o", 2_500).unwrap().input_price_per_1k_micros, 2_500);
+        assert!(catalog.pricing_at("gpt-4o", 500).is_none());
+
+        // A version inserted between the two ends where the later one starts
+        let middle = service.set_pricing(manual("gpt-4o", 0.004, 1_500)).await.unwrap();
+        assert_eq!(middle.effective_until, Some(1_999));
+        assert_eq!(service.history("gpt-4o")[0].effective_until, Some(1_499));
+    }
+
+    #[tokio::test]
+    async fn test_delete_version_reopens_previous() {
+        let service = create_service();
+
+        service.set_pricing(manual("gpt-4o", 0.005, 1_000)).await.unwrap();
+        let latest = service.set_pricing(manual("gpt-4o", 0.0025, 2_000)).await.unwrap();
+
+        assert!(service.delete_version("gpt-4o", &latest.id).await.unwrap());
+        assert!(!service.delete_version("gpt-4o", &latest.id).await.unwrap());
+
+        let history = service.history("gpt-4o");
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].effective_until, None);
+
+        assert_eq!(service.delete_model("gpt-4o").await.unwrap(), 1);
+        assert!(service.catalog().current("gpt-4o").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_set_pricing_rejects_negative_prices() {
+        let service = create_service();
+        let result = service.set_pricing(manual("gpt-4o", -0.01, 1_000)).await;
+
+        assert!(matches!(result, Err(DomainError::Validation { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_sync() {
+        let service = create_service();
+        service.set_pricing(manual("gpt-4o", 0.005, 1_000)).await.unwrap();
+        service
+            .set_pricing(ModelPricing::new("gpt-4o-mini", "openai", 0.00015, 0.0006).with_effective_from(1_000))
+            .await
+            .unwrap();
+
+        let feed = vec![
+            ModelPricing::new("gpt-4o", "openai", 0.0025, 0.01),
+            ModelPricing::new("gpt-4o-mini", "openai", 0.00015, 0.0006),
+            ModelPricing::new("o3", "openai", 0.002, 0.008),
+        ];
+
+        let report = service.sync(feed).await.unwrap();
+        assert_eq!(
+            report,
+            PricingSyncReport {
+                added: 1,
+                updated: 0,
+                unchanged: 1,
+                skipped_manual: 1,
+            }
+        );
+
+        let o3 = service.catalog().current("o3").unwrap();
+        assert_eq!(o3.source, PricingSource::Sync);
+
+        let changed = vec![ModelPricing::new("o3", "openai", 0.001, 0.004)];
+        assert_eq!(service.sync(changed).await.unwrap().updated, 1);
+        assert!(service.sync_from_feed().await.is_err());
+    }
+}
@@ -0,0 +1,239 @@
+//! Pricing feeds used to keep the pricing catalog up to date
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::domain::usage::{ModelPricing, PricingSource};
+use crate::domain::DomainError;
+
+/// Format of a pricing feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PricingFeedFormat {
+    /// `{"models": [{"model_id", "provider", "input_per_1k", "output_per_1k", "effective_from"?}]}`
+    Native,
+    /// LiteLLM's `model_prices_and_context_window.json`, keyed by model ID
+    /// with `input_cost_per_token` / `output_cost_per_token` in USD
+    Litellm,
+}
+
+impl std::str::FromStr for PricingFeedFormat {
+    type Err = DomainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "native" => Ok(Self::Native),
+            "litellm" => Ok(Self::Litellm),
+            other => Err(DomainError::validation(format!(
+                "Unknown pricing feed format '{}', expected 'native' or 'litellm'",
+                other
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct NativeFeed {
+    models: Vec<NativeFeedEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NativeFeedEntry {
+    model_id: String,
+    provider: String,
+    input_per_1k: f64,
+    output_per_1k: f64,
+    #[serde(default)]
+    effective_from: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LitellmFeedEntry {
+    #[serde(default)]
+    input_cost_per_token: Option<f64>,
+    #[serde(default)]
+    output_cost_per_token: Option<f64>,
+    #[serde(default)]
+    litellm_provider: Option<String>,
+}
+
+/// Parse a pricing feed into pricing versions, optionally keeping only the
+/// listed model IDs
+pub fn parse_pricing_feed(
+    body: &str,
+    format: PricingFeedFormat,
+    models: &[String],
+) -> Result<Vec<ModelPricing>, DomainError> {
+    let invalid = |e: serde_json::Error| DomainError::validation(format!("Invalid pricing feed: {}", e));
+
+    let pricing: Vec<ModelPricing> = match format {
+        PricingFeedFormat::Native => serde_json::from_str::<NativeFeed>(body)
+            .map_err(invalid)?
+            .models
+            .into_iter()
+            .map(|entry| {
+                let pricing = feed_pricing(
+                    entry.model_id,
+                    entry.provider,
+                    entry.input_per_1k,
+                    entry.output_per_1k,
+                );
+                match entry.effective_from {
+                    Some(from) => pricing.with_effective_from(from),
+                    None => pricing,
+                }
+            })
+            .collect(),
+        PricingFeedFormat::Litellm => {
+            // Entries that are not models (e.g. `sample_spec`) lack prices and are skipped
+            let entries: HashMap<String, serde_json::Value> =
+                serde_json::from_str(body).map_err(invalid)?;
+
+            entries
+                .into_iter()
+                .filter_map(|(model_id, value)| {
+                    let entry: LitellmFeedEntry = serde_json::from_value(value).ok()?;
+                    let input = entry.input_cost_per_token?;
+                    let output = entry.output_cost_per_token.unwrap_or(0.0);
+
+                    Some(feed_pricing(
+                        model_id,
+                        entry.litellm_provider.unwrap_or_default(),
+                        input * 1000.0,
+                        output * 1000.0,
+                    ))
+                })
+                .collect()
+        }
+    };
+
+    Ok(pricing
+        .into_iter()
+        .filter(|p| models.is_empty() || models.contains(&p.model_id))
+        .filter(|p| p.input_price_per_1k_micros >= 0 && p.output_price_per_1k_micros >= 0)
+        .map(|p| p.with_source(PricingSource::Sync))
+        .collect())
+}
+
+/// Build pricing from USD prices per 1K tokens, rounding to whole
+/// micro-dollars so that float noise in feeds does not truncate prices
+fn feed_pricing(model_id: String, provider: String, input_per_1k: f64, output_per_1k: f64) -> ModelPricing {
+    let mut pricing = ModelPricing::new(model_id, provider, input_per_1k, output_per_1k);
+    pricing.input_price_per_1k_micros = (input_per_1k * 1_000_000.0).round() as i64;
+    pricing.output_price_per_1k_micros = (output_per_1k * 1_000_000.0).round() as i64;
+    pricing
+}
+
+/// HTTP pricing feed
+#[derive(Debug)]
+pub struct PricingFeed {
+    url: String,
+    format: PricingFeedFormat,
+    models: Vec<String>,
+    http_client: Client,
+}
+
+impl PricingFeed {
+    /// Create a feed fetched from `url`
+    pub fn new(url: impl Into<String>, format: PricingFeedFormat, timeout: Duration) -> Self {
+        let http_client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            url: url.into(),
+            format,
+            models: Vec::new(),
+            http_client,
+        }
+    }
+
+    /// Only synchronise the listed model IDs (empty = all)
+    pub fn with_models(mut self, models: Vec<String>) -> Self {
+        self.models = models;
+        self
+    }
+
+    /// URL of the feed
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Download and parse the feed
+    pub async fn fetch(&self) -> Result<Vec<ModelPricing>, DomainError> {
+        let response = self
+            .http_client
+            .get(&self.url)
+            .send()
+            .await
+            .map_err(|e| DomainError::internal(format!("Pricing feed request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(DomainError::internal(format!(
+                "Pricing feed request failed with HTTP status {}",
+                response.status().as_u16()
+            )));
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| DomainError::internal(format!("Failed to read pricing feed: {}", e)))?;
+
+        parse_pricing_feed(&body, self.format, &self.models)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_native_feed() {
+        let body = r#"{"models": [
+            {"model_id": "gpt-4o", "provider": "openai", "input_per_1k": 0.0025, "output_per_1k": 0.01},
+            {"model_id": "gpt-4o-mini", "provider": "openai", "input_per_1k": 0.00015, "output_per_1k": 0.0006, "effective_from": 1700000000}
+        ]}"#;
+
+        let pricing = parse_pricing_feed(body, PricingFeedFormat::Native, &[]).unwrap();
+        assert_eq!(pricing.len(), 2);
+        assert_eq!(pricing[0].input_price_per_1k_micros, 2500);
+        assert_eq!(pricing[0].source, PricingSource::Sync);
+        assert_eq!(pricing[1].effective_from, Some(1_700_000_000));
+
+        let filtered =
+            parse_pricing_feed(body, PricingFeedFormat::Native, &["gpt-4o".to_string()]).unwrap();
+        assert_eq!(filtered.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_litellm_feed() {
+        let body = r#"{
+            "sample_spec": {"max_tokens": "set to max_output_tokens if provider specifies it"},
+            "gpt-4o": {"input_cost_per_token": 2.5e-06, "output_cost_per_token": 1e-05, "litellm_provider": "openai"},
+            "text-embedding-3-small": {"input_cost_per_token": 2e-08, "litellm_provider": "openai"}
+        }"#;
+
+        let mut pricing = parse_pricing_feed(body, PricingFeedFormat::Litellm, &[]).unwrap();
+        pricing.sort_by(|a, b| a.model_id.cmp(&b.model_id));
+
+        assert_eq!(pricing.len(), 2);
+        assert_eq!(pricing[0].model_id, "gpt-4o");
+        assert_eq!(pricing[0].input_price_per_1k_micros, 2500);
+        assert_eq!(pricing[0].output_price_per_1k_micros, 10_000);
+        assert_eq!(pricing[1].output_price_per_1k_micros, 0);
+    }
+
+    #[test]
+    fn test_parse_invalid_feed() {
+        assert!(parse_pricing_feed("[]", PricingFeedFormat::Native, &[]).is_err());
+        assert!("csv".parse::<PricingFeedFormat>().is_err());
+        assert_eq!(
+            "LiteLLM".parse::<PricingFeedFormat>().unwrap(),
+            PricingFeedFormat::Litellm
+        );
+    }
+}
@@ -0,0 +1,374 @@
+//! Provider usage APIs used to reconcile gateway usage
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use reqwest::{Client, RequestBuilder};
+use serde::Deserialize;
+
+use crate::domain::usage::{ProviderUsage, ProviderUsageSource};
+use crate::domain::DomainError;
+
+/// Base URL of the OpenAI API
+pub const OPENAI_API_URL: &str = "https://api.openai.com";
+/// Base URL of the Anthropic API
+pub const ANTHROPIC_API_URL: &str = "https://api.anthropic.com";
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+/// Upper bound on followed pages, guarding against pagination loops
+const MAX_PAGES: usize = 50;
+
+fn http_client() -> Client {
+    Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .expect("Failed to create HTTP client")
+}
+
+/// Unix timestamps of the start (inclusive) and end (exclusive) of a UTC day
+fn day_bounds(date: NaiveDate) -> (i64, i64) {
+    let start = date
+        .and_hms_opt(0, 0, 0)
+        .unwrap_or_default()
+        .and_utc()
+        .timestamp();
+
+    (start, start + 86_400)
+}
+
+/// Send a usage request, returning the body of a successful response
+async fn fetch_page(provider: &str, request: RequestBuilder) -> Result<String, DomainError> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| DomainError::provider(provider, format!("Usage API request failed: {}", e)))?;
+
+    let status = response.status();
+    let body = response.text().await.map_err(|e| {
+        DomainError::provider(
+            provider,
+            format!("Failed to read usage API response: {}", e),
+        )
+    })?;
+
+    if !status.is_success() {
+        return Err(DomainError::provider(
+            provider,
+            format!("Usage API returned HTTP {}: {}", status.as_u16(), body),
+        ));
+    }
+
+    Ok(body)
+}
+
+/// Page of a usage API response, shared by OpenAI and Anthropic
+#[derive(Debug, Deserialize)]
+struct UsagePage<T> {
+    #[serde(default = "Vec::new")]
+    data: Vec<UsagePageBucket<T>>,
+    #[serde(default)]
+    has_more: bool,
+    #[serde(default)]
+    next_page: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UsagePageBucket<T> {
+    #[serde(default = "Vec::new")]
+    results: Vec<T>,
+}
+
+impl<T> UsagePage<T> {
+    fn into_parts(self) -> (Vec<T>, Option<String>) {
+        let next_page = if self.has_more { self.next_page } else { None };
+        let results = self.data.into_iter().flat_map(|b| b.results).collect();
+
+        (results, next_page)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiUsageResult {
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+    #[serde(default)]
+    num_model_requests: u64,
+}
+
+/// Parse a page of the OpenAI completions usage API, returning its usage
+/// and the cursor of the next page
+pub fn parse_openai_usage(body: &str) -> Result<(Vec<ProviderUsage>, Option<String>), DomainError> {
+    let page: UsagePage<OpenAiUsageResult> = serde_json::from_str(body).map_err(|e| {
+        DomainError::provider("openai", format!("Invalid usage API response: {}", e))
+    })?;
+    let (results, next_page) = page.into_parts();
+
+    let usage = results
+        .into_iter()
+        .map(|r| ProviderUsage {
+            model: r.model.unwrap_or_default(),
+            requests: Some(r.num_model_requests),
+            input_tokens: r.input_tokens,
+            output_tokens: r.output_tokens,
+        })
+        .collect();
+
+    Ok((usage, next_page))
+}
+
+/// Completions usage of an OpenAI organization, read with an admin key
+#[derive(Debug)]
+pub struct OpenAiUsageSource {
+    admin_key: String,
+    base_url: String,
+    http_client: Client,
+}
+
+impl OpenAiUsageSource {
+    /// Create a source using an OpenAI admin API key
+    pub fn new(admin_key: impl Into<String>) -> Self {
+        Self {
+            admin_key: admin_key.into(),
+            base_url: OPENAI_API_URL.to_string(),
+            http_client: http_client(),
+        }
+    }
+
+    /// Override the API base URL (builder pattern)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+}
+
+#[async_trait]
+impl ProviderUsageSource for OpenAiUsageSource {
+    fn provider(&self) -> &str {
+        "openai"
+    }
+
+    async fn daily_usage(&self, date: NaiveDate) -> Result<Vec<ProviderUsage>, DomainError> {
+        let (start, end) = day_bounds(date);
+        let url = format!("{}/v1/organization/usage/completions", self.base_url);
+        let mut usage = Vec::new();
+        let mut page: Option<String> = None;
+
+        for _ in 0..MAX_PAGES {
+            let mut query = vec![
+                ("start_time", start.to_string()),
+                ("end_time", end.to_string()),
+                ("bucket_width", "1d".to_string()),
+                ("group_by", "model".to_string()),
+            ];
+
+            if let Some(page) = &page {
+                query.push(("page", page.clone()));
+            }
+
+            let request = self
+                .http_client
+                .get(&url)
+                .bearer_auth(&self.admin_key)
+                .query(&query);
+            let (results, next_page) = parse_openai_usage(&fetch_page("openai", request).await?)?;
+            usage.extend(results);
+
+            match next_page {
+                Some(next) => page = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(usage)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AnthropicCacheCreation {
+    #[serde(default)]
+    ephemeral_1h_input_tokens: u64,
+    #[serde(default)]
+    ephemeral_5m_input_tokens: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicUsageResult {
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    uncached_input_tokens: u64,
+    #[serde(default)]
+    cache_creation: Option<AnthropicCacheCreation>,
+    #[serde(default)]
+    cache_read_input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+}
+
+/// Parse a page of the Anthropic messages usage report, returning its usage
+/// and the cursor of the next page. Cache writes and reads count as input.
+pub fn parse_anthropic_usage(
+    body: &str,
+) -> Result<(Vec<ProviderUsage>, Option<String>), DomainError> {
+    let page: UsagePage<AnthropicUsageResult> = serde_json::from_str(body).map_err(|e| {
+        DomainError::provider("anthropic", format!("Invalid usage report response: {}", e))
+    })?;
+    let (results, next_page) = page.into_parts();
+
+    let usage = results
+        .into_iter()
+        .map(|r| {
+            let cache_creation = r.cache_creation.unwrap_or_default();
+
+            ProviderUsage {
+                model: r.model.unwrap_or_default(),
+                requests: None,
+                input_tokens: r.uncached_input_tokens
+                    + cache_creation.ephemeral_1h_input_tokens
+                    + cache_creation.ephemeral_5m_input_tokens
+                    + r.cache_read_input_tokens,
+                output_tokens: r.output_tokens,
+            }
+        })
+        .collect();
+
+    Ok((usage, next_page))
+}
+
+/// Messages usage of an Anthropic organization, read with an admin key
+#[derive(Debug)]
+pub struct AnthropicUsageSource {
+    admin_key: String,
+    base_url: String,
+    http_client: Client,
+}
+
+impl AnthropicUsageSource {
+    /// Create a source using an Anthropic admin API key
+    pub fn new(admin_key: impl Into<String>) -> Self {
+        Self {
+            admin_key: admin_key.into(),
+            base_url: ANTHROPIC_API_URL.to_string(),
+            http_client: http_client(),
+        }
+    }
+
+    /// Override the API base URL (builder pattern)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+}
+
+#[async_trait]
+impl ProviderUsageSource for AnthropicUsageSource {
+    fn provider(&self) -> &str {
+        "anthropic"
+    }
+
+    async fn daily_usage(&self, date: NaiveDate) -> Result<Vec<ProviderUsage>, DomainError> {
+        let start = date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+        let end = start + chrono::Duration::days(1);
+        let url = format!("{}/v1/organizations/usage_report/messages", self.base_url);
+        let mut usage = Vec::new();
+        let mut page: Option<String> = None;
+
+        for _ in 0..MAX_PAGES {
+            let mut query = vec![
+                ("starting_at", start.to_rfc3339()),
+                ("ending_at", end.to_rfc3339()),
+                ("bucket_width", "1d".to_string()),
+                ("group_by[]", "model".to_string()),
+            ];
+
+            if let Some(page) = &page {
+                query.push(("page", page.clone()));
+            }
+
+            let request = self
+                .http_client
+                .get(&url)
+                .header("x-api-key", &self.admin_key)
+                .header("anthropic-version", ANTHROPIC_VERSION)
+                .query(&query);
+            let (results, next_page) =
+                parse_anthropic_usage(&fetch_page("anthropic", request).await?)?;
+            usage.extend(results);
+
+            match next_page {
+                Some(next) => page = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(usage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_openai_usage() {
+        let body = r#"{
+            "object": "page",
+            "data": [{
+                "object": "bucket",
+                "start_time": 1768435200,
+                "end_time": 1768521600,
+                "results": [
+                    {"object": "organization.usage.completions.result", "input_tokens": 1200, "output_tokens": 300, "input_cached_tokens": 200, "num_model_requests": 4, "model": "gpt-4o-2024-08-06"},
+                    {"object": "organization.usage.completions.result", "input_tokens": 50, "output_tokens": 10, "num_model_requests": 1, "model": "gpt-4o-mini"}
+                ]
+            }],
+            "has_more": true,
+            "next_page": "page_AAAA"
+        }"#;
+
+        let (usage, next_page) = parse_openai_usage(body).unwrap();
+
+        assert_eq!(next_page.as_deref(), Some("page_AAAA"));
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage[0].model, "gpt-4o-2024-08-06");
+        assert_eq!(usage[0].requests, Some(4));
+        assert_eq!(usage[0].input_tokens, 1200);
+        assert_eq!(usage[0].output_tokens, 300);
+    }
+
+    #[test]
+    fn test_parse_anthropic_usage() {
+        let body = r#"{
+            "data": [{
+                "starting_at": "2026-01-15T00:00:00Z",
+                "ending_at": "2026-01-16T00:00:00Z",
+                "results": [{
+                    "uncached_input_tokens": 1000,
+                    "cache_creation": {"ephemeral_1h_input_tokens": 100, "ephemeral_5m_input_tokens": 50},
+                    "cache_read_input_tokens": 25,
+                    "output_tokens": 400,
+                    "server_tool_use": {"web_search_requests": 0},
+                    "model": "claude-sonnet-4-20250514"
+                }]
+            }],
+            "has_more": false,
+            "next_page": "page_BBBB"
+        }"#;
+
+        let (usage, next_page) = parse_anthropic_usage(body).unwrap();
+
+        assert_eq!(next_page, None);
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].model, "claude-sonnet-4-20250514");
+        assert_eq!(usage[0].requests, None);
+        assert_eq!(usage[0].input_tokens, 1175);
+        assert_eq!(usage[0].output_tokens, 400);
+
+        assert!(parse_anthropic_usage("not json").is_err());
+    }
+}
@@ -0,0 +1,311 @@
+//! Scheduled reconciliation of gateway usage with provider usage APIs
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{Days, NaiveDate, Utc};
+use tracing::{info, warn};
+
+use super::provider_usage::{AnthropicUsageSource, OpenAiUsageSource};
+use super::UsageTrackingServiceTrait;
+use crate::config::ReconciliationConfig;
+use crate::domain::notification::Notification;
+use crate::domain::storage::Storage;
+use crate::domain::usage::{
+    reconcile_usage, ProviderUsageSource, ReconciliationReport, TimeBucket, UsageGroupBy,
+    UsageQuery,
+};
+use crate::domain::{DomainError, Model};
+use crate::infrastructure::leader::Leadership;
+use crate::infrastructure::notification::NotificationDispatcher;
+
+/// Compares the usage the gateway recorded for a day with the usage each
+/// provider reports, notifying about models that do not match
+pub struct UsageReconciler {
+    usage: Arc<dyn UsageTrackingServiceTrait>,
+    models: Arc<dyn Storage<Model>>,
+    sources: Vec<Arc<dyn ProviderUsageSource>>,
+    tolerance_percent: f64,
+    notifications: Option<Arc<NotificationDispatcher>>,
+}
+
+impl std::fmt::Debug for UsageReconciler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UsageReconciler")
+            .field("sources", &self.providers())
+            .field("tolerance_percent", &self.tolerance_percent)
+            .finish()
+    }
+}
+
+impl UsageReconciler {
+    /// Create a reconciler without sources and a 2% tolerance
+    pub fn new(usage: Arc<dyn UsageTrackingServiceTrait>, models: Arc<dyn Storage<Model>>) -> Self {
+        Self {
+            usage,
+            models,
+            sources: Vec::new(),
+            tolerance_percent: 2.0,
+            notifications: None,
+        }
+    }
+
+    /// Create a reconciler from the `[reconciliation]` configuration, `None`
+    /// when no provider admin key is configured
+    pub fn from_config(
+        config: &ReconciliationConfig,
+        usage: Arc<dyn UsageTrackingServiceTrait>,
+        models: Arc<dyn Storage<Model>>,
+    ) -> Option<Self> {
+        let mut reconciler =
+            Self::new(usage, models).with_tolerance_percent(config.tolerance_percent);
+
+        if let Some(key) = &config.openai_admin_key {
+            reconciler = reconciler.with_source(Arc::new(OpenAiUsageSource::new(key)));
+        }
+
+        if let Some(key) = &config.anthropic_admin_key {
+            reconciler = reconciler.with_source(Arc::new(AnthropicUsageSource::new(key)));
+        }
+
+        (!reconciler.sources.is_empty()).then_some(reconciler)
+    }
+
+    /// Add a provider usage source (builder pattern)
+    pub fn with_source(mut self, source: Arc<dyn ProviderUsageSource>) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    /// Set the token difference (percent) still counted as matched (builder pattern)
+    pub fn with_tolerance_percent(mut self, tolerance_percent: f64) -> Self {
+        self.tolerance_percent = tolerance_percent.max(0.0);
+        self
+    }
+
+    /// Notify about discrepancies found by the daily job (builder pattern)
+    pub fn with_notifications(mut self, notifications: Arc<NotificationDispatcher>) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Providers usage is reconciled for
+    pub fn providers(&self) -> Vec<&str> {
+        self.sources.iter().map(|s| s.provider()).collect()
+    }
+
+    /// Reconcile the usage of one provider on a UTC day
+    pub async fn reconcile(
+        &self,
+        provider: &str,
+        date: NaiveDate,
+    ) -> Result<ReconciliationReport, DomainError> {
+        let source = self
+            .sources
+            .iter()
+            .find(|s| s.provider() == provider)
+            .ok_or_else(|| {
+                DomainError::not_found(format!(
+                    "No usage source configured for provider '{}'",
+                    provider
+                ))
+            })?;
+
+        let gateway = self.gateway_usage(date).await?;
+        let reported = source.daily_usage(date).await?;
+
+        Ok(reconcile_usage(
+            provider,
+            date,
+            gateway.get(provider).unwrap_or(&HashMap::new()),
+            &reported,
+            self.tolerance_percent,
+        ))
+    }
+
+    /// Reconcile every provider on a UTC day, notifying about discrepancies.
+    /// A failing provider is logged and skipped.
+    pub async fn reconcile_day(&self, date: NaiveDate) -> Vec<ReconciliationReport> {
+        let mut reports = Vec::new();
+
+        for provider in self.providers() {
+            match self.reconcile(provider, date).await {
+                Ok(report) => {
+                    let discrepancies = report.discrepancies().len();
+
+                    if discrepancies == 0 {
+                        info!(provider = provider, date = %date, "Usage matches provider");
+                    } else {
+                        warn!(
+                            provider = provider,
+                            date = %date,
+                            discrepancies = discrepancies,
+                            "Usage does not match provider"
+                        );
+
+                        if let Some(notifications) = &self.notifications {
+                            notifications
+                                .notify(&Notification::usage_discrepancy(&report))
+                                .await;
+                        }
+                    }
+
+                    reports.push(report);
+                }
+                Err(e) => {
+                    warn!(provider = provider, date = %date, error = %e, "Usage reconciliation failed")
+                }
+            }
+        }
+
+        reports
+    }
+
+    /// Gateway requests and tokens of a day per provider and provider model.
+    /// Usage of models that no longer exist cannot be attributed and is skipped.
+    async fn gateway_usage(
+        &self,
+        date: NaiveDate,
+    ) -> Result<HashMap<String, HashMap<String, (u64, u64)>>, DomainError> {
+        let start = date
+            .and_hms_opt(0, 0, 0)
+            .unwrap_or_default()
+            .and_utc()
+            .timestamp() as u64;
+        let buckets = self
+            .usage
+            .timeseries(
+                &UsageQuery::new().with_time_range(start, start + 86_400),
+                TimeBucket::Day,
+                Some(UsageGroupBy::Model),
+            )
+            .await?;
+
+        let models: HashMap<String, Model> = self
+            .models
+            .list()
+            .await?
+            .into_iter()
+            .map(|m| (m.id().as_str().to_string(), m))
+            .collect();
+
+        let mut usage: HashMap<String, HashMap<String, (u64, u64)>> = HashMap::new();
+
+        for bucket in buckets {
+            let Some(model) = bucket.group.as_ref().and_then(|id| models.get(id)) else {
+                continue;
+            };
+
+            let entry = usage
+                .entry(model.provider().to_string())
+                .or_default()
+                .entry(model.provider_model().to_string())
+                .or_default();
+            entry.0 += bucket.requests;
+            entry.1 += bucket.input_tokens + bucket.output_tokens;
+        }
+
+        Ok(usage)
+    }
+}
+
+/// Reconcile the previous UTC day every day at `hour_utc`, on the leader
+/// replica only
+pub fn spawn_daily_usage_reconciliation(
+    reconciler: Arc<UsageReconciler>,
+    hour_utc: u32,
+    leadership: Leadership,
+) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(super::s3_export::delay_until_next_run(Utc::now(), hour_utc)).await;
+
+            if !leadership.is_leader() {
+                continue;
+            }
+
+            if let Some(date) = Utc::now().date_naive().checked_sub_days(Days::new(1)) {
+                reconciler.reconcile_day(date).await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::domain::usage::{
+        ProviderUsage, ReconciliationStatus, UsageRecord, UsageRepository, UsageType,
+    };
+    use crate::domain::{CredentialType, ModelId};
+    use crate::infrastructure::storage::InMemoryStorage;
+    use crate::infrastructure::usage::{InMemoryUsageRepository, UsageTrackingService};
+
+    #[derive(Debug)]
+    struct StaticSource(Vec<ProviderUsage>);
+
+    #[async_trait]
+    impl ProviderUsageSource for StaticSource {
+        fn provider(&self) -> &str {
+            "openai"
+        }
+
+        async fn daily_usage(&self, _date: NaiveDate) -> Result<Vec<ProviderUsage>, DomainError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_maps_gateway_models_to_provider_models() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let repository = Arc::new(InMemoryUsageRepository::default());
+
+        for (id, model, timestamp) in [
+            ("r-1", "fast", 1_704_070_000),
+            ("r-2", "fast", 1_704_080_000),
+            ("r-3", "deleted", 1_704_080_000),
+            ("r-4", "fast", 1_704_160_000),
+        ] {
+            let mut record = UsageRecord::new(id, UsageType::ChatCompletion, "key-1")
+                .with_model_id(model)
+                .with_tokens(100, 50);
+            record.timestamp = timestamp;
+            repository.record(record).await.unwrap();
+        }
+
+        let models: Arc<InMemoryStorage<Model>> = Arc::new(InMemoryStorage::new());
+        models
+            .create(Model::new(
+                ModelId::new("fast").unwrap(),
+                "Fast",
+                CredentialType::OpenAi,
+                "gpt-4o-mini",
+                "cred-1",
+            ))
+            .await
+            .unwrap();
+
+        let reconciler =
+            UsageReconciler::new(Arc::new(UsageTrackingService::new(repository)), models)
+                .with_source(Arc::new(StaticSource(vec![ProviderUsage {
+                    model: "gpt-4o-mini-2024-07-18".to_string(),
+                    requests: Some(3),
+                    input_tokens: 300,
+                    output_tokens: 150,
+                }])));
+
+        let report = reconciler.reconcile("openai", date).await.unwrap();
+
+        assert_eq!(report.lines.len(), 1);
+        assert_eq!(report.lines[0].model, "gpt-4o-mini");
+        assert_eq!(report.lines[0].gateway_requests, 2);
+        assert_eq!(report.lines[0].gateway_tokens, 300);
+        assert_eq!(report.lines[0].token_diff, -150);
+        assert_eq!(report.lines[0].status, ReconciliationStatus::Missing);
+
+        assert!(reconciler.reconcile("anthropic", date).await.is_err());
+        assert_eq!(reconciler.reconcile_day(date).await.len(), 1);
+    }
+}
@@ -0,0 +1,242 @@
+//! Scheduled delivery of daily usage files to S3
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use aws_sdk_s3::primitives::ByteStream;
+use chrono::{DateTime, Days, NaiveDate, TimeZone, Utc};
+use tracing::{info, warn};
+
+use crate::config::UsageExportConfig;
+use crate::domain::usage::UsageQuery;
+use crate::domain::DomainError;
+use crate::infrastructure::leader::Leadership;
+
+use super::export::{encode_usage_records, UsageExportFormat};
+use super::service::UsageTrackingServiceTrait;
+
+/// Attempts made for a daily export before giving up until the next day
+const EXPORT_ATTEMPTS: u32 = 3;
+/// Delay between failed export attempts
+const EXPORT_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+/// Writes the usage records of a day to an S3 bucket
+pub struct S3UsageExporter {
+    client: aws_sdk_s3::Client,
+    bucket: String,
+    prefix: String,
+    format: UsageExportFormat,
+    usage: Arc<dyn UsageTrackingServiceTrait>,
+}
+
+impl std::fmt::Debug for S3UsageExporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("S3UsageExporter")
+            .field("bucket", &self.bucket)
+            .field("prefix", &self.prefix)
+            .field("format", &self.format)
+            .finish()
+    }
+}
+
+impl S3UsageExporter {
+    /// Create an exporter writing to `bucket` with the given client
+    pub fn new(
+        client: aws_sdk_s3::Client,
+        bucket: impl Into<String>,
+        usage: Arc<dyn UsageTrackingServiceTrait>,
+    ) -> Self {
+        Self {
+            client,
+            bucket: bucket.into(),
+            prefix: String::new(),
+            format: UsageExportFormat::default(),
+            usage,
+        }
+    }
+
+    /// Create an exporter from the usage export settings, `None` when no bucket is configured
+    pub async fn from_config(
+        config: &UsageExportConfig,
+        usage: Arc<dyn UsageTrackingServiceTrait>,
+    ) -> Result<Option<Self>, DomainError> {
+        let Some(bucket) = &config.s3_bucket else {
+            return Ok(None);
+        };
+
+        let format: UsageExportFormat = config.format.parse()?;
+
+        let sdk_config = match &config.s3_region {
+            Some(region) => {
+                aws_config::defaults(aws_config::BehaviorVersion::latest())
+                    .region(aws_config::Region::new(region.clone()))
+                    .load()
+                    .await
+            }
+            None => aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await,
+        };
+
+        let mut s3_config = aws_sdk_s3::config::Builder::from(&sdk_config);
+
+        if let Some(endpoint_url) = &config.s3_endpoint_url {
+            s3_config = s3_config.endpoint_url(endpoint_url).force_path_style(true);
+        }
+
+        let client = aws_sdk_s3::Client::from_conf(s3_config.build());
+
+        Ok(Some(
+            Self::new(client, bucket.clone(), usage)
+                .with_prefix(config.s3_prefix.clone())
+                .with_format(format),
+        ))
+    }
+
+    /// Set the key prefix of the exported files
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Set the file format
+    pub fn with_format(mut self, format: UsageExportFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Export the usage records of a UTC day, returning the object key
+    pub async fn export_day(&self, date: NaiveDate) -> Result<String, DomainError> {
+        let (from, to) = day_range(date);
+        let records = self
+            .usage
+            .query(&UsageQuery::new().with_time_range(from, to))
+            .await?;
+
+        let body = encode_usage_records(&records, self.format)?;
+        let key = usage_export_key(&self.prefix, date, self.format);
+
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(&key)
+            .content_type(self.format.content_type())
+            .body(ByteStream::from(body))
+            .send()
+            .await
+            .map_err(|e| {
+                DomainError::internal(format!(
+                    "Failed to upload usage export s3://{}/{}: {}",
+                    self.bucket, key, e
+                ))
+            })?;
+
+        info!(
+            bucket = %self.bucket,
+            key = %key,
+            records = records.len(),
+            "Exported daily usage"
+        );
+
+        Ok(key)
+    }
+}
+
+/// Export the previous UTC day every day at `hour_utc`, on the leader
+/// replica only
+pub fn spawn_daily_usage_export(
+    exporter: Arc<S3UsageExporter>,
+    hour_utc: u32,
+    leadership: Leadership,
+) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(delay_until_next_run(Utc::now(), hour_utc)).await;
+
+            if !leadership.is_leader() {
+                continue;
+            }
+
+            let Some(date) = Utc::now().date_naive().checked_sub_days(Days::new(1)) else {
+                continue;
+            };
+
+            for attempt in 1..=EXPORT_ATTEMPTS {
+                match exporter.export_day(date).await {
+                    Ok(_) => break,
+                    Err(e) => {
+                        warn!(error = %e, %date, attempt, "Daily usage export failed");
+
+                        if attempt < EXPORT_ATTEMPTS {
+                            tokio::time::sleep(EXPORT_RETRY_DELAY).await;
+                        }
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Object key of a day's export, partitioned by date
+pub fn usage_export_key(prefix: &str, date: NaiveDate, format: UsageExportFormat) -> String {
+    let file = format!("dt={}/usage-{}.{}", date, date, format.extension());
+    let prefix = prefix.trim_matches('/');
+
+    if prefix.is_empty() {
+        file
+    } else {
+        format!("{}/{}", prefix, file)
+    }
+}
+
+/// Unix timestamps of the start (inclusive) and end (exclusive) of a UTC day
+fn day_range(date: NaiveDate) -> (u64, u64) {
+    let start = date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().timestamp() as u64;
+
+    (start, start + 86_400)
+}
+
+/// Time until the next occurrence of `hour_utc`
+pub(super) fn delay_until_next_run(now: DateTime<Utc>, hour_utc: u32) -> Duration {
+    let today = now.date_naive().and_hms_opt(hour_utc.min(23), 0, 0).unwrap_or_default();
+    let mut next = Utc.from_utc_datetime(&today);
+
+    if next <= now {
+        next += chrono::Duration::days(1);
+    }
+
+    (next - now).to_std().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_export_key() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+
+        assert_eq!(
+            usage_export_key("usage/", date, UsageExportFormat::Csv),
+            "usage/dt=2026-01-15/usage-2026-01-15.csv"
+        );
+        assert_eq!(
+            usage_export_key("", date, UsageExportFormat::Parquet),
+            "dt=2026-01-15/usage-2026-01-15.parquet"
+        );
+    }
+
+    #[test]
+    fn test_day_range() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+
+        assert_eq!(day_range(date), (1_704_067_200, 1_704_153_600));
+    }
+
+    #[test]
+    fn test_delay_until_next_run() {
+        let now = Utc.with_ymd_and_hms(2026, 1, 15, 0, 30, 0).unwrap();
+        assert_eq!(delay_until_next_run(now, 1), Duration::from_secs(30 * 60));
+
+        let now = Utc.with_ymd_and_hms(2026, 1, 15, 1, 0, 0).unwrap();
+        assert_eq!(delay_until_next_run(now, 1), Duration::from_secs(86_400));
+    }
+}
@@ -8,18 +8,22 @@ use std::time::SystemTime;
 use async_trait::async_trait;
 use uuid::Uuid;
 
+use crate::domain::team::{TeamId, TeamRepository};
 use crate::domain::usage::{
     Budget, BudgetAlert, BudgetId, BudgetPeriod, BudgetRepository, BudgetStatus, ModelPricing,
-    UsageAggregate, UsageQuery, UsageRecord, UsageRecordId, UsageRepository, UsageSummary,
-    UsageType,
+    TimeBucket, UsageAggregate, UsageBucket, UsageGroupBy, UsageQuery, UsageRecord,
+    UsageRecordId, UsageRepository, UsageSummary, UsageType,
 };
 use crate::domain::DomainError;
 
+use super::pricing::PricingCatalog;
+
 /// Parameters for recording usage
 #[derive(Debug, Clone)]
 pub struct RecordUsageParams {
     pub usage_type: UsageType,
     pub api_key_id: String,
+    pub team_id: Option<String>,
     pub model_id: Option<String>,
     pub input_tokens: u32,
     pub output_tokens: u32,
@@ -27,6 +31,9 @@ pub struct RecordUsageParams {
     pub success: bool,
     pub error: Option<String>,
     pub metadata: HashMap<String, String>,
+    pub tags: HashMap<String, String>,
+    /// Cost already computed by the caller, instead of the model's current pricing
+    pub cost_micros: Option<i64>,
 }
 
 impl RecordUsageParams {
@@ -34,6 +41,7 @@ impl RecordUsageParams {
         Self {
             usage_type,
             api_key_id: api_key_id.into(),
+            team_id: None,
             model_id: None,
             input_tokens: 0,
             output_tokens: 0,
@@ -41,9 +49,16 @@ impl RecordUsageParams {
             success: true,
             error: None,
             metadata: HashMap::new(),
+            tags: HashMap::new(),
+            cost_micros: None,
         }
     }
 
+    pub fn with_team(mut self, team_id: impl Into<String>) -> Self {
+        self.team_id = Some(team_id.into());
+        self
+    }
+
     pub fn with_model(mut self, model_id: impl Into<String>) -> Self {
         self.model_id = Some(model_id.into());
         self
@@ -65,6 +80,16 @@ impl RecordUsageParams {
         self.error = Some(error.into());
         self
     }
+
+    pub fn with_tags(mut self, tags: HashMap<String, String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn with_cost_micros(mut self, cost_micros: i64) -> Self {
+        self.cost_micros = Some(cost_micros);
+        self
+    }
 }
 
 /// Trait for usage tracking service
@@ -88,14 +113,25 @@ pub trait UsageTrackingServiceTrait: Send + Sync + Debug {
     /// Get usage summary with daily breakdown
     async fn summary(&self, query: &UsageQuery) -> Result<UsageSummary, DomainError>;
 
+    /// Get usage per time bucket, optionally split by team, model or API key
+    async fn timeseries(
+        &self,
+        query: &UsageQuery,
+        bucket: TimeBucket,
+        group_by: Option<UsageGroupBy>,
+    ) -> Result<Vec<UsageBucket>, DomainError>;
+
     /// Delete records older than timestamp
     async fn delete_before(&self, timestamp: u64) -> Result<usize, DomainError>;
 
     /// Delete all records for an API key
     async fn delete_by_api_key(&self, api_key_id: &str) -> Result<usize, DomainError>;
 
+    /// Delete all records carrying a tag
+    async fn delete_by_tag(&self, key: &str, value: &str) -> Result<usize, DomainError>;
+
     /// Get pricing for a model
-    fn get_pricing(&self, model_id: &str) -> Option<&ModelPricing>;
+    fn get_pricing(&self, model_id: &str) -> Option<ModelPricing>;
 
     /// Calculate cost for tokens
     fn calculate_cost(&self, model_id: &str, input_tokens: u32, output_tokens: u32) -> i64;
@@ -105,26 +141,28 @@ pub trait UsageTrackingServiceTrait: Send + Sync + Debug {
 #[derive(Debug)]
 pub struct UsageTrackingService<R: UsageRepository> {
     repository: Arc<R>,
-    pricing: HashMap<String, ModelPricing>,
+    pricing: Arc<PricingCatalog>,
 }
 
 impl<R: UsageRepository> UsageTrackingService<R> {
     /// Create a new usage tracking service
     pub fn new(repository: Arc<R>) -> Self {
-        Self {
-            repository,
-            pricing: crate::domain::usage::default_model_pricing(),
-        }
+        Self::with_pricing(repository, crate::domain::usage::default_model_pricing())
     }
 
     /// Create with custom pricing
     pub fn with_pricing(repository: Arc<R>, pricing: HashMap<String, ModelPricing>) -> Self {
+        Self::with_catalog(repository, Arc::new(PricingCatalog::new(pricing.into_values())))
+    }
+
+    /// Create with a shared pricing catalog, kept up to date by the pricing service
+    pub fn with_catalog(repository: Arc<R>, pricing: Arc<PricingCatalog>) -> Self {
         Self { repository, pricing }
     }
 
     /// Add or update pricing for a model
     pub fn set_pricing(&mut self, pricing: ModelPricing) {
-        self.pricing.insert(pricing.model_id.clone(), pricing);
+        self.pricing.upsert(pricing);
     }
 
     fn generate_id(&self) -> String {
@@ -135,16 +173,23 @@ impl<R: UsageRepository> UsageTrackingService<R> {
 #[async_trait]
 impl<R: UsageRepository + 'static> UsageTrackingServiceTrait for UsageTrackingService<R> {
     async fn record(&self, params: RecordUsageParams) -> Result<UsageRecord, DomainError> {
-        let cost = params
-            .model_id
-            .as_ref()
-            .map(|m| self.calculate_cost(m, params.input_tokens, params.output_tokens))
-            .unwrap_or(0);
+        let cost = params.cost_micros.unwrap_or_else(|| {
+            params
+                .model_id
+                .as_ref()
+                .map(|m| self.calculate_cost(m, params.input_tokens, params.output_tokens))
+                .unwrap_or(0)
+        });
 
         let mut record = UsageRecord::new(self.generate_id(), params.usage_type, params.api_key_id)
             .with_tokens(params.input_tokens, params.output_tokens)
             .with_cost_micros(cost)
-            .with_latency_ms(params.latency_ms);
+            .with_latency_ms(params.latency_ms)
+            .with_tags(params.tags);
+
+        if let Some(team_id) = params.team_id {
+            record = record.with_team_id(team_id);
+        }
 
         if let Some(model_id) = params.model_id {
             record = record.with_model_id(model_id);
@@ -183,6 +228,15 @@ impl<R: UsageRepository + 'static> UsageTrackingServiceTrait for UsageTrackingSe
         self.repository.summary(query).await
     }
 
+    async fn timeseries(
+        &self,
+        query: &UsageQuery,
+        bucket: TimeBucket,
+        group_by: Option<UsageGroupBy>,
+    ) -> Result<Vec<UsageBucket>, DomainError> {
+        self.repository.timeseries(query, bucket, group_by).await
+    }
+
     async fn delete_before(&self, timestamp: u64) -> Result<usize, DomainError> {
         self.repository.delete_before(timestamp).await
     }
@@ -191,13 +245,17 @@ impl<R: UsageRepository + 'static> UsageTrackingServiceTrait for UsageTrackingSe
         self.repository.delete_by_api_key(api_key_id).await
     }
 
-    fn get_pricing(&self, model_id: &str) -> Option<&ModelPricing> {
-        self.pricing.get(model_id)
+    async fn delete_by_tag(&self, key: &str, value: &str) -> Result<usize, DomainError> {
+        self.repository.delete_by_tag(key, value).await
+    }
+
+    fn get_pricing(&self, model_id: &str) -> Option<ModelPricing> {
+        self.pricing.current(model_id)
     }
 
     fn calculate_cost(&self, model_id: &str, input_tokens: u32, output_tokens: u32) -> i64 {
         self.pricing
-            .get(model_id)
+            .current(model_id)
             .map(|p| p.calculate_cost(input_tokens, output_tokens))
             .unwrap_or(0)
     }
@@ -214,6 +272,10 @@ pub struct BudgetCheckResult {
     pub warning_budgets: Vec<BudgetId>,
     /// Estimated cost in micro-dollars
     pub estimated_cost_micros: i64,
+    /// Fallback model of the first exceeded budget that defines one
+    pub fallback_model_id: Option<String>,
+    /// Applicable budget with the least remaining headroom
+    pub most_constrained: Option<BudgetHeadroom>,
 }
 
 impl BudgetCheckResult {
@@ -223,8 +285,58 @@ impl BudgetCheckResult {
             exceeded_budgets: Vec::new(),
             warning_budgets: Vec::new(),
             estimated_cost_micros: estimated_cost,
+            fallback_model_id: None,
+            most_constrained: None,
         }
     }
+
+    fn evaluate(budgets: Vec<Budget>, estimated_cost: i64) -> Self {
+        let mut result = Self::new(estimated_cost);
+
+        for budget in budgets {
+            if !budget.allows_cost(estimated_cost) {
+                result.allowed = false;
+                result.exceeded_budgets.push(budget.id().clone());
+
+                if result.fallback_model_id.is_none() {
+                    result.fallback_model_id = budget.fallback_model_id.clone();
+                }
+            } else if budget.status == BudgetStatus::Warning {
+                result.warning_budgets.push(budget.id().clone());
+            }
+
+            if !budget.enabled || budget.status == BudgetStatus::Paused {
+                continue;
+            }
+
+            let remaining_micros = budget.remaining_micros();
+            if result
+                .most_constrained
+                .as_ref()
+                .is_none_or(|c| remaining_micros < c.remaining_micros)
+            {
+                result.most_constrained = Some(BudgetHeadroom {
+                    budget_id: budget.id().clone(),
+                    limit_micros: budget.hard_limit_micros,
+                    remaining_micros,
+                    resets_at: (budget.period != BudgetPeriod::Lifetime)
+                        .then(|| calculate_period_end(budget.period, budget.period_start)),
+                });
+            }
+        }
+
+        result
+    }
+}
+
+/// Remaining headroom of a budget
+#[derive(Debug, Clone)]
+pub struct BudgetHeadroom {
+    pub budget_id: BudgetId,
+    pub limit_micros: i64,
+    pub remaining_micros: i64,
+    /// When the current period ends (Unix seconds), `None` for lifetime budgets
+    pub resets_at: Option<u64>,
 }
 
 /// Alert notification
@@ -300,12 +412,38 @@ pub trait BudgetServiceTrait: Send + Sync + Debug {
 #[derive(Debug)]
 pub struct BudgetService<R: BudgetRepository> {
     repository: Arc<R>,
+    teams: Option<Arc<dyn TeamRepository>>,
 }
 
 impl<R: BudgetRepository> BudgetService<R> {
     /// Create a new budget service
     pub fn new(repository: Arc<R>) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            teams: None,
+        }
+    }
+
+    /// Resolve the organization of teams so organization budgets apply (builder pattern)
+    pub fn with_team_repository(mut self, teams: Arc<dyn TeamRepository>) -> Self {
+        self.teams = Some(teams);
+        self
+    }
+
+    /// Organization of the given team, if known
+    async fn organization_of(&self, team_id: Option<&str>) -> Result<Option<String>, DomainError> {
+        let (Some(teams), Some(team_id)) = (&self.teams, team_id) else {
+            return Ok(None);
+        };
+
+        let Ok(team_id) = TeamId::new(team_id) else {
+            return Ok(None);
+        };
+
+        Ok(teams
+            .get(&team_id)
+            .await?
+            .and_then(|team| team.organization_id().map(|o| o.as_str().to_string())))
     }
 
     fn current_timestamp() -> u64 {
@@ -314,14 +452,14 @@ impl<R: BudgetRepository> BudgetService<R> {
             .unwrap_or_default()
             .as_secs()
     }
+}
 
-    fn calculate_period_end(period: BudgetPeriod, period_start: u64) -> u64 {
-        match period {
-            BudgetPeriod::Daily => period_start + 86400,
-            BudgetPeriod::Weekly => period_start + 604800,
-            BudgetPeriod::Monthly => period_start + 2592000, // ~30 days
-            BudgetPeriod::Lifetime => u64::MAX,
-        }
+fn calculate_period_end(period: BudgetPeriod, period_start: u64) -> u64 {
+    match period {
+        BudgetPeriod::Daily => period_start + 86400,
+        BudgetPeriod::Weekly => period_start + 604800,
+        BudgetPeriod::Monthly => period_start + 2592000, // ~30 days
+        BudgetPeriod::Lifetime => u64::MAX,
     }
 }
 
@@ -389,18 +527,7 @@ impl<R: BudgetRepository + 'static> BudgetServiceTrait for BudgetService<R> {
             .find_applicable(api_key_id, model_id)
             .await?;
 
-        let mut result = BudgetCheckResult::new(estimated_cost_micros);
-
-        for budget in budgets {
-            if !budget.allows_cost(estimated_cost_micros) {
-                result.allowed = false;
-                result.exceeded_budgets.push(budget.id().clone());
-            } else if budget.status == BudgetStatus::Warning {
-                result.warning_budgets.push(budget.id().clone());
-            }
-        }
-
-        Ok(result)
+        Ok(BudgetCheckResult::evaluate(budgets, estimated_cost_micros))
     }
 
     async fn check_budget_with_team(
@@ -410,23 +537,13 @@ impl<R: BudgetRepository + 'static> BudgetServiceTrait for BudgetService<R> {
         model_id: Option<&str>,
         estimated_cost_micros: i64,
     ) -> Result<BudgetCheckResult, DomainError> {
+        let organization_id = self.organization_of(team_id).await?;
         let budgets = self
             .repository
-            .find_applicable_with_team(api_key_id, team_id, model_id)
+            .find_applicable_with_team(api_key_id, team_id, organization_id.as_deref(), model_id)
             .await?;
 
-        let mut result = BudgetCheckResult::new(estimated_cost_micros);
-
-        for budget in budgets {
-            if !budget.allows_cost(estimated_cost_micros) {
-                result.allowed = false;
-                result.exceeded_budgets.push(budget.id().clone());
-            } else if budget.status == BudgetStatus::Warning {
-                result.warning_budgets.push(budget.id().clone());
-            }
-        }
-
-        Ok(result)
+        Ok(BudgetCheckResult::evaluate(budgets, estimated_cost_micros))
     }
 
     async fn record_usage(
@@ -473,9 +590,10 @@ impl<R: BudgetRepository + 'static> BudgetServiceTrait for BudgetService<R> {
         model_id: Option<&str>,
         cost_micros: i64,
     ) -> Result<Vec<AlertNotification>, DomainError> {
+        let organization_id = self.organization_of(team_id).await?;
         let budgets = self
             .repository
-            .find_applicable_with_team(api_key_id, team_id, model_id)
+            .find_applicable_with_team(api_key_id, team_id, organization_id.as_deref(), model_id)
             .await?;
 
         let mut notifications = Vec::new();
@@ -510,7 +628,7 @@ impl<R: BudgetRepository + 'static> BudgetServiceTrait for BudgetService<R> {
         let count = expired.len();
 
         for mut budget in expired {
-            let period_end = Self::calculate_period_end(budget.period, budget.period_start);
+            let period_end = calculate_period_end(budget.period, budget.period_start);
 
             if now >= period_end {
                 budget.reset_period();
@@ -656,6 +774,49 @@ mod tests {
         assert_eq!(result.exceeded_budgets.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_budget_check_reports_fallback_and_headroom() {
+        let repo = Arc::new(InMemoryBudgetRepository::new());
+        let service = BudgetService::new(repo);
+
+        service
+            .create(
+                Budget::new("wide", "Wide", BudgetPeriod::Lifetime)
+                    .with_hard_limit(100.0)
+                    .with_api_key("api-key-1"),
+            )
+            .await
+            .unwrap();
+        service
+            .create(
+                Budget::new("narrow", "Narrow", BudgetPeriod::Daily)
+                    .with_hard_limit(10.0)
+                    .with_api_key("api-key-1")
+                    .with_fallback_model("gpt-4o-mini"),
+            )
+            .await
+            .unwrap();
+
+        let result = service
+            .check_budget("api-key-1", None, 1_000_000)
+            .await
+            .unwrap();
+        assert!(result.allowed);
+        assert!(result.fallback_model_id.is_none());
+
+        let headroom = result.most_constrained.unwrap();
+        assert_eq!(headroom.budget_id.as_str(), "narrow");
+        assert_eq!(headroom.remaining_micros, 10_000_000);
+        assert!(headroom.resets_at.is_some());
+
+        let result = service
+            .check_budget("api-key-1", None, 20_000_000)
+            .await
+            .unwrap();
+        assert!(!result.allowed);
+        assert_eq!(result.fallback_model_id.as_deref(), Some("gpt-4o-mini"));
+    }
+
     #[tokio::test]
     async fn test_budget_service_record_usage() {
         let repo = Arc::new(InMemoryBudgetRepository::new());
@@ -698,4 +859,45 @@ mod tests {
         assert!(result.allowed);
         assert!(result.exceeded_budgets.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_budget_service_organization_budget() {
+        use crate::domain::organization::OrganizationId;
+        use crate::domain::team::Team;
+        use crate::infrastructure::storage::InMemoryStorage;
+        use crate::infrastructure::team::StorageTeamRepository;
+
+        let teams = StorageTeamRepository::new(Arc::new(InMemoryStorage::<Team>::new()));
+        let mut team = Team::new(TeamId::new("team-1").unwrap(), "Team 1").unwrap();
+        team.set_organization_id(Some(OrganizationId::new("acme").unwrap()));
+        teams.create(team).await.unwrap();
+
+        let repo = Arc::new(InMemoryBudgetRepository::new());
+        let service = BudgetService::new(repo).with_team_repository(Arc::new(teams));
+
+        let budget = Budget::new("budget-1", "Acme Budget", BudgetPeriod::Monthly)
+            .with_hard_limit(100.0)
+            .with_organization("acme");
+
+        service.create(budget).await.unwrap();
+
+        service
+            .record_usage_with_team("api-key-1", Some("team-1"), None, 80_000_000)
+            .await
+            .unwrap();
+
+        // The organization budget is shared by all of its teams
+        let result = service
+            .check_budget_with_team("api-key-2", Some("team-1"), None, 30_000_000)
+            .await
+            .unwrap();
+        assert!(!result.allowed);
+
+        // Teams outside the organization are not affected
+        let result = service
+            .check_budget_with_team("api-key-2", Some("team-2"), None, 30_000_000)
+            .await
+            .unwrap();
+        assert!(result.allowed);
+    }
 }
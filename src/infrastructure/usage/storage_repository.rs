@@ -1,4 +1,4 @@
-//! Storage-backed usage and budget repository implementations
+//! Storage-backed usage, budget and pricing repository implementations
 
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -6,21 +6,34 @@ use std::sync::Arc;
 
 use crate::domain::storage::Storage;
 use crate::domain::usage::{
-    Budget, BudgetId, BudgetPeriod, BudgetRepository, DailyUsage, UsageAggregate, UsageQuery,
-    UsageRecord, UsageRecordId, UsageRepository, UsageSummary,
+    bucket_usage_records, Budget, BudgetId, BudgetPeriod, BudgetRepository, DailyUsage,
+    ModelPricing, PricingId, PricingRepository, TimeBucket, UsageAggregate, UsageBucket,
+    UsageGroupBy, UsageQuery, UsageRecord, UsageRecordId, UsageRepository, UsageSummary,
 };
 use crate::domain::DomainError;
 
+use super::postgres_aggregation::PostgresUsageAggregator;
+
 /// Storage-backed implementation of UsageRepository
 #[derive(Debug)]
 pub struct StorageUsageRepository {
     storage: Arc<dyn Storage<UsageRecord>>,
+    aggregator: Option<PostgresUsageAggregator>,
 }
 
 impl StorageUsageRepository {
     /// Create a new storage-backed repository
     pub fn new(storage: Arc<dyn Storage<UsageRecord>>) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            aggregator: None,
+        }
+    }
+
+    /// Compute time series in PostgreSQL instead of loading every record
+    pub fn with_aggregator(mut self, aggregator: PostgresUsageAggregator) -> Self {
+        self.aggregator = Some(aggregator);
+        self
     }
 
     fn filter_records<'a>(
@@ -36,6 +49,12 @@ impl StorageUsageRepository {
                     }
                 }
 
+                if let Some(ref team) = query.team_id
+                    && r.team_id.as_ref() != Some(team)
+                {
+                    return false;
+                }
+
                 if let Some(ref model) = query.model_id {
                     if r.model_id.as_ref() != Some(model) {
                         return false;
@@ -54,7 +73,7 @@ impl StorageUsageRepository {
                     }
                 }
 
-                true
+                r.has_tags(&query.tags)
             })
             .collect()
     }
@@ -178,6 +197,25 @@ impl UsageRepository for StorageUsageRepository {
         })
     }
 
+    async fn timeseries(
+        &self,
+        query: &UsageQuery,
+        bucket: TimeBucket,
+        group_by: Option<UsageGroupBy>,
+    ) -> Result<Vec<UsageBucket>, DomainError> {
+        if let Some(aggregator) = &self.aggregator {
+            return aggregator.timeseries(query, bucket, group_by).await;
+        }
+
+        let all = self.storage.list().await?;
+
+        Ok(bucket_usage_records(
+            self.filter_records(all.iter(), query),
+            bucket,
+            group_by,
+        ))
+    }
+
     async fn delete_before(&self, timestamp: u64) -> Result<usize, DomainError> {
         let all = self.storage.list().await?;
         let mut deleted = 0;
@@ -207,6 +245,21 @@ impl UsageRepository for StorageUsageRepository {
 
         Ok(deleted)
     }
+
+    async fn delete_by_tag(&self, key: &str, value: &str) -> Result<usize, DomainError> {
+        let all = self.storage.list().await?;
+        let mut deleted = 0;
+
+        for record in all {
+            if record.tags.get(key).map(String::as_str) == Some(value)
+                && self.storage.delete(record.id()).await?
+            {
+                deleted += 1;
+            }
+        }
+
+        Ok(deleted)
+    }
 }
 
 fn timestamp_to_day_start(timestamp: u64) -> u64 {
@@ -284,6 +337,7 @@ impl BudgetRepository for StorageBudgetRepository {
         &self,
         api_key_id: &str,
         team_id: Option<&str>,
+        organization_id: Option<&str>,
         model_id: Option<&str>,
     ) -> Result<Vec<Budget>, DomainError> {
         let all = self.storage.list().await?;
@@ -291,7 +345,7 @@ impl BudgetRepository for StorageBudgetRepository {
             .into_iter()
             .filter(|b| {
                 b.enabled
-                    && b.applies_to_api_key_with_team(api_key_id, team_id)
+                    && b.applies_to_api_key_in_organization(api_key_id, team_id, organization_id)
                     && model_id.map_or(true, |m| b.applies_to_model(m))
             })
             .collect())
@@ -323,6 +377,44 @@ impl BudgetRepository for StorageBudgetRepository {
     }
 }
 
+/// Storage-backed implementation of PricingRepository
+#[derive(Debug)]
+pub struct StoragePricingRepository {
+    storage: Arc<dyn Storage<ModelPricing>>,
+}
+
+impl StoragePricingRepository {
+    /// Create a new storage-backed repository
+    pub fn new(storage: Arc<dyn Storage<ModelPricing>>) -> Self {
+        Self { storage }
+    }
+}
+
+#[async_trait]
+impl PricingRepository for StoragePricingRepository {
+    async fn get(&self, id: &PricingId) -> Result<Option<ModelPricing>, DomainError> {
+        self.storage.get(id).await
+    }
+
+    async fn list(&self) -> Result<Vec<ModelPricing>, DomainError> {
+        let mut versions = self.storage.list().await?;
+        versions.sort_by(|a, b| {
+            a.model_id
+                .cmp(&b.model_id)
+                .then(a.effective_from.cmp(&b.effective_from))
+        });
+        Ok(versions)
+    }
+
+    async fn save(&self, pricing: ModelPricing) -> Result<ModelPricing, DomainError> {
+        self.storage.save(pricing).await
+    }
+
+    async fn delete(&self, id: &PricingId) -> Result<bool, DomainError> {
+        self.storage.delete(id).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -424,4 +516,24 @@ mod tests {
         let applicable = repo.find_applicable("key-2", None).await.unwrap();
         assert_eq!(applicable.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_pricing_save_and_list() {
+        let repo = StoragePricingRepository::new(Arc::new(InMemoryStorage::<ModelPricing>::new()));
+
+        let current = ModelPricing::new("gpt-4o", "openai", 0.0025, 0.01).with_effective_from(200);
+        let previous = ModelPricing::new("gpt-4o", "openai", 0.005, 0.015)
+            .with_effective_dates(100, 199);
+
+        repo.save(current.clone()).await.unwrap();
+        repo.save(previous).await.unwrap();
+        repo.save(current.clone()).await.unwrap();
+
+        let versions = repo.list().await.unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].effective_from, Some(100));
+
+        assert!(repo.delete(&current.id).await.unwrap());
+        assert!(repo.get(&current.id).await.unwrap().is_none());
+    }
 }
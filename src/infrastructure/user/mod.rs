@@ -1,14 +1,19 @@
 //! User infrastructure module
 //!
 //! This module provides implementations for user authentication and management,
-//! including password hashing with Argon2, in-memory repository, and user service.
+//! including password hashing with Argon2, TOTP multi-factor authentication,
+//! in-memory repository, and user service.
 
 mod password;
 mod postgres_repository;
 mod repository;
 mod service;
+mod totp;
 
 pub use password::{Argon2Hasher, PasswordHasher};
 pub use postgres_repository::PostgresUserRepository;
 pub use repository::InMemoryUserRepository;
-pub use service::{CreateUserRequest, UpdatePasswordRequest, UserService};
+pub use service::{
+    CreateUserRequest, LoginOutcome, MfaEnrollment, UpdatePasswordRequest, UserService,
+};
+pub use totp::Totp;
@@ -25,7 +25,7 @@ impl UserRepository for PostgresUserRepository {
     async fn get(&self, id: &UserId) -> Result<Option<User>, DomainError> {
         let row = sqlx::query(
             r#"
-            SELECT id, username, password_hash, status, team_id, team_role,
+            SELECT id, username, password_hash, status, team_id, team_role, role_id, mfa,
                    created_at, updated_at, last_login_at
             FROM users
             WHERE id = $1
@@ -45,7 +45,7 @@ impl UserRepository for PostgresUserRepository {
     async fn get_by_username(&self, username: &str) -> Result<Option<User>, DomainError> {
         let row = sqlx::query(
             r#"
-            SELECT id, username, password_hash, status, team_id, team_role,
+            SELECT id, username, password_hash, status, team_id, team_role, role_id, mfa,
                    created_at, updated_at, last_login_at
             FROM users
             WHERE username = $1
@@ -66,8 +66,8 @@ impl UserRepository for PostgresUserRepository {
         sqlx::query(
             r#"
             INSERT INTO users (id, username, password_hash, status, team_id, team_role,
-                             created_at, updated_at, last_login_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                             role_id, mfa, created_at, updated_at, last_login_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
             "#,
         )
         .bind(user.id().as_str())
@@ -76,6 +76,8 @@ impl UserRepository for PostgresUserRepository {
         .bind(status_to_str(user.status()))
         .bind(user.team_id().as_str())
         .bind(role_to_str(user.team_role()))
+        .bind(user.role_id().map(|r| r.as_str()))
+        .bind(mfa_to_json(&user)?)
         .bind(user.created_at())
         .bind(user.updated_at())
         .bind(user.last_login_at())
@@ -109,7 +111,7 @@ impl UserRepository for PostgresUserRepository {
             r#"
             UPDATE users
             SET username = $2, password_hash = $3, status = $4, team_id = $5,
-                team_role = $6, updated_at = $7, last_login_at = $8
+                team_role = $6, role_id = $7, mfa = $8, updated_at = $9, last_login_at = $10
             WHERE id = $1
             "#,
         )
@@ -119,6 +121,8 @@ impl UserRepository for PostgresUserRepository {
         .bind(status_to_str(user.status()))
         .bind(user.team_id().as_str())
         .bind(role_to_str(user.team_role()))
+        .bind(user.role_id().map(|r| r.as_str()))
+        .bind(mfa_to_json(user)?)
         .bind(user.updated_at())
         .bind(user.last_login_at())
         .execute(&self.pool)
@@ -161,7 +165,7 @@ impl UserRepository for PostgresUserRepository {
             Some(s) => {
                 sqlx::query(
                     r#"
-                    SELECT id, username, password_hash, status, team_id, team_role,
+                    SELECT id, username, password_hash, status, team_id, team_role, role_id, mfa,
                            created_at, updated_at, last_login_at
                     FROM users
                     WHERE status = $1
@@ -175,7 +179,7 @@ impl UserRepository for PostgresUserRepository {
             None => {
                 sqlx::query(
                     r#"
-                    SELECT id, username, password_hash, status, team_id, team_role,
+                    SELECT id, username, password_hash, status, team_id, team_role, role_id, mfa,
                            created_at, updated_at, last_login_at
                     FROM users
                     ORDER BY created_at
@@ -242,6 +246,8 @@ fn row_to_user(row: &sqlx::postgres::PgRow) -> Result<User, DomainError> {
     let status: String = row.get("status");
     let team_id: String = row.get("team_id");
     let team_role: String = row.get("team_role");
+    let role_id: Option<String> = row.get("role_id");
+    let mfa: Option<serde_json::Value> = row.get("mfa");
     let created_at: chrono::DateTime<chrono::Utc> = row.get("created_at");
     let updated_at: chrono::DateTime<chrono::Utc> = row.get("updated_at");
     let last_login_at: Option<chrono::DateTime<chrono::Utc>> = row.get("last_login_at");
@@ -274,6 +280,15 @@ fn row_to_user(row: &sqlx::postgres::PgRow) -> Result<User, DomainError> {
     user_json["updated_at"] = serde_json::to_value(updated_at)
         .map_err(|e| DomainError::storage(format!("Failed to serialize updated_at: {}", e)))?;
 
+    if let Some(role_id) = role_id {
+        user_json["role_id"] = serde_json::Value::String(role_id);
+    }
+
+    // mfa is skipped during serialization as it holds the TOTP secret
+    if let Some(mfa) = mfa {
+        user_json["mfa"] = mfa;
+    }
+
     if let Some(login_at) = last_login_at {
         user_json["last_login_at"] = serde_json::to_value(login_at)
             .map_err(|e| DomainError::storage(format!("Failed to serialize last_login_at: {}", e)))?;
@@ -283,6 +298,13 @@ fn row_to_user(row: &sqlx::postgres::PgRow) -> Result<User, DomainError> {
         .map_err(|e| DomainError::storage(format!("Failed to deserialize user: {}", e)))
 }
 
+fn mfa_to_json(user: &User) -> Result<Option<serde_json::Value>, DomainError> {
+    user.mfa()
+        .map(serde_json::to_value)
+        .transpose()
+        .map_err(|e| DomainError::storage(format!("Failed to serialize MFA state: {}", e)))
+}
+
 fn status_to_str(status: UserStatus) -> &'static str {
     match status {
         UserStatus::Active => "active",
@@ -2,13 +2,18 @@
 
 use std::sync::Arc;
 
+use crate::domain::role::RoleId;
 use crate::domain::team::{TeamId, TeamRole};
 use crate::domain::user::{
-    validate_password, validate_username, User, UserId, UserRepository, UserStatus,
+    validate_password, validate_username, User, UserId, UserMfa, UserRepository, UserStatus,
 };
 use crate::domain::DomainError;
 
 use super::password::PasswordHasher;
+use super::totp::{generate_recovery_codes, hash_recovery_code, Totp};
+
+/// Number of recovery codes issued when MFA is enabled
+const RECOVERY_CODE_COUNT: usize = 10;
 
 /// Request for creating a new user
 #[derive(Debug, Clone)]
@@ -27,17 +32,48 @@ pub struct UpdatePasswordRequest {
     pub new_password: String,
 }
 
+/// Pending MFA enrollment details returned to the user
+#[derive(Debug, Clone)]
+pub struct MfaEnrollment {
+    /// Base32-encoded TOTP secret
+    pub secret: String,
+    /// `otpauth://` URI for QR code provisioning
+    pub provisioning_uri: String,
+}
+
+/// Outcome of a login attempt
+#[derive(Debug, Clone)]
+pub enum LoginOutcome {
+    /// Credentials (and second factor, if enabled) are valid
+    Authenticated(Box<User>),
+    /// Password is valid but a TOTP or recovery code is required
+    MfaRequired,
+    /// Invalid credentials, invalid second factor or inactive user
+    Rejected,
+}
+
 /// User service for authentication and management
 #[derive(Debug)]
 pub struct UserService<R: UserRepository, H: PasswordHasher> {
     repository: Arc<R>,
     hasher: Arc<H>,
+    totp: Totp,
 }
 
 impl<R: UserRepository, H: PasswordHasher> UserService<R, H> {
     /// Create a new user service
     pub fn new(repository: Arc<R>, hasher: Arc<H>) -> Self {
-        Self { repository, hasher }
+        Self {
+            repository,
+            hasher,
+            totp: Totp::default(),
+        }
+    }
+
+    /// Use a custom TOTP configuration
+    pub fn with_totp(mut self, totp: Totp) -> Self {
+        self.totp = totp;
+        self
     }
 
     /// Create a new user
@@ -80,27 +116,191 @@ impl<R: UserRepository, H: PasswordHasher> UserService<R, H> {
         username: &str,
         password: &str,
     ) -> Result<Option<User>, DomainError> {
+        match self.login(username, password, None).await? {
+            LoginOutcome::Authenticated(user) => Ok(Some(*user)),
+            LoginOutcome::MfaRequired | LoginOutcome::Rejected => Ok(None),
+        }
+    }
+
+    /// Authenticate a user with username, password and, if MFA is enabled,
+    /// a TOTP or recovery code
+    pub async fn login(
+        &self,
+        username: &str,
+        password: &str,
+        mfa_code: Option<&str>,
+    ) -> Result<LoginOutcome, DomainError> {
         // Look up user by username
         let user = match self.repository.get_by_username(username).await? {
             Some(u) => u,
-            None => return Ok(None),
+            None => return Ok(LoginOutcome::Rejected),
         };
 
         // Check if user is active
         if !user.is_active() {
-            return Ok(None);
+            return Ok(LoginOutcome::Rejected);
         }
 
         // Verify password
         if !self.hasher.verify(password, user.password_hash()) {
-            return Ok(None);
+            return Ok(LoginOutcome::Rejected);
+        }
+
+        let user_id = user.id().clone();
+
+        // Verify second factor
+        if user.is_mfa_enabled() {
+            let Some(code) = mfa_code else {
+                return Ok(LoginOutcome::MfaRequired);
+            };
+
+            if !self.check_mfa_code(user, code).await? {
+                return Ok(LoginOutcome::Rejected);
+            }
         }
 
         // Record login
-        self.repository.record_login(user.id()).await?;
+        self.repository.record_login(&user_id).await?;
 
         // Re-fetch user to get updated last_login_at
-        self.repository.get(user.id()).await
+        match self.repository.get(&user_id).await? {
+            Some(user) => Ok(LoginOutcome::Authenticated(Box::new(user))),
+            None => Ok(LoginOutcome::Rejected),
+        }
+    }
+
+    /// Verify a TOTP or recovery code for a user with MFA enabled, persisting
+    /// the consumed time step or recovery code
+    async fn check_mfa_code(&self, mut user: User, code: &str) -> Result<bool, DomainError> {
+        let Some(mut mfa) = user.mfa().cloned() else {
+            return Ok(false);
+        };
+
+        let accepted = match self.totp.verify(mfa.secret(), code, unix_now()) {
+            Some(step) => mfa.accept_step(step),
+            None => mfa.consume_recovery_code(&hash_recovery_code(code)),
+        };
+
+        if accepted {
+            user.set_mfa(Some(mfa));
+            self.repository.update(&user).await?;
+        }
+
+        Ok(accepted)
+    }
+
+    async fn get_existing(&self, id: &str) -> Result<User, DomainError> {
+        let user_id = UserId::new(id).map_err(|e| DomainError::invalid_id(e.to_string()))?;
+
+        self.repository
+            .get(&user_id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("User '{}' not found", id)))
+    }
+
+    /// Start TOTP enrollment, replacing any unconfirmed enrollment
+    pub async fn begin_mfa_enrollment(&self, id: &str) -> Result<MfaEnrollment, DomainError> {
+        let mut user = self.get_existing(id).await?;
+
+        if user.is_mfa_enabled() {
+            return Err(DomainError::conflict("MFA is already enabled"));
+        }
+
+        let secret = self.totp.generate_secret();
+        let provisioning_uri = self.totp.provisioning_uri(user.username(), &secret);
+
+        user.set_mfa(Some(UserMfa::pending(&secret)));
+        self.repository.update(&user).await?;
+
+        Ok(MfaEnrollment {
+            secret,
+            provisioning_uri,
+        })
+    }
+
+    /// Confirm TOTP enrollment with a valid code. Returns the recovery codes,
+    /// which are only shown once.
+    pub async fn confirm_mfa_enrollment(
+        &self,
+        id: &str,
+        code: &str,
+    ) -> Result<Vec<String>, DomainError> {
+        let mut user = self.get_existing(id).await?;
+
+        let mut mfa = match user.mfa() {
+            Some(mfa) if !mfa.is_enabled() => mfa.clone(),
+            Some(_) => return Err(DomainError::conflict("MFA is already enabled")),
+            None => return Err(DomainError::validation("MFA enrollment has not been started")),
+        };
+
+        let step = self
+            .totp
+            .verify(mfa.secret(), code, unix_now())
+            .ok_or_else(|| DomainError::validation("Invalid MFA code"))?;
+        mfa.accept_step(step);
+
+        let codes = generate_recovery_codes(RECOVERY_CODE_COUNT);
+        mfa.enable(codes.iter().map(|c| hash_recovery_code(c)).collect());
+
+        user.set_mfa(Some(mfa));
+        self.repository.update(&user).await?;
+
+        Ok(codes)
+    }
+
+    /// Issue a new set of recovery codes, invalidating the previous ones
+    pub async fn regenerate_recovery_codes(
+        &self,
+        id: &str,
+        code: &str,
+    ) -> Result<Vec<String>, DomainError> {
+        let user = self.get_existing(id).await?;
+
+        if !user.is_mfa_enabled() {
+            return Err(DomainError::validation("MFA is not enabled"));
+        }
+
+        if !self.check_mfa_code(user, code).await? {
+            return Err(DomainError::validation("Invalid MFA code"));
+        }
+
+        let mut user = self.get_existing(id).await?;
+        let mut mfa = user
+            .mfa()
+            .cloned()
+            .ok_or_else(|| DomainError::internal("MFA state missing"))?;
+
+        let codes = generate_recovery_codes(RECOVERY_CODE_COUNT);
+        mfa.set_recovery_code_hashes(codes.iter().map(|c| hash_recovery_code(c)).collect());
+
+        user.set_mfa(Some(mfa));
+        self.repository.update(&user).await?;
+
+        Ok(codes)
+    }
+
+    /// Disable MFA after verifying a TOTP or recovery code
+    pub async fn disable_mfa(&self, id: &str, code: &str) -> Result<User, DomainError> {
+        let user = self.get_existing(id).await?;
+
+        if !user.is_mfa_enabled() {
+            return Err(DomainError::validation("MFA is not enabled"));
+        }
+
+        if !self.check_mfa_code(user, code).await? {
+            return Err(DomainError::validation("Invalid MFA code"));
+        }
+
+        self.reset_mfa(id).await
+    }
+
+    /// Remove MFA without verification (admin-forced reset)
+    pub async fn reset_mfa(&self, id: &str) -> Result<User, DomainError> {
+        let mut user = self.get_existing(id).await?;
+
+        user.set_mfa(None);
+
+        self.repository.update(&user).await
     }
 
     /// Get a user by ID
@@ -185,6 +385,21 @@ impl<R: UserRepository, H: PasswordHasher> UserService<R, H> {
         self.repository.update(&user).await
     }
 
+    /// Assign an admin API role to a user (None restores the team role default)
+    pub async fn set_role(&self, id: &str, role_id: Option<RoleId>) -> Result<User, DomainError> {
+        let user_id = UserId::new(id).map_err(|e| DomainError::invalid_id(e.to_string()))?;
+
+        let mut user = self
+            .repository
+            .get(&user_id)
+            .await?
+            .ok_or_else(|| DomainError::not_found(format!("User '{}' not found", id)))?;
+
+        user.set_role_id(role_id);
+
+        self.repository.update(&user).await
+    }
+
     /// Delete a user
     pub async fn delete(&self, id: &str) -> Result<bool, DomainError> {
         let user_id = UserId::new(id).map_err(|e| DomainError::invalid_id(e.to_string()))?;
@@ -192,6 +407,10 @@ impl<R: UserRepository, H: PasswordHasher> UserService<R, H> {
     }
 }
 
+fn unix_now() -> u64 {
+    chrono::Utc::now().timestamp().max(0) as u64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -426,4 +645,201 @@ mod tests {
         let user = service.get("user-1").await.unwrap();
         assert!(user.is_none());
     }
+
+    #[tokio::test]
+    async fn test_set_role() {
+        let service = create_service();
+
+        service
+            .create(make_request("user-1", "testuser", "secure_password123"))
+            .await
+            .unwrap();
+
+        let user = service
+            .set_role("user-1", Some(RoleId::new("viewer").unwrap()))
+            .await
+            .unwrap();
+        assert_eq!(user.role_id().map(|r| r.as_str()), Some("viewer"));
+
+        let user = service.set_role("user-1", None).await.unwrap();
+        assert!(user.role_id().is_none());
+
+        assert!(service.set_role("missing", None).await.is_err());
+    }
+
+    fn code_at_offset(secret: &str, offset: i64) -> String {
+        let totp = Totp::default();
+        let step = (totp.step_at(unix_now()) as i64 + offset) as u64;
+        totp.code_at_step(secret, step).unwrap()
+    }
+
+    async fn create_mfa_user(
+        service: &UserService<InMemoryUserRepository, Argon2Hasher>,
+    ) -> (String, Vec<String>) {
+        service
+            .create(make_request("user-1", "testuser", "secure_password123"))
+            .await
+            .unwrap();
+
+        let enrollment = service.begin_mfa_enrollment("user-1").await.unwrap();
+        assert!(enrollment.provisioning_uri.starts_with("otpauth://totp/"));
+
+        let codes = service
+            .confirm_mfa_enrollment("user-1", &code_at_offset(&enrollment.secret, 0))
+            .await
+            .unwrap();
+
+        (enrollment.secret, codes)
+    }
+
+    #[tokio::test]
+    async fn test_mfa_enrollment() {
+        let service = create_service();
+        let (_, codes) = create_mfa_user(&service).await;
+
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+
+        let user = service.get("user-1").await.unwrap().unwrap();
+        assert!(user.is_mfa_enabled());
+        assert!(service.begin_mfa_enrollment("user-1").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mfa_confirm_invalid_code() {
+        let service = create_service();
+
+        service
+            .create(make_request("user-1", "testuser", "secure_password123"))
+            .await
+            .unwrap();
+
+        assert!(service.confirm_mfa_enrollment("user-1", "000000").await.is_err());
+
+        service.begin_mfa_enrollment("user-1").await.unwrap();
+        assert!(service.confirm_mfa_enrollment("user-1", "abc").await.is_err());
+
+        let user = service.get("user-1").await.unwrap().unwrap();
+        assert!(!user.is_mfa_enabled());
+    }
+
+    #[tokio::test]
+    async fn test_login_requires_mfa() {
+        let service = create_service();
+        let (secret, _) = create_mfa_user(&service).await;
+
+        let outcome = service
+            .login("testuser", "secure_password123", None)
+            .await
+            .unwrap();
+        assert!(matches!(outcome, LoginOutcome::MfaRequired));
+
+        // Password-only authentication never bypasses MFA
+        let user = service
+            .authenticate("testuser", "secure_password123")
+            .await
+            .unwrap();
+        assert!(user.is_none());
+
+        let outcome = service
+            .login("testuser", "secure_password123", Some("000000"))
+            .await
+            .unwrap();
+        assert!(matches!(outcome, LoginOutcome::Rejected));
+
+        let code = code_at_offset(&secret, 1);
+        let outcome = service
+            .login("testuser", "secure_password123", Some(&code))
+            .await
+            .unwrap();
+        assert!(matches!(outcome, LoginOutcome::Authenticated(_)));
+
+        // The same code cannot be replayed
+        let outcome = service
+            .login("testuser", "secure_password123", Some(&code))
+            .await
+            .unwrap();
+        assert!(matches!(outcome, LoginOutcome::Rejected));
+    }
+
+    #[tokio::test]
+    async fn test_login_wrong_password_with_mfa() {
+        let service = create_service();
+        let (secret, _) = create_mfa_user(&service).await;
+
+        let outcome = service
+            .login("testuser", "wrong_password", Some(&code_at_offset(&secret, 1)))
+            .await
+            .unwrap();
+        assert!(matches!(outcome, LoginOutcome::Rejected));
+    }
+
+    #[tokio::test]
+    async fn test_login_with_recovery_code() {
+        let service = create_service();
+        let (_, codes) = create_mfa_user(&service).await;
+
+        let outcome = service
+            .login("testuser", "secure_password123", Some(&codes[0]))
+            .await
+            .unwrap();
+        assert!(matches!(outcome, LoginOutcome::Authenticated(_)));
+
+        let outcome = service
+            .login("testuser", "secure_password123", Some(&codes[0]))
+            .await
+            .unwrap();
+        assert!(matches!(outcome, LoginOutcome::Rejected));
+
+        let user = service.get("user-1").await.unwrap().unwrap();
+        assert_eq!(
+            user.mfa().unwrap().recovery_codes_remaining(),
+            RECOVERY_CODE_COUNT - 1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_regenerate_recovery_codes() {
+        let service = create_service();
+        let (_, codes) = create_mfa_user(&service).await;
+
+        let new_codes = service
+            .regenerate_recovery_codes("user-1", &codes[0])
+            .await
+            .unwrap();
+        assert_eq!(new_codes.len(), RECOVERY_CODE_COUNT);
+
+        // Old codes are invalidated
+        let outcome = service
+            .login("testuser", "secure_password123", Some(&codes[1]))
+            .await
+            .unwrap();
+        assert!(matches!(outcome, LoginOutcome::Rejected));
+    }
+
+    #[tokio::test]
+    async fn test_disable_mfa() {
+        let service = create_service();
+        let (_, codes) = create_mfa_user(&service).await;
+
+        assert!(service.disable_mfa("user-1", "000000").await.is_err());
+
+        let user = service.disable_mfa("user-1", &codes[0]).await.unwrap();
+        assert!(!user.is_mfa_enabled());
+        assert!(user.mfa().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reset_mfa() {
+        let service = create_service();
+        create_mfa_user(&service).await;
+
+        let user = service.reset_mfa("user-1").await.unwrap();
+        assert!(!user.is_mfa_enabled());
+
+        let user = service
+            .authenticate("testuser", "secure_password123")
+            .await
+            .unwrap();
+        assert!(user.is_some());
+    }
 }
@@ -0,0 +1,283 @@
+//! TOTP (RFC 6238) code generation and verification
+//!
+//! Uses HMAC-SHA1 with 6-digit codes and a 30-second period, the parameters
+//! supported by common authenticator apps.
+
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+type HmacSha1 = Hmac<Sha1>;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const RECOVERY_CODE_ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+
+/// TOTP generator and verifier
+#[derive(Debug, Clone)]
+pub struct Totp {
+    /// Issuer shown in authenticator apps
+    issuer: String,
+    /// Number of digits in a code
+    digits: u32,
+    /// Time step in seconds
+    period: u64,
+    /// Number of adjacent steps accepted to tolerate clock drift
+    skew: u64,
+}
+
+impl Default for Totp {
+    fn default() -> Self {
+        Self::new("PMP LLM Gateway")
+    }
+}
+
+impl Totp {
+    /// Create a new TOTP helper with the given issuer
+    pub fn new(issuer: impl Into<String>) -> Self {
+        Self {
+            issuer: issuer.into(),
+            digits: 6,
+            period: 30,
+            skew: 1,
+        }
+    }
+
+    /// Generate a new random base32-encoded secret (160 bits)
+    pub fn generate_secret(&self) -> String {
+        let mut bytes = [0u8; 20];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        base32_encode(&bytes)
+    }
+
+    /// Build an `otpauth://` provisioning URI for QR codes
+    pub fn provisioning_uri(&self, account: &str, secret: &str) -> String {
+        let issuer = percent_encode(&self.issuer);
+        let account = percent_encode(account);
+
+        format!(
+            "otpauth://totp/{issuer}:{account}?secret={secret}&issuer={issuer}&algorithm=SHA1&digits={}&period={}",
+            self.digits, self.period
+        )
+    }
+
+    /// Time step for a Unix timestamp
+    pub fn step_at(&self, unix_time: u64) -> u64 {
+        unix_time / self.period
+    }
+
+    /// Compute the code for a time step
+    pub fn code_at_step(&self, secret: &str, step: u64) -> Option<String> {
+        let key = base32_decode(secret)?;
+
+        let mut mac = HmacSha1::new_from_slice(&key).expect("HMAC can take key of any size");
+        mac.update(&step.to_be_bytes());
+        let digest = mac.finalize().into_bytes();
+
+        // Dynamic truncation (RFC 4226 section 5.3)
+        let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([
+            digest[offset] & 0x7f,
+            digest[offset + 1],
+            digest[offset + 2],
+            digest[offset + 3],
+        ]);
+
+        let code = binary % 10u32.pow(self.digits);
+        Some(format!("{:0width$}", code, width = self.digits as usize))
+    }
+
+    /// Verify a code at a Unix timestamp, returning the matching time step
+    pub fn verify(&self, secret: &str, code: &str, unix_time: u64) -> Option<u64> {
+        let code = code.trim();
+
+        if code.len() != self.digits as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        let current = self.step_at(unix_time);
+        let first = current.saturating_sub(self.skew);
+
+        (first..=current + self.skew).find(|step| {
+            self.code_at_step(secret, *step)
+                .is_some_and(|expected| constant_time_eq(expected.as_bytes(), code.as_bytes()))
+        })
+    }
+}
+
+/// Generate one-time recovery codes in `xxxxx-xxxxx` format
+pub fn generate_recovery_codes(count: usize) -> Vec<String> {
+    let mut rng = rand::thread_rng();
+
+    (0..count)
+        .map(|_| {
+            let chars: String = (0..10)
+                .map(|_| {
+                    let index = (rng.next_u32() as usize) % RECOVERY_CODE_ALPHABET.len();
+                    RECOVERY_CODE_ALPHABET[index] as char
+                })
+                .collect();
+            format!("{}-{}", &chars[..5], &chars[5..])
+        })
+        .collect()
+}
+
+/// Hash a recovery code for storage; ignores case, whitespace and dashes
+pub fn hash_recovery_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+
+    hex::encode(Sha256::digest(normalized.as_bytes()))
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn base32_encode(data: &[u8]) -> String {
+    let mut output = String::with_capacity(data.len().div_ceil(5) * 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for &byte in data {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+
+        while bits >= 5 {
+            bits -= 5;
+            output.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+
+    if bits > 0 {
+        output.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+
+    output
+}
+
+fn base32_decode(input: &str) -> Option<Vec<u8>> {
+    let mut output = Vec::with_capacity(input.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in input.chars().filter(|c| *c != '=' && !c.is_whitespace()) {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&a| a as char == c.to_ascii_uppercase())? as u32;
+
+        buffer = (buffer << 5) | value;
+        bits += 5;
+
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 6238 test secret "12345678901234567890" in base32
+    const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn test_base32_roundtrip() {
+        let data = b"12345678901234567890";
+        let encoded = base32_encode(data);
+
+        assert_eq!(encoded, RFC_SECRET);
+        assert_eq!(base32_decode(&encoded).unwrap(), data);
+        assert_eq!(base32_decode("gezd gnbv").unwrap(), b"12345");
+        assert!(base32_decode("invalid!").is_none());
+    }
+
+    #[test]
+    fn test_rfc6238_vectors() {
+        let totp = Totp::default();
+
+        // RFC 6238 appendix B, SHA1, truncated to 6 digits
+        let vectors = [
+            (59, "287082"),
+            (1111111109, "081804"),
+            (1111111111, "050471"),
+            (1234567890, "005924"),
+            (2000000000, "279037"),
+        ];
+
+        for (time, expected) in vectors {
+            let step = totp.step_at(time);
+            assert_eq!(totp.code_at_step(RFC_SECRET, step).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_verify_with_skew() {
+        let totp = Totp::default();
+        let time = 1111111111;
+        let step = totp.step_at(time);
+
+        let previous = totp.code_at_step(RFC_SECRET, step - 1).unwrap();
+        let current = totp.code_at_step(RFC_SECRET, step).unwrap();
+        let far = totp.code_at_step(RFC_SECRET, step + 5).unwrap();
+
+        assert_eq!(totp.verify(RFC_SECRET, &current, time), Some(step));
+        assert_eq!(totp.verify(RFC_SECRET, &previous, time), Some(step - 1));
+        assert!(totp.verify(RFC_SECRET, &far, time).is_none());
+        assert!(totp.verify(RFC_SECRET, "12345", time).is_none());
+        assert!(totp.verify(RFC_SECRET, "abcdef", time).is_none());
+    }
+
+    #[test]
+    fn test_generate_secret() {
+        let totp = Totp::default();
+        let secret = totp.generate_secret();
+
+        assert_eq!(secret.len(), 32);
+        assert_eq!(base32_decode(&secret).unwrap().len(), 20);
+        assert_ne!(secret, totp.generate_secret());
+    }
+
+    #[test]
+    fn test_provisioning_uri() {
+        let totp = Totp::new("LLM Gateway");
+        let uri = totp.provisioning_uri("alice@example.com", "ABC");
+
+        assert_eq!(
+            uri,
+            "otpauth://totp/LLM%20Gateway:alice%40example.com?secret=ABC&issuer=LLM%20Gateway&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+
+    #[test]
+    fn test_recovery_codes() {
+        let codes = generate_recovery_codes(10);
+
+        assert_eq!(codes.len(), 10);
+        assert!(codes.iter().all(|c| c.len() == 11 && c.as_bytes()[5] == b'-'));
+        assert_eq!(
+            hash_recovery_code(&codes[0]),
+            hash_recovery_code(&codes[0].to_uppercase().replace('-', ""))
+        );
+        assert_ne!(hash_recovery_code(&codes[0]), hash_recovery_code(&codes[1]));
+    }
+}
@@ -2,8 +2,13 @@
 
 mod in_memory;
 mod service;
+mod signature;
 mod storage_repository;
 
 pub use in_memory::{InMemoryWebhookDeliveryRepository, InMemoryWebhookRepository};
-pub use service::{WebhookService, WebhookServiceTrait};
+pub use service::{spawn_webhook_retries, WebhookService, WebhookServiceTrait};
+pub use signature::{
+    generate_secret, sign_payload, verify_signature, SIGNATURE_HEADER, SIGNATURE_TOLERANCE_SECS,
+    WEBHOOK_SECRET_PREFIX,
+};
 pub use storage_repository::{StorageWebhookDeliveryRepository, StorageWebhookRepository};
@@ -1,7 +1,8 @@
 //! Webhook service for sending HTTP callbacks
 
+use crate::domain::event::GatewayEvent;
 use crate::domain::{
-    DeliveryStatus, DomainError, Webhook, WebhookDelivery, WebhookDeliveryId,
+    validate_payload_template, DeliveryStatus, DomainError, Webhook, WebhookDelivery, WebhookDeliveryId,
     WebhookDeliveryRepository, WebhookEvent, WebhookId, WebhookRepository, WebhookStatus,
 };
 use async_trait::async_trait;
@@ -15,6 +16,11 @@ use tracing::{info, warn};
 
 type HmacSha256 = Hmac<Sha256>;
 
+use super::signature::{generate_secret, sign_payload, SIGNATURE_HEADER};
+use crate::infrastructure::background::spawn_tracked;
+use crate::infrastructure::event::EventBus;
+use crate::infrastructure::leader::Leadership;
+use crate::infrastructure::observability::{current_traceparent, TRACEPARENT_HEADER};
 use crate::infrastructure::usage::AlertNotification;
 
 /// Trait for webhook service operations
@@ -41,6 +47,13 @@ pub trait WebhookServiceTrait: Send + Sync {
     /// Retries failed deliveries
     async fn retry_failed_deliveries(&self) -> Result<u32, DomainError>;
 
+    /// Sends the payload of a delivery again as a new delivery
+    async fn redeliver(
+        &self,
+        webhook_id: &str,
+        delivery_id: &str,
+    ) -> Result<WebhookDelivery, DomainError>;
+
     /// Gets delivery history for a webhook
     async fn get_deliveries(
         &self,
@@ -76,6 +89,7 @@ pub struct WebhookService<W: WebhookRepository, D: WebhookDeliveryRepository> {
     webhook_repo: Arc<W>,
     delivery_repo: Arc<D>,
     http_client: Client,
+    events: Option<Arc<EventBus>>,
 }
 
 impl<W: WebhookRepository, D: WebhookDeliveryRepository> WebhookService<W, D> {
@@ -90,9 +104,16 @@ impl<W: WebhookRepository, D: WebhookDeliveryRepository> WebhookService<W, D> {
             webhook_repo,
             delivery_repo,
             http_client,
+            events: None,
         }
     }
 
+    /// Publish failed delivery attempts as `webhook_failed` events
+    pub fn with_events(mut self, events: Arc<EventBus>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
     /// Generates HMAC-SHA256 signature for a payload
     fn generate_signature(secret: &str, payload: &str) -> String {
         let mut mac =
@@ -119,10 +140,20 @@ impl<W: WebhookRepository, D: WebhookDeliveryRepository> WebhookService<W, D> {
             .header("X-Webhook-Event", delivery.event_type.as_str())
             .header("X-Webhook-Delivery-Id", delivery.id.as_str());
 
-        // Add HMAC signature if secret is configured
+        // Add HMAC signatures if secret is configured; X-Webhook-Signature is
+        // kept for receivers verifying the untimestamped signature
         if let Some(ref secret) = webhook.secret {
             let signature = Self::generate_signature(secret, &payload);
-            request = request.header("X-Webhook-Signature", format!("sha256={}", signature));
+            request = request
+                .header("X-Webhook-Signature", format!("sha256={}", signature))
+                .header(
+                    SIGNATURE_HEADER,
+                    sign_payload(secret, Utc::now().timestamp(), &payload),
+                );
+        }
+
+        if let Some(ref traceparent) = delivery.traceparent {
+            request = request.header(TRACEPARENT_HEADER, traceparent);
         }
 
         // Add custom headers
@@ -161,6 +192,7 @@ impl<W: WebhookRepository, D: WebhookDeliveryRepository> WebhookService<W, D> {
                         status = status,
                         "Webhook delivery failed with HTTP error"
                     );
+                    self.publish_failure(delivery);
                 }
             }
             Err(e) => {
@@ -185,12 +217,30 @@ impl<W: WebhookRepository, D: WebhookDeliveryRepository> WebhookService<W, D> {
                     error = %error_msg,
                     "Webhook delivery failed"
                 );
+                self.publish_failure(delivery);
             }
         }
 
         Ok(())
     }
 
+    /// Validates the filter and payload template of a webhook
+    fn validate_delivery_options(webhook: &Webhook) -> Result<(), DomainError> {
+        webhook.filter.validate()?;
+
+        if let Some(template) = &webhook.payload_template {
+            validate_payload_template(template)?;
+        }
+
+        Ok(())
+    }
+
+    fn publish_failure(&self, delivery: &WebhookDelivery) {
+        if let Some(events) = &self.events {
+            events.spawn_publish(GatewayEvent::webhook_failed(delivery));
+        }
+    }
+
     /// Updates webhook failure tracking based on delivery result
     async fn update_webhook_status(
         &self,
@@ -213,7 +263,7 @@ impl<W: WebhookRepository, D: WebhookDeliveryRepository> WebhookService<W, D> {
 impl<W: WebhookRepository, D: WebhookDeliveryRepository> WebhookServiceTrait
     for WebhookService<W, D>
 {
-    async fn create(&self, webhook: Webhook) -> Result<Webhook, DomainError> {
+    async fn create(&self, mut webhook: Webhook) -> Result<Webhook, DomainError> {
         // Validate URL
         if webhook.url.is_empty() {
             return Err(DomainError::validation("URL is required"));
@@ -232,6 +282,12 @@ impl<W: WebhookRepository, D: WebhookDeliveryRepository> WebhookServiceTrait
             ));
         }
 
+        Self::validate_delivery_options(&webhook)?;
+
+        if webhook.secret.as_deref().is_none_or(str::is_empty) {
+            webhook.secret = Some(generate_secret());
+        }
+
         self.webhook_repo.create(webhook).await
     }
 
@@ -258,6 +314,8 @@ impl<W: WebhookRepository, D: WebhookDeliveryRepository> WebhookServiceTrait
             ));
         }
 
+        Self::validate_delivery_options(&webhook)?;
+
         self.webhook_repo.update(webhook).await
     }
 
@@ -280,7 +338,10 @@ impl<W: WebhookRepository, D: WebhookDeliveryRepository> WebhookServiceTrait
         let webhooks = self
             .webhook_repo
             .find_active_by_event(event.event_type)
-            .await?;
+            .await?
+            .into_iter()
+            .filter(|webhook| webhook.filter.matches(&event))
+            .collect::<Vec<_>>();
 
         if webhooks.is_empty() {
             return Ok(vec![]);
@@ -294,8 +355,9 @@ impl<W: WebhookRepository, D: WebhookDeliveryRepository> WebhookServiceTrait
                 delivery_id.clone(),
                 webhook.id.clone(),
                 event.event_type,
-                serde_json::to_value(&event).unwrap_or_default(),
+                webhook.payload_for(&event),
             );
+            delivery.traceparent = current_traceparent();
 
             // Create delivery record first
             self.delivery_repo.create(delivery.clone()).await?;
@@ -348,6 +410,45 @@ impl<W: WebhookRepository, D: WebhookDeliveryRepository> WebhookServiceTrait
         Ok(retried)
     }
 
+    async fn redeliver(
+        &self,
+        webhook_id: &str,
+        delivery_id: &str,
+    ) -> Result<WebhookDelivery, DomainError> {
+        let webhook = WebhookServiceTrait::get(self, webhook_id).await?;
+        let original = self
+            .delivery_repo
+            .find_by_id(&WebhookDeliveryId::new(delivery_id))
+            .await?
+            .filter(|d| d.webhook_id == webhook.id)
+            .ok_or_else(|| {
+                DomainError::not_found(format!(
+                    "Delivery '{}' not found for webhook '{}'",
+                    delivery_id, webhook_id
+                ))
+            })?;
+
+        let mut delivery =
+            original.redelivery(WebhookDeliveryId::new(uuid::Uuid::new_v4().to_string()));
+        delivery.traceparent = current_traceparent();
+        self.delivery_repo.create(delivery.clone()).await?;
+
+        self.send_delivery(&webhook, &mut delivery).await?;
+        self.delivery_repo.update(delivery.clone()).await?;
+
+        let success = delivery.status == DeliveryStatus::Success;
+        self.update_webhook_status(&webhook.id, success).await?;
+
+        info!(
+            delivery_id = %delivery.id,
+            redelivery_of = %original.id,
+            webhook_id = %webhook.id,
+            "Webhook delivery replayed"
+        );
+
+        Ok(delivery)
+    }
+
     async fn get_deliveries(
         &self,
         webhook_id: &str,
@@ -446,11 +547,40 @@ impl<W: WebhookRepository, D: WebhookDeliveryRepository> WebhookServiceTrait
     }
 }
 
+/// Retry failed deliveries whose backoff elapsed every `interval`, on the
+/// leader replica only
+pub fn spawn_webhook_retries(
+    service: Arc<dyn WebhookServiceTrait>,
+    interval: Duration,
+    leadership: Leadership,
+) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+
+        loop {
+            ticker.tick().await;
+
+            if !leadership.is_leader() {
+                continue;
+            }
+
+            // Tracked so shutdown lets a round of deliveries finish
+            let service = service.clone();
+            let _ = spawn_tracked(async move {
+                if let Err(e) = service.retry_failed_deliveries().await {
+                    warn!(error = %e, "Failed to retry webhook deliveries");
+                }
+            })
+            .await;
+        }
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::infrastructure::webhook::{
-        InMemoryWebhookDeliveryRepository, InMemoryWebhookRepository,
+        InMemoryWebhookDeliveryRepository, InMemoryWebhookRepository, WEBHOOK_SECRET_PREFIX,
     };
 
     fn create_service(
@@ -472,6 +602,9 @@ mod tests {
 
         let found = service.get("hook-1").await.unwrap();
         assert_eq!(found.name, "Test Hook");
+        assert!(found
+            .secret
+            .is_some_and(|s| s.starts_with(WEBHOOK_SECRET_PREFIX)));
     }
 
     #[tokio::test]
@@ -503,6 +636,16 @@ mod tests {
             result,
             Err(DomainError::Validation { message: _ })
         ));
+
+        // Template that is not a JSON document
+        let webhook = Webhook::new("hook-1", "Test Hook", "https://example.com/webhook")
+            .with_event(crate::domain::WebhookEventType::BudgetAlert)
+            .with_payload_template(serde_json::json!("{{data}}"));
+        let result = service.create(webhook).await;
+        assert!(matches!(
+            result,
+            Err(DomainError::Validation { message: _ })
+        ));
     }
 
     #[tokio::test]
@@ -573,6 +716,83 @@ mod tests {
         assert_eq!(found.status, WebhookStatus::Active);
     }
 
+    #[tokio::test]
+    async fn test_redeliver_replays_payload_as_new_delivery() {
+        let deliveries = Arc::new(InMemoryWebhookDeliveryRepository::new());
+        let service = WebhookService::new(
+            Arc::new(InMemoryWebhookRepository::new()),
+            deliveries.clone(),
+        );
+        let webhook = Webhook::new("hook-1", "Test Hook", "http://127.0.0.1:1/webhook")
+            .with_event(crate::domain::WebhookEventType::BudgetAlert);
+        service.create(webhook).await.unwrap();
+
+        let mut original = WebhookDelivery::new(
+            "del-1",
+            WebhookId::new("hook-1"),
+            crate::domain::WebhookEventType::BudgetAlert,
+            serde_json::json!({"budget_id": "budget-1"}),
+        );
+        original.status = DeliveryStatus::DeadLetter;
+        deliveries.create(original).await.unwrap();
+
+        let replayed = service.redeliver("hook-1", "del-1").await.unwrap();
+
+        assert_ne!(replayed.id.as_str(), "del-1");
+        assert_eq!(
+            replayed.redelivery_of,
+            Some(WebhookDeliveryId::new("del-1"))
+        );
+        assert_eq!(replayed.payload["budget_id"], "budget-1");
+        assert_eq!(replayed.attempts, 1);
+        assert!(deliveries.find_by_id(&replayed.id).await.unwrap().is_some());
+
+        assert!(matches!(
+            service.redeliver("hook-1", "del-404").await,
+            Err(DomainError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_send_event_filters_and_templates_payloads() {
+        let deliveries = Arc::new(InMemoryWebhookDeliveryRepository::new());
+        let service = WebhookService::new(
+            Arc::new(InMemoryWebhookRepository::new()),
+            deliveries.clone(),
+        );
+        let slack = Webhook::new("slack", "Slack", "http://127.0.0.1:1/slack")
+            .with_event(crate::domain::WebhookEventType::UsageAnomaly)
+            .with_filter(crate::domain::WebhookFilter::new().with_team_ids(vec!["team-1".into()]))
+            .with_payload_template(serde_json::json!({"text": "Anomaly in {{data.team_id}}"}));
+        let firehose = Webhook::new("firehose", "Firehose", "http://127.0.0.1:1/firehose")
+            .with_event(crate::domain::WebhookEventType::UsageAnomaly);
+        service.create(slack).await.unwrap();
+        service.create(firehose).await.unwrap();
+
+        let event = WebhookEvent::new(
+            crate::domain::WebhookEventType::UsageAnomaly,
+            serde_json::json!({"team_id": "team-2"}),
+        );
+        assert_eq!(service.send_event(event).await.unwrap().len(), 1);
+
+        let event = WebhookEvent::new(
+            crate::domain::WebhookEventType::UsageAnomaly,
+            serde_json::json!({"team_id": "team-1"}),
+        );
+        let ids = service.send_event(event).await.unwrap();
+        assert_eq!(ids.len(), 2);
+
+        let payloads = deliveries
+            .find_by_webhook(&WebhookId::new("slack"), 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(
+            payloads[0].payload,
+            serde_json::json!({"text": "Anomaly in team-1"})
+        );
+    }
+
     #[test]
     fn test_generate_signature() {
         let secret = "my-secret";
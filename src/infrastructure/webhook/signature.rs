@@ -0,0 +1,131 @@
+//! Webhook payload signing
+//!
+//! Deliveries of webhooks with a secret carry an `X-PMP-Signature` header of
+//! the form `t=<unix timestamp>,v1=<hex signature>`, where the signature is
+//! the HMAC-SHA256 of `"<timestamp>.<raw request body>"` keyed with the
+//! webhook secret. Receivers recompute the signature, compare it in constant
+//! time and reject timestamps older than [`SIGNATURE_TOLERANCE_SECS`] so a
+//! captured delivery cannot be replayed later.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Header carrying the timestamped payload signature
+pub const SIGNATURE_HEADER: &str = "X-PMP-Signature";
+/// Maximum age of a signature timestamp accepted by [`verify_signature`]
+pub const SIGNATURE_TOLERANCE_SECS: i64 = 300;
+/// Prefix of generated webhook secrets
+pub const WEBHOOK_SECRET_PREFIX: &str = "whsec_";
+
+/// Generate a random webhook signing secret
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+
+    format!("{}{}", WEBHOOK_SECRET_PREFIX, URL_SAFE_NO_PAD.encode(bytes))
+}
+
+fn payload_mac(secret: &str, timestamp: i64, payload: &str) -> HmacSha256 {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(payload.as_bytes());
+    mac
+}
+
+/// `X-PMP-Signature` header value of a payload sent at `timestamp`
+pub fn sign_payload(secret: &str, timestamp: i64, payload: &str) -> String {
+    let signature = payload_mac(secret, timestamp, payload)
+        .finalize()
+        .into_bytes();
+
+    format!("t={},v1={}", timestamp, hex::encode(signature))
+}
+
+/// Check an `X-PMP-Signature` header against a payload received at `now`
+pub fn verify_signature(secret: &str, header: &str, payload: &str, now: i64) -> bool {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+
+    let Some(timestamp) = timestamp else {
+        return false;
+    };
+
+    if (now - timestamp).abs() > SIGNATURE_TOLERANCE_SECS {
+        return false;
+    }
+
+    signatures.into_iter().any(|signature| {
+        hex::decode(signature).is_ok_and(|bytes| {
+            payload_mac(secret, timestamp, payload)
+                .verify_slice(&bytes)
+                .is_ok()
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify_payload() {
+        let payload = r#"{"event_type":"budget_alert"}"#;
+        let header = sign_payload("whsec_test", 1_700_000_000, payload);
+
+        assert!(header.starts_with("t=1700000000,v1="));
+        assert!(verify_signature(
+            "whsec_test",
+            &header,
+            payload,
+            1_700_000_100
+        ));
+
+        assert!(!verify_signature(
+            "whsec_other",
+            &header,
+            payload,
+            1_700_000_100
+        ));
+        assert!(!verify_signature(
+            "whsec_test",
+            &header,
+            "{}",
+            1_700_000_100
+        ));
+        assert!(!verify_signature(
+            "whsec_test",
+            &header,
+            payload,
+            1_700_000_301
+        ));
+        assert!(!verify_signature(
+            "whsec_test",
+            "v1=abc",
+            payload,
+            1_700_000_000
+        ));
+    }
+
+    #[test]
+    fn test_generate_secret() {
+        let secret = generate_secret();
+
+        assert!(secret.starts_with(WEBHOOK_SECRET_PREFIX));
+        assert_eq!(secret.len(), WEBHOOK_SECRET_PREFIX.len() + 43);
+        assert_ne!(secret, generate_secret());
+    }
+}
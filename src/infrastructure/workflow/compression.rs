@@ -0,0 +1,157 @@
+//! Contextual compression of knowledge base search results
+//!
+//! Results are split into sentences and only the sentences relevant to the
+//! query are kept, selected either by a model (extractive: the model answers
+//! with sentence indices, never with text) or by embedding similarity.
+
+use serde::Deserialize;
+
+pub(crate) const LLM_COMPRESSION_PROMPT: &str = "You filter search results for a question. \
+Every document is split into numbered sentences. For every document, select the sentences \
+needed to answer the question; leave out documents without any. Reply with JSON only: \
+{\"documents\": [{\"index\": <document index>, \"sentences\": [<sentence index>]}]}";
+
+/// Sentences of a text, split after sentence punctuation followed by
+/// whitespace and at line breaks
+pub(crate) fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        let end = match c {
+            '\n' => Some(index),
+            '.' | '!' | '?' => chars
+                .peek()
+                .filter(|(_, next)| next.is_whitespace())
+                .map(|_| index + c.len_utf8()),
+            _ => None,
+        };
+
+        if let Some(end) = end {
+            sentences.push(&text[start..end]);
+            start = end;
+        }
+    }
+    sentences.push(&text[start..]);
+
+    sentences
+        .into_iter()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Indices of the sentences kept by similarity to the query, in text order
+///
+/// Sentences below `threshold` are dropped; of the others at most
+/// `max_sentences` are kept, the most similar first.
+pub(crate) fn select_by_similarity(
+    similarities: &[f32],
+    threshold: f32,
+    max_sentences: Option<usize>,
+) -> Vec<usize> {
+    let mut kept: Vec<usize> = (0..similarities.len())
+        .filter(|&i| similarities[i] >= threshold)
+        .collect();
+
+    if let Some(max) = max_sentences {
+        kept.sort_by(|&a, &b| similarities[b].total_cmp(&similarities[a]));
+        kept.truncate(max);
+    }
+
+    kept.sort_unstable();
+    kept
+}
+
+/// User message of the extractive compression call
+pub(crate) fn selection_prompt(query: &str, documents: &[Vec<&str>]) -> String {
+    let mut prompt = format!("Question: {}\n\n", query);
+
+    for (index, sentences) in documents.iter().enumerate() {
+        prompt.push_str(&format!("<document index=\"{}\">\n", index));
+        for (sentence_index, sentence) in sentences.iter().enumerate() {
+            prompt.push_str(&format!("[{}] {}\n", sentence_index, sentence));
+        }
+        prompt.push_str("</document>\n");
+    }
+
+    prompt
+}
+
+#[derive(Deserialize)]
+struct SelectionReply {
+    documents: Vec<SelectedDocument>,
+}
+
+#[derive(Deserialize)]
+struct SelectedDocument {
+    index: usize,
+    #[serde(default)]
+    sentences: Vec<usize>,
+}
+
+/// Sentence indices selected per document from the extractive compression
+/// reply, `None` when the reply cannot be read
+///
+/// `sentence_counts` holds the sentence count of every document; documents
+/// the model left out get no sentences and out of range indices are ignored.
+pub(crate) fn parse_selection(reply: &str, sentence_counts: &[usize]) -> Option<Vec<Vec<usize>>> {
+    // Models sometimes wrap the JSON in a code fence
+    let json = match (reply.find('{'), reply.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &reply[start..=end],
+        _ => reply,
+    };
+    let reply: SelectionReply = serde_json::from_str(json).ok()?;
+
+    let mut selection = vec![Vec::new(); sentence_counts.len()];
+    for document in reply.documents {
+        let Some(selected) = selection.get_mut(document.index) else {
+            continue;
+        };
+        let count = sentence_counts[document.index];
+
+        for sentence in document.sentences {
+            if sentence < count && !selected.contains(&sentence) {
+                selected.push(sentence);
+            }
+        }
+        selected.sort_unstable();
+    }
+
+    Some(selection)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_sentences() {
+        let text = "Refunds take 5 days. Contact support!\nVersion 2.1 adds exports?  Yes";
+
+        assert_eq!(
+            split_sentences(text),
+            vec!["Refunds take 5 days.", "Contact support!", "Version 2.1 adds exports?", "Yes"]
+        );
+        assert!(split_sentences("  \n ").is_empty());
+    }
+
+    #[test]
+    fn test_select_by_similarity() {
+        let similarities = [0.9, 0.2, 0.6, 0.7];
+
+        assert_eq!(select_by_similarity(&similarities, 0.5, None), vec![0, 2, 3]);
+        assert_eq!(select_by_similarity(&similarities, 0.5, Some(2)), vec![0, 3]);
+        assert!(select_by_similarity(&similarities, 0.95, None).is_empty());
+    }
+
+    #[test]
+    fn test_parse_selection() {
+        let reply = "```json\n{\"documents\": [{\"index\": 1, \"sentences\": [2, 0, 0, 9]}, \
+                     {\"index\": 5, \"sentences\": [0]}]}\n```";
+
+        assert_eq!(parse_selection(reply, &[2, 3]), Some(vec![vec![], vec![0, 2]]));
+        assert_eq!(parse_selection("not json", &[1]), None);
+    }
+}
@@ -5,17 +5,33 @@ use std::time::Instant;
 
 use async_trait::async_trait;
 use serde_json::{json, Value};
-use tracing::debug;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, Instrument};
 
-use crate::domain::knowledge_base::{MetadataFilter, SearchParams, SearchResult};
-use crate::domain::llm::ProviderResolver;
+use crate::domain::knowledge_base::{
+    validate_namespace, KnowledgeBaseCaller, MetadataFilter, SearchParams, SearchResult,
+};
+use crate::domain::llm::{LlmResponseFormat, ProviderResolver, ResolvedModel};
 use crate::domain::storage::Storage;
 use crate::domain::{
-    ConditionalAction, HttpMethod, HttpRequestStep, LlmRequest, OnErrorAction, Prompt,
-    StepExecutionResult, Workflow, WorkflowContext, WorkflowError, WorkflowExecutor,
-    WorkflowResult, WorkflowStep, WorkflowStepType, WorkflowTokenUsage,
+    ConditionalAction, ContextCompression, HttpMethod, HttpRequestStep, LlmRequest,
+    OnErrorAction, Prompt, QueryTransform, StepExecutionResult, StepProgress, Workflow,
+    WorkflowContext, WorkflowError, WorkflowExecutor, WorkflowResult, WorkflowStep,
+    WorkflowStepType, WorkflowTokenUsage, MAX_QUERY_EXPANSIONS,
+};
+use crate::infrastructure::knowledge_base::{
+    KnowledgeBaseAccessTracker, KnowledgeBaseProviderRegistryTrait,
+};
+use crate::infrastructure::observability::{
+    crag_scoring_span, current_trace_headers, kb_search_span, provider_call_span, record_error,
+    record_result, record_token_usage, workflow_step_span,
+};
+use crate::infrastructure::services::{resolve_partials, EmbeddingConfig};
+
+use super::compression::{
+    parse_selection, select_by_similarity, selection_prompt, split_sentences,
+    LLM_COMPRESSION_PROMPT,
 };
-use crate::infrastructure::knowledge_base::KnowledgeBaseProviderRegistryTrait;
 
 /// Build XML representation of search results
 ///
@@ -44,6 +60,81 @@ fn escape_xml(s: &str) -> String {
         .replace('\'', "&apos;")
 }
 
+const QUERY_REWRITE_PROMPT: &str = "Rewrite the user's question into a concise, standalone \
+search query for a document search engine. Keep the language of the question. Reply with the \
+query only.";
+
+const QUERY_EXPANSION_PROMPT: &str = "Write alternative search queries for the user's question, \
+each phrasing it differently or covering another aspect, in the language of the question. \
+Reply with JSON only: {\"queries\": [\"...\"]}";
+
+const HYDE_PROMPT: &str = "Write a short passage, as it would appear in a reference document, \
+that answers the user's question. Write it even if unsure of the facts; it is only used to \
+find similar documents.";
+
+/// Alternative queries from a query expansion reply, at most `limit`
+fn parse_expanded_queries(reply: &str, limit: usize) -> Vec<String> {
+    // Models sometimes wrap the JSON in a code fence
+    let json = match (reply.find('{'), reply.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &reply[start..=end],
+        _ => reply,
+    };
+
+    serde_json::from_str::<Value>(json)
+        .ok()
+        .and_then(|v| v.get("queries").and_then(|q| q.as_array()).cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|q| q.as_str())
+        .map(|q| q.trim().to_string())
+        .filter(|q| !q.is_empty())
+        .take(limit)
+        .collect()
+}
+
+/// Merge the results of several searches, keeping the best score of each
+/// document, best first and at most `top_k`
+fn merge_search_results(searches: Vec<Vec<SearchResult>>, top_k: usize) -> Vec<SearchResult> {
+    let mut merged: Vec<SearchResult> = Vec::new();
+
+    for result in searches.into_iter().flatten() {
+        match merged.iter_mut().find(|r| r.id == result.id) {
+            Some(existing) if existing.score < result.score => *existing = result,
+            Some(_) => {}
+            None => merged.push(result),
+        }
+    }
+
+    merged.sort_by(|a, b| b.score.total_cmp(&a.score));
+    merged.truncate(top_k);
+    merged
+}
+
+/// Queries searched by a knowledge base search step after its query
+/// transformation
+#[derive(Debug, Default)]
+struct TransformedQuery {
+    rewritten_query: Option<String>,
+    hypothetical_document: Option<String>,
+    queries: Vec<String>,
+    prompt_tokens: u32,
+    completion_tokens: u32,
+}
+
+impl TransformedQuery {
+    fn to_json(&self) -> Value {
+        json!({
+            "rewritten_query": self.rewritten_query,
+            "queries": self.queries,
+            "hypothetical_document": self.hypothetical_document,
+            "usage": {
+                "prompt_tokens": self.prompt_tokens,
+                "completion_tokens": self.completion_tokens,
+            },
+        })
+    }
+}
+
 /// Configuration for the workflow executor
 #[derive(Debug, Clone)]
 pub struct WorkflowExecutorConfig {
@@ -84,6 +175,12 @@ pub struct WorkflowExecutorImpl {
     /// Knowledge base provider registry for KB search steps
     kb_provider_registry: Arc<dyn KnowledgeBaseProviderRegistryTrait>,
 
+    /// Knowledge base embeddings for embedding-based context compression
+    embedding_config: Option<EmbeddingConfig>,
+
+    /// Counts the searches of KB search steps
+    access_tracker: Option<Arc<KnowledgeBaseAccessTracker>>,
+
     /// Executor configuration
     config: WorkflowExecutorConfig,
 }
@@ -111,6 +208,8 @@ impl WorkflowExecutorImpl {
             credential_service,
             external_api_service,
             kb_provider_registry,
+            embedding_config: None,
+            access_tracker: None,
             config: WorkflowExecutorConfig::default(),
         }
     }
@@ -130,12 +229,32 @@ impl WorkflowExecutorImpl {
             credential_service,
             external_api_service,
             kb_provider_registry,
+            embedding_config: None,
+            access_tracker: None,
             config,
         }
     }
 
-    /// Resolve a prompt_id to its content
-    async fn resolve_prompt(&self, prompt_id: &str) -> Result<String, WorkflowError> {
+    /// Embed with knowledge base embedding models, enabling embedding-based
+    /// context compression
+    pub fn with_embedding_config(mut self, embedding_config: EmbeddingConfig) -> Self {
+        self.embedding_config = Some(embedding_config);
+        self
+    }
+
+    /// Count the searches of KB search steps
+    pub fn with_access_tracker(mut self, tracker: Arc<KnowledgeBaseAccessTracker>) -> Self {
+        self.access_tracker = Some(tracker);
+        self
+    }
+
+    /// Resolve a prompt_id to its content and variable declarations, in the
+    /// variant matching the `locale` of the workflow input
+    async fn resolve_prompt(
+        &self,
+        prompt_id: &str,
+        context: &WorkflowContext,
+    ) -> Result<(String, Vec<crate::domain::PromptVariable>), WorkflowError> {
         use crate::domain::PromptId;
 
         let id = PromptId::new(prompt_id)
@@ -160,17 +279,25 @@ impl WorkflowExecutorImpl {
             ));
         }
 
-        Ok(prompt.content().to_string())
+        let locale = context.request_input().get("locale").and_then(Value::as_str);
+        let (_, content) = prompt.localized_content(locale);
+        let content = resolve_partials(self.prompt_storage.as_ref(), Some(prompt_id), content)
+            .await
+            .map_err(|e| WorkflowError::step_execution("prompt_resolution", e.to_string()))?;
+
+        Ok((content, prompt.variables().to_vec()))
     }
 
     /// Render a prompt template with variables
     ///
     /// This performs two-phase variable resolution:
     /// 1. Resolve ${request:*} and ${step:*:*} references in the variable values
-    /// 2. Substitute ${var:*} placeholders in the template with resolved values
+    /// 2. Substitute ${var:*} placeholders in the template with resolved values,
+    ///    checked against the prompt's declared variable types
     fn render_prompt_with_variables(
         &self,
         template: &str,
+        declarations: &[crate::domain::PromptVariable],
         prompt_variables: &std::collections::HashMap<String, String>,
         context: &WorkflowContext,
     ) -> Result<String, WorkflowError> {
@@ -191,7 +318,8 @@ impl WorkflowExecutorImpl {
 
         // Phase 2: Parse and render the prompt template with resolved variables
         let parsed = PromptTemplate::parse(template)
-            .map_err(|e| WorkflowError::step_execution("prompt_rendering", e.to_string()))?;
+            .map_err(|e| WorkflowError::step_execution("prompt_rendering", e.to_string()))?
+            .with_declarations(declarations);
 
         let rendered = parsed.render(&resolved_variables)
             .map_err(|e| WorkflowError::step_execution("prompt_rendering", e.to_string()))?;
@@ -232,13 +360,14 @@ impl WorkflowExecutorImpl {
         context: &WorkflowContext,
     ) -> Result<Value, WorkflowError> {
         // Resolve prompt_id to message content
-        let prompt_template = self.resolve_prompt(&step.prompt_id).await?;
+        let (prompt_template, declarations) = self.resolve_prompt(&step.prompt_id, context).await?;
 
         // Render the prompt template with prompt_variables
         // First, resolve any ${request:*} or ${step:*:*} in the variable values
         // Then, substitute ${var:*} placeholders with the resolved values
         let rendered_prompt = self.render_prompt_with_variables(
             &prompt_template,
+            &declarations,
             &step.prompt_variables,
             context,
         )?;
@@ -276,10 +405,14 @@ impl WorkflowExecutorImpl {
             })?;
 
         // Execute using the resolved provider
+        let span = provider_call_span(provider.provider_name(), &step.model_id, false);
         let response = provider
             .chat(&step.model_id, request)
+            .instrument(span.clone())
             .await
+            .inspect(|response| record_token_usage(&span, response.usage.as_ref()))
             .map_err(|e| {
+                record_error(&span, &e);
                 tracing::error!(
                     model_id = %step.model_id,
                     prompt_id = %step.prompt_id,
@@ -387,19 +520,79 @@ impl WorkflowExecutorImpl {
             }
         }
 
-        // Execute the search
-        let results = provider
-            .search(search_params)
-            .await
-            .map_err(|e| {
-                tracing::error!(
-                    kb_id = %step.knowledge_base_id,
-                    error = %e,
-                    query = %query,
-                    "KB search failed"
-                );
-                WorkflowError::step_execution("kb_search", e.to_string())
-            })?;
+        // Restrict the search to a namespace, possibly taken from the request
+        if let Some(namespace) = &step.namespace {
+            let namespace = context.resolve_string(namespace)?;
+            validate_namespace(&namespace)
+                .map_err(|e| WorkflowError::step_execution("kb_search", e.to_string()))?;
+            search_params = search_params.with_namespace(namespace);
+        }
+
+        // Only return the documents the workflow's caller may read
+        if let Some(caller) = context.caller() {
+            search_params = search_params.with_caller(caller.clone());
+        }
+
+        // Transform the query into the queries to search with
+        let transformed = match &step.query_transform {
+            Some(transform) if transform.is_enabled() => {
+                Some(self.transform_query(transform, &query).await?)
+            }
+            _ => None,
+        };
+        let queries = transformed
+            .as_ref()
+            .map(|t| t.queries.clone())
+            .unwrap_or_else(|| vec![query.clone()]);
+
+        // Execute the searches
+        let mut searches = Vec::with_capacity(queries.len());
+        for search_query in &queries {
+            let mut params = search_params.clone();
+            params.query = search_query.clone();
+
+            let span = kb_search_span(&step.knowledge_base_id, step.top_k);
+            let results = provider
+                .search(params)
+                .instrument(span.clone())
+                .await
+                .inspect(|results| {
+                    span.record("kb.results", results.len());
+                })
+                .map_err(|e| {
+                    record_error(&span, &e);
+                    tracing::error!(
+                        kb_id = %step.knowledge_base_id,
+                        error = %e,
+                        query = %search_query,
+                        "KB search failed"
+                    );
+                    WorkflowError::step_execution("kb_search", e.to_string())
+                })?;
+            searches.push(results);
+        }
+
+        let mut results = if searches.len() == 1 {
+            searches.pop().unwrap_or_default()
+        } else {
+            merge_search_results(searches, step.top_k as usize)
+        };
+
+        if let Some(tracker) = &self.access_tracker {
+            tracker.track(&step.knowledge_base_id, &query, &results);
+        }
+
+        // Reduce the results to their query-relevant sentences
+        let compressed = match &step.compression {
+            Some(compression) if !results.is_empty() => {
+                let kb_id = &step.knowledge_base_id;
+                Some(
+                    self.compress_results(compression, kb_id, &queries[0], &mut results)
+                        .await?,
+                )
+            }
+            _ => None,
+        };
 
         debug!("KB search returned {} results", results.len());
 
@@ -420,15 +613,320 @@ impl WorkflowExecutorImpl {
         // Build XML representation for easy template injection
         let documents_xml = build_documents_xml(&results);
 
-        Ok(json!({
+        let mut output = json!({
             "documents": documents,
             "documents_xml": documents_xml,
             "total": documents.len(),
             "knowledge_base_id": step.knowledge_base_id,
             "query": query,
+        });
+
+        if let Some(transformed) = transformed {
+            output["query_transform"] = transformed.to_json();
+        }
+        if let Some(compressed) = compressed {
+            output["compression"] = compressed;
+        }
+
+        Ok(output)
+    }
+
+    /// Keep only the sentences of the results relevant to the query,
+    /// dropping results left without any
+    ///
+    /// Returns the compression summary added to the step output.
+    async fn compress_results(
+        &self,
+        compression: &ContextCompression,
+        kb_id: &str,
+        query: &str,
+        results: &mut Vec<SearchResult>,
+    ) -> Result<Value, WorkflowError> {
+        let original_chars: usize = results.iter().map(|r| r.content.chars().count()).sum();
+        let original_documents = results.len();
+        let sentences: Vec<Vec<String>> = results
+            .iter()
+            .map(|r| split_sentences(&r.content).into_iter().map(String::from).collect())
+            .collect();
+        let mut usage = (0u32, 0u32);
+
+        let selection = match compression {
+            ContextCompression::Llm { model_id } => {
+                self.select_sentences_with_llm(model_id, query, &sentences, &mut usage)
+                    .await?
+            }
+            ContextCompression::Embedding {
+                threshold,
+                max_sentences,
+            } => {
+                let embedding_config = self.embedding_config.as_ref().ok_or_else(|| {
+                    WorkflowError::step_execution(
+                        "kb_search",
+                        "Embedding-based compression is not available",
+                    )
+                })?;
+
+                let mut texts = vec![query.to_string()];
+                texts.extend(sentences.iter().flatten().cloned());
+                let embeddings = embedding_config
+                    .embed_for_knowledge_base(kb_id, texts)
+                    .await
+                    .map_err(|e| WorkflowError::step_execution("kb_search", e.to_string()))?;
+                let metric = embedding_config
+                    .distance_metric_for_knowledge_base(kb_id)
+                    .await
+                    .map_err(|e| WorkflowError::step_execution("kb_search", e.to_string()))?;
+
+                let (query_embedding, sentence_embeddings) = embeddings
+                    .split_first()
+                    .ok_or_else(|| {
+                        WorkflowError::step_execution("kb_search", "No query embedding returned")
+                    })?;
+                let mut sentence_embeddings = sentence_embeddings.iter();
+
+                Some(
+                    sentences
+                        .iter()
+                        .map(|document| {
+                            let similarities: Vec<f32> = sentence_embeddings
+                                .by_ref()
+                                .take(document.len())
+                                .map(|e| metric.similarity(query_embedding, e))
+                                .collect();
+                            select_by_similarity(&similarities, *threshold, *max_sentences)
+                        })
+                        .collect(),
+                )
+            }
+        };
+
+        if let Some(selection) = selection {
+            let mut selection = selection.into_iter();
+            let mut sentences = sentences.into_iter();
+            results.retain_mut(|result| {
+                let selected = selection.next().unwrap_or_default();
+                let document = sentences.next().unwrap_or_default();
+                result.content = selected
+                    .iter()
+                    .map(|&i| document[i].as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                !result.content.is_empty()
+            });
+        }
+
+        let compressed_chars: usize = results.iter().map(|r| r.content.chars().count()).sum();
+        debug!(
+            strategy = compression.strategy(),
+            original_chars,
+            compressed_chars,
+            "Compressed KB search results"
+        );
+
+        Ok(json!({
+            "strategy": compression.strategy(),
+            "original_chars": original_chars,
+            "compressed_chars": compressed_chars,
+            "removed_documents": original_documents - results.len(),
+            "usage": {
+                "prompt_tokens": usage.0,
+                "completion_tokens": usage.1,
+            },
         }))
     }
 
+    /// Sentences of each document a model selects as relevant to the query,
+    /// `None` (keep the results as they are) when its reply cannot be read
+    async fn select_sentences_with_llm(
+        &self,
+        model_id: &str,
+        query: &str,
+        sentences: &[Vec<String>],
+        usage: &mut (u32, u32),
+    ) -> Result<Option<Vec<Vec<usize>>>, WorkflowError> {
+        let resolved = self
+            .provider_resolver
+            .resolve_with_model(model_id)
+            .await
+            .map_err(|e| {
+                tracing::error!(
+                    model_id = %model_id,
+                    error = %e,
+                    "Failed to resolve LLM provider for context compression"
+                );
+                WorkflowError::step_execution("kb_search", e.to_string())
+            })?;
+
+        let documents: Vec<Vec<&str>> = sentences
+            .iter()
+            .map(|document| document.iter().map(String::as_str).collect())
+            .collect();
+        let request = LlmRequest::builder()
+            .system(LLM_COMPRESSION_PROMPT)
+            .user(selection_prompt(query, &documents))
+            .temperature(0.0)
+            .response_format(LlmResponseFormat::JsonObject)
+            .build();
+
+        let span = provider_call_span(
+            resolved.provider.provider_name(),
+            &resolved.provider_model,
+            false,
+        );
+        let response = resolved
+            .provider
+            .chat(&resolved.provider_model, request)
+            .instrument(span.clone())
+            .await
+            .inspect(|response| record_token_usage(&span, response.usage.as_ref()))
+            .map_err(|e| {
+                record_error(&span, &e);
+                tracing::error!(
+                    model_id = %model_id,
+                    error = %e,
+                    "Context compression LLM call failed"
+                );
+                WorkflowError::step_execution("kb_search", e.to_string())
+            })?;
+
+        if let Some(response_usage) = &response.usage {
+            usage.0 += response_usage.prompt_tokens;
+            usage.1 += response_usage.completion_tokens;
+        }
+
+        let counts: Vec<usize> = sentences.iter().map(Vec::len).collect();
+        let reply = response.message.content_text().unwrap_or_default();
+        let selection = parse_selection(reply, &counts);
+        if selection.is_none() {
+            tracing::warn!(
+                model_id = %model_id,
+                "Unreadable context compression reply, keeping results uncompressed"
+            );
+        }
+
+        Ok(selection)
+    }
+
+    /// Rewrite, expand and/or answer (HyDE) a search query with the
+    /// transformation's model
+    ///
+    /// The first query is the rewritten query, or the original one when not
+    /// rewritten; expansions and the hypothetical document follow.
+    async fn transform_query(
+        &self,
+        transform: &QueryTransform,
+        query: &str,
+    ) -> Result<TransformedQuery, WorkflowError> {
+        let resolved = self
+            .provider_resolver
+            .resolve_with_model(&transform.model_id)
+            .await
+            .map_err(|e| {
+                tracing::error!(
+                    model_id = %transform.model_id,
+                    error = %e,
+                    "Failed to resolve LLM provider for query transformation"
+                );
+                WorkflowError::step_execution("kb_search", e.to_string())
+            })?;
+
+        let mut transformed = TransformedQuery::default();
+        let mut search_query = query.to_string();
+
+        if transform.rewrite {
+            let request = LlmRequest::builder()
+                .system(QUERY_REWRITE_PROMPT)
+                .user(query)
+                .temperature(0.0)
+                .build();
+            let rewritten = self
+                .transform_completion(&resolved, request, &mut transformed)
+                .await?;
+            if !rewritten.is_empty() {
+                search_query = rewritten.clone();
+                transformed.rewritten_query = Some(rewritten);
+            }
+        }
+        transformed.queries.push(search_query.clone());
+
+        if transform.expansions > 0 {
+            let count = transform.expansions.min(MAX_QUERY_EXPANSIONS);
+            let request = LlmRequest::builder()
+                .system(QUERY_EXPANSION_PROMPT)
+                .user(format!("Queries to write: {}\n\nQuestion: {}", count, search_query))
+                .temperature(0.7)
+                .response_format(LlmResponseFormat::JsonObject)
+                .build();
+            let reply = self
+                .transform_completion(&resolved, request, &mut transformed)
+                .await?;
+            for expansion in parse_expanded_queries(&reply, count as usize) {
+                if !transformed.queries.contains(&expansion) {
+                    transformed.queries.push(expansion);
+                }
+            }
+        }
+
+        if transform.hyde {
+            let request = LlmRequest::builder()
+                .system(HYDE_PROMPT)
+                .user(search_query.as_str())
+                .temperature(0.0)
+                .build();
+            let passage = self
+                .transform_completion(&resolved, request, &mut transformed)
+                .await?;
+            if !passage.is_empty() {
+                transformed.queries.push(passage.clone());
+                transformed.hypothetical_document = Some(passage);
+            }
+        }
+
+        debug!(
+            model_id = %transform.model_id,
+            queries = transformed.queries.len(),
+            "Transformed KB search query"
+        );
+
+        Ok(transformed)
+    }
+
+    /// Run one query transformation call, adding its token usage
+    async fn transform_completion(
+        &self,
+        resolved: &ResolvedModel,
+        request: LlmRequest,
+        transformed: &mut TransformedQuery,
+    ) -> Result<String, WorkflowError> {
+        let span = provider_call_span(
+            resolved.provider.provider_name(),
+            &resolved.provider_model,
+            false,
+        );
+        let response = resolved
+            .provider
+            .chat(&resolved.provider_model, request)
+            .instrument(span.clone())
+            .await
+            .inspect(|response| record_token_usage(&span, response.usage.as_ref()))
+            .map_err(|e| {
+                record_error(&span, &e);
+                tracing::error!(
+                    provider_model = %resolved.provider_model,
+                    error = %e,
+                    "Query transformation LLM call failed"
+                );
+                WorkflowError::step_execution("kb_search", e.to_string())
+            })?;
+
+        if let Some(usage) = &response.usage {
+            transformed.prompt_tokens += usage.prompt_tokens;
+            transformed.completion_tokens += usage.completion_tokens;
+        }
+
+        Ok(response.message.content_text().unwrap_or_default().trim().to_string())
+    }
+
     /// Execute a CRAG scoring step
     ///
     /// This step:
@@ -449,7 +947,7 @@ impl WorkflowExecutorImpl {
         );
 
         // Get the prompt template
-        let prompt = self.resolve_prompt(&step.prompt_id).await?;
+        let (prompt, declarations) = self.resolve_prompt(&step.prompt_id, context).await?;
 
         // Get documents array from documents_source
         // Can be a variable reference like "${step:search:documents}" or a simple field name
@@ -466,6 +964,7 @@ impl WorkflowExecutorImpl {
         // Render the prompt with all prompt_variables resolved
         let rendered_prompt = self.render_prompt_with_variables(
             &prompt,
+            &declarations,
             &step.prompt_variables.clone().unwrap_or_default(),
             context,
         )?;
@@ -543,11 +1042,23 @@ impl WorkflowExecutorImpl {
             .build();
 
         // Execute scoring with ONE LLM call using the provider_model
+        let crag_span = crag_scoring_span("llm", doc_array.len());
+        let span = crag_span.in_scope(|| {
+            provider_call_span(
+                resolved.provider.provider_name(),
+                &resolved.provider_model,
+                false,
+            )
+        });
         let response = resolved
             .provider
             .chat(&resolved.provider_model, request)
+            .instrument(span.clone())
             .await
+            .inspect(|response| record_token_usage(&span, response.usage.as_ref()))
             .map_err(|e| {
+                record_error(&span, &e);
+                record_error(&crag_span, &e);
                 tracing::error!(
                     model_id = %step.model_id,
                     provider_model = %resolved.provider_model,
@@ -610,6 +1121,7 @@ impl WorkflowExecutorImpl {
         }
 
         let relevant_count = relevant_documents.len();
+        crag_span.record("crag.relevant", relevant_count);
 
         Ok(json!({
             "documents": relevant_documents.clone(),
@@ -709,7 +1221,8 @@ impl WorkflowExecutorImpl {
             HttpMethod::OPTIONS => reqwest::Method::OPTIONS,
         };
 
-        let mut request = client.request(method, &url);
+        // Continue the workflow trace; base and step headers may override it
+        let mut request = client.request(method, &url).headers(current_trace_headers());
 
         // Add base headers from external API
         for (key, value) in external_api.base_headers() {
@@ -959,16 +1472,15 @@ fn get_step_type_name(step_type: &WorkflowStepType) -> &'static str {
     }
 }
 
-#[async_trait]
-impl WorkflowExecutor for WorkflowExecutorImpl {
-    async fn execute(
+impl WorkflowExecutorImpl {
+    /// Run the steps of a workflow within the given context
+    async fn run(
         &self,
         workflow: &Workflow,
-        input: Value,
+        mut context: WorkflowContext,
     ) -> Result<WorkflowResult, WorkflowError> {
         let start = Instant::now();
         let mut step_results = Vec::new();
-        let mut context = WorkflowContext::new(input);
         let mut total_token_usage = WorkflowTokenUsage::default();
 
         // Validate workflow
@@ -985,8 +1497,17 @@ impl WorkflowExecutor for WorkflowExecutorImpl {
         let steps = workflow.steps();
         let mut step_index = 0;
         let mut steps_executed = 0;
+        let cancellation = context.cancellation().cloned();
+        let step_progress = context.step_progress().cloned();
 
         while step_index < steps.len() && steps_executed < self.config.max_steps {
+            if let Some(step_progress) = &step_progress {
+                step_progress.report(steps_executed, steps.len());
+            }
+            if cancellation.as_ref().is_some_and(|c| c.is_cancelled()) {
+                return Err(WorkflowError::cancelled(workflow.id().as_str()));
+            }
+
             let step = &steps[step_index];
             let step_start = Instant::now();
             steps_executed += 1;
@@ -994,13 +1515,18 @@ impl WorkflowExecutor for WorkflowExecutorImpl {
             debug!("Executing step '{}' (index {})", step.name(), step_index);
 
             let step_type_name = get_step_type_name(step.step_type());
+            let step_span =
+                workflow_step_span(workflow.id().as_str(), step.name(), step_type_name, step_index);
 
             // Build step input (best effort - if it fails, we still execute the step)
             let step_input = self.build_step_input(step, &context).ok();
 
             // Handle conditional step specially
             if let WorkflowStepType::Conditional(cond_step) = step.step_type() {
-                let action = self.get_conditional_action(cond_step, &context)?;
+                let action = record_result(
+                    &step_span,
+                    step_span.in_scope(|| self.get_conditional_action(cond_step, &context)),
+                )?;
 
                 let mut step_result = StepExecutionResult::success(
                     step.name(),
@@ -1043,8 +1569,23 @@ impl WorkflowExecutor for WorkflowExecutorImpl {
                 continue;
             }
 
-            // Execute non-conditional step
-            match self.execute_step(step, &mut context).await {
+            // Execute non-conditional step; on cancellation the step is
+            // dropped, aborting its in-flight provider calls
+            let step_output = self
+                .execute_step(step, &mut context)
+                .instrument(step_span.clone());
+            let step_output = match &cancellation {
+                Some(cancellation) => tokio::select! {
+                    biased;
+                    _ = cancellation.cancelled() => {
+                        return Err(WorkflowError::cancelled(workflow.id().as_str()));
+                    }
+                    output = step_output => output,
+                },
+                None => step_output.await,
+            };
+
+            match step_output {
                 Ok(output) => {
                     context.set_step_output(step.name(), output.clone());
 
@@ -1055,25 +1596,33 @@ impl WorkflowExecutor for WorkflowExecutorImpl {
                         step_start.elapsed().as_millis() as u64,
                     );
 
-                    // Extract token usage from ChatCompletion step output
-                    if matches!(step.step_type(), WorkflowStepType::ChatCompletion(_)) {
-                        if let Some(usage) = output
-                            .get("response")
-                            .and_then(|r| r.get("usage"))
-                        {
-                            let input_tokens = usage
-                                .get("prompt_tokens")
-                                .and_then(|v| v.as_u64())
-                                .unwrap_or(0) as u32;
-                            let output_tokens = usage
-                                .get("completion_tokens")
-                                .and_then(|v| v.as_u64())
-                                .unwrap_or(0) as u32;
-
-                            let step_usage = WorkflowTokenUsage::new(input_tokens, output_tokens);
-                            total_token_usage.add(&step_usage);
-                            step_result = step_result.with_token_usage(step_usage);
+                    // Extract token usage from ChatCompletion step output, and from
+                    // the query transformation and compression of KB search steps
+                    let usage_sources: &[&str] = match step.step_type() {
+                        WorkflowStepType::ChatCompletion(_) => &["response"],
+                        WorkflowStepType::KnowledgeBaseSearch(_) => {
+                            &["query_transform", "compression"]
                         }
+                        _ => &[],
+                    };
+                    let usages: Vec<&Value> = usage_sources
+                        .iter()
+                        .filter_map(|source| output.get(*source)?.get("usage"))
+                        .collect();
+                    if !usages.is_empty() {
+                        let tokens = |key: &str| -> u32 {
+                            usages
+                                .iter()
+                                .filter_map(|usage| usage.get(key).and_then(|v| v.as_u64()))
+                                .sum::<u64>() as u32
+                        };
+
+                        let step_usage = WorkflowTokenUsage::new(
+                            tokens("prompt_tokens"),
+                            tokens("completion_tokens"),
+                        );
+                        total_token_usage.add(&step_usage);
+                        step_result = step_result.with_token_usage(step_usage);
                     }
 
                     if let Some(input) = step_input {
@@ -1083,6 +1632,8 @@ impl WorkflowExecutor for WorkflowExecutorImpl {
                     step_index += 1;
                 }
                 Err(e) => {
+                    record_error(&step_span, &e);
+
                     let mut step_result = StepExecutionResult::failure(
                         step.name(),
                         step_type_name,
@@ -1141,6 +1692,42 @@ impl WorkflowExecutor for WorkflowExecutorImpl {
     }
 }
 
+#[async_trait]
+impl WorkflowExecutor for WorkflowExecutorImpl {
+    async fn execute(
+        &self,
+        workflow: &Workflow,
+        input: Value,
+    ) -> Result<WorkflowResult, WorkflowError> {
+        self.run(workflow, WorkflowContext::new(input)).await
+    }
+
+    async fn execute_as(
+        &self,
+        workflow: &Workflow,
+        input: Value,
+        caller: KnowledgeBaseCaller,
+    ) -> Result<WorkflowResult, WorkflowError> {
+        self.run(workflow, WorkflowContext::new(input).with_caller(caller))
+            .await
+    }
+
+    async fn execute_cancellable(
+        &self,
+        workflow: &Workflow,
+        input: Value,
+        caller: KnowledgeBaseCaller,
+        cancellation: CancellationToken,
+        step_progress: StepProgress,
+    ) -> Result<WorkflowResult, WorkflowError> {
+        let context = WorkflowContext::new(input)
+            .with_caller(caller)
+            .with_cancellation(cancellation)
+            .with_step_progress(step_progress);
+        self.run(workflow, context).await
+    }
+}
+
 /// Recursively resolve variable references in a JSON value
 fn resolve_json_variables(
     value: &Value,
@@ -1358,6 +1945,32 @@ mod tests {
         Arc::new(StaticProviderResolver::new(provider))
     }
 
+    fn search_result(id: &str, score: f32) -> SearchResult {
+        SearchResult::new(id, id, score)
+    }
+
+    #[test]
+    fn test_merge_search_results_keeps_best_score() {
+        let merged = merge_search_results(
+            vec![
+                vec![search_result("a", 0.9), search_result("b", 0.5)],
+                vec![search_result("b", 0.8), search_result("c", 0.6)],
+            ],
+            2,
+        );
+
+        let ids: Vec<_> = merged.iter().map(|r| (r.id.as_str(), r.score)).collect();
+        assert_eq!(ids, vec![("a", 0.9), ("b", 0.8)]);
+    }
+
+    #[test]
+    fn test_parse_expanded_queries() {
+        let reply = "```json\n{\"queries\": [\" refund policy \", \"\", \"returns\", \"x\"]}\n```";
+
+        assert_eq!(parse_expanded_queries(reply, 2), vec!["refund policy", "returns"]);
+        assert!(parse_expanded_queries("no json", 3).is_empty());
+    }
+
     #[tokio::test]
     async fn test_execute_simple_workflow() {
         let resolver = create_resolver("Hello there!");
@@ -1387,6 +2000,29 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("disabled"));
     }
 
+    #[tokio::test]
+    async fn test_execute_cancelled_workflow() {
+        use crate::domain::knowledge_base::KnowledgeBaseCaller;
+
+        let resolver = create_resolver("test");
+        let prompt_storage = create_prompt_storage();
+        let executor = WorkflowExecutorImpl::new(resolver, prompt_storage, create_mock_credential_service(), create_mock_external_api_service(), create_mock_kb_registry());
+
+        let cancellation = CancellationToken::new();
+        cancellation.cancel();
+        let result = executor
+            .execute_cancellable(
+                &create_simple_workflow(),
+                json!({"name": "World"}),
+                KnowledgeBaseCaller::new("team"),
+                cancellation,
+                StepProgress::new(|_, _| {}),
+            )
+            .await;
+
+        assert!(matches!(result, Err(WorkflowError::Cancelled(_))));
+    }
+
     #[tokio::test]
     async fn test_execute_empty_workflow() {
         use crate::domain::WorkflowId;
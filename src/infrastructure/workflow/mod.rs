@@ -1,5 +1,6 @@
 //! Workflow infrastructure implementations
 
+mod compression;
 mod executor_impl;
 
 pub use executor_impl::{WorkflowExecutorConfig, WorkflowExecutorImpl};
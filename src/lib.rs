@@ -8,6 +8,8 @@
 
 pub mod api;
 pub mod cli;
+#[cfg(feature = "client")]
+pub mod client;
 pub mod config;
 pub mod domain;
 pub mod infrastructure;
@@ -16,52 +18,100 @@ pub use config::AppConfig;
 
 use std::sync::Arc;
 
+use api::middleware::ClientIpResolver;
 use api::state::AppState;
+use api::v1::spawn_operation_reaper;
 use domain::{
     api_key::ApiKeyPermissions,
+    audit::{AuditLog, AuditSink},
+    cache::CachePolicy,
     config::ExecutionLog,
     credentials::StoredCredential,
-    knowledge_base::KnowledgeBase,
+    embedding::EmbeddingProvider,
+    guardrail::ContentPolicy,
+    ingestion::OcrEngine,
+    knowledge_base::{KnowledgeBase, KnowledgeBaseAccessStats},
+    network::IpNetwork,
+    organization::Organization,
+    role::Role,
+    semantic_cache::{SemanticCache, SemanticCacheConfig},
+    service_account::ServiceAccount,
+    slo::Slo,
     team::Team,
+    transform::TransformRule,
     workflow::Workflow,
     Model, Prompt,
 };
 use infrastructure::{
     api_key::{ApiKeyGenerator, ApiKeyService, InMemoryApiKeyRepository, StorageApiKeyRepository},
+    assistant::{AssistantService, StorageAssistantRepository},
+    mcp::{McpToolService, StorageMcpToolRepository},
+    fine_tune::{
+        spawn_fine_tune_polling, CredentialFineTuneProviderFactory, FineTuneService,
+        StorageFineTuneJobRepository,
+    },
+    audit::{AuditLogService, HttpAuditSink, LogAuditSink, StorageAuditLogRepository},
+    cache::{InMemoryCache, InMemoryCacheConfig},
     auth::{JwtConfig, JwksJwtService, JwtService},
     config::{InMemoryConfigRepository, PostgresConfigRepository, StorageExecutionLogRepository},
-    credentials::{CredentialService, InMemoryStoredCredentialRepository, StorageStoredCredentialRepository},
+    credentials::{
+        CredentialInvalidationBus, CredentialService, InMemoryStoredCredentialRepository,
+        StorageStoredCredentialRepository,
+    },
+    dataset::{DatasetService, StorageDatasetRepository},
+    embedding::{HttpClient, OpenAiEmbeddingProvider},
+    event::EventBus,
+    health::{spawn_canary_runs, CanaryHealth, CanaryRunner, DependencyProber},
     experiment::{
         InMemoryExperimentRecordRepository, InMemoryExperimentRepository,
         StorageExperimentRecordRepository, StorageExperimentRepository,
     },
     external_api,
+    guardrail::{ContentPolicyService, OpenAiModerationProvider},
     knowledge_base::{
-        KnowledgeBaseProviderRegistry, KnowledgeBaseProviderRegistryTrait,
-        LazyKnowledgeBaseProviderRegistry, LazyRegistryConfig,
+        KnowledgeBaseAccessTracker, KnowledgeBaseProviderRegistry,
+        KnowledgeBaseProviderRegistryTrait, LazyKnowledgeBaseProviderRegistry, LazyRegistryConfig,
+    },
+    leader::{
+        spawn_leader_election, KubernetesLeaseElector, LeaderElector, Leadership,
+        PostgresAdvisoryLockElector,
     },
     llm::LlmProviderFactory,
+    notification::NotificationDispatcher,
     operation::{InMemoryOperationRepository, StorageOperationRepository},
+    organization::{OrganizationService, StorageOrganizationRepository},
     plugin::{register_builtin_plugins, PluginRegistry, ProviderRouter, RoutingProviderResolver},
     services::{
-        ConfigService, ExecutionLogService, ExperimentService, IngestionService,
-        KnowledgeBaseService, ModelService, OperationService, PromptService, TestCaseService,
-        TestCaseServiceDeps, WorkflowService,
+        spawn_experiment_auto_stop, ConfigService, ExecutionLogService, ExperimentService, IngestionService,
+        KnowledgeBaseService, LlmCacheConfig, LlmCacheService, ModelService, OperationService,
+        OperationServiceConfig, PromptService, RegressionService, ResponseCacheService,
+        SemanticLlmCacheService,
+        TestCaseService, TestCaseServiceDeps, TestSuiteService, WorkflowService,
     },
+    role::{RoleService, StorageRoleRepository},
+    semantic_cache::{InMemorySemanticCache, RedisSemanticCache, RedisSemanticCacheConfig},
+    service_account::{ServiceAccountService, StorageServiceAccountRepository},
+    slo::{spawn_slo_evaluation, SloService, StorageSloRepository},
     storage::{InMemoryStorage, StorageFactory},
-    team::{StorageTeamRepository, TeamService},
+    team::{StorageTeamRepository, TeamQuotaGuard, TeamService},
     test_case::{
         InMemoryTestCaseRepository, InMemoryTestCaseResultRepository,
-        StorageTestCaseRepository, StorageTestCaseResultRepository,
+        StorageTestCaseRepository, StorageTestCaseResultRepository, StorageTestSuiteRepository,
     },
+    transform::TransformRuleService,
     usage::{
-        BudgetService, InMemoryBudgetRepository, InMemoryUsageRepository,
-        StorageBudgetRepository, StorageUsageRepository, UsageTrackingService,
+        spawn_daily_usage_export, spawn_daily_usage_reconciliation, spawn_pricing_sync,
+        spawn_usage_anomaly_detection, BudgetService, InMemoryBudgetRepository, InMemoryUsageRepository,
+        PostgresUsageAggregator, PricingCatalog, PricingFeed, PricingFeedFormat, PricingService,
+        S3UsageExporter, StorageBudgetRepository, StoragePricingRepository,
+        StorageUsageRepository, UsageAnomalyDetector, UsageReconciler, UsageTrackingService,
+        UsageTrackingServiceTrait,
     },
     user::{Argon2Hasher, CreateUserRequest, PostgresUserRepository, UserService},
     webhook::{
-        InMemoryWebhookDeliveryRepository, InMemoryWebhookRepository,
+        spawn_webhook_retries, InMemoryWebhookDeliveryRepository, InMemoryWebhookRepository,
         StorageWebhookDeliveryRepository, StorageWebhookRepository, WebhookService,
+        WebhookServiceTrait,
     },
     workflow::WorkflowExecutorImpl,
 };
@@ -75,12 +125,21 @@ pub async fn create_app_state() -> anyhow::Result<AppState> {
 
 /// Create the application state with custom configuration
 pub async fn create_app_state_with_config(config: &AppConfig) -> anyhow::Result<AppState> {
+    use domain::agent::AgentLimits;
     use domain::api_key::ApiKey;
+    use domain::assistant::Assistant;
+    use domain::mcp::McpTool;
+    use domain::fine_tune::FineTuneJob;
+    use domain::dataset::{Dataset, DatasetVersion};
     use domain::experiment::{Experiment, ExperimentRecord};
+    use domain::guardrail::{InjectionGuard, SecretLeakGuard};
     use domain::operation::Operation;
-    use domain::test_case::{TestCase, TestCaseResult};
-    use domain::usage::{Budget, UsageRecord};
+    use domain::test_case::{RegressionReport, TestCase, TestCaseResult, TestSuite, TestSuiteRun};
+    use domain::usage::{Budget, InvoiceMarkup, ModelPricing, UsageRecord};
     use domain::webhook::{Webhook, WebhookDelivery};
+    use infrastructure::ingestion::{
+        DefaultOcrResolver, LlmChunkEnricher, PdfTools, UploadLimits,
+    };
     use infrastructure::storage::StorageType;
 
     let llm_provider = create_llm_provider()?;
@@ -110,6 +169,8 @@ pub async fn create_app_state_with_config(config: &AppConfig) -> anyhow::Result<
         .map_err(|e| anyhow::anyhow!("Failed to connect to PostgreSQL: {}", e))?;
     info!("PostgreSQL connection established");
 
+    let leadership = create_leadership(config, &pg_pool)?;
+
     use domain::storage::Storage as StorageTrait;
 
     // Create services based on storage backend selection
@@ -175,6 +236,8 @@ pub async fn create_app_state_with_config(config: &AppConfig) -> anyhow::Result<
 
     // Credential service - needed for provider resolution
     // We need both infrastructure and api::state trait versions
+    // Updated and deleted credentials are published so cached clients are rebuilt
+    let credential_invalidation = CredentialInvalidationBus::new();
     let (credential_service_infra, credential_service): (
         Arc<dyn infrastructure::credentials::CredentialServiceTrait>,
         Arc<dyn api::state::CredentialServiceTrait>,
@@ -183,20 +246,23 @@ pub async fn create_app_state_with_config(config: &AppConfig) -> anyhow::Result<
             pg_pool.clone(),
             "credentials",
         );
-        let service = Arc::new(CredentialService::new(Arc::new(
-            StorageStoredCredentialRepository::new(storage),
-        )));
+        let service = Arc::new(
+            CredentialService::new(Arc::new(StorageStoredCredentialRepository::new(storage)))
+                .with_invalidation_bus(credential_invalidation.clone()),
+        );
         (service.clone(), service)
     } else {
-        let service = Arc::new(CredentialService::new(Arc::new(
-            InMemoryStoredCredentialRepository::new(),
-        )));
+        let service = Arc::new(
+            CredentialService::new(Arc::new(InMemoryStoredCredentialRepository::new()))
+                .with_invalidation_bus(credential_invalidation.clone()),
+        );
         (service.clone(), service)
     };
 
     // Plugin system - register built-in providers (must be before workflow executor)
     let plugin_registry = PluginRegistry::new();
     let provider_router = Arc::new(ProviderRouter::new());
+    credential_invalidation.subscribe(provider_router.clone());
 
     // Provider resolver for workflow execution - routes to appropriate provider per model
     let provider_resolver = Arc::new(RoutingProviderResolver::new(
@@ -206,18 +272,24 @@ pub async fn create_app_state_with_config(config: &AppConfig) -> anyhow::Result<
         llm_provider.clone(),
     ));
 
+    // Team repository and quota guard - quotas are enforced by the services below
+    let team_repository = Arc::new(StorageTeamRepository::new(team_storage));
+    let quota_guard = TeamQuotaGuard::new(team_repository.clone());
+
     // API Key service
     let api_key_service: Arc<dyn api::state::ApiKeyServiceTrait> = if use_postgres {
         let storage =
             StorageFactory::create_postgres_with_pool::<ApiKey>(pg_pool.clone(), "api_keys");
         Arc::new(
             ApiKeyService::new(Arc::new(StorageApiKeyRepository::new(storage)))
-                .with_generator(ApiKeyGenerator::new("pk_test_")),
+                .with_generator(ApiKeyGenerator::new("pk_test_"))
+                .with_quota_guard(quota_guard.clone()),
         )
     } else {
         Arc::new(
             ApiKeyService::new(Arc::new(InMemoryApiKeyRepository::new()))
-                .with_generator(ApiKeyGenerator::new("pk_test_")),
+                .with_generator(ApiKeyGenerator::new("pk_test_"))
+                .with_quota_guard(quota_guard.clone()),
         )
     };
 
@@ -248,45 +320,109 @@ pub async fn create_app_state_with_config(config: &AppConfig) -> anyhow::Result<
     let inner_registry = Arc::new(KnowledgeBaseProviderRegistry::new());
     let lazy_config = LazyRegistryConfig::new().with_pg_pool(pg_pool.clone());
 
-    let kb_provider_registry: Arc<dyn KnowledgeBaseProviderRegistryTrait> = Arc::new(
-        LazyKnowledgeBaseProviderRegistry::new(
-            inner_registry,
-            knowledge_base_storage.clone(),
-            model_storage_for_kb.clone(),
-            credential_service_infra.clone(),
-            lazy_config,
-        ),
-    );
-
-    let workflow_executor: Arc<dyn domain::WorkflowExecutor> = Arc::new(WorkflowExecutorImpl::new(
-        provider_resolver,
-        prompt_storage_for_workflow.clone(),
+    let lazy_registry = Arc::new(LazyKnowledgeBaseProviderRegistry::new(
+        inner_registry,
+        knowledge_base_storage.clone(),
+        model_storage_for_kb.clone(),
         credential_service_infra.clone(),
-        external_api_service_infra.clone(),
-        kb_provider_registry.clone(),
+        lazy_config,
     ));
-    let workflow_service = Arc::new(WorkflowService::new(workflow_storage.clone(), workflow_executor));
+    credential_invalidation.subscribe(lazy_registry.clone());
+    let kb_provider_registry: Arc<dyn KnowledgeBaseProviderRegistryTrait> = lazy_registry;
+
+    // Search and retrieval counts of knowledge bases, for their analytics
+    let kb_access_storage: Arc<dyn StorageTrait<KnowledgeBaseAccessStats>> = if use_postgres {
+        StorageFactory::create_postgres_with_pool::<KnowledgeBaseAccessStats>(
+            pg_pool.clone(),
+            "knowledge_base_access_stats",
+        )
+    } else {
+        Arc::new(InMemoryStorage::<KnowledgeBaseAccessStats>::new())
+    };
+    let kb_access_tracker = Arc::new(KnowledgeBaseAccessTracker::new(kb_access_storage));
+
+    // Create embedding config for dynamic provider creation
+    let embedding_config = infrastructure::services::EmbeddingConfig::new(
+        knowledge_base_storage.clone(),
+        model_storage_for_kb.clone(),
+        credential_service_infra.clone(),
+    );
+
+    let workflow_executor: Arc<dyn domain::WorkflowExecutor> = Arc::new(
+        WorkflowExecutorImpl::new(
+            provider_resolver.clone(),
+            prompt_storage_for_workflow.clone(),
+            credential_service_infra.clone(),
+            external_api_service_infra.clone(),
+            kb_provider_registry.clone(),
+        )
+        .with_embedding_config(embedding_config.clone())
+        .with_access_tracker(kb_access_tracker.clone()),
+    );
+    let workflow_service = Arc::new(
+        WorkflowService::new(workflow_storage.clone(), workflow_executor)
+            .with_quota_guard(quota_guard.clone()),
+    );
 
     // Operation service
+    let operation_config = create_operation_config(config);
     let operation_service: Arc<dyn api::state::OperationServiceTrait> = if use_postgres {
         let storage =
             StorageFactory::create_postgres_with_pool::<Operation>(pg_pool.clone(), "operations");
-        Arc::new(OperationService::new(Arc::new(
-            StorageOperationRepository::new(storage),
-        )))
+        Arc::new(OperationService::with_config(
+            Arc::new(StorageOperationRepository::new(storage)),
+            operation_config,
+        ))
     } else {
-        Arc::new(OperationService::new(Arc::new(
-            InMemoryOperationRepository::new(),
-        )))
+        Arc::new(OperationService::with_config(
+            Arc::new(InMemoryOperationRepository::new()),
+            operation_config,
+        ))
     };
 
     // Team service - must be initialized before users and API keys
-    let team_repository = Arc::new(StorageTeamRepository::new(team_storage));
-    let team_service = Arc::new(TeamService::new(team_repository));
+    let team_service = Arc::new(TeamService::new(team_repository.clone()));
 
     // Ensure administrators team exists before creating users/API keys
     team_service.ensure_administrators_team().await?;
 
+    // Organization service - groups teams into business units
+    let organization_storage: Arc<dyn StorageTrait<Organization>> = if use_postgres {
+        StorageFactory::create_postgres_with_pool::<Organization>(pg_pool.clone(), "organizations")
+    } else {
+        Arc::new(InMemoryStorage::<Organization>::new())
+    };
+    let organization_service: Arc<dyn api::state::OrganizationServiceTrait> =
+        Arc::new(OrganizationService::new(
+            Arc::new(StorageOrganizationRepository::new(organization_storage)),
+            team_repository.clone(),
+        ));
+
+    // Role service - custom roles for admin API authorization
+    let role_storage: Arc<dyn StorageTrait<Role>> = if use_postgres {
+        StorageFactory::create_postgres_with_pool::<Role>(pg_pool.clone(), "roles")
+    } else {
+        Arc::new(InMemoryStorage::<Role>::new())
+    };
+    let role_service: Arc<dyn api::state::RoleServiceTrait> = Arc::new(RoleService::new(
+        Arc::new(StorageRoleRepository::new(role_storage)),
+    ));
+
+    // Service account service - machine identities using the client credentials flow
+    let service_account_storage: Arc<dyn StorageTrait<ServiceAccount>> = if use_postgres {
+        StorageFactory::create_postgres_with_pool::<ServiceAccount>(
+            pg_pool.clone(),
+            "service_accounts",
+        )
+    } else {
+        Arc::new(InMemoryStorage::<ServiceAccount>::new())
+    };
+    let service_account_service: Arc<dyn api::state::ServiceAccountServiceTrait> = Arc::new(
+        ServiceAccountService::new(Arc::new(StorageServiceAccountRepository::new(
+            service_account_storage,
+        ))),
+    );
+
     // User authentication services - PostgreSQL required for persistence
     let user_repository = Arc::new(PostgresUserRepository::new(pg_pool.clone()));
     let password_hasher = Arc::new(Argon2Hasher::new());
@@ -297,62 +433,192 @@ pub async fn create_app_state_with_config(config: &AppConfig) -> anyhow::Result<
     create_initial_admin_user(user_service.as_ref()).await?;
 
     // Knowledge base service
-    let knowledge_base_service = Arc::new(KnowledgeBaseService::new(knowledge_base_storage.clone()));
-
-    // Create embedding config for dynamic provider creation
-    let embedding_config = infrastructure::services::EmbeddingConfig::new(
-        knowledge_base_storage.clone(),
-        model_storage_for_kb.clone(),
-        credential_service_infra.clone(),
+    let knowledge_base_service = Arc::new(
+        KnowledgeBaseService::new(knowledge_base_storage.clone())
+            .with_quota_guard(quota_guard.clone()),
     );
 
     // Document ingestion service - uses the kb_provider_registry created earlier
-    let ingestion_service = Arc::new(IngestionService::with_embedding_config(
-        kb_provider_registry.clone(),
-        embedding_config,
-    ));
+    let ingestion_service = Arc::new(
+        IngestionService::with_embedding_config(kb_provider_registry.clone(), embedding_config)
+            .with_quota_guard(quota_guard, knowledge_base_storage.clone())
+            .with_ocr(
+                Arc::new(DefaultOcrResolver::new(
+                    config.ocr.tesseract_path.clone(),
+                    provider_resolver.clone(),
+                )),
+                PdfTools {
+                    pdftotext: config.ocr.pdftotext_path.clone(),
+                    pdftoppm: config.ocr.pdftoppm_path.clone(),
+                    dpi: config.ocr.dpi,
+                    temp_dir: std::env::temp_dir(),
+                },
+                create_ocr_engine(config)?,
+            )
+            .with_chunk_enricher(Arc::new(LlmChunkEnricher::new(provider_resolver.clone()))),
+    );
+
+    // Assistants, retrieving context through the same provider registry
+    let assistant_storage: Arc<dyn StorageTrait<Assistant>> = if use_postgres {
+        StorageFactory::create_postgres_with_pool::<Assistant>(pg_pool.clone(), "assistants")
+    } else {
+        Arc::new(InMemoryStorage::<Assistant>::new())
+    };
+    let assistant_service: Arc<dyn api::state::AssistantServiceTrait> =
+        Arc::new(
+            AssistantService::new(
+                Arc::new(StorageAssistantRepository::new(assistant_storage)),
+                kb_provider_registry.clone(),
+            )
+            .with_access_tracker(kb_access_tracker.clone()),
+        );
+
+    // Workflows and knowledge base searches exposed by the MCP server
+    let mcp_tool_storage: Arc<dyn StorageTrait<McpTool>> = if use_postgres {
+        StorageFactory::create_postgres_with_pool::<McpTool>(pg_pool.clone(), "mcp_tools")
+    } else {
+        Arc::new(InMemoryStorage::<McpTool>::new())
+    };
+    let mcp_tool_service: Arc<dyn api::state::McpToolServiceTrait> =
+        Arc::new(
+            McpToolService::new(
+                Arc::new(StorageMcpToolRepository::new(mcp_tool_storage)),
+                kb_provider_registry.clone(),
+            )
+            .with_access_tracker(kb_access_tracker.clone()),
+        );
+
+    // Pricing catalog, shared by the pricing service and cost calculation
+    let pricing_storage: Arc<dyn StorageTrait<ModelPricing>> = if use_postgres {
+        StorageFactory::create_postgres_with_pool::<ModelPricing>(pg_pool.clone(), "model_pricing")
+    } else {
+        Arc::new(InMemoryStorage::<ModelPricing>::new())
+    };
+    let mut pricing_service = PricingService::new(
+        Arc::new(StoragePricingRepository::new(pricing_storage)),
+        Arc::new(PricingCatalog::default()),
+    );
+
+    if let Some(url) = &config.pricing.sync_url {
+        let format: PricingFeedFormat = config.pricing.sync_format.parse()?;
+        pricing_service = pricing_service.with_feed(
+            PricingFeed::new(
+                url.clone(),
+                format,
+                std::time::Duration::from_secs(config.pricing.sync_timeout_secs),
+            )
+            .with_models(config.pricing.sync_models.clone()),
+        );
+    }
+
+    pricing_service.load().await?;
+    let pricing_service = Arc::new(pricing_service);
+    let pricing_catalog = pricing_service.catalog().clone();
+
+    if let Some(url) = &config.pricing.sync_url {
+        info!("Synchronising model pricing from {}", url);
+        spawn_pricing_sync(
+            pricing_service.clone(),
+            std::time::Duration::from_secs(config.pricing.sync_interval_secs.max(1)),
+        );
+    }
 
     // Usage tracking and budget services
-    let usage_service: Arc<dyn api::state::UsageServiceTrait> = if use_postgres {
+    let (usage_service, usage_tracking): (
+        Arc<dyn api::state::UsageServiceTrait>,
+        Arc<dyn UsageTrackingServiceTrait>,
+    ) = if use_postgres {
         let storage =
             StorageFactory::create_postgres_with_pool::<UsageRecord>(pg_pool.clone(), "usage_records");
-        Arc::new(UsageTrackingService::new(Arc::new(
-            StorageUsageRepository::new(storage),
-        )))
+        let repository = StorageUsageRepository::new(storage).with_aggregator(
+            PostgresUsageAggregator::new(pg_pool.clone(), "usage_records"),
+        );
+        let service = Arc::new(UsageTrackingService::with_catalog(
+            Arc::new(repository),
+            pricing_catalog,
+        ));
+        (service.clone(), service)
     } else {
-        Arc::new(UsageTrackingService::new(Arc::new(
-            InMemoryUsageRepository::default(),
-        )))
+        let service = Arc::new(UsageTrackingService::with_catalog(
+            Arc::new(InMemoryUsageRepository::default()),
+            pricing_catalog,
+        ));
+        (service.clone(), service)
     };
 
+    if let Some(exporter) = S3UsageExporter::from_config(&config.usage_export, usage_tracking.clone()).await? {
+        info!(
+            "Exporting daily usage to {:?} at {:02}:00 UTC",
+            config.usage_export.s3_bucket, config.usage_export.hour_utc
+        );
+        spawn_daily_usage_export(
+            Arc::new(exporter),
+            config.usage_export.hour_utc,
+            leadership.clone(),
+        );
+    }
+
     let budget_service: Arc<dyn api::state::BudgetServiceStateTrait> = if use_postgres {
         let storage =
             StorageFactory::create_postgres_with_pool::<Budget>(pg_pool.clone(), "budgets");
-        Arc::new(BudgetService::new(Arc::new(StorageBudgetRepository::new(
-            storage,
-        ))))
+        Arc::new(
+            BudgetService::new(Arc::new(StorageBudgetRepository::new(storage)))
+                .with_team_repository(team_repository.clone()),
+        )
     } else {
-        Arc::new(BudgetService::new(Arc::new(InMemoryBudgetRepository::new())))
+        Arc::new(
+            BudgetService::new(Arc::new(InMemoryBudgetRepository::new()))
+                .with_team_repository(team_repository),
+        )
     };
 
-    // Experiment (A/B testing) service
-    let experiment_service: Arc<dyn api::state::ExperimentServiceTrait> = if use_postgres {
-        let exp_storage =
-            StorageFactory::create_postgres_with_pool::<Experiment>(pg_pool.clone(), "experiments");
-        let record_storage = StorageFactory::create_postgres_with_pool::<ExperimentRecord>(
-            pg_pool.clone(),
-            "experiment_records",
-        );
-        Arc::new(ExperimentService::new(
-            Arc::new(StorageExperimentRepository::new(exp_storage)),
-            Arc::new(StorageExperimentRecordRepository::new(record_storage)),
-        ))
+    // Evaluation datasets
+    let (dataset_storage, dataset_version_storage): (
+        Arc<dyn StorageTrait<Dataset>>,
+        Arc<dyn StorageTrait<DatasetVersion>>,
+    ) = if use_postgres {
+        (
+            StorageFactory::create_postgres_with_pool::<Dataset>(pg_pool.clone(), "datasets"),
+            StorageFactory::create_postgres_with_pool::<DatasetVersion>(
+                pg_pool.clone(),
+                "dataset_versions",
+            ),
+        )
     } else {
-        Arc::new(ExperimentService::new(
-            Arc::new(InMemoryExperimentRepository::new()),
-            Arc::new(InMemoryExperimentRecordRepository::new()),
-        ))
+        (
+            Arc::new(InMemoryStorage::<Dataset>::new()),
+            Arc::new(InMemoryStorage::<DatasetVersion>::new()),
+        )
     };
+    let dataset_service: Arc<dyn api::state::DatasetServiceTrait> =
+        Arc::new(DatasetService::new(Arc::new(StorageDatasetRepository::new(
+            dataset_storage,
+            dataset_version_storage,
+        ))));
+
+    // Provider fine-tuning jobs trained on datasets
+    let fine_tune_storage: Arc<dyn StorageTrait<FineTuneJob>> = if use_postgres {
+        StorageFactory::create_postgres_with_pool::<FineTuneJob>(pg_pool.clone(), "fine_tune_jobs")
+    } else {
+        Arc::new(InMemoryStorage::<FineTuneJob>::new())
+    };
+    let fine_tune_service: Arc<dyn api::state::FineTuneServiceTrait> =
+        Arc::new(FineTuneService::new(
+            Arc::new(StorageFineTuneJobRepository::new(fine_tune_storage)),
+            dataset_service.clone(),
+            model_service.clone(),
+            Arc::new(CredentialFineTuneProviderFactory::new(
+                credential_service.clone(),
+            )),
+        ));
+
+    if config.fine_tunes.poll_interval_secs > 0 {
+        spawn_fine_tune_polling(
+            fine_tune_service.clone(),
+            std::time::Duration::from_secs(config.fine_tunes.poll_interval_secs),
+            leadership.clone(),
+        );
+    }
 
     // Test case service
     let test_case_deps = TestCaseServiceDeps {
@@ -370,19 +636,67 @@ pub async fn create_app_state_with_config(config: &AppConfig) -> anyhow::Result<
             pg_pool.clone(),
             "test_case_results",
         );
-        Arc::new(TestCaseService::new(
-            Arc::new(StorageTestCaseRepository::new(tc_storage)),
-            Arc::new(StorageTestCaseResultRepository::new(result_storage)),
-            test_case_deps,
-        ))
+        Arc::new(
+            TestCaseService::new(
+                Arc::new(StorageTestCaseRepository::new(tc_storage)),
+                Arc::new(StorageTestCaseResultRepository::new(result_storage)),
+                test_case_deps,
+            )
+            .with_datasets(dataset_service.clone()),
+        )
     } else {
-        Arc::new(TestCaseService::new(
-            Arc::new(InMemoryTestCaseRepository::new()),
-            Arc::new(InMemoryTestCaseResultRepository::new()),
-            test_case_deps,
-        ))
+        Arc::new(
+            TestCaseService::new(
+                Arc::new(InMemoryTestCaseRepository::new()),
+                Arc::new(InMemoryTestCaseResultRepository::new()),
+                test_case_deps,
+            )
+            .with_datasets(dataset_service.clone()),
+        )
     };
 
+    let (test_suite_storage, test_suite_run_storage): (
+        Arc<dyn StorageTrait<TestSuite>>,
+        Arc<dyn StorageTrait<TestSuiteRun>>,
+    ) = if use_postgres {
+        (
+            StorageFactory::create_postgres_with_pool::<TestSuite>(pg_pool.clone(), "test_suites"),
+            StorageFactory::create_postgres_with_pool::<TestSuiteRun>(
+                pg_pool.clone(),
+                "test_suite_runs",
+            ),
+        )
+    } else {
+        (
+            Arc::new(InMemoryStorage::<TestSuite>::new()),
+            Arc::new(InMemoryStorage::<TestSuiteRun>::new()),
+        )
+    };
+    let test_suite_service: Arc<dyn api::state::TestSuiteServiceTrait> =
+        Arc::new(TestSuiteService::new(
+            Arc::new(StorageTestSuiteRepository::new(
+                test_suite_storage,
+                test_suite_run_storage,
+            )),
+            test_case_service.clone(),
+        ));
+
+    let regression_storage: Arc<dyn StorageTrait<RegressionReport>> = if use_postgres {
+        StorageFactory::create_postgres_with_pool::<RegressionReport>(
+            pg_pool.clone(),
+            "regression_reports",
+        )
+    } else {
+        Arc::new(InMemoryStorage::<RegressionReport>::new())
+    };
+    let regression_service: Arc<dyn api::state::RegressionServiceTrait> =
+        Arc::new(RegressionService::new(
+            test_case_service.clone(),
+            prompt_service.clone(),
+            workflow_service.clone(),
+            regression_storage,
+        ));
+
     if let Err(errors) = register_builtin_plugins(&plugin_registry, &provider_router).await {
         for error in &errors {
             tracing::error!("Failed to register plugin: {}", error);
@@ -424,30 +738,198 @@ pub async fn create_app_state_with_config(config: &AppConfig) -> anyhow::Result<
     };
     let execution_log_repository = Arc::new(StorageExecutionLogRepository::new(execution_log_storage));
     let execution_log_service = Arc::new(ExecutionLogService::new(
-        execution_log_repository,
+        execution_log_repository.clone(),
         config_repository,
     ));
 
+    // Model SLOs, evaluated over the execution logs
+    let slo_storage: Arc<dyn StorageTrait<Slo>> = if use_postgres {
+        StorageFactory::create_postgres_with_pool::<Slo>(pg_pool.clone(), "slos")
+    } else {
+        Arc::new(InMemoryStorage::<Slo>::new())
+    };
+    let slo_service = Arc::new(SloService::new(
+        Arc::new(StorageSloRepository::new(slo_storage)),
+        execution_log_repository,
+    ));
+
+    if config.slo.evaluation_interval_secs > 0 {
+        spawn_slo_evaluation(
+            slo_service.clone(),
+            std::time::Duration::from_secs(config.slo.evaluation_interval_secs),
+        );
+    }
+
+    // Content policies, optionally scoring toxicity with the OpenAI moderation API
+    let content_policy_storage: Arc<dyn StorageTrait<ContentPolicy>> = if use_postgres {
+        StorageFactory::create_postgres_with_pool::<ContentPolicy>(pg_pool.clone(), "content_policies")
+    } else {
+        Arc::new(InMemoryStorage::<ContentPolicy>::new())
+    };
+    let mut content_policy_service = ContentPolicyService::new(content_policy_storage);
+
+    if let Some(api_key) = &config.content_policy.moderation_api_key {
+        content_policy_service = content_policy_service.with_moderation(Arc::new(
+            OpenAiModerationProvider::new(api_key)
+                .with_model(&config.content_policy.moderation_model),
+        ));
+    }
+
+    // Request transform rules
+    let transform_rule_storage: Arc<dyn StorageTrait<TransformRule>> = if use_postgres {
+        StorageFactory::create_postgres_with_pool::<TransformRule>(pg_pool.clone(), "transform_rules")
+    } else {
+        Arc::new(InMemoryStorage::<TransformRule>::new())
+    };
+    let transform_rule_service = TransformRuleService::new(transform_rule_storage);
+
+    let events = Arc::new(create_event_bus(config).await?);
+
+    // Audit log service
+    let audit_log_storage: Arc<dyn StorageTrait<AuditLog>> = if use_postgres {
+        StorageFactory::create_postgres_with_pool::<AuditLog>(pg_pool.clone(), "audit_logs")
+    } else {
+        Arc::new(InMemoryStorage::<AuditLog>::new())
+    };
+    let mut audit_log_service =
+        AuditLogService::new(Arc::new(StorageAuditLogRepository::new(audit_log_storage)))
+            .with_events(events.clone());
+
+    if let Some(sink) = create_audit_sink(config)? {
+        audit_log_service = audit_log_service.with_sink(sink);
+    }
+
     // Webhook service
-    let webhook_service: Arc<dyn api::state::WebhookServiceStateTrait> = if use_postgres {
+    let (webhook_service, webhook_events): (
+        Arc<dyn api::state::WebhookServiceStateTrait>,
+        Arc<dyn WebhookServiceTrait>,
+    ) = if use_postgres {
         let wh_storage =
             StorageFactory::create_postgres_with_pool::<Webhook>(pg_pool.clone(), "webhooks");
         let delivery_storage = StorageFactory::create_postgres_with_pool::<WebhookDelivery>(
             pg_pool.clone(),
             "webhook_deliveries",
         );
-        Arc::new(WebhookService::new(
-            Arc::new(StorageWebhookRepository::new(wh_storage)),
-            Arc::new(StorageWebhookDeliveryRepository::new(delivery_storage)),
-        ))
+        let service = Arc::new(
+            WebhookService::new(
+                Arc::new(StorageWebhookRepository::new(wh_storage)),
+                Arc::new(StorageWebhookDeliveryRepository::new(delivery_storage)),
+            )
+            .with_events(events.clone()),
+        );
+        (service.clone(), service)
     } else {
-        Arc::new(WebhookService::new(
-            Arc::new(InMemoryWebhookRepository::new()),
-            Arc::new(InMemoryWebhookDeliveryRepository::new()),
-        ))
+        let service = Arc::new(
+            WebhookService::new(
+                Arc::new(InMemoryWebhookRepository::new()),
+                Arc::new(InMemoryWebhookDeliveryRepository::new()),
+            )
+            .with_events(events.clone()),
+        );
+        (service.clone(), service)
     };
 
-    Ok(AppState::new(
+    if config.webhooks.retry_interval_secs > 0 {
+        spawn_webhook_retries(
+            webhook_events.clone(),
+            std::time::Duration::from_secs(config.webhooks.retry_interval_secs),
+            leadership.clone(),
+        );
+    }
+
+    // Experiment (A/B testing) service
+    let experiment_service: Arc<dyn api::state::ExperimentServiceTrait> = if use_postgres {
+        let exp_storage =
+            StorageFactory::create_postgres_with_pool::<Experiment>(pg_pool.clone(), "experiments");
+        let record_storage = StorageFactory::create_postgres_with_pool::<ExperimentRecord>(
+            pg_pool.clone(),
+            "experiment_records",
+        );
+        Arc::new(
+            ExperimentService::new(
+                Arc::new(StorageExperimentRepository::new(exp_storage)),
+                Arc::new(StorageExperimentRecordRepository::new(record_storage)),
+            )
+            .with_webhooks(webhook_events.clone())
+            .with_datasets(dataset_service.clone()),
+        )
+    } else {
+        Arc::new(
+            ExperimentService::new(
+                Arc::new(InMemoryExperimentRepository::new()),
+                Arc::new(InMemoryExperimentRecordRepository::new()),
+            )
+            .with_webhooks(webhook_events.clone())
+            .with_datasets(dataset_service.clone()),
+        )
+    };
+
+    if config.experiments.auto_stop_interval_secs > 0 {
+        spawn_experiment_auto_stop(
+            experiment_service.clone(),
+            std::time::Duration::from_secs(config.experiments.auto_stop_interval_secs),
+            leadership.clone(),
+        );
+    }
+
+    let notifications = Arc::new(create_notification_dispatcher(config)?);
+
+    if config.anomaly_detection.enabled {
+        info!(
+            "Detecting usage anomalies every {}s",
+            config.anomaly_detection.interval_secs
+        );
+        let detector = UsageAnomalyDetector::from_config(
+            &config.anomaly_detection,
+            usage_tracking.clone(),
+            notifications.clone(),
+        )
+        .with_webhooks(webhook_events);
+        spawn_usage_anomaly_detection(
+            Arc::new(detector),
+            std::time::Duration::from_secs(config.anomaly_detection.interval_secs.max(1)),
+            leadership.clone(),
+        );
+    }
+
+    let usage_reconciler = UsageReconciler::from_config(
+        &config.reconciliation,
+        usage_tracking,
+        model_storage_for_kb.clone(),
+    )
+    .map(|reconciler| Arc::new(reconciler.with_notifications(notifications.clone())));
+
+    if let Some(reconciler) = &usage_reconciler {
+        info!(
+            providers = ?reconciler.providers(),
+            "Reconciling daily usage with provider usage APIs at {:02}:00 UTC",
+            config.reconciliation.hour_utc
+        );
+        spawn_daily_usage_reconciliation(
+            reconciler.clone(),
+            config.reconciliation.hour_utc,
+            leadership.clone(),
+        );
+    }
+
+    let canary_runner = Arc::new(CanaryRunner::new(
+        test_case_service.clone(),
+        model_service.clone(),
+        credential_service.clone(),
+        provider_router.clone(),
+        notifications.clone(),
+        Arc::new(CanaryHealth::new(config.canary.failure_threshold)),
+    ));
+
+    if config.canary.interval_secs > 0 {
+        info!("Running canary test cases every {}s", config.canary.interval_secs);
+        spawn_canary_runs(
+            canary_runner.clone(),
+            std::time::Duration::from_secs(config.canary.interval_secs),
+        );
+    }
+
+    let state = AppState::new(
         model_service,
         prompt_service,
         api_key_service,
@@ -455,20 +937,330 @@ pub async fn create_app_state_with_config(config: &AppConfig) -> anyhow::Result<
         operation_service,
         user_service,
         team_service,
+        organization_service,
+        role_service,
+        service_account_service,
         jwt_service,
         credential_service,
         external_api_service,
         knowledge_base_service,
         ingestion_service,
+        assistant_service,
+        mcp_tool_service,
+        fine_tune_service,
         usage_service,
         budget_service,
+        pricing_service,
+        slo_service,
+        Arc::new(content_policy_service),
+        Arc::new(transform_rule_service),
         experiment_service,
         test_case_service,
+        test_suite_service,
+        dataset_service,
+        regression_service,
         config_service,
         execution_log_service,
+        Arc::new(audit_log_service),
         webhook_service,
         llm_provider,
         provider_router,
+    )
+    .with_client_ip_resolver(create_client_ip_resolver(config)?)
+    .with_invoice_markup(InvoiceMarkup {
+        default_percent: config.billing.markup_percent,
+        team_percent: config.billing.team_markup_percent.clone(),
+        model_percent: config.billing.model_markup_percent.clone(),
+    })
+    .with_notifications(notifications)
+    .with_canary_runner(canary_runner)
+    .with_injection_guard(InjectionGuard {
+        default_action: config.injection_guard.default_action,
+        threshold: config.injection_guard.threshold,
+        classifier_model_id: config.injection_guard.classifier_model_id.clone(),
+    })
+    .with_secret_leak_guard(SecretLeakGuard {
+        action: config.secret_scanner.action,
+        scan_stored_credentials: config.secret_scanner.scan_stored_credentials,
+    })
+    .with_agent_limits(AgentLimits {
+        max_iterations: config.agent.max_iterations,
+        tool_timeout: std::time::Duration::from_secs(config.agent.tool_timeout_secs),
+        max_cost_micros: config.agent.max_cost_micros,
+    })
+    .with_upload_limits(UploadLimits {
+        max_file_bytes: config.uploads.max_file_bytes,
+        max_files: config.uploads.max_files,
+        temp_dir: config
+            .uploads
+            .temp_dir
+            .as_ref()
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(std::env::temp_dir),
+    })
+    .with_kb_access_tracker(kb_access_tracker)
+    .with_response_cache(create_response_cache(config).await?)
+    .with_event_bus(events)
+    .with_dependency_prober(create_dependency_prober(config, pg_pool)?);
+
+    let state = match usage_reconciler {
+        Some(reconciler) => state.with_usage_reconciler(reconciler),
+        None => state,
+    };
+
+    if config.operations.heartbeat_interval_secs > 0 {
+        spawn_operation_reaper(
+            state.clone(),
+            std::time::Duration::from_secs(config.operations.heartbeat_interval_secs),
+            leadership.clone(),
+        );
+    }
+
+    Ok(state)
+}
+
+/// Longest time the exact response cache keeps an entry, whatever the TTL
+/// of its cache policy
+const RESPONSE_CACHE_MAX_TTL: std::time::Duration = std::time::Duration::from_secs(30 * 86_400);
+
+/// Response caches of chat completions from `[cache]`; the semantic cache
+/// embeds questions with the OpenAI embeddings API
+async fn create_response_cache(config: &AppConfig) -> anyhow::Result<ResponseCacheService> {
+    let api_key = std::env::var("OPENAI_API_KEY").unwrap_or_else(|_| "sk-placeholder".to_string());
+    let embeddings = match std::env::var("OPENAI_BASE_URL") {
+        Ok(url) => OpenAiEmbeddingProvider::with_base_url(HttpClient::new(), api_key, url),
+        Err(_) => OpenAiEmbeddingProvider::new(HttpClient::new(), api_key),
+    };
+
+    build_response_cache(config, Arc::new(embeddings)).await
+}
+
+async fn build_response_cache(
+    config: &AppConfig,
+    embeddings: Arc<dyn EmbeddingProvider>,
+) -> anyhow::Result<ResponseCacheService> {
+    let cache = &config.cache;
+    let ttl = std::time::Duration::from_secs(cache.ttl_secs.max(1));
+    let exact_cache = InMemoryCache::with_config(
+        InMemoryCacheConfig::default()
+            .with_max_capacity(cache.max_entries)
+            .with_default_ttl(RESPONSE_CACHE_MAX_TTL),
+    );
+    let service = ResponseCacheService::new(LlmCacheService::with_config(
+        Arc::new(exact_cache),
+        LlmCacheConfig::default().with_default_ttl(ttl),
+    ))
+    .with_default_policy(CachePolicy::new(cache.default_mode));
+
+    if !cache.semantic.enabled {
+        return Ok(service);
+    }
+
+    let semantic = &cache.semantic;
+    let store: Arc<dyn SemanticCache> = match semantic.backend.to_lowercase().as_str() {
+        "memory" | "" => Arc::new(InMemorySemanticCache::new(semantic.max_entries)),
+        "redis" => {
+            let url = semantic
+                .redis_url
+                .clone()
+                .or_else(|| std::env::var("REDIS_URL").ok())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("cache.semantic.redis_url or REDIS_URL is required for the redis backend")
+                })?;
+
+            info!(namespace = %semantic.namespace, "Storing semantic cache entries in Redis");
+            Arc::new(
+                RedisSemanticCache::new(
+                    RedisSemanticCacheConfig::new(url)
+                        .with_namespace(&semantic.namespace)
+                        .with_dimensions(semantic.dimensions)
+                        .with_max_entries(semantic.max_entries),
+                )
+                .await?,
+            )
+        }
+        other => return Err(anyhow::anyhow!("Unknown semantic cache backend '{}'", other)),
+    };
+
+    info!(
+        embedding_model = %semantic.embedding_model,
+        backend = %semantic.backend,
+        "Semantic response cache enabled"
+    );
+
+    Ok(service.with_semantic(Arc::new(SemanticLlmCacheService::with_config(
+        store,
+        embeddings,
+        SemanticCacheConfig::default()
+            .with_embedding_model(&semantic.embedding_model)
+            .with_similarity_threshold(semantic.similarity_threshold)
+            .with_max_entries(semantic.max_entries)
+            .with_ttl(ttl),
+    ))))
+}
+
+fn create_audit_sink(config: &AppConfig) -> anyhow::Result<Option<Arc<dyn AuditSink>>> {
+    let audit = &config.audit;
+
+    match audit.sink.to_lowercase().as_str() {
+        "none" | "" => Ok(None),
+        "log" => Ok(Some(Arc::new(LogAuditSink::new()))),
+        "http" => {
+            let url = audit
+                .http_url
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("audit.http_url is required for the http sink"))?;
+
+            info!("Exporting audit logs to {}", url);
+            Ok(Some(Arc::new(HttpAuditSink::new(
+                url,
+                audit.http_token.clone(),
+                std::time::Duration::from_secs(audit.http_timeout_secs),
+            ))))
+        }
+        other => Err(anyhow::anyhow!("Unknown audit sink '{}'", other)),
+    }
+}
+
+async fn create_event_bus(config: &AppConfig) -> anyhow::Result<EventBus> {
+    let bus = EventBus::from_config(&config.events)
+        .await
+        .map_err(|e| anyhow::anyhow!("Invalid events configuration: {}", e))?;
+
+    if let Some(broker) = bus.publisher_name() {
+        info!(broker = broker, prefix = %config.events.topic_prefix, "Publishing gateway events");
+    }
+
+    Ok(bus)
+}
+
+fn create_dependency_prober(
+    config: &AppConfig,
+    pg_pool: sqlx::PgPool,
+) -> anyhow::Result<DependencyProber> {
+    let redis_url = std::env::var("REDIS_URL").ok();
+
+    DependencyProber::from_config(&config.health, pg_pool, redis_url.as_deref())
+        .map_err(|e| anyhow::anyhow!("Invalid health configuration: {}", e))
+}
+
+/// Operation service settings from `[operations]`, owning operations as
+/// the leader election identity, `POD_NAME` or `HOSTNAME` when set
+fn create_operation_config(config: &AppConfig) -> OperationServiceConfig {
+    let operation_config = OperationServiceConfig::default().with_lease(
+        std::time::Duration::from_secs(config.operations.lease_secs),
+        config.operations.max_attempts,
+    );
+
+    match config
+        .leader_election
+        .identity
+        .clone()
+        .or_else(|| std::env::var("POD_NAME").ok())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+    {
+        Some(identity) => operation_config.with_replica_id(identity),
+        None => operation_config,
+    }
+}
+
+/// Leadership of this replica over the scheduled jobs acting on shared
+/// state; every replica leads unless `[leader_election]` is enabled
+fn create_leadership(config: &AppConfig, pg_pool: &sqlx::PgPool) -> anyhow::Result<Leadership> {
+    let election = &config.leader_election;
+
+    if !election.enabled {
+        return Ok(Leadership::always());
+    }
+
+    let elector: Arc<dyn LeaderElector> = match election.backend.as_str() {
+        "postgres" => Arc::new(PostgresAdvisoryLockElector::new(
+            pg_pool.clone(),
+            election.lock_id,
+        )),
+        "kubernetes" => {
+            let identity = election
+                .identity
+                .clone()
+                .or_else(|| std::env::var("POD_NAME").ok())
+                .or_else(|| std::env::var("HOSTNAME").ok())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Set leader_election.identity, POD_NAME or HOSTNAME")
+                })?;
+
+            Arc::new(
+                KubernetesLeaseElector::in_cluster(
+                    election.lease_name.clone(),
+                    election.namespace.clone(),
+                    identity,
+                    election.lease_duration_secs,
+                )
+                .map_err(|e| anyhow::anyhow!("Invalid leader election configuration: {}", e))?,
+            )
+        }
+        other => anyhow::bail!("Unknown leader election backend '{}'", other),
+    };
+
+    info!(backend = %election.backend, "Electing the leader replica for scheduled jobs");
+
+    let leadership = Leadership::elected();
+    spawn_leader_election(
+        elector,
+        leadership.clone(),
+        std::time::Duration::from_secs(election.renew_interval_secs.max(1)),
+    );
+
+    Ok(leadership)
+}
+
+/// OCR engine for scanned documents ingested without one, from `[ocr]`
+fn create_ocr_engine(config: &AppConfig) -> anyhow::Result<Option<OcrEngine>> {
+    let ocr = &config.ocr;
+
+    let engine = match ocr.engine.as_deref() {
+        None => return Ok(None),
+        Some("tesseract") => OcrEngine::tesseract(ocr.language.clone()),
+        Some("vision_model") => {
+            let model_id = ocr
+                .model_id
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("Set ocr.model_id for the vision_model engine"))?;
+            OcrEngine::vision_model(model_id)
+        }
+        Some(other) => anyhow::bail!("Unknown OCR engine '{}'", other),
+    };
+
+    info!(engine = engine.name(), "OCR enabled for scanned documents");
+
+    Ok(Some(engine))
+}
+
+fn create_notification_dispatcher(config: &AppConfig) -> anyhow::Result<NotificationDispatcher> {
+    let dispatcher = NotificationDispatcher::from_config(&config.notifications)
+        .map_err(|e| anyhow::anyhow!("Invalid notifications configuration: {}", e))?;
+    let channels = dispatcher.channel_names();
+
+    if !channels.is_empty() {
+        info!(channels = ?channels, "Notification channels enabled");
+    }
+
+    Ok(dispatcher)
+}
+
+fn create_client_ip_resolver(config: &AppConfig) -> anyhow::Result<ClientIpResolver> {
+    let trusted_proxies = config
+        .server
+        .trusted_proxies
+        .iter()
+        .map(|p| {
+            p.parse::<IpNetwork>()
+                .map_err(|e| anyhow::anyhow!("Invalid trusted proxy '{}': {}", p, e))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(ClientIpResolver::new(
+        trusted_proxies,
+        config.server.client_ip_headers.clone(),
     ))
 }
 
@@ -1072,3 +1864,176 @@ pub(crate) fn default_workflows() -> Vec<Workflow> {
             )),
     ]
 }
+
+// ============================================================================
+// Application State - TEST ONLY
+// ============================================================================
+// In-memory application state for handler tests, answering every chat
+// completion with the given provider (models without a stored credential
+// fall back to it).
+// ============================================================================
+
+#[cfg(test)]
+pub(crate) async fn test_app_state(llm_provider: Arc<dyn domain::LlmProvider>) -> AppState {
+    use domain::dataset::{Dataset, DatasetVersion};
+    use domain::test_case::{RegressionReport, TestSuite, TestSuiteRun};
+    use infrastructure::user::{Argon2Hasher, InMemoryUserRepository, UserService};
+    use infrastructure::webhook::{
+        InMemoryWebhookDeliveryRepository, InMemoryWebhookRepository, WebhookService,
+    };
+
+    let config = AppConfig::default();
+    let model_service = Arc::new(ModelService::new(Arc::new(InMemoryStorage::<Model>::new())));
+    let prompt_service =
+        Arc::new(PromptService::new(Arc::new(InMemoryStorage::<Prompt>::new())));
+    let team_repository = Arc::new(StorageTeamRepository::new(Arc::new(
+        InMemoryStorage::<Team>::new(),
+    )));
+    let knowledge_base_storage = Arc::new(InMemoryStorage::<KnowledgeBase>::new());
+    let credential_service =
+        Arc::new(CredentialService::new(Arc::new(InMemoryStoredCredentialRepository::new())));
+    let external_api_service = Arc::new(external_api::ExternalApiService::new(Arc::new(
+        InMemoryStorage::<domain::ExternalApi>::new(),
+    )));
+    let provider_router = Arc::new(ProviderRouter::new());
+    let provider_resolver = Arc::new(RoutingProviderResolver::new(
+        model_service.clone(),
+        credential_service.clone(),
+        provider_router.clone(),
+        llm_provider.clone(),
+    ));
+    let kb_provider_registry: Arc<dyn KnowledgeBaseProviderRegistryTrait> =
+        Arc::new(KnowledgeBaseProviderRegistry::new());
+
+    let workflow_executor: Arc<dyn domain::WorkflowExecutor> = Arc::new(WorkflowExecutorImpl::new(
+        provider_resolver,
+        Arc::new(InMemoryStorage::<Prompt>::new()),
+        credential_service.clone(),
+        external_api_service.clone(),
+        kb_provider_registry.clone(),
+    ));
+    let workflow_service = Arc::new(WorkflowService::new(
+        Arc::new(InMemoryStorage::<Workflow>::new()),
+        workflow_executor,
+    ));
+    let operation_service = Arc::new(OperationService::new(Arc::new(
+        InMemoryOperationRepository::new(),
+    )));
+    let dataset_service = Arc::new(DatasetService::new(Arc::new(StorageDatasetRepository::new(
+        Arc::new(InMemoryStorage::<Dataset>::new()),
+        Arc::new(InMemoryStorage::<DatasetVersion>::new()),
+    ))));
+    let test_case_service = Arc::new(TestCaseService::new(
+        Arc::new(InMemoryTestCaseRepository::new()),
+        Arc::new(InMemoryTestCaseResultRepository::new()),
+        TestCaseServiceDeps {
+            model_service: model_service.clone(),
+            prompt_service: prompt_service.clone(),
+            workflow_service: workflow_service.clone(),
+            credential_service: credential_service.clone(),
+            provider_router: provider_router.clone(),
+        },
+    ));
+    let config_repository: Arc<dyn domain::ConfigRepository> =
+        Arc::new(InMemoryConfigRepository::with_defaults());
+    let execution_log_repository = Arc::new(StorageExecutionLogRepository::new(Arc::new(
+        InMemoryStorage::<ExecutionLog>::new(),
+    )));
+    let pricing_service = Arc::new(PricingService::new(
+        Arc::new(StoragePricingRepository::new(Arc::new(InMemoryStorage::new()))),
+        Arc::new(PricingCatalog::default()),
+    ));
+
+    AppState::new(
+        model_service.clone(),
+        prompt_service.clone(),
+        Arc::new(ApiKeyService::new(Arc::new(InMemoryApiKeyRepository::new()))),
+        workflow_service.clone(),
+        operation_service,
+        Arc::new(UserService::new(
+            Arc::new(InMemoryUserRepository::new()),
+            Arc::new(Argon2Hasher::new()),
+        )),
+        Arc::new(TeamService::new(team_repository.clone())),
+        Arc::new(OrganizationService::new(
+            Arc::new(StorageOrganizationRepository::new(Arc::new(InMemoryStorage::<
+                Organization,
+            >::new()))),
+            team_repository.clone(),
+        )),
+        Arc::new(RoleService::new(Arc::new(StorageRoleRepository::new(Arc::new(
+            InMemoryStorage::<Role>::new(),
+        ))))),
+        Arc::new(ServiceAccountService::new(Arc::new(
+            StorageServiceAccountRepository::new(Arc::new(InMemoryStorage::<ServiceAccount>::new())),
+        ))),
+        create_jwt_service_from_secret(&config, 1),
+        credential_service.clone(),
+        external_api_service,
+        Arc::new(KnowledgeBaseService::new(knowledge_base_storage)),
+        Arc::new(IngestionService::new(kb_provider_registry.clone())),
+        Arc::new(AssistantService::new(
+            Arc::new(StorageAssistantRepository::new(Arc::new(InMemoryStorage::new()))),
+            kb_provider_registry.clone(),
+        )),
+        Arc::new(McpToolService::new(
+            Arc::new(StorageMcpToolRepository::new(Arc::new(InMemoryStorage::new()))),
+            kb_provider_registry,
+        )),
+        Arc::new(FineTuneService::new(
+            Arc::new(StorageFineTuneJobRepository::new(Arc::new(InMemoryStorage::new()))),
+            dataset_service.clone(),
+            model_service,
+            Arc::new(CredentialFineTuneProviderFactory::new(credential_service)),
+        )),
+        Arc::new(UsageTrackingService::with_catalog(
+            Arc::new(InMemoryUsageRepository::default()),
+            pricing_service.catalog().clone(),
+        )),
+        Arc::new(
+            BudgetService::new(Arc::new(InMemoryBudgetRepository::new()))
+                .with_team_repository(team_repository),
+        ),
+        pricing_service,
+        Arc::new(SloService::new(
+            Arc::new(StorageSloRepository::new(Arc::new(InMemoryStorage::<Slo>::new()))),
+            execution_log_repository.clone(),
+        )),
+        Arc::new(ContentPolicyService::new(Arc::new(
+            InMemoryStorage::<ContentPolicy>::new(),
+        ))),
+        Arc::new(TransformRuleService::new(Arc::new(
+            InMemoryStorage::<TransformRule>::new(),
+        ))),
+        Arc::new(ExperimentService::new(
+            Arc::new(InMemoryExperimentRepository::new()),
+            Arc::new(InMemoryExperimentRecordRepository::new()),
+        )),
+        test_case_service.clone(),
+        Arc::new(TestSuiteService::new(
+            Arc::new(StorageTestSuiteRepository::new(
+                Arc::new(InMemoryStorage::<TestSuite>::new()),
+                Arc::new(InMemoryStorage::<TestSuiteRun>::new()),
+            )),
+            test_case_service.clone(),
+        )),
+        dataset_service,
+        Arc::new(RegressionService::new(
+            test_case_service,
+            prompt_service,
+            workflow_service,
+            Arc::new(InMemoryStorage::<RegressionReport>::new()),
+        )),
+        Arc::new(ConfigService::new(config_repository.clone())),
+        Arc::new(ExecutionLogService::new(execution_log_repository, config_repository)),
+        Arc::new(AuditLogService::new(Arc::new(StorageAuditLogRepository::new(Arc::new(
+            InMemoryStorage::<AuditLog>::new(),
+        ))))),
+        Arc::new(WebhookService::new(
+            Arc::new(InMemoryWebhookRepository::new()),
+            Arc::new(InMemoryWebhookDeliveryRepository::new()),
+        )),
+        llm_provider,
+        provider_router,
+    )
+}
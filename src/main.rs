@@ -20,5 +20,12 @@ async fn async_main() -> anyhow::Result<()> {
         Command::Serve => cli::serve::run().await,
         Command::Api => cli::api::run().await,
         Command::Ui(args) => cli::ui::run(args).await,
+        Command::Test(args) => {
+            if !cli::test::run(args).await? {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Command::ImportLitellm(args) => cli::import_litellm::run(args).await,
     }
 }